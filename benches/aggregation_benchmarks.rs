@@ -0,0 +1,155 @@
+//! Statistics Aggregation Benchmarks
+//!
+//! Compares a single write-locked map, as consumer plugins used to keep their
+//! statistics, with a `ShardedTally` when several workers aggregate the
+//! commits of a synthetic 100k-commit history concurrently.
+
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId, Throughput};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use gstats::plugin::aggregation::{Merge, ShardedTally};
+
+const COMMIT_COUNT: usize = 100_000;
+const AUTHOR_COUNT: usize = 200;
+
+/// A commit of the synthetic history
+struct SyntheticCommit {
+    hash: String,
+    author: String,
+    lines_added: usize,
+    lines_removed: usize,
+}
+
+/// Per-author statistics, shaped like those the commits plugin keeps
+#[derive(Debug, Default, Clone)]
+struct AuthorStats {
+    commit_count: usize,
+    authors: HashMap<String, (usize, usize)>,
+}
+
+impl AuthorStats {
+    fn record(&mut self, commit: &SyntheticCommit) {
+        self.commit_count += 1;
+        let churn = self.authors.entry(commit.author.clone()).or_insert((0, 0));
+        churn.0 += commit.lines_added;
+        churn.1 += commit.lines_removed;
+    }
+}
+
+impl Merge for AuthorStats {
+    fn merge(&mut self, other: Self) {
+        self.commit_count += other.commit_count;
+        for (author, (added, removed)) in other.authors {
+            let churn = self.authors.entry(author).or_insert((0, 0));
+            churn.0 += added;
+            churn.1 += removed;
+        }
+    }
+}
+
+/// Create a synthetic history with commits spread over a fixed set of authors
+fn synthetic_history(commit_count: usize) -> Arc<Vec<SyntheticCommit>> {
+    Arc::new((0..commit_count).map(|i| SyntheticCommit {
+        hash: format!("{:040x}", i),
+        author: format!("author{}@example.com", i % AUTHOR_COUNT),
+        lines_added: i % 50,
+        lines_removed: i % 20,
+    }).collect())
+}
+
+/// Commits handled by one of `workers` workers
+fn partition(history: &[SyntheticCommit], worker: usize, workers: usize) -> impl Iterator<Item = &SyntheticCommit> {
+    history.iter().skip(worker).step_by(workers)
+}
+
+/// Aggregate behind a single write lock, keyed by scan id as the plugins did
+async fn aggregate_locked(history: Arc<Vec<SyntheticCommit>>, workers: usize) -> usize {
+    let scans: Arc<RwLock<HashMap<String, AuthorStats>>> = Arc::new(RwLock::new(HashMap::new()));
+    let tasks: Vec<_> = (0..workers).map(|worker| {
+        let history = Arc::clone(&history);
+        let scans = Arc::clone(&scans);
+        tokio::spawn(async move {
+            for commit in partition(&history, worker, workers) {
+                let mut scans = scans.write().await;
+                scans.entry("scan".to_string()).or_default().record(commit);
+            }
+        })
+    }).collect();
+    for task in tasks {
+        task.await.expect("worker panicked");
+    }
+    let commit_count = scans.read().await["scan"].commit_count;
+    commit_count
+}
+
+/// Aggregate into a sharded tally under the read lock of the scan map
+async fn aggregate_sharded(history: Arc<Vec<SyntheticCommit>>, workers: usize) -> usize {
+    let scans: Arc<RwLock<HashMap<String, ShardedTally<AuthorStats>>>> = Arc::new(RwLock::new(HashMap::new()));
+    scans.write().await.insert("scan".to_string(), ShardedTally::new());
+    let tasks: Vec<_> = (0..workers).map(|worker| {
+        let history = Arc::clone(&history);
+        let scans = Arc::clone(&scans);
+        tokio::spawn(async move {
+            for commit in partition(&history, worker, workers) {
+                let scans = scans.read().await;
+                scans["scan"].update(&commit.hash, |stats| stats.record(commit));
+            }
+        })
+    }).collect();
+    for task in tasks {
+        task.await.expect("worker panicked");
+    }
+    let commit_count = scans.read().await["scan"].snapshot().commit_count;
+    commit_count
+}
+
+/// Benchmark both strategies with an increasing number of concurrent workers
+fn bench_commit_aggregation(c: &mut Criterion) {
+    let history = synthetic_history(COMMIT_COUNT);
+    let mut group = c.benchmark_group("commit_aggregation");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(COMMIT_COUNT as u64));
+
+    for workers in [1, 4, 8] {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(workers)
+            .build()
+            .expect("Failed to build runtime");
+
+        group.bench_with_input(BenchmarkId::new("single_lock", workers), &workers, |b, &workers| {
+            b.iter(|| {
+                let count = rt.block_on(aggregate_locked(Arc::clone(&history), workers));
+                assert_eq!(count, COMMIT_COUNT);
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("sharded", workers), &workers, |b, &workers| {
+            b.iter(|| {
+                let count = rt.block_on(aggregate_sharded(Arc::clone(&history), workers));
+                assert_eq!(count, COMMIT_COUNT);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark merging the shards into totals, as done once at export
+fn bench_snapshot(c: &mut Criterion) {
+    let history = synthetic_history(COMMIT_COUNT);
+    let tally: ShardedTally<AuthorStats> = ShardedTally::new();
+    for commit in history.iter() {
+        tally.update(&commit.hash, |stats| stats.record(commit));
+    }
+
+    c.bench_function("sharded_snapshot", |b| {
+        b.iter(|| {
+            assert_eq!(tally.snapshot().commit_count, COMMIT_COUNT);
+        });
+    });
+}
+
+criterion_group!(aggregation_benches, bench_commit_aggregation, bench_snapshot);
+criterion_main!(aggregation_benches);
@@ -0,0 +1,97 @@
+//! Pipeline Benchmarks
+//!
+//! Times the stages `--profile` reports on a generated 200-commit repository:
+//! commit traversal alone, traversal feeding the multi-consumer queue with one
+//! draining consumer, and the cost of the profiler's stage guards themselves.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+use gstats::notifications::AsyncNotificationManager;
+use gstats::profiling::{self, Profile};
+use gstats::queue::MultiConsumerQueue;
+use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+use gstats::scanner::query::QueryParams;
+use gstats::test_support::TestRepo;
+
+const COMMIT_COUNT: usize = 200;
+
+/// A repository whose commits each change a few files
+fn create_repository() -> TestRepo {
+    let mut builder = TestRepo::builder().author("Bench", "bench@example.com");
+    for index in 0..COMMIT_COUNT {
+        let content = (0..=index % 40).map(|line| format!("line {line} of revision {index}\n")).collect::<String>();
+        builder = builder
+            .file(&format!("src/module_{}.rs", index % 10), &content)
+            .file(&format!("docs/page_{}.md", index % 7), &content)
+            .commit(&format!("Change {index}"));
+    }
+    builder.build().expect("Failed to create benchmark repository")
+}
+
+fn bench_traversal(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let repo = create_repository();
+
+    let mut group = c.benchmark_group("pipeline");
+    group.throughput(Throughput::Elements(COMMIT_COUNT as u64));
+    group.sample_size(20);
+
+    group.bench_function("traverse", |b| {
+        b.iter(|| runtime.block_on(async {
+            let scanner = EventDrivenScanner::new(QueryParams::default());
+            let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+            assert!(!messages.is_empty());
+        }));
+    });
+
+    group.bench_function("traverse_and_queue", |b| {
+        b.iter(|| runtime.block_on(async {
+            let queue = MultiConsumerQueue::new(
+                Arc::new(AsyncNotificationManager::new()),
+                Arc::new(AsyncNotificationManager::new()),
+            );
+            queue.start().await.unwrap();
+            let consumer = queue.register_consumer("bench".to_string()).await.unwrap();
+
+            let scanner = EventDrivenScanner::new(QueryParams::default());
+            let mut stream = scanner.scan_async(repo.path()).await.unwrap();
+            let mut enqueued = 0;
+            while let Some(message) = stream.next().await {
+                queue.enqueue(message.unwrap()).await.unwrap();
+                enqueued += 1;
+            }
+
+            let mut consumed = 0;
+            while consumed < enqueued {
+                let batch = consumer.read_batch(64).await.unwrap();
+                consumer.acknowledge(batch.last().unwrap().header().sequence).await.unwrap();
+                consumed += batch.len();
+            }
+        }));
+    });
+
+    group.finish();
+}
+
+fn bench_profiler_overhead(c: &mut Criterion) {
+    let mut group = c.benchmark_group("profiler");
+
+    // Guards taken while profiling is off, as in every run without --profile
+    group.bench_function("disabled_stage", |b| {
+        b.iter(|| drop(std::hint::black_box(profiling::stage("queue.enqueue"))));
+    });
+
+    let profile = Profile::new(false);
+    group.bench_function("record", |b| {
+        b.iter(|| profile.record("queue.enqueue", Instant::now(), Duration::from_micros(12)));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_traversal, bench_profiler_overhead);
+criterion_main!(benches);
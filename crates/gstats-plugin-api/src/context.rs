@@ -0,0 +1,375 @@
+//! Plugin Context and Communication Types
+//! 
+//! Defines the context a plugin is initialised with and the requests and
+//! responses exchanged when it is executed.
+
+use std::any::Any;
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use crate::messages::ScanMessage;
+use crate::metadata::PluginCapability;
+
+/// Context provided to plugins during initialization
+/// 
+/// gstats passes its own context behind this trait, so the plugin traits do not
+/// depend on application types. Plugins built into gstats reach the full context
+/// through [`ScanContext::as_any`].
+pub trait ScanContext: Send + Sync {
+    /// Plugin API version of the running gstats
+    fn api_version(&self) -> u32;
+    
+    /// Plugin-specific configuration data
+    fn plugin_config(&self) -> &HashMap<String, serde_json::Value>;
+    
+    /// Get plugin configuration value
+    fn get_config_value(&self, key: &str) -> Option<&serde_json::Value> {
+        self.plugin_config().get(key)
+    }
+    
+    /// Check if capability is available
+    fn has_capability(&self, capability: &str) -> bool;
+    
+    /// The concrete context, for downcasting by plugins that know its type
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// How the plugin was invoked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvocationType {
+    /// Called as a specific function
+    Function(String),
+    /// Called by plugin name directly
+    Direct,
+    /// Using plugin's default function
+    Default,
+}
+
+/// Request types for plugin execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PluginRequest {
+    /// Execute scan
+    Execute {
+        /// Request identifier for tracking
+        request_id: String,
+        /// Request-specific parameters
+        parameters: HashMap<String, serde_json::Value>,
+        /// Maximum execution time in milliseconds
+        timeout_ms: Option<u64>,
+        /// Priority level for execution
+        priority: RequestPriority,
+        /// How the plugin was invoked (function name or direct)
+        invoked_as: String,
+        /// Type of invocation
+        invocation_type: InvocationType,
+    },
+    /// Get plugin statistics
+    GetStatistics,
+    /// Get plugin capabilities
+    GetCapabilities,
+    /// Export data
+    Export,
+    /// Process specific data
+    ProcessData {
+        /// Data to process
+        data: serde_json::Value,
+    },
+}
+
+/// Response types from plugin execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PluginResponse {
+    /// Execution response with full metadata
+    Execute {
+        /// Request identifier this response corresponds to
+        request_id: String,
+        /// Execution status
+        status: ExecutionStatus,
+        /// Response data
+        data: serde_json::Value,
+        /// Execution metadata
+        metadata: ExecutionMetadata,
+        /// Any errors that occurred
+        errors: Vec<String>,
+    },
+    /// Statistics response
+    Statistics(ScanMessage),
+    /// Capabilities response
+    Capabilities(Vec<PluginCapability>),
+    /// Data export response
+    Data(String),
+    /// Process data response
+    ProcessedData(Vec<ScanMessage>),
+}
+
+/// Request priority levels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RequestPriority {
+    /// Low priority - background processing
+    Low = 1,
+    /// Normal priority - standard operations
+    #[default]
+    Normal = 2,
+    /// High priority - user-requested operations
+    High = 3,
+    /// Critical priority - system operations
+    Critical = 4,
+}
+
+/// Execution status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionStatus {
+    /// Execution completed successfully
+    Success,
+    /// Execution completed with warnings
+    Warning,
+    /// Execution failed
+    Failed,
+    /// Execution was cancelled
+    Cancelled,
+    /// Execution timed out
+    Timeout,
+}
+
+/// Execution metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionMetadata {
+    /// Execution duration in microseconds
+    pub duration_us: u64,
+    
+    /// Memory usage in bytes
+    pub memory_used: u64,
+    
+    /// Number of entries processed
+    pub entries_processed: u64,
+    
+    /// Plugin version that executed the request
+    pub plugin_version: String,
+    
+    /// Additional metadata
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl PluginRequest {
+    /// Create a new execute plugin request
+    pub fn new() -> Self {
+        Self::Execute {
+            request_id: uuid::Uuid::now_v7().to_string(),
+            parameters: HashMap::new(),
+            timeout_ms: None,
+            priority: RequestPriority::Normal,
+            invoked_as: "default".to_string(),
+            invocation_type: InvocationType::Default,
+        }
+    }
+    
+    /// Create a new execute plugin request with invocation context
+    pub fn new_with_invocation(invoked_as: String, invocation_type: InvocationType) -> Self {
+        Self::Execute {
+            request_id: uuid::Uuid::now_v7().to_string(),
+            parameters: HashMap::new(),
+            timeout_ms: None,
+            priority: RequestPriority::Normal,
+            invoked_as,
+            invocation_type,
+        }
+    }
+    
+    /// Set request priority (only for Execute requests)
+    pub fn with_priority(self, priority: RequestPriority) -> Self {
+        match self {
+            Self::Execute { request_id, parameters, timeout_ms, invoked_as, invocation_type, .. } => {
+                Self::Execute { request_id, parameters, timeout_ms, priority, invoked_as, invocation_type }
+            }
+            _ => self,
+        }
+    }
+    
+    /// Set timeout (only for Execute requests)
+    pub fn with_timeout(self, timeout_ms: u64) -> Self {
+        match self {
+            Self::Execute { request_id, parameters, priority, invoked_as, invocation_type, .. } => {
+                Self::Execute { request_id, parameters, timeout_ms: Some(timeout_ms), priority, invoked_as, invocation_type }
+            }
+            _ => self,
+        }
+    }
+    
+    /// Add parameter (only for Execute requests)
+    pub fn with_parameter<T: Serialize>(self, key: String, value: T) -> Self {
+        match self {
+            Self::Execute { request_id, mut parameters, timeout_ms, priority, invoked_as, invocation_type } => {
+                if let Ok(json_value) = serde_json::to_value(value) {
+                    parameters.insert(key, json_value);
+                }
+                Self::Execute { request_id, parameters, timeout_ms, priority, invoked_as, invocation_type }
+            }
+            _ => self,
+        }
+    }
+    
+    /// Get parameter value (only for Execute requests)
+    pub fn get_parameter(&self, key: &str) -> Option<&serde_json::Value> {
+        match self {
+            Self::Execute { parameters, .. } => parameters.get(key),
+            _ => None,
+        }
+    }
+    
+    /// Get request ID
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::Execute { request_id, .. } => Some(request_id),
+            _ => None,
+        }
+    }
+}
+
+impl PluginResponse {
+    /// Create a successful execute response
+    pub fn success(request_id: String, data: serde_json::Value, metadata: ExecutionMetadata) -> Self {
+        Self::Execute {
+            request_id,
+            status: ExecutionStatus::Success,
+            data,
+            metadata,
+            errors: Vec::new(),
+        }
+    }
+    
+    /// Create a failed execute response
+    pub fn failed(request_id: String, error: String, metadata: ExecutionMetadata) -> Self {
+        Self::Execute {
+            request_id,
+            status: ExecutionStatus::Failed,
+            data: serde_json::Value::Null,
+            metadata,
+            errors: vec![error],
+        }
+    }
+    
+    /// Create a warning execute response
+    pub fn warning(request_id: String, data: serde_json::Value, warnings: Vec<String>, metadata: ExecutionMetadata) -> Self {
+        Self::Execute {
+            request_id,
+            status: ExecutionStatus::Warning,
+            data,
+            metadata,
+            errors: warnings,
+        }
+    }
+    
+    /// Check if response indicates success
+    pub fn is_success(&self) -> bool {
+        match self {
+            Self::Execute { status, .. } => matches!(status, ExecutionStatus::Success | ExecutionStatus::Warning),
+            Self::Statistics(_) | Self::Capabilities(_) | Self::Data(_) | Self::ProcessedData(_) => true,
+        }
+    }
+    
+    /// Check if response indicates failure
+    pub fn is_failure(&self) -> bool {
+        match self {
+            Self::Execute { status, .. } => matches!(status, ExecutionStatus::Failed | ExecutionStatus::Cancelled | ExecutionStatus::Timeout),
+            _ => false,
+        }
+    }
+    
+    /// Get errors if any
+    pub fn get_errors(&self) -> &[String] {
+        match self {
+            Self::Execute { errors, .. } => errors,
+            _ => &[],
+        }
+    }
+}
+
+impl Default for PluginRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecutionMetadata {
+    /// Create new execution metadata
+    pub fn new(duration_us: u64, memory_used: u64, entries_processed: u64, plugin_version: String) -> Self {
+        Self {
+            duration_us,
+            memory_used,
+            entries_processed,
+            plugin_version,
+            extra: HashMap::new(),
+        }
+    }
+    
+    /// Add extra metadata
+    pub fn with_extra(mut self, key: String, value: serde_json::Value) -> Self {
+        self.extra.insert(key, value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_plugin_request_creation() {
+        let request = PluginRequest::new()
+            .with_priority(RequestPriority::High)
+            .with_timeout(5000)
+            .with_parameter("limit".to_string(), 100);
+        
+        match request {
+            PluginRequest::Execute { priority, timeout_ms, .. } => {
+                assert_eq!(priority, RequestPriority::High);
+                assert_eq!(timeout_ms, Some(5000));
+            }
+            _ => panic!("Expected Execute request"),
+        }
+        assert!(request.get_parameter("limit").is_some());
+    }
+    
+    #[test]
+    fn test_plugin_response_creation() {
+        let metadata = ExecutionMetadata::new(100, 1024, 5, "1.0.0".to_string());
+        
+        let success_response = PluginResponse::success(
+            "test-id".to_string(),
+            serde_json::Value::String("test data".to_string()),
+            metadata.clone(),
+        );
+        
+        assert!(success_response.is_success());
+        assert!(!success_response.is_failure());
+        assert_eq!(success_response.get_errors().len(), 0);
+        
+        let failed_response = PluginResponse::failed(
+            "test-id".to_string(),
+            "Test error".to_string(),
+            metadata,
+        );
+        
+        assert!(!failed_response.is_success());
+        assert!(failed_response.is_failure());
+        assert_eq!(failed_response.get_errors().len(), 1);
+    }
+    
+    #[test]
+    fn test_request_priority_ordering() {
+        assert!(RequestPriority::Critical > RequestPriority::High);
+        assert!(RequestPriority::High > RequestPriority::Normal);
+        assert!(RequestPriority::Normal > RequestPriority::Low);
+    }
+    
+    #[test]
+    fn test_execution_metadata() {
+        let mut metadata = ExecutionMetadata::new(150, 2048, 10, "1.1.0".to_string());
+        metadata = metadata.with_extra("custom_field".to_string(), serde_json::Value::Bool(true));
+        
+        assert_eq!(metadata.duration_us, 150);
+        assert_eq!(metadata.memory_used, 2048);
+        assert_eq!(metadata.entries_processed, 10);
+        assert_eq!(metadata.plugin_version, "1.1.0");
+        assert_eq!(metadata.extra.get("custom_field"), Some(&serde_json::Value::Bool(true)));
+    }
+}
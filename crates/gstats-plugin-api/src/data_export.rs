@@ -0,0 +1,819 @@
+//! Plugin Data Export Protocol
+//! 
+//! Native Rust data transfer protocol for efficient, type-safe data sharing
+//! between plugins and the export system.
+
+use std::sync::Arc;
+use std::collections::HashMap;
+use std::time::{SystemTime, Duration};
+use serde::{Serialize, Deserialize};
+
+/// Main data export structure that plugins create
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginDataExport {
+    /// Unique identifier of the source plugin
+    pub plugin_id: String,
+    
+    /// Human-readable title for the data
+    pub title: String,
+    
+    /// Optional description of the data
+    pub description: Option<String>,
+    
+    /// Type of data being exported
+    pub data_type: DataExportType,
+    
+    /// Schema definition for the data
+    pub schema: DataSchema,
+    
+    /// Actual data payload
+    pub data: DataPayload,
+    
+    /// Hints for export formatting
+    pub export_hints: ExportHints,
+    
+    /// Timestamp when data was created
+    pub timestamp: SystemTime,
+    
+    /// Repository the data describes, when several repositories are scanned together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
+
+/// Types of data that can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DataExportType {
+    /// Rows and columns (most common)
+    Tabular,
+    
+    /// Tree/nested structure
+    Hierarchical,
+    
+    /// Simple key-value pairs
+    KeyValue,
+    
+    /// Labelled two-dimensional grid of values
+    Matrix,
+    
+    /// Unstructured data
+    Raw,
+}
+
+/// Schema definition for structured data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSchema {
+    /// Column definitions for tabular data
+    pub columns: Vec<ColumnDef>,
+    
+    /// Optional metadata about the schema
+    pub metadata: HashMap<String, String>,
+}
+
+/// Definition of a single column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnDef {
+    /// Column name
+    pub name: String,
+    
+    /// Data type of the column
+    pub data_type: ColumnType,
+    
+    /// Optional description
+    pub description: Option<String>,
+    
+    /// Format hint for rendering (e.g., "percentage", "bytes", "timestamp")
+    pub format_hint: Option<String>,
+    
+    /// Whether this column should be hidden by default
+    pub hidden: bool,
+    
+    /// Preferred width for console output (characters)
+    pub preferred_width: Option<usize>,
+}
+
+/// Data types for columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Timestamp,
+    Duration,
+}
+
+/// Container for actual data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DataPayload {
+    /// Tabular data as rows
+    Rows(Arc<Vec<Row>>),
+    
+    /// Hierarchical tree structure
+    Tree(Arc<TreeNode>),
+    
+    /// Key-value pairs
+    KeyValue(Arc<HashMap<String, Value>>),
+    
+    /// Labelled matrix of values
+    Matrix(Arc<Matrix>),
+    
+    /// Raw unstructured data
+    Raw(Arc<String>),
+    
+    /// Empty payload (no data)
+    Empty,
+}
+
+/// A single row of data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Row {
+    /// Values in the row, matching schema column order
+    pub values: Vec<Value>,
+    
+    /// Optional metadata for this row
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Individual data values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Timestamp(SystemTime),
+    Duration(Duration),
+    Null,
+}
+
+/// Tree node for hierarchical data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeNode {
+    /// Node label
+    pub label: String,
+    
+    /// Node value
+    pub value: Option<Value>,
+    
+    /// Child nodes
+    pub children: Vec<Arc<TreeNode>>,
+    
+    /// Node metadata
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Labelled two-dimensional grid of values (e.g. author-by-author counts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Matrix {
+    /// Label shown above the row labels (e.g. "Editor")
+    pub corner_label: String,
+    
+    /// Row labels, one per row of cells
+    pub row_labels: Vec<String>,
+    
+    /// Column labels, one per cell in each row
+    pub column_labels: Vec<String>,
+    
+    /// Cell values, indexed as `cells[row][column]`
+    pub cells: Vec<Vec<Value>>,
+}
+
+/// Hints for how to export/format the data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportHints {
+    /// Preferred output formats in order of preference
+    pub preferred_formats: Vec<ExportFormat>,
+    
+    /// Column to sort by (for tabular data)
+    pub sort_by: Option<String>,
+    
+    /// Sort order
+    pub sort_ascending: bool,
+    
+    /// Maximum number of rows to display
+    pub limit: Option<usize>,
+    
+    /// Whether to include summary/totals
+    pub include_totals: bool,
+    
+    /// Whether to include row numbers
+    pub include_row_numbers: bool,
+    
+    /// Custom hints for specific formatters
+    pub custom_hints: HashMap<String, String>,
+}
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// Console table output
+    Console,
+    
+    /// JSON format
+    Json,
+    
+    /// CSV format
+    Csv,
+    
+    /// XML format
+    Xml,
+    
+    /// YAML format
+    Yaml,
+    
+    /// HTML format
+    Html,
+    
+    /// Markdown format
+    Markdown,
+    
+    /// Template-based format
+    Template,
+}
+
+// Builder implementation for PluginDataExport
+impl PluginDataExport {
+    /// Create a new builder for PluginDataExport
+    pub fn builder() -> PluginDataExportBuilder {
+        PluginDataExportBuilder::new()
+    }
+}
+
+/// Builder for PluginDataExport
+pub struct PluginDataExportBuilder {
+    plugin_id: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    data_type: Option<DataExportType>,
+    schema: Option<DataSchema>,
+    data: Option<DataPayload>,
+    export_hints: Option<ExportHints>,
+    repository: Option<String>,
+}
+
+impl Default for PluginDataExportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluginDataExportBuilder {
+    /// Create a new builder
+    pub fn new() -> Self {
+        Self {
+            plugin_id: None,
+            title: None,
+            description: None,
+            data_type: None,
+            schema: None,
+            data: None,
+            export_hints: None,
+            repository: None,
+        }
+    }
+    
+    /// Set the plugin ID
+    pub fn plugin_id(mut self, id: impl Into<String>) -> Self {
+        self.plugin_id = Some(id.into());
+        self
+    }
+    
+    /// Set the title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+    
+    /// Set the description
+    pub fn description(mut self, desc: impl Into<String>) -> Self {
+        self.description = Some(desc.into());
+        self
+    }
+    
+    /// Set the data type
+    pub fn data_type(mut self, dtype: DataExportType) -> Self {
+        self.data_type = Some(dtype);
+        self
+    }
+    
+    /// Set the schema
+    pub fn schema(mut self, schema: DataSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+    
+    /// Set the data payload
+    pub fn data(mut self, data: DataPayload) -> Self {
+        self.data = Some(data);
+        self
+    }
+    
+    /// Set export hints
+    pub fn export_hints(mut self, hints: ExportHints) -> Self {
+        self.export_hints = Some(hints);
+        self
+    }
+    
+    /// Set the repository the data describes
+    pub fn repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = Some(repository.into());
+        self
+    }
+    
+    /// Build the PluginDataExport
+    pub fn build(self) -> Result<PluginDataExport, String> {
+        Ok(PluginDataExport {
+            plugin_id: self.plugin_id.ok_or("plugin_id is required")?,
+            title: self.title.ok_or("title is required")?,
+            description: self.description,
+            data_type: self.data_type.unwrap_or(DataExportType::Tabular),
+            schema: self.schema.unwrap_or_else(|| DataSchema {
+                columns: Vec::new(),
+                metadata: HashMap::new(),
+            }),
+            data: self.data.unwrap_or(DataPayload::Empty),
+            export_hints: self.export_hints.unwrap_or_else(|| ExportHints {
+                preferred_formats: vec![ExportFormat::Console],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            }),
+            timestamp: SystemTime::now(),
+            repository: self.repository,
+        })
+    }
+}
+
+// Helper implementations
+impl Row {
+    /// Create a new row with values
+    pub fn new(values: Vec<Value>) -> Self {
+        Self {
+            values,
+            metadata: None,
+        }
+    }
+    
+    /// Create a row with metadata
+    pub fn with_metadata(values: Vec<Value>, metadata: HashMap<String, String>) -> Self {
+        Self {
+            values,
+            metadata: Some(metadata),
+        }
+    }
+}
+
+impl TreeNode {
+    /// Create a new tree node
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: None,
+            children: Vec::new(),
+            metadata: None,
+        }
+    }
+    
+    /// Add a child node
+    pub fn add_child(mut self, child: TreeNode) -> Self {
+        self.children.push(Arc::new(child));
+        self
+    }
+    
+    /// Set the node value
+    pub fn with_value(mut self, value: Value) -> Self {
+        self.value = Some(value);
+        self
+    }
+}
+
+impl Matrix {
+    /// Create a matrix with every cell set to `Value::Null`
+    pub fn new(corner_label: impl Into<String>, row_labels: Vec<String>, column_labels: Vec<String>) -> Self {
+        let cells = vec![vec![Value::Null; column_labels.len()]; row_labels.len()];
+        Self {
+            corner_label: corner_label.into(),
+            row_labels,
+            column_labels,
+            cells,
+        }
+    }
+    
+    /// Set the value of a cell (out of range positions are ignored)
+    pub fn set(&mut self, row: usize, column: usize, value: Value) {
+        if let Some(cell) = self.cells.get_mut(row).and_then(|r| r.get_mut(column)) {
+            *cell = value;
+        }
+    }
+    
+    /// Get the value of a cell
+    pub fn get(&self, row: usize, column: usize) -> Option<&Value> {
+        self.cells.get(row).and_then(|r| r.get(column))
+    }
+    
+    /// Largest numeric cell value, used to scale heat renderings
+    pub fn max_value(&self) -> Option<f64> {
+        self.cells
+            .iter()
+            .flatten()
+            .filter_map(Value::as_f64)
+            .reduce(f64::max)
+    }
+    
+    /// Header row for tabular renderings: the corner label followed by the column labels
+    pub fn header(&self) -> Vec<String> {
+        std::iter::once(self.corner_label.clone())
+            .chain(self.column_labels.iter().cloned())
+            .collect()
+    }
+    
+    /// Convert to rows, each starting with its row label, for tabular renderings
+    pub fn to_rows(&self) -> Vec<Row> {
+        self.row_labels
+            .iter()
+            .zip(&self.cells)
+            .map(|(label, cells)| {
+                let mut values = Vec::with_capacity(cells.len() + 1);
+                values.push(Value::String(label.clone()));
+                values.extend(cells.iter().cloned());
+                Row::new(values)
+            })
+            .collect()
+    }
+}
+
+impl DataSchema {
+    /// Create a new schema with columns
+    pub fn new(columns: Vec<ColumnDef>) -> Self {
+        Self {
+            columns,
+            metadata: HashMap::new(),
+        }
+    }
+    
+    /// Add metadata to the schema
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl ColumnDef {
+    /// Create a new column definition
+    pub fn new(name: impl Into<String>, data_type: ColumnType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            description: None,
+            format_hint: None,
+            hidden: false,
+            preferred_width: None,
+        }
+    }
+    
+    /// Set description
+    pub fn with_description(mut self, desc: impl Into<String>) -> Self {
+        self.description = Some(desc.into());
+        self
+    }
+    
+    /// Set format hint
+    pub fn with_format_hint(mut self, hint: impl Into<String>) -> Self {
+        self.format_hint = Some(hint.into());
+        self
+    }
+    
+    /// Set preferred width
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.preferred_width = Some(width);
+        self
+    }
+}
+
+impl std::fmt::Display for Value {
+    /// Format value as its string representation
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", s),
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Float(v) => write!(f, "{:.2}", v),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Timestamp(t) => {
+                // Format as ISO-like string
+                if let Ok(duration) = t.duration_since(SystemTime::UNIX_EPOCH) {
+                    write!(f, "{}", duration.as_secs())
+                } else {
+                    write!(f, "invalid")
+                }
+            }
+            Value::Duration(d) => write!(f, "{:.1}s", d.as_secs_f64()),
+            Value::Null => Ok(()),
+        }
+    }
+}
+
+impl Value {
+    /// Check if value is null
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+    
+    /// Numeric value as f64, if this is an integer or float
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ExportHints {
+    fn default() -> Self {
+        Self {
+            preferred_formats: vec![ExportFormat::Console],
+            sort_by: None,
+            sort_ascending: true,
+            limit: None,
+            include_totals: false,
+            include_row_numbers: false,
+            custom_hints: HashMap::new(),
+        }
+    }
+}
+
+/// Severity of a finding, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl FindingSeverity {
+    /// Level name as shown in exports and accepted by `--fail-on`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+
+    /// Parse a level name (`low`, `medium`, `high`, `critical`)
+    pub fn parse(level: &str) -> Option<Self> {
+        match level.trim().to_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "medium" | "moderate" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+
+    /// SARIF result level (`note`, `warning` or `error`) for the severity
+    pub fn sarif_level(&self) -> &'static str {
+        match self {
+            Self::Low => "note",
+            Self::Medium => "warning",
+            Self::High | Self::Critical => "error",
+        }
+    }
+}
+
+/// Something an analysis flags for attention, in a shape shared by all analyses
+///
+/// Analyses publish their findings as a table built by [`findings_export`]
+/// alongside their own tables, so findings from every plugin can be listed
+/// together, gated on by severity, and mapped to other formats such as SARIF.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Stable rule identifier, such as `hotspot` or `mass-deletion`
+    pub id: String,
+    pub severity: FindingSeverity,
+    /// Kind of analysis that raised the finding, such as `maintainability`
+    pub category: String,
+    pub message: String,
+    /// File path, commit or period the finding is about
+    pub location: Option<String>,
+    /// Measurements behind the finding, in the order they were added
+    pub metrics: Vec<(String, f64)>,
+}
+
+impl Finding {
+    /// Create a finding without location or metrics
+    pub fn new(
+        id: impl Into<String>,
+        severity: FindingSeverity,
+        category: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            severity,
+            category: category.into(),
+            message: message.into(),
+            location: None,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Set the location the finding is about
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Add a measurement behind the finding
+    pub fn with_metric(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.metrics.push((name.into(), value));
+        self
+    }
+
+    /// Measurements as `name=value` pairs
+    fn metrics_text(&self) -> String {
+        self.metrics
+            .iter()
+            .map(|(name, value)| if value.fract() == 0.0 && value.abs() < 1e15 {
+                format!("{}={}", name, *value as i64)
+            } else {
+                format!("{}={:.2}", name, value)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Title of findings tables, which `--fail-on findings.severity>=high` names
+pub const FINDINGS_TITLE: &str = "Findings";
+
+/// Schema metadata key marking a findings table
+pub const FINDINGS_METADATA_KEY: &str = "findings";
+
+/// Column of a findings table holding the severity
+pub const FINDINGS_SEVERITY_COLUMN: usize = 1;
+
+/// Columns of a findings table
+pub fn findings_columns() -> Vec<ColumnDef> {
+    vec![
+        ColumnDef::new("Location", ColumnType::String)
+            .with_description("File, commit or period the finding is about"),
+        ColumnDef::new("Severity", ColumnType::String).with_format_hint("severity"),
+        ColumnDef::new("Category", ColumnType::String),
+        ColumnDef::new("Rule", ColumnType::String),
+        ColumnDef::new("Message", ColumnType::String),
+        ColumnDef::new("Metrics", ColumnType::String)
+            .with_description("Measurements behind the finding"),
+        ColumnDef::new("Source", ColumnType::String)
+            .with_description("Plugin that raised the finding"),
+    ]
+}
+
+/// Table of a plugin's findings, most severe first, or none without findings
+pub fn findings_export(plugin_id: &str, findings: &[Finding]) -> Option<PluginDataExport> {
+    if findings.is_empty() {
+        return None;
+    }
+    let mut findings: Vec<&Finding> = findings.iter().collect();
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+
+    let rows = findings
+        .iter()
+        .map(|finding| Row::new(vec![
+            finding.location.clone().map(Value::String).unwrap_or(Value::Null),
+            Value::String(finding.severity.as_str().to_string()),
+            Value::String(finding.category.clone()),
+            Value::String(finding.id.clone()),
+            Value::String(finding.message.clone()),
+            Value::String(finding.metrics_text()),
+            Value::String(plugin_id.to_string()),
+        ]))
+        .collect::<Vec<_>>();
+
+    let most_severe = findings[0].severity;
+    PluginDataExport::builder()
+        .plugin_id(plugin_id)
+        .title(FINDINGS_TITLE)
+        .description(format!("{} findings, the most severe {}", rows.len(), most_severe.as_str()))
+        .schema(DataSchema::new(findings_columns()).with_metadata(FINDINGS_METADATA_KEY, "true"))
+        .data(DataPayload::Rows(Arc::new(rows)))
+        .build()
+        .ok()
+}
+
+impl PluginDataExport {
+    /// Whether this is a findings table built by [`findings_export`]
+    pub fn is_findings(&self) -> bool {
+        self.schema.metadata.get(FINDINGS_METADATA_KEY).is_some_and(|flag| flag == "true")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_plugin_data_export_builder() {
+        let export = PluginDataExport::builder()
+            .plugin_id("test_plugin")
+            .title("Test Data")
+            .description("Test description")
+            .data_type(DataExportType::Tabular)
+            .build()
+            .unwrap();
+        
+        assert_eq!(export.plugin_id, "test_plugin");
+        assert_eq!(export.title, "Test Data");
+        assert_eq!(export.description, Some("Test description".to_string()));
+        assert_eq!(export.data_type, DataExportType::Tabular);
+    }
+    
+    #[test]
+    fn test_row_creation() {
+        let row = Row::new(vec![
+            Value::String("test".to_string()),
+            Value::Integer(42),
+            Value::Boolean(true),
+        ]);
+        
+        assert_eq!(row.values.len(), 3);
+        assert!(row.metadata.is_none());
+    }
+    
+    #[test]
+    fn test_tree_node() {
+        let root = TreeNode::new("root")
+            .with_value(Value::String("root_value".to_string()))
+            .add_child(TreeNode::new("child1"))
+            .add_child(TreeNode::new("child2"));
+        
+        assert_eq!(root.label, "root");
+        assert_eq!(root.children.len(), 2);
+    }
+    
+    #[test]
+    fn test_value_to_string() {
+        assert_eq!(Value::String("test".to_string()).to_string(), "test");
+        assert_eq!(Value::Integer(42).to_string(), "42");
+        assert_eq!(Value::Float(2.5).to_string(), "2.50");
+        assert_eq!(Value::Boolean(true).to_string(), "true");
+        assert_eq!(Value::Null.to_string(), "");
+    }
+    
+    #[test]
+    fn test_matrix() {
+        let mut matrix = Matrix::new(
+            "Editor",
+            vec!["alice".to_string(), "bob".to_string()],
+            vec!["alice".to_string(), "bob".to_string(), "carol".to_string()],
+        );
+        matrix.set(0, 1, Value::Integer(12));
+        matrix.set(1, 2, Value::Integer(3));
+        matrix.set(5, 5, Value::Integer(99)); // ignored
+        
+        assert_eq!(matrix.get(0, 1), Some(&Value::Integer(12)));
+        assert_eq!(matrix.get(0, 0), Some(&Value::Null));
+        assert_eq!(matrix.max_value(), Some(12.0));
+        assert_eq!(matrix.header(), vec!["Editor", "alice", "bob", "carol"]);
+        
+        let rows = matrix.to_rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].values[0], Value::String("bob".to_string()));
+        assert_eq!(rows[1].values[3], Value::Integer(3));
+    }
+    
+    #[test]
+    fn test_findings_export_orders_by_severity() {
+        let findings = vec![
+            Finding::new("drought", FindingSeverity::Low, "activity", "No commits"),
+            Finding::new("hotspot", FindingSeverity::Critical, "maintainability", "Hot file")
+                .with_location("src/lib.rs")
+                .with_metric("score", 91.25)
+                .with_metric("changes", 40.0),
+        ];
+
+        let export = findings_export("metrics", &findings).unwrap();
+        assert!(export.is_findings());
+        assert_eq!(export.title, FINDINGS_TITLE);
+        let DataPayload::Rows(rows) = &export.data else { panic!("expected rows") };
+        assert_eq!(rows[0].values[0], Value::String("src/lib.rs".to_string()));
+        assert_eq!(rows[0].values[1], Value::String("critical".to_string()));
+        assert_eq!(rows[0].values[5], Value::String("score=91.25, changes=40".to_string()));
+        assert_eq!(rows[1].values[0], Value::Null);
+        assert!(findings_export("metrics", &[]).is_none());
+    }
+    
+    #[test]
+    fn test_finding_severity_levels() {
+        assert_eq!(FindingSeverity::parse("High"), Some(FindingSeverity::High));
+        assert!(FindingSeverity::Critical > FindingSeverity::Medium);
+        assert_eq!(FindingSeverity::Medium.sarif_level(), "warning");
+        assert_eq!(FindingSeverity::Critical.sarif_level(), "error");
+    }
+}
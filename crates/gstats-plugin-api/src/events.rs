@@ -0,0 +1,184 @@
+//! Queue Events
+//!
+//! Events the gstats queue emits while a scan runs, delivered to consumer
+//! plugins through `ConsumerPlugin::handle_queue_event`.
+
+use serde::{Deserialize, Serialize};
+
+/// Events emitted by the queue system for coordination
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QueueEvent {
+    /// Scanning has started
+    ScanStarted {
+        scan_id: String,
+        timestamp: u64,
+    },
+
+    /// One or more messages have been added to the queue
+    MessageAdded {
+        scan_id: String,
+        count: usize,
+        queue_size: usize,
+        timestamp: u64,
+    },
+
+    /// Scanning has completed - no more messages will be added
+    ScanComplete {
+        scan_id: String,
+        total_messages: u64,
+        timestamp: u64,
+    },
+
+    /// Queue has been drained - all messages processed
+    QueueDrained {
+        scan_id: String,
+        timestamp: u64,
+    },
+
+    /// Memory usage warning
+    MemoryWarning {
+        scan_id: String,
+        current_size: usize,
+        threshold: usize,
+        timestamp: u64,
+    },
+
+    /// The producer was held back until consumers made room in the queue
+    ProducerBlocked {
+        scan_id: String,
+        waited_ms: u64,
+        timestamp: u64,
+    },
+
+    /// Messages were discarded before every consumer had read them
+    MessagesDropped {
+        scan_id: String,
+        count: u64,
+        first_sequence: u64,
+        last_sequence: u64,
+        timestamp: u64,
+    },
+
+    /// Messages were written to disk and will be read back from there
+    MessagesSpilled {
+        scan_id: String,
+        count: u64,
+        first_sequence: u64,
+        last_sequence: u64,
+        timestamp: u64,
+    },
+}
+
+impl QueueEvent {
+    /// Get the scan ID associated with this event
+    pub fn scan_id(&self) -> &str {
+        match self {
+            QueueEvent::ScanStarted { scan_id, .. } => scan_id,
+            QueueEvent::MessageAdded { scan_id, .. } => scan_id,
+            QueueEvent::ScanComplete { scan_id, .. } => scan_id,
+            QueueEvent::QueueDrained { scan_id, .. } => scan_id,
+            QueueEvent::MemoryWarning { scan_id, .. } => scan_id,
+            QueueEvent::ProducerBlocked { scan_id, .. } => scan_id,
+            QueueEvent::MessagesDropped { scan_id, .. } => scan_id,
+            QueueEvent::MessagesSpilled { scan_id, .. } => scan_id,
+        }
+    }
+
+    /// Get the timestamp of this event
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            QueueEvent::ScanStarted { timestamp, .. } => *timestamp,
+            QueueEvent::MessageAdded { timestamp, .. } => *timestamp,
+            QueueEvent::ScanComplete { timestamp, .. } => *timestamp,
+            QueueEvent::QueueDrained { timestamp, .. } => *timestamp,
+            QueueEvent::MemoryWarning { timestamp, .. } => *timestamp,
+            QueueEvent::ProducerBlocked { timestamp, .. } => *timestamp,
+            QueueEvent::MessagesDropped { timestamp, .. } => *timestamp,
+            QueueEvent::MessagesSpilled { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// Create a scan started event
+    pub fn scan_started(scan_id: String) -> Self {
+        Self::ScanStarted {
+            scan_id,
+            timestamp: now_millis(),
+        }
+    }
+
+    /// Create a message added event
+    pub fn message_added(scan_id: String, count: usize, queue_size: usize) -> Self {
+        Self::MessageAdded {
+            scan_id,
+            count,
+            queue_size,
+            timestamp: now_millis(),
+        }
+    }
+
+    /// Create a scan complete event
+    pub fn scan_complete(scan_id: String, total_messages: u64) -> Self {
+        Self::ScanComplete {
+            scan_id,
+            total_messages,
+            timestamp: now_millis(),
+        }
+    }
+
+    /// Create a producer blocked event
+    pub fn producer_blocked(scan_id: String, waited_ms: u64) -> Self {
+        Self::ProducerBlocked {
+            scan_id,
+            waited_ms,
+            timestamp: now_millis(),
+        }
+    }
+
+    /// Create a messages dropped event for an inclusive range of sequence numbers
+    pub fn messages_dropped(scan_id: String, first_sequence: u64, last_sequence: u64) -> Self {
+        Self::MessagesDropped {
+            scan_id,
+            count: last_sequence - first_sequence + 1,
+            first_sequence,
+            last_sequence,
+            timestamp: now_millis(),
+        }
+    }
+
+    /// Create a messages spilled event for an inclusive range of sequence numbers
+    pub fn messages_spilled(scan_id: String, first_sequence: u64, last_sequence: u64) -> Self {
+        Self::MessagesSpilled {
+            scan_id,
+            count: last_sequence - first_sequence + 1,
+            first_sequence,
+            last_sequence,
+            timestamp: now_millis(),
+        }
+    }
+}
+
+/// Milliseconds since the unix epoch
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_event_creation() {
+        let event = QueueEvent::scan_started("test-scan".to_string());
+        assert_eq!(event.scan_id(), "test-scan");
+        assert!(event.timestamp() > 0);
+
+        if let QueueEvent::ScanStarted { .. } = event {
+            // ScanStarted event created successfully
+        } else {
+            panic!("Expected ScanStarted event");
+        }
+    }
+}
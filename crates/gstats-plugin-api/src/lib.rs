@@ -0,0 +1,34 @@
+//! gstats Plugin API
+//! 
+//! Stable, plugin-facing traits and types shared between gstats and external plugins.
+//! External plugin authors depend on this crate rather than the full application,
+//! and the application re-exports these types at their historical paths
+//! (`gstats::plugin::data_export`, `gstats::scanner::messages`, etc.).
+//! 
+//! This crate is versioned with semver independently of gstats itself.
+//! 
+//! # Modules
+//! 
+//! - [`traits`]: the traits plugins implement, and the consumer handle they read messages through
+//! - [`context`]: the context plugins are initialised with, and execution requests and responses
+//! - [`settings`]: output settings passed to plugins
+//! - [`messages`]: scan messages delivered to plugins through the queue
+//! - [`events`]: queue events delivered to consumer plugins
+//! - [`data_export`]: structured data plugins publish for export
+//! - [`metadata`]: plugin identity, capabilities and advertised functions
+//! - [`error`]: plugin error and result types
+
+pub mod context;
+pub mod data_export;
+pub mod error;
+pub mod events;
+pub mod messages;
+pub mod metadata;
+pub mod settings;
+pub mod traits;
+
+pub use context::{PluginRequest, PluginResponse, ScanContext};
+pub use error::{PluginError, PluginResult};
+pub use metadata::{PluginCapability, PluginDependency, PluginFunction, PluginInfo, PluginType};
+pub use settings::PluginSettings;
+pub use traits::{ConsumerPlugin, MessageConsumer, Plugin, PluginClapParser};
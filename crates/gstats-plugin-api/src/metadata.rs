@@ -0,0 +1,201 @@
+//! Plugin Metadata
+//! 
+//! Descriptive metadata a plugin publishes about itself: identity, version,
+//! capabilities, dependencies and the functions it provides.
+
+use serde::{Serialize, Deserialize};
+
+/// Function that a plugin can provide
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginFunction {
+    /// Primary function name
+    pub name: String,
+    
+    /// Alternative names/aliases for this function
+    pub aliases: Vec<String>,
+    
+    /// Human-readable description
+    pub description: String,
+    
+    /// Whether this is the default function when plugin is invoked directly
+    pub is_default: bool,
+}
+
+/// Plugin metadata and information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// Plugin name (unique identifier)
+    pub name: String,
+    
+    /// Plugin version
+    pub version: String,
+    
+    /// API version this plugin targets
+    pub api_version: u32,
+    
+    /// Human-readable description
+    pub description: String,
+    
+    /// Plugin author
+    pub author: String,
+    
+    /// Plugin website or repository URL
+    pub url: Option<String>,
+    
+    /// Plugin dependencies
+    pub dependencies: Vec<PluginDependency>,
+    
+    /// Plugin capabilities
+    pub capabilities: Vec<PluginCapability>,
+    
+    /// Plugin type
+    pub plugin_type: PluginType,
+    
+    /// License information
+    pub license: Option<String>,
+    
+    /// Plugin execution priority (higher values = higher priority, default = 0)
+    pub priority: i32,
+    
+    /// Export plugins typically set this to set to true, else false
+    #[serde(default)]
+    pub active_by_default: bool,
+}
+
+/// Plugin dependency specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginDependency {
+    /// Dependency name
+    pub name: String,
+    
+    /// Version requirement (semver)
+    pub version_requirement: String,
+    
+    /// Whether dependency is optional
+    pub optional: bool,
+}
+
+/// Plugin capability specification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginCapability {
+    /// Capability name
+    pub name: String,
+    
+    /// Capability description
+    pub description: String,
+    
+    /// Capability version
+    pub version: String,
+    
+    /// Function name this capability represents (if applicable)
+    pub function_name: Option<String>,
+    
+    /// Aliases for the function
+    pub aliases: Vec<String>,
+    
+    /// Whether this is a default function
+    pub is_default: bool,
+}
+
+/// Plugin type classification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PluginType {
+    /// Notification plugin for event handling
+    Notification,
+    
+    /// Processing plugin for data transformation
+    Processing,
+    
+    /// Output plugin for data formatting
+    Output,
+    
+    /// Composite plugin with multiple capabilities
+    Composite,
+}
+
+impl PluginInfo {
+    /// Create a new PluginInfo
+    pub fn new(
+        name: String,
+        version: String,
+        api_version: u32,
+        description: String,
+        author: String,
+        plugin_type: PluginType,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            api_version,
+            description,
+            author,
+            url: None,
+            dependencies: Vec::new(),
+            capabilities: Vec::new(),
+            plugin_type,
+            license: None,
+            priority: 5, // Default priority
+            active_by_default: false, // Default to manual activation
+        }
+    }
+    
+    /// Add a dependency
+    pub fn with_dependency(mut self, name: String, version_requirement: String, optional: bool) -> Self {
+        self.dependencies.push(PluginDependency {
+            name,
+            version_requirement,
+            optional,
+        });
+        self
+    }
+    
+    /// Declare a required dependency on another plugin, of any version
+    ///
+    /// The registry activates dependencies before the plugins that need them.
+    pub fn depends_on(self, name: String) -> Self {
+        self.with_dependency(name, "*".to_string(), false)
+    }
+    
+    /// Add a capability
+    pub fn with_capability(mut self, name: String, description: String, version: String) -> Self {
+        self.capabilities.push(PluginCapability {
+            name,
+            description,
+            version,
+            function_name: None,
+            aliases: Vec::new(),
+            is_default: false,
+        });
+        self
+    }
+    
+    /// Set URL
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = Some(url);
+        self
+    }
+    
+    /// Set license
+    pub fn with_license(mut self, license: String) -> Self {
+        self.license = Some(license);
+        self
+    }
+    
+    /// Set plugin execution priority
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+    
+    /// Set whether plugin should be activated by default
+    pub fn with_active_by_default(mut self, active_by_default: bool) -> Self {
+        self.active_by_default = active_by_default;
+        self
+    }
+    
+    /// Check if plugin is compatible with API version
+    pub fn is_compatible_with_api(&self, api_version: u32) -> bool {
+        // Simple compatibility check - same major version
+        self.api_version / 10000 == api_version / 10000
+    }
+}
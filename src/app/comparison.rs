@@ -0,0 +1,560 @@
+//! Branch Comparison
+//!
+//! Scans two refs of the same repository and reports how their statistics
+//! differ, e.g. what changed between two release branches. Each ref is scanned
+//! in full with the CLI query filters applied, and every change is `head - base`.
+//! The results are ordinary tabular exports, so any export format can show them.
+//!
+//! A ref matrix (`--matrix-refs`) scans any number of refs and lists each metric
+//! and author with one column per ref instead, for side-by-side branch health.
+//! Ref scans go through the scan cache, so repeated runs only read new commits,
+//! and refs pointing at the same commit are scanned once.
+
+use crate::cache::ScanCache;
+use crate::plugin::data_export::{
+    ColumnDef, ColumnType, DataExportType, DataPayload, DataSchema, ExportHints, PluginDataExport, Row, Value,
+};
+use crate::scanner::async_engine::error::{ScanError, ScanResult};
+use crate::scanner::async_engine::scanners::EventDrivenScanner;
+use crate::scanner::identity::IdentityRules;
+use crate::scanner::imports::ImportRules;
+use crate::scanner::messages::{MessageData, ScanMessage};
+use crate::scanner::query::QueryParams;
+use futures::StreamExt;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The two refs named by `--compare`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareRefs {
+    /// Ref the changes are measured from
+    pub base: String,
+    /// Ref the changes are measured to; `None` uses the scanned branch
+    pub head: Option<String>,
+}
+
+impl CompareRefs {
+    /// Parse `BASE` or `BASE..HEAD`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (base, head) = match spec.split_once("..") {
+            Some((base, head)) => (base.trim(), Some(head.trim())),
+            None => (spec.trim(), None),
+        };
+        if base.is_empty() || head.is_some_and(str::is_empty) {
+            return Err(format!("Invalid comparison '{spec}': expected BASE or BASE..HEAD"));
+        }
+        Ok(Self {
+            base: base.to_string(),
+            head: head.map(str::to_string),
+        })
+    }
+}
+
+/// Commits and changed lines attributed to one author on a ref
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AuthorStats {
+    pub commits: usize,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+impl AuthorStats {
+    fn lines_changed(&self) -> usize {
+        self.lines_added + self.lines_removed
+    }
+}
+
+/// Statistics collected from one scanned ref
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RefStats {
+    /// Ref name as given on the command line, or the repository's identifier in multi-repository scans
+    pub name: String,
+    pub commits: usize,
+    pub authors: BTreeMap<String, AuthorStats>,
+    /// Distinct paths changed by the scanned commits
+    pub files: BTreeSet<String>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    /// Timestamps of the oldest and newest scanned commits
+    pub first_commit: Option<i64>,
+    pub last_commit: Option<i64>,
+}
+
+impl RefStats {
+    /// Summarise the messages produced by scanning a ref
+    pub fn from_messages(name: impl Into<String>, messages: &[ScanMessage]) -> Self {
+        let mut stats = RefStats { name: name.into(), ..Default::default() };
+        let mut commit_authors: HashMap<&str, &str> = HashMap::new();
+
+        for message in messages {
+            if let MessageData::CommitInfo { hash, author, timestamp, .. } = &message.data {
+                commit_authors.insert(hash, author);
+                stats.commits += 1;
+                stats.authors.entry(author.clone()).or_default().commits += 1;
+                stats.first_commit = Some(stats.first_commit.map_or(*timestamp, |first| first.min(*timestamp)));
+                stats.last_commit = Some(stats.last_commit.map_or(*timestamp, |last| last.max(*timestamp)));
+            }
+        }
+
+        for message in messages {
+            if let MessageData::FileChange { path, insertions, deletions, commit_hash, .. } = &message.data {
+                stats.files.insert(path.clone());
+                stats.lines_added += insertions;
+                stats.lines_removed += deletions;
+                if let Some(author) = commit_authors.get(commit_hash.as_str()) {
+                    let author = stats.authors.entry(author.to_string()).or_default();
+                    author.lines_added += insertions;
+                    author.lines_removed += deletions;
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Days between the oldest and newest scanned commits (inclusive)
+    pub fn active_days(&self) -> usize {
+        match (self.first_commit, self.last_commit) {
+            (Some(first), Some(last)) => ((last - first) / SECONDS_PER_DAY + 1) as usize,
+            _ => 0,
+        }
+    }
+}
+
+/// Commit a ref points at, if it can be resolved
+pub fn resolve_ref(repository_path: &Path, reference: &str) -> Option<String> {
+    let repo = crate::scanner::repository::open_repository(repository_path).ok()?;
+    let object = repo.rev_parse_single(reference).ok()?.object().ok()?;
+    let commit = object.peel_to_commit().ok()?;
+    Some(commit.id.to_string())
+}
+
+/// Scan a single ref with the given query, import rules and author identities and summarise the result
+pub async fn scan_ref(
+    repository_path: &Path,
+    query_params: &QueryParams,
+    imports: &ImportRules,
+    identities: &IdentityRules,
+    cache: Option<&ScanCache>,
+    reference: &str,
+) -> ScanResult<RefStats> {
+    let query = QueryParams {
+        branch: Some(reference.to_string()),
+        scanned_ref: None,
+        shallow: None,
+        ..query_params.clone()
+    };
+    let mut scanner = EventDrivenScanner::new(query)
+        .with_imports(imports.clone())
+        .with_identities(identities.clone());
+    if let Some(cache) = cache {
+        scanner = scanner.with_cache(cache.clone());
+    }
+    let mut stream = scanner.scan_async(repository_path).await?;
+
+    let mut messages = Vec::new();
+    while let Some(message) = stream.next().await {
+        messages.push(message?);
+    }
+    if messages.is_empty() {
+        return Err(ScanError::Repository(format!("No commits matched the filters on '{reference}'")));
+    }
+    Ok(RefStats::from_messages(reference, &messages))
+}
+
+/// Statistics of two refs and the tables describing their differences
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub base: RefStats,
+    pub head: RefStats,
+}
+
+impl Comparison {
+    pub fn new(base: RefStats, head: RefStats) -> Self {
+        Self { base, head }
+    }
+
+    /// Commit, author and metric delta tables
+    pub fn exports(&self) -> Vec<Arc<PluginDataExport>> {
+        vec![
+            Arc::new(self.commits_export()),
+            Arc::new(self.authors_export()),
+            Arc::new(self.metrics_export()),
+        ]
+    }
+
+    fn commits_export(&self) -> PluginDataExport {
+        let rows = vec![
+            self.delta_row("Commits", self.base.commits, self.head.commits),
+            self.delta_row("Authors", self.base.authors.len(), self.head.authors.len()),
+            self.delta_row("Active Days", self.base.active_days(), self.head.active_days()),
+        ];
+        self.export("compare-commits", "Commit Comparison", "Commit activity", self.delta_schema(), rows)
+    }
+
+    fn metrics_export(&self) -> PluginDataExport {
+        let net = |stats: &RefStats| stats.lines_added as i64 - stats.lines_removed as i64;
+        let rows = vec![
+            self.delta_row("Files Changed", self.base.files.len(), self.head.files.len()),
+            self.delta_row("Lines Added", self.base.lines_added, self.head.lines_added),
+            self.delta_row("Lines Removed", self.base.lines_removed, self.head.lines_removed),
+            Row::new(vec![
+                Value::String("Net Lines".to_string()),
+                Value::Integer(net(&self.base)),
+                Value::Integer(net(&self.head)),
+                Value::Integer(net(&self.head) - net(&self.base)),
+            ]),
+        ];
+        self.export("compare-metrics", "Change Metrics Comparison", "File and line change metrics", self.delta_schema(), rows)
+    }
+
+    fn authors_export(&self) -> PluginDataExport {
+        let schema = DataSchema::new(vec![
+            ColumnDef::new("Author", ColumnType::String),
+            ColumnDef::new(format!("{} Commits", self.base.name), ColumnType::Integer),
+            ColumnDef::new(format!("{} Commits", self.head.name), ColumnType::Integer),
+            ColumnDef::new("Change", ColumnType::Integer)
+                .with_description("Commits on the head ref minus commits on the base ref")
+                .with_format_hint("delta"),
+            ColumnDef::new("Lines Change", ColumnType::Integer)
+                .with_description("Lines added and removed on the head ref minus those on the base ref")
+                .with_format_hint("delta"),
+            ColumnDef::new("Status", ColumnType::String)
+                .with_description("'new' for authors only on the head ref, 'absent' for authors only on the base ref"),
+        ]);
+
+        let mut authors: Vec<(&String, AuthorStats, AuthorStats)> = self.base.authors.keys()
+            .chain(self.head.authors.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|author| {
+                let base = self.base.authors.get(author).copied().unwrap_or_default();
+                let head = self.head.authors.get(author).copied().unwrap_or_default();
+                (author, base, head)
+            })
+            .collect();
+        authors.sort_by_key(|(_, base, head)| std::cmp::Reverse(head.commits.abs_diff(base.commits)));
+
+        let rows = authors
+            .into_iter()
+            .map(|(author, base, head)| {
+                let status = match (base.commits, head.commits) {
+                    (0, _) => "new",
+                    (_, 0) => "absent",
+                    _ => "",
+                };
+                Row::new(vec![
+                    Value::String(author.clone()),
+                    Value::Integer(base.commits as i64),
+                    Value::Integer(head.commits as i64),
+                    Value::Integer(head.commits as i64 - base.commits as i64),
+                    Value::Integer(head.lines_changed() as i64 - base.lines_changed() as i64),
+                    Value::String(status.to_string()),
+                ])
+            })
+            .collect();
+
+        self.export("compare-authors", "Author Comparison", "Commits per author", schema, rows)
+    }
+
+    /// Schema shared by the metric-per-row tables
+    fn delta_schema(&self) -> DataSchema {
+        DataSchema::new(vec![
+            ColumnDef::new("Metric", ColumnType::String),
+            ColumnDef::new(self.base.name.clone(), ColumnType::Integer),
+            ColumnDef::new(self.head.name.clone(), ColumnType::Integer),
+            ColumnDef::new("Change", ColumnType::Integer)
+                .with_description("Head ref value minus base ref value")
+                .with_format_hint("delta"),
+        ])
+    }
+
+    fn delta_row(&self, metric: &str, base: usize, head: usize) -> Row {
+        Row::new(vec![
+            Value::String(metric.to_string()),
+            Value::Integer(base as i64),
+            Value::Integer(head as i64),
+            Value::Integer(head as i64 - base as i64),
+        ])
+    }
+
+    fn export(&self, id: &str, title: &str, what: &str, schema: DataSchema, rows: Vec<Row>) -> PluginDataExport {
+        let schema = schema
+            .with_metadata("base_ref", self.base.name.clone())
+            .with_metadata("head_ref", self.head.name.clone());
+        PluginDataExport {
+            plugin_id: id.to_string(),
+            title: title.to_string(),
+            description: Some(format!("{} on {} compared with {}", what, self.head.name, self.base.name)),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints::default(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+}
+
+/// Statistics of several refs, reported with one column per ref
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefMatrix {
+    pub refs: Vec<RefStats>,
+}
+
+impl RefMatrix {
+    pub fn new(refs: Vec<RefStats>) -> Self {
+        Self { refs }
+    }
+
+    /// Refs named by `--matrix-refs`, checking there are at least two and none is repeated
+    pub fn parse_refs(refs: &[String]) -> Result<Vec<String>, String> {
+        let refs: Vec<String> = refs.iter().map(|reference| reference.trim().to_string()).collect();
+        if refs.iter().any(String::is_empty) {
+            return Err("Invalid --matrix-refs: empty ref name".to_string());
+        }
+        if let Some(duplicate) = refs.iter().enumerate().find(|(i, reference)| refs[..*i].contains(reference)) {
+            return Err(format!("Invalid --matrix-refs: '{}' is listed twice", duplicate.1));
+        }
+        if refs.len() < 2 {
+            return Err("--matrix-refs needs at least two refs (use --compare BASE to compare with the scanned branch)".to_string());
+        }
+        Ok(refs)
+    }
+
+    /// Metric and author tables
+    pub fn exports(&self) -> Vec<Arc<PluginDataExport>> {
+        vec![
+            Arc::new(self.metrics_export()),
+            Arc::new(self.authors_export()),
+        ]
+    }
+
+    fn metrics_export(&self) -> PluginDataExport {
+        const METRICS: [&str; 7] = ["Commits", "Authors", "Active Days", "Files Changed", "Lines Added", "Lines Removed", "Net Lines"];
+        let values: Vec<[i64; 7]> = self.refs.iter()
+            .map(|stats| [
+                stats.commits as i64,
+                stats.authors.len() as i64,
+                stats.active_days() as i64,
+                stats.files.len() as i64,
+                stats.lines_added as i64,
+                stats.lines_removed as i64,
+                stats.lines_added as i64 - stats.lines_removed as i64,
+            ])
+            .collect();
+        let rows = METRICS.iter().enumerate()
+            .map(|(i, metric)| {
+                let values = values.iter().map(|values| Value::Integer(values[i]));
+                Row::new(std::iter::once(Value::String(metric.to_string())).chain(values).collect())
+            })
+            .collect();
+        self.export("matrix-metrics", "Ref Matrix", "Commit activity and change metrics", self.schema("Metric"), rows)
+    }
+
+    fn authors_export(&self) -> PluginDataExport {
+        let mut authors: Vec<(&String, usize)> = self.refs.iter()
+            .flat_map(|stats| stats.authors.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|author| (author, self.refs.iter().filter_map(|stats| stats.authors.get(author)).map(|stats| stats.commits).sum()))
+            .collect();
+        authors.sort_by_key(|(_, commits)| std::cmp::Reverse(*commits));
+
+        let rows = authors.into_iter()
+            .map(|(author, _)| {
+                let commits = self.refs.iter()
+                    .map(|stats| Value::Integer(stats.authors.get(author).map_or(0, |author| author.commits) as i64));
+                Row::new(std::iter::once(Value::String(author.clone())).chain(commits).collect())
+            })
+            .collect();
+        self.export("matrix-authors", "Author Ref Matrix", "Commits per author", self.schema("Author"), rows)
+    }
+
+    /// A name column followed by one column per ref
+    fn schema(&self, name: &str) -> DataSchema {
+        let columns = std::iter::once(ColumnDef::new(name, ColumnType::String))
+            .chain(self.refs.iter().map(|stats| ColumnDef::new(stats.name.clone(), ColumnType::Integer)))
+            .collect();
+        DataSchema::new(columns)
+    }
+
+    fn export(&self, id: &str, title: &str, what: &str, schema: DataSchema, rows: Vec<Row>) -> PluginDataExport {
+        let refs: Vec<&str> = self.refs.iter().map(|stats| stats.name.as_str()).collect();
+        PluginDataExport {
+            plugin_id: id.to_string(),
+            title: title.to_string(),
+            description: Some(format!("{} on {}", what, refs.join(", "))),
+            data_type: DataExportType::Tabular,
+            schema: schema.with_metadata("refs", refs.join(",")),
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints::default(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::report::render;
+    use crate::display::ColourManager;
+    use crate::plugin::builtin::export::config::ExportFormat as OutputFormat;
+    use crate::scanner::messages::{ChangeType, MessageHeader};
+
+    fn commit(hash: &str, author: &str, timestamp: i64) -> ScanMessage {
+        ScanMessage {
+            header: MessageHeader { sequence: 0, timestamp: 0, scan_id: "test".to_string(), repository: None, branch: None },
+            data: MessageData::CommitInfo {
+                hash: hash.to_string(),
+                author: author.to_string(),
+                message: "change".to_string(),
+                timestamp,
+                committer: author.to_string(),
+                author_timestamp: timestamp,
+                parents: vec![],
+                changed_files: Vec::new(),
+            },
+        }
+    }
+
+    fn change(hash: &str, path: &str, insertions: usize, deletions: usize) -> ScanMessage {
+        ScanMessage {
+            header: MessageHeader { sequence: 0, timestamp: 0, scan_id: "test".to_string(), repository: None, branch: None },
+            data: MessageData::FileChange {
+                path: path.to_string(),
+                change_type: ChangeType::Modified,
+                old_path: None,
+                insertions,
+                deletions,
+                is_binary: false,
+                binary_size: None,
+                line_count: None,
+                commit_hash: hash.to_string(),
+                commit_timestamp: 0,
+                checkout_path: None,
+                old_mode: None,
+                new_mode: None,
+                replaced_lines: Vec::new(),
+            },
+        }
+    }
+
+    fn base() -> RefStats {
+        RefStats::from_messages("v1", &[
+            commit("a", "alice", 0),
+            change("a", "src/lib.rs", 10, 0),
+        ])
+    }
+
+    fn head() -> RefStats {
+        RefStats::from_messages("main", &[
+            commit("b", "bob", 2 * SECONDS_PER_DAY),
+            change("b", "README.md", 4, 1),
+            commit("a", "alice", 0),
+            change("a", "src/lib.rs", 10, 0),
+        ])
+    }
+
+    fn rows(export: &PluginDataExport) -> Vec<Vec<Value>> {
+        match &export.data {
+            DataPayload::Rows(rows) => rows.iter().map(|row| row.values.clone()).collect(),
+            _ => panic!("expected rows"),
+        }
+    }
+
+    #[test]
+    fn test_parse_compare_refs() {
+        assert_eq!(
+            CompareRefs::parse("v1.0").unwrap(),
+            CompareRefs { base: "v1.0".to_string(), head: None }
+        );
+        assert_eq!(
+            CompareRefs::parse("release/1..release/2").unwrap(),
+            CompareRefs { base: "release/1".to_string(), head: Some("release/2".to_string()) }
+        );
+        assert!(CompareRefs::parse("v1.0..").is_err());
+        assert!(CompareRefs::parse("..main").is_err());
+    }
+
+    #[test]
+    fn test_ref_stats_attribute_lines_to_authors() {
+        let stats = head();
+        assert_eq!(stats.commits, 2);
+        assert_eq!(stats.files.len(), 2);
+        assert_eq!((stats.lines_added, stats.lines_removed), (14, 1));
+        assert_eq!(stats.authors["bob"], AuthorStats { commits: 1, lines_added: 4, lines_removed: 1 });
+        assert_eq!(stats.active_days(), 3);
+    }
+
+    #[test]
+    fn test_comparison_tables_report_deltas() {
+        let exports = Comparison::new(base(), head()).exports();
+        let ids: Vec<&str> = exports.iter().map(|export| export.plugin_id.as_str()).collect();
+        assert_eq!(ids, vec!["compare-commits", "compare-authors", "compare-metrics"]);
+
+        let commits = rows(&exports[0]);
+        assert_eq!(commits[0], vec![
+            Value::String("Commits".to_string()), Value::Integer(1), Value::Integer(2), Value::Integer(1),
+        ]);
+
+        let authors = rows(&exports[1]);
+        assert_eq!(authors[0][0], Value::String("bob".to_string()));
+        assert_eq!(authors[0][4], Value::Integer(5));
+        assert_eq!(authors[0][5], Value::String("new".to_string()));
+        assert_eq!(authors[1][5], Value::String(String::new()));
+
+        let metrics = rows(&exports[2]);
+        assert_eq!(metrics[3], vec![
+            Value::String("Net Lines".to_string()), Value::Integer(10), Value::Integer(13), Value::Integer(3),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_matrix_refs() {
+        let refs = |refs: &[&str]| RefMatrix::parse_refs(&refs.iter().map(|r| r.to_string()).collect::<Vec<_>>());
+        assert_eq!(refs(&["main", " develop"]).unwrap(), vec!["main".to_string(), "develop".to_string()]);
+        assert!(refs(&["main"]).is_err());
+        assert!(refs(&["main", ""]).is_err());
+        assert!(refs(&["main", "develop", "main"]).unwrap_err().contains("'main' is listed twice"));
+    }
+
+    #[test]
+    fn test_ref_matrix_has_a_column_per_ref() {
+        let exports = RefMatrix::new(vec![base(), head()]).exports();
+        let columns: Vec<&str> = exports[0].schema.columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(columns, vec!["Metric", "v1", "main"]);
+
+        let metrics = rows(&exports[0]);
+        assert_eq!(metrics[0], vec![Value::String("Commits".to_string()), Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(metrics[6], vec![Value::String("Net Lines".to_string()), Value::Integer(10), Value::Integer(13)]);
+
+        let authors = rows(&exports[1]);
+        assert_eq!(authors[0], vec![Value::String("alice".to_string()), Value::Integer(1), Value::Integer(1)]);
+        assert_eq!(authors[1], vec![Value::String("bob".to_string()), Value::Integer(0), Value::Integer(1)]);
+    }
+
+    #[test]
+    fn test_changes_marked_with_symbols() {
+        let exports = Comparison::new(base(), head()).exports();
+
+        // Without colour, changes carry their direction as a symbol
+        let plain = Arc::new(ColourManager::with_config(crate::display::ColourConfig::disabled()));
+        let console = render(&exports, OutputFormat::Console, Some(plain)).unwrap();
+        assert!(console.contains("▲ 1"), "{}", console);
+
+        let html = render(&exports, OutputFormat::Html, None).unwrap();
+        assert!(html.contains("<td class=\"up\">1</td>"), "{}", html);
+
+        let mut config = crate::display::ColourConfig::new();
+        config.set_accessible(true);
+        let accessible = Arc::new(ColourManager::with_config(config));
+        let html = render(&exports, OutputFormat::Html, Some(accessible)).unwrap();
+        assert!(html.contains("<td class=\"up\">▲ 1</td>"), "{}", html);
+    }
+}
@@ -0,0 +1,455 @@
+//! Technical Debt Trend
+//!
+//! The metrics plugin assesses technical debt in the scanned tree only. A debt
+//! trend (`--debt-trend <INTERVAL>`) samples the history instead: every N
+//! commits, the last commit of each month, or every tagged commit. Each sample
+//! is scanned with the content of its tree, so files are assessed as they were
+//! at that commit, with the change history up to it and ages measured from its
+//! date. The result is one row per sample, oldest first.
+
+use crate::cache::ScanCache;
+use crate::plugin::data_export::{
+    ColumnDef, ColumnType, DataExportType, DataPayload, DataSchema, ExportHints, PluginDataExport, Row, Value,
+};
+use crate::plugin::processors::change_frequency::FileChangeStats;
+use crate::plugin::processors::complexity::ComplexityMetrics;
+use crate::plugin::processors::debt_assessment::{DebtAssessmentProcessor, DebtConfig, DebtLevel};
+use crate::scanner::async_engine::error::{ScanError, ScanResult};
+use crate::scanner::async_engine::scanners::EventDrivenScanner;
+use crate::scanner::identity::IdentityRules;
+use crate::scanner::imports::ImportRules;
+use crate::scanner::messages::{MessageData, ScanMessage};
+use crate::scanner::query::QueryParams;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// How the history is sampled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleInterval {
+    /// Every N commits, counted from the oldest
+    Commits(usize),
+    /// The last commit of each calendar month
+    Monthly,
+    /// Every tagged commit
+    Tags,
+}
+
+impl SampleInterval {
+    /// Parse `--debt-trend`: a number of commits, `monthly` or `tags`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match spec.trim().to_lowercase().as_str() {
+            "monthly" => Ok(Self::Monthly),
+            "tags" => Ok(Self::Tags),
+            other => match other.parse::<usize>() {
+                Ok(0) => Err("Invalid --debt-trend: the commit interval must be at least 1".to_string()),
+                Ok(commits) => Ok(Self::Commits(commits)),
+                Err(_) => Err(format!("Invalid --debt-trend '{}': expected a number of commits, 'monthly' or 'tags'", spec)),
+            },
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Commits(1) => "every commit".to_string(),
+            Self::Commits(commits) => format!("every {} commits", commits),
+            Self::Monthly => "monthly".to_string(),
+            Self::Tags => "at each tag".to_string(),
+        }
+    }
+}
+
+/// A commit of the sampled history
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryCommit {
+    pub hash: String,
+    pub timestamp: i64,
+}
+
+/// A commit chosen for assessment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// Short commit id, month or tag names
+    pub label: String,
+    pub hash: String,
+    pub timestamp: i64,
+}
+
+/// Choose the commits to assess from a history listed oldest first
+///
+/// Commit intervals always include the newest commit, so the trend ends at the
+/// scanned tree. `tags` maps commit ids to the names of the tags on them.
+pub fn select_samples(
+    history: &[HistoryCommit],
+    interval: SampleInterval,
+    tags: &HashMap<String, Vec<String>>,
+) -> Vec<Sample> {
+    let sample = |commit: &HistoryCommit, label: String| Sample {
+        label,
+        hash: commit.hash.clone(),
+        timestamp: commit.timestamp,
+    };
+    match interval {
+        SampleInterval::Commits(every) => history.iter()
+            .enumerate()
+            .filter(|(index, _)| (index + 1) % every == 0 || index + 1 == history.len())
+            .map(|(_, commit)| sample(commit, commit.hash.chars().take(8).collect()))
+            .collect(),
+        SampleInterval::Monthly => {
+            let mut samples: Vec<Sample> = Vec::new();
+            for commit in history {
+                let month = format_month(commit.timestamp);
+                match samples.last_mut() {
+                    Some(last) if last.label == month => *last = sample(commit, month),
+                    _ => samples.push(sample(commit, month)),
+                }
+            }
+            samples
+        }
+        SampleInterval::Tags => history.iter()
+            .filter_map(|commit| {
+                let mut names = tags.get(&commit.hash)?.clone();
+                names.sort();
+                Some(sample(commit, names.join(", ")))
+            })
+            .collect(),
+    }
+}
+
+/// Debt and complexity of the tree at one sampled commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtTrendPoint {
+    pub sample: Sample,
+    /// Text files assessed, leaving out generated files
+    pub files: usize,
+    pub lines_of_code: usize,
+    pub average_complexity: f64,
+    pub average_debt: f64,
+    pub max_debt: f64,
+    /// Files at or above the debt reporting threshold
+    pub debt_files: usize,
+    pub critical_files: usize,
+    pub estimated_hours: f64,
+}
+
+impl DebtTrendPoint {
+    /// Assess the files and change history of a sample's scan
+    ///
+    /// Ages and change frequencies are measured from the run clock, which the
+    /// caller fixes at the sample's date.
+    pub fn assess(sample: Sample, messages: &[ScanMessage], config: &DebtConfig) -> Self {
+        let mut complexity = HashMap::new();
+        let mut changes: HashMap<String, FileChangeStats> = HashMap::new();
+        for message in messages {
+            match &message.data {
+                MessageData::FileContent { path, content, generated, .. } if !generated => {
+                    complexity.insert(path.clone(), ComplexityMetrics::from_content(path.clone(), content));
+                }
+                MessageData::CommitInfo { hash, author, timestamp, changed_files, .. } => {
+                    for file in changed_files {
+                        changes.entry(file.path.clone())
+                            .or_insert_with(|| FileChangeStats::new(file.path.clone()))
+                            .add_change(*timestamp, author.clone(), hash.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Every file is assessed so the averages cover the whole tree
+        let mut debt = DebtAssessmentProcessor::with_config(DebtConfig { debt_threshold: 0.0, ..config.clone() });
+        debt.assess_debt(&complexity, &changes, &HashMap::new());
+        let assessments = debt.get_top_debt_files(usize::MAX);
+
+        let files = complexity.len();
+        let mean = |total: f64| if files == 0 { 0.0 } else { total / files as f64 };
+        Self {
+            sample,
+            files,
+            lines_of_code: complexity.values().map(|metrics| metrics.lines_of_code as usize).sum(),
+            average_complexity: mean(complexity.values().map(ComplexityMetrics::complexity_score).sum()),
+            average_debt: mean(assessments.iter().map(|assessment| assessment.debt_score).sum()),
+            max_debt: assessments.first().map_or(0.0, |assessment| assessment.debt_score),
+            debt_files: assessments.iter().filter(|assessment| assessment.debt_score >= config.debt_threshold).count(),
+            critical_files: assessments.iter().filter(|assessment| assessment.debt_level == DebtLevel::Critical).count(),
+            estimated_hours: assessments.iter()
+                .filter(|assessment| assessment.debt_score >= config.debt_threshold)
+                .fold(0.0, |total, assessment| total + assessment.estimated_hours),
+        }
+    }
+}
+
+/// Debt assessments of the sampled commits, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtTrend {
+    pub interval: SampleInterval,
+    pub points: Vec<DebtTrendPoint>,
+}
+
+impl DebtTrend {
+    pub fn new(interval: SampleInterval, points: Vec<DebtTrendPoint>) -> Self {
+        Self { interval, points }
+    }
+
+    /// The time-series table
+    pub fn exports(&self) -> Vec<Arc<PluginDataExport>> {
+        vec![Arc::new(self.trend_export())]
+    }
+
+    fn trend_export(&self) -> PluginDataExport {
+        let schema = DataSchema::new(vec![
+            ColumnDef::new("Sample", ColumnType::String),
+            ColumnDef::new("Commit", ColumnType::String),
+            ColumnDef::new("Date", ColumnType::String),
+            ColumnDef::new("Files", ColumnType::Integer),
+            ColumnDef::new("Lines", ColumnType::Integer),
+            ColumnDef::new("Avg Complexity", ColumnType::Float),
+            ColumnDef::new("Avg Debt", ColumnType::Float)
+                .with_description("Mean debt score of all files, 0-100"),
+            ColumnDef::new("Debt Change", ColumnType::Float)
+                .with_description("Change in mean debt score since the previous sample"),
+            ColumnDef::new("Max Debt", ColumnType::Float),
+            ColumnDef::new("Debt Files", ColumnType::Integer)
+                .with_description("Files at or above the debt reporting threshold"),
+            ColumnDef::new("Critical", ColumnType::Integer),
+            ColumnDef::new("Est. Hours", ColumnType::Float)
+                .with_description("Estimated hours to address the debt files"),
+        ])
+        .with_metadata("interval", self.interval.describe())
+        .with_metadata("samples", self.points.len().to_string());
+
+        let mut previous: Option<f64> = None;
+        let rows = self.points.iter()
+            .map(|point| {
+                let change = previous.map_or(0.0, |previous| point.average_debt - previous);
+                previous = Some(point.average_debt);
+                Row::new(vec![
+                    Value::String(point.sample.label.clone()),
+                    Value::String(point.sample.hash.chars().take(12).collect()),
+                    Value::String(crate::plugin::processors::commit_size::format_date(point.sample.timestamp)),
+                    Value::Integer(point.files as i64),
+                    Value::Integer(point.lines_of_code as i64),
+                    Value::Float(round2(point.average_complexity)),
+                    Value::Float(round2(point.average_debt)),
+                    Value::Float(round2(change)),
+                    Value::Float(round2(point.max_debt)),
+                    Value::Integer(point.debt_files as i64),
+                    Value::Integer(point.critical_files as i64),
+                    Value::Float(round2(point.estimated_hours)),
+                ])
+            })
+            .collect();
+
+        PluginDataExport {
+            plugin_id: "debt-trend".to_string(),
+            title: "Debt Trend".to_string(),
+            description: Some(format!(
+                "Technical debt and complexity sampled {} at {} commits",
+                self.interval.describe(), self.points.len()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints::default(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+}
+
+/// Commits of the scanned history, oldest first
+pub async fn scan_history(
+    repository_path: &Path,
+    query_params: &QueryParams,
+    imports: &ImportRules,
+    identities: &IdentityRules,
+    cache: Option<&ScanCache>,
+) -> ScanResult<Vec<HistoryCommit>> {
+    let messages = scan(repository_path, query_params.clone(), imports, identities, cache, false).await?;
+    let mut history: Vec<HistoryCommit> = messages.into_iter()
+        .filter_map(|message| match message.data {
+            MessageData::CommitInfo { hash, timestamp, .. } => Some(HistoryCommit { hash, timestamp }),
+            _ => None,
+        })
+        .collect();
+    // The walk lists the newest commits first; commits made in the same second keep that order reversed
+    history.reverse();
+    history.sort_by_key(|commit| commit.timestamp);
+    if history.is_empty() {
+        return Err(ScanError::Repository("No commits matched the filters".to_string()));
+    }
+    Ok(history)
+}
+
+/// Scan a sampled commit's history and the content of its tree
+pub async fn scan_sample(
+    repository_path: &Path,
+    query_params: &QueryParams,
+    imports: &ImportRules,
+    identities: &IdentityRules,
+    cache: Option<&ScanCache>,
+    sample: &Sample,
+) -> ScanResult<Vec<ScanMessage>> {
+    let query = QueryParams {
+        branch: Some(sample.hash.clone()),
+        scanned_ref: None,
+        shallow: None,
+        ..query_params.clone()
+    };
+    scan(repository_path, query, imports, identities, cache, true).await
+}
+
+async fn scan(
+    repository_path: &Path,
+    query: QueryParams,
+    imports: &ImportRules,
+    identities: &IdentityRules,
+    cache: Option<&ScanCache>,
+    file_content: bool,
+) -> ScanResult<Vec<ScanMessage>> {
+    let mut scanner = EventDrivenScanner::new(query)
+        .with_imports(imports.clone())
+        .with_identities(identities.clone())
+        .with_current_file_content(file_content);
+    if let Some(cache) = cache {
+        scanner = scanner.with_cache(cache.clone());
+    }
+    let mut stream = scanner.scan_async(repository_path).await?;
+
+    let mut messages = Vec::new();
+    while let Some(message) = stream.next().await {
+        messages.push(message?);
+    }
+    Ok(messages)
+}
+
+/// Names of the tags on each commit, keyed by commit id
+pub fn tagged_commits(repository_path: &Path) -> anyhow::Result<HashMap<String, Vec<String>>> {
+    let repo = crate::scanner::repository::open_repository(repository_path)?;
+    let references = repo.references().map_err(|e| anyhow::anyhow!("Failed to read tags: {}", e))?;
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    for reference in references.tags().map_err(|e| anyhow::anyhow!("Failed to read tags: {}", e))? {
+        let Ok(mut reference) = reference else {
+            continue;
+        };
+        // Tags of trees and blobs have no place in the history
+        if let Ok(commit) = reference.peel_to_commit() {
+            tags.entry(commit.id.to_string()).or_default().push(reference.name().shorten().to_string());
+        }
+    }
+    Ok(tags)
+}
+
+/// Year and month of a commit time, e.g. `2024-03`
+fn format_month(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::messages::{FileChangeData, MessageHeader};
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn history() -> Vec<HistoryCommit> {
+        // 2024-01-30, 2024-01-31, 2024-02-01, 2024-02-02, 2024-03-15
+        [1_706_572_800, 1_706_659_200, 1_706_745_600, 1_706_832_000, 1_710_460_800]
+            .iter()
+            .enumerate()
+            .map(|(index, timestamp)| HistoryCommit { hash: format!("{:040}", index), timestamp: *timestamp })
+            .collect()
+    }
+
+    fn message(data: MessageData) -> ScanMessage {
+        ScanMessage::new(MessageHeader::new(0, "test".to_string()), data)
+    }
+
+    #[test]
+    fn test_parse_sample_interval() {
+        assert_eq!(SampleInterval::parse("50"), Ok(SampleInterval::Commits(50)));
+        assert_eq!(SampleInterval::parse("Monthly"), Ok(SampleInterval::Monthly));
+        assert_eq!(SampleInterval::parse("tags"), Ok(SampleInterval::Tags));
+        assert!(SampleInterval::parse("0").is_err());
+        assert!(SampleInterval::parse("weekly").is_err());
+    }
+
+    #[test]
+    fn test_select_samples() {
+        let history = history();
+        let hashes = |samples: Vec<Sample>| samples.into_iter().map(|sample| sample.hash).collect::<Vec<_>>();
+
+        // Every second commit, ending at the newest
+        let samples = select_samples(&history, SampleInterval::Commits(2), &HashMap::new());
+        assert_eq!(hashes(samples), [&history[1].hash, &history[3].hash, &history[4].hash].map(String::clone));
+
+        // The last commit of each month
+        let samples = select_samples(&history, SampleInterval::Monthly, &HashMap::new());
+        let labels: Vec<&str> = samples.iter().map(|sample| sample.label.as_str()).collect();
+        assert_eq!(labels, ["2024-01", "2024-02", "2024-03"]);
+        assert_eq!(hashes(samples), [&history[1].hash, &history[3].hash, &history[4].hash].map(String::clone));
+
+        let tags = HashMap::from([
+            (history[2].hash.clone(), vec!["v1.0.0".to_string(), "release".to_string()]),
+            ("f".repeat(40), vec!["elsewhere".to_string()]),
+        ]);
+        let samples = select_samples(&history, SampleInterval::Tags, &tags);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].label, "release, v1.0.0");
+    }
+
+    #[test]
+    fn test_trend_point_assesses_tree_and_history() {
+        let sample = Sample { label: "2024-01".to_string(), hash: "a".repeat(40), timestamp: 30 * DAY };
+        let messages = vec![
+            message(MessageData::CommitInfo {
+                hash: "a".repeat(40),
+                author: "alice".to_string(),
+                message: "change".to_string(),
+                timestamp: 29 * DAY,
+                committer: "alice".to_string(),
+                author_timestamp: 29 * DAY,
+                parents: vec![],
+                changed_files: vec![FileChangeData { path: "src/lib.rs".to_string(), lines_added: 4, lines_removed: 0 }],
+            }),
+            message(MessageData::FileContent {
+                path: "src/lib.rs".to_string(),
+                size: 0,
+                content: "fn main() {\n    if true {\n        println!(\"yes\");\n    }\n}\n".to_string(),
+                generated: false,
+            }),
+            message(MessageData::FileContent {
+                path: "src/generated.rs".to_string(),
+                size: 0,
+                content: "// @generated\nfn generated() {}\n".to_string(),
+                generated: true,
+            }),
+        ];
+
+        let point = DebtTrendPoint::assess(sample, &messages, &DebtConfig::default());
+        assert_eq!(point.files, 1);
+        assert!(point.lines_of_code > 0);
+        assert!(point.average_debt > 0.0);
+        assert_eq!(point.average_debt, point.max_debt);
+
+        let trend = DebtTrend::new(SampleInterval::Monthly, vec![point.clone(), point]);
+        let export = &trend.exports()[0];
+        assert_eq!(export.title, "Debt Trend");
+        match &export.data {
+            DataPayload::Rows(rows) => {
+                assert_eq!(rows.len(), 2);
+                assert_eq!(rows[1].values[2], Value::String("1970-01-31".to_string()));
+                assert_eq!(rows[1].values[7], Value::Float(0.0));
+            }
+            _ => panic!("expected rows"),
+        }
+    }
+}
@@ -4,16 +4,31 @@ use anyhow::Result;
 use std::path::PathBuf;
 use log::{info, debug, error};
 use crate::{cli, config, display, plugin, scanner};
-use crate::scanner::branch_detection::BranchDetection;
+use crate::scanner::branch_detection::{BranchDetection, BranchDetectionError, BranchDetectionResult, BranchSelectionSource};
+use crate::scanner::file_policy::FilePolicy;
 use crate::scanner::traits::QueueMessageProducer;
 
+/// Exit status for a run with `--expect-data` that found nothing to report
+pub const EXIT_NO_DATA: i32 = 3;
+
+/// Raised when `--expect-data` is set and the filters left nothing to report
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct NoDataError(pub String);
+
+/// Raised when a scan was stopped before it finished; the partial results have been exported
+#[derive(Debug, thiserror::Error)]
+#[error("Scan stopped early ({0}); results are partial")]
+pub struct InterruptedError(pub String);
+
 
 /// Resolve plugin commands using CommandMapper
+/// Returns the plugin name and the function the command selects (if any)
 pub async fn resolve_single_plugin_command(
     plugin_handler: &cli::plugin_handler::PluginHandler,
     command: &str,
     args: &cli::Args,
-) -> Result<String> {
+) -> Result<(String, Option<String>)> {
     use cli::command_mapper::CommandResolution;
     
     debug!("Resolving command: '{}'", command);
@@ -23,15 +38,15 @@ pub async fn resolve_single_plugin_command(
             match resolution {
                 CommandResolution::Function { plugin_name, function_name, .. } => {
                     debug!("Resolved '{}' to plugin '{}' function '{}'", command, plugin_name, function_name);
-                    Ok(plugin_name)
+                    Ok((plugin_name, Some(function_name)))
                 }
                 CommandResolution::DirectPlugin { plugin_name, default_function } => {
                     debug!("Resolved '{}' to plugin '{}' (default: {:?})", command, plugin_name, default_function);
-                    Ok(plugin_name)
+                    Ok((plugin_name, default_function))
                 }
                 CommandResolution::Explicit { plugin_name, function_name } => {
                     debug!("Resolved '{}' to plugin '{}' function '{}'", command, plugin_name, function_name);
-                    Ok(plugin_name)
+                    Ok((plugin_name, Some(function_name)))
                 }
             }
         }
@@ -42,231 +57,97 @@ pub async fn resolve_single_plugin_command(
     }
 }
 
-pub async fn handle_plugin_commands(args: &cli::Args, config: &config::ConfigManager) -> Result<()> {
-    use cli::plugin_handler::PluginHandler;
+/// Print the result of a management command as JSON (`--json`)
+pub(super) fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
 
-    let plugin_config = cli::converter::merge_plugin_config(&args, Some(config));
-    let mut handler = PluginHandler::with_plugin_config(plugin_config)?;
-    
-    // Handle --plugins command (display plugins with their functions)
-    if args.show_plugins {
-        handler.build_command_mappings().await?;
-        
-        // Create a colour manager for enhanced output
-        let colour_manager = super::initialization::create_colour_manager(args, config);
-        
-        println!("{}", colour_manager.highlight("Available Plugins:"));
-        println!("{}", colour_manager.highlight("=================="));
-        println!();
-        
-        let mappings = handler.get_function_mappings();
-        if mappings.is_empty() {
-            println!("No plugins available.");
-        } else {
-            use std::collections::HashMap;
-            
-            // Group functions by plugin for better organization
-            let mut by_plugin: HashMap<String, Vec<_>> = HashMap::new();
-            for mapping in &mappings {
-                by_plugin.entry(mapping.plugin_name.clone())
-                    .or_insert_with(Vec::new)
-                    .push(mapping);
-            }
-            
-            // Sort plugins alphabetically
-            let mut plugin_names: Vec<_> = by_plugin.keys().cloned().collect();
-            plugin_names.sort();
-            
-            // Prepare data for table formatting
-            let mut plugin_data = Vec::new();
-            for plugin_name in &plugin_names {
-                if let Some(funcs) = by_plugin.get(plugin_name) {
-                    // Sort functions, putting default first
-                    let mut sorted_funcs = funcs.clone();
-                    sorted_funcs.sort_by(|a, b| {
-                        match (a.is_default, b.is_default) {
-                            (true, false) => std::cmp::Ordering::Less,
-                            (false, true) => std::cmp::Ordering::Greater,
-                            _ => a.function_name.cmp(&b.function_name),
-                        }
-                    });
-                    
-                    // Build function list string
-                    let function_strs: Vec<String> = sorted_funcs.iter().map(|f| {
-                        let default_marker = if f.is_default { "*" } else { "" };
-                        let aliases = if !f.aliases.is_empty() {
-                            format!(", {}", f.aliases.join(", "))
-                        } else {
-                            String::new()
-                        };
-                        format!("{}{}{}", f.function_name, default_marker, aliases)
-                    }).collect();
-                    
-                    let functions_str = function_strs.join(", ");
-                    plugin_data.push((plugin_name.clone(), functions_str));
-                }
-            }
-            
-            // Use the display module's table formatting
-            let table_output = crate::display::create_plugin_table(plugin_data, &colour_manager);
-            print!("{}", table_output);
-            
-            println!();
-            println!("{}", colour_manager.highlight("Usage:"));
-            println!("  {}                # Use plugin's default function",
-                colour_manager.command("gstats <plugin>"));
-            println!("  {}              # Use function if unambiguous",
-                colour_manager.command("gstats <function>"));
-            println!("  {}     # Explicit plugin:function syntax",
-                colour_manager.command("gstats <plugin>:<function>"));
-            println!();
-            println!("{}", colour_manager.orange("* = default function for plugin"));
-        }
-        
-        return Ok(());
+pub fn run_scanner(
+    repo_path: PathBuf, 
+    args: cli::Args,
+    config_manager: config::ConfigManager,
+) -> Result<()> {
+    if !args.watch {
+        return scan_repository(repo_path, &args, &config_manager);
     }
     
-    // Handle --plugins-help command (display functions with their providers)
-    if args.plugins_help {
-        handler.build_command_mappings().await?;
-        
-        // Create color manager for styled output
-        let colour_manager = display::ColourManager::from_color_args(args.no_color, args.color, None);
-        
-        println!("{}", colour_manager.highlight("Available Plugin Functions and Commands:"));
-        println!("{}", colour_manager.info("========================================"));
-        
-        let mappings = handler.get_function_mappings();
-        if mappings.is_empty() {
-            println!("No plugin functions available.");
-        } else {
-            use std::collections::HashMap;
-            
-            // Group functions by plugin for better organization
-            let mut by_plugin: HashMap<String, Vec<_>> = HashMap::new();
-            for mapping in &mappings {
-                by_plugin.entry(mapping.plugin_name.clone())
-                    .or_insert_with(Vec::new)
-                    .push(mapping);
-            }
-            
-            // Sort plugins alphabetically
-            let mut plugin_names: Vec<_> = by_plugin.keys().cloned().collect();
-            plugin_names.sort();
-            
-            // Calculate column widths for proper alignment
-            let max_plugin_width = plugin_names.iter().map(|name| name.len()).max().unwrap_or(6).max(6); // "Plugin" length
-            
-            // Print sleek header with underline using colors
-            println!(" {:<width$} {}", 
-                colour_manager.highlight("Plugin"), 
-                colour_manager.highlight("Functions & Description"), 
-                width = max_plugin_width);
-            println!(" {} {}", 
-                colour_manager.info(&"-".repeat(max_plugin_width)), 
-                colour_manager.info("--"));
-            
-            // Print each plugin row
-            for plugin_name in &plugin_names {
-                if let Some(funcs) = by_plugin.get(plugin_name) {
-                    // Sort functions, putting default first
-                    let mut sorted_funcs = funcs.clone();
-                    sorted_funcs.sort_by(|a, b| {
-                        match (a.is_default, b.is_default) {
-                            (true, false) => std::cmp::Ordering::Less,
-                            (false, true) => std::cmp::Ordering::Greater,
-                            _ => a.function_name.cmp(&b.function_name),
-                        }
-                    });
-                    
-                    // Build function list string without brackets around aliases
-                    let function_strs: Vec<String> = sorted_funcs.iter().map(|f| {
-                        let default_marker = if f.is_default { "*" } else { "" };
-                        let aliases = if !f.aliases.is_empty() {
-                            format!(", {}", f.aliases.join(", "))
-                        } else {
-                            String::new()
-                        };
-                        format!("{}{}{}", f.function_name, default_marker, aliases)
-                    }).collect();
-                    
-                    let functions_str = function_strs.join(", ");
-                    
-                    // Get plugin description (use first function's description or plugin name)
-                    let description = sorted_funcs.first()
-                        .and_then(|f| if !f.description.is_empty() { Some(f.description.as_str()) } else { None })
-                        .unwrap_or("Plugin for data processing");
-                    
-                    // Print plugin row with functions on first line, description on second using colors
-                    println!(" {:<width$} {}", 
-                        colour_manager.command(plugin_name), 
-                        colour_manager.success(&functions_str), 
-                        width = max_plugin_width);
-                    println!(" {:<width$} {}", 
-                        "", 
-                        colour_manager.info(description), 
-                        width = max_plugin_width);
-                }
-            }
-            
-            println!();
-            println!("{}", colour_manager.highlight("Usage Examples:"));
-            println!("  {}              # Use function if unambiguous",
-                colour_manager.command("gstats <function>"));
-            println!("  {}                # Use plugin's default function",
-                colour_manager.command("gstats <plugin>"));
-            println!("  {}     # Explicit plugin:function syntax",
-                colour_manager.command("gstats <plugin>:<function>"));
-            println!();
-            println!("{}", colour_manager.orange("* = default function for plugin"));
-        }
-        
-        // Show any ambiguities as warnings
-        let ambiguities = handler.get_ambiguity_reports();
-        if !ambiguities.is_empty() {
-            println!();
-            println!("Ambiguous Functions (require plugin:function syntax):");
-            for ambiguity in ambiguities {
-                println!("  ⚠️  {}", ambiguity);
-            }
-        }
-        
-        return Ok(());
-    }
+    // Watch the repository that is scanned, including an explicit git directory
+    let watch_path = cli::converter::args_to_scanner_config(&args, Some(&config_manager))?
+        .git_dir
+        .unwrap_or_else(|| repo_path.clone());
+    let interval = args.watch_interval.map(std::time::Duration::from_secs).unwrap_or(super::watch::DEFAULT_INTERVAL);
+    // Commits landing during a scan are picked up by the next check
+    let mut watcher = super::watch::RefWatcher::new(&watch_path, interval)?;
+    let progress = display::ProgressIndicator::new(super::initialization::create_colour_manager(&args, &config_manager));
     
-    if args.list_plugins {
-        let plugins = handler.list_plugins().await?;
-        for plugin in plugins {
-            println!("{}: {} ({})", plugin.name, plugin.description, plugin.version);
+    loop {
+        // A failed scan is reported and retried after the next change rather than ending the watch
+        if let Err(e) = scan_repository(repo_path.clone(), &args, &config_manager) {
+            progress.status(display::StatusType::Warning, &format!("Scan failed: {}", e));
         }
-        return Ok(());
+        progress.status(display::StatusType::Info, &format!("Watching {} for new commits (Ctrl-C to stop)", watch_path.display()));
+        watcher.wait_for_change();
+        progress.status(display::StatusType::Info, "New commits detected, rescanning...");
     }
-    
-    if args.list_formats {
-        // TODO: Move this functionality to export plugin (see GS-90)
-        println!("Format listing moved to export plugin - functionality temporarily unavailable");
-        return Ok(());
-    }
-    
-    Ok(())
 }
 
-pub fn run_scanner(
-    repo_path: PathBuf, 
-    args: cli::Args,
-    config_manager: config::ConfigManager,
+/// Scan the repository once and run the requested plugin on the results
+fn scan_repository(
+    repo_path: PathBuf,
+    args: &cli::Args,
+    config_manager: &config::ConfigManager,
 ) -> Result<()> {
     use std::sync::Arc;
     
     // Convert CLI args to scanner config and query params - these are sync
-    let scanner_config = cli::converter::args_to_scanner_config(&args, Some(&config_manager))?;
-    let query_params = cli::converter::args_to_query_params(&args, Some(&config_manager))?;
+    let scanner_config = cli::converter::args_to_scanner_config(args, Some(config_manager))?;
+    let mut query_params = cli::converter::args_to_query_params(args, Some(config_manager))?;
     
     debug!("Scanner configuration: {:?}", scanner_config);
     debug!("Query parameters: {:?}", query_params);
     
+    // Trends are recorded under the repository as given, whichever directory is then opened
+    let trend_repository = repo_path.clone();
+    
+    // A patch series (--patch) or an explicit git directory (--git-dir or [scanner] git-dir) is scanned as is
+    let patch_series = scanner_config.patch_series.is_some();
+    let repo_path = scanner_config.patch_series.clone()
+        .or_else(|| scanner_config.git_dir.clone())
+        .unwrap_or(repo_path);
+    // --staged and --working-tree scan the uncommitted changes instead of the history
+    let pending_changes = scanner_config.pending_changes;
+    
+    // The start point is resolved up front so plugins can report the ref that was scanned
+    if !patch_series && pending_changes.is_none() {
+        if args.unshallow {
+            let repo = scanner::repository::open_repository(&repo_path)?;
+            if repo.is_shallow() {
+                info!("Fetching the full history of the shallow clone");
+                scanner::shallow::unshallow(&repo).map_err(|e| anyhow::anyhow!(e))?;
+            }
+        }
+        let scanned_ref = resolve_scan_start(&repo_path, args, query_params.branch.as_deref())
+            .map_err(|e| anyhow::anyhow!("Failed to determine the branch to scan: {}", e))?;
+        debug!("Scanning {} ({}) at {}", scanned_ref.branch_name, scanned_ref.selection_source.debug(), scanned_ref.commit_id);
+        query_params.shallow = scanner::shallow::ShallowHistory::detect(&scanner::repository::open_repository(&repo_path)?, &scanned_ref.commit_id)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        query_params.scanned_ref = Some(scanned_ref);
+    }
+    
+    // --reproducible replaces the clock with the date of the latest scanned commit
+    // and derives the scan ID from the scan's input
+    let reproducible_scan_id = if args.reproducible {
+        let (latest, input) = reproducible_input(&repo_path, patch_series, &query_params)?;
+        debug!("Reproducible run: clock fixed at {:?}", latest);
+        crate::clock::fix(latest);
+        Some(scanner::AsyncScannerManager::reproducible_scan_id(&input))
+    } else {
+        None
+    };
+    
     // Create plugin configuration
-    let plugin_config = cli::converter::merge_plugin_config(&args, Some(&config_manager));
+    let plugin_config = cli::converter::merge_plugin_config(args, Some(config_manager));
     
     // Create a plugin registry and initialise plugins
     let plugin_registry = plugin::SharedPluginRegistry::new();
@@ -274,7 +155,10 @@ pub fn run_scanner(
     debug!("Initializing scanner system");
     
     // Create colour manager early for plugin initialization
-    let colour_manager = super::initialization::create_colour_manager(&args, &config_manager);
+    let mut colour_manager = super::initialization::create_colour_manager(args, config_manager);
+    if let Some(links) = super::initialization::resolve_hyperlinks(args, config_manager, &trend_repository)? {
+        colour_manager = colour_manager.with_hyperlinks(links);
+    }
     
     // CREATE UNIFIED NOTIFICATION MANAGER
     let unified_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::<crate::notifications::events::UnifiedEvent>::new());
@@ -284,24 +168,68 @@ pub fn run_scanner(
     let scan_publisher = Arc::new(ScanEventPublisher::new(unified_notification_manager.clone()));
     let queue_publisher = Arc::new(QueueEventPublisher::new(unified_notification_manager.clone()));
     let plugin_publisher = Arc::new(PluginEventPublisher::new(unified_notification_manager.clone()));
+    // Plugins publish their results (DataReady) to the export plugin over this manager
+    let plugin_notifications = Arc::new(crate::notifications::AsyncNotificationManager::<crate::notifications::events::PluginEvent>::new());
+    
+    // --fail-on conditions are checked against the results once the scan is done
+    let fail_conditions = args.fail_on.iter()
+        .map(|expression| super::gate::FailCondition::parse(expression))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let results = Arc::new(super::gate::ResultCollector::new());
+    if !fail_conditions.is_empty() || args.record_trends || args.expect_data {
+        use crate::notifications::traits::NotificationManager;
+        crate::runtime::block_on(plugin_notifications.subscribe(results.clone()))?;
+    }
+    
+    // Ctrl-C stops the scan and keeps partial results; a second press quits at once
+    let shutdown = crate::shutdown::ShutdownController::new();
+    crate::shutdown::handle_ctrl_c(shutdown.clone());
+    
+    // --tui shows progress, queue statistics and plugin tables in a dashboard
+    #[cfg(feature = "tui")]
+    let dashboard = if args.tui {
+        use crate::notifications::traits::NotificationManager;
+        let dashboard = Arc::new(display::tui::Dashboard::new(repo_path.display().to_string(), shutdown.clone()));
+        crate::runtime::block_on(plugin_notifications.subscribe(dashboard.clone()))?;
+        Some(dashboard)
+    } else {
+        None
+    };
     
     // Initialize plugins using the proper UnifiedPluginDiscovery system
     // Get excluded plugins from configuration (if any)  
     let excluded_plugins = plugin_config.plugin_exclude.clone();
     
+    // Consumer plugin calls are cancelled past the deadline and reported as plugin errors
+    crate::runtime::block_on(async {
+        let mut registry = plugin_registry.inner().write().await;
+        registry.set_execution_timeout(plugin_config.timeout);
+        registry.set_plugin_publisher(plugin_publisher.clone());
+        registry.set_shutdown(shutdown.clone());
+    });
+    
     // Plugin initialization is now sync - pass the plugin publisher
-    super::initialization::initialize_plugins_via_discovery(&plugin_registry, &colour_manager, excluded_plugins, plugin_publisher)?;
+    let setup_stage = crate::profiling::stage("plugins.initialize");
+    super::initialization::initialize_plugins_via_discovery(
+        &plugin_registry,
+        &colour_manager,
+        plugin_config.directories.first().map(std::path::PathBuf::from),
+        excluded_plugins,
+        super::initialization::PluginSetup {
+            plugin_publisher,
+            plugin_notifications,
+            scanner_config: Arc::new(scanner_config.clone()),
+            query_params: Arc::new(query_params.clone()),
+        },
+    )?;
+    
+    drop(setup_stage);
     
     // Create a plugin handler with enhanced configuration
     let mut plugin_handler = cli::plugin_handler::PluginHandler::with_plugin_config(plugin_config)?;
     
-    // Create minimal runtime for plugin handler async operations only
-    let init_rt = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .map_err(|e| anyhow::anyhow!("Failed to create initialization runtime: {}", e))?;
-    
-    init_rt.block_on(plugin_handler.build_command_mappings())?;
+    crate::runtime::block_on(plugin_handler.build_command_mappings())?;
     
     // Resolve plugin command using CommandMapper
     let command = if let Some(cmd) = args.command.as_ref() {
@@ -405,15 +333,91 @@ pub fn run_scanner(
         
         return Err(anyhow::anyhow!("No command specified. Please specify a plugin or function to execute."));
     };
-    let resolved_plugin = init_rt.block_on(resolve_single_plugin_command(&plugin_handler, &command, &args))?;
-    let plugin_names = vec![resolved_plugin.clone()];
+    let (resolved_plugin, resolved_function) = crate::runtime::block_on(resolve_single_plugin_command(&plugin_handler, &command, args))?;
+    // Plugins the command depends on are activated and consume scan data first
+    let plugin_names = crate::runtime::block_on(async {
+        plugin_registry.inner().write().await.activate_with_dependencies(&resolved_plugin).await
+    }).map_err(|e| anyhow::anyhow!("Cannot run '{}': {}", resolved_plugin, e))?;
+    let line_attribution = resolved_function.as_deref().is_some_and(|function| {
+        plugin::builtin::builtin_function_requires_line_attribution(&resolved_plugin, function)
+    });
+    // File content is only streamed when an active plugin asks for it, and is read once however many do
+    let content_plugins = crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        plugin_names.iter().filter(|name| {
+            registry.get_plugin(name)
+                .and_then(|plugin| plugin.as_data_requirements())
+                .is_some_and(|requirements| requirements.requires_current_file_content())
+        }).count()
+    });
+    let current_file_content = content_plugins > 0;
+    // Content is fingerprinted once for all active plugins detecting duplicates
+    let content_fingerprints = crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        plugin_names.iter().any(|name| {
+            registry.get_plugin(name)
+                .and_then(|plugin| plugin.as_data_requirements())
+                .is_some_and(|requirements| requirements.requires_content_fingerprints())
+        })
+    });
+    // Binary file changes are only reported when an active plugin handles them
+    let binary_files = crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        plugin_names.iter().any(|name| {
+            registry.get_plugin(name)
+                .and_then(|plugin| plugin.as_data_requirements())
+                .is_some_and(|requirements| requirements.handles_binary_files())
+        })
+    });
+    // Plugins reading a few repository files by path have just those files streamed
+    let required_files: Vec<String> = crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        let mut required_files: Vec<String> = plugin_names.iter()
+            .filter_map(|name| registry.get_plugin(name).and_then(|plugin| plugin.as_data_requirements()))
+            .flat_map(|requirements| requirements.required_files())
+            .collect();
+        required_files.sort();
+        required_files.dedup();
+        required_files
+    });
+    // Each plugin is given only the files its data requirements say it handles, whichever plugins run with it
+    let file_policies: Vec<(String, FilePolicy)> = crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        plugin_names.iter().filter_map(|name| {
+            let requirements = registry.get_plugin(name)?.as_data_requirements()?;
+            let policy = FilePolicy::from_requirements(requirements, scanner_config.oversized_files);
+            (!policy.is_unrestricted()).then(|| (name.clone(), policy))
+        }).collect()
+    });
+    
+    // A patch series carries only the diffs, not the history or trees they apply to
+    if patch_series && (line_attribution || current_file_content || content_fingerprints) {
+        log::warn!("'{}' needs repository content that a patch series does not include; results may be incomplete", command);
+    }
+    if pending_changes.is_some() && line_attribution {
+        log::warn!("'{}' attributes removed lines to their authors, which is not done for pending changes; results may be incomplete", command);
+    }
+    
+    // Named presets and argument files are replaced by the arguments they hold
+    let arg_presets = config_manager.get_plugin_arg_presets();
+    let plugin_args = arg_presets
+        .expand(&resolved_plugin, &args.plugin_args)
+        .map_err(|e| anyhow::anyhow!("Failed to parse plugin arguments for {}: {}", resolved_plugin, e))?;
     
     debug!("Active plugins: {:?}", plugin_names);
-    debug!("Plugin arguments: {:?}", args.plugin_args);
+    debug!("Plugin arguments: {:?}", plugin_args);
     
     // CREATE THE QUEUE using typed publishers
-    let queue = crate::queue::SharedMessageQueue::new(queue_publisher.clone(), scan_publisher.clone());
-    init_rt.block_on(async {
+    let queue_config = crate::queue::multi_consumer::MultiConsumerConfig {
+        shutdown: shutdown.clone(),
+        ..crate::queue::multi_consumer::MultiConsumerConfig::from_scanner_config(&scanner_config)
+    };
+    let queue = crate::queue::SharedMessageQueue::with_config(
+        queue_config,
+        queue_publisher.clone(),
+        scan_publisher.clone(),
+    );
+    crate::runtime::block_on(async {
         queue.start().await?;
         
         // Subscribe queue to ScanEvents for statistical tracking
@@ -423,30 +427,56 @@ pub fn run_scanner(
     debug!("Queue created and started");
     
     // 2. ADD CONSUMERS (register all active plugins BEFORE scanning starts)
+    let mut consumers = Vec::new();
     for plugin_name in &plugin_names {
-        let consumer = init_rt.block_on(queue.register_consumer(plugin_name.clone()))?;
+        let consumer = crate::runtime::block_on(queue.register_consumer(plugin_name.clone()))?;
+        consumers.push((plugin_name.clone(), consumer.clone()));
         
             // Get the plugin and configure it with arguments
-        init_rt.block_on(async {
+        crate::runtime::block_on(async {
             let mut plugin_registry_guard = plugin_registry.inner().write().await;
             if let Some(plugin) = plugin_registry_guard.get_plugin_mut(plugin_name) {
-                // Parse plugin arguments before starting consumption
-                plugin.parse_plugin_arguments(&args.plugin_args).await
+                // Parse plugin arguments before starting consumption; command-line
+                // arguments belong to the requested plugin, dependencies use defaults,
+                // and each starts from the options configured in its [plugin.<name>] section
+                let plugin_args: &[String] = if *plugin_name == resolved_plugin { &plugin_args } else { &[] };
+                let plugin_args = arg_presets.apply_defaults(plugin_name, plugin_args);
+                plugin.parse_plugin_arguments(&plugin_args).await
                     .map_err(|e| anyhow::anyhow!("Failed to parse plugin arguments for {}: {}", plugin_name, e))?;
                 debug!("Plugin {} arguments parsed successfully", plugin_name);
                 
-                if let Some(consumer_plugin) = plugin.as_consumer_plugin_mut() {
-                    consumer_plugin.start_consuming(consumer).await
-                        .map_err(|e| anyhow::anyhow!("Failed to start consuming for plugin {}: {}", plugin_name, e))?;
-                    debug!("Plugin {} registered as consumer and started consuming", plugin_name);
+                if let Some((_, policy)) = file_policies.iter().find(|(name, _)| name == plugin_name) {
+                    consumer.set_file_policy(*policy).await;
                 }
+                plugin_registry_guard.start_consuming(plugin_name, consumer).await
+                    .map_err(|e| anyhow::anyhow!("Failed to start consuming for plugin {}: {}", plugin_name, e))?;
+                debug!("Plugin {} registered as consumer and started consuming", plugin_name);
             }
             Result::<_, anyhow::Error>::Ok(())
         })?
     }
     
     debug!("All active plugins registered as consumers");
-    
+
+    // Output plugins run alongside every command, taking their options from their [plugin.<name>] section
+    let output_plugins = crate::runtime::block_on(async {
+        let mut registry = plugin_registry.inner().write().await;
+        let output_plugins: Vec<String> = registry.get_plugins_by_type(plugin::traits::PluginType::Output).into_iter()
+            .filter(|name| registry.is_plugin_active(name) && !plugin_names.contains(name))
+            .collect();
+        for name in &output_plugins {
+            let defaults = arg_presets.apply_defaults(name, &[]);
+            if defaults.is_empty() {
+                continue;
+            }
+            if let Some(plugin) = registry.get_plugin_mut(name) {
+                plugin.parse_plugin_arguments(&defaults).await
+                    .map_err(|e| anyhow::anyhow!("Failed to parse the [plugin.{}] options: {}", name, e))?;
+            }
+        }
+        Result::<_, anyhow::Error>::Ok(output_plugins)
+    })?;
+
     // 3. CREATE SCANNER WITH QUEUE-BASED MESSAGE PRODUCER
     let message_producer = Arc::new(QueueMessageProducer::new(
         queue.clone(),
@@ -454,20 +484,55 @@ pub fn run_scanner(
     ));
     
     // Create a scanner manager with the repository path, queue producer and shared notification manager
-    // Let the manager create its own runtime to avoid nested runtime issues
     let mut engine_builder = scanner::AsyncScannerManagerBuilder::new()
         .repository_path(repo_path.clone())
         .config(scanner_config.clone())
         .message_producer(message_producer as Arc<dyn scanner::MessageProducer + Send + Sync>)
         .notification_manager(Arc::new(crate::notifications::AsyncNotificationManager::<crate::notifications::events::ScanEvent>::new()))
-        .plugin_registry(plugin_registry.clone());
+        .plugin_registry(plugin_registry.clone())
+        .shutdown(shutdown.clone());
+    if let Some(shallow) = &query_params.shallow {
+        engine_builder = engine_builder.warning(shallow.warning());
+    }
+    if let Some(scan_id) = reproducible_scan_id {
+        engine_builder = engine_builder.scan_id(scan_id);
+    }
     
     // Create an event-driven scanner - no plugin wrapping needed, uses queue directly
-    let query_params = scanner::QueryParams::default();
-    let event_scanner = Arc::new(scanner::async_engine::scanners::EventDrivenScanner::new(query_params));
+    // The scanner applies the CLI query (date range, branch) during commit traversal
+    // Unless disabled, previously scanned history is replayed from the scan cache
+    let mut event_scanner = scanner::async_engine::scanners::EventDrivenScanner::new(query_params.clone())
+        .with_line_attribution(line_attribution)
+        .with_follow_renames(scanner_config.follow_renames)
+        .with_imports(config_manager.get_import_rules())
+        .with_identities(config_manager.get_identity_rules())
+        .with_binary_files(binary_files)
+        .with_current_file_content(current_file_content)
+        .with_content_fingerprints(content_fingerprints.then_some(scanner_config.fingerprints))
+        .with_content_encoding(scanner_config.encoding)
+        .with_file_policies(file_policies)
+        .with_required_files(required_files)
+        .with_patch_series(patch_series)
+        .with_pending_changes(pending_changes)
+        .with_shutdown(shutdown.clone());
+    if !args.no_cache && !patch_series && pending_changes.is_none() {
+        if let Some(directory) = crate::cache::ScanCache::default_directory() {
+            event_scanner = event_scanner.with_cache(crate::cache::ScanCache::new(directory));
+        }
+    }
+    // Long scans show a progress bar unless output is quiet, not going to a terminal or on the dashboard
+    let progress_bar = display::ProgressBar::should_display(args.quiet || args.tui).then(|| Arc::new(display::ProgressBar::new()));
+    if let Some(progress_bar) = &progress_bar {
+        event_scanner = event_scanner.with_progress(progress_bar.clone());
+    }
+    #[cfg(feature = "tui")]
+    if let Some(dashboard) = &dashboard {
+        event_scanner = event_scanner.with_progress(dashboard.clone());
+    }
+    let event_scanner = Arc::new(event_scanner);
     
     // Add scanner directly to manager
-    engine_builder = engine_builder.add_scanner(event_scanner);
+    engine_builder = engine_builder.add_scanner(event_scanner.clone());
     
     // Build and run scanner engine
     let engine = engine_builder.build()?;
@@ -478,14 +543,43 @@ pub fn run_scanner(
     let progress = display::ProgressIndicator::new(colour_manager.clone());
     
     // Show repository information  
-    progress.status(display::StatusType::Info, &format!("Using git repository: {}", repo_path.display()));
+    if patch_series {
+        progress.status(display::StatusType::Info, &format!("Using patch series: {}", repo_path.display()));
+        progress.status(display::StatusType::Info, "Starting patch series scan...");
+    } else if let Some(pending) = pending_changes {
+        progress.status(display::StatusType::Info, &format!("Using git repository: {}", repo_path.display()));
+        progress.status(display::StatusType::Info, &format!("Scanning {}...", pending.subject().to_lowercase()));
+    } else {
+        progress.status(display::StatusType::Info, &format!("Using git repository: {}", repo_path.display()));
+        if let Some(scanned_ref) = &query_params.scanned_ref {
+            progress.status(display::StatusType::Info, &format!("Scanning branch: {} ({}, {}){}",
+                scanned_ref.branch_name, scanned_ref.selection_source.debug(), &scanned_ref.commit_id[..scanned_ref.commit_id.len().min(12)],
+                if query_params.all_branches { " and all other branches" } else { "" }));
+        }
+        if let Some(shallow) = &query_params.shallow {
+            progress.status(display::StatusType::Warning, &shallow.warning());
+        }
+        progress.status(display::StatusType::Info, "Starting repository scan...");
+    }
     
-    progress.status(display::StatusType::Info, "Starting repository scan...");
+    // The dashboard takes over the terminal for the scan, polling the queue for its statistics
+    #[cfg(feature = "tui")]
+    let dashboard_view = dashboard.as_ref().map(|dashboard| {
+        let (polled, queue) = (dashboard.clone(), queue.clone());
+        crate::runtime::shared().spawn(async move {
+            while !polled.is_finished() {
+                polled.update_queue(queue.get_statistics().await);
+                tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+            }
+        });
+        dashboard.open()
+    });
     
-    // Execute scan in scanner's own runtime - no mode filtering needed
-    // Scanner creates its own runtime internally to avoid nested runtime conflicts
-    init_rt.block_on(async {
-        match engine.scan().await {
+    // Execute scan on the shared runtime - no mode filtering needed
+    // Plugins consume the queue as the scanner fills it, and have read all of it once the scan returns
+    let consumer_driver = ConsumerDriver::spawn(&plugin_registry, &queue, consumers);
+    let scan_result = crate::runtime::block_on(async {
+        let scan_result = match engine.scan().await {
             Ok(()) => {
                 info!("Scanner execution completed successfully");
                 Ok(())
@@ -494,12 +588,620 @@ pub fn run_scanner(
                 error!("Scanner execution failed: {}", e);
                 Err(anyhow::anyhow!("Scanner execution failed: {}", e))
             }
+        };
+        consumer_driver.finish().await;
+        scan_result
+    });
+    if let Some(progress_bar) = &progress_bar {
+        progress_bar.finish();
+    }
+    
+    // Results stay on the dashboard until the user closes it
+    #[cfg(feature = "tui")]
+    if let (Some(dashboard), Some(view)) = (&dashboard, dashboard_view) {
+        dashboard.finish(match (&scan_result, shutdown.is_requested()) {
+            (Err(e), _) => display::tui::ScanOutcome::Failed(e.to_string()),
+            (Ok(()), true) => display::tui::ScanOutcome::Interrupted,
+            (Ok(()), false) => display::tui::ScanOutcome::Completed,
+        });
+        view.join()
+            .map_err(|_| anyhow::anyhow!("The dashboard stopped unexpectedly"))?
+            .map_err(|e| anyhow::anyhow!("Dashboard error: {}", e))?;
+    }
+    scan_result?;
+    
+    if let Some(reason) = shutdown.reason() {
+        progress.status(display::StatusType::Warning, "Scan interrupted; results cover only the history read before the interruption");
+        return Err(InterruptedError(reason).into());
+    }
+    
+    let content = event_scanner.content_cache_stats();
+    if args.verbose && content.requests() > 0 {
+        progress.status(display::StatusType::Info, &format!(
+            "File content: {} blobs read, {} cache hits ({:.1}% hit rate), shared by {} plugin(s)",
+            content.blobs_read, content.hits, content.hit_rate() * 100.0, content_plugins.max(1)));
+    }
+    
+    // Plugins are held to the resource limits they declared; breaches were logged as they occurred
+    let breaches = crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        plugin_names.iter()
+            .map(|name| (name.clone(), registry.resource_breaches(name)))
+            .filter(|(_, breaches)| breaches.total() > 0)
+            .collect::<Vec<_>>()
+    });
+    for (name, breaches) in breaches {
+        progress.status(display::StatusType::Warning, &format!(
+            "Plugin '{}' exceeded its declared resource limits: {} slow messages, {} times over its memory limit",
+            name, breaches.message_time, breaches.memory));
+    }
+    
+    if args.memory_report {
+        let usage = crate::runtime::block_on(async { plugin_registry.inner().read().await.memory_usage().await });
+        progress.status(display::StatusType::Info, "Plugin memory:");
+        print!("{}", plugin::memory::memory_table(&usage, &colour_manager));
+    }
+    
+    if args.queue_stats {
+        let (consumers, totals) = crate::runtime::block_on(async {
+            (plugin_registry.inner().read().await.queue_statistics().await, queue.get_statistics().await)
+        });
+        progress.status(display::StatusType::Info, &format!("Queue consumers: {}", crate::queue::statistics::queue_summary(&totals)));
+        print!("{}", crate::queue::statistics::consumer_table(&consumers, &colour_manager));
+    }
+    
+    if let Some(profile) = crate::profiling::profile() {
+        progress.status(display::StatusType::Info, &format!("Stage timings over {:.2}s:", profile.elapsed().as_secs_f64()));
+        print!("{}", crate::profiling::profile_table(&profile.stages(), profile.elapsed(), &colour_manager));
+        if let Some(path) = &args.profile_trace {
+            let events = profile.write_trace(path)
+                .map_err(|e| anyhow::anyhow!("Failed to write profile trace to {}: {}", path.display(), e))?;
+            progress.status(display::StatusType::Info, &format!("Wrote {} trace events to {}", events, path.display()));
+        }
+    }
+    
+    if args.expect_data {
+        let counts = event_scanner.match_counts();
+        debug!("Scan matched {} commits and {} file changes", counts.commits, counts.file_changes);
+        if counts.is_empty(&query_params) {
+            return Err(explain_empty_scan(&repo_path, &query_params, &config_manager.get_identity_rules()).into());
         }
+        // Commits can match and still leave a plugin with nothing to report
+        let selected: Vec<_> = crate::runtime::block_on(async {
+            let registry = plugin_registry.inner().read().await;
+            plugin_names.iter()
+                .filter_map(|name| Some((name.clone(), registry.get_plugin(name)?.plugin_info().plugin_type.clone())))
+                .collect()
+        });
+        let empty = super::gate::plugins_without_rows(&selected, &results.exports());
+        if !empty.is_empty() {
+            return Err(NoDataError(format!("No data to report: {} produced no rows", empty.join(", "))).into());
+        }
+    }
+    
+    // Output plugins deliver what was exported once the scan is done, such as notify's summary
+    crate::runtime::block_on(async {
+        let registry = plugin_registry.inner().read().await;
+        for name in output_plugins.iter().chain(&plugin_names) {
+            let Some(plugin) = registry.get_plugin(name).filter(|plugin| plugin.plugin_info().plugin_type == plugin::traits::PluginType::Output) else {
+                continue;
+            };
+            plugin.execute(plugin::PluginRequest::Export).await
+                .map_err(|e| anyhow::anyhow!("{} failed: {}", name, e))?;
+        }
+        Result::<_, anyhow::Error>::Ok(())
     })?;
+
+    // Metrics are recorded whether or not a --fail-on condition then holds
+    if args.record_trends {
+        let path = super::trends::database_path(args, config_manager)?;
+        let run = super::trends::Run {
+            repository: super::trends::repository_key(&trend_repository),
+            recorded_at: crate::clock::now_seconds(),
+            branch: query_params.scanned_ref.as_ref().map(|scanned_ref| scanned_ref.branch_name.clone()),
+            commit: query_params.scanned_ref.as_ref().map(|scanned_ref| scanned_ref.commit_id.clone()),
+            command: args.command.clone(),
+        };
+        let recorded = super::trends::record(&path, &run, &results.exports())?;
+        debug!("Recorded {} metrics to {}", recorded, path.display());
+    }
+    
+    if !fail_conditions.is_empty() {
+        let exports = results.exports();
+        debug!("Checking {} --fail-on conditions against {} results", fail_conditions.len(), exports.len());
+        super::gate::check(&fail_conditions, &exports)?;
+    }
     
     Ok(())
 }
 
+/// Interval at which an idle consumer looks for new messages
+const CONSUMER_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Shards a batch is split into for plugins that do not require ordered delivery
+const CONSUMER_SHARDS: usize = 4;
+
+/// Tasks that feed each consumer plugin its messages while a scan runs
+/// 
+/// One task runs per registered consumer. It reads batches of the plugin's
+/// preferred size and hands them to the registry, which holds the plugin to its
+/// deadline, then acknowledges them so the queue can release them. Queue events
+/// are passed on as they are published. A plugin is told a scan has started
+/// before its first message and that it is complete once the consumer has read
+/// everything, so it publishes its results having seen every message.
+/// 
+/// Batches for plugins that do not require ordered delivery are split into
+/// shards, each handed to the plugin by its own task, so a plugin keeping
+/// sharded statistics updates them in parallel.
+pub struct ConsumerDriver {
+    scan_finished: tokio_util::sync::CancellationToken,
+    tasks: Vec<(String, tokio::task::JoinHandle<()>)>,
+}
+
+impl ConsumerDriver {
+    /// Start a task on the shared runtime for each plugin's consumer
+    pub fn spawn(
+        registry: &plugin::SharedPluginRegistry,
+        queue: &crate::queue::SharedMessageQueue,
+        consumers: Vec<(String, crate::queue::QueueConsumer)>,
+    ) -> Self {
+        let scan_finished = tokio_util::sync::CancellationToken::new();
+        let tasks = consumers.into_iter()
+            .map(|(name, consumer)| {
+                let task = drive_consumer(
+                    registry.inner().clone(),
+                    name.clone(),
+                    consumer,
+                    queue.subscribe_consumer_events(),
+                    scan_finished.clone(),
+                );
+                (name, crate::runtime::shared().spawn(task))
+            })
+            .collect();
+        Self { scan_finished, tasks }
+    }
+    
+    /// Tell the consumers the scan is over and wait for each to drain the queue
+    pub async fn finish(self) {
+        self.scan_finished.cancel();
+        for (name, task) in self.tasks {
+            if let Err(e) = task.await {
+                error!("Consumer of plugin '{}' stopped unexpectedly: {}", name, e);
+            }
+        }
+    }
+}
+
+/// Feed one plugin its messages until the scan has finished and the queue is drained
+/// 
+/// A plugin whose unacknowledged messages exceed its declared memory limit has
+/// its batches halved each time it does so. Plugins that do not consume
+/// messages, and plugins cancelled for overrunning their deadline or panicking,
+/// have their messages acknowledged unread, so they do not hold the queue back.
+/// A cancelled plugin is not told the scan is complete.
+async fn drive_consumer(
+    registry: std::sync::Arc<tokio::sync::RwLock<plugin::PluginRegistry>>,
+    name: String,
+    consumer: crate::queue::QueueConsumer,
+    mut events: tokio::sync::broadcast::Receiver<crate::queue::QueueEvent>,
+    scan_finished: tokio_util::sync::CancellationToken,
+) {
+    use crate::queue::QueueEvent;
+    
+    let preferences = registry.read().await.consumer_preferences(&name);
+    let mut batch_size = preferences.as_ref().map_or(1, |preferences| preferences.preferred_batch_size.max(1));
+    let mut memory_breaches = 0;
+    let cancellation = registry.read().await.cancellation_token(&name).unwrap_or_default();
+    let deliver = |event: QueueEvent| {
+        let (registry, name, cancellation) = (registry.clone(), name.as_str(), cancellation.clone());
+        async move {
+            if cancellation.is_cancelled() {
+                return;
+            }
+            if let Err(e) = registry.read().await.handle_queue_event(name, &event).await {
+                log::warn!("Plugin '{}' failed to handle {:?}: {}", name, event, e);
+            }
+        }
+    };
+    let mut scan_ids: Vec<String> = Vec::new();
+    
+    loop {
+        // Checked before reading, so messages enqueued before the scan finished are not missed
+        let finished = scan_finished.is_cancelled();
+        
+        // The driver announces the start and completion of scans itself
+        loop {
+            match events.try_recv() {
+                Ok(QueueEvent::ScanStarted { .. } | QueueEvent::ScanComplete { .. }) => {}
+                Ok(event) if preferences.is_some() => deliver(event).await,
+                Ok(_) => {}
+                Err(tokio::sync::broadcast::error::TryRecvError::Lagged(missed)) => {
+                    log::debug!("Plugin '{}' missed {} queue events", name, missed);
+                }
+                Err(_) => break,
+            }
+        }
+        
+        let batch = match consumer.read_batch(batch_size).await {
+            Ok(batch) => batch,
+            Err(e) => {
+                log::warn!("Failed to read messages for plugin '{}': {}", name, e);
+                Vec::new()
+            }
+        };
+        if batch.is_empty() {
+            if finished {
+                break;
+            }
+            tokio::select! {
+                _ = scan_finished.cancelled() => {}
+                _ = tokio::time::sleep(CONSUMER_POLL_INTERVAL) => {}
+            }
+            continue;
+        }
+        
+        let sequences: Vec<u64> = batch.iter().map(|message| message.header().sequence()).collect();
+        if preferences.is_some() && !cancellation.is_cancelled() {
+            for message in &batch {
+                let scan_id = &message.header().scan_id;
+                if !scan_ids.contains(scan_id) {
+                    scan_ids.push(scan_id.clone());
+                    deliver(QueueEvent::scan_started(scan_id.clone())).await;
+                }
+            }
+            let result = if preferences.as_ref().is_some_and(|preferences| !preferences.requires_ordered_delivery) {
+                process_shards(&registry, &name, &consumer, batch).await
+            } else {
+                registry.read().await.process_batch(&name, &consumer, batch).await
+            };
+            match result {
+                Ok(()) => {}
+                // The registry has reported the overrun; the rest of the scan goes unprocessed
+                Err(_) if cancellation.is_cancelled() => {
+                    log::warn!("Plugin '{}' was cancelled; its remaining messages are skipped", name);
+                }
+                Err(e) => log::warn!("Plugin '{}' failed to process {} messages: {}", name, sequences.len(), e),
+            }
+            
+            // A plugin holding more queue memory than it declared is given smaller batches
+            let breaches = registry.read().await.resource_breaches(&name).memory;
+            if breaches > memory_breaches && batch_size > 1 {
+                batch_size /= 2;
+                log::debug!("Plugin '{}' is over its memory limit; reading batches of {}", name, batch_size);
+            }
+            memory_breaches = breaches;
+        }
+        if let Err(e) = consumer.acknowledge_batch(&sequences).await {
+            log::warn!("Failed to acknowledge messages for plugin '{}': {}", name, e);
+        }
+    }
+    
+    let total_messages = consumer.statistics().await.read;
+    for scan_id in scan_ids {
+        deliver(QueueEvent::scan_complete(scan_id, total_messages)).await;
+    }
+}
+
+/// Hand a plugin a batch split into shards, processing the shards concurrently
+/// 
+/// Messages about the same commit, or otherwise the same file, share a shard and
+/// keep their order within it. The first failure is returned once every shard
+/// has been processed.
+async fn process_shards(
+    registry: &std::sync::Arc<tokio::sync::RwLock<plugin::PluginRegistry>>,
+    name: &str,
+    consumer: &crate::queue::QueueConsumer,
+    batch: Vec<std::sync::Arc<scanner::messages::ScanMessage>>,
+) -> plugin::PluginResult<()> {
+    let tasks: Vec<_> = shard_batch(batch, CONSUMER_SHARDS).into_iter()
+        .map(|shard| {
+            let (registry, name, consumer) = (registry.clone(), name.to_string(), consumer.clone());
+            tokio::spawn(async move { registry.read().await.process_batch(&name, &consumer, shard).await })
+        })
+        .collect();
+    let mut result = Ok(());
+    for task in tasks {
+        let shard_result = task.await
+            .unwrap_or_else(|e| Err(plugin::PluginError::execution_failed(format!("Shard task failed: {}", e))));
+        if result.is_ok() {
+            result = shard_result;
+        }
+    }
+    result
+}
+
+/// Split a batch into at most `shards` non-empty shards by commit, or by file for messages about no commit
+fn shard_batch(
+    batch: Vec<std::sync::Arc<scanner::messages::ScanMessage>>,
+    shards: usize,
+) -> Vec<Vec<std::sync::Arc<scanner::messages::ScanMessage>>> {
+    use scanner::messages::MessageData;
+    use std::hash::{Hash, Hasher};
+    
+    let mut sharded = vec![Vec::new(); shards.max(1)];
+    for message in batch {
+        let key = match &message.data {
+            MessageData::CommitInfo { hash, .. } => hash.as_str(),
+            MessageData::FileChange { commit_hash, .. } => commit_hash.as_str(),
+            MessageData::FileInfo { path, .. }
+            | MessageData::FileContent { path, .. }
+            | MessageData::ContentFingerprints { path, .. } => path.as_str(),
+            MessageData::ChangeFrequencyInfo { file_path, .. } => file_path.as_str(),
+            _ => "",
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        let shard = (hasher.finish() % sharded.len() as u64) as usize;
+        sharded[shard].push(message);
+    }
+    sharded.retain(|shard| !shard.is_empty());
+    sharded
+}
+
+/// Run `--compare`: scan the base and head refs and report the differences
+pub fn run_comparison(
+    repo_path: PathBuf,
+    args: &cli::Args,
+    config_manager: &config::ConfigManager,
+) -> Result<()> {
+    use super::comparison::{self, CompareRefs, Comparison};
+    use super::report;
+    use std::sync::Arc;
+
+    let spec = args.compare.as_deref().unwrap_or_default();
+    let refs = CompareRefs::parse(spec).map_err(|e| anyhow::anyhow!(e))?;
+    let scanner_config = cli::converter::args_to_scanner_config(args, Some(config_manager))?;
+    let query_params = cli::converter::args_to_query_params(args, Some(config_manager))?;
+    let repo_path = scanner_config.git_dir.clone().unwrap_or(repo_path);
+
+    // Without an explicit head, compare against the branch a normal scan would use
+    let head = match refs.head {
+        Some(head) => head,
+        None => {
+            let cli_fallbacks: Option<Vec<String>> = args.fallback_branch.as_ref()
+                .map(|s| s.split(',').map(|s| s.trim().to_string()).collect());
+            BranchDetection::new()
+                .detect_branch(&repo_path, args.branch.as_deref(), args.remote.as_deref(), cli_fallbacks.as_deref())?
+                .branch_name
+        }
+    };
+
+    let format = report::output_format(args.compare_output.as_deref())?;
+
+    let mut colour_manager = super::initialization::create_colour_manager(args, config_manager);
+    if let Some(links) = super::initialization::resolve_hyperlinks(args, config_manager, &repo_path)? {
+        colour_manager = colour_manager.with_hyperlinks(links);
+    }
+    let progress = display::ProgressIndicator::new(colour_manager.clone());
+    progress.status(display::StatusType::Info, &format!("Comparing {} with {} in {}", head, refs.base, repo_path.display()));
+
+    let imports = config_manager.get_import_rules();
+    let identities = config_manager.get_identity_rules();
+    let cache = scan_cache(args);
+    let (base, head) = crate::runtime::block_on(async {
+        let base = comparison::scan_ref(&repo_path, &query_params, &imports, &identities, cache.as_ref(), &refs.base).await?;
+        let head = comparison::scan_ref(&repo_path, &query_params, &imports, &identities, cache.as_ref(), &head).await?;
+        Ok::<_, scanner::async_engine::error::ScanError>((base, head))
+    }).map_err(|e| anyhow::anyhow!("Comparison scan failed: {}", e))?;
+
+    let exports = Comparison::new(base, head).exports();
+    let output = report::render(&exports, format, Some(Arc::new(colour_manager)))?;
+    match &args.compare_output {
+        Some(path) => {
+            std::fs::write(path, output)?;
+            progress.status(display::StatusType::Info, &format!("Comparison written to {}", path.display()));
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Run `--matrix-refs`: scan every ref and report each metric with one column per ref
+pub fn run_ref_matrix(
+    repo_path: PathBuf,
+    args: &cli::Args,
+    config_manager: &config::ConfigManager,
+) -> Result<()> {
+    use super::comparison::{self, RefMatrix, RefStats};
+    use super::report;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let refs = RefMatrix::parse_refs(&args.matrix_refs).map_err(|e| anyhow::anyhow!(e))?;
+    let scanner_config = cli::converter::args_to_scanner_config(args, Some(config_manager))?;
+    let query_params = cli::converter::args_to_query_params(args, Some(config_manager))?;
+    let repo_path = scanner_config.git_dir.clone().unwrap_or(repo_path);
+    let format = report::output_format(args.matrix_output.as_deref())?;
+
+    let mut colour_manager = super::initialization::create_colour_manager(args, config_manager);
+    if let Some(links) = super::initialization::resolve_hyperlinks(args, config_manager, &repo_path)? {
+        colour_manager = colour_manager.with_hyperlinks(links);
+    }
+    let progress = display::ProgressIndicator::new(colour_manager.clone());
+    progress.status(display::StatusType::Info, &format!("Scanning {} refs in {}", refs.len(), repo_path.display()));
+
+    let imports = config_manager.get_import_rules();
+    let identities = config_manager.get_identity_rules();
+    let cache = scan_cache(args);
+    // Refs pointing at the same commit have the same statistics, so each commit is scanned once
+    let mut scanned: HashMap<String, RefStats> = HashMap::new();
+    let mut stats = Vec::new();
+    for reference in &refs {
+        let commit = comparison::resolve_ref(&repo_path, reference);
+        if let Some(existing) = commit.as_ref().and_then(|commit| scanned.get(commit)) {
+            debug!("Ref {} is at the same commit as {}; reusing its scan", reference, existing.name);
+            stats.push(RefStats { name: reference.clone(), ..existing.clone() });
+            continue;
+        }
+        let ref_stats = crate::runtime::block_on(
+            comparison::scan_ref(&repo_path, &query_params, &imports, &identities, cache.as_ref(), reference)
+        ).map_err(|e| anyhow::anyhow!("Scan of '{}' failed: {}", reference, e))?;
+        if let Some(commit) = commit {
+            scanned.insert(commit, ref_stats.clone());
+        }
+        stats.push(ref_stats);
+    }
+
+    let exports = RefMatrix::new(stats).exports();
+    let output = report::render(&exports, format, Some(Arc::new(colour_manager)))?;
+    match &args.matrix_output {
+        Some(path) => {
+            std::fs::write(path, output)?;
+            progress.status(display::StatusType::Info, &format!("Ref matrix written to {}", path.display()));
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Run `--debt-trend`: assess technical debt at sampled commits of the history
+pub fn run_debt_trend(
+    repo_path: PathBuf,
+    args: &cli::Args,
+    config_manager: &config::ConfigManager,
+) -> Result<()> {
+    use super::debt_trend::{self, DebtTrend, DebtTrendPoint, SampleInterval};
+    use super::report;
+    use crate::plugin::processors::debt_assessment::DebtConfig;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let spec = args.debt_trend.as_deref().unwrap_or_default();
+    let interval = SampleInterval::parse(spec).map_err(|e| anyhow::anyhow!(e))?;
+    let scanner_config = cli::converter::args_to_scanner_config(args, Some(config_manager))?;
+    let query_params = cli::converter::args_to_query_params(args, Some(config_manager))?;
+    let repo_path = scanner_config.git_dir.clone().unwrap_or(repo_path);
+    let format = report::output_format(args.debt_trend_output.as_deref())?;
+
+    let mut colour_manager = super::initialization::create_colour_manager(args, config_manager);
+    if let Some(links) = super::initialization::resolve_hyperlinks(args, config_manager, &repo_path)? {
+        colour_manager = colour_manager.with_hyperlinks(links);
+    }
+    let progress = display::ProgressIndicator::new(colour_manager.clone());
+
+    let imports = config_manager.get_import_rules();
+    let identities = config_manager.get_identity_rules();
+    let cache = scan_cache(args);
+    let history = crate::runtime::block_on(
+        debt_trend::scan_history(&repo_path, &query_params, &imports, &identities, cache.as_ref())
+    ).map_err(|e| anyhow::anyhow!("Debt trend scan failed: {}", e))?;
+    let tags = match interval {
+        SampleInterval::Tags => debt_trend::tagged_commits(&repo_path)?,
+        _ => HashMap::new(),
+    };
+    let samples = debt_trend::select_samples(&history, interval, &tags);
+    if samples.is_empty() {
+        return Err(anyhow::anyhow!("No tagged commits in the scanned history to sample"));
+    }
+    progress.status(display::StatusType::Info, &format!(
+        "Assessing technical debt at {} of {} commits in {}", samples.len(), history.len(), repo_path.display()
+    ));
+
+    let config = DebtConfig::default();
+    let mut points = Vec::new();
+    for sample in samples {
+        debug!("Assessing technical debt at {} ({})", sample.label, sample.hash);
+        let messages = crate::runtime::block_on(
+            debt_trend::scan_sample(&repo_path, &query_params, &imports, &identities, cache.as_ref(), &sample)
+        ).map_err(|e| anyhow::anyhow!("Scan of {} failed: {}", sample.label, e))?;
+        // File ages and change frequencies are measured from the sample's date
+        crate::clock::fix(UNIX_EPOCH + Duration::from_secs(sample.timestamp.max(0) as u64));
+        points.push(DebtTrendPoint::assess(sample, &messages, &config));
+    }
+
+    let exports = DebtTrend::new(interval, points).exports();
+    let output = report::render(&exports, format, Some(Arc::new(colour_manager)))?;
+    match &args.debt_trend_output {
+        Some(path) => {
+            std::fs::write(path, output)?;
+            progress.status(display::StatusType::Info, &format!("Debt trend written to {}", path.display()));
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Scan cache for ref scans, unless disabled with `--no-cache`
+fn scan_cache(args: &cli::Args) -> Option<crate::cache::ScanCache> {
+    if args.no_cache {
+        return None;
+    }
+    crate::cache::ScanCache::default_directory().map(crate::cache::ScanCache::new)
+}
+
+/// Run `--repos`/`--repos-file`: scan every repository and report them individually and together
+pub fn run_repositories(
+    args: &cli::Args,
+    config_manager: &config::ConfigManager,
+) -> Result<()> {
+    use super::repositories::{self, MultiRepositoryReport};
+    use super::report;
+    use std::sync::Arc;
+
+    let mut paths: Vec<PathBuf> = args.repos.iter().map(|path| repositories::expand_home(path)).collect();
+    if let Some(manifest) = &args.repos_file {
+        paths.extend(repositories::read_manifest(manifest)?);
+    }
+    let repositories = repositories::identify(paths);
+    if repositories.is_empty() {
+        return Err(anyhow::anyhow!("No repositories to scan"));
+    }
+
+    let query_params = cli::converter::args_to_query_params(args, Some(config_manager))?;
+    let format = report::output_format(args.repos_output.as_deref())?;
+    let colour_manager = super::initialization::create_colour_manager(args, config_manager);
+    let progress = display::ProgressIndicator::new(colour_manager.clone());
+
+    let imports = config_manager.get_import_rules();
+    let identities = config_manager.get_identity_rules();
+    // One repository failing to scan is reported without losing the others
+    let mut scanned = Vec::new();
+    for repository in &repositories {
+        progress.status(display::StatusType::Info, &format!("Scanning {} ({})", repository.id, repository.path.display()));
+        let result = crate::runtime::block_on(
+            repositories::scan_repository(repository, &query_params, &imports, &identities)
+        );
+        match result {
+            Ok(stats) => scanned.push(stats),
+            Err(e) => progress.status(display::StatusType::Warning, &format!("Skipping {}: {}", repository.id, e)),
+        }
+    }
+    if scanned.is_empty() {
+        return Err(anyhow::anyhow!("None of the {} repositories could be scanned", repositories.len()));
+    }
+
+    let exports = MultiRepositoryReport::new(scanned).exports();
+    let output = report::render(&exports, format, Some(Arc::new(colour_manager)))?;
+    match &args.repos_output {
+        Some(path) => {
+            std::fs::write(path, output)?;
+            progress.status(display::StatusType::Info, &format!("Multi-repository report written to {}", path.display()));
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Build the `--expect-data` failure, naming the filter that matched nothing
+fn explain_empty_scan(
+    repo_path: &std::path::Path,
+    query_params: &scanner::query::QueryParams,
+    identities: &scanner::identity::IdentityRules,
+) -> NoDataError {
+    let reason = match scanner::async_engine::scanners::diagnose_empty_scan(repo_path, query_params, identities) {
+        Ok(funnel) => funnel.explain(query_params),
+        Err(e) => {
+            debug!("Failed to diagnose empty scan: {}", e);
+            None
+        }
+    };
+    NoDataError(format!(
+        "No data to report: {}",
+        reason.unwrap_or_else(|| "no commits matched the filters".to_string())
+    ))
+}
+
 /// Handle --show-branch command
 pub async fn handle_show_branch_command(
     args: &cli::Args,
@@ -528,6 +1230,22 @@ pub async fn handle_show_branch_command(
             std::env::current_dir()?
         }
     };
+    let repo_path = cli::converter::args_to_scanner_config(args, Some(config_manager))?
+        .git_dir
+        .unwrap_or(repo_path);
+    
+    // Detect the branch exactly as a scan would, including the configured default branch
+    let branch = cli::converter::args_to_query_params(args, Some(config_manager))?.branch;
+    let detected = resolve_scan_start(&repo_path, args, branch.as_deref());
+    if args.json {
+        let branch_result = detected?;
+        return print_json(&serde_json::json!({
+            "repository": repo_path,
+            "branch": branch_result.branch_name,
+            "source": branch_result.selection_source.debug(),
+            "commit_id": branch_result.commit_id,
+        }));
+    }
     
     // Create colour manager for progress display
     let colour_manager = super::initialization::create_colour_manager(args, config_manager);
@@ -536,17 +1254,7 @@ pub async fn handle_show_branch_command(
     // Show repository information
     progress.status(display::StatusType::Info, &format!("Repository: {}", repo_path.display()));
     
-    // Get CLI branch parameters
-    let cli_branch = args.branch.as_deref();
-    let cli_remote = args.remote.as_deref(); 
-    let cli_fallbacks: Option<Vec<String>> = args.fallback_branch.as_ref()
-        .map(|s| s.split(',').map(|s| s.trim().to_string()).collect());
-    
-    // Create branch detection
-    let branch_detection = BranchDetection::new();
-    
-    // Detect the branch
-    match branch_detection.detect_branch(&repo_path, cli_branch, cli_remote, cli_fallbacks.as_deref()) {
+    match detected {
         Ok(branch_result) => {
             progress.status(display::StatusType::Info, &format!(
                 "Selected branch: {} ({})",
@@ -567,3 +1275,50 @@ pub async fn handle_show_branch_command(
     Ok(())
 }
 
+/// Resolve the ref a scan starts from
+///
+/// A branch named with `--branch` or the configured default branch may also be a
+/// tag or commit; otherwise the repository's default branch is detected, so a
+/// scan from a feature branch or a detached HEAD still covers the mainline.
+/// Latest commit date of a scan and a description of its input, for `--reproducible`
+///
+/// The input is the scanned commit (or the patches of a series) and the query.
+fn reproducible_input(
+    repo_path: &std::path::Path,
+    patch_series: bool,
+    query_params: &scanner::QueryParams,
+) -> Result<(std::time::SystemTime, String)> {
+    let (latest, commits) = if patch_series {
+        let patches = scanner::patch::read_patch_series(repo_path)?;
+        let latest = patches.iter().map(|patch| patch.timestamp).max().unwrap_or_default();
+        (latest, patches.iter().map(|patch| patch.hash.as_str()).collect::<Vec<_>>().join(","))
+    } else {
+        let commit_id = query_params.scanned_ref.as_ref()
+            .map(|scanned_ref| scanned_ref.commit_id.clone())
+            .ok_or_else(|| anyhow::anyhow!("--reproducible needs a commit to scan"))?;
+        let repo = scanner::repository::open_repository(repo_path)?;
+        let id = gix::ObjectId::from_hex(commit_id.as_bytes())?;
+        let latest = repo.find_commit(id)?.time()?.seconds;
+        (latest, commit_id)
+    };
+    let latest = std::time::UNIX_EPOCH + std::time::Duration::from_secs(latest.max(0) as u64);
+    Ok((latest, format!("{commits}\n{query_params:?}")))
+}
+
+fn resolve_scan_start(
+    repo_path: &std::path::Path,
+    args: &cli::Args,
+    branch: Option<&str>,
+) -> std::result::Result<BranchDetectionResult, BranchDetectionError> {
+    let branch_detection = BranchDetection::new();
+    if let Some(branch) = branch {
+        return Ok(BranchDetectionResult {
+            branch_name: branch.to_string(),
+            selection_source: if args.branch.is_some() { BranchSelectionSource::CliOverride } else { BranchSelectionSource::ConfigDefault },
+            commit_id: branch_detection.resolve_branch_ref(repo_path, branch)?,
+        });
+    }
+    let fallbacks: Option<Vec<String>> = args.fallback_branch.as_ref()
+        .map(|s| s.split(',').map(|s| s.trim().to_string()).collect());
+    branch_detection.detect_branch(repo_path, None, args.remote.as_deref(), fallbacks.as_deref())
+}
@@ -0,0 +1,414 @@
+//! Result Gates
+//!
+//! `--fail-on` conditions turn a scan into a CI quality gate: after the plugins
+//! have published their results, each condition is checked against them and
+//! gstats exits with [`EXIT_GATE_FAILED`] when any of them holds.
+//!
+//! A condition names a table, a column, an optional aggregate and a threshold:
+//! `hotspots.score>80`, `hotspots.cyclomatic.max>=50`, `hotspots.risk>=high`.
+//! Tables are matched by title or plugin name and columns by name, ignoring case
+//! and punctuation (`change-score` matches the `Change Score` column). Without an
+//! aggregate the condition holds when any row's value meets the threshold.
+//! Thresholds are numbers or severity levels (`low` to `critical`), which compare
+//! in order of severity.
+
+use crate::display::Severity;
+use crate::notifications::error::NotificationResult;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{RateLimit, Subscriber};
+use crate::plugin::data_export::{DataPayload, PluginDataExport, Value};
+use crate::plugin::table_match::{column_index, normalise, Operator};
+use crate::plugin::traits::PluginType;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Exit status for a run where a `--fail-on` condition held
+pub const EXIT_GATE_FAILED: i32 = 4;
+
+/// Raised when one or more `--fail-on` conditions held for the results
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct GateFailedError(pub String);
+
+/// How a column's values are reduced before comparing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    /// Each value is compared on its own
+    Any,
+    Max,
+    Min,
+    Sum,
+    Avg,
+    Count,
+}
+
+impl Aggregate {
+    /// Parse an aggregate name (`max`, `min`, `sum`, `avg`, `count`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "max" => Some(Self::Max),
+            "min" => Some(Self::Min),
+            "sum" | "total" => Some(Self::Sum),
+            "avg" | "mean" => Some(Self::Avg),
+            "count" => Some(Self::Count),
+            _ => None,
+        }
+    }
+}
+
+/// The value a condition compares against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Threshold {
+    Number(f64),
+    Severity(Severity),
+}
+
+/// A parsed `--fail-on` condition
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailCondition {
+    expression: String,
+    table: String,
+    column: String,
+    aggregate: Aggregate,
+    operator: Operator,
+    threshold: Threshold,
+}
+
+impl FailCondition {
+    /// Parse a condition such as `hotspots.score>80` or `hotspots.risk>=high`
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let invalid = |reason: &str| format!("Invalid --fail-on condition '{}': {}", expression, reason);
+
+        let (position, operator, length) = expression.find(['<', '>', '=', '!'])
+            .and_then(|position| Operator::parse_prefix(&expression[position..])
+                .filter(|(operator, _)| !operator.is_text_match())
+                .map(|(operator, length)| (position, operator, length)))
+            .ok_or_else(|| invalid("expected a comparison (>, >=, <, <=, ==, !=)"))?;
+        let (path, value) = (expression[..position].trim(), expression[position + length..].trim());
+
+        let parts: Vec<&str> = path.split('.').map(str::trim).collect();
+        let (table, column, aggregate) = match parts.as_slice() {
+            [table, column] => (*table, *column, Aggregate::Any),
+            [table, column, aggregate] => {
+                let aggregate = Aggregate::parse(aggregate)
+                    .ok_or_else(|| invalid("the aggregate must be one of max, min, sum, avg or count"))?;
+                (*table, *column, aggregate)
+            }
+            _ => return Err(invalid("expected <table>.<column>[.<aggregate>] before the comparison")),
+        };
+        if table.is_empty() || column.is_empty() {
+            return Err(invalid("the table and column names cannot be empty"));
+        }
+
+        let threshold = match (value.parse::<f64>(), Severity::parse(value)) {
+            (Ok(number), _) => Threshold::Number(number),
+            (_, Some(severity)) => Threshold::Severity(severity),
+            _ => return Err(invalid("the threshold must be a number or a severity (low, medium, high, critical)")),
+        };
+        if let (Threshold::Severity(_), Aggregate::Sum | Aggregate::Avg | Aggregate::Count) = (threshold, aggregate) {
+            return Err(invalid("severities can only be compared directly or with max and min"));
+        }
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            table: normalise(table),
+            column: normalise(column),
+            aggregate,
+            operator,
+            threshold,
+        })
+    }
+
+    /// Check the condition against the exported results
+    ///
+    /// Returns a description of what met the threshold when the condition holds,
+    /// and an error when no exported table has the column it names.
+    pub fn evaluate(&self, exports: &[Arc<PluginDataExport>]) -> Result<Option<String>, String> {
+        let mut found = false;
+        for export in exports.iter().filter(|export| self.matches_table(export)) {
+            let Some(values) = self.column_values(export) else { continue };
+            found = true;
+            if let Some(reason) = self.check(&export.title, &values) {
+                return Ok(Some(reason));
+            }
+        }
+        if found {
+            return Ok(None);
+        }
+
+        let mut tables: Vec<String> = exports.iter().map(|export| normalise(&export.title)).collect();
+        tables.sort();
+        tables.dedup();
+        Err(format!(
+            "--fail-on condition '{}' matches no column of the results (tables: {})",
+            self.expression,
+            if tables.is_empty() { "none".to_string() } else { tables.join(", ") }
+        ))
+    }
+
+    fn matches_table(&self, export: &PluginDataExport) -> bool {
+        let title = normalise(&export.title);
+        // `hotspot` names the `Hotspots` table as well
+        title == self.table || title == format!("{}s", self.table) || normalise(&export.plugin_id) == self.table
+    }
+
+    /// Non-null values of the named column, labelled by the row they came from
+    fn column_values(&self, export: &PluginDataExport) -> Option<Vec<(String, Value)>> {
+        match &export.data {
+            DataPayload::Rows(rows) => {
+                let index = column_index(export, &self.column)?;
+                // Rows are labelled by their first text column (usually a file or author)
+                let label = export.schema.columns.iter().position(|column| column.data_type == crate::plugin::data_export::ColumnType::String);
+                Some(rows.iter().enumerate()
+                    .filter_map(|(number, row)| {
+                        let value = row.values.get(index).filter(|value| !value.is_null())?;
+                        let label = label.and_then(|label| row.values.get(label))
+                            .map(|label| label.to_string())
+                            .unwrap_or_else(|| format!("row {}", number + 1));
+                        Some((label, value.clone()))
+                    })
+                    .collect())
+            }
+            DataPayload::KeyValue(values) => values.iter()
+                .find(|(key, _)| normalise(key) == self.column)
+                .map(|(key, value)| vec![(key.clone(), value.clone())]),
+            _ => None,
+        }
+    }
+
+    fn check(&self, title: &str, values: &[(String, Value)]) -> Option<String> {
+        match self.threshold {
+            Threshold::Number(threshold) => {
+                let numbers: Vec<(&str, f64)> = values.iter()
+                    .filter_map(|(label, value)| numeric(value).map(|number| (label.as_str(), number)))
+                    .collect();
+                let compare = |number: f64| number.partial_cmp(&threshold).is_some_and(|ordering| self.operator.holds(ordering));
+                // Counting includes values that are not numbers
+                let total = match self.aggregate {
+                    Aggregate::Count => Some(values.len() as f64),
+                    _ => self.reduce(&numbers),
+                };
+                match total {
+                    Some(total) => compare(total).then(|| format!("{}: {} is {}", self.expression, title, round(total))),
+                    None => numbers.iter()
+                        .find(|(_, number)| compare(*number))
+                        .map(|(label, number)| format!("{}: {} in {} is {}", self.expression, label, title, round(*number))),
+                }
+            }
+            Threshold::Severity(threshold) => {
+                let mut levels: Vec<(&str, Severity)> = values.iter()
+                    .filter_map(|(label, value)| Severity::parse(&value.to_string()).map(|level| (label.as_str(), level)))
+                    .collect();
+                match self.aggregate {
+                    Aggregate::Max => levels.sort_by_key(|(_, level)| std::cmp::Reverse(*level)),
+                    Aggregate::Min => levels.sort_by_key(|(_, level)| *level),
+                    _ => {}
+                }
+                if self.aggregate != Aggregate::Any {
+                    levels.truncate(1);
+                }
+                levels.into_iter()
+                    .find(|(_, level)| self.operator.holds(level.cmp(&threshold)))
+                    .map(|(label, level)| format!("{}: {} in {} is {:?}", self.expression, label, title, level))
+            }
+        }
+    }
+
+    /// The aggregate of the numbers, or none when each is compared on its own
+    fn reduce(&self, numbers: &[(&str, f64)]) -> Option<f64> {
+        let values = numbers.iter().map(|(_, number)| *number);
+        match self.aggregate {
+            Aggregate::Any => None,
+            Aggregate::Count => Some(numbers.len() as f64),
+            Aggregate::Sum => Some(values.sum()),
+            Aggregate::Avg if numbers.is_empty() => None,
+            Aggregate::Avg => Some(values.sum::<f64>() / numbers.len() as f64),
+            Aggregate::Max => values.reduce(f64::max),
+            Aggregate::Min => values.reduce(f64::min),
+        }
+    }
+}
+
+/// Check every condition, failing with all of those that held
+pub fn check(conditions: &[FailCondition], exports: &[Arc<PluginDataExport>]) -> anyhow::Result<()> {
+    let mut failures = Vec::new();
+    for condition in conditions {
+        if let Some(reason) = condition.evaluate(exports).map_err(|e| anyhow::anyhow!(e))? {
+            failures.push(reason);
+        }
+    }
+    if failures.is_empty() {
+        return Ok(());
+    }
+    Err(GateFailedError(format!("Quality gate failed:\n  {}", failures.join("\n  "))).into())
+}
+
+/// The plugins, of those given, that published no rows
+///
+/// A plugin counts as empty when every table it published came out empty, or
+/// when it published nothing at all. Output plugins, which deliver other plugins'
+/// results rather than tables of their own, are not listed.
+pub fn plugins_without_rows<'a>(plugins: &'a [(String, PluginType)], exports: &[Arc<PluginDataExport>]) -> Vec<&'a str> {
+    plugins.iter()
+        .filter(|(plugin, plugin_type)| {
+            *plugin_type != PluginType::Output
+                && exports.iter()
+                    .filter(|export| &export.plugin_id == plugin)
+                    .all(|export| match &export.data {
+                        DataPayload::Rows(rows) => rows.is_empty(),
+                        DataPayload::Empty => true,
+                        _ => false,
+                    })
+        })
+        .map(|(plugin, _)| plugin.as_str())
+        .collect()
+}
+
+/// Collects the results plugins publish, for checking once the scan is done
+#[derive(Default)]
+pub struct ResultCollector {
+    exports: Mutex<Vec<Arc<PluginDataExport>>>,
+}
+
+impl ResultCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The results published so far
+    pub fn exports(&self) -> Vec<Arc<PluginDataExport>> {
+        self.exports.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Subscriber<PluginEvent> for ResultCollector {
+    async fn handle_event(&self, event: PluginEvent) -> NotificationResult<()> {
+        if let PluginEvent::DataReady { export, .. } = event {
+            self.exports.lock().unwrap().push(export);
+        }
+        Ok(())
+    }
+
+    fn subscriber_id(&self) -> &str {
+        "fail-on-gate"
+    }
+
+    // Every table is needed, however quickly plugins publish them
+    fn rate_limit(&self) -> Option<RateLimit> {
+        None
+    }
+}
+
+/// A value as a number, including numbers formatted as text
+fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(text) => text.trim().trim_end_matches('%').parse().ok(),
+        value => value.as_f64(),
+    }
+}
+
+fn round(number: f64) -> f64 {
+    (number * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, ColumnType, DataExportType, DataSchema, ExportHints, Row};
+    use std::collections::HashMap;
+
+    fn hotspots() -> Arc<PluginDataExport> {
+        let rows = vec![
+            Row::new(vec![Value::String("src/main.rs".into()), Value::Float(92.5), Value::String("critical".into())]),
+            Row::new(vec![Value::String("src/lib.rs".into()), Value::Float(40.0), Value::String("medium".into())]),
+        ];
+        Arc::new(PluginDataExport {
+            plugin_id: "metrics".to_string(),
+            title: "Hotspots".to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema {
+                columns: vec![
+                    ColumnDef::new("File", ColumnType::String),
+                    ColumnDef::new("Score", ColumnType::Float),
+                    ColumnDef::new("Risk", ColumnType::String),
+                ],
+                metadata: HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints::default(),
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    fn holds(expression: &str) -> Option<String> {
+        FailCondition::parse(expression).unwrap().evaluate(&[hotspots()]).unwrap()
+    }
+
+    #[test]
+    fn test_parse_conditions() {
+        let condition = FailCondition::parse("Hotspots.Change Score.max >= 50").unwrap();
+        assert_eq!(condition.table, "hotspots");
+        assert_eq!(condition.column, "change-score");
+        assert_eq!(condition.aggregate, Aggregate::Max);
+        assert_eq!(condition.operator, Operator::GreaterOrEqual);
+        assert_eq!(condition.threshold, Threshold::Number(50.0));
+
+        assert_eq!(FailCondition::parse("hotspots.risk>=high").unwrap().threshold, Threshold::Severity(Severity::High));
+        assert!(FailCondition::parse("hotspots.score").is_err());
+        assert!(FailCondition::parse("score>80").is_err());
+        assert!(FailCondition::parse("hotspots.score.median>80").is_err());
+        assert!(FailCondition::parse("hotspots.score>lots").is_err());
+        assert!(FailCondition::parse("hotspots.risk.avg>high").is_err());
+        assert!(FailCondition::parse("hotspots.file!~main").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_rows_and_aggregates() {
+        assert_eq!(holds("hotspots.score>80").unwrap(), "hotspots.score>80: src/main.rs in Hotspots is 92.5");
+        assert!(holds("hotspot.score>95").is_none());
+        assert!(holds("metrics.score.avg>70").is_none());
+        assert!(holds("hotspots.score.avg>=66.25").is_some());
+        assert!(holds("hotspots.file.count>2").is_none());
+        assert!(holds("hotspots.file.count>1").is_some());
+        assert!(holds("hotspots.score.min<50").is_some());
+    }
+
+    #[test]
+    fn test_evaluate_severities() {
+        assert!(holds("hotspots.risk>=critical").is_some());
+        assert!(holds("hotspots.risk.min>=high").is_none());
+        assert_eq!(holds("hotspots.risk.min<=medium").unwrap(), "hotspots.risk.min<=medium: src/lib.rs in Hotspots is Medium");
+    }
+
+    #[test]
+    fn test_unknown_column_is_an_error() {
+        let condition = FailCondition::parse("hotspots.churn>1").unwrap();
+        assert!(condition.evaluate(&[hotspots()]).unwrap_err().contains("tables: hotspots"));
+
+        let conditions = [FailCondition::parse("hotspots.score>80").unwrap(), FailCondition::parse("hotspots.score<10").unwrap()];
+        let error = check(&conditions, &[hotspots()]).unwrap_err();
+        assert!(error.downcast_ref::<GateFailedError>().is_some());
+        assert!(check(&conditions[1..], &[hotspots()]).is_ok());
+    }
+
+    #[test]
+    fn test_plugins_without_rows_are_listed() {
+        let mut empty = PluginDataExport::clone(&hotspots());
+        empty.plugin_id = "commits".to_string();
+        empty.data = DataPayload::Rows(Arc::new(Vec::new()));
+        let exports = [hotspots(), Arc::new(empty)];
+        let plugins = [
+            ("commits".to_string(), PluginType::Processing),
+            ("metrics".to_string(), PluginType::Processing),
+            ("export".to_string(), PluginType::Output),
+        ];
+
+        // Empty tables and no tables at all both count; output plugins never do
+        assert_eq!(plugins_without_rows(&plugins, &exports), ["commits"]);
+        assert_eq!(plugins_without_rows(&plugins, &exports[..1]), ["commits"]);
+        assert_eq!(plugins_without_rows(&plugins, &[]), ["commits", "metrics"]);
+    }
+}
@@ -23,8 +23,8 @@ pub fn handle_export_config(config_manager: &config::ConfigManager, export_path:
     
     info!("Exporting configuration to: {}", export_path.display());
     
-    // Generate complete configuration
-    let config_content = config_manager.export_complete_config()?;
+    // Generate complete configuration, listing the options of every built-in plugin
+    let config_content = config_manager.export_complete_config(&crate::plugin::builtin::builtin_plugin_commands())?;
     
     // Write to file
     fs::write(export_path, config_content)
@@ -43,7 +43,8 @@ pub fn configure_logging(args: &cli::Args, config: &config::ConfigManager) -> Re
         LevelFilter::Trace
     } else if args.verbose {
         LevelFilter::Debug
-    } else if args.quiet {
+    } else if args.quiet || args.tui {
+        // The dashboard owns the terminal; only errors are written around it
         LevelFilter::Error
     } else {
         match config.get_log_level("base", "console-level") {
@@ -117,6 +118,29 @@ pub fn configure_logging(args: &cli::Args, config: &config::ConfigManager) -> Re
         }
     };
     
+    let filter = match args.log_filter.as_deref().or(config.get_value_root("log-filter").map(String::as_str)) {
+        Some(spec) => logging::LogFilter::parse(spec)?,
+        None => logging::LogFilter::default(),
+    };
+    if !filter.is_empty() {
+        debug!("Per-module log levels: {:?}", filter);
+    }
+    
+    let rotation = match args.log_max_size.as_deref().or(config.get_value_root("log-max-size").map(String::as_str)) {
+        Some(size) => {
+            let max_size = cli::memory_parser::parse_memory_size(size)
+                .map_err(|e| anyhow::anyhow!("Invalid log-max-size: {}", e))? as u64;
+            let keep = match (args.log_keep, config.get_value_root("log-keep")) {
+                (Some(keep), _) => keep,
+                (None, Some(keep)) => keep.parse::<usize>()
+                    .map_err(|_| anyhow::anyhow!("Invalid log-keep value in config: {}", keep))?,
+                (None, None) => logging::LogRotation::DEFAULT_KEEP,
+            };
+            Some(logging::LogRotation { max_size, keep })
+        }
+        None => None,
+    };
+    
     // Create color configuration based on args and config file
     // Precedence: --no-color > --color > config file > default behavior
     let (colour_config, enable_colours) = if args.no_color {
@@ -142,39 +166,76 @@ pub fn configure_logging(args: &cli::Args, config: &config::ConfigManager) -> Re
         destination,
         colour_config,
         enable_colours,
+        filter,
+        rotation,
     })
 }
 
 /// Create a ColourManager from CLI arguments and configuration file
 pub fn create_colour_manager(args: &cli::Args, config: &config::ConfigManager) -> display::ColourManager {
-    let colour_config = config.get_colour_config().ok();
+    let mut colour_config = config.get_colour_config().ok();
+    if args.accessible {
+        colour_config.get_or_insert_with(display::ColourConfig::default).set_accessible(true);
+    }
     display::ColourManager::from_color_args(args.no_color, args.color, colour_config)
 }
 
+/// Link templates for console tables, or `None` when links are off
+///
+/// `--hyperlinks` takes precedence over the configured mode. Templates missing
+/// from the `[links]` section are inferred from the repository's remote (the
+/// `--remote` one, else `origin`).
+pub fn resolve_hyperlinks(args: &cli::Args, config: &config::ConfigManager, repo_path: &std::path::Path) -> Result<Option<display::Hyperlinks>> {
+    let mode = match args.hyperlinks.as_deref() {
+        Some(mode) => display::HyperlinkMode::parse(mode),
+        None => config.get_hyperlink_mode()?,
+    }.unwrap_or_default();
+    if !mode.enabled() {
+        return Ok(None);
+    }
+    
+    let inferred = gix::open(repo_path).ok()
+        .and_then(|repo| {
+            let remote = repo.find_remote(args.remote.as_deref().unwrap_or("origin")).ok()?;
+            let url = remote.url(gix::remote::Direction::Fetch)?.to_bstring();
+            display::Hyperlinks::from_remote_url(&url.to_string())
+        })
+        .unwrap_or_default();
+    let links = config.get_link_templates().or(inferred);
+    debug!("Console table links: {:?}", links);
+    Ok((!links.is_empty()).then_some(links))
+}
+
+
+/// Shared services and scan settings handed to plugins when they are initialized
+pub struct PluginSetup {
+    /// Publisher plugins announce their lifecycle and results through
+    pub plugin_publisher: std::sync::Arc<crate::notifications::typed_publishers::PluginEventPublisher>,
+    /// Manager plugins exchange results over (e.g. DataReady to the export plugin)
+    pub plugin_notifications: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>,
+    /// Effective scanner configuration
+    pub scanner_config: std::sync::Arc<crate::scanner::ScannerConfig>,
+    /// Effective query, e.g. the date range
+    pub query_params: std::sync::Arc<crate::scanner::QueryParams>,
+}
 
 /// Initialize plugins using the plugin system's discovery mechanism
 pub fn initialize_plugins_via_discovery(
     plugin_registry: &plugin::SharedPluginRegistry,
     colour_manager: &display::ColourManager,
+    plugin_directory: Option<PathBuf>,
     excluded_plugins: Vec<String>,
-    plugin_publisher: std::sync::Arc<crate::notifications::typed_publishers::PluginEventPublisher>,
+    setup: PluginSetup,
 ) -> Result<()> {
     debug!("Initializing plugins via plugin system's discovery mechanism");
     
     // Create plugin context for initialization with the plugin publisher
     // Note: plugin context may need updating to accept Publisher trait instead of manager
-    let context = create_plugin_context(&std::env::current_dir()?, colour_manager, plugin_publisher)?;
-    
-    // The plugin registry should handle all discovery and instantiation internally
-    // TODO: Add discover_and_load_plugins method to SharedPluginRegistry that:
-    // 1. Uses UnifiedPluginDiscovery internally 
-    // 2. Handles both builtin and external plugin instantiation
-    // 3. Applies excluded_plugins filter
-    // 4. Registers and initializes plugins with context
-    // 5. Activates plugins based on active_by_default setting
-    
-    // For now, this is a placeholder that should be replaced with the proper registry method
-    plugin_registry.discover_and_load_plugins(&context, excluded_plugins)
+    let context = create_plugin_context(&std::env::current_dir()?, colour_manager, setup)?;
+    
+    // The registry discovers builtin plugins and plugin libraries in the plugin
+    // directory, applies exclusions, then initializes and activates them
+    plugin_registry.discover_and_load_plugins(&context, plugin_directory, excluded_plugins)
         .map_err(|e| anyhow::anyhow!("Plugin discovery and loading failed: {}", e))?;
     
     info!("Successfully initialized plugins via discovery system");
@@ -184,18 +245,15 @@ pub fn initialize_plugins_via_discovery(
 pub fn create_plugin_context(
     _repo_path: &PathBuf, 
     colour_manager: &display::ColourManager,
-    plugin_publisher: std::sync::Arc<crate::notifications::typed_publishers::PluginEventPublisher>,
+    setup: PluginSetup,
 ) -> Result<plugin::PluginContext> {
-    use crate::scanner::{ScannerConfig, QueryParams};
     use std::sync::Arc;
     
-    // Create default scanner config and query params
-    let scanner_config = Arc::new(ScannerConfig::default());
-    let query_params = Arc::new(QueryParams::default());
-    
-    // Use the provided plugin publisher
-    Ok(plugin::PluginContext::new(scanner_config, query_params)
-        .with_plugin_publisher(plugin_publisher)
+    // Plugins see the effective scanner config and query (e.g. date range)
+    // Use the provided plugin publisher; plugins exchange results over the shared notification manager
+    Ok(plugin::PluginContext::new(setup.scanner_config, setup.query_params)
+        .with_plugin_publisher(setup.plugin_publisher)
+        .with_notification_manager(setup.plugin_notifications)
         .with_colour_manager(Arc::new(colour_manager.clone())))
 }
 
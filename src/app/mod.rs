@@ -2,13 +2,29 @@
 
 pub mod initialization;
 pub mod execution;
+pub mod plugins;
+pub mod gate;
+pub mod trends;
+pub mod comparison;
+pub mod debt_trend;
+pub mod report;
+pub mod repositories;
+pub mod watch;
 
 pub use initialization::{
     load_configuration, 
     configure_logging
 };
 pub use execution::{
-    handle_plugin_commands,
     handle_show_branch_command,
-    run_scanner
-};
\ No newline at end of file
+    run_comparison,
+    run_debt_trend,
+    run_ref_matrix,
+    run_repositories,
+    run_scanner,
+    InterruptedError,
+    NoDataError,
+    EXIT_NO_DATA
+};
+pub use plugins::handle_plugin_commands;
+pub use gate::{GateFailedError, EXIT_GATE_FAILED};
@@ -0,0 +1,344 @@
+//! Plugin management commands (`gstats plugins ...`)
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use crate::{cli, config, display};
+use crate::cli::plugin_handler::{FunctionMapping, PluginHandler, PluginInfo};
+use crate::cli::plugins_command::PluginsCommand;
+use crate::display::{Indicator, Severity, TableBuilder};
+use crate::plugin::builtin::export::{AnnotationStyle, ExportFormat};
+use crate::plugin::builtin::utils::format_detection::FormatDetector;
+use crate::plugin::compatibility::{ApiCompatibility, VersionCompatibilityChecker};
+use super::execution::print_json;
+
+/// Formats accepted by `--format`, with whether this build can write them
+const FORMATS: &[(&str, ExportFormat, bool)] = &[
+    ("json", ExportFormat::Json, true),
+    ("ndjson", ExportFormat::Ndjson, true),
+    ("csv", ExportFormat::Csv, true),
+    ("xml", ExportFormat::Xml, true),
+    ("yaml", ExportFormat::Yaml, true),
+    ("html", ExportFormat::Html, true),
+    ("markdown", ExportFormat::Markdown, true),
+    ("sqlite", ExportFormat::Sqlite, cfg!(feature = "sqlite")),
+    ("parquet", ExportFormat::Parquet, cfg!(feature = "parquet")),
+    ("github", ExportFormat::Annotations(AnnotationStyle::GithubCommands), true),
+    ("github-json", ExportFormat::Annotations(AnnotationStyle::GithubJson), true),
+    ("gitlab", ExportFormat::Annotations(AnnotationStyle::GitlabCodeQuality), true),
+];
+
+/// Outcome of one `gstats plugins doctor` check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Ok,
+    Warning,
+    Failed,
+}
+
+impl CheckStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Warning => "WARN",
+            Self::Failed => "FAIL",
+        }
+    }
+
+    fn indicator(&self) -> Indicator {
+        match self {
+            Self::Ok => Indicator::Severity(Severity::Low),
+            Self::Warning => Indicator::Severity(Severity::Medium),
+            Self::Failed => Indicator::Severity(Severity::High),
+        }
+    }
+}
+
+/// One `gstats plugins doctor` check
+#[derive(Debug, Clone, serde::Serialize)]
+struct Check {
+    check: String,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl Check {
+    fn new(check: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { check: check.into(), status, detail: detail.into() }
+    }
+}
+
+pub async fn handle_plugin_commands(command: &PluginsCommand, args: &cli::Args, config: &config::ConfigManager) -> Result<()> {
+    let plugin_config = cli::converter::merge_plugin_config(args, Some(config));
+    let directories = plugin_config.directories.clone();
+    let mut handler = PluginHandler::with_plugin_config(plugin_config)?;
+    let colour_manager = super::initialization::create_colour_manager(args, config);
+
+    match command {
+        PluginsCommand::List => list_plugins(&mut handler, args, &colour_manager).await,
+        PluginsCommand::Info(plugin) => plugin_info(&mut handler, plugin, args).await,
+        PluginsCommand::Formats => list_formats(args, &colour_manager),
+        PluginsCommand::Doctor => doctor(&mut handler, &directories, args, &colour_manager).await,
+    }
+}
+
+/// `gstats plugins list`: a table of plugins and their functions
+async fn list_plugins(handler: &mut PluginHandler, args: &cli::Args, colour_manager: &display::ColourManager) -> Result<()> {
+    let plugins = handler.list_plugins().await?;
+    handler.build_command_mappings().await?;
+    let mappings = handler.get_function_mappings();
+    let functions = functions_by_plugin(&mappings);
+    let ambiguities = handler.get_ambiguity_reports();
+    let (min, max) = VersionCompatibilityChecker::default().supported_range();
+
+    if args.json {
+        let plugins = plugins.iter()
+            .map(|plugin| plugin_json(plugin, functions.get(plugin.name.as_str())))
+            .collect::<Result<Vec<_>>>()?;
+        return print_json(&serde_json::json!({
+            "api": { "current": crate::scanner::get_api_version(), "min": min, "max": max },
+            "plugins": plugins,
+            "ambiguities": ambiguities,
+        }));
+    }
+
+    if plugins.is_empty() {
+        println!("No plugins available.");
+        return Ok(());
+    }
+
+    let mut table = TableBuilder::new()
+        .headers(["Plugin", "Version", "API", "Status", "Functions"].map(String::from).to_vec());
+    for plugin in &plugins {
+        let functions = functions.get(plugin.name.as_str())
+            .map(|functions| function_list(functions))
+            .unwrap_or_default();
+        let row = vec![
+            plugin.name.clone(),
+            plugin.version.clone(),
+            plugin.api_version.to_string(),
+            compatibility_label(&plugin.compatibility).to_string(),
+            functions,
+        ];
+        let indicators = vec![None, None, None, compatibility_indicator(&plugin.compatibility), None];
+        table = table.add_row_with_indicators(row, indicators);
+    }
+    print!("{}", table.build_with_colors(colour_manager));
+
+    println!();
+    println!("Supported plugin API versions: {}-{} (this build: {})", min, max, crate::scanner::get_api_version());
+    println!();
+    println!("{}", colour_manager.highlight("Usage:"));
+    println!("  {}                # Use plugin's default function",
+        colour_manager.command("gstats <plugin>"));
+    println!("  {}              # Use function if unambiguous",
+        colour_manager.command("gstats <function>"));
+    println!("  {}     # Explicit plugin:function syntax",
+        colour_manager.command("gstats <plugin>:<function>"));
+    println!("  {}   # Plugin details",
+        colour_manager.command("gstats plugins info <plugin>"));
+    println!();
+    println!("{}", colour_manager.orange("* = default function for plugin"));
+
+    if !ambiguities.is_empty() {
+        println!();
+        println!("Ambiguous Functions (require plugin:function syntax):");
+        for ambiguity in ambiguities {
+            println!("  ⚠️  {}", ambiguity);
+        }
+    }
+
+    Ok(())
+}
+
+/// `gstats plugins info <PLUGIN>`: details of one plugin
+async fn plugin_info(handler: &mut PluginHandler, plugin_name: &str, args: &cli::Args) -> Result<()> {
+    let info = handler.get_plugin_info(plugin_name).await?
+        .ok_or_else(|| anyhow::anyhow!("Plugin '{}' not found (see gstats plugins list)", plugin_name))?;
+    handler.build_command_mappings().await?;
+    let mappings = handler.get_function_mappings();
+    let functions = functions_by_plugin(&mappings);
+    let functions = functions.get(info.name.as_str());
+
+    if args.json {
+        return print_json(&plugin_json(&info, functions)?);
+    }
+
+    println!("Name:         {}", info.name);
+    println!("Version:      {}", info.version);
+    println!("Type:         {:?}", info.plugin_type);
+    println!("Author:       {}", info.author);
+    println!("API version:  {} ({})", info.api_version, info.compatibility);
+    println!("Description:  {}", info.description);
+    if !info.capabilities.is_empty() {
+        println!("Capabilities: {}", info.capabilities.join(", "));
+    }
+    if let Some(path) = &info.file_path {
+        println!("Location:     {}", path.display());
+    }
+    if let Some(functions) = functions {
+        println!("Functions:");
+        for function in functions {
+            let name = if function.is_default { format!("{}*", function.function_name) } else { function.function_name.clone() };
+            let aliases = if function.aliases.is_empty() { String::new() } else { format!(" ({})", function.aliases.join(", ")) };
+            println!("  {}{} - {}", name, aliases, function.description);
+        }
+    }
+    Ok(())
+}
+
+/// `gstats plugins formats`: export formats and the file extensions selecting them
+fn list_formats(args: &cli::Args, colour_manager: &display::ColourManager) -> Result<()> {
+    let detector = FormatDetector::new();
+
+    if args.json {
+        let formats: Vec<_> = FORMATS.iter()
+            .map(|(name, format, available)| serde_json::json!({
+                "format": name,
+                "extensions": detector.get_extensions_for_format(format),
+                "binary": format.is_binary(),
+                "available": available,
+            }))
+            .collect();
+        return print_json(&serde_json::json!({ "formats": formats }));
+    }
+
+    let mut table = TableBuilder::new()
+        .headers(["Format", "Available", "Extensions"].map(String::from).to_vec());
+    for (name, format, available) in FORMATS {
+        let extensions = detector.get_extensions_for_format(format)
+            .iter()
+            .map(|extension| format!(".{}", extension))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (status, indicator) = if *available {
+            ("yes", None)
+        } else {
+            ("no (feature not built)", Some(Indicator::Severity(Severity::High)))
+        };
+        table = table.add_row_with_indicators(vec![name.to_string(), status.to_string(), extensions], vec![None, indicator, None]);
+    }
+    print!("{}", table.build_with_colors(colour_manager));
+    println!();
+    println!("Select a format with {} or from the output file's extension ({}).",
+        colour_manager.command("--format <FORMAT>"), colour_manager.command("--output <FILE>"));
+    Ok(())
+}
+
+/// `gstats plugins doctor`: check plugin discovery, API compatibility and function names
+///
+/// Fails when any check fails; warnings are reported but do not fail.
+async fn doctor(handler: &mut PluginHandler, directories: &[String], args: &cli::Args, colour_manager: &display::ColourManager) -> Result<()> {
+    let mut checks = Vec::new();
+
+    let (min, max) = VersionCompatibilityChecker::default().supported_range();
+    checks.push(Check::new("Plugin API", CheckStatus::Ok,
+        format!("this build: {}, supported: {}-{}", crate::scanner::get_api_version(), min, max)));
+
+    for directory in directories {
+        if !std::path::Path::new(directory).is_dir() {
+            checks.push(Check::new(format!("Directory {}", directory), CheckStatus::Warning, "not found"));
+        }
+    }
+
+    let plugins = handler.list_plugins().await?;
+    if plugins.is_empty() {
+        checks.push(Check::new("Discovery", CheckStatus::Failed, "no plugins found"));
+    }
+    for plugin in &plugins {
+        let status = match &plugin.compatibility {
+            ApiCompatibility::Compatible => CheckStatus::Ok,
+            ApiCompatibility::Shimmed { .. } => CheckStatus::Warning,
+            ApiCompatibility::Incompatible { .. } => CheckStatus::Failed,
+        };
+        checks.push(Check::new(format!("Plugin {}", plugin.name), status,
+            format!("{} (API {}, {})", plugin.version, plugin.api_version, plugin.compatibility)));
+    }
+
+    handler.build_command_mappings().await?;
+    let ambiguities = handler.get_ambiguity_reports();
+    if ambiguities.is_empty() {
+        checks.push(Check::new("Function names", CheckStatus::Ok, "no conflicts"));
+    }
+    for ambiguity in ambiguities {
+        checks.push(Check::new("Function names", CheckStatus::Warning, ambiguity));
+    }
+
+    let failures = checks.iter().filter(|check| check.status == CheckStatus::Failed).count();
+    if args.json {
+        print_json(&serde_json::json!({ "checks": checks, "healthy": failures == 0 }))?;
+    } else {
+        let mut table = TableBuilder::new()
+            .headers(["Check", "Status", "Detail"].map(String::from).to_vec());
+        for check in &checks {
+            table = table.add_row_with_indicators(
+                vec![check.check.clone(), check.status.label().to_string(), check.detail.clone()],
+                vec![None, Some(check.status.indicator()), None],
+            );
+        }
+        print!("{}", table.build_with_colors(colour_manager));
+    }
+
+    if failures > 0 {
+        anyhow::bail!("Plugin doctor found {} failing check{}", failures, if failures == 1 { "" } else { "s" });
+    }
+    Ok(())
+}
+
+/// Short compatibility status for the plugin table
+fn compatibility_label(compatibility: &ApiCompatibility) -> &'static str {
+    match compatibility {
+        ApiCompatibility::Compatible => "compatible",
+        ApiCompatibility::Shimmed { .. } => "shimmed",
+        ApiCompatibility::Incompatible { .. } => "incompatible",
+    }
+}
+
+fn compatibility_indicator(compatibility: &ApiCompatibility) -> Option<Indicator> {
+    match compatibility {
+        ApiCompatibility::Compatible => None,
+        ApiCompatibility::Shimmed { .. } => Some(Indicator::Severity(Severity::Medium)),
+        ApiCompatibility::Incompatible { .. } => Some(Indicator::Severity(Severity::High)),
+    }
+}
+
+/// Functions grouped by plugin, each plugin's default function first
+fn functions_by_plugin(mappings: &[FunctionMapping]) -> BTreeMap<&str, Vec<&FunctionMapping>> {
+    let mut by_plugin: BTreeMap<&str, Vec<&FunctionMapping>> = BTreeMap::new();
+    for mapping in mappings {
+        by_plugin.entry(mapping.plugin_name.as_str()).or_default().push(mapping);
+    }
+    for functions in by_plugin.values_mut() {
+        functions.sort_by(|a, b| b.is_default.cmp(&a.is_default).then_with(|| a.function_name.cmp(&b.function_name)));
+    }
+    by_plugin
+}
+
+/// Function names for the plugin table, the default marked with `*` and followed by aliases
+fn function_list(functions: &[&FunctionMapping]) -> String {
+    functions.iter()
+        .map(|function| {
+            let default_marker = if function.is_default { "*" } else { "" };
+            let aliases = if function.aliases.is_empty() { String::new() } else { format!(", {}", function.aliases.join(", ")) };
+            format!("{}{}{}", function.function_name, default_marker, aliases)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A plugin's details with its functions, for `--json`
+fn plugin_json(plugin: &PluginInfo, functions: Option<&Vec<&FunctionMapping>>) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(plugin)?;
+    let functions: Vec<_> = functions.into_iter().flatten()
+        .map(|function| serde_json::json!({
+            "name": function.function_name,
+            "aliases": function.aliases,
+            "description": function.description,
+            "default": function.is_default,
+        }))
+        .collect();
+    if let Some(object) = value.as_object_mut() {
+        object.insert("functions".to_string(), serde_json::Value::Array(functions));
+    }
+    Ok(value)
+}
@@ -0,0 +1,63 @@
+//! Report Rendering
+//!
+//! Renders the tables built by the app itself rather than by a plugin, such as
+//! branch comparisons and multi-repository summaries, with the export formatters.
+
+use crate::display::ColourManager;
+use crate::plugin::builtin::export::config::ExportFormat as OutputFormat;
+use crate::plugin::builtin::export::formats::{
+    annotations::AnnotationFormatter, console::ConsoleFormatter, csv::CsvFormatter, html::HtmlFormatter, json::JsonFormatter,
+    markdown::MarkdownFormatter, ndjson::NdjsonFormatter, xml::XmlFormatter, yaml::YamlFormatter,
+    FormatExporter,
+};
+use crate::plugin::builtin::utils::format_detection::{FormatDetectionResult, FormatDetector};
+use crate::plugin::data_export::PluginDataExport;
+use crate::plugin::{PluginError, PluginResult};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Output format for a report file, taken from its extension; without a file, the console
+pub fn output_format(path: Option<&Path>) -> anyhow::Result<OutputFormat> {
+    match path {
+        Some(path) => match FormatDetector::new().detect_format_from_path(path) {
+            FormatDetectionResult::Detected(format) => Ok(format),
+            _ => Err(anyhow::anyhow!(
+                "Cannot determine the output format of '{}' from its extension", path.display()
+            )),
+        },
+        None => Ok(OutputFormat::Console),
+    }
+}
+
+/// Render report tables in the requested output format
+pub fn render(
+    exports: &[Arc<PluginDataExport>],
+    format: OutputFormat,
+    colour_manager: Option<Arc<ColourManager>>,
+) -> PluginResult<String> {
+    match format {
+        OutputFormat::Console => match colour_manager {
+            Some(colour_manager) => ConsoleFormatter::with_colors(colour_manager).format_with_colors(exports),
+            None => ConsoleFormatter::new().format_data(exports),
+        },
+        OutputFormat::Json => JsonFormatter::new().format_data(exports),
+        OutputFormat::Ndjson => NdjsonFormatter::new().format_data(exports),
+        OutputFormat::Csv => CsvFormatter::new().format_data(exports),
+        OutputFormat::Xml => XmlFormatter::new().format_data(exports),
+        OutputFormat::Yaml => YamlFormatter::new().format_data(exports),
+        OutputFormat::Html => HtmlFormatter::new()
+            .with_accessible(colour_manager.is_some_and(|colour_manager| colour_manager.accessible()))
+            .format_data(exports),
+        OutputFormat::Markdown => MarkdownFormatter::new().format_data(exports),
+        OutputFormat::Annotations(style) => AnnotationFormatter::new(style).format_data(exports),
+        OutputFormat::Template => Err(PluginError::configuration_error(
+            "Templates are not supported for comparison and multi-repository reports".to_string(),
+        )),
+        OutputFormat::Sqlite => Err(PluginError::configuration_error(
+            "SQLite is not supported for comparison and multi-repository reports".to_string(),
+        )),
+        OutputFormat::Parquet => Err(PluginError::configuration_error(
+            "Parquet is not supported for comparison and multi-repository reports".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,341 @@
+//! Multi-Repository Scans
+//!
+//! Scans several repositories in one invocation, such as every service of a
+//! microservice estate, and reports each repository alongside totals across all
+//! of them. Repositories are named with `--repos` or listed in a manifest given
+//! with `--repos-file`. Each is identified by its directory name, with a numeric
+//! suffix when two share a name, and every scan message and per-repository table
+//! is tagged with that identifier.
+//!
+//! The report has a summary row per repository, authors' activity summed across
+//! repositories and an author table for each repository. As with branch
+//! comparisons, the results are ordinary tabular exports, so any export format
+//! can show them.
+
+use super::comparison::{AuthorStats, RefStats};
+use crate::plugin::data_export::{
+    ColumnDef, ColumnType, DataExportType, DataPayload, DataSchema, ExportHints, PluginDataExport, Row, Value,
+};
+use crate::scanner::async_engine::error::ScanResult;
+use crate::scanner::async_engine::scanners::EventDrivenScanner;
+use crate::scanner::identity::IdentityRules;
+use crate::scanner::imports::ImportRules;
+use crate::scanner::query::QueryParams;
+use futures::StreamExt;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A repository to scan and the identifier its results are tagged with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repository {
+    pub id: String,
+    pub path: PathBuf,
+}
+
+/// Repository paths listed in a manifest
+///
+/// One path per line; blank lines and `#` comments are ignored, and relative
+/// paths are taken relative to the manifest's directory.
+pub fn parse_manifest(content: &str, base: &Path) -> Vec<PathBuf> {
+    content.lines()
+        .map(|line| line.split_once('#').map_or(line, |(path, _)| path).trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| base.join(expand_home(line)))
+        .collect()
+}
+
+/// A path with a leading `~` replaced by the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Read a repository manifest file
+pub fn read_manifest(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read repository manifest '{}': {}", path.display(), e))?;
+    Ok(parse_manifest(&content, path.parent().unwrap_or(Path::new("."))))
+}
+
+/// Identify each repository by its directory name, skipping paths given twice
+pub fn identify(paths: Vec<PathBuf>) -> Vec<Repository> {
+    let mut seen = HashSet::new();
+    let mut ids = HashSet::new();
+    paths.into_iter()
+        .filter(|path| seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())))
+        .map(|path| {
+            let name = directory_name(&path);
+            let mut id = name.clone();
+            let mut suffix = 1;
+            while !ids.insert(id.clone()) {
+                suffix += 1;
+                id = format!("{}-{}", name, suffix);
+            }
+            Repository { id, path }
+        })
+        .collect()
+}
+
+/// Name of a repository's directory: `service` for `service`, `service.git` and `service/.git`
+fn directory_name(path: &Path) -> String {
+    let path = if path.file_name().is_some_and(|name| name == ".git") {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    let name = path.canonicalize().ok()
+        .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .or_else(|| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| path.display().to_string());
+    name.strip_suffix(".git").map(str::to_string).unwrap_or(name)
+}
+
+/// Scan one repository with the given query, import rules and author identities and summarise the result
+pub async fn scan_repository(
+    repository: &Repository,
+    query_params: &QueryParams,
+    imports: &ImportRules,
+    identities: &IdentityRules,
+) -> ScanResult<RefStats> {
+    let scanner = EventDrivenScanner::new(query_params.clone())
+        .with_imports(imports.clone())
+        .with_identities(identities.clone())
+        .with_repository(repository.id.clone());
+    let mut stream = scanner.scan_async(&repository.path).await?;
+
+    let mut messages = Vec::new();
+    while let Some(message) = stream.next().await {
+        messages.push(message?);
+    }
+    Ok(RefStats::from_messages(repository.id.clone(), &messages))
+}
+
+/// Statistics of several scanned repositories and the tables describing them
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiRepositoryReport {
+    pub repositories: Vec<RefStats>,
+}
+
+impl MultiRepositoryReport {
+    pub fn new(repositories: Vec<RefStats>) -> Self {
+        Self { repositories }
+    }
+
+    /// Aggregate tables followed by an author table for each repository
+    pub fn exports(&self) -> Vec<Arc<PluginDataExport>> {
+        let mut exports = vec![
+            Arc::new(self.summary_export()),
+            Arc::new(self.authors_export()),
+        ];
+        exports.extend(self.repositories.iter().map(|stats| Arc::new(self.repository_authors_export(stats))));
+        exports
+    }
+
+    fn summary_export(&self) -> PluginDataExport {
+        let schema = DataSchema::new(vec![
+            ColumnDef::new("Repository", ColumnType::String),
+            ColumnDef::new("Commits", ColumnType::Integer),
+            ColumnDef::new("Authors", ColumnType::Integer),
+            ColumnDef::new("Files Changed", ColumnType::Integer),
+            ColumnDef::new("Lines Added", ColumnType::Integer),
+            ColumnDef::new("Lines Removed", ColumnType::Integer),
+            ColumnDef::new("First Commit", ColumnType::Timestamp),
+            ColumnDef::new("Last Commit", ColumnType::Timestamp),
+        ]);
+
+        let mut rows: Vec<Row> = self.repositories.iter()
+            .map(|stats| summary_row(&stats.name, stats, stats.authors.len()))
+            .collect();
+        // Authors contributing to several repositories are counted once in the total
+        let authors: BTreeSet<&String> = self.repositories.iter().flat_map(|stats| stats.authors.keys()).collect();
+        rows.push(summary_row("Total", &self.total(), authors.len()));
+
+        self.export("repos-summary", "Repository Summary", "Commit activity in each repository", schema, rows)
+    }
+
+    fn authors_export(&self) -> PluginDataExport {
+        let schema = DataSchema::new(vec![
+            ColumnDef::new("Author", ColumnType::String),
+            ColumnDef::new("Repositories", ColumnType::Integer)
+                .with_description("Number of repositories the author committed to"),
+            ColumnDef::new("Commits", ColumnType::Integer),
+            ColumnDef::new("Lines Added", ColumnType::Integer),
+            ColumnDef::new("Lines Removed", ColumnType::Integer),
+        ]);
+
+        let mut authors: BTreeMap<&String, (usize, AuthorStats)> = BTreeMap::new();
+        for (author, stats) in self.repositories.iter().flat_map(|stats| &stats.authors) {
+            let (repositories, total) = authors.entry(author).or_default();
+            *repositories += 1;
+            total.commits += stats.commits;
+            total.lines_added += stats.lines_added;
+            total.lines_removed += stats.lines_removed;
+        }
+        let mut authors: Vec<_> = authors.into_iter().collect();
+        authors.sort_by_key(|(_, (_, stats))| std::cmp::Reverse(stats.commits));
+
+        let rows = authors.into_iter()
+            .map(|(author, (repositories, stats))| Row::new(vec![
+                Value::String(author.clone()),
+                Value::Integer(repositories as i64),
+                Value::Integer(stats.commits as i64),
+                Value::Integer(stats.lines_added as i64),
+                Value::Integer(stats.lines_removed as i64),
+            ]))
+            .collect();
+
+        self.export("repos-authors", "Authors Across Repositories", "Commits per author across all repositories", schema, rows)
+    }
+
+    fn repository_authors_export(&self, stats: &RefStats) -> PluginDataExport {
+        let schema = DataSchema::new(vec![
+            ColumnDef::new("Author", ColumnType::String),
+            ColumnDef::new("Commits", ColumnType::Integer),
+            ColumnDef::new("Lines Added", ColumnType::Integer),
+            ColumnDef::new("Lines Removed", ColumnType::Integer),
+        ]);
+
+        let mut authors: Vec<(&String, &AuthorStats)> = stats.authors.iter().collect();
+        authors.sort_by_key(|(_, author)| std::cmp::Reverse(author.commits));
+        let rows = authors.into_iter()
+            .map(|(author, stats)| Row::new(vec![
+                Value::String(author.clone()),
+                Value::Integer(stats.commits as i64),
+                Value::Integer(stats.lines_added as i64),
+                Value::Integer(stats.lines_removed as i64),
+            ]))
+            .collect();
+
+        PluginDataExport {
+            repository: Some(stats.name.clone()),
+            ..self.export(
+                "repository-authors",
+                &format!("Authors in {}", stats.name),
+                &format!("Commits per author in {}", stats.name),
+                schema,
+                rows,
+            )
+        }
+    }
+
+    /// Commits, lines and the commit date range over every repository
+    fn total(&self) -> RefStats {
+        let mut total = RefStats::default();
+        for stats in &self.repositories {
+            total.commits += stats.commits;
+            total.lines_added += stats.lines_added;
+            total.lines_removed += stats.lines_removed;
+            total.files.extend(stats.files.iter().map(|file| format!("{}/{}", stats.name, file)));
+            total.first_commit = total.first_commit.into_iter().chain(stats.first_commit).min();
+            total.last_commit = total.last_commit.into_iter().chain(stats.last_commit).max();
+        }
+        total
+    }
+
+    fn export(&self, id: &str, title: &str, description: &str, schema: DataSchema, rows: Vec<Row>) -> PluginDataExport {
+        let repositories: Vec<&str> = self.repositories.iter().map(|stats| stats.name.as_str()).collect();
+        PluginDataExport {
+            plugin_id: id.to_string(),
+            title: title.to_string(),
+            description: Some(description.to_string()),
+            data_type: DataExportType::Tabular,
+            schema: schema.with_metadata("repositories", repositories.join(",")),
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints::default(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+}
+
+fn summary_row(name: &str, stats: &RefStats, authors: usize) -> Row {
+    let timestamp = |seconds: Option<i64>| match seconds {
+        Some(seconds) => Value::Timestamp(UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64)),
+        None => Value::Null,
+    };
+    Row::new(vec![
+        Value::String(name.to_string()),
+        Value::Integer(stats.commits as i64),
+        Value::Integer(authors as i64),
+        Value::Integer(stats.files.len() as i64),
+        Value::Integer(stats.lines_added as i64),
+        Value::Integer(stats.lines_removed as i64),
+        timestamp(stats.first_commit),
+        timestamp(stats.last_commit),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(name: &str, authors: &[(&str, usize)], first_commit: i64) -> RefStats {
+        RefStats {
+            name: name.to_string(),
+            commits: authors.iter().map(|(_, commits)| commits).sum(),
+            authors: authors.iter()
+                .map(|(author, commits)| (author.to_string(), AuthorStats { commits: *commits, lines_added: 10, lines_removed: 1 }))
+                .collect(),
+            files: ["src/main.rs".to_string()].into(),
+            lines_added: 10 * authors.len(),
+            lines_removed: authors.len(),
+            first_commit: Some(first_commit),
+            last_commit: Some(first_commit + 100),
+        }
+    }
+
+    fn rows(export: &PluginDataExport) -> Vec<Vec<Value>> {
+        match &export.data {
+            DataPayload::Rows(rows) => rows.iter().map(|row| row.values.clone()).collect(),
+            _ => panic!("expected rows"),
+        }
+    }
+
+    #[test]
+    fn test_manifest_paths_relative_to_manifest() {
+        let manifest = "# services\nbilling\n\n/srv/git/auth.git  # bare mirror\n";
+        assert_eq!(
+            parse_manifest(manifest, Path::new("/work")),
+            vec![PathBuf::from("/work/billing"), PathBuf::from("/srv/git/auth.git")]
+        );
+    }
+
+    #[test]
+    fn test_repositories_identified_by_directory_name() {
+        let repositories = identify(vec![
+            PathBuf::from("/nonexistent/billing"),
+            PathBuf::from("/nonexistent/auth.git"),
+            PathBuf::from("/nonexistent/legacy/billing/.git"),
+            PathBuf::from("/nonexistent/billing"),
+        ]);
+        let ids: Vec<&str> = repositories.iter().map(|repository| repository.id.as_str()).collect();
+        assert_eq!(ids, vec!["billing", "auth", "billing-2"]);
+    }
+
+    #[test]
+    fn test_report_has_aggregate_and_per_repository_tables() {
+        let report = MultiRepositoryReport::new(vec![
+            stats("billing", &[("alice", 3), ("bob", 1)], 1_000),
+            stats("auth", &[("alice", 2)], 500),
+        ]);
+        let exports = report.exports();
+        let titles: Vec<&str> = exports.iter().map(|export| export.title.as_str()).collect();
+        assert_eq!(titles, vec!["Repository Summary", "Authors Across Repositories", "Authors in billing", "Authors in auth"]);
+        assert_eq!(exports[0].repository, None);
+        assert_eq!(exports[3].repository.as_deref(), Some("auth"));
+
+        let summary = rows(&exports[0]);
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[2][..4], [
+            Value::String("Total".to_string()), Value::Integer(6), Value::Integer(2), Value::Integer(2),
+        ]);
+        assert_eq!(summary[2][6], Value::Timestamp(UNIX_EPOCH + Duration::from_secs(500)));
+
+        let authors = rows(&exports[1]);
+        assert_eq!(authors[0][..3], [Value::String("alice".to_string()), Value::Integer(2), Value::Integer(5)]);
+    }
+}
@@ -0,0 +1,366 @@
+//! Metric Trends
+//!
+//! With `--record-trends` every scan appends its key metrics to an embedded
+//! SQLite database (`--trend-db`, `database` in `[trends]`, or
+//! `trends.db` in the platform data directory), keyed by repository, time,
+//! branch and commit. `gstats trends <METRIC>` lists a metric over the recorded
+//! runs of a repository, so its evolution can be followed without keeping
+//! export files around. The database needs a build with the `sqlite` feature.
+//!
+//! Metrics are named like `--fail-on` paths, from each result table's title:
+//! `<table>.rows` is its row count, `<table>.<column>.<aggregate>` the `sum`,
+//! `avg`, `min` and `max` of each numeric column, and `<table>.<key>` each
+//! numeric value of a summary table. Names are lowercased with punctuation
+//! replaced by `-`, as in `hotspots.change-score.max`.
+
+use crate::plugin::table_match::normalise;
+use crate::plugin::data_export::{ColumnType, DataPayload, PluginDataExport};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// One metric of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub plugin: String,
+    pub name: String,
+    pub value: f64,
+}
+
+/// What a recorded run scanned
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub repository: String,
+    /// Seconds since the epoch, from the run clock
+    pub recorded_at: i64,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+    /// Plugin command the run executed
+    pub command: Option<String>,
+}
+
+/// A metric's value in one recorded run
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TrendPoint {
+    pub recorded_at: i64,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+    pub value: f64,
+}
+
+/// The key metrics of a run's results, by name
+///
+/// A name produced by two tables keeps the first table's value.
+pub fn metrics(exports: &[Arc<PluginDataExport>]) -> Vec<Metric> {
+    let mut metrics: BTreeMap<String, Metric> = BTreeMap::new();
+    let mut add = |plugin: &str, name: String, value: f64| {
+        if value.is_finite() {
+            metrics.entry(name.clone()).or_insert_with(|| Metric { plugin: plugin.to_string(), name, value });
+        }
+    };
+
+    for export in exports {
+        let table = normalise(&export.title);
+        match &export.data {
+            DataPayload::Rows(rows) => {
+                add(&export.plugin_id, format!("{}.rows", table), rows.len() as f64);
+                for (index, column) in export.schema.columns.iter().enumerate() {
+                    if !matches!(column.data_type, ColumnType::Integer | ColumnType::Float) {
+                        continue;
+                    }
+                    let values: Vec<f64> = rows.iter()
+                        .filter_map(|row| row.values.get(index).and_then(|value| value.as_f64()))
+                        .collect();
+                    if values.is_empty() {
+                        continue;
+                    }
+                    let name = format!("{}.{}", table, normalise(&column.name));
+                    let sum: f64 = values.iter().sum();
+                    add(&export.plugin_id, format!("{}.sum", name), sum);
+                    add(&export.plugin_id, format!("{}.avg", name), sum / values.len() as f64);
+                    add(&export.plugin_id, format!("{}.min", name), values.iter().copied().fold(f64::INFINITY, f64::min));
+                    add(&export.plugin_id, format!("{}.max", name), values.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+                }
+            }
+            DataPayload::KeyValue(values) => {
+                for (key, value) in values.iter() {
+                    if let Some(number) = value.as_f64() {
+                        add(&export.plugin_id, format!("{}.{}", table, normalise(key)), number);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    metrics.into_values().collect()
+}
+
+/// Path of the trend database: `--trend-db`, then `database` in `[trends]`, then the platform data directory
+pub fn database_path(args: &crate::cli::Args, config: &crate::config::ConfigManager) -> anyhow::Result<PathBuf> {
+    args.trend_db.as_ref().map(PathBuf::from)
+        .or_else(|| config.get_path("trends", "database"))
+        .or_else(|| dirs::data_dir().map(|dir| dir.join("gstats").join("trends.db")))
+        .ok_or_else(|| anyhow::anyhow!("No location for the trend database; set one with --trend-db"))
+}
+
+/// Key under which a repository's runs are recorded: its canonical path
+pub fn repository_key(path: &Path) -> String {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()).display().to_string()
+}
+
+#[cfg(feature = "sqlite")]
+pub use store::TrendStore;
+
+#[cfg(feature = "sqlite")]
+mod store {
+    use super::{Metric, Run, TrendPoint};
+    use anyhow::{Context, Result};
+    use rusqlite::{params, Connection, OptionalExtension};
+    use std::path::Path;
+
+    /// Trend database
+    pub struct TrendStore {
+        connection: Connection,
+    }
+
+    impl TrendStore {
+        /// Open the database at `path`, creating it and its directory when missing
+        pub fn open(path: &Path) -> Result<Self> {
+            if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            let connection = Connection::open(path)
+                .with_context(|| format!("Failed to open the trend database {}", path.display()))?;
+            connection.execute_batch(
+                "CREATE TABLE IF NOT EXISTS runs (id INTEGER PRIMARY KEY, repository TEXT NOT NULL, \
+                 recorded_at INTEGER NOT NULL, branch TEXT, commit_hash TEXT, command TEXT);\n\
+                 CREATE TABLE IF NOT EXISTS metrics (run_id INTEGER NOT NULL REFERENCES runs(id), \
+                 plugin TEXT NOT NULL, name TEXT NOT NULL, value REAL NOT NULL, PRIMARY KEY (run_id, name));\n\
+                 CREATE INDEX IF NOT EXISTS runs_by_repository ON runs (repository, recorded_at);"
+            )?;
+            Ok(Self { connection })
+        }
+
+        /// Append a run and its metrics
+        pub fn record(&mut self, run: &Run, metrics: &[Metric]) -> Result<()> {
+            let transaction = self.connection.transaction()?;
+            transaction.execute(
+                "INSERT INTO runs (repository, recorded_at, branch, commit_hash, command) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![run.repository, run.recorded_at, run.branch, run.commit, run.command],
+            )?;
+            let run_id = transaction.last_insert_rowid();
+            {
+                let mut insert = transaction.prepare("INSERT INTO metrics (run_id, plugin, name, value) VALUES (?1, ?2, ?3, ?4)")?;
+                for metric in metrics {
+                    insert.execute(params![run_id, metric.plugin, metric.name, metric.value])?;
+                }
+            }
+            transaction.commit()?;
+            Ok(())
+        }
+
+        /// A metric's values over the runs of a repository, oldest first
+        pub fn history(&self, repository: &str, metric: &str) -> Result<Vec<TrendPoint>> {
+            let mut query = self.connection.prepare(
+                "SELECT runs.recorded_at, runs.branch, runs.commit_hash, metrics.value FROM metrics \
+                 JOIN runs ON runs.id = metrics.run_id WHERE runs.repository = ?1 AND metrics.name = ?2 \
+                 ORDER BY runs.recorded_at, runs.id"
+            )?;
+            let points = query.query_map(params![repository, metric], |row| Ok(TrendPoint {
+                recorded_at: row.get(0)?,
+                branch: row.get(1)?,
+                commit: row.get(2)?,
+                value: row.get(3)?,
+            }))?;
+            Ok(points.collect::<rusqlite::Result<_>>()?)
+        }
+
+        /// Names of the metrics recorded for a repository, sorted
+        pub fn metric_names(&self, repository: &str) -> Result<Vec<String>> {
+            let mut query = self.connection.prepare(
+                "SELECT DISTINCT metrics.name FROM metrics JOIN runs ON runs.id = metrics.run_id \
+                 WHERE runs.repository = ?1 ORDER BY metrics.name"
+            )?;
+            let names = query.query_map(params![repository], |row| row.get(0))?;
+            Ok(names.collect::<rusqlite::Result<_>>()?)
+        }
+
+        /// Number of runs recorded for a repository
+        pub fn run_count(&self, repository: &str) -> Result<usize> {
+            let count: Option<i64> = self.connection
+                .query_row("SELECT COUNT(*) FROM runs WHERE repository = ?1", params![repository], |row| row.get(0))
+                .optional()?;
+            Ok(count.unwrap_or(0) as usize)
+        }
+    }
+}
+
+/// Append a run's metrics to the trend database
+#[cfg(feature = "sqlite")]
+pub fn record(path: &Path, run: &Run, exports: &[Arc<PluginDataExport>]) -> anyhow::Result<usize> {
+    let metrics = metrics(exports);
+    TrendStore::open(path)?.record(run, &metrics)?;
+    Ok(metrics.len())
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub fn record(_path: &Path, _run: &Run, _exports: &[Arc<PluginDataExport>]) -> anyhow::Result<usize> {
+    Err(unavailable())
+}
+
+/// `gstats trends <METRIC>`: a metric's values over the recorded runs of a repository
+#[cfg(feature = "sqlite")]
+pub fn show(metric: &str, repo_path: &Path, args: &crate::cli::Args, config: &crate::config::ConfigManager) -> anyhow::Result<()> {
+    use crate::display::{Indicator, TableBuilder, Trend};
+
+    let path = database_path(args, config)?;
+    if !path.exists() {
+        anyhow::bail!("No trends recorded yet: {} does not exist (record runs with --record-trends)", path.display());
+    }
+    let store = TrendStore::open(&path)?;
+    let repository = repository_key(repo_path);
+    let name = metric.split('.').map(normalise).collect::<Vec<_>>().join(".");
+    let points = store.history(&repository, &name)?;
+    if points.is_empty() {
+        if store.run_count(&repository)? == 0 {
+            anyhow::bail!("No trends recorded for {} (record runs with --record-trends)", repository);
+        }
+        let names = store.metric_names(&repository)?;
+        if names.is_empty() {
+            anyhow::bail!("No metrics recorded for {}: its recorded runs produced no results", repository);
+        }
+        let table = name.split('.').next().unwrap_or_default();
+        let related: Vec<&String> = names.iter().filter(|candidate| candidate.split('.').next() == Some(table)).collect();
+        let available = if related.is_empty() { names.iter().collect() } else { related };
+        anyhow::bail!("No metric '{}' recorded for {} (metrics: {})", name, repository,
+            available.iter().map(|name| name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({
+            "metric": name,
+            "repository": repository,
+            "runs": points,
+        }))?);
+        return Ok(());
+    }
+
+    let colour_manager = super::initialization::create_colour_manager(args, config);
+    let mut table = TableBuilder::new()
+        .headers(["Recorded", "Branch", "Commit", "Value", "Change"].map(String::from).to_vec());
+    let mut previous: Option<f64> = None;
+    for point in &points {
+        let recorded = chrono::DateTime::from_timestamp(point.recorded_at, 0)
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let commit = point.commit.as_deref().map(|commit| &commit[..commit.len().min(12)]).unwrap_or("-");
+        let (change, indicator) = match previous {
+            Some(previous) => {
+                let delta = point.value - previous;
+                let trend = Trend::from_delta(delta).map(Indicator::Trend);
+                let text = format_value(delta);
+                (trend.map_or(text.clone(), |trend| colour_manager.indicator_text(&text, trend)), trend)
+            }
+            None => (String::new(), None),
+        };
+        table = table.add_row_with_indicators(
+            vec![recorded, point.branch.clone().unwrap_or_else(|| "-".to_string()), commit.to_string(), format_value(point.value), change],
+            vec![None, None, None, None, indicator],
+        );
+        previous = Some(point.value);
+    }
+    println!("{}", colour_manager.highlight(&format!("{} ({} runs)", name, points.len())));
+    print!("{}", table.build_with_colors(&colour_manager));
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub fn show(_metric: &str, _repo_path: &Path, _args: &crate::cli::Args, _config: &crate::config::ConfigManager) -> anyhow::Result<()> {
+    Err(unavailable())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn unavailable() -> anyhow::Error {
+    anyhow::anyhow!("Trend recording is not available: gstats was built without the `sqlite` feature")
+}
+
+/// A value with up to two decimals and no trailing zeros
+#[cfg(feature = "sqlite")]
+fn format_value(value: f64) -> String {
+    let rounded = format!("{:.2}", value);
+    rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, DataExportType, DataSchema, ExportHints, Row, Value};
+    use std::collections::HashMap;
+
+    fn export(title: &str, columns: Vec<ColumnDef>, data: DataPayload) -> Arc<PluginDataExport> {
+        Arc::new(PluginDataExport {
+            plugin_id: "metrics".to_string(),
+            title: title.to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema { columns, metadata: HashMap::new() },
+            data,
+            export_hints: ExportHints::default(),
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    #[test]
+    fn test_metrics_from_rows_and_summaries() {
+        let rows = vec![
+            Row::new(vec![Value::String("src/main.rs".into()), Value::Float(90.0), Value::Integer(3)]),
+            Row::new(vec![Value::String("src/lib.rs".into()), Value::Float(30.0), Value::Null]),
+        ];
+        let hotspots = export("Hotspots", vec![
+            ColumnDef::new("File", ColumnType::String),
+            ColumnDef::new("Change Score", ColumnType::Float),
+            ColumnDef::new("Authors", ColumnType::Integer),
+        ], DataPayload::Rows(Arc::new(rows)));
+        let summary = export("Summary", Vec::new(), DataPayload::KeyValue(Arc::new(HashMap::from([
+            ("Total Commits".to_string(), Value::Integer(42)),
+            ("Branch".to_string(), Value::String("main".into())),
+        ]))));
+
+        let metrics: HashMap<String, f64> = metrics(&[hotspots, summary]).into_iter()
+            .map(|metric| (metric.name, metric.value))
+            .collect();
+        assert_eq!(metrics["hotspots.rows"], 2.0);
+        assert_eq!(metrics["hotspots.change-score.sum"], 120.0);
+        assert_eq!(metrics["hotspots.change-score.avg"], 60.0);
+        assert_eq!(metrics["hotspots.change-score.max"], 90.0);
+        assert_eq!(metrics["hotspots.authors.min"], 3.0);
+        assert_eq!(metrics["summary.total-commits"], 42.0);
+        assert!(!metrics.contains_key("hotspots.file.sum") && !metrics.contains_key("summary.branch"));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_store_records_and_queries_runs() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("nested").join("trends.db");
+        let mut store = TrendStore::open(&path).unwrap();
+        let run = |recorded_at: i64, commit: &str| Run {
+            repository: "/repo".to_string(),
+            recorded_at,
+            branch: Some("main".to_string()),
+            commit: Some(commit.to_string()),
+            command: Some("metrics".to_string()),
+        };
+        let metric = |value: f64| Metric { plugin: "metrics".to_string(), name: "hotspots.rows".to_string(), value };
+        store.record(&run(200, "bbb"), &[metric(5.0)]).unwrap();
+        store.record(&run(100, "aaa"), &[metric(3.0)]).unwrap();
+
+        let history = store.history("/repo", "hotspots.rows").unwrap();
+        let values: Vec<(Option<&str>, f64)> = history.iter().map(|point| (point.commit.as_deref(), point.value)).collect();
+        assert_eq!(values, vec![(Some("aaa"), 3.0), (Some("bbb"), 5.0)]);
+        assert_eq!(store.metric_names("/repo").unwrap(), vec!["hotspots.rows"]);
+        assert_eq!(store.run_count("/other").unwrap(), 0);
+    }
+}
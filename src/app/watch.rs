@@ -0,0 +1,103 @@
+//! Watch mode
+//!
+//! Keeps gstats resident after a scan and rescans whenever the repository's
+//! refs move: a new commit, merge, fetch or branch switch. Refs are polled
+//! rather than watched with filesystem events, which behaves the same for bare
+//! repositories, packed refs and network filesystems. Each rescan replays the
+//! history scanned before from the scan cache, so only new commits are traversed.
+
+use crate::scanner::repository::open_repository;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Time between checks of the repository's refs when no interval is given
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where HEAD and every ref of a repository point
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefState(BTreeMap<String, String>);
+
+impl RefState {
+    /// Read the current state of the repository at `path`
+    pub fn read(path: &Path) -> Result<Self> {
+        // A fresh handle each time, so packed refs are re-read
+        let repo = open_repository(path)?;
+        let mut refs = BTreeMap::new();
+        if let Ok(id) = repo.head_id() {
+            refs.insert("HEAD".to_string(), id.to_string());
+        }
+        for reference in repo.references()?.all()? {
+            let reference = reference.map_err(|e| anyhow::anyhow!("Failed to read refs: {}", e))?;
+            if let Some(id) = reference.target().try_id() {
+                refs.insert(reference.name().as_bstr().to_string(), id.to_string());
+            }
+        }
+        Ok(Self(refs))
+    }
+}
+
+/// Polls a repository until its refs change
+pub struct RefWatcher {
+    path: PathBuf,
+    interval: Duration,
+    state: RefState,
+}
+
+impl RefWatcher {
+    /// Start watching from the repository's current state
+    pub fn new(path: &Path, interval: Duration) -> Result<Self> {
+        let state = RefState::read(path)?;
+        Ok(Self { path: path.to_path_buf(), interval, state })
+    }
+
+    /// Check the refs once, returning whether they moved since the last change seen
+    ///
+    /// A repository that cannot be read (e.g. mid-rewrite of packed refs) counts as unchanged.
+    pub fn poll(&mut self) -> bool {
+        match RefState::read(&self.path) {
+            Ok(state) if state != self.state => {
+                self.state = state;
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                log::debug!("Failed to read refs of {}: {}", self.path.display(), e);
+                false
+            }
+        }
+    }
+
+    /// Block until the refs change
+    pub fn wait_for_change(&mut self) {
+        while !self.poll() {
+            std::thread::sleep(self.interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestRepo;
+
+    #[test]
+    fn test_watcher_sees_new_commits() {
+        let repo = TestRepo::builder()
+            .file("README.md", "hello\n")
+            .commit("Initial commit")
+            .build()
+            .unwrap();
+        let mut watcher = RefWatcher::new(repo.path(), Duration::from_millis(10)).unwrap();
+        assert!(!watcher.poll());
+
+        repo.git(&["commit", "-q", "--allow-empty", "-m", "Second commit"], &[]).unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        repo.git(&["branch", "feature"], &[]).unwrap();
+        watcher.wait_for_change();
+        assert!(!watcher.poll());
+    }
+}
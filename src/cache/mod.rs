@@ -0,0 +1,305 @@
+//! Incremental Scan Cache
+//!
+//! Persistent cache of per-commit scanner output, keyed by repository path and
+//! query. Each entry records the tip (HEAD commit) it was built from, so a later
+//! scan only needs to traverse commits added since that tip and can replay the
+//! cached messages for everything older.
+//!
+//! The date window is not part of the key: an entry holds every commit back to the
+//! time it covers, and the scanner applies the window as it replays them, so a
+//! relative `--since` keeps hitting the same entry from one day to the next. Only
+//! the most recently written entries of each repository are kept.
+
+use crate::scanner::messages::MessageData;
+use crate::scanner::query::QueryParams;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use log::debug;
+
+/// On-disk format version; bump when the entry layout or message types change
+const CACHE_FORMAT_VERSION: u32 = 7;
+
+/// Entries kept per repository in a cache directory; older ones are removed as new ones are stored
+pub const MAX_ENTRIES_PER_REPOSITORY: usize = 8;
+
+/// Scan cache errors
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("Cache I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Cache serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+}
+
+/// Scanner output for a single commit, in emission order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedCommit {
+    /// Full commit hash
+    pub hash: String,
+    /// Commit and file change messages produced for this commit
+    pub messages: Vec<MessageData>,
+}
+
+/// Cached scan results for one repository and query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanCacheEntry {
+    /// Format version the entry was written with
+    pub version: u32,
+    /// Repository the entry belongs to
+    pub repository: PathBuf,
+    /// Commit the cached history was scanned from
+    pub tip: String,
+    /// Commit time (seconds) the cached history reaches back to, or `None` for all history
+    pub since: Option<i64>,
+    /// Cached commits, newest first
+    pub commits: Vec<CachedCommit>,
+}
+
+impl ScanCacheEntry {
+    /// Create a new entry for the given repository and tip, covering all history
+    pub fn new(repository: PathBuf, tip: String, commits: Vec<CachedCommit>) -> Self {
+        Self {
+            version: CACHE_FORMAT_VERSION,
+            repository,
+            tip,
+            since: None,
+            commits,
+        }
+    }
+
+    /// Limit the entry to history from a commit time (seconds) onwards
+    pub fn with_since(mut self, since: Option<i64>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Check whether the cached history reaches back to a window's start
+    pub fn covers(&self, start: Option<i64>) -> bool {
+        match (self.since, start) {
+            (None, _) => true,
+            (Some(since), Some(start)) => since <= start,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Persistent store for scan cache entries
+#[derive(Debug, Clone)]
+pub struct ScanCache {
+    directory: PathBuf,
+}
+
+impl ScanCache {
+    /// Create a cache rooted at the given directory
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Default cache directory (platform cache dir, e.g. ~/.cache/gstats/scans)
+    pub fn default_directory() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("gstats").join("scans"))
+    }
+
+    /// Cache stored in a named subdirectory, for scans whose output differs by option
+    pub fn scoped(&self, name: &str) -> Self {
+        Self::new(self.directory.join(name))
+    }
+
+    /// Path of the entry file for a repository and query
+    ///
+    /// The query's date range is left out, so every window over the same filters shares an entry.
+    pub fn entry_path(&self, repository: &Path, query_params: &QueryParams) -> PathBuf {
+        let query = QueryParams { date_range: None, ..query_params.clone() };
+        let query = serde_json::to_string(&query).unwrap_or_default();
+        let key = fnv1a_64(format!("{}\0{}", repository.display(), query).as_bytes());
+        self.directory.join(format!("{}-{key:016x}.bin", repository_prefix(repository)))
+    }
+
+    /// Load the entry for a repository and query, if present and readable
+    ///
+    /// Missing, corrupt or outdated entries are treated as a cache miss.
+    pub fn load(&self, repository: &Path, query_params: &QueryParams) -> Option<ScanCacheEntry> {
+        let path = self.entry_path(repository, query_params);
+        let bytes = std::fs::read(&path).ok()?;
+
+        match bincode::deserialize::<ScanCacheEntry>(&bytes) {
+            Ok(entry) if entry.version == CACHE_FORMAT_VERSION && entry.repository == repository => Some(entry),
+            Ok(_) => {
+                debug!("Ignoring outdated scan cache entry {}", path.display());
+                None
+            }
+            Err(e) => {
+                debug!("Ignoring unreadable scan cache entry {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Store an entry, replacing any previous entry for the same repository and query
+    pub fn store(&self, query_params: &QueryParams, entry: &ScanCacheEntry) -> Result<(), CacheError> {
+        std::fs::create_dir_all(&self.directory)?;
+
+        let path = self.entry_path(&entry.repository, query_params);
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, bincode::serialize(entry)?)?;
+        std::fs::rename(&temp_path, &path)?;
+
+        debug!("Stored scan cache entry {} ({} commits)", path.display(), entry.commits.len());
+        self.evict(&entry.repository, MAX_ENTRIES_PER_REPOSITORY)?;
+        Ok(())
+    }
+
+    /// Remove all but the `keep` most recently stored entries of a repository
+    fn evict(&self, repository: &Path, keep: usize) -> Result<(), CacheError> {
+        let prefix = format!("{}-", repository_prefix(repository));
+        let mut entries = Vec::new();
+        for file in std::fs::read_dir(&self.directory)? {
+            let file = file?;
+            let name = file.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with(&prefix) && name.ends_with(".bin") {
+                let modified = file.metadata()?.modified()?;
+                entries.push((modified, file.path()));
+            }
+        }
+        entries.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+        for (_, path) in entries.into_iter().skip(keep) {
+            debug!("Evicting scan cache entry {}", path.display());
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+/// File name prefix shared by the entries of one repository
+fn repository_prefix(repository: &Path) -> String {
+    format!("{:016x}", fnv1a_64(repository.display().to_string().as_bytes()))
+}
+
+/// Stable 64-bit FNV-1a hash used for cache file names
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_entry(repository: &Path) -> ScanCacheEntry {
+        ScanCacheEntry::new(
+            repository.to_path_buf(),
+            "abc123".to_string(),
+            vec![CachedCommit {
+                hash: "abc123".to_string(),
+                messages: vec![MessageData::CommitInfo {
+                    hash: "abc123".to_string(),
+                    author: "Test Author".to_string(),
+                    message: "Initial commit".to_string(),
+                    timestamp: 1_700_000_000,
+                    committer: "Test Author".to_string(),
+                    author_timestamp: 1_700_000_000,
+                    parents: vec![],
+                    changed_files: vec![],
+                }],
+            }],
+        )
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScanCache::new(temp_dir.path().to_path_buf());
+        let repository = PathBuf::from("/tmp/repo");
+        let query = QueryParams::default();
+
+        let entry = sample_entry(&repository);
+        cache.store(&query, &entry).unwrap();
+
+        assert_eq!(cache.load(&repository, &query), Some(entry));
+    }
+
+    #[test]
+    fn test_entries_keyed_by_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScanCache::new(temp_dir.path().to_path_buf());
+        let repository = PathBuf::from("/tmp/repo");
+
+        cache.store(&QueryParams::default(), &sample_entry(&repository)).unwrap();
+
+        let branch_query = QueryParams {
+            branch: Some("develop".to_string()),
+            ..Default::default()
+        };
+        assert!(cache.load(&repository, &branch_query).is_none());
+        assert!(cache.load(Path::new("/tmp/other"), &QueryParams::default()).is_none());
+    }
+
+    #[test]
+    fn test_entries_shared_across_date_windows() {
+        use crate::scanner::query::DateRange;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScanCache::new(temp_dir.path().to_path_buf());
+        let repository = PathBuf::from("/tmp/repo");
+        let window = |days: u64| QueryParams {
+            date_range: Some(DateRange::from(UNIX_EPOCH + Duration::from_secs(days * 86400))),
+            ..Default::default()
+        };
+
+        let entry = sample_entry(&repository).with_since(Some(100 * 86400));
+        cache.store(&window(100), &entry).unwrap();
+
+        let loaded = cache.load(&repository, &window(101)).unwrap();
+        assert!(loaded.covers(Some(101 * 86400)));
+        assert!(!loaded.covers(Some(99 * 86400)));
+        assert!(!loaded.covers(None));
+        assert!(sample_entry(&repository).covers(None));
+    }
+
+    #[test]
+    fn test_oldest_entries_of_a_repository_are_evicted() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScanCache::new(temp_dir.path().to_path_buf());
+        let repository = PathBuf::from("/tmp/repo");
+        let other = PathBuf::from("/tmp/other");
+        let branch = |index: usize| QueryParams {
+            branch: Some(format!("branch-{index}")),
+            ..Default::default()
+        };
+
+        cache.store(&branch(0), &sample_entry(&other)).unwrap();
+        for index in 0..=MAX_ENTRIES_PER_REPOSITORY {
+            cache.store(&branch(index), &sample_entry(&repository)).unwrap();
+            // Entries are ordered by modification time, which may be coarse
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let count = std::fs::read_dir(temp_dir.path()).unwrap().count();
+        assert_eq!(count, MAX_ENTRIES_PER_REPOSITORY + 1);
+        assert!(cache.load(&repository, &branch(0)).is_none());
+        assert!(cache.load(&repository, &branch(MAX_ENTRIES_PER_REPOSITORY)).is_some());
+        assert!(cache.load(&other, &branch(0)).is_some());
+    }
+
+    #[test]
+    fn test_corrupt_or_outdated_entries_are_misses() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScanCache::new(temp_dir.path().to_path_buf());
+        let repository = PathBuf::from("/tmp/repo");
+        let query = QueryParams::default();
+
+        std::fs::write(cache.entry_path(&repository, &query), b"not a cache entry").unwrap();
+        assert!(cache.load(&repository, &query).is_none());
+
+        let mut outdated = sample_entry(&repository);
+        outdated.version = CACHE_FORMAT_VERSION + 1;
+        cache.store(&query, &outdated).unwrap();
+        assert!(cache.load(&repository, &query).is_none());
+    }
+}
@@ -1,9 +1,10 @@
-use clap::{Parser, ArgAction};
+use clap::{Parser, ArgAction, ArgGroup};
 use anyhow::Result;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use log::debug;
 
-use super::enhanced_parser::EnhancedParser;
+use super::enhanced_parser::{parse_comma_separated, EnhancedParser};
 use super::help_formatter::HelpFormatter;
 
 /// Git Repository Analytics Tool
@@ -35,12 +36,23 @@ For command-specific help: gstats <command> --help
 For plugin discovery: gstats --plugins
 For export formats: gstats --list-formats")]
 #[command(version)]
+#[command(group(ArgGroup::new("repository_list").args(["repos", "repos_file"]).multiple(true)))]
 pub struct Args {
     /// Path to git repository (defaults to current directory)
     /// Examples: -r /path/to/repo, --repo ~/project
     #[arg(short = 'r', long = "repo", alias = "repository", value_name = "PATH", help = "Repository path (default: current directory)")]
     pub repository: Option<String>,
     
+    /// Git directory to scan without a checkout (bare repositories, server-side mirrors)
+    /// Examples: --git-dir /srv/git/project.git, --git-dir ~/project/.git
+    #[arg(long = "git-dir", value_name = "PATH", conflicts_with = "repository", help = "Git directory to scan (bare repository or .git)")]
+    pub git_dir: Option<String>,
+    
+    /// Analyse a patch series (git format-patch output) without a repository
+    /// Examples: --patch fixes.mbox, --patch outgoing/ (also: gstats patch <FILE|DIR> <command>)
+    #[arg(long = "patch", value_name = "FILE|DIR", conflicts_with_all = ["repository", "git_dir", "compare"], help = "Analyse an mbox or directory of patches instead of a repository")]
+    pub patch: Option<String>,
+    
     /// Enable verbose output with detailed logging
     #[arg(short, long, help = "Verbose output (debug level logging)")]
     pub verbose: bool,
@@ -65,6 +77,18 @@ pub struct Args {
     #[arg(long, value_name = "LEVEL")]
     pub log_file_level: Option<String>,
     
+    /// Per-module log levels, e.g. scanner=debug,plugin=warn
+    #[arg(long, value_name = "FILTER")]
+    pub log_filter: Option<String>,
+    
+    /// Rotate the log file when it would grow past this size (e.g. 10MB)
+    #[arg(long, value_name = "SIZE")]
+    pub log_max_size: Option<String>,
+    
+    /// Number of rotated log files to keep [default: 5]
+    #[arg(long, value_name = "COUNT")]
+    pub log_keep: Option<usize>,
+    
     /// Force colored output (overrides TTY detection and NO_COLOR)
     #[arg(long = "color", help = "Force colored output even when redirected")]
     pub color: bool,
@@ -73,6 +97,15 @@ pub struct Args {
     #[arg(long = "no-color", help = "Disable colored output")]
     pub no_color: bool,
     
+    /// Mark changes and severities with symbols and labels (▲/▼, [HIGH]/[LOW]) as well as colour
+    #[arg(long = "accessible", help = "Mark changes and severities with symbols and labels, not colour alone")]
+    pub accessible: bool,
+    
+    /// Render commit hashes, file paths and issue references in console tables as terminal links
+    /// Examples: --hyperlinks always, --hyperlinks never
+    #[arg(long = "hyperlinks", value_name = "WHEN", value_parser = ["auto", "always", "never"], help = "Link commits, files and issues in console tables (auto, always, never) [default: auto]")]
+    pub hyperlinks: Option<String>,
+    
     
     /// Configuration file path
     #[arg(long, value_name = "FILE")]
@@ -86,10 +119,59 @@ pub struct Args {
     #[arg(short = 'b', long = "branch", value_name = "BRANCH", help = "Git branch to scan (overrides automatic detection)")]
     pub branch: Option<String>,
     
+    /// Scan the history of every local and remote-tracking branch, not just one
+    #[arg(long = "all-branches", conflicts_with = "compare", help = "Scan the union of all branches; --branch sets the branch listed first")]
+    pub all_branches: bool,
+    
+    /// Fetch the missing history of a shallow clone before scanning
+    #[arg(long = "unshallow", help = "Fetch the full history of a shallow clone before scanning")]
+    pub unshallow: bool,
+    
+    /// Analyse the changes staged for commit instead of the history
+    /// Examples: --staged --fail-on "hotspots.cyclomatic.max>20" metrics:hotspots
+    #[arg(long = "staged", conflicts_with_all = ["working_tree", "patch", "compare", "watch", "all_branches"], help = "Analyse the staged changes as one pending commit (for pre-commit hooks)")]
+    pub staged: bool,
+    
+    /// Analyse every uncommitted change in the working tree instead of the history
+    #[arg(long = "working-tree", conflicts_with_all = ["patch", "compare", "watch", "all_branches"], help = "Analyse all uncommitted changes, including untracked files, as one pending commit")]
+    pub working_tree: bool,
+    
     /// Display the selected branch and exit (no scanning)
     #[arg(long = "show-branch", help = "Show which branch would be scanned and exit")]
     pub show_branch: bool,
-    
+
+    /// Compare statistics between two refs instead of running a plugin
+    /// BASE compares against the scanned branch; BASE..HEAD names both refs
+    /// Examples: --compare release/1.0, --compare v1.0..v2.0
+    #[arg(long = "compare", value_name = "BASE[..HEAD]", help = "Scan two refs and report the differences in commits, authors and metrics")]
+    pub compare: Option<String>,
+
+    /// Write the comparison to a file, with the format taken from its extension
+    #[arg(long = "compare-output", value_name = "FILE", requires = "compare", help = "Write comparison tables to FILE (format from extension)")]
+    pub compare_output: Option<PathBuf>,
+
+    /// Scan several refs and report each metric side by side, one column per ref
+    /// Examples: --matrix-refs main,release/1.x,develop
+    #[arg(long = "matrix-refs", value_name = "REFS", value_delimiter = ',', num_args = 1..,
+          conflicts_with_all = ["compare", "repos", "repos_file", "patch", "watch", "all_branches", "staged", "working_tree", "fail_on", "record_trends", "tui"],
+          help = "Scan each ref and report commits, authors and metrics with one column per ref (comma-separated)")]
+    pub matrix_refs: Vec<String>,
+
+    /// Write the ref matrix to a file, with the format taken from its extension
+    #[arg(long = "matrix-output", value_name = "FILE", requires = "matrix_refs", help = "Write ref matrix tables to FILE (format from extension)")]
+    pub matrix_output: Option<PathBuf>,
+
+    /// Sample the history and report technical debt and complexity at each sample
+    /// Examples: --debt-trend 100, --debt-trend monthly, --debt-trend tags
+    #[arg(long = "debt-trend", value_name = "INTERVAL",
+          conflicts_with_all = ["compare", "matrix_refs", "repos", "repos_file", "patch", "watch", "all_branches", "staged", "working_tree", "fail_on", "record_trends", "tui"],
+          help = "Report technical debt over time, sampled every N commits, 'monthly' or at 'tags'")]
+    pub debt_trend: Option<String>,
+
+    /// Write the debt trend to a file, with the format taken from its extension
+    #[arg(long = "debt-trend-output", value_name = "FILE", requires = "debt_trend", help = "Write the debt trend table to FILE (format from extension)")]
+    pub debt_trend_output: Option<PathBuf>,
+
     /// Specify fallback branches in priority order (comma-separated)
     /// Example: --fallback-branch "main,master,develop"
     #[arg(long = "fallback-branch", value_name = "LIST", help = "Comma-separated fallback branch list")]
@@ -112,14 +194,14 @@ pub struct Args {
     #[arg(short = 'U', long = "until", value_name = "DATE", help = "End date filter (ISO 8601 or relative)")]
     pub until: Option<String>,
     
-    /// Only analyze specific directories
-    /// Examples: --include-path src/ --include-path tests/
-    #[arg(short = 'I', long = "include-path", value_name = "PATH", action = ArgAction::Append, help = "Include specific paths (supports comma-separated)")]
+    /// Only analyze paths matching these .gitignore-style globs
+    /// Examples: --path src/ --path "crates/*/src/**/*.rs"
+    #[arg(short = 'I', long = "include-path", visible_alias = "path", value_name = "GLOB", action = ArgAction::Append, help = "Include paths matching a .gitignore-style glob (supports comma-separated)")]
     pub include_path: Vec<String>,
     
-    /// Skip specific directories from analysis
-    /// Examples: --exclude-path target/ --exclude-path node_modules/
-    #[arg(short = 'X', long = "exclude-path", value_name = "PATH", action = ArgAction::Append, help = "Exclude specific paths (supports comma-separated)")]
+    /// Skip paths matching these .gitignore-style globs
+    /// Examples: --exclude-path target/ --exclude-path "**/*.snap"
+    #[arg(short = 'X', long = "exclude-path", value_name = "GLOB", action = ArgAction::Append, help = "Exclude paths matching a .gitignore-style glob (supports comma-separated)")]
     pub exclude_path: Vec<String>,
     
     /// Only analyze files matching these patterns
@@ -132,21 +214,104 @@ pub struct Args {
     #[arg(short = 'N', long = "exclude-file", value_name = "PATTERN", action = ArgAction::Append, help = "Exclude file patterns (supports comma-separated)")]
     pub exclude_file: Vec<String>,
     
-    /// Only show commits from specific authors
-    /// Examples: --author "john@example.com" --author "Jane Doe"
-    #[arg(short = 'A', long = "author", value_name = "AUTHOR", action = ArgAction::Append, help = "Include specific authors (supports comma-separated)")]
+    /// Only show commits from specific authors (substring of name or email, or /regex/)
+    /// Examples: --author "john@example.com" --author "Jane Doe" --author "/^(alice|bob)@/"
+    #[arg(short = 'A', long = "author", value_name = "AUTHOR", action = ArgAction::Append, help = "Include authors whose name or email contains AUTHOR or matches /REGEX/ (supports comma-separated)")]
     pub author: Vec<String>,
     
-    /// Hide commits from specific authors
-    /// Examples: --exclude-author "bot@automated.com"
-    #[arg(short = 'E', long = "exclude-author", value_name = "AUTHOR", action = ArgAction::Append, help = "Exclude specific authors (supports comma-separated)")]
+    /// Hide commits from specific authors (substring of name or email, or /regex/)
+    /// Examples: --exclude-author "bot@automated.com" --exclude-author "/\[bot\]$/"
+    #[arg(short = 'E', long = "exclude-author", value_name = "AUTHOR", action = ArgAction::Append, help = "Exclude authors whose name or email contains AUTHOR or matches /REGEX/ (supports comma-separated)")]
     pub exclude_author: Vec<String>,
     
+    /// Exclude the build output, dependencies and lockfiles of an ecosystem, and weight down its generated files
+    /// Examples: --preset rust --preset node (rust, node, python)
+    #[arg(long = "preset", value_name = "NAME", action = ArgAction::Append, help = "Apply exclude presets: rust, node, python (supports comma-separated)")]
+    pub preset: Vec<String>,
+    
     /// Limit the number of commits to scan from repository
     /// Example: --limit 100
     #[arg(long = "limit", value_name = "N", help = "Maximum number of commits to scan from repository")]
     pub scan_limit: Option<usize>,
     
+    /// Exit with a non-zero status when the filters leave nothing to report
+    /// Intended for scheduled jobs, where an empty report usually means a filter typo
+    #[arg(long = "expect-data", help = "Fail if no commits match the filters (exit code 3)")]
+    pub expect_data: bool,
+    
+    /// Print the memory each plugin retains once the scan is done
+    /// Covers the plugin's unacknowledged queue messages and its analysis state
+    #[arg(long = "memory-report", help = "Print a per-plugin memory table at the end of the run")]
+    pub memory_report: bool,
+    
+    /// Print each queue consumer's lag, throughput, batching and backoff once the scan is done
+    /// Helps tune a plugin's preferred_batch_size
+    #[arg(long = "queue-stats", help = "Print per-consumer queue lag, throughput and backoff at the end of the run")]
+    pub queue_stats: bool,
+    
+    /// Print how long each pipeline stage took at the end of the run
+    #[arg(long = "profile", help = "Print per-stage timings (traversal, queue, plugins, exports) at the end of the run")]
+    pub profile: bool,
+    
+    /// Also write every timed call as a Chrome trace, for flame charts in Perfetto or speedscope
+    #[arg(long = "profile-trace", value_name = "FILE", help = "Write a Chrome trace of the profiled stages to FILE (implies --profile)")]
+    pub profile_trace: Option<PathBuf>,
+    
+    /// Make reruns over the same history produce byte-identical exports
+    /// Uses a scan ID derived from the input and the latest commit date in place of the clock
+    #[arg(long = "reproducible", conflicts_with_all = ["watch", "staged", "working_tree"], help = "Use deterministic scan IDs and the latest commit date instead of the clock")]
+    pub reproducible: bool,
+    
+    /// Exit with a non-zero status when a condition holds for the plugin results
+    /// Examples: --fail-on "hotspots.score>80", --fail-on hotspots.risk>=high
+    #[arg(long = "fail-on", value_name = "EXPR", action = ArgAction::Append, conflicts_with_all = ["repos", "repos_file", "compare"], help = "Fail if a condition on the results holds, e.g. hotspots.score>80 (exit code 4)")]
+    pub fail_on: Vec<String>,
+    
+    /// Append the run's key metrics to the trend database, for `gstats trends <METRIC>`
+    /// Requires a build with the `sqlite` feature
+    #[arg(long = "record-trends", conflicts_with_all = ["repos", "repos_file", "compare"], help = "Append this run's metrics to the trend database (see gstats trends <METRIC>)")]
+    pub record_trends: bool,
+    
+    /// Trend database to record to and query
+    /// Examples: --trend-db .gstats/trends.db
+    #[arg(long = "trend-db", value_name = "FILE", help = "Trend database file [default: trends.db in the platform data directory]")]
+    pub trend_db: Option<String>,
+    
+    /// Show scan progress, queue statistics and plugin tables in an interactive dashboard
+    /// Requires a terminal and a build with the `tui` feature
+    #[arg(long = "tui", conflicts_with_all = ["repos", "repos_file", "compare", "watch"], help = "Show progress and results in an interactive terminal dashboard")]
+    pub tui: bool,
+    
+    /// Export OpenTelemetry trace spans to this OTLP/HTTP collector
+    /// Requires a build with the `otel` feature; overrides OTEL_EXPORTER_OTLP_ENDPOINT
+    #[arg(long, value_name = "URL", help = "Export trace spans to an OTLP/HTTP collector (e.g. http://localhost:4318)")]
+    pub otlp_endpoint: Option<String>,
+    
+    /// Stay resident and rescan whenever new commits land (Ctrl-C to stop)
+    /// Each rescan publishes fresh plugin results, so exported reports are regenerated
+    #[arg(long = "watch", conflicts_with_all = ["patch", "compare"], help = "Rescan whenever new commits land, regenerating reports")]
+    pub watch: bool,
+    
+    /// Seconds between checks for new commits in watch mode
+    /// Example: --watch --watch-interval 60
+    #[arg(long = "watch-interval", value_name = "SECONDS", requires = "watch", help = "Seconds between checks for new commits (default: 5)")]
+    pub watch_interval: Option<u64>,
+    
+    /// Scan several repositories and report each one alongside totals across all of them
+    /// Examples: --repos ~/src/billing,~/src/auth, --repos billing --repos auth
+    #[arg(long = "repos", value_name = "PATH", action = ArgAction::Append, conflicts_with_all = ["repository", "git_dir", "patch", "compare", "watch"], help = "Scan several repositories (supports comma-separated)")]
+    pub repos: Vec<String>,
+    
+    /// File listing repositories to scan, one path per line (# starts a comment)
+    /// Relative paths are taken relative to the file; combines with --repos
+    /// Example: --repos-file services.txt
+    #[arg(long = "repos-file", value_name = "FILE", conflicts_with_all = ["repository", "git_dir", "patch", "compare", "watch"], help = "Scan the repositories listed in FILE, one per line")]
+    pub repos_file: Option<PathBuf>,
+    
+    /// Write the multi-repository report to a file, with the format taken from its extension
+    #[arg(long = "repos-output", value_name = "FILE", requires = "repository_list", help = "Write multi-repository tables to FILE (format from extension)")]
+    pub repos_output: Option<PathBuf>,
+    
     // ============ SCANNER CONFIGURATION ============
     
     /// Enable performance mode (optimized for speed over memory usage)
@@ -165,6 +330,26 @@ pub struct Args {
     #[arg(long = "queue-size", value_name = "N")]
     pub queue_size: Option<usize>,
     
+    /// What the queue does at its limits: block, drop-oldest or spill-to-disk
+    #[arg(long = "backpressure", value_name = "POLICY", value_parser = crate::queue::backpressure::POLICIES)]
+    pub backpressure: Option<String>,
+    
+    /// Lines or tokens in the smallest block reported as duplicated code
+    #[arg(long = "duplicate-min-block", value_name = "N")]
+    pub duplicate_min_block: Option<usize>,
+    
+    /// Unit of content duplicate detection hashes: lines, or tokens to ignore layout
+    #[arg(long = "duplicate-hashing", value_name = "MODE", value_parser = crate::scanner::fingerprints::FingerprintMode::NAMES)]
+    pub duplicate_hashing: Option<String>,
+    
+    /// Disable the incremental scan cache (always traverse the full history)
+    #[arg(long = "no-cache")]
+    pub no_cache: bool,
+    
+    /// Worker threads for the shared async runtime (default: one per CPU core)
+    #[arg(long = "worker-threads", value_name = "N")]
+    pub worker_threads: Option<usize>,
+    
     /// Plugin command to execute
     /// Examples: commits, metrics, export, export:csv
     #[arg(value_name = "COMMAND", help = "Plugin command to execute (e.g., commits, metrics, export)")]
@@ -176,23 +361,28 @@ pub struct Args {
     
     // ============ PLUGIN DISCOVERY & HELP ============
     
-    /// List all available plugins
-    #[arg(long = "list-plugins", help = "List all available plugins")]
+    /// List all available plugins (hidden alias for `gstats plugins list`)
+    #[arg(long = "list-plugins", hide = true, help = "List all available plugins")]
     pub list_plugins: bool,
     
     /// Show plugins with their functions and descriptions
-    #[arg(long = "plugins", help = "Show all plugins with functions and descriptions")]
+    #[arg(long = "plugins", hide = true, help = "Show all plugins with functions and descriptions")]
     pub show_plugins: bool,
     
     /// Display comprehensive plugin help and command mappings
-    #[arg(long = "plugins-help", help = "Show detailed plugin functions and command mappings")]
+    #[arg(long = "plugins-help", hide = true, help = "Show detailed plugin functions and command mappings")]
     pub plugins_help: bool,
     
-    /// Get detailed information about a specific plugin
+    /// Get detailed information about a specific plugin (hidden alias for `gstats plugins info`)
     /// Example: --plugin-info commits
-    #[arg(long = "plugin-info", value_name = "PLUGIN", help = "Show detailed information about specific plugin")]
+    #[arg(long = "plugin-info", hide = true, value_name = "PLUGIN", help = "Show detailed information about specific plugin")]
     pub plugin_info: Option<String>,
     
+    /// Print the output of management commands as JSON, for scripts
+    /// Applies to --plugins, --plugins-help, --list-plugins, --plugin-info, --list-formats and --show-branch
+    #[arg(long = "json", help = "Print management command output as JSON")]
+    pub json: bool,
+    
     
     
     /// Override default plugin discovery directory
@@ -220,8 +410,13 @@ pub struct Args {
     #[arg(long = "plugin-directory", value_name = "DIR", help = "Plugin directory path for external plugin discovery")]
     pub plugin_directory: Option<String>,
     
-    /// List all supported export formats with their extensions
-    #[arg(long = "list-formats", help = "List all supported export formats and their file extensions")]
+    /// Deadline in seconds for each consumer plugin call; 0 disables it
+    /// Example: --plugin-timeout 300
+    #[arg(long = "plugin-timeout", value_name = "SECONDS", help = "Cancel consumer plugin calls that run longer than this many seconds (0 disables)")]
+    pub plugin_timeout: Option<u64>,
+    
+    /// List all supported export formats with their extensions (hidden alias for `gstats plugins formats`)
+    #[arg(long = "list-formats", hide = true, help = "List all supported export formats and their file extensions")]
     pub list_formats: bool,
     
     /// Export complete configuration to TOML file
@@ -243,6 +438,8 @@ impl Args {
         self.exclude_file = EnhancedParser::parse_file_patterns(self.exclude_file);
         self.author = EnhancedParser::parse_authors(self.author);
         self.exclude_author = EnhancedParser::parse_authors(self.exclude_author);
+        self.preset = parse_comma_separated(self.preset);
+        self.repos = parse_comma_separated(self.repos);
         
         // Parse plugin load/exclude lists (comma-separated)
         if let Some(load_list) = self.plugin_load.take() {
@@ -302,6 +499,38 @@ pub fn validate_args(args: &Args) -> Result<()> {
         ));
     }
     
+    if let Some(ref filter) = args.log_filter {
+        crate::logging::LogFilter::parse(filter)?;
+    }
+    
+    if let Some(ref size) = args.log_max_size {
+        crate::cli::memory_parser::parse_memory_size(size)
+            .map_err(|e| anyhow::anyhow!("Invalid --log-max-size: {}", e))?;
+    }
+    
+    if args.log_keep.is_some() && args.log_max_size.is_none() {
+        return Err(anyhow::anyhow!(
+            "--log-keep requires --log-max-size to be specified"
+        ));
+    }
+    
+    if args.worker_threads == Some(0) {
+        return Err(anyhow::anyhow!("--worker-threads must be at least 1"));
+    }
+    
+    if args.tui {
+        if !cfg!(feature = "tui") {
+            return Err(anyhow::anyhow!("--tui is not available: gstats was built without the `tui` feature"));
+        }
+        if !std::io::stdout().is_terminal() {
+            return Err(anyhow::anyhow!("--tui requires output to a terminal"));
+        }
+    }
+    
+    if args.otlp_endpoint.is_some() && !cfg!(feature = "otel") {
+        return Err(anyhow::anyhow!("--otlp-endpoint is not available: gstats was built without the `otel` feature"));
+    }
+    
     debug!("CLI arguments validated successfully");
     Ok(())
 }
@@ -327,8 +556,13 @@ mod tests {
             log_format: "text".to_string(),
             log_file: None,
             log_file_level: None,
+            log_filter: None,
+            log_max_size: None,
+            log_keep: None,
             color: false,
             no_color: false,
+            accessible: false,
+            hyperlinks: None,
             config_file: None,
             since: None,
             until: None,
@@ -339,16 +573,45 @@ mod tests {
             author: Vec::new(),
             exclude_author: Vec::new(),
             scan_limit: None,
+            preset: Vec::new(),
+            expect_data: false,
+            memory_report: false,
+            queue_stats: false,
+            profile: false,
+            profile_trace: None,
+            reproducible: false,
+            fail_on: Vec::new(),
+            matrix_refs: Vec::new(),
+            matrix_output: None,
+            debt_trend: None,
+            debt_trend_output: None,
+            record_trends: false,
+            trend_db: None,
+            tui: false,
+            otlp_endpoint: None,
+            watch: false,
+            watch_interval: None,
+            repos: Vec::new(),
+            repos_file: None,
+            repos_output: None,
+            git_dir: None,
+            patch: None,
             performance_mode: false,
             no_performance_mode: false,
             max_memory: None,
             queue_size: None,
+            backpressure: None,
+            duplicate_min_block: None,
+            duplicate_hashing: None,
+            no_cache: false,
+            worker_threads: None,
             command: None,
             plugin_args: Vec::new(),
             list_plugins: false,
             show_plugins: false,
             plugins_help: false,
             plugin_info: None,
+            json: false,
             plugin_dir: None,
             plugins_dir: Vec::new(),
             plugin_load: None,
@@ -356,8 +619,15 @@ mod tests {
             list_formats: false,
             export_config: None,
             plugin_directory: None,
+            plugin_timeout: None,
             branch: None,
+            all_branches: false,
+            unshallow: false,
+            staged: false,
+            working_tree: false,
             show_branch: false,
+            compare: None,
+            compare_output: None,
             fallback_branch: None,
             remote: None,
             help: false,
@@ -420,6 +690,21 @@ mod tests {
         assert!(validate_args(&args).is_err());
     }
 
+    #[test]
+    fn test_validate_args_log_filter_and_rotation() {
+        let args = |filter: &str, max_size: Option<&str>| Args {
+            log_filter: Some(filter.to_string()),
+            log_file: Some(PathBuf::from("gstats.log")),
+            log_max_size: max_size.map(str::to_string),
+            log_keep: Some(3),
+            ..create_test_args()
+        };
+        assert!(validate_args(&args("scanner=debug,plugin=warn", Some("10MB"))).is_ok());
+        assert!(validate_args(&args("scanner", Some("10MB"))).is_err());
+        assert!(validate_args(&args("scanner=debug", Some("big"))).is_err());
+        assert!(validate_args(&args("scanner=debug", None)).is_err());
+    }
+
     #[test]
     fn test_cli_run_with_path() {
         let args = Args {
@@ -603,6 +888,14 @@ mod tests {
         assert!(validate_args(&args).is_ok(), "Args should pass validation");
     }
 
+    #[test]
+    fn test_worker_threads_must_be_positive() {
+        let args = Args { worker_threads: Some(0), ..create_test_args() };
+        assert!(validate_args(&args).is_err());
+        let args = Args { worker_threads: Some(2), ..create_test_args() };
+        assert!(validate_args(&args).is_ok());
+    }
+
     #[test]
     fn test_plugin_directory_option() {
         // Test that plugin_directory field exists and can be set
@@ -628,6 +921,8 @@ mod tests {
         let args = Args {
             branch: Some("develop".to_string()),
             show_branch: true,
+            compare: None,
+            compare_output: None,
             fallback_branch: Some("main,master,trunk".to_string()),
             remote: Some("upstream".to_string()),
             ..create_test_args()
@@ -653,7 +948,13 @@ mod tests {
         // Branch arguments should pass basic CLI validation
         let args = Args {
             branch: Some("feature/branch-detection".to_string()),
+            all_branches: false,
+            unshallow: false,
+            staged: false,
+            working_tree: false,
             show_branch: false,
+            compare: None,
+            compare_output: None,
             fallback_branch: Some("main,master".to_string()),
             remote: Some("origin".to_string()),
             ..create_test_args()
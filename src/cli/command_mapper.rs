@@ -56,15 +56,12 @@ impl std::fmt::Display for AmbiguityReport {
 
 /// Maps commands to plugins and their functions
 pub struct CommandMapper {
-    /// Function name -> Vec<(plugin_name, is_default)>
+    /// Function name or alias -> Vec<(plugin_name, is_default)>
     function_map: HashMap<String, Vec<(String, bool)>>,
     
     /// Plugin name -> Plugin functions
     plugin_map: HashMap<String, Vec<PluginFunction>>,
     
-    /// Alias to canonical function name mapping
-    alias_map: HashMap<String, String>,
-    
     /// Smart suggestion engine for "did you mean?" functionality
     suggestion_engine: SuggestionEngine,
     
@@ -78,7 +75,6 @@ impl CommandMapper {
         Self {
             function_map: HashMap::new(),
             plugin_map: HashMap::new(),
-            alias_map: HashMap::new(),
             suggestion_engine: SuggestionEngine::new(SuggestionConfig::default()),
             contextual_help: ContextualHelp::new(),
         }
@@ -109,11 +105,8 @@ impl CommandMapper {
                 .or_insert_with(Vec::new)
                 .push((plugin_name.to_string(), func.is_default));
             
-            // Map aliases to canonical name
+            // Aliases are looked up like function names, so an alias two plugins share is ambiguous
             for alias in &func.aliases {
-                self.alias_map.insert(alias.clone(), func.name.clone());
-                
-                // Also add alias to function map
                 self.function_map
                     .entry(alias.clone())
                     .or_insert_with(Vec::new)
@@ -164,23 +157,15 @@ impl CommandMapper {
             }
             
             // Verify function exists in plugin
-            let plugin_functions = &self.plugin_map[plugin_name];
-            let canonical_name = self.alias_map.get(function_name)
-                .map(|s| s.as_str())
-                .unwrap_or(function_name);
-            
-            let has_function = plugin_functions.iter()
-                .any(|f| f.name == canonical_name || f.aliases.contains(&function_name.to_string()));
-            
-            if !has_function {
-                let available: Vec<String> = plugin_functions.iter()
+            let Some(canonical_name) = self.canonical_function(plugin_name, function_name) else {
+                let available: Vec<String> = self.plugin_map[plugin_name].iter()
                     .map(|f| f.name.clone())
                     .collect();
                 bail!(
                     "Plugin '{}' does not provide function '{}'. Available functions: {:?}",
                     plugin_name, function_name, available
                 );
-            }
+            };
             
             return Ok(CommandResolution::Explicit {
                 plugin_name: plugin_name.to_string(),
@@ -188,19 +173,16 @@ impl CommandMapper {
             });
         }
         
-        // Check if it's a function name (with possible alias resolution)
-        let canonical_name = self.alias_map.get(input)
-            .map(|s| s.as_str())
-            .unwrap_or(input);
-        
-        if let Some(providers) = self.function_map.get(canonical_name) {
-            debug!("Function '{}' found in {} plugin(s)", canonical_name, providers.len());
+        // Check if it's a function name or alias, resolved within the plugin providing it
+        if let Some(providers) = self.function_map.get(input) {
+            debug!("Function '{}' found in {} plugin(s)", input, providers.len());
             
             if providers.len() == 1 {
                 let (plugin_name, _is_default) = &providers[0];
+                let function_name = self.canonical_function(plugin_name, input).unwrap_or(input);
                 return Ok(CommandResolution::Function {
                     plugin_name: plugin_name.clone(),
-                    function_name: canonical_name.to_string(),
+                    function_name: function_name.to_string(),
                 });
             } else {
                 // Multiple providers - ambiguous
@@ -262,7 +244,14 @@ impl CommandMapper {
         bail!("{}", final_error);
     }
     
-    /// Detect all ambiguous function names
+    /// Name of a plugin's function with the given name or alias
+    fn canonical_function(&self, plugin_name: &str, name: &str) -> Option<&str> {
+        self.plugin_map.get(plugin_name)?.iter()
+            .find(|f| f.name == name || f.aliases.iter().any(|alias| alias == name))
+            .map(|f| f.name.as_str())
+    }
+    
+    /// Detect all ambiguous function names and aliases
     pub fn detect_ambiguities(&self) -> Vec<AmbiguityReport> {
         let mut reports = Vec::new();
         
@@ -384,6 +373,38 @@ mod tests {
         assert!(error.contains("analyze"));
     }
     
+    #[tokio::test]
+    async fn test_shared_alias_is_ambiguous() {
+        let mut mapper = CommandMapper::new();
+        let function = |name: &str, alias: &str| PluginFunction {
+            name: name.to_string(),
+            aliases: vec![alias.to_string()],
+            description: String::new(),
+            is_default: false,
+        };
+        mapper.register_plugin("contributors", vec![function("freshness", "stale")]);
+        mapper.register_plugin("staleness", vec![function("staleness", "stale"), function("orphans", "untested")]);
+        
+        let error = mapper.resolve_command("stale").await.unwrap_err().to_string();
+        assert!(error.contains("Ambiguous function 'stale'"), "{error}");
+        assert!(error.contains("contributors") && error.contains("staleness"), "{error}");
+        let reports = mapper.detect_ambiguities();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].function_name, "stale");
+        
+        // An alias only one plugin has resolves to that plugin's function
+        match mapper.resolve_command("untested").await.unwrap() {
+            CommandResolution::Function { plugin_name, function_name } => {
+                assert_eq!((plugin_name.as_str(), function_name.as_str()), ("staleness", "orphans"));
+            }
+            other => panic!("Expected Function resolution, got {:?}", other),
+        }
+        match mapper.resolve_command("contributors:stale").await.unwrap() {
+            CommandResolution::Explicit { function_name, .. } => assert_eq!(function_name, "freshness"),
+            other => panic!("Expected Explicit resolution, got {:?}", other),
+        }
+    }
+    
     #[tokio::test]
     async fn test_unknown_command_error() {
         let mapper = CommandMapper::new();
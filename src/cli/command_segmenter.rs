@@ -260,20 +260,22 @@ mod tests {
     #[tokio::test]
     async fn test_is_help_request() {
         let segmenter = create_test_segmenter().await;
+        let args = vec![
+            "debug".to_string(),
+            "--help".to_string(),
+            "commits".to_string(),
+            "--output".to_string(),
+            "file.json".to_string()
+        ];
         
-        let help_segment = PluginArgumentSegment {
-            plugin_name: "debug".to_string(),
-            function_name: None,
-            args: vec!["--help".to_string()],
-        };
-        
-        let normal_segment = PluginArgumentSegment {
-            plugin_name: "debug".to_string(),
-            function_name: None,
-            args: vec!["--output".to_string(), "file.json".to_string()],
-        };
+        let result = segmenter.segment_arguments(&args).unwrap();
         
-        assert!(segmenter.is_help_request(&help_segment));
-        assert!(!segmenter.is_help_request(&normal_segment));
+        // Help requests stay with the plugin, which handles its own help
+        assert!(result.global_args.is_empty());
+        assert_eq!(result.plugin_segments.len(), 2);
+        assert_eq!(result.plugin_segments[0].plugin_name, "debug");
+        assert_eq!(result.plugin_segments[0].args, vec!["--help"]);
+        assert_eq!(result.plugin_segments[1].plugin_name, "commits");
+        assert_eq!(result.plugin_segments[1].args, vec!["--output", "file.json"]);
     }
-}
\ No newline at end of file
+}
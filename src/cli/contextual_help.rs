@@ -176,10 +176,10 @@ impl ContextualHelp {
         }
         
         if error.contains("plugin") {
-            troubleshooting.push_str("• Use 'gstats --plugins' to see available plugins\n");
+            troubleshooting.push_str("• Use 'gstats plugins' to see available plugins\n");
             troubleshooting.push_str("• Check plugin name spelling with suggestions above\n");
             if let Some(command) = &context.command {
-                troubleshooting.push_str(&format!("• Try: gstats --plugin-info {}\n", command));
+                troubleshooting.push_str(&format!("• Try: gstats plugins info {}\n", command));
             }
         }
         
@@ -254,6 +254,7 @@ impl ContextualHelp {
     /// Initialize built-in help content
     fn initialize_builtin_help(&mut self) {
         self.add_commits_help();
+        self.add_contributors_help();
         self.add_metrics_help();
         self.add_export_help();
         self.add_workflow_guides();
@@ -304,6 +305,43 @@ impl ContextualHelp {
         self.help_sections.insert("commits".to_string(), section);
     }
     
+    /// Add help for contributors command
+    fn add_contributors_help(&mut self) {
+        let section = HelpSection {
+            title: "Contributor Ownership Analysis".to_string(),
+            basic_description: "Show who owns which parts of the repository.".to_string(),
+            intermediate_description: Some("Reports per-directory ownership percentages, the bus factor of each directory, and active contributors over time.".to_string()),
+            advanced_description: Some("Directory grouping depth and trend period are configurable, and the usual date filters limit the history considered.".to_string()),
+            examples: vec![
+                HelpExample {
+                    description: "Directory ownership".to_string(),
+                    command: "gstats contributors".to_string(),
+                    use_case: "See the main owners of each top-level area".to_string(),
+                    level: HelpLevel::Basic,
+                },
+                HelpExample {
+                    description: "Bus factor".to_string(),
+                    command: "gstats bus-factor --depth 3".to_string(),
+                    use_case: "Find directories that depend on a single person".to_string(),
+                    level: HelpLevel::Intermediate,
+                },
+                HelpExample {
+                    description: "Contributor trends".to_string(),
+                    command: "gstats --since \"1 year ago\" trends --period quarter".to_string(),
+                    use_case: "Track community growth or attrition over time".to_string(),
+                    level: HelpLevel::Advanced,
+                },
+            ],
+            related_commands: vec!["commits".to_string(), "export".to_string()],
+            common_patterns: vec![
+                "Knowledge risk review: gstats bus-factor".to_string(),
+                "Onboarding map: gstats contributors --depth 1 --top 5".to_string(),
+            ],
+        };
+        
+        self.help_sections.insert("contributors".to_string(), section);
+    }
+    
     /// Add help for metrics command
     fn add_metrics_help(&mut self) {
         let section = HelpSection {
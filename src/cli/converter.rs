@@ -2,11 +2,16 @@
 //! 
 //! Converts parsed CLI arguments to QueryParams for integration with the GS-24 filtering system.
 
-use crate::cli::date_parser::{parse_date, validate_date_range, DateParseError};
+use crate::cli::date_parser::{parse_date, parse_end_date, validate_date_range, DateParseError};
 use crate::cli::memory_parser::{parse_memory_size, MemoryParseError};
-use crate::scanner::query::{QueryParams, DateRange, FilePathFilter, AuthorFilter};
+use crate::queue::BackpressurePolicy;
+use crate::scanner::query::{QueryParams, DateRange, FilePathFilter, PathWeight, AuthorFilter};
 use crate::scanner::config::ScannerConfig;
+use crate::scanner::fingerprints::{FingerprintConfig, FingerprintMode};
+use crate::scanner::pending::PendingChanges;
+use crate::scanner::repository::is_git_dir;
 use std::path::PathBuf;
+use std::time::SystemTime;
 use thiserror::Error;
 
 /// CLI conversion errors
@@ -15,27 +20,51 @@ pub enum CliError {
     #[error("Date parsing error: {0}")]
     DateParse(#[from] DateParseError),
     
+    #[error("Invalid {option} value: {source}")]
+    InvalidDate { option: String, source: DateParseError },
+    
     #[error("Memory parsing error: {0}")]
     MemoryParse(#[from] MemoryParseError),
     
     #[error("Invalid path: {path}")]
     InvalidPath { path: String },
     
+    #[error("Not a git directory: {path} (expected a bare repository or a .git directory)")]
+    NotAGitDirectory { path: String },
+    
+    #[error("Patch series not found: {path} (expected an mbox file or a directory of patches)")]
+    PatchSeriesNotFound { path: String },
+    
     
     #[error("Empty author name provided")]
     EmptyAuthor,
     
+    #[error("Invalid author pattern {pattern}: {message}")]
+    InvalidAuthorPattern { pattern: String, message: String },
+    
     #[error("Invalid limit: {limit} must be greater than 0")]
     InvalidLimit { limit: usize },
     
     #[error("Invalid queue size: {size} must be greater than 0")]
     InvalidQueueSize { size: usize },
     
+    #[error("Invalid backpressure policy: {policy} (expected block, drop-oldest or spill-to-disk)")]
+    InvalidBackpressure { policy: String },
+    
+    #[error("Invalid duplicate hashing mode: {mode} (expected lines or tokens)")]
+    InvalidDuplicateHashing { mode: String },
+    
+    #[error("Invalid duplicate block size: {size} must be greater than 0")]
+    InvalidDuplicateMinBlock { size: usize },
+    
     #[error("Conflicting performance mode options: cannot specify both --performance-mode and --no-performance-mode")]
     ConflictingPerformanceModes,
     
     #[error("Plugin validation error: {message}")]
     PluginValidation { message: String },
+    
+    #[error("Unknown exclude preset: {name} (available presets: {available})")]
+    UnknownPreset { name: String, available: String },
 }
 
 /// Convert CLI arguments to ScannerConfig with ConfigManager integration
@@ -80,6 +109,44 @@ pub fn args_to_scanner_config(args: &crate::cli::Args, config_manager: Option<&c
         config.queue_size = queue_size;
     }
     
+    // Override the queue backpressure policy if provided
+    if let Some(policy) = &args.backpressure {
+        config.backpressure = Some(BackpressurePolicy::parse(policy)
+            .ok_or_else(|| CliError::InvalidBackpressure { policy: policy.clone() })?);
+    }
+    
+    // Override the duplicate detection settings if provided; the hashing mode sets the default block size
+    if let Some(mode) = &args.duplicate_hashing {
+        config.fingerprints = FingerprintConfig::new(FingerprintMode::parse(mode)
+            .ok_or_else(|| CliError::InvalidDuplicateHashing { mode: mode.clone() })?);
+    }
+    if let Some(min_block) = args.duplicate_min_block {
+        if min_block == 0 {
+            return Err(CliError::InvalidDuplicateMinBlock { size: min_block });
+        }
+        config.fingerprints.min_block = min_block;
+    }
+    
+    // An explicit git directory (CLI over config file) is scanned without repository discovery
+    if let Some(git_dir) = &args.git_dir {
+        config.git_dir = Some(PathBuf::from(git_dir));
+    }
+    if let Some(git_dir) = config.git_dir.take() {
+        config.git_dir = Some(resolve_git_dir(git_dir)?);
+    }
+    
+    // A patch series is read in place of the repository
+    if let Some(patch) = &args.patch {
+        config.patch_series = Some(resolve_patch_series(PathBuf::from(patch))?);
+    }
+    
+    // Uncommitted changes are read in place of the history
+    if args.staged {
+        config.pending_changes = Some(PendingChanges::Staged);
+    } else if args.working_tree {
+        config.pending_changes = Some(PendingChanges::WorkingTree);
+    }
+    
     // Validate the final configuration
     config.validate()
         .map_err(|e| CliError::PluginValidation { message: e.to_string() })?;
@@ -88,6 +155,32 @@ pub fn args_to_scanner_config(args: &crate::cli::Args, config_manager: Option<&c
 }
 
 
+/// Expand a leading `~` and check that the path is a git directory
+fn resolve_git_dir(git_dir: PathBuf) -> Result<PathBuf, CliError> {
+    let expanded = match (git_dir.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home_dir)) => home_dir.join(rest),
+        _ => git_dir,
+    };
+    
+    if !is_git_dir(&expanded) {
+        return Err(CliError::NotAGitDirectory { path: expanded.display().to_string() });
+    }
+    Ok(expanded.canonicalize().unwrap_or(expanded))
+}
+
+/// Expand a leading `~` and check that the patch series exists
+fn resolve_patch_series(patch: PathBuf) -> Result<PathBuf, CliError> {
+    let expanded = match (patch.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home_dir)) => home_dir.join(rest),
+        _ => patch,
+    };
+    
+    if !expanded.exists() {
+        return Err(CliError::PatchSeriesNotFound { path: expanded.display().to_string() });
+    }
+    Ok(expanded.canonicalize().unwrap_or(expanded))
+}
+
 /// Convert CLI arguments to QueryParams with ConfigManager integration
 /// 
 /// This function takes the parsed CLI arguments and converts them into the QueryParams
@@ -106,10 +199,29 @@ pub fn args_to_scanner_config(args: &crate::cli::Args, config_manager: Option<&c
 /// ```
 pub fn args_to_query_params(args: &crate::cli::Args, config_manager: Option<&crate::config::ConfigManager>) -> Result<QueryParams, CliError> {
     // Convert date arguments
-    let date_range = convert_date_arguments(&args.since, &args.until)?;
+    // Priority: CLI --since/--until > config [scanner] since/until
+    let date_range = match convert_date_arguments(&args.since, &args.until)? {
+        Some(date_range) => Some(date_range),
+        None => match config_manager {
+            Some(cm) => convert_date_options(
+                cm.get_value("scanner", "since").map(|value| ("[scanner] since", value.as_str())),
+                cm.get_value("scanner", "until").map(|value| ("[scanner] until", value.as_str())),
+            )?,
+            None => None,
+        },
+    };
     
-    // Convert path arguments
-    let file_paths = convert_path_arguments(&args.include_path, &args.exclude_path)?;
+    // Convert path arguments; exclude presets add to the excluded paths
+    // Priority: CLI --preset > config [scanner] presets
+    let presets = if args.preset.is_empty() {
+        config_manager.map(|cm| cm.get_scanner_presets()).unwrap_or_default()
+    } else {
+        args.preset.clone()
+    };
+    let mut file_paths = convert_path_arguments(&args.include_path, &args.exclude_path)?;
+    let (excluded, weights) = convert_presets(&presets)?;
+    file_paths.exclude.extend(excluded);
+    file_paths.weights = weights;
     
     // Convert author arguments
     let authors = convert_author_arguments(&args.author, &args.exclude_author)?;
@@ -134,27 +246,35 @@ pub fn args_to_query_params(args: &crate::cli::Args, config_manager: Option<&cra
         limit: scan_limit,
         authors,
         branch,
+        all_branches: args.all_branches,
+        scanned_ref: None,
+        shallow: None,
     })
 }
 
 /// Convert CLI date arguments to DateRange
 fn convert_date_arguments(since: &Option<String>, until: &Option<String>) -> Result<Option<DateRange>, CliError> {
-    // Validate date range logic first
-    if let (Some(since_str), Some(until_str)) = (since.as_ref(), until.as_ref()) {
-        validate_date_range(Some(since_str), Some(until_str))?;
-    }
-    
-    let start_time = if let Some(since_str) = since {
-        Some(parse_date(since_str)?)
-    } else {
-        None
+    convert_date_options(
+        since.as_deref().map(|value| ("--since", value)),
+        until.as_deref().map(|value| ("--until", value)),
+    )
+}
+
+/// Convert `(option, value)` pairs for the start and end dates to a DateRange
+/// The option name is used to point parse errors at the offending flag or config key
+fn convert_date_options(since: Option<(&str, &str)>, until: Option<(&str, &str)>) -> Result<Option<DateRange>, CliError> {
+    let parse = |parser: fn(&str) -> Result<SystemTime, DateParseError>| {
+        move |(option, value): (&str, &str)| {
+            parser(value).map_err(|source| CliError::InvalidDate { option: option.to_string(), source })
+        }
     };
     
-    let end_time = if let Some(until_str) = until {
-        Some(parse_date(until_str)?)
-    } else {
-        None
-    };
+    // A date-only end includes the whole of its day
+    let start_time = since.map(parse(parse_date)).transpose()?;
+    let end_time = until.map(parse(parse_end_date)).transpose()?;
+    
+    // Validate date range logic once both values are known to parse
+    validate_date_range(since.map(|(_, value)| value), until.map(|(_, value)| value))?;
     
     match (start_time, end_time) {
         (Some(start), Some(end)) => Ok(Some(DateRange::new(start, end))),
@@ -174,7 +294,21 @@ fn convert_path_arguments(include_paths: &[String], exclude_paths: &[String]) ->
         .map(|path| validate_and_convert_path(path))
         .collect::<Result<Vec<_>, _>>()?;
     
-    Ok(FilePathFilter { include, exclude })
+    Ok(FilePathFilter { include, exclude, weights: Vec::new() })
+}
+
+/// Expand exclude preset names to the paths they exclude and the paths they weight
+fn convert_presets(presets: &[String]) -> Result<(Vec<PathBuf>, Vec<PathWeight>), CliError> {
+    let unknown = |name| CliError::UnknownPreset {
+        name,
+        available: crate::scanner::presets::names().join(", "),
+    };
+    let paths = crate::scanner::presets::excluded_paths(presets).map_err(unknown)?;
+    let weights = crate::scanner::presets::weighted_paths(presets).map_err(unknown)?;
+    Ok((
+        paths.into_iter().map(PathBuf::from).collect(),
+        weights.into_iter().map(|(path, weight)| PathWeight { path: PathBuf::from(path), weight }).collect(),
+    ))
 }
 
 /// Validate and convert a path string to PathBuf
@@ -204,12 +338,14 @@ fn convert_author_arguments(include_authors: &[String], exclude_authors: &[Strin
     Ok(AuthorFilter { include, exclude })
 }
 
-/// Validate an author name/email
+/// Validate an author name/email, or a `/regex/` pattern
 fn validate_author(author: &str) -> Result<String, CliError> {
     let trimmed = author.trim();
-    if trimmed.is_empty() {
+    if trimmed.is_empty() || trimmed == "//" {
         return Err(CliError::EmptyAuthor);
     }
+    crate::scanner::filters::AuthorPattern::parse(trimmed)
+        .map_err(|e| CliError::InvalidAuthorPattern { pattern: trimmed.to_string(), message: e.to_string() })?;
     Ok(trimmed.to_string())
 }
 
@@ -232,6 +368,8 @@ pub struct PluginConfig {
     pub plugin_load: Vec<String>,
     /// Plugins to exclude (CLI --plugin-exclude or config plugin-exclude)
     pub plugin_exclude: Vec<String>,
+    /// Deadline for each consumer plugin call (CLI --plugin-timeout or config plugin-timeout)
+    pub timeout: Option<std::time::Duration>,
 }
 
 /// Merge plugin configuration from CLI arguments and config file
@@ -241,6 +379,7 @@ pub fn merge_plugin_config(args: &crate::cli::Args, config_manager: Option<&crat
         directories: Vec::new(),
         plugin_load: Vec::new(),
         plugin_exclude: Vec::new(),
+        timeout: Some(crate::plugin::registry::DEFAULT_PLUGIN_TIMEOUT),
     };
 
     // Start with configuration file values
@@ -255,6 +394,10 @@ pub fn merge_plugin_config(args: &crate::cli::Args, config_manager: Option<&crat
         
         // Add config plugin-exclude list  
         config.plugin_exclude.extend(manager.get_plugins_exclude());
+        
+        if let Some(seconds) = manager.get_plugins_timeout() {
+            config.timeout = plugin_timeout(seconds);
+        }
     }
 
     // Override with CLI arguments (higher precedence)
@@ -274,6 +417,10 @@ pub fn merge_plugin_config(args: &crate::cli::Args, config_manager: Option<&crat
         config.plugin_exclude.extend(parse_comma_separated(cli_exclude));
     }
 
+    if let Some(seconds) = args.plugin_timeout {
+        config.timeout = plugin_timeout(seconds);
+    }
+
     // Add CLI plugin directory if specified
     if let Some(cli_plugin_dir) = &args.plugin_directory {
         config.directories.push(cli_plugin_dir.clone());
@@ -287,6 +434,11 @@ pub fn merge_plugin_config(args: &crate::cli::Args, config_manager: Option<&crat
     config
 }
 
+/// Plugin call deadline from a number of seconds, where 0 means no deadline
+fn plugin_timeout(seconds: u64) -> Option<std::time::Duration> {
+    (seconds > 0).then(|| std::time::Duration::from_secs(seconds))
+}
+
 /// Parse comma-separated string into Vec<String> (helper for CLI arguments)
 fn parse_comma_separated(input: &str) -> Vec<String> {
     input
@@ -324,8 +476,13 @@ mod tests {
             log_format: "text".to_string(),
             log_file: None,
             log_file_level: None,
+            log_filter: None,
+            log_max_size: None,
+            log_keep: None,
             color: false,
             no_color: false,
+            accessible: false,
+            hyperlinks: None,
             config_file: None,
             since: None,
             until: None,
@@ -336,25 +493,61 @@ mod tests {
             author: Vec::new(),
             exclude_author: Vec::new(),
             scan_limit: None,
+            preset: vec![],
+            expect_data: false,
+            memory_report: false,
+            queue_stats: false,
+            profile: false,
+            profile_trace: None,
+            reproducible: false,
+            fail_on: Vec::new(),
+            matrix_refs: Vec::new(),
+            matrix_output: None,
+            debt_trend: None,
+            debt_trend_output: None,
+            record_trends: false,
+            trend_db: None,
+            tui: false,
+            otlp_endpoint: None,
+            watch: false,
+            watch_interval: None,
+            repos: Vec::new(),
+            repos_file: None,
+            repos_output: None,
+            git_dir: None,
+            patch: None,
             performance_mode: false,
             no_performance_mode: false,
             max_memory: None,
             queue_size: None,
+            backpressure: None,
+            duplicate_min_block: None,
+            duplicate_hashing: None,
+            no_cache: false,
+            worker_threads: None,
             command: None,
             plugin_args: Vec::new(),
             list_plugins: false,
             plugin_info: None,
+            json: false,
             plugin_dir: None,
             plugins_dir: Vec::new(),
             plugin_load: None,
             plugin_exclude: None,
             plugin_directory: None,
+            plugin_timeout: None,
             show_plugins: false,
             plugins_help: false,
             export_config: None,
             list_formats: false,
             branch: None,
+            all_branches: false,
+            unshallow: false,
+            staged: false,
+            working_tree: false,
             show_branch: false,
+            compare: None,
+            compare_output: None,
             fallback_branch: None,
             remote: None,
             help: false,
@@ -414,6 +607,39 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    fn test_convert_date_arguments_error_names_option() {
+        let error = convert_date_arguments(&Some("2 weeks".to_string()), &None).unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("Invalid --since value"), "{message}");
+        assert!(message.contains("'2 weeks ago'"), "{message}");
+    }
+    
+    #[test]
+    fn test_args_to_query_params_dates_from_config() {
+        use crate::config::{ConfigManager, Configuration};
+        use std::collections::HashMap;
+        
+        let mut config = Configuration::new();
+        let mut scanner_section = HashMap::new();
+        scanner_section.insert("since".to_string(), "90d".to_string());
+        config.insert("scanner".to_string(), scanner_section);
+        let config_manager = ConfigManager::from_config(config);
+        
+        let result = args_to_query_params(&create_test_args(), Some(&config_manager)).unwrap();
+        let date_range = result.date_range.unwrap();
+        assert!(date_range.start.is_some());
+        assert!(date_range.end.is_none());
+        
+        // CLI dates replace the configured window
+        let args = Args {
+            until: Some("2023-12-31".to_string()),
+            ..create_test_args()
+        };
+        let result = args_to_query_params(&args, Some(&config_manager)).unwrap();
+        assert!(result.date_range.unwrap().start.is_none());
+    }
+    
     #[test]
     fn test_convert_path_arguments() {
         let include = vec!["src/".to_string(), "tests/".to_string()];
@@ -476,8 +702,13 @@ mod tests {
             log_format: "text".to_string(),
             log_file: None,
             log_file_level: None,
+            log_filter: None,
+            log_max_size: None,
+            log_keep: None,
             color: false,
             no_color: false,
+            accessible: false,
+            hyperlinks: None,
             config_file: None,
             since: Some("2023-01-01".to_string()),
             until: Some("2023-12-31".to_string()),
@@ -488,25 +719,61 @@ mod tests {
             author: vec!["alice@example.com".to_string()],
             exclude_author: vec!["spam@example.com".to_string()],
             scan_limit: Some(100),
+            preset: vec![],
+            expect_data: false,
+            memory_report: false,
+            queue_stats: false,
+            profile: false,
+            profile_trace: None,
+            reproducible: false,
+            fail_on: Vec::new(),
+            matrix_refs: Vec::new(),
+            matrix_output: None,
+            debt_trend: None,
+            debt_trend_output: None,
+            record_trends: false,
+            trend_db: None,
+            tui: false,
+            otlp_endpoint: None,
+            watch: false,
+            watch_interval: None,
+            repos: Vec::new(),
+            repos_file: None,
+            repos_output: None,
+            git_dir: None,
+            patch: None,
             performance_mode: false,
             no_performance_mode: false,
             max_memory: None,
             queue_size: None,
+            backpressure: None,
+            duplicate_min_block: None,
+            duplicate_hashing: None,
+            no_cache: false,
+            worker_threads: None,
             command: Some("commits".to_string()),
             plugin_args: Vec::new(),
             list_plugins: false,
             plugin_info: None,
+            json: false,
             plugin_dir: None,
             plugins_dir: Vec::new(),
             plugin_load: None,
             plugin_exclude: None,
             plugin_directory: None,
+            plugin_timeout: None,
             show_plugins: false,
             plugins_help: false,
             export_config: None,
             list_formats: false,
             branch: None,
+            all_branches: false,
+            unshallow: false,
+            staged: false,
+            working_tree: false,
             show_branch: false,
+            compare: None,
+            compare_output: None,
             fallback_branch: None,
             remote: None,
             help: false,
@@ -537,6 +804,35 @@ mod tests {
         assert!(result.branch.is_none());
     }
 
+    #[test]
+    fn test_args_to_query_params_presets() {
+        use crate::config::{ConfigManager, Configuration};
+        use std::collections::HashMap;
+        
+        let mut args = create_test_args();
+        args.exclude_path = vec!["docs/".to_string()];
+        args.preset = vec!["rust".to_string(), "node".to_string()];
+        let result = args_to_query_params(&args, None).unwrap();
+        assert!(result.file_paths.exclude.contains(&PathBuf::from("docs/")));
+        assert!(result.file_paths.exclude.contains(&PathBuf::from("target/")));
+        assert!(result.file_paths.exclude.contains(&PathBuf::from("node_modules/")));
+        
+        // Configured presets apply unless presets are given on the command line
+        let mut config = Configuration::new();
+        let mut scanner_section = HashMap::new();
+        scanner_section.insert("presets".to_string(), "[\"python\"]".to_string());
+        config.insert("scanner".to_string(), scanner_section);
+        let config_manager = ConfigManager::from_config(config);
+        let result = args_to_query_params(&create_test_args(), Some(&config_manager)).unwrap();
+        assert!(result.file_paths.exclude.contains(&PathBuf::from(".venv/")));
+        assert!(result.file_paths.weights.contains(&PathWeight { path: PathBuf::from("migrations/"), weight: 0.1 }));
+        let result = args_to_query_params(&args, Some(&config_manager)).unwrap();
+        assert!(!result.file_paths.exclude.contains(&PathBuf::from(".venv/")));
+        
+        args.preset = vec!["cobol".to_string()];
+        assert!(matches!(args_to_query_params(&args, None), Err(CliError::UnknownPreset { .. })));
+    }
+
     // GS-75 Phase 4: Branch Configuration Integration Tests
     #[test]
     fn test_args_to_query_params_branch_from_cli() {
@@ -644,6 +940,36 @@ mod tests {
         assert_eq!(result.queue_size, 2000);
     }
 
+    #[test]
+    fn test_args_to_scanner_config_git_dir_must_be_git_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let args = Args {
+            git_dir: Some(temp_dir.path().display().to_string()),
+            ..create_test_args()
+        };
+        
+        let result = args_to_scanner_config(&args, None);
+        assert!(matches!(result, Err(CliError::NotAGitDirectory { .. })));
+    }
+
+    #[test]
+    fn test_args_to_scanner_config_patch_series_must_exist() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let args = Args {
+            patch: Some(temp_dir.path().display().to_string()),
+            ..create_test_args()
+        };
+        let config = args_to_scanner_config(&args, None).unwrap();
+        assert_eq!(config.patch_series, Some(temp_dir.path().canonicalize().unwrap()));
+        
+        let args = Args {
+            patch: Some(temp_dir.path().join("missing.mbox").display().to_string()),
+            ..create_test_args()
+        };
+        let result = args_to_scanner_config(&args, None);
+        assert!(matches!(result, Err(CliError::PatchSeriesNotFound { .. })));
+    }
+
     #[test]
     fn test_args_to_scanner_config_memory_units() {
         let test_cases = vec![
@@ -663,8 +989,13 @@ mod tests {
                 log_format: "text".to_string(),
                 log_file: None,
                 log_file_level: None,
+                log_filter: None,
+                log_max_size: None,
+                log_keep: None,
                 color: false,
                 no_color: false,
+                accessible: false,
+                hyperlinks: None,
                     config_file: None,
                     since: None,
                 until: None,
@@ -675,25 +1006,61 @@ mod tests {
                 author: vec![],
                 exclude_author: vec![],
                 scan_limit: None,
+                preset: vec![],
+                expect_data: false,
+                memory_report: false,
+                queue_stats: false,
+                profile: false,
+                profile_trace: None,
+                reproducible: false,
+                fail_on: Vec::new(),
+                matrix_refs: Vec::new(),
+                matrix_output: None,
+                debt_trend: None,
+                debt_trend_output: None,
+                record_trends: false,
+                trend_db: None,
+                tui: false,
+                otlp_endpoint: None,
+                watch: false,
+                watch_interval: None,
+                repos: Vec::new(),
+                repos_file: None,
+                repos_output: None,
+                git_dir: None,
+                patch: None,
                 performance_mode: false,
                 no_performance_mode: false,
                 max_memory: Some(memory_str.to_string()),
                 queue_size: None,
+                backpressure: None,
+                duplicate_min_block: None,
+                duplicate_hashing: None,
+                no_cache: false,
+                worker_threads: None,
                 command: None,
                 plugin_args: Vec::new(),
                 list_plugins: false,
                 plugin_info: None,
+                json: false,
                     plugin_dir: None,
                 plugins_dir: Vec::new(),
                 plugin_load: None,
                 plugin_exclude: None,
                 plugin_directory: None,
+                plugin_timeout: None,
                 show_plugins: false,
                 plugins_help: false,
                 export_config: None,
                 list_formats: false,
                 branch: None,
+                all_branches: false,
+                unshallow: false,
+                staged: false,
+                working_tree: false,
                 show_branch: false,
+                compare: None,
+                compare_output: None,
                 fallback_branch: None,
                 remote: None,
                 help: false,
@@ -716,6 +1083,7 @@ mod tests {
                "Expected default directory to contain .config/gstats/plugins, got: {}", default_dir);
         assert!(config.plugin_load.is_empty());
         assert!(config.plugin_exclude.is_empty());
+        assert_eq!(config.timeout, Some(crate::plugin::registry::DEFAULT_PLUGIN_TIMEOUT));
     }
 
     #[test]
@@ -766,6 +1134,7 @@ mod tests {
         plugins_section.insert("directory".to_string(), "/config/plugins".to_string());
         plugins_section.insert("plugin-load".to_string(), "[\"config_plugin1\", \"config_plugin2\"]".to_string());
         plugins_section.insert("plugin-exclude".to_string(), "[\"config_exclude1\"]".to_string());
+        plugins_section.insert("plugin-timeout".to_string(), "120".to_string());
         config_data.insert("plugins".to_string(), plugins_section);
         
         let config_manager = ConfigManager::from_config(config_data);
@@ -774,6 +1143,7 @@ mod tests {
             plugins_dir: vec!["./cli_plugins".to_string()],
             plugin_load: Some("cli_plugin1,cli_plugin2".to_string()),
             plugin_exclude: Some("cli_exclude1".to_string()),
+            plugin_timeout: Some(0),
             ..create_test_args()
         };
         
@@ -783,6 +1153,7 @@ mod tests {
         assert_eq!(config.directories, vec!["/config/plugins", "./cli_plugins"]); // Combined
         assert_eq!(config.plugin_load, vec!["cli_plugin1", "cli_plugin2"]); // CLI overrides
         assert_eq!(config.plugin_exclude, vec!["cli_exclude1"]); // CLI overrides
+        assert_eq!(config.timeout, None); // CLI 0 disables the config deadline
     }
 
     #[test]
@@ -851,8 +1222,13 @@ mod tests {
             log_format: "text".to_string(),
             log_file: None,
             log_file_level: None,
+            log_filter: None,
+            log_max_size: None,
+            log_keep: None,
             color: false,
             no_color: false,
+            accessible: false,
+            hyperlinks: None,
             config_file: None,
             since: None,
             until: None,
@@ -863,25 +1239,61 @@ mod tests {
             author: vec![],
             exclude_author: vec![],
             scan_limit: None,
+            preset: vec![],
+            expect_data: false,
+            memory_report: false,
+            queue_stats: false,
+            profile: false,
+            profile_trace: None,
+            reproducible: false,
+            fail_on: Vec::new(),
+            matrix_refs: Vec::new(),
+            matrix_output: None,
+            debt_trend: None,
+            debt_trend_output: None,
+            record_trends: false,
+            trend_db: None,
+            tui: false,
+            otlp_endpoint: None,
+            watch: false,
+            watch_interval: None,
+            repos: Vec::new(),
+            repos_file: None,
+            repos_output: None,
+            git_dir: None,
+            patch: None,
             performance_mode: true,
             no_performance_mode: true,
             max_memory: None,
             queue_size: None,
+            backpressure: None,
+            duplicate_min_block: None,
+            duplicate_hashing: None,
+            no_cache: false,
+            worker_threads: None,
             command: None,
             plugin_args: Vec::new(),
             list_plugins: false,
             plugin_info: None,
+            json: false,
             plugin_dir: None,
             plugins_dir: Vec::new(),
             plugin_load: None,
             plugin_exclude: None,
             plugin_directory: None,
+            plugin_timeout: None,
             show_plugins: false,
             plugins_help: false,
             export_config: None,
             list_formats: false,
             branch: None,
+            all_branches: false,
+            unshallow: false,
+            staged: false,
+            working_tree: false,
             show_branch: false,
+            compare: None,
+            compare_output: None,
             fallback_branch: None,
             remote: None,
             help: false,
@@ -906,8 +1318,13 @@ mod tests {
             log_format: "text".to_string(),
             log_file: None,
             log_file_level: None,
+            log_filter: None,
+            log_max_size: None,
+            log_keep: None,
             color: false,
             no_color: false,
+            accessible: false,
+            hyperlinks: None,
             config_file: None,
             since: None,
             until: None,
@@ -918,25 +1335,61 @@ mod tests {
             author: vec![],
             exclude_author: vec![],
             scan_limit: None,
+            preset: vec![],
+            expect_data: false,
+            memory_report: false,
+            queue_stats: false,
+            profile: false,
+            profile_trace: None,
+            reproducible: false,
+            fail_on: Vec::new(),
+            matrix_refs: Vec::new(),
+            matrix_output: None,
+            debt_trend: None,
+            debt_trend_output: None,
+            record_trends: false,
+            trend_db: None,
+            tui: false,
+            otlp_endpoint: None,
+            watch: false,
+            watch_interval: None,
+            repos: Vec::new(),
+            repos_file: None,
+            repos_output: None,
+            git_dir: None,
+            patch: None,
             performance_mode: false,
             no_performance_mode: false,
             max_memory: Some("invalid".to_string()),
             queue_size: None,
+            backpressure: None,
+            duplicate_min_block: None,
+            duplicate_hashing: None,
+            no_cache: false,
+            worker_threads: None,
             command: None,
             plugin_args: Vec::new(),
             list_plugins: false,
             plugin_info: None,
+            json: false,
             plugin_dir: None,
             plugins_dir: Vec::new(),
             plugin_load: None,
             plugin_exclude: None,
             plugin_directory: None,
+            plugin_timeout: None,
             show_plugins: false,
             plugins_help: false,
             export_config: None,
             list_formats: false,
             branch: None,
+            all_branches: false,
+            unshallow: false,
+            staged: false,
+            working_tree: false,
             show_branch: false,
+            compare: None,
+            compare_output: None,
             fallback_branch: None,
             remote: None,
             help: false,
@@ -1,37 +1,47 @@
-//! Date parsing module for CLI arguments
+//! Date and duration parsing for CLI arguments and configuration values
 //! 
-//! Supports both absolute and relative date formats:
-//! - Absolute: ISO 8601 formats like "2023-01-01", "2023-01-01T10:30:00"
+//! All time-valued options share this parser so they accept the same forms:
+//! - Absolute: ISO 8601 / RFC 3339 like "2023-01-01", "2023-01-01T10:30:00", "2023-01-01T10:30:00Z"
 //! - Relative: Human-readable formats like "1 week ago", "yesterday", "last month"
+//! - Durations: "90d", "6m", "2w" or "90 days"; as a date, a compact duration means that long ago
+//!
+//! Months count as 30 days and years as 365 days.
 
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use std::time::SystemTime;
 
+/// Units accepted in relative dates and durations
+const SUPPORTED_UNITS: &str = "s, min, h, d, w, m (months), y or their long names (seconds, minutes, hours, days, weeks, months, years)";
+
 /// Error types for date parsing
 #[derive(Debug, thiserror::Error)]
 pub enum DateParseError {
-    #[error("Invalid date format: {input}. Expected ISO 8601 (YYYY-MM-DD) or relative format (e.g., '1 week ago')")]
+    #[error("Invalid date '{input}'. Expected ISO 8601 (YYYY-MM-DD, YYYY-MM-DDTHH:MM:SS, RFC 3339), a relative date ('2 weeks ago', 'yesterday') or a duration ('90d', '6m')")]
     InvalidFormat { input: String },
     
-    #[error("Invalid relative date: {input}. Expected format like '1 week ago', 'yesterday', 'last month'")]
-    InvalidRelativeFormat { input: String },
+    #[error("Invalid relative date '{input}'. Expected a form like '1 week ago', 'yesterday', 'last month'{hint}")]
+    InvalidRelativeFormat { input: String, hint: String },
     
-    #[error("Unsupported time unit: {unit}. Supported units: seconds, minutes, hours, days, weeks, months, years")]
+    #[error("Unsupported time unit '{unit}'. Supported units: {SUPPORTED_UNITS}")]
     UnsupportedUnit { unit: String },
     
-    #[error("Invalid number in relative date: {input}")]
+    #[error("Invalid number in '{input}': expected a whole number of at least 0")]
     InvalidNumber { input: String },
     
+    #[error("Invalid duration '{input}'. Expected a number and unit like '90d', '6m', '12h' or '2 weeks'")]
+    InvalidDuration { input: String },
+    
     #[error("Date range validation failed: start date {start} is after end date {end}")]
     InvalidRange { start: String, end: String },
 }
 
 /// Parse a date string into SystemTime
 /// 
-/// Supports both absolute and relative formats:
+/// Supports absolute, relative and duration formats:
 /// - Absolute: "2023-01-01", "2023-01-01T10:30:00", "2023-01-01T10:30:00Z"
 /// - Relative: "1 week ago", "3 months ago", "yesterday", "today", "tomorrow"
 /// - Named periods: "last week", "last month", "last year"
+/// - Durations before now: "90d", "6m", "1y"
 /// 
 /// # Examples
 /// 
@@ -45,6 +55,7 @@ pub enum DateParseError {
 /// // Relative dates
 /// let date3 = parse_date("1 week ago").unwrap();
 /// let date4 = parse_date("yesterday").unwrap();
+/// let date5 = parse_date("90d").unwrap();
 /// ```
 pub fn parse_date(input: &str) -> Result<SystemTime, DateParseError> {
     let trimmed = input.trim();
@@ -54,10 +65,118 @@ pub fn parse_date(input: &str) -> Result<SystemTime, DateParseError> {
         return Ok(system_time);
     }
     
+    // Compact durations ("90d") count back from now
+    if let Some((number, unit)) = split_compact_duration(trimmed) {
+        let duration = unit_duration(number, unit, trimmed)?;
+        return Ok((Local::now() - duration).into());
+    }
+    
     // Try relative date formats
     parse_relative_date(trimmed)
 }
 
+/// Parse the end of a date range
+/// 
+/// A calendar day without a time ("2024-06-30", "today", "yesterday") includes the
+/// whole of that day, so it ends at the day's last second rather than at its start.
+/// Anything else parses as with [`parse_date`].
+/// 
+/// # Examples
+/// 
+/// ```
+/// use gstats::cli::date_parser::{parse_date, parse_end_date};
+/// 
+/// let start = parse_date("2024-06-30").unwrap();
+/// let end = parse_end_date("2024-06-30").unwrap();
+/// assert_eq!(end.duration_since(start).unwrap().as_secs(), 24 * 60 * 60 - 1);
+/// ```
+pub fn parse_end_date(input: &str) -> Result<SystemTime, DateParseError> {
+    let trimmed = input.trim();
+    match calendar_day(trimmed) {
+        Some(day) => {
+            let naive_dt = day.and_hms_opt(23, 59, 59).unwrap();
+            let dt = Local.from_local_datetime(&naive_dt).latest()
+                .ok_or_else(|| DateParseError::InvalidFormat { input: trimmed.to_string() })?;
+            Ok(dt.into())
+        }
+        None => parse_date(trimmed),
+    }
+}
+
+/// The day named by a date without a time of day
+fn calendar_day(input: &str) -> Option<NaiveDate> {
+    let today = Local::now().date_naive();
+    match input.to_lowercase().as_str() {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        "tomorrow" => today.succ_opt(),
+        _ => NaiveDate::parse_from_str(input, "%Y-%m-%d").ok(),
+    }
+}
+
+/// Parse a duration such as "90d", "6m", "12h" or "2 weeks"
+/// 
+/// # Examples
+/// 
+/// ```
+/// use gstats::cli::date_parser::parse_duration;
+/// 
+/// assert_eq!(parse_duration("90d").unwrap(), chrono::Duration::days(90));
+/// assert_eq!(parse_duration("2 weeks").unwrap(), chrono::Duration::weeks(2));
+/// ```
+pub fn parse_duration(input: &str) -> Result<chrono::Duration, DateParseError> {
+    let trimmed = input.trim();
+    let lower = trimmed.to_lowercase();
+    
+    if let Some((number, unit)) = split_compact_duration(&lower) {
+        return unit_duration(number, unit, trimmed);
+    }
+    
+    match lower.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [number, unit] => unit_duration(number, unit, trimmed),
+        _ => Err(DateParseError::InvalidDuration { input: trimmed.to_string() }),
+    }
+}
+
+/// Parse a duration where a bare number is taken in `default_unit`
+/// 
+/// Lets count-style options such as "--anomaly-window 8" (weeks) also accept "3m".
+pub fn parse_duration_or(input: &str, default_unit: &str) -> Result<chrono::Duration, DateParseError> {
+    let trimmed = input.trim();
+    if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_digit()) {
+        return unit_duration(trimmed, default_unit, trimmed);
+    }
+    parse_duration(trimmed)
+}
+
+/// Split a compact duration like "90d" into its number and unit
+fn split_compact_duration(input: &str) -> Option<(&str, &str)> {
+    let split = input.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = input.split_at(split);
+    if number.is_empty() || unit.is_empty() || !unit.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some((number, unit))
+}
+
+/// Convert a number and unit into a duration
+fn unit_duration(number: &str, unit: &str, input: &str) -> Result<chrono::Duration, DateParseError> {
+    let number = number.parse::<u32>()
+        .map_err(|_| DateParseError::InvalidNumber { input: input.to_string() })? as i64;
+    
+    let duration = match unit.to_lowercase().as_str() {
+        "s" | "sec" | "secs" | "second" | "seconds" => chrono::Duration::seconds(number),
+        "min" | "mins" | "minute" | "minutes" => chrono::Duration::minutes(number),
+        "h" | "hr" | "hrs" | "hour" | "hours" => chrono::Duration::hours(number),
+        "d" | "day" | "days" => chrono::Duration::days(number),
+        "w" | "wk" | "wks" | "week" | "weeks" => chrono::Duration::weeks(number),
+        "m" | "mo" | "month" | "months" => chrono::Duration::days(number * 30), // Approximate
+        "y" | "yr" | "yrs" | "year" | "years" => chrono::Duration::days(number * 365), // Approximate
+        _ => return Err(DateParseError::UnsupportedUnit { unit: unit.to_string() }),
+    };
+    Ok(duration)
+}
+
 /// Parse absolute date formats (ISO 8601)
 fn parse_absolute_date(input: &str) -> Result<SystemTime, DateParseError> {
     // Try various ISO 8601 formats
@@ -108,36 +227,28 @@ fn parse_relative_date(input: &str) -> Result<SystemTime, DateParseError> {
     
     // Parse "X unit ago" format
     let parts: Vec<&str> = input_lower.split_whitespace().collect();
-    if parts.len() >= 3 && parts[parts.len() - 1] == "ago" {
-        let number_str = parts[0];
-        let unit = parts[1];
-        
-        let number = number_str.parse::<i64>()
-            .map_err(|_| DateParseError::InvalidNumber { input: input.to_string() })?;
-        
-        let duration = match unit {
-            "second" | "seconds" => chrono::Duration::seconds(number),
-            "minute" | "minutes" => chrono::Duration::minutes(number),
-            "hour" | "hours" => chrono::Duration::hours(number),
-            "day" | "days" => chrono::Duration::days(number),
-            "week" | "weeks" => chrono::Duration::weeks(number),
-            "month" | "months" => chrono::Duration::days(number * 30), // Approximate
-            "year" | "years" => chrono::Duration::days(number * 365), // Approximate
-            _ => return Err(DateParseError::UnsupportedUnit { unit: unit.to_string() }),
-        };
-        
-        let result_time = now - duration;
-        return Ok(result_time.into());
+    match parts.as_slice() {
+        [number, unit, "ago"] => {
+            let duration = unit_duration(number, unit, input)?;
+            Ok((now - duration).into())
+        }
+        // A duration without "ago" is ambiguous as a date
+        [number, unit] if unit_duration(number, unit, input).is_ok() => Err(DateParseError::InvalidRelativeFormat {
+            input: input.to_string(),
+            hint: format!(" (did you mean '{input} ago'?)"),
+        }),
+        [_, ..] if input_lower.chars().next().is_some_and(|c| c.is_ascii_digit()) => {
+            Err(DateParseError::InvalidFormat { input: input.to_string() })
+        }
+        _ => Err(DateParseError::InvalidRelativeFormat { input: input.to_string(), hint: String::new() }),
     }
-    
-    Err(DateParseError::InvalidRelativeFormat { input: input.to_string() })
 }
 
 /// Validate that a date range is logical (start <= end)
 pub fn validate_date_range(start: Option<&str>, end: Option<&str>) -> Result<(), DateParseError> {
     if let (Some(start_str), Some(end_str)) = (start, end) {
         let start_time = parse_date(start_str)?;
-        let end_time = parse_date(end_str)?;
+        let end_time = parse_end_date(end_str)?;
         
         if start_time > end_time {
             return Err(DateParseError::InvalidRange {
@@ -169,6 +280,20 @@ mod tests {
         assert!(result > SystemTime::UNIX_EPOCH);
     }
     
+    #[test]
+    fn test_parse_end_date_covers_the_whole_day() {
+        let start = parse_date("2024-06-30").unwrap();
+        let end = parse_end_date("2024-06-30").unwrap();
+        assert_eq!(end.duration_since(start).unwrap(), Duration::from_secs(24 * 60 * 60 - 1));
+        
+        // A time of day is kept as given
+        assert_eq!(parse_end_date("2024-06-30T10:30:00Z").unwrap(), parse_date("2024-06-30T10:30:00Z").unwrap());
+        
+        let today = parse_end_date("today").unwrap();
+        assert!(today > parse_date("today").unwrap());
+        assert!(today < parse_date("tomorrow").unwrap());
+    }
+    
     #[test]
     fn test_parse_relative_date_named() {
         let now = SystemTime::now();
@@ -266,4 +391,44 @@ mod tests {
         assert!(parse_date("1 DAY AGO").is_ok());
         assert!(parse_date("LAST WEEK").is_ok());
     }
+    
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90d").unwrap(), chrono::Duration::days(90));
+        assert_eq!(parse_duration("6m").unwrap(), chrono::Duration::days(180));
+        assert_eq!(parse_duration("12h").unwrap(), chrono::Duration::hours(12));
+        assert_eq!(parse_duration("30min").unwrap(), chrono::Duration::minutes(30));
+        assert_eq!(parse_duration("1Y").unwrap(), chrono::Duration::days(365));
+        assert_eq!(parse_duration(" 2 weeks ").unwrap(), chrono::Duration::weeks(2));
+        
+        assert!(matches!(parse_duration("90"), Err(DateParseError::InvalidDuration { .. })));
+        assert!(matches!(parse_duration("3 fortnights"), Err(DateParseError::UnsupportedUnit { .. })));
+        assert!(matches!(parse_duration("-3d"), Err(DateParseError::InvalidDuration { .. })));
+        
+        assert_eq!(parse_duration_or("8", "w").unwrap(), chrono::Duration::weeks(8));
+        assert_eq!(parse_duration_or("3m", "w").unwrap(), chrono::Duration::days(90));
+    }
+    
+    #[test]
+    fn test_parse_date_compact_duration() {
+        let now = SystemTime::now();
+        let ninety_days = parse_date("90d").unwrap();
+        let expected = now - Duration::from_secs(90 * 24 * 60 * 60);
+        let diff = ninety_days.duration_since(expected).unwrap_or_else(|e| e.duration());
+        assert!(diff < Duration::from_secs(60));
+        
+        assert!(parse_date("6m").unwrap() < parse_date("90d").unwrap());
+    }
+    
+    #[test]
+    fn test_parse_date_error_messages() {
+        let missing_ago = parse_date("2 weeks").unwrap_err().to_string();
+        assert!(missing_ago.contains("did you mean '2 weeks ago'?"), "{missing_ago}");
+        
+        let unit = parse_date("3 fortnights ago").unwrap_err().to_string();
+        assert!(unit.contains("'fortnights'"), "{unit}");
+        
+        let format = parse_date("2023/01/01").unwrap_err().to_string();
+        assert!(format.contains("YYYY-MM-DD"), "{format}");
+    }
 }
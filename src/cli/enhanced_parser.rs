@@ -57,8 +57,16 @@ impl EnhancedParser {
     }
 
     /// Parse include/exclude author arguments
+    ///
+    /// A `/regex/` pattern is kept whole, so it may contain commas.
     pub fn parse_authors(authors: Vec<String>) -> Vec<String> {
-        parse_comma_separated(authors)
+        authors.into_iter()
+            .flat_map(|author| if crate::scanner::filters::AuthorPattern::is_regex(author.trim()) {
+                vec![author.trim().to_string()]
+            } else {
+                parse_comma_separated(vec![author])
+            })
+            .collect()
     }
 }
 
@@ -87,6 +95,13 @@ mod tests {
         assert_eq!(result, vec!["src/", "tests/", "lib/"]);
     }
 
+    #[test]
+    fn test_parse_authors_keeps_regex_whole() {
+        let input = vec!["alice,bob".to_string(), " /^ci-[a-z]{2,8}@/ ".to_string()];
+        let result = EnhancedParser::parse_authors(input);
+        assert_eq!(result, vec!["alice", "bob", "/^ci-[a-z]{2,8}@/"]);
+    }
+
     #[test]
     fn test_parse_comma_separated_with_spaces() {
         let input = vec!["src/ , tests/ ".to_string(), " lib/ ".to_string()];
@@ -102,7 +102,7 @@ impl HelpFormatter {
     }
 
     /// Write the plugin/function table
-    /// Write dynamic plugin table using the same format as --plugins command
+    /// Write dynamic plugin table using the same function format as gstats plugins list
     fn write_dynamic_plugin_table(&self, output: &mut String, mappings: &[crate::cli::plugin_handler::FunctionMapping]) {
         use std::collections::HashMap;
         
@@ -221,7 +221,7 @@ impl HelpFormatter {
         let plugins = vec![
             ("commits", "authors, contributors, committers, commits, history"),
             ("metrics", "metrics, complexity, quality"),
-            ("export", "export with auto-format detection (json, csv, xml, yaml, html, markdown)"),
+            ("export", "export with auto-format detection (json, ndjson, csv, xml, yaml, html, markdown)"),
         ];
         
         // Calculate column widths based on content
@@ -260,17 +260,28 @@ impl HelpFormatter {
     fn write_main_options(&self, output: &mut String) {
         let options = vec![
             ("-r, --repo <PATH>", "Repository path (default: current directory)"),
+            ("--git-dir <PATH>", "Git directory to scan (bare repository or .git)"),
+            ("--patch <FILE|DIR>", "Analyse an mbox or directory of patches instead of a repository"),
+            ("--watch", "Rescan whenever new commits land, regenerating reports"),
+            ("--watch-interval <SECONDS>", "Seconds between checks for new commits (default: 5)"),
+            ("--repos <PATH,...>", "Scan several repositories (supports comma-separated)"),
+            ("--repos-file <FILE>", "Scan the repositories listed in FILE, one per line"),
+            ("--repos-output <FILE>", "Write multi-repository tables to FILE (format from extension)"),
             ("-v, --verbose", "Verbose output (debug level logging)"),
             ("-q, --quiet", "Quiet output (errors only)"),
             ("--debug", "Debug output (trace level logging)"),
             ("--compact", "Display results in compact, one-line format suitable for CI/CD"),
             ("--color", "Force colored output even when redirected"),
             ("--no-color", "Disable colored output"),
+            ("--accessible", "Mark changes and severities with symbols and labels, not colour alone"),
+            ("--hyperlinks <WHEN>", "Link commits, files and issues in console tables: auto, always or never"),
             ("--log-format <FORMAT>", "Log format: text or json [default: text]"),
             ("--log-file <FILE>", "Log file path for file output"),
             ("--log-file-level <LEVEL>", "Log level for file output (independent of console level)"),
+            ("--log-filter <FILTER>", "Per-module log levels, e.g. scanner=debug,plugin=warn"),
+            ("--log-max-size <SIZE>", "Rotate the log file past this size (e.g. 10MB)"),
+            ("--log-keep <COUNT>", "Number of rotated log files to keep [default: 5]"),
             ("--config-file <FILE>", "Configuration file path"),
-            ("--list-formats", "List all supported export formats and their file extensions"),
             ("--export-config <FILE>", "Export complete configuration to specified TOML file"),
             ("-h, --help", "Print help information"),
             ("-V, --version", "Print version information"),
@@ -287,12 +298,13 @@ impl HelpFormatter {
         let options = vec![
             ("-S, --since <DATE>", "Start date filter (ISO 8601 or relative like '1 week ago')"),
             ("-U, --until <DATE>", "End date filter (ISO 8601 or relative like 'yesterday')"),
-            ("-I, --include-path <PATH>", "Include specific paths (supports comma-separated)"),
-            ("-X, --exclude-path <PATH>", "Exclude specific paths (supports comma-separated)"),
+            ("-I, --path <GLOB>", "Include paths matching a .gitignore-style glob (alias --include-path)"),
+            ("-X, --exclude-path <GLOB>", "Exclude paths matching a .gitignore-style glob"),
+            ("--preset <NAME>", "Exclude an ecosystem's build output and dependencies, weight down generated files: rust, node, python"),
             ("-F, --include-file <PATTERN>", "Include file patterns (supports comma-separated)"),
             ("--exclude-file <PATTERN>", "Skip files matching these patterns"),
-            ("--author <AUTHOR>", "Filter commits by author name or email"),
-            ("--exclude-author <AUTHOR>", "Exclude commits by author name or email"),
+            ("-A, --author <AUTHOR>", "Include authors by name or email substring, or /REGEX/"),
+            ("-E, --exclude-author <AUTHOR>", "Exclude authors by name or email substring, or /REGEX/"),
             ("--limit <N>", "Maximum number of commits to scan from repository"),
         ];
         
@@ -324,6 +336,10 @@ impl HelpFormatter {
             ("--no-performance-mode", "Disable performance mode (prioritize memory over speed)"),
             ("--max-memory <SIZE>", "Maximum memory usage (supports units: MB, GB, K, T)"),
             ("--queue-size <N>", "Queue size for scanner operations"),
+            ("--backpressure <POLICY>", "Queue policy at the memory limit: block, drop-oldest, spill-to-disk"),
+            ("--duplicate-min-block <N>", "Smallest block reported as duplicated code"),
+            ("--duplicate-hashing <MODE>", "Hash blocks of lines or tokens for duplicate detection"),
+            ("--worker-threads <N>", "Worker threads for the async runtime (default: CPU cores)"),
         ];
         
         for (option, desc) in options {
@@ -341,6 +357,7 @@ impl HelpFormatter {
             ("--plugin-directory <DIR>", "Plugin directory path for external plugin discovery"),
             ("--plugin-load <LIST>", "Comma-separated list of plugins to load explicitly"),
             ("--plugin-exclude <LIST>", "Comma-separated list of plugins to exclude"),
+            ("--plugin-timeout <SECONDS>", "Cancel consumer plugin calls that run longer than this (0 disables)"),
         ];
         
         for (option, desc) in config_options {
@@ -350,11 +367,12 @@ impl HelpFormatter {
         
         // Plugin discovery and information options
         let discovery_options = vec![
-            ("--list-plugins", "List all available plugins"),
-            ("--plugins", "Show all plugins with functions and descriptions"),
-            ("--plugins-help", "Show detailed plugin functions and command mappings"),
-            ("--plugin-info <PLUGIN>", "Show detailed information about specific plugin"),
+            ("plugins list", "List plugins with their versions, API compatibility and functions"),
+            ("plugins info <PLUGIN>", "Show detailed information about specific plugin"),
+            ("plugins formats", "List export formats and the file extensions selecting them"),
+            ("plugins doctor", "Check plugin directories, API compatibility and function names"),
             ("--check-plugin <PLUGIN>", "Check plugin compatibility with current API"),
+            ("--json", "Print plugins commands and --show-branch as JSON"),
         ];
         
         for (option, desc) in discovery_options {
@@ -385,7 +403,7 @@ impl HelpFormatter {
             ("gstats --show-branch", "Show which branch would be scanned"),
             ("gstats -b develop commits", "Scan specific branch"),
             ("gstats --remote upstream commits", "Use specific remote for branch detection"),
-            ("gstats --plugins", "Show detailed plugin information"),
+            ("gstats plugins", "Show plugins and their functions"),
         ];
         
         for (command, desc) in examples {
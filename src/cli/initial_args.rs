@@ -13,7 +13,7 @@ use std::path::PathBuf;
 /// This parser uses clap's derive API but with settings that allow
 /// it to ignore unknown arguments gracefully. Only configuration-related
 /// arguments are captured here.
-#[derive(Parser, Debug, Clone)]
+#[derive(Parser, Debug, Clone, Default)]
 #[command(name = "gstats")]
 #[command(disable_help_flag = true)]  // We'll handle help ourselves
 #[command(disable_version_flag = true)]  // We'll handle version ourselves
@@ -50,6 +50,10 @@ pub struct InitialArgs {
     /// Disable colored output (overrides configuration and NO_COLOR)
     #[arg(long = "no-color", action = ArgAction::SetTrue)]
     pub no_color: bool,
+    
+    /// Worker threads for the shared async runtime
+    #[arg(long = "worker-threads", value_name = "N")]
+    pub worker_threads: Option<usize>,
 }
 
 impl InitialArgs {
@@ -104,6 +108,11 @@ impl InitialArgs {
                 .long("no-color")
                 .action(clap::ArgAction::SetTrue)
                 .help("Disable colored output"))
+            .arg(clap::Arg::new("worker-threads")
+                .long("worker-threads")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Worker threads for the shared async runtime"))
             .allow_external_subcommands(true)
             .ignore_errors(true);
         
@@ -127,6 +136,7 @@ impl InitialArgs {
             version_requested: matches.get_flag("version"),
             color: matches.get_flag("color"),
             no_color: matches.get_flag("no-color"),
+            worker_threads: matches.get_one::<usize>("worker-threads").copied(),
         }
     }
     
@@ -141,6 +151,7 @@ impl InitialArgs {
             version_requested: false,
             color: false,
             no_color: false,
+            worker_threads: None,
         }
     }
     
@@ -265,4 +276,17 @@ mod tests {
         assert_eq!(initial.config_file, None);
         assert!(!initial.help_requested);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_worker_threads() {
+        let args = vec![
+            "gstats".to_string(),
+            "--worker-threads".to_string(),
+            "2".to_string(),
+            "commits".to_string(),
+        ];
+        
+        let initial = InitialArgs::parse_from_args(&args);
+        assert_eq!(initial.worker_threads, Some(2));
+    }
+}
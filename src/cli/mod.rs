@@ -13,6 +13,8 @@ pub mod help_formatter;
 pub mod plugin_args;
 pub mod initial_args;
 pub mod command_segmenter;
+pub mod plugins_command;
+pub mod trends_command;
 
 #[cfg(test)]
 pub mod tests;
@@ -4,6 +4,7 @@
 
 use crate::plugin::{
     registry::SharedPluginRegistry, 
+    compatibility::{ApiCompatibility, VersionCompatibilityChecker},
     discovery::{PluginDiscovery, UnifiedPluginDiscovery},
     traits::{PluginDescriptor, PluginType, PluginFunction},
     error::{PluginError, PluginResult}
@@ -13,6 +14,7 @@ use crate::cli::command_mapper::{CommandMapper, CommandResolution};
 use std::path::{Path, PathBuf};
 use anyhow::Result;
 use log::debug;
+use serde::Serialize;
 
 /// CLI Plugin Handler for managing plugin operations
 pub struct PluginHandler {
@@ -147,17 +149,7 @@ impl PluginHandler {
         let descriptors = self.discover_plugins().await?;
         
         let mut plugins: Vec<PluginInfo> = descriptors.into_iter()
-            .map(|desc| PluginInfo {
-                name: desc.info.name.clone(),
-                version: desc.info.version.clone(),
-                plugin_type: desc.info.plugin_type.clone(),
-                description: desc.info.description.clone(),
-                author: desc.info.author.clone(),
-                file_path: desc.file_path.clone(),
-                capabilities: desc.info.capabilities.iter()
-                    .map(|cap| cap.name.clone())
-                    .collect(),
-            })
+            .map(|desc| PluginInfo::from(&desc))
             .collect();
         
         // Sort by name for consistent output
@@ -173,17 +165,7 @@ impl PluginHandler {
         
         for descriptor in descriptors {
             if descriptor.info.name == plugin_name {
-                return Ok(Some(PluginInfo {
-                    name: descriptor.info.name.clone(),
-                    version: descriptor.info.version.clone(),
-                    plugin_type: descriptor.info.plugin_type.clone(),
-                    description: descriptor.info.description.clone(),
-                    author: descriptor.info.author.clone(),
-                    file_path: descriptor.file_path.clone(),
-                    capabilities: descriptor.info.capabilities.iter()
-                        .map(|cap| cap.name.clone())
-                        .collect(),
-                }));
+                return Ok(Some(PluginInfo::from(&descriptor)));
             }
         }
         
@@ -195,17 +177,7 @@ impl PluginHandler {
         let descriptors = self.discovery.discover_plugins_by_type(plugin_type)?;
         
         let plugins = descriptors.into_iter()
-            .map(|desc| PluginInfo {
-                name: desc.info.name.clone(),
-                version: desc.info.version.clone(),
-                plugin_type: desc.info.plugin_type.clone(),
-                description: desc.info.description.clone(),
-                author: desc.info.author.clone(),
-                file_path: desc.file_path.clone(),
-                capabilities: desc.info.capabilities.iter()
-                    .map(|cap| cap.name.clone())
-                    .collect(),
-            })
+            .map(|desc| PluginInfo::from(&desc))
             .collect();
         
         Ok(plugins)
@@ -296,7 +268,7 @@ impl PluginHandler {
 }
 
 /// Plugin information for CLI display
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PluginInfo {
     pub name: String,
     pub version: String,
@@ -305,11 +277,33 @@ pub struct PluginInfo {
     pub author: String,
     pub file_path: Option<PathBuf>,
     pub capabilities: Vec<String>,
+    pub api_version: u32,
+    /// Result of negotiating the plugin's API version with this build
+    pub compatibility: ApiCompatibility,
+}
+
+impl From<&PluginDescriptor> for PluginInfo {
+    fn from(descriptor: &PluginDescriptor) -> Self {
+        let checker = VersionCompatibilityChecker::default();
+        Self {
+            name: descriptor.info.name.clone(),
+            version: descriptor.info.version.clone(),
+            plugin_type: descriptor.info.plugin_type.clone(),
+            description: descriptor.info.description.clone(),
+            author: descriptor.info.author.clone(),
+            file_path: descriptor.file_path.clone(),
+            capabilities: descriptor.info.capabilities.iter()
+                .map(|cap| cap.name.clone())
+                .collect(),
+            api_version: descriptor.info.api_version,
+            compatibility: checker.negotiate(descriptor.info.api_version),
+        }
+    }
 }
 
 
 /// Function mapping information for plugin-help display
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FunctionMapping {
     pub function_name: String,
     pub aliases: Vec<String>,
@@ -348,6 +342,7 @@ mod tests {
             file_path: None,
             entry_point: "main".to_string(),
             config: HashMap::new(),
+            functions: Vec::new(),
         };
         
         let yaml_content = serde_yaml::to_string(&descriptor).unwrap();
@@ -364,6 +359,16 @@ mod tests {
         assert_eq!(handler.discovery.plugin_directory(), temp_dir.path());
     }
 
+    /// Number of builtin plugins, as found alongside an empty plugin directory
+    async fn builtin_count(plugin_type: Option<PluginType>) -> usize {
+        let empty_dir = tempdir().unwrap();
+        let handler = PluginHandler::with_plugin_directory(empty_dir.path()).unwrap();
+        match plugin_type {
+            Some(plugin_type) => handler.get_plugins_by_type(plugin_type).await.unwrap().len(),
+            None => handler.discover_plugins().await.unwrap().len(),
+        }
+    }
+
     #[tokio::test]
     async fn test_discover_plugins() {
         let temp_dir = tempdir().unwrap();
@@ -375,7 +380,7 @@ mod tests {
         let handler = PluginHandler::with_plugin_directory(temp_dir.path()).unwrap();
         let plugins = handler.discover_plugins().await.unwrap();
         
-        assert_eq!(plugins.len(), 6); // 4 builtin + 2 external
+        assert_eq!(plugins.len(), builtin_count(None).await + 2); // builtin + 2 external
         
         let names: Vec<String> = plugins.iter().map(|p| p.info.name.clone()).collect();
         assert!(names.contains(&"test-scanner".to_string()));
@@ -392,7 +397,7 @@ mod tests {
         let handler = PluginHandler::with_plugin_directory(temp_dir.path()).unwrap();
         let plugins = handler.list_plugins().await.unwrap();
         
-        assert_eq!(plugins.len(), 6); // 4 builtin + 2 external
+        assert_eq!(plugins.len(), builtin_count(None).await + 2); // builtin + 2 external
         
         // Should be sorted by name - check that external plugins are in the mix
         let plugin_names: Vec<&str> = plugins.iter().map(|p| p.name.as_str()).collect();
@@ -432,7 +437,7 @@ mod tests {
         let handler = PluginHandler::with_plugin_directory(temp_dir.path()).unwrap();
         let scanners = handler.get_plugins_by_type(PluginType::Processing).await.unwrap();
         
-        assert_eq!(scanners.len(), 5); // 2 external + 3 builtin Processing (debug, commits, metrics)
+        assert_eq!(scanners.len(), builtin_count(Some(PluginType::Processing)).await + 2); // builtin Processing + 2 external
         for plugin in &scanners {
             assert_eq!(plugin.plugin_type, PluginType::Processing);
         }
@@ -479,12 +484,13 @@ mod tests {
             ],
             plugin_load: Vec::new(),
             plugin_exclude: Vec::new(),
+            timeout: None,
         };
         
         let handler = PluginHandler::with_plugin_config(config).unwrap();
         let plugins = handler.discover_plugins().await.unwrap();
         
-        assert_eq!(plugins.len(), 5); // 4 builtin + 1 external (only first directory used)
+        assert_eq!(plugins.len(), builtin_count(None).await + 1); // builtin + 1 external (only first directory used)
         let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
         assert!(plugin_names.contains(&"plugin1")); // From first directory
         // plugin2 should not be found since only first directory is used now
@@ -507,13 +513,14 @@ mod tests {
             directories: vec![temp_dir.path().to_string_lossy().to_string()],
             plugin_load: vec!["wanted".to_string()], // Only load 'wanted' - but this is no longer supported
             plugin_exclude: Vec::new(),
+            timeout: None,
         };
         
         let handler = PluginHandler::with_plugin_config(config).unwrap();
         let plugins = handler.discover_plugins().await.unwrap();
         
         // Note: explicit loading (plugin_load) is no longer supported, so this finds all plugins
-        assert_eq!(plugins.len(), 6); // 4 builtin + 2 external
+        assert_eq!(plugins.len(), builtin_count(None).await + 2); // builtin + 2 external
         let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
         assert!(plugin_names.contains(&"wanted"));
         assert!(plugin_names.contains(&"unwanted")); // Not filtered out anymore
@@ -534,12 +541,13 @@ mod tests {
             directories: vec![temp_dir.path().to_string_lossy().to_string()],
             plugin_load: Vec::new(),
             plugin_exclude: vec!["unwanted".to_string()], // Exclude 'unwanted'
+            timeout: None,
         };
         
         let handler = PluginHandler::with_plugin_config(config).unwrap();
         let plugins = handler.discover_plugins().await.unwrap();
         
-        assert_eq!(plugins.len(), 5); // 4 builtin + 1 external (unwanted excluded)
+        assert_eq!(plugins.len(), builtin_count(None).await + 1); // builtin + 1 external (unwanted excluded)
         let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
         assert!(plugin_names.contains(&"wanted"));
         assert!(!plugin_names.contains(&"unwanted")); // Should be excluded
@@ -556,6 +564,7 @@ mod tests {
             directories: vec![temp_dir.path().to_string_lossy().to_string()],
             plugin_load: Vec::new(),
             plugin_exclude: vec!["commits".to_string(), "export".to_string()], // Exclude built-in plugins
+            timeout: None,
         };
         
         let handler = PluginHandler::with_plugin_config(config).unwrap();
@@ -0,0 +1,102 @@
+//! Plugin Management Commands
+//!
+//! `gstats plugins [list | info <PLUGIN> | formats | doctor]` inspects the
+//! installed plugins instead of scanning a repository. The older discovery
+//! flags (`--list-plugins`, `--plugins`, `--plugins-help`, `--plugin-info` and
+//! `--list-formats`) are kept as hidden aliases for these commands.
+
+use crate::cli::Args;
+
+/// A `gstats plugins` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginsCommand {
+    /// Table of plugins with their versions, API compatibility and functions
+    List,
+    /// Details of one plugin
+    Info(String),
+    /// Export formats with their file extensions
+    Formats,
+    /// Health checks of plugin discovery and compatibility
+    Doctor,
+}
+
+/// Error parsing a `gstats plugins` command line
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PluginsCommandError {
+    #[error("Unknown plugins command '{0}' (expected list, info <PLUGIN>, formats or doctor)")]
+    Unknown(String),
+    #[error("'gstats plugins info' needs a plugin name")]
+    MissingPlugin,
+}
+
+impl PluginsCommand {
+    /// Take `plugins [COMMAND [PLUGIN]]` off the front of the command line
+    ///
+    /// Returns `None`, leaving the arguments alone, unless the first argument is
+    /// `plugins`. Options (such as `--json`) are left in place for clap.
+    pub fn extract(raw_args: &mut Vec<String>) -> Result<Option<Self>, PluginsCommandError> {
+        if raw_args.first().map(String::as_str) != Some("plugins") {
+            return Ok(None);
+        }
+        raw_args.remove(0);
+
+        let position = raw_args.iter().position(|arg| !arg.starts_with('-'));
+        let Some(position) = position else {
+            return Ok(Some(Self::List));
+        };
+        let command = raw_args.remove(position);
+        let parsed = match command.as_str() {
+            "list" | "ls" => Self::List,
+            "formats" => Self::Formats,
+            "doctor" => Self::Doctor,
+            "info" => {
+                let position = raw_args.iter().position(|arg| !arg.starts_with('-'))
+                    .ok_or(PluginsCommandError::MissingPlugin)?;
+                Self::Info(raw_args.remove(position))
+            }
+            _ => return Err(PluginsCommandError::Unknown(command)),
+        };
+        Ok(Some(parsed))
+    }
+
+    /// Command selected by the legacy discovery flags, if any
+    pub fn from_flags(args: &Args) -> Option<Self> {
+        if let Some(plugin) = &args.plugin_info {
+            Some(Self::Info(plugin.clone()))
+        } else if args.list_formats {
+            Some(Self::Formats)
+        } else if args.list_plugins || args.show_plugins || args.plugins_help {
+            Some(Self::List)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(args: &[&str]) -> (Result<Option<PluginsCommand>, PluginsCommandError>, Vec<String>) {
+        let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let command = PluginsCommand::extract(&mut args);
+        (command, args)
+    }
+
+    #[test]
+    fn test_extract_subcommands() {
+        assert_eq!(extract(&["plugins"]), (Ok(Some(PluginsCommand::List)), vec![]));
+        assert_eq!(extract(&["plugins", "--json"]), (Ok(Some(PluginsCommand::List)), vec!["--json".to_string()]));
+        assert_eq!(extract(&["plugins", "doctor", "--no-color"]), (Ok(Some(PluginsCommand::Doctor)), vec!["--no-color".to_string()]));
+        assert_eq!(extract(&["plugins", "--json", "info", "commits"]).0, Ok(Some(PluginsCommand::Info("commits".to_string()))));
+        assert_eq!(extract(&["plugins", "formats"]).0, Ok(Some(PluginsCommand::Formats)));
+        // Anything else is a scan
+        assert_eq!(extract(&["commits", "plugins"]), (Ok(None), vec!["commits".to_string(), "plugins".to_string()]));
+    }
+
+    #[test]
+    fn test_extract_errors() {
+        assert_eq!(extract(&["plugins", "info"]).0, Err(PluginsCommandError::MissingPlugin));
+        assert_eq!(extract(&["plugins", "remove"]).0, Err(PluginsCommandError::Unknown("remove".to_string())));
+    }
+}
@@ -2,10 +2,24 @@
 
 use crate::cli::plugin_handler::PluginHandler;
 use crate::plugin::SharedPluginRegistry;
-use crate::app::initialization::initialize_plugins_via_discovery;
+use crate::app::initialization::{initialize_plugins_via_discovery, PluginSetup};
 use crate::display::ColourManager;
 
-#[tokio::test]
+/// Plugin services and a default scan for initializing the builtin plugins
+fn plugin_setup() -> PluginSetup {
+    use crate::notifications::AsyncNotificationManager;
+    use crate::notifications::typed_publishers::PluginEventPublisher;
+    use std::sync::Arc;
+
+    PluginSetup {
+        plugin_publisher: Arc::new(PluginEventPublisher::new(Arc::new(AsyncNotificationManager::new()))),
+        plugin_notifications: Arc::new(AsyncNotificationManager::new()),
+        scanner_config: Arc::new(crate::scanner::ScannerConfig::default()),
+        query_params: Arc::new(crate::scanner::QueryParams::default()),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
 async fn test_plugin_handler_uses_registry_not_new_instances() {
     // This test expects PluginHandler to use plugins from the registry
     // instead of creating new instances
@@ -13,7 +27,7 @@ async fn test_plugin_handler_uses_registry_not_new_instances() {
     // Initialize builtin plugins in a registry
     let registry = SharedPluginRegistry::new();
     let colour_manager = ColourManager::from_color_args(false, false, None);
-    initialize_plugins_via_discovery(&registry, &colour_manager, Vec::new()).await.unwrap();
+    initialize_plugins_via_discovery(&registry, &colour_manager, None, Vec::new(), plugin_setup()).unwrap();
     
     // Create PluginHandler with the same registry
     let mut handler = PluginHandler::with_registry(registry.clone()).unwrap();
@@ -31,23 +45,24 @@ async fn test_plugin_handler_uses_registry_not_new_instances() {
     
     // Note: build_command_mappings includes ALL registered plugins for command routing,
     // not just active ones. This allows plugin-specific help to work even for inactive plugins.
-    // All 4 builtin plugins should be in command mappings
+    // Every builtin plugin should be in command mappings
     assert!(plugin_names.contains("export"), "Export plugin should appear in command mappings");
     assert!(plugin_names.contains("debug"), "Debug plugin should appear in command mappings");
     assert!(plugin_names.contains("commits"), "Commits plugin should appear in command mappings");
     assert!(plugin_names.contains("metrics"), "Metrics plugin should appear in command mappings");
     
     // The command mappings include all plugins to enable help functionality
-    assert_eq!(plugin_names.len(), 4, "All 4 builtin plugins should be in command mappings");
+    let registered = registry.inner().read().await.plugin_count();
+    assert_eq!(plugin_names.len(), registered, "All builtin plugins should be in command mappings");
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_command_resolution_without_plugin_duplication() {
     // This test expects command resolution to work without creating duplicate plugins
     
     let registry = SharedPluginRegistry::new();
     let colour_manager = ColourManager::from_color_args(false, false, None);
-    initialize_plugins_via_discovery(&registry, &colour_manager, Vec::new()).await.unwrap();
+    initialize_plugins_via_discovery(&registry, &colour_manager, None, Vec::new(), plugin_setup()).unwrap();
     
     // Manually activate commits plugin for this test
     {
@@ -68,7 +83,7 @@ async fn test_command_resolution_without_plugin_duplication() {
     match export_resolution {
         crate::cli::command_mapper::CommandResolution::Function { plugin_name, function_name, .. } => {
             assert_eq!(plugin_name, "export");
-            assert_eq!(function_name, "output"); // Default function
+            assert_eq!(function_name, "export"); // Default function
         }
         crate::cli::command_mapper::CommandResolution::DirectPlugin { plugin_name, .. } => {
             assert_eq!(plugin_name, "export");
@@ -83,13 +98,13 @@ async fn test_command_resolution_without_plugin_duplication() {
     assert!(commits_resolution.is_ok(), "Commits command should resolve (manually activated)");
 }
 
-#[tokio::test] 
+#[tokio::test(flavor = "multi_thread")]
 async fn test_inactive_plugins_not_in_command_mappings() {
     // This test expects that only ACTIVE plugins appear in command mappings
     
     let registry = SharedPluginRegistry::new();
     let colour_manager = ColourManager::from_color_args(false, false, None);
-    initialize_plugins_via_discovery(&registry, &colour_manager, Vec::new()).await.unwrap();
+    initialize_plugins_via_discovery(&registry, &colour_manager, None, Vec::new(), plugin_setup()).unwrap();
     
     let mut handler = PluginHandler::with_registry(registry.clone()).unwrap();
     handler.build_command_mappings().await.unwrap();
@@ -101,8 +116,9 @@ async fn test_inactive_plugins_not_in_command_mappings() {
     
     // Note: build_command_mappings includes ALL registered plugins for command routing,
     // not just active ones. This allows plugin-specific help to work even for inactive plugins.
-    // All 4 builtin plugins should be in command mappings
-    assert_eq!(plugin_names.len(), 4, "All 4 builtin plugins should be in command mappings");
+    // Every builtin plugin should be in command mappings
+    let registered = registry.inner().read().await.plugin_count();
+    assert_eq!(plugin_names.len(), registered, "All builtin plugins should be in command mappings");
     assert!(plugin_names.contains("export"), "Export plugin should be in command mappings");
     assert!(plugin_names.contains("debug"), "Debug plugin should be in command mappings");
     assert!(plugin_names.contains("commits"), "Commits plugin should be in command mappings");
@@ -122,8 +138,8 @@ async fn test_inactive_plugins_not_in_command_mappings() {
         .map(|m| m.plugin_name.clone())
         .collect();
     
-    // Should still have the same 4 plugins (activation doesn't change command mappings)
-    assert_eq!(updated_plugin_names.len(), 4, "Should still have all 4 plugins in command mappings");
+    // Should still have the same plugins (activation doesn't change command mappings)
+    assert_eq!(updated_plugin_names, plugin_names, "Should still have all plugins in command mappings");
     assert!(updated_plugin_names.contains("export"));
     assert!(updated_plugin_names.contains("debug"));
     assert!(updated_plugin_names.contains("commits"));
@@ -0,0 +1,41 @@
+//! Trend Queries
+//!
+//! `gstats trends <METRIC>` lists a metric recorded with `--record-trends` over
+//! past runs. Metric names always contain a `.` (`commits.rows`,
+//! `hotspots.score.max`); without one, `trends` still selects the contributors
+//! plugin's trends function.
+
+/// Take `trends <METRIC>` off the front of the command line, returning the metric
+///
+/// Options (such as `--json` or `--repo`) are left in place for clap.
+pub fn extract(raw_args: &mut Vec<String>) -> Option<String> {
+    let is_query = raw_args.first().is_some_and(|arg| arg == "trends")
+        && raw_args.get(1).is_some_and(|metric| !metric.starts_with('-') && metric.contains('.'));
+    if !is_query {
+        return None;
+    }
+    let metric = raw_args.remove(1);
+    raw_args.remove(0);
+    Some(metric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract_from(args: &[&str]) -> (Option<String>, Vec<String>) {
+        let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let metric = extract(&mut args);
+        (metric, args)
+    }
+
+    #[test]
+    fn test_extract_metric() {
+        assert_eq!(extract_from(&["trends", "hotspots.score.max", "--json"]),
+            (Some("hotspots.score.max".to_string()), vec!["--json".to_string()]));
+        // The contributors plugin's trends function
+        assert_eq!(extract_from(&["trends", "--period", "week"]).0, None);
+        assert_eq!(extract_from(&["trends"]).0, None);
+        assert_eq!(extract_from(&["contributors", "trends.x"]).0, None);
+    }
+}
@@ -0,0 +1,32 @@
+//! Run Clock
+//!
+//! Results that depend on the current time - export timestamps, file ages,
+//! recency weighting and time windows - read it from this module rather than
+//! the system clock. With `--reproducible` the clock is fixed to the date of
+//! the latest scanned commit before the scan starts, so reruns over the same
+//! history produce identical results whatever the wall clock says.
+
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static FIXED: RwLock<Option<SystemTime>> = RwLock::new(None);
+
+/// Fix the clock at `time` for the rest of the run
+pub fn fix(time: SystemTime) {
+    *FIXED.write().unwrap_or_else(|e| e.into_inner()) = Some(time);
+}
+
+/// The fixed time, if the clock has been fixed
+pub fn fixed() -> Option<SystemTime> {
+    *FIXED.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Current time of the run: the fixed time, or the system clock
+pub fn now() -> SystemTime {
+    fixed().unwrap_or_else(SystemTime::now)
+}
+
+/// Current time of the run in seconds since the epoch
+pub fn now_seconds() -> i64 {
+    now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs() as i64)
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -6,7 +6,10 @@ use anyhow::{Context, Result};
 use toml::Value;
 use log::{debug, info};
 use crate::scanner::config::ScannerConfig;
-use crate::display::{ColourConfig, ColourTheme, ColourPalette};
+use crate::scanner::identity::IdentityRules;
+use crate::scanner::imports::ImportRules;
+use crate::display::{ColourConfig, ColourTheme, ColourPalette, Hyperlinks, HyperlinkMode};
+use crate::plugin::arg_presets::ArgPresets;
 
 /// Configuration storage - section_name -> key -> value
 pub type Configuration = HashMap<String, HashMap<String, String>>;
@@ -200,6 +203,46 @@ impl ConfigManager {
             config.default_remote = Some(default_remote.clone());
         }
         
+        // Handle git-dir setting (bare repositories and mirrors)
+        if let Some(git_dir) = self.get_path("scanner", "git-dir") {
+            config.git_dir = Some(git_dir);
+        }
+        
+        // Handle follow-renames setting (rename detection across history)
+        if let Some(follow_renames) = self.get_bool("scanner", "follow-renames")? {
+            config.follow_renames = follow_renames;
+        }
+        
+        // Handle backpressure setting (queue policy at the memory limit)
+        if let Some(policy) = self.get_value("scanner", "backpressure") {
+            config.backpressure = Some(crate::queue::BackpressurePolicy::parse(policy)
+                .with_context(|| format!("Invalid backpressure value in config: {} (expected block, drop-oldest or spill-to-disk)", policy))?);
+        }
+        
+        // Handle duplicate detection settings (content fingerprinting); the hashing mode sets the default block size
+        if let Some(mode) = self.get_value("scanner", "duplicate-hashing") {
+            let mode = crate::scanner::fingerprints::FingerprintMode::parse(mode)
+                .with_context(|| format!("Invalid duplicate-hashing value in config: {} (expected lines or tokens)", mode))?;
+            config.fingerprints = crate::scanner::fingerprints::FingerprintConfig::new(mode);
+        }
+        if let Some(min_block_str) = self.get_value("scanner", "duplicate-min-block") {
+            let min_block = min_block_str.parse::<usize>().ok().filter(|min_block| *min_block > 0)
+                .with_context(|| format!("Invalid duplicate-min-block value in config: {} (expected a positive number)", min_block_str))?;
+            config.fingerprints.min_block = min_block;
+        }
+        
+        // Handle encoding setting (decoding file content that is not UTF-8)
+        if let Some(encoding) = self.get_value("scanner", "encoding") {
+            config.encoding = crate::scanner::encoding::ContentEncoding::parse(encoding)
+                .with_context(|| format!("Invalid encoding value in config: {} (expected auto or an encoding such as windows-1252)", encoding))?;
+        }
+        
+        // Handle oversized-files setting (files over a plugin's size limit)
+        if let Some(policy) = self.get_value("scanner", "oversized-files") {
+            config.oversized_files = crate::scanner::file_policy::OversizedFiles::parse(policy)
+                .with_context(|| format!("Invalid oversized-files value in config: {} (expected skip or truncate)", policy))?;
+        }
+        
         // Validate final configuration
         config.validate()
             .with_context(|| "Scanner configuration validation failed")?;
@@ -239,9 +282,31 @@ impl ConfigManager {
             config.set_theme(theme);
         }
         
+        // Accessible mode marks changes and severities with symbols as well as colour
+        if let Some(accessible) = self.get_bool_root("accessible")? {
+            config.set_accessible(accessible);
+        }
+        
         Ok(config)
     }
     
+    /// Get when console tables carry hyperlinks, from the root-level `hyperlinks` key
+    pub fn get_hyperlink_mode(&self) -> Result<Option<HyperlinkMode>> {
+        self.get_value_root("hyperlinks")
+            .map(|value| HyperlinkMode::parse(value)
+                .ok_or_else(|| anyhow::anyhow!("Invalid hyperlinks value '{}' (expected auto, always or never)", value)))
+            .transpose()
+    }
+    
+    /// Get the URL templates of console table links from the `[links]` section
+    pub fn get_link_templates(&self) -> Hyperlinks {
+        Hyperlinks {
+            commit: self.get_value("links", "commit").cloned(),
+            file: self.get_value("links", "file").cloned(),
+            issue: self.get_value("links", "issue").cloned(),
+        }
+    }
+    
     /// Get custom colour palette from config file
     fn get_custom_colour_palette(&self) -> Result<ColourPalette> {
         let mut palette = ColourPalette::default();
@@ -292,6 +357,13 @@ impl ConfigManager {
         Ok(palette)
     }
     
+    /// Get the exclude presets applied to every scan
+    pub fn get_scanner_presets(&self) -> Vec<String> {
+        self.get_value("scanner", "presets")
+            .map(|s| self.parse_toml_array(s))
+            .unwrap_or_default()
+    }
+    
     /// Get plugin directory from configuration
     pub fn get_plugins_directory(&self) -> Option<&str> {
         self.get_value("plugins", "directory").map(|s| s.as_str())
@@ -311,6 +383,86 @@ impl ConfigManager {
             .unwrap_or_else(Vec::new)
     }
     
+    /// Get the deadline in seconds for each consumer plugin call (0 disables it)
+    pub fn get_plugins_timeout(&self) -> Option<u64> {
+        let value = self.get_value("plugins", "plugin-timeout")?;
+        value.parse().map_err(|_| {
+            log::warn!("Ignoring invalid plugin-timeout value in config: {}", value);
+        }).ok()
+    }
+    
+    /// Get the commits and paths whose authorship is credited to the import author
+    pub fn get_import_rules(&self) -> ImportRules {
+        let list = |key: &str| {
+            self.get_value("imports", key)
+                .map(|s| self.parse_toml_array(s))
+                .unwrap_or_default()
+        };
+        let mut rules = ImportRules {
+            commits: list("commits"),
+            paths: list("paths"),
+            ..Default::default()
+        };
+        if let Some(author) = self.get_value("imports", "author").filter(|a| !a.trim().is_empty()) {
+            rules.author = author.trim().to_string();
+        }
+        rules
+    }
+    
+    /// Get the author aliases and whether `.mailmap` is applied when resolving author identities
+    pub fn get_identity_rules(&self) -> IdentityRules {
+        let mut aliases: Vec<(String, Vec<String>)> = self.config.get("authors.aliases")
+            .map(|section| section.iter()
+                .map(|(canonical, merged)| (canonical.clone(), self.parse_toml_array(merged)))
+                .collect())
+            .unwrap_or_default();
+        aliases.sort();
+        
+        let use_mailmap = !self.get_value("authors", "mailmap")
+            .is_some_and(|value| matches!(value.to_lowercase().as_str(), "false" | "0" | "no" | "off"));
+        IdentityRules { aliases, use_mailmap }
+    }
+    
+    /// Get the named argument presets of each plugin from `[plugin.<name>.presets.<preset>]` sections,
+    /// and its default arguments from the options in `[plugin.<name>]`
+    pub fn get_plugin_arg_presets(&self) -> ArgPresets {
+        let mut presets = ArgPresets::new();
+        for (section, values) in &self.config {
+            let Some((plugin, preset)) = section.strip_prefix("plugin.").and_then(|rest| rest.split_once(".presets.")) else {
+                continue;
+            };
+            presets.insert(plugin, preset, values.iter().map(|(flag, value)| (flag.as_str(), value.as_str())));
+        }
+        for (plugin, options) in self.get_plugin_options() {
+            presets.insert_defaults(plugin, options);
+        }
+        presets
+    }
+    
+    /// Get the options of each `[plugin.<name>]` section, by plugin and flag name
+    fn get_plugin_options(&self) -> BTreeMap<&str, BTreeMap<&str, &str>> {
+        let mut options: BTreeMap<&str, BTreeMap<&str, &str>> = BTreeMap::new();
+        for (section, values) in &self.config {
+            let Some(rest) = section.strip_prefix("plugin.") else {
+                continue;
+            };
+            match rest.split_once('.') {
+                None => options.entry(rest).or_default()
+                    .extend(values.iter().map(|(flag, value)| (flag.as_str(), value.as_str()))),
+                // A plugin section that also holds presets is flattened one option per section
+                Some((plugin, flag)) if flag != "presets" && !flag.contains('.') => {
+                    if let Some(value) = values.get("value") {
+                        options.entry(plugin).or_default().insert(flag, value.as_str());
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        // Sections whose options are all commented out (as --export-config writes them) set nothing
+        options.retain(|_, options| !options.is_empty());
+        options
+    }
+    
     /// Parse TOML array string back to Vec<String>
     fn parse_toml_array(&self, toml_string: &str) -> Vec<String> {
         // Handle TOML array format stored as string: ["item1", "item2", "item3"]
@@ -347,52 +499,46 @@ impl ConfigManager {
     }
 
     /// Export complete configuration with all available options and their current values
-    /// This includes default values for options not explicitly set
-    pub fn export_complete_config(&self) -> Result<String> {
+    ///
+    /// Options that are not set are written commented out with their defaults, so the
+    /// file loads back to the same configuration and any option can be enabled in place.
+    /// `plugins` are the argument definitions of the plugins whose options are listed,
+    /// by plugin name; options configured for other plugins are kept as they are set.
+    pub fn export_complete_config(&self, plugins: &[(&str, clap::Command)]) -> Result<String> {
         let mut output = String::new();
+        let quoted = |value: &String| format!("{:?}", value);
         
         // Add header comment
         output.push_str("# Complete gstats configuration file\n");
-        output.push_str("# Generated with all available configuration options and their current values\n");
+        output.push_str("# Generated with all available configuration options and their current values;\n");
+        output.push_str("# options that are commented out show their defaults\n");
         output.push_str("# This file can be used as-is without any changes\n\n");
         
         // Root-level configuration keys (no [base] section)
-        if let Some(quiet) = self.get_value_root("quiet") {
-            output.push_str(&format!("quiet = {}\n", quiet));
-        } else {
-            output.push_str("# quiet = false\n");
-        }
-        
-        if let Some(log_format) = self.get_value_root("log-format") {
-            output.push_str(&format!("log-format = \"{}\"\n", log_format));
-        } else {
-            output.push_str("# log-format = \"text\"\n");
-        }
-        
-        if let Some(log_file) = self.get_value_root("log-file") {
-            output.push_str(&format!("log-file = \"{}\"\n", log_file));
-        } else {
-            output.push_str("# log-file = \"/path/to/log/file\"\n");
-        }
-        
-        if let Some(log_level) = self.get_value_root("log-level") {
-            output.push_str(&format!("log-level = \"{}\"\n", log_level));
-        } else {
-            output.push_str("# log-level = \"info\"\n");
-        }
+        write_setting(&mut output, "Only report errors", "quiet",
+            self.get_value_root("quiet").cloned(), "false");
+        write_setting(&mut output, "Log message format: text or json", "log-format",
+            self.get_value_root("log-format").map(quoted), "\"text\"");
+        write_setting(&mut output, "Also write log messages to this file", "log-file",
+            self.get_value_root("log-file").map(quoted), "\"/path/to/log/file\"");
+        write_setting(&mut output, "Log level: error, warn, info, debug or trace", "log-level",
+            self.get_value_root("log-level").map(quoted), "\"info\"");
+        write_setting(&mut output, "Per-module log levels", "log-filter",
+            self.get_value_root("log-filter").map(quoted), "\"scanner=debug,plugin=warn\"");
+        write_setting(&mut output, "Rotate the log file past this size", "log-max-size",
+            self.get_value_root("log-max-size").map(quoted), "\"10MB\"");
+        write_setting(&mut output, "Rotated log files kept", "log-keep",
+            self.get_value_root("log-keep").cloned(), &crate::logging::LogRotation::DEFAULT_KEEP.to_string());
         
         // Color configuration as root-level keys
-        if let Some(color) = self.get_value_root("color") {
-            output.push_str(&format!("color = {}\n", color));
-        } else {
-            output.push_str("# color = true\n");
-        }
-        
-        if let Some(theme) = self.get_value_root("theme") {
-            output.push_str(&format!("theme = \"{}\"\n", theme));
-        } else {
-            output.push_str("# theme = \"auto\"  # Options: auto, light, dark, custom\n");
-        }
+        write_setting(&mut output, "Colour output", "color",
+            self.get_value_root("color").cloned(), "true");
+        write_setting(&mut output, "Colour theme: auto, light, dark or custom (with colors below)", "theme",
+            self.get_value_root("theme").map(quoted), "\"auto\"");
+        write_setting(&mut output, "Mark changes and severities with symbols as well as colour", "accessible",
+            self.get_value_root("accessible").cloned(), "false");
+        write_setting(&mut output, "Link commits, files and issues in console tables: auto, always or never", "hyperlinks",
+            self.get_value_root("hyperlinks").map(quoted), "\"auto\"");
         
         // Colors as inline table format at root level
         let color_keys = [
@@ -416,6 +562,7 @@ impl ConfigManager {
             }
         }
         
+        output.push_str("# Colours of the custom theme\n");
         if has_custom_colors {
             output.push_str(&format!("colors = {{ {} }}\n", colors.join(", ")));
         } else {
@@ -424,90 +571,162 @@ impl ConfigManager {
         
         output.push('\n');
         
-        // Scanner configuration section
+        // Scanner configuration section, with the defaults the scanner itself uses
+        let defaults = ScannerConfig::default();
         output.push_str("[scanner]\n");
-        if let Some(max_memory) = self.get_value("scanner", "max-memory") {
-            output.push_str(&format!("max-memory = \"{}\"\n", max_memory));
-        } else {
-            output.push_str("# max-memory = \"64MB\"\n");
-        }
-        
-        if let Some(queue_size) = self.get_value("scanner", "queue-size") {
-            output.push_str(&format!("queue-size = {}\n", queue_size));
-        } else {
-            output.push_str("# queue-size = 1000\n");
-        }
-        
-        if let Some(max_threads) = self.get_value("scanner", "max-threads") {
-            output.push_str(&format!("max-threads = {}\n", max_threads));
-        } else {
-            output.push_str("# max-threads = 4\n");
-        }
-        
-        if let Some(performance_mode) = self.get_value("scanner", "performance-mode") {
-            output.push_str(&format!("performance-mode = {}\n", performance_mode));
-        } else {
-            output.push_str("# performance-mode = false\n");
-        }
+        write_setting(&mut output, "Memory available to the scan queue", "max-memory",
+            self.get_value("scanner", "max-memory").map(quoted),
+            &format!("\"{}MB\"", defaults.max_memory_bytes / (1024 * 1024)));
+        write_setting(&mut output, "Messages the scan queue holds", "queue-size",
+            self.get_value("scanner", "queue-size").cloned(), &defaults.queue_size.to_string());
+        write_setting(&mut output, "Worker threads (default: one per CPU)", "max-threads",
+            self.get_value("scanner", "max-threads").cloned(), "4");
+        write_setting(&mut output, "Larger queue and memory limits (256MB, 5000 messages), replacing the two above", "performance-mode",
+            self.get_value("scanner", "performance-mode").cloned(), "false");
+        write_setting(&mut output, "Queue policy at max-memory: block, drop-oldest or spill-to-disk (default: none)", "backpressure",
+            self.get_value("scanner", "backpressure").map(quoted), "\"block\"");
         
         // GS-75 Phase 4: Branch configuration settings
-        if let Some(default_branch) = self.get_value("scanner", "default-branch") {
-            output.push_str(&format!("default-branch = \"{}\"\n", default_branch));
-        } else {
-            output.push_str("# default-branch = \"develop\"\n");
-        }
-        
-        if let Some(fallbacks) = self.get_value("scanner", "branch-fallbacks") {
-            output.push_str(&format!("branch-fallbacks = {}\n", fallbacks));
-        } else {
-            output.push_str("# branch-fallbacks = [\"main\", \"master\", \"develop\", \"trunk\"]\n");
-        }
-        
-        if let Some(default_remote) = self.get_value("scanner", "default-remote") {
-            output.push_str(&format!("default-remote = \"{}\"\n", default_remote));
-        } else {
-            output.push_str("# default-remote = \"origin\"\n");
-        }
+        write_setting(&mut output, "Branch scanned when none is given", "default-branch",
+            self.get_value("scanner", "default-branch").map(quoted), "\"develop\"");
+        write_setting(&mut output, "Branches tried in order when the default branch does not exist", "branch-fallbacks",
+            self.get_value("scanner", "branch-fallbacks").cloned(), &format!("{:?}", defaults.branch_fallbacks));
+        write_setting(&mut output, "Remote used to find remote branches", "default-remote",
+            self.get_value("scanner", "default-remote").map(quoted), "\"origin\"");
+        write_setting(&mut output, "Start of the scanned history", "since",
+            self.get_value("scanner", "since").map(quoted), "\"90d\"");
+        write_setting(&mut output, "End of the scanned history", "until",
+            self.get_value("scanner", "until").map(quoted), "\"yesterday\"");
+        write_setting(&mut output, "Exclude presets applied to every scan", "presets",
+            self.get_value("scanner", "presets").cloned(), "[\"rust\"]");
+        write_setting(&mut output, "Git directory opened without discovery (bare repositories and mirrors)", "git-dir",
+            self.get_value("scanner", "git-dir").map(quoted), "\"/srv/git/project.git\"");
+        write_setting(&mut output, "Report changes under each file's current name across renames", "follow-renames",
+            self.get_value("scanner", "follow-renames").cloned(), &defaults.follow_renames.to_string());
+        write_setting(&mut output, "Duplicate detection: compare lines or tokens", "duplicate-hashing",
+            self.get_value("scanner", "duplicate-hashing").map(quoted), "\"lines\"");
+        write_setting(&mut output, "Lines (or tokens) in a duplicated block", "duplicate-min-block",
+            self.get_value("scanner", "duplicate-min-block").cloned(), &defaults.fingerprints.min_block.to_string());
+        write_setting(&mut output, "Encoding of files that are not UTF-8: auto to detect it, or a name such as windows-1252", "encoding",
+            self.get_value("scanner", "encoding").map(quoted), &format!("{:?}", defaults.encoding.name()));
+        write_setting(&mut output, "Files over a plugin's size limit: skip or truncate them for that plugin", "oversized-files",
+            self.get_value("scanner", "oversized-files").map(quoted), "\"skip\"");
         
         output.push('\n');
         
         // Plugins configuration section
         output.push_str("[plugins]\n");
-        if let Some(directory) = self.get_plugins_directory() {
-            output.push_str(&format!("directory = \"{}\"\n", directory));
-        } else {
-            output.push_str("# directory = \"plugins\"\n");
-        }
-        
+        write_setting(&mut output, "Directory searched for plugin libraries", "directory",
+            self.get_plugins_directory().map(|directory| format!("{:?}", directory)), "\"plugins\"");
         let plugin_load = self.get_plugins_load();
-        if !plugin_load.is_empty() {
-            output.push_str(&format!("plugin-load = {:?}\n", plugin_load));
-        } else {
-            output.push_str("# plugin-load = [\"plugin1\", \"plugin2\"]\n");
-        }
-        
+        write_setting(&mut output, "Plugins loaded explicitly", "plugin-load",
+            (!plugin_load.is_empty()).then(|| format!("{:?}", plugin_load)), "[\"plugin1\", \"plugin2\"]");
         let plugin_exclude = self.get_plugins_exclude();
-        if !plugin_exclude.is_empty() {
-            output.push_str(&format!("plugin-exclude = {:?}\n", plugin_exclude));
+        write_setting(&mut output, "Plugins left out, by name or path", "plugin-exclude",
+            (!plugin_exclude.is_empty()).then(|| format!("{:?}", plugin_exclude)), "[\"unwanted-plugin\"]");
+        write_setting(&mut output, "Deadline in seconds for each plugin call (0 disables it)", "plugin-timeout",
+            self.get_plugins_timeout().map(|timeout| timeout.to_string()), "60");
+        output.push('\n');
+        
+        // Imported code configuration section
+        let imports = self.get_import_rules();
+        output.push_str("[imports]\n");
+        write_setting(&mut output, "Commits (or ranges) whose changes were imported", "commits",
+            (!imports.commits.is_empty()).then(|| format!("{:?}", imports.commits)), "[\"..3f2c1a9\"]");
+        write_setting(&mut output, "Paths holding imported code", "paths",
+            (!imports.paths.is_empty()).then(|| format!("{:?}", imports.paths)), "[\"vendor/\", \"third_party/\"]");
+        write_setting(&mut output, "Author credited with imported code", "author",
+            self.get_value("imports", "author").map(quoted), "\"import\"");
+        output.push('\n');
+        
+        // Author identity configuration section
+        let identities = self.get_identity_rules();
+        output.push_str("[authors]\n");
+        output.push_str("# Apply the repository's .mailmap to author identities\n");
+        output.push_str(&format!("mailmap = {}\n\n", identities.use_mailmap));
+        if !identities.aliases.is_empty() {
+            output.push_str("[authors.aliases]\n");
+            for (canonical, merged) in &identities.aliases {
+                output.push_str(&format!("{:?} = {:?}\n", canonical, merged));
+            }
         } else {
-            output.push_str("# plugin-exclude = [\"unwanted-plugin\"]\n");
+            output.push_str("# [authors.aliases]\n");
+            output.push_str("# \"Jane Doe <jane@example.com>\" = [\"jdoe <jane@home.net>\"]\n");
         }
         output.push('\n');
         
-        // Module-specific configurations (example modules)
-        output.push_str("# Module-specific configurations\n");
-        output.push_str("# [module.commits]\n");
-        output.push_str("# since = \"30d\"\n");
-        output.push_str("# per-day = true\n");
-        output.push_str("# format = \"json\"\n\n");
+        // Console table link templates
+        output.push_str("[links]\n");
+        write_setting(&mut output, "Commit link, with a {hash} placeholder", "commit",
+            self.get_value("links", "commit").map(quoted), "\"https://git.example.com/project/commit/{hash}\"");
+        write_setting(&mut output, "File link, with a {path} placeholder", "file",
+            self.get_value("links", "file").map(quoted), "\"https://git.example.com/project/blob/main/{path}\"");
+        write_setting(&mut output, "Issue link, with a {number} placeholder", "issue",
+            self.get_value("links", "issue").map(quoted), "\"https://tracker.example.com/browse/PROJ-{number}\"");
+        output.push('\n');
+        
+        // Metric trend database
+        output.push_str("[trends]\n");
+        write_setting(&mut output, "Trend database (default: in the platform data directory)", "database",
+            self.get_value("trends", "database").map(quoted), "\".gstats/trends.db\"");
+        output.push('\n');
         
-        output.push_str("# [module.metrics]\n");
-        output.push_str("# complexity-threshold = 10\n");
-        output.push_str("# include-tests = false\n\n");
+        // Plugin option defaults: every option of each known plugin, then those set for other plugins
+        let mut plugin_options = self.get_plugin_options();
+        for (plugin, command) in plugins {
+            let mut options = plugin_options.remove(plugin).unwrap_or_default();
+            if let Some(about) = command.get_about() {
+                write_comment(&mut output, &about.to_string());
+            }
+            output.push_str(&format!("[plugin.{}]\n", plugin));
+            for arg in command.get_arguments() {
+                let Some(flag) = arg.get_long() else {
+                    continue;
+                };
+                if arg.is_hide_set() || matches!(flag, "help" | "version" | "preset" | "args-from") {
+                    continue;
+                }
+                if let Some(help) = arg.get_help() {
+                    write_comment(&mut output, &help.to_string());
+                }
+                match options.remove(flag) {
+                    Some(value) => write_plugin_flag(&mut output, flag, value),
+                    None => output.push_str(&format!("# {} = {}\n", flag, plugin_flag_default(arg))),
+                }
+            }
+            for (flag, value) in options {
+                write_plugin_flag(&mut output, flag, value);
+            }
+            output.push('\n');
+        }
+        for (plugin, options) in plugin_options {
+            output.push_str(&format!("[plugin.{}]\n", plugin));
+            for (flag, value) in options {
+                write_plugin_flag(&mut output, flag, value);
+            }
+            output.push('\n');
+        }
         
-        output.push_str("# [module.export]\n");
-        output.push_str("# default-format = \"json\"\n");
-        output.push_str("# output-file = \"gstats-report\"\n");
+        // Plugin argument preset sections
+        let mut preset_sections: Vec<&String> = self.config.keys()
+            .filter(|section| section.starts_with("plugin.") && section.contains(".presets."))
+            .collect();
+        preset_sections.sort();
+        if preset_sections.is_empty() {
+            output.push_str("# Named sets of plugin options, applied with --preset <name>\n");
+            output.push_str("# [plugin.metrics.presets.strict]\n");
+            output.push_str("# top = 10\n");
+            output.push_str("# complexity-weight = 0.6\n");
+        }
+        for section in preset_sections {
+            output.push_str(&format!("[{}]\n", section));
+            let mut values: Vec<_> = self.config[section].iter().collect();
+            values.sort();
+            for (flag, value) in values {
+                write_plugin_flag(&mut output, flag, value);
+            }
+            output.push('\n');
+        }
         
         Ok(output)
     }
@@ -588,6 +807,56 @@ fn flatten_toml_table(table: &toml::Table, prefix: String, config: &mut Configur
     }
 }
 
+/// Write a plugin flag value: numbers, booleans and arrays as they were read, anything else as a string
+fn write_plugin_flag(output: &mut String, flag: &str, value: &str) {
+    if crate::plugin::arg_presets::parse_toml_value(value).is_some() {
+        output.push_str(&format!("{} = {}\n", flag, value));
+    } else {
+        output.push_str(&format!("{} = {:?}\n", flag, value));
+    }
+}
+
+/// Write text as comment lines
+fn write_comment(output: &mut String, text: &str) {
+    for line in text.lines() {
+        output.push_str(format!("# {}", line).trim_end());
+        output.push('\n');
+    }
+}
+
+/// Write a documented setting: its value when set, otherwise its default commented out
+fn write_setting(output: &mut String, description: &str, key: &str, value: Option<String>, default: &str) {
+    output.push_str(&format!("# {}\n", description));
+    match value {
+        Some(value) => output.push_str(&format!("{} = {}\n", key, value)),
+        None => output.push_str(&format!("# {} = {}\n", key, default)),
+    }
+}
+
+/// Default of a plugin option as written in configuration: `false` for switches,
+/// otherwise its default value, or the name of its value when it has none
+fn plugin_flag_default(arg: &clap::Arg) -> String {
+    if !arg.get_action().takes_values() {
+        return "false".to_string();
+    }
+    let value = |value: &str| if crate::plugin::arg_presets::parse_toml_value(value).is_some() {
+        value.to_string()
+    } else {
+        format!("{:?}", value)
+    };
+    let defaults: Vec<String> = arg.get_default_values().iter()
+        .map(|default| value(&default.to_string_lossy()))
+        .collect();
+    match defaults.as_slice() {
+        [] => {
+            let name = arg.get_value_names().and_then(|names| names.first()).map_or("VALUE", |name| name.as_str());
+            format!("{:?}", name)
+        }
+        [default] => default.clone(),
+        _ => format!("[{}]", defaults.join(", ")),
+    }
+}
+
 /// Convert TOML Value to string representation
 fn toml_value_to_string(value: &Value) -> String {
     match value {
@@ -652,6 +921,7 @@ format = "json"
 directory = "/custom/plugins"
 plugin-load = ["plugin1", "plugin2"]
 plugin-exclude = ["plugin3", "plugin4"]
+plugin-timeout = 300
 "#;
         
         let config = parse_toml_config(toml_content).unwrap();
@@ -671,6 +941,8 @@ plugin-exclude = ["plugin3", "plugin4"]
         assert_eq!(plugin_exclude.len(), 2);
         assert!(plugin_exclude.contains(&"plugin3".to_string()));
         assert!(plugin_exclude.contains(&"plugin4".to_string()));
+        
+        assert_eq!(manager.get_plugins_timeout(), Some(300));
     }
 
     #[test]
@@ -680,6 +952,7 @@ plugin-exclude = ["plugin3", "plugin4"]
         assert_eq!(manager.get_plugins_directory(), None);
         assert!(manager.get_plugins_load().is_empty());
         assert!(manager.get_plugins_exclude().is_empty());
+        assert_eq!(manager.get_plugins_timeout(), None);
     }
 
     #[test]
@@ -939,6 +1212,106 @@ branch-fallbacks = []
         assert_eq!(scanner_config.branch_fallbacks, Vec::<String>::new());
     }
 
+    #[test]
+    fn test_scanner_config_git_dir() {
+        let toml_content = r#"
+[scanner]
+git-dir = "/srv/git/project.git"
+"#;
+        
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+        
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        let scanner_config = manager.get_scanner_config().unwrap();
+        
+        assert_eq!(scanner_config.git_dir, Some(PathBuf::from("/srv/git/project.git")));
+    }
+
+    #[test]
+    fn test_scanner_config_follow_renames() {
+        let toml_content = r#"
+[scanner]
+follow-renames = false
+"#;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert!(!manager.get_scanner_config().unwrap().follow_renames);
+        assert!(ScannerConfig::default().follow_renames);
+    }
+
+    #[test]
+    fn test_scanner_config_backpressure() {
+        let toml_content = r#"
+[scanner]
+backpressure = "spill-to-disk"
+"#;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert_eq!(manager.get_scanner_config().unwrap().backpressure, Some(crate::queue::BackpressurePolicy::SpillToDisk));
+        assert_eq!(ScannerConfig::default().backpressure, None);
+
+        fs::write(&temp_file, "[scanner]\nbackpressure = \"discard\"\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert!(manager.get_scanner_config().is_err());
+    }
+
+    #[test]
+    fn test_scanner_config_duplicate_detection() {
+        let toml_content = r#"
+[scanner]
+duplicate-hashing = "tokens"
+"#;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        let fingerprints = manager.get_scanner_config().unwrap().fingerprints;
+        assert_eq!(fingerprints.mode, crate::scanner::fingerprints::FingerprintMode::Tokens);
+        assert_eq!(fingerprints.min_block, 50);
+
+        fs::write(&temp_file, "[scanner]\nduplicate-min-block = 10\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert_eq!(manager.get_scanner_config().unwrap().fingerprints.min_block, 10);
+
+        fs::write(&temp_file, "[scanner]\nduplicate-min-block = 0\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert!(manager.get_scanner_config().is_err());
+    }
+
+    #[test]
+    fn test_scanner_config_encoding() {
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, "[scanner]\nencoding = \"latin1\"\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert_eq!(manager.get_scanner_config().unwrap().encoding.name(), "windows-1252");
+
+        fs::write(&temp_file, "[scanner]\nencoding = \"klingon\"\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert!(manager.get_scanner_config().is_err());
+    }
+
+    #[test]
+    fn test_scanner_config_oversized_files() {
+        use crate::scanner::file_policy::OversizedFiles;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, "[scanner]\noversized-files = \"truncate\"\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert_eq!(manager.get_scanner_config().unwrap().oversized_files, OversizedFiles::Truncate);
+
+        fs::write(&temp_file, "[scanner]\noversized-files = \"drop\"\n").unwrap();
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert!(manager.get_scanner_config().is_err());
+    }
+
     #[test]
     fn test_scanner_config_mixed_branch_and_performance() {
         let toml_content = r#"
@@ -978,7 +1351,7 @@ max-memory = "128MB"
         fs::write(&temp_file, toml_content).unwrap();
         
         let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
-        let exported = manager.export_complete_config().unwrap();
+        let exported = manager.export_complete_config(&[]).unwrap();
         
         // Check that branch settings are present in export
         assert!(exported.contains("default-branch = \"develop\""));
@@ -1039,6 +1412,25 @@ theme = "light"
         assert_eq!(colour_config.theme, ColourTheme::Light);
     }
 
+    #[test]
+    fn test_hyperlink_config_from_toml() {
+        let toml_content = r#"
+hyperlinks = "always"
+
+[links]
+issue = "https://tracker.example.com/browse/PROJ-{number}"
+"#;
+        
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+        
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        assert_eq!(manager.get_hyperlink_mode().unwrap(), Some(HyperlinkMode::Always));
+        let links = manager.get_link_templates();
+        assert_eq!(links.issue.as_deref(), Some("https://tracker.example.com/browse/PROJ-{number}"));
+        assert_eq!(links.commit, None);
+    }
+
     #[test]
     fn test_colour_config_custom_theme() {
         let toml_content = r#"
@@ -1151,7 +1543,7 @@ color = false
             selected_section: None,
         };
         
-        let exported = manager.export_complete_config().unwrap();
+        let exported = manager.export_complete_config(&[]).unwrap();
         
         // Check that it contains expected sections (no [base] section, just [scanner] and [plugins])
         assert!(exported.contains("[scanner]"));
@@ -1184,7 +1576,7 @@ queue-size = 2000
         fs::write(&temp_file, toml_content).unwrap();
         
         let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
-        let exported = manager.export_complete_config().unwrap();
+        let exported = manager.export_complete_config(&[]).unwrap();
         
         // Check that actual values are present (not commented)
         assert!(exported.contains("quiet = true"));
@@ -1206,7 +1598,7 @@ colors = { error = "bright_red", warning = "bright_yellow" }
         fs::write(&temp_file, toml_content).unwrap();
         
         let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
-        let exported = manager.export_complete_config().unwrap();
+        let exported = manager.export_complete_config(&[]).unwrap();
         
         // Check that custom colors are present in inline table format
         assert!(exported.contains("theme = \"custom\""));
@@ -1230,7 +1622,7 @@ plugin-exclude = ["bad-plugin"]
         fs::write(&temp_file, toml_content).unwrap();
         
         let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
-        let exported = manager.export_complete_config().unwrap();
+        let exported = manager.export_complete_config(&[]).unwrap();
         
         // Check that plugins section is present with actual values
         assert!(exported.contains("[plugins]"));
@@ -1243,4 +1635,133 @@ plugin-exclude = ["bad-plugin"]
         assert!(!exported.contains("# plugin-load = [\"plugin1\", \"plugin2\"]"));
         assert!(!exported.contains("# plugin-exclude = [\"unwanted-plugin\"]"));
     }
+
+    #[test]
+    fn test_import_rules_from_config() {
+        let toml_content = r#"
+[imports]
+commits = ["..v0.1", "abc1234"]
+paths = ["vendor/"]
+author = "vendored"
+"#;
+        
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+        
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        let rules = manager.get_import_rules();
+        assert_eq!(rules.commits, vec!["..v0.1", "abc1234"]);
+        assert_eq!(rules.paths, vec!["vendor/"]);
+        assert_eq!(rules.author, "vendored");
+        
+        let exported = manager.export_complete_config(&[]).unwrap();
+        assert!(exported.contains("[imports]"));
+        assert!(exported.contains("paths = [\"vendor/\"]"));
+        
+        let empty = ConfigManager::from_config(Configuration::new());
+        assert!(empty.get_import_rules().is_empty());
+        assert_eq!(empty.get_import_rules().author, "import");
+    }
+
+    #[test]
+    fn test_identity_rules_from_config() {
+        let toml_content = r#"
+[authors]
+mailmap = false
+
+[authors.aliases]
+"Jane Doe <jane@x.org>" = ["jdoe <jane@y.org>", "jane@home.net"]
+"#;
+        
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+        
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        let rules = manager.get_identity_rules();
+        assert!(!rules.use_mailmap);
+        assert_eq!(rules.aliases, vec![(
+            "Jane Doe <jane@x.org>".to_string(),
+            vec!["jdoe <jane@y.org>".to_string(), "jane@home.net".to_string()],
+        )]);
+        
+        let exported = manager.export_complete_config(&[]).unwrap();
+        assert!(exported.contains("mailmap = false"));
+        assert!(exported.contains("\"Jane Doe <jane@x.org>\" = [\"jdoe <jane@y.org>\", \"jane@home.net\"]"));
+        
+        let empty = ConfigManager::from_config(Configuration::new());
+        assert!(empty.get_identity_rules().use_mailmap);
+        assert!(empty.get_identity_rules().aliases.is_empty());
+    }
+
+    #[test]
+    fn test_plugin_arg_presets_from_config() {
+        let toml_content = r#"
+[plugin.metrics.presets.strict]
+top = 10
+complexity-weight = 0.6
+
+[plugin.contributors.presets.tree]
+tree = true
+depth = 1
+
+[plugin.contributors]
+top = 5
+since = "90d"
+
+[plugin.metrics]
+complexity-weight = 0.5
+"#;
+        
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(&temp_file, toml_content).unwrap();
+        
+        let manager = ConfigManager::load_from_file(temp_file.path().to_path_buf()).unwrap();
+        let presets = manager.get_plugin_arg_presets();
+        assert_eq!(presets.get("metrics", "strict").unwrap(), ["--complexity-weight", "0.6", "--top", "10"]);
+        assert_eq!(presets.get("contributors", "tree").unwrap(), ["--depth", "1", "--tree"]);
+        assert_eq!(presets.names("loc"), Vec::<&str>::new());
+        assert_eq!(presets.apply_defaults("contributors", &["--top".to_string(), "3".to_string()]), ["--since", "90d", "--top", "3"]);
+        // Options beside presets are flattened into their own sections
+        assert_eq!(presets.apply_defaults("metrics", &[]), ["--complexity-weight", "0.5"]);
+        
+        let exported = manager.export_complete_config(&[]).unwrap();
+        assert!(exported.contains("[plugin.metrics.presets.strict]\ncomplexity-weight = 0.6\ntop = 10\n"));
+        assert!(exported.contains("[plugin.contributors]\nsince = \"90d\"\ntop = 5\n"));
+        let reloaded = parse_toml_config(&exported).unwrap();
+        assert_eq!(ConfigManager::from_config(reloaded).get_plugin_arg_presets(), presets);
+    }
+
+    #[test]
+    fn test_export_complete_config_lists_plugin_options() {
+        let toml_content = r#"
+[scanner]
+git-dir = 'C:\repos\project.git'
+
+[plugin.metrics]
+top = 5
+"#;
+        let manager = ConfigManager::from_config(parse_toml_config(toml_content).unwrap());
+        let metrics = clap::Command::new("metrics")
+            .about("Analyzes code quality metrics")
+            .arg(clap::Arg::new("top").long("top").value_name("N").default_value("10").help("Number of hotspots to report"))
+            .arg(clap::Arg::new("detailed").long("detailed").action(clap::ArgAction::SetTrue).help("Include detailed metrics"))
+            .arg(clap::Arg::new("since").long("since").value_name("DATE"));
+        let loc = clap::Command::new("loc").arg(clap::Arg::new("path").help("Positional arguments are not options"));
+        
+        let exported = manager.export_complete_config(&[("metrics", metrics), ("loc", loc)]).unwrap();
+        assert!(exported.contains(concat!(
+            "# Analyzes code quality metrics\n[plugin.metrics]\n",
+            "# Number of hotspots to report\ntop = 5\n",
+            "# Include detailed metrics\n# detailed = false\n",
+            "# since = \"DATE\"\n",
+        )));
+        assert!(exported.contains("[plugin.loc]\n\n"));
+        assert!(exported.contains("# Lines (or tokens) in a duplicated block\n# duplicate-min-block = 6\n"));
+        
+        // Loading the exported file gives back the configuration it was exported from
+        let reloaded = ConfigManager::from_config(parse_toml_config(&exported).unwrap());
+        assert_eq!(reloaded.get_path("scanner", "git-dir"), Some(PathBuf::from("C:\\repos\\project.git")));
+        assert_eq!(reloaded.get_plugin_arg_presets(), manager.get_plugin_arg_presets());
+        assert_eq!(reloaded.export_complete_config(&[]).unwrap(), manager.export_complete_config(&[]).unwrap());
+    }
 }
@@ -5,12 +5,16 @@
 
 use colored::{ColoredString, Colorize};
 use super::config::{ColourConfig, ColourPalette};
+use super::indicators::{Indicator, Severity, Trend};
+use super::hyperlinks::Hyperlinks;
 
 /// Manages colour output for the CLI application
 #[derive(Debug, Clone)]
 pub struct ColourManager {
     config: ColourConfig,
     palette: ColourPalette,
+    /// URL templates of the links in console tables, when links are enabled
+    hyperlinks: Option<Hyperlinks>,
 }
 
 impl ColourManager {
@@ -18,14 +22,14 @@ impl ColourManager {
     pub fn new() -> Self {
         let config = ColourConfig::default();
         let palette = config.get_palette();
-        Self { config, palette }
+        Self { config, palette, hyperlinks: None }
     }
     
     
     /// Create a ColourManager with a specific configuration
     pub fn with_config(config: ColourConfig) -> Self {
         let palette = config.get_palette();
-        Self { config, palette }
+        Self { config, palette, hyperlinks: None }
     }
     
     /// Create a ColourManager from both CLI color flags and optional configuration
@@ -49,15 +53,56 @@ impl ColourManager {
         }
         
         let palette = final_config.get_palette();
-        Self { config: final_config, palette }
+        Self { config: final_config, palette, hyperlinks: None }
     }
     
     
+    /// Render commit hashes, file paths and issue references as links to these templates
+    pub fn with_hyperlinks(mut self, hyperlinks: Hyperlinks) -> Self {
+        self.hyperlinks = (!hyperlinks.is_empty()).then_some(hyperlinks);
+        self
+    }
+    
+    /// URL templates of table links, if links are enabled
+    pub fn hyperlinks(&self) -> Option<&Hyperlinks> {
+        self.hyperlinks.as_ref()
+    }
+    
     /// Check if colours are enabled
     pub fn colours_enabled(&self) -> bool {
         self.config.should_use_colours()
     }
     
+    /// Check if accessible mode was requested
+    pub fn accessible(&self) -> bool {
+        self.config.accessible
+    }
+    
+    /// Check if indicators carry symbols and labels: in accessible mode, and
+    /// whenever colours are off so colour alone would convey nothing
+    pub fn symbols_enabled(&self) -> bool {
+        self.accessible() || !self.colours_enabled()
+    }
+    
+    /// Text of a value with its indicator symbol or label, when symbols are enabled
+    pub fn indicator_text(&self, text: &str, indicator: Indicator) -> String {
+        if self.symbols_enabled() {
+            indicator.mark(text)
+        } else {
+            text.to_string()
+        }
+    }
+    
+    /// Colour text by its indicator: increases and low severities as success,
+    /// decreases and high severities as errors, medium severities as warnings
+    pub fn indicator(&self, text: &str, indicator: Indicator) -> ColoredString {
+        match indicator {
+            Indicator::Trend(Trend::Up) | Indicator::Severity(Severity::Low) => self.success(text),
+            Indicator::Trend(Trend::Down) | Indicator::Severity(Severity::High | Severity::Critical) => self.error(text),
+            Indicator::Severity(Severity::Medium) => self.warning(text),
+        }
+    }
+    
     /// Format text as an error using the configured error colour
     pub fn error(&self, text: &str) -> ColoredString {
         if self.colours_enabled() {
@@ -170,6 +215,25 @@ mod tests {
         // The actual color detection is tested elsewhere
     }
     
+    #[test]
+    fn test_indicators_use_symbols_without_colour() {
+        let indicator = Indicator::Trend(Trend::Down);
+        
+        let mut config = ColourConfig::new();
+        config.set_color_forced(true);
+        let manager = ColourManager::with_config(config.clone());
+        assert_eq!(manager.indicator_text("-4", indicator), "-4");
+        
+        config.set_accessible(true);
+        let manager = ColourManager::with_config(config);
+        assert_eq!(manager.indicator_text("-4", indicator), "▼ -4");
+        
+        let manager = ColourManager::with_config(ColourConfig::disabled());
+        assert!(!manager.accessible());
+        assert_eq!(manager.indicator_text("high", Indicator::Severity(Severity::High)), "[HIGH]");
+        assert_eq!(manager.indicator("high", Indicator::Severity(Severity::High)).to_string(), "high");
+    }
+    
     #[test]
     fn test_colour_manager_explicit_enable() {
         let mut config = ColourConfig::new();
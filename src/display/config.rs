@@ -18,6 +18,9 @@ pub struct ColourConfig {
     /// Force colours even when not in a TTY (--color flag)
     #[serde(skip, default)]
     pub color_forced: bool,
+    /// Show changes and severities with symbols and labels as well as colour
+    #[serde(default)]
+    pub accessible: bool,
 }
 
 impl Default for ColourConfig {
@@ -27,6 +30,7 @@ impl Default for ColourConfig {
             theme: ColourTheme::Auto,
             respect_no_color: true,
             color_forced: false,
+            accessible: false,
         }
     }
 }
@@ -152,6 +156,7 @@ impl ColourConfig {
             theme: ColourTheme::Auto,
             respect_no_color: true,
             color_forced: false,
+            accessible: false,
         }
     }
     
@@ -162,6 +167,7 @@ impl ColourConfig {
             theme,
             respect_no_color: true,
             color_forced: false,
+            accessible: false,
         }
     }
     
@@ -185,6 +191,11 @@ impl ColourConfig {
         self.color_forced = forced;
     }
     
+    /// Set whether changes and severities are shown with symbols and labels
+    pub fn set_accessible(&mut self, accessible: bool) {
+        self.accessible = accessible;
+    }
+    
     /// Check if colours should be enabled based on configuration and environment
     pub fn should_use_colours(&self) -> bool {
         if !self.enabled {
@@ -0,0 +1,238 @@
+//! Terminal hyperlinks (OSC 8)
+//!
+//! Console tables can render commit hashes, file paths and issue references as
+//! clickable links. URLs come from templates, either configured in the `[links]`
+//! section or inferred from a GitHub, GitLab or Bitbucket remote:
+//!
+//! - `commit` with `{hash}`
+//! - `file` with `{path}`
+//! - `issue` with `{number}`
+//!
+//! Terminals without OSC 8 support get plain text.
+
+use std::io::IsTerminal;
+
+/// When console output carries hyperlinks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HyperlinkMode {
+    /// Only on a terminal known to support OSC 8
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl HyperlinkMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether links should be written to standard output
+    pub fn enabled(&self) -> bool {
+        match self {
+            Self::Auto => std::io::stdout().is_terminal() && supports_hyperlinks(|name| std::env::var(name).ok()),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// URL templates for the values that console tables link
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hyperlinks {
+    /// Commit URL, with `{hash}`
+    pub commit: Option<String>,
+    /// File URL, with `{path}`
+    pub file: Option<String>,
+    /// Issue URL, with `{number}`
+    pub issue: Option<String>,
+}
+
+impl Hyperlinks {
+    /// Templates for a repository hosted on GitHub, GitLab or Bitbucket
+    ///
+    /// Accepts HTTPS, `ssh://` and scp-style (`git@host:owner/repo.git`) URLs.
+    pub fn from_remote_url(url: &str) -> Option<Self> {
+        let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+            let rest = rest.rsplit_once('@').map_or(rest, |(_, rest)| rest);
+            rest.split_once('/')?
+        } else {
+            let rest = url.rsplit_once('@').map_or(url, |(_, rest)| rest);
+            rest.split_once(':')?
+        };
+        // Drop any port from the host
+        let host = host.split(':').next()?.to_lowercase();
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if path.is_empty() {
+            return None;
+        }
+
+        let base = format!("https://{host}/{path}");
+        let (commit, file, issue) = if host.contains("github") {
+            ("commit/{hash}", "blob/HEAD/{path}", "issues/{number}")
+        } else if host.contains("gitlab") {
+            ("-/commit/{hash}", "-/blob/HEAD/{path}", "-/issues/{number}")
+        } else if host.contains("bitbucket") {
+            ("commits/{hash}", "src/HEAD/{path}", "issues/{number}")
+        } else {
+            return None;
+        };
+        Some(Self {
+            commit: Some(format!("{base}/{commit}")),
+            file: Some(format!("{base}/{file}")),
+            issue: Some(format!("{base}/{issue}")),
+        })
+    }
+
+    /// These templates, with any missing ones taken from `fallback`
+    pub fn or(self, fallback: Hyperlinks) -> Self {
+        Self {
+            commit: self.commit.or(fallback.commit),
+            file: self.file.or(fallback.file),
+            issue: self.issue.or(fallback.issue),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commit.is_none() && self.file.is_none() && self.issue.is_none()
+    }
+
+    /// A cell of the named column with its links, or `None` when it has none
+    ///
+    /// Cells of `Hash` or `Commit` columns that look like commit hashes link to
+    /// the commit, cells of `File` or `Path` columns link to the file, and
+    /// `#123` references in any other cell link to the issue.
+    pub fn link(&self, column: &str, text: &str) -> Option<String> {
+        let column = column.to_lowercase();
+        if matches!(column.as_str(), "hash" | "commit") {
+            let template = self.commit.as_ref()?;
+            let is_hash = (7..=40).contains(&text.len()) && text.chars().all(|c| c.is_ascii_hexdigit());
+            return is_hash.then(|| osc8(&template.replace("{hash}", text), text));
+        }
+        if matches!(column.as_str(), "file" | "path") {
+            let template = self.file.as_ref()?;
+            let is_path = !text.is_empty() && !text.contains(' ');
+            return is_path.then(|| osc8(&template.replace("{path}", &encode_path(text)), text));
+        }
+        self.link_issues(text)
+    }
+
+    /// Text with each `#123` reference linked to its issue
+    fn link_issues(&self, text: &str) -> Option<String> {
+        let template = self.issue.as_ref()?;
+        let mut output = String::new();
+        let mut rest = text;
+        let mut linked = false;
+        while let Some(position) = rest.find('#') {
+            let (before, reference) = rest.split_at(position);
+            output.push_str(before);
+            let digits = reference[1..].chars().take_while(char::is_ascii_digit).count();
+            let preceded = output.chars().last().is_some_and(|c| c.is_alphanumeric() || c == '&');
+            let followed = reference[1 + digits..].chars().next().is_some_and(char::is_alphanumeric);
+            if digits == 0 || preceded || followed {
+                output.push('#');
+                rest = &reference[1..];
+                continue;
+            }
+            let (reference, after) = reference.split_at(1 + digits);
+            output.push_str(&osc8(&template.replace("{number}", &reference[1..]), reference));
+            rest = after;
+            linked = true;
+        }
+        output.push_str(rest);
+        linked.then_some(output)
+    }
+}
+
+/// Text wrapped in an OSC 8 hyperlink to `url`
+pub fn osc8(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Whether the terminal described by the environment renders OSC 8 hyperlinks
+///
+/// `FORCE_HYPERLINK=1` or `0` overrides detection.
+pub fn supports_hyperlinks(env: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = env("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if env("CI").is_some() {
+        return false;
+    }
+    if env("DOMTERM").is_some() || env("WT_SESSION").is_some() || env("KONSOLE_VERSION").is_some() {
+        return true;
+    }
+    if env("VTE_VERSION").and_then(|version| version.parse::<u32>().ok()).is_some_and(|version| version >= 5000) {
+        return true;
+    }
+    if env("TERM_PROGRAM").is_some_and(|program| {
+        matches!(program.as_str(), "iTerm.app" | "WezTerm" | "vscode" | "Hyper" | "ghostty")
+    }) {
+        return true;
+    }
+    env("TERM").is_some_and(|term| ["kitty", "alacritty", "foot", "ghostty"].iter().any(|name| term.contains(name)))
+}
+
+/// Percent-encode a repository path for use in a URL, keeping its `/` separators
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_from_remote_url() {
+        let github = Hyperlinks::from_remote_url("git@github.com:deeprave/gstats.git").unwrap();
+        assert_eq!(github.commit.as_deref(), Some("https://github.com/deeprave/gstats/commit/{hash}"));
+        assert_eq!(Hyperlinks::from_remote_url("https://github.com/deeprave/gstats"), Some(github));
+
+        let gitlab = Hyperlinks::from_remote_url("ssh://git@gitlab.example.com:2222/group/sub/project.git").unwrap();
+        assert_eq!(gitlab.issue.as_deref(), Some("https://gitlab.example.com/group/sub/project/-/issues/{number}"));
+
+        assert_eq!(Hyperlinks::from_remote_url("https://git.example.com/project.git"), None);
+        assert_eq!(Hyperlinks::from_remote_url("/srv/git/project.git"), None);
+    }
+
+    #[test]
+    fn test_link_cells() {
+        let links = Hyperlinks::from_remote_url("https://github.com/o/r").unwrap();
+        assert_eq!(links.link("Hash", "abc1234"), Some(osc8("https://github.com/o/r/commit/abc1234", "abc1234")));
+        assert_eq!(links.link("Commits", "42"), None);
+        assert_eq!(links.link("File", "src/my file.rs"), None);
+        assert_eq!(links.link("File", "src/a+b.rs"), Some(osc8("https://github.com/o/r/blob/HEAD/src/a%2Bb.rs", "src/a+b.rs")));
+        assert_eq!(links.link("Subject", "Fix parser (#12)"),
+            Some(format!("Fix parser ({})", osc8("https://github.com/o/r/issues/12", "#12"))));
+        assert_eq!(links.link("Subject", "Use C# in step#2, #x and &#38;"), None);
+        assert_eq!(Hyperlinks::default().link("Hash", "abc1234"), None);
+    }
+
+    #[test]
+    fn test_supports_hyperlinks() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+        assert!(supports_hyperlinks(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supports_hyperlinks(env(&[("VTE_VERSION", "6003")])));
+        assert!(!supports_hyperlinks(env(&[("VTE_VERSION", "4000")])));
+        assert!(!supports_hyperlinks(env(&[("TERM", "xterm-256color")])));
+        assert!(!supports_hyperlinks(env(&[("TERM_PROGRAM", "WezTerm"), ("CI", "true")])));
+        assert!(supports_hyperlinks(env(&[("FORCE_HYPERLINK", "1"), ("CI", "true")])));
+        assert!(!supports_hyperlinks(env(&[("FORCE_HYPERLINK", "0"), ("TERM", "kitty")])));
+    }
+}
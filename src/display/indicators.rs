@@ -0,0 +1,100 @@
+//! Indicators that do not rely on colour alone
+//!
+//! Changes and severities are coloured in terminal and HTML output (green for
+//! increases, red for decreases and high severities). In accessible mode, and
+//! whenever colours are off, each also carries a symbol or text label:
+//! `▲ 12` and `▼ 3` for changes, `[HIGH]` and `[LOW]` for severities.
+
+/// Direction of a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Up,
+    Down,
+}
+
+impl Trend {
+    /// Direction of a signed change, if it is not zero
+    pub fn from_delta(delta: f64) -> Option<Self> {
+        if delta > 0.0 {
+            Some(Self::Up)
+        } else if delta < 0.0 {
+            Some(Self::Down)
+        } else {
+            None
+        }
+    }
+
+    /// Symbol shown before the value
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Up => "▲",
+            Self::Down => "▼",
+        }
+    }
+}
+
+/// Severity or risk level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Parse a level name (`low`, `medium`, `high`, `critical`)
+    pub fn parse(level: &str) -> Option<Self> {
+        match level.trim().to_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "medium" | "moderate" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+
+    /// Text label shown in place of the level name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "[LOW]",
+            Self::Medium => "[MEDIUM]",
+            Self::High => "[HIGH]",
+            Self::Critical => "[CRITICAL]",
+        }
+    }
+}
+
+/// Meaning of a value that output formats convey with colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indicator {
+    /// A signed change, coloured by its direction
+    Trend(Trend),
+    /// A severity or risk level
+    Severity(Severity),
+}
+
+impl Indicator {
+    /// Text with its symbol or label, for output that cannot rely on colour
+    pub fn mark(&self, text: &str) -> String {
+        match self {
+            Self::Trend(trend) => format!("{} {}", trend.symbol(), text),
+            Self::Severity(severity) => severity.label().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator_marks() {
+        assert_eq!(Indicator::Trend(Trend::from_delta(12.0).unwrap()).mark("12"), "▲ 12");
+        assert_eq!(Indicator::Trend(Trend::from_delta(-3.0).unwrap()).mark("-3"), "▼ -3");
+        assert_eq!(Trend::from_delta(0.0), None);
+        assert_eq!(Indicator::Severity(Severity::parse("High").unwrap()).mark("High"), "[HIGH]");
+        assert_eq!(Severity::parse("unknown"), None);
+        assert!(Severity::Critical > Severity::High);
+    }
+}
@@ -9,9 +9,15 @@ pub mod themes;
 pub mod progress;
 pub mod format;
 pub mod table;
+pub mod indicators;
+pub mod hyperlinks;
+#[cfg(feature = "tui")]
+pub mod tui;
 
 pub use colours::*;
 pub use config::*;
 pub use progress::*;
 pub use format::*;
-pub use table::*;
\ No newline at end of file
+pub use table::*;
+pub use indicators::*;
+pub use hyperlinks::{Hyperlinks, HyperlinkMode};
\ No newline at end of file
@@ -1,9 +1,21 @@
 //! Progress indicators and status displays for CLI output
 //! 
 //! Provides visual feedback for long-running operations including
-//! status indicators with color support.
+//! status indicators with color support and a live progress bar for scans.
+
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use crate::display::ColourManager;
+use crate::scanner::async_engine::events::RepositoryEvent;
+use crate::scanner::async_engine::progress::ProgressReporter;
+
+/// Width of the bar itself, in characters
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// Shortest interval between two redraws of a progress bar
+const PROGRESS_REDRAW_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Status indicator symbols with unicode support
 #[derive(Debug, Clone)]
@@ -97,6 +109,140 @@ impl Clone for ProgressIndicator {
     }
 }
 
+/// Live progress bar with an estimated time remaining, drawn on stderr
+///
+/// As a [`ProgressReporter`] it counts the commits and files of a repository
+/// scan against the totals announced when the scan starts.
+pub struct ProgressBar {
+    state: Mutex<ProgressState>,
+}
+
+struct ProgressState {
+    done: usize,
+    total: Option<usize>,
+    started: Instant,
+    last_draw: Option<Instant>,
+}
+
+impl ProgressBar {
+    /// Create a progress bar; nothing is drawn until work is reported
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(ProgressState {
+                done: 0,
+                total: None,
+                started: Instant::now(),
+                last_draw: None,
+            }),
+        }
+    }
+    
+    /// Whether a progress bar should be shown: never with `--quiet` or when output is not a terminal
+    pub fn should_display(quiet: bool) -> bool {
+        !quiet && std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
+    }
+    
+    /// Start counting towards a total, if it is known
+    pub fn start(&self, total: Option<usize>) {
+        let mut state = self.lock();
+        state.done = 0;
+        state.total = total;
+        state.started = Instant::now();
+        state.last_draw = None;
+    }
+    
+    /// Count completed work and redraw if enough time has passed
+    pub fn advance(&self, amount: usize) {
+        let mut state = self.lock();
+        state.done += amount;
+        let now = Instant::now();
+        if state.last_draw.is_some_and(|last| now.duration_since(last) < PROGRESS_REDRAW_INTERVAL) {
+            return;
+        }
+        state.last_draw = Some(now);
+        let line = format_progress(state.done, state.total, state.started.elapsed());
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{}", line);
+        let _ = stderr.flush();
+    }
+    
+    /// Remove the bar from the terminal
+    pub fn finish(&self) {
+        let mut state = self.lock();
+        if state.last_draw.take().is_some() {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+    
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProgressState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for ProgressBar {
+    fn repository_event(&self, event: &RepositoryEvent) {
+        match event {
+            RepositoryEvent::RepositoryStarted { total_commits, total_files } => {
+                let total = match (total_commits, total_files) {
+                    (None, None) => None,
+                    (commits, files) => Some(commits.unwrap_or(0) + files.unwrap_or(0)),
+                };
+                self.start(total);
+            }
+            RepositoryEvent::RepositoryCompleted { .. } => self.finish(),
+            _ => {}
+        }
+    }
+    
+    fn commit_scanned(&self) {
+        self.advance(1);
+    }
+    
+    fn file_scanned(&self) {
+        self.advance(1);
+    }
+}
+
+/// Render one line of progress, with a bar and estimate when the total is known
+fn format_progress(done: usize, total: Option<usize>, elapsed: Duration) -> String {
+    let Some(total) = total.filter(|&total| total > 0) else {
+        return format!("Scanning... {} done ({} elapsed)", done, format_duration(elapsed));
+    };
+    
+    let done = done.min(total);
+    let filled = PROGRESS_BAR_WIDTH * done / total;
+    let bar = if filled < PROGRESS_BAR_WIDTH {
+        format!("{}>{}", "=".repeat(filled), " ".repeat(PROGRESS_BAR_WIDTH - filled - 1))
+    } else {
+        "=".repeat(PROGRESS_BAR_WIDTH)
+    };
+    let eta = if done == 0 {
+        "--:--".to_string()
+    } else {
+        format_duration(elapsed.mul_f64((total - done) as f64 / done as f64))
+    };
+    format!("Scanning [{}] {:>3}% {}/{} ETA {}", bar, done * 100 / total, done, total, eta)
+}
+
+/// Format a duration as `m:ss`, or `h:mm:ss` from an hour up
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
 /// Status type for different kinds of messages
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StatusType {
@@ -133,6 +279,25 @@ mod tests {
     }
     
     
+    #[test]
+    fn test_progress_line_with_known_total() {
+        let line = format_progress(25, Some(100), Duration::from_secs(30));
+        assert!(line.contains(" 25% 25/100"), "{}", line);
+        assert!(line.ends_with("ETA 1:30"), "{}", line);
+        
+        let line = format_progress(0, Some(100), Duration::ZERO);
+        assert!(line.ends_with("ETA --:--"), "{}", line);
+        
+        let line = format_progress(100, Some(100), Duration::from_secs(5));
+        assert!(line.contains(&format!("[{}] 100%", "=".repeat(PROGRESS_BAR_WIDTH))), "{}", line);
+    }
+    
+    #[test]
+    fn test_progress_line_without_total() {
+        let line = format_progress(42, None, Duration::from_secs(3725));
+        assert_eq!(line, "Scanning... 42 done (1:02:05 elapsed)");
+    }
+    
     #[test]
     fn test_unicode_support_detection() {
         // This test depends on the environment, so we just verify the function runs
@@ -3,12 +3,14 @@
 //! This module provides reusable table formatting functionality that can be used
 //! by different parts of the application to maintain consistent output styling.
 
-use crate::display::ColourManager;
+use crate::display::{ColourManager, Indicator};
 
 /// A generic table builder for consistent formatting
 pub struct TableBuilder {
     headers: Vec<String>,
     rows: Vec<Vec<String>>,
+    /// Indicator of each cell, coloured in place of the default value colour
+    indicators: Vec<Vec<Option<Indicator>>>,
     max_plugin_width: Option<usize>,
 }
 
@@ -18,6 +20,7 @@ impl TableBuilder {
         Self {
             headers: Vec::new(),
             rows: Vec::new(),
+            indicators: Vec::new(),
             max_plugin_width: None,
         }
     }
@@ -31,6 +34,16 @@ impl TableBuilder {
     /// Add a row to the table
     pub fn add_row(mut self, row: Vec<String>) -> Self {
         self.rows.push(row);
+        self.indicators.push(Vec::new());
+        self
+    }
+    
+    /// Add a row whose cells are coloured by their indicators
+    ///
+    /// Cells should already carry their symbols (see [`ColourManager::indicator_text`]).
+    pub fn add_row_with_indicators(mut self, row: Vec<String>, indicators: Vec<Option<Indicator>>) -> Self {
+        self.rows.push(row);
+        self.indicators.push(indicators);
         self
     }
     
@@ -41,57 +54,48 @@ impl TableBuilder {
     }
     
     /// Build and format the table with colors
+    ///
+    /// Every column but the last is padded to its widest cell; the first
+    /// column's width can be fixed with [`with_plugin_width`](Self::with_plugin_width).
     pub fn build_with_colors(&self, colour_manager: &ColourManager) -> String {
         if self.headers.is_empty() && self.rows.is_empty() {
             return String::new();
         }
         
+        let widths = self.column_widths();
         let mut output = String::new();
         
-        // Calculate column width for proper alignment
-        let first_col_width = if let Some(width) = self.max_plugin_width {
-            width
-        } else if !self.headers.is_empty() {
-            self.headers[0].len().max(6)
-        } else {
-            6
-        };
-        
-        // Print headers if available
+        // Print headers and separator line if available
         if !self.headers.is_empty() {
-            let header_line = if self.headers.len() >= 2 {
-                format!(" {:<width$} {}", 
-                    colour_manager.highlight(&self.headers[0]), 
-                    colour_manager.highlight(&self.headers[1]), 
-                    width = first_col_width)
-            } else {
-                format!(" {}", colour_manager.highlight(&self.headers[0]))
-            };
-            output.push_str(&header_line);
-            output.push('\n');
+            let header_cells: Vec<String> = self.headers.iter().enumerate()
+                .map(|(i, header)| colour_manager.highlight(&pad_cell(header, &widths, i)).to_string())
+                .collect();
+            output.push_str(&format!(" {}\n", header_cells.join(" ")));
             
-            // Print separator line
-            let separator = if self.headers.len() >= 2 {
-                format!(" {} {}", 
-                    colour_manager.info(&"-".repeat(first_col_width)), 
-                    colour_manager.info("--"))
-            } else {
-                format!(" {}", colour_manager.info(&"-".repeat(first_col_width)))
-            };
-            output.push_str(&separator);
-            output.push('\n');
+            let separator_cells: Vec<String> = (0..self.headers.len())
+                .map(|i| colour_manager.info(&"-".repeat(widths[i])).to_string())
+                .collect();
+            output.push_str(&format!(" {}\n", separator_cells.join(" ")));
         }
         
-        // Print data rows
-        for row in &self.rows {
-            if row.len() >= 2 {
-                let row_line = format!(" {:<width$} {}", 
-                    colour_manager.command(&row[0]), 
-                    colour_manager.success(&row[1]), 
-                    width = first_col_width);
-                output.push_str(&row_line);
-            } else if !row.is_empty() {
-                output.push_str(&format!(" {}", colour_manager.command(&row[0])));
+        // Print data rows: the first column as a name, the rest as values
+        for (row, indicators) in self.rows.iter().zip(&self.indicators) {
+            let cells: Vec<String> = row.iter().enumerate()
+                .map(|(i, cell)| {
+                    // Links change the text but not its width, so pad after colouring
+                    let text = self.headers.get(i)
+                        .and_then(|header| colour_manager.hyperlinks()?.link(header, cell))
+                        .unwrap_or_else(|| cell.clone());
+                    let coloured = match indicators.get(i).copied().flatten() {
+                        Some(indicator) => colour_manager.indicator(&text, indicator).to_string(),
+                        None if i == 0 => colour_manager.command(&text).to_string(),
+                        None => colour_manager.success(&text).to_string(),
+                    };
+                    format!("{}{}", coloured, padding(cell, &widths, i))
+                })
+                .collect();
+            if !cells.is_empty() {
+                output.push_str(&format!(" {}", cells.join(" ")));
             }
             output.push('\n');
         }
@@ -99,6 +103,26 @@ impl TableBuilder {
         output
     }
     
+    /// Width of each column, measured in characters
+    fn column_widths(&self) -> Vec<usize> {
+        let columns = self.rows.iter().map(Vec::len).chain(std::iter::once(self.headers.len())).max().unwrap_or(0);
+        (0..columns)
+            .map(|i| {
+                if i == 0 {
+                    if let Some(width) = self.max_plugin_width {
+                        return width;
+                    }
+                }
+                let widest = self.headers.get(i).into_iter()
+                    .chain(self.rows.iter().filter_map(|row| row.get(i)))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                if i == 0 { widest.max(6) } else { widest }
+            })
+            .collect()
+    }
+    
     /// Build and format the table without colors
     pub fn build(&self) -> String {
         let no_color_manager = ColourManager::new();
@@ -112,6 +136,19 @@ impl Default for TableBuilder {
     }
 }
 
+/// Left-align a cell to its column width, leaving the last column unpadded
+fn pad_cell(text: &str, widths: &[usize], column: usize) -> String {
+    format!("{}{}", text, padding(text, widths, column))
+}
+
+/// Spaces that fill a cell out to its column width
+fn padding(text: &str, widths: &[usize], column: usize) -> String {
+    if column + 1 >= widths.len() {
+        return String::new();
+    }
+    " ".repeat(widths[column].saturating_sub(text.chars().count()))
+}
+
 /// Helper function to create a plugin listing table
 pub fn create_plugin_table(plugin_data: Vec<(String, String)>, colour_manager: &ColourManager) -> String {
     if plugin_data.is_empty() {
@@ -173,6 +210,37 @@ mod tests {
         assert!(table.contains("export"));
     }
     
+    #[test]
+    fn test_table_builder_aligns_all_columns() {
+        let table = TableBuilder::new()
+            .headers(vec!["Metric".to_string(), "v1".to_string(), "v2".to_string()])
+            .add_row(vec!["Commits".to_string(), "120".to_string(), "135".to_string()])
+            .add_row(vec!["Lines Added".to_string(), "9".to_string(), "42".to_string()])
+            .build();
+        let lines: Vec<&str> = table.lines().collect();
+        
+        assert_eq!(lines[0], " Metric      v1  v2");
+        assert_eq!(lines[1], " ----------- --- ---");
+        assert_eq!(lines[2], " Commits     120 135");
+        assert_eq!(lines[3], " Lines Added 9   42");
+    }
+    
+    #[test]
+    fn test_linked_cells_keep_alignment() {
+        let links = crate::display::Hyperlinks::from_remote_url("https://github.com/o/r").unwrap();
+        let colour_manager = ColourManager::with_config(ColourConfig::disabled()).with_hyperlinks(links);
+        let table = TableBuilder::new()
+            .headers(vec!["Hash".to_string(), "Subject".to_string()])
+            .add_row(vec!["abc1234".to_string(), "Fix #7".to_string()])
+            .build_with_colors(&colour_manager);
+        let lines: Vec<&str> = table.lines().collect();
+        
+        let hash = crate::display::hyperlinks::osc8("https://github.com/o/r/commit/abc1234", "abc1234");
+        let issue = crate::display::hyperlinks::osc8("https://github.com/o/r/issues/7", "#7");
+        assert_eq!(lines[0], " Hash    Subject");
+        assert_eq!(lines[2], format!(" {} Fix {}", hash, issue));
+    }
+    
     #[test]
     fn test_empty_table() {
         let table = TableBuilder::new().build();
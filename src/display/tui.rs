@@ -0,0 +1,480 @@
+//! Interactive terminal dashboard (`--tui`)
+//!
+//! Replaces the progress bar and console tables with a full-screen view that
+//! updates while the scan runs: scan progress at the top, the tables published
+//! by plugins in the middle and queue statistics at the bottom. The dashboard
+//! stays open once the scan is done so the results can be browsed, until the
+//! user quits.
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Gauge, List, ListItem, ListState, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::display::progress::format_duration;
+use crate::notifications::error::NotificationResult;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{RateLimit, Subscriber};
+use crate::plugin::data_export::{DataPayload, PluginDataExport};
+use crate::queue::QueueStatistics;
+use crate::scanner::async_engine::events::RepositoryEvent;
+use crate::scanner::async_engine::progress::ProgressReporter;
+use crate::shutdown::ShutdownController;
+
+/// How long the dashboard waits for a key press before redrawing
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Rows moved by Page Up and Page Down
+const PAGE_ROWS: usize = 10;
+
+/// How a scan shown on the dashboard ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanOutcome {
+    Completed,
+    Interrupted,
+    Failed(String),
+}
+
+/// Live scan dashboard fed by the scanner, the queue and plugin results
+pub struct Dashboard {
+    state: Mutex<DashboardState>,
+    shutdown: ShutdownController,
+}
+
+#[derive(Clone)]
+struct DashboardState {
+    repository: String,
+    done: usize,
+    total: Option<usize>,
+    started: Instant,
+    elapsed: Option<Duration>,
+    outcome: Option<ScanOutcome>,
+    queue: Option<QueueStatistics>,
+    tables: Vec<Arc<PluginDataExport>>,
+    /// Set when output may have been written around the dashboard, to repaint the whole screen
+    repaint: bool,
+}
+
+impl Dashboard {
+    /// Create a dashboard for a repository; quitting during the scan stops it through `shutdown`
+    pub fn new(repository: impl Into<String>, shutdown: ShutdownController) -> Self {
+        Self {
+            state: Mutex::new(DashboardState {
+                repository: repository.into(),
+                done: 0,
+                total: None,
+                started: Instant::now(),
+                elapsed: None,
+                outcome: None,
+                queue: None,
+                tables: Vec::new(),
+                repaint: false,
+            }),
+            shutdown,
+        }
+    }
+
+    /// Take over the terminal and draw the dashboard until the user quits
+    pub fn open(self: &Arc<Self>) -> JoinHandle<io::Result<()>> {
+        let dashboard = self.clone();
+        std::thread::spawn(move || {
+            let mut terminal = ratatui::init();
+            let result = dashboard.run(&mut terminal);
+            ratatui::restore();
+            result
+        })
+    }
+
+    /// Record the latest queue statistics
+    pub fn update_queue(&self, statistics: QueueStatistics) {
+        self.lock().queue = Some(statistics);
+    }
+
+    /// Mark the scan as done; the dashboard stays open until the user quits
+    pub fn finish(&self, outcome: ScanOutcome) {
+        let mut state = self.lock();
+        if state.elapsed.is_none() {
+            state.elapsed = Some(state.started.elapsed());
+        }
+        state.outcome = Some(outcome);
+    }
+
+    /// Whether the scan has finished
+    pub fn is_finished(&self) -> bool {
+        self.lock().outcome.is_some()
+    }
+
+    /// Add a plugin's table, replacing an earlier version of the same table
+    fn add_table(&self, export: Arc<PluginDataExport>) {
+        let mut state = self.lock();
+        match state.tables.iter_mut().find(|table| table.plugin_id == export.plugin_id && table.title == export.title) {
+            Some(table) => *table = export,
+            None => state.tables.push(export),
+        }
+        state.repaint = true;
+    }
+
+    fn run(&self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut view = View::default();
+        loop {
+            let state = {
+                let mut state = self.lock();
+                let snapshot = state.clone();
+                state.repaint = false;
+                snapshot
+            };
+            if state.repaint {
+                terminal.clear()?;
+            }
+            view.clamp(&state.tables);
+            terminal.draw(|frame| render(frame, &state, &mut view))?;
+
+            if !event::poll(REFRESH_INTERVAL)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if view.handle_key(key, &state.tables) == Action::Quit {
+                // Raw mode turns Ctrl-C into a key press, so quitting stops a running scan first
+                if state.outcome.is_some() {
+                    return Ok(());
+                }
+                self.shutdown.abort("stopped from the dashboard");
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DashboardState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ProgressReporter for Dashboard {
+    fn repository_event(&self, event: &RepositoryEvent) {
+        if let RepositoryEvent::RepositoryStarted { total_commits, total_files } = event {
+            let mut state = self.lock();
+            state.total = match (total_commits, total_files) {
+                (None, None) => None,
+                (commits, files) => Some(commits.unwrap_or(0) + files.unwrap_or(0)),
+            };
+            state.done = 0;
+            state.started = Instant::now();
+        }
+    }
+
+    fn commit_scanned(&self) {
+        self.lock().done += 1;
+    }
+
+    fn file_scanned(&self) {
+        self.lock().done += 1;
+    }
+}
+
+#[async_trait]
+impl Subscriber<PluginEvent> for Dashboard {
+    async fn handle_event(&self, event: PluginEvent) -> NotificationResult<()> {
+        if let PluginEvent::DataReady { export, .. } = event {
+            self.add_table(export);
+        }
+        Ok(())
+    }
+
+    fn subscriber_id(&self) -> &str {
+        "tui-dashboard"
+    }
+
+    // Every table is shown, however quickly plugins publish them
+    fn rate_limit(&self) -> Option<RateLimit> {
+        None
+    }
+}
+
+/// What a key press asks the dashboard to do
+#[derive(Debug, PartialEq)]
+enum Action {
+    None,
+    Quit,
+}
+
+/// Selected table and row, kept by the drawing thread
+#[derive(Debug, Default)]
+struct View {
+    table: usize,
+    row: usize,
+}
+
+impl View {
+    fn handle_key(&mut self, key: KeyEvent, tables: &[Arc<PluginDataExport>]) -> Action {
+        let rows = tables.get(self.table).map_or(0, |table| row_count(table));
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Action::Quit,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') if !tables.is_empty() => {
+                self.table = (self.table + 1) % tables.len();
+                self.row = 0;
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') if !tables.is_empty() => {
+                self.table = (self.table + tables.len() - 1) % tables.len();
+                self.row = 0;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.row = (self.row + 1).min(rows.saturating_sub(1)),
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::PageDown => self.row = (self.row + PAGE_ROWS).min(rows.saturating_sub(1)),
+            KeyCode::PageUp => self.row = self.row.saturating_sub(PAGE_ROWS),
+            KeyCode::Home | KeyCode::Char('g') => self.row = 0,
+            KeyCode::End | KeyCode::Char('G') => self.row = rows.saturating_sub(1),
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// Keep the selection inside the tables, which may have been replaced since the last key press
+    fn clamp(&mut self, tables: &[Arc<PluginDataExport>]) {
+        self.table = self.table.min(tables.len().saturating_sub(1));
+        let rows = tables.get(self.table).map_or(0, |table| row_count(table));
+        self.row = self.row.min(rows.saturating_sub(1));
+    }
+}
+
+/// Number of rows a table shows on the dashboard
+fn row_count(export: &PluginDataExport) -> usize {
+    match &export.data {
+        DataPayload::Rows(rows) => rows.len(),
+        DataPayload::KeyValue(values) => values.len(),
+        DataPayload::Matrix(matrix) => matrix.row_labels.len(),
+        _ => 0,
+    }
+}
+
+fn render(frame: &mut Frame, state: &DashboardState, view: &mut View) {
+    let [header, body, queue, help] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list, table] = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(body);
+
+    render_progress(frame, header, state);
+    render_table_list(frame, list, state, view);
+    render_table(frame, table, state, view);
+    frame.render_widget(
+        Paragraph::new(queue_summary(state.queue.as_ref())).block(Block::bordered().title(" Queue ")),
+        queue,
+    );
+    let keys = if state.outcome.is_some() {
+        "←/→ table  ↑/↓ row  PgUp/PgDn page  q quit"
+    } else {
+        "←/→ table  ↑/↓ row  PgUp/PgDn page  q stop the scan"
+    };
+    frame.render_widget(Line::from(keys).dim(), help);
+}
+
+fn render_progress(frame: &mut Frame, area: Rect, state: &DashboardState) {
+    let ratio = match (state.outcome.as_ref(), state.total) {
+        (Some(ScanOutcome::Completed), _) => 1.0,
+        (_, Some(total)) if total > 0 => (state.done.min(total) as f64 / total as f64).clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+    let gauge = Gauge::default()
+        .block(Block::bordered().title(format!(" gstats: {} ", state.repository)))
+        .gauge_style(Style::new().cyan())
+        .ratio(ratio)
+        .label(progress_label(state));
+    frame.render_widget(gauge, area);
+}
+
+/// Text shown on the progress gauge
+fn progress_label(state: &DashboardState) -> String {
+    let elapsed = format_duration(state.elapsed.unwrap_or_else(|| state.started.elapsed()));
+    match &state.outcome {
+        Some(ScanOutcome::Completed) => format!("Completed: {} commits and files in {}", state.done, elapsed),
+        Some(ScanOutcome::Interrupted) => format!("Interrupted after {} commits and files; results are partial", state.done),
+        Some(ScanOutcome::Failed(error)) => format!("Failed: {}", error),
+        None => match state.total.filter(|&total| total > 0) {
+            Some(total) => format!("Scanning {}/{} ({}%), {} elapsed", state.done.min(total), total, state.done.min(total) * 100 / total, elapsed),
+            None => format!("Scanning... {} done, {} elapsed", state.done, elapsed),
+        },
+    }
+}
+
+fn render_table_list(frame: &mut Frame, area: Rect, state: &DashboardState, view: &View) {
+    let items: Vec<ListItem> = state.tables.iter()
+        .map(|table| ListItem::new(format!("{} ({})", table.title, row_count(table))))
+        .collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(view.table));
+    let list = List::new(items)
+        .block(Block::bordered().title(" Results "))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn render_table(frame: &mut Frame, area: Rect, state: &DashboardState, view: &View) {
+    let Some(export) = state.tables.get(view.table) else {
+        let waiting = if state.outcome.is_some() { "No results were published" } else { "Waiting for results..." };
+        frame.render_widget(Paragraph::new(waiting).block(Block::bordered()), area);
+        return;
+    };
+    let block = Block::bordered().title(format!(" {} ", export.title));
+    let (header, rows): (Vec<String>, Vec<Vec<String>>) = match &export.data {
+        DataPayload::Rows(rows) => (
+            export.schema.columns.iter().map(|column| column.name.clone()).collect(),
+            rows.iter().map(|row| row.values.iter().map(ToString::to_string).collect()).collect(),
+        ),
+        DataPayload::KeyValue(values) => {
+            let mut values: Vec<_> = values.iter().collect();
+            values.sort_by(|a, b| a.0.cmp(b.0));
+            (
+                vec!["Key".to_string(), "Value".to_string()],
+                values.into_iter().map(|(key, value)| vec![key.clone(), value.to_string()]).collect(),
+            )
+        }
+        DataPayload::Matrix(matrix) => (
+            std::iter::once(matrix.corner_label.clone()).chain(matrix.column_labels.iter().cloned()).collect(),
+            matrix.row_labels.iter().zip(&matrix.cells)
+                .map(|(label, cells)| std::iter::once(label.clone()).chain(cells.iter().map(ToString::to_string)).collect())
+                .collect(),
+        ),
+        _ => {
+            let text = export.description.clone().unwrap_or_else(|| "This result cannot be shown as a table; export it instead".to_string());
+            frame.render_widget(Paragraph::new(text).block(block), area);
+            return;
+        }
+    };
+    let widths = column_widths(&header, &rows);
+    let table = Table::new(rows.into_iter().map(|row| Row::new(row.into_iter().map(Cell::from))), widths)
+        .header(Row::new(header).bold().underlined())
+        .block(block)
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut table_state = TableState::default().with_selected(Some(view.row));
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+/// Column widths fitting the longest value of each column, header included
+fn column_widths(header: &[String], rows: &[Vec<String>]) -> Vec<Constraint> {
+    (0..header.len())
+        .map(|column| {
+            let width = std::iter::once(&header[column])
+                .chain(rows.iter().filter_map(|row| row.get(column)))
+                .map(|value| value.chars().count())
+                .max()
+                .unwrap_or(0);
+            Constraint::Length(width as u16)
+        })
+        .collect()
+}
+
+/// One line of queue statistics
+fn queue_summary(queue: Option<&QueueStatistics>) -> String {
+    let Some(queue) = queue else {
+        return "Waiting for the queue to start...".to_string();
+    };
+    let mut summary = format!(
+        "{} queued | {:.1} MB | {} consumers | {} processed",
+        queue.queue_size,
+        queue.memory_usage as f64 / (1024.0 * 1024.0),
+        queue.active_consumers,
+        queue.total_messages,
+    );
+    if queue.messages_dropped > 0 {
+        summary.push_str(&format!(" | {} dropped", queue.messages_dropped));
+    }
+    if queue.messages_spilled > 0 {
+        summary.push_str(&format!(" | {} spilled to disk", queue.messages_spilled));
+    }
+    if queue.producer_blocks > 0 {
+        summary.push_str(&format!(" | producer blocked {}x", queue.producer_blocks));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, ColumnType, DataExportType, DataSchema, ExportHints, Row as DataRow, Value};
+    use std::collections::HashMap;
+
+    fn table(title: &str, rows: usize) -> Arc<PluginDataExport> {
+        Arc::new(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: title.to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema {
+                columns: vec![ColumnDef::new("Author", ColumnType::String)],
+                metadata: HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new((0..rows).map(|row| DataRow::new(vec![Value::Integer(row as i64)])).collect())),
+            export_hints: ExportHints::default(),
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_view_navigation() {
+        let tables = vec![table("Authors", 25), table("Files", 3)];
+        let mut view = View::default();
+
+        assert_eq!(view.handle_key(key(KeyCode::PageDown), &tables), Action::None);
+        assert_eq!(view.row, 10);
+        view.handle_key(key(KeyCode::End), &tables);
+        assert_eq!(view.row, 24);
+        view.handle_key(key(KeyCode::Down), &tables);
+        assert_eq!(view.row, 24);
+
+        // Switching tables starts at the top and wraps around
+        view.handle_key(key(KeyCode::Tab), &tables);
+        assert_eq!((view.table, view.row), (1, 0));
+        view.handle_key(key(KeyCode::Tab), &tables);
+        assert_eq!(view.table, 0);
+        view.handle_key(key(KeyCode::Left), &tables);
+        assert_eq!(view.table, 1);
+
+        assert_eq!(view.handle_key(key(KeyCode::Char('q')), &tables), Action::Quit);
+        assert_eq!(view.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL), &tables), Action::Quit);
+
+        view.row = 2;
+        view.clamp(&tables[..1]);
+        assert_eq!((view.table, view.row), (0, 2));
+        view.clamp(&[]);
+        assert_eq!((view.table, view.row), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_tracks_scan() {
+        let shutdown = ShutdownController::new();
+        let dashboard = Dashboard::new("repo", shutdown);
+        dashboard.repository_event(&RepositoryEvent::RepositoryStarted { total_commits: Some(3), total_files: Some(1) });
+        dashboard.commit_scanned();
+        dashboard.file_scanned();
+        assert!(progress_label(&dashboard.lock()).starts_with("Scanning 2/4 (50%)"));
+
+        dashboard.handle_event(PluginEvent::DataReady { plugin_id: "commits".to_string(), scan_id: "scan".to_string(), export: table("Authors", 1) }).await.unwrap();
+        dashboard.handle_event(PluginEvent::DataReady { plugin_id: "commits".to_string(), scan_id: "scan".to_string(), export: table("Authors", 4) }).await.unwrap();
+        assert_eq!(dashboard.lock().tables.len(), 1);
+        assert_eq!(row_count(&dashboard.lock().tables[0]), 4);
+
+        dashboard.finish(ScanOutcome::Completed);
+        assert!(dashboard.is_finished());
+        assert!(progress_label(&dashboard.lock()).starts_with("Completed: 2 commits and files"));
+    }
+}
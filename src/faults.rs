@@ -0,0 +1,164 @@
+//! Fault Injection
+//!
+//! Simulated failures for exercising the recovery paths of the queue, the
+//! plugin registry and the notification system. Only built with the
+//! `fault-injection` feature; release builds contain none of these hooks.
+//!
+//! Faults are read from the environment the first time a hook runs:
+//!
+//! - `GSTATS_FAULT_QUEUE_ERROR=N` - every Nth enqueue fails
+//! - `GSTATS_FAULT_CONSUMER_DELAY=MS` or `PLUGIN:MS` - each message delivered to
+//!   a plugin (or to every plugin) is delayed by MS milliseconds
+//! - `GSTATS_FAULT_PLUGIN_PANIC=PLUGIN[:N]` - the plugin panics on its Nth message (default 1)
+//! - `GSTATS_FAULT_NOTIFICATION_DROP=N` - every Nth notification delivery is dropped
+//!
+//! Faults are counted rather than random, so a failing run can be repeated
+//! exactly. Tests install a plan directly with [`install`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+
+/// Failures to inject, with the counters that decide when each one fires
+#[derive(Debug, Default)]
+pub struct FaultPlan {
+    queue_error_every: Option<u64>,
+    consumer_delay: Option<(Option<String>, Duration)>,
+    plugin_panic: Option<(String, u64)>,
+    notification_drop_every: Option<u64>,
+    enqueues: AtomicU64,
+    deliveries: AtomicU64,
+    plugin_messages: Mutex<HashMap<String, u64>>,
+}
+
+impl FaultPlan {
+    /// Create a plan that injects nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail every nth enqueue
+    pub fn queue_error_every(mut self, n: u64) -> Self {
+        self.queue_error_every = Some(n.max(1));
+        self
+    }
+
+    /// Delay each message delivered to a plugin, or to every plugin when none is named
+    pub fn consumer_delay(mut self, plugin: Option<&str>, delay: Duration) -> Self {
+        self.consumer_delay = Some((plugin.map(str::to_string), delay));
+        self
+    }
+
+    /// Panic inside a plugin when it is given its nth message
+    pub fn plugin_panic(mut self, plugin: &str, n: u64) -> Self {
+        self.plugin_panic = Some((plugin.to_string(), n.max(1)));
+        self
+    }
+
+    /// Drop every nth notification delivery
+    pub fn notification_drop_every(mut self, n: u64) -> Self {
+        self.notification_drop_every = Some(n.max(1));
+        self
+    }
+
+    /// Build the plan from the `GSTATS_FAULT_*` environment variables
+    pub fn from_env() -> Self {
+        let mut plan = Self::new();
+        if let Some(n) = env_value("GSTATS_FAULT_QUEUE_ERROR", |v| v.parse().ok()) {
+            plan = plan.queue_error_every(n);
+        }
+        if let Some((plugin, ms)) = env_value("GSTATS_FAULT_CONSUMER_DELAY", |v| match v.rsplit_once(':') {
+            Some((plugin, ms)) => Some((Some(plugin.to_string()), ms.parse().ok()?)),
+            None => Some((None, v.parse().ok()?)),
+        }) {
+            plan = plan.consumer_delay(plugin.as_deref(), Duration::from_millis(ms));
+        }
+        if let Some((plugin, n)) = env_value("GSTATS_FAULT_PLUGIN_PANIC", |v| match v.rsplit_once(':') {
+            Some((plugin, n)) => Some((plugin.to_string(), n.parse().ok()?)),
+            None => Some((v.to_string(), 1)),
+        }) {
+            plan = plan.plugin_panic(&plugin, n);
+        }
+        if let Some(n) = env_value("GSTATS_FAULT_NOTIFICATION_DROP", |v| v.parse().ok()) {
+            plan = plan.notification_drop_every(n);
+        }
+        plan
+    }
+
+    fn queue_error(&self) -> bool {
+        every(self.queue_error_every, &self.enqueues)
+    }
+
+    fn drop_notification(&self) -> bool {
+        every(self.notification_drop_every, &self.deliveries)
+    }
+
+    fn delay_for(&self, plugin: &str) -> Option<Duration> {
+        match &self.consumer_delay {
+            Some((None, delay)) => Some(*delay),
+            Some((Some(target), delay)) if target == plugin => Some(*delay),
+            _ => None,
+        }
+    }
+
+    fn should_panic(&self, plugin: &str) -> bool {
+        let Some((target, n)) = &self.plugin_panic else {
+            return false;
+        };
+        if target != plugin {
+            return false;
+        }
+        let mut counts = self.plugin_messages.lock().unwrap_or_else(|e| e.into_inner());
+        let count = counts.entry(plugin.to_string()).or_default();
+        *count += 1;
+        *count == *n
+    }
+}
+
+fn every(n: Option<u64>, counter: &AtomicU64) -> bool {
+    n.is_some_and(|n| (counter.fetch_add(1, Ordering::Relaxed) + 1).is_multiple_of(n))
+}
+
+fn env_value<T>(name: &str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    let parsed = parse(value.trim());
+    if parsed.is_none() {
+        log::warn!("Ignoring invalid {}='{}'", name, value);
+    }
+    parsed
+}
+
+static PLAN: OnceLock<RwLock<Arc<FaultPlan>>> = OnceLock::new();
+
+fn plan() -> Arc<FaultPlan> {
+    let plan = PLAN.get_or_init(|| RwLock::new(Arc::new(FaultPlan::from_env())));
+    Arc::clone(&plan.read().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Replace the active plan, resetting its counters
+pub fn install(new_plan: FaultPlan) {
+    let plan = PLAN.get_or_init(|| RwLock::new(Arc::new(FaultPlan::new())));
+    *plan.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(new_plan);
+}
+
+/// Check whether this enqueue should fail
+pub fn queue_error() -> bool {
+    plan().queue_error()
+}
+
+/// Check whether this notification delivery should be dropped
+pub fn drop_notification() -> bool {
+    plan().drop_notification()
+}
+
+/// Apply any delay or panic planned for a plugin's next message
+pub async fn before_plugin_message(plugin: &str) {
+    let plan = plan();
+    if let Some(delay) = plan.delay_for(plugin) {
+        tokio::time::sleep(delay).await;
+    }
+    if plan.should_panic(plugin) {
+        panic!("injected panic in plugin '{}'", plugin);
+    }
+}
@@ -1,11 +1,44 @@
 // Library modules for the gstats application
+//
+// Plugins built outside gstats use the `sdk` module; the other modules are
+// application internals, hidden from the documentation and not covered by
+// semver.
 
+#[doc(hidden)]
 pub mod app;
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
 pub mod cli;
+#[doc(hidden)]
+pub mod clock;
+#[doc(hidden)]
 pub mod config;
+#[doc(hidden)]
 pub mod display;
+#[cfg(feature = "fault-injection")]
+#[doc(hidden)]
+pub mod faults;
+#[doc(hidden)]
 pub mod logging;
+#[doc(hidden)]
 pub mod notifications;
+#[doc(hidden)]
 pub mod queue;
+#[doc(hidden)]
+pub mod runtime;
+#[doc(hidden)]
+pub mod shutdown;
+#[doc(hidden)]
 pub mod scanner;
+#[doc(hidden)]
 pub mod plugin;
+#[doc(hidden)]
+pub mod profiling;
+pub mod sdk;
+#[cfg(feature = "otel")]
+#[doc(hidden)]
+pub mod telemetry;
+
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
@@ -5,6 +5,8 @@
 // - Multiple output formats: Text and JSON
 // - Multiple destinations: Console, File, or Both
 // - Independent log levels for console and file output
+// - Per-module level overrides (`scanner=debug,plugin=warn`)
+// - Size-based log file rotation
 // - Structured timestamp formatting (YYYY-MM-DD HH:mm:ss)
 // - Extensible JSON structure with optional detail field for future enhancements
 //
@@ -15,6 +17,9 @@
 //     file_level: Some(LevelFilter::Debug),
 //     format: LogFormat::Json,
 //     destination: LogDestination::Both(PathBuf::from("app.log")),
+//     filter: LogFilter::parse("scanner=debug")?,
+//     rotation: Some(LogRotation { max_size: 10 * 1024 * 1024, keep: 5 }),
+//     ..LogConfig::default()
 // };
 // init_logger(config)?;
 // log::info!("Application started");
@@ -25,7 +30,8 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Local};
 use std::fs::OpenOptions;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use anyhow::{Context, Result};
 use crate::display::{ColourManager, ColourConfig};
 
@@ -60,11 +66,103 @@ pub enum LogDestination {
 pub struct JsonLogEntry {
     pub timestamp: String,
     pub level: String,
+    /// Module path of the record, e.g. `gstats::scanner::engine`
+    pub target: String,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<serde_json::Value>,
 }
 
+/// Per-module log level overrides
+///
+/// Parsed from a comma-separated list of `module=level` directives such as
+/// `scanner=debug,plugin=warn`. A module matches its own records and those of
+/// its submodules, with or without the `gstats::` crate prefix; the most
+/// specific directive wins. Overrides apply to both console and file output.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut directives = Vec::new();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (module, level) = directive.split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid log filter '{}': expected module=level", directive))?;
+            let module = module.trim();
+            if module.is_empty() {
+                return Err(anyhow::anyhow!("Invalid log filter '{}': missing module name", directive));
+            }
+            directives.push((module.to_string(), parse_log_level(level.trim())?));
+        }
+        Ok(Self { directives })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// The overriding level for records from `target`, if any directive matches
+    pub fn level_for(&self, target: &str) -> Option<LevelFilter> {
+        let local = target.strip_prefix("gstats::").unwrap_or(target);
+        self.directives.iter()
+            .filter(|(module, _)| [target, local].iter().any(|path| {
+                path.strip_prefix(module.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            }))
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+    }
+
+    /// The most verbose level any directive enables
+    pub fn max_level(&self) -> LevelFilter {
+        self.directives.iter().map(|(_, level)| *level).max().unwrap_or(LevelFilter::Off)
+    }
+}
+
+/// Log file rotation by size
+///
+/// Before a write would grow the log file past `max_size` bytes it is renamed
+/// to `<file>.1`, older files shift up to `<file>.<keep>`, and the oldest is
+/// removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRotation {
+    pub max_size: u64,
+    pub keep: usize,
+}
+
+impl LogRotation {
+    /// Default number of rotated files kept
+    pub const DEFAULT_KEEP: usize = 5;
+
+    /// Rotate `path` if appending `incoming` bytes would exceed the size limit
+    fn rotate_if_needed(&self, path: &Path, incoming: u64) -> io::Result<()> {
+        let size = match std::fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => return Ok(()),
+        };
+        if size == 0 || size + incoming <= self.max_size {
+            return Ok(());
+        }
+        if self.keep == 0 {
+            return std::fs::remove_file(path);
+        }
+        let rotated = |index: usize| {
+            let mut name = path.as_os_str().to_owned();
+            name.push(format!(".{}", index));
+            PathBuf::from(name)
+        };
+        let _ = std::fs::remove_file(rotated(self.keep));
+        for index in (1..self.keep).rev() {
+            let from = rotated(index);
+            if from.exists() {
+                std::fs::rename(&from, rotated(index + 1))?;
+            }
+        }
+        std::fs::rename(path, rotated(1))
+    }
+}
+
 /// Logging configuration
 #[derive(Debug, Clone)]
 pub struct LogConfig {
@@ -74,6 +172,8 @@ pub struct LogConfig {
     pub destination: LogDestination,
     pub colour_config: Option<ColourConfig>,
     pub enable_colours: bool,
+    pub filter: LogFilter,
+    pub rotation: Option<LogRotation>,
 }
 
 impl Default for LogConfig {
@@ -85,6 +185,8 @@ impl Default for LogConfig {
             destination: LogDestination::Console,
             colour_config: None,
             enable_colours: true,
+            filter: LogFilter::default(),
+            rotation: None,
         }
     }
 }
@@ -103,6 +205,8 @@ impl LogConfig {
 pub struct GstatsLogger {
     config: LogConfig,
     colour_manager: Option<ColourManager>,
+    /// Serialises file writes so rotation never races an append
+    file_lock: Mutex<()>,
 }
 
 impl GstatsLogger {
@@ -120,6 +224,7 @@ impl GstatsLogger {
         Self {
             config,
             colour_manager,
+            file_lock: Mutex::new(()),
         }
     }
 
@@ -189,7 +294,7 @@ impl GstatsLogger {
         }
     }
 
-    fn format_json_message(&self, level: Level, message: &str) -> Result<String> {
+    fn format_json_message(&self, level: Level, target: &str, message: &str) -> Result<String> {
         let level_str = Self::level_to_abbreviation(level);
         
         // Add color information to JSON when colors are enabled
@@ -212,6 +317,7 @@ impl GstatsLogger {
         let entry = JsonLogEntry {
             timestamp: Self::format_timestamp(),
             level: level_str,
+            target: target.to_string(),
             message: message.to_string(),
             detail,
         };
@@ -220,13 +326,13 @@ impl GstatsLogger {
             .context("Failed to serialize log entry to JSON")
     }
 
-    fn should_log_to_console(&self, level: Level) -> bool {
-        level <= self.config.console_level
+    fn should_log_to_console(&self, level: Level, target: &str) -> bool {
+        level <= self.config.filter.level_for(target).unwrap_or(self.config.console_level)
     }
 
-    fn should_log_to_file(&self, level: Level) -> bool {
+    fn should_log_to_file(&self, level: Level, target: &str) -> bool {
         if let Some(file_level) = self.config.file_level {
-            level <= file_level
+            level <= self.config.filter.level_for(target).unwrap_or(file_level)
         } else {
             false
         }
@@ -238,6 +344,11 @@ impl GstatsLogger {
     }
 
     fn write_to_file(&self, formatted_message: &str, file_path: &PathBuf) -> Result<()> {
+        let _guard = self.file_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(rotation) = &self.config.rotation {
+            rotation.rotate_if_needed(file_path, formatted_message.len() as u64 + 1)
+                .with_context(|| format!("Failed to rotate log file: {}", file_path.display()))?;
+        }
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
@@ -251,8 +362,8 @@ impl GstatsLogger {
 
 impl log::Log for GstatsLogger {
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        self.should_log_to_console(metadata.level()) || 
-        self.should_log_to_file(metadata.level())
+        self.should_log_to_console(metadata.level(), metadata.target()) ||
+        self.should_log_to_file(metadata.level(), metadata.target())
     }
 
     fn log(&self, record: &log::Record) {
@@ -262,12 +373,13 @@ impl log::Log for GstatsLogger {
 
         let message = record.args().to_string();
         let level = record.level();
+        let target = record.target();
 
         // Format message based on configured format
         let formatted_message = match self.config.format {
             LogFormat::Text => self.format_text_message(level, &message),
             LogFormat::Json => {
-                match self.format_json_message(level, &message) {
+                match self.format_json_message(level, target, &message) {
                     Ok(json) => json,
                     Err(e) => {
                         // Fallback to text format if JSON serialization fails
@@ -281,19 +393,19 @@ impl log::Log for GstatsLogger {
         // Write to appropriate destinations
         match &self.config.destination {
             LogDestination::Console => {
-                if self.should_log_to_console(level) {
+                if self.should_log_to_console(level, target) {
                     if let Err(e) = self.write_to_console(&formatted_message) {
                         eprintln!("Console logging error: {}", e);
                     }
                 }
             }
             LogDestination::Both(path) => {
-                if self.should_log_to_console(level) {
+                if self.should_log_to_console(level, target) {
                     if let Err(e) = self.write_to_console(&formatted_message) {
                         eprintln!("Console logging error: {}", e);
                     }
                 }
-                if self.should_log_to_file(level) {
+                if self.should_log_to_file(level, target) {
                     if let Err(e) = self.write_to_file(&formatted_message, path) {
                         eprintln!("File logging error: {}", e);
                     }
@@ -320,7 +432,7 @@ pub fn init_logger(config: LogConfig) -> Result<()> {
             }
         }
         (None, console_level) => console_level,
-    };
+    }.max(config.filter.max_level());
 
     log::set_boxed_logger(Box::new(logger))
         .context("Failed to set global logger")?;
@@ -386,6 +498,7 @@ mod tests {
         let entry = JsonLogEntry {
             timestamp: "2025-07-26 14:30:45".to_string(),
             level: "INFO".to_string(),
+            target: "gstats::scanner".to_string(),
             message: "Test message".to_string(),
             detail: None,
         };
@@ -393,6 +506,7 @@ mod tests {
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains(r#""timestamp":"2025-07-26 14:30:45""#));
         assert!(json.contains(r#""level":"INFO""#));
+        assert!(json.contains(r#""target":"gstats::scanner""#));
         assert!(json.contains(r#""message":"Test message""#));
         // detail field should be omitted when None
         assert!(!json.contains(r#""detail""#));
@@ -406,7 +520,7 @@ mod tests {
         let formatted = logger.format_text_message(Level::Info, "Test message");
         assert!(formatted.contains("INF"));
         assert!(formatted.contains("Test message"));
-        assert!(formatted.contains("2025-")); // Should contain current year
+        assert!(formatted.contains(&format!("{}-", chrono::Local::now().format("%Y")))); // Should contain current year
         // Should not contain square brackets
         assert!(!formatted.contains("[INF]"));
     }
@@ -419,7 +533,7 @@ mod tests {
         let formatted = logger.format_text_message(Level::Info, "Test message");
         assert!(formatted.contains("INF"));
         assert!(formatted.contains("Test message"));
-        assert!(formatted.contains("2025-")); // Should contain current year
+        assert!(formatted.contains(&format!("{}-", chrono::Local::now().format("%Y")))); // Should contain current year
         // Should not contain ANSI color codes
         assert!(!formatted.contains("\x1b["));
         // Should not contain square brackets
@@ -431,7 +545,7 @@ mod tests {
         let config = LogConfig::default();
         let logger = GstatsLogger::new(config);
         
-        let formatted = logger.format_json_message(Level::Info, "Test message").unwrap();
+        let formatted = logger.format_json_message(Level::Info, "gstats", "Test message").unwrap();
         assert!(formatted.contains(r#""level":"INF""#));
         assert!(formatted.contains(r#""message":"Test message""#));
         assert!(formatted.contains(r#""timestamp":"#));
@@ -445,7 +559,7 @@ mod tests {
         let config = LogConfig::default().with_colors(false);
         let logger = GstatsLogger::new(config);
         
-        let formatted = logger.format_json_message(Level::Info, "Test message").unwrap();
+        let formatted = logger.format_json_message(Level::Info, "gstats", "Test message").unwrap();
         assert!(formatted.contains(r#""level":"INF""#));
         assert!(formatted.contains(r#""message":"Test message""#));
         assert!(formatted.contains(r#""timestamp":"#));
@@ -460,23 +574,23 @@ mod tests {
         let logger = GstatsLogger::new(config);
         
         // Test all log levels have appropriate color mapping and abbreviations
-        let error_json = logger.format_json_message(Level::Error, "Error").unwrap();
+        let error_json = logger.format_json_message(Level::Error, "gstats", "Error").unwrap();
         assert!(error_json.contains(r#""level":"ERR""#));
         assert!(error_json.contains(r#""color":"error""#));
         
-        let warn_json = logger.format_json_message(Level::Warn, "Warning").unwrap();
+        let warn_json = logger.format_json_message(Level::Warn, "gstats", "Warning").unwrap();
         assert!(warn_json.contains(r#""level":"WRN""#));
         assert!(warn_json.contains(r#""color":"warning""#));
         
-        let info_json = logger.format_json_message(Level::Info, "Info").unwrap();
+        let info_json = logger.format_json_message(Level::Info, "gstats", "Info").unwrap();
         assert!(info_json.contains(r#""level":"INF""#));
         assert!(info_json.contains(r#""color":"info""#));
         
-        let debug_json = logger.format_json_message(Level::Debug, "Debug").unwrap();
+        let debug_json = logger.format_json_message(Level::Debug, "gstats", "Debug").unwrap();
         assert!(debug_json.contains(r#""level":"DBG""#));
         assert!(debug_json.contains(r#""color":"debug""#));
         
-        let trace_json = logger.format_json_message(Level::Trace, "Trace").unwrap();
+        let trace_json = logger.format_json_message(Level::Trace, "gstats", "Trace").unwrap();
         assert!(trace_json.contains(r#""level":"TRC""#));
         assert!(trace_json.contains(r#""color":"debug""#)); // Trace uses debug color
     }
@@ -489,4 +603,59 @@ mod tests {
         assert_eq!(GstatsLogger::level_to_abbreviation(Level::Debug), "DBG");
         assert_eq!(GstatsLogger::level_to_abbreviation(Level::Trace), "TRC");
     }
+
+    #[test]
+    fn test_log_filter() {
+        let filter = LogFilter::parse("scanner=debug, plugin=warn,scanner::engine=trace").unwrap();
+        assert_eq!(filter.level_for("gstats::scanner::async_engine"), Some(LevelFilter::Debug));
+        assert_eq!(filter.level_for("gstats::scanner::engine::tasks"), Some(LevelFilter::Trace));
+        assert_eq!(filter.level_for("scanner"), Some(LevelFilter::Debug));
+        assert_eq!(filter.level_for("gstats::plugin::registry"), Some(LevelFilter::Warn));
+        assert_eq!(filter.level_for("gstats::plugins"), None);
+        assert_eq!(filter.level_for("gstats::queue"), None);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+
+        assert!(LogFilter::parse("").unwrap().is_empty());
+        assert!(LogFilter::parse("scanner").is_err());
+        assert!(LogFilter::parse("=debug").is_err());
+        assert!(LogFilter::parse("scanner=loud").is_err());
+    }
+
+    #[test]
+    fn test_filter_overrides_console_level() {
+        let config = LogConfig {
+            console_level: LevelFilter::Info,
+            filter: LogFilter::parse("scanner=debug,plugin=error").unwrap(),
+            ..LogConfig::default()
+        };
+        let logger = GstatsLogger::new(config);
+        assert!(logger.should_log_to_console(Level::Debug, "gstats::scanner::engine"));
+        assert!(!logger.should_log_to_console(Level::Debug, "gstats::queue"));
+        assert!(!logger.should_log_to_console(Level::Warn, "gstats::plugin::registry"));
+        assert!(logger.should_log_to_console(Level::Warn, "gstats::queue"));
+    }
+
+    #[test]
+    fn test_log_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gstats.log");
+        let rotation = LogRotation { max_size: 10, keep: 2 };
+        for generation in ["first", "second", "third"] {
+            rotation.rotate_if_needed(&path, 8).unwrap();
+            std::fs::write(&path, generation).unwrap();
+        }
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("gstats.log"), "third");
+        assert_eq!(read("gstats.log.1"), "second");
+        assert_eq!(read("gstats.log.2"), "first");
+
+        rotation.rotate_if_needed(&path, 8).unwrap();
+        assert_eq!(read("gstats.log.2"), "second");
+        assert!(!path.exists());
+
+        // Small writes don't rotate
+        std::fs::write(&path, "abc").unwrap();
+        rotation.rotate_if_needed(&path, 4).unwrap();
+        assert!(path.exists());
+    }
 }
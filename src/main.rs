@@ -1,12 +1,23 @@
 mod cli;
+mod clock;
 mod config;
 mod display;
+#[cfg(feature = "fault-injection")]
+mod faults;
 mod logging;
 mod notifications;
 mod queue;
 mod scanner;
 mod plugin;
+mod profiling;
 mod app;
+mod cache;
+mod runtime;
+mod shutdown;
+#[cfg(feature = "otel")]
+mod telemetry;
+#[cfg(test)]
+mod test_support;
 
 use anyhow::{Result, Context};
 use std::process;
@@ -72,6 +83,12 @@ fn main() {
             process::exit(0);
         }
         
+        // A plugin's panic is caught by the plugin registry, which cancels the plugin and carries on
+        if plugin::registry::panic_is_contained() {
+            log::debug!("Plugin panicked: {}", panic_info);
+            return;
+        }
+        
         // For other panics, show error and exit with error code
         error!("Application panicked: {:?}", panic_info);
         eprintln!("Panic: {:?}", panic_info);
@@ -79,6 +96,21 @@ fn main() {
     }));
     
     if let Err(e) = run() {
+        // --expect-data found nothing: a distinct status lets scheduled jobs retry or alert
+        if let Some(no_data) = e.downcast_ref::<app::NoDataError>() {
+            eprintln!("{}", no_data);
+            process::exit(app::EXIT_NO_DATA);
+        }
+        // A --fail-on condition held: the run succeeded but the results fail the gate
+        if let Some(gate) = e.downcast_ref::<app::GateFailedError>() {
+            eprintln!("{}", gate);
+            process::exit(app::EXIT_GATE_FAILED);
+        }
+        if let Some(interrupted) = e.downcast_ref::<app::InterruptedError>() {
+            eprintln!("{}", interrupted);
+            process::exit(shutdown::EXIT_INTERRUPTED);
+        }
+        
         let error_msg = e.to_string();
         
         // Check if this is a user-facing command error (not a system error)
@@ -119,10 +151,20 @@ fn run() -> Result<()> {
     }
     
     // Stage 1.5: Create plugin settings from initial args and setup plugin system
-    let raw_args: Vec<String> = std::env::args().skip(1).collect();
+    let mut raw_args: Vec<String> = std::env::args().skip(1).collect();
+    
+    // `gstats patch <FILE|DIR> ...` is shorthand for `gstats --patch <FILE|DIR> ...`
+    if raw_args.first().is_some_and(|arg| arg == "patch") {
+        raw_args[0] = "--patch".to_string();
+    }
+    
+    // `gstats plugins [list|info <PLUGIN>|formats|doctor]` manages plugins instead of scanning
+    let plugins_command = cli::plugins_command::PluginsCommand::extract(&mut raw_args)?;
+    // `gstats trends <METRIC>` lists a recorded metric; plain `trends` stays the contributors function
+    let trends_metric = cli::trends_command::extract(&mut raw_args);
     
     // Create plugin settings early from parsed initial args
-    let plugin_settings = crate::plugin::PluginSettings::from_initial_args(&initial_args);
+    let plugin_settings = crate::plugin::PluginSettings::from_color_flags(initial_args.color, initial_args.no_color);
     
     // Set up early color override based on initial args (not global args parsing)
     if initial_args.no_color {
@@ -148,15 +190,13 @@ fn run() -> Result<()> {
         } else {
             Vec::new()
         },
+        timeout: Some(crate::plugin::registry::DEFAULT_PLUGIN_TIMEOUT),
     };
     
-    // Create a minimal runtime for plugin initialization
-    let rt = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .context("Failed to create tokio runtime for plugin initialization")?;
+    // Size the shared runtime before its first use during plugin initialization
+    runtime::configure(initial_args.worker_threads);
     
-    let segmenter = rt.block_on(async {
+    let segmenter = runtime::block_on(async {
         let plugin_handler = PluginHandler::with_plugin_config_and_settings(plugin_config, plugin_settings.clone())
             .map_err(|e| anyhow::anyhow!("Failed to create plugin handler: {}", e))?;
         let mut plugin_handler = plugin_handler;
@@ -183,8 +223,13 @@ fn run() -> Result<()> {
     
     // Add ONLY the first plugin command as the positional command argument
     // Do NOT include plugin arguments - they should be handled by the plugin itself
+    // Keep the selected function so it is not collapsed to the plugin's default
     if let Some(first_segment) = segmented.plugin_segments.first() {
-        clap_args.push(first_segment.plugin_name.clone());
+        let command = match &first_segment.function_name {
+            Some(function) => format!("{}:{}", first_segment.plugin_name, function),
+            None => first_segment.plugin_name.clone(),
+        };
+        clap_args.push(command);
     }
     
     // Stage 2: Parse global arguments with clap
@@ -200,14 +245,23 @@ fn run() -> Result<()> {
     let log_config = app::configure_logging(&args, &config_manager)?;
     logging::init_logger(log_config)?;
     
+    // Stage timings are collected from here so setup is included in the run time
+    if args.profile || args.profile_trace.is_some() {
+        profiling::enable(args.profile_trace.is_some());
+    }
+    
+    // Trace spans are exported until the guard is dropped at the end of the run
+    #[cfg(feature = "otel")]
+    let _telemetry = telemetry::init(args.otlp_endpoint.as_deref());
+    
     // Enhanced logging system is now ready
     
-    // Handle configuration export command first (before creating runtime)
+    // Handle configuration export command first (before any async work)
     if let Some(export_path) = &args.export_config {
         return app::initialization::handle_export_config(&config_manager, export_path);
     }
     
-    // Main is no longer async - components can create their own runtimes cleanly
+    // Main is not async - all components share the runtime in crate::runtime
     
     // Handle help command
     if args.help {
@@ -216,20 +270,46 @@ fn run() -> Result<()> {
     }
     
     // Handle plugin management commands
-    if args.list_plugins || args.show_plugins || args.plugins_help || args.plugin_info.is_some() || args.list_formats {
-        let config_manager = app::load_configuration(&args)?;
-        return rt.block_on(app::handle_plugin_commands(&args, &config_manager));
+    if let Some(command) = plugins_command.or_else(|| cli::plugins_command::PluginsCommand::from_flags(&args)) {
+        return runtime::block_on(app::handle_plugin_commands(&command, &args, &config_manager));
+    }
+    
+    // Handle trend queries
+    if let Some(metric) = &trends_metric {
+        let repo_path = resolve_repository_path(args.repository.as_deref())?;
+        return app::trends::show(metric, &repo_path, &args, &config_manager);
     }
     
     // Handle --show-branch command
     if args.show_branch {
-        return rt.block_on(app::handle_show_branch_command(&args, &config_manager));
+        return runtime::block_on(app::handle_show_branch_command(&args, &config_manager));
+    }
+    
+    // Handle --repos/--repos-file: every listed repository scanned and reported together
+    if !args.repos.is_empty() || args.repos_file.is_some() {
+        return app::run_repositories(&args, &config_manager);
     }
     
     // Resolve repository path (scanner will validate it's a git repository)
+    // An explicit --git-dir replaces this path once the scanner config is resolved
     let repo_path = resolve_repository_path(args.repository.as_deref())?;
     
-    // Scanner handles its own runtime internally - clean sync interface
+    // Handle --compare: two scans reported as delta tables instead of a plugin run
+    if args.compare.is_some() {
+        return app::run_comparison(repo_path, &args, &config_manager);
+    }
+    
+    // Handle --matrix-refs: one scan per ref reported side by side
+    if !args.matrix_refs.is_empty() {
+        return app::run_ref_matrix(repo_path, &args, &config_manager);
+    }
+    
+    // Handle --debt-trend: technical debt assessed at sampled commits
+    if args.debt_trend.is_some() {
+        return app::run_debt_trend(repo_path, &args, &config_manager);
+    }
+    
+    // Scanner runs on the shared runtime behind a sync interface
     app::run_scanner(repo_path, args, config_manager)
 }
 
@@ -307,7 +307,14 @@ impl ScanEvent {
         }
     }
     
-    
+    /// Create a scan warning event
+    pub fn warning(scan_id: String, warning: String, recoverable: bool) -> Self {
+        Self::ScanWarning {
+            scan_id,
+            warning,
+            recoverable,
+        }
+    }
     
     /// Create a scan error event
     pub fn error(scan_id: String, error: String, fatal: bool) -> Self {
@@ -137,6 +137,12 @@ where
             ));
         }
         
+        #[cfg(feature = "fault-injection")]
+        if crate::faults::drop_notification() {
+            subscriber_info.stats.events_dropped += 1;
+            return Err(NotificationError::delivery_failed(subscriber_id, "injected notification drop".to_string()));
+        }
+        
         // Update stats
         subscriber_info.stats.events_received += 1;
         subscriber_info.stats.last_event_at = Some(SystemTime::now());
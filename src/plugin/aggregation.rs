@@ -0,0 +1,159 @@
+//! Sharded Statistics Aggregation
+//!
+//! Consumer plugins update their statistics once per message. Keeping them
+//! behind a single lock serialises every update, so a [`ShardedTally`] splits
+//! them into independently locked shards instead: each update locks only the
+//! shard its key hashes to, and readers merge the shards into one tally when
+//! they need the totals (at export or when a function is executed).
+//!
+//! Updates that depend on each other must use the same key. The commits
+//! plugin, for example, keys by commit hash so that a commit and its file
+//! changes always meet in the same shard.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::plugin::memory::HeapSize;
+
+/// Statistics that can be combined with another set collected separately
+pub trait Merge: Default {
+    /// Add `other` to these statistics
+    fn merge(&mut self, other: Self);
+}
+
+/// Statistics split over independently locked shards
+#[derive(Debug)]
+pub struct ShardedTally<T> {
+    shards: Box<[Mutex<T>]>,
+}
+
+impl<T: Merge> ShardedTally<T> {
+    /// Create a tally with a few shards per available CPU
+    pub fn new() -> Self {
+        let cpus = std::thread::available_parallelism().map_or(4, |cpus| cpus.get());
+        Self::with_shards(cpus * 4)
+    }
+
+    /// Create a tally with a fixed number of shards (at least one)
+    pub fn with_shards(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1)).map(|_| Mutex::new(T::default())).collect(),
+        }
+    }
+
+    /// Number of shards
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Update the shard a key belongs to; the lock is held only while `update` runs
+    pub fn update<K: Hash + ?Sized, R>(&self, key: &K, update: impl FnOnce(&mut T) -> R) -> R {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let shard = (hasher.finish() % self.shards.len() as u64) as usize;
+        update(&mut lock(&self.shards[shard]))
+    }
+
+    /// Merge all shards into one tally, leaving the shards empty
+    pub fn take(&self) -> T {
+        self.shards.iter().fold(T::default(), |mut total, shard| {
+            total.merge(std::mem::take(&mut *lock(shard)));
+            total
+        })
+    }
+
+    /// Empty every shard
+    pub fn reset(&self) {
+        for shard in self.shards.iter() {
+            *lock(shard) = T::default();
+        }
+    }
+}
+
+impl<T: Merge + Clone> ShardedTally<T> {
+    /// Merged copy of all shards, leaving them as they are
+    pub fn snapshot(&self) -> T {
+        self.shards.iter().fold(T::default(), |mut total, shard| {
+            total.merge(lock(shard).clone());
+            total
+        })
+    }
+}
+
+impl<T: HeapSize> HeapSize for ShardedTally<T> {
+    fn heap_size(&self) -> usize {
+        self.shards.iter()
+            .map(|shard| std::mem::size_of::<Mutex<T>>() + lock(shard).heap_size())
+            .sum()
+    }
+}
+
+impl<T: Merge> Default for ShardedTally<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lock a shard, recovering its statistics if an update panicked
+fn lock<T>(shard: &Mutex<T>) -> MutexGuard<'_, T> {
+    shard.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct AuthorCounts(HashMap<String, usize>);
+
+    impl Merge for AuthorCounts {
+        fn merge(&mut self, other: Self) {
+            for (author, count) in other.0 {
+                *self.0.entry(author).or_insert(0) += count;
+            }
+        }
+    }
+
+    #[test]
+    fn test_sharded_updates_merge() {
+        let tally = Arc::new(ShardedTally::<AuthorCounts>::with_shards(8));
+        let threads: Vec<_> = (0..4)
+            .map(|thread| {
+                let tally = tally.clone();
+                std::thread::spawn(move || {
+                    for commit in 0..1000 {
+                        let author = format!("author-{}", (thread + commit) % 5);
+                        tally.update(&format!("{}-{}", thread, commit), |counts| {
+                            *counts.0.entry(author).or_insert(0) += 1;
+                        });
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let snapshot = tally.snapshot();
+        assert_eq!(snapshot.0.values().sum::<usize>(), 4000);
+        assert_eq!(snapshot.0["author-0"], 800);
+
+        assert_eq!(tally.take(), snapshot);
+        assert!(tally.snapshot().0.is_empty());
+    }
+
+    #[test]
+    fn test_same_key_same_shard() {
+        let tally = ShardedTally::<AuthorCounts>::with_shards(16);
+        tally.update("abc123", |counts| counts.0.insert("editor".to_string(), 1));
+        let seen = tally.update("abc123", |counts| counts.0.get("editor").copied());
+        assert_eq!(seen, Some(1));
+
+        tally.reset();
+        assert!(tally.snapshot().0.is_empty());
+        assert_eq!(ShardedTally::<AuthorCounts>::with_shards(0).shard_count(), 1);
+    }
+}
@@ -0,0 +1,288 @@
+//! Plugin Argument Presets
+//!
+//! Long lists of plugin flags can be stored once and reused. A preset is a
+//! configuration section naming a plugin and the flags it applies:
+//!
+//! ```toml
+//! [plugin.metrics.presets.strict]
+//! top = 10
+//! complexity-weight = 0.6
+//! ```
+//!
+//! Each key is a long flag name. `true` adds a switch (`--tree`), `false` leaves
+//! it out, an array repeats the flag once per element and any other value is
+//! passed as the flag's value. `gstats metrics --preset strict` then runs as
+//! `gstats metrics --complexity-weight 0.6 --top 10`.
+//!
+//! Arguments can also be kept in a file and applied with `--args-from FILE`,
+//! written as they would be on the command line, over as many lines as needed
+//! (`#` starts a comment). Presets and files are expanded in place before the
+//! plugin parses its arguments, and a flag given again later on the command
+//! line overrides the earlier value, so `--preset strict --top 5` lists five.
+//!
+//! Options set directly in a plugin's own section are its defaults, applied
+//! on every run of the plugin, including as a dependency of another command:
+//!
+//! ```toml
+//! [plugin.metrics]
+//! complexity-weight = 0.5
+//! ```
+//!
+//! A default is left out when the plugin's arguments (after expanding presets
+//! and files) set the same long flag, so switches and repeated flags given on
+//! the command line replace their configured values rather than adding to them.
+
+use crate::plugin::{PluginError, PluginResult};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Flag applying a named preset
+pub const PRESET_FLAG: &str = "--preset";
+
+/// Flag applying the arguments in a file
+pub const ARGS_FROM_FLAG: &str = "--args-from";
+
+/// Named argument presets of each plugin
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArgPresets {
+    presets: HashMap<String, BTreeMap<String, Vec<String>>>,
+    /// Arguments of each plugin's configured defaults, by flag name
+    defaults: HashMap<String, BTreeMap<String, Vec<String>>>,
+}
+
+impl ArgPresets {
+    /// Create an empty set of presets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a preset for a plugin from its configured flag values
+    pub fn insert<'a>(&mut self, plugin: &str, preset: &str, values: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        let mut values: Vec<(&str, &str)> = values.into_iter().collect();
+        values.sort();
+        let args = values.into_iter().flat_map(|(flag, value)| flag_args(flag, value)).collect();
+        self.presets.entry(plugin.to_string()).or_default().insert(preset.to_string(), args);
+    }
+
+    /// Add a plugin's configured default flag values
+    pub fn insert_defaults<'a>(&mut self, plugin: &str, values: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        let defaults = self.defaults.entry(plugin.to_string()).or_default();
+        for (flag, value) in values {
+            defaults.insert(flag.trim_start_matches('-').to_string(), flag_args(flag, value));
+        }
+    }
+    
+    /// Arguments of a plugin's preset
+    pub fn get(&self, plugin: &str, preset: &str) -> Option<&[String]> {
+        self.presets.get(plugin)?.get(preset).map(Vec::as_slice)
+    }
+
+    /// Names of a plugin's presets, sorted
+    pub fn names(&self, plugin: &str) -> Vec<&str> {
+        self.presets.get(plugin)
+            .map(|presets| presets.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Check whether no presets or defaults are configured
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty() && self.defaults.is_empty()
+    }
+
+    /// Put a plugin's configured defaults before its (expanded) arguments, leaving out
+    /// each default whose flag the arguments set themselves
+    pub fn apply_defaults(&self, plugin: &str, args: &[String]) -> Vec<String> {
+        let Some(defaults) = self.defaults.get(plugin) else {
+            return args.to_vec();
+        };
+        defaults.iter()
+            .filter(|(flag, _)| !args.iter().any(|arg| sets_flag(arg, flag)))
+            .flat_map(|(_, default)| default.iter().cloned())
+            .chain(args.iter().cloned())
+            .collect()
+    }
+
+    /// Replace each `--preset NAME` and `--args-from FILE` in a plugin's arguments with the arguments it stands for
+    pub fn expand(&self, plugin: &str, args: &[String]) -> PluginResult<Vec<String>> {
+        let mut expanded = Vec::with_capacity(args.len());
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if let Some(name) = flag_value(arg, PRESET_FLAG, &mut args)? {
+                let preset = self.get(plugin, &name).ok_or_else(|| {
+                    let names = self.names(plugin);
+                    let available = if names.is_empty() {
+                        format!("no presets are configured in [plugin.{}.presets.*]", plugin)
+                    } else {
+                        format!("available presets: {}", names.join(", "))
+                    };
+                    PluginError::invalid_argument(PRESET_FLAG, &format!("unknown preset '{}' for {}; {}", name, plugin, available))
+                })?;
+                expanded.extend_from_slice(preset);
+            } else if let Some(path) = flag_value(arg, ARGS_FROM_FLAG, &mut args)? {
+                // Files may apply presets but not other files
+                let from_file = read_args_file(Path::new(&path))?;
+                if from_file.iter().any(|arg| arg == ARGS_FROM_FLAG || arg.starts_with(&format!("{}=", ARGS_FROM_FLAG))) {
+                    return Err(PluginError::invalid_argument(ARGS_FROM_FLAG, &format!("'{}' cannot itself use {}", path, ARGS_FROM_FLAG)));
+                }
+                expanded.extend(self.expand(plugin, &from_file)?);
+            } else {
+                expanded.push(arg.clone());
+            }
+        }
+        Ok(expanded)
+    }
+}
+
+/// The value of `--flag VALUE` or `--flag=VALUE`, if `arg` is that flag
+fn flag_value<'a>(arg: &str, flag: &str, rest: &mut impl Iterator<Item = &'a String>) -> PluginResult<Option<String>> {
+    if arg == flag {
+        return rest.next()
+            .map(|value| Some(value.clone()))
+            .ok_or_else(|| PluginError::invalid_argument(flag, "a value is required"));
+    }
+    Ok(arg.strip_prefix(flag).and_then(|rest| rest.strip_prefix('=')).map(str::to_string))
+}
+
+/// Whether `arg` is the long flag `--flag` or `--flag=VALUE`
+fn sets_flag(arg: &str, flag: &str) -> bool {
+    arg.strip_prefix("--")
+        .and_then(|arg| arg.strip_prefix(flag))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+}
+
+/// Command line arguments for one configured flag value
+fn flag_args(flag: &str, value: &str) -> Vec<String> {
+    let flag = format!("--{}", flag.trim_start_matches('-'));
+    match value {
+        "true" => vec![flag],
+        "false" => Vec::new(),
+        _ => match parse_toml_value(value) {
+            Some(toml::Value::Array(items)) => items.iter()
+                .flat_map(|item| {
+                    let item = match item {
+                        toml::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    [flag.clone(), item]
+                })
+                .collect(),
+            _ => vec![flag, value.to_string()],
+        },
+    }
+}
+
+/// Parse a configuration value kept as text back into a TOML value, if it is not a plain string
+pub(crate) fn parse_toml_value(value: &str) -> Option<toml::Value> {
+    format!("value = {}", value).parse::<toml::Table>().ok()?.remove("value")
+}
+
+/// Read the arguments stored in a file
+fn read_args_file(path: &Path) -> PluginResult<Vec<String>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        PluginError::invalid_argument(ARGS_FROM_FLAG, &format!("cannot read '{}': {}", path.display(), e))
+    })?;
+    let mut args = Vec::new();
+    for line in content.lines() {
+        args.extend(split_line(line).map_err(|e| {
+            PluginError::invalid_argument(ARGS_FROM_FLAG, &format!("'{}': {}", path.display(), e))
+        })?);
+    }
+    Ok(args)
+}
+
+/// Split a line into arguments at whitespace, keeping quoted text together and dropping comments
+fn split_line(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_with(String::new).push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            None if c == '#' && current.is_none() => break,
+            None if c.is_whitespace() => args.extend(current.take()),
+            None => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("unterminated {} quote", q));
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn presets() -> ArgPresets {
+        let mut presets = ArgPresets::new();
+        presets.insert("metrics", "strict", [("top", "10"), ("complexity-weight", "0.6"), ("verbose", "true"), ("quiet", "false")]);
+        presets.insert("commits", "team", [("author", "[\"alice\", \"bob\"]")]);
+        presets
+    }
+
+    #[test]
+    fn test_preset_expansion() {
+        let presets = presets();
+        assert_eq!(presets.get("metrics", "strict").unwrap(), args(&["--complexity-weight", "0.6", "--top", "10", "--verbose"]));
+        assert_eq!(presets.get("commits", "team").unwrap(), args(&["--author", "alice", "--author", "bob"]));
+
+        // Presets expand in place, so later flags override them
+        assert_eq!(
+            presets.expand("metrics", &args(&["--preset", "strict", "--top", "5"])).unwrap(),
+            args(&["--complexity-weight", "0.6", "--top", "10", "--verbose", "--top", "5"])
+        );
+        assert_eq!(presets.expand("metrics", &args(&["--preset=strict"])).unwrap().len(), 5);
+
+        let error = presets.expand("metrics", &args(&["--preset", "loose"])).unwrap_err().to_string();
+        assert!(error.contains("available presets: strict"), "{}", error);
+        assert!(presets.expand("loc", &args(&["--preset", "strict"])).is_err());
+        assert!(presets.expand("metrics", &args(&["--preset"])).is_err());
+    }
+
+    #[test]
+    fn test_plugin_defaults() {
+        let mut presets = presets();
+        presets.insert_defaults("metrics", [("top", "20"), ("author", "[\"alice\", \"bob\"]"), ("verbose", "true")]);
+
+        assert_eq!(
+            presets.apply_defaults("metrics", &args(&["--complexity-weight", "0.6"])),
+            args(&["--author", "alice", "--author", "bob", "--top", "20", "--verbose", "--complexity-weight", "0.6"])
+        );
+
+        // Flags set by the arguments, directly or through a preset, replace their defaults
+        let expanded = presets.expand("metrics", &args(&["--preset", "strict", "--author=carol"])).unwrap();
+        assert_eq!(
+            presets.apply_defaults("metrics", &expanded),
+            args(&["--complexity-weight", "0.6", "--top", "10", "--verbose", "--author=carol"])
+        );
+        assert_eq!(presets.apply_defaults("commits", &args(&["--top", "1"])), args(&["--top", "1"]));
+    }
+
+    #[test]
+    fn test_args_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.args");
+        std::fs::write(&path, "# Hotspot weights\n--top 15\n--preset strict --label 'two words' # trailing\n").unwrap();
+
+        let expanded = presets().expand("metrics", &args(&["--args-from", path.to_str().unwrap(), "--top", "3"])).unwrap();
+        assert_eq!(expanded, args(&[
+            "--top", "15", "--complexity-weight", "0.6", "--top", "10", "--verbose", "--label", "two words", "--top", "3",
+        ]));
+
+        let nested = dir.path().join("nested.args");
+        std::fs::write(&nested, format!("--args-from {}", path.display())).unwrap();
+        assert!(presets().expand("metrics", &args(&["--args-from", nested.to_str().unwrap()])).is_err());
+        assert!(presets().expand("metrics", &args(&["--args-from=/nonexistent/args"])).is_err());
+        assert!(split_line("--label 'open").is_err());
+    }
+}
@@ -0,0 +1,831 @@
+//! Blobs Plugin
+//!
+//! Built-in plugin that follows the size of every file across history to find
+//! what makes a repository large: binary files, files that grew, and files
+//! whose many versions add up even after they were deleted from the tree.
+//! Each file change carries the size of the blob it committed, so the plugin
+//! tallies the versions of each path and ranks paths by the bytes their
+//! versions take together. Binary files at or above a threshold are flagged
+//! as candidates for Git LFS.
+//!
+//! Sizes are uncompressed blob sizes; git stores similar versions as deltas,
+//! so the pack on disk is usually smaller than the history size reported here.
+
+use crate::plugin::{
+    Plugin, PluginInfo, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, PluginDataRequirements, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
+};
+use crate::plugin::aggregation::{Merge, ShardedTally};
+use crate::plugin::memory::{format_bytes, no_heap, HeapSize};
+use crate::plugin::data_export::{
+    PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
+    DataPayload, Row, Value, ExportHints, ExportFormat
+};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader, ChangeType};
+use crate::notifications::AsyncNotificationManager;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use serde_json::json;
+
+/// Default number of files in the ranked table
+const DEFAULT_TOP: usize = 25;
+
+/// Default history size at which a binary file is an LFS candidate
+const DEFAULT_LFS_THRESHOLD: &str = "1MB";
+
+/// Size history of one path
+#[derive(Debug, Default, Clone, PartialEq)]
+struct BlobHistory {
+    /// Whether any version was binary
+    binary: bool,
+    /// Versions committed with a known size
+    versions: usize,
+    /// Bytes of all versions together
+    history_bytes: u64,
+    /// Size of the largest version
+    largest: u64,
+    /// Commit time and size of the earliest version
+    first: Option<(i64, u64)>,
+    /// Commit time and size after the latest change; no size once the path was deleted or renamed away
+    latest: Option<(i64, Option<u64>)>,
+    /// Size of the file in the scanned tree, when file information was scanned
+    current: Option<u64>,
+}
+
+no_heap!(BlobHistory);
+
+impl BlobHistory {
+    /// Record a version committed at `timestamp`
+    fn add_version(&mut self, timestamp: i64, size: u64, binary: bool) {
+        self.binary |= binary;
+        self.versions += 1;
+        self.history_bytes += size;
+        self.largest = self.largest.max(size);
+        self.first = Some(self.first.map_or((timestamp, size), |first| first.min((timestamp, size))));
+        self.set_latest(timestamp, Some(size));
+    }
+
+    /// Record that the path left the tree at `timestamp`
+    fn remove(&mut self, timestamp: i64) {
+        self.set_latest(timestamp, None);
+    }
+
+    /// Keep the latest change whatever order changes arrive in; a version
+    /// wins over a removal made at the same time
+    fn set_latest(&mut self, timestamp: i64, size: Option<u64>) {
+        self.latest = self.latest.max(Some((timestamp, size)));
+    }
+
+    /// Size in the current tree, or `None` once the file was deleted
+    fn current_size(&self) -> Option<u64> {
+        self.current.or_else(|| self.latest.and_then(|(_, size)| size))
+    }
+
+    /// Bytes the path takes in history, counting a scanned file without history as one version
+    fn total_bytes(&self) -> u64 {
+        if self.versions == 0 {
+            self.current.unwrap_or(0)
+        } else {
+            self.history_bytes
+        }
+    }
+
+    /// Largest version, including a scanned current file
+    fn largest_size(&self) -> u64 {
+        self.largest.max(self.current.unwrap_or(0))
+    }
+
+    /// Change in size from the first version to the current one
+    fn growth(&self) -> Option<i64> {
+        let (_, first) = self.first?;
+        Some(self.current_size()? as i64 - first as i64)
+    }
+
+    /// Whether the file should be moved to Git LFS
+    fn lfs_candidate(&self, threshold: u64) -> bool {
+        self.binary && self.total_bytes() >= threshold
+    }
+}
+
+impl Merge for BlobHistory {
+    fn merge(&mut self, other: Self) {
+        self.binary |= other.binary;
+        self.versions += other.versions;
+        self.history_bytes += other.history_bytes;
+        self.largest = self.largest.max(other.largest);
+        self.first = match (self.first, other.first) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.latest = self.latest.max(other.latest);
+        self.current = self.current.or(other.current);
+    }
+}
+
+/// Size histories per path
+#[derive(Debug, Default, Clone)]
+struct BlobTally {
+    files: BTreeMap<String, BlobHistory>,
+}
+
+impl HeapSize for BlobTally {
+    fn heap_size(&self) -> usize {
+        self.files.heap_size()
+    }
+}
+
+impl Merge for BlobTally {
+    fn merge(&mut self, other: Self) {
+        for (path, history) in other.files {
+            self.files.entry(path).or_default().merge(history);
+        }
+    }
+}
+
+impl BlobTally {
+    /// Paths ordered by history size, largest first
+    fn ranked(&self) -> Vec<(&str, &BlobHistory)> {
+        let mut ranked: Vec<_> = self.files.iter()
+            .map(|(path, history)| (path.as_str(), history))
+            .filter(|(_, history)| history.total_bytes() > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_bytes().cmp(&a.1.total_bytes()).then(a.0.cmp(b.0)));
+        ranked
+    }
+
+    /// Bytes of every version of every path
+    fn total_bytes(&self) -> u64 {
+        self.files.values().map(BlobHistory::total_bytes).sum()
+    }
+
+    fn binary_files(&self) -> usize {
+        self.files.values().filter(|history| history.binary).count()
+    }
+
+    fn lfs_candidates(&self, threshold: u64) -> usize {
+        self.files.values().filter(|history| history.lfs_candidate(threshold)).count()
+    }
+}
+
+/// Blob size plugin
+pub struct BlobsPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+
+    /// Number of files in the ranked table
+    top: usize,
+
+    /// History size at which a binary file is an LFS candidate
+    lfs_threshold: u64,
+
+    /// Size histories for the scan in progress, sharded by path; reset when a scan starts
+    tally: Arc<ShardedTally<BlobTally>>,
+
+    consuming: Arc<RwLock<bool>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
+
+    /// Notification publishing - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl BlobsPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "blobs".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Ranks binary and large files by the space their history takes".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Processing,
+        )
+        .with_capability(
+            "blob_sizes".to_string(),
+            "Tracks the size of each file's versions, their growth and the largest version".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "lfs_candidates".to_string(),
+            "Flags binary files large enough to move to Git LFS".to_string(),
+            "1.0.0".to_string(),
+        );
+
+        Self {
+            command_name: "blobs".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            top: DEFAULT_TOP,
+            lfs_threshold: crate::cli::memory_parser::parse_memory_size(DEFAULT_LFS_THRESHOLD).unwrap_or(1024 * 1024) as u64,
+            tally: Arc::new(ShardedTally::new()),
+            consuming: Arc::new(RwLock::new(false)),
+            consumer: Arc::new(RwLock::new(None)),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new blobs plugin with all required dependencies (REQUIRED)
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Process a file change or file information message and record the file's size
+    async fn process_file(&self, message: &ScanMessage) -> PluginResult<()> {
+        match &message.data {
+            MessageData::FileChange { path, change_type, old_path, is_binary, binary_size, commit_timestamp, .. } => {
+                if *change_type == ChangeType::Deleted {
+                    self.tally.update(path, |tally| tally.files.entry(path.clone()).or_default().remove(*commit_timestamp));
+                    return Ok(());
+                }
+                if let (ChangeType::Renamed, Some(old_path)) = (change_type, old_path) {
+                    self.tally.update(old_path, |tally| tally.files.entry(old_path.clone()).or_default().remove(*commit_timestamp));
+                }
+                if let Some(size) = binary_size {
+                    self.tally.update(path, |tally| {
+                        tally.files.entry(path.clone()).or_default().add_version(*commit_timestamp, *size, *is_binary)
+                    });
+                }
+            }
+            MessageData::FileInfo { path, size, .. } => {
+                self.tally.update(path, |tally| tally.files.entry(path.clone()).or_default().current = Some(*size));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Generate repository size summary statistics
+    async fn generate_summary(&self) -> PluginResult<ScanMessage> {
+        let tally = self.tally.snapshot();
+
+        let data = MessageData::MetricInfo {
+            file_count: tally.files.len() as u32,
+            line_count: tally.total_bytes(),
+            complexity: tally.lfs_candidates(self.lfs_threshold) as f64,
+        };
+
+        let header = MessageHeader::new(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            "plugin-generated".to_string(),
+        );
+
+        Ok(ScanMessage::new(header, data))
+    }
+
+    /// Create the ranked table of the files taking the most space in history
+    fn create_size_export(tally: &BlobTally, scan_id: &str, top: usize, lfs_threshold: u64) -> Option<PluginDataExport> {
+        let ranked = tally.ranked();
+        if ranked.is_empty() {
+            return None;
+        }
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("File", ColumnType::String),
+                ColumnDef::new("Binary", ColumnType::Boolean),
+                ColumnDef::new("Versions", ColumnType::Integer)
+                    .with_description("Versions of the file committed in the scanned history"),
+                ColumnDef::new("Current Size", ColumnType::Integer)
+                    .with_description("Size in bytes in the current tree; empty once the file was deleted")
+                    .with_format_hint("bytes"),
+                ColumnDef::new("Largest", ColumnType::Integer)
+                    .with_description("Size in bytes of the largest version")
+                    .with_format_hint("bytes"),
+                ColumnDef::new("Growth", ColumnType::Integer)
+                    .with_description("Change in bytes from the first version to the current one"),
+                ColumnDef::new("History Size", ColumnType::Integer)
+                    .with_description("Bytes of all versions together, before compression")
+                    .with_format_hint("bytes"),
+                ColumnDef::new("LFS Candidate", ColumnType::Boolean)
+                    .with_description(format!("Binary file whose versions take at least {}", format_bytes(lfs_threshold as usize))),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("lfs_threshold".to_string(), lfs_threshold.to_string());
+                meta.insert("history_bytes".to_string(), tally.total_bytes().to_string());
+                meta.insert("binary_files".to_string(), tally.binary_files().to_string());
+                meta.insert("lfs_candidates".to_string(), tally.lfs_candidates(lfs_threshold).to_string());
+                meta
+            },
+        };
+
+        let rows: Vec<Row> = ranked.iter()
+            .take(top)
+            .map(|(path, history)| Row::new(vec![
+                Value::String(path.to_string()),
+                Value::Boolean(history.binary),
+                Value::Integer(history.versions as i64),
+                history.current_size().map_or(Value::Null, |size| Value::Integer(size as i64)),
+                Value::Integer(history.largest_size() as i64),
+                history.growth().map_or(Value::Null, Value::Integer),
+                Value::Integer(history.total_bytes() as i64),
+                Value::Boolean(history.lfs_candidate(lfs_threshold)),
+            ]))
+            .collect();
+
+        Some(PluginDataExport {
+            plugin_id: "blobs".to_string(),
+            title: "Repository Size".to_string(),
+            description: Some(format!(
+                "{} files take {} across history, {} binary and {} LFS candidates; top {} of {} in scan {}",
+                tally.files.len(), format_bytes(tally.total_bytes() as usize), tally.binary_files(),
+                tally.lfs_candidates(lfs_threshold), rows.len(), ranked.len(), scan_id
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Html,
+                    ExportFormat::Markdown,
+                ],
+                sort_by: None,
+                sort_ascending: false,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Publish the ranked size table if any file sizes were recorded
+    async fn publish_export(&self, scan_id: &str) {
+        let tally = self.tally.snapshot();
+        let Some(export_data) = Self::create_size_export(&tally, scan_id, self.top, self.lfs_threshold) else {
+            return;
+        };
+
+        let event = PluginEvent::DataReady {
+            plugin_id: "blobs".to_string(),
+            scan_id: scan_id.to_string(),
+            export: Arc::new(export_data),
+        };
+
+        if let Err(e) = self.publish(event).await {
+            log::warn!("Failed to publish Repository Size DataReady event: {}", e);
+        } else {
+            log::debug!("Published Repository Size DataReady event for blobs plugin");
+        }
+    }
+
+    /// Execute the repository size function
+    async fn execute_blobs_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let tally = self.tally.snapshot();
+        let threshold = self.lfs_threshold;
+
+        let data = json!({
+            "total_files": tally.files.len(),
+            "history_bytes": tally.total_bytes(),
+            "binary_files": tally.binary_files(),
+            "lfs_threshold": threshold,
+            "lfs_candidates": tally.lfs_candidates(threshold),
+            "files": tally.ranked().iter().take(self.top).map(|(path, history)| {
+                json!({
+                    "path": path,
+                    "binary": history.binary,
+                    "versions": history.versions,
+                    "current_size": history.current_size(),
+                    "largest": history.largest_size(),
+                    "growth": history.growth(),
+                    "history_size": history.total_bytes(),
+                    "lfs_candidate": history.lfs_candidate(threshold),
+                })
+            }).collect::<Vec<_>>(),
+            "function": "blobs"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "blobs_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: tally.files.values().map(|history| history.versions).sum::<usize>() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+}
+
+impl Default for BlobsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for BlobsPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        self.tally.reset();
+        self.initialized = true;
+
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        if !self.initialized {
+            return Err(PluginError::invalid_state("Plugin not initialized"));
+        }
+
+        match request {
+            PluginRequest::Execute { invocation_type, .. } => {
+                let function_name = match invocation_type {
+                    crate::plugin::InvocationType::Function(ref func) => func.as_str(),
+                    crate::plugin::InvocationType::Direct => self.default_function().unwrap_or("blobs"),
+                    crate::plugin::InvocationType::Default => "blobs",
+                };
+
+                match function_name {
+                    "blobs" | "large-files" | "binaries" => self.execute_blobs_analysis().await,
+                    _ => Err(PluginError::execution_failed(
+                        format!("Unknown function: {}", function_name)
+                    )),
+                }
+            }
+            PluginRequest::GetStatistics => {
+                let summary = self.generate_summary().await?;
+                Ok(PluginResponse::Statistics(summary))
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        if *self.consuming.read().await {
+            self.stop_consuming().await?;
+        }
+
+        self.initialized = false;
+        self.tally.reset();
+        Ok(())
+    }
+
+    /// Get all functions this plugin can handle
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "blobs".to_string(),
+                aliases: vec!["large-files".to_string(), "binaries".to_string()],
+                description: "Binary and large files ranked by history size, with LFS candidates".to_string(),
+                is_default: true,
+            },
+        ]
+    }
+
+    /// Get the default function name
+    fn default_function(&self) -> Option<&str> {
+        Some("blobs")
+    }
+
+    /// Cast to ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Cast to mutable ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Expose data requirements so the scanner reports binary file changes
+    fn as_data_requirements(&self) -> Option<&dyn PluginDataRequirements> {
+        Some(self)
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for BlobsPlugin {
+    async fn publish(&self, event: PluginEvent) -> crate::notifications::NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+/// Data requirements implementation for BlobsPlugin
+/// Sizes come with the file changes, so no content is read, but binary files must not be filtered out
+impl PluginDataRequirements for BlobsPlugin {
+    fn requires_current_file_content(&self) -> bool {
+        false
+    }
+
+    fn requires_historical_file_content(&self) -> bool {
+        false
+    }
+
+    fn handles_binary_files(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for BlobsPlugin {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if *consuming {
+            return Err(PluginError::invalid_state("Already consuming"));
+        }
+
+        *consuming = true;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = Some(consumer);
+        }
+
+        log::info!("Blobs plugin started consuming messages");
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        self.process_file(&message).await?;
+
+        consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge message: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        log::debug!("Blobs plugin received queue event: {:?}", event);
+
+        match event {
+            QueueEvent::ScanStarted { scan_id, .. } => {
+                log::info!("Blobs plugin: scan started for {}", scan_id);
+                self.tally.reset();
+            }
+            QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
+                let (file_count, history_bytes) = {
+                    let tally = self.tally.snapshot();
+                    (tally.files.len(), tally.total_bytes())
+                };
+                log::info!(
+                    "Blobs plugin: scan {} complete - {} files taking {} bytes in history (total {} messages)",
+                    scan_id, file_count, history_bytes, total_messages
+                );
+
+                self.publish_export(scan_id).await;
+            }
+            _ => {
+                // Other events are just logged
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if !*consuming {
+            return Ok(()); // Already stopped
+        }
+
+        *consuming = false;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = None;
+        }
+
+        log::info!("Blobs plugin stopped consuming messages");
+        Ok(())
+    }
+
+    async fn state_memory(&self) -> usize {
+        self.tally.heap_size()
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages: false, // Only interested in file sizes
+            interested_message_types: vec!["FileChange".to_string(), "FileInfo".to_string()],
+            high_frequency_capable: true,
+            preferred_batch_size: 100,
+            requires_ordered_delivery: false, // First and latest versions are picked by commit time
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_millis(10)),
+        }
+    }
+}
+
+/// Modern clap-based argument parsing implementation for blobs plugin
+#[async_trait]
+impl PluginClapParser for BlobsPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Ranks binary and large files by the space their history takes"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        use clap::Arg;
+
+        command
+            .override_usage("blobs [OPTIONS]")
+            .help_template("Usage: {usage}\n\nRanks binary and large files by the space their history takes\n\nOptions:\n{options}\n{after-help}")
+            .after_help("The history size of a file is the size of every version committed in the scanned \
+                history, before git's compression; files deleted from the tree are still counted. Binary files \
+                whose history size reaches the LFS threshold are flagged as Git LFS candidates.")
+            .arg(Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .help("Number of files to report")
+                .value_parser(clap::value_parser!(usize))
+                .default_value(DEFAULT_TOP.to_string()))
+            .arg(Arg::new("lfs-threshold")
+                .long("lfs-threshold")
+                .value_name("SIZE")
+                .help("History size at which a binary file is an LFS candidate (e.g. 512K, 10MB)")
+                .default_value(DEFAULT_LFS_THRESHOLD))
+    }
+
+    async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
+        if let Some(top) = matches.get_one::<usize>("top") {
+            self.top = *top;
+        }
+        if let Some(threshold) = matches.get_one::<String>("lfs-threshold") {
+            self.lfs_threshold = crate::cli::memory_parser::parse_memory_size(threshold)
+                .map_err(|e| PluginError::configuration_error(format!("Invalid --lfs-threshold: {}", e)))? as u64;
+        }
+
+        log::debug!("Blobs plugin configured with top {} and LFS threshold {} bytes", self.top, self.lfs_threshold);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginContext;
+
+    fn create_test_change(path: &str, change_type: ChangeType, size: Option<u64>, is_binary: bool, timestamp: i64) -> ScanMessage {
+        let data = MessageData::FileChange {
+            path: path.to_string(),
+            change_type,
+            old_path: None,
+            insertions: 0,
+            deletions: 0,
+            is_binary,
+            binary_size: size,
+            line_count: None,
+            commit_hash: format!("commit-{timestamp}"),
+            commit_timestamp: timestamp,
+            checkout_path: None,
+            old_mode: None,
+            new_mode: None,
+            replaced_lines: vec![],
+        };
+
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    fn create_test_context() -> PluginContext {
+        PluginContext::new(
+            Arc::new(crate::scanner::ScannerConfig::default()),
+            Arc::new(crate::scanner::QueryParams::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_blobs_plugin_creation() {
+        let plugin = BlobsPlugin::new();
+        assert_eq!(plugin.plugin_info().name, "blobs");
+        assert_eq!(plugin.default_function(), Some("blobs"));
+        assert_eq!(plugin.lfs_threshold, 1024 * 1024);
+        assert!(plugin.as_data_requirements().is_some_and(|r| r.handles_binary_files() && !r.requires_current_file_content()));
+    }
+
+    #[tokio::test]
+    async fn test_blob_histories_ignore_message_order() {
+        let mut plugin = BlobsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        // Delivered newest first: the logo grew twice, the dump was added then deleted
+        let messages = [
+            create_test_change("logo.png", ChangeType::Modified, Some(3_000_000), true, 300),
+            create_test_change("dump.sql", ChangeType::Deleted, None, false, 250),
+            create_test_change("logo.png", ChangeType::Modified, Some(2_000_000), true, 200),
+            create_test_change("dump.sql", ChangeType::Added, Some(4_000_000), false, 150),
+            create_test_change("logo.png", ChangeType::Added, Some(1_000_000), true, 100),
+            create_test_change("README.md", ChangeType::Added, Some(100), false, 100),
+        ];
+        for message in &messages {
+            plugin.process_file(message).await.unwrap();
+        }
+
+        let tally = plugin.tally.snapshot();
+        let logo = &tally.files["logo.png"];
+        assert_eq!((logo.versions, logo.total_bytes(), logo.largest_size()), (3, 6_000_000, 3_000_000));
+        assert_eq!((logo.current_size(), logo.growth()), (Some(3_000_000), Some(2_000_000)));
+        let dump = &tally.files["dump.sql"];
+        assert_eq!((dump.current_size(), dump.growth()), (None, None));
+
+        let export = BlobsPlugin::create_size_export(&tally, "scan", 2, plugin.lfs_threshold).unwrap();
+        assert_eq!(export.schema.metadata.get("lfs_candidates"), Some(&"1".to_string()));
+        if let DataPayload::Rows(rows) = &export.data {
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].values, vec![
+                Value::String("logo.png".to_string()), Value::Boolean(true), Value::Integer(3),
+                Value::Integer(3_000_000), Value::Integer(3_000_000), Value::Integer(2_000_000),
+                Value::Integer(6_000_000), Value::Boolean(true),
+            ]);
+            assert_eq!(rows[1].values[0], Value::String("dump.sql".to_string()));
+            assert_eq!(rows[1].values[3], Value::Null);
+            assert_eq!(rows[1].values[7], Value::Boolean(false));
+        } else {
+            panic!("expected row payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_ends_the_old_path() {
+        let mut plugin = BlobsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        plugin.process_file(&create_test_change("a.bin", ChangeType::Added, Some(10), true, 100)).await.unwrap();
+        let mut rename = create_test_change("b.bin", ChangeType::Renamed, Some(10), true, 200);
+        if let MessageData::FileChange { old_path, .. } = &mut rename.data {
+            *old_path = Some("a.bin".to_string());
+        }
+        plugin.process_file(&rename).await.unwrap();
+
+        let tally = plugin.tally.snapshot();
+        assert_eq!(tally.files["a.bin"].current_size(), None);
+        assert_eq!(tally.files["b.bin"].current_size(), Some(10));
+
+        plugin.handle_queue_event(&QueueEvent::ScanStarted { scan_id: "next".to_string(), timestamp: 0 }).await.unwrap();
+        assert!(BlobsPlugin::create_size_export(&plugin.tally.snapshot(), "next", 10, 0).is_none());
+    }
+}
@@ -0,0 +1,346 @@
+//! Repository Activity Anomaly Detection
+//!
+//! Flags unusual periods in commit activity by comparing each week against a
+//! rolling baseline of the preceding weeks. Detection uses simple statistical
+//! thresholds (mean + N standard deviations) so results are easy to explain.
+
+use crate::plugin::data_export::{Finding, FindingSeverity};
+use crate::plugin::memory::HeapSize;
+use chrono::{TimeZone, Utc};
+use std::collections::{BTreeMap, HashSet};
+
+/// Seconds in one week, used for bucketing commit timestamps
+const WEEK_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Minimal per-commit record needed for anomaly detection
+#[derive(Debug, Clone)]
+pub struct CommitActivity {
+    pub hash: String,
+    pub author: String,
+    pub timestamp: i64,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+impl HeapSize for CommitActivity {
+    fn heap_size(&self) -> usize {
+        self.hash.heap_size() + self.author.heap_size()
+    }
+}
+
+/// Kind of anomaly detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// Commit volume well above the rolling baseline
+    VolumeSpike,
+    /// No commits in a period where activity was normally expected
+    Drought,
+    /// Sudden influx of first-time contributors
+    ContributorSurge,
+    /// A single commit removing a large number of lines
+    MassDeletion,
+}
+
+impl AnomalyKind {
+    /// Display name used in exported findings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnomalyKind::VolumeSpike => "volume-spike",
+            AnomalyKind::Drought => "drought",
+            AnomalyKind::ContributorSurge => "contributor-surge",
+            AnomalyKind::MassDeletion => "mass-deletion",
+        }
+    }
+
+    /// Severity of the finding raised for the anomaly
+    pub fn severity(&self) -> FindingSeverity {
+        match self {
+            AnomalyKind::MassDeletion => FindingSeverity::High,
+            AnomalyKind::VolumeSpike => FindingSeverity::Medium,
+            AnomalyKind::Drought | AnomalyKind::ContributorSurge => FindingSeverity::Low,
+        }
+    }
+}
+
+/// A single dated anomaly finding
+#[derive(Debug, Clone)]
+pub struct Anomaly {
+    /// Start of the week (unix seconds) in which the anomaly occurred
+    pub period_start: i64,
+    pub kind: AnomalyKind,
+    /// Observed value for the period (commits, new authors or lines removed)
+    pub observed: f64,
+    /// Baseline value the observation was compared against
+    pub baseline: f64,
+    pub detail: String,
+}
+
+impl Anomaly {
+    /// Format the period start as an ISO date (YYYY-MM-DD)
+    pub fn period_label(&self) -> String {
+        match Utc.timestamp_opt(self.period_start, 0) {
+            chrono::LocalResult::Single(dt) => dt.format("%Y-%m-%d").to_string(),
+            _ => self.period_start.to_string(),
+        }
+    }
+
+    /// The anomaly as a finding, located at the week it occurred in
+    pub fn to_finding(&self) -> Finding {
+        Finding::new(self.kind.as_str(), self.kind.severity(), "activity", self.detail.clone())
+            .with_location(self.period_label())
+            .with_metric("observed", self.observed)
+            .with_metric("baseline", self.baseline)
+    }
+}
+
+/// Thresholds controlling anomaly detection
+#[derive(Debug, Clone)]
+pub struct AnomalyConfig {
+    /// Number of preceding weeks forming the rolling baseline
+    pub window_weeks: usize,
+    /// Standard deviations above the baseline mean to flag a spike or surge
+    pub sigma: f64,
+    /// Minimum baseline mean (commits/week) before a silent week counts as a drought
+    pub min_drought_baseline: f64,
+    /// Minimum number of new contributors in a week to count as a surge
+    pub min_surge_authors: usize,
+    /// Minimum lines removed by one commit to count as a mass deletion
+    pub mass_deletion_lines: usize,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            window_weeks: 8,
+            sigma: 2.0,
+            min_drought_baseline: 3.0,
+            min_surge_authors: 3,
+            mass_deletion_lines: 1000,
+        }
+    }
+}
+
+/// Per-week aggregated activity
+#[derive(Debug, Default, Clone)]
+struct WeekBucket {
+    commits: usize,
+    new_authors: usize,
+}
+
+/// Detect anomalies in the given commit activity, ordered by period
+pub fn detect_anomalies(activity: &[CommitActivity], config: &AnomalyConfig) -> Vec<Anomaly> {
+    if activity.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sorted: Vec<&CommitActivity> = activity.iter().collect();
+    sorted.sort_by_key(|a| a.timestamp);
+
+    let mut anomalies = detect_mass_deletions(&sorted, config);
+    let weeks = bucket_by_week(&sorted);
+    anomalies.extend(detect_weekly_anomalies(&weeks, config));
+
+    anomalies.sort_by_key(|a| a.period_start);
+    anomalies
+}
+
+/// Aggregate commits into contiguous weekly buckets (including empty weeks)
+fn bucket_by_week(sorted: &[&CommitActivity]) -> Vec<(i64, WeekBucket)> {
+    let mut buckets: BTreeMap<i64, WeekBucket> = BTreeMap::new();
+    let mut seen_authors: HashSet<&str> = HashSet::new();
+
+    for commit in sorted {
+        let bucket = buckets.entry(week_start(commit.timestamp)).or_default();
+        bucket.commits += 1;
+        if seen_authors.insert(commit.author.as_str()) {
+            bucket.new_authors += 1;
+        }
+    }
+
+    let (first, last) = match (buckets.keys().next(), buckets.keys().next_back()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Vec::new(),
+    };
+
+    (0..=((last - first) / WEEK_SECONDS))
+        .map(|i| {
+            let start = first + i * WEEK_SECONDS;
+            (start, buckets.remove(&start).unwrap_or_default())
+        })
+        .collect()
+}
+
+/// Flag volume spikes, droughts and contributor surges against a rolling baseline
+fn detect_weekly_anomalies(weeks: &[(i64, WeekBucket)], config: &AnomalyConfig) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+    let window = config.window_weeks.max(1);
+
+    for i in window..weeks.len() {
+        let (start, bucket) = &weeks[i];
+        let history = &weeks[i - window..i];
+
+        let (commit_mean, commit_std) = mean_and_std(history.iter().map(|(_, b)| b.commits as f64));
+        let commits = bucket.commits as f64;
+
+        if commits > commit_mean + config.sigma * commit_std.max(1.0) {
+            anomalies.push(Anomaly {
+                period_start: *start,
+                kind: AnomalyKind::VolumeSpike,
+                observed: commits,
+                baseline: commit_mean,
+                detail: format!("{} commits vs {:.1}/week baseline", bucket.commits, commit_mean),
+            });
+        } else if bucket.commits == 0 && commit_mean >= config.min_drought_baseline {
+            anomalies.push(Anomaly {
+                period_start: *start,
+                kind: AnomalyKind::Drought,
+                observed: 0.0,
+                baseline: commit_mean,
+                detail: format!("no commits vs {:.1}/week baseline", commit_mean),
+            });
+        }
+
+        let (author_mean, author_std) = mean_and_std(history.iter().map(|(_, b)| b.new_authors as f64));
+        let new_authors = bucket.new_authors as f64;
+
+        if bucket.new_authors >= config.min_surge_authors
+            && new_authors > author_mean + config.sigma * author_std.max(1.0)
+        {
+            anomalies.push(Anomaly {
+                period_start: *start,
+                kind: AnomalyKind::ContributorSurge,
+                observed: new_authors,
+                baseline: author_mean,
+                detail: format!("{} new contributors vs {:.1}/week baseline", bucket.new_authors, author_mean),
+            });
+        }
+    }
+
+    anomalies
+}
+
+/// Flag individual commits that remove far more lines than they add
+fn detect_mass_deletions(sorted: &[&CommitActivity], config: &AnomalyConfig) -> Vec<Anomaly> {
+    sorted
+        .iter()
+        .filter(|c| c.lines_removed >= config.mass_deletion_lines && c.lines_removed > c.lines_added * 2)
+        .map(|c| Anomaly {
+            period_start: week_start(c.timestamp),
+            kind: AnomalyKind::MassDeletion,
+            observed: c.lines_removed as f64,
+            baseline: c.lines_added as f64,
+            detail: format!(
+                "commit {} by {} removed {} lines (added {})",
+                c.hash.chars().take(8).collect::<String>(), c.author, c.lines_removed, c.lines_added
+            ),
+        })
+        .collect()
+}
+
+/// Start of the week containing the given timestamp
+fn week_start(timestamp: i64) -> i64 {
+    timestamp.div_euclid(WEEK_SECONDS) * WEEK_SECONDS
+}
+
+/// Population mean and standard deviation of the given values
+fn mean_and_std(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, week: i64, added: usize, removed: usize) -> CommitActivity {
+        CommitActivity {
+            hash: format!("{:040}", week),
+            author: author.to_string(),
+            timestamp: week * WEEK_SECONDS + 3600,
+            lines_added: added,
+            lines_removed: removed,
+        }
+    }
+
+    /// Steady activity: three commits a week from the same two authors
+    fn steady_history(weeks: i64) -> Vec<CommitActivity> {
+        (0..weeks)
+            .flat_map(|w| vec![commit("alice", w, 10, 2), commit("bob", w, 5, 1), commit("alice", w, 3, 3)])
+            .collect()
+    }
+
+    #[test]
+    fn test_no_anomalies_for_steady_activity() {
+        let anomalies = detect_anomalies(&steady_history(20), &AnomalyConfig::default());
+        assert!(anomalies.is_empty(), "unexpected anomalies: {:?}", anomalies);
+    }
+
+    #[test]
+    fn test_volume_spike_detected() {
+        let mut activity = steady_history(10);
+        for _ in 0..20 {
+            activity.push(commit("alice", 10, 1, 0));
+        }
+
+        let anomalies = detect_anomalies(&activity, &AnomalyConfig::default());
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::VolumeSpike);
+        assert_eq!(anomalies[0].period_start, 10 * WEEK_SECONDS);
+        assert_eq!(anomalies[0].observed, 20.0);
+    }
+
+    #[test]
+    fn test_drought_detected() {
+        let mut activity = steady_history(10);
+        activity.extend(vec![commit("alice", 11, 1, 0), commit("bob", 11, 1, 0), commit("alice", 11, 1, 0)]);
+
+        let anomalies = detect_anomalies(&activity, &AnomalyConfig::default());
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::Drought);
+        assert_eq!(anomalies[0].period_start, 10 * WEEK_SECONDS);
+    }
+
+    #[test]
+    fn test_contributor_surge_detected() {
+        let mut activity = steady_history(10);
+        for name in ["carol", "dave", "erin", "frank"] {
+            activity.push(commit(name, 10, 1, 0));
+        }
+
+        let anomalies = detect_anomalies(&activity, &AnomalyConfig::default());
+        assert!(anomalies.iter().any(|a| a.kind == AnomalyKind::ContributorSurge && a.observed == 4.0));
+    }
+
+    #[test]
+    fn test_mass_deletion_detected() {
+        let mut activity = steady_history(3);
+        activity.push(commit("bob", 1, 10, 5000));
+
+        let anomalies = detect_anomalies(&activity, &AnomalyConfig::default());
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::MassDeletion);
+        assert!(anomalies[0].detail.contains("removed 5000 lines"));
+
+        let finding = anomalies[0].to_finding();
+        assert_eq!((finding.id.as_str(), finding.severity), ("mass-deletion", FindingSeverity::High));
+        assert_eq!(finding.location, Some(anomalies[0].period_label()));
+    }
+
+    #[test]
+    fn test_period_label_is_iso_date() {
+        let anomaly = Anomaly {
+            period_start: 1_704_067_200, // 2024-01-01
+            kind: AnomalyKind::Drought,
+            observed: 0.0,
+            baseline: 4.0,
+            detail: String::new(),
+        };
+        assert_eq!(anomaly.period_label(), "2024-01-01");
+    }
+}
@@ -2,16 +2,25 @@
 //! 
 //! Built-in plugin for analyzing git commit history and statistics.
 
+pub mod anomalies;
+pub mod pairing;
+pub mod permissions;
+pub mod raw;
+pub mod timeline;
+pub mod working_set;
+
 use crate::plugin::{
-    Plugin, PluginInfo, PluginContext, PluginRequest, PluginResponse,
-    PluginResult, PluginError, traits::{PluginType, PluginFunction, PluginDataRequirements, ConsumerPlugin, ConsumerPreferences, PluginClapParser}
+    Plugin, PluginInfo, PluginContext, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, PluginDataRequirements, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
 };
 use crate::plugin::data_export::{
     PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
-    DataPayload, Row, Value, ExportHints, ExportFormat
+    DataPayload, Row, Value, ExportHints, ExportFormat, Finding, findings_export
 };
-use crate::queue::{QueueConsumer, QueueEvent};
+use crate::queue::QueueEvent;
 use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
+use crate::scanner::query::DateRange;
+use crate::cli::date_parser::parse_duration_or;
 use crate::notifications::AsyncNotificationManager;
 use crate::notifications::events::PluginEvent;
 use crate::notifications::traits::{NotificationManager, Publisher};
@@ -21,6 +30,15 @@ use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::RwLock;
 use serde_json::json;
+use self::anomalies::{AnomalyConfig, CommitActivity, detect_anomalies};
+use self::pairing::{PairingMatrix, MAX_MATRIX_AUTHORS};
+use self::permissions::{PermissionChangeKind, PermissionTracker, format_mode};
+use self::raw::RawCommit;
+use self::timeline::{Grouping, GROUPINGS};
+use self::working_set::{WorkingSetConfig, WorkingSetTracker, SAMPLE_PERIODS};
+use crate::plugin::aggregation::{Merge, ShardedTally};
+use crate::plugin::memory::HeapSize;
+use crate::plugin::builtin::utils::time_buckets::{BucketPeriod, SeriesMode};
 
 /// Statistics for commits plugin operation
 #[derive(Debug, Default, Clone)]
@@ -29,21 +47,57 @@ struct CommitsStats {
     commit_count: usize,
     /// Commits by author for contributor analysis
     author_stats: HashMap<String, usize>,
+    /// Per-commit activity records for anomaly detection
+    activity: Vec<CommitActivity>,
+    /// File mode changes for permission reporting
+    permissions: PermissionTracker,
+    /// Who modified whose lines, from scanner line attribution
+    pairing: PairingMatrix,
+    /// Files changed by each commit for working set sampling
+    working_set: WorkingSetTracker,
+    /// Commits as delivered, kept only for `--raw-export`
+    raw: Vec<RawCommit>,
+}
+
+impl Merge for CommitsStats {
+    fn merge(&mut self, other: Self) {
+        self.commit_count += other.commit_count;
+        for (author, count) in other.author_stats {
+            *self.author_stats.entry(author).or_insert(0) += count;
+        }
+        self.activity.extend(other.activity);
+        self.permissions.merge(other.permissions);
+        self.pairing.merge(other.pairing);
+        self.working_set.merge(other.working_set);
+        self.raw.extend(other.raw);
+    }
+}
+
+impl HeapSize for CommitsStats {
+    fn heap_size(&self) -> usize {
+        self.author_stats.heap_size() + self.activity.heap_size() + self.permissions.heap_size()
+            + self.pairing.heap_size() + self.working_set.heap_size() + self.raw.heap_size()
+    }
 }
 
 /// Per-scan data for commits plugin
 #[derive(Debug)]
 struct CommitsScanData {
-    /// Statistics for this scan
-    stats: CommitsStats,
+    /// Statistics for this scan, sharded by commit hash so concurrent updates rarely contend
+    tally: ShardedTally<CommitsStats>,
 }
 
 impl CommitsScanData {
     fn new() -> Self {
         Self {
-            stats: CommitsStats::default(),
+            tally: ShardedTally::new(),
         }
     }
+    
+    /// Statistics for this scan, merged from all shards
+    fn stats(&self) -> CommitsStats {
+        self.tally.snapshot()
+    }
 }
 
 /// Commits analysis plugin
@@ -63,8 +117,26 @@ pub struct CommitsPlugin {
     /// Per-scan commit data and statistics
     scan_data: Arc<RwLock<HashMap<String, CommitsScanData>>>,
     
+    /// Thresholds for activity anomaly detection
+    anomaly_config: AnomalyConfig,
+    
+    /// Trailing window and sampling period for the working set series
+    working_set_config: WorkingSetConfig,
+    
+    /// Grouping of the commit activity table, exported only when set
+    group_by: Option<Grouping>,
+    
+    /// Layout of per-period activity: every period, or empty periods collapsed into gaps
+    series_mode: SeriesMode,
+    
+    /// Export the commit dataset itself instead of summaries
+    raw_export: bool,
+    
+    /// Effective commit date range of the scan, reported in exports
+    date_range: Option<DateRange>,
+    
     consuming: Arc<RwLock<bool>>,
-    consumer: Arc<RwLock<Option<QueueConsumer>>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
     
     /// Notification publishing - REQUIRED for all plugins
     notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
@@ -101,6 +173,12 @@ impl CommitsPlugin {
             initialized: false,
             started_at: std::time::Instant::now(),
             scan_data: Arc::new(RwLock::new(HashMap::new())),
+            anomaly_config: AnomalyConfig::default(),
+            working_set_config: WorkingSetConfig::default(),
+            group_by: None,
+            series_mode: SeriesMode::Dense,
+            raw_export: false,
+            date_range: None,
             consuming: Arc::new(RwLock::new(false)),
             consumer: Arc::new(RwLock::new(None)),
             notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
@@ -141,6 +219,12 @@ impl CommitsPlugin {
             initialized: false,
             started_at: std::time::Instant::now(),
             scan_data: Arc::new(RwLock::new(HashMap::new())),
+            anomaly_config: AnomalyConfig::default(),
+            working_set_config: WorkingSetConfig::default(),
+            group_by: None,
+            series_mode: SeriesMode::Dense,
+            raw_export: false,
+            date_range: None,
             consuming: Arc::new(RwLock::new(false)),
             consumer: Arc::new(RwLock::new(None)),
             notification_manager,
@@ -199,7 +283,8 @@ impl CommitsPlugin {
                 let (count, author_count) = {
                     let scan_data = self.scan_data.read().await;
                     if let Some(data) = scan_data.get(&scan_id) {
-                        (data.stats.commit_count, data.stats.author_stats.len())
+                        let stats = data.stats();
+                        (stats.commit_count, stats.author_stats.len())
                     } else {
                         (0, 0)
                     }
@@ -207,22 +292,8 @@ impl CommitsPlugin {
                 log::info!("CommitsPlugin processed {} commits from {} authors for scan {}", 
                           count, author_count, scan_id);
                 
-                // Create and publish data export before cleanup
-                {
-                    if let Ok(export_data) = self.create_data_export(&scan_id).await {
-                        let event = PluginEvent::DataReady {
-                            plugin_id: "commits".to_string(),
-                            scan_id: scan_id.clone(),
-                            export: Arc::new(export_data),
-                        };
-                        
-                        if let Err(e) = self.publish(event).await {
-                            log::warn!("Failed to publish DataReady event: {}", e);
-                        } else {
-                            log::debug!("Published DataReady event for commits plugin");
-                        }
-                    }
-                }
+                // Create and publish data exports before cleanup
+                self.publish_exports(&scan_id).await;
                 
                 // Clean up scan data for completed scan
                 let remaining_scans = {
@@ -253,17 +324,72 @@ impl CommitsPlugin {
     /// Process a commit message and extract statistics  
     async fn process_commit(&self, scan_id: &str, message: &ScanMessage) -> PluginResult<()> {
         // Extract commit information from scan message
-        if let MessageData::CommitInfo { author, .. } = &message.data {
-            let mut scan_data = self.scan_data.write().await;
-            let data = scan_data.entry(scan_id.to_string())
-                .or_insert_with(CommitsScanData::new);
-            
-            data.stats.commit_count += 1;
-            *data.stats.author_stats.entry(author.clone()).or_insert(0) += 1;
+        if let MessageData::CommitInfo {
+            hash, author, message: subject, timestamp, committer, author_timestamp, changed_files, ..
+        } = &message.data {
+            let raw = self.raw_export.then(|| RawCommit {
+                hash: hash.clone(),
+                author: author.clone(),
+                committer: committer.clone(),
+                authored: *author_timestamp,
+                committed: *timestamp,
+                subject: subject.clone(),
+                files: changed_files.clone(),
+            });
+            let activity = CommitActivity {
+                hash: hash.clone(),
+                author: author.clone(),
+                timestamp: *timestamp,
+                lines_added: changed_files.iter().map(|f| f.lines_added).sum(),
+                lines_removed: changed_files.iter().map(|f| f.lines_removed).sum(),
+            };
+            self.update_stats(scan_id, hash, |stats| {
+                stats.commit_count += 1;
+                *stats.author_stats.entry(author.clone()).or_insert(0) += 1;
+                stats.pairing.record_commit(hash, author);
+                stats.activity.push(activity);
+                stats.working_set.record(*timestamp, changed_files.iter().map(|f| f.path.as_str()));
+                stats.raw.extend(raw);
+            }).await;
         }
         Ok(())
     }
 
+    /// Process a file change message and record any file mode changes and replaced lines
+    async fn process_file_change(&self, scan_id: &str, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::FileChange {
+            path, change_type, insertions, deletions, commit_hash, old_mode, new_mode, replaced_lines, ..
+        } = &message.data {
+            // Keyed by commit hash, like the commit itself, so its author is known to the pairing matrix
+            self.update_stats(scan_id, commit_hash, |stats| {
+                stats.permissions.record(
+                    commit_hash, path, change_type, *old_mode, *new_mode, *insertions, *deletions,
+                );
+                stats.pairing.record_replaced_lines(commit_hash, replaced_lines);
+            }).await;
+        }
+        Ok(())
+    }
+    
+    /// Update a scan's statistics in the shard of a commit, creating the scan's data on first use
+    ///
+    /// Only the first message of a scan takes the scan map's write lock; every other
+    /// update shares the read lock and contends only with updates to the same shard.
+    async fn update_stats(&self, scan_id: &str, commit_hash: &str, update: impl FnOnce(&mut CommitsStats)) {
+        {
+            let scan_data = self.scan_data.read().await;
+            if let Some(data) = scan_data.get(scan_id) {
+                data.tally.update(commit_hash, update);
+                return;
+            }
+        }
+        let mut scan_data = self.scan_data.write().await;
+        scan_data.entry(scan_id.to_string())
+            .or_insert_with(CommitsScanData::new)
+            .tally
+            .update(commit_hash, update);
+    }
+
     /// Generate commit summary statistics
     async fn generate_summary(&self) -> PluginResult<ScanMessage> {
         // Aggregate statistics from all active scans
@@ -272,9 +398,9 @@ impl CommitsPlugin {
             let mut total_commits = 0;
             let mut all_authors = HashMap::new();
             
-            for data in scan_data.values() {
-                total_commits += data.stats.commit_count;
-                for (author, count) in &data.stats.author_stats {
+            for stats in scan_data.values().map(CommitsScanData::stats) {
+                total_commits += stats.commit_count;
+                for (author, count) in &stats.author_stats {
                     *all_authors.entry(author.clone()).or_insert(0) += count;
                 }
             }
@@ -303,12 +429,25 @@ impl CommitsPlugin {
         Ok(ScanMessage::new(header, data))
     }
     
+    /// Schema metadata describing the effective date range (`since`/`until`)
+    fn date_range_metadata(&self) -> HashMap<String, String> {
+        self.date_range.as_ref().map(DateRange::to_metadata).unwrap_or_default()
+    }
+    
+    /// Description suffix naming the effective date range, empty for all history
+    fn date_range_suffix(&self) -> String {
+        self.date_range.as_ref()
+            .map(|range| format!(" ({})", range.describe()))
+            .unwrap_or_default()
+    }
+    
     /// Create PluginDataExport from current commit statistics
     async fn create_data_export(&self, scan_id: &str) -> PluginResult<PluginDataExport> {
         let (commit_count, author_stats) = {
             let scan_data_guard = self.scan_data.read().await;
             if let Some(data) = scan_data_guard.get(scan_id) {
-                (data.stats.commit_count, data.stats.author_stats.clone())
+                let stats = data.stats();
+                (stats.commit_count, stats.author_stats)
             } else {
                 (0, HashMap::new())
             }
@@ -322,12 +461,12 @@ impl CommitsPlugin {
                 ColumnDef::new("Percentage", ColumnType::Float)
                     .with_format_hint("percentage"),
             ],
-            metadata: HashMap::new(),
+            metadata: self.date_range_metadata(),
         };
         
         // Convert author stats to rows, sorted by commit count
         let mut author_list: Vec<_> = author_stats.iter().collect();
-        author_list.sort_by(|a, b| b.1.cmp(a.1)); // Sort by commit count descending
+        author_list.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0))); // Sort by commit count descending
         
         let rows: Vec<Row> = author_list
             .into_iter()
@@ -367,17 +506,552 @@ impl CommitsPlugin {
             plugin_id: "commits".to_string(),
             title: "Commit Analysis".to_string(),
             description: Some(format!(
-                "Analysis of {} commits from {} authors in scan {}",
-                commit_count, author_stats.len(), scan_id
+                "Analysis of {} commits from {} authors in scan {}{}",
+                commit_count, author_stats.len(), scan_id, self.date_range_suffix()
             )),
             data_type: DataExportType::Tabular,
             schema,
             data: DataPayload::Rows(Arc::new(rows)),
             export_hints,
             timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport listing activity anomalies, if any were detected
+    async fn create_anomalies_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let anomalies = {
+            let scan_data_guard = self.scan_data.read().await;
+            let data = scan_data_guard.get(scan_id)?;
+            detect_anomalies(&data.stats().activity, &self.anomaly_config)
+        };
+        
+        if anomalies.is_empty() {
+            return None;
+        }
+        
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Week", ColumnType::String)
+                    .with_description("Start date of the week the anomaly occurred in"),
+                ColumnDef::new("Type", ColumnType::String)
+                    .with_description("Kind of anomaly detected"),
+                ColumnDef::new("Observed", ColumnType::Float)
+                    .with_description("Observed value for the period"),
+                ColumnDef::new("Baseline", ColumnType::Float)
+                    .with_description("Rolling baseline the observation was compared against"),
+                ColumnDef::new("Detail", ColumnType::String),
+            ],
+            metadata: self.date_range_metadata(),
+        };
+        
+        let rows: Vec<Row> = anomalies
+            .iter()
+            .map(|anomaly| Row::new(vec![
+                Value::String(anomaly.period_label()),
+                Value::String(anomaly.kind.as_str().to_string()),
+                Value::Float(anomaly.observed),
+                Value::Float(anomaly.baseline),
+                Value::String(anomaly.detail.clone()),
+            ]))
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Activity Anomalies".to_string(),
+            description: Some(format!(
+                "{} unusual periods detected against a {}-week rolling baseline in scan {}{}",
+                anomalies.len(), self.anomaly_config.window_weeks, scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                ],
+                sort_by: Some("Week".to_string()),
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create the findings export raised by activity anomalies, if any were detected
+    async fn create_findings_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let anomalies = {
+            let scan_data_guard = self.scan_data.read().await;
+            let data = scan_data_guard.get(scan_id)?;
+            detect_anomalies(&data.stats().activity, &self.anomaly_config)
+        };
+        
+        let findings: Vec<Finding> = anomalies.iter().map(|anomaly| anomaly.to_finding()).collect();
+        let mut export = findings_export("commits", &findings)?;
+        export.schema.metadata.extend(self.date_range_metadata());
+        Some(export)
+    }
+    
+    /// Create PluginDataExport with the working set size at the end of each period
+    async fn create_working_set_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let series = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats().working_set.series(&self.working_set_config)
+        };
+        
+        if series.is_empty() {
+            return None;
+        }
+        
+        let window_days = self.working_set_config.window.num_days();
+        let period = self.working_set_config.period.as_str();
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Period", ColumnType::String),
+                ColumnDef::new("Active Files", ColumnType::Integer)
+                    .with_description("Distinct files changed within the trailing window ending with the period"),
+                ColumnDef::new("Files Changed", ColumnType::Integer)
+                    .with_description("Distinct files changed within the period"),
+                ColumnDef::new("Commits", ColumnType::Integer),
+            ],
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("window_days".to_string(), window_days.to_string());
+                meta.insert("period".to_string(), period.to_string());
+                meta
+            },
+        };
+        
+        let rows: Vec<Row> = series
+            .iter()
+            .map(|sample| Row::new(vec![
+                Value::String(sample.label.clone()),
+                Value::Integer(sample.active_files as i64),
+                Value::Integer(sample.files_changed as i64),
+                Value::Integer(sample.commits as i64),
+            ]))
+            .collect();
+        
+        let peak = series.iter().max_by_key(|sample| sample.active_files)?;
+        
+        Some(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Working Set".to_string(),
+            description: Some(format!(
+                "Files changed within a trailing {}-day window per {} (peak {} files in {}) in scan {}{}",
+                window_days, period, peak.active_files, peak.label, scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                ],
+                sort_by: Some("Period".to_string()),
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport with commit totals per period or author, if grouping was requested
+    async fn create_activity_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let grouping = self.group_by?;
+        let activity = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats().activity
+        };
+        
+        let groups = timeline::group(&activity, grouping, self.series_mode);
+        if groups.is_empty() {
+            return None;
+        }
+        let sparse = matches!(grouping, Grouping::Period(_)) && self.series_mode == SeriesMode::Sparse;
+        
+        let (key, description) = match grouping {
+            Grouping::Period(_) => ("Period", format!("Commits per {}", grouping.as_str())),
+            Grouping::Author => ("Author", "Commits per author".to_string()),
+        };
+        let mut columns = vec![ColumnDef::new(key, ColumnType::String), ColumnDef::new("Commits", ColumnType::Integer)];
+        if matches!(grouping, Grouping::Period(_)) {
+            columns.push(ColumnDef::new("Authors", ColumnType::Integer)
+                .with_description("Distinct authors committing in the period"));
+        }
+        columns.push(ColumnDef::new("Lines Added", ColumnType::Integer));
+        columns.push(ColumnDef::new("Lines Removed", ColumnType::Integer));
+        if sparse {
+            columns.push(ColumnDef::new("Empty Periods", ColumnType::Integer)
+                .with_description("Consecutive periods without commits collapsed into this row"));
+        }
+        let schema = DataSchema {
+            columns,
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("group_by".to_string(), grouping.as_str().to_string());
+                if matches!(grouping, Grouping::Period(_)) {
+                    meta.insert("series".to_string(), self.series_mode.as_str().to_string());
+                }
+                meta
+            },
+        };
+        
+        let rows: Vec<Row> = groups
+            .iter()
+            .map(|group| {
+                let totals = &group.totals;
+                let mut values = vec![Value::String(group.label.clone()), Value::Integer(totals.commits as i64)];
+                if matches!(grouping, Grouping::Period(_)) {
+                    values.push(Value::Integer(totals.authors.len() as i64));
+                }
+                values.push(Value::Integer(totals.lines_added as i64));
+                values.push(Value::Integer(totals.lines_removed as i64));
+                if sparse {
+                    values.push(Value::Integer(group.empty_periods as i64));
+                }
+                Row::new(values)
+            })
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Commit Activity".to_string(),
+            description: Some(format!(
+                "{} ({} rows) in scan {}{}",
+                description, rows.len(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                ],
+                sort_by: Some(key.to_string()),
+                sort_ascending: matches!(grouping, Grouping::Period(_)),
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport holding the commit dataset itself, one row per changed file
+    async fn create_raw_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let commits = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats().raw
+        };
+        
+        let rows = raw::rows(&commits);
+        let schema = DataSchema {
+            columns: raw::columns(),
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("raw_export".to_string(), "true".to_string());
+                meta
+            },
+        };
+        
+        Some(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Commits".to_string(),
+            description: Some(format!(
+                "{} commits ({} rows, one per changed file) in scan {}{}",
+                commits.len(), rows.len(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Console,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport listing file permission changes, if any were recorded
+    async fn create_permissions_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let permissions = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats().permissions
+        };
+        
+        if permissions.is_empty() {
+            return None;
+        }
+        
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Commit", ColumnType::String),
+                ColumnDef::new("File", ColumnType::String),
+                ColumnDef::new("Change", ColumnType::String)
+                    .with_description("Kind of permission change"),
+                ColumnDef::new("Old Mode", ColumnType::String),
+                ColumnDef::new("New Mode", ColumnType::String),
+                ColumnDef::new("Mode Only", ColumnType::Boolean)
+                    .with_description("Whether the commit changed only file modes"),
+            ],
+            metadata: self.date_range_metadata(),
+        };
+        
+        let rows: Vec<Row> = permissions
+            .changes()
+            .iter()
+            .map(|change| Row::new(vec![
+                Value::String(change.commit_hash.chars().take(8).collect()),
+                Value::String(change.path.clone()),
+                Value::String(change.kind.as_str().to_string()),
+                Value::String(format_mode(change.old_mode)),
+                Value::String(format_mode(change.new_mode)),
+                Value::Boolean(permissions.is_mode_only_commit(&change.commit_hash)),
+            ]))
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Permission Changes".to_string(),
+            description: Some(format!(
+                "{} new executables, {} executable-bit changes and {} mode-change-only commits in scan {}{}",
+                permissions.count(PermissionChangeKind::NewExecutable),
+                permissions.count(PermissionChangeKind::ExecutableSet)
+                    + permissions.count(PermissionChangeKind::ExecutableCleared),
+                permissions.mode_only_commits().len(),
+                scan_id,
+                self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
         })
     }
     
+    /// Create the author pairing matrix export, if line attribution was available
+    async fn create_pairing_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let pairing = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats().pairing
+        };
+        
+        if pairing.is_empty() {
+            return None;
+        }
+        
+        let matrix = pairing.to_matrix(MAX_MATRIX_AUTHORS);
+        let shown = if matrix.row_labels.len() < pairing.authors().len() {
+            format!(" (top {} authors shown)", matrix.row_labels.len())
+        } else {
+            String::new()
+        };
+        
+        Some(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Author Pairing".to_string(),
+            description: Some(format!(
+                "Lines modified by each editor (rows) that were last touched by each author (columns): \
+                 {} lines, {} across authors{} in scan {}{}",
+                pairing.total_lines(),
+                pairing.cross_author_lines(),
+                shown,
+                scan_id,
+                self.date_range_suffix()
+            )),
+            data_type: DataExportType::Matrix,
+            schema: DataSchema {
+                columns: vec![],
+                metadata: self.date_range_metadata(),
+            },
+            data: DataPayload::Matrix(Arc::new(matrix)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Publish the author pairing export, if line attribution was available
+    async fn publish_pairing_export(&self, scan_id: &str) {
+        if let Some(export_data) = self.create_pairing_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish pairing DataReady event: {}", e);
+            }
+        }
+    }
+    
+    /// Publish the permission changes export, if any were recorded
+    async fn publish_permissions_export(&self, scan_id: &str) {
+        if let Some(export_data) = self.create_permissions_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish permissions DataReady event: {}", e);
+            }
+        }
+    }
+    
+    /// Publish the exports of a completed scan: the commit dataset with `--raw-export`, summaries otherwise
+    async fn publish_exports(&self, scan_id: &str) {
+        if self.raw_export {
+            if let Some(export_data) = self.create_raw_export(scan_id).await {
+                let event = PluginEvent::DataReady {
+                    plugin_id: "commits".to_string(),
+                    scan_id: scan_id.to_string(),
+                    export: Arc::new(export_data),
+                };
+                
+                if let Err(e) = self.publish(event).await {
+                    log::warn!("Failed to publish raw commits DataReady event: {}", e);
+                }
+            }
+            return;
+        }
+        
+        if let Ok(export_data) = self.create_data_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish DataReady event: {}", e);
+            } else {
+                log::debug!("Published DataReady event for commits plugin");
+            }
+        }
+        self.publish_anomalies_export(scan_id).await;
+        self.publish_findings_export(scan_id).await;
+        self.publish_working_set_export(scan_id).await;
+        self.publish_activity_export(scan_id).await;
+        self.publish_permissions_export(scan_id).await;
+        self.publish_pairing_export(scan_id).await;
+    }
+    
+    /// Publish the commit activity table, if grouping was requested
+    async fn publish_activity_export(&self, scan_id: &str) {
+        if let Some(export_data) = self.create_activity_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish activity DataReady event: {}", e);
+            }
+        }
+    }
+    
+    /// Publish the findings raised by activity anomalies, if any were detected
+    async fn publish_findings_export(&self, scan_id: &str) {
+        if let Some(export_data) = self.create_findings_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish findings DataReady event: {}", e);
+            }
+        }
+    }
+    
+    /// Publish the working set series, if any commits were processed
+    async fn publish_working_set_export(&self, scan_id: &str) {
+        if let Some(export_data) = self.create_working_set_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish working set DataReady event: {}", e);
+            }
+        }
+    }
+    
+    /// Publish the anomalies export alongside the commit statistics, if any were found
+    async fn publish_anomalies_export(&self, scan_id: &str) {
+        if let Some(export_data) = self.create_anomalies_export(scan_id).await {
+            let event = PluginEvent::DataReady {
+                plugin_id: "commits".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+            
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish anomalies DataReady event: {}", e);
+            }
+        }
+    }
+    
     /// Execute commits analysis function
     async fn execute_commits_analysis(&self) -> PluginResult<PluginResponse> {
         let start_time = std::time::Instant::now();
@@ -387,9 +1061,9 @@ impl CommitsPlugin {
             let mut total_commits = 0;
             let mut all_authors = HashMap::new();
             
-            for data in scan_data.values() {
-                total_commits += data.stats.commit_count;
-                for author in data.stats.author_stats.keys() {
+            for stats in scan_data.values().map(CommitsScanData::stats) {
+                total_commits += stats.commit_count;
+                for author in stats.author_stats.keys() {
                     all_authors.insert(author.clone(), true);
                 }
             }
@@ -416,7 +1090,7 @@ impl CommitsPlugin {
             data,
             metadata: crate::plugin::context::ExecutionMetadata {
                 duration_us,
-                memory_used: 0,
+                memory_used: self.state_memory().await as u64,
                 entries_processed: commit_count as u64,
                 plugin_version: self.info.version.clone(),
                 extra: HashMap::new(),
@@ -434,8 +1108,8 @@ impl CommitsPlugin {
             let mut aggregated_stats = HashMap::new();
             
             // Aggregate author stats from all scans
-            for data in scan_data.values() {
-                for (author, count) in &data.stats.author_stats {
+            for stats in scan_data.values().map(CommitsScanData::stats) {
+                for (author, count) in &stats.author_stats {
                     *aggregated_stats.entry(author.clone()).or_insert(0) += count;
                 }
             }
@@ -444,7 +1118,7 @@ impl CommitsPlugin {
             (aggregated_stats.len(), aggregated_stats.clone(), authors)
         };
         
-        authors.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by commit count descending
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))); // Sort by commit count descending
 
         let data = json!({
             "total_authors": author_count,
@@ -463,7 +1137,7 @@ impl CommitsPlugin {
             data,
             metadata: crate::plugin::context::ExecutionMetadata {
                 duration_us,
-                memory_used: 0,
+                memory_used: self.state_memory().await as u64,
                 entries_processed: author_count as u64,
                 plugin_version: self.info.version.clone(),
                 extra: HashMap::new(),
@@ -471,6 +1145,237 @@ impl CommitsPlugin {
             errors: vec![],
         })
     }
+    
+    /// Execute activity anomaly detection function
+    async fn execute_anomaly_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let activity: Vec<CommitActivity> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values()
+                .flat_map(|data| data.stats().activity)
+                .collect()
+        };
+        let anomalies = detect_anomalies(&activity, &self.anomaly_config);
+
+        let data = json!({
+            "total_anomalies": anomalies.len(),
+            "window_weeks": self.anomaly_config.window_weeks,
+            "sigma": self.anomaly_config.sigma,
+            "anomalies": anomalies.iter().map(|anomaly| {
+                json!({
+                    "week": anomaly.period_label(),
+                    "type": anomaly.kind.as_str(),
+                    "observed": anomaly.observed,
+                    "baseline": anomaly.baseline,
+                    "detail": anomaly.detail,
+                })
+            }).collect::<Vec<_>>(),
+            "function": "anomalies"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "anomaly_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: activity.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+    
+    /// Execute working set size function
+    async fn execute_working_set_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let trackers: Vec<WorkingSetTracker> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values().map(|data| data.stats().working_set).collect()
+        };
+        let series: Vec<_> = trackers.iter()
+            .flat_map(|tracker| tracker.series(&self.working_set_config))
+            .collect();
+
+        let data = json!({
+            "window_days": self.working_set_config.window.num_days(),
+            "period": self.working_set_config.period.as_str(),
+            "peak_active_files": series.iter().map(|sample| sample.active_files).max().unwrap_or(0),
+            "series": series.iter().map(|sample| {
+                json!({
+                    "period": sample.label,
+                    "active_files": sample.active_files,
+                    "files_changed": sample.files_changed,
+                    "commits": sample.commits,
+                })
+            }).collect::<Vec<_>>(),
+            "function": "working-set"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "working_set_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: series.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+    
+    /// Execute commit timeline function (by month unless `--group-by` is given)
+    async fn execute_timeline_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let grouping = self.group_by.unwrap_or(Grouping::Period(BucketPeriod::Month));
+        let activity: Vec<CommitActivity> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values().flat_map(|data| data.stats().activity).collect()
+        };
+        let groups = timeline::group(&activity, grouping, self.series_mode);
+
+        let data = json!({
+            "group_by": grouping.as_str(),
+            "series": self.series_mode.as_str(),
+            "groups": groups.iter().map(|group| {
+                json!({
+                    "label": group.label,
+                    "commits": group.totals.commits,
+                    "authors": group.totals.authors.len(),
+                    "lines_added": group.totals.lines_added,
+                    "lines_removed": group.totals.lines_removed,
+                    "empty_periods": group.empty_periods,
+                })
+            }).collect::<Vec<_>>(),
+            "function": "timeline"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "timeline_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: activity.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+    
+    /// Execute file permission change reporting function
+    async fn execute_permissions_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let trackers: Vec<PermissionTracker> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values().map(|data| data.stats().permissions).collect()
+        };
+
+        let changes: Vec<_> = trackers.iter().flat_map(|tracker| {
+            tracker.changes().iter().map(move |change| {
+                json!({
+                    "commit": change.commit_hash,
+                    "file": change.path,
+                    "change": change.kind.as_str(),
+                    "old_mode": format_mode(change.old_mode),
+                    "new_mode": format_mode(change.new_mode),
+                    "mode_only": change.mode_only,
+                    "mode_only_commit": tracker.is_mode_only_commit(&change.commit_hash),
+                })
+            })
+        }).collect();
+        let mode_only_commits: Vec<String> = trackers.iter()
+            .flat_map(|tracker| tracker.mode_only_commits())
+            .collect();
+        let new_executables: usize = trackers.iter()
+            .map(|tracker| tracker.count(PermissionChangeKind::NewExecutable))
+            .sum();
+
+        let data = json!({
+            "total_changes": changes.len(),
+            "new_executables": new_executables,
+            "mode_only_commits": mode_only_commits,
+            "changes": changes,
+            "function": "permissions"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "permissions_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: changes.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute author pairing analysis function
+    async fn execute_pairing_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let matrices: Vec<PairingMatrix> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values().map(|data| data.stats().pairing).collect()
+        };
+
+        let pairs: Vec<_> = matrices.iter()
+            .flat_map(|matrix| matrix.pairs())
+            .map(|pair| json!({
+                "editor": pair.editor,
+                "original_author": pair.original,
+                "lines": pair.lines,
+            }))
+            .collect();
+        let total_lines: usize = matrices.iter().map(|m| m.total_lines()).sum();
+        let cross_author_lines: usize = matrices.iter().map(|m| m.cross_author_lines()).sum();
+
+        let data = json!({
+            "total_lines": total_lines,
+            "cross_author_lines": cross_author_lines,
+            "pairs": pairs,
+            "function": "pairing"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "pairing_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: pairs.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
 }
 
 // Default implementation removed - plugins require notification managers via with_dependencies()
@@ -481,7 +1386,8 @@ impl Plugin for CommitsPlugin {
         &self.info
     }
 
-    async fn initialize(&mut self, _context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
         if self.initialized {
             return Ok(()); // Idempotent - allow re-initialization
         }
@@ -492,6 +1398,9 @@ impl Plugin for CommitsPlugin {
             scan_data.clear();
         }
         
+        // Record the effective date range so exports can report it
+        self.date_range = context.query_params.date_range.clone();
+        
         // TODO: Initialize notification manager when PluginContext supports it
         // For now, the notification manager will be None until the context is extended
         log::debug!("CommitsPlugin: Initialization complete (notification manager not yet implemented in context)");
@@ -520,9 +1429,24 @@ impl Plugin for CommitsPlugin {
                     "commits" | "commit" | "history" => {
                         self.execute_commits_analysis().await
                     }
-                    "authors" | "contributors" | "committers" => {
+                    "authors" | "committers" => {
                         self.execute_author_analysis().await
                     }
+                    "anomalies" | "anomaly" => {
+                        self.execute_anomaly_analysis().await
+                    }
+                    "working-set" | "wip" => {
+                        self.execute_working_set_analysis().await
+                    }
+                    "timeline" | "time-series" => {
+                        self.execute_timeline_analysis().await
+                    }
+                    "permissions" | "modes" => {
+                        self.execute_permissions_analysis().await
+                    }
+                    "pairing" | "collaboration" => {
+                        self.execute_pairing_analysis().await
+                    }
                     _ => Err(PluginError::execution_failed(
                         format!("Unknown function: {}", function_name)
                     )),
@@ -564,10 +1488,40 @@ impl Plugin for CommitsPlugin {
             },
             PluginFunction {
                 name: "authors".to_string(),
-                aliases: vec!["contributors".to_string(), "committers".to_string()],
+                aliases: vec!["committers".to_string()],
                 description: "Analyze commit authors and contributor statistics".to_string(),
                 is_default: false,
             },
+            PluginFunction {
+                name: "anomalies".to_string(),
+                aliases: vec!["anomaly".to_string()],
+                description: "Detect unusual periods of repository activity".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "working-set".to_string(),
+                aliases: vec!["wip".to_string()],
+                description: "Distinct files changed within a trailing window over time".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "timeline".to_string(),
+                aliases: vec!["time-series".to_string()],
+                description: "Commits, authors and line changes per week, month or author".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "permissions".to_string(),
+                aliases: vec!["modes".to_string()],
+                description: "Report new executables and file mode changes".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "pairing".to_string(),
+                aliases: vec!["collaboration".to_string()],
+                description: "Matrix of which authors modify lines last touched by others".to_string(),
+                is_default: false,
+            },
         ]
     }
     
@@ -677,7 +1631,7 @@ impl PluginDataRequirements for CommitsPlugin {
 
 #[async_trait]
 impl ConsumerPlugin for CommitsPlugin {
-    async fn start_consuming(&mut self, consumer: QueueConsumer) -> PluginResult<()> {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
         let mut consuming = self.consuming.write().await;
         
         if *consuming {
@@ -696,10 +1650,11 @@ impl ConsumerPlugin for CommitsPlugin {
         Ok(())
     }
     
-    async fn process_message(&self, consumer: &QueueConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
         // Process the commit message and update statistics
-        let scan_id = "unknown"; // TODO: Get actual scan_id from message context
+        let scan_id = message.header().scan_id.as_str();
         self.process_commit(scan_id, &message).await?;
+        self.process_file_change(scan_id, &message).await?;
         
         // Acknowledge the message
         consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
@@ -726,7 +1681,8 @@ impl ConsumerPlugin for CommitsPlugin {
                 let (count, author_count) = {
                     let scan_data = self.scan_data.read().await;
                     if let Some(data) = scan_data.get(scan_id) {
-                        (data.stats.commit_count, data.stats.author_stats.len())
+                        let stats = data.stats();
+                        (stats.commit_count, stats.author_stats.len())
                     } else {
                         (0, 0)
                     }
@@ -736,22 +1692,8 @@ impl ConsumerPlugin for CommitsPlugin {
                     scan_id, count, author_count, total_messages
                 );
                 
-                // Create and publish data export
-                {
-                    if let Ok(export_data) = self.create_data_export(scan_id).await {
-                        let event = PluginEvent::DataReady {
-                            plugin_id: "commits".to_string(),
-                            scan_id: scan_id.clone(),
-                            export: Arc::new(export_data),
-                        };
-                        
-                        if let Err(e) = self.publish(event).await {
-                            log::warn!("Failed to publish DataReady event: {}", e);
-                        } else {
-                            log::debug!("Published DataReady event for commits plugin");
-                        }
-                    }
-                }
+                // Create and publish data exports
+                self.publish_exports(scan_id).await;
             }
             _ => {
                 // Other events are just logged
@@ -782,13 +1724,19 @@ impl ConsumerPlugin for CommitsPlugin {
         Ok(())
     }
     
+    async fn state_memory(&self) -> usize {
+        self.scan_data.read().await.values().map(|data| data.tally.heap_size()).sum()
+    }
+    
     fn consumer_preferences(&self) -> ConsumerPreferences {
         ConsumerPreferences {
-            consume_all_messages: false, // Only interested in commit messages
-            interested_message_types: vec!["CommitInfo".to_string()],
+            consume_all_messages: false, // Only interested in commit and file change messages
+            interested_message_types: vec!["CommitInfo".to_string(), "FileChange".to_string()],
             high_frequency_capable: true, // Can handle many commits
             preferred_batch_size: 10, // Process in small batches
             requires_ordered_delivery: false, // Order doesn't matter for statistics
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_secs(1)),
         }
     }
 }
@@ -822,6 +1770,50 @@ impl PluginClapParser for CommitsPlugin {
                 .long("stats")
                 .help("Include detailed statistical analysis")
                 .action(ArgAction::SetTrue))
+            .arg(Arg::new("anomaly-window")
+                .long("anomaly-window")
+                .value_name("WEEKS")
+                .help("Preceding weeks used as the anomaly baseline (a count of weeks or a duration like 3m)")
+                .value_parser(|value: &str| {
+                    parse_duration_or(value, "w")
+                        .map(|duration| duration.num_weeks() as usize)
+                        .map_err(|e| e.to_string())
+                })
+                .default_value("8"))
+            .arg(Arg::new("anomaly-sigma")
+                .long("anomaly-sigma")
+                .value_name("N")
+                .help("Standard deviations above baseline to flag a spike or surge")
+                .value_parser(clap::value_parser!(f64))
+                .default_value("2.0"))
+            .arg(Arg::new("working-set-window")
+                .long("working-set-window")
+                .value_name("DAYS")
+                .help("Trailing window in which a change keeps a file in the working set (a count of days or a duration like 6w)")
+                .value_parser(|value: &str| parse_duration_or(value, "d").map_err(|e| e.to_string()))
+                .default_value("30"))
+            .arg(Arg::new("working-set-period")
+                .long("working-set-period")
+                .value_name("PERIOD")
+                .help("Period at the end of which the working set is sampled")
+                .value_parser(SAMPLE_PERIODS)
+                .default_value("week"))
+            .arg(Arg::new("group-by")
+                .long("group-by")
+                .value_name("GROUPING")
+                .help("Also export commits, authors and line changes per week, month or author")
+                .value_parser(GROUPINGS))
+            .arg(Arg::new("series")
+                .long("series")
+                .value_name("MODE")
+                .help("Activity series layout: dense lists every period, sparse collapses empty periods into gap rows")
+                .value_parser(["dense", "sparse"])
+                .default_value("dense"))
+            .arg(Arg::new("raw-export")
+                .long("raw-export")
+                .help("Export every scanned commit with its changed files instead of summaries")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("group-by"))
     }
     
     async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
@@ -829,22 +1821,37 @@ impl PluginClapParser for CommitsPlugin {
         // The arguments are handled during execution based on the function being called
         
         
-        if let Some(authors) = matches.get_many::<String>("author-filter") {
-            log::debug!("Commits plugin configured with author filters: {:?}", 
-                       authors.collect::<Vec<_>>());
+        if matches.get_flag("include-stats") {
+            log::debug!("Commits plugin configured to include detailed statistics");
         }
         
-        if matches.get_flag("exclude-merges") {
-            log::debug!("Commits plugin configured to exclude merge commits");
+        if let Some(window) = matches.get_one::<usize>("anomaly-window") {
+            if *window == 0 {
+                return Err(PluginError::configuration_error("--anomaly-window must be at least one week"));
+            }
+            self.anomaly_config.window_weeks = *window;
         }
         
-        if matches.get_flag("include-stats") {
-            log::debug!("Commits plugin configured to include detailed statistics");
+        if let Some(sigma) = matches.get_one::<f64>("anomaly-sigma") {
+            self.anomaly_config.sigma = *sigma;
+        }
+        
+        if let Some(window) = matches.get_one::<chrono::Duration>("working-set-window") {
+            if window.num_days() < 1 {
+                return Err(PluginError::configuration_error("--working-set-window must be at least one day"));
+            }
+            self.working_set_config.window = *window;
+        }
+        
+        if let Some(period) = matches.get_one::<String>("working-set-period").and_then(|p| BucketPeriod::parse(p)) {
+            self.working_set_config.period = period;
         }
         
-        if let Some(format) = matches.get_one::<String>("output-format") {
-            log::debug!("Commits plugin configured with output format: {}", format);
+        self.group_by = matches.get_one::<String>("group-by").and_then(|grouping| Grouping::parse(grouping));
+        if let Some(mode) = matches.get_one::<String>("series").and_then(|m| SeriesMode::parse(m)) {
+            self.series_mode = mode;
         }
+        self.raw_export = matches.get_flag("raw-export");
         
         Ok(())
     }
@@ -865,6 +1872,9 @@ mod tests {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs() as i64,
+            committer: author.to_string(),
+            author_timestamp: 0,
+            parents: vec![],
             changed_files: vec![crate::scanner::messages::FileChangeData {
                 path: "src/main.rs".to_string(),
                 lines_added: 10,
@@ -989,12 +1999,10 @@ mod tests {
             message_count: 1,
         };
         
-        // This should fail because handle_scan_data_ready is not implemented yet
-        let result = plugin.handle_scan_data_ready(event).await;
+        // Data-ready notifications arrive through the subscriber interface
+        use crate::notifications::traits::Subscriber;
+        let result = plugin.handle_event(crate::notifications::events::UnifiedEvent::Scan(event)).await;
         assert!(result.is_ok());
-        
-        // Verify that the plugin processed the commit data
-        // For now, just verify the method exists and returns Ok
     }
     
     #[tokio::test]
@@ -1011,7 +2019,8 @@ mod tests {
             recoverable: true,
         };
         
-        let result = plugin.handle_scan_warning(event).await;
+        use crate::notifications::traits::Subscriber;
+        let result = plugin.handle_event(crate::notifications::events::UnifiedEvent::Scan(event)).await;
         assert!(result.is_ok());
     }
     
@@ -1051,4 +2060,33 @@ mod tests {
         let result = plugin.handle_scan_completed(event).await;
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_raw_export_lists_each_changed_file() {
+        let mut plugin = CommitsPlugin::new();
+        plugin.raw_export = true;
+        plugin.process_commit("scan", &create_test_commit_message("Alice", "abc123", "Add main")).await.unwrap();
+        
+        let export = plugin.create_raw_export("scan").await.unwrap();
+        assert_eq!(export.title, "Commits");
+        assert_eq!(export.schema.metadata.get("raw_export").map(String::as_str), Some("true"));
+        let DataPayload::Rows(rows) = &export.data else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values[0], Value::String("abc123".to_string()));
+        assert_eq!(rows[0].values[5], Value::String("Add main".to_string()));
+        assert_eq!(rows[0].values[6], Value::String("src/main.rs".to_string()));
+        assert_eq!(rows[0].values[7], Value::Integer(10));
+    }
+    
+    #[tokio::test]
+    async fn test_state_memory_grows_with_commits() {
+        let plugin = CommitsPlugin::new();
+        let empty = plugin.state_memory().await;
+        plugin.process_commit("scan", &create_test_commit_message("Alice", "abc123", "Add main")).await.unwrap();
+        let one = plugin.state_memory().await;
+        plugin.process_commit("scan", &create_test_commit_message("Bob", "def456", "Add lib")).await.unwrap();
+        
+        assert!(one > empty);
+        assert!(plugin.state_memory().await > one);
+    }
+}
@@ -0,0 +1,199 @@
+//! Author Pairing Analysis
+//!
+//! Builds a matrix of "author A modified lines last touched by author B" from
+//! the line attribution the scanner attaches to file changes. The diagonal shows
+//! authors reworking their own code; off-diagonal cells show who edits whose
+//! code, a useful proxy for collaboration patterns and review load.
+
+use crate::plugin::aggregation::Merge;
+use crate::plugin::data_export::{Matrix, Value};
+use crate::plugin::memory::HeapSize;
+use crate::scanner::messages::LineAttribution;
+use std::collections::HashMap;
+
+/// Maximum number of authors shown on each axis of the exported matrix
+pub const MAX_MATRIX_AUTHORS: usize = 15;
+
+/// Lines one author modified that were last touched by another
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorPair {
+    /// Author of the modifying commit
+    pub editor: String,
+    /// Author who last touched the modified lines
+    pub original: String,
+    /// Number of lines modified
+    pub lines: usize,
+}
+
+/// Accumulates editor/original-author line counts across a scan
+#[derive(Debug, Default, Clone)]
+pub struct PairingMatrix {
+    commit_authors: HashMap<String, String>,
+    counts: HashMap<(String, String), usize>,
+}
+
+impl PairingMatrix {
+    /// Remember the author of a commit so its file changes can be attributed
+    pub fn record_commit(&mut self, commit_hash: &str, author: &str) {
+        self.commit_authors.insert(commit_hash.to_string(), author.to_string());
+    }
+
+    /// Record the lines a commit replaced, attributed to their previous authors
+    pub fn record_replaced_lines(&mut self, commit_hash: &str, replaced: &[LineAttribution]) {
+        if replaced.is_empty() {
+            return;
+        }
+
+        let editor = self.commit_authors
+            .get(commit_hash)
+            .cloned()
+            .unwrap_or_else(|| "Unknown".to_string());
+        for attribution in replaced {
+            *self.counts.entry((editor.clone(), attribution.author.clone())).or_insert(0) += attribution.lines;
+        }
+    }
+
+    /// Lines `editor` modified that were last touched by `original`
+    pub fn lines(&self, editor: &str, original: &str) -> usize {
+        self.counts
+            .get(&(editor.to_string(), original.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Total attributed lines
+    pub fn total_lines(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Attributed lines where the editor was not the original author
+    pub fn cross_author_lines(&self) -> usize {
+        self.counts
+            .iter()
+            .filter(|((editor, original), _)| editor != original)
+            .map(|(_, lines)| lines)
+            .sum()
+    }
+
+    /// All pairs, largest first
+    pub fn pairs(&self) -> Vec<AuthorPair> {
+        let mut pairs: Vec<AuthorPair> = self.counts
+            .iter()
+            .map(|((editor, original), lines)| AuthorPair {
+                editor: editor.clone(),
+                original: original.clone(),
+                lines: *lines,
+            })
+            .collect();
+        pairs.sort_by(|a, b| {
+            b.lines.cmp(&a.lines)
+                .then_with(|| a.editor.cmp(&b.editor))
+                .then_with(|| a.original.cmp(&b.original))
+        });
+        pairs
+    }
+
+    /// Authors ordered by the number of lines they edited or had edited, most involved first
+    pub fn authors(&self) -> Vec<String> {
+        let mut involvement: HashMap<&str, usize> = HashMap::new();
+        for ((editor, original), lines) in &self.counts {
+            *involvement.entry(editor).or_insert(0) += lines;
+            if original != editor {
+                *involvement.entry(original).or_insert(0) += lines;
+            }
+        }
+
+        let mut authors: Vec<(&str, usize)> = involvement.into_iter().collect();
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        authors.into_iter().map(|(author, _)| author.to_string()).collect()
+    }
+
+    /// Build the editor-by-original-author matrix for the most involved authors
+    pub fn to_matrix(&self, max_authors: usize) -> Matrix {
+        let authors: Vec<String> = self.authors().into_iter().take(max_authors).collect();
+        let mut matrix = Matrix::new("Editor \\ Original", authors.clone(), authors.clone());
+
+        for (row, editor) in authors.iter().enumerate() {
+            for (column, original) in authors.iter().enumerate() {
+                matrix.set(row, column, Value::Integer(self.lines(editor, original) as i64));
+            }
+        }
+        matrix
+    }
+
+    /// Check whether any attributed lines were recorded
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+impl Merge for PairingMatrix {
+    fn merge(&mut self, other: Self) {
+        self.commit_authors.extend(other.commit_authors);
+        for (pair, lines) in other.counts {
+            *self.counts.entry(pair).or_insert(0) += lines;
+        }
+    }
+}
+
+impl HeapSize for PairingMatrix {
+    fn heap_size(&self) -> usize {
+        self.commit_authors.heap_size() + self.counts.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribution(author: &str, lines: usize) -> LineAttribution {
+        LineAttribution { author: author.to_string(), lines }
+    }
+
+    #[test]
+    fn test_pairs_attributed_to_commit_author() {
+        let mut matrix = PairingMatrix::default();
+        matrix.record_commit("c1", "alice");
+        matrix.record_commit("c2", "bob");
+        matrix.record_replaced_lines("c1", &[attribution("bob", 5), attribution("alice", 2)]);
+        matrix.record_replaced_lines("c2", &[attribution("alice", 7)]);
+        matrix.record_replaced_lines("c2", &[attribution("alice", 1)]);
+
+        assert_eq!(matrix.lines("alice", "bob"), 5);
+        assert_eq!(matrix.lines("bob", "alice"), 8);
+        assert_eq!(matrix.lines("alice", "alice"), 2);
+        assert_eq!(matrix.total_lines(), 15);
+        assert_eq!(matrix.cross_author_lines(), 13);
+        assert_eq!(matrix.pairs()[0], AuthorPair {
+            editor: "bob".to_string(),
+            original: "alice".to_string(),
+            lines: 8,
+        });
+    }
+
+    #[test]
+    fn test_to_matrix_orders_and_limits_authors() {
+        let mut matrix = PairingMatrix::default();
+        matrix.record_commit("c1", "alice");
+        matrix.record_commit("c2", "carol");
+        matrix.record_replaced_lines("c1", &[attribution("bob", 10)]);
+        matrix.record_replaced_lines("c2", &[attribution("alice", 1)]);
+
+        assert_eq!(matrix.authors(), vec!["alice", "bob", "carol"]);
+
+        let grid = matrix.to_matrix(2);
+        assert_eq!(grid.row_labels, vec!["alice", "bob"]);
+        assert_eq!(grid.get(0, 1), Some(&Value::Integer(10)));
+        assert_eq!(grid.get(1, 0), Some(&Value::Integer(0)));
+    }
+
+    #[test]
+    fn test_unknown_commit_and_empty_attribution() {
+        let mut matrix = PairingMatrix::default();
+        matrix.record_replaced_lines("c1", &[]);
+        assert!(matrix.is_empty());
+
+        matrix.record_replaced_lines("c9", &[attribution("bob", 3)]);
+        assert_eq!(matrix.lines("Unknown", "bob"), 3);
+    }
+}
@@ -0,0 +1,232 @@
+//! File Permission Change Tracking
+//!
+//! Surfaces security-relevant file mode changes from the commit history:
+//! newly added executables, executable-bit flips on existing files and
+//! commits whose only effect is changing file modes.
+
+use crate::plugin::aggregation::Merge;
+use crate::plugin::memory::HeapSize;
+use crate::scanner::messages::{is_executable_mode, ChangeType};
+use std::collections::HashMap;
+
+/// Kind of permission change detected for a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionChangeKind {
+    /// A new file was added with the executable bit set
+    NewExecutable,
+    /// An existing file gained the executable bit
+    ExecutableSet,
+    /// An existing file lost the executable bit
+    ExecutableCleared,
+    /// Any other mode change (e.g. regular file to symlink)
+    ModeChanged,
+}
+
+impl PermissionChangeKind {
+    /// Display name used in exported findings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionChangeKind::NewExecutable => "new-executable",
+            PermissionChangeKind::ExecutableSet => "executable-set",
+            PermissionChangeKind::ExecutableCleared => "executable-cleared",
+            PermissionChangeKind::ModeChanged => "mode-changed",
+        }
+    }
+}
+
+/// A single file permission change within a commit
+#[derive(Debug, Clone)]
+pub struct PermissionChange {
+    pub commit_hash: String,
+    pub path: String,
+    pub kind: PermissionChangeKind,
+    pub old_mode: Option<u32>,
+    pub new_mode: Option<u32>,
+    /// True when the file's content was unchanged and only its mode changed
+    pub mode_only: bool,
+}
+
+/// Per-commit file change counters used to identify mode-change-only commits
+#[derive(Debug, Default, Clone)]
+struct CommitModeSummary {
+    files: usize,
+    mode_only_files: usize,
+}
+
+/// Accumulates permission changes from file change messages
+#[derive(Debug, Default, Clone)]
+pub struct PermissionTracker {
+    changes: Vec<PermissionChange>,
+    commits: HashMap<String, CommitModeSummary>,
+}
+
+impl PermissionTracker {
+    /// Record a file change, keeping it if it involves a permission change
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &mut self,
+        commit_hash: &str,
+        path: &str,
+        change_type: &ChangeType,
+        old_mode: Option<u32>,
+        new_mode: Option<u32>,
+        insertions: usize,
+        deletions: usize,
+    ) {
+        let summary = self.commits.entry(commit_hash.to_string()).or_default();
+        summary.files += 1;
+
+        let Some(kind) = classify(change_type, old_mode, new_mode) else {
+            return;
+        };
+
+        let mode_only = kind != PermissionChangeKind::NewExecutable && insertions == 0 && deletions == 0;
+        if mode_only {
+            summary.mode_only_files += 1;
+        }
+
+        self.changes.push(PermissionChange {
+            commit_hash: commit_hash.to_string(),
+            path: path.to_string(),
+            kind,
+            old_mode,
+            new_mode,
+            mode_only,
+        });
+    }
+
+    /// All recorded permission changes; those of one commit are in the order they were seen
+    pub fn changes(&self) -> &[PermissionChange] {
+        &self.changes
+    }
+
+    /// Count of changes of the given kind
+    pub fn count(&self, kind: PermissionChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    /// Hashes of commits in which every changed file only changed mode
+    pub fn mode_only_commits(&self) -> Vec<String> {
+        let mut commits: Vec<String> = self.commits
+            .iter()
+            .filter(|(_, s)| s.files > 0 && s.files == s.mode_only_files)
+            .map(|(hash, _)| hash.clone())
+            .collect();
+        commits.sort();
+        commits
+    }
+
+    /// Check whether the given commit only changed file modes
+    pub fn is_mode_only_commit(&self, commit_hash: &str) -> bool {
+        self.commits
+            .get(commit_hash)
+            .is_some_and(|s| s.files > 0 && s.files == s.mode_only_files)
+    }
+
+    /// Check whether any permission changes were recorded
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Merge for PermissionTracker {
+    fn merge(&mut self, other: Self) {
+        self.changes.extend(other.changes);
+        for (commit_hash, summary) in other.commits {
+            let merged = self.commits.entry(commit_hash).or_default();
+            merged.files += summary.files;
+            merged.mode_only_files += summary.mode_only_files;
+        }
+    }
+}
+
+/// Classify a file change by its modes, returning None if permissions are unaffected
+fn classify(change_type: &ChangeType, old_mode: Option<u32>, new_mode: Option<u32>) -> Option<PermissionChangeKind> {
+    if *change_type == ChangeType::Added {
+        return new_mode
+            .filter(|mode| is_executable_mode(*mode))
+            .map(|_| PermissionChangeKind::NewExecutable);
+    }
+
+    let (old, new) = (old_mode?, new_mode?);
+    if old == new {
+        return None;
+    }
+
+    Some(match (is_executable_mode(old), is_executable_mode(new)) {
+        (false, true) => PermissionChangeKind::ExecutableSet,
+        (true, false) if new & 0o170000 == 0o100000 => PermissionChangeKind::ExecutableCleared,
+        _ => PermissionChangeKind::ModeChanged,
+    })
+}
+
+/// Format a git file mode as six-digit octal, or "-" when unknown
+pub fn format_mode(mode: Option<u32>) -> String {
+    mode.map(|m| format!("{:06o}", m)).unwrap_or_else(|| "-".to_string())
+}
+
+impl HeapSize for PermissionTracker {
+    fn heap_size(&self) -> usize {
+        let changes = self.changes.capacity() * std::mem::size_of::<PermissionChange>()
+            + self.changes.iter().map(|change| change.commit_hash.heap_size() + change.path.heap_size()).sum::<usize>();
+        let commits = self.commits.capacity() * (std::mem::size_of::<(String, CommitModeSummary)>() + 1)
+            + self.commits.keys().map(HeapSize::heap_size).sum::<usize>();
+        changes + commits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_executable_detected() {
+        let mut tracker = PermissionTracker::default();
+        tracker.record("c1", "bin/run", &ChangeType::Added, None, Some(0o100755), 10, 0);
+        tracker.record("c1", "README.md", &ChangeType::Added, None, Some(0o100644), 5, 0);
+
+        assert_eq!(tracker.changes().len(), 1);
+        assert_eq!(tracker.changes()[0].kind, PermissionChangeKind::NewExecutable);
+        assert!(!tracker.changes()[0].mode_only);
+        assert!(tracker.mode_only_commits().is_empty());
+    }
+
+    #[test]
+    fn test_mode_only_commit_detected() {
+        let mut tracker = PermissionTracker::default();
+        tracker.record("c1", "deploy.sh", &ChangeType::Modified, Some(0o100644), Some(0o100755), 0, 0);
+        tracker.record("c1", "install.sh", &ChangeType::Modified, Some(0o100644), Some(0o100755), 0, 0);
+        tracker.record("c2", "tool.sh", &ChangeType::Modified, Some(0o100755), Some(0o100644), 0, 0);
+        tracker.record("c2", "src/main.rs", &ChangeType::Modified, Some(0o100644), Some(0o100644), 3, 1);
+
+        assert_eq!(tracker.count(PermissionChangeKind::ExecutableSet), 2);
+        assert_eq!(tracker.count(PermissionChangeKind::ExecutableCleared), 1);
+        assert_eq!(tracker.mode_only_commits(), vec!["c1".to_string()]);
+        assert!(tracker.is_mode_only_commit("c1"));
+        assert!(!tracker.is_mode_only_commit("c2"));
+    }
+
+    #[test]
+    fn test_unchanged_and_unknown_modes_ignored() {
+        let mut tracker = PermissionTracker::default();
+        tracker.record("c1", "a.rs", &ChangeType::Modified, Some(0o100644), Some(0o100644), 1, 1);
+        tracker.record("c1", "b.rs", &ChangeType::Modified, None, None, 1, 1);
+        tracker.record("c1", "c.sh", &ChangeType::Deleted, Some(0o100755), None, 0, 4);
+
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_symlink_change_is_mode_changed() {
+        let mut tracker = PermissionTracker::default();
+        tracker.record("c1", "link", &ChangeType::Modified, Some(0o100755), Some(0o120000), 1, 1);
+
+        assert_eq!(tracker.changes()[0].kind, PermissionChangeKind::ModeChanged);
+    }
+
+    #[test]
+    fn test_format_mode() {
+        assert_eq!(format_mode(Some(0o100755)), "100755");
+        assert_eq!(format_mode(None), "-");
+    }
+}
@@ -0,0 +1,126 @@
+//! Raw Commit Dataset
+//!
+//! With `--raw-export` the commits plugin exports the commits it was given
+//! instead of its summaries: one row per changed file, repeating the commit's
+//! hash, identities, dates and subject, and one row without a file for commits
+//! that changed none. Rows carry exactly what the scanner delivered, so the
+//! date range, author filters, path filters and identity mapping applied to
+//! every other report apply to the dataset too.
+
+use crate::plugin::data_export::{ColumnDef, ColumnType, Row, Value};
+use crate::plugin::memory::HeapSize;
+use crate::scanner::messages::FileChangeData;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// One commit as delivered by the scanner
+#[derive(Debug, Clone)]
+pub struct RawCommit {
+    pub hash: String,
+    pub author: String,
+    pub committer: String,
+    /// Author time in seconds since the epoch
+    pub authored: i64,
+    /// Commit time in seconds since the epoch
+    pub committed: i64,
+    pub subject: String,
+    pub files: Vec<FileChangeData>,
+}
+
+/// Columns of the raw dataset
+pub fn columns() -> Vec<ColumnDef> {
+    vec![
+        ColumnDef::new("Hash", ColumnType::String),
+        ColumnDef::new("Author", ColumnType::String),
+        ColumnDef::new("Committer", ColumnType::String),
+        ColumnDef::new("Authored", ColumnType::Timestamp),
+        ColumnDef::new("Committed", ColumnType::Timestamp),
+        ColumnDef::new("Subject", ColumnType::String),
+        ColumnDef::new("File", ColumnType::String)
+            .with_description("Changed file, empty for commits without file changes"),
+        ColumnDef::new("Lines Added", ColumnType::Integer),
+        ColumnDef::new("Lines Removed", ColumnType::Integer),
+    ]
+}
+
+/// Rows of the raw dataset, newest commit first and files in commit order
+pub fn rows(commits: &[RawCommit]) -> Vec<Row> {
+    let mut commits: Vec<&RawCommit> = commits.iter().collect();
+    commits.sort_by(|a, b| b.committed.cmp(&a.committed).then_with(|| a.hash.cmp(&b.hash)));
+
+    let mut rows = Vec::new();
+    for commit in commits {
+        let row = |file: String, added: usize, removed: usize| Row::new(vec![
+            Value::String(commit.hash.clone()),
+            Value::String(commit.author.clone()),
+            Value::String(commit.committer.clone()),
+            timestamp(commit.authored),
+            timestamp(commit.committed),
+            Value::String(commit.subject.clone()),
+            Value::String(file),
+            Value::Integer(added as i64),
+            Value::Integer(removed as i64),
+        ]);
+        if commit.files.is_empty() {
+            rows.push(row(String::new(), 0, 0));
+        }
+        for file in &commit.files {
+            rows.push(row(file.path.clone(), file.lines_added, file.lines_removed));
+        }
+    }
+    rows
+}
+
+fn timestamp(seconds: i64) -> Value {
+    Value::Timestamp(UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64))
+}
+
+impl HeapSize for RawCommit {
+    fn heap_size(&self) -> usize {
+        self.hash.heap_size() + self.author.heap_size() + self.committer.heap_size()
+            + self.subject.heap_size() + self.files.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, committed: i64, files: &[(&str, usize, usize)]) -> RawCommit {
+        RawCommit {
+            hash: hash.to_string(),
+            author: "Alice".to_string(),
+            committer: "Bob".to_string(),
+            authored: committed - 60,
+            committed,
+            subject: format!("Commit {hash}"),
+            files: files.iter().map(|(path, added, removed)| FileChangeData {
+                path: path.to_string(),
+                lines_added: *added,
+                lines_removed: *removed,
+            }).collect(),
+        }
+    }
+
+    #[test]
+    fn test_one_row_per_file_newest_first() {
+        let commits = vec![
+            commit("aaa", 100, &[("src/lib.rs", 3, 1), ("README.md", 2, 0)]),
+            commit("bbb", 200, &[]),
+        ];
+
+        let rows = rows(&commits);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].values[0], Value::String("bbb".to_string()));
+        assert_eq!(rows[0].values[6], Value::String(String::new()));
+        assert_eq!(rows[1].values[2], Value::String("Bob".to_string()));
+        assert_eq!(rows[1].values[3], timestamp(40));
+        assert_eq!(rows[1].values[4], timestamp(100));
+        assert_eq!(rows[1].values[6], Value::String("src/lib.rs".to_string()));
+        assert_eq!(rows[2].values[6..], [
+            Value::String("README.md".to_string()),
+            Value::Integer(2),
+            Value::Integer(0),
+        ]);
+        assert!(rows.iter().all(|row| row.values.len() == columns().len()));
+    }
+}
@@ -0,0 +1,181 @@
+//! Commit Activity Timeline
+//!
+//! Groups commits by calendar week (weeks start on Monday, as ISO weeks do),
+//! by month or by author, and totals the commits, distinct authors and lines
+//! added and removed of each group. Periods without commits between the first
+//! and last active one are listed with zero totals, so the series can be
+//! charted directly, or collapsed into gap rows in a sparse series.
+
+use super::anomalies::CommitActivity;
+use crate::plugin::builtin::utils::time_buckets::{bucket_series, BucketPeriod, SeriesMode};
+use std::collections::{BTreeMap, HashSet};
+
+/// Groupings accepted by `--group-by`
+pub const GROUPINGS: [&str; 3] = ["week", "month", "author"];
+
+/// How commits are grouped in the activity table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// One row per calendar period
+    Period(BucketPeriod),
+    /// One row per author
+    Author,
+}
+
+impl Grouping {
+    /// Parse a grouping as accepted on the command line
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "week" => Some(Grouping::Period(BucketPeriod::Week)),
+            "month" => Some(Grouping::Period(BucketPeriod::Month)),
+            "author" => Some(Grouping::Author),
+            _ => None,
+        }
+    }
+
+    /// Grouping name as accepted on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Grouping::Period(period) => period.as_str(),
+            Grouping::Author => "author",
+        }
+    }
+}
+
+/// Totals of the commits in one group
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityTotals {
+    pub commits: usize,
+    pub authors: HashSet<String>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+impl ActivityTotals {
+    fn add(&mut self, commit: &CommitActivity) {
+        self.commits += 1;
+        self.authors.insert(commit.author.clone());
+        self.lines_added += commit.lines_added;
+        self.lines_removed += commit.lines_removed;
+    }
+}
+
+/// One row of the activity table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityGroup {
+    /// Period, range of empty periods, or author
+    pub label: String,
+    pub totals: ActivityTotals,
+    /// Consecutive empty periods collapsed into this row (0 for other rows)
+    pub empty_periods: usize,
+}
+
+/// Totals per group, labelled by period or author
+///
+/// Periods are in calendar order from the first to the last active one, laid
+/// out in the given series mode; authors are ordered by commit count, most
+/// active first.
+pub fn group(activity: &[CommitActivity], grouping: Grouping, mode: SeriesMode) -> Vec<ActivityGroup> {
+    match grouping {
+        Grouping::Period(period) => {
+            let mut buckets: BTreeMap<_, ActivityTotals> = BTreeMap::new();
+            for commit in activity {
+                if let Some(start) = period.start_of_timestamp(commit.timestamp) {
+                    buckets.entry(start).or_default().add(commit);
+                }
+            }
+            bucket_series(buckets, period, mode)
+                .into_iter()
+                .map(|entry| ActivityGroup {
+                    label: entry.label().to_string(),
+                    totals: entry.value().cloned().unwrap_or_default(),
+                    empty_periods: entry.gap_periods(),
+                })
+                .collect()
+        }
+        Grouping::Author => {
+            let mut authors: BTreeMap<&str, ActivityTotals> = BTreeMap::new();
+            for commit in activity {
+                authors.entry(&commit.author).or_default().add(commit);
+            }
+            let mut groups: Vec<_> = authors.into_iter()
+                .map(|(author, totals)| ActivityGroup { label: author.to_string(), totals, empty_periods: 0 })
+                .collect();
+            groups.sort_by(|a, b| b.totals.commits.cmp(&a.totals.commits).then_with(|| a.label.cmp(&b.label)));
+            groups
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, date: &str, lines_added: usize) -> CommitActivity {
+        let timestamp = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+            .and_hms_opt(12, 0, 0).unwrap()
+            .and_utc()
+            .timestamp();
+        CommitActivity {
+            hash: format!("{}-{}", author, date),
+            author: author.to_string(),
+            timestamp,
+            lines_added,
+            lines_removed: 1,
+        }
+    }
+
+    #[test]
+    fn test_group_by_period() {
+        let activity = vec![
+            commit("alice", "2024-01-03", 10),
+            commit("bob", "2024-01-04", 5),
+            commit("alice", "2024-03-20", 2),
+        ];
+
+        let months = group(&activity, Grouping::Period(BucketPeriod::Month), SeriesMode::Dense);
+        let labels: Vec<_> = months.iter().map(|group| group.label.as_str()).collect();
+        assert_eq!(labels, vec!["2024-01", "2024-02", "2024-03"]);
+        assert_eq!(months[0].totals.commits, 2);
+        assert_eq!(months[0].totals.authors.len(), 2);
+        assert_eq!(months[0].totals.lines_added, 15);
+        assert_eq!(months[1].totals, ActivityTotals::default());
+        assert_eq!(months[1].empty_periods, 0);
+
+        // 2024-01-03 is a Wednesday; its week starts on Monday 2024-01-01
+        let weeks = group(&activity, Grouping::Period(BucketPeriod::Week), SeriesMode::Dense);
+        assert_eq!(weeks[0].label, "2024-01-01");
+        assert_eq!(weeks[0].totals.commits, 2);
+    }
+
+    #[test]
+    fn test_sparse_series_collapses_empty_periods() {
+        let activity = vec![
+            commit("alice", "2024-01-03", 10),
+            commit("alice", "2024-05-20", 2),
+        ];
+
+        let months = group(&activity, Grouping::Period(BucketPeriod::Month), SeriesMode::Sparse);
+        let labels: Vec<_> = months.iter().map(|group| group.label.as_str()).collect();
+        assert_eq!(labels, vec!["2024-01", "2024-02..2024-04", "2024-05"]);
+        assert_eq!(months[1].empty_periods, 3);
+        assert_eq!(months[1].totals, ActivityTotals::default());
+        assert_eq!(months[2].totals.commits, 1);
+    }
+
+    #[test]
+    fn test_group_by_author() {
+        let activity = vec![
+            commit("bob", "2024-01-04", 5),
+            commit("alice", "2024-01-03", 10),
+            commit("alice", "2024-03-20", 2),
+        ];
+
+        let authors = group(&activity, Grouping::Author, SeriesMode::Sparse);
+        assert_eq!(authors[0].label, "alice");
+        assert_eq!(authors[0].totals.commits, 2);
+        assert_eq!(authors[0].totals.lines_removed, 2);
+        assert_eq!(authors[1].label, "bob");
+        assert_eq!(Grouping::parse("quarter"), None);
+    }
+}
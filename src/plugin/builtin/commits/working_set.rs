@@ -0,0 +1,180 @@
+//! Working Set Size
+//!
+//! Tracks how many distinct files are "active" - changed within a trailing
+//! window - over the history of a repository. The working set is sampled at the
+//! end of each calendar period, so a 30-day window sampled weekly reports, for
+//! every week, the files changed in the 30 days up to the end of that week. A
+//! wide working set is a proxy for work in progress being spread across much of
+//! the codebase at once.
+
+use crate::plugin::aggregation::Merge;
+use crate::plugin::builtin::utils::time_buckets::BucketPeriod;
+use crate::plugin::memory::HeapSize;
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+
+/// Sampling periods accepted by `--working-set-period`
+pub const SAMPLE_PERIODS: [&str; 4] = ["day", "week", "month", "quarter"];
+
+/// Trailing window and sampling period for the working set series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingSetConfig {
+    /// How far back a change keeps a file in the working set
+    pub window: chrono::Duration,
+    /// Calendar period at the end of which the working set is sampled
+    pub period: BucketPeriod,
+}
+
+impl Default for WorkingSetConfig {
+    fn default() -> Self {
+        Self {
+            window: chrono::Duration::days(30),
+            period: BucketPeriod::Week,
+        }
+    }
+}
+
+/// Working set sampled at the end of one period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingSetSample {
+    /// Label of the period (e.g. "2024-03-04" for a week)
+    pub label: String,
+    /// Distinct files changed within the window ending with the period
+    pub active_files: usize,
+    /// Distinct files changed within the period itself
+    pub files_changed: usize,
+    /// Commits within the period itself
+    pub commits: usize,
+}
+
+/// Collects commit timestamps and the files each commit changed
+#[derive(Debug, Clone, Default)]
+pub struct WorkingSetTracker {
+    /// Commit timestamp and path of every file change
+    touches: Vec<(i64, String)>,
+    /// Timestamp of every commit
+    commits: Vec<i64>,
+}
+
+impl WorkingSetTracker {
+    /// Record the files changed by a commit
+    pub fn record<'a>(&mut self, timestamp: i64, paths: impl IntoIterator<Item = &'a str>) {
+        self.commits.push(timestamp);
+        self.touches.extend(paths.into_iter().map(|path| (timestamp, path.to_string())));
+    }
+
+    /// Working set at the end of every period from the first to the last commit
+    pub fn series(&self, config: &WorkingSetConfig) -> Vec<WorkingSetSample> {
+        let mut touches: Vec<(i64, &str)> = self.touches.iter().map(|(ts, path)| (*ts, path.as_str())).collect();
+        touches.sort_unstable();
+        let mut commits = self.commits.clone();
+        commits.sort_unstable();
+
+        let (Some(first), Some(last)) = (
+            commits.first().and_then(|ts| config.period.start_of_timestamp(*ts)),
+            commits.last().and_then(|ts| config.period.start_of_timestamp(*ts)),
+        ) else {
+            return Vec::new();
+        };
+
+        let window = config.window.num_seconds();
+        let mut active: HashMap<&str, usize> = HashMap::new();
+        let (mut added, mut removed) = (0, 0);
+        let mut samples = Vec::new();
+        let mut start = first;
+        while start <= last {
+            let next = config.period.next(start);
+            if next <= start {
+                break;
+            }
+            let (start_ts, end_ts) = (midnight(start), midnight(next));
+
+            // Slide the window to [end - window, end)
+            while added < touches.len() && touches[added].0 < end_ts {
+                *active.entry(touches[added].1).or_insert(0) += 1;
+                added += 1;
+            }
+            while removed < added && touches[removed].0 < end_ts - window {
+                let path = touches[removed].1;
+                if let Some(count) = active.get_mut(path) {
+                    *count -= 1;
+                    if *count == 0 {
+                        active.remove(path);
+                    }
+                }
+                removed += 1;
+            }
+
+            let before_period = |ts: &i64| *ts < start_ts;
+            let period_touches = &touches[touches.partition_point(|(ts, _)| before_period(ts))..added];
+            let files_changed: HashSet<&str> = period_touches.iter().map(|(_, path)| *path).collect();
+            let commit_count = commits.partition_point(|ts| *ts < end_ts) - commits.partition_point(before_period);
+
+            samples.push(WorkingSetSample {
+                label: config.period.label(start),
+                active_files: active.len(),
+                files_changed: files_changed.len(),
+                commits: commit_count,
+            });
+            start = next;
+        }
+        samples
+    }
+}
+
+impl Merge for WorkingSetTracker {
+    fn merge(&mut self, other: Self) {
+        self.touches.extend(other.touches);
+        self.commits.extend(other.commits);
+    }
+}
+
+/// Unix timestamp of midnight UTC at the start of a date
+fn midnight(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc().timestamp()).unwrap_or_default()
+}
+
+impl HeapSize for WorkingSetTracker {
+    fn heap_size(&self) -> usize {
+        self.touches.heap_size() + self.commits.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+    /// Monday 2024-01-01 00:00 UTC
+    const MONDAY: i64 = 1_704_067_200;
+
+    #[test]
+    fn test_working_set_slides_with_window() {
+        let mut tracker = WorkingSetTracker::default();
+        tracker.record(MONDAY, ["a.rs", "b.rs"]);
+        tracker.record(MONDAY + 8 * DAY, ["b.rs", "c.rs"]);
+        tracker.record(MONDAY + 22 * DAY, ["d.rs"]);
+
+        let config = WorkingSetConfig { window: chrono::Duration::days(14), period: BucketPeriod::Week };
+        let series = tracker.series(&config);
+        let labels: Vec<&str> = series.iter().map(|sample| sample.label.as_str()).collect();
+        assert_eq!(labels, vec!["2024-01-01", "2024-01-08", "2024-01-15", "2024-01-22"]);
+
+        let active: Vec<usize> = series.iter().map(|sample| sample.active_files).collect();
+        // Week 3's window (Jan 8 - Jan 21) only holds the second commit; week 4 drops it
+        assert_eq!(active, vec![2, 3, 2, 1]);
+        assert_eq!(series[1].files_changed, 2);
+        assert_eq!(series[2], WorkingSetSample {
+            label: "2024-01-15".to_string(),
+            active_files: 2,
+            files_changed: 0,
+            commits: 0,
+        });
+        assert_eq!(series[3].commits, 1);
+    }
+
+    #[test]
+    fn test_empty_tracker_has_no_series() {
+        assert!(WorkingSetTracker::default().series(&WorkingSetConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+//! CODEOWNERS Overlap
+//!
+//! Compares the owners a repository's CODEOWNERS file declares with the people
+//! who actually change the files. Changed lines are grouped by the rule that
+//! owns each file (as on GitHub, the last matching pattern wins) and a rule has
+//! diverged when its declared owners made less than half of the changes.
+//!
+//! Commits carry author names, not account handles, so an owner is matched to
+//! an author by comparing letters and digits only: `@jane-doe` and
+//! `jane.doe@example.com` both match the author `Jane Doe`. Team owners such as
+//! `@org/team` cannot be matched, and rules owned only by teams are reported as
+//! unverified.
+
+use regex::Regex;
+use std::collections::HashMap;
+use crate::plugin::memory::HeapSize;
+
+/// Locations GitHub reads a CODEOWNERS file from, in order of precedence
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Share of a rule's changed lines (0-100) its declared owners must have made
+pub const OWNER_SHARE_THRESHOLD: f64 = 50.0;
+
+/// Pattern label for changed files no rule assigns an owner to
+pub const UNOWNED_LABEL: &str = "(no owner)";
+
+/// One CODEOWNERS line: a path pattern and the owners declared for it
+#[derive(Debug, Clone)]
+pub struct OwnershipRule {
+    pub pattern: String,
+    /// Owners as written, `@user`, `@org/team` or an email address; empty to remove ownership
+    pub owners: Vec<String>,
+    matcher: Regex,
+}
+
+/// Rules of a CODEOWNERS file in file order
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<OwnershipRule>,
+}
+
+impl CodeOwners {
+    /// Parse a CODEOWNERS file, skipping comments, blank lines and invalid patterns
+    pub fn parse(content: &str) -> Self {
+        let rules = content.lines()
+            .filter_map(|line| {
+                let line = line.split_once('#').map_or(line, |(rule, _)| rule).trim();
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?;
+                let Some(matcher) = pattern_regex(pattern) else {
+                    log::warn!("Ignoring CODEOWNERS pattern '{}' that cannot be matched", pattern);
+                    return None;
+                };
+                Some(OwnershipRule {
+                    pattern: pattern.to_string(),
+                    owners: fields.map(str::to_string).collect(),
+                    matcher,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Index of the rule owning a path, the last one whose pattern matches
+    pub fn rule_index(&self, path: &str) -> Option<usize> {
+        self.rules.iter().rposition(|rule| rule.matcher.is_match(path))
+    }
+}
+
+/// Translate a CODEOWNERS (gitignore style) pattern into an anchored regular expression
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let directory = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let glob = trimmed.trim_start_matches('/');
+    if glob.is_empty() {
+        return None;
+    }
+
+    let mut expression = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    expression.push_str("(?:.*/)?");
+                } else {
+                    expression.push_str(".*");
+                }
+            }
+            '*' => expression.push_str("[^/]*"),
+            '?' => expression.push_str("[^/]"),
+            c => expression.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    // `docs/*` owns the files directly in docs; other patterns also own everything below a matching directory
+    expression.push_str(if directory {
+        "/.*$"
+    } else if glob.ends_with('*') && !glob.ends_with("**") {
+        "$"
+    } else {
+        "(?:/.*)?$"
+    });
+    Regex::new(&expression).ok()
+}
+
+/// Letters and digits of a name in lower case, used to compare owners with authors
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Whether an owner names a team rather than a person
+fn is_team(owner: &str) -> bool {
+    owner.starts_with('@') && owner.contains('/')
+}
+
+/// The comparable name of a personal owner, `None` for teams
+fn owner_name(owner: &str) -> Option<String> {
+    if is_team(owner) {
+        None
+    } else if let Some(handle) = owner.strip_prefix('@') {
+        Some(normalize(handle))
+    } else {
+        Some(normalize(owner.split('@').next().unwrap_or(owner)))
+    }
+}
+
+/// Changed lines per file and author
+#[derive(Debug, Clone, Default)]
+pub struct FileAuthors {
+    files: HashMap<String, HashMap<String, usize>>,
+}
+
+impl FileAuthors {
+    /// Attribute changed lines in a file to an author
+    pub fn record(&mut self, author: &str, path: &str, lines_changed: usize) {
+        *self.files.entry(path.to_string()).or_default().entry(author.to_string()).or_default() += lines_changed;
+    }
+}
+
+/// How declared owners and actual committers compare for a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OverlapStatus {
+    /// The declared owners made less than the threshold share of the changes
+    Diverged,
+    /// No rule assigns an owner to the files
+    Unowned,
+    /// Only teams own the files, so the owners cannot be matched to authors
+    Unverified,
+    /// The declared owners made most of the changes
+    Aligned,
+}
+
+impl OverlapStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverlapStatus::Diverged => "diverged",
+            OverlapStatus::Unowned => "unowned",
+            OverlapStatus::Unverified => "unverified",
+            OverlapStatus::Aligned => "aligned",
+        }
+    }
+}
+
+/// Declared owners compared with the committers of the files a rule owns
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleOverlap {
+    /// Rule pattern, or `UNOWNED_LABEL` for files without an owner
+    pub pattern: String,
+    pub owners: Vec<String>,
+    /// Changed files the rule owns
+    pub files: usize,
+    pub lines_changed: usize,
+    /// Lines changed by the declared owners
+    pub owner_lines: usize,
+    /// Author with the most changed lines and their line count
+    pub top_committer: Option<(String, usize)>,
+    pub status: OverlapStatus,
+}
+
+impl RuleOverlap {
+    /// Percentage of the changed lines made by the declared owners (0-100)
+    pub fn owner_share(&self) -> f64 {
+        if self.lines_changed == 0 {
+            0.0
+        } else {
+            self.owner_lines as f64 / self.lines_changed as f64 * 100.0
+        }
+    }
+}
+
+/// Compare each rule that owns changed files with the files' committers, diverged rules first
+pub fn ownership_overlap(codeowners: &CodeOwners, authors: &FileAuthors) -> Vec<RuleOverlap> {
+    let mut grouped: HashMap<Option<usize>, (usize, HashMap<&str, usize>)> = HashMap::new();
+    for (path, lines) in &authors.files {
+        let group = grouped.entry(codeowners.rule_index(path)).or_default();
+        group.0 += 1;
+        for (author, changed) in lines {
+            *group.1.entry(author.as_str()).or_default() += changed;
+        }
+    }
+
+    let mut overlaps: Vec<RuleOverlap> = grouped.into_iter()
+        .map(|(index, (files, lines))| {
+            let rule = index.map(|index| &codeowners.rules[index]);
+            let owners = rule.map(|rule| rule.owners.clone()).unwrap_or_default();
+            let names: Vec<String> = owners.iter().filter_map(|owner| owner_name(owner)).collect();
+            let lines_changed = lines.values().sum();
+            let owner_lines = lines.iter()
+                .filter(|(author, _)| names.contains(&normalize(author)))
+                .map(|(_, changed)| changed)
+                .sum();
+            let top_committer = lines.iter()
+                .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(author, changed)| (author.to_string(), *changed));
+
+            let mut overlap = RuleOverlap {
+                pattern: rule.map_or_else(|| UNOWNED_LABEL.to_string(), |rule| rule.pattern.clone()),
+                owners,
+                files,
+                lines_changed,
+                owner_lines,
+                top_committer,
+                status: OverlapStatus::Aligned,
+            };
+            overlap.status = if overlap.owners.is_empty() {
+                OverlapStatus::Unowned
+            } else if names.is_empty() {
+                OverlapStatus::Unverified
+            } else if overlap.owner_share() < OWNER_SHARE_THRESHOLD {
+                OverlapStatus::Diverged
+            } else {
+                OverlapStatus::Aligned
+            };
+            overlap
+        })
+        .collect();
+
+    overlaps.sort_by(|a, b| {
+        a.status.cmp(&b.status)
+            .then_with(|| b.lines_changed.cmp(&a.lines_changed))
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+    overlaps
+}
+
+impl HeapSize for CodeOwners {
+    fn heap_size(&self) -> usize {
+        self.rules.capacity() * std::mem::size_of::<OwnershipRule>()
+            + self.rules.iter().map(|rule| rule.pattern.heap_size() + rule.owners.heap_size()).sum::<usize>()
+    }
+}
+
+impl HeapSize for FileAuthors {
+    fn heap_size(&self) -> usize {
+        self.files.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*           @alice
+/docs/      @bob docs@example.com
+*.rs        @carol   # Rust code
+/build/*    @org/release
+/vendor/
+";
+
+    #[test]
+    fn test_rule_matching() {
+        let codeowners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(codeowners.rules.len(), 5);
+
+        let pattern = |path: &str| codeowners.rule_index(path).map(|index| codeowners.rules[index].pattern.as_str());
+        assert_eq!(pattern("README.md"), Some("*"));
+        assert_eq!(pattern("docs/guide/intro.md"), Some("/docs/"));
+        assert_eq!(pattern("docs/lib.rs"), Some("*.rs"));
+        assert_eq!(pattern("build/release.sh"), Some("/build/*"));
+        assert_eq!(pattern("build/ci/release.sh"), Some("*"));
+        assert_eq!(pattern("vendor/lib/a.c"), Some("/vendor/"));
+        assert!(codeowners.rules[4].owners.is_empty());
+    }
+
+    #[test]
+    fn test_ownership_overlap() {
+        let codeowners = CodeOwners::parse(CODEOWNERS);
+        let mut authors = FileAuthors::default();
+        authors.record("Alice", "README.md", 10);
+        authors.record("Dave", "src/main.rs", 30);
+        authors.record("Carol", "src/lib.rs", 10);
+        authors.record("Docs", "docs/index.md", 5);
+        authors.record("Dave", "build/release.sh", 5);
+        authors.record("Dave", "vendor/lib/a.c", 50);
+
+        let overlaps = ownership_overlap(&codeowners, &authors);
+        let statuses: Vec<(&str, OverlapStatus)> = overlaps.iter().map(|o| (o.pattern.as_str(), o.status)).collect();
+        assert_eq!(statuses, vec![
+            ("*.rs", OverlapStatus::Diverged),
+            ("/vendor/", OverlapStatus::Unowned),
+            ("/build/*", OverlapStatus::Unverified),
+            ("*", OverlapStatus::Aligned),
+            ("/docs/", OverlapStatus::Aligned),
+        ]);
+
+        let rust = &overlaps[0];
+        assert_eq!((rust.files, rust.lines_changed, rust.owner_lines), (2, 40, 10));
+        assert_eq!(rust.owner_share(), 25.0);
+        assert_eq!(rust.top_committer, Some(("Dave".to_string(), 30)));
+    }
+}
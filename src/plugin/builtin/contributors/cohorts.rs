@@ -0,0 +1,126 @@
+//! Contributor Retention Cohorts
+//!
+//! Groups contributors into cohorts by the quarter of their first commit and
+//! counts how many of each cohort commit again in every following quarter, the
+//! retention chart community managers use to see whether newcomers stay. Column
+//! `Q+0` is the cohort's size and `Q+n` the number of its members active `n`
+//! quarters later; quarters after the last scanned commit are left empty.
+
+use crate::plugin::builtin::utils::time_buckets::BucketPeriod;
+use crate::plugin::data_export::{Matrix, Value};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Contributors who first committed in one quarter and their later activity
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cohort {
+    /// First day of the quarter the cohort joined in
+    pub start: NaiveDate,
+    /// Members active in each quarter from the joining quarter to the last scanned quarter
+    pub retained: Vec<usize>,
+}
+
+impl Cohort {
+    /// Number of contributors who joined in the quarter
+    pub fn size(&self) -> usize {
+        self.retained.first().copied().unwrap_or(0)
+    }
+
+    /// Quarter label, e.g. `2024-Q1`
+    pub fn label(&self) -> String {
+        BucketPeriod::Quarter.label(self.start)
+    }
+}
+
+/// Build retention cohorts from `(author, timestamp)` pairs, oldest cohort first
+pub fn retention_cohorts(commits: &[(String, i64)]) -> Vec<Cohort> {
+    let period = BucketPeriod::Quarter;
+    let mut active: HashMap<&str, BTreeSet<NaiveDate>> = HashMap::new();
+    for (author, timestamp) in commits {
+        if let Some(start) = period.start_of_timestamp(*timestamp) {
+            active.entry(author.as_str()).or_default().insert(start);
+        }
+    }
+    let Some(last) = active.values().filter_map(|quarters| quarters.last()).max().copied() else {
+        return Vec::new();
+    };
+
+    let mut members: BTreeMap<NaiveDate, Vec<&BTreeSet<NaiveDate>>> = BTreeMap::new();
+    for quarters in active.values() {
+        if let Some(first) = quarters.first() {
+            members.entry(*first).or_default().push(quarters);
+        }
+    }
+
+    members.into_iter()
+        .map(|(start, members)| {
+            let mut retained = Vec::new();
+            let mut quarter = start;
+            while quarter <= last {
+                retained.push(members.iter().filter(|quarters| quarters.contains(&quarter)).count());
+                quarter = period.next(quarter);
+            }
+            Cohort { start, retained }
+        })
+        .collect()
+}
+
+/// Cohorts as a matrix of cohort quarters (rows) by quarters since joining (columns)
+pub fn cohort_matrix(cohorts: &[Cohort]) -> Matrix {
+    let width = cohorts.iter().map(|cohort| cohort.retained.len()).max().unwrap_or(0);
+    let mut matrix = Matrix::new(
+        "Cohort",
+        cohorts.iter().map(Cohort::label).collect(),
+        (0..width).map(|offset| format!("Q+{}", offset)).collect(),
+    );
+    for (row, cohort) in cohorts.iter().enumerate() {
+        for (column, count) in cohort.retained.iter().enumerate() {
+            matrix.set(row, column, Value::Integer(*count as i64));
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> i64 {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    fn commits() -> Vec<(String, i64)> {
+        vec![
+            ("alice".to_string(), at("2024-01-03")),
+            ("bob".to_string(), at("2024-02-20")),
+            ("alice".to_string(), at("2024-04-05")),
+            ("carol".to_string(), at("2024-05-09")),
+            ("bob".to_string(), at("2024-08-01")),
+            ("carol".to_string(), at("2024-07-15")),
+        ]
+    }
+
+    #[test]
+    fn test_cohorts_by_quarter_of_first_commit() {
+        let cohorts = retention_cohorts(&commits());
+        let labels: Vec<String> = cohorts.iter().map(Cohort::label).collect();
+        assert_eq!(labels, vec!["2024-Q1", "2024-Q2"]);
+
+        assert_eq!(cohorts[0].size(), 2);
+        assert_eq!(cohorts[0].retained, vec![2, 1, 1]);
+        assert_eq!(cohorts[1].retained, vec![1, 1]);
+        assert!(retention_cohorts(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_cohort_matrix_leaves_unobserved_quarters_empty() {
+        let matrix = cohort_matrix(&retention_cohorts(&commits()));
+        assert_eq!(matrix.column_labels, vec!["Q+0", "Q+1", "Q+2"]);
+        assert_eq!(matrix.cells[1], vec![Value::Integer(1), Value::Integer(1), Value::Null]);
+    }
+}
@@ -0,0 +1,251 @@
+//! First Response to New Files
+//!
+//! Measures how quickly other people start working on the files an author
+//! creates: for each file added in the scanned history, the days from its
+//! creation until a second author first changes it. Summarised per creator and
+//! per directory, it shows whose new code others pick up and where new files
+//! stay with the person who wrote them.
+//!
+//! A file's creation is its earliest addition in the scanned history and its
+//! creator the author of that commit; files added before the scanned range are
+//! left out. Files no other author has changed yet count as created but not
+//! responded to, and do not enter the response times.
+
+use super::ownership::{directory_key, DEFAULT_DIRECTORY_DEPTH, REPOSITORY_LABEL};
+use crate::plugin::memory::HeapSize;
+use std::collections::{BTreeMap, HashMap};
+
+const DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// Response to one new file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileResponse {
+    pub path: String,
+    pub creator: String,
+    /// Timestamp of the commit adding the file
+    pub created: i64,
+    /// First other author to change the file, and when
+    pub responder: Option<(String, i64)>,
+}
+
+impl FileResponse {
+    /// Days from creation to the first change by another author
+    pub fn days(&self) -> Option<f64> {
+        self.responder.as_ref().map(|(_, timestamp)| (timestamp - self.created) as f64 / DAY)
+    }
+}
+
+/// Response to the new files of one creator or directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseSummary {
+    pub label: String,
+    /// Files created
+    pub files: usize,
+    /// Days until the second author, for each file another author has changed
+    pub response_days: Vec<f64>,
+}
+
+impl ResponseSummary {
+    fn new(label: String, responses: &[&FileResponse]) -> Self {
+        let mut response_days: Vec<f64> = responses.iter().filter_map(|response| response.days()).collect();
+        response_days.sort_by(f64::total_cmp);
+        Self {
+            label,
+            files: responses.len(),
+            response_days,
+        }
+    }
+
+    /// Files another author has changed
+    pub fn responded(&self) -> usize {
+        self.response_days.len()
+    }
+
+    /// Percentage of the files another author has changed (0-100)
+    pub fn response_rate(&self) -> f64 {
+        if self.files == 0 {
+            return 0.0;
+        }
+        self.responded() as f64 / self.files as f64 * 100.0
+    }
+
+    /// Average days until the second author, if any file was responded to
+    pub fn average_days(&self) -> Option<f64> {
+        if self.response_days.is_empty() {
+            return None;
+        }
+        Some(self.response_days.iter().sum::<f64>() / self.response_days.len() as f64)
+    }
+
+    /// Median days until the second author, if any file was responded to
+    pub fn median_days(&self) -> Option<f64> {
+        let days = &self.response_days;
+        match days.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(days[n / 2]),
+            n => Some((days[n / 2 - 1] + days[n / 2]) / 2.0),
+        }
+    }
+}
+
+/// Joins the commits adding files with the authors changing them afterwards
+#[derive(Debug, Clone)]
+pub struct FirstResponseTracker {
+    depth: usize,
+    /// Author and timestamp of each commit, by hash
+    commits: HashMap<String, (String, i64)>,
+    /// Earliest addition of each file, as commit timestamp and hash
+    additions: HashMap<String, (i64, String)>,
+    /// Earliest change of each file by each author
+    changes: HashMap<String, HashMap<String, i64>>,
+}
+
+impl Default for FirstResponseTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIRECTORY_DEPTH)
+    }
+}
+
+impl FirstResponseTracker {
+    /// Create a tracker grouping files by their first `depth` directory components
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            commits: HashMap::new(),
+            additions: HashMap::new(),
+            changes: HashMap::new(),
+        }
+    }
+
+    /// Record a commit and the files it changed
+    pub fn record_commit<'a>(&mut self, hash: &str, author: &str, timestamp: i64, paths: impl IntoIterator<Item = &'a str>) {
+        self.commits.insert(hash.to_string(), (author.to_string(), timestamp));
+        for path in paths {
+            let first = self.changes.entry(path.to_string()).or_default().entry(author.to_string()).or_insert(timestamp);
+            *first = (*first).min(timestamp);
+        }
+    }
+
+    /// Record that a commit added a file
+    pub fn record_addition(&mut self, path: &str, hash: &str, timestamp: i64) {
+        let addition = (timestamp, hash.to_string());
+        match self.additions.get_mut(path) {
+            Some(earliest) if *earliest <= addition => {}
+            Some(earliest) => *earliest = addition,
+            None => {
+                self.additions.insert(path.to_string(), addition);
+            }
+        }
+    }
+
+    /// Directory grouping depth
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Response to each file created by a commit in the scan, sorted by path
+    pub fn responses(&self) -> Vec<FileResponse> {
+        let mut responses: Vec<FileResponse> = self.additions.iter()
+            .filter_map(|(path, (created, hash))| {
+                // Commits excluded by the scan's filters have no author to credit
+                let (creator, _) = self.commits.get(hash)?;
+                let responder = self.changes.get(path).and_then(|authors| {
+                    authors.iter()
+                        .filter(|(author, timestamp)| *author != creator && **timestamp >= *created)
+                        .min_by(|a, b| a.1.cmp(b.1).then(a.0.cmp(b.0)))
+                        .map(|(author, timestamp)| (author.clone(), *timestamp))
+                });
+                Some(FileResponse { path: path.clone(), creator: creator.clone(), created: *created, responder })
+            })
+            .collect();
+        responses.sort_by(|a, b| a.path.cmp(&b.path));
+        responses
+    }
+
+    /// Response per creator, most files created first
+    pub fn by_creator(&self) -> Vec<ResponseSummary> {
+        let responses = self.responses();
+        let mut creators: BTreeMap<&str, Vec<&FileResponse>> = BTreeMap::new();
+        for response in &responses {
+            creators.entry(response.creator.as_str()).or_default().push(response);
+        }
+        let mut summaries: Vec<ResponseSummary> = creators.into_iter()
+            .map(|(creator, responses)| ResponseSummary::new(creator.to_string(), &responses))
+            .collect();
+        summaries.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.label.cmp(&b.label)));
+        summaries
+    }
+
+    /// Response for the whole repository, then per directory at the grouping depth, sorted by directory
+    pub fn by_directory(&self) -> Vec<ResponseSummary> {
+        let responses = self.responses();
+        let mut directories: BTreeMap<String, Vec<&FileResponse>> = BTreeMap::new();
+        for response in &responses {
+            directories.entry(directory_key(&response.path, self.depth)).or_default().push(response);
+        }
+        let overall = ResponseSummary::new(REPOSITORY_LABEL.to_string(), &responses.iter().collect::<Vec<_>>());
+        std::iter::once(overall)
+            .chain(directories.into_iter().map(|(directory, responses)| ResponseSummary::new(directory, &responses)))
+            .collect()
+    }
+}
+
+impl HeapSize for FirstResponseTracker {
+    fn heap_size(&self) -> usize {
+        self.commits.heap_size() + self.additions.heap_size() + self.changes.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T0: i64 = 1_700_000_000;
+    const DAY_SECONDS: i64 = 86_400;
+
+    fn tracker() -> FirstResponseTracker {
+        let mut tracker = FirstResponseTracker::new(1);
+        // Arrival order does not matter: the latest commits come first
+        tracker.record_commit("c4", "alice", T0 + 10 * DAY_SECONDS, ["src/lib.rs"]);
+        tracker.record_commit("c3", "carol", T0 + 4 * DAY_SECONDS, ["src/lib.rs", "docs/guide.md"]);
+        tracker.record_commit("c2", "bob", T0 + 2 * DAY_SECONDS, ["src/lib.rs", "src/main.rs"]);
+        tracker.record_addition("src/main.rs", "c2", T0 + 2 * DAY_SECONDS);
+        tracker.record_commit("c1", "alice", T0, ["src/lib.rs", "docs/guide.md"]);
+        tracker.record_addition("src/lib.rs", "c1", T0);
+        tracker.record_addition("docs/guide.md", "c1", T0);
+        // Added by a commit the scan filtered out
+        tracker.record_addition("vendor/dep.rs", "c0", T0 - DAY_SECONDS);
+        tracker
+    }
+
+    #[test]
+    fn test_second_author_after_creation() {
+        let responses = tracker().responses();
+        let summary: Vec<(&str, &str, Option<f64>)> = responses.iter()
+            .map(|response| (response.path.as_str(), response.creator.as_str(), response.days()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("docs/guide.md", "alice", Some(4.0)),
+            ("src/lib.rs", "alice", Some(2.0)),
+            ("src/main.rs", "bob", None),
+        ]);
+        assert_eq!(responses[1].responder, Some(("bob".to_string(), T0 + 2 * DAY_SECONDS)));
+    }
+
+    #[test]
+    fn test_summaries_by_creator_and_directory() {
+        let tracker = tracker();
+        let creators = tracker.by_creator();
+        assert_eq!(creators[0].label, "alice");
+        assert_eq!((creators[0].files, creators[0].responded()), (2, 2));
+        assert_eq!((creators[0].average_days(), creators[0].median_days()), (Some(3.0), Some(3.0)));
+        assert_eq!((creators[1].label.as_str(), creators[1].average_days(), creators[1].response_rate()), ("bob", None, 0.0));
+
+        let directories = tracker.by_directory();
+        let labels: Vec<&str> = directories.iter().map(|summary| summary.label.as_str()).collect();
+        assert_eq!(labels, vec![REPOSITORY_LABEL, "docs", "src"]);
+        assert_eq!(directories[0].files, 3);
+        assert_eq!(directories[2].response_rate(), 50.0);
+        assert!(FirstResponseTracker::default().responses().is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! Directory Freshness
+//!
+//! Reports how recently each directory has been worked on: the date of its last
+//! change, the number of commits touching it in the last 90 days, and the share
+//! of its files nobody has changed for a year. Freshness is measured against a
+//! reference time, normally now or the end of the scanned date range, so old
+//! history can be assessed as it stood at the time.
+//!
+//! Files deleted after their last change are left out, so a directory is not
+//! judged stale on the strength of code that no longer exists.
+
+use super::ownership::{directory_key, DEFAULT_DIRECTORY_DEPTH, REPOSITORY_LABEL};
+use chrono::{TimeZone, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::plugin::memory::{no_heap, HeapSize};
+
+/// Window in which changes count as recent
+pub const RECENT_DAYS: i64 = 90;
+
+/// Age after which an unchanged file counts as stale
+pub const STALE_DAYS: i64 = 365;
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// Freshness summary for a single directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryFreshness {
+    pub directory: String,
+    /// Timestamp of the latest commit touching the directory
+    pub last_changed: i64,
+    /// Commits touching the directory within [`RECENT_DAYS`] of the reference time
+    pub recent_changes: usize,
+    /// Files in the directory that still exist
+    pub files: usize,
+    /// Files unchanged for more than [`STALE_DAYS`]
+    pub stale_files: usize,
+}
+
+impl DirectoryFreshness {
+    /// Percentage of the directory's files that are stale (0-100)
+    pub fn stale_share(&self) -> f64 {
+        if self.files == 0 {
+            return 0.0;
+        }
+        self.stale_files as f64 / self.files as f64 * 100.0
+    }
+
+    /// Date of the last change as an ISO date (YYYY-MM-DD)
+    pub fn last_changed_date(&self) -> String {
+        match Utc.timestamp_opt(self.last_changed, 0) {
+            chrono::LocalResult::Single(dt) => dt.format("%Y-%m-%d").to_string(),
+            _ => self.last_changed.to_string(),
+        }
+    }
+
+    /// Last path component, used to label tree nodes
+    pub fn name(&self) -> &str {
+        self.directory.rsplit('/').next().unwrap_or(&self.directory)
+    }
+}
+
+/// Directory freshness at each level of the tree, parents before their children
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreshnessNode {
+    pub freshness: DirectoryFreshness,
+    pub children: Vec<FreshnessNode>,
+}
+
+/// Last change and deletion of a file
+#[derive(Debug, Clone, Copy, Default)]
+struct FileActivity {
+    last_changed: i64,
+    deleted_at: Option<i64>,
+}
+
+no_heap!(FileActivity);
+
+impl FileActivity {
+    fn exists(&self) -> bool {
+        self.deleted_at.is_none_or(|deleted| deleted < self.last_changed)
+    }
+}
+
+/// Collects the commits touching each file and directory
+#[derive(Debug, Clone)]
+pub struct FreshnessTracker {
+    depth: usize,
+    files: HashMap<String, FileActivity>,
+    /// Commit timestamps touching each directory, keyed by level and directory
+    commits: HashMap<(usize, String), Vec<i64>>,
+}
+
+impl Default for FreshnessTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIRECTORY_DEPTH)
+    }
+}
+
+impl FreshnessTracker {
+    /// Create a tracker grouping files by their first `depth` directory components
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            files: HashMap::new(),
+            commits: HashMap::new(),
+        }
+    }
+
+    /// Record the files changed by a commit
+    pub fn record<'a>(&mut self, timestamp: i64, paths: impl IntoIterator<Item = &'a str>) {
+        let mut directories = HashSet::new();
+        directories.insert((0, REPOSITORY_LABEL.to_string()));
+        for path in paths {
+            let activity = self.files.entry(path.to_string()).or_default();
+            activity.last_changed = activity.last_changed.max(timestamp);
+            for level in 1..=self.depth {
+                directories.insert((level, directory_key(path, level)));
+            }
+        }
+        for directory in directories {
+            self.commits.entry(directory).or_default().push(timestamp);
+        }
+    }
+
+    /// Record that a commit deleted a file
+    pub fn record_deletion(&mut self, timestamp: i64, path: &str) {
+        let activity = self.files.entry(path.to_string()).or_default();
+        activity.deleted_at = Some(activity.deleted_at.unwrap_or(timestamp).max(timestamp));
+    }
+
+    /// Check whether no commits have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.commits.is_empty()
+    }
+
+    /// Directory grouping depth
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Freshness of each directory at the grouping depth, sorted by directory
+    pub fn directories(&self, now: i64) -> Vec<DirectoryFreshness> {
+        self.level(self.depth, now).into_values().collect()
+    }
+
+    /// Freshness of the whole repository
+    pub fn overall(&self, now: i64) -> DirectoryFreshness {
+        self.level(0, now).into_values().next().unwrap_or_else(|| DirectoryFreshness {
+            directory: REPOSITORY_LABEL.to_string(),
+            last_changed: 0,
+            recent_changes: 0,
+            files: 0,
+            stale_files: 0,
+        })
+    }
+
+    /// Freshness of the repository and every directory down to the grouping depth
+    pub fn tree(&self, now: i64) -> FreshnessNode {
+        let levels: Vec<BTreeMap<String, DirectoryFreshness>> = (1..=self.depth).map(|level| self.level(level, now)).collect();
+        FreshnessNode {
+            freshness: self.overall(now),
+            children: children(&levels, 1, None),
+        }
+    }
+
+    /// Freshness of every directory at one level, where level 0 is the whole repository
+    fn level(&self, level: usize, now: i64) -> BTreeMap<String, DirectoryFreshness> {
+        let key = |path: &str| if level == 0 { REPOSITORY_LABEL.to_string() } else { directory_key(path, level) };
+        let recent_since = now - RECENT_DAYS * DAY;
+        let stale_before = now - STALE_DAYS * DAY;
+
+        let mut directories: BTreeMap<String, DirectoryFreshness> = BTreeMap::new();
+        for ((commit_level, directory), timestamps) in &self.commits {
+            if *commit_level != level {
+                continue;
+            }
+            directories.insert(directory.clone(), DirectoryFreshness {
+                directory: directory.clone(),
+                last_changed: timestamps.iter().copied().max().unwrap_or_default(),
+                recent_changes: timestamps.iter().filter(|ts| **ts >= recent_since && **ts <= now).count(),
+                files: 0,
+                stale_files: 0,
+            });
+        }
+        for (path, activity) in &self.files {
+            if !activity.exists() {
+                continue;
+            }
+            if let Some(directory) = directories.get_mut(&key(path)) {
+                directory.files += 1;
+                if activity.last_changed < stale_before {
+                    directory.stale_files += 1;
+                }
+            }
+        }
+        directories
+    }
+}
+
+/// Tree nodes for the directories at `level` below `parent`
+///
+/// Shallower directories reappear at deeper levels when they hold files
+/// directly, so only keys with exactly `level` components are taken.
+fn children(levels: &[BTreeMap<String, DirectoryFreshness>], level: usize, parent: Option<&str>) -> Vec<FreshnessNode> {
+    let Some(directories) = levels.get(level - 1) else {
+        return Vec::new();
+    };
+    directories
+        .values()
+        .filter(|freshness| {
+            let components = freshness.directory.split('/').count();
+            let under_parent = match parent {
+                Some(parent) => freshness.directory.strip_prefix(parent).is_some_and(|rest| rest.starts_with('/')),
+                None => true,
+            };
+            components == level && under_parent
+        })
+        .map(|freshness| FreshnessNode {
+            freshness: freshness.clone(),
+            children: children(levels, level + 1, Some(&freshness.directory)),
+        })
+        .collect()
+}
+
+impl HeapSize for FreshnessTracker {
+    fn heap_size(&self) -> usize {
+        self.files.heap_size() + self.commits.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-06-01 00:00 UTC
+    const NOW: i64 = 1_717_200_000;
+
+    #[test]
+    fn test_directory_freshness() {
+        let mut tracker = FreshnessTracker::new(1);
+        tracker.record(NOW - 400 * DAY, ["src/old.rs", "src/gone.rs", "docs/guide.md"]);
+        tracker.record(NOW - 10 * DAY, ["src/new.rs"]);
+        tracker.record(NOW - 5 * DAY, ["src/new.rs", "README.md"]);
+        tracker.record(NOW - 200 * DAY, ["src/gone.rs"]);
+        tracker.record_deletion(NOW - 200 * DAY, "src/gone.rs");
+
+        let directories = tracker.directories(NOW);
+        let names: Vec<&str> = directories.iter().map(|d| d.directory.as_str()).collect();
+        assert_eq!(names, vec![".", "docs", "src"]);
+
+        let src = &directories[2];
+        assert_eq!(src.last_changed, NOW - 5 * DAY);
+        assert_eq!(src.recent_changes, 2);
+        // The deleted file is not counted
+        assert_eq!((src.files, src.stale_files), (2, 1));
+        assert_eq!(src.stale_share(), 50.0);
+
+        let docs = &directories[1];
+        assert_eq!((docs.recent_changes, docs.stale_files, docs.files), (0, 1, 1));
+        assert_eq!(docs.last_changed_date(), "2023-04-28");
+
+        let overall = tracker.overall(NOW);
+        assert_eq!(overall.recent_changes, 2);
+        assert_eq!((overall.files, overall.stale_files), (4, 2));
+    }
+
+    #[test]
+    fn test_freshness_tree() {
+        let mut tracker = FreshnessTracker::new(2);
+        tracker.record(NOW - DAY, ["src/main.rs", "src/plugin/mod.rs", "src/scanner/mod.rs", "build.rs"]);
+
+        let tree = tracker.tree(NOW);
+        assert_eq!(tree.freshness.directory, REPOSITORY_LABEL);
+        let top: Vec<&str> = tree.children.iter().map(|n| n.freshness.directory.as_str()).collect();
+        assert_eq!(top, vec![".", "src"]);
+
+        let src = &tree.children[1];
+        assert_eq!(src.freshness.files, 3);
+        let nested: Vec<&str> = src.children.iter().map(|n| n.freshness.name()).collect();
+        assert_eq!(nested, vec!["plugin", "scanner"]);
+    }
+}
@@ -0,0 +1,1866 @@
+//! Contributors Analysis Plugin
+//!
+//! Built-in plugin for analyzing who owns which parts of a repository:
+//! per-directory ownership percentages, bus factor, active-contributor
+//! trends over time, contributor retention cohorts, how recently each
+//! directory has changed, how soon other authors join the files someone
+//! creates, and how well the repository's CODEOWNERS file matches the
+//! people who change each path.
+
+pub mod codeowners;
+pub mod cohorts;
+pub mod first_response;
+pub mod freshness;
+pub mod ownership;
+pub mod trends;
+
+use crate::plugin::{
+    Plugin, PluginInfo, PluginContext, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, PluginDataRequirements, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
+};
+use crate::plugin::data_export::{
+    PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
+    DataPayload, Row, Value, ExportHints, ExportFormat, TreeNode
+};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader, ChangeType};
+use crate::scanner::query::DateRange;
+use crate::notifications::AsyncNotificationManager;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use serde_json::json;
+use self::codeowners::{ownership_overlap, CodeOwners, FileAuthors, OverlapStatus, RuleOverlap, CODEOWNERS_LOCATIONS, OWNER_SHARE_THRESHOLD};
+use self::cohorts::{cohort_matrix, retention_cohorts};
+use self::first_response::{FirstResponseTracker, ResponseSummary};
+use self::freshness::{DirectoryFreshness, FreshnessNode, FreshnessTracker, RECENT_DAYS, STALE_DAYS};
+use self::ownership::{DirectoryOwnership, OwnershipTracker, DEFAULT_DIRECTORY_DEPTH};
+use self::trends::{contributor_trends, TREND_PERIODS};
+use crate::plugin::builtin::utils::time_buckets::{BucketPeriod, SeriesEntry, SeriesMode};
+use crate::plugin::memory::HeapSize;
+
+/// Default number of owners listed per directory in the ownership export
+const DEFAULT_TOP_OWNERS: usize = 3;
+
+/// Statistics for contributors plugin operation
+#[derive(Debug, Default, Clone)]
+struct ContributorsStats {
+    /// Author and timestamp of each commit for trend analysis
+    commits: Vec<(String, i64)>,
+    /// Changed lines per directory and author
+    ownership: OwnershipTracker,
+    /// Commit recency per directory
+    freshness: FreshnessTracker,
+    /// Files added and the authors changing them afterwards
+    first_response: FirstResponseTracker,
+    /// Changed lines per file and author, compared with CODEOWNERS
+    file_authors: FileAuthors,
+    /// CODEOWNERS file found in the scanned tree, with its index in `CODEOWNERS_LOCATIONS`
+    codeowners: Option<(usize, CodeOwners)>,
+}
+
+impl HeapSize for ContributorsStats {
+    fn heap_size(&self) -> usize {
+        self.commits.heap_size() + self.ownership.heap_size() + self.freshness.heap_size()
+            + self.first_response.heap_size() + self.file_authors.heap_size() + self.codeowners.heap_size()
+    }
+}
+
+/// Per-scan data for contributors plugin
+#[derive(Debug)]
+struct ContributorsScanData {
+    /// Statistics for this scan
+    stats: ContributorsStats,
+}
+
+impl ContributorsScanData {
+    fn new(directory_depth: usize) -> Self {
+        Self {
+            stats: ContributorsStats {
+                commits: Vec::new(),
+                ownership: OwnershipTracker::new(directory_depth),
+                freshness: FreshnessTracker::new(directory_depth),
+                first_response: FirstResponseTracker::new(directory_depth),
+                file_authors: FileAuthors::default(),
+                codeowners: None,
+            },
+        }
+    }
+}
+
+/// Contributors analysis plugin
+pub struct ContributorsPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+
+    /// Per-scan contributor data
+    scan_data: Arc<RwLock<HashMap<String, ContributorsScanData>>>,
+
+    /// Number of leading path components used to group files into directories
+    directory_depth: usize,
+
+    /// Number of owners listed per directory in the ownership export
+    top_owners: usize,
+
+    /// Calendar period used for active-contributor trends
+    trend_period: BucketPeriod,
+
+    /// Whether empty trend periods are listed or collapsed into gaps
+    series_mode: SeriesMode,
+
+    /// Whether the freshness export is also published as a directory tree
+    freshness_tree: bool,
+
+    /// Effective commit date range of the scan, reported in exports
+    date_range: Option<DateRange>,
+
+    consuming: Arc<RwLock<bool>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
+
+    /// Notification publishing - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl ContributorsPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "contributors".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Analyzes code ownership, bus factor and contributor activity trends".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Processing,
+        )
+        .with_capability(
+            "ownership".to_string(),
+            "Calculates per-directory ownership percentages".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "bus_factor".to_string(),
+            "Calculates the bus factor per directory and for the repository".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "contributor_trends".to_string(),
+            "Tracks active and new contributors over time".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "retention_cohorts".to_string(),
+            "Tracks how many contributors of each joining quarter stay active".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "freshness".to_string(),
+            "Reports recent changes and stale code per directory".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "first_response".to_string(),
+            "Measures how soon other authors change the files each author creates".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "codeowners".to_string(),
+            "Compares CODEOWNERS declarations with the authors changing each path".to_string(),
+            "1.0.0".to_string(),
+        );
+
+        Self {
+            command_name: "contributors".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            scan_data: Arc::new(RwLock::new(HashMap::new())),
+            directory_depth: DEFAULT_DIRECTORY_DEPTH,
+            top_owners: DEFAULT_TOP_OWNERS,
+            trend_period: BucketPeriod::Month,
+            series_mode: SeriesMode::Dense,
+            freshness_tree: false,
+            date_range: None,
+            consuming: Arc::new(RwLock::new(false)),
+            consumer: Arc::new(RwLock::new(None)),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new contributors plugin with all required dependencies (REQUIRED)
+    /// This is the correct way to instantiate ContributorsPlugin - it MUST have notification manager
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Process a commit message and attribute its changed files to the author
+    async fn process_commit(&self, scan_id: &str, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::CommitInfo { hash, author, timestamp, changed_files, .. } = &message.data {
+            let mut scan_data = self.scan_data.write().await;
+            let data = scan_data.entry(scan_id.to_string())
+                .or_insert_with(|| ContributorsScanData::new(self.directory_depth));
+
+            data.stats.commits.push((author.clone(), *timestamp));
+            for file in changed_files {
+                data.stats.ownership.record(author, &file.path, file.lines_added + file.lines_removed);
+                data.stats.file_authors.record(author, &file.path, file.lines_added + file.lines_removed);
+            }
+            data.stats.freshness.record(*timestamp, changed_files.iter().map(|file| file.path.as_str()));
+            data.stats.first_response.record_commit(hash, author, *timestamp, changed_files.iter().map(|file| file.path.as_str()));
+        }
+        Ok(())
+    }
+
+    /// Process a file change message and record added files and deleted files, including the old path of a rename
+    async fn process_file_change(&self, scan_id: &str, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::FileChange { path, change_type, old_path, commit_hash, commit_timestamp, .. } = &message.data {
+            if *change_type == ChangeType::Added {
+                let mut scan_data = self.scan_data.write().await;
+                let data = scan_data.entry(scan_id.to_string())
+                    .or_insert_with(|| ContributorsScanData::new(self.directory_depth));
+                data.stats.first_response.record_addition(path, commit_hash, *commit_timestamp);
+            }
+
+            let deleted = match change_type {
+                ChangeType::Deleted => Some(path),
+                ChangeType::Renamed => old_path.as_ref(),
+                _ => None,
+            };
+            if let Some(deleted) = deleted {
+                let mut scan_data = self.scan_data.write().await;
+                let data = scan_data.entry(scan_id.to_string())
+                    .or_insert_with(|| ContributorsScanData::new(self.directory_depth));
+                data.stats.freshness.record_deletion(*commit_timestamp, deleted);
+            }
+        }
+        Ok(())
+    }
+
+    /// Process a file content message, keeping the CODEOWNERS file GitHub would use
+    async fn process_file_content(&self, scan_id: &str, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::FileContent { path, content, .. } = &message.data {
+            let Some(location) = CODEOWNERS_LOCATIONS.iter().position(|location| location == path) else {
+                return Ok(());
+            };
+
+            let mut scan_data = self.scan_data.write().await;
+            let data = scan_data.entry(scan_id.to_string())
+                .or_insert_with(|| ContributorsScanData::new(self.directory_depth));
+            if data.stats.codeowners.as_ref().is_none_or(|(current, _)| location < *current) {
+                data.stats.codeowners = Some((location, CodeOwners::parse(content)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate contributor summary statistics
+    async fn generate_summary(&self) -> PluginResult<ScanMessage> {
+        let (author_count, commit_count, directory_count) = {
+            let scan_data = self.scan_data.read().await;
+            let mut authors = std::collections::HashSet::new();
+            let mut commits = 0;
+            let mut directories = 0;
+
+            for data in scan_data.values() {
+                commits += data.stats.commits.len();
+                authors.extend(data.stats.commits.iter().map(|(author, _)| author.clone()));
+                directories += data.stats.ownership.directories().len();
+            }
+
+            (authors.len(), commits, directories)
+        };
+
+        let data = MessageData::MetricInfo {
+            file_count: directory_count as u32,
+            line_count: commit_count as u64,
+            complexity: author_count as f64,
+        };
+
+        let header = MessageHeader::new(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            "plugin-generated".to_string(),
+        );
+
+        Ok(ScanMessage::new(header, data))
+    }
+
+    /// Schema metadata describing the effective date range (`since`/`until`)
+    fn date_range_metadata(&self) -> HashMap<String, String> {
+        self.date_range.as_ref().map(DateRange::to_metadata).unwrap_or_default()
+    }
+
+    /// Description suffix naming the effective date range, empty for all history
+    fn date_range_suffix(&self) -> String {
+        self.date_range.as_ref()
+            .map(|range| format!(" ({})", range.describe()))
+            .unwrap_or_default()
+    }
+
+    /// Export hints shared by all contributor exports
+    fn export_hints(&self) -> ExportHints {
+        ExportHints {
+            preferred_formats: vec![
+                ExportFormat::Console,
+                ExportFormat::Json,
+                ExportFormat::Csv,
+                ExportFormat::Html,
+                ExportFormat::Markdown,
+            ],
+            sort_by: None,
+            sort_ascending: true,
+            limit: None,
+            include_totals: false,
+            include_row_numbers: false,
+            custom_hints: HashMap::new(),
+        }
+    }
+
+    /// Create PluginDataExport with ownership percentages of the top owners of each directory
+    async fn create_ownership_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let ownership = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats.ownership.clone()
+        };
+
+        if ownership.is_empty() {
+            return None;
+        }
+
+        let directories = ownership.directories();
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Directory", ColumnType::String),
+                ColumnDef::new("Author", ColumnType::String),
+                ColumnDef::new("Lines Changed", ColumnType::Integer)
+                    .with_description("Lines added and removed by the author in the directory"),
+                ColumnDef::new("Ownership", ColumnType::Float)
+                    .with_description("Author's share of the directory's changed lines")
+                    .with_format_hint("percentage"),
+            ],
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("directory_depth".to_string(), ownership.depth().to_string());
+                meta
+            },
+        };
+
+        let rows: Vec<Row> = directories
+            .iter()
+            .flat_map(|directory| {
+                directory.owners.iter().take(self.top_owners).map(move |owner| Row::new(vec![
+                    Value::String(directory.directory.clone()),
+                    Value::String(owner.author.clone()),
+                    Value::Integer(owner.lines as i64),
+                    Value::Float(owner.percentage),
+                ]))
+            })
+            .collect();
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Directory Ownership".to_string(),
+            description: Some(format!(
+                "Top {} owners of {} directories (depth {}) by changed lines in scan {}{}",
+                self.top_owners, directories.len(), ownership.depth(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Create PluginDataExport with the bus factor of the repository and each directory
+    async fn create_bus_factor_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let ownership = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats.ownership.clone()
+        };
+
+        if ownership.is_empty() {
+            return None;
+        }
+
+        let overall = ownership.overall();
+        let directories = ownership.directories();
+        let at_risk = directories.iter().filter(|directory| directory.bus_factor == 1).count();
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Directory", ColumnType::String),
+                ColumnDef::new("Bus Factor", ColumnType::Integer)
+                    .with_description("Fewest authors accounting for more than half of the changes"),
+                ColumnDef::new("Contributors", ColumnType::Integer),
+                ColumnDef::new("Top Owner", ColumnType::String),
+                ColumnDef::new("Top Share", ColumnType::Float)
+                    .with_format_hint("percentage"),
+                ColumnDef::new("Lines Changed", ColumnType::Integer),
+            ],
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("directory_depth".to_string(), ownership.depth().to_string());
+                meta
+            },
+        };
+
+        let rows: Vec<Row> = std::iter::once(&overall)
+            .chain(directories.iter())
+            .map(bus_factor_row)
+            .collect();
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Bus Factor".to_string(),
+            description: Some(format!(
+                "Repository bus factor {}; {} of {} directories depend on a single author in scan {}{}",
+                overall.bus_factor, at_risk, directories.len(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Create PluginDataExport with active and new contributors per period
+    async fn create_trends_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let trends = {
+            let scan_data_guard = self.scan_data.read().await;
+            contributor_trends(&scan_data_guard.get(scan_id)?.stats.commits, self.trend_period, self.series_mode)
+        };
+
+        if trends.is_empty() {
+            return None;
+        }
+
+        let sparse = self.series_mode == SeriesMode::Sparse;
+        let mut columns = vec![
+            ColumnDef::new("Period", ColumnType::String),
+            ColumnDef::new("Active Contributors", ColumnType::Integer)
+                .with_description("Distinct authors with commits in the period"),
+            ColumnDef::new("New Contributors", ColumnType::Integer)
+                .with_description("Authors whose first commit falls in the period"),
+            ColumnDef::new("Commits", ColumnType::Integer),
+        ];
+        if sparse {
+            columns.push(ColumnDef::new("Empty Periods", ColumnType::Integer)
+                .with_description("Consecutive periods without commits collapsed into this row"));
+        }
+
+        let schema = DataSchema {
+            columns,
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("period".to_string(), self.trend_period.as_str().to_string());
+                meta.insert("series".to_string(), self.series_mode.as_str().to_string());
+                meta
+            },
+        };
+
+        let rows: Vec<Row> = trends
+            .iter()
+            .map(|entry| {
+                let activity = entry.value().cloned().unwrap_or_default();
+                let mut values = vec![
+                    Value::String(entry.label().to_string()),
+                    Value::Integer(activity.active as i64),
+                    Value::Integer(activity.new as i64),
+                    Value::Integer(activity.commits as i64),
+                ];
+                if sparse {
+                    values.push(Value::Integer(entry.gap_periods() as i64));
+                }
+                Row::new(values)
+            })
+            .collect();
+
+        let peak = trends.iter().filter_map(|entry| entry.value()).map(|activity| activity.active).max().unwrap_or(0);
+        let periods: usize = trends.iter().map(|entry| entry.gap_periods().max(1)).sum();
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Active Contributors".to_string(),
+            description: Some(format!(
+                "Active contributors per {} over {} periods (peak {}) in scan {}{}",
+                self.trend_period.as_str(), periods, peak, scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Create PluginDataExport with the retention of contributors grouped by the quarter of their first commit
+    async fn create_cohorts_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let cohorts = {
+            let scan_data_guard = self.scan_data.read().await;
+            retention_cohorts(&scan_data_guard.get(scan_id)?.stats.commits)
+        };
+
+        if cohorts.is_empty() {
+            return None;
+        }
+
+        let contributors: usize = cohorts.iter().map(|cohort| cohort.size()).sum();
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Contributor Retention".to_string(),
+            description: Some(format!(
+                "Contributors active in each quarter after the quarter of their first commit (Q+0 is the cohort size): \
+                 {} contributors in {} cohorts in scan {}{}",
+                contributors, cohorts.len(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Matrix,
+            schema: DataSchema {
+                columns: Vec::new(),
+                metadata: {
+                    let mut meta = self.date_range_metadata();
+                    meta.insert("period".to_string(), BucketPeriod::Quarter.as_str().to_string());
+                    meta
+                },
+            },
+            data: DataPayload::Matrix(Arc::new(cohort_matrix(&cohorts))),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Reference time for freshness: the end of the scanned date range, or now
+    fn freshness_reference(&self) -> i64 {
+        self.date_range.as_ref()
+            .and_then(DateRange::end_seconds)
+            .unwrap_or_else(crate::clock::now_seconds)
+    }
+
+    /// Schema metadata describing how freshness was measured
+    fn freshness_metadata(&self, depth: usize, now: i64) -> HashMap<String, String> {
+        let mut meta = self.date_range_metadata();
+        meta.insert("directory_depth".to_string(), depth.to_string());
+        meta.insert("recent_days".to_string(), RECENT_DAYS.to_string());
+        meta.insert("stale_days".to_string(), STALE_DAYS.to_string());
+        meta.insert("reference_time".to_string(), now.to_string());
+        meta
+    }
+
+    /// Create PluginDataExport with the last change, recent changes and stale share of each directory
+    async fn create_freshness_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let freshness = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats.freshness.clone()
+        };
+
+        if freshness.is_empty() {
+            return None;
+        }
+
+        let now = self.freshness_reference();
+        let overall = freshness.overall(now);
+        let directories = freshness.directories(now);
+        let stale_directories = directories.iter().filter(|directory| directory.recent_changes == 0).count();
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Directory", ColumnType::String),
+                ColumnDef::new("Last Change", ColumnType::String)
+                    .with_description("Date of the latest commit touching the directory"),
+                ColumnDef::new("Recent Changes", ColumnType::Integer)
+                    .with_description(format!("Commits in the last {} days", RECENT_DAYS)),
+                ColumnDef::new("Files", ColumnType::Integer),
+                ColumnDef::new("Stale Files", ColumnType::Integer)
+                    .with_description(format!("Files unchanged for more than {} days", STALE_DAYS)),
+                ColumnDef::new("Stale Share", ColumnType::Float)
+                    .with_format_hint("percentage"),
+            ],
+            metadata: self.freshness_metadata(freshness.depth(), now),
+        };
+
+        let rows: Vec<Row> = std::iter::once(&overall)
+            .chain(directories.iter())
+            .map(freshness_row)
+            .collect();
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Directory Freshness".to_string(),
+            description: Some(format!(
+                "{:.0}% of files unchanged for a year; {} of {} directories without changes in {} days in scan {}{}",
+                overall.stale_share(), stale_directories, directories.len(), RECENT_DAYS, scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Create PluginDataExport with directory freshness as a tree, when `--tree` is given
+    async fn create_freshness_tree_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        if !self.freshness_tree {
+            return None;
+        }
+
+        let freshness = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats.freshness.clone()
+        };
+
+        if freshness.is_empty() {
+            return None;
+        }
+
+        let now = self.freshness_reference();
+        let root = freshness_tree_node(&freshness.tree(now));
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Directory Freshness Tree".to_string(),
+            description: Some(format!(
+                "Directory freshness to depth {} in scan {}{}",
+                freshness.depth(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Hierarchical,
+            schema: DataSchema {
+                columns: Vec::new(),
+                metadata: self.freshness_metadata(freshness.depth(), now),
+            },
+            data: DataPayload::Tree(Arc::new(root)),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Schema of the first-response exports, grouped by `group` (creator or directory)
+    fn first_response_schema(&self, group: &str, depth: usize) -> DataSchema {
+        DataSchema {
+            columns: vec![
+                ColumnDef::new(group, ColumnType::String),
+                ColumnDef::new("Files Created", ColumnType::Integer),
+                ColumnDef::new("Responded", ColumnType::Integer)
+                    .with_description("New files another author has since changed"),
+                ColumnDef::new("Response Rate", ColumnType::Float)
+                    .with_format_hint("percentage"),
+                ColumnDef::new("Average Days", ColumnType::Float)
+                    .with_description("Average days from creation until another author first changed the file"),
+                ColumnDef::new("Median Days", ColumnType::Float),
+            ],
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("directory_depth".to_string(), depth.to_string());
+                meta
+            },
+        }
+    }
+
+    /// Create PluginDataExport with how soon other authors change the files each author creates
+    async fn create_first_response_by_creator_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let tracker = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats.first_response.clone()
+        };
+
+        let creators = tracker.by_creator();
+        if creators.is_empty() {
+            return None;
+        }
+
+        let unanswered = creators.iter().filter(|creator| creator.responded() == 0).count();
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "First Response by Creator".to_string(),
+            description: Some(format!(
+                "Days until a second author changes new files; {} of {} creators have no new file changed by others in scan {}{}",
+                unanswered, creators.len(), scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema: self.first_response_schema("Creator", tracker.depth()),
+            data: DataPayload::Rows(Arc::new(creators.iter().map(first_response_row).collect())),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Create PluginDataExport with how soon a second author changes new files in each directory
+    async fn create_first_response_by_directory_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let tracker = {
+            let scan_data_guard = self.scan_data.read().await;
+            scan_data_guard.get(scan_id)?.stats.first_response.clone()
+        };
+
+        let directories = tracker.by_directory();
+        let overall = directories.first().filter(|overall| overall.files > 0)?;
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "First Response by Directory".to_string(),
+            description: Some(format!(
+                "{:.0}% of {} new files changed by a second author, after {} days on average in scan {}{}",
+                overall.response_rate(), overall.files,
+                overall.average_days().map_or_else(|| "-".to_string(), |days| format!("{:.1}", days)),
+                scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema: self.first_response_schema("Directory", tracker.depth()),
+            data: DataPayload::Rows(Arc::new(directories.iter().map(first_response_row).collect())),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// CODEOWNERS location and its rules compared with the committers of each path, when the scan found the file
+    async fn codeowners_overlap(&self, scan_id: &str) -> Option<(&'static str, Vec<RuleOverlap>)> {
+        let scan_data_guard = self.scan_data.read().await;
+        let stats = &scan_data_guard.get(scan_id)?.stats;
+        let (location, codeowners) = stats.codeowners.as_ref()?;
+        Some((CODEOWNERS_LOCATIONS[*location], ownership_overlap(codeowners, &stats.file_authors)))
+    }
+
+    /// Create PluginDataExport comparing the owners declared in CODEOWNERS with the authors changing their paths
+    async fn create_codeowners_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let (location, overlaps) = self.codeowners_overlap(scan_id).await?;
+
+        if overlaps.is_empty() {
+            return None;
+        }
+
+        let diverged = overlaps.iter().filter(|overlap| overlap.status == OverlapStatus::Diverged).count();
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Pattern", ColumnType::String),
+                ColumnDef::new("Owners", ColumnType::String),
+                ColumnDef::new("Files", ColumnType::Integer)
+                    .with_description("Changed files the pattern owns"),
+                ColumnDef::new("Lines Changed", ColumnType::Integer),
+                ColumnDef::new("Owner Share", ColumnType::Float)
+                    .with_description("Share of the changed lines made by the declared owners")
+                    .with_format_hint("percentage"),
+                ColumnDef::new("Top Committer", ColumnType::String),
+                ColumnDef::new("Status", ColumnType::String)
+                    .with_description(format!(
+                        "diverged when the owners made less than {:.0}% of the changes; unverified when only teams own the path",
+                        OWNER_SHARE_THRESHOLD
+                    )),
+            ],
+            metadata: {
+                let mut meta = self.date_range_metadata();
+                meta.insert("codeowners_file".to_string(), location.to_string());
+                meta
+            },
+        };
+
+        Some(PluginDataExport {
+            plugin_id: "contributors".to_string(),
+            title: "Code Owner Overlap".to_string(),
+            description: Some(format!(
+                "{} of {} {} patterns owning changed files have diverged from their committers in scan {}{}",
+                diverged, overlaps.len(), location, scan_id, self.date_range_suffix()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(overlaps.iter().map(codeowners_row).collect())),
+            export_hints: self.export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Publish all contributor exports that have data
+    async fn publish_exports(&self, scan_id: &str) {
+        let exports = [
+            self.create_ownership_export(scan_id).await,
+            self.create_bus_factor_export(scan_id).await,
+            self.create_trends_export(scan_id).await,
+            self.create_cohorts_export(scan_id).await,
+            self.create_freshness_export(scan_id).await,
+            self.create_freshness_tree_export(scan_id).await,
+            self.create_first_response_by_creator_export(scan_id).await,
+            self.create_first_response_by_directory_export(scan_id).await,
+            self.create_codeowners_export(scan_id).await,
+        ];
+
+        for export_data in exports.into_iter().flatten() {
+            let title = export_data.title.clone();
+            let event = PluginEvent::DataReady {
+                plugin_id: "contributors".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish {} DataReady event: {}", title, e);
+            } else {
+                log::debug!("Published {} DataReady event for contributors plugin", title);
+            }
+        }
+    }
+
+    /// Ownership trackers for all active scans
+    async fn ownership_trackers(&self) -> Vec<OwnershipTracker> {
+        let scan_data = self.scan_data.read().await;
+        scan_data.values().map(|data| data.stats.ownership.clone()).collect()
+    }
+
+    /// Execute ownership analysis function
+    async fn execute_ownership_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let directories: Vec<DirectoryOwnership> = self.ownership_trackers().await
+            .iter()
+            .flat_map(|tracker| tracker.directories())
+            .collect();
+
+        let data = json!({
+            "total_directories": directories.len(),
+            "directory_depth": self.directory_depth,
+            "directories": directories.iter().map(|directory| {
+                json!({
+                    "directory": directory.directory,
+                    "lines_changed": directory.lines_changed,
+                    "bus_factor": directory.bus_factor,
+                    "owners": directory.owners.iter().take(self.top_owners).map(|owner| {
+                        json!({
+                            "author": owner.author,
+                            "lines": owner.lines,
+                            "percentage": owner.percentage,
+                        })
+                    }).collect::<Vec<_>>(),
+                })
+            }).collect::<Vec<_>>(),
+            "function": "contributors"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "ownership_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: directories.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute bus factor analysis function
+    async fn execute_bus_factor_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let trackers = self.ownership_trackers().await;
+        let repository_bus_factor = trackers.iter()
+            .map(|tracker| tracker.overall().bus_factor)
+            .max()
+            .unwrap_or(0);
+        let directories: Vec<DirectoryOwnership> = trackers.iter()
+            .flat_map(|tracker| tracker.directories())
+            .collect();
+        let at_risk: Vec<_> = directories.iter()
+            .filter(|directory| directory.bus_factor == 1)
+            .map(|directory| {
+                json!({
+                    "directory": directory.directory,
+                    "owner": directory.top_owner().map(|owner| owner.author.clone()),
+                    "share": directory.top_owner().map(|owner| owner.percentage),
+                })
+            })
+            .collect();
+
+        let data = json!({
+            "repository_bus_factor": repository_bus_factor,
+            "total_directories": directories.len(),
+            "single_owner_directories": at_risk,
+            "bus_factors": directories.iter()
+                .map(|directory| (directory.directory.clone(), directory.bus_factor))
+                .collect::<HashMap<_, _>>(),
+            "function": "bus-factor"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "bus_factor_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: directories.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute directory freshness analysis function
+    async fn execute_freshness_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let now = self.freshness_reference();
+        let directories: Vec<DirectoryFreshness> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values()
+                .flat_map(|data| data.stats.freshness.directories(now))
+                .collect()
+        };
+
+        let data = json!({
+            "directory_depth": self.directory_depth,
+            "recent_days": RECENT_DAYS,
+            "stale_days": STALE_DAYS,
+            "total_directories": directories.len(),
+            "directories": directories.iter().map(|directory| {
+                json!({
+                    "directory": directory.directory,
+                    "last_change": directory.last_changed_date(),
+                    "recent_changes": directory.recent_changes,
+                    "files": directory.files,
+                    "stale_files": directory.stale_files,
+                    "stale_share": directory.stale_share(),
+                })
+            }).collect::<Vec<_>>(),
+            "function": "freshness"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "freshness_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: directories.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute first-response analysis function
+    async fn execute_first_response_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let trackers: Vec<FirstResponseTracker> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values().map(|data| data.stats.first_response.clone()).collect()
+        };
+        let creators: Vec<ResponseSummary> = trackers.iter().flat_map(|tracker| tracker.by_creator()).collect();
+        let directories: Vec<ResponseSummary> = trackers.iter().flat_map(|tracker| tracker.by_directory()).collect();
+        let summary_json = |summary: &ResponseSummary| json!({
+            "files_created": summary.files,
+            "responded": summary.responded(),
+            "response_rate": summary.response_rate(),
+            "average_days": summary.average_days(),
+            "median_days": summary.median_days(),
+        });
+
+        let data = json!({
+            "directory_depth": self.directory_depth,
+            "total_files": creators.iter().map(|creator| creator.files).sum::<usize>(),
+            "creators": creators.iter().map(|creator| {
+                let mut entry = summary_json(creator);
+                entry["creator"] = json!(creator.label);
+                entry
+            }).collect::<Vec<_>>(),
+            "directories": directories.iter().map(|directory| {
+                let mut entry = summary_json(directory);
+                entry["directory"] = json!(directory.label);
+                entry
+            }).collect::<Vec<_>>(),
+            "function": "first-response"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "first_response_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: creators.iter().map(|creator| creator.files).sum::<usize>() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute CODEOWNERS overlap analysis function
+    async fn execute_codeowners_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let scan_ids: Vec<String> = self.scan_data.read().await.keys().cloned().collect();
+        let mut location = None;
+        let mut overlaps = Vec::new();
+        for scan_id in &scan_ids {
+            if let Some((file, scan_overlaps)) = self.codeowners_overlap(scan_id).await {
+                location = Some(file);
+                overlaps.extend(scan_overlaps);
+            }
+        }
+
+        let data = json!({
+            "codeowners_file": location,
+            "owner_share_threshold": OWNER_SHARE_THRESHOLD,
+            "total_patterns": overlaps.len(),
+            "diverged_patterns": overlaps.iter().filter(|overlap| overlap.status == OverlapStatus::Diverged).count(),
+            "patterns": overlaps.iter().map(|overlap| {
+                json!({
+                    "pattern": overlap.pattern,
+                    "owners": overlap.owners,
+                    "files": overlap.files,
+                    "lines_changed": overlap.lines_changed,
+                    "owner_share": overlap.owner_share(),
+                    "top_committer": overlap.top_committer.as_ref().map(|(author, _)| author),
+                    "status": overlap.status.as_str(),
+                })
+            }).collect::<Vec<_>>(),
+            "function": "codeowners"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "codeowners_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: overlaps.iter().map(|overlap| overlap.files).sum::<usize>() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute active-contributor trend analysis function
+    async fn execute_trend_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let commits: Vec<(String, i64)> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values()
+                .flat_map(|data| data.stats.commits.iter().cloned())
+                .collect()
+        };
+        let trends = contributor_trends(&commits, self.trend_period, self.series_mode);
+
+        let data = json!({
+            "period": self.trend_period.as_str(),
+            "series": self.series_mode.as_str(),
+            "total_periods": trends.iter().map(|entry| entry.gap_periods().max(1)).sum::<usize>(),
+            "trends": trends.iter().map(|entry| match entry {
+                SeriesEntry::Bucket { label, value, .. } => json!({
+                    "period": label,
+                    "active": value.active,
+                    "new": value.new,
+                    "commits": value.commits,
+                }),
+                SeriesEntry::Gap(gap) => json!({
+                    "period": gap.label,
+                    "gap": true,
+                    "empty_periods": gap.periods,
+                }),
+            }).collect::<Vec<_>>(),
+            "function": "trends"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "trend_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: commits.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute contributor retention cohort analysis function
+    async fn execute_cohort_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let commits: Vec<(String, i64)> = {
+            let scan_data = self.scan_data.read().await;
+            scan_data.values()
+                .flat_map(|data| data.stats.commits.iter().cloned())
+                .collect()
+        };
+        let cohorts = retention_cohorts(&commits);
+
+        let data = json!({
+            "period": BucketPeriod::Quarter.as_str(),
+            "total_cohorts": cohorts.len(),
+            "cohorts": cohorts.iter().map(|cohort| {
+                json!({
+                    "cohort": cohort.label(),
+                    "size": cohort.size(),
+                    "retained": cohort.retained,
+                })
+            }).collect::<Vec<_>>(),
+            "function": "cohorts"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "cohort_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: commits.len() as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+}
+
+/// Bus factor export row for a directory (or the whole repository)
+fn bus_factor_row(directory: &DirectoryOwnership) -> Row {
+    let top_owner = directory.top_owner();
+    Row::new(vec![
+        Value::String(directory.directory.clone()),
+        Value::Integer(directory.bus_factor as i64),
+        Value::Integer(directory.owners.len() as i64),
+        top_owner.map(|owner| Value::String(owner.author.clone())).unwrap_or(Value::Null),
+        top_owner.map(|owner| Value::Float(owner.percentage)).unwrap_or(Value::Null),
+        Value::Integer(directory.lines_changed as i64),
+    ])
+}
+
+/// Freshness export row for a directory (or the whole repository)
+fn freshness_row(directory: &DirectoryFreshness) -> Row {
+    Row::new(vec![
+        Value::String(directory.directory.clone()),
+        Value::String(directory.last_changed_date()),
+        Value::Integer(directory.recent_changes as i64),
+        Value::Integer(directory.files as i64),
+        Value::Integer(directory.stale_files as i64),
+        Value::Float(directory.stale_share()),
+    ])
+}
+
+/// First-response export row for a creator or directory
+fn first_response_row(summary: &ResponseSummary) -> Row {
+    Row::new(vec![
+        Value::String(summary.label.clone()),
+        Value::Integer(summary.files as i64),
+        Value::Integer(summary.responded() as i64),
+        Value::Float(summary.response_rate()),
+        summary.average_days().map_or(Value::Null, Value::Float),
+        summary.median_days().map_or(Value::Null, Value::Float),
+    ])
+}
+
+fn codeowners_row(overlap: &RuleOverlap) -> Row {
+    Row::new(vec![
+        Value::String(overlap.pattern.clone()),
+        Value::String(overlap.owners.join(" ")),
+        Value::Integer(overlap.files as i64),
+        Value::Integer(overlap.lines_changed as i64),
+        Value::Float(overlap.owner_share()),
+        overlap.top_committer.as_ref().map_or(Value::Null, |(author, _)| Value::String(author.clone())),
+        Value::String(overlap.status.as_str().to_string()),
+    ])
+}
+
+/// Freshness tree node labelled by directory name, with a summary value and the figures as metadata
+fn freshness_tree_node(node: &FreshnessNode) -> TreeNode {
+    let freshness = &node.freshness;
+    let summary = format!(
+        "last change {}, {} recent changes, {:.0}% stale",
+        freshness.last_changed_date(), freshness.recent_changes, freshness.stale_share()
+    );
+    let mut tree = TreeNode::new(freshness.name()).with_value(Value::String(summary));
+    tree.metadata = Some(HashMap::from([
+        ("directory".to_string(), freshness.directory.clone()),
+        ("last_change".to_string(), freshness.last_changed_date()),
+        ("recent_changes".to_string(), freshness.recent_changes.to_string()),
+        ("files".to_string(), freshness.files.to_string()),
+        ("stale_files".to_string(), freshness.stale_files.to_string()),
+    ]));
+    node.children.iter().fold(tree, |tree, child| tree.add_child(freshness_tree_node(child)))
+}
+
+impl Default for ContributorsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for ContributorsPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
+        if self.initialized {
+            return Ok(());
+        }
+
+        {
+            let mut scan_data = self.scan_data.write().await;
+            scan_data.clear();
+        }
+
+        // Record the effective date range so exports can report it
+        self.date_range = context.query_params.date_range.clone();
+
+        self.initialized = true;
+
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        if !self.initialized {
+            return Err(PluginError::invalid_state("Plugin not initialized"));
+        }
+
+        match request {
+            PluginRequest::Execute { invocation_type, .. } => {
+                let function_name = match invocation_type {
+                    crate::plugin::InvocationType::Function(ref func) => func.as_str(),
+                    crate::plugin::InvocationType::Direct => self.default_function().unwrap_or("contributors"),
+                    crate::plugin::InvocationType::Default => "contributors",
+                };
+
+                match function_name {
+                    "contributors" | "ownership" | "owners" => {
+                        self.execute_ownership_analysis().await
+                    }
+                    "bus-factor" | "busfactor" | "bus" => {
+                        self.execute_bus_factor_analysis().await
+                    }
+                    "trends" | "activity" => {
+                        self.execute_trend_analysis().await
+                    }
+                    "freshness" | "recency" | "stale" => {
+                        self.execute_freshness_analysis().await
+                    }
+                    "cohorts" | "retention" => {
+                        self.execute_cohort_analysis().await
+                    }
+                    "first-response" | "response" => {
+                        self.execute_first_response_analysis().await
+                    }
+                    "codeowners" | "code-owners" => {
+                        self.execute_codeowners_analysis().await
+                    }
+                    _ => Err(PluginError::execution_failed(
+                        format!("Unknown function: {}", function_name)
+                    )),
+                }
+            }
+            PluginRequest::GetStatistics => {
+                let summary = self.generate_summary().await?;
+                Ok(PluginResponse::Statistics(summary))
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        if *self.consuming.read().await {
+            self.stop_consuming().await?;
+        }
+
+        self.initialized = false;
+        {
+            let mut scan_data = self.scan_data.write().await;
+            scan_data.clear();
+        }
+        Ok(())
+    }
+
+    /// Get all functions this plugin can handle
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "contributors".to_string(),
+                aliases: vec!["ownership".to_string(), "owners".to_string()],
+                description: "Per-directory ownership percentages by author".to_string(),
+                is_default: true,
+            },
+            PluginFunction {
+                name: "bus-factor".to_string(),
+                aliases: vec!["busfactor".to_string(), "bus".to_string()],
+                description: "Fewest authors covering most changes, per directory".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "trends".to_string(),
+                aliases: vec!["activity".to_string()],
+                description: "Active and new contributors over time".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "cohorts".to_string(),
+                aliases: vec!["retention".to_string()],
+                description: "Retention of contributors by quarter of first commit".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "freshness".to_string(),
+                aliases: vec!["recency".to_string(), "stale".to_string()],
+                description: "Last change, recent changes and stale code per directory".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "first-response".to_string(),
+                aliases: vec!["response".to_string()],
+                description: "Days until a second author changes new files, per creator and directory".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "codeowners".to_string(),
+                aliases: vec!["code-owners".to_string()],
+                description: "Paths whose committers diverge from their CODEOWNERS owners".to_string(),
+                is_default: false,
+            },
+        ]
+    }
+
+    /// Get the default function name
+    fn default_function(&self) -> Option<&str> {
+        Some("contributors")
+    }
+
+    /// Cast to ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Cast to mutable ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for ContributorsPlugin {
+    async fn publish(&self, event: PluginEvent) -> crate::notifications::NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+/// Data requirements implementation for ContributorsPlugin
+/// This plugin only needs commit metadata and per-file line counts
+impl PluginDataRequirements for ContributorsPlugin {
+    fn requires_current_file_content(&self) -> bool {
+        false // Ownership is derived from commit line counts, not file content
+    }
+
+    fn requires_historical_file_content(&self) -> bool {
+        false
+    }
+
+    fn required_files(&self) -> Vec<String> {
+        CODEOWNERS_LOCATIONS.iter().map(|location| location.to_string()).collect()
+    }
+
+    fn preferred_buffer_size(&self) -> usize {
+        4096 // Small buffer, only CODEOWNERS is read
+    }
+
+    fn max_file_size(&self) -> Option<usize> {
+        None // Only CODEOWNERS is read, and it is small
+    }
+
+    fn handles_binary_files(&self) -> bool {
+        false // CODEOWNERS is text
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for ContributorsPlugin {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if *consuming {
+            return Err(PluginError::invalid_state("Already consuming"));
+        }
+
+        *consuming = true;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = Some(consumer);
+        }
+
+        log::info!("Contributors plugin started consuming messages");
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        let scan_id = message.header().scan_id.as_str();
+        match &message.data {
+            MessageData::CommitInfo { .. } => self.process_commit(scan_id, &message).await?,
+            MessageData::FileChange { .. } => self.process_file_change(scan_id, &message).await?,
+            MessageData::FileContent { .. } => self.process_file_content(scan_id, &message).await?,
+            _ => {}
+        }
+
+        consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge message: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        log::debug!("Contributors plugin received queue event: {:?}", event);
+
+        match event {
+            QueueEvent::ScanStarted { scan_id, .. } => {
+                log::info!("Contributors plugin: scan started for {}", scan_id);
+
+                let mut scan_data = self.scan_data.write().await;
+                scan_data.insert(scan_id.clone(), ContributorsScanData::new(self.directory_depth));
+            }
+            QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
+                let (commit_count, directory_count) = {
+                    let scan_data = self.scan_data.read().await;
+                    scan_data.get(scan_id)
+                        .map(|data| (data.stats.commits.len(), data.stats.ownership.directories().len()))
+                        .unwrap_or((0, 0))
+                };
+                log::info!(
+                    "Contributors plugin: scan {} complete - processed {} commits across {} directories (total {} messages)",
+                    scan_id, commit_count, directory_count, total_messages
+                );
+
+                self.publish_exports(scan_id).await;
+            }
+            _ => {
+                // Other events are just logged
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if !*consuming {
+            return Ok(()); // Already stopped
+        }
+
+        *consuming = false;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = None;
+        }
+
+        log::info!("Contributors plugin stopped consuming messages");
+        Ok(())
+    }
+
+    async fn state_memory(&self) -> usize {
+        self.scan_data.read().await.values().map(|data| data.stats.heap_size()).sum()
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages: false, // Commits, file changes for deletions, and the CODEOWNERS file
+            interested_message_types: vec!["CommitInfo".to_string(), "FileChange".to_string(), "FileContent".to_string()],
+            high_frequency_capable: true,
+            preferred_batch_size: 10,
+            requires_ordered_delivery: false, // Trends are bucketed by timestamp, not arrival order
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_secs(1)),
+        }
+    }
+}
+
+/// Modern clap-based argument parsing implementation for contributors plugin
+#[async_trait]
+impl PluginClapParser for ContributorsPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Analyzes code ownership, bus factor and contributor trends"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        use clap::Arg;
+
+        command
+            .override_usage("contributors [OPTIONS]")
+            .help_template("Usage: {usage}\n\nAnalyzes code ownership, bus factor and contributor trends\n\nOptions:\n{options}\n{after-help}")
+            .after_help("Ownership is measured in lines changed per author within each directory.")
+            .arg(Arg::new("depth")
+                .long("depth")
+                .value_name("N")
+                .help("Number of leading directory components used to group files")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("2"))
+            .arg(Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .help("Number of owners listed per directory")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("3"))
+            .arg(Arg::new("period")
+                .long("period")
+                .value_name("PERIOD")
+                .help("Period for active-contributor trends")
+                .value_parser(TREND_PERIODS)
+                .default_value("month"))
+            .arg(Arg::new("series")
+                .long("series")
+                .value_name("MODE")
+                .help("Trend series layout: dense lists every period, sparse collapses empty periods into gap rows")
+                .value_parser(["dense", "sparse"])
+                .default_value("dense"))
+            .arg(Arg::new("tree")
+                .long("tree")
+                .help("Also report directory freshness as a tree")
+                .action(clap::ArgAction::SetTrue))
+    }
+
+    async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
+        if let Some(depth) = matches.get_one::<usize>("depth") {
+            if *depth == 0 {
+                return Err(PluginError::configuration_error("--depth must be greater than 0"));
+            }
+            self.directory_depth = *depth;
+        }
+
+        if let Some(top) = matches.get_one::<usize>("top") {
+            self.top_owners = *top;
+        }
+
+        if let Some(period) = matches.get_one::<String>("period").and_then(|p| BucketPeriod::parse(p)) {
+            self.trend_period = period;
+        }
+
+        if let Some(mode) = matches.get_one::<String>("series").and_then(|m| SeriesMode::parse(m)) {
+            self.series_mode = mode;
+        }
+
+        self.freshness_tree = matches.get_flag("tree");
+
+        log::debug!(
+            "Contributors plugin configured with depth {}, top {} owners, {} {} trends",
+            self.directory_depth, self.top_owners, self.series_mode.as_str(), self.trend_period.as_str()
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_commit_message(author: &str, timestamp: i64, files: &[(&str, usize)]) -> ScanMessage {
+        let data = MessageData::CommitInfo {
+            hash: format!("{author}-{timestamp}"),
+            author: author.to_string(),
+            message: "Test commit".to_string(),
+            timestamp,
+            committer: author.to_string(),
+            author_timestamp: timestamp,
+            parents: vec![],
+            changed_files: files.iter().map(|(path, lines)| crate::scanner::messages::FileChangeData {
+                path: path.to_string(),
+                lines_added: *lines,
+                lines_removed: 0,
+            }).collect(),
+        };
+
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    fn create_test_context() -> PluginContext {
+        PluginContext::new(
+            Arc::new(crate::scanner::ScannerConfig::default()),
+            Arc::new(crate::scanner::QueryParams::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_contributors_plugin_creation() {
+        let plugin = ContributorsPlugin::new();
+        assert_eq!(plugin.plugin_info().name, "contributors");
+        assert_eq!(plugin.default_function(), Some("contributors"));
+        assert!(!plugin.initialized);
+    }
+
+    #[tokio::test]
+    async fn test_contributors_exports() {
+        let mut plugin = ContributorsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        let messages = [
+            create_test_commit_message("alice", 1_700_000_000, &[("src/lib.rs", 90), ("README.md", 5)]),
+            create_test_commit_message("bob", 1_703_000_000, &[("src/main.rs", 10), ("docs/guide.md", 20)]),
+        ];
+        for message in &messages {
+            plugin.process_commit("scan", message).await.unwrap();
+        }
+
+        let bus_factor = plugin.create_bus_factor_export("scan").await.unwrap();
+        assert_eq!(bus_factor.title, "Bus Factor");
+        if let DataPayload::Rows(rows) = &bus_factor.data {
+            // Repository summary first, then directories by lines changed
+            assert_eq!(rows[0].values[0], Value::String(ownership::REPOSITORY_LABEL.to_string()));
+            assert_eq!(rows[0].values[1], Value::Integer(1));
+            assert_eq!(rows[1].values[0], Value::String("src".to_string()));
+            assert_eq!(rows.len(), 4);
+        } else {
+            panic!("expected row payload");
+        }
+
+        let ownership = plugin.create_ownership_export("scan").await.unwrap();
+        assert_eq!(ownership.schema.columns.len(), 4);
+
+        let trends = plugin.create_trends_export("scan").await.unwrap();
+        assert_eq!(trends.schema.metadata.get("period"), Some(&"month".to_string()));
+
+        let cohorts = plugin.create_cohorts_export("scan").await.unwrap();
+        if let DataPayload::Matrix(matrix) = &cohorts.data {
+            assert_eq!(matrix.row_labels, vec!["2023-Q4"]);
+            assert_eq!(matrix.cells[0], vec![Value::Integer(2)]);
+        } else {
+            panic!("expected matrix payload");
+        }
+
+        assert!(plugin.create_trends_export("missing").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_freshness_exports() {
+        let mut plugin = ContributorsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+        plugin.directory_depth = 1;
+        plugin.freshness_tree = true;
+
+        let now = chrono::Utc::now().timestamp();
+        let messages = [
+            create_test_commit_message("alice", now - 800 * 86_400, &[("src/lib.rs", 90), ("docs/guide.md", 5)]),
+            create_test_commit_message("bob", now - 10 * 86_400, &[("src/main.rs", 10)]),
+        ];
+        for message in &messages {
+            plugin.process_commit("scan", message).await.unwrap();
+        }
+
+        let freshness = plugin.create_freshness_export("scan").await.unwrap();
+        assert_eq!(freshness.title, "Directory Freshness");
+        if let DataPayload::Rows(rows) = &freshness.data {
+            assert_eq!(rows.len(), 3);
+            assert_eq!(rows[1].values[0], Value::String("docs".to_string()));
+            assert_eq!(rows[1].values[5], Value::Float(100.0));
+            assert_eq!(rows[2].values[2], Value::Integer(1));
+            assert_eq!(rows[2].values[5], Value::Float(50.0));
+        } else {
+            panic!("expected row payload");
+        }
+
+        let tree = plugin.create_freshness_tree_export("scan").await.unwrap();
+        if let DataPayload::Tree(root) = &tree.data {
+            assert_eq!(root.label, ownership::REPOSITORY_LABEL);
+            assert_eq!(root.children.len(), 2);
+        } else {
+            panic!("expected tree payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_codeowners_export() {
+        let mut plugin = ContributorsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        let messages = [
+            create_test_commit_message("alice", 1_700_000_000, &[("src/lib.rs", 10)]),
+            create_test_commit_message("bob", 1_703_000_000, &[("src/main.rs", 40), ("docs/guide.md", 20)]),
+        ];
+        for message in &messages {
+            plugin.process_commit("scan", message).await.unwrap();
+        }
+        assert!(plugin.create_codeowners_export("scan").await.is_none());
+
+        let codeowners = |path: &str, content: &str| ScanMessage::new(
+            MessageHeader::new(0, "test-scan".to_string()),
+            MessageData::FileContent { path: path.to_string(), size: content.len() as u64, content: content.to_string(), generated: false },
+        );
+        plugin.process_file_content("scan", &codeowners(".github/CODEOWNERS", "/src/ @alice\n/docs/ @bob\n")).await.unwrap();
+        plugin.process_file_content("scan", &codeowners("CODEOWNERS", "* @carol\n")).await.unwrap();
+
+        let export = plugin.create_codeowners_export("scan").await.unwrap();
+        assert_eq!(export.schema.metadata.get("codeowners_file"), Some(&".github/CODEOWNERS".to_string()));
+        if let DataPayload::Rows(rows) = &export.data {
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].values[0], Value::String("/src/".to_string()));
+            assert_eq!(rows[0].values[4], Value::Float(20.0));
+            assert_eq!(rows[0].values[5], Value::String("bob".to_string()));
+            assert_eq!(rows[0].values[6], Value::String("diverged".to_string()));
+            assert_eq!(rows[1].values[6], Value::String("aligned".to_string()));
+        } else {
+            panic!("expected row payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_response_exports() {
+        let mut plugin = ContributorsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+        plugin.directory_depth = 1;
+
+        let added = |path: &str, author: &str, timestamp: i64| ScanMessage::new(
+            MessageHeader::new(0, "test-scan".to_string()),
+            MessageData::FileChange {
+                path: path.to_string(),
+                change_type: ChangeType::Added,
+                old_path: None,
+                insertions: 10,
+                deletions: 0,
+                is_binary: false,
+                binary_size: None,
+                line_count: None,
+                commit_hash: format!("{author}-{timestamp}"),
+                commit_timestamp: timestamp,
+                checkout_path: None,
+                old_mode: None,
+                new_mode: None,
+                replaced_lines: Vec::new(),
+            },
+        );
+        let messages = [
+            create_test_commit_message("alice", 1_700_000_000, &[("src/lib.rs", 10), ("docs/guide.md", 5)]),
+            create_test_commit_message("bob", 1_700_000_000 + 3 * 86_400, &[("src/lib.rs", 2)]),
+        ];
+        for message in &messages {
+            plugin.process_commit("scan", message).await.unwrap();
+        }
+        plugin.process_file_change("scan", &added("src/lib.rs", "alice", 1_700_000_000)).await.unwrap();
+        plugin.process_file_change("scan", &added("docs/guide.md", "alice", 1_700_000_000)).await.unwrap();
+
+        let creators = plugin.create_first_response_by_creator_export("scan").await.unwrap();
+        if let DataPayload::Rows(rows) = &creators.data {
+            assert_eq!(rows[0].values, vec![
+                Value::String("alice".to_string()),
+                Value::Integer(2),
+                Value::Integer(1),
+                Value::Float(50.0),
+                Value::Float(3.0),
+                Value::Float(3.0),
+            ]);
+        } else {
+            panic!("expected row payload");
+        }
+
+        let directories = plugin.create_first_response_by_directory_export("scan").await.unwrap();
+        if let DataPayload::Rows(rows) = &directories.data {
+            assert_eq!(rows.len(), 3);
+            assert_eq!(rows[1].values[0], Value::String("docs".to_string()));
+            assert_eq!(rows[1].values[4], Value::Null);
+        } else {
+            panic!("expected row payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contributors_execute_functions() {
+        let mut plugin = ContributorsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+        plugin.process_commit("scan", &create_test_commit_message("alice", 1_700_000_000, &[("src/lib.rs", 10)])).await.unwrap();
+
+        let request = PluginRequest::new_with_invocation(
+            "bus-factor".to_string(),
+            crate::plugin::InvocationType::Function("bus-factor".to_string()),
+        );
+        match plugin.execute(request).await.unwrap() {
+            PluginResponse::Execute { data, .. } => {
+                assert_eq!(data["function"], "bus-factor");
+                assert_eq!(data["repository_bus_factor"], 1);
+            }
+            _ => panic!("expected execute response"),
+        }
+    }
+}
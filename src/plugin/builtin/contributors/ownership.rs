@@ -0,0 +1,225 @@
+//! Directory Ownership and Bus Factor
+//!
+//! Attributes changed lines to authors per directory and derives ownership
+//! shares and a bus factor from them. The bus factor is the smallest number of
+//! authors who together account for more than half of a directory's changes,
+//! so a value of 1 means a single person wrote most of that code.
+
+use std::collections::HashMap;
+use crate::plugin::memory::HeapSize;
+
+/// Default number of leading path components used to group files into directories
+pub const DEFAULT_DIRECTORY_DEPTH: usize = 2;
+
+/// Share of changes the bus factor authors must account for
+pub const BUS_FACTOR_THRESHOLD: f64 = 0.5;
+
+/// Directory label for files at the repository root
+pub const ROOT_DIRECTORY: &str = ".";
+
+/// Label for the repository-wide ownership summary
+pub const REPOSITORY_LABEL: &str = "(repository)";
+
+/// One author's share of the changes in a directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorShare {
+    pub author: String,
+    /// Lines changed by the author
+    pub lines: usize,
+    /// Percentage of the directory's changed lines (0-100)
+    pub percentage: f64,
+}
+
+/// Ownership summary for a single directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryOwnership {
+    pub directory: String,
+    /// Total lines changed in the directory
+    pub lines_changed: usize,
+    /// Author shares, largest first
+    pub owners: Vec<AuthorShare>,
+    /// Smallest number of authors covering more than half of the changes
+    pub bus_factor: usize,
+}
+
+impl DirectoryOwnership {
+    fn from_lines(directory: String, lines: &HashMap<String, usize>) -> Self {
+        let lines_changed: usize = lines.values().sum();
+        let mut owners: Vec<AuthorShare> = lines
+            .iter()
+            .map(|(author, lines)| AuthorShare {
+                author: author.clone(),
+                lines: *lines,
+                percentage: if lines_changed > 0 {
+                    *lines as f64 / lines_changed as f64 * 100.0
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+        owners.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.author.cmp(&b.author)));
+
+        let shares: Vec<usize> = owners.iter().map(|owner| owner.lines).collect();
+        Self {
+            directory,
+            lines_changed,
+            bus_factor: bus_factor(&shares),
+            owners,
+        }
+    }
+
+    /// The author with the largest share, if any changes were recorded
+    pub fn top_owner(&self) -> Option<&AuthorShare> {
+        self.owners.first()
+    }
+}
+
+/// Accumulates changed lines per directory and author
+#[derive(Debug, Clone)]
+pub struct OwnershipTracker {
+    depth: usize,
+    directories: HashMap<String, HashMap<String, usize>>,
+    totals: HashMap<String, usize>,
+}
+
+impl Default for OwnershipTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIRECTORY_DEPTH)
+    }
+}
+
+impl OwnershipTracker {
+    /// Create a tracker grouping files by their first `depth` directory components
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            directories: HashMap::new(),
+            totals: HashMap::new(),
+        }
+    }
+
+    /// Directory grouping depth
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Record a file change by an author
+    ///
+    /// Changes without line counts (binary files, renames, mode changes) still
+    /// count as a single line so that touching a file registers as involvement.
+    pub fn record(&mut self, author: &str, path: &str, lines_changed: usize) {
+        let lines = lines_changed.max(1);
+        let directory = directory_key(path, self.depth);
+
+        *self.directories
+            .entry(directory)
+            .or_default()
+            .entry(author.to_string())
+            .or_insert(0) += lines;
+        *self.totals.entry(author.to_string()).or_insert(0) += lines;
+    }
+
+    /// Ownership per directory, most changed first
+    pub fn directories(&self) -> Vec<DirectoryOwnership> {
+        let mut directories: Vec<DirectoryOwnership> = self.directories
+            .iter()
+            .map(|(directory, lines)| DirectoryOwnership::from_lines(directory.clone(), lines))
+            .collect();
+        directories.sort_by(|a, b| {
+            b.lines_changed.cmp(&a.lines_changed).then_with(|| a.directory.cmp(&b.directory))
+        });
+        directories
+    }
+
+    /// Ownership across the whole repository
+    pub fn overall(&self) -> DirectoryOwnership {
+        DirectoryOwnership::from_lines(REPOSITORY_LABEL.to_string(), &self.totals)
+    }
+
+    /// Check whether any changes were recorded
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+}
+
+/// Directory a file is grouped under: its first `depth` parent directory components
+pub fn directory_key(path: &str, depth: usize) -> String {
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    let parents = components.len().saturating_sub(1);
+    if parents == 0 {
+        return ROOT_DIRECTORY.to_string();
+    }
+    components[..parents.min(depth)].join("/")
+}
+
+/// Smallest number of authors whose combined share exceeds [`BUS_FACTOR_THRESHOLD`]
+///
+/// `shares` must be sorted largest first. Returns 0 when there are no changes.
+pub fn bus_factor(shares: &[usize]) -> usize {
+    let total: usize = shares.iter().sum();
+    if total == 0 {
+        return 0;
+    }
+
+    let mut covered = 0;
+    for (index, lines) in shares.iter().enumerate() {
+        covered += lines;
+        if covered as f64 / total as f64 > BUS_FACTOR_THRESHOLD {
+            return index + 1;
+        }
+    }
+    shares.len()
+}
+
+impl HeapSize for OwnershipTracker {
+    fn heap_size(&self) -> usize {
+        self.directories.heap_size() + self.totals.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_key() {
+        assert_eq!(directory_key("README.md", 2), ".");
+        assert_eq!(directory_key("src/main.rs", 2), "src");
+        assert_eq!(directory_key("src/plugin/builtin/mod.rs", 2), "src/plugin");
+        assert_eq!(directory_key("src/plugin/builtin/mod.rs", 1), "src");
+    }
+
+    #[test]
+    fn test_bus_factor() {
+        assert_eq!(bus_factor(&[]), 0);
+        assert_eq!(bus_factor(&[90, 10]), 1);
+        assert_eq!(bus_factor(&[50, 50]), 2);
+        assert_eq!(bus_factor(&[30, 30, 20, 20]), 2);
+        assert_eq!(bus_factor(&[25, 25, 25, 25]), 3);
+    }
+
+    #[test]
+    fn test_ownership_by_directory() {
+        let mut tracker = OwnershipTracker::new(1);
+        tracker.record("alice", "src/lib.rs", 80);
+        tracker.record("bob", "src/main.rs", 20);
+        tracker.record("bob", "docs/guide.md", 10);
+        tracker.record("carol", "docs/image.png", 0);
+
+        let directories = tracker.directories();
+        assert_eq!(directories[0].directory, "src");
+        assert_eq!(directories[0].lines_changed, 100);
+        assert_eq!(directories[0].bus_factor, 1);
+        assert_eq!(directories[0].top_owner().unwrap().author, "alice");
+        assert!((directories[0].owners[0].percentage - 80.0).abs() < f64::EPSILON);
+
+        // Binary changes count as a single line
+        assert_eq!(directories[1].directory, "docs");
+        assert_eq!(directories[1].lines_changed, 11);
+
+        let overall = tracker.overall();
+        assert_eq!(overall.directory, REPOSITORY_LABEL);
+        assert_eq!(overall.lines_changed, 111);
+        assert_eq!(overall.owners.len(), 3);
+    }
+}
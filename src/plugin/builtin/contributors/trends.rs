@@ -0,0 +1,112 @@
+//! Active Contributor Trends
+//!
+//! Counts distinct active authors per calendar period (week, month or quarter)
+//! and how many of them committed for the first time in that period. Periods
+//! without commits between the first and last active period are kept, either as
+//! zero-count periods (dense) or collapsed into gap markers (sparse), so that
+//! declines in activity stay visible.
+
+use crate::plugin::builtin::utils::time_buckets::{bucket_series, BucketPeriod, SeriesEntry, SeriesMode};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashSet};
+
+/// Calendar periods offered for contributor trends
+pub const TREND_PERIODS: [&str; 3] = ["week", "month", "quarter"];
+
+/// Contributor activity within one period
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContributorActivity {
+    /// Distinct authors with at least one commit in the period
+    pub active: usize,
+    /// Authors whose first commit falls in the period
+    pub new: usize,
+    /// Commits in the period
+    pub commits: usize,
+}
+
+/// Build the per-period contributor trend from `(author, timestamp)` pairs
+pub fn contributor_trends(
+    commits: &[(String, i64)],
+    period: BucketPeriod,
+    mode: SeriesMode,
+) -> Vec<SeriesEntry<ContributorActivity>> {
+    let mut buckets: BTreeMap<NaiveDate, (HashSet<&str>, usize)> = BTreeMap::new();
+    for (author, timestamp) in commits {
+        let Some(start) = period.start_of_timestamp(*timestamp) else {
+            continue;
+        };
+        let bucket = buckets.entry(start).or_default();
+        bucket.0.insert(author.as_str());
+        bucket.1 += 1;
+    }
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    let activity = buckets
+        .into_iter()
+        .map(|(start, (authors, commits))| {
+            let new = authors.iter().filter(|author| seen.insert(**author)).count();
+            (start, ContributorActivity { active: authors.len(), new, commits })
+        })
+        .collect();
+
+    bucket_series(activity, period, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> i64 {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    fn commits() -> Vec<(String, i64)> {
+        vec![
+            ("alice".to_string(), at("2024-01-03")),
+            ("bob".to_string(), at("2024-01-20")),
+            ("alice".to_string(), at("2024-03-05")),
+            ("carol".to_string(), at("2024-03-09")),
+        ]
+    }
+
+    fn counts(entry: &SeriesEntry<ContributorActivity>) -> (usize, usize, usize) {
+        let activity = entry.value().unwrap();
+        (activity.active, activity.new, activity.commits)
+    }
+
+    #[test]
+    fn test_monthly_trends_include_gaps() {
+        let trends = contributor_trends(&commits(), BucketPeriod::Month, SeriesMode::Dense);
+        let labels: Vec<&str> = trends.iter().map(|t| t.label()).collect();
+        assert_eq!(labels, vec!["2024-01", "2024-02", "2024-03"]);
+
+        assert_eq!(counts(&trends[0]), (2, 2, 2));
+        assert_eq!(counts(&trends[1]), (0, 0, 0));
+        assert_eq!(counts(&trends[2]), (2, 1, 2));
+    }
+
+    #[test]
+    fn test_sparse_trends_mark_gaps() {
+        let trends = contributor_trends(&commits(), BucketPeriod::Week, SeriesMode::Sparse);
+        let labels: Vec<&str> = trends.iter().map(|t| t.label()).collect();
+        assert_eq!(labels, vec!["2024-01-01", "2024-01-08", "2024-01-15", "2024-01-22..2024-02-26", "2024-03-04"]);
+
+        assert_eq!(trends[1].gap_periods(), 1);
+        assert_eq!(trends[3].gap_periods(), 6);
+        assert_eq!(counts(&trends[4]), (2, 1, 2));
+    }
+
+    #[test]
+    fn test_quarterly_trends() {
+        let trends = contributor_trends(&commits(), BucketPeriod::Quarter, SeriesMode::Dense);
+        assert_eq!(trends.len(), 1);
+        assert_eq!(trends[0].label(), "2024-Q1");
+        assert_eq!(counts(&trends[0]).0, 3);
+        assert!(contributor_trends(&[], BucketPeriod::Week, SeriesMode::Dense).is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+//! Commit Message Language Detection
+//!
+//! Guesses the natural language of a commit message. Messages in a non-Latin
+//! script are identified by the script of their letters (kana for Japanese,
+//! Hangul for Korean, and so on). Latin-script messages are scored against a
+//! small profile of frequent character trigrams per language, taken from common
+//! words and commit vocabulary ("fix", "hinzugefügt", "corrigé"). Each trigram
+//! weighs less the more profiles share it, so `" de"` barely separates French
+//! from Spanish while `"ção"` settles Portuguese.
+//!
+//! Identifiers, paths and words containing digits are ignored, as is the
+//! conventional commit prefix. Messages with too little prose to score are
+//! left undetected rather than guessed.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A language commit messages are recognised in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+    Portuguese,
+    Italian,
+    Dutch,
+    Russian,
+    Greek,
+    Arabic,
+    Hebrew,
+    Chinese,
+    Japanese,
+    Korean,
+}
+
+impl Language {
+    /// ISO 639-1 code of the language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Portuguese => "pt",
+            Language::Italian => "it",
+            Language::Dutch => "nl",
+            Language::Russian => "ru",
+            Language::Greek => "el",
+            Language::Arabic => "ar",
+            Language::Hebrew => "he",
+            Language::Chinese => "zh",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+        }
+    }
+
+    /// English name of the language
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "German",
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+            Language::Portuguese => "Portuguese",
+            Language::Italian => "Italian",
+            Language::Dutch => "Dutch",
+            Language::Russian => "Russian",
+            Language::Greek => "Greek",
+            Language::Arabic => "Arabic",
+            Language::Hebrew => "Hebrew",
+            Language::Chinese => "Chinese",
+            Language::Japanese => "Japanese",
+            Language::Korean => "Korean",
+        }
+    }
+
+    /// Parse a language code or name (`en`, `german`)
+    pub fn parse(language: &str) -> Option<Self> {
+        let language = language.trim().to_lowercase();
+        ALL.iter().copied().find(|candidate| candidate.code() == language || candidate.name().to_lowercase() == language)
+    }
+}
+
+/// Every recognised language
+pub const ALL: [Language; 14] = [
+    Language::English, Language::German, Language::French, Language::Spanish, Language::Portuguese,
+    Language::Italian, Language::Dutch, Language::Russian, Language::Greek, Language::Arabic,
+    Language::Hebrew, Language::Chinese, Language::Japanese, Language::Korean,
+];
+
+/// Frequent trigrams of each Latin-script language, words padded with spaces
+const PROFILES: [(Language, &[&str]); 7] = [
+    (Language::English, &[
+        " th", "the", "he ", " an", "and", "nd ", " to", "to ", "ing", "ng ", " of", "of ", " fi", "fix", "ix ",
+        " ad", "add", "dd ", "ed ", " in", "in ", " fo", "for", "or ", " wh", "whe", "ion", "tio", " up", "upd",
+        "pda", "dat", "ate", " re", "rem", "emo", "mov", "ove", " us", "use", " wi", "wit", "ith", "th ", " is",
+        "is ", "ly ", "all", " wo", "ork", "er ", "es ", "re ", "ent", " ch", "cha", "han", "ang", "nge", "ear", "rep", "ort", "ach", "ame",
+        " bu", "bum", "ump", "typ", "ypo", "rea", "ead", "ted", "ers", "ter", "nt ", "ts ", "rs ", "sio",
+    ]),
+    (Language::German, &[
+        "en ", "er ", " de", "der", "die", "ie ", " di", "und", " un", "nd ", "ein", " ei", "ich", "sch", "che",
+        "cht", "ung", "ng ", " zu", "zu ", " fü", "für", "ür ", " ge", "gef", "efü", "ügt", "gt ", " hi", "hin",
+        "inz", "nzu", "ehl", "hle", "ler", " fe", "feh", "ert", " be", "beh", "hob", "obe", "ben", " mi", "mit",
+        " ni", "nic", "ktu", "ali", "sie", "ier", "ent", "tfe", "rnt",
+        "gen", "ige", "kei", "eit", "ten", " au", "auf", "uf ", "ück", "übe", "ber", " ne", "neu", "eue", "ue ",
+    ]),
+    (Language::French, &[
+        " le", "le ", " la", "la ", " de", "de ", "es ", "les", "ent", " et", "et ", " pa", "par", "ar ", " po",
+        "pou", "our", "ur ", " un", "une", "ne ", "ion", "tio", " du", "du ", "ée ", " aj", "ajo", "jou",
+        "out", "ut ", "rré", "orr", " co", "cor", "igé", "gé ", "que", "ue ", " mi", "mis", "ise", " à ", "des",
+        " da", "dan", "ans", "ns ", "sup", "upp", "ppr", "pri", "rim", "imé",
+    ]),
+    (Language::Spanish, &[
+        " de", "de ", " la", "la ", " el", "el ", "os ", " lo", "los", "as ", " en", "en ", " y ", "que", "ue ",
+        " se", "ión", "ció", "aci", "ar ", " pa", "par", "ara", "ra ", " po", "por", "or ", " co", "con", "ón ",
+        " añ", "aña", "ñad", "adi", "dir", "ido", "ado", "do ", "cor", "orr", "rre", "egi", "gid", " un", "una",
+        "na ", "eli", "lim", "imi", "min", "ina", " al", "ual",
+    ]),
+    (Language::Portuguese, &[
+        " de", "de ", "ão ", "ção", "ões", " qu", "que", "ue ", " do", "do ", " da", "da ", "os ", "as ",
+        " em", "em ", " pa", "par", "ara", "ra ", " co", "com", "om ", " nã", "não", "cor", "orr", "rre", "eçã",
+        "adi", "dic", "ici", "ion", "ona", "nad", "ado", " um", "uma", "ma ", "aju", "jus", "ust", "ste",
+        "lho", "nho",
+    ]),
+    (Language::Italian, &[
+        " di", "di ", " il", "il ", " la", "la ", " de", "del", "ell", "lla", "che", "he ", " e ", "re ", "are",
+        " pe", "per", "er ", " co", "con", "one", "ne ", "zio", "ion", "agg", "ggi", "giu", "iun", "unt", "nto",
+        "to ", "ato", "cor", "orr", "rre", "ett", "tto", "sto", " un", "uno", "una", "gli", " gl", "zza", "ità",
+        "tà ", "ali", "ior", "nat",
+    ]),
+    (Language::Dutch, &[
+        " de", "de ", " he", "het", "et ", " en", "en ", "van", " va", "an ", "een", " ee", "ij ", "ijk", "toe",
+        " to", "oev", "eve", "voe", "oeg", "egd", "gd ", " vo", "voo", "oor", "or ", "ver", " ve", "erh", "sch",
+        "cht", " te", "te ", " me", "met", " ni", "nie", "iet", "wij", "ijz", "zig", "igi", "gin", "aan", "oe ",
+        "ijd", "erw", "rwi", "ege",
+    ]),
+];
+
+/// Smallest weighted trigram score a Latin-script message is recognised with
+const MIN_SCORE: f64 = 1.5;
+
+/// Smallest score per trigram of the message, so a stray match in a short message is not enough
+const MIN_DENSITY: f64 = 0.1;
+
+/// Trigram weights per language, lower for trigrams in several profiles
+fn weights() -> &'static HashMap<&'static str, Vec<(Language, f64)>> {
+    static WEIGHTS: OnceLock<HashMap<&'static str, Vec<(Language, f64)>>> = OnceLock::new();
+    WEIGHTS.get_or_init(|| {
+        let mut languages: HashMap<&'static str, Vec<Language>> = HashMap::new();
+        for (language, trigrams) in PROFILES {
+            for trigram in trigrams {
+                let entry = languages.entry(*trigram).or_default();
+                if !entry.contains(&language) {
+                    entry.push(language);
+                }
+            }
+        }
+        languages.into_iter()
+            .map(|(trigram, languages)| {
+                let weight = 1.0 / languages.len() as f64;
+                (trigram, languages.into_iter().map(|language| (language, weight)).collect())
+            })
+            .collect()
+    })
+}
+
+/// Detect the language of a commit message, `None` when it cannot be told
+pub fn detect(message: &str) -> Option<Language> {
+    let text = prose(message);
+    detect_script(&text).or_else(|| detect_latin(&text))
+}
+
+/// The words of a message that read as prose, lowercased and space-separated
+fn prose(message: &str) -> String {
+    // The type and scope of a conventional header are not prose
+    let message = match super::parser::parse(message) {
+        Some(_) => message.split_once(':').map_or(message, |(_, rest)| rest),
+        None => message,
+    };
+    message.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty() && word.chars().all(char::is_alphabetic))
+        // camelCase and other identifiers
+        .filter(|word| !word.chars().skip(1).any(char::is_uppercase) || word.chars().all(char::is_uppercase))
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The language of a message written mostly in a non-Latin script
+fn detect_script(text: &str) -> Option<Language> {
+    let mut counts: HashMap<Language, usize> = HashMap::new();
+    let mut letters = 0;
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        let language = match c as u32 {
+            0x0400..=0x04FF => Language::Russian,
+            0x0370..=0x03FF => Language::Greek,
+            0x0600..=0x06FF => Language::Arabic,
+            0x0590..=0x05FF => Language::Hebrew,
+            0x3040..=0x30FF => Language::Japanese,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => Language::Korean,
+            0x4E00..=0x9FFF => Language::Chinese,
+            _ => continue,
+        };
+        *counts.entry(language).or_default() += 1;
+    }
+    let non_latin: usize = counts.values().sum();
+    if non_latin == 0 || non_latin * 2 < letters {
+        return None;
+    }
+    // Japanese mixes kana with Han characters
+    if counts.contains_key(&Language::Japanese) {
+        return Some(Language::Japanese);
+    }
+    counts.into_iter().max_by_key(|(language, count)| (*count, std::cmp::Reverse(*language))).map(|(language, _)| language)
+}
+
+/// The Latin-script language whose trigram profile the text matches best
+fn detect_latin(text: &str) -> Option<Language> {
+    if text.is_empty() {
+        return None;
+    }
+    let padded: Vec<char> = format!(" {} ", text).chars().collect();
+    let weights = weights();
+    let mut scores: HashMap<Language, f64> = HashMap::new();
+    for window in padded.windows(3) {
+        let trigram: String = window.iter().collect();
+        for (language, weight) in weights.get(trigram.as_str()).into_iter().flatten() {
+            *scores.entry(*language).or_default() += weight;
+        }
+    }
+    let min_score = MIN_SCORE.max(MIN_DENSITY * (padded.len() - 2) as f64);
+    let mut ranked: Vec<(Language, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    match ranked.as_slice() {
+        [(best, score), (_, second), ..] if *score >= min_score && score > second => Some(*best),
+        [(best, score)] if *score >= min_score => Some(*best),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_latin_languages() {
+        assert_eq!(detect("fix: handle the empty input in the parser"), Some(Language::English));
+        assert_eq!(detect("Add support for tabs and update the docs"), Some(Language::English));
+        assert_eq!(detect("Fehler beim Speichern behoben und Tests hinzugefügt"), Some(Language::German));
+        assert_eq!(detect("feat: ajout de la gestion des erreurs pour les fichiers"), Some(Language::French));
+        assert_eq!(detect("Corregido el error de conexión con la base de datos"), Some(Language::Spanish));
+        assert_eq!(detect("Correção da validação dos dados de entrada"), Some(Language::Portuguese));
+        assert_eq!(detect("Aggiunto il controllo della configurazione"), Some(Language::Italian));
+        assert_eq!(detect("Fout in het laden van de configuratie opgelost"), Some(Language::Dutch));
+    }
+
+    #[test]
+    fn test_detect_scripts() {
+        assert_eq!(detect("Исправлена ошибка в парсере"), Some(Language::Russian));
+        assert_eq!(detect("修复解析器中的错误"), Some(Language::Chinese));
+        assert_eq!(detect("パーサーのバグを修正"), Some(Language::Japanese));
+        assert_eq!(detect("파서 버그 수정"), Some(Language::Korean));
+    }
+
+    #[test]
+    fn test_undetectable_messages() {
+        assert_eq!(detect("v1.2.3"), None);
+        assert_eq!(detect("parseConfig src/main.rs"), None);
+        assert_eq!(detect("wip"), None);
+        assert_eq!(Language::parse("EN"), Some(Language::English));
+        assert_eq!(Language::parse("german"), Some(Language::German));
+        assert_eq!(Language::parse("klingon"), None);
+    }
+}
@@ -0,0 +1,1233 @@
+//! Commit Conventions Plugin
+//!
+//! Built-in plugin that checks commit messages against the Conventional Commits
+//! format and reports how often each commit type and scope is used, how many
+//! changes are marked as breaking, and how consistently each author follows
+//! the convention. Merge commits written by git are left out of every count.
+//!
+//! Repository scans carry each commit's subject line, so breaking changes are
+//! recognised by the `!` marker in the header; a `BREAKING CHANGE:` footer is
+//! recognised when a message includes its body.
+//!
+//! The natural language of each message is detected as well and broken down per
+//! author and per period, tracking the share written in a target language (English
+//! by default) for teams adopting a single language for commit messages.
+
+pub mod language;
+pub mod parser;
+
+use self::language::Language;
+use crate::plugin::builtin::utils::time_buckets::{bucket_series, BucketPeriod, SeriesEntry, SeriesMode};
+use crate::plugin::memory::{no_heap, HeapSize};
+
+use crate::plugin::{
+    Plugin, PluginInfo, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
+};
+use crate::plugin::data_export::{
+    PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
+    DataPayload, Row, Value, ExportHints, ExportFormat
+};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
+use crate::notifications::AsyncNotificationManager;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use serde_json::json;
+
+/// Commits and breaking changes recorded under one type or scope
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct UsageStats {
+    commits: usize,
+    breaking: usize,
+}
+
+/// How closely one author's commits follow the convention
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct AuthorCompliance {
+    commits: usize,
+    conventional: usize,
+    breaking: usize,
+}
+
+impl AuthorCompliance {
+    /// Share of the author's commits that follow the convention, 0-100
+    fn compliance(&self) -> f64 {
+        percentage(self.conventional, self.commits)
+    }
+}
+
+fn percentage(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+/// Calendar periods offered for the language breakdown over time
+pub const LANGUAGE_PERIODS: [&str; 3] = ["week", "month", "quarter"];
+
+/// Commits per detected language, `None` for messages whose language could not be told
+type LanguageCounts = BTreeMap<Option<Language>, usize>;
+
+/// Language detected in one commit message
+#[derive(Debug, Clone, PartialEq)]
+struct LanguageSample {
+    author: String,
+    timestamp: i64,
+    language: Option<Language>,
+}
+
+/// Share of the messages in a detected language that are in `target`, 0-100
+fn language_share(counts: &LanguageCounts, target: Language) -> f64 {
+    let detected: usize = counts.iter().filter(|(language, _)| language.is_some()).map(|(_, commits)| commits).sum();
+    percentage(counts.get(&Some(target)).copied().unwrap_or(0), detected)
+}
+
+/// Column name of a detected language, or of messages in no detected language
+fn language_label(language: Option<Language>) -> &'static str {
+    language.map_or("Unknown", |language| language.name())
+}
+
+no_heap!(UsageStats, AuthorCompliance);
+
+impl HeapSize for LanguageSample {
+    fn heap_size(&self) -> usize {
+        self.author.heap_size()
+    }
+}
+
+/// Convention usage over the commits of a scan
+#[derive(Debug, Default, Clone)]
+struct ConventionTally {
+    types: BTreeMap<String, UsageStats>,
+    scopes: BTreeMap<String, UsageStats>,
+    authors: BTreeMap<String, AuthorCompliance>,
+    /// Merge commits, which are not checked
+    merges: usize,
+    /// Language of each checked commit message
+    languages: Vec<LanguageSample>,
+}
+
+impl HeapSize for ConventionTally {
+    fn heap_size(&self) -> usize {
+        self.types.heap_size() + self.scopes.heap_size() + self.authors.heap_size() + self.languages.heap_size()
+    }
+}
+
+impl ConventionTally {
+    /// Check one commit message against the allowed types and record the outcome
+    fn record(&mut self, author: &str, message: &str, timestamp: i64, allowed: &BTreeSet<String>) {
+        if parser::is_merge(message) {
+            self.merges += 1;
+            return;
+        }
+
+        self.languages.push(LanguageSample {
+            author: author.to_string(),
+            timestamp,
+            language: language::detect(message),
+        });
+
+        let author = self.authors.entry(author.to_string()).or_default();
+        author.commits += 1;
+
+        let Some(commit) = parser::parse(message).filter(|commit| allowed.contains(&commit.kind)) else {
+            return;
+        };
+        author.conventional += 1;
+        author.breaking += commit.breaking as usize;
+
+        let kind = self.types.entry(commit.kind).or_default();
+        kind.commits += 1;
+        kind.breaking += commit.breaking as usize;
+        if let Some(scope) = commit.scope {
+            let scope = self.scopes.entry(scope).or_default();
+            scope.commits += 1;
+            scope.breaking += commit.breaking as usize;
+        }
+    }
+
+    /// Commits checked against the convention
+    fn commits(&self) -> usize {
+        self.authors.values().map(|author| author.commits).sum()
+    }
+
+    /// Commits that follow the convention
+    fn conventional(&self) -> usize {
+        self.authors.values().map(|author| author.conventional).sum()
+    }
+
+    /// Commits marked as breaking changes
+    fn breaking(&self) -> usize {
+        self.authors.values().map(|author| author.breaking).sum()
+    }
+
+    /// Commits per language over the whole scan
+    fn language_counts(&self) -> LanguageCounts {
+        let mut counts = LanguageCounts::new();
+        for sample in &self.languages {
+            *counts.entry(sample.language).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Languages ordered by commit count, with undetected messages last
+    fn ranked_languages(&self) -> Vec<(Option<Language>, usize)> {
+        let mut ranked: Vec<_> = self.language_counts().into_iter().collect();
+        ranked.sort_by(|a, b| a.0.is_none().cmp(&b.0.is_none()).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Commits per language of each author
+    fn languages_by_author(&self) -> BTreeMap<&str, LanguageCounts> {
+        let mut authors: BTreeMap<&str, LanguageCounts> = BTreeMap::new();
+        for sample in &self.languages {
+            *authors.entry(sample.author.as_str()).or_default().entry(sample.language).or_default() += 1;
+        }
+        authors
+    }
+
+    /// Commits per language in each period from the first to the last commit
+    fn languages_over_time(&self, period: BucketPeriod, mode: SeriesMode) -> Vec<SeriesEntry<LanguageCounts>> {
+        let mut buckets: BTreeMap<chrono::NaiveDate, LanguageCounts> = BTreeMap::new();
+        for sample in &self.languages {
+            if let Some(start) = period.start_of_timestamp(sample.timestamp) {
+                *buckets.entry(start).or_default().entry(sample.language).or_default() += 1;
+            }
+        }
+        bucket_series(buckets, period, mode)
+    }
+
+    /// Entries ordered by commit count, most used first
+    fn ranked<T: Copy>(entries: &BTreeMap<String, T>, commits: impl Fn(&T) -> usize) -> Vec<(&str, T)> {
+        let mut ranked: Vec<_> = entries.iter().map(|(name, stats)| (name.as_str(), *stats)).collect();
+        ranked.sort_by(|a, b| commits(&b.1).cmp(&commits(&a.1)).then(a.0.cmp(b.0)));
+        ranked
+    }
+}
+
+/// Commit conventions plugin
+pub struct ConventionsPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+
+    /// Commit types accepted as conventional
+    allowed_types: BTreeSet<String>,
+
+    /// Language whose share of commit messages is tracked
+    target_language: Language,
+
+    /// Calendar period of the language breakdown over time
+    language_period: BucketPeriod,
+
+    /// Layout of the language breakdown over time: every period, or empty periods collapsed into gaps
+    series_mode: SeriesMode,
+
+    /// Convention usage for the scan in progress; reset when a scan starts
+    tally: Arc<RwLock<ConventionTally>>,
+
+    consuming: Arc<RwLock<bool>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
+
+    /// Notification publishing - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl ConventionsPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "conventions".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Checks commit messages against the Conventional Commits format".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Processing,
+        )
+        .with_capability(
+            "commit_types".to_string(),
+            "Counts conventional commit types, scopes and breaking changes".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "convention_compliance".to_string(),
+            "Measures how many of each author's commits follow the convention".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "message_languages".to_string(),
+            "Detects the natural language of commit messages per author and over time".to_string(),
+            "1.0.0".to_string(),
+        );
+
+        Self {
+            command_name: "conventions".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            allowed_types: parser::DEFAULT_TYPES.iter().map(|kind| kind.to_string()).collect(),
+            target_language: Language::English,
+            language_period: BucketPeriod::Month,
+            series_mode: SeriesMode::Dense,
+            tally: Arc::new(RwLock::new(ConventionTally::default())),
+            consuming: Arc::new(RwLock::new(false)),
+            consumer: Arc::new(RwLock::new(None)),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new conventions plugin with all required dependencies (REQUIRED)
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Process a commit message and record whether it follows the convention
+    async fn process_commit(&self, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::CommitInfo { author, message, timestamp, .. } = &message.data {
+            self.tally.write().await.record(author, message, *timestamp, &self.allowed_types);
+        }
+        Ok(())
+    }
+
+    /// Generate convention summary statistics
+    async fn generate_summary(&self) -> PluginResult<ScanMessage> {
+        let tally = self.tally.read().await.clone();
+
+        let data = MessageData::MetricInfo {
+            file_count: tally.commits() as u32,
+            line_count: tally.conventional() as u64,
+            complexity: percentage(tally.conventional(), tally.commits()),
+        };
+
+        let header = MessageHeader::new(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            "plugin-generated".to_string(),
+        );
+
+        Ok(ScanMessage::new(header, data))
+    }
+
+    fn export_hints() -> ExportHints {
+        ExportHints {
+            preferred_formats: vec![
+                ExportFormat::Console,
+                ExportFormat::Json,
+                ExportFormat::Csv,
+                ExportFormat::Html,
+                ExportFormat::Markdown,
+            ],
+            sort_by: None,
+            sort_ascending: false,
+            limit: None,
+            include_totals: false,
+            include_row_numbers: false,
+            custom_hints: HashMap::new(),
+        }
+    }
+
+    fn tabular_export(plugin_id: &str, title: &str, description: String, schema: DataSchema, rows: Vec<Row>) -> PluginDataExport {
+        PluginDataExport {
+            plugin_id: plugin_id.to_string(),
+            title: title.to_string(),
+            description: Some(description),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: Self::export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+
+    /// Create the commit type and scope frequency exports
+    fn create_usage_exports(tally: &ConventionTally, scan_id: &str) -> Vec<PluginDataExport> {
+        let commits = tally.commits();
+        let mut exports = Vec::new();
+
+        if !tally.types.is_empty() {
+            let schema = DataSchema {
+                columns: vec![
+                    ColumnDef::new("Type", ColumnType::String),
+                    ColumnDef::new("Commits", ColumnType::Integer),
+                    ColumnDef::new("Share", ColumnType::Float)
+                        .with_description("Percentage of checked commits with this type")
+                        .with_format_hint("percentage"),
+                    ColumnDef::new("Breaking", ColumnType::Integer)
+                        .with_description("Commits marked as breaking changes"),
+                ],
+                metadata: HashMap::new(),
+            };
+            let rows = ConventionTally::ranked(&tally.types, |stats| stats.commits)
+                .into_iter()
+                .map(|(kind, stats)| Row::new(vec![
+                    Value::String(kind.to_string()),
+                    Value::Integer(stats.commits as i64),
+                    Value::Float(percentage(stats.commits, commits)),
+                    Value::Integer(stats.breaking as i64),
+                ]))
+                .collect();
+            exports.push(Self::tabular_export(
+                "conventions-types",
+                "Commit Types",
+                format!("{} of {} commits use a conventional type in scan {}", tally.conventional(), commits, scan_id),
+                schema,
+                rows,
+            ));
+        }
+
+        if !tally.scopes.is_empty() {
+            let schema = DataSchema {
+                columns: vec![
+                    ColumnDef::new("Scope", ColumnType::String),
+                    ColumnDef::new("Commits", ColumnType::Integer),
+                    ColumnDef::new("Breaking", ColumnType::Integer)
+                        .with_description("Commits marked as breaking changes"),
+                ],
+                metadata: HashMap::new(),
+            };
+            let rows = ConventionTally::ranked(&tally.scopes, |stats| stats.commits)
+                .into_iter()
+                .map(|(scope, stats)| Row::new(vec![
+                    Value::String(scope.to_string()),
+                    Value::Integer(stats.commits as i64),
+                    Value::Integer(stats.breaking as i64),
+                ]))
+                .collect();
+            exports.push(Self::tabular_export(
+                "conventions-scopes",
+                "Commit Scopes",
+                format!("{} scopes used by conventional commits in scan {}", tally.scopes.len(), scan_id),
+                schema,
+                rows,
+            ));
+        }
+
+        exports
+    }
+
+    /// Create the per-author compliance export
+    fn create_compliance_export(tally: &ConventionTally, scan_id: &str) -> Option<PluginDataExport> {
+        if tally.authors.is_empty() {
+            return None;
+        }
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Author", ColumnType::String),
+                ColumnDef::new("Commits", ColumnType::Integer),
+                ColumnDef::new("Conventional", ColumnType::Integer)
+                    .with_description("Commits with a conventional header and an accepted type"),
+                ColumnDef::new("Compliance", ColumnType::Float)
+                    .with_description("Percentage of the author's commits that follow the convention")
+                    .with_format_hint("percentage"),
+                ColumnDef::new("Breaking", ColumnType::Integer)
+                    .with_description("Commits marked as breaking changes"),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("compliance".to_string(), format!("{:.1}", percentage(tally.conventional(), tally.commits())));
+                meta.insert("merge_commits".to_string(), tally.merges.to_string());
+                meta
+            },
+        };
+
+        let rows = ConventionTally::ranked(&tally.authors, |stats| stats.commits)
+            .into_iter()
+            .map(|(author, stats)| Row::new(vec![
+                Value::String(author.to_string()),
+                Value::Integer(stats.commits as i64),
+                Value::Integer(stats.conventional as i64),
+                Value::Float(stats.compliance()),
+                Value::Integer(stats.breaking as i64),
+            ]))
+            .collect();
+
+        Some(Self::tabular_export(
+            "conventions",
+            "Convention Compliance",
+            format!(
+                "{:.1}% of {} commits follow the convention ({} merge commits not checked) in scan {}",
+                percentage(tally.conventional(), tally.commits()), tally.commits(), tally.merges, scan_id
+            ),
+            schema,
+            rows,
+        ))
+    }
+
+    /// Create the language distribution exports: overall, per author and per period
+    fn create_language_exports(tally: &ConventionTally, scan_id: &str, target: Language, period: BucketPeriod, mode: SeriesMode) -> Vec<PluginDataExport> {
+        let ranked = tally.ranked_languages();
+        if ranked.is_empty() {
+            return Vec::new();
+        }
+        let commits = tally.languages.len();
+        let share_column = format!("{} Share", target.name());
+        let share_description = format!("Percentage of messages in a detected language written in {}", target.name());
+        let metadata = || {
+            let mut meta = HashMap::new();
+            meta.insert("target_language".to_string(), target.code().to_string());
+            meta
+        };
+        // One count column per language, most used first, as in the overall table
+        let language_columns = || ranked.iter()
+            .map(|(language, _)| ColumnDef::new(language_label(*language), ColumnType::Integer));
+        let language_values = |counts: &LanguageCounts| ranked.iter()
+            .map(|(language, _)| Value::Integer(counts.get(language).copied().unwrap_or(0) as i64))
+            .collect::<Vec<_>>();
+        let mut exports = Vec::new();
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Language", ColumnType::String),
+                ColumnDef::new("Code", ColumnType::String),
+                ColumnDef::new("Commits", ColumnType::Integer),
+                ColumnDef::new("Share", ColumnType::Float)
+                    .with_description("Percentage of checked commits written in the language")
+                    .with_format_hint("percentage"),
+            ],
+            metadata: metadata(),
+        };
+        let rows = ranked.iter()
+            .map(|(language, count)| Row::new(vec![
+                Value::String(language_label(*language).to_string()),
+                language.map_or(Value::Null, |language| Value::String(language.code().to_string())),
+                Value::Integer(*count as i64),
+                Value::Float(percentage(*count, commits)),
+            ]))
+            .collect();
+        exports.push(Self::tabular_export(
+            "conventions-languages",
+            "Message Languages",
+            format!(
+                "{:.1}% of {} commit messages in a detected language are in {} in scan {}",
+                language_share(&tally.language_counts(), target), commits, target.name(), scan_id
+            ),
+            schema,
+            rows,
+        ));
+
+        let by_author = tally.languages_by_author();
+        let schema = DataSchema {
+            columns: std::iter::once(ColumnDef::new("Author", ColumnType::String))
+                .chain(std::iter::once(ColumnDef::new("Commits", ColumnType::Integer)))
+                .chain(language_columns())
+                .chain(std::iter::once(ColumnDef::new(share_column.as_str(), ColumnType::Float)
+                    .with_description(share_description.as_str())
+                    .with_format_hint("percentage")))
+                .collect(),
+            metadata: metadata(),
+        };
+        let mut authors: Vec<(&str, &LanguageCounts, usize)> = by_author.iter()
+            .map(|(author, counts)| (*author, counts, counts.values().sum()))
+            .collect();
+        authors.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(b.0)));
+        let rows = authors.into_iter()
+            .map(|(author, counts, commits)| {
+                let mut values = vec![Value::String(author.to_string()), Value::Integer(commits as i64)];
+                values.extend(language_values(counts));
+                values.push(Value::Float(language_share(counts, target)));
+                Row::new(values)
+            })
+            .collect();
+        exports.push(Self::tabular_export(
+            "conventions-languages",
+            "Languages by Author",
+            format!("Commit message languages of {} authors in scan {}", by_author.len(), scan_id),
+            schema,
+            rows,
+        ));
+
+        let series = tally.languages_over_time(period, mode);
+        let sparse = mode == SeriesMode::Sparse;
+        let mut schema = DataSchema {
+            columns: std::iter::once(ColumnDef::new("Period", ColumnType::String))
+                .chain(std::iter::once(ColumnDef::new("Commits", ColumnType::Integer)))
+                .chain(language_columns())
+                .chain(std::iter::once(ColumnDef::new(share_column.as_str(), ColumnType::Float)
+                    .with_description(share_description.as_str())
+                    .with_format_hint("percentage")))
+                .chain(sparse.then(|| ColumnDef::new("Empty Periods", ColumnType::Integer)
+                    .with_description("Consecutive periods without commits collapsed into this row")))
+                .collect(),
+            metadata: metadata(),
+        };
+        schema.metadata.insert("period".to_string(), period.as_str().to_string());
+        schema.metadata.insert("series".to_string(), mode.as_str().to_string());
+        let rows = series.iter()
+            .map(|entry| {
+                let counts = entry.value().cloned().unwrap_or_default();
+                let mut values = vec![Value::String(entry.label().to_string()), Value::Integer(counts.values().sum::<usize>() as i64)];
+                values.extend(language_values(&counts));
+                // Periods without commits have no share rather than 0%
+                values.push(if counts.is_empty() { Value::Null } else { Value::Float(language_share(&counts, target)) });
+                if sparse {
+                    values.push(Value::Integer(entry.gap_periods() as i64));
+                }
+                Row::new(values)
+            })
+            .collect();
+        let periods: usize = series.iter().map(|entry| entry.gap_periods().max(1)).sum();
+        exports.push(Self::tabular_export(
+            "conventions-languages",
+            "Languages over Time",
+            format!("Commit message languages per {} over {} periods in scan {}", period.as_str(), periods, scan_id),
+            schema,
+            rows,
+        ));
+
+        exports
+    }
+
+    /// Publish the compliance, type, scope and language exports if any commits were checked
+    async fn publish_exports(&self, scan_id: &str) {
+        let tally = self.tally.read().await.clone();
+        let exports = Self::create_compliance_export(&tally, scan_id)
+            .into_iter()
+            .chain(Self::create_usage_exports(&tally, scan_id))
+            .chain(Self::create_language_exports(&tally, scan_id, self.target_language, self.language_period, self.series_mode));
+
+        for export_data in exports {
+            let title = export_data.title.clone();
+            let event = PluginEvent::DataReady {
+                plugin_id: "conventions".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish {} DataReady event: {}", title, e);
+            } else {
+                log::debug!("Published {} DataReady event for conventions plugin", title);
+            }
+        }
+    }
+
+    /// Execute the convention analysis function
+    async fn execute_conventions_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let tally = self.tally.read().await.clone();
+
+        let data = json!({
+            "total_commits": tally.commits(),
+            "conventional_commits": tally.conventional(),
+            "breaking_changes": tally.breaking(),
+            "merge_commits": tally.merges,
+            "compliance": percentage(tally.conventional(), tally.commits()),
+            "types": ConventionTally::ranked(&tally.types, |stats| stats.commits).iter().map(|(kind, stats)| {
+                json!({ "type": kind, "commits": stats.commits, "breaking": stats.breaking })
+            }).collect::<Vec<_>>(),
+            "scopes": ConventionTally::ranked(&tally.scopes, |stats| stats.commits).iter().map(|(scope, stats)| {
+                json!({ "scope": scope, "commits": stats.commits, "breaking": stats.breaking })
+            }).collect::<Vec<_>>(),
+            "authors": ConventionTally::ranked(&tally.authors, |stats| stats.commits).iter().map(|(author, stats)| {
+                json!({
+                    "author": author,
+                    "commits": stats.commits,
+                    "conventional": stats.conventional,
+                    "compliance": stats.compliance(),
+                    "breaking": stats.breaking,
+                })
+            }).collect::<Vec<_>>(),
+            "function": "conventions"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "conventions_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: (tally.commits() + tally.merges) as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+
+    /// Execute the commit message language analysis function
+    async fn execute_language_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let tally = self.tally.read().await.clone();
+        let target = self.target_language;
+        let commits = tally.languages.len();
+        let by_author = tally.languages_by_author();
+
+        let counts_json = |counts: &LanguageCounts| counts.iter()
+            .map(|(language, commits)| (language.map_or("unknown", |language| language.code()).to_string(), json!(commits)))
+            .collect::<serde_json::Map<_, _>>();
+
+        let data = json!({
+            "total_commits": commits,
+            "merge_commits": tally.merges,
+            "target_language": target.code(),
+            "target_share": language_share(&tally.language_counts(), target),
+            "languages": tally.ranked_languages().iter().map(|(language, count)| {
+                json!({
+                    "language": language_label(*language),
+                    "code": language.map(|language| language.code()),
+                    "commits": count,
+                    "share": percentage(*count, commits),
+                })
+            }).collect::<Vec<_>>(),
+            "authors": by_author.iter().map(|(author, counts)| {
+                json!({
+                    "author": author,
+                    "commits": counts.values().sum::<usize>(),
+                    "languages": counts_json(counts),
+                    "target_share": language_share(counts, target),
+                })
+            }).collect::<Vec<_>>(),
+            "period": self.language_period.as_str(),
+            "series": self.series_mode.as_str(),
+            "periods": tally.languages_over_time(self.language_period, self.series_mode).iter().map(|entry| {
+                let counts = entry.value().cloned().unwrap_or_default();
+                json!({
+                    "period": entry.label(),
+                    "commits": counts.values().sum::<usize>(),
+                    "languages": counts_json(&counts),
+                    "target_share": (!counts.is_empty()).then(|| language_share(&counts, target)),
+                    "empty_periods": entry.gap_periods(),
+                })
+            }).collect::<Vec<_>>(),
+            "function": "languages"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "language_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: (commits + tally.merges) as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+}
+
+impl Default for ConventionsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for ConventionsPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        *self.tally.write().await = ConventionTally::default();
+        self.initialized = true;
+
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        if !self.initialized {
+            return Err(PluginError::invalid_state("Plugin not initialized"));
+        }
+
+        match request {
+            PluginRequest::Execute { invocation_type, .. } => {
+                let function_name = match invocation_type {
+                    crate::plugin::InvocationType::Function(ref func) => func.as_str(),
+                    crate::plugin::InvocationType::Direct => self.default_function().unwrap_or("conventions"),
+                    crate::plugin::InvocationType::Default => "conventions",
+                };
+
+                match function_name {
+                    "conventions" | "conventional" | "commit-types" => self.execute_conventions_analysis().await,
+                    "languages" | "language" | "message-languages" => self.execute_language_analysis().await,
+                    _ => Err(PluginError::execution_failed(
+                        format!("Unknown function: {}", function_name)
+                    )),
+                }
+            }
+            PluginRequest::GetStatistics => {
+                let summary = self.generate_summary().await?;
+                Ok(PluginResponse::Statistics(summary))
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        if *self.consuming.read().await {
+            self.stop_consuming().await?;
+        }
+
+        self.initialized = false;
+        *self.tally.write().await = ConventionTally::default();
+        Ok(())
+    }
+
+    /// Get all functions this plugin can handle
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "conventions".to_string(),
+                aliases: vec!["conventional".to_string(), "commit-types".to_string()],
+                description: "Conventional commit types, scopes, breaking changes and compliance per author".to_string(),
+                is_default: true,
+            },
+            PluginFunction {
+                name: "languages".to_string(),
+                aliases: vec!["language".to_string(), "message-languages".to_string()],
+                description: "Natural language of commit messages per author and over time".to_string(),
+                is_default: false,
+            },
+        ]
+    }
+
+    /// Get the default function name
+    fn default_function(&self) -> Option<&str> {
+        Some("conventions")
+    }
+
+    /// Cast to ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Cast to mutable ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for ConventionsPlugin {
+    async fn publish(&self, event: PluginEvent) -> crate::notifications::NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for ConventionsPlugin {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if *consuming {
+            return Err(PluginError::invalid_state("Already consuming"));
+        }
+
+        *consuming = true;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = Some(consumer);
+        }
+
+        log::info!("Conventions plugin started consuming messages");
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        self.process_commit(&message).await?;
+
+        consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge message: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        log::debug!("Conventions plugin received queue event: {:?}", event);
+
+        match event {
+            QueueEvent::ScanStarted { scan_id, .. } => {
+                log::info!("Conventions plugin: scan started for {}", scan_id);
+                *self.tally.write().await = ConventionTally::default();
+            }
+            QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
+                let (commits, conventional) = {
+                    let tally = self.tally.read().await;
+                    (tally.commits(), tally.conventional())
+                };
+                log::info!(
+                    "Conventions plugin: scan {} complete - {} of {} commits conventional (total {} messages)",
+                    scan_id, conventional, commits, total_messages
+                );
+
+                self.publish_exports(scan_id).await;
+            }
+            _ => {
+                // Other events are just logged
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if !*consuming {
+            return Ok(()); // Already stopped
+        }
+
+        *consuming = false;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = None;
+        }
+
+        log::info!("Conventions plugin stopped consuming messages");
+        Ok(())
+    }
+
+    async fn state_memory(&self) -> usize {
+        self.tally.read().await.heap_size()
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages: false, // Only interested in commit messages
+            interested_message_types: vec!["CommitInfo".to_string()],
+            high_frequency_capable: true,
+            preferred_batch_size: 100,
+            requires_ordered_delivery: false, // Counts are order independent
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_millis(10)),
+        }
+    }
+}
+
+/// Modern clap-based argument parsing implementation for conventions plugin
+#[async_trait]
+impl PluginClapParser for ConventionsPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Checks commit messages against the Conventional Commits format"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        use clap::Arg;
+
+        command
+            .override_usage("conventions [OPTIONS]")
+            .help_template("Usage: {usage}\n\nChecks commit messages against the Conventional Commits format\n\nOptions:\n{options}\n{after-help}")
+            .after_help("A commit follows the convention when its subject reads `type(scope): description` with an \
+                accepted type; the scope is optional and `!` before the colon marks a breaking change. \
+                Merge commits are not checked. The language of each message is detected from its \
+                letters and common words; messages too short or too technical to tell are reported as Unknown.")
+            .arg(Arg::new("types")
+                .long("types")
+                .value_name("LIST")
+                .help("Comma-separated commit types accepted as conventional")
+                .default_value(parser::DEFAULT_TYPES.join(",")))
+            .arg(Arg::new("target-language")
+                .long("target-language")
+                .value_name("LANGUAGE")
+                .help("Language whose share of commit messages is tracked (code or name, e.g. en, german)")
+                .default_value("en"))
+            .arg(Arg::new("language-period")
+                .long("language-period")
+                .value_name("PERIOD")
+                .help("Period of the language breakdown over time")
+                .value_parser(LANGUAGE_PERIODS)
+                .default_value("month"))
+            .arg(Arg::new("series")
+                .long("series")
+                .value_name("MODE")
+                .help("Language series layout: dense lists every period, sparse collapses empty periods into gap rows")
+                .value_parser(["dense", "sparse"])
+                .default_value("dense"))
+    }
+
+    async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
+        if let Some(types) = matches.get_one::<String>("types") {
+            let allowed: BTreeSet<String> = types.split(',')
+                .map(|kind| kind.trim().to_ascii_lowercase())
+                .filter(|kind| !kind.is_empty())
+                .collect();
+            if allowed.is_empty() {
+                return Err(PluginError::configuration_error("--types must list at least one commit type"));
+            }
+            self.allowed_types = allowed;
+        }
+
+        if let Some(target) = matches.get_one::<String>("target-language") {
+            self.target_language = Language::parse(target).ok_or_else(|| PluginError::configuration_error(format!(
+                "Unknown --target-language '{}'; expected one of {}",
+                target,
+                language::ALL.iter().map(|language| language.code()).collect::<Vec<_>>().join(", ")
+            )))?;
+        }
+        if let Some(period) = matches.get_one::<String>("language-period").and_then(|period| BucketPeriod::parse(period)) {
+            self.language_period = period;
+        }
+        if let Some(mode) = matches.get_one::<String>("series").and_then(|m| SeriesMode::parse(m)) {
+            self.series_mode = mode;
+        }
+
+        log::debug!("Conventions plugin configured with types {:?}", self.allowed_types);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginContext;
+
+    fn create_test_commit_message(author: &str, message: &str) -> ScanMessage {
+        let data = MessageData::CommitInfo {
+            hash: "abc123".to_string(),
+            author: author.to_string(),
+            message: message.to_string(),
+            timestamp: 1_700_000_000,
+            committer: author.to_string(),
+            author_timestamp: 1_700_000_000,
+            parents: vec![],
+            changed_files: vec![],
+        };
+
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    fn create_test_context() -> PluginContext {
+        PluginContext::new(
+            Arc::new(crate::scanner::ScannerConfig::default()),
+            Arc::new(crate::scanner::QueryParams::default()),
+        )
+    }
+
+    async fn plugin_with_commits() -> ConventionsPlugin {
+        let mut plugin = ConventionsPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        let commits = [
+            ("alice", "feat(parser): accept tabs"),
+            ("alice", "fix(parser): handle empty input"),
+            ("alice", "feat(api)!: remove v1 routes"),
+            ("alice", "Merge branch 'main' into parser"),
+            ("bob", "fix: typo in README"),
+            ("bob", "Update docs"),
+            ("bob", "wip: experiments"),
+        ];
+        for (author, message) in commits {
+            plugin.process_commit(&create_test_commit_message(author, message)).await.unwrap();
+        }
+        plugin
+    }
+
+    #[tokio::test]
+    async fn test_conventions_plugin_creation() {
+        let plugin = ConventionsPlugin::new();
+        assert_eq!(plugin.plugin_info().name, "conventions");
+        assert_eq!(plugin.default_function(), Some("conventions"));
+        assert_eq!(plugin.consumer_preferences().interested_message_types, vec!["CommitInfo".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_conventions_compliance_export() {
+        let plugin = plugin_with_commits().await;
+        let tally = plugin.tally.read().await.clone();
+        assert_eq!((tally.commits(), tally.conventional(), tally.breaking(), tally.merges), (6, 4, 1, 1));
+
+        let export = ConventionsPlugin::create_compliance_export(&tally, "scan").unwrap();
+        assert_eq!(export.schema.metadata.get("merge_commits"), Some(&"1".to_string()));
+        if let DataPayload::Rows(rows) = &export.data {
+            assert_eq!(rows[0].values, vec![
+                Value::String("alice".to_string()), Value::Integer(3), Value::Integer(3), Value::Float(100.0), Value::Integer(1),
+            ]);
+            assert_eq!(rows[1].values[3], Value::Float(100.0 / 3.0));
+        } else {
+            panic!("expected row payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conventions_usage_exports() {
+        let plugin = plugin_with_commits().await;
+        let tally = plugin.tally.read().await.clone();
+
+        let exports = ConventionsPlugin::create_usage_exports(&tally, "scan");
+        let titles: Vec<&str> = exports.iter().map(|export| export.title.as_str()).collect();
+        assert_eq!(titles, vec!["Commit Types", "Commit Scopes"]);
+        if let DataPayload::Rows(rows) = &exports[0].data {
+            assert_eq!(rows[0].values[..2], [Value::String("feat".to_string()), Value::Integer(2)]);
+            assert_eq!(rows[0].values[3], Value::Integer(1));
+            assert_eq!(rows[1].values[..2], [Value::String("fix".to_string()), Value::Integer(2)]);
+        } else {
+            panic!("expected row payload");
+        }
+        if let DataPayload::Rows(rows) = &exports[1].data {
+            assert_eq!(rows[0].values[..2], [Value::String("parser".to_string()), Value::Integer(2)]);
+        } else {
+            panic!("expected row payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conventions_custom_types() {
+        let mut plugin = ConventionsPlugin::new();
+        let command = PluginClapParser::build_clap_command(&plugin);
+        let matches = command.try_get_matches_from(["conventions", "--types", "feat, WIP"]).unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        plugin.process_commit(&create_test_commit_message("bob", "wip: experiments")).await.unwrap();
+        plugin.process_commit(&create_test_commit_message("bob", "fix: typo")).await.unwrap();
+        assert_eq!(plugin.tally.read().await.conventional(), 1);
+
+        plugin.handle_queue_event(&QueueEvent::ScanStarted { scan_id: "next".to_string(), timestamp: 0 }).await.unwrap();
+        assert!(plugin.tally.read().await.authors.is_empty());
+    }
+    #[tokio::test]
+    async fn test_conventions_language_exports() {
+        let mut plugin = ConventionsPlugin::new();
+        let command = PluginClapParser::build_clap_command(&plugin);
+        let matches = command.try_get_matches_from(["conventions", "--target-language", "english"]).unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        let commits = [
+            ("alice", "Fix the error handling when the file is not found"),
+            ("alice", "Add support for the new configuration format"),
+            ("bob", "Fehler bei der Verarbeitung von leeren Dateien behoben"),
+            ("bob", "wip"),
+        ];
+        for (author, message) in commits {
+            plugin.process_commit(&create_test_commit_message(author, message)).await.unwrap();
+        }
+        let tally = plugin.tally.read().await.clone();
+
+        let exports = ConventionsPlugin::create_language_exports(&tally, "scan", plugin.target_language, plugin.language_period, plugin.series_mode);
+        let titles: Vec<&str> = exports.iter().map(|export| export.title.as_str()).collect();
+        assert_eq!(titles, vec!["Message Languages", "Languages by Author", "Languages over Time"]);
+        if let DataPayload::Rows(rows) = &exports[0].data {
+            assert_eq!(rows[0].values[..3], [Value::String("English".to_string()), Value::String("en".to_string()), Value::Integer(2)]);
+            assert_eq!(rows[1].values[..3], [Value::String("German".to_string()), Value::String("de".to_string()), Value::Integer(1)]);
+            assert_eq!(rows[2].values[..3], [Value::String("Unknown".to_string()), Value::Null, Value::Integer(1)]);
+        } else {
+            panic!("expected row payload");
+        }
+        if let DataPayload::Rows(rows) = &exports[1].data {
+            // Undetected messages count as commits but not towards the share
+            assert_eq!(rows[0].values[0], Value::String("alice".to_string()));
+            assert_eq!(rows[0].values[5], Value::Float(100.0));
+            assert_eq!(rows[1].values[..2], [Value::String("bob".to_string()), Value::Integer(2)]);
+            assert_eq!(rows[1].values[5], Value::Float(0.0));
+        } else {
+            panic!("expected row payload");
+        }
+        if let DataPayload::Rows(rows) = &exports[2].data {
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].values[..2], [Value::String("2023-11".to_string()), Value::Integer(4)]);
+        } else {
+            panic!("expected row payload");
+        }
+
+        let matches = PluginClapParser::build_clap_command(&plugin)
+            .try_get_matches_from(["conventions", "--target-language", "klingon"])
+            .unwrap();
+        assert!(plugin.configure_from_matches(&matches).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sparse_language_series_collapses_idle_months() {
+        let mut plugin = ConventionsPlugin::new();
+        let matches = PluginClapParser::build_clap_command(&plugin)
+            .try_get_matches_from(["conventions", "--series", "sparse"])
+            .unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        plugin.process_commit(&create_test_commit_message("alice", "Fix the error handling when the file is not found")).await.unwrap();
+        let mut later = create_test_commit_message("alice", "Add support for the new configuration format");
+        if let MessageData::CommitInfo { timestamp, .. } = &mut later.data {
+            // Four months on, in March 2024
+            *timestamp += 120 * 86_400;
+        }
+        plugin.process_commit(&later).await.unwrap();
+        let tally = plugin.tally.read().await.clone();
+
+        let exports = ConventionsPlugin::create_language_exports(&tally, "scan", plugin.target_language, plugin.language_period, plugin.series_mode);
+        let over_time = &exports[2];
+        assert_eq!(over_time.schema.columns.last().unwrap().name, "Empty Periods");
+        if let DataPayload::Rows(rows) = &over_time.data {
+            let labels: Vec<String> = rows.iter().map(|row| row.values[0].to_string()).collect();
+            assert_eq!(labels, ["2023-11", "2023-12..2024-02", "2024-03"]);
+            let empty = rows[1].values.len() - 1;
+            assert_eq!(rows[1].values[1], Value::Integer(0));
+            assert_eq!(rows[1].values[empty - 1], Value::Null);
+            assert_eq!(rows[1].values[empty], Value::Integer(3));
+            assert_eq!(rows[2].values[empty], Value::Integer(0));
+        } else {
+            panic!("expected row payload");
+        }
+    }
+}
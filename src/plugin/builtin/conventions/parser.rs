@@ -0,0 +1,101 @@
+//! Conventional Commit Parsing
+//!
+//! Parses commit messages of the form `type(scope)!: description` as defined by
+//! the Conventional Commits specification. The type is case-insensitive and
+//! reported in lower case; the scope is optional. A change is breaking when the
+//! header carries `!` before the colon or the message has a `BREAKING CHANGE:`
+//! (or `BREAKING-CHANGE:`) footer.
+
+/// Commit types of the widely used conventional configuration
+pub const DEFAULT_TYPES: [&str; 11] = [
+    "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+];
+
+/// Type, scope and breaking-change marker of a conventional commit header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+}
+
+/// Parse a commit message, returning `None` when its first line is not a conventional header
+pub fn parse(message: &str) -> Option<ConventionalCommit> {
+    let header = message.lines().next()?;
+    let (prefix, description) = header.split_once(':')?;
+    if !description.starts_with(' ') || description.trim().is_empty() {
+        return None;
+    }
+
+    let (prefix, bang) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, scope)) => {
+            let scope = scope.strip_suffix(')')?.trim();
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            (kind, Some(scope.to_string()))
+        }
+        None => (prefix, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+
+    Some(ConventionalCommit {
+        kind: kind.to_ascii_lowercase(),
+        scope,
+        breaking: bang || has_breaking_footer(message),
+    })
+}
+
+/// Whether a message after its header has a `BREAKING CHANGE:` footer
+fn has_breaking_footer(message: &str) -> bool {
+    message.lines()
+        .skip(1)
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"))
+}
+
+/// Whether a message is one git writes for merges, which no convention applies to
+pub fn is_merge(message: &str) -> bool {
+    message.starts_with("Merge branch ")
+        || message.starts_with("Merge pull request ")
+        || message.starts_with("Merge remote-tracking branch ")
+        || message.starts_with("Merge tag ")
+        || message.starts_with("Merge commit ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(kind: &str, scope: Option<&str>, breaking: bool) -> Option<ConventionalCommit> {
+        Some(ConventionalCommit { kind: kind.to_string(), scope: scope.map(str::to_string), breaking })
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        assert_eq!(parse("feat: add login"), commit("feat", None, false));
+        assert_eq!(parse("Fix(parser): handle tabs"), commit("fix", Some("parser"), false));
+        assert_eq!(parse("refactor(api)!: drop v1 routes"), commit("refactor", Some("api"), true));
+        assert_eq!(parse("chore!: bump MSRV"), commit("chore", None, true));
+    }
+
+    #[test]
+    fn test_breaking_change_footer() {
+        let message = "feat(config): read TOML\n\nBREAKING CHANGE: YAML files are no longer read";
+        assert_eq!(parse(message), commit("feat", Some("config"), true));
+    }
+
+    #[test]
+    fn test_reject_non_conventional_messages() {
+        for message in ["Add login", "feat:missing space", "feat: ", "feat(): empty scope", "fix bug: typo", ""] {
+            assert_eq!(parse(message), None, "{}", message);
+        }
+        assert!(is_merge("Merge branch 'main' into feature"));
+        assert!(!is_merge("Merged the parser changes"));
+    }
+}
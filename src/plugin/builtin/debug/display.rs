@@ -54,7 +54,7 @@ impl MessageFormatter {
         
         // Format based on message type
         match message.data() {
-            MessageData::CommitInfo { hash, author, message: commit_msg, timestamp, changed_files } => {
+            MessageData::CommitInfo { hash, author, message: commit_msg, timestamp, changed_files, .. } => {
                 let file_paths: Vec<String> = changed_files.iter()
                     .map(|f| f.path.clone())
                     .collect();
@@ -70,6 +70,9 @@ impl MessageFormatter {
             MessageData::FileInfo { path, size, lines } => {
                 self.format_file_info(message, path, *size, *lines as u64, &config);
             }
+            MessageData::FileContent { path, size, content, .. } => {
+                self.format_file_info(message, path, *size, content.lines().count() as u64, &config);
+            }
             _ => {
                 if config.verbose {
                     if config.message_index {
@@ -393,6 +396,9 @@ mod tests {
             author: "Test Author".to_string(),
             message: "Test commit message".to_string(),
             timestamp: 1234567890,
+            committer: "Test Author".to_string(),
+            author_timestamp: 1234567890,
+            parents: vec![],
             changed_files: vec![
                 FileChangeData {
                     path: "file1.rs".to_string(),
@@ -430,6 +436,9 @@ mod tests {
             commit_hash: "abc123".to_string(),
             commit_timestamp: 1234567890,
             checkout_path: None,
+            old_mode: None,
+            new_mode: None,
+            replaced_lines: vec![],
         };
         let message = ScanMessage::new(header, data);
         
@@ -488,6 +497,9 @@ mod tests {
             author: "Test Author".to_string(),
             message: "Test commit message that is very long and should be truncated in compact mode".to_string(),
             timestamp: 1234567890,
+            committer: "Test Author".to_string(),
+            author_timestamp: 1234567890,
+            parents: vec![],
             changed_files: vec![
                 FileChangeData {
                     path: "file1.rs".to_string(),
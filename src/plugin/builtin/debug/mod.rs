@@ -17,17 +17,17 @@ use std::collections::HashMap;
 use tokio::sync::RwLock;
 
 use crate::plugin::traits::{
-    Plugin, PluginInfo, PluginType, ConsumerPlugin, PluginDataRequirements,
+    Plugin, PluginInfo, PluginType, ConsumerPlugin, MessageConsumer, PluginDataRequirements,
     ConsumerPreferences, PluginClapParser,
 };
 use crate::plugin::error::{PluginError, PluginResult};
-use crate::plugin::context::{PluginContext, PluginRequest, PluginResponse};
+use crate::plugin::context::{PluginContext, ScanContext, PluginRequest, PluginResponse};
 use crate::plugin::data_export::{
     PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
     DataPayload, Row, Value, ExportHints, ExportFormat
 };
 use crate::scanner::messages::{ScanMessage, MessageData};
-use crate::queue::{QueueEvent, QueueConsumer};
+use crate::queue::QueueEvent;
 use crate::cli::plugin_args::{PluginArguments, PluginArgValue};
 use crate::notifications::AsyncNotificationManager;
 use crate::notifications::events::PluginEvent;
@@ -64,7 +64,7 @@ pub struct DebugPlugin {
     consuming: Arc<RwLock<bool>>,
     
     /// Queue consumer handle
-    consumer: Arc<RwLock<Option<QueueConsumer>>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
     
     /// Notification publishing - REQUIRED for all plugins
     notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
@@ -311,6 +311,7 @@ impl DebugPlugin {
             data: DataPayload::Rows(Arc::new(rows)),
             export_hints,
             timestamp: std::time::SystemTime::now(),
+            repository: None,
         })
     }
 }
@@ -327,7 +328,8 @@ impl Plugin for DebugPlugin {
         &self.info
     }
     
-    async fn initialize(&mut self, context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
         // Initialize plugin with context if needed
         log::info!("Debug plugin initialized");
         
@@ -482,7 +484,7 @@ impl Publisher<PluginEvent> for DebugPlugin {
 
 #[async_trait]
 impl ConsumerPlugin for DebugPlugin {
-    async fn start_consuming(&mut self, consumer: QueueConsumer) -> PluginResult<()> {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
         let mut consuming = self.consuming.write().await;
         
         if *consuming {
@@ -580,7 +582,7 @@ impl ConsumerPlugin for DebugPlugin {
         Ok(())
     }
     
-    async fn process_message(&self, consumer: &QueueConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
         // Display the message only if export is not enabled
         let export_enabled = *self.export_enabled.read().await;
         if !export_enabled {
@@ -681,6 +683,13 @@ impl ConsumerPlugin for DebugPlugin {
                             current_size, threshold);
                 }
             }
+            QueueEvent::MessagesDropped { count, first_sequence, last_sequence, .. } => {
+                // Dropped messages never reach this consumer, so say which ones are missing
+                if config.verbose && !export_enabled {
+                    println!("\n!!! MESSAGES DROPPED: {} (sequences {}-{}) !!!\n",
+                            count, first_sequence, last_sequence);
+                }
+            }
             _ => {
                 // Other events are logged but not displayed
                 log::debug!("Debug plugin received queue event: {:?}", event);
@@ -723,6 +732,8 @@ impl ConsumerPlugin for DebugPlugin {
             high_frequency_capable: true, // Can handle high message rates
             preferred_batch_size: 1, // Process one at a time for display
             requires_ordered_delivery: true, // Display in order
+            max_memory: None,
+            max_message_time: None, // Display speed depends on the terminal
         }
     }
 }
@@ -934,6 +945,9 @@ mod tests {
             author: "Test Author".to_string(),
             message: "Test commit".to_string(),
             timestamp: 1234567890,
+            committer: "Test Author".to_string(),
+            author_timestamp: 1234567890,
+            parents: vec![],
             changed_files: vec![],
         };
         let message = ScanMessage::new(header, data);
@@ -1006,26 +1020,18 @@ mod tests {
         use crate::notifications::{AsyncNotificationManager};
         use crate::notifications::events::PluginEvent;
         
-        let mut plugin = DebugPlugin::new();
-        
-        // Test initialization without notification manager
-        let context_without = create_test_context();
-        plugin.initialize(&context_without).await.unwrap();
-        assert!(plugin.notification_manager.is_none());
-        
-        // Test initialization with notification manager
+        // The notification manager is a required dependency, kept through initialization
         let notification_manager = Arc::new(
             AsyncNotificationManager::<PluginEvent>::new()
         );
-        let context_with = create_test_context()
-            .with_notification_manager(notification_manager.clone());
-        
-        let mut plugin2 = DebugPlugin::new();
-        plugin2.initialize(&context_with).await.unwrap();
-        assert!(plugin2.notification_manager.is_some());
+        let mut plugin = DebugPlugin::with_dependencies(
+            crate::plugin::PluginSettings::default(),
+            notification_manager.clone(),
+        );
+        plugin.initialize(&create_test_context()).await.unwrap();
         
         // Verify the notification manager is the same instance
-        assert!(plugin2.notification_manager.is_some());
+        assert!(Arc::ptr_eq(&plugin.notification_manager, &notification_manager));
     }
     
     #[tokio::test]
@@ -1136,7 +1142,6 @@ mod tests {
         // without a complex mock setup, but we can verify the export enabled state
         // and that create_data_export was called successfully
         assert!(*plugin.export_enabled.read().await);
-        assert!(plugin.notification_manager.is_some());
     }
     
     fn create_test_context() -> PluginContext {
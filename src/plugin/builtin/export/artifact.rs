@@ -0,0 +1,107 @@
+//! Content-Addressed Output Files
+//!
+//! With `--content-hash` each written output file is renamed to carry a short
+//! hash of its content (`report.json` becomes `report-3fa2c1.json`) and copied
+//! to a stable `report-latest.json`. The hash is git's blob hash of the file, as
+//! `git hash-object` prints it, so an unchanged report keeps its name from one
+//! run to the next and CI jobs can skip uploading it again.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Hex digits of the content hash in file names
+pub const HASH_LENGTH: usize = 6;
+
+/// Name suffix of the copy of the most recent output
+pub const LATEST_SUFFIX: &str = "latest";
+
+/// An output file named after its content
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artifact {
+    /// File named with the content hash
+    pub path: PathBuf,
+    /// Copy under the stable `-latest` name
+    pub latest: PathBuf,
+    /// Whether a file with the same content already existed
+    pub unchanged: bool,
+}
+
+/// Short git blob hash of some content
+pub fn content_hash(content: &[u8]) -> String {
+    let id = gix::objs::compute_hash(gix::hash::Kind::Sha1, gix::objs::Kind::Blob, content)
+        .expect("SHA-1 hashing of in-memory content cannot fail");
+    id.to_hex_with_len(HASH_LENGTH).to_string()
+}
+
+/// The path with a suffix added to its file stem, before the extension
+fn suffixed_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    match path.extension() {
+        Some(extension) => path.with_file_name(format!("{}-{}.{}", stem, suffix, extension.to_string_lossy())),
+        None => path.with_file_name(format!("{}-{}", stem, suffix)),
+    }
+}
+
+/// Name of an output file with the given content hash
+pub fn hashed_path(path: &Path, hash: &str) -> PathBuf {
+    suffixed_path(path, hash)
+}
+
+/// Stable name of the most recent output written to a path
+pub fn latest_path(path: &Path) -> PathBuf {
+    suffixed_path(path, LATEST_SUFFIX)
+}
+
+/// Rename a written output file after its content and refresh its `-latest` copy
+///
+/// When a file with the same content already exists the new file is removed
+/// and the existing one is reported as unchanged.
+pub fn publish(path: &Path) -> io::Result<Artifact> {
+    let content = std::fs::read(path)?;
+    let hashed = hashed_path(path, &content_hash(&content));
+    let unchanged = hashed.exists();
+    if unchanged {
+        std::fs::remove_file(path)?;
+    } else {
+        std::fs::rename(path, &hashed)?;
+    }
+    let latest = latest_path(path);
+    std::fs::copy(&hashed, &latest)?;
+    Ok(Artifact { path: hashed, latest, unchanged })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_matches_git() {
+        // `printf 'hello\n' | git hash-object --stdin`
+        assert_eq!(content_hash(b"hello\n"), "ce0136");
+        assert_eq!(hashed_path(Path::new("out/report.json"), "ce0136"), PathBuf::from("out/report-ce0136.json"));
+        assert_eq!(latest_path(Path::new("out/report")), PathBuf::from("out/report-latest"));
+    }
+
+    #[test]
+    fn test_publish_detects_unchanged_output() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("report.json");
+
+        std::fs::write(&output, "{}\n").unwrap();
+        let first = publish(&output).unwrap();
+        assert!(!first.unchanged);
+        assert!(!output.exists());
+        assert_eq!(std::fs::read_to_string(&first.latest).unwrap(), "{}\n");
+
+        std::fs::write(&output, "{}\n").unwrap();
+        let second = publish(&output).unwrap();
+        assert!(second.unchanged);
+        assert_eq!(second.path, first.path);
+
+        std::fs::write(&output, "{\"commits\": 1}\n").unwrap();
+        let third = publish(&output).unwrap();
+        assert!(!third.unchanged);
+        assert_ne!(third.path, first.path);
+        assert_eq!(std::fs::read_to_string(dir.path().join("report-latest.json")).unwrap(), "{\"commits\": 1}\n");
+    }
+}
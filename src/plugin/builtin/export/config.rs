@@ -1,39 +1,133 @@
 //! Export configuration types and defaults
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use super::formats::annotations::AnnotationStyle;
+use super::formats::columns::SortKey;
 use super::formats::csv::QuotingStyle;
+use super::formats::filter::RowFilter;
+use super::formats::html::Theme;
+use super::formats::totals::Totals;
+use super::formats::{Layout, Transpose};
 
 #[derive(Debug, Clone)]
 pub struct ExportConfig {
     pub output_format: ExportFormat,
+    /// Whether the format was chosen by an option or the output file's extension
+    /// rather than left to the exports' preferred formats
+    pub format_chosen: bool,
     pub output_file: Option<PathBuf>,
     pub csv_delimiter: String,
     pub csv_quote_char: String,
     pub csv_quoting_style: QuotingStyle,
+    /// Whether CSV values are written unformatted, ignoring column format hints
+    pub csv_raw: bool,
+    /// Whether CSV output is one table per export or a single long-format table
+    pub layout: Layout,
     pub template_file: Option<PathBuf>,
+    /// Directory (or glob) of templates that the template can include or extend
+    pub template_dir: Option<PathBuf>,
+    /// User variables passed to templates with `--template-var key=value`
+    pub template_vars: HashMap<String, String>,
+    pub transpose: Transpose,
+    /// Expression the rows of each table must meet to be exported
+    pub row_filter: Option<RowFilter>,
+    /// Columns each table is cut down to, in this order (all columns if empty)
+    pub columns: Vec<String>,
+    /// Columns the rows of each table are sorted by, overriding the plugin's order
+    pub sort: Vec<SortKey>,
+    /// Which tables end with a totals row in console, CSV, HTML and Markdown output
+    pub totals: Totals,
+    /// Whether console output lists column descriptions below each table
+    pub describe_columns: bool,
+    /// Colour scheme of HTML reports
+    pub html_theme: Theme,
+    /// Whether HTML reports include charts drawn from the exports
+    pub html_charts: bool,
+    /// Further outputs written from the same collected data
+    pub additional_targets: Vec<ExportTarget>,
+    /// Whether output files are named after a hash of their content, with a `-latest` copy
+    pub content_hash: bool,
+}
+
+/// One output of an export run: a format and the file it is written to (console if none)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTarget {
+    pub format: ExportFormat,
+    pub output_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ExportFormat {
     Console,
     Json,
+    Ndjson,
     Csv,
     Xml,
     Yaml,
     Html,
     Markdown,
     Template,
+    /// SQLite database, one table per export (requires the `sqlite` feature)
+    Sqlite,
+    /// Parquet files, one per export or a single long-format file (requires the `parquet` feature)
+    Parquet,
+    /// Pull and merge request annotations of findings about files
+    Annotations(AnnotationStyle),
+}
+
+impl ExportFormat {
+    /// Whether output is written straight to a file rather than rendered as text
+    pub fn is_binary(&self) -> bool {
+        matches!(self, ExportFormat::Sqlite | ExportFormat::Parquet)
+    }
 }
 
 impl Default for ExportConfig {
     fn default() -> Self {
         Self {
             output_format: ExportFormat::Console,
+            format_chosen: false,
             output_file: None,
             csv_delimiter: ",".to_string(),
             csv_quote_char: "\"".to_string(),
             csv_quoting_style: QuotingStyle::Minimal,
+            csv_raw: false,
+            layout: Layout::default(),
             template_file: None,
+            template_dir: None,
+            template_vars: HashMap::new(),
+            transpose: Transpose::default(),
+            row_filter: None,
+            columns: Vec::new(),
+            sort: Vec::new(),
+            totals: Totals::default(),
+            describe_columns: false,
+            html_theme: Theme::default(),
+            html_charts: false,
+            additional_targets: Vec::new(),
+            content_hash: false,
+        }
+    }
+}
+
+impl ExportConfig {
+    /// All outputs of an export run, starting with the primary format and file
+    pub fn targets(&self) -> Vec<ExportTarget> {
+        let primary = ExportTarget {
+            format: self.output_format,
+            output_file: self.output_file.clone(),
+        };
+        std::iter::once(primary).chain(self.additional_targets.iter().cloned()).collect()
+    }
+    
+    /// Configuration for writing a single target with the shared format options
+    pub fn for_target(&self, target: &ExportTarget) -> ExportConfig {
+        ExportConfig {
+            output_format: target.format,
+            output_file: target.output_file.clone(),
+            additional_targets: Vec::new(),
+            ..self.clone()
         }
     }
 }
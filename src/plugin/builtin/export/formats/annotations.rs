@@ -0,0 +1,292 @@
+//! Code review annotation formats
+//!
+//! Findings about files (such as hotspots and technical debt) are written in the
+//! formats CI systems show inline on pull and merge requests:
+//!
+//! - `github`: GitHub Actions workflow commands (`::warning file=...::message`),
+//!   printed by a workflow step to annotate the run and its pull request
+//! - `github-json`: check run annotations (`path`, `start_line`, `annotation_level`, ...)
+//!   as accepted by the GitHub Checks API
+//! - `gitlab`: a GitLab Code Quality report, uploaded as the `codequality` artifact
+//!
+//! Only findings tables are annotated; other exports are left out. A finding's
+//! location is a file path, optionally followed by `:line` or `:start-end`;
+//! without a line the annotation is placed on the first line of the file.
+//! Findings about periods or commits rather than files are written as workflow
+//! commands without a file and left out of the JSON reports, which need a path.
+
+use super::FormatExporter;
+use crate::plugin::PluginResult;
+use crate::plugin::data_export::{DataPayload, FindingSeverity, PluginDataExport, Value};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Annotation format written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationStyle {
+    /// GitHub Actions workflow commands
+    GithubCommands,
+    /// GitHub check run annotations as JSON
+    GithubJson,
+    /// GitLab Code Quality report
+    GitlabCodeQuality,
+}
+
+impl AnnotationStyle {
+    /// Suffix of files written with `--formats`, which has no extension of its own to use
+    pub fn file_suffix(&self) -> &'static str {
+        match self {
+            Self::GithubCommands => "github.txt",
+            Self::GithubJson => "github.json",
+            Self::GitlabCodeQuality => "gitlab.json",
+        }
+    }
+}
+
+/// A finding placed on a file
+#[derive(Debug, Clone, PartialEq)]
+struct Annotation {
+    path: Option<String>,
+    start_line: u32,
+    end_line: u32,
+    severity: FindingSeverity,
+    rule: String,
+    message: String,
+    metrics: String,
+    source: String,
+}
+
+impl Annotation {
+    /// Message with the measurements behind the finding
+    fn details(&self) -> String {
+        if self.metrics.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{} ({})", self.message, self.metrics)
+        }
+    }
+
+    fn title(&self) -> String {
+        format!("{} ({})", self.rule, self.source)
+    }
+}
+
+/// Annotation formatter
+pub struct AnnotationFormatter {
+    style: AnnotationStyle,
+}
+
+impl AnnotationFormatter {
+    pub fn new(style: AnnotationStyle) -> Self {
+        Self { style }
+    }
+
+    fn github_commands(&self, annotations: &[Annotation]) -> String {
+        let mut output = String::new();
+        for annotation in annotations {
+            let level = match annotation.severity {
+                FindingSeverity::Low => "notice",
+                FindingSeverity::Medium => "warning",
+                FindingSeverity::High | FindingSeverity::Critical => "error",
+            };
+            let mut properties = Vec::new();
+            if let Some(path) = &annotation.path {
+                properties.push(format!("file={}", escape_property(path)));
+                properties.push(format!("line={}", annotation.start_line));
+                if annotation.end_line != annotation.start_line {
+                    properties.push(format!("endLine={}", annotation.end_line));
+                }
+            }
+            properties.push(format!("title={}", escape_property(&annotation.title())));
+            output.push_str(&format!("::{} {}::{}\n", level, properties.join(","), escape_data(&annotation.details())));
+        }
+        output
+    }
+
+    fn github_json(&self, annotations: &[Annotation]) -> PluginResult<String> {
+        let annotations: Vec<_> = annotations.iter()
+            .filter_map(|annotation| Some(json!({
+                "path": annotation.path.as_ref()?,
+                "start_line": annotation.start_line,
+                "end_line": annotation.end_line,
+                "annotation_level": match annotation.severity {
+                    FindingSeverity::Low => "notice",
+                    FindingSeverity::Medium => "warning",
+                    FindingSeverity::High | FindingSeverity::Critical => "failure",
+                },
+                "title": annotation.title(),
+                "message": annotation.message,
+                "raw_details": annotation.metrics,
+            })))
+            .collect();
+        to_json(&annotations)
+    }
+
+    fn gitlab_code_quality(&self, annotations: &[Annotation]) -> PluginResult<String> {
+        let issues: Vec<_> = annotations.iter()
+            .filter_map(|annotation| {
+                let path = annotation.path.as_ref()?;
+                // Stable across runs while the finding stays on the same file, even as its measurements change
+                let key = format!("{}\0{}\0{}\0{}", annotation.source, annotation.rule, path, annotation.start_line);
+                Some(json!({
+                    "description": annotation.details(),
+                    "check_name": annotation.rule,
+                    "fingerprint": format!("{:016x}", crate::cache::fnv1a_64(key.as_bytes())),
+                    "severity": match annotation.severity {
+                        FindingSeverity::Low => "minor",
+                        FindingSeverity::Medium => "major",
+                        FindingSeverity::High => "critical",
+                        FindingSeverity::Critical => "blocker",
+                    },
+                    "engine_name": "gstats",
+                    "location": {
+                        "path": path,
+                        "lines": { "begin": annotation.start_line, "end": annotation.end_line },
+                    },
+                }))
+            })
+            .collect();
+        to_json(&issues)
+    }
+}
+
+impl FormatExporter for AnnotationFormatter {
+    fn format_data(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
+        let annotations = annotations(data);
+        match self.style {
+            AnnotationStyle::GithubCommands => Ok(self.github_commands(&annotations)),
+            AnnotationStyle::GithubJson => self.github_json(&annotations),
+            AnnotationStyle::GitlabCodeQuality => self.gitlab_code_quality(&annotations),
+        }
+    }
+}
+
+/// Annotations for the rows of every findings table, in table order
+fn annotations(data: &[Arc<PluginDataExport>]) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    for export in data.iter().filter(|export| export.is_findings()) {
+        let DataPayload::Rows(rows) = &export.data else {
+            continue;
+        };
+        for row in rows.iter() {
+            let text = |index: usize| match row.values.get(index) {
+                Some(Value::Null) | None => String::new(),
+                Some(value) => value.to_string(),
+            };
+            let Some(severity) = FindingSeverity::parse(&text(1)) else {
+                continue;
+            };
+            let (path, (start_line, end_line)) = match split_location(&text(0)) {
+                Some((path, lines)) => (Some(path), lines.unwrap_or((1, 1))),
+                None => (None, (1, 1)),
+            };
+            let source = text(6);
+            annotations.push(Annotation {
+                path,
+                start_line,
+                end_line,
+                severity,
+                rule: text(3),
+                message: text(4),
+                metrics: text(5),
+                source: if source.is_empty() { export.plugin_id.clone() } else { source },
+            });
+        }
+    }
+    annotations
+}
+
+/// A finding's file path and lines, or `None` when it is about a period or commit rather than a file
+fn split_location(location: &str) -> Option<(String, Option<(u32, u32)>)> {
+    let location = location.trim();
+    let is_period = location.chars().all(|c| c.is_ascii_digit() || c == '-');
+    let is_commit = (7..=40).contains(&location.len()) && location.chars().all(|c| c.is_ascii_hexdigit());
+    if location.is_empty() || is_period || is_commit {
+        return None;
+    }
+    if let Some((path, lines)) = location.rsplit_once(':') {
+        let range = match lines.split_once('-') {
+            Some((start, end)) => start.parse().ok().zip(end.parse().ok()),
+            None => lines.parse().ok().map(|line| (line, line)),
+        };
+        if let Some((start, end)) = range.filter(|(start, end): &(u32, u32)| *start > 0 && end >= start) {
+            return Some((path.to_string(), Some((start, end))));
+        }
+    }
+    Some((location.to_string(), None))
+}
+
+/// Escape a workflow command message
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> PluginResult<String> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| crate::plugin::PluginError::execution_failed(format!("Failed to serialize annotations: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{findings_export, Finding};
+
+    fn findings() -> Vec<Arc<PluginDataExport>> {
+        let findings = vec![
+            Finding::new("hotspot", FindingSeverity::High, "maintainability", "Complex file changed 40 times")
+                .with_location("src/engine.rs")
+                .with_metric("score", 8.5),
+            Finding::new("technical-debt", FindingSeverity::Medium, "technical-debt", "Debt score 61.0, about 12 hours")
+                .with_location("src/parse, util.rs:10-24"),
+            Finding::new("mass-deletion", FindingSeverity::Low, "activity", "Removed 5000 lines")
+                .with_location("2024-03-04"),
+        ];
+        vec![Arc::new(findings_export("metrics", &findings).unwrap())]
+    }
+
+    #[test]
+    fn test_github_workflow_commands() {
+        let output = AnnotationFormatter::new(AnnotationStyle::GithubCommands).format_data(&findings()).unwrap();
+        assert_eq!(output.lines().collect::<Vec<_>>(), [
+            "::error file=src/engine.rs,line=1,title=hotspot (metrics)::Complex file changed 40 times (score=8.50)",
+            "::warning file=src/parse%2C util.rs,line=10,endLine=24,title=technical-debt (metrics)::Debt score 61.0, about 12 hours",
+            "::notice title=mass-deletion (metrics)::Removed 5000 lines",
+        ]);
+    }
+
+    #[test]
+    fn test_json_reports_annotate_files() {
+        let github: serde_json::Value = serde_json::from_str(
+            &AnnotationFormatter::new(AnnotationStyle::GithubJson).format_data(&findings()).unwrap()
+        ).unwrap();
+        assert_eq!(github.as_array().unwrap().len(), 2);
+        assert_eq!(github[0]["annotation_level"], "failure");
+        assert_eq!(github[1]["path"], "src/parse, util.rs");
+        assert_eq!((github[1]["start_line"].as_u64(), github[1]["end_line"].as_u64()), (Some(10), Some(24)));
+
+        let gitlab: serde_json::Value = serde_json::from_str(
+            &AnnotationFormatter::new(AnnotationStyle::GitlabCodeQuality).format_data(&findings()).unwrap()
+        ).unwrap();
+        let issues = gitlab.as_array().unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0]["severity"], "critical");
+        assert_eq!(issues[0]["location"]["path"], "src/engine.rs");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 1);
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_locations() {
+        assert_eq!(split_location("src/lib.rs:7"), Some(("src/lib.rs".to_string(), Some((7, 7)))));
+        assert_eq!(split_location("C:/work/lib.rs"), Some(("C:/work/lib.rs".to_string(), None)));
+        assert_eq!(split_location("Makefile"), Some(("Makefile".to_string(), None)));
+        assert_eq!(split_location("2024-03-04"), None);
+        assert_eq!(split_location("3fa2c1d9"), None);
+        assert_eq!(split_location(""), None);
+    }
+}
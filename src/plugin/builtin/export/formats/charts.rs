@@ -0,0 +1,257 @@
+//! Charts for the enhanced HTML report
+//!
+//! Charts are derived from the shape of the collected exports rather than from
+//! particular plugins: a table with `Period` and `Commits` columns becomes the
+//! commit activity line chart, one with `Author` and `Commits` columns the top
+//! authors bar chart, and one with `File` and `Score` columns the hotspot
+//! treemap. The first export matching each shape is used, leaving out any totals
+//! row, and the charts are drawn as SVG by a small embedded script so the report
+//! needs no network access.
+
+use super::totals::is_totals_row;
+use crate::plugin::data_export::{DataPayload, PluginDataExport};
+use crate::plugin::table_match::column_index;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Number of authors shown in the top authors chart
+const TOP_AUTHORS: usize = 10;
+
+/// Number of files shown in the hotspot treemap
+const TREEMAP_FILES: usize = 40;
+
+/// How a chart is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartKind {
+    Line,
+    Bar,
+    Treemap,
+}
+
+/// A chart of labelled values taken from one export
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub title: String,
+    /// Name of the plotted column
+    pub series: String,
+    pub labels: Vec<String>,
+    pub values: Vec<f64>,
+}
+
+/// Shape of export a chart is drawn from
+struct ChartShape {
+    kind: ChartKind,
+    title: &'static str,
+    label_column: &'static str,
+    value_column: &'static str,
+    /// Keep only the largest values, at most this many
+    top: Option<usize>,
+}
+
+const SHAPES: [ChartShape; 3] = [
+    ChartShape { kind: ChartKind::Line, title: "Commit Activity", label_column: "Period", value_column: "Commits", top: None },
+    ChartShape { kind: ChartKind::Bar, title: "Top Authors", label_column: "Author", value_column: "Commits", top: Some(TOP_AUTHORS) },
+    ChartShape { kind: ChartKind::Treemap, title: "Hotspots", label_column: "File", value_column: "Score", top: Some(TREEMAP_FILES) },
+];
+
+/// Charts for the exports that have a recognised shape
+pub fn charts(data: &[Arc<PluginDataExport>]) -> Vec<Chart> {
+    SHAPES.iter()
+        .filter_map(|shape| data.iter().find_map(|export| chart(shape, export)))
+        .collect()
+}
+
+/// Chart of an export if it has the columns of a shape and at least one plottable row
+fn chart(shape: &ChartShape, export: &PluginDataExport) -> Option<Chart> {
+    let DataPayload::Rows(rows) = &export.data else {
+        return None;
+    };
+    let column = |name: &str| column_index(export, name);
+    let (label, value) = (column(shape.label_column)?, column(shape.value_column)?);
+
+    let mut points: Vec<(String, f64)> = rows.iter()
+        .filter(|row| !is_totals_row(row))
+        .filter_map(|row| {
+            let value = row.values.get(value)?.as_f64()?;
+            Some((row.values.get(label)?.to_string(), value))
+        })
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+    if let Some(top) = shape.top {
+        points.sort_by(|a, b| b.1.total_cmp(&a.1));
+        points.truncate(top);
+    }
+
+    let (labels, values) = points.into_iter().unzip();
+    Some(Chart {
+        kind: shape.kind,
+        title: shape.title.to_string(),
+        series: export.schema.columns[value].name.clone(),
+        labels,
+        values,
+    })
+}
+
+/// Chart definitions as a script literal, safe to embed in a `<script>` element
+pub fn charts_json(charts: &[Chart]) -> String {
+    serde_json::to_string(charts)
+        .unwrap_or_else(|_| "[]".to_string())
+        .replace("</", "<\\/")
+}
+
+/// Script drawing each chart definition in `GSTATS_CHARTS` as SVG into `#charts`
+pub const CHART_SCRIPT: &str = r#"(function () {
+    const NS = "http://www.w3.org/2000/svg";
+    const W = 720, H = 280, PAD = 40;
+    const container = document.getElementById("charts");
+    function el(name, attrs, text) {
+        const node = document.createElementNS(NS, name);
+        for (const key in attrs) node.setAttribute(key, attrs[key]);
+        if (text !== undefined) node.textContent = text;
+        return node;
+    }
+    function tip(node, label, value) {
+        node.appendChild(el("title", {}, label + ": " + value));
+        return node;
+    }
+    function line(svg, chart) {
+        const max = Math.max(...chart.values, 1);
+        const step = chart.values.length > 1 ? (W - 2 * PAD) / (chart.values.length - 1) : 0;
+        const x = i => PAD + i * step, y = v => H - PAD - v / max * (H - 2 * PAD);
+        svg.appendChild(el("polyline", { class: "series", fill: "none",
+            points: chart.values.map((v, i) => x(i) + "," + y(v)).join(" ") }));
+        chart.values.forEach((v, i) => svg.appendChild(tip(el("circle", { class: "point", cx: x(i), cy: y(v), r: 3 }), chart.labels[i], v)));
+        svg.appendChild(el("text", { class: "axis", x: PAD, y: H - 10 }, chart.labels[0]));
+        svg.appendChild(el("text", { class: "axis", x: W - PAD, y: H - 10, "text-anchor": "end" }, chart.labels[chart.labels.length - 1]));
+        svg.appendChild(el("text", { class: "axis", x: 4, y: PAD }, max));
+    }
+    function bar(svg, chart) {
+        const max = Math.max(...chart.values, 1);
+        const height = (H - PAD) / chart.values.length;
+        chart.values.forEach((v, i) => {
+            const width = v / max * (W - 220);
+            svg.appendChild(el("text", { class: "axis", x: 200, y: i * height + height / 2 + 4, "text-anchor": "end" }, chart.labels[i]));
+            svg.appendChild(tip(el("rect", { class: "bar", x: 210, y: i * height + 2, width: width, height: height - 4 }), chart.labels[i], v));
+            svg.appendChild(el("text", { class: "axis", x: 214 + width, y: i * height + height / 2 + 4 }, v));
+        });
+    }
+    function treemap(svg, chart) {
+        const items = chart.values.map((v, i) => ({ label: chart.labels[i], value: v })).filter(item => item.value > 0);
+        function layout(items, x, y, w, h) {
+            if (items.length === 0) return;
+            if (items.length === 1) {
+                const item = items[0];
+                svg.appendChild(tip(el("rect", { class: "cell", x: x, y: y, width: w, height: h }), item.label, item.value));
+                if (w > 60 && h > 16) svg.appendChild(el("text", { class: "cell-label", x: x + 4, y: y + 14 }, item.label.split("/").pop()));
+                return;
+            }
+            const total = items.reduce((sum, item) => sum + item.value, 0);
+            let split = 0, first = 0;
+            while (split < items.length - 1 && first + items[split].value < total / 2) first += items[split++].value;
+            split = Math.max(split, 1);
+            first = items.slice(0, split).reduce((sum, item) => sum + item.value, 0);
+            const share = first / total;
+            if (w >= h) {
+                layout(items.slice(0, split), x, y, w * share, h);
+                layout(items.slice(split), x + w * share, y, w * (1 - share), h);
+            } else {
+                layout(items.slice(0, split), x, y, w, h * share);
+                layout(items.slice(split), x, y + h * share, w, h * (1 - share));
+            }
+        }
+        layout(items, 0, 0, W, H);
+    }
+    const draw = { line: line, bar: bar, treemap: treemap };
+    GSTATS_CHARTS.forEach(chart => {
+        const figure = document.createElement("figure");
+        const caption = document.createElement("figcaption");
+        caption.textContent = chart.title + " (" + chart.series + ")";
+        const svg = el("svg", { viewBox: "0 0 " + W + " " + H, role: "img", "aria-label": chart.title });
+        draw[chart.kind](svg, chart);
+        figure.appendChild(caption);
+        figure.appendChild(svg);
+        container.appendChild(figure);
+    });
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, ColumnType, DataExportType, DataSchema, ExportHints, Row, Value};
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+
+    fn export(title: &str, columns: &[(&str, ColumnType)], rows: Vec<Vec<Value>>) -> Arc<PluginDataExport> {
+        Arc::new(PluginDataExport {
+            plugin_id: "test".to_string(),
+            title: title.to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema {
+                columns: columns.iter().map(|(name, data_type)| ColumnDef::new(*name, *data_type)).collect(),
+                metadata: HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(rows.into_iter().map(Row::new).collect())),
+            export_hints: ExportHints::default(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    #[test]
+    fn test_charts_from_export_shapes() {
+        let authors = export("Commit Analysis", &[("Author", ColumnType::String), ("Commits", ColumnType::Integer)], vec![
+            vec![Value::String("alice".to_string()), Value::Integer(3)],
+            vec![Value::String("bob".to_string()), Value::Integer(7)],
+        ]);
+        let activity = export("Working Set", &[("Period", ColumnType::String), ("Commits", ColumnType::Integer)], vec![
+            vec![Value::String("2024-01".to_string()), Value::Integer(4)],
+            vec![Value::String("2024-02".to_string()), Value::Integer(6)],
+        ]);
+        let other = export("Other", &[("Name", ColumnType::String)], vec![vec![Value::String("x".to_string())]]);
+
+        let charts = charts(&[authors, other, activity]);
+        assert_eq!(charts.len(), 2);
+        assert_eq!(charts[0].kind, ChartKind::Line);
+        assert_eq!(charts[0].labels, vec!["2024-01", "2024-02"]);
+        assert_eq!(charts[1].kind, ChartKind::Bar);
+        assert_eq!(charts[1].labels, vec!["bob", "alice"]);
+        assert_eq!(charts[1].values, vec![7.0, 3.0]);
+    }
+
+    #[test]
+    fn test_totals_rows_are_not_charted() {
+        use crate::plugin::builtin::export::formats::totals::Totals;
+
+        let authors = export("Commit Analysis", &[("Author", ColumnType::String), ("Commits", ColumnType::Integer)], vec![
+            vec![Value::String("alice".to_string()), Value::Integer(3)],
+            vec![Value::String("bob".to_string()), Value::Integer(7)],
+        ]);
+        let totalled = Totals::parse("sum").unwrap().apply(&[authors]);
+        let DataPayload::Rows(rows) = &totalled[0].data else { panic!("expected rows") };
+        assert_eq!(rows.len(), 3);
+
+        let charts = charts(&totalled);
+        assert_eq!(charts[0].labels, vec!["bob", "alice"]);
+        assert_eq!(charts[0].values, vec![7.0, 3.0]);
+    }
+
+    #[test]
+    fn test_charts_json_escapes_script_end() {
+        let chart = Chart {
+            kind: ChartKind::Bar,
+            title: "Top Authors".to_string(),
+            series: "Commits".to_string(),
+            labels: vec!["</script>".to_string()],
+            values: vec![1.0],
+        };
+        let json = charts_json(&[chart]);
+        assert!(!json.contains("</script>"));
+        assert!(json.contains("\"kind\":\"bar\""));
+    }
+}
@@ -0,0 +1,196 @@
+//! Column Selection and Sorting
+//!
+//! Reshapes tables before they are formatted: `--sort` orders the rows of each table
+//! by one or more of its columns and `--columns` keeps a subset of its columns in the
+//! order given. Both apply to any plugin's tables by column name, ignoring case
+//! and punctuation as `--where` does.
+
+use crate::plugin::{PluginError, PluginResult};
+use crate::plugin::data_export::{DataPayload, PluginDataExport, Row, Value};
+use crate::plugin::table_match::column_index;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// A column to sort table rows by, and its direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: String,
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Parse `column`, `column:asc` or `column:desc`
+    pub fn parse(key: &str) -> Option<Self> {
+        let (column, direction) = match key.rsplit_once(':') {
+            Some((column, direction)) => (column, direction),
+            None => (key, "asc"),
+        };
+        let descending = match direction.to_lowercase().as_str() {
+            "asc" | "ascending" => false,
+            "desc" | "descending" => true,
+            _ => return None,
+        };
+        let column = column.trim();
+        (!column.is_empty()).then(|| Self { column: column.to_string(), descending })
+    }
+}
+
+/// The exports with their tables sorted and cut down to the chosen columns
+///
+/// A table is only reshaped by the columns it has, so one `--columns` list can serve
+/// several plugins; a name no table has is an error, as it is most likely a typo.
+pub fn reshape(data: &[Arc<PluginDataExport>], columns: &[String], sort: &[SortKey]) -> PluginResult<Vec<Arc<PluginDataExport>>> {
+    if columns.is_empty() && sort.is_empty() {
+        return Ok(data.to_vec());
+    }
+    let tables: Vec<&PluginDataExport> = data.iter()
+        .map(|export| export.as_ref())
+        .filter(|export| matches!(export.data, DataPayload::Rows(_)))
+        .collect();
+    if !tables.is_empty() {
+        let names = sort.iter().map(|key| ("--sort", &key.column)).chain(columns.iter().map(|column| ("--columns", column)));
+        for (option, name) in names {
+            if !tables.iter().any(|export| column_index(export, name).is_some()) {
+                return Err(PluginError::invalid_argument(option, &format!("No table has a column named '{}'", name)));
+            }
+        }
+    }
+    Ok(data.iter().map(|export| reshape_export(export, columns, sort)).collect())
+}
+
+/// An export with its table sorted and cut down to the chosen columns it has
+pub fn reshape_export(export: &Arc<PluginDataExport>, columns: &[String], sort: &[SortKey]) -> Arc<PluginDataExport> {
+    let DataPayload::Rows(rows) = &export.data else {
+        return Arc::clone(export);
+    };
+    let keys: Vec<(usize, bool)> = sort.iter()
+        .filter_map(|key| column_index(export, &key.column).map(|index| (index, key.descending)))
+        .collect();
+    let selected: Vec<usize> = columns.iter()
+        .filter_map(|column| column_index(export, column))
+        .collect();
+    if keys.is_empty() && selected.is_empty() {
+        return Arc::clone(export);
+    }
+
+    let mut rows = rows.as_ref().clone();
+    if !keys.is_empty() {
+        rows.sort_by(|a, b| {
+            keys.iter()
+                .map(|(index, descending)| compare(a.values.get(*index), b.values.get(*index), *descending))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+    let mut export = export.as_ref().clone();
+    if !selected.is_empty() {
+        rows = rows.into_iter()
+            .map(|row| Row {
+                values: selected.iter().map(|index| row.values.get(*index).cloned().unwrap_or(Value::Null)).collect(),
+                metadata: row.metadata,
+            })
+            .collect();
+        export.schema.columns = selected.iter().map(|index| export.schema.columns[*index].clone()).collect();
+    }
+    export.data = DataPayload::Rows(Arc::new(rows));
+    Arc::new(export)
+}
+
+/// Order two table values, keeping empty values last whichever the direction
+fn compare(a: Option<&Value>, b: Option<&Value>, descending: bool) -> Ordering {
+    let (a, b) = match (a.filter(|value| !value.is_null()), b.filter(|value| !value.is_null())) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a), Some(b)) => (a, b),
+    };
+    let ordering = match (a, b) {
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+        (Value::Timestamp(a), Value::Timestamp(b)) => a.cmp(b),
+        (Value::Duration(a), Value::Duration(b)) => a.cmp(b),
+        _ => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => a.to_string().cmp(&b.to_string()),
+        },
+    };
+    if descending { ordering.reverse() } else { ordering }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, ColumnType, DataExportType, DataSchema, ExportHints};
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+
+    fn authors() -> Arc<PluginDataExport> {
+        let row = |author: &str, commits: Value, lines: i64| Row::new(vec![Value::String(author.to_string()), commits, Value::Integer(lines)]);
+        Arc::new(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Authors".to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema {
+                columns: vec![
+                    ColumnDef::new("Author", ColumnType::String),
+                    ColumnDef::new("Commits", ColumnType::Integer),
+                    ColumnDef::new("Lines", ColumnType::Integer),
+                ],
+                metadata: HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(vec![
+                row("carol", Value::Integer(2), 40),
+                row("alice", Value::Null, 10),
+                row("bob", Value::Integer(5), 40),
+            ])),
+            export_hints: ExportHints::default(),
+            timestamp: SystemTime::UNIX_EPOCH,
+            repository: None,
+        })
+    }
+
+    fn cells(export: &PluginDataExport) -> Vec<Vec<String>> {
+        match &export.data {
+            DataPayload::Rows(rows) => rows.iter().map(|row| row.values.iter().map(|value| value.to_string()).collect()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_sort_keys_parse_directions() {
+        assert_eq!(SortKey::parse("Commits"), Some(SortKey { column: "Commits".to_string(), descending: false }));
+        assert_eq!(SortKey::parse("Commits:DESC"), Some(SortKey { column: "Commits".to_string(), descending: true }));
+        assert_eq!(SortKey::parse("Commits:sideways"), None);
+        assert_eq!(SortKey::parse(":desc"), None);
+    }
+
+    #[test]
+    fn test_rows_sort_by_several_columns_with_empty_values_last() {
+        let sort = [SortKey::parse("lines:desc").unwrap(), SortKey::parse("author").unwrap()];
+        let data = reshape(&[authors()], &[], &sort).unwrap();
+        let order: Vec<String> = cells(&data[0]).into_iter().map(|row| row[0].clone()).collect();
+        assert_eq!(order, ["bob", "carol", "alice"]);
+
+        let data = reshape(&[authors()], &[], &[SortKey::parse("Commits:desc").unwrap()]).unwrap();
+        let order: Vec<String> = cells(&data[0]).into_iter().map(|row| row[0].clone()).collect();
+        assert_eq!(order, ["bob", "carol", "alice"]);
+    }
+
+    #[test]
+    fn test_columns_are_selected_in_the_order_given() {
+        let columns = ["lines".to_string(), "Author".to_string(), "current_size".to_string()];
+        let mut other = PluginDataExport::clone(&authors());
+        other.schema.columns[2].name = "Current Size".to_string();
+        let data = reshape(&[authors(), Arc::new(other)], &columns, &[SortKey::parse("Author").unwrap()]).unwrap();
+
+        let names: Vec<&str> = data[0].schema.columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(names, ["Lines", "Author"]);
+        assert_eq!(cells(&data[0])[0], ["10", "alice"]);
+        let names: Vec<&str> = data[1].schema.columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(names, ["Author", "Current Size"]);
+
+        assert!(reshape(&[authors()], &["Churn".to_string()], &[]).is_err());
+        assert!(reshape(&[authors()], &[], &[SortKey::parse("Churn").unwrap()]).is_err());
+    }
+}
@@ -1,15 +1,23 @@
 //! Console table format for terminal output
 
-use super::FormatExporter;
+use super::{column_descriptions, indicator, FormatExporter, Transpose};
+use super::values::format_value;
 use crate::plugin::PluginResult;
-use crate::plugin::data_export::{PluginDataExport, DataPayload};
+use crate::plugin::data_export::{PluginDataExport, DataPayload, Matrix, TreeNode};
 use crate::display::{ColourManager, TableBuilder};
 use std::sync::Arc;
 
+/// Shading used for matrix heat rendering, from coolest to hottest
+const HEAT_SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
 /// Console table formatter
 pub struct ConsoleFormatter {
     /// Optional color manager for styled output
     pub colour_manager: Option<Arc<ColourManager>>,
+    /// When to render single-row tables as `field: value` lines
+    pub transpose: Transpose,
+    /// Whether column descriptions are listed below each table
+    pub describe_columns: bool,
 }
 
 impl ConsoleFormatter {
@@ -17,6 +25,8 @@ impl ConsoleFormatter {
     pub fn new() -> Self {
         Self {
             colour_manager: None,
+            transpose: Transpose::default(),
+            describe_columns: false,
         }
     }
     
@@ -24,9 +34,23 @@ impl ConsoleFormatter {
     pub fn with_colors(colour_manager: Arc<ColourManager>) -> Self {
         Self {
             colour_manager: Some(colour_manager),
+            transpose: Transpose::default(),
+            describe_columns: false,
         }
     }
     
+    /// Set when single-row tables are rendered vertically
+    pub fn with_transpose(mut self, transpose: Transpose) -> Self {
+        self.transpose = transpose;
+        self
+    }
+    
+    /// Set whether column descriptions are listed below each table
+    pub fn with_describe_columns(mut self, describe_columns: bool) -> Self {
+        self.describe_columns = describe_columns;
+        self
+    }
+    
     /// Format with color support using stored ColourManager
     pub fn format_with_colors(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
         if let Some(ref colour_manager) = self.colour_manager {
@@ -52,6 +76,14 @@ impl ConsoleFormatter {
                 output.push_str(&format!("{}\n\n", desc));
             }
             
+            // Wide single-row tables read better as one field per line
+            if let Some(fields) = self.transpose.fields(export) {
+                output.push_str(&format_fields(&fields, colour_manager));
+                output.push_str(&self.format_descriptions(export, colour_manager));
+                output.push('\n');
+                continue;
+            }
+            
             // Format data based on type using TableBuilder
             match &export.data {
                 DataPayload::Rows(rows) => {
@@ -65,10 +97,19 @@ impl ConsoleFormatter {
                         let mut table = TableBuilder::new().headers(headers);
                         
                         for row in rows.iter() {
-                            let row_values: Vec<String> = row.values.iter()
-                                .map(|v| v.to_string())
+                            let indicators: Vec<_> = row.values.iter().enumerate()
+                                .map(|(i, value)| export.schema.columns.get(i).and_then(|column| indicator(column, value)))
+                                .collect();
+                            let row_values: Vec<String> = row.values.iter().enumerate().zip(&indicators)
+                                .map(|((i, value), indicator)| {
+                                    let text = format_value(export.schema.columns.get(i), value);
+                                    match indicator {
+                                        Some(indicator) => colour_manager.indicator_text(&text, *indicator),
+                                        None => text,
+                                    }
+                                })
                                 .collect();
-                            table = table.add_row(row_values);
+                            table = table.add_row_with_indicators(row_values, indicators);
                         }
                         
                         let table_output = table.build_with_colors(colour_manager);
@@ -103,9 +144,21 @@ impl ConsoleFormatter {
                     }
                 }
                 
+                DataPayload::Matrix(matrix) => {
+                    // Add 2-space indent to each line to match table output
+                    for line in format_matrix_heat(matrix, colour_manager).lines() {
+                        output.push_str("  ");
+                        output.push_str(line);
+                        output.push('\n');
+                    }
+                }
+                
                 DataPayload::Tree(root) => {
-                    output.push_str(&format!("Tree: {}\n", colour_manager.success(&root.label)));
-                    // TODO: Implement proper tree formatting with colors
+                    for line in format_tree(root, colour_manager).lines() {
+                        output.push_str("  ");
+                        output.push_str(line);
+                        output.push('\n');
+                    }
                 }
                 
                 DataPayload::Raw(raw) => {
@@ -118,6 +171,7 @@ impl ConsoleFormatter {
                 }
             }
             
+            output.push_str(&self.format_descriptions(export, colour_manager));
             output.push('\n');
         }
         
@@ -125,6 +179,135 @@ impl ConsoleFormatter {
     }
 }
 
+impl ConsoleFormatter {
+    /// Column descriptions of an export as `name: description` lines, when enabled
+    fn format_descriptions(&self, export: &PluginDataExport, colour_manager: &ColourManager) -> String {
+        let descriptions = column_descriptions(export);
+        if !self.describe_columns || descriptions.is_empty() {
+            return String::new();
+        }
+        let mut output = String::from("\n");
+        for (column, desc) in descriptions {
+            output.push_str(&format!("  {} {}\n", colour_manager.highlight(&format!("{column}:")), desc));
+        }
+        output
+    }
+}
+
+/// Render a matrix as an aligned grid, shading each cell by its share of the largest value
+fn format_matrix_heat(matrix: &Matrix, colour_manager: &ColourManager) -> String {
+    let max = matrix.max_value().filter(|m| *m > 0.0);
+    
+    // Cell text and heat level (None for empty cells)
+    let cells: Vec<Vec<(String, Option<usize>)>> = matrix.cells.iter()
+        .map(|row| row.iter().map(|value| {
+            match (value.as_f64(), max) {
+                (Some(v), Some(max)) if v > 0.0 => {
+                    let level = (((v / max) * HEAT_SHADES.len() as f64).ceil() as usize)
+                        .clamp(1, HEAT_SHADES.len()) - 1;
+                    (format!("{} {}", value, HEAT_SHADES[level]), Some(level))
+                }
+                _ if value.is_null() || value.as_f64() == Some(0.0) => ("·".to_string(), None),
+                _ => (value.to_string(), None),
+            }
+        }).collect())
+        .collect();
+    
+    let label_width = matrix.row_labels.iter()
+        .chain(std::iter::once(&matrix.corner_label))
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let column_widths: Vec<usize> = matrix.column_labels.iter().enumerate()
+        .map(|(i, label)| {
+            cells.iter()
+                .filter_map(|row| row.get(i))
+                .map(|(text, _)| text.chars().count())
+                .chain(std::iter::once(label.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    
+    let mut output = String::new();
+    
+    output.push_str(&colour_manager.highlight(&pad(&matrix.corner_label, label_width)).to_string());
+    for (label, width) in matrix.column_labels.iter().zip(&column_widths) {
+        output.push_str("  ");
+        output.push_str(&colour_manager.highlight(&pad(label, *width)).to_string());
+    }
+    output.push('\n');
+    
+    for (label, row) in matrix.row_labels.iter().zip(&cells) {
+        output.push_str(&colour_manager.command(&pad(label, label_width)).to_string());
+        for ((text, level), width) in row.iter().zip(&column_widths) {
+            let padded = pad(text, *width);
+            output.push_str("  ");
+            output.push_str(&match level {
+                Some(3) => colour_manager.error(&padded).to_string(),
+                Some(2) => colour_manager.warning(&padded).to_string(),
+                _ => padded,
+            });
+        }
+        output.push('\n');
+    }
+    
+    output
+}
+
+/// Render a tree with box-drawing branches, one node per line as `label: value`
+fn format_tree(root: &TreeNode, colour_manager: &ColourManager) -> String {
+    fn node_line(node: &TreeNode, colour_manager: &ColourManager) -> String {
+        match node.value.as_ref().filter(|value| !value.is_null()) {
+            Some(value) => format!("{}: {}", colour_manager.command(&node.label), value),
+            None => colour_manager.command(&node.label).to_string(),
+        }
+    }
+
+    fn format_children(node: &TreeNode, prefix: &str, colour_manager: &ColourManager, output: &mut String) {
+        for (i, child) in node.children.iter().enumerate() {
+            let last = i + 1 == node.children.len();
+            output.push_str(prefix);
+            output.push_str(if last { "└─ " } else { "├─ " });
+            output.push_str(&node_line(child, colour_manager));
+            output.push('\n');
+            let child_prefix = format!("{}{}", prefix, if last { "   " } else { "│  " });
+            format_children(child, &child_prefix, colour_manager, output);
+        }
+    }
+
+    let mut output = match root.value.as_ref().filter(|value| !value.is_null()) {
+        Some(value) => format!("{}: {}", colour_manager.success(&root.label), value),
+        None => colour_manager.success(&root.label).to_string(),
+    };
+    output.push('\n');
+    format_children(root, "", colour_manager, &mut output);
+    output
+}
+
+/// Render fields as aligned `field: value` lines, indented to match table output
+fn format_fields(fields: &[(String, String)], colour_manager: &ColourManager) -> String {
+    let label_width = fields.iter()
+        .map(|(field, _)| field.chars().count() + 1)
+        .max()
+        .unwrap_or(0);
+    
+    let mut output = String::new();
+    for (field, value) in fields {
+        output.push_str("  ");
+        output.push_str(&colour_manager.highlight(&pad(&format!("{field}:"), label_width)).to_string());
+        output.push(' ');
+        output.push_str(value);
+        output.push('\n');
+    }
+    output
+}
+
+/// Left-align text to a width measured in characters
+fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.chars().count())))
+}
+
 impl Default for ConsoleFormatter {
     fn default() -> Self {
         Self::new()
@@ -1,6 +1,7 @@
 //! CSV/TSV export format implementation
 
-use super::FormatExporter;
+use super::{column_descriptions, melt, FormatExporter, Layout};
+use super::values::format_value;
 use crate::plugin::PluginResult;
 use crate::plugin::data_export::{PluginDataExport, DataPayload};
 use std::sync::Arc;
@@ -21,6 +22,9 @@ pub struct CsvFormatter {
     delimiter: char,
     quote_char: char,
     quoting_style: QuotingStyle,
+    layout: Layout,
+    /// Whether table values are written as they are rather than formatted by their column hints
+    raw_values: bool,
 }
 
 impl CsvFormatter {
@@ -30,6 +34,8 @@ impl CsvFormatter {
             delimiter: ',', 
             quote_char: '"',
             quoting_style: QuotingStyle::Minimal,
+            layout: Layout::Wide,
+            raw_values: false,
         }
     }
     
@@ -39,6 +45,8 @@ impl CsvFormatter {
             delimiter, 
             quote_char: '"',  // Still needed for the escaping logic
             quoting_style: QuotingStyle::NoQuotes,
+            layout: Layout::Wide,
+            raw_values: false,
         }
     }
     
@@ -50,12 +58,47 @@ impl CsvFormatter {
             QuotingStyle::AlwaysQuote
         };
         
-        Self { delimiter, quote_char, quoting_style }
+        Self { delimiter, quote_char, quoting_style, layout: Layout::Wide, raw_values: false }
     }
     
     /// Create a formatter with full configuration
     pub fn with_config(delimiter: char, quote_char: char, quoting_style: QuotingStyle) -> Self {
-        Self { delimiter, quote_char, quoting_style }
+        Self { delimiter, quote_char, quoting_style, layout: Layout::Wide, raw_values: false }
+    }
+    
+    /// Set the layout: one table per export (wide) or a single long-format table
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+    
+    /// Write table values as they are (`42.5`) instead of as their column hints format them (`42.5%`)
+    pub fn with_raw_values(mut self, raw_values: bool) -> Self {
+        self.raw_values = raw_values;
+        self
+    }
+    
+    /// Format every export as one `plugin, table, row_key, column, value` table
+    fn format_long(&self, data: &[Arc<PluginDataExport>]) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut output = ["plugin", "table", "row_key", "column", "value"].join(&delimiter);
+        output.push('\n');
+        
+        for export in data {
+            for (row_key, column, value) in melt(export) {
+                let fields = [
+                    self.escape_field(&export.plugin_id),
+                    self.escape_field(&export.title),
+                    self.escape_field(&row_key),
+                    self.escape_field(&column),
+                    self.escape_field(&value),
+                ];
+                output.push_str(&fields.join(&delimiter));
+                output.push('\n');
+            }
+        }
+        
+        output
     }
     
     /// Escape a field based on the configured quoting style
@@ -114,6 +157,10 @@ impl Default for CsvFormatter {
 
 impl FormatExporter for CsvFormatter {
     fn format_data(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
+        if self.layout == Layout::Long {
+            return Ok(self.format_long(data));
+        }
+        
         let mut output = String::new();
         
         for export in data {
@@ -122,6 +169,9 @@ impl FormatExporter for CsvFormatter {
             if let Some(ref desc) = export.description {
                 output.push_str(&format!("# {}\n", desc));
             }
+            for (column, desc) in column_descriptions(export) {
+                output.push_str(&format!("# {}: {}\n", column, desc));
+            }
             
             match &export.data {
                 DataPayload::Rows(rows) => {
@@ -135,8 +185,11 @@ impl FormatExporter for CsvFormatter {
                         
                         // Data rows
                         for row in rows.iter() {
-                            let fields: Vec<String> = row.values.iter()
-                                .map(|value| self.escape_field(&value.to_string()))
+                            let fields: Vec<String> = row.values.iter().enumerate()
+                                .map(|(i, value)| {
+                                    let text = if self.raw_values { value.to_string() } else { format_value(export.schema.columns.get(i), value) };
+                                    self.escape_field(&text)
+                                })
                                 .collect();
                             output.push_str(&fields.join(&self.delimiter.to_string()));
                             output.push('\n');
@@ -156,6 +209,23 @@ impl FormatExporter for CsvFormatter {
                     }
                 }
                 
+                DataPayload::Matrix(matrix) => {
+                    // Header row is the corner label followed by the column labels
+                    let headers: Vec<String> = matrix.header().iter()
+                        .map(|label| self.escape_field(label))
+                        .collect();
+                    output.push_str(&headers.join(&self.delimiter.to_string()));
+                    output.push('\n');
+                    
+                    for row in matrix.to_rows() {
+                        let fields: Vec<String> = row.values.iter()
+                            .map(|value| self.escape_field(&value.to_string()))
+                            .collect();
+                        output.push_str(&fields.join(&self.delimiter.to_string()));
+                        output.push('\n');
+                    }
+                }
+                
                 DataPayload::Tree(_) => {
                     // Trees don't translate well to CSV - provide a note
                     output.push_str("# Tree data not supported in CSV format\n");
@@ -0,0 +1,454 @@
+//! Row Filters
+//!
+//! `--where` keeps the table rows for which an expression holds, such as
+//! `Commits > 10 && Author != 'bot'`. Comparisons name a column and a literal:
+//! a number, a quoted string, `true`, `false`, `null` or, for timestamp columns,
+//! a `'YYYY-MM-DD'` date. `~` and `!~` test whether text contains a string,
+//! ignoring case. Comparisons combine with `&&`, `||`, `!` and parentheses.
+//!
+//! Columns are matched ignoring case and punctuation (`current_size` matches
+//! `Current Size`); names with other characters can be quoted in backticks.
+//! A table is filtered when it has every column the expression names.
+
+use crate::plugin::{PluginError, PluginResult};
+use crate::plugin::data_export::{ColumnType, DataPayload, PluginDataExport, Value};
+use crate::plugin::table_match::{column_index, Operator};
+use chrono::NaiveDate;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Literal a column is compared with
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    /// A number, with its text for comparing against text columns
+    Number(f64, String),
+    Text(String),
+    Boolean(bool),
+    Null,
+}
+
+/// Parsed filter expression
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare { column: String, operator: Operator, literal: Literal },
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A parsed `--where` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFilter {
+    expression: String,
+    root: Expr,
+}
+
+impl RowFilter {
+    /// Parse an expression such as `Commits > 10 && Author != 'bot'`
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let invalid = |reason: String| format!("Invalid --where expression '{}': {}", expression, reason);
+        let tokens = tokenize(expression).map_err(invalid)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let root = parser.or().map_err(invalid)?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(invalid(format!("unexpected {}", token.describe())));
+        }
+        Ok(Self { expression: expression.trim().to_string(), root })
+    }
+
+    /// The exports with the rows of each table the expression applies to filtered
+    ///
+    /// A column no table has, or a literal that cannot be compared with a column's
+    /// type, is an error.
+    pub fn apply(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<Vec<Arc<PluginDataExport>>> {
+        let tables: Vec<&PluginDataExport> = data.iter()
+            .map(|export| export.as_ref())
+            .filter(|export| matches!(export.data, DataPayload::Rows(_)))
+            .collect();
+        if !tables.is_empty() {
+            for column in self.columns() {
+                if !tables.iter().any(|export| column_index(export, column).is_some()) {
+                    return Err(PluginError::invalid_argument("--where", &format!("No table has a column named '{}'", column)));
+                }
+            }
+        }
+        data.iter().map(|export| self.apply_export(export)).collect()
+    }
+
+    /// An export with the rows of its table filtered, if it has every column the expression names
+    pub fn apply_export(&self, export: &Arc<PluginDataExport>) -> PluginResult<Arc<PluginDataExport>> {
+        let DataPayload::Rows(rows) = &export.data else {
+            return Ok(Arc::clone(export));
+        };
+        let mut indices = HashMap::new();
+        for column in self.columns() {
+            match column_index(export, column) {
+                Some(index) => indices.insert(column, index),
+                None => return Ok(Arc::clone(export)),
+            };
+        }
+        self.check(&self.root, export, &indices)?;
+
+        let kept: Vec<_> = rows.iter()
+            .filter(|row| evaluate(&self.root, &|column| indices.get(column).and_then(|index| row.values.get(*index))))
+            .cloned()
+            .collect();
+        if kept.len() == rows.len() {
+            return Ok(Arc::clone(export));
+        }
+        let mut export = export.as_ref().clone();
+        export.data = DataPayload::Rows(Arc::new(kept));
+        Ok(Arc::new(export))
+    }
+
+    /// Names of the columns the expression compares
+    fn columns(&self) -> Vec<&str> {
+        fn collect<'a>(expr: &'a Expr, columns: &mut Vec<&'a str>) {
+            match expr {
+                Expr::Compare { column, .. } => {
+                    if !columns.contains(&column.as_str()) {
+                        columns.push(column);
+                    }
+                }
+                Expr::Not(inner) => collect(inner, columns),
+                Expr::And(left, right) | Expr::Or(left, right) => {
+                    collect(left, columns);
+                    collect(right, columns);
+                }
+            }
+        }
+        let mut columns = Vec::new();
+        collect(&self.root, &mut columns);
+        columns
+    }
+
+    /// Check that each comparison's literal suits its column's type
+    fn check(&self, expr: &Expr, export: &PluginDataExport, indices: &HashMap<&str, usize>) -> PluginResult<()> {
+        match expr {
+            Expr::Compare { column, operator, literal } => {
+                let definition = &export.schema.columns[indices[column.as_str()]];
+                let suits = match (&definition.data_type, operator, literal) {
+                    (_, Operator::Equal | Operator::NotEqual, Literal::Null) => true,
+                    (_, _, Literal::Null) => false,
+                    (ColumnType::String, _, Literal::Text(_) | Literal::Number(..)) => true,
+                    (_, Operator::Contains | Operator::NotContains, _) => false,
+                    (ColumnType::Integer | ColumnType::Float | ColumnType::Duration, _, Literal::Number(..)) => true,
+                    (ColumnType::Timestamp, _, Literal::Number(..)) => true,
+                    (ColumnType::Timestamp, _, Literal::Text(date)) => parse_date(date).is_some(),
+                    (ColumnType::Boolean, Operator::Equal | Operator::NotEqual, Literal::Boolean(_)) => true,
+                    _ => false,
+                };
+                if suits {
+                    return Ok(());
+                }
+                let expected = match definition.data_type {
+                    ColumnType::String => "text",
+                    ColumnType::Integer | ColumnType::Float => "a number",
+                    ColumnType::Duration => "a number of seconds",
+                    ColumnType::Timestamp => "a number of seconds or a 'YYYY-MM-DD' date",
+                    ColumnType::Boolean => "true or false with == or !=",
+                };
+                Err(PluginError::invalid_argument("--where", &format!(
+                    "'{}' in '{}' cannot compare the {:?} column '{}'; expected {}",
+                    operator.symbol(), self.expression, definition.data_type, definition.name, expected
+                )))
+            }
+            Expr::Not(inner) => self.check(inner, export, indices),
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                self.check(left, export, indices)?;
+                self.check(right, export, indices)
+            }
+        }
+    }
+}
+
+/// Whether an expression holds for a row, given its values by column name
+fn evaluate<'a>(expr: &Expr, value_of: &dyn Fn(&str) -> Option<&'a Value>) -> bool {
+    match expr {
+        Expr::Compare { column, operator, literal } => {
+            let value = value_of(column).unwrap_or(&Value::Null);
+            match (value, literal) {
+                (Value::Null, Literal::Null) => *operator == Operator::Equal,
+                (_, Literal::Null) => *operator == Operator::NotEqual,
+                (Value::Null, _) => false,
+                _ => compare(value, *operator, literal).is_some_and(|ordering| operator.holds(ordering)),
+            }
+        }
+        Expr::Not(inner) => !evaluate(inner, value_of),
+        Expr::And(left, right) => evaluate(left, value_of) && evaluate(right, value_of),
+        Expr::Or(left, right) => evaluate(left, value_of) || evaluate(right, value_of),
+    }
+}
+
+/// Order a value against a literal, or `None` when they cannot be compared
+///
+/// For `~` and `!~` the ordering is `Equal` when the value contains the literal.
+fn compare(value: &Value, operator: Operator, literal: &Literal) -> Option<Ordering> {
+    if operator.is_text_match() {
+        let needle = match literal {
+            Literal::Text(text) | Literal::Number(_, text) => text.to_lowercase(),
+            _ => return None,
+        };
+        let contains = value.to_string().to_lowercase().contains(&needle);
+        return Some(if contains { Ordering::Equal } else { Ordering::Less });
+    }
+    match (value, literal) {
+        (Value::String(text), Literal::Text(literal) | Literal::Number(_, literal)) => Some(text.as_str().cmp(literal)),
+        (Value::Integer(_) | Value::Float(_), Literal::Number(number, _)) => value.as_f64().map(|value| value.total_cmp(number)),
+        (Value::Duration(duration), Literal::Number(seconds, _)) => Some(duration.as_secs_f64().total_cmp(seconds)),
+        (Value::Timestamp(time), Literal::Number(seconds, _)) => Some(epoch_seconds(*time).total_cmp(seconds)),
+        (Value::Timestamp(time), Literal::Text(date)) => parse_date(date).map(|seconds| epoch_seconds(*time).total_cmp(&seconds)),
+        (Value::Boolean(flag), Literal::Boolean(literal)) => Some(flag.cmp(literal)),
+        _ => None,
+    }
+}
+
+/// Seconds since the epoch of a time, negative before it
+fn epoch_seconds(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs_f64(),
+        Err(error) => -error.duration().as_secs_f64(),
+    }
+}
+
+/// Seconds since the epoch of the start of a `YYYY-MM-DD` day (UTC)
+fn parse_date(date: &str) -> Option<f64> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(day.and_hms_opt(0, 0, 0)?.and_utc().timestamp() as f64)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Column(String),
+    Text(String),
+    Number(f64, String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(word) | Token::Column(word) => format!("'{}'", word),
+            Token::Text(text) => format!("string '{}'", text),
+            Token::Number(_, text) => format!("number {}", text),
+            Token::Operator(operator) => format!("'{}'", operator.symbol()),
+            Token::And => "'&&'".to_string(),
+            Token::Or => "'||'".to_string(),
+            Token::Not => "'!'".to_string(),
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let (token, length) = match c {
+            '(' => (Token::Open, 1),
+            ')' => (Token::Close, 1),
+            '&' if rest.starts_with("&&") => (Token::And, 2),
+            '|' if rest.starts_with("||") => (Token::Or, 2),
+            '\'' | '"' | '`' => {
+                let end = rest[1..].find(c).ok_or_else(|| format!("unterminated {}", c))?;
+                let quoted = rest[1..end + 1].to_string();
+                (if c == '`' { Token::Column(quoted) } else { Token::Text(quoted) }, end + 2)
+            }
+            _ => match Operator::parse_prefix(rest) {
+                Some((operator, length)) => (Token::Operator(operator), length),
+                None if c == '!' => (Token::Not, 1),
+                None => {
+                    let length = rest.find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '.' | '-')))
+                        .unwrap_or(rest.len());
+                    if length == 0 {
+                        return Err(format!("unexpected '{}'", c));
+                    }
+                    let word = &rest[..length];
+                    match word.parse::<f64>() {
+                        Ok(number) if number.is_finite() => (Token::Number(number, word.to_string()), length),
+                        _ => (Token::Word(word.to_string()), length),
+                    }
+                }
+            },
+        };
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens: `||` binds loosest, then `&&`, then `!`
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn accept(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.position) == Some(token);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.accept(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.accept(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("expected ')'".to_string()),
+                }
+            }
+            Some(Token::Word(column) | Token::Column(column)) => {
+                let operator = match self.next() {
+                    Some(Token::Operator(operator)) => operator,
+                    _ => return Err(format!("expected a comparison (==, !=, >, >=, <, <=, ~, !~) after '{}'", column)),
+                };
+                let literal = match self.next() {
+                    Some(Token::Number(number, text)) => Literal::Number(number, text),
+                    Some(Token::Text(text)) => Literal::Text(text),
+                    Some(Token::Word(word)) => match word.to_lowercase().as_str() {
+                        "true" => Literal::Boolean(true),
+                        "false" => Literal::Boolean(false),
+                        "null" => Literal::Null,
+                        _ => return Err(format!("expected a number, a quoted string, true, false or null, found '{}'; quote strings", word)),
+                    },
+                    Some(token) => return Err(format!("expected a value after '{}', found {}", column, token.describe())),
+                    None => return Err(format!("expected a value after '{}'", column)),
+                };
+                Ok(Expr::Compare { column, operator, literal })
+            }
+            Some(token) => Err(format!("expected a column, '!' or '(', found {}", token.describe())),
+            None => Err("expected a comparison".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, DataExportType, DataSchema, ExportHints, Row};
+    use std::time::Duration;
+
+    fn authors() -> Arc<PluginDataExport> {
+        let row = |author: &str, commits: i64, bot: bool, days: u64| Row::new(vec![
+            Value::String(author.to_string()),
+            Value::Integer(commits),
+            Value::Boolean(bot),
+            Value::Timestamp(UNIX_EPOCH + Duration::from_secs(days * 86400)),
+        ]);
+        Arc::new(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Authors".to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema {
+                columns: vec![
+                    ColumnDef::new("Author", ColumnType::String),
+                    ColumnDef::new("Commits", ColumnType::Integer),
+                    ColumnDef::new("Is Bot", ColumnType::Boolean),
+                    ColumnDef::new("Last Commit", ColumnType::Timestamp),
+                ],
+                metadata: HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(vec![
+                row("alice", 40, false, 19000),
+                row("dependabot[bot]", 25, true, 19500),
+                row("bob", 3, false, 19700),
+                row("bot", 12, true, 19800),
+            ])),
+            export_hints: ExportHints::default(),
+            timestamp: UNIX_EPOCH,
+            repository: None,
+        })
+    }
+
+    fn kept(expression: &str) -> Vec<String> {
+        let filter = RowFilter::parse(expression).unwrap();
+        let data = filter.apply(&[authors()]).unwrap();
+        match &data[0].data {
+            DataPayload::Rows(rows) => rows.iter().map(|row| row.values[0].to_string()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rows_are_kept_when_the_expression_holds() {
+        assert_eq!(kept("Commits > 10 && Author != 'bot'"), ["alice", "dependabot[bot]"]);
+        assert_eq!(kept("commits<=12 || author == \"alice\""), ["alice", "bob", "bot"]);
+        assert_eq!(kept("!(author ~ 'BOT') && commits >= 3"), ["alice", "bob"]);
+        assert_eq!(kept("author !~ 'bot' || commits > 20 && is_bot == true"), ["alice", "dependabot[bot]", "bob"]);
+        assert_eq!(kept("`Is Bot` == false"), ["alice", "bob"]);
+        assert_eq!(kept("last-commit >= '2023-12-01'"), ["bob", "bot"]);
+        assert_eq!(kept("Author != null && Commits == 3"), ["bob"]);
+    }
+
+    #[test]
+    fn test_and_binds_tighter_than_or() {
+        assert_eq!(kept("Commits > 30 || Commits < 20 && Author == 'bob'"), ["alice", "bob"]);
+        assert_eq!(kept("(Commits > 30 || Commits < 20) && Author == 'bob'"), ["bob"]);
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_reported() {
+        for expression in ["", "Commits >", "Commits 10", "Commits > 10 &&", "(Commits > 10", "Author == 'bot", "Commits > 10 Author", "Commits > bob"] {
+            assert!(RowFilter::parse(expression).is_err(), "{} should not parse", expression);
+        }
+
+        let apply = |expression: &str| RowFilter::parse(expression).unwrap().apply(&[authors()]);
+        assert!(apply("Churn > 10").is_err());
+        assert!(apply("Commits > 'many'").is_err());
+        assert!(apply("Commits ~ '1'").is_err());
+        assert!(apply("last_commit > 'yesterday'").is_err());
+        assert!(apply("is_bot > true").is_err());
+    }
+
+    #[test]
+    fn test_tables_without_the_columns_are_left_alone() {
+        let mut other = PluginDataExport::clone(&authors());
+        other.schema.columns[1].name = "Files".to_string();
+        let filter = RowFilter::parse("Commits > 10").unwrap();
+        let data = filter.apply(&[authors(), Arc::new(other)]).unwrap();
+        let counts: Vec<usize> = data.iter()
+            .map(|export| match &export.data {
+                DataPayload::Rows(rows) => rows.len(),
+                _ => 0,
+            })
+            .collect();
+        assert_eq!(counts, [3, 4]);
+    }
+}
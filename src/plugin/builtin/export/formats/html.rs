@@ -1,17 +1,90 @@
 //! HTML export format implementation
 
-use super::FormatExporter;
+use super::{indicator, FormatExporter};
+use super::values::format_value;
+use crate::display::{Indicator, Severity, Trend};
 use crate::plugin::PluginResult;
-use crate::plugin::data_export::{PluginDataExport, DataPayload};
+use crate::plugin::data_export::{ColumnDef, PluginDataExport, DataPayload, Value};
 use std::sync::Arc;
 
+/// Styles of cells coloured by their indicator, shared by the HTML reports
+pub(crate) const INDICATOR_STYLES: &str = "        td.up, td.low { color: #1a7f37; }
+        td.down, td.high, td.critical { color: #cf222e; }
+        td.medium { color: #9a6700; }
+";
+
+/// Colour scheme of the HTML report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// Parse a theme name (`light`, `dark`)
+    pub fn parse(theme: &str) -> Option<Self> {
+        match theme.to_lowercase().as_str() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            _ => None,
+        }
+    }
+    
+    /// Styles of the page, tables and charts in this theme
+    pub(crate) fn styles(&self) -> &'static str {
+        match self {
+            Theme::Light => LIGHT_STYLES,
+            Theme::Dark => DARK_STYLES,
+        }
+    }
+}
+
+const LIGHT_STYLES: &str = "        body { font-family: Arial, sans-serif; margin: 20px; }
+        h1 { color: #333; }
+        h2 { color: #666; border-bottom: 1px solid #ccc; }
+        table { border-collapse: collapse; width: 100%; margin: 20px 0; }
+        th, td { border: 1px solid #ddd; padding: 8px; text-align: left; }
+        th { background-color: #f2f2f2; }
+        tr:nth-child(even) { background-color: #f9f9f9; }
+        svg .series { stroke: #0969da; stroke-width: 2; }
+        svg .point, svg .bar { fill: #0969da; }
+        svg .cell { fill: #54aeff; stroke: #fff; }
+        svg .axis, svg .cell-label { fill: #333; font-size: 11px; }
+";
+
+const DARK_STYLES: &str = "        body { font-family: Arial, sans-serif; margin: 20px; background-color: #0d1117; color: #c9d1d9; }
+        h1 { color: #e6edf3; }
+        h2 { color: #8b949e; border-bottom: 1px solid #30363d; }
+        table { border-collapse: collapse; width: 100%; margin: 20px 0; }
+        th, td { border: 1px solid #30363d; padding: 8px; text-align: left; }
+        th { background-color: #161b22; }
+        tr:nth-child(even) { background-color: #11161d; }
+        td.up, td.low { color: #3fb950; }
+        td.down, td.high, td.critical { color: #f85149; }
+        td.medium { color: #d29922; }
+        svg .series { stroke: #58a6ff; stroke-width: 2; }
+        svg .point, svg .bar { fill: #58a6ff; }
+        svg .cell { fill: #1f6feb; stroke: #0d1117; }
+        svg .axis, svg .cell-label { fill: #c9d1d9; font-size: 11px; }
+";
+
 /// HTML formatter
-pub struct HtmlFormatter;
+pub struct HtmlFormatter {
+    /// Whether changes and severities carry symbols and labels as well as colour
+    accessible: bool,
+}
 
 impl HtmlFormatter {
     /// Create a new HTML formatter
     pub fn new() -> Self {
-        Self
+        Self { accessible: false }
+    }
+    
+    /// Set whether changes and severities carry symbols and labels as well as colour
+    pub fn with_accessible(mut self, accessible: bool) -> Self {
+        self.accessible = accessible;
+        self
     }
 }
 
@@ -31,6 +104,7 @@ impl FormatExporter for HtmlFormatter {
         output.push_str("        th, td { border: 1px solid #ddd; padding: 8px; text-align: left; }\n");
         output.push_str("        th { background-color: #f2f2f2; }\n");
         output.push_str("        h2 { color: #333; border-bottom: 2px solid #333; }\n");
+        output.push_str(INDICATOR_STYLES);
         output.push_str("    </style>\n");
         output.push_str("</head>\n<body>\n");
         output.push_str("    <h1>Git Statistics Report</h1>\n");
@@ -47,14 +121,14 @@ impl FormatExporter for HtmlFormatter {
                     if !rows.is_empty() && !export.schema.columns.is_empty() {
                         output.push_str("    <table>\n        <thead>\n            <tr>\n");
                         for col in &export.schema.columns {
-                            output.push_str(&format!("                <th>{}</th>\n", escape_html(&col.name)));
+                            output.push_str(&format!("                {}\n", header_cell(col)));
                         }
                         output.push_str("            </tr>\n        </thead>\n        <tbody>\n");
                         
                         for row in rows.iter() {
                             output.push_str("            <tr>\n");
-                            for value in &row.values {
-                                output.push_str(&format!("                <td>{}</td>\n", escape_html(&value.to_string())));
+                            for (i, value) in row.values.iter().enumerate() {
+                                output.push_str(&format!("                {}\n", value_cell(export.schema.columns.get(i), value, self.accessible)));
                             }
                             output.push_str("            </tr>\n");
                         }
@@ -71,6 +145,24 @@ impl FormatExporter for HtmlFormatter {
                     output.push_str("    </table>\n");
                 }
                 
+                DataPayload::Matrix(matrix) => {
+                    output.push_str("    <table class=\"matrix\">\n        <thead>\n            <tr>\n");
+                    for label in matrix.header() {
+                        output.push_str(&format!("                <th>{}</th>\n", escape_html(&label)));
+                    }
+                    output.push_str("            </tr>\n        </thead>\n        <tbody>\n");
+                    
+                    for (label, cells) in matrix.row_labels.iter().zip(&matrix.cells) {
+                        output.push_str("            <tr>\n");
+                        output.push_str(&format!("                <th>{}</th>\n", escape_html(label)));
+                        for value in cells {
+                            output.push_str(&format!("                <td>{}</td>\n", escape_html(&value.to_string())));
+                        }
+                        output.push_str("            </tr>\n");
+                    }
+                    output.push_str("        </tbody>\n    </table>\n");
+                }
+                
                 DataPayload::Tree(_) => {
                     output.push_str("    <p><em>Tree structure not yet implemented</em></p>\n");
                 }
@@ -91,6 +183,38 @@ impl FormatExporter for HtmlFormatter {
     
 }
 
+/// Table header cell for a column, with its description as a tooltip
+pub(crate) fn header_cell(column: &ColumnDef) -> String {
+    match &column.description {
+        Some(desc) => format!("<th title=\"{}\">{}</th>", escape_html(desc), escape_html(&column.name)),
+        None => format!("<th>{}</th>", escape_html(&column.name)),
+    }
+}
+
+/// Table cell for a value, classed by its indicator and, in accessible mode, marked with its symbol or label
+pub(crate) fn value_cell(column: Option<&ColumnDef>, value: &Value, accessible: bool) -> String {
+    let text = format_value(column, value);
+    match column.and_then(|column| indicator(column, value)) {
+        Some(indicator) => {
+            let text = if accessible { indicator.mark(&text) } else { text };
+            format!("<td class=\"{}\">{}</td>", indicator_class(indicator), escape_html(&text))
+        }
+        None => format!("<td>{}</td>", escape_html(&text)),
+    }
+}
+
+/// CSS class of an indicator cell
+fn indicator_class(indicator: Indicator) -> &'static str {
+    match indicator {
+        Indicator::Trend(Trend::Up) => "up",
+        Indicator::Trend(Trend::Down) => "down",
+        Indicator::Severity(Severity::Low) => "low",
+        Indicator::Severity(Severity::Medium) => "medium",
+        Indicator::Severity(Severity::High) => "high",
+        Indicator::Severity(Severity::Critical) => "critical",
+    }
+}
+
 /// Escape text for HTML output
 fn escape_html(text: &str) -> String {
     text.replace('&', "&amp;")
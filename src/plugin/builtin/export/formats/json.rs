@@ -2,10 +2,38 @@
 
 use super::FormatExporter;
 use crate::plugin::{PluginResult, PluginError};
-use crate::plugin::data_export::{PluginDataExport, DataPayload};
+use crate::plugin::data_export::{PluginDataExport, DataPayload, TreeNode};
 use std::sync::Arc;
 use serde_json::{json, Value};
 
+/// Convert an export value to its JSON representation
+pub(crate) fn value_to_json(value: &crate::plugin::data_export::Value) -> Value {
+    match value {
+        crate::plugin::data_export::Value::String(s) => json!(s),
+        crate::plugin::data_export::Value::Integer(i) => json!(i),
+        crate::plugin::data_export::Value::Float(f) => json!(f),
+        crate::plugin::data_export::Value::Boolean(b) => json!(b),
+        crate::plugin::data_export::Value::Timestamp(ts) => json!(format!("{:?}", ts)),
+        crate::plugin::data_export::Value::Duration(d) => json!(format!("{:?}", d)),
+        crate::plugin::data_export::Value::Null => json!(null),
+    }
+}
+
+/// Convert a tree node and its descendants to nested JSON objects
+pub(crate) fn tree_to_json(node: &TreeNode) -> Value {
+    let mut object = json!({ "label": node.label });
+    if let Some(value) = &node.value {
+        object["value"] = value_to_json(value);
+    }
+    if let Some(metadata) = &node.metadata {
+        object["metadata"] = json!(metadata);
+    }
+    if !node.children.is_empty() {
+        object["children"] = Value::Array(node.children.iter().map(|child| tree_to_json(child)).collect());
+    }
+    object
+}
+
 /// JSON formatter
 pub struct JsonFormatter;
 
@@ -33,6 +61,12 @@ impl FormatExporter for JsonFormatter {
                 "description": export.description,
                 "type": export.data_type,
             });
+            if let Some(repository) = &export.repository {
+                plugin_data["repository"] = json!(repository);
+            }
+            if !export.schema.metadata.is_empty() {
+                plugin_data["metadata"] = json!(export.schema.metadata);
+            }
             
             // Add data based on type
             match &export.data {
@@ -44,16 +78,7 @@ impl FormatExporter for JsonFormatter {
                         
                         for (i, value) in row.values.iter().enumerate() {
                             if let Some(column) = export.schema.columns.get(i) {
-                                let json_value = match value {
-                                    crate::plugin::data_export::Value::String(s) => json!(s),
-                                    crate::plugin::data_export::Value::Integer(i) => json!(i),
-                                    crate::plugin::data_export::Value::Float(f) => json!(f),
-                                    crate::plugin::data_export::Value::Boolean(b) => json!(b),
-                                    crate::plugin::data_export::Value::Timestamp(ts) => json!(format!("{:?}", ts)),
-                                    crate::plugin::data_export::Value::Duration(d) => json!(format!("{:?}", d)),
-                                    crate::plugin::data_export::Value::Null => json!(null),
-                                };
-                                json_row.insert(column.name.clone(), json_value);
+                                json_row.insert(column.name.clone(), value_to_json(value));
                             }
                         }
                         
@@ -76,14 +101,21 @@ impl FormatExporter for JsonFormatter {
                     plugin_data["data"] = json!(kv);
                 }
                 
-                DataPayload::Tree(root) => {
-                    plugin_data["tree"] = json!({
-                        "label": root.label,
-                        "children": root.children.len()
-                        // TODO: Recursively serialize tree structure
+                DataPayload::Matrix(matrix) => {
+                    plugin_data["matrix"] = json!({
+                        "corner_label": matrix.corner_label,
+                        "row_labels": matrix.row_labels,
+                        "column_labels": matrix.column_labels,
+                        "cells": matrix.cells.iter()
+                            .map(|row| row.iter().map(value_to_json).collect::<Vec<_>>())
+                            .collect::<Vec<_>>()
                     });
                 }
                 
+                DataPayload::Tree(root) => {
+                    plugin_data["tree"] = tree_to_json(root);
+                }
+                
                 DataPayload::Raw(raw) => {
                     plugin_data["raw"] = json!(raw.as_str());
                 }
@@ -93,11 +125,16 @@ impl FormatExporter for JsonFormatter {
                 }
             }
             
-            json_obj.insert(export.plugin_id.clone(), plugin_data);
+            // Exports from several repositories are kept apart by their repository
+            let key = match &export.repository {
+                Some(repository) => format!("{}.{}", export.plugin_id, repository),
+                None => export.plugin_id.clone(),
+            };
+            json_obj.insert(key, plugin_data);
         }
         
         serde_json::to_string_pretty(&json_data)
             .map_err(|e| PluginError::execution_failed(format!("JSON serialization failed: {}", e)))
     }
     
-}
\ No newline at end of file
+}
@@ -1,17 +1,22 @@
 //! Markdown export format implementation
 
-use super::FormatExporter;
+use super::{FormatExporter, Transpose};
 use crate::plugin::PluginResult;
 use crate::plugin::data_export::{PluginDataExport, DataPayload};
 use std::sync::Arc;
 
 /// Markdown formatter
-pub struct MarkdownFormatter;
+pub struct MarkdownFormatter {
+    /// When to render single-row tables as a field list
+    pub transpose: Transpose,
+}
 
 impl MarkdownFormatter {
     /// Create a new Markdown formatter
     pub fn new() -> Self {
-        Self
+        Self {
+            transpose: Transpose::default(),
+        }
     }
 }
 
@@ -32,6 +37,11 @@ impl FormatExporter for MarkdownFormatter {
                 output.push_str(&format!("{}\n\n", desc));
             }
             
+            if let Some(fields) = self.transpose.fields(export) {
+                output.push_str(&format_field_list(&fields));
+                continue;
+            }
+            
             match &export.data {
                 DataPayload::Rows(rows) => {
                     if !rows.is_empty() && !export.schema.columns.is_empty() {
@@ -62,9 +72,32 @@ impl FormatExporter for MarkdownFormatter {
                 }
                 
                 DataPayload::KeyValue(kv) => {
-                    for (key, value) in kv.iter() {
-                        output.push_str(&format!("- **{}**: {}\n", 
-                            escape_markdown(key), escape_markdown(&value.to_string())));
+                    let fields: Vec<(String, String)> = kv.iter()
+                        .map(|(key, value)| (key.clone(), value.to_string()))
+                        .collect();
+                    output.push_str(&format_field_list(&fields));
+                }
+                
+                DataPayload::Matrix(matrix) => {
+                    let header = matrix.header();
+                    output.push('|');
+                    for label in &header {
+                        output.push_str(&format!(" {} |", escape_markdown(label)));
+                    }
+                    output.push('\n');
+                    
+                    output.push('|');
+                    for _ in &header {
+                        output.push_str(" --- |");
+                    }
+                    output.push('\n');
+                    
+                    for row in matrix.to_rows() {
+                        output.push('|');
+                        for value in &row.values {
+                            output.push_str(&format!(" {} |", escape_markdown(&value.to_string())));
+                        }
+                        output.push('\n');
                     }
                     output.push('\n');
                 }
@@ -88,6 +121,16 @@ impl FormatExporter for MarkdownFormatter {
     
 }
 
+/// Render fields as a bulleted `**field**: value` list
+pub(crate) fn format_field_list(fields: &[(String, String)]) -> String {
+    let mut output = String::new();
+    for (field, value) in fields {
+        output.push_str(&format!("- **{}**: {}\n", escape_markdown(field), escape_markdown(value)));
+    }
+    output.push('\n');
+    output
+}
+
 /// Escape text for Markdown output
 fn escape_markdown(text: &str) -> String {
     text.replace('\\', "\\\\")
@@ -5,20 +5,187 @@
 
 pub mod console;
 pub mod json;
+pub mod ndjson;
 pub mod csv;
 pub mod xml;
 pub mod yaml;
 pub mod html;
+pub mod charts;
+pub mod columns;
+pub mod filter;
 pub mod markdown;
 pub mod template;
+pub mod annotations;
+pub mod totals;
+pub mod values;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(any(feature = "sqlite", feature = "parquet"))]
+pub mod naming;
 
+use crate::display::{Indicator, Severity, Trend};
 use crate::plugin::PluginResult;
-use crate::plugin::data_export::PluginDataExport;
+use crate::plugin::data_export::{ColumnDef, DataPayload, PluginDataExport, Value};
 use std::sync::Arc;
 
+/// Number of columns from which a single-row table is transposed automatically
+pub const AUTO_TRANSPOSE_COLUMNS: usize = 5;
+
 /// Trait for format-specific exporters
 pub trait FormatExporter {
     /// Format the plugin data for export
     fn format_data(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String>;
 }
 
+/// Names and descriptions of the columns of a tabular export that carry a description
+pub fn column_descriptions(export: &PluginDataExport) -> Vec<(&str, &str)> {
+    match &export.data {
+        DataPayload::Rows(_) => export.schema.columns.iter()
+            .filter_map(|column| column.description.as_deref().map(|desc| (column.name.as_str(), desc)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Format hint of columns holding signed changes, marked `▲`/`▼`
+pub const DELTA_HINT: &str = "delta";
+
+/// Format hint of columns holding severity levels (`low` to `critical`), marked `[LOW]` to `[CRITICAL]`
+pub const SEVERITY_HINT: &str = "severity";
+
+/// Indicator of a table value, from its column's format hint (none for a zero change)
+pub fn indicator(column: &ColumnDef, value: &Value) -> Option<Indicator> {
+    match column.format_hint.as_deref()? {
+        DELTA_HINT => value.as_f64().and_then(Trend::from_delta).map(Indicator::Trend),
+        SEVERITY_HINT => Severity::parse(&value.to_string()).map(Indicator::Severity),
+        _ => None,
+    }
+}
+
+/// How tabular output lays out each export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// One table per export, one column per field
+    #[default]
+    Wide,
+    /// One `plugin, table, row_key, column, value` row per value, across all exports
+    Long,
+}
+
+impl Layout {
+    /// Parse a layout name (`wide`, `long`)
+    pub fn parse(layout: &str) -> Option<Self> {
+        match layout.to_lowercase().as_str() {
+            "wide" => Some(Self::Wide),
+            "long" | "tidy" => Some(Self::Long),
+            _ => None,
+        }
+    }
+}
+
+/// The values of an export as `(row_key, column, value)` triples, for long-format output
+///
+/// Table rows are keyed by their first column (by row number when the table has a
+/// single column), matrix cells by row and column label, key-value pairs by key under
+/// a `value` column and tree nodes by their `/`-separated path. Raw text is keyed by
+/// nothing under a `raw` column. Null values are left out.
+pub fn melt(export: &PluginDataExport) -> Vec<(String, String, String)> {
+    let mut values = Vec::new();
+    match &export.data {
+        DataPayload::Rows(rows) => {
+            let columns = &export.schema.columns;
+            let keyed = columns.len() > 1;
+            for (index, row) in rows.iter().enumerate() {
+                let row_key = match (keyed, row.values.first()) {
+                    (true, Some(key)) => key.to_string(),
+                    _ => (index + 1).to_string(),
+                };
+                let skip = if keyed { 1 } else { 0 };
+                for (column, value) in columns.iter().zip(row.values.iter()).skip(skip) {
+                    if value.is_null() {
+                        continue;
+                    }
+                    values.push((row_key.clone(), column.name.clone(), value.to_string()));
+                }
+            }
+        }
+        DataPayload::KeyValue(kv) => {
+            let mut keys: Vec<&String> = kv.keys().collect();
+            keys.sort();
+            for key in keys.into_iter().filter(|key| !kv[*key].is_null()) {
+                values.push((key.clone(), "value".to_string(), kv[key].to_string()));
+            }
+        }
+        DataPayload::Matrix(matrix) => {
+            for (row, row_label) in matrix.row_labels.iter().enumerate() {
+                for (column, column_label) in matrix.column_labels.iter().enumerate() {
+                    if let Some(value) = matrix.get(row, column).filter(|value| !value.is_null()) {
+                        values.push((row_label.clone(), column_label.clone(), value.to_string()));
+                    }
+                }
+            }
+        }
+        DataPayload::Tree(root) => {
+            let mut stack = vec![(root.label.clone(), Arc::clone(root))];
+            while let Some((path, node)) = stack.pop() {
+                if let Some(value) = node.value.as_ref().filter(|value| !value.is_null()) {
+                    values.push((path.clone(), "value".to_string(), value.to_string()));
+                }
+                for child in node.children.iter().rev() {
+                    stack.push((format!("{}/{}", path, child.label), Arc::clone(child)));
+                }
+            }
+        }
+        DataPayload::Raw(raw) => values.push((String::new(), "raw".to_string(), raw.to_string())),
+        DataPayload::Empty => {}
+    }
+    values
+}
+
+/// When console and markdown output render an export vertically, one `field: value` per line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transpose {
+    /// Transpose single-row tables with many columns
+    #[default]
+    Auto,
+    /// Transpose every single-row table and key-value export
+    Always,
+    /// Always render tables as tables
+    Never,
+}
+
+impl Transpose {
+    /// Parse a transpose mode name (`auto`, `always`, `never`)
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+    
+    /// Field names and values to render vertically, or `None` to render the export as usual
+    pub fn fields(self, export: &PluginDataExport) -> Option<Vec<(String, String)>> {
+        match (&export.data, self) {
+            (_, Transpose::Never) => None,
+            (DataPayload::Rows(rows), _) if rows.len() == 1 && !export.schema.columns.is_empty() => {
+                let columns = &export.schema.columns;
+                if self == Transpose::Auto && columns.len() < AUTO_TRANSPOSE_COLUMNS {
+                    return None;
+                }
+                Some(columns.iter()
+                    .zip(rows[0].values.iter())
+                    .map(|(column, value)| (column.name.clone(), values::format_value(Some(column), value)))
+                    .collect())
+            }
+            (DataPayload::KeyValue(kv), Transpose::Always) if !kv.is_empty() => {
+                Some(kv.iter().map(|(key, value)| (key.clone(), value.to_string())).collect())
+            }
+            _ => None,
+        }
+    }
+}
+
@@ -0,0 +1,47 @@
+//! Names for exports written as database tables and files
+//!
+//! Names are lower-case ASCII with runs of other characters replaced by `_`, so
+//! they can be used unquoted in SQL and in file names on any platform.
+
+use crate::plugin::data_export::PluginDataExport;
+use std::collections::HashSet;
+
+/// Table name for an export: its plugin followed by its title, unless the title already starts with it
+pub fn table_name(export: &PluginDataExport) -> String {
+    let plugin = identifier(&export.plugin_id);
+    let title = identifier(&export.title);
+    if title == plugin || title.starts_with(&format!("{}_", plugin)) {
+        title
+    } else {
+        format!("{}_{}", plugin, title)
+    }
+}
+
+/// Lower-case identifier with runs of other characters replaced by `_`
+pub fn identifier(name: &str) -> String {
+    let mut identifier = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            identifier.push(c.to_ascii_lowercase());
+        } else if !identifier.is_empty() && !identifier.ends_with('_') {
+            identifier.push('_');
+        }
+    }
+    let identifier = identifier.trim_end_matches('_');
+    match identifier.chars().next() {
+        None => "field".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{}", identifier),
+        Some(_) => identifier.to_string(),
+    }
+}
+
+/// `name`, or `name_2`, `name_3`, ... if it is already taken
+pub fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut unique = name.to_string();
+    let mut suffix = 2;
+    while !taken.insert(unique.clone()) {
+        unique = format!("{}_{}", name, suffix);
+        suffix += 1;
+    }
+    unique
+}
@@ -0,0 +1,132 @@
+//! NDJSON (newline-delimited JSON) export format implementation
+//!
+//! Each record is written as one compact JSON object per line, so output can be
+//! piped into `jq` and similar tools and written as soon as a plugin's data is
+//! ready, without holding the whole report in memory. Every object carries a
+//! `plugin` field naming its source:
+//!
+//! - rows become one object keyed by column name
+//! - key/value data becomes a single object
+//! - matrix rows become one object keyed by the corner label and column labels
+//! - tree nodes become one object per node with its `/`-separated `path`
+//! - raw data becomes one object with a `raw` field
+
+use super::FormatExporter;
+use super::json::value_to_json;
+use crate::plugin::{PluginResult, PluginError};
+use crate::plugin::data_export::{PluginDataExport, DataPayload, TreeNode};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::sync::Arc;
+
+/// NDJSON formatter
+pub struct NdjsonFormatter;
+
+impl NdjsonFormatter {
+    /// Create a new NDJSON formatter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write one plugin's data, one JSON object per line
+    pub fn write_export<W: Write>(&self, export: &PluginDataExport, writer: &mut W) -> std::io::Result<()> {
+        match &export.data {
+            DataPayload::Rows(rows) => {
+                for row in rows.iter() {
+                    let mut record = self.record(export);
+                    for (column, value) in export.schema.columns.iter().zip(row.values.iter()) {
+                        record.insert(column.name.clone(), value_to_json(value));
+                    }
+                    write_record(writer, record)?;
+                }
+            }
+
+            DataPayload::KeyValue(map) => {
+                let mut record = self.record(export);
+                for (key, value) in map.iter() {
+                    record.insert(key.clone(), value_to_json(value));
+                }
+                write_record(writer, record)?;
+            }
+
+            DataPayload::Matrix(matrix) => {
+                for (label, cells) in matrix.row_labels.iter().zip(matrix.cells.iter()) {
+                    let mut record = self.record(export);
+                    record.insert(matrix.corner_label.clone(), json!(label));
+                    for (column, value) in matrix.column_labels.iter().zip(cells.iter()) {
+                        record.insert(column.clone(), value_to_json(value));
+                    }
+                    write_record(writer, record)?;
+                }
+            }
+
+            DataPayload::Tree(root) => {
+                self.write_tree_node(export, root, "", writer)?;
+            }
+
+            DataPayload::Raw(raw) => {
+                let mut record = self.record(export);
+                record.insert("raw".to_string(), json!(raw.as_str()));
+                write_record(writer, record)?;
+            }
+
+            DataPayload::Empty => {}
+        }
+
+        writer.flush()
+    }
+
+    /// Start a record with the fields identifying its source plugin and repository
+    fn record(&self, export: &PluginDataExport) -> Map<String, Value> {
+        let mut record = Map::new();
+        record.insert("plugin".to_string(), json!(export.plugin_id));
+        if let Some(repository) = &export.repository {
+            record.insert("repository".to_string(), json!(repository));
+        }
+        record
+    }
+
+    fn write_tree_node<W: Write>(&self, export: &PluginDataExport, node: &TreeNode, parent: &str, writer: &mut W) -> std::io::Result<()> {
+        let path = if parent.is_empty() {
+            node.label.clone()
+        } else {
+            format!("{}/{}", parent, node.label)
+        };
+
+        let mut record = self.record(export);
+        record.insert("path".to_string(), json!(path));
+        if let Some(value) = &node.value {
+            record.insert("value".to_string(), value_to_json(value));
+        }
+        write_record(writer, record)?;
+
+        for child in &node.children {
+            self.write_tree_node(export, child, &path, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for NdjsonFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatExporter for NdjsonFormatter {
+    fn format_data(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
+        let mut output = Vec::new();
+        for export in data {
+            self.write_export(export, &mut output)
+                .map_err(|e| PluginError::execution_failed(format!("NDJSON serialization failed: {}", e)))?;
+        }
+        String::from_utf8(output)
+            .map_err(|e| PluginError::execution_failed(format!("NDJSON serialization failed: {}", e)))
+    }
+}
+
+fn write_record<W: Write>(writer: &mut W, record: Map<String, Value>) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *writer, &Value::Object(record))?;
+    writer.write_all(b"\n")
+}
+
@@ -0,0 +1,221 @@
+//! Parquet export format implementation
+//!
+//! Writes exports as Parquet files for loading into pandas, Polars and other
+//! data-science tools. In the default wide layout each export is written to its
+//! own file next to the output path, named after the plugin and export title:
+//! `report.parquet` becomes `report.commits_summary.parquet`,
+//! `report.contributors_bus_factor.parquet` and so on. Columns keep their names
+//! and take their Arrow type from the schema:
+//!
+//! - strings as `Utf8`, integers as `Int64`, floats as `Float64`, booleans as `Boolean`
+//! - timestamps as `Timestamp(Second, UTC)` and durations as `Float64` seconds
+//!
+//! Exports that are not tables are written as `row_key, column, value` rows.
+//! With the long layout every export goes into the single output file as
+//! `plugin, table, row_key, column, value` rows, as in the long CSV layout.
+//!
+//! Column descriptions and format hints are kept as Arrow field metadata, and the
+//! export's plugin, title, description and schema metadata as file metadata.
+//! Files are compressed with Snappy.
+
+use super::{melt, Layout};
+use super::naming::{table_name, unique_name};
+use crate::plugin::{PluginResult, PluginError};
+use crate::plugin::data_export::{ColumnType, DataPayload, PluginDataExport, Value};
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
+    TimestampSecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+/// Parquet file writer
+pub struct ParquetWriter {
+    layout: Layout,
+}
+
+impl ParquetWriter {
+    /// Create a new Parquet writer for the wide layout
+    pub fn new() -> Self {
+        Self { layout: Layout::default() }
+    }
+
+    /// Set the layout: one file per export (wide) or a single long-format file
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Write all exports for the output path, returning the files written
+    pub fn write(&self, data: &[Arc<PluginDataExport>], path: &Path) -> PluginResult<Vec<PathBuf>> {
+        match self.layout {
+            Layout::Long => {
+                write_batch(path, long_batch(data)?)?;
+                Ok(vec![path.to_path_buf()])
+            }
+            Layout::Wide => {
+                let mut names = HashSet::new();
+                data.iter()
+                    .map(|export| {
+                        let file = export_path(path, &unique_name(&table_name(export), &mut names));
+                        write_batch(&file, export_batch(export)?)?;
+                        Ok(file)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Default for ParquetWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// File for one export in the wide layout: the output file name with the export's name before the extension
+pub fn export_path(path: &Path, name: &str) -> PathBuf {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!("{}.{}.parquet", stem, name))
+}
+
+/// One export as a record batch, with typed columns for tables
+fn export_batch(export: &PluginDataExport) -> PluginResult<RecordBatch> {
+    let mut metadata: HashMap<String, String> = export.schema.metadata.clone();
+    metadata.insert("plugin".to_string(), export.plugin_id.clone());
+    metadata.insert("title".to_string(), export.title.clone());
+    if let Some(description) = &export.description {
+        metadata.insert("description".to_string(), description.clone());
+    }
+
+    let columns = &export.schema.columns;
+    let rows = match &export.data {
+        DataPayload::Rows(rows) if !columns.is_empty() => rows,
+        _ => {
+            let values = melt(export);
+            let fields = ["row_key", "column", "value"]
+                .map(|name| Field::new(name, DataType::Utf8, false));
+            let arrays: Vec<ArrayRef> = vec![
+                string_array(values.iter().map(|(row_key, _, _)| Some(row_key.as_str()))),
+                string_array(values.iter().map(|(_, column, _)| Some(column.as_str()))),
+                string_array(values.iter().map(|(_, _, value)| Some(value.as_str()))),
+            ];
+            return record_batch(Schema::new_with_metadata(fields.to_vec(), metadata), arrays);
+        }
+    };
+
+    let mut names = HashSet::new();
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::with_capacity(columns.len());
+    for (i, column) in columns.iter().enumerate() {
+        let mut field_metadata = HashMap::new();
+        if let Some(description) = &column.description {
+            field_metadata.insert("description".to_string(), description.clone());
+        }
+        if let Some(format_hint) = &column.format_hint {
+            field_metadata.insert("format_hint".to_string(), format_hint.clone());
+        }
+        let name = unique_name(&column.name, &mut names);
+        fields.push(Field::new(name, arrow_type(&column.data_type), true).with_metadata(field_metadata));
+
+        let values: Vec<Option<&Value>> = rows.iter()
+            .map(|row| row.values.get(i).filter(|value| !value.is_null()))
+            .collect();
+        arrays.push(column_array(&column.data_type, &values));
+    }
+    record_batch(Schema::new_with_metadata(fields, metadata), arrays)
+}
+
+/// Every export as `plugin, table, row_key, column, value` rows
+fn long_batch(data: &[Arc<PluginDataExport>]) -> PluginResult<RecordBatch> {
+    let mut rows = Vec::new();
+    for export in data {
+        for (row_key, column, value) in melt(export) {
+            rows.push([export.plugin_id.clone(), export.title.clone(), row_key, column, value]);
+        }
+    }
+    let fields: Vec<Field> = ["plugin", "table", "row_key", "column", "value"].iter()
+        .map(|name| Field::new(*name, DataType::Utf8, false))
+        .collect();
+    let arrays = (0..fields.len())
+        .map(|i| string_array(rows.iter().map(|row| Some(row[i].as_str()))))
+        .collect();
+    record_batch(Schema::new(fields), arrays)
+}
+
+/// Arrow type of a schema column
+fn arrow_type(column_type: &ColumnType) -> DataType {
+    match column_type {
+        ColumnType::String => DataType::Utf8,
+        ColumnType::Integer => DataType::Int64,
+        ColumnType::Float | ColumnType::Duration => DataType::Float64,
+        ColumnType::Boolean => DataType::Boolean,
+        ColumnType::Timestamp => DataType::Timestamp(TimeUnit::Second, Some("UTC".into())),
+    }
+}
+
+/// Values of one column as an array of its Arrow type; values of another type are left null
+fn column_array(column_type: &ColumnType, values: &[Option<&Value>]) -> ArrayRef {
+    match column_type {
+        ColumnType::String => string_array(values.iter().map(|value| value.map(|value| value.to_string()))),
+        ColumnType::Integer => Arc::new(values.iter()
+            .map(|value| match value {
+                Some(Value::Integer(i)) => Some(*i),
+                _ => None,
+            })
+            .collect::<Int64Array>()),
+        ColumnType::Float => Arc::new(values.iter()
+            .map(|value| value.and_then(Value::as_f64))
+            .collect::<Float64Array>()),
+        ColumnType::Duration => Arc::new(values.iter()
+            .map(|value| match value {
+                Some(Value::Duration(d)) => Some(d.as_secs_f64()),
+                _ => None,
+            })
+            .collect::<Float64Array>()),
+        ColumnType::Boolean => Arc::new(values.iter()
+            .map(|value| match value {
+                Some(Value::Boolean(b)) => Some(*b),
+                _ => None,
+            })
+            .collect::<BooleanArray>()),
+        ColumnType::Timestamp => Arc::new(values.iter()
+            .map(|value| match value {
+                Some(Value::Timestamp(t)) => t.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs() as i64),
+                Some(Value::Integer(i)) => Some(*i),
+                _ => None,
+            })
+            .collect::<TimestampSecondArray>()
+            .with_timezone("UTC")),
+    }
+}
+
+fn string_array<S: AsRef<str>>(values: impl Iterator<Item = Option<S>>) -> ArrayRef {
+    Arc::new(values.collect::<StringArray>())
+}
+
+fn record_batch(schema: Schema, arrays: Vec<ArrayRef>) -> PluginResult<RecordBatch> {
+    RecordBatch::try_new(Arc::new(schema), arrays).map_err(|e| parquet_error(e.to_string()))
+}
+
+/// Write a record batch to a new Snappy-compressed Parquet file
+fn write_batch(path: &Path, batch: RecordBatch) -> PluginResult<()> {
+    let file = std::fs::File::create(path)
+        .map_err(|e| PluginError::io_error(format!("Failed to create {}: {}", path.display(), e)))?;
+    let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties))
+        .map_err(|e| parquet_error(e.to_string()))?;
+    writer.write(&batch).map_err(|e| parquet_error(e.to_string()))?;
+    writer.close().map_err(|e| parquet_error(e.to_string()))?;
+    Ok(())
+}
+
+fn parquet_error(error: String) -> PluginError {
+    PluginError::io_error(format!("Parquet export failed: {}", error))
+}
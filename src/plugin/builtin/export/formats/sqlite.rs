@@ -0,0 +1,170 @@
+//! SQLite export format implementation
+//!
+//! Writes every export into a SQLite database, so results can be queried with
+//! SQL instead of parsed. Each export becomes a table named after its plugin
+//! and title (`contributors_bus_factor`), with one column per schema column.
+//! Table and column names are lower-cased with other characters replaced by
+//! `_`, so most can be used in queries without quoting.
+//!
+//! Columns take their SQL type from the schema: integers and booleans are
+//! `INTEGER`, floats `REAL`, timestamps `INTEGER` seconds since the epoch,
+//! durations `REAL` seconds and strings `TEXT`. Exports that are not tables
+//! are stored as `row_key, column_name, value` rows, as in the long CSV layout.
+//!
+//! Two catalogue tables describe the database: `gstats_exports` lists each
+//! table with its plugin, title, description and row count, and
+//! `gstats_columns` gives each column's original name, type, description and
+//! format hint. An existing file at the output path is replaced.
+
+use super::melt;
+use super::naming::{identifier, table_name, unique_name};
+use crate::plugin::{PluginResult, PluginError};
+use crate::plugin::data_export::{ColumnType, DataPayload, PluginDataExport, Value};
+use rusqlite::{params, params_from_iter, Connection, Transaction};
+use rusqlite::types::Value as SqlValue;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+/// Table listing the exports in a database
+pub const EXPORTS_TABLE: &str = "gstats_exports";
+
+/// Table describing the columns of each export table
+pub const COLUMNS_TABLE: &str = "gstats_columns";
+
+/// SQLite database writer
+pub struct SqliteWriter;
+
+impl SqliteWriter {
+    /// Create a new SQLite writer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write all exports to a new database at `path`
+    pub fn write(&self, data: &[Arc<PluginDataExport>], path: &Path) -> PluginResult<()> {
+        if path.exists() {
+            std::fs::remove_file(path)
+                .map_err(|e| PluginError::io_error(format!("Failed to replace {}: {}", path.display(), e)))?;
+        }
+        let mut connection = Connection::open(path).map_err(sql_error)?;
+        let transaction = connection.transaction().map_err(sql_error)?;
+        transaction.execute_batch(&format!(
+            "CREATE TABLE {} (table_name TEXT PRIMARY KEY, plugin_id TEXT NOT NULL, title TEXT NOT NULL, \
+             description TEXT, data_type TEXT NOT NULL, row_count INTEGER NOT NULL, exported_at INTEGER);\n\
+             CREATE TABLE {} (table_name TEXT NOT NULL, position INTEGER NOT NULL, column_name TEXT NOT NULL, \
+             label TEXT NOT NULL, data_type TEXT NOT NULL, description TEXT, format_hint TEXT, \
+             PRIMARY KEY (table_name, position));",
+            EXPORTS_TABLE, COLUMNS_TABLE
+        )).map_err(sql_error)?;
+
+        let mut tables = HashSet::from([EXPORTS_TABLE.to_string(), COLUMNS_TABLE.to_string()]);
+        for export in data {
+            let table = unique_name(&table_name(export), &mut tables);
+            let row_count = match &export.data {
+                DataPayload::Rows(_) => write_rows(&transaction, &table, export)?,
+                _ => write_long(&transaction, &table, export)?,
+            };
+            transaction.execute(
+                &format!("INSERT INTO {} VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)", EXPORTS_TABLE),
+                params![
+                    table,
+                    export.plugin_id,
+                    export.title,
+                    export.description,
+                    format!("{:?}", export.data_type),
+                    row_count as i64,
+                    export.timestamp.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs() as i64),
+                ],
+            ).map_err(sql_error)?;
+        }
+        transaction.commit().map_err(sql_error)
+    }
+}
+
+/// Write a table export with one typed column per schema column, returning the number of rows
+fn write_rows(transaction: &Transaction, table: &str, export: &PluginDataExport) -> PluginResult<usize> {
+    let DataPayload::Rows(rows) = &export.data else {
+        return Ok(0);
+    };
+    let columns = &export.schema.columns;
+    let mut names = HashSet::new();
+    let mut definitions = Vec::with_capacity(columns.len());
+    for (position, column) in columns.iter().enumerate() {
+        let name = unique_name(&identifier(&column.name), &mut names);
+        let sql_type = sql_type(&column.data_type);
+        definitions.push(format!("{} {}", quoted(&name), sql_type));
+        transaction.execute(
+            &format!("INSERT INTO {} VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)", COLUMNS_TABLE),
+            params![table, position as i64, name, column.name, sql_type, column.description, column.format_hint],
+        ).map_err(sql_error)?;
+    }
+    if definitions.is_empty() {
+        definitions.push(format!("{} TEXT", quoted("value")));
+    }
+    transaction.execute(&format!("CREATE TABLE {} ({})", quoted(table), definitions.join(", ")), [])
+        .map_err(sql_error)?;
+
+    let placeholders = vec!["?"; definitions.len()].join(", ");
+    let mut insert = transaction.prepare(&format!("INSERT INTO {} VALUES ({})", quoted(table), placeholders))
+        .map_err(sql_error)?;
+    for row in rows.iter() {
+        let values = (0..definitions.len()).map(|i| row.values.get(i).map(sql_value).unwrap_or(SqlValue::Null));
+        insert.execute(params_from_iter(values)).map_err(sql_error)?;
+    }
+    Ok(rows.len())
+}
+
+/// Write a non-tabular export as `row_key, column_name, value` rows, returning the number of rows
+fn write_long(transaction: &Transaction, table: &str, export: &PluginDataExport) -> PluginResult<usize> {
+    transaction.execute(&format!("CREATE TABLE {} (row_key TEXT, column_name TEXT, value TEXT)", quoted(table)), [])
+        .map_err(sql_error)?;
+    for (position, name) in ["row_key", "column_name", "value"].iter().enumerate() {
+        transaction.execute(
+            &format!("INSERT INTO {} (table_name, position, column_name, label, data_type) VALUES (?1, ?2, ?3, ?3, 'TEXT')", COLUMNS_TABLE),
+            params![table, position as i64, name],
+        ).map_err(sql_error)?;
+    }
+
+    let values = melt(export);
+    let mut insert = transaction.prepare(&format!("INSERT INTO {} VALUES (?1, ?2, ?3)", quoted(table)))
+        .map_err(sql_error)?;
+    for (row_key, column, value) in &values {
+        insert.execute(params![row_key, column, value]).map_err(sql_error)?;
+    }
+    Ok(values.len())
+}
+
+/// SQL type of a schema column
+fn sql_type(column_type: &ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::Integer | ColumnType::Boolean | ColumnType::Timestamp => "INTEGER",
+        ColumnType::Float | ColumnType::Duration => "REAL",
+        ColumnType::String => "TEXT",
+    }
+}
+
+/// SQL value of an export value
+fn sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Integer(i) => SqlValue::Integer(*i),
+        Value::Float(f) => SqlValue::Real(*f),
+        Value::Boolean(b) => SqlValue::Integer(i64::from(*b)),
+        Value::Timestamp(t) => t.duration_since(UNIX_EPOCH)
+            .map(|d| SqlValue::Integer(d.as_secs() as i64))
+            .unwrap_or(SqlValue::Null),
+        Value::Duration(d) => SqlValue::Real(d.as_secs_f64()),
+        Value::Null => SqlValue::Null,
+    }
+}
+
+/// Quote an identifier, in case it is an SQL keyword
+fn quoted(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn sql_error(error: rusqlite::Error) -> PluginError {
+    PluginError::io_error(format!("SQLite export failed: {}", error))
+}
@@ -2,22 +2,29 @@
 //! 
 //! This formatter uses the Tera template engine to render data using custom templates.
 //! Templates are specified via the --template flag and can access all plugin data and metadata.
+//! A --template-dir of partials and base templates can be included or extended, and
+//! --template-var values are available to every template.
 
 use super::FormatExporter;
 use crate::plugin::PluginResult;
 use crate::plugin::data_export::PluginDataExport;
 use crate::plugin::builtin::export::template_engine::TemplateEngine;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub struct TemplateExporter {
-    template_file: std::path::PathBuf,
+    template_file: Option<PathBuf>,
+    template_dir: Option<PathBuf>,
+    template_vars: HashMap<String, String>,
 }
 
 impl TemplateExporter {
-    pub fn new(template_file: &Path) -> Self {
+    pub fn new(template_file: Option<&Path>, template_dir: Option<&Path>, template_vars: HashMap<String, String>) -> Self {
         Self {
-            template_file: template_file.to_path_buf(),
+            template_file: template_file.map(Path::to_path_buf),
+            template_dir: template_dir.map(Path::to_path_buf),
+            template_vars,
         }
     }
 }
@@ -26,17 +33,17 @@ impl FormatExporter for TemplateExporter {
     fn format_data(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
         // Create and configure template engine
         let mut engine = TemplateEngine::new();
-        engine.load_template(&self.template_file)?;
+        engine.load(self.template_file.as_deref(), self.template_dir.as_deref())?;
         
         // Add global template variables
         engine.add_template_var("plugin_count".to_string(), data.len().to_string());
         engine.add_template_var("timestamp".to_string(), 
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()
-                .to_string()
+            crate::clock::now_seconds().to_string()
         );
+        // User variables come last so they can override the global ones
+        for (key, value) in &self.template_vars {
+            engine.add_template_var(key.clone(), value.clone());
+        }
         
         let mut output = String::new();
         
@@ -90,6 +97,20 @@ impl FormatExporter for TemplateExporter {
                     
                     serde_json::Value::Object(json_obj)
                 },
+                crate::plugin::data_export::DataPayload::Matrix(matrix) => {
+                    serde_json::json!({
+                        "title": export.title,
+                        "description": export.description,
+                        "plugin_id": export.plugin_id,
+                        "data_type": "matrix",
+                        "corner_label": matrix.corner_label,
+                        "row_labels": matrix.row_labels,
+                        "column_labels": matrix.column_labels,
+                        "cells": matrix.cells.iter()
+                            .map(|row| row.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+                            .collect::<Vec<_>>()
+                    })
+                },
                 crate::plugin::data_export::DataPayload::Raw(_) => {
                     serde_json::json!({
                         "title": export.title,
@@ -0,0 +1,245 @@
+//! Totals Rows
+//!
+//! Appends a summary row to tables before they are formatted, so console, CSV,
+//! HTML and Markdown output all end a table with the same totals.
+
+use crate::plugin::data_export::{ColumnDef, ColumnType, DataPayload, PluginDataExport, Row, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Prefix of the custom export hints that choose how a column is totalled
+///
+/// `totals.Percentage = avg` averages the `Percentage` column, and `none` leaves it empty.
+pub const TOTALS_HINT_PREFIX: &str = "totals.";
+
+/// Row metadata key marking a totals row, so it can be told apart from the table's data
+pub const TOTALS_ROW_METADATA: &str = "totals";
+
+/// Check whether a row is a totals row added by [`Totals::apply`]
+pub fn is_totals_row(row: &Row) -> bool {
+    row.metadata.as_ref().is_some_and(|metadata| metadata.contains_key(TOTALS_ROW_METADATA))
+}
+
+/// How the values of a numeric column are summarised
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Average,
+}
+
+impl Aggregate {
+    /// Parse an aggregate name (`sum`, `avg`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "sum" | "total" => Some(Self::Sum),
+            "avg" | "average" | "mean" => Some(Self::Average),
+            _ => None,
+        }
+    }
+
+    /// Label of a summary row of this aggregate
+    fn label(self) -> &'static str {
+        match self {
+            Self::Sum => "Total",
+            Self::Average => "Average",
+        }
+    }
+}
+
+/// Which tables end with a totals row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Totals {
+    /// Tables whose export hints include totals, totalled as the hints say (summed by default)
+    #[default]
+    Auto,
+    /// Every table, with each numeric column summarised the same way
+    All(Aggregate),
+    /// No totals rows
+    Never,
+}
+
+impl Totals {
+    /// Parse a totals mode name (`auto`, `sum`, `avg`, `never`)
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "never" | "none" => Some(Self::Never),
+            other => Aggregate::parse(other).map(Self::All),
+        }
+    }
+
+    /// The exports with a totals row added to each table that should have one
+    pub fn apply(self, data: &[Arc<PluginDataExport>]) -> Vec<Arc<PluginDataExport>> {
+        data.iter()
+            .map(|export| match (self.totals_row(export), &export.data) {
+                (Some(totals), DataPayload::Rows(rows)) => {
+                    let mut rows = rows.as_ref().clone();
+                    rows.push(totals);
+                    let mut export = export.as_ref().clone();
+                    export.data = DataPayload::Rows(Arc::new(rows));
+                    Arc::new(export)
+                }
+                _ => Arc::clone(export),
+            })
+            .collect()
+    }
+
+    /// The totals row of an export, for tables of two or more rows that should have one
+    ///
+    /// Numeric and duration columns are summarised; a leading text column holds the
+    /// row's label and other columns are left empty. Findings are never totalled.
+    pub fn totals_row(self, export: &PluginDataExport) -> Option<Row> {
+        let DataPayload::Rows(rows) = &export.data else {
+            return None;
+        };
+        let label = match self {
+            Totals::Never => return None,
+            Totals::Auto if !export.export_hints.include_totals => return None,
+            Totals::Auto => Aggregate::Sum.label(),
+            Totals::All(aggregate) => aggregate.label(),
+        };
+        if rows.len() < 2 || export.is_findings() {
+            return None;
+        }
+
+        let mut values: Vec<Value> = export.schema.columns.iter().enumerate()
+            .map(|(index, column)| match self.aggregate_of(export, column) {
+                Some(aggregate) => summarise(&column.data_type, rows.iter().filter_map(|row| row.values.get(index)), aggregate),
+                None => Value::Null,
+            })
+            .collect();
+        if values.iter().all(Value::is_null) {
+            return None;
+        }
+        if let (Some(first), Some(ColumnType::String)) = (values.first_mut(), export.schema.columns.first().map(|column| &column.data_type)) {
+            *first = Value::String(label.to_string());
+        }
+        let mut row = Row::new(values);
+        row.metadata = Some(HashMap::from([(TOTALS_ROW_METADATA.to_string(), label.to_lowercase())]));
+        Some(row)
+    }
+
+    /// How a column is totalled, or `None` to leave it empty
+    fn aggregate_of(self, export: &PluginDataExport, column: &ColumnDef) -> Option<Aggregate> {
+        match self {
+            Totals::All(aggregate) => Some(aggregate),
+            _ => match export.export_hints.custom_hints.get(&format!("{}{}", TOTALS_HINT_PREFIX, column.name)) {
+                Some(name) => Aggregate::parse(name),
+                None => Some(Aggregate::Sum),
+            },
+        }
+    }
+}
+
+/// Summarise the values of a column of the given type, ignoring nulls and other types
+fn summarise<'a>(column_type: &ColumnType, values: impl Iterator<Item = &'a Value>, aggregate: Aggregate) -> Value {
+    match column_type {
+        ColumnType::Integer => {
+            let numbers: Vec<i64> = values.filter_map(|value| match value {
+                Value::Integer(number) => Some(*number),
+                _ => None,
+            }).collect();
+            let sum = numbers.iter().fold(0i64, |sum, number| sum.saturating_add(*number));
+            match aggregate {
+                _ if numbers.is_empty() => Value::Null,
+                Aggregate::Sum => Value::Integer(sum),
+                Aggregate::Average => Value::Float(sum as f64 / numbers.len() as f64),
+            }
+        }
+        ColumnType::Float => {
+            let numbers: Vec<f64> = values.filter_map(Value::as_f64).collect();
+            let sum: f64 = numbers.iter().sum();
+            match aggregate {
+                _ if numbers.is_empty() => Value::Null,
+                Aggregate::Sum => Value::Float(sum),
+                Aggregate::Average => Value::Float(sum / numbers.len() as f64),
+            }
+        }
+        ColumnType::Duration => {
+            let durations: Vec<Duration> = values.filter_map(|value| match value {
+                Value::Duration(duration) => Some(*duration),
+                _ => None,
+            }).collect();
+            let sum: Duration = durations.iter().sum();
+            match aggregate {
+                _ if durations.is_empty() => Value::Null,
+                Aggregate::Sum => Value::Duration(sum),
+                Aggregate::Average => Value::Duration(sum.div_f64(durations.len() as f64)),
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{DataExportType, DataSchema, ExportHints};
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+
+    fn authors(include_totals: bool) -> PluginDataExport {
+        PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Commit Analysis".to_string(),
+            description: None,
+            data_type: DataExportType::Tabular,
+            schema: DataSchema {
+                columns: vec![
+                    ColumnDef::new("Author", ColumnType::String),
+                    ColumnDef::new("Commits", ColumnType::Integer),
+                    ColumnDef::new("Percentage", ColumnType::Float).with_format_hint("percentage"),
+                    ColumnDef::new("Active", ColumnType::Boolean),
+                ],
+                metadata: HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(vec![
+                Row::new(vec![Value::String("alice".to_string()), Value::Integer(3), Value::Float(75.0), Value::Boolean(true)]),
+                Row::new(vec![Value::String("bob".to_string()), Value::Integer(1), Value::Float(25.0), Value::Null]),
+            ])),
+            export_hints: ExportHints { include_totals, ..ExportHints::default() },
+            timestamp: SystemTime::UNIX_EPOCH,
+            repository: None,
+        }
+    }
+
+    #[test]
+    fn test_totals_follow_export_hints() {
+        assert!(Totals::Auto.totals_row(&authors(false)).is_none());
+
+        let totals = Totals::Auto.totals_row(&authors(true)).unwrap();
+        assert_eq!(totals.values, vec![Value::String("Total".to_string()), Value::Integer(4), Value::Float(100.0), Value::Null]);
+        assert!(is_totals_row(&totals));
+
+        let mut export = authors(true);
+        export.export_hints.custom_hints.insert("totals.Percentage".to_string(), "avg".to_string());
+        export.export_hints.custom_hints.insert("totals.Commits".to_string(), "none".to_string());
+        let totals = Totals::Auto.totals_row(&export).unwrap();
+        assert_eq!(totals.values[1..3], [Value::Null, Value::Float(50.0)]);
+
+        assert!(Totals::Never.totals_row(&authors(true)).is_none());
+    }
+
+    #[test]
+    fn test_totals_mode_applies_to_every_table() {
+        let data = [Arc::new(authors(false))];
+        let totalled = Totals::parse("avg").unwrap().apply(&data);
+        let DataPayload::Rows(rows) = &totalled[0].data else { panic!("expected rows") };
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].values[..3], [Value::String("Average".to_string()), Value::Float(2.0), Value::Float(50.0)]);
+
+        // A single row is its own total
+        let mut single = authors(false);
+        single.data = DataPayload::Rows(Arc::new(vec![Row::new(vec![Value::String("alice".to_string()), Value::Integer(3)])]));
+        assert!(Totals::All(Aggregate::Sum).totals_row(&single).is_none());
+    }
+
+    #[test]
+    fn test_durations_are_totalled() {
+        let values = [Value::Duration(Duration::from_secs(30)), Value::Null, Value::Duration(Duration::from_secs(90))];
+        assert_eq!(summarise(&ColumnType::Duration, values.iter(), Aggregate::Sum), Value::Duration(Duration::from_secs(120)));
+        assert_eq!(summarise(&ColumnType::Duration, values.iter(), Aggregate::Average), Value::Duration(Duration::from_secs(60)));
+        assert_eq!(summarise(&ColumnType::Timestamp, values.iter(), Aggregate::Sum), Value::Null);
+    }
+}
@@ -0,0 +1,119 @@
+//! Column Value Formatting
+//!
+//! Renders table values as text following their column's format hint, shared by
+//! console, HTML, Markdown and CSV output so a value reads the same in each.
+
+use crate::plugin::data_export::{ColumnDef, Value};
+use crate::plugin::memory::format_bytes;
+use std::time::Duration;
+
+/// Format hint of columns holding percentages from 0 to 100, shown as `42.5%`
+pub const PERCENTAGE_HINT: &str = "percentage";
+
+/// Format hint of columns holding byte counts, shown as `1.5 KB`
+pub const BYTES_HINT: &str = "bytes";
+
+/// Format hint of columns holding durations or seconds, shown as `3h 20m`
+pub const DURATION_HINT: &str = "duration";
+
+/// Format hint of columns holding large counts, shown as `12,345`
+pub const THOUSANDS_HINT: &str = "thousands";
+
+/// Prefix of format hints fixing the decimals of a number, e.g. `decimals:1`
+pub const DECIMALS_HINT_PREFIX: &str = "decimals:";
+
+/// A table value as text, formatted as its column's hint asks
+///
+/// Values without a hint, or of a type the hint does not apply to, are shown as usual.
+pub fn format_value(column: Option<&ColumnDef>, value: &Value) -> String {
+    let Some(hint) = column.and_then(|column| column.format_hint.as_deref()) else {
+        return value.to_string();
+    };
+    let formatted = match (hint, value) {
+        (_, Value::Null) => None,
+        (PERCENTAGE_HINT, value) => value.as_f64().map(|percent| format!("{:.1}%", percent)),
+        (BYTES_HINT, Value::Integer(bytes)) => Some(match usize::try_from(*bytes) {
+            Ok(bytes) => format_bytes(bytes),
+            Err(_) => format!("-{}", format_bytes(bytes.unsigned_abs() as usize)),
+        }),
+        (DURATION_HINT, Value::Duration(duration)) => Some(format_span(*duration)),
+        (DURATION_HINT, value) => value.as_f64()
+            .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+            .map(|seconds| format_span(Duration::from_secs_f64(seconds))),
+        (THOUSANDS_HINT, Value::Integer(number)) => Some(with_separators(*number)),
+        (THOUSANDS_HINT, Value::Float(number)) if number.is_finite() => {
+            let fixed = format!("{:.2}", number.abs());
+            let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, "00"));
+            let sign = if *number < 0.0 { "-" } else { "" };
+            whole.parse::<i64>().ok().map(|whole| format!("{}{}.{}", sign, with_separators(whole), fraction))
+        }
+        (hint, value) => hint.strip_prefix(DECIMALS_HINT_PREFIX)
+            .and_then(|decimals| decimals.parse::<usize>().ok())
+            .zip(value.as_f64())
+            .map(|(decimals, number)| format!("{:.*}", decimals, number)),
+    };
+    formatted.unwrap_or_else(|| value.to_string())
+}
+
+/// An integer with `,` between each group of three digits
+fn with_separators(number: i64) -> String {
+    let digits = number.unsigned_abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if number < 0 {
+        grouped.push('-');
+    }
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// A duration in its two largest units, e.g. `45s`, `12m 05s`, `3h 20m`, `4d 06h`
+fn format_span(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, hours, minutes, seconds) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60, seconds % 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", seconds),
+        (0, 0, _) => format!("{}m {:02}s", minutes, seconds),
+        (0, _, _) => format!("{}h {:02}m", hours, minutes),
+        _ => format!("{}d {:02}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::ColumnType;
+
+    fn format(hint: &str, value: Value) -> String {
+        format_value(Some(&ColumnDef::new("Value", ColumnType::Float).with_format_hint(hint)), &value)
+    }
+
+    #[test]
+    fn test_values_follow_format_hints() {
+        assert_eq!(format(PERCENTAGE_HINT, Value::Float(42.456)), "42.5%");
+        assert_eq!(format(PERCENTAGE_HINT, Value::Integer(100)), "100.0%");
+        assert_eq!(format(BYTES_HINT, Value::Integer(1536)), "1.5 KB");
+        assert_eq!(format(BYTES_HINT, Value::Integer(-512)), "-512 B");
+        assert_eq!(format(DURATION_HINT, Value::Integer(45)), "45s");
+        assert_eq!(format(DURATION_HINT, Value::Duration(Duration::from_secs(12 * 60 + 5))), "12m 05s");
+        assert_eq!(format(DURATION_HINT, Value::Float(4.0 * 86400.0 + 6.0 * 3600.0)), "4d 06h");
+        assert_eq!(format(THOUSANDS_HINT, Value::Integer(-1234567)), "-1,234,567");
+        assert_eq!(format(THOUSANDS_HINT, Value::Float(12345.678)), "12,345.68");
+        assert_eq!(format(THOUSANDS_HINT, Value::Integer(999)), "999");
+        assert_eq!(format("decimals:1", Value::Float(2.26)), "2.3");
+        assert_eq!(format("decimals:0", Value::Integer(7)), "7");
+    }
+
+    #[test]
+    fn test_values_without_an_applicable_hint_are_unchanged() {
+        assert_eq!(format_value(None, &Value::Float(1.5)), "1.50");
+        assert_eq!(format("delta", Value::Integer(-3)), "-3");
+        assert_eq!(format(BYTES_HINT, Value::String("n/a".to_string())), "n/a");
+        assert_eq!(format(PERCENTAGE_HINT, Value::Null), "");
+        assert_eq!(format("decimals:x", Value::Float(1.0)), "1.00");
+    }
+}
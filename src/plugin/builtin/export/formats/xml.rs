@@ -58,6 +58,19 @@ impl FormatExporter for XmlFormatter {
                     output.push_str("    </data>\n");
                 }
                 
+                DataPayload::Matrix(matrix) => {
+                    output.push_str(&format!("    <data type=\"matrix\" corner=\"{}\">\n", escape_xml(&matrix.corner_label)));
+                    for (label, cells) in matrix.row_labels.iter().zip(&matrix.cells) {
+                        output.push_str(&format!("      <row label=\"{}\">\n", escape_xml(label)));
+                        for (column, value) in matrix.column_labels.iter().zip(cells) {
+                            output.push_str(&format!("        <cell column=\"{}\">{}</cell>\n",
+                                escape_xml(column), escape_xml(&value.to_string())));
+                        }
+                        output.push_str("      </row>\n");
+                    }
+                    output.push_str("    </data>\n");
+                }
+                
                 DataPayload::Tree(_) => {
                     output.push_str("    <data type=\"tree\">\n");
                     output.push_str("      <!-- Tree structure not yet implemented -->\n");
@@ -41,7 +41,7 @@ impl FormatExporter for YamlFormatter {
                         for (i, value) in row.values.iter().enumerate() {
                             if let Some(column) = export.schema.columns.get(i) {
                                 if i > 0 { output.push_str(", "); }
-                                output.push_str(&format!("{}: {}", column.name, value.to_string()));
+                                output.push_str(&format!("{}: {}", column.name, value));
                             }
                         }
                         output.push('\n');
@@ -51,7 +51,18 @@ impl FormatExporter for YamlFormatter {
                 DataPayload::KeyValue(kv) => {
                     output.push_str("  data:\n");
                     for (key, value) in kv.iter() {
-                        output.push_str(&format!("    {}: {}\n", key, value.to_string()));
+                        output.push_str(&format!("    {}: {}\n", key, value));
+                    }
+                }
+                
+                DataPayload::Matrix(matrix) => {
+                    output.push_str("  data:\n");
+                    for (label, cells) in matrix.row_labels.iter().zip(&matrix.cells) {
+                        output.push_str(&format!("    - {}: {}", matrix.corner_label, label));
+                        for (column, value) in matrix.column_labels.iter().zip(cells) {
+                            output.push_str(&format!(", {}: {}", column, value));
+                        }
+                        output.push('\n');
                     }
                 }
                 
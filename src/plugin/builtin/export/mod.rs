@@ -5,12 +5,13 @@
 pub mod template_engine;
 pub mod config;
 pub mod formats;
+pub mod artifact;
 
 use crate::plugin::{
-    Plugin, PluginInfo, PluginContext, PluginRequest, PluginResponse,
+    Plugin, PluginInfo, PluginContext, ScanContext, PluginRequest, PluginResponse,
     PluginResult, PluginError, traits::{PluginType, PluginClapParser, PluginDataRequirements}
 };
-use crate::plugin::data_export::{PluginDataExport, DataPayload, ColumnType};
+use crate::plugin::data_export::{PluginDataExport, DataPayload, ColumnType, FindingSeverity, Row, FINDINGS_SEVERITY_COLUMN};
 use crate::plugin::data_coordinator::DataCoordinator;
 use crate::plugin::builtin::utils::format_detection::{FormatDetector, FormatDetectionResult};
 use crate::notifications::events::PluginEvent;
@@ -19,12 +20,14 @@ use crate::notifications::{NotificationResult, AsyncNotificationManager};
 use crate::notifications::error::NotificationError;
 use crate::display::ColourManager;
 use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use serde_json::json;
 
-pub use config::{ExportConfig, ExportFormat};
+pub use config::{ExportConfig, ExportFormat, ExportTarget};
+pub use formats::annotations::AnnotationStyle;
 pub use template_engine::TemplateEngine;
 
 /// Data export plugin for various output formats
@@ -48,6 +51,12 @@ pub struct ExportPlugin {
     /// Scan tracking and export state
     export_triggered: Arc<RwLock<bool>>,
     
+    /// Output files that streamed (NDJSON) output has been written to in this round
+    stream_started: Arc<RwLock<HashSet<PathBuf>>>,
+    
+    /// Scan details added to the metadata of every export, such as the ref scanned
+    scan_metadata: HashMap<String, String>,
+    
     /// Notification manager for publishing events - REQUIRED for all plugins
     notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
     
@@ -103,6 +112,8 @@ impl ExportPlugin {
                 ])
             )),
             export_triggered: Arc::new(RwLock::new(false)),
+            stream_started: Arc::new(RwLock::new(HashSet::new())),
+            scan_metadata: HashMap::new(),
             notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
             colour_manager: Arc::new(RwLock::new(None)),
         }
@@ -122,6 +133,24 @@ impl ExportPlugin {
     }
     
     
+    /// Add the scan metadata to an export, keeping any values the plugin set itself
+    ///
+    /// In reproducible runs the export is also stamped with the fixed clock.
+    fn with_scan_metadata(&self, export: Arc<PluginDataExport>) -> Arc<PluginDataExport> {
+        let fixed_time = crate::clock::fixed().filter(|time| export.timestamp != *time);
+        if fixed_time.is_none() && self.scan_metadata.keys().all(|key| export.schema.metadata.contains_key(key)) {
+            return export;
+        }
+        let mut export = (*export).clone();
+        for (key, value) in &self.scan_metadata {
+            export.schema.metadata.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        if let Some(time) = fixed_time {
+            export.timestamp = time;
+        }
+        Arc::new(export)
+    }
+    
     /// Handle PluginEvent::DataReady - core export functionality
     async fn handle_data_ready_event(&self, 
         plugin_id: String, 
@@ -129,37 +158,31 @@ impl ExportPlugin {
         export_data: Arc<PluginDataExport>
     ) -> PluginResult<()> {
         log::info!("ExportPlugin: Received DataReady from plugin '{}' for scan '{}'", plugin_id, scan_id);
+        let export_data = self.with_scan_metadata(export_data);
+        
+        let config = self.export_config.read().await.clone();
+        
+        // NDJSON targets are written as each plugin's data arrives; other targets share the collected data
+        let (streamed, collected): (Vec<_>, Vec<_>) = config.targets().into_iter()
+            .partition(|target| target.format == ExportFormat::Ndjson);
+        for target in &streamed {
+            self.stream_ndjson(&export_data, &config.for_target(target)).await?;
+        }
         
         // Add the data to our coordinator
         {
             let mut coordinator = self.data_coordinator.write().await;
-            coordinator.add_data(plugin_id.clone(), export_data.clone());
+            coordinator.set_scan_id(scan_id.clone());
+            if collected.is_empty() {
+                coordinator.mark_received(plugin_id.clone());
+            } else {
+                coordinator.add_data(plugin_id.clone(), export_data.clone());
+            }
             
             // Check if we have all expected data
             if coordinator.is_complete() {
                 log::info!("ExportPlugin: All expected data collected for scan '{}', triggering export", scan_id);
-                
-                // Collect all data
-                let collected_data = coordinator.get_all_data();
-                
-                // Perform the export using the configured format
-                let config = self.export_config.read().await;
-                let formatted = self.format_data(&collected_data, &config).await?;
-                
-                // Output the formatted data
-                if let Some(ref output_path) = config.output_file {
-                    std::fs::write(output_path, &formatted)
-                        .map_err(|e| PluginError::io_error(format!("Failed to write output file: {}", e)))?;
-                    log::info!("Exported data to {}", output_path.display());
-                } else {
-                    println!("{}", formatted);
-                }
-                
-                // Publish completion event using Publisher trait
-                self.publish_export_completion_event(&scan_id, &plugin_id).await?;
-                
-                // Clear coordinator for next round
-                coordinator.clear();
+                self.export_collected(&mut coordinator, &config, collected, &scan_id, &plugin_id).await?;
             } else {
                 let pending = coordinator.get_pending_plugins();
                 log::debug!("ExportPlugin: Still waiting for data from plugins: {:?}", pending);
@@ -169,6 +192,165 @@ impl ExportPlugin {
         Ok(())
     }
     
+    /// Format the collected data for each target and start a new round
+    async fn export_collected(
+        &self,
+        coordinator: &mut DataCoordinator,
+        config: &ExportConfig,
+        mut collected: Vec<ExportTarget>,
+        scan_id: &str,
+        plugin_id: &str,
+    ) -> PluginResult<()> {
+        // Collect all data once and format it for each target
+        let mut collected_data = unify_findings(coordinator.get_all_data());
+        if let Some(filter) = &config.row_filter {
+            collected_data = filter.apply(&collected_data)?;
+        }
+        let collected_data = formats::columns::reshape(&collected_data, &config.columns, &config.sort)?;
+        if !config.format_chosen {
+            if let (Some(format), Some(primary)) = (preferred_format(&collected_data), collected.first_mut()) {
+                log::debug!("ExportPlugin: Using the preferred format {:?}", format);
+                primary.format = format;
+            }
+        }
+        for target in &collected {
+            match target.format {
+                ExportFormat::Sqlite => {
+                    let file = self.write_sqlite(&collected_data, target)?;
+                    publish_outputs(config, &[file])?;
+                    continue;
+                }
+                ExportFormat::Parquet => {
+                    let files = self.write_parquet(&collected_data, target, config.layout)?;
+                    publish_outputs(config, &files)?;
+                    continue;
+                }
+                _ => {}
+            }
+            let formatted = self.format_data(&collected_data, &config.for_target(target)).await?;
+            
+            // Output the formatted data
+            if let Some(ref output_path) = target.output_file {
+                std::fs::write(output_path, &formatted)
+                    .map_err(|e| PluginError::io_error(format!("Failed to write output file: {}", e)))?;
+                log::info!("Exported data to {}", output_path.display());
+                publish_outputs(config, std::slice::from_ref(output_path))?;
+            } else {
+                println!("{}", formatted);
+            }
+        }
+        
+        // Streamed files are complete once the round is
+        let streamed_files: Vec<PathBuf> = self.stream_started.read().await.iter().cloned().collect();
+        publish_outputs(config, &streamed_files)?;
+        
+        // Publish completion event using Publisher trait
+        self.publish_export_completion_event(scan_id, plugin_id).await?;
+        
+        // Clear coordinator for next round
+        coordinator.clear();
+        self.stream_started.write().await.clear();
+        Ok(())
+    }
+    
+    /// Export data from plugins that reported but left the round incomplete
+    /// 
+    /// Called once the scan is done, when every plugin that will report has
+    /// done so, so commands that run only some of the expected plugins still
+    /// produce their output.
+    async fn export_pending(&self) -> PluginResult<()> {
+        let mut coordinator = self.data_coordinator.write().await;
+        if coordinator.received_count() == 0 {
+            return Ok(());
+        }
+        let config = self.export_config.read().await.clone();
+        let collected: Vec<_> = config.targets().into_iter()
+            .filter(|target| target.format != ExportFormat::Ndjson)
+            .collect();
+        let scan_id = coordinator.scan_id().unwrap_or_default().to_string();
+        log::info!("ExportPlugin: Scan '{}' is done, exporting without data from {:?}", scan_id, coordinator.get_pending_plugins());
+        self.export_collected(&mut coordinator, &config, collected, &scan_id, "export").await
+    }
+    
+    /// Write the collected data to a SQLite database, one table per export
+    #[cfg(feature = "sqlite")]
+    fn write_sqlite(&self, data: &[Arc<PluginDataExport>], target: &ExportTarget) -> PluginResult<PathBuf> {
+        use self::formats::sqlite::SqliteWriter;
+        
+        let output_path = target.output_file.as_ref()
+            .ok_or_else(|| PluginError::configuration_error("SQLite export requires an output file (--outfile)"))?;
+        SqliteWriter::new().write(data, output_path)?;
+        log::info!("Exported data to {}", output_path.display());
+        Ok(output_path.clone())
+    }
+    
+    #[cfg(not(feature = "sqlite"))]
+    fn write_sqlite(&self, _data: &[Arc<PluginDataExport>], _target: &ExportTarget) -> PluginResult<PathBuf> {
+        Err(PluginError::configuration_error("SQLite export is not available: gstats was built without the `sqlite` feature"))
+    }
+    
+    /// Write the collected data as Parquet files, one per export or a single long-format file
+    #[cfg(feature = "parquet")]
+    fn write_parquet(&self, data: &[Arc<PluginDataExport>], target: &ExportTarget, layout: formats::Layout) -> PluginResult<Vec<PathBuf>> {
+        use self::formats::parquet::ParquetWriter;
+        
+        let output_path = target.output_file.as_ref()
+            .ok_or_else(|| PluginError::configuration_error("Parquet export requires an output file (--outfile)"))?;
+        let files = ParquetWriter::new().with_layout(layout).write(data, output_path)?;
+        for file in &files {
+            log::info!("Exported data to {}", file.display());
+        }
+        Ok(files)
+    }
+    
+    #[cfg(not(feature = "parquet"))]
+    fn write_parquet(&self, _data: &[Arc<PluginDataExport>], _target: &ExportTarget, _layout: formats::Layout) -> PluginResult<Vec<PathBuf>> {
+        Err(PluginError::configuration_error("Parquet export is not available: gstats was built without the `parquet` feature"))
+    }
+    
+    /// Write one plugin's data as NDJSON and flush it immediately
+    ///
+    /// The output file is truncated by the first export of a round and appended
+    /// to afterwards. A closed pipe (e.g. `| head`) ends output without an error.
+    async fn stream_ndjson(&self, export: &Arc<PluginDataExport>, config: &ExportConfig) -> PluginResult<()> {
+        use self::formats::ndjson::NdjsonFormatter;
+        use std::io::{BufWriter, ErrorKind};
+        
+        let formatter = NdjsonFormatter::new();
+        let export = match &config.row_filter {
+            Some(filter) => filter.apply_export(export)?,
+            None => Arc::clone(export),
+        };
+        let export = formats::columns::reshape_export(&export, &config.columns, &config.sort);
+        let result = if let Some(ref output_path) = config.output_file {
+            let mut started = self.stream_started.write().await;
+            let file = if started.contains(output_path) {
+                std::fs::OpenOptions::new().append(true).open(output_path)
+            } else {
+                std::fs::File::create(output_path)
+            }
+            .map_err(|e| PluginError::io_error(format!("Failed to open output file: {}", e)))?;
+            started.insert(output_path.clone());
+            
+            let result = formatter.write_export(&export, &mut BufWriter::new(file));
+            if result.is_ok() {
+                log::info!("Appended {} data to {}", export.plugin_id, output_path.display());
+            }
+            result
+        } else {
+            formatter.write_export(&export, &mut std::io::stdout().lock())
+        };
+        
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => {
+                log::debug!("ExportPlugin: Output closed while streaming NDJSON");
+                Ok(())
+            }
+            Err(e) => Err(PluginError::io_error(format!("Failed to write NDJSON output: {}", e))),
+        }
+    }
+    
     /// Handle other PluginEvent types
     async fn handle_other_plugin_event(&self, event: &PluginEvent) -> PluginResult<()> {
         match event {
@@ -258,6 +440,17 @@ impl ExportPlugin {
     
     /// Format data according to the configured format
     async fn format_data(&self, data: &[Arc<PluginDataExport>], config: &ExportConfig) -> PluginResult<String> {
+        // Tables shown to people end with their totals; data formats and long-format CSV keep the rows as they are
+        let totalled;
+        let data = match config.output_format {
+            ExportFormat::Csv if config.layout == formats::Layout::Long => data,
+            ExportFormat::Console | ExportFormat::Csv | ExportFormat::Html | ExportFormat::Markdown => {
+                totalled = config.totals.apply(data);
+                &totalled[..]
+            }
+            _ => data,
+        };
+        
         match config.output_format {
             ExportFormat::Console => {
                 // Use the ConsoleFormatter for console output with color support
@@ -266,14 +459,23 @@ impl ExportPlugin {
                 
                 // Check if we have a color manager available
                 if let Some(colour_manager) = self.colour_manager.read().await.as_ref() {
-                    let formatter = ConsoleFormatter::with_colors(Arc::clone(colour_manager));
+                    let formatter = ConsoleFormatter::with_colors(Arc::clone(colour_manager))
+                        .with_transpose(config.transpose)
+                        .with_describe_columns(config.describe_columns);
                     formatter.format_with_colors(data)
                 } else {
-                    let formatter = ConsoleFormatter::new();
+                    let formatter = ConsoleFormatter::new()
+                        .with_transpose(config.transpose)
+                        .with_describe_columns(config.describe_columns);
                     formatter.format_data(data)
                 }
             },
             ExportFormat::Json => self.format_json(data).await,
+            ExportFormat::Ndjson => {
+                use self::formats::ndjson::NdjsonFormatter;
+                use self::formats::FormatExporter;
+                NdjsonFormatter::new().format_data(data)
+            },
             ExportFormat::Csv => self.format_csv(data).await,
             ExportFormat::Xml => self.format_xml(data).await,
             ExportFormat::Yaml => self.format_yaml(data).await,
@@ -283,11 +485,27 @@ impl ExportPlugin {
                 // Use the template formatter from formats module
                 use self::formats::template::TemplateExporter;
                 use self::formats::FormatExporter;
-                let template_file = config.template_file.as_ref()
-                    .ok_or_else(|| PluginError::configuration_error("Template format selected but no template file configured".to_string()))?;
-                let formatter = TemplateExporter::new(template_file);
+                if config.template_file.is_none() && config.template_dir.is_none() {
+                    return Err(PluginError::configuration_error("Template format selected but no template file configured".to_string()));
+                }
+                let formatter = TemplateExporter::new(
+                    config.template_file.as_deref(),
+                    config.template_dir.as_deref(),
+                    config.template_vars.clone(),
+                );
                 formatter.format_data(data)
             },
+            ExportFormat::Annotations(style) => {
+                use self::formats::annotations::AnnotationFormatter;
+                use self::formats::FormatExporter;
+                AnnotationFormatter::new(style).format_data(data)
+            },
+            ExportFormat::Sqlite => Err(PluginError::configuration_error(
+                "SQLite output is written directly to a database file, not formatted as text".to_string()
+            )),
+            ExportFormat::Parquet => Err(PluginError::configuration_error(
+                "Parquet output is written directly to files, not formatted as text".to_string()
+            )),
         }
     }
     
@@ -298,12 +516,21 @@ impl ExportPlugin {
         use self::formats::console::ConsoleFormatter;
         use self::formats::FormatExporter;
         
+        let (transpose, describe_columns) = {
+            let config = self.export_config.read().await;
+            (config.transpose, config.describe_columns)
+        };
+        
         // Check if we have a color manager available
         if let Some(colour_manager) = self.colour_manager.read().await.as_ref() {
-            let formatter = ConsoleFormatter::with_colors(Arc::clone(colour_manager));
+            let formatter = ConsoleFormatter::with_colors(Arc::clone(colour_manager))
+                .with_transpose(transpose)
+                .with_describe_columns(describe_columns);
             formatter.format_with_colors(data)
         } else {
-            let formatter = ConsoleFormatter::new();
+            let formatter = ConsoleFormatter::new()
+                .with_transpose(transpose)
+                .with_describe_columns(describe_columns);
             formatter.format_data(data)
         }
     }
@@ -318,6 +545,9 @@ impl ExportPlugin {
                 "description": export.description,
                 "type": export.data_type,
             });
+            if !export.schema.metadata.is_empty() {
+                plugin_data["metadata"] = json!(export.schema.metadata);
+            }
             
             // Add data based on type
             match &export.data {
@@ -335,10 +565,22 @@ impl ExportPlugin {
                         json_rows.push(json_row);
                     }
                     plugin_data["data"] = json!(json_rows);
+                    plugin_data["schema"] = json!({
+                        "columns": export.schema.columns.iter().map(|col| {
+                            json!({
+                                "name": col.name,
+                                "type": format!("{:?}", col.data_type),
+                                "description": col.description
+                            })
+                        }).collect::<Vec<_>>()
+                    });
                 }
                 DataPayload::KeyValue(map) => {
                     plugin_data["data"] = json!(**map);
                 }
+                DataPayload::Tree(root) => {
+                    plugin_data["tree"] = formats::json::tree_to_json(root);
+                }
                 _ => {
                     plugin_data["data"] = json!(null);
                 }
@@ -361,7 +603,9 @@ impl ExportPlugin {
         let delimiter = config.csv_delimiter.chars().next().unwrap_or(',');
         let quote_char = config.csv_quote_char.chars().next().unwrap_or('"');
         
-        let formatter = CsvFormatter::with_config(delimiter, quote_char, config.csv_quoting_style);
+        let formatter = CsvFormatter::with_config(delimiter, quote_char, config.csv_quoting_style)
+            .with_layout(config.layout)
+            .with_raw_values(config.csv_raw);
         formatter.format_data(data)
     }
     
@@ -383,7 +627,7 @@ impl ExportPlugin {
                         for (i, value) in row.values.iter().enumerate() {
                             if let Some(col) = export.schema.columns.get(i) {
                                 output.push_str(&format!("        <{}>{}</{}>\n", 
-                                                        col.name, value.to_string(), col.name));
+                                                        col.name, value, col.name));
                             }
                         }
                         output.push_str("      </row>\n");
@@ -420,7 +664,7 @@ impl ExportPlugin {
                                 if i > 0 {
                                     output.push_str("\n      ");
                                 }
-                                output.push_str(&format!("{}: {}", col.name, value.to_string()));
+                                output.push_str(&format!("{}: {}", col.name, value));
                             }
                         }
                         output.push('\n');
@@ -434,23 +678,20 @@ impl ExportPlugin {
     }
     
     pub async fn format_html(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
-        let mut output = String::from(r#"<!DOCTYPE html>
-<html>
-<head>
-    <title>Export Report</title>
-    <style>
-        body { font-family: Arial, sans-serif; margin: 20px; }
-        h1 { color: #333; }
-        h2 { color: #666; border-bottom: 1px solid #ccc; }
-        table { border-collapse: collapse; width: 100%; margin: 20px 0; }
-        th, td { border: 1px solid #ddd; padding: 8px; text-align: left; }
-        th { background-color: #f2f2f2; }
-        tr:nth-child(even) { background-color: #f9f9f9; }
-    </style>
-</head>
-<body>
-    <h1>Export Report</h1>
-"#);
+        let accessible = self.colour_manager.read().await.as_ref().is_some_and(|manager| manager.accessible());
+        let (theme, with_charts) = {
+            let config = self.export_config.read().await;
+            (config.html_theme, config.html_charts)
+        };
+        let mut output = String::from("<!DOCTYPE html>\n<html>\n<head>\n    <title>Export Report</title>\n    <style>\n");
+        output.push_str(formats::html::INDICATOR_STYLES);
+        output.push_str(theme.styles());
+        output.push_str("    </style>\n</head>\n<body>\n    <h1>Export Report</h1>\n");
+        
+        let charts = if with_charts { formats::charts::charts(data) } else { Vec::new() };
+        if !charts.is_empty() {
+            output.push_str("    <section id=\"charts\"></section>\n");
+        }
         
         for export in data {
             output.push_str(&format!("    <h2>{}</h2>\n", export.title));
@@ -462,14 +703,14 @@ impl ExportPlugin {
                 DataPayload::Rows(rows) if !rows.is_empty() => {
                     output.push_str("    <table>\n        <thead>\n            <tr>\n");
                     for col in &export.schema.columns {
-                        output.push_str(&format!("                <th>{}</th>\n", col.name));
+                        output.push_str(&format!("                {}\n", formats::html::header_cell(col)));
                     }
                     output.push_str("            </tr>\n        </thead>\n        <tbody>\n");
                     
                     for row in rows.iter() {
                         output.push_str("            <tr>\n");
-                        for value in &row.values {
-                            output.push_str(&format!("                <td>{}</td>\n", value.to_string()));
+                        for (i, value) in row.values.iter().enumerate() {
+                            output.push_str(&format!("                {}\n", formats::html::value_cell(export.schema.columns.get(i), value, accessible)));
                         }
                         output.push_str("            </tr>\n");
                     }
@@ -479,11 +720,17 @@ impl ExportPlugin {
             }
         }
         
+        if !charts.is_empty() {
+            output.push_str(&format!("    <script>\nconst GSTATS_CHARTS = {};\n", formats::charts::charts_json(&charts)));
+            output.push_str(formats::charts::CHART_SCRIPT);
+            output.push_str("    </script>\n");
+        }
         output.push_str("</body>\n</html>\n");
         Ok(output)
     }
     
     pub async fn format_markdown(&self, data: &[Arc<PluginDataExport>]) -> PluginResult<String> {
+        let transpose = self.export_config.read().await.transpose;
         let mut output = String::from("# Export Report\n\n");
         
         for export in data {
@@ -492,6 +739,11 @@ impl ExportPlugin {
                 output.push_str(&format!("{}\n\n", desc));
             }
             
+            if let Some(fields) = transpose.fields(export) {
+                output.push_str(&formats::markdown::format_field_list(&fields));
+                continue;
+            }
+            
             match &export.data {
                 DataPayload::Rows(rows) if !rows.is_empty() => {
                     // Table header
@@ -516,8 +768,8 @@ impl ExportPlugin {
                     // Rows
                     for row in rows.iter() {
                         output.push('|');
-                        for value in &row.values {
-                            output.push_str(&format!(" {} |", value.to_string()));
+                        for (i, value) in row.values.iter().enumerate() {
+                            output.push_str(&format!(" {} |", formats::values::format_value(export.schema.columns.get(i), value)));
                         }
                         output.push('\n');
                     }
@@ -537,17 +789,28 @@ impl Plugin for ExportPlugin {
         &self.info
     }
     
-    async fn initialize(&mut self, context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
         if self.initialized {
             return Ok(());
         }
         
+        if let Some(scanned_ref) = &context.query_params.scanned_ref {
+            self.scan_metadata = scanned_ref.to_metadata();
+        }
+        if context.query_params.all_branches {
+            self.scan_metadata.insert("all_branches".to_string(), "true".to_string());
+        }
+        if let Some(shallow) = &context.query_params.shallow {
+            self.scan_metadata.extend(shallow.to_metadata());
+        }
+        
         // Initialize template engine if template file is configured
         {
             let config = self.export_config.read().await;
-            if let Some(ref template_file) = config.template_file {
+            if config.template_file.is_some() || config.template_dir.is_some() {
                 let mut engine = self.template_engine.write().await;
-                engine.load_template(template_file)?;
+                engine.load(config.template_file.as_deref(), config.template_dir.as_deref())?;
             }
         }
         
@@ -592,6 +855,14 @@ impl Plugin for ExportPlugin {
         };
         
         match request {
+            crate::plugin::context::PluginRequest::Export => {
+                self.export_pending().await?;
+                Ok(PluginResponse::success(
+                    "export_info".to_string(),
+                    serde_json::json!({"message": "Exported the data collected during the scan"}),
+                    metadata
+                ))
+            }
             crate::plugin::context::PluginRequest::GetStatistics => {
                 Ok(PluginResponse::success(
                     "export_statistics".to_string(),
@@ -691,9 +962,17 @@ impl Subscriber<PluginEvent> for ExportPlugin {
     async fn handle_event(&self, event: PluginEvent) -> NotificationResult<()> {
         match event {
             PluginEvent::DataReady { plugin_id, scan_id, export } => {
+                let _stage = crate::profiling::plugin_stage("export.data_ready", &plugin_id);
+                #[cfg(feature = "otel")]
+                let mut span = crate::telemetry::plugin_span("export.data_ready", "export", vec![
+                    crate::telemetry::KeyValue::new("source_plugin_id", plugin_id.clone()),
+                    crate::telemetry::KeyValue::new("export", export.title.clone()),
+                ]);
                 // Use the new comprehensive DataReady handler
-                self.handle_data_ready_event(plugin_id, scan_id, export).await
-                    .map_err(|e| NotificationError::delivery_failed("export-plugin", &e.to_string()))?;
+                let result = self.handle_data_ready_event(plugin_id, scan_id, export).await;
+                #[cfg(feature = "otel")]
+                span.record_result(&result);
+                result.map_err(|e| NotificationError::delivery_failed("export-plugin", &e.to_string()))?;
                 return Ok(());
             }
             _ => {
@@ -735,39 +1014,121 @@ impl PluginClapParser for ExportPlugin {
         command
             .override_usage("export [OPTIONS]")
             .help_template("Usage: {usage}\n\nExports analysis results\n\nOptions:\n{options}\n{after-help}")
-            .after_help("File extensions (.json, .csv, .xml, .yaml, .html, .md, .htm, .yml) auto-detect format when using --outfile.")
+            .after_help("File extensions (.json, .ndjson, .jsonl, .csv, .xml, .yaml, .html, .md, .htm, .yml, .sqlite, .db, .parquet) auto-detect format when using --outfile.\n\
+                Repeat --outfile (or use --formats) to write several formats from the same run.")
             .arg(Arg::new("outfile")
                 .short('o')
                 .long("outfile")
                 .value_name("FILE")
-                .help("Output file path (if not specified, output to console)")
+                .action(clap::ArgAction::Append)
+                .help("Output file path (if not specified, output to console); may be repeated")
                 .value_hint(clap::ValueHint::FilePath))
             .arg(Arg::new("template")
                 .short('t')
                 .long("template")
                 .value_name("FILE")
-                .help("Template file for custom formatting (Tera/Jinja2-compatible)")
+                .help("Template file for custom formatting (Tera/Jinja2-compatible), or the name of a template in --template-dir")
                 .value_hint(clap::ValueHint::FilePath))
+            .arg(Arg::new("template-dir")
+                .long("template-dir")
+                .value_name("DIR")
+                .help("Directory or glob of templates to include or extend; renders its index template unless --template names one")
+                .value_hint(clap::ValueHint::DirPath))
+            .arg(Arg::new("template-var")
+                .long("template-var")
+                .value_name("KEY=VALUE")
+                .action(clap::ArgAction::Append)
+                .help("Variable available to templates; may be repeated"))
             .arg(Arg::new("format")
                 .short('f')
                 .long("format")
                 .value_name("FORMAT")
-                .help("Output format: json, csv, xml, yaml, html, markdown")
-                .value_parser(["json", "csv", "xml", "yaml", "html", "markdown"])
+                .help("Output format: json, ndjson, csv, xml, yaml, html, markdown, sqlite, parquet, github, github-json, gitlab, console [default: the plugins' preferred format]")
+                .value_parser(FORMAT_NAMES)
+                .hide_possible_values(true))
+            .arg(Arg::new("formats")
+                .long("formats")
+                .value_name("LIST")
+                .value_delimiter(',')
+                .conflicts_with_all(["format", "template", "template-dir"])
+                .help("Comma-separated output formats, each written to --outfile with the format's extension")
+                .value_parser(FORMAT_NAMES)
+                .hide_possible_values(true))
+            .arg(Arg::new("transpose")
+                .long("transpose")
+                .value_name("WHEN")
+                .num_args(0..=1)
+                .default_missing_value("always")
+                .help("Show single-row tables as one field per line in console and markdown output: auto, always, never [default: auto]")
+                .value_parser(["auto", "always", "never"])
                 .hide_possible_values(true))
+            .arg(Arg::new("where")
+                .long("where")
+                .value_name("EXPR")
+                .help("Export only table rows meeting an expression, e.g. \"Commits > 10 && Author != 'bot'\""))
+            .arg(Arg::new("columns")
+                .long("columns")
+                .value_name("LIST")
+                .value_delimiter(',')
+                .help("Comma-separated columns to show in each table, in this order"))
+            .arg(Arg::new("sort")
+                .long("sort")
+                .value_name("LIST")
+                .value_delimiter(',')
+                .help("Comma-separated columns to sort table rows by, each optionally :asc or :desc (e.g. Commits:desc,Author)"))
+            .arg(Arg::new("totals")
+                .long("totals")
+                .value_name("MODE")
+                .num_args(0..=1)
+                .default_missing_value("sum")
+                .help("End tables with a totals row in console, CSV, HTML and markdown output: auto (tables whose plugin asks), sum, avg, never [default: auto]")
+                .value_parser(["auto", "sum", "avg", "never"])
+                .hide_possible_values(true))
+            .arg(Arg::new("layout")
+                .long("layout")
+                .value_name("LAYOUT")
+                .help("CSV layout: wide (one table per export) or long (plugin, table, row_key, column, value rows) [default: wide]")
+                .value_parser(["wide", "long", "tidy"])
+                .hide_possible_values(true))
+            .arg(Arg::new("csv-raw")
+                .long("csv-raw")
+                .action(clap::ArgAction::SetTrue)
+                .help("Write CSV values unformatted (42.5, 1536) rather than as their columns suggest (42.5%, 1.5 KB)"))
+            .arg(Arg::new("html-theme")
+                .long("html-theme")
+                .value_name("THEME")
+                .help("HTML report colour scheme: light or dark [default: light]")
+                .value_parser(["light", "dark"])
+                .hide_possible_values(true))
+            .arg(Arg::new("html-charts")
+                .long("html-charts")
+                .action(clap::ArgAction::SetTrue)
+                .help("Add commit activity, top author and hotspot charts to HTML reports"))
+            .arg(Arg::new("describe-columns")
+                .long("describe-columns")
+                .action(clap::ArgAction::SetTrue)
+                .help("List column descriptions below each table in console output"))
+            .arg(Arg::new("content-hash")
+                .long("content-hash")
+                .action(clap::ArgAction::SetTrue)
+                .help("Name output files after a hash of their content (report-3fa2c1.json) and keep a copy as report-latest.json"))
     }
     
     async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
         let mut config = self.export_config.write().await;
+        let outfiles: Vec<&String> = matches.get_many::<String>("outfile")
+            .map(|files| files.collect())
+            .unwrap_or_default();
         
         // Handle output file
-        if let Some(outfile) = matches.get_one::<String>("outfile") {
+        if let Some(outfile) = outfiles.first() {
             config.output_file = Some(PathBuf::from(outfile));
             
             // Auto-detect format from extension
             match self.format_detector.detect_format_from_path(outfile) {
                 FormatDetectionResult::Detected(format) => {
                     config.output_format = format;
+                    config.format_chosen = true;
                 }
                 _ => {}
             }
@@ -775,38 +1136,234 @@ impl PluginClapParser for ExportPlugin {
         
         // Handle format
         if let Some(format) = matches.get_one::<String>("format") {
-            config.output_format = match format.to_lowercase().as_str() {
-                "json" => ExportFormat::Json,
-                "csv" => ExportFormat::Csv,
-                "xml" => ExportFormat::Xml,
-                "yaml" | "yml" => ExportFormat::Yaml,
-                "html" | "htm" => ExportFormat::Html,
-                "markdown" | "md" => ExportFormat::Markdown,
+            config.output_format = parse_format_name("--format", format)?;
+            config.format_chosen = true;
+        }
+        
+        // Further output files take their format from the extension
+        config.additional_targets.clear();
+        for outfile in outfiles.iter().skip(1) {
+            let format = match self.format_detector.detect_format_from_path(outfile) {
+                FormatDetectionResult::Detected(format) => format,
                 _ => return Err(PluginError::invalid_argument(
-                    "--format",
-                    &format!("Unknown format: {}", format)
+                    "--outfile",
+                    &format!("Cannot determine the format of '{}' from its extension", outfile)
                 )),
             };
+            config.additional_targets.push(ExportTarget { format, output_file: Some(PathBuf::from(outfile)) });
+        }
+        
+        // Handle a format list: one target per format, sharing the output file name
+        if let Some(formats) = matches.get_many::<String>("formats") {
+            if outfiles.len() > 1 {
+                return Err(PluginError::invalid_argument(
+                    "--formats",
+                    "cannot be combined with more than one --outfile"
+                ));
+            }
+            let mut targets = Vec::new();
+            for name in formats {
+                let format = parse_format_name("--formats", name)?;
+                let output_file = config.output_file.as_ref().map(|file| {
+                    match format {
+                        ExportFormat::Annotations(style) => file.with_extension(style.file_suffix()),
+                        _ => file.with_extension(self.format_detector.get_extensions_for_format(&format).first().copied().unwrap_or("txt")),
+                    }
+                });
+                targets.push(ExportTarget { format, output_file });
+            }
+            let mut targets = targets.into_iter();
+            if let Some(primary) = targets.next() {
+                config.output_format = primary.format;
+                config.output_file = primary.output_file;
+                config.format_chosen = true;
+            }
+            config.additional_targets = targets.collect();
+        }
+        
+        // Handle transposition of single-row tables
+        if let Some(transpose) = matches.get_one::<String>("transpose") {
+            config.transpose = formats::Transpose::parse(transpose)
+                .ok_or_else(|| PluginError::invalid_argument("--transpose", &format!("Unknown mode: {}", transpose)))?;
+        }
+        
+        config.row_filter = matches.get_one::<String>("where")
+            .map(|expression| formats::filter::RowFilter::parse(expression))
+            .transpose()
+            .map_err(|e| PluginError::invalid_argument("--where", &e))?;
+        config.columns = matches.get_many::<String>("columns")
+            .map(|columns| columns.map(|column| column.trim().to_string()).filter(|column| !column.is_empty()).collect())
+            .unwrap_or_default();
+        config.sort = matches.get_many::<String>("sort").into_iter().flatten()
+            .map(|key| formats::columns::SortKey::parse(key)
+                .ok_or_else(|| PluginError::invalid_argument("--sort", &format!("Expected COLUMN, COLUMN:asc or COLUMN:desc, got '{}'", key))))
+            .collect::<PluginResult<_>>()?;
+        
+        if let Some(totals) = matches.get_one::<String>("totals") {
+            config.totals = formats::totals::Totals::parse(totals)
+                .ok_or_else(|| PluginError::invalid_argument("--totals", &format!("Unknown mode: {}", totals)))?;
+        }
+        
+        if let Some(layout) = matches.get_one::<String>("layout") {
+            config.layout = formats::Layout::parse(layout)
+                .ok_or_else(|| PluginError::invalid_argument("--layout", &format!("Unknown layout: {}", layout)))?;
+        }
+        
+        if let Some(theme) = matches.get_one::<String>("html-theme") {
+            config.html_theme = formats::html::Theme::parse(theme)
+                .ok_or_else(|| PluginError::invalid_argument("--html-theme", &format!("Unknown theme: {}", theme)))?;
         }
+        config.html_charts = matches.get_flag("html-charts");
+        config.csv_raw = matches.get_flag("csv-raw");
+        
+        config.describe_columns = matches.get_flag("describe-columns");
+        config.content_hash = matches.get_flag("content-hash");
         
         // Handle template
-        if let Some(template) = matches.get_one::<String>("template") {
-            let template_path = PathBuf::from(template);
-            config.template_file = Some(template_path.clone());
+        config.template_file = matches.get_one::<String>("template").map(PathBuf::from);
+        config.template_dir = matches.get_one::<String>("template-dir").map(PathBuf::from);
+        config.template_vars.clear();
+        for assignment in matches.get_many::<String>("template-var").into_iter().flatten() {
+            let (key, value) = template_engine::parse_template_var(assignment)?;
+            config.template_vars.insert(key, value);
+        }
+        if config.template_file.is_some() || config.template_dir.is_some() {
             config.output_format = ExportFormat::Template; // Set format to Template when --template is used
+            config.format_chosen = true;
             
-            // Load template
+            // Load templates now so errors are reported before scanning
             let mut engine = self.template_engine.write().await;
-            engine.load_template(&template_path)?;
+            engine.load(config.template_file.as_deref(), config.template_dir.as_deref())?;
         }
         
-        log::debug!("Export plugin configured with clap: format={:?}, outfile={:?}", 
-                   config.output_format, config.output_file);
+        // Databases and Parquet files cannot be written to the console
+        if let Some(target) = config.targets().iter().find(|target| target.format.is_binary() && target.output_file.is_none()) {
+            let name = format!("{:?}", target.format).to_lowercase();
+            return Err(PluginError::invalid_argument("--format", &format!("{} output requires --outfile", name)));
+        }
+        
+        log::debug!("Export plugin configured with clap: format={:?}, outfile={:?}, additional targets={:?}", 
+                   config.output_format, config.output_file, config.additional_targets);
         
         Ok(())
     }
 }
 
+/// Merge the findings tables of all plugins into one, listed after the other tables
+fn unify_findings(data: Vec<Arc<PluginDataExport>>) -> Vec<Arc<PluginDataExport>> {
+    let (findings, mut tables): (Vec<_>, Vec<_>) = data.into_iter().partition(|export| export.is_findings());
+    if findings.len() < 2 {
+        tables.extend(findings);
+        return tables;
+    }
+    
+    let mut rows: Vec<Row> = findings.iter()
+        .filter_map(|export| match &export.data {
+            DataPayload::Rows(rows) => Some(rows.iter().cloned()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    // Most severe first; the sort is stable, so each plugin's own order is kept within a level
+    rows.sort_by_key(|row| std::cmp::Reverse(row.values.get(FINDINGS_SEVERITY_COLUMN).and_then(|value| FindingSeverity::parse(&value.to_string()))));
+    
+    let mut sources: Vec<&str> = findings.iter().map(|export| export.plugin_id.as_str()).collect();
+    sources.dedup();
+    let mut unified = (*findings[0]).clone();
+    for export in &findings[1..] {
+        for (key, value) in &export.schema.metadata {
+            unified.schema.metadata.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    unified.plugin_id = "findings".to_string();
+    unified.description = Some(format!("{} findings from {}", rows.len(), sources.join(", ")));
+    unified.data = DataPayload::Rows(Arc::new(rows));
+    tables.push(Arc::new(unified));
+    tables
+}
+
+/// Name written output files after their content when `--content-hash` is set
+fn publish_outputs(config: &ExportConfig, files: &[PathBuf]) -> PluginResult<()> {
+    if !config.content_hash {
+        return Ok(());
+    }
+    for file in files {
+        let artifact = artifact::publish(file)
+            .map_err(|e| PluginError::io_error(format!("Failed to name {} after its content: {}", file.display(), e)))?;
+        if artifact.unchanged {
+            log::info!("Output unchanged since a previous export: {}", artifact.path.display());
+        } else {
+            log::info!("Output named after its content: {}", artifact.path.display());
+        }
+    }
+    Ok(())
+}
+
+/// The format the collected exports prefer, for runs where none was chosen
+///
+/// Of the formats every export lists, this is the one ranked highest overall,
+/// so the choice does not depend on the order the exports arrived in. Exports
+/// without preferences accept any format.
+fn preferred_format(data: &[Arc<PluginDataExport>]) -> Option<ExportFormat> {
+    use crate::plugin::data_export::ExportFormat as Preferred;
+    // Ties go to the first of these
+    const CANDIDATES: [(Preferred, ExportFormat); 7] = [
+        (Preferred::Console, ExportFormat::Console),
+        (Preferred::Markdown, ExportFormat::Markdown),
+        (Preferred::Json, ExportFormat::Json),
+        (Preferred::Csv, ExportFormat::Csv),
+        (Preferred::Yaml, ExportFormat::Yaml),
+        (Preferred::Html, ExportFormat::Html),
+        (Preferred::Xml, ExportFormat::Xml),
+    ];
+    
+    let preferences: Vec<&Vec<Preferred>> = data.iter()
+        .map(|export| &export.export_hints.preferred_formats)
+        .filter(|formats| !formats.is_empty())
+        .collect();
+    if preferences.is_empty() {
+        return None;
+    }
+    CANDIDATES.iter().enumerate()
+        .filter_map(|(order, (preferred, format))| {
+            let rank: usize = preferences.iter()
+                .map(|formats| formats.iter().position(|format| format == preferred))
+                .sum::<Option<usize>>()?;
+            Some(((rank, order), *format))
+        })
+        .min_by_key(|(key, _)| *key)
+        .map(|(_, format)| format)
+}
+
+/// Format names accepted by `--format` and `--formats`, including short aliases
+const FORMAT_NAMES: [&str; 21] = [
+    "json", "ndjson", "jsonl", "csv", "xml", "yaml", "yml", "html", "htm", "markdown", "md",
+    "sqlite", "sqlite3", "db", "parquet", "pq", "github", "github-json", "gitlab", "console", "table",
+];
+
+/// Parse an export format name given on the command line
+fn parse_format_name(option: &str, format: &str) -> PluginResult<ExportFormat> {
+    match format.to_lowercase().as_str() {
+        "console" | "table" => Ok(ExportFormat::Console),
+        "json" => Ok(ExportFormat::Json),
+        "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
+        "csv" => Ok(ExportFormat::Csv),
+        "xml" => Ok(ExportFormat::Xml),
+        "yaml" | "yml" => Ok(ExportFormat::Yaml),
+        "html" | "htm" => Ok(ExportFormat::Html),
+        "markdown" | "md" => Ok(ExportFormat::Markdown),
+        "sqlite" | "sqlite3" | "db" => Ok(ExportFormat::Sqlite),
+        "parquet" | "pq" => Ok(ExportFormat::Parquet),
+        "github" => Ok(ExportFormat::Annotations(AnnotationStyle::GithubCommands)),
+        "github-json" => Ok(ExportFormat::Annotations(AnnotationStyle::GithubJson)),
+        "gitlab" => Ok(ExportFormat::Annotations(AnnotationStyle::GitlabCodeQuality)),
+        _ => Err(PluginError::invalid_argument(
+            option,
+            &format!("Unknown format: {}", format)
+        )),
+    }
+}
+
 impl PluginDataRequirements for ExportPlugin {
     fn requires_current_file_content(&self) -> bool {
         false // Export plugin doesn't need file content
@@ -870,6 +1427,7 @@ mod tests {
             data: DataPayload::Rows(Arc::new(rows)),
             export_hints: crate::plugin::data_export::ExportHints::default(),
             timestamp: std::time::SystemTime::now(),
+            repository: None,
         })
     }
 
@@ -928,8 +1486,7 @@ mod tests {
         assert!(coordinator.has_data_from("debug"));
         
         // Verify scan ID was set
-        let scan_id = plugin.scan_id.read().await;
-        assert_eq!(scan_id.as_ref().unwrap(), "test-scan");
+        assert_eq!(coordinator.scan_id(), Some("test-scan"));
     }
 
     #[tokio::test]
@@ -967,6 +1524,368 @@ mod tests {
         assert!(md_output.contains("Test Data"));
     }
 
+    #[tokio::test]
+    async fn test_ndjson_streams_each_plugin_as_it_arrives() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("report.ndjson");
+        std::fs::write(&output_path, "stale\n").unwrap();
+
+        let plugin = ExportPlugin::new();
+        {
+            let mut config = plugin.export_config.write().await;
+            config.output_format = ExportFormat::Ndjson;
+            config.output_file = Some(output_path.clone());
+        }
+
+        for plugin_id in ["commits", "metrics"] {
+            let event = PluginEvent::DataReady {
+                plugin_id: plugin_id.to_string(),
+                scan_id: "test-scan".to_string(),
+                export: create_test_export_data(),
+            };
+            plugin.handle_event(event).await.unwrap();
+
+            // Each plugin's rows are on disk before the export is complete
+            let written = std::fs::read_to_string(&output_path).unwrap();
+            assert!(!written.contains("stale"));
+            assert!(written.lines().all(|line| serde_json::from_str::<serde_json::Value>(line).is_ok()));
+        }
+
+        let written = std::fs::read_to_string(&output_path).unwrap();
+        let lines: Vec<serde_json::Value> = written.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], json!({"plugin": "test", "metric": "total_commits", "value": 100}));
+        assert!(plugin.data_coordinator.read().await.get_all_data().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_outfiles_write_each_format() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let html_path = temp_dir.path().join("report.html");
+        let json_path = temp_dir.path().join("data.json");
+        let ndjson_path = temp_dir.path().join("rows.ndjson");
+
+        let mut plugin = ExportPlugin::new();
+        let args: Vec<String> = [&html_path, &json_path, &ndjson_path].iter()
+            .flat_map(|path| ["--outfile".to_string(), path.display().to_string()])
+            .collect();
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+
+        for plugin_id in ["commits", "metrics"] {
+            let event = PluginEvent::DataReady {
+                plugin_id: plugin_id.to_string(),
+                scan_id: "test-scan".to_string(),
+                export: create_test_export_data(),
+            };
+            plugin.handle_event(event).await.unwrap();
+        }
+
+        assert!(std::fs::read_to_string(&html_path).unwrap().contains("<html>"));
+        assert!(serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(&json_path).unwrap()).is_ok());
+        assert_eq!(std::fs::read_to_string(&ndjson_path).unwrap().lines().count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_export_request_writes_incomplete_round() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let json_path = temp_dir.path().join("data.json");
+
+        let mut plugin = ExportPlugin::new();
+        plugin.parse_plugin_arguments(&["--outfile".to_string(), json_path.display().to_string()]).await.unwrap();
+
+        // Only commits reports, so the round waits for metrics until the scan is done
+        let event = PluginEvent::DataReady {
+            plugin_id: "commits".to_string(),
+            scan_id: "test-scan".to_string(),
+            export: create_test_export_data(),
+        };
+        plugin.handle_event(event).await.unwrap();
+        assert!(!json_path.exists());
+
+        plugin.execute(PluginRequest::Export).await.unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(&json_path).unwrap()).is_ok());
+        assert_eq!(plugin.data_coordinator.read().await.received_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_columns_and_sort_reshape_every_output() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("report.csv");
+        let ndjson_path = temp_dir.path().join("rows.ndjson");
+
+        let mut plugin = ExportPlugin::new();
+        let mut args: Vec<String> = [&csv_path, &ndjson_path].iter()
+            .flat_map(|path| ["--outfile".to_string(), path.display().to_string()])
+            .collect();
+        args.extend(["--columns", "Value", "--sort", "value:asc"].map(String::from));
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+
+        for plugin_id in ["commits", "metrics"] {
+            let event = PluginEvent::DataReady {
+                plugin_id: plugin_id.to_string(),
+                scan_id: "test-scan".to_string(),
+                export: create_test_export_data(),
+            };
+            plugin.handle_event(event).await.unwrap();
+        }
+
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        assert!(!csv.contains("total_commits"));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines.iter().position(|line| *line == "5") < lines.iter().position(|line| *line == "100"));
+        assert!(!std::fs::read_to_string(&ndjson_path).unwrap().contains("total_commits"));
+
+        let mut plugin = ExportPlugin::new();
+        plugin.parse_plugin_arguments(&["--sort", "Value:up"].map(String::from)).await.unwrap_err();
+        plugin.parse_plugin_arguments(&["--columns", "Churn"].map(String::from)).await.unwrap();
+        let event = PluginEvent::DataReady { plugin_id: "commits".to_string(), scan_id: "test-scan".to_string(), export: create_test_export_data() };
+        plugin.handle_event(event).await.unwrap();
+        let event = PluginEvent::DataReady { plugin_id: "metrics".to_string(), scan_id: "test-scan".to_string(), export: create_test_export_data() };
+        assert!(plugin.handle_event(event).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_where_filters_rows_before_formatting() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let csv_path = temp_dir.path().join("report.csv");
+
+        let mut plugin = ExportPlugin::new();
+        assert!(plugin.parse_plugin_arguments(&["--where", "value >"].map(String::from)).await.is_err());
+        let args = ["--outfile".to_string(), csv_path.display().to_string(), "--where".to_string(), "value > 50 && metric ~ 'commits'".to_string()];
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+
+        for plugin_id in ["commits", "metrics"] {
+            let event = PluginEvent::DataReady {
+                plugin_id: plugin_id.to_string(),
+                scan_id: "test-scan".to_string(),
+                export: create_test_export_data(),
+            };
+            plugin.handle_event(event).await.unwrap();
+        }
+
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        assert_eq!(csv.matches("total_commits").count(), 2);
+        assert!(!csv.contains("total_authors"));
+    }
+
+    #[tokio::test]
+    async fn test_format_list_shares_output_file_name() {
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile", "out/report", "--formats", "json,html"].map(String::from);
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+
+        let targets = plugin.export_config.read().await.targets();
+        assert_eq!(targets, vec![
+            ExportTarget { format: ExportFormat::Json, output_file: Some(PathBuf::from("out/report.json")) },
+            ExportTarget { format: ExportFormat::Html, output_file: Some(PathBuf::from("out/report.html")) },
+        ]);
+
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile", "a.json", "--outfile", "b.unknown"].map(String::from);
+        assert!(plugin.parse_plugin_arguments(&args).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_binary_formats_require_output_file() {
+        for format in ["sqlite", "parquet"] {
+            let mut plugin = ExportPlugin::new();
+            let args = ["--format", format].map(String::from);
+            assert!(plugin.parse_plugin_arguments(&args).await.is_err());
+        }
+
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile", "out/report", "--formats", "json,sqlite"].map(String::from);
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+        let targets = plugin.export_config.read().await.targets();
+        assert_eq!(targets[1], ExportTarget { format: ExportFormat::Sqlite, output_file: Some(PathBuf::from("out/report.sqlite")) });
+    }
+
+    #[tokio::test]
+    async fn test_totals_rows_only_in_tables_for_people() {
+        let plugin = ExportPlugin::new();
+        let mut export = PluginDataExport::clone(&create_test_export_data());
+        export.export_hints.include_totals = true;
+        let data = vec![Arc::new(export)];
+
+        let mut config = ExportConfig::default();
+        for format in [ExportFormat::Markdown, ExportFormat::Csv, ExportFormat::Html, ExportFormat::Console] {
+            config.output_format = format;
+            let output = plugin.format_data(&data, &config).await.unwrap();
+            assert!(output.contains("Total") && output.contains("105"), "{:?} output has no totals row", format);
+        }
+
+        config.output_format = ExportFormat::Json;
+        assert!(!plugin.format_data(&data, &config).await.unwrap().contains("Total"));
+
+        config.output_format = ExportFormat::Markdown;
+        config.totals = formats::totals::Totals::Never;
+        assert!(!plugin.format_data(&data, &config).await.unwrap().contains("Total"));
+    }
+
+    #[tokio::test]
+    async fn test_format_hints_shape_values_unless_csv_is_raw() {
+        let mut export = PluginDataExport::clone(&create_test_export_data());
+        export.schema.columns[1] = ColumnDef::new("value", ColumnType::Integer).with_format_hint("thousands");
+        export.data = DataPayload::Rows(Arc::new(vec![
+            Row::new(vec![Value::String("lines".to_string()), Value::Integer(12345)]),
+            Row::new(vec![Value::String("files".to_string()), Value::Integer(678)]),
+        ]));
+        let data = vec![Arc::new(export)];
+
+        let mut plugin = ExportPlugin::new();
+        let mut config = ExportConfig::default();
+        for format in [ExportFormat::Markdown, ExportFormat::Html, ExportFormat::Csv, ExportFormat::Console] {
+            config.output_format = format;
+            assert!(plugin.format_data(&data, &config).await.unwrap().contains("12,345"), "{:?} output is not formatted", format);
+        }
+        config.output_format = ExportFormat::Json;
+        assert!(!plugin.format_data(&data, &config).await.unwrap().contains("12,345"));
+
+        plugin.parse_plugin_arguments(&["--csv-raw".to_string()]).await.unwrap();
+        let csv = plugin.format_csv(&data).await.unwrap();
+        assert!(csv.contains("lines,12345") && !csv.contains("12,345"));
+    }
+
+    #[tokio::test]
+    async fn test_format_aliases_select_stdout_format() {
+        for (name, format) in [("md", ExportFormat::Markdown), ("yml", ExportFormat::Yaml), ("htm", ExportFormat::Html), ("markdown", ExportFormat::Markdown)] {
+            let mut plugin = ExportPlugin::new();
+            plugin.parse_plugin_arguments(&["--format".to_string(), name.to_string()]).await.unwrap();
+            let config = plugin.export_config.read().await;
+            assert_eq!(config.targets(), vec![ExportTarget { format, output_file: None }]);
+            assert!(config.format_chosen);
+        }
+
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile", "out/report", "--formats", "md,yml"].map(String::from);
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+        assert_eq!(plugin.export_config.read().await.targets(), vec![
+            ExportTarget { format: ExportFormat::Markdown, output_file: Some(PathBuf::from("out/report.md")) },
+            ExportTarget { format: ExportFormat::Yaml, output_file: Some(PathBuf::from("out/report.yaml")) },
+        ]);
+    }
+
+    #[test]
+    fn test_preferred_format_is_shared_by_all_exports() {
+        use crate::plugin::data_export::ExportFormat as Preferred;
+        let with_preferences = |formats: Vec<Preferred>| {
+            let mut export = PluginDataExport::clone(&create_test_export_data());
+            export.export_hints.preferred_formats = formats;
+            Arc::new(export)
+        };
+
+        let data = [with_preferences(vec![Preferred::Markdown, Preferred::Json]), with_preferences(Vec::new())];
+        assert_eq!(preferred_format(&data), Some(ExportFormat::Markdown));
+
+        let data = [with_preferences(vec![Preferred::Markdown, Preferred::Json]), with_preferences(vec![Preferred::Csv, Preferred::Json])];
+        assert_eq!(preferred_format(&data), Some(ExportFormat::Json));
+
+        // Equally ranked formats are chosen the same way whatever order the exports arrive in
+        let json_first = with_preferences(vec![Preferred::Json, Preferred::Console]);
+        let console_first = with_preferences(vec![Preferred::Console, Preferred::Json]);
+        assert_eq!(preferred_format(&[json_first.clone(), console_first.clone()]), Some(ExportFormat::Console));
+        assert_eq!(preferred_format(&[console_first, json_first]), Some(ExportFormat::Console));
+
+        let data = [with_preferences(vec![Preferred::Markdown]), with_preferences(vec![Preferred::Csv])];
+        assert_eq!(preferred_format(&data), None);
+        assert_eq!(preferred_format(&[with_preferences(vec![Preferred::Template])]), None);
+        assert_eq!(preferred_format(&[create_test_export_data()]), Some(ExportFormat::Console));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sqlite_writes_one_table_per_export() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("report.db");
+        std::fs::write(&db_path, "stale").unwrap();
+
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile".to_string(), db_path.display().to_string()];
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+        assert_eq!(plugin.export_config.read().await.output_format, ExportFormat::Sqlite);
+
+        let mut tree = PluginDataExport::clone(&create_test_export_data());
+        tree.title = "Layout".to_string();
+        tree.data = DataPayload::Tree(Arc::new(crate::plugin::data_export::TreeNode {
+            label: "src".to_string(),
+            value: Some(Value::Integer(3)),
+            children: Vec::new(),
+            metadata: None,
+        }));
+        for (plugin_id, export) in [("commits", Arc::new(tree)), ("commits", create_test_export_data()), ("metrics", create_test_export_data())] {
+            let event = PluginEvent::DataReady { plugin_id: plugin_id.to_string(), scan_id: "test-scan".to_string(), export };
+            plugin.handle_event(event).await.unwrap();
+        }
+
+        let connection = rusqlite::Connection::open(&db_path).unwrap();
+        let tables: Vec<String> = connection.prepare("SELECT table_name FROM gstats_exports ORDER BY table_name").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .map(Result::unwrap)
+            .collect();
+        // Exports of the same plugin and title get distinct tables
+        assert_eq!(tables, vec!["test_data", "test_data_2", "test_layout"]);
+
+        let total: i64 = connection.query_row("SELECT value FROM test_data WHERE metric = 'total_commits'", [], |row| row.get(0)).unwrap();
+        assert_eq!(total, 100);
+        let column_type: String = connection.query_row(
+            "SELECT data_type FROM gstats_columns WHERE table_name = 'test_data' AND column_name = 'value'", [], |row| row.get(0)
+        ).unwrap();
+        assert_eq!(column_type, "INTEGER");
+        let node: (String, String) = connection.query_row("SELECT row_key, value FROM test_layout", [], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!(node, ("src".to_string(), "3".to_string()));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn test_parquet_writes_one_file_per_export() {
+        use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use arrow_array::Int64Array;
+        use arrow_schema::DataType;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("report.parquet");
+
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile".to_string(), output_path.display().to_string()];
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+        assert_eq!(plugin.export_config.read().await.output_format, ExportFormat::Parquet);
+
+        for plugin_id in ["commits", "metrics"] {
+            let event = PluginEvent::DataReady { plugin_id: plugin_id.to_string(), scan_id: "test-scan".to_string(), export: create_test_export_data() };
+            plugin.handle_event(event).await.unwrap();
+        }
+
+        let mut files: Vec<String> = std::fs::read_dir(temp_dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["report.test_data.parquet", "report.test_data_2.parquet"]);
+
+        let file = std::fs::File::open(temp_dir.path().join("report.test_data.parquet")).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let schema = builder.schema().clone();
+        assert_eq!(schema.field_with_name("value").unwrap().data_type(), &DataType::Int64);
+        assert_eq!(schema.metadata().get("plugin").map(String::as_str), Some("test"));
+        let batch = builder.build().unwrap().next().unwrap().unwrap();
+        let values = batch.column_by_name("value").unwrap().as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(values.value(0), 100);
+
+        // The long layout writes every export to the output file itself
+        let mut plugin = ExportPlugin::new();
+        let args = ["--outfile".to_string(), output_path.display().to_string(), "--layout".to_string(), "long".to_string()];
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+        for plugin_id in ["commits", "metrics"] {
+            let event = PluginEvent::DataReady { plugin_id: plugin_id.to_string(), scan_id: "test-scan".to_string(), export: create_test_export_data() };
+            plugin.handle_event(event).await.unwrap();
+        }
+        let file = std::fs::File::open(&output_path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let names: Vec<&str> = builder.schema().fields().iter().map(|field| field.name().as_str()).collect();
+        assert_eq!(names, vec!["plugin", "table", "row_key", "column", "value"]);
+    }
+
     #[tokio::test]
     async fn test_export_plugin_clone() {
         let plugin = ExportPlugin::new();
@@ -991,7 +1910,7 @@ mod tests {
         let data_vec = vec![export_data];
         
         // Test console formatting - should use prettytable-rs clean format
-        let console_result = plugin.format_console(&data_vec).await;
+        let console_result = plugin.format_as_console(&data_vec).await;
         assert!(console_result.is_ok());
         let console_output = console_result.unwrap();
         
@@ -1001,18 +1920,12 @@ mod tests {
         assert!(console_output.contains("total_commits"));
         assert!(console_output.contains("100"));
         
-        // Should have table format with pipe separators
-        assert!(console_output.contains("| metric        | value |"));
-        
-        // Should have 2-space indent for table
-        assert!(console_output.lines().any(|line| line.starts_with("  |")));
+        // Should have an aligned header row underlined with dashes
+        assert!(console_output.contains("metric        value"));
+        assert!(console_output.contains("------------- -----"));
         
-        // Should contain ASCII table borders (the current format)
-        assert!(console_output.contains("+-------+"));
-        assert!(console_output.contains("| metric"));
-        assert!(console_output.contains("| value"));
-        assert!(console_output.contains("| total_commits"));
-        assert!(console_output.contains("| 100"));
+        // Should indent the table rows
+        assert!(console_output.lines().any(|line| line.starts_with("   total_commits 100")));
     }
 
     #[tokio::test]
@@ -1027,7 +1940,7 @@ mod tests {
         let mut config = crate::display::ColourConfig::default();
         config.set_enabled(true);
         let color_manager = ColourManager::with_config(config);
-        let console_result = plugin.format_console_with_colors(&data_vec, &color_manager).await;
+        let console_result = formats::console::ConsoleFormatter::with_colors(Arc::new(color_manager.clone())).format_with_colors(&data_vec);
         assert!(console_result.is_ok());
         let console_output = console_result.unwrap();
         
@@ -1040,7 +1953,7 @@ mod tests {
         let mut no_config = crate::display::ColourConfig::default();
         no_config.set_enabled(false);
         let no_color_manager = ColourManager::with_config(no_config);
-        let no_color_result = plugin.format_console_with_colors(&data_vec, &no_color_manager).await;
+        let no_color_result = formats::console::ConsoleFormatter::with_colors(Arc::new(no_color_manager)).format_with_colors(&data_vec);
         assert!(no_color_result.is_ok());
         let no_color_output = no_color_result.unwrap();
         
@@ -1086,10 +1999,11 @@ mod tests {
             data: DataPayload::Rows(Arc::new(rows)),
             export_hints: crate::plugin::data_export::ExportHints::default(),
             timestamp: std::time::SystemTime::now(),
+            repository: None,
         });
         
         let data_vec = vec![export_data];
-        let console_result = plugin.format_console(&data_vec).await;
+        let console_result = plugin.format_as_console(&data_vec).await;
         assert!(console_result.is_ok());
         let console_output = console_result.unwrap();
         
@@ -1102,4 +2016,146 @@ mod tests {
         let lines: Vec<&str> = console_output.lines().collect();
         assert!(lines.len() >= 4); // Header + separator + 2 data rows minimum
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_single_row_summary_is_transposed() {
+        let columns = ["Commits", "Authors", "Files", "Lines Added", "Lines Removed"];
+        let summary = Arc::new(PluginDataExport {
+            plugin_id: "test".to_string(),
+            title: "Summary".to_string(),
+            description: None,
+            data_type: crate::plugin::data_export::DataExportType::Tabular,
+            schema: DataSchema {
+                columns: columns.iter().map(|name| ColumnDef::new(*name, ColumnType::Integer)).collect(),
+                metadata: std::collections::HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(vec![Row::new((1..=5).map(Value::Integer).collect())])),
+            export_hints: crate::plugin::data_export::ExportHints::default(),
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        });
+        let data_vec = vec![summary, create_test_export_data()];
+        
+        let plugin = ExportPlugin::new();
+        let console_output = plugin.format_as_console(&data_vec).await.unwrap();
+        assert!(console_output.contains("  Lines Removed: 5\n"));
+        assert!(console_output.contains("  Commits:       1\n"));
+        // Multi-row tables are unaffected
+        assert!(console_output.contains("total_commits"));
+        
+        let markdown_output = plugin.format_markdown(&data_vec).await.unwrap();
+        assert!(markdown_output.contains("- **Lines Removed**: 5\n"));
+        
+        plugin.export_config.write().await.transpose = formats::Transpose::Never;
+        let console_output = plugin.format_as_console(&data_vec).await.unwrap();
+        assert!(!console_output.contains("Commits:"));
+    }
+
+    #[tokio::test]
+    async fn test_column_descriptions_are_rendered() {
+        let data_vec = vec![create_test_export_data()];
+        let plugin = ExportPlugin::new();
+        
+        let html_output = plugin.format_html(&data_vec).await.unwrap();
+        assert!(html_output.contains("<th title=\"Metric name\">metric</th>"));
+        
+        let csv_output = plugin.format_csv(&data_vec).await.unwrap();
+        assert!(csv_output.contains("# metric: Metric name\n# value: Metric value\n"));
+        
+        let json_output: serde_json::Value = serde_json::from_str(&plugin.format_json(&data_vec).await.unwrap()).unwrap();
+        assert_eq!(json_output["test"]["schema"]["columns"][1]["description"], "Metric value");
+        
+        // Console output lists descriptions only on request
+        let console_output = plugin.format_as_console(&data_vec).await.unwrap();
+        assert!(!console_output.contains("Metric name"));
+        plugin.export_config.write().await.describe_columns = true;
+        let console_output = plugin.format_as_console(&data_vec).await.unwrap();
+        assert!(console_output.contains("  metric: Metric name\n"));
+    }
+    
+    #[tokio::test]
+    async fn test_html_theme_and_charts() {
+        let authors = Arc::new(PluginDataExport {
+            plugin_id: "commits".to_string(),
+            title: "Commit Analysis".to_string(),
+            description: None,
+            data_type: crate::plugin::data_export::DataExportType::Tabular,
+            schema: DataSchema {
+                columns: vec![ColumnDef::new("Author", ColumnType::String), ColumnDef::new("Commits", ColumnType::Integer)],
+                metadata: std::collections::HashMap::new(),
+            },
+            data: DataPayload::Rows(Arc::new(vec![Row::new(vec![Value::String("alice".to_string()), Value::Integer(3)])])),
+            export_hints: crate::plugin::data_export::ExportHints::default(),
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        });
+        let data_vec = vec![authors];
+        let mut plugin = ExportPlugin::new();
+        
+        let html_output = plugin.format_html(&data_vec).await.unwrap();
+        assert!(html_output.contains("background-color: #f2f2f2"));
+        assert!(!html_output.contains("GSTATS_CHARTS"));
+        
+        let args = ["--html-theme", "dark", "--html-charts"].map(String::from);
+        plugin.parse_plugin_arguments(&args).await.unwrap();
+        
+        let html_output = plugin.format_html(&data_vec).await.unwrap();
+        assert!(html_output.contains("background-color: #0d1117"));
+        assert!(html_output.contains("<section id=\"charts\"></section>"));
+        assert!(html_output.contains("\"title\":\"Top Authors\""));
+    }
+    
+    #[tokio::test]
+    async fn test_scanned_ref_in_export_metadata() {
+        let mut plugin = ExportPlugin::new();
+        plugin.scan_metadata = crate::scanner::branch_detection::BranchDetectionResult {
+            branch_name: "main".to_string(),
+            selection_source: crate::scanner::branch_detection::BranchSelectionSource::RemoteDefault,
+            commit_id: "abc123".to_string(),
+        }.to_metadata();
+
+        let export = plugin.with_scan_metadata(create_test_export_data());
+        assert_eq!(export.schema.metadata.get("branch").map(String::as_str), Some("main"));
+        assert_eq!(export.schema.metadata.get("branch_source").map(String::as_str), Some("Remote Default"));
+
+        let json_output = plugin.format_json(&[export]).await.unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json_output).unwrap();
+        assert_eq!(json["test"]["metadata"]["commit"], "abc123");
+    }
+
+    #[tokio::test]
+    async fn test_long_layout_csv() {
+        let data_vec = vec![create_test_export_data()];
+        let plugin = ExportPlugin::new();
+        plugin.export_config.write().await.layout = formats::Layout::Long;
+        
+        let csv_output = plugin.format_csv(&data_vec).await.unwrap();
+        let mut lines = csv_output.lines();
+        assert_eq!(lines.next(), Some("plugin,table,row_key,column,value"));
+        assert_eq!(lines.next(), Some("test,Test Data,total_commits,value,100"));
+        assert_eq!(lines.next(), Some("test,Test Data,total_authors,value,5"));
+        assert!(!csv_output.contains("# "));
+    }
+    
+    #[test]
+    fn test_findings_of_all_plugins_are_listed_together() {
+        use crate::plugin::data_export::{Finding, findings_export};
+        
+        let hotspot = Finding::new("hotspot", FindingSeverity::Medium, "maintainability", "Busy file");
+        let deletion = Finding::new("mass-deletion", FindingSeverity::High, "activity", "Removed 5000 lines");
+        let data = vec![
+            Arc::new(findings_export("metrics", &[hotspot]).unwrap()),
+            create_test_export_data(),
+            Arc::new(findings_export("commits", &[deletion]).unwrap()),
+        ];
+        
+        let unified = unify_findings(data);
+        assert_eq!(unified.len(), 2);
+        assert_eq!(unified[0].title, "Test Data");
+        assert!(unified[1].is_findings());
+        let DataPayload::Rows(rows) = &unified[1].data else { panic!("expected rows") };
+        let rules: Vec<String> = rows.iter().map(|row| row.values[3].to_string()).collect();
+        assert_eq!(rules, ["mass-deletion", "hotspot"]);
+        assert_eq!(rows[0].values[6].to_string(), "commits");
+    }
+}
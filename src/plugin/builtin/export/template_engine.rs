@@ -1,4 +1,8 @@
 //! Template engine for custom output formatting using Tera (Jinja2-like syntax)
+//!
+//! A single template file can be used on its own, or together with a directory of
+//! templates that it (and they) can `{% include %}` or `{% extends %}`. Templates loaded
+//! from a directory are named by their path relative to it, e.g. `partials/table.html`.
 
 use crate::plugin::{PluginResult, PluginError};
 use std::collections::HashMap;
@@ -9,6 +13,8 @@ use tera::{Tera, Context};
 pub struct TemplateEngine {
     tera: Tera,
     template_path: Option<std::path::PathBuf>,
+    /// Name of the template rendered, the entry point into any includes or inheritance
+    template_name: Option<String>,
     pub template_vars: HashMap<String, String>,
 }
 
@@ -21,10 +27,64 @@ impl TemplateEngine {
         Self {
             tera,
             template_path: None,
+            template_name: None,
             template_vars: HashMap::new(),
         }
     }
     
+    /// Load the templates for an export: every template in `template_dir` (if any), then
+    /// `template`, which is either a template file or the name of one in the directory.
+    /// Without `template`, the directory's `index` template is rendered.
+    pub fn load(&mut self, template: Option<&Path>, template_dir: Option<&Path>) -> PluginResult<()> {
+        if let Some(dir) = template_dir {
+            self.load_template_dir(dir)?;
+        }
+        match template {
+            Some(path) if path.is_file() => self.load_template(path),
+            Some(name) if template_dir.is_some() => self.select_template(&name.to_string_lossy()),
+            Some(path) => Err(PluginError::configuration_error(format!("Template file not found: {}", path.display()))),
+            None => {
+                let index = self.tera.get_template_names()
+                    .filter(|name| Path::new(name).file_stem().is_some_and(|stem| stem == "index"))
+                    .min()
+                    .map(str::to_string)
+                    .ok_or_else(|| PluginError::configuration_error(
+                        "No index template found; name the template to render with --template".to_string()
+                    ))?;
+                self.select_template(&index)
+            }
+        }
+    }
+    
+    /// Load every template in a directory (or matching a glob such as `templates/**/*.html`)
+    pub fn load_template_dir(&mut self, template_dir: &Path) -> PluginResult<()> {
+        let pattern = template_glob(template_dir);
+        let mut tera = Tera::new(&pattern)
+            .map_err(|e| PluginError::configuration_error(format!("Failed to load templates from {}: {}", pattern, error_chain(&e))))?;
+        if tera.get_template_names().next().is_none() {
+            return Err(PluginError::configuration_error(format!("No templates found in {}", pattern)));
+        }
+        tera.autoescape_on(vec![]);
+        self.tera = tera;
+        self.register_custom_filters();
+        
+        Ok(())
+    }
+    
+    /// Render a template already loaded from the template directory
+    pub fn select_template(&mut self, name: &str) -> PluginResult<()> {
+        if !self.tera.get_template_names().any(|loaded| loaded == name) {
+            let mut names: Vec<&str> = self.tera.get_template_names().collect();
+            names.sort_unstable();
+            return Err(PluginError::configuration_error(format!(
+                "Template '{}' not found; available templates: {}", name, names.join(", ")
+            )));
+        }
+        self.template_name = Some(name.to_string());
+        
+        Ok(())
+    }
+    
     pub fn load_template(&mut self, template_path: &Path) -> PluginResult<()> {
         let content = std::fs::read_to_string(template_path)
             .map_err(|e| PluginError::execution_failed(format!("Failed to read template file: {}", e)))?;
@@ -35,9 +95,10 @@ impl TemplateEngine {
             .unwrap_or("template");
             
         self.tera.add_raw_template(template_name, &content)
-            .map_err(|e| PluginError::configuration_error(format!("Template syntax error: {}", e)))?;
+            .map_err(|e| PluginError::configuration_error(format!("Template syntax error: {}", error_chain(&e))))?;
             
         self.template_path = Some(template_path.to_path_buf());
+        self.template_name = Some(template_name.to_string());
         
         // Register custom filters
         self.register_custom_filters();
@@ -126,14 +187,94 @@ impl TemplateEngine {
             context.insert(key, value);
         }
         
-        // Get the template name
-        let template_name = self.template_path.as_ref()
-            .and_then(|p| p.file_name())
-            .and_then(|n| n.to_str())
-            .unwrap_or("template");
-        
         // Render the template
+        let template_name = self.template_name.as_deref().unwrap_or("template");
         self.tera.render(template_name, &context)
-            .map_err(|e| PluginError::execution_failed(format!("Template rendering error: {}", e)))
+            .map_err(|e| PluginError::execution_failed(format!("Template rendering error: {}", error_chain(&e))))
+    }
+}
+
+/// Glob matching the templates to load: a directory means every file below it
+fn template_glob(template_dir: &Path) -> String {
+    let pattern = template_dir.to_string_lossy();
+    if pattern.contains(['*', '?', '[', '{']) {
+        pattern.into_owned()
+    } else {
+        format!("{}/**/*", pattern.trim_end_matches('/'))
+    }
+}
+
+/// Tera reports the cause of a failure (a missing include, the offending line) as the
+/// error's source, so include the whole chain in messages
+fn error_chain(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Parse a `--template-var key=value` assignment
+pub fn parse_template_var(assignment: &str) -> PluginResult<(String, String)> {
+    match assignment.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(PluginError::invalid_argument(
+            "--template-var",
+            &format!("expected key=value, got '{}'", assignment)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn template_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("partials")).unwrap();
+        std::fs::write(dir.path().join("base.txt"), "[{% block body %}{% endblock %}] {{ team }}").unwrap();
+        std::fs::write(dir.path().join("partials/title.txt"), "{{ title | upper }}").unwrap();
+        std::fs::write(
+            dir.path().join("index.txt"),
+            "{% extends \"base.txt\" %}{% block body %}{% include \"partials/title.txt\" %}{% endblock %}",
+        ).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_template_directory_with_includes_and_inheritance() {
+        let dir = template_dir();
+        let mut engine = TemplateEngine::new();
+        engine.load(None, Some(dir.path())).unwrap();
+        engine.add_template_var("team".to_string(), "core".to_string());
+
+        let rendered = engine.render(&serde_json::json!({ "title": "commits" })).unwrap();
+        assert_eq!(rendered, "[COMMITS] core");
+    }
+
+    #[test]
+    fn test_template_file_extends_directory_template() {
+        let dir = template_dir();
+        let report = dir.path().join("report.md");
+        std::fs::write(&report, "{% extends \"base.txt\" %}{% block body %}{{ title }}{% endblock %}").unwrap();
+
+        let mut engine = TemplateEngine::new();
+        engine.load(Some(&report), Some(&dir.path().join("*.txt"))).unwrap();
+        let rendered = engine.render(&serde_json::json!({ "title": "files", "team": "ops" })).unwrap();
+        assert_eq!(rendered, "[files] ops");
+
+        assert!(engine.load(Some(Path::new("missing.txt")), Some(dir.path())).is_err());
+    }
+
+    #[test]
+    fn test_parse_template_var() {
+        assert_eq!(parse_template_var("team=core=1").unwrap(), ("team".to_string(), "core=1".to_string()));
+        assert_eq!(parse_template_var("empty=").unwrap(), ("empty".to_string(), String::new()));
+        assert!(parse_template_var("=value").is_err());
+        assert!(parse_template_var("team").is_err());
     }
 }
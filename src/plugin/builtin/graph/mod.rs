@@ -0,0 +1,1046 @@
+//! Commit Graph Plugin
+//!
+//! Built-in plugin that analyzes the shape of the commit history rather than its
+//! content: how many commits are merges, how long branches live before they are
+//! merged, and how often changes are reverted.
+//!
+//! A merge is a commit with more than one parent. Each further parent of a merge
+//! brings in a branch: the commits reachable from that parent but not from the
+//! first parent, the mainline. A branch's lifetime runs from its earliest commit,
+//! where it left the mainline, to the merge. Merges whose parents fall outside the
+//! scanned history (a shallow clone or a `--since` limit) still count as merges,
+//! but their branches cannot be measured and are left out of the lifetimes.
+//!
+//! A revert is a commit whose subject is git's default for `git revert`
+//! (`Revert "..."`) or uses the conventional `revert` type.
+
+use crate::plugin::builtin::utils::time_buckets::{bucket_series, BucketPeriod, SeriesEntry, SeriesMode};
+use crate::plugin::memory::HeapSize;
+
+use crate::plugin::{
+    Plugin, PluginInfo, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
+};
+use crate::plugin::data_export::{
+    PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
+    DataPayload, Row, Value, ExportHints, ExportFormat
+};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
+use crate::notifications::AsyncNotificationManager;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use serde_json::json;
+
+/// Calendar periods offered for the trend table
+pub const TREND_PERIODS: [&str; 3] = ["week", "month", "quarter"];
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+fn percentage(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+/// Whether a commit subject marks a revert of an earlier commit
+fn is_revert(subject: &str) -> bool {
+    let subject = subject.trim_start();
+    let lower = subject.to_ascii_lowercase();
+    subject.starts_with("Revert \"") || lower.starts_with("revert:") || lower.starts_with("revert(")
+}
+
+/// One scanned commit as a node of the graph
+#[derive(Debug, Clone, PartialEq)]
+struct GraphCommit {
+    parents: Vec<String>,
+    /// Commit time
+    timestamp: i64,
+    /// Author time, which survives rebases and so dates the start of the work
+    author_timestamp: i64,
+    revert: bool,
+}
+
+impl HeapSize for GraphCommit {
+    fn heap_size(&self) -> usize {
+        self.parents.heap_size()
+    }
+}
+
+/// A branch brought in by a merge
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MergedBranch {
+    /// Time of the merge
+    merged: i64,
+    /// Commits on the branch
+    commits: usize,
+    /// Seconds from the branch's earliest commit to the merge
+    lifetime: i64,
+}
+
+impl MergedBranch {
+    fn lifetime_days(&self) -> f64 {
+        self.lifetime as f64 / SECONDS_PER_DAY
+    }
+}
+
+/// Commits, merges, branches and reverts in one period
+#[derive(Debug, Default, Clone, PartialEq)]
+struct PeriodStats {
+    commits: usize,
+    merges: usize,
+    reverts: usize,
+    branches: usize,
+    /// Sum of the lifetimes of the branches merged, in seconds
+    lifetime: i64,
+}
+
+impl PeriodStats {
+    fn merge_density(&self) -> f64 {
+        percentage(self.merges, self.commits)
+    }
+
+    /// Reverts as a share of the commits that are not merges
+    fn revert_rate(&self) -> f64 {
+        percentage(self.reverts, self.commits - self.merges)
+    }
+
+    fn average_lifetime_days(&self) -> Option<f64> {
+        (self.branches > 0).then(|| self.lifetime as f64 / self.branches as f64 / SECONDS_PER_DAY)
+    }
+}
+
+/// Graph measures over a whole scan
+#[derive(Debug, Default, Clone, PartialEq)]
+struct GraphAnalysis {
+    commits: usize,
+    merges: usize,
+    reverts: usize,
+    branches: Vec<MergedBranch>,
+}
+
+impl GraphAnalysis {
+    fn merge_density(&self) -> f64 {
+        percentage(self.merges, self.commits)
+    }
+
+    fn revert_rate(&self) -> f64 {
+        percentage(self.reverts, self.commits - self.merges)
+    }
+
+    /// Branch lifetimes in days, shortest first
+    fn lifetimes(&self) -> Vec<f64> {
+        let mut lifetimes: Vec<f64> = self.branches.iter().map(MergedBranch::lifetime_days).collect();
+        lifetimes.sort_by(f64::total_cmp);
+        lifetimes
+    }
+
+    fn average_lifetime(&self) -> Option<f64> {
+        let lifetimes = self.lifetimes();
+        (!lifetimes.is_empty()).then(|| lifetimes.iter().sum::<f64>() / lifetimes.len() as f64)
+    }
+
+    fn median_lifetime(&self) -> Option<f64> {
+        let lifetimes = self.lifetimes();
+        let middle = lifetimes.len() / 2;
+        match lifetimes.len() {
+            0 => None,
+            len if len % 2 == 0 => Some((lifetimes[middle - 1] + lifetimes[middle]) / 2.0),
+            _ => Some(lifetimes[middle]),
+        }
+    }
+
+    fn longest_lifetime(&self) -> Option<f64> {
+        self.lifetimes().last().copied()
+    }
+}
+
+/// Commit graph of the scan in progress
+#[derive(Debug, Default, Clone)]
+struct GraphTally {
+    commits: HashMap<String, GraphCommit>,
+}
+
+impl HeapSize for GraphTally {
+    fn heap_size(&self) -> usize {
+        self.commits.heap_size()
+    }
+}
+
+/// Marks set on commits while separating a branch from the mainline
+const ON_BRANCH: u8 = 1;
+const ON_MAINLINE: u8 = 2;
+
+impl GraphTally {
+    fn record(&mut self, hash: &str, parents: &[String], subject: &str, timestamp: i64, author_timestamp: i64) {
+        self.commits.insert(hash.to_string(), GraphCommit {
+            parents: parents.to_vec(),
+            timestamp,
+            author_timestamp,
+            revert: is_revert(subject),
+        });
+    }
+
+    /// Commits reachable from `branch` but not from `mainline`
+    ///
+    /// Both sides are walked together, newest commit first, marking each commit
+    /// with the sides it is reachable from, until every commit still to be walked
+    /// is on the mainline. Commits outside the scan end the walk on their side.
+    fn branch_commits(&self, mainline: &str, branch: &str) -> Vec<&GraphCommit> {
+        let mut marks: HashMap<&str, u8> = HashMap::new();
+        let mut queue: BinaryHeap<(i64, &str)> = BinaryHeap::new();
+        self.mark(&mut marks, &mut queue, branch, ON_BRANCH);
+        self.mark(&mut marks, &mut queue, mainline, ON_MAINLINE);
+
+        let mut walked: HashMap<&str, u8> = HashMap::new();
+        while queue.iter().any(|(_, hash)| marks[hash] & ON_MAINLINE == 0) {
+            let Some((_, hash)) = queue.pop() else {
+                break;
+            };
+            let side = marks[hash];
+            if walked.insert(hash, side) == Some(side) {
+                continue;
+            }
+            for parent in &self.commits[hash].parents {
+                self.mark(&mut marks, &mut queue, parent, side);
+            }
+        }
+
+        marks.into_iter()
+            .filter(|(_, side)| *side == ON_BRANCH)
+            .map(|(hash, _)| &self.commits[hash])
+            .collect()
+    }
+
+    /// Mark a scanned commit as reachable from a side, queueing it to be walked again if the mark is new
+    fn mark<'a>(&'a self, marks: &mut HashMap<&'a str, u8>, queue: &mut BinaryHeap<(i64, &'a str)>, hash: &str, side: u8) {
+        if let Some((hash, commit)) = self.commits.get_key_value(hash) {
+            let marked = marks.entry(hash.as_str()).or_default();
+            if *marked & side != side {
+                *marked |= side;
+                queue.push((commit.timestamp, hash.as_str()));
+            }
+        }
+    }
+
+    /// Branches brought in by a merge, one for each parent after the first that adds commits
+    fn merged_branches(&self, merge: &GraphCommit) -> Vec<MergedBranch> {
+        let Some((mainline, branches)) = merge.parents.split_first() else {
+            return Vec::new();
+        };
+        if !self.commits.contains_key(mainline) {
+            return Vec::new();
+        }
+        branches.iter()
+            .filter_map(|branch| {
+                let commits = self.branch_commits(mainline, branch);
+                let started = commits.iter().map(|commit| commit.author_timestamp.min(commit.timestamp)).min()?;
+                Some(MergedBranch {
+                    merged: merge.timestamp,
+                    commits: commits.len(),
+                    lifetime: (merge.timestamp - started).max(0),
+                })
+            })
+            .collect()
+    }
+
+    fn analyse(&self) -> GraphAnalysis {
+        let mut analysis = GraphAnalysis { commits: self.commits.len(), ..GraphAnalysis::default() };
+        for commit in self.commits.values() {
+            if commit.parents.len() > 1 {
+                analysis.merges += 1;
+                analysis.branches.extend(self.merged_branches(commit));
+            } else if commit.revert {
+                analysis.reverts += 1;
+            }
+        }
+        analysis.branches.sort_by_key(|branch| branch.merged);
+        analysis
+    }
+
+    /// Graph measures in each period from the first to the last commit
+    fn over_time(&self, analysis: &GraphAnalysis, period: BucketPeriod, mode: SeriesMode) -> Vec<SeriesEntry<PeriodStats>> {
+        let mut buckets: BTreeMap<chrono::NaiveDate, PeriodStats> = BTreeMap::new();
+        for commit in self.commits.values() {
+            if let Some(start) = period.start_of_timestamp(commit.timestamp) {
+                let stats = buckets.entry(start).or_default();
+                stats.commits += 1;
+                if commit.parents.len() > 1 {
+                    stats.merges += 1;
+                } else if commit.revert {
+                    stats.reverts += 1;
+                }
+            }
+        }
+        for branch in &analysis.branches {
+            if let Some(start) = period.start_of_timestamp(branch.merged) {
+                let stats = buckets.entry(start).or_default();
+                stats.branches += 1;
+                stats.lifetime += branch.lifetime;
+            }
+        }
+        bucket_series(buckets, period, mode)
+    }
+}
+
+/// Commit graph plugin
+pub struct GraphPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+
+    /// Calendar period of the trend table
+    period: BucketPeriod,
+
+    /// Layout of the trend table: every period, or empty periods collapsed into gaps
+    series_mode: SeriesMode,
+
+    /// Commit graph for the scan in progress; reset when a scan starts
+    tally: Arc<RwLock<GraphTally>>,
+
+    consuming: Arc<RwLock<bool>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
+
+    /// Notification publishing - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl GraphPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "graph".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Analyzes the commit graph: merge density, branch lifetimes and reverts".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Processing,
+        )
+        .with_capability(
+            "merge_density".to_string(),
+            "Measures the share of commits that are merges".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "branch_lifetimes".to_string(),
+            "Measures how long merged branches lived from their first commit to the merge".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "revert_rate".to_string(),
+            "Detects reverted changes and how often they occur".to_string(),
+            "1.0.0".to_string(),
+        );
+
+        Self {
+            command_name: "graph".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            period: BucketPeriod::Month,
+            series_mode: SeriesMode::Dense,
+            tally: Arc::new(RwLock::new(GraphTally::default())),
+            consuming: Arc::new(RwLock::new(false)),
+            consumer: Arc::new(RwLock::new(None)),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new graph plugin with all required dependencies (REQUIRED)
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Process a commit message and add the commit to the graph
+    async fn process_commit(&self, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::CommitInfo { hash, message, timestamp, author_timestamp, parents, .. } = &message.data {
+            self.tally.write().await.record(hash, parents, message, *timestamp, *author_timestamp);
+        }
+        Ok(())
+    }
+
+    /// Generate commit graph summary statistics
+    async fn generate_summary(&self) -> PluginResult<ScanMessage> {
+        let analysis = self.tally.read().await.analyse();
+
+        let data = MessageData::MetricInfo {
+            file_count: analysis.commits as u32,
+            line_count: analysis.merges as u64,
+            complexity: analysis.merge_density(),
+        };
+
+        let header = MessageHeader::new(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            "plugin-generated".to_string(),
+        );
+
+        Ok(ScanMessage::new(header, data))
+    }
+
+    fn export_hints() -> ExportHints {
+        ExportHints {
+            preferred_formats: vec![
+                ExportFormat::Console,
+                ExportFormat::Json,
+                ExportFormat::Csv,
+                ExportFormat::Html,
+                ExportFormat::Markdown,
+            ],
+            sort_by: None,
+            sort_ascending: false,
+            limit: None,
+            include_totals: false,
+            include_row_numbers: false,
+            custom_hints: HashMap::new(),
+        }
+    }
+
+    fn tabular_export(title: &str, description: String, schema: DataSchema, rows: Vec<Row>) -> PluginDataExport {
+        PluginDataExport {
+            plugin_id: "graph".to_string(),
+            title: title.to_string(),
+            description: Some(description),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: Self::export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+
+    /// Create the summary export: one row per measure
+    fn create_summary_export(analysis: &GraphAnalysis, scan_id: &str) -> Option<PluginDataExport> {
+        if analysis.commits == 0 {
+            return None;
+        }
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Metric", ColumnType::String),
+                ColumnDef::new("Value", ColumnType::Float),
+            ],
+            metadata: HashMap::new(),
+        };
+        let days = |lifetime: Option<f64>| lifetime.map_or(Value::Null, Value::Float);
+        let rows = vec![
+            ("Commits", Value::Integer(analysis.commits as i64)),
+            ("Merges", Value::Integer(analysis.merges as i64)),
+            ("Merge Density (%)", Value::Float(analysis.merge_density())),
+            ("Branches Merged", Value::Integer(analysis.branches.len() as i64)),
+            ("Average Branch Lifetime (days)", days(analysis.average_lifetime())),
+            ("Median Branch Lifetime (days)", days(analysis.median_lifetime())),
+            ("Longest Branch Lifetime (days)", days(analysis.longest_lifetime())),
+            ("Reverts", Value::Integer(analysis.reverts as i64)),
+            ("Revert Rate (%)", Value::Float(analysis.revert_rate())),
+        ]
+        .into_iter()
+        .map(|(metric, value)| Row::new(vec![Value::String(metric.to_string()), value]))
+        .collect();
+
+        Some(Self::tabular_export(
+            "Commit Graph",
+            format!(
+                "{} merges in {} commits ({:.1}%), {} branches merged and {} reverts in scan {}",
+                analysis.merges, analysis.commits, analysis.merge_density(), analysis.branches.len(), analysis.reverts, scan_id
+            ),
+            schema,
+            rows,
+        ))
+    }
+
+    /// Create the trend export: the graph measures per period
+    fn create_trend_export(tally: &GraphTally, analysis: &GraphAnalysis, scan_id: &str, period: BucketPeriod, mode: SeriesMode) -> Option<PluginDataExport> {
+        let series = tally.over_time(analysis, period, mode);
+        if series.is_empty() {
+            return None;
+        }
+
+        let period_column = match period {
+            BucketPeriod::Day => "Day",
+            BucketPeriod::Week => "Week",
+            BucketPeriod::Month => "Month",
+            BucketPeriod::Quarter => "Quarter",
+        };
+        let sparse = mode == SeriesMode::Sparse;
+        let mut schema = DataSchema {
+            columns: vec![
+                ColumnDef::new(period_column, ColumnType::String),
+                ColumnDef::new("Commits", ColumnType::Integer),
+                ColumnDef::new("Merges", ColumnType::Integer),
+                ColumnDef::new("Merge Density", ColumnType::Float)
+                    .with_description("Percentage of the period's commits that are merges")
+                    .with_format_hint("percentage"),
+                ColumnDef::new("Branches Merged", ColumnType::Integer),
+                ColumnDef::new("Avg Branch Lifetime", ColumnType::Float)
+                    .with_description("Average days from a branch's first commit to its merge, for branches merged in the period"),
+                ColumnDef::new("Reverts", ColumnType::Integer),
+                ColumnDef::new("Revert Rate", ColumnType::Float)
+                    .with_description("Percentage of the period's non-merge commits that are reverts")
+                    .with_format_hint("percentage"),
+            ],
+            metadata: HashMap::new(),
+        };
+        if sparse {
+            schema.columns.push(ColumnDef::new("Empty Periods", ColumnType::Integer)
+                .with_description("Consecutive periods without commits collapsed into this row"));
+        }
+        schema.metadata.insert("period".to_string(), period.as_str().to_string());
+        schema.metadata.insert("series".to_string(), mode.as_str().to_string());
+        let rows = series.iter()
+            .map(|entry| {
+                let stats = entry.value().cloned().unwrap_or_default();
+                let mut values = vec![
+                    Value::String(entry.label().to_string()),
+                    Value::Integer(stats.commits as i64),
+                    Value::Integer(stats.merges as i64),
+                    Value::Float(stats.merge_density()),
+                    Value::Integer(stats.branches as i64),
+                    stats.average_lifetime_days().map_or(Value::Null, Value::Float),
+                    Value::Integer(stats.reverts as i64),
+                    Value::Float(stats.revert_rate()),
+                ];
+                if sparse {
+                    values.push(Value::Integer(entry.gap_periods() as i64));
+                }
+                Row::new(values)
+            })
+            .collect();
+        let periods: usize = series.iter().map(|entry| entry.gap_periods().max(1)).sum();
+
+        Some(Self::tabular_export(
+            "Commit Graph over Time",
+            format!("Merges, branch lifetimes and reverts per {} over {} periods in scan {}", period.as_str(), periods, scan_id),
+            schema,
+            rows,
+        ))
+    }
+
+    /// Publish the summary and trend exports if any commits were scanned
+    async fn publish_exports(&self, scan_id: &str) {
+        let (exports, commits) = {
+            let tally = self.tally.read().await;
+            let analysis = tally.analyse();
+            let exports: Vec<_> = Self::create_summary_export(&analysis, scan_id)
+                .into_iter()
+                .chain(Self::create_trend_export(&tally, &analysis, scan_id, self.period, self.series_mode))
+                .collect();
+            (exports, analysis.commits)
+        };
+        log::debug!("Graph plugin publishing {} exports for {} commits", exports.len(), commits);
+
+        for export_data in exports {
+            let title = export_data.title.clone();
+            let event = PluginEvent::DataReady {
+                plugin_id: "graph".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish {} DataReady event: {}", title, e);
+            } else {
+                log::debug!("Published {} DataReady event for graph plugin", title);
+            }
+        }
+    }
+
+    /// Execute the commit graph analysis function
+    async fn execute_graph_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let (analysis, series) = {
+            let tally = self.tally.read().await;
+            let analysis = tally.analyse();
+            let series = tally.over_time(&analysis, self.period, self.series_mode);
+            (analysis, series)
+        };
+
+        let data = json!({
+            "total_commits": analysis.commits,
+            "merge_commits": analysis.merges,
+            "merge_density": analysis.merge_density(),
+            "branches_merged": analysis.branches.len(),
+            "average_branch_lifetime_days": analysis.average_lifetime(),
+            "median_branch_lifetime_days": analysis.median_lifetime(),
+            "longest_branch_lifetime_days": analysis.longest_lifetime(),
+            "average_branch_commits": (!analysis.branches.is_empty()).then(|| {
+                analysis.branches.iter().map(|branch| branch.commits).sum::<usize>() as f64 / analysis.branches.len() as f64
+            }),
+            "reverts": analysis.reverts,
+            "revert_rate": analysis.revert_rate(),
+            "period": self.period.as_str(),
+            "series": self.series_mode.as_str(),
+            "periods": series.iter().map(|entry| {
+                let stats = entry.value().cloned().unwrap_or_default();
+                json!({
+                    "period": entry.label(),
+                    "commits": stats.commits,
+                    "merges": stats.merges,
+                    "merge_density": stats.merge_density(),
+                    "branches_merged": stats.branches,
+                    "average_branch_lifetime_days": stats.average_lifetime_days(),
+                    "reverts": stats.reverts,
+                    "revert_rate": stats.revert_rate(),
+                    "empty_periods": entry.gap_periods(),
+                })
+            }).collect::<Vec<_>>(),
+            "function": "graph"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "graph_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: analysis.commits as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+}
+
+impl Default for GraphPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for GraphPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        *self.tally.write().await = GraphTally::default();
+        self.initialized = true;
+
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        if !self.initialized {
+            return Err(PluginError::invalid_state("Plugin not initialized"));
+        }
+
+        match request {
+            PluginRequest::Execute { invocation_type, .. } => {
+                let function_name = match invocation_type {
+                    crate::plugin::InvocationType::Function(ref func) => func.as_str(),
+                    crate::plugin::InvocationType::Direct => self.default_function().unwrap_or("graph"),
+                    crate::plugin::InvocationType::Default => "graph",
+                };
+
+                match function_name {
+                    "graph" | "merges" | "branches" => self.execute_graph_analysis().await,
+                    _ => Err(PluginError::execution_failed(
+                        format!("Unknown function: {}", function_name)
+                    )),
+                }
+            }
+            PluginRequest::GetStatistics => {
+                let summary = self.generate_summary().await?;
+                Ok(PluginResponse::Statistics(summary))
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        if *self.consuming.read().await {
+            self.stop_consuming().await?;
+        }
+
+        self.initialized = false;
+        *self.tally.write().await = GraphTally::default();
+        Ok(())
+    }
+
+    /// Get all functions this plugin can handle
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "graph".to_string(),
+                aliases: vec!["merges".to_string(), "branches".to_string()],
+                description: "Merge density, branch lifetimes and revert rate, overall and over time".to_string(),
+                is_default: true,
+            },
+        ]
+    }
+
+    /// Get the default function name
+    fn default_function(&self) -> Option<&str> {
+        Some("graph")
+    }
+
+    /// Cast to ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Cast to mutable ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for GraphPlugin {
+    async fn publish(&self, event: PluginEvent) -> crate::notifications::NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for GraphPlugin {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if *consuming {
+            return Err(PluginError::invalid_state("Already consuming"));
+        }
+
+        *consuming = true;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = Some(consumer);
+        }
+
+        log::info!("Graph plugin started consuming messages");
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        self.process_commit(&message).await?;
+
+        consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge message: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        log::debug!("Graph plugin received queue event: {:?}", event);
+
+        match event {
+            QueueEvent::ScanStarted { scan_id, .. } => {
+                log::info!("Graph plugin: scan started for {}", scan_id);
+                *self.tally.write().await = GraphTally::default();
+            }
+            QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
+                log::info!(
+                    "Graph plugin: scan {} complete - {} commits in the graph (total {} messages)",
+                    scan_id, self.tally.read().await.commits.len(), total_messages
+                );
+
+                self.publish_exports(scan_id).await;
+            }
+            _ => {
+                // Other events are just logged
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if !*consuming {
+            return Ok(()); // Already stopped
+        }
+
+        *consuming = false;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = None;
+        }
+
+        log::info!("Graph plugin stopped consuming messages");
+        Ok(())
+    }
+
+    async fn state_memory(&self) -> usize {
+        self.tally.read().await.heap_size()
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages: false, // Only interested in commit messages
+            interested_message_types: vec!["CommitInfo".to_string()],
+            high_frequency_capable: true,
+            preferred_batch_size: 100,
+            requires_ordered_delivery: false, // The graph is analysed once the scan completes
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_millis(10)),
+        }
+    }
+}
+
+/// Modern clap-based argument parsing implementation for graph plugin
+#[async_trait]
+impl PluginClapParser for GraphPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Analyzes the commit graph: merge density, branch lifetimes and reverts"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        use clap::Arg;
+
+        command
+            .override_usage("graph [OPTIONS]")
+            .help_template("Usage: {usage}\n\nAnalyzes the commit graph: merge density, branch lifetimes and reverts\n\nOptions:\n{options}\n{after-help}")
+            .after_help("A merge is a commit with more than one parent; each parent after the first brings in a branch \
+                made of the commits not reachable from the first parent. A branch's lifetime runs from its \
+                earliest commit to the merge, so rebased or squashed histories have no branches to measure. \
+                Reverts are commits whose subject starts with `Revert \"` or uses the `revert` commit type.")
+            .arg(Arg::new("period")
+                .long("period")
+                .value_name("PERIOD")
+                .help("Period of the trend table")
+                .value_parser(TREND_PERIODS)
+                .default_value("month"))
+            .arg(Arg::new("series")
+                .long("series")
+                .value_name("MODE")
+                .help("Trend series layout: dense lists every period, sparse collapses empty periods into gap rows")
+                .value_parser(["dense", "sparse"])
+                .default_value("dense"))
+    }
+
+    async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
+        if let Some(period) = matches.get_one::<String>("period").and_then(|period| BucketPeriod::parse(period)) {
+            self.period = period;
+        }
+
+        if let Some(mode) = matches.get_one::<String>("series").and_then(|m| SeriesMode::parse(m)) {
+            self.series_mode = mode;
+        }
+
+        log::debug!("Graph plugin configured with {} {} trend periods", self.series_mode.as_str(), self.period.as_str());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginContext;
+
+    const DAY: i64 = 86_400;
+    /// 2023-11-01T00:00:00Z
+    const START: i64 = 1_698_796_800;
+
+    fn create_test_commit_message(hash: &str, parents: &[&str], subject: &str, day: i64) -> ScanMessage {
+        let data = MessageData::CommitInfo {
+            hash: hash.to_string(),
+            author: "alice".to_string(),
+            message: subject.to_string(),
+            timestamp: START + day * DAY,
+            committer: "alice".to_string(),
+            author_timestamp: START + day * DAY,
+            parents: parents.iter().map(|parent| parent.to_string()).collect(),
+            changed_files: vec![],
+        };
+
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    fn create_test_context() -> PluginContext {
+        PluginContext::new(
+            Arc::new(crate::scanner::ScannerConfig::default()),
+            Arc::new(crate::scanner::QueryParams::default()),
+        )
+    }
+
+    /// Mainline a-b-c-m1-d-m2-r with a feature branch f1-f2 from b merged at m1,
+    /// a fix branch x1 from d merged at m2 in December, and a revert of d
+    async fn plugin_with_history() -> GraphPlugin {
+        let mut plugin = GraphPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        let commits: [(&str, &[&str], &str, i64); 10] = [
+            ("a", &[], "Initial commit", 0),
+            ("b", &["a"], "Add parser", 1),
+            ("f1", &["b"], "Start feature", 2),
+            ("c", &["b"], "Fix typo", 3),
+            ("f2", &["f1"], "Finish feature", 5),
+            ("m1", &["c", "f2"], "Merge branch 'feature'", 6),
+            ("d", &["m1"], "Speed up lexer", 20),
+            ("x1", &["d"], "Fix crash", 25),
+            ("m2", &["d", "x1"], "Merge branch 'fix'", 31),
+            ("r", &["m2"], "Revert \"Speed up lexer\"", 32),
+        ];
+        for (hash, parents, subject, day) in commits {
+            plugin.process_commit(&create_test_commit_message(hash, parents, subject, day)).await.unwrap();
+        }
+        plugin
+    }
+
+    #[tokio::test]
+    async fn test_graph_plugin_creation() {
+        let plugin = GraphPlugin::new();
+        assert_eq!(plugin.plugin_info().name, "graph");
+        assert_eq!(plugin.default_function(), Some("graph"));
+        assert_eq!(plugin.consumer_preferences().interested_message_types, vec!["CommitInfo".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_branches_merges_and_reverts() {
+        let plugin = plugin_with_history().await;
+        let tally = plugin.tally.read().await.clone();
+
+        let mut branch: Vec<i64> = tally.branch_commits("c", "f2").iter().map(|commit| commit.timestamp).collect();
+        branch.sort();
+        assert_eq!(branch, vec![START + 2 * DAY, START + 5 * DAY]);
+        // A parent already on the mainline brings in no branch
+        assert!(tally.branch_commits("m1", "b").is_empty());
+
+        let analysis = tally.analyse();
+        assert_eq!((analysis.commits, analysis.merges, analysis.reverts), (10, 2, 1));
+        assert_eq!(analysis.branches.iter().map(|branch| (branch.commits, branch.lifetime / DAY)).collect::<Vec<_>>(), [(2, 4), (1, 6)]);
+        assert_eq!(analysis.average_lifetime(), Some(5.0));
+        assert_eq!(analysis.median_lifetime(), Some(5.0));
+        assert_eq!(analysis.longest_lifetime(), Some(6.0));
+        assert_eq!(analysis.merge_density(), 20.0);
+        assert_eq!(analysis.revert_rate(), 12.5);
+
+        assert!(is_revert("revert(lexer): speed up"));
+        assert!(!is_revert("Reverted behaviour of the lexer"));
+    }
+
+    #[tokio::test]
+    async fn test_graph_exports() {
+        let plugin = plugin_with_history().await;
+        let tally = plugin.tally.read().await.clone();
+        let analysis = tally.analyse();
+
+        let summary = GraphPlugin::create_summary_export(&analysis, "scan").unwrap();
+        if let DataPayload::Rows(rows) = &summary.data {
+            assert_eq!(rows[1].values, vec![Value::String("Merges".to_string()), Value::Integer(2)]);
+            assert_eq!(rows[4].values[1], Value::Float(5.0));
+        } else {
+            panic!("expected row payload");
+        }
+
+        let trend = GraphPlugin::create_trend_export(&tally, &analysis, "scan", plugin.period, plugin.series_mode).unwrap();
+        assert_eq!(trend.schema.columns[0].name, "Month");
+        if let DataPayload::Rows(rows) = &trend.data {
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].values[..5], [
+                Value::String("2023-11".to_string()), Value::Integer(8), Value::Integer(1), Value::Float(12.5), Value::Integer(1),
+            ]);
+            assert_eq!(rows[0].values[5], Value::Float(4.0));
+            assert_eq!(rows[1].values[..3], [Value::String("2023-12".to_string()), Value::Integer(2), Value::Integer(1)]);
+            assert_eq!(rows[1].values[6..], [Value::Integer(1), Value::Float(100.0)]);
+        } else {
+            panic!("expected row payload");
+        }
+
+        assert!(GraphPlugin::create_summary_export(&GraphTally::default().analyse(), "scan").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sparse_trend_collapses_idle_weeks() {
+        let mut plugin = plugin_with_history().await;
+        let matches = PluginClapParser::build_clap_command(&plugin)
+            .try_get_matches_from(["graph", "--period", "week", "--series", "sparse"])
+            .unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        assert_eq!(plugin.series_mode, SeriesMode::Sparse);
+
+        let tally = plugin.tally.read().await.clone();
+        let analysis = tally.analyse();
+        let trend = GraphPlugin::create_trend_export(&tally, &analysis, "scan", plugin.period, plugin.series_mode).unwrap();
+        assert_eq!(trend.schema.columns.last().unwrap().name, "Empty Periods");
+        if let DataPayload::Rows(rows) = &trend.data {
+            // No commits fall in the week starting 2023-11-13
+            let labels: Vec<String> = rows.iter().map(|row| row.values[0].to_string()).collect();
+            assert_eq!(labels, ["2023-10-30", "2023-11-06", "2023-11-13", "2023-11-20", "2023-11-27"]);
+            assert_eq!(rows[2].values[1], Value::Integer(0));
+            assert_eq!(rows[2].values[8], Value::Integer(1));
+            assert_eq!(rows[3].values[8], Value::Integer(0));
+        } else {
+            panic!("expected row payload");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graph_period_and_reset() {
+        let mut plugin = plugin_with_history().await;
+        let matches = PluginClapParser::build_clap_command(&plugin)
+            .try_get_matches_from(["graph", "--period", "quarter"])
+            .unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        assert_eq!(plugin.period, BucketPeriod::Quarter);
+
+        plugin.handle_queue_event(&QueueEvent::ScanStarted { scan_id: "next".to_string(), timestamp: 0 }).await.unwrap();
+        assert!(plugin.tally.read().await.commits.is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! Language Detection and Line Classification
+//!
+//! Files are assigned a language by well-known file name, then by extension,
+//! then by the interpreter named on a `#!` line. Each line is classified as
+//! code, comment or blank using the language's comment syntax; a line holding
+//! both code and a comment counts as code. Comment markers inside string
+//! literals are not recognised.
+
+use std::path::Path;
+
+/// Block comment delimiters shared by the C family of languages
+const C_BLOCK: Option<(&str, &str)> = Some(("/*", "*/"));
+
+/// Block comment delimiters for markup languages
+const MARKUP_BLOCK: Option<(&str, &str)> = Some(("<!--", "-->"));
+
+/// Comment syntax of a language and the extensions that identify it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Language {
+    pub name: &'static str,
+    extensions: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+}
+
+/// Known languages
+static LANGUAGES: &[Language] = &[
+    Language::new("C", &["c"], &["//"], C_BLOCK),
+    Language::new("C Header", &["h"], &["//"], C_BLOCK),
+    Language::new("C++", &["cc", "cpp", "cxx", "c++", "hh", "hpp", "hxx"], &["//"], C_BLOCK),
+    Language::new("C#", &["cs"], &["//"], C_BLOCK),
+    Language::new("CMake", &["cmake"], &["#"], None),
+    Language::new("CSS", &["css"], &[], C_BLOCK),
+    Language::new("Dart", &["dart"], &["//"], C_BLOCK),
+    Language::new("Dockerfile", &["dockerfile"], &["#"], None),
+    Language::new("Elixir", &["ex", "exs"], &["#"], None),
+    Language::new("Erlang", &["erl", "hrl"], &["%"], None),
+    Language::new("Go", &["go"], &["//"], C_BLOCK),
+    Language::new("Groovy", &["groovy", "gradle"], &["//"], C_BLOCK),
+    Language::new("Haskell", &["hs"], &["--"], Some(("{-", "-}"))),
+    Language::new("HTML", &["html", "htm"], &[], MARKUP_BLOCK),
+    Language::new("Java", &["java"], &["//"], C_BLOCK),
+    Language::new("JavaScript", &["js", "mjs", "cjs", "jsx"], &["//"], C_BLOCK),
+    Language::new("JSON", &["json"], &[], None),
+    Language::new("Kotlin", &["kt", "kts"], &["//"], C_BLOCK),
+    Language::new("Lua", &["lua"], &["--"], Some(("--[[", "]]"))),
+    Language::new("Makefile", &["mk"], &["#"], None),
+    Language::new("Markdown", &["md", "markdown"], &[], MARKUP_BLOCK),
+    Language::new("Perl", &["pl", "pm"], &["#"], None),
+    Language::new("PHP", &["php"], &["//", "#"], C_BLOCK),
+    Language::new("PowerShell", &["ps1", "psm1"], &["#"], Some(("<#", "#>"))),
+    Language::new("Protocol Buffers", &["proto"], &["//"], C_BLOCK),
+    Language::new("Python", &["py", "pyi", "pyw"], &["#"], None),
+    Language::new("R", &["r"], &["#"], None),
+    Language::new("Ruby", &["rb", "rake", "gemspec"], &["#"], None),
+    Language::new("Rust", &["rs"], &["//"], C_BLOCK),
+    Language::new("Scala", &["scala"], &["//"], C_BLOCK),
+    Language::new("SCSS", &["scss", "less"], &["//"], C_BLOCK),
+    Language::new("Shell", &["sh", "bash", "zsh", "ksh", "fish"], &["#"], None),
+    Language::new("SQL", &["sql"], &["--"], C_BLOCK),
+    Language::new("Swift", &["swift"], &["//"], C_BLOCK),
+    Language::new("TOML", &["toml"], &["#"], None),
+    Language::new("TypeScript", &["ts", "tsx", "mts", "cts"], &["//"], C_BLOCK),
+    Language::new("XML", &["xml", "xsd", "xsl", "svg"], &[], MARKUP_BLOCK),
+    Language::new("YAML", &["yml", "yaml"], &["#"], None),
+    Language::new("Zig", &["zig"], &["//"], None),
+];
+
+/// Well-known file names without a distinguishing extension
+static FILE_NAMES: &[(&str, &str)] = &[
+    ("CMakeLists.txt", "CMake"),
+    ("Dockerfile", "Dockerfile"),
+    ("Gemfile", "Ruby"),
+    ("GNUmakefile", "Makefile"),
+    ("Jenkinsfile", "Groovy"),
+    ("Makefile", "Makefile"),
+    ("makefile", "Makefile"),
+    ("Rakefile", "Ruby"),
+];
+
+/// Interpreters named on `#!` lines, without version suffixes
+static INTERPRETERS: &[(&str, &str)] = &[
+    ("bash", "Shell"),
+    ("dash", "Shell"),
+    ("fish", "Shell"),
+    ("ksh", "Shell"),
+    ("lua", "Lua"),
+    ("node", "JavaScript"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("python", "Python"),
+    ("ruby", "Ruby"),
+    ("sh", "Shell"),
+    ("zsh", "Shell"),
+];
+
+/// Code, comment and blank line counts
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineCounts {
+    pub code: usize,
+    pub comments: usize,
+    pub blank: usize,
+}
+
+impl LineCounts {
+    /// Total number of lines
+    pub fn total(&self) -> usize {
+        self.code + self.comments + self.blank
+    }
+
+    /// Add another set of counts to these
+    pub fn add(&mut self, other: LineCounts) {
+        self.code += other.code;
+        self.comments += other.comments;
+        self.blank += other.blank;
+    }
+}
+
+/// Detect the language of a file from its path and, failing that, its `#!` line
+pub fn detect(path: &str, content: &str) -> Option<&'static Language> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    if let Some((_, name)) = FILE_NAMES.iter().find(|(known, _)| *known == file_name) {
+        return by_name(name);
+    }
+
+    match Path::new(file_name).extension() {
+        Some(extension) => {
+            let extension = extension.to_string_lossy().to_lowercase();
+            LANGUAGES.iter().find(|language| language.extensions.contains(&extension.as_str()))
+        }
+        None => shebang_interpreter(content)
+            .and_then(|interpreter| INTERPRETERS.iter().find(|(known, _)| *known == interpreter))
+            .and_then(|(_, name)| by_name(name)),
+    }
+}
+
+fn by_name(name: &str) -> Option<&'static Language> {
+    LANGUAGES.iter().find(|language| language.name == name)
+}
+
+/// Interpreter named on a `#!` first line, looking through `env` and dropping any version suffix
+fn shebang_interpreter(content: &str) -> Option<&str> {
+    let mut words = content.lines().next()?.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    Some(program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.'))
+}
+
+impl Language {
+    const fn new(
+        name: &'static str,
+        extensions: &'static [&'static str],
+        line_comments: &'static [&'static str],
+        block_comment: Option<(&'static str, &'static str)>,
+    ) -> Self {
+        Self { name, extensions, line_comments, block_comment }
+    }
+
+    /// Count code, comment and blank lines in a file's content
+    pub fn count_lines(&self, content: &str) -> LineCounts {
+        let mut counts = LineCounts::default();
+        let mut in_block = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                counts.blank += 1;
+            } else if self.has_code(line, &mut in_block) {
+                counts.code += 1;
+            } else {
+                counts.comments += 1;
+            }
+        }
+        counts
+    }
+
+    /// Check whether a line holds code outside comments, tracking whether it ends inside a block comment
+    fn has_code(&self, mut line: &str, in_block: &mut bool) -> bool {
+        let mut code = false;
+        loop {
+            if *in_block {
+                let Some((_, end)) = self.block_comment else { return code };
+                match line.find(end) {
+                    Some(pos) => {
+                        line = line[pos + end.len()..].trim_start();
+                        *in_block = false;
+                    }
+                    None => return code,
+                }
+            }
+
+            if line.is_empty() {
+                return code;
+            }
+            // Block comments are checked first as their opening may start with a line comment marker (Lua)
+            let block_start = self.block_comment.and_then(|(start, _)| line.find(start).map(|pos| (pos, start.len())));
+            let line_comment = self.line_comments.iter().filter_map(|marker| line.find(marker)).min();
+            match block_start {
+                Some((pos, len)) if line_comment.is_none_or(|comment| pos <= comment) => {
+                    code |= !line[..pos].trim().is_empty();
+                    line = &line[pos + len..];
+                    *in_block = true;
+                }
+                _ => return code || line_comment != Some(0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_by_name_extension_and_shebang() {
+        assert_eq!(detect("src/main.rs", "").map(|l| l.name), Some("Rust"));
+        assert_eq!(detect("web/App.TSX", "").map(|l| l.name), Some("TypeScript"));
+        assert_eq!(detect("build/Makefile", "all:\n").map(|l| l.name), Some("Makefile"));
+        assert_eq!(detect("bin/tool", "#!/usr/bin/env python3\nprint(1)\n").map(|l| l.name), Some("Python"));
+        assert_eq!(detect("bin/run", "#!/bin/bash\n").map(|l| l.name), Some("Shell"));
+        assert_eq!(detect("LICENSE", "MIT License\n"), None);
+        assert_eq!(detect("data.bin", ""), None);
+    }
+
+    #[test]
+    fn test_count_lines_with_line_and_block_comments() {
+        let rust = detect("lib.rs", "").unwrap();
+        let content = "// header\n\nfn main() { /* inline */\n    /*\n     * block\n     */\n    let x = 1; // trailing\n}\n";
+        assert_eq!(rust.count_lines(content), LineCounts { code: 3, comments: 4, blank: 1 });
+
+        let python = detect("app.py", "").unwrap();
+        assert_eq!(python.count_lines("# comment\nimport os\n\n"), LineCounts { code: 1, comments: 1, blank: 1 });
+
+        let lua = detect("init.lua", "").unwrap();
+        assert_eq!(lua.count_lines("--[[\nblock\n]]\n-- line\nprint(1)\n"), LineCounts { code: 1, comments: 4, blank: 0 });
+    }
+}
@@ -0,0 +1,649 @@
+//! Lines of Code Plugin
+//!
+//! Built-in plugin that classifies the files of the current tree by language
+//! and counts code, comment and blank lines per language. File content is
+//! streamed by the scanner after the commit history, so this plugin declares
+//! that it requires current file content.
+
+pub mod languages;
+
+use crate::plugin::{
+    Plugin, PluginInfo, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, PluginDataRequirements, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
+};
+use crate::plugin::aggregation::{Merge, ShardedTally};
+use crate::plugin::memory::{no_heap, HeapSize};
+use crate::plugin::data_export::{
+    PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
+    DataPayload, Row, Value, ExportHints, ExportFormat
+};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
+use crate::notifications::AsyncNotificationManager;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use serde_json::json;
+use self::languages::LineCounts;
+
+/// File and line counts for one language
+#[derive(Debug, Default, Clone, Copy)]
+struct LanguageStats {
+    files: usize,
+    lines: LineCounts,
+}
+
+/// Line counts per language for the files of the current tree
+#[derive(Debug, Default, Clone)]
+struct LanguageTally {
+    languages: BTreeMap<&'static str, LanguageStats>,
+    /// Text files whose language was not recognised
+    unrecognised: usize,
+}
+
+no_heap!(LanguageStats);
+
+impl HeapSize for LanguageTally {
+    fn heap_size(&self) -> usize {
+        self.languages.heap_size()
+    }
+}
+
+impl LanguageTally {
+    /// Classify a file and add its line counts to its language
+    fn record(&mut self, path: &str, content: &str) {
+        match languages::detect(path, content) {
+            Some(language) => {
+                let stats = self.languages.entry(language.name).or_default();
+                stats.files += 1;
+                stats.lines.add(language.count_lines(content));
+            }
+            None => self.unrecognised += 1,
+        }
+    }
+
+    /// Languages ordered by code lines, largest first
+    fn ranked(&self) -> Vec<(&'static str, LanguageStats)> {
+        let mut ranked: Vec<_> = self.languages.iter().map(|(name, stats)| (*name, *stats)).collect();
+        ranked.sort_by(|a, b| b.1.lines.code.cmp(&a.1.lines.code).then(a.0.cmp(b.0)));
+        ranked
+    }
+
+    /// Combined counts over all recognised files
+    fn totals(&self) -> LanguageStats {
+        self.languages.values().fold(LanguageStats::default(), |mut totals, stats| {
+            totals.files += stats.files;
+            totals.lines.add(stats.lines);
+            totals
+        })
+    }
+}
+
+impl Merge for LanguageTally {
+    fn merge(&mut self, other: Self) {
+        for (name, stats) in other.languages {
+            let merged = self.languages.entry(name).or_default();
+            merged.files += stats.files;
+            merged.lines.add(stats.lines);
+        }
+        self.unrecognised += other.unrecognised;
+    }
+}
+
+/// Lines of code plugin
+pub struct LocPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+
+    /// Line counts for the tree being scanned, sharded by path; reset when a scan starts
+    tally: Arc<ShardedTally<LanguageTally>>,
+
+    consuming: Arc<RwLock<bool>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
+
+    /// Notification publishing - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl LocPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "loc".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Counts code, comment and blank lines per language in the current tree".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Processing,
+        )
+        .with_capability(
+            "language_detection".to_string(),
+            "Classifies files by extension, file name and shebang".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "line_counts".to_string(),
+            "Counts code, comment and blank lines".to_string(),
+            "1.0.0".to_string(),
+        );
+
+        Self {
+            command_name: "loc".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            tally: Arc::new(ShardedTally::new()),
+            consuming: Arc::new(RwLock::new(false)),
+            consumer: Arc::new(RwLock::new(None)),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new loc plugin with all required dependencies (REQUIRED)
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Process a file content message and add the file to its language's counts
+    async fn process_file(&self, message: &ScanMessage) -> PluginResult<()> {
+        if let MessageData::FileContent { path, content, .. } = &message.data {
+            self.tally.update(path, |tally| tally.record(path, content));
+        }
+        Ok(())
+    }
+
+    /// Generate line count summary statistics
+    async fn generate_summary(&self) -> PluginResult<ScanMessage> {
+        let (totals, language_count) = {
+            let tally = self.tally.snapshot();
+            (tally.totals(), tally.languages.len())
+        };
+
+        let data = MessageData::MetricInfo {
+            file_count: totals.files as u32,
+            line_count: totals.lines.code as u64,
+            complexity: language_count as f64,
+        };
+
+        let header = MessageHeader::new(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            "plugin-generated".to_string(),
+        );
+
+        Ok(ScanMessage::new(header, data))
+    }
+
+    /// Create PluginDataExport with file and line counts per language
+    async fn create_language_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let tally = self.tally.snapshot();
+        if tally.languages.is_empty() {
+            return None;
+        }
+
+        let totals = tally.totals();
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Language", ColumnType::String),
+                ColumnDef::new("Files", ColumnType::Integer),
+                ColumnDef::new("Code", ColumnType::Integer)
+                    .with_description("Lines holding code, including those with trailing comments"),
+                ColumnDef::new("Comments", ColumnType::Integer)
+                    .with_description("Lines holding only comments"),
+                ColumnDef::new("Blank", ColumnType::Integer),
+                ColumnDef::new("Total", ColumnType::Integer),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("unrecognised_files".to_string(), tally.unrecognised.to_string());
+                meta
+            },
+        };
+
+        let rows: Vec<Row> = tally.ranked()
+            .into_iter()
+            .map(|(name, stats)| Row::new(vec![
+                Value::String(name.to_string()),
+                Value::Integer(stats.files as i64),
+                Value::Integer(stats.lines.code as i64),
+                Value::Integer(stats.lines.comments as i64),
+                Value::Integer(stats.lines.blank as i64),
+                Value::Integer(stats.lines.total() as i64),
+            ]))
+            .collect();
+
+        Some(PluginDataExport {
+            plugin_id: "loc".to_string(),
+            title: "Lines of Code".to_string(),
+            description: Some(format!(
+                "{} code lines in {} files across {} languages ({} unrecognised files skipped) in scan {}",
+                totals.lines.code, totals.files, tally.languages.len(), tally.unrecognised, scan_id
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Html,
+                    ExportFormat::Markdown,
+                ],
+                sort_by: None,
+                sort_ascending: false,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: SystemTime::now(),
+            repository: None,
+        })
+    }
+
+    /// Publish the per-language export if any files were counted
+    async fn publish_export(&self, scan_id: &str) {
+        let Some(export_data) = self.create_language_export(scan_id).await else {
+            return;
+        };
+
+        let event = PluginEvent::DataReady {
+            plugin_id: "loc".to_string(),
+            scan_id: scan_id.to_string(),
+            export: Arc::new(export_data),
+        };
+
+        if let Err(e) = self.publish(event).await {
+            log::warn!("Failed to publish Lines of Code DataReady event: {}", e);
+        } else {
+            log::debug!("Published Lines of Code DataReady event for loc plugin");
+        }
+    }
+
+    /// Execute the per-language line count function
+    async fn execute_loc_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+
+        let tally = self.tally.snapshot();
+        let totals = tally.totals();
+
+        let data = json!({
+            "total_files": totals.files,
+            "unrecognised_files": tally.unrecognised,
+            "totals": {
+                "code": totals.lines.code,
+                "comments": totals.lines.comments,
+                "blank": totals.lines.blank,
+            },
+            "languages": tally.ranked().iter().map(|(name, stats)| {
+                json!({
+                    "language": name,
+                    "files": stats.files,
+                    "code": stats.lines.code,
+                    "comments": stats.lines.comments,
+                    "blank": stats.lines.blank,
+                })
+            }).collect::<Vec<_>>(),
+            "function": "loc"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "loc_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: (totals.files + tally.unrecognised) as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+}
+
+impl Default for LocPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for LocPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        self.tally.reset();
+        self.initialized = true;
+
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        if !self.initialized {
+            return Err(PluginError::invalid_state("Plugin not initialized"));
+        }
+
+        match request {
+            PluginRequest::Execute { invocation_type, .. } => {
+                let function_name = match invocation_type {
+                    crate::plugin::InvocationType::Function(ref func) => func.as_str(),
+                    crate::plugin::InvocationType::Direct => self.default_function().unwrap_or("loc"),
+                    crate::plugin::InvocationType::Default => "loc",
+                };
+
+                match function_name {
+                    "loc" | "sloc" => self.execute_loc_analysis().await,
+                    _ => Err(PluginError::execution_failed(
+                        format!("Unknown function: {}", function_name)
+                    )),
+                }
+            }
+            PluginRequest::GetStatistics => {
+                let summary = self.generate_summary().await?;
+                Ok(PluginResponse::Statistics(summary))
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        if *self.consuming.read().await {
+            self.stop_consuming().await?;
+        }
+
+        self.initialized = false;
+        self.tally.reset();
+        Ok(())
+    }
+
+    /// Get all functions this plugin can handle
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "loc".to_string(),
+                aliases: vec!["sloc".to_string()],
+                description: "Code, comment and blank lines per language in the current tree".to_string(),
+                is_default: true,
+            },
+        ]
+    }
+
+    /// Get the default function name
+    fn default_function(&self) -> Option<&str> {
+        Some("loc")
+    }
+
+    /// Cast to ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Cast to mutable ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Expose data requirements so the scanner streams current file content
+    fn as_data_requirements(&self) -> Option<&dyn PluginDataRequirements> {
+        Some(self)
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for LocPlugin {
+    async fn publish(&self, event: PluginEvent) -> crate::notifications::NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+/// Data requirements implementation for LocPlugin
+/// Line counts come from the content of text files in the current tree
+impl PluginDataRequirements for LocPlugin {
+    fn requires_current_file_content(&self) -> bool {
+        true
+    }
+
+    fn requires_historical_file_content(&self) -> bool {
+        false
+    }
+
+    fn handles_binary_files(&self) -> bool {
+        false // Binary files have no lines to count
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for LocPlugin {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if *consuming {
+            return Err(PluginError::invalid_state("Already consuming"));
+        }
+
+        *consuming = true;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = Some(consumer);
+        }
+
+        log::info!("Loc plugin started consuming messages");
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        self.process_file(&message).await?;
+
+        consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge message: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        log::debug!("Loc plugin received queue event: {:?}", event);
+
+        match event {
+            QueueEvent::ScanStarted { scan_id, .. } => {
+                log::info!("Loc plugin: scan started for {}", scan_id);
+                self.tally.reset();
+            }
+            QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
+                let (file_count, language_count) = {
+                    let tally = self.tally.snapshot();
+                    (tally.totals().files, tally.languages.len())
+                };
+                log::info!(
+                    "Loc plugin: scan {} complete - counted {} files in {} languages (total {} messages)",
+                    scan_id, file_count, language_count, total_messages
+                );
+
+                self.publish_export(scan_id).await;
+            }
+            _ => {
+                // Other events are just logged
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if !*consuming {
+            return Ok(()); // Already stopped
+        }
+
+        *consuming = false;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = None;
+        }
+
+        log::info!("Loc plugin stopped consuming messages");
+        Ok(())
+    }
+
+    async fn state_memory(&self) -> usize {
+        self.tally.heap_size()
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages: false, // Only interested in file content
+            interested_message_types: vec!["FileContent".to_string()],
+            high_frequency_capable: true,
+            preferred_batch_size: 10,
+            requires_ordered_delivery: false, // Counts are summed per language
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_secs(1)), // Large files take longest
+        }
+    }
+}
+
+/// Modern clap-based argument parsing implementation for loc plugin
+#[async_trait]
+impl PluginClapParser for LocPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Counts code, comment and blank lines per language"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        command
+            .override_usage("loc [OPTIONS]")
+            .help_template("Usage: {usage}\n\nCounts code, comment and blank lines per language\n\nOptions:\n{options}\n{after-help}")
+            .after_help("Languages are detected by file name, extension and shebang line. Binary files and files \
+                excluded by the scan's path filters or size limit are not counted.")
+    }
+
+    async fn configure_from_matches(&mut self, _matches: &clap::ArgMatches) -> PluginResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginContext;
+
+    fn create_test_file_message(path: &str, content: &str) -> ScanMessage {
+        let data = MessageData::FileContent {
+            path: path.to_string(),
+            size: content.len() as u64,
+            content: content.to_string(),
+            generated: false,
+        };
+
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    fn create_test_context() -> PluginContext {
+        PluginContext::new(
+            Arc::new(crate::scanner::ScannerConfig::default()),
+            Arc::new(crate::scanner::QueryParams::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_loc_plugin_creation() {
+        let plugin = LocPlugin::new();
+        assert_eq!(plugin.plugin_info().name, "loc");
+        assert_eq!(plugin.default_function(), Some("loc"));
+        assert!(plugin.as_data_requirements().is_some_and(|r| r.requires_current_file_content()));
+    }
+
+    #[tokio::test]
+    async fn test_loc_language_export() {
+        let mut plugin = LocPlugin::new();
+        plugin.initialize(&create_test_context()).await.unwrap();
+
+        let messages = [
+            create_test_file_message("src/main.rs", "// entry\nfn main() {\n\n}\n"),
+            create_test_file_message("src/lib.rs", "pub mod a;\n"),
+            create_test_file_message("setup.py", "# build\nimport os\n"),
+            create_test_file_message("LICENSE", "MIT\n"),
+        ];
+        for message in &messages {
+            plugin.process_file(message).await.unwrap();
+        }
+
+        let export = plugin.create_language_export("scan").await.unwrap();
+        assert_eq!(export.schema.metadata.get("unrecognised_files"), Some(&"1".to_string()));
+        if let DataPayload::Rows(rows) = &export.data {
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].values[0], Value::String("Rust".to_string()));
+            assert_eq!(rows[0].values[1..], [
+                Value::Integer(2), Value::Integer(3), Value::Integer(1), Value::Integer(1), Value::Integer(5),
+            ]);
+            assert_eq!(rows[1].values[0], Value::String("Python".to_string()));
+        } else {
+            panic!("expected row payload");
+        }
+
+        plugin.handle_queue_event(&QueueEvent::ScanStarted { scan_id: "next".to_string(), timestamp: 0 }).await.unwrap();
+        assert!(plugin.create_language_export("next").await.is_none());
+    }
+}
@@ -5,17 +5,19 @@
 //! plugin processors module for advanced analysis capabilities.
 
 use crate::plugin::{
-    Plugin, PluginInfo, PluginContext, PluginRequest, PluginResponse,
-    PluginResult, PluginError, traits::{PluginType, PluginDataRequirements, ConsumerPlugin, ConsumerPreferences, PluginClapParser}
+    Plugin, PluginInfo, PluginContext, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginDataRequirements, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser}
 };
 use crate::plugin::data_export::{
     PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
-    DataPayload, Row, Value, ExportHints, ExportFormat
+    DataPayload, Row, Value, ExportHints, ExportFormat, Finding, findings_export
 };
 use crate::notifications::AsyncNotificationManager;
 use crate::notifications::events::PluginEvent;
 use crate::notifications::traits::{NotificationManager, Publisher};
-use crate::queue::{QueueConsumer, QueueEvent};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::MessageData;
+use crate::scanner::query::DateRange;
 use crate::scanner::async_engine::processors::{EventProcessor, EventProcessingCoordinator};
 use crate::plugin::processors::{
     ChangeFrequencyProcessor,
@@ -25,8 +27,15 @@ use crate::plugin::processors::{
     FormatDetectionProcessor,
     DuplicationDetectorProcessor,
 };
+use crate::plugin::processors::change_frequency::FileChangeStats;
+use crate::plugin::processors::commit_size::{CommitSize, CommitSizeAnalyzer, CommitSizeConfig};
+use crate::plugin::processors::complexity::ComplexityMetrics;
+use crate::plugin::processors::debt_assessment::DebtConfig;
+use crate::plugin::processors::duplication_detector::FileFingerprints;
+use crate::plugin::processors::hotspot::HotspotConfig;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use crate::plugin::memory::HeapSize;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -37,6 +46,18 @@ struct MetricsScanData {
     results: HashMap<String, serde_json::Value>,
     /// Scan start time for performance tracking
     started_at: std::time::Instant,
+    /// Files whose content counts toward complexity and duplication metrics
+    analysed_files: usize,
+    /// Generated files left out of complexity and duplication metrics
+    generated_files_excluded: usize,
+    /// Change history of each file, from commit messages
+    changes: HashMap<String, FileChangeStats>,
+    /// Complexity of each analysed file, from its current content
+    complexity: HashMap<String, ComplexityMetrics>,
+    /// Lines and files changed by each commit
+    commit_sizes: Vec<CommitSize>,
+    /// Content fingerprints of each analysed file, for duplicate detection
+    fingerprints: HashMap<String, FileFingerprints>,
 }
 
 impl MetricsScanData {
@@ -44,8 +65,40 @@ impl MetricsScanData {
         Self {
             results: HashMap::new(),
             started_at: std::time::Instant::now(),
+            analysed_files: 0,
+            generated_files_excluded: 0,
+            changes: HashMap::new(),
+            complexity: HashMap::new(),
+            commit_sizes: Vec::new(),
+            fingerprints: HashMap::new(),
         }
     }
+    
+    /// The scan's commit sizes, ready for analysis
+    fn commit_size_analyzer(&self) -> CommitSizeAnalyzer {
+        let mut analyzer = CommitSizeAnalyzer::new(CommitSizeConfig::default());
+        for commit in &self.commit_sizes {
+            analyzer.add_commit(commit.clone());
+        }
+        analyzer
+    }
+    
+    /// Duplicates among the scan's files
+    fn duplication(&self) -> DuplicationDetectorProcessor {
+        let mut processor = DuplicationDetectorProcessor::new();
+        for (path, file) in &self.fingerprints {
+            processor.add_fingerprints(path, file.lines, &file.fingerprints);
+        }
+        processor.detect_duplicates();
+        processor
+    }
+}
+
+impl HeapSize for MetricsScanData {
+    fn heap_size(&self) -> usize {
+        self.results.heap_size() + self.changes.heap_size() + self.complexity.heap_size() + self.commit_sizes.heap_size()
+            + self.fingerprints.heap_size()
+    }
 }
 
 /// Code Metrics Plugin using comprehensive event-driven processors
@@ -63,9 +116,18 @@ pub struct MetricsPlugin {
     /// Per-scan metrics data and results
     scan_data: Arc<RwLock<HashMap<String, MetricsScanData>>>,
     
+    /// Effective commit date range of the scan, reported in exports
+    date_range: Option<DateRange>,
+    
+    /// Whether files tagged as generated count toward content metrics
+    include_generated: bool,
+    
+    /// Weights and limits for hotspot scoring
+    hotspot_config: HotspotConfig,
+    
     /// Consumer plugin fields
     consuming: Arc<RwLock<bool>>,
-    consumer: Arc<RwLock<Option<QueueConsumer>>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
     
     /// Notification publishing - REQUIRED for all plugins
     notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
@@ -119,6 +181,14 @@ impl MetricsPlugin {
             initialized: false,
             processor_coordinator: None,
             scan_data: Arc::new(RwLock::new(HashMap::new())),
+            date_range: None,
+            include_generated: false,
+            // Every changed file is ranked; --top limits the report instead
+            hotspot_config: HotspotConfig {
+                complexity_threshold: 0.0,
+                frequency_threshold: 0.0,
+                ..HotspotConfig::default()
+            },
             consuming: Arc::new(RwLock::new(false)),
             consumer: Arc::new(RwLock::new(None)),
             notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
@@ -153,14 +223,434 @@ impl MetricsPlugin {
         processors
     }
     
+    /// Count a file toward the content metrics, or toward the generated files left out of them
+    fn record_file(&self, data: &mut MetricsScanData, path: &str, content: &str, generated: bool) {
+        if generated && !self.include_generated {
+            log::debug!("Metrics plugin: excluding generated file {}", path);
+            data.generated_files_excluded += 1;
+        } else {
+            data.analysed_files += 1;
+            data.complexity.insert(path.to_string(), ComplexityMetrics::from_content(path.to_string(), content));
+        }
+    }
+    
+    /// Record a file's content fingerprints, unless it is generated and generated files are excluded
+    fn record_fingerprints(&self, data: &mut MetricsScanData, path: &str, file: FileFingerprints, generated: bool) {
+        if generated && !self.include_generated {
+            return;
+        }
+        data.fingerprints.insert(path.to_string(), file);
+    }
+    
+    /// Record a commit against each file it changed
+    fn record_commit<'a>(data: &mut MetricsScanData, hash: &str, author: &str, timestamp: i64, paths: impl Iterator<Item = &'a str>) {
+        for path in paths {
+            data.changes
+                .entry(path.to_string())
+                .or_insert_with(|| FileChangeStats::new(path.to_string()))
+                .add_change(timestamp, author.to_string(), hash.to_string());
+        }
+    }
+    
+    /// Record what a message tells the metrics about its scan
+    fn record_message(&self, scan_data: &mut HashMap<String, MetricsScanData>, message: &crate::scanner::messages::ScanMessage) {
+        let recorded = matches!(message.data(),
+            MessageData::FileContent { .. } | MessageData::ContentFingerprints { .. } | MessageData::CommitInfo { .. });
+        if !recorded {
+            return;
+        }
+        let data = scan_data.entry(message.header().scan_id.clone()).or_insert_with(MetricsScanData::new);
+        match message.data() {
+            MessageData::FileContent { path, content, generated, .. } => {
+                self.record_file(data, path, content, *generated);
+            }
+            MessageData::ContentFingerprints { path, lines, generated, fingerprints } => {
+                let file = FileFingerprints { lines: *lines, fingerprints: fingerprints.clone() };
+                self.record_fingerprints(data, path, file, *generated);
+            }
+            MessageData::CommitInfo { hash, author, timestamp, author_timestamp, changed_files, .. } => {
+                Self::record_commit(data, hash, author, *timestamp, changed_files.iter().map(|file| file.path.as_str()));
+                data.commit_sizes.push(CommitSize {
+                    hash: hash.clone(),
+                    author: author.clone(),
+                    timestamp: *timestamp,
+                    // Messages from older caches carry no author time
+                    author_timestamp: if *author_timestamp == 0 { *timestamp } else { *author_timestamp },
+                    lines_added: changed_files.iter().map(|file| file.lines_added).sum(),
+                    lines_removed: changed_files.iter().map(|file| file.lines_removed).sum(),
+                    files: changed_files.len(),
+                });
+            }
+            _ => {}
+        }
+    }
+    
+    /// Create PluginDataExport of how many commits fall in each size range
+    async fn create_commit_sizes_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let analyzer = self.scan_data.read().await.get(scan_id)?.commit_size_analyzer();
+        if analyzer.commit_count() == 0 {
+            return None;
+        }
+        let percentiles = analyzer.percentiles();
+        let total = analyzer.commit_count();
+        
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Lines Changed", ColumnType::String)
+                    .with_description("Lines added plus removed per commit"),
+                ColumnDef::new("Commits", ColumnType::Integer),
+                ColumnDef::new("Share", ColumnType::Float)
+                    .with_description("Percentage of commits"),
+                ColumnDef::new("Total Lines", ColumnType::Integer),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("commits".to_string(), total.to_string());
+                meta.insert("mean".to_string(), format!("{:.1}", percentiles.mean));
+                for (name, value) in [("p50", percentiles.p50), ("p75", percentiles.p75), ("p90", percentiles.p90),
+                    ("p95", percentiles.p95), ("p99", percentiles.p99), ("max", percentiles.max)] {
+                    meta.insert(name.to_string(), value.to_string());
+                }
+                meta.insert("giant_threshold".to_string(), analyzer.giant_threshold().to_string());
+                if let Some(range) = &self.date_range {
+                    meta.extend(range.to_metadata());
+                }
+                meta
+            },
+        };
+        
+        let rows: Vec<Row> = analyzer.distribution()
+            .into_iter()
+            .map(|bucket| Row::new(vec![
+                Value::String(bucket.range),
+                Value::Integer(bucket.commits as i64),
+                Value::Float(round2(bucket.commits as f64 / total as f64 * 100.0)),
+                Value::Integer(bucket.lines as i64),
+            ]))
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "metrics".to_string(),
+            title: "Commit Sizes".to_string(),
+            description: Some(format!(
+                "Lines changed per commit over {} commits: median {}, p90 {}, p99 {}, largest {} in scan {}{}",
+                total, percentiles.p50, percentiles.p90, percentiles.p99, percentiles.max, scan_id,
+                self.date_range.as_ref().map(|range| format!(" ({})", range.describe())).unwrap_or_default()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Html,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport of giant commits and rewritten epochs, if there are any
+    async fn create_commit_anomalies_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let analyzer = self.scan_data.read().await.get(scan_id)?.commit_size_analyzer();
+        let anomalies = analyzer.anomalies();
+        if anomalies.is_empty() {
+            return None;
+        }
+        
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Anomaly", ColumnType::String),
+                ColumnDef::new("Commit", ColumnType::String)
+                    .with_description("The giant commit, or the newest commit of a rewritten epoch"),
+                ColumnDef::new("Author", ColumnType::String),
+                ColumnDef::new("Date", ColumnType::String),
+                ColumnDef::new("Lines", ColumnType::Integer),
+                ColumnDef::new("Commits", ColumnType::Integer),
+                ColumnDef::new("Detail", ColumnType::String),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("giant_threshold".to_string(), analyzer.giant_threshold().to_string());
+                if let Some(range) = &self.date_range {
+                    meta.extend(range.to_metadata());
+                }
+                meta
+            },
+        };
+        
+        let rows: Vec<Row> = anomalies
+            .iter()
+            .map(|anomaly| Row::new(vec![
+                Value::String(anomaly.kind.as_str().to_string()),
+                Value::String(anomaly.hash.chars().take(12).collect()),
+                Value::String(anomaly.author.clone()),
+                Value::String(crate::plugin::processors::commit_size::format_date(anomaly.timestamp)),
+                Value::Integer(anomaly.lines as i64),
+                Value::Integer(anomaly.commits as i64),
+                Value::String(anomaly.detail.clone()),
+            ]))
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "metrics".to_string(),
+            title: "Commit Anomalies".to_string(),
+            description: Some(format!(
+                "{} giant commits and rewritten epochs in scan {}{}",
+                rows.len(), scan_id,
+                self.date_range.as_ref().map(|range| format!(" ({})", range.describe())).unwrap_or_default()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Html,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport of the blocks of code duplicated across files, if there are any
+    async fn create_duplicates_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let processor = self.scan_data.read().await.get(scan_id)?.duplication();
+        let groups = processor.duplicate_groups();
+        if groups.is_empty() {
+            return None;
+        }
+        let summary = processor.generate_summary();
+        
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Group", ColumnType::Integer),
+                ColumnDef::new("File", ColumnType::String),
+                ColumnDef::new("Lines", ColumnType::String)
+                    .with_description("First and last line of the duplicated block"),
+                ColumnDef::new("Copies", ColumnType::Integer)
+                    .with_description("Blocks in the group"),
+                ColumnDef::new("Impact", ColumnType::Float)
+                    .with_description("Copies, size and density of the duplicated code"),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("files_analysed".to_string(), summary.total_files_analyzed.to_string());
+                meta.insert("duplicate_groups".to_string(), summary.duplicate_groups.to_string());
+                meta.insert("duplicate_lines".to_string(), summary.total_duplicate_lines.to_string());
+                if let Some(range) = &self.date_range {
+                    meta.extend(range.to_metadata());
+                }
+                meta
+            },
+        };
+        
+        let rows: Vec<Row> = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(index, group)| group.blocks.iter().map(move |block| Row::new(vec![
+                Value::Integer(index as i64 + 1),
+                Value::String(block.file_path.clone()),
+                Value::String(format!("{}-{}", block.start_line, block.end_line)),
+                Value::Integer(group.blocks.len() as i64),
+                Value::Float(round2(group.impact_score)),
+            ])))
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "metrics".to_string(),
+            title: "Duplicates".to_string(),
+            description: Some(format!(
+                "{} blocks of code duplicated across {} files ({:.1}% of {} lines) in scan {}{}",
+                summary.duplicate_groups, summary.files_with_duplicates, summary.duplication_percentage,
+                summary.total_lines_analyzed, scan_id,
+                self.date_range.as_ref().map(|range| format!(" ({})", range.describe())).unwrap_or_default()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Html,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create PluginDataExport ranking the files that are both complex and frequently changed
+    async fn create_hotspots_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let mut processor = HotspotProcessor::with_config(self.hotspot_config.clone());
+        {
+            let scan_data_guard = self.scan_data.read().await;
+            let data = scan_data_guard.get(scan_id)?;
+            processor.analyze_hotspots(&data.complexity, &data.changes);
+        }
+        
+        let config = processor.config();
+        let hotspots = processor.get_top_hotspots(config.max_hotspots);
+        if hotspots.is_empty() {
+            return None;
+        }
+        
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Rank", ColumnType::Integer),
+                ColumnDef::new("File", ColumnType::String),
+                ColumnDef::new("Score", ColumnType::Float)
+                    .with_description("Weighted average of the component scores, 0-100"),
+                ColumnDef::new("Risk", ColumnType::String)
+                    .with_format_hint("severity"),
+                ColumnDef::new("Change Score", ColumnType::Float)
+                    .with_description("Recency-weighted change frequency, 0-100"),
+                ColumnDef::new("Complexity Score", ColumnType::Float)
+                    .with_description("Cyclomatic and cognitive complexity, size and nesting, 0-100"),
+                ColumnDef::new("Author Score", ColumnType::Float)
+                    .with_description("Number of distinct authors, 0-100"),
+                ColumnDef::new("Changes", ColumnType::Integer),
+                ColumnDef::new("Authors", ColumnType::Integer),
+                ColumnDef::new("Cyclomatic", ColumnType::Float),
+                ColumnDef::new("Lines", ColumnType::Integer),
+            ],
+            metadata: {
+                let mut meta = HashMap::new();
+                meta.insert("change_weight".to_string(), config.frequency_weight.to_string());
+                meta.insert("complexity_weight".to_string(), config.complexity_weight.to_string());
+                meta.insert("author_weight".to_string(), config.author_weight.to_string());
+                meta.insert("top".to_string(), config.max_hotspots.to_string());
+                if let Some(range) = &self.date_range {
+                    meta.extend(range.to_metadata());
+                }
+                meta
+            },
+        };
+        
+        let rows: Vec<Row> = hotspots
+            .iter()
+            .enumerate()
+            .map(|(rank, hotspot)| Row::new(vec![
+                Value::Integer(rank as i64 + 1),
+                Value::String(hotspot.file_path.clone()),
+                Value::Float(round2(hotspot.hotspot_score)),
+                Value::String(hotspot.risk_level.as_str().to_string()),
+                Value::Float(round2(hotspot.frequency_score)),
+                Value::Float(round2(hotspot.complexity_score)),
+                Value::Float(round2(hotspot.author_score)),
+                Value::Integer(hotspot.change_count as i64),
+                Value::Integer(hotspot.author_count as i64),
+                Value::Float(hotspot.cyclomatic_complexity),
+                Value::Integer(hotspot.lines_of_code as i64),
+            ]))
+            .collect();
+        
+        Some(PluginDataExport {
+            plugin_id: "metrics".to_string(),
+            title: "Hotspots".to_string(),
+            description: Some(format!(
+                "Top {} of {} files ranked by change frequency, complexity and authors in scan {}{}",
+                rows.len(), processor.generate_summary().total_hotspots, scan_id,
+                self.date_range.as_ref().map(|range| format!(" ({})", range.describe())).unwrap_or_default()
+            )),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: ExportHints {
+                preferred_formats: vec![
+                    ExportFormat::Console,
+                    ExportFormat::Json,
+                    ExportFormat::Csv,
+                    ExportFormat::Html,
+                ],
+                sort_by: None,
+                sort_ascending: true,
+                limit: None,
+                include_totals: false,
+                include_row_numbers: false,
+                custom_hints: HashMap::new(),
+            },
+            timestamp: std::time::SystemTime::now(),
+            repository: None,
+        })
+    }
+    
+    /// Create the findings export raised by hotspots and technical debt, if any files warrant one
+    async fn create_findings_export(&self, scan_id: &str) -> Option<PluginDataExport> {
+        let mut hotspots = HotspotProcessor::with_config(self.hotspot_config.clone());
+        let mut debt = DebtAssessmentProcessor::with_config(DebtConfig::default());
+        {
+            let scan_data_guard = self.scan_data.read().await;
+            let data = scan_data_guard.get(scan_id)?;
+            hotspots.analyze_hotspots(&data.complexity, &data.changes);
+            debt.assess_debt(&data.complexity, &data.changes, &data.duplication().file_impacts());
+        }
+        
+        let findings: Vec<Finding> = hotspots.get_top_hotspots(hotspots.config().max_hotspots)
+            .into_iter()
+            .filter_map(|hotspot| hotspot.to_finding())
+            .chain(debt.get_top_debt_files(DebtConfig::default().max_files)
+                .into_iter()
+                .filter_map(|assessment| assessment.to_finding()))
+            .collect();
+        let mut export = findings_export("metrics", &findings)?;
+        if let Some(range) = &self.date_range {
+            export.schema.metadata.extend(range.to_metadata());
+        }
+        Some(export)
+    }
+    
     /// Create PluginDataExport from current metrics results
     async fn create_data_export(&self, scan_id: &str) -> PluginResult<PluginDataExport> {
-        let results = {
+        let (results, generated_files_excluded) = {
             let scan_data_guard = self.scan_data.read().await;
             if let Some(data) = scan_data_guard.get(scan_id) {
-                data.results.clone()
+                let mut results = data.results.clone();
+                results.insert("files_analysed".to_string(), data.analysed_files.into());
+                if !data.fingerprints.is_empty() {
+                    let summary = data.duplication().generate_summary();
+                    results.insert("duplication_groups".to_string(), summary.duplicate_groups.into());
+                    results.insert("duplication_lines".to_string(), summary.total_duplicate_lines.into());
+                    results.insert("duplication_percentage".to_string(), round2(summary.duplication_percentage).into());
+                }
+                if !data.commit_sizes.is_empty() {
+                    let percentiles = data.commit_size_analyzer().percentiles();
+                    results.insert("commit_size_mean".to_string(), round2(percentiles.mean).into());
+                    results.insert("commit_size_p50".to_string(), percentiles.p50.into());
+                    results.insert("commit_size_p90".to_string(), percentiles.p90.into());
+                    results.insert("commit_size_p99".to_string(), percentiles.p99.into());
+                    results.insert("commit_size_max".to_string(), percentiles.max.into());
+                }
+                results.insert("generated_files_excluded".to_string(), data.generated_files_excluded.into());
+                (results, data.generated_files_excluded)
             } else {
-                HashMap::new()
+                (HashMap::new(), 0)
             }
         };
         
@@ -178,6 +668,10 @@ impl MetricsPlugin {
                 let mut meta = HashMap::new();
                 meta.insert("description".to_string(), "Code quality metrics and analysis results".to_string());
                 meta.insert("generated_by".to_string(), "metrics_plugin".to_string());
+                meta.insert("generated_files_excluded".to_string(), generated_files_excluded.to_string());
+                if let Some(range) = &self.date_range {
+                    meta.extend(range.to_metadata());
+                }
                 meta
             },
         };
@@ -199,6 +693,8 @@ impl MetricsPlugin {
                     "Duplication"
                 } else if key.contains("format") {
                     "Format Detection"
+                } else if key.starts_with("commit_size") {
+                    "Commit Size"
                 } else {
                     "General"
                 };
@@ -252,18 +748,25 @@ impl MetricsPlugin {
             plugin_id: "metrics".to_string(),
             title: "Code Quality Metrics".to_string(),
             description: Some(format!(
-                "Comprehensive code quality analysis with {} metrics for scan {}",
-                results.len(), scan_id
+                "Comprehensive code quality analysis with {} metrics for scan {}{}",
+                results.len(), scan_id,
+                self.date_range.as_ref().map(|range| format!(" ({})", range.describe())).unwrap_or_default()
             )),
             data_type: DataExportType::Tabular,
             schema,
             data: DataPayload::Rows(Arc::new(rows)),
             export_hints,
             timestamp: std::time::SystemTime::now(),
+            repository: None,
         })
     }
 }
 
+/// Round a score to two decimal places for display
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
 impl Default for MetricsPlugin {
     fn default() -> Self {
         Self::new()
@@ -272,7 +775,7 @@ impl Default for MetricsPlugin {
 
 #[async_trait]
 impl ConsumerPlugin for MetricsPlugin {
-    async fn start_consuming(&mut self, consumer: QueueConsumer) -> PluginResult<()> {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
         let mut consuming = self.consuming.write().await;
         
         if *consuming {
@@ -291,10 +794,11 @@ impl ConsumerPlugin for MetricsPlugin {
         Ok(())
     }
     
-    async fn process_message(&self, consumer: &QueueConsumer, message: Arc<crate::scanner::messages::ScanMessage>) -> PluginResult<()> {
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<crate::scanner::messages::ScanMessage>) -> PluginResult<()> {
         // Process the message through our event processors
-        // For now, just acknowledge the message
+        // For now, file content and commits are recorded
         // TODO: Integrate with the event processing coordinator
+        self.record_message(&mut *self.scan_data.write().await, &message);
         
         // Acknowledge the message
         consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
@@ -304,6 +808,22 @@ impl ConsumerPlugin for MetricsPlugin {
         Ok(())
     }
     
+    async fn process_batch(&self, consumer: &dyn MessageConsumer, messages: Vec<Arc<crate::scanner::messages::ScanMessage>>) -> PluginResult<()> {
+        // The scan data is locked once for the whole batch, which is acknowledged together
+        let mut scan_data = self.scan_data.write().await;
+        for message in &messages {
+            self.record_message(&mut scan_data, message);
+        }
+        drop(scan_data);
+        
+        let sequences: Vec<u64> = messages.iter().map(|message| message.header().sequence()).collect();
+        consumer.acknowledge_batch(&sequences).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge messages: {}", e))
+        })?;
+        
+        Ok(())
+    }
+    
     async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
         log::debug!("Metrics plugin received queue event: {:?}", event);
         
@@ -318,34 +838,47 @@ impl ConsumerPlugin for MetricsPlugin {
                 }
             }
             QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
-                let (result_count, elapsed) = {
+                let (result_count, excluded, elapsed) = {
                     let scan_data = self.scan_data.read().await;
                     if let Some(data) = scan_data.get(scan_id) {
                         let elapsed = data.started_at.elapsed();
-                        (data.results.len(), elapsed)
+                        (data.results.len(), data.generated_files_excluded, elapsed)
                     } else {
-                        (0, std::time::Duration::from_secs(0))
+                        (0, 0, std::time::Duration::from_secs(0))
                     }
                 };
+                if excluded > 0 {
+                    log::info!(
+                        "Metrics plugin: excluded {} generated files from complexity and duplication metrics (use --include-generated to keep them)",
+                        excluded
+                    );
+                }
                 log::info!(
                     "Metrics plugin: scan complete for {} - generated {} metrics (total {} messages) in {:?}", 
                     scan_id, result_count, total_messages, elapsed
                 );
                 
-                // Create and publish data export
-                {
-                    if let Ok(export_data) = self.create_data_export(scan_id).await {
-                        let event = PluginEvent::DataReady {
-                            plugin_id: "metrics".to_string(),
-                            scan_id: scan_id.clone(),
-                            export: Arc::new(export_data),
-                        };
-                        
-                        if let Err(e) = self.publish(event).await {
-                            log::warn!("Failed to publish DataReady event: {}", e);
-                        } else {
-                            log::debug!("Published DataReady event for metrics plugin");
-                        }
+                // Create and publish data exports
+                let exports = [
+                    self.create_data_export(scan_id).await.ok(),
+                    self.create_hotspots_export(scan_id).await,
+                    self.create_duplicates_export(scan_id).await,
+                    self.create_commit_sizes_export(scan_id).await,
+                    self.create_commit_anomalies_export(scan_id).await,
+                    self.create_findings_export(scan_id).await,
+                ];
+                for export_data in exports.into_iter().flatten() {
+                    let title = export_data.title.clone();
+                    let event = PluginEvent::DataReady {
+                        plugin_id: "metrics".to_string(),
+                        scan_id: scan_id.clone(),
+                        export: Arc::new(export_data),
+                    };
+                    
+                    if let Err(e) = self.publish(event).await {
+                        log::warn!("Failed to publish {} DataReady event: {}", title, e);
+                    } else {
+                        log::debug!("Published {} DataReady event for metrics plugin", title);
                     }
                 }
             }
@@ -376,6 +909,10 @@ impl ConsumerPlugin for MetricsPlugin {
         Ok(())
     }
     
+    async fn state_memory(&self) -> usize {
+        self.scan_data.read().await.values().map(HeapSize::heap_size).sum()
+    }
+    
     fn consumer_preferences(&self) -> ConsumerPreferences {
         ConsumerPreferences {
             consume_all_messages: true, // Metrics needs all data types for comprehensive analysis
@@ -383,6 +920,8 @@ impl ConsumerPlugin for MetricsPlugin {
             high_frequency_capable: true, // Can handle high message rates
             preferred_batch_size: 100, // Process in larger batches for efficiency
             requires_ordered_delivery: false, // Order doesn't matter for metrics
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_secs(1)),
         }
     }
 }
@@ -393,10 +932,14 @@ impl Plugin for MetricsPlugin {
         &self.info
     }
 
-    async fn initialize(&mut self, _context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
         if self.initialized {
             return Ok(());
         }
+        
+        // Record the effective date range so exports can report it
+        self.date_range = context.query_params.date_range.clone();
 
         let mut coordinator = EventProcessingCoordinator::new();
         let processors = self.create_processors();
@@ -444,7 +987,7 @@ impl Plugin for MetricsPlugin {
                 
                 let metadata = crate::plugin::context::ExecutionMetadata {
                     duration_us,
-                    memory_used: 0,
+                    memory_used: self.state_memory().await as u64,
                     entries_processed: results.len() as u64,
                     plugin_version: self.info.version.clone(),
                     extra: HashMap::new(),
@@ -510,6 +1053,11 @@ impl Plugin for MetricsPlugin {
         Some(self)
     }
     
+    /// Expose data requirements so the scanner streams file content and its fingerprints
+    fn as_data_requirements(&self) -> Option<&dyn PluginDataRequirements> {
+        Some(self)
+    }
+    
     /// Get all functions this plugin can handle
     fn advertised_functions(&self) -> Vec<crate::plugin::traits::PluginFunction> {
         vec![
@@ -575,6 +1123,10 @@ impl PluginDataRequirements for MetricsPlugin {
         true // Needs to analyze code complexity, duplication, etc.
     }
     
+    fn requires_content_fingerprints(&self) -> bool {
+        true // Matches duplicated blocks across files
+    }
+    
     fn requires_historical_file_content(&self) -> bool {
         false // Focuses on current state metrics, not historical comparison
     }
@@ -643,11 +1195,39 @@ impl PluginClapParser for MetricsPlugin {
                 .long("detailed")
                 .help("Include detailed metrics breakdown")
                 .action(ArgAction::SetTrue))
+            .arg(Arg::new("include-generated")
+                .long("include-generated")
+                .help("Include generated and minified files in complexity and duplication metrics")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .help("Number of hotspots to report")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("20"))
+            .arg(Arg::new("change-weight")
+                .long("change-weight")
+                .value_name("WEIGHT")
+                .help("Weight of change frequency in the hotspot score")
+                .value_parser(clap::value_parser!(f64))
+                .default_value("0.4"))
+            .arg(Arg::new("complexity-weight")
+                .long("complexity-weight")
+                .value_name("WEIGHT")
+                .help("Weight of complexity in the hotspot score")
+                .value_parser(clap::value_parser!(f64))
+                .default_value("0.4"))
+            .arg(Arg::new("author-weight")
+                .long("author-weight")
+                .value_name("WEIGHT")
+                .help("Weight of the number of authors in the hotspot score")
+                .value_parser(clap::value_parser!(f64))
+                .default_value("0.2"))
     }
     
     async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
-        // Metrics plugin doesn't have complex configuration state to update
-        // The arguments are handled during execution based on the function being called
+        // Only --include-generated is kept as configuration state; the other
+        // arguments are handled during execution based on the function being called
         
         if let Some(threshold) = matches.get_one::<u32>("complexity-threshold") {
             log::debug!("Metrics plugin configured with complexity threshold: {}", threshold);
@@ -670,6 +1250,28 @@ impl PluginClapParser for MetricsPlugin {
             log::debug!("Metrics plugin configured for detailed analysis");
         }
         
+        self.include_generated = matches.get_flag("include-generated");
+        if self.include_generated {
+            log::debug!("Metrics plugin configured to include generated files");
+        }
+        
+        let weight = |name: &str| -> PluginResult<f64> {
+            let value = matches.get_one::<f64>(name).copied().unwrap_or_default();
+            if !value.is_finite() || value < 0.0 {
+                return Err(PluginError::invalid_argument(format!("--{}", name), "weights must be zero or more".to_string()));
+            }
+            Ok(value)
+        };
+        self.hotspot_config.frequency_weight = weight("change-weight")?;
+        self.hotspot_config.complexity_weight = weight("complexity-weight")?;
+        self.hotspot_config.author_weight = weight("author-weight")?;
+        if self.hotspot_config.frequency_weight + self.hotspot_config.complexity_weight + self.hotspot_config.author_weight == 0.0 {
+            return Err(PluginError::invalid_argument("--change-weight".to_string(), "at least one hotspot weight must be above zero".to_string()));
+        }
+        if let Some(top) = matches.get_one::<usize>("top") {
+            self.hotspot_config.max_hotspots = *top;
+        }
+        
         
         Ok(())
     }
@@ -721,4 +1323,108 @@ mod tests {
         assert!(processor_names.contains(&"format_detection"));
         assert!(processor_names.contains(&"duplication_detector"));
     }
+
+    #[tokio::test]
+    async fn test_generated_files_excluded_from_content_metrics() {
+        let mut plugin = MetricsPlugin::new();
+        {
+            let mut scan_data = plugin.scan_data.write().await;
+            let data = scan_data.entry("scan".to_string()).or_insert_with(MetricsScanData::new);
+            plugin.record_file(data, "src/lib.rs", "", false);
+            plugin.record_file(data, "src/parser_tables.rs", "", true);
+        }
+
+        let export = plugin.create_data_export("scan").await.unwrap();
+        assert_eq!(export.schema.metadata.get("generated_files_excluded").map(String::as_str), Some("1"));
+
+        plugin.include_generated = true;
+        let mut scan_data = plugin.scan_data.write().await;
+        plugin.record_file(scan_data.get_mut("scan").unwrap(), "dist/app.min.js", "", true);
+
+        assert_eq!(scan_data["scan"].analysed_files, 2);
+        assert_eq!(scan_data["scan"].generated_files_excluded, 1);
+    }
+
+    #[tokio::test]
+    async fn test_hotspots_export_is_ranked() {
+        let plugin = MetricsPlugin::new();
+        let branchy = "fn f(x: u32) {\n    if x > 1 && x < 9 {\n        while x > 0 {}\n    }\n}\n";
+        {
+            let mut scan_data = plugin.scan_data.write().await;
+            let data = scan_data.entry("scan".to_string()).or_insert_with(MetricsScanData::new);
+            plugin.record_file(data, "src/busy.rs", branchy, false);
+            plugin.record_file(data, "src/quiet.rs", "fn g() {}\n", false);
+            for (i, author) in ["alice", "bob", "carol"].iter().enumerate() {
+                MetricsPlugin::record_commit(data, &format!("c{}", i), author, i as i64, ["src/busy.rs"].into_iter());
+            }
+            MetricsPlugin::record_commit(data, "c9", "alice", 9, ["src/quiet.rs", "README.md"].into_iter());
+        }
+
+        let export = plugin.create_hotspots_export("scan").await.unwrap();
+        assert_eq!(export.title, "Hotspots");
+        assert_eq!(export.schema.metadata.get("author_weight").map(String::as_str), Some("0.2"));
+        let DataPayload::Rows(rows) = &export.data else { panic!("expected rows") };
+        // Files without current content are not ranked
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].values[0], Value::Integer(1));
+        assert_eq!(rows[0].values[1], Value::String("src/busy.rs".to_string()));
+        assert_eq!(rows[0].values[8], Value::Integer(3));
+        assert_eq!(rows[1].values[1], Value::String("src/quiet.rs".to_string()));
+
+        assert!(plugin.create_hotspots_export("missing").await.is_none());
+        
+        // Hotspots above low risk are raised as findings
+        let risky = rows.iter().filter(|row| row.values[3] != Value::String("low".to_string())).count();
+        let findings = plugin.create_findings_export("scan").await;
+        let hotspot_findings = findings.as_ref().map_or(0, |export| match &export.data {
+            DataPayload::Rows(rows) => rows.iter().filter(|row| row.values[3] == Value::String("hotspot".to_string())).count(),
+            _ => 0,
+        });
+        assert_eq!(hotspot_findings, risky);
+        assert!(findings.is_none_or(|export| export.is_findings()));
+    }
+
+    #[tokio::test]
+    async fn test_commit_size_exports() {
+        let plugin = MetricsPlugin::new();
+        let mut scan_data = plugin.scan_data.write().await;
+        let data = scan_data.entry("scan".to_string()).or_insert_with(MetricsScanData::new);
+        for (i, lines) in [10, 12, 15, 20, 30, 40, 50, 60, 80, 5000].iter().enumerate() {
+            data.commit_sizes.push(CommitSize {
+                hash: format!("{:040x}", i),
+                author: "alice".to_string(),
+                timestamp: 1_700_000_000 + i as i64 * 86_400,
+                author_timestamp: 1_700_000_000 + i as i64 * 86_400,
+                lines_added: *lines,
+                lines_removed: 0,
+                files: 2,
+            });
+        }
+        drop(scan_data);
+
+        let sizes = plugin.create_commit_sizes_export("scan").await.unwrap();
+        assert_eq!(sizes.title, "Commit Sizes");
+        assert_eq!(sizes.schema.metadata.get("p50").map(String::as_str), Some("30"));
+        let DataPayload::Rows(rows) = &sizes.data else { panic!("expected rows") };
+        assert_eq!(rows[2].values, vec![
+            Value::String("11-50".to_string()), Value::Integer(6), Value::Float(60.0), Value::Integer(167),
+        ]);
+
+        let anomalies = plugin.create_commit_anomalies_export("scan").await.unwrap();
+        let DataPayload::Rows(rows) = &anomalies.data else { panic!("expected rows") };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values[0], Value::String("giant commit".to_string()));
+        assert_eq!(rows[0].values[1], Value::String("000000000000".to_string()));
+        assert_eq!(rows[0].values[4], Value::Integer(5000));
+
+        // Percentiles join the metrics table
+        let metrics = plugin.create_data_export("scan").await.unwrap();
+        let DataPayload::Rows(rows) = &metrics.data else { panic!("expected rows") };
+        assert!(rows.iter().any(|row| row.values[0] == Value::String("commit_size_p90".to_string())
+            && row.values[1] == Value::String("80".to_string())
+            && row.values[2] == Value::String("Commit Size".to_string())));
+
+        assert!(plugin.create_commit_sizes_export("missing").await.is_none());
+    }
+
 }
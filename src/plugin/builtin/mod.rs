@@ -3,20 +3,34 @@
 //! Reference implementations of common plugins for git analytics.
 
 pub mod commits;
+pub mod contributors;
 pub mod metrics;
+pub mod loc;
+pub mod blobs;
+pub mod conventions;
+pub mod graph;
+pub mod staleness;
 pub mod export;
+pub mod notify;
 pub mod debug;
 pub mod utils;
 
 // Re-export built-in plugins
 pub use commits::CommitsPlugin;
+pub use contributors::ContributorsPlugin;
 pub use metrics::MetricsPlugin;
+pub use loc::LocPlugin;
+pub use blobs::BlobsPlugin;
+pub use conventions::ConventionsPlugin;
+pub use graph::GraphPlugin;
+pub use staleness::StalenessPlugin;
 pub use export::ExportPlugin;
+pub use notify::NotifyPlugin;
 pub use debug::DebugPlugin;
 
 /// Get all built-in plugins as descriptors for registration
 pub(super) fn get_builtin_plugins() -> Vec<&'static str> {
-    vec!["debug", "commits", "metrics", "export"]
+    vec!["debug", "commits", "contributors", "metrics", "loc", "blobs", "conventions", "graph", "staleness", "export", "notify"]
 }
 
 /// Get the advertised functions for a builtin plugin (INTERNAL USE ONLY)
@@ -42,10 +56,84 @@ pub(super) fn get_builtin_plugin_functions(name: &str) -> Vec<crate::plugin::tra
             },
             PluginFunction {
                 name: "authors".to_string(),
-                aliases: vec!["contributors".to_string(), "committers".to_string()],
+                aliases: vec!["committers".to_string()],
                 description: "Analyze author contributions and statistics".to_string(),
                 is_default: false,
             },
+            PluginFunction {
+                name: "anomalies".to_string(),
+                aliases: vec!["anomaly".to_string()],
+                description: "Detect unusual periods of repository activity".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "working-set".to_string(),
+                aliases: vec!["wip".to_string()],
+                description: "Distinct files changed within a trailing window over time".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "timeline".to_string(),
+                aliases: vec!["time-series".to_string()],
+                description: "Commits, authors and line changes per week, month or author".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "permissions".to_string(),
+                aliases: vec!["modes".to_string()],
+                description: "Report new executables and file mode changes".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "pairing".to_string(),
+                aliases: vec!["collaboration".to_string()],
+                description: "Matrix of which authors modify lines last touched by others".to_string(),
+                is_default: false,
+            },
+        ],
+        "contributors" => vec![
+            PluginFunction {
+                name: "contributors".to_string(),
+                aliases: vec!["ownership".to_string(), "owners".to_string()],
+                description: "Per-directory ownership percentages by author".to_string(),
+                is_default: true,
+            },
+            PluginFunction {
+                name: "bus-factor".to_string(),
+                aliases: vec!["busfactor".to_string(), "bus".to_string()],
+                description: "Fewest authors covering most changes, per directory".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "trends".to_string(),
+                aliases: vec!["activity".to_string()],
+                description: "Active and new contributors over time".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "cohorts".to_string(),
+                aliases: vec!["retention".to_string()],
+                description: "Retention of contributors by quarter of first commit".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "freshness".to_string(),
+                aliases: vec!["recency".to_string(), "stale".to_string()],
+                description: "Last change, recent changes and stale code per directory".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "first-response".to_string(),
+                aliases: vec!["response".to_string()],
+                description: "Days until a second author changes new files, per creator and directory".to_string(),
+                is_default: false,
+            },
+            PluginFunction {
+                name: "codeowners".to_string(),
+                aliases: vec!["code-owners".to_string()],
+                description: "Paths whose committers diverge from their CODEOWNERS owners".to_string(),
+                is_default: false,
+            },
         ],
         "metrics" => vec![
             PluginFunction {
@@ -67,6 +155,52 @@ pub(super) fn get_builtin_plugin_functions(name: &str) -> Vec<crate::plugin::tra
                 is_default: false,
             },
         ],
+        "loc" => vec![
+            PluginFunction {
+                name: "loc".to_string(),
+                aliases: vec!["sloc".to_string()],
+                description: "Code, comment and blank lines per language in the current tree".to_string(),
+                is_default: true,
+            },
+        ],
+        "blobs" => vec![
+            PluginFunction {
+                name: "blobs".to_string(),
+                aliases: vec!["large-files".to_string(), "binaries".to_string()],
+                description: "Binary and large files ranked by history size, with LFS candidates".to_string(),
+                is_default: true,
+            },
+        ],
+        "conventions" => vec![
+            PluginFunction {
+                name: "conventions".to_string(),
+                aliases: vec!["conventional".to_string(), "commit-types".to_string()],
+                description: "Conventional commit types, scopes, breaking changes and compliance per author".to_string(),
+                is_default: true,
+            },
+            PluginFunction {
+                name: "languages".to_string(),
+                aliases: vec!["language".to_string(), "message-languages".to_string()],
+                description: "Natural language of commit messages per author and over time".to_string(),
+                is_default: false,
+            },
+        ],
+        "graph" => vec![
+            PluginFunction {
+                name: "graph".to_string(),
+                aliases: vec!["merges".to_string(), "branches".to_string()],
+                description: "Merge density, branch lifetimes and revert rate, overall and over time".to_string(),
+                is_default: true,
+            },
+        ],
+        "staleness" => vec![
+            PluginFunction {
+                name: "staleness".to_string(),
+                aliases: vec!["dead-files".to_string()],
+                description: "Stale files, files abandoned by their committers and orphaned tests".to_string(),
+                is_default: true,
+            },
+        ],
         "export" => vec![
             PluginFunction {
                 name: "export".to_string(),
@@ -75,10 +209,36 @@ pub(super) fn get_builtin_plugin_functions(name: &str) -> Vec<crate::plugin::tra
                 is_default: true,
             },
         ],
+        "notify" => vec![
+            PluginFunction {
+                name: "notify".to_string(),
+                aliases: vec!["webhook".to_string(), "summary".to_string()],
+                description: "Send a summary of the exported results to a webhook or file".to_string(),
+                is_default: true,
+            },
+        ],
         _ => vec![],
     }
 }
 
+/// Check whether a builtin plugin function needs line attribution from the scanner (INTERNAL USE ONLY)
+/// Line attribution runs `git blame` for each changed file, so it is only enabled on request
+pub(crate) fn builtin_function_requires_line_attribution(plugin: &str, function: &str) -> bool {
+    matches!((plugin, function), ("commits", "pairing" | "collaboration"))
+}
+
+/// Get the argument definitions of every built-in plugin, by plugin name (INTERNAL USE ONLY)
+pub(crate) fn builtin_plugin_commands() -> Vec<(&'static str, clap::Command)> {
+    let settings = crate::plugin::PluginSettings::default();
+    let notification_manager = std::sync::Arc::new(crate::notifications::AsyncNotificationManager::new());
+    get_builtin_plugins().into_iter()
+        .filter_map(|name| {
+            let plugin = create_builtin_plugin_with_dependencies(name, &settings, notification_manager.clone())?;
+            Some((name, plugin.build_clap_command()?))
+        })
+        .collect()
+}
+
 // Removed dead code functions: create_builtin_plugin, create_builtin_plugin_with_settings
 
 /// Create a built-in plugin by name with all required dependencies (REQUIRED)
@@ -91,8 +251,15 @@ pub(super) fn create_builtin_plugin_with_dependencies(
     match name {
         "debug" => Some(Box::new(DebugPlugin::with_dependencies(settings.clone(), notification_manager))),
         "commits" => Some(Box::new(CommitsPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "contributors" => Some(Box::new(ContributorsPlugin::with_dependencies(settings.clone(), notification_manager))),
         "metrics" => Some(Box::new(MetricsPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "loc" => Some(Box::new(LocPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "blobs" => Some(Box::new(BlobsPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "conventions" => Some(Box::new(ConventionsPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "graph" => Some(Box::new(GraphPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "staleness" => Some(Box::new(StalenessPlugin::with_dependencies(settings.clone(), notification_manager))),
         "export" => Some(Box::new(ExportPlugin::with_dependencies(settings.clone(), notification_manager))),
+        "notify" => Some(Box::new(NotifyPlugin::with_dependencies(settings.clone(), notification_manager))),
         _ => None,
     }
 }
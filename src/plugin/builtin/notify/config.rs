@@ -0,0 +1,127 @@
+//! Notification configuration: where summaries are sent and how delivery is retried
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Payload shape expected by a webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// Slack incoming webhook (`{"text": ...}`)
+    Slack,
+    /// Microsoft Teams incoming webhook (MessageCard)
+    Teams,
+    /// Any HTTP endpoint: the summary and the exported tables as JSON
+    Generic,
+}
+
+impl WebhookFormat {
+    /// Accepted names of the formats, for argument parsing
+    pub const NAMES: [&'static str; 3] = ["slack", "teams", "generic"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "slack" => Some(Self::Slack),
+            "teams" => Some(Self::Teams),
+            "generic" | "http" | "json" => Some(Self::Generic),
+            _ => None,
+        }
+    }
+
+    /// Format implied by a webhook URL's host, generic unless it is Slack's or Teams'
+    pub fn detect(url: &str) -> Self {
+        let host = url.split("://").nth(1).unwrap_or(url)
+            .split(['/', '?', '#']).next().unwrap_or_default()
+            .to_lowercase();
+        if host == "hooks.slack.com" {
+            Self::Slack
+        } else if host.ends_with(".webhook.office.com") || host == "outlook.office.com" {
+            Self::Teams
+        } else {
+            Self::Generic
+        }
+    }
+}
+
+/// Attempts and waits for webhook delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts after the first one fails
+    pub retries: u32,
+    /// Wait before the first retry, doubled for each one after
+    pub initial_delay: Duration,
+    /// Longest wait between attempts
+    pub max_delay: Duration,
+}
+
+#[cfg(feature = "webhooks")]
+impl RetryPolicy {
+    /// Wait before retry `attempt` (counting from 1)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Webhook the summary is posted to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookTarget {
+    pub url: String,
+    pub format: WebhookFormat,
+}
+
+/// Notification plugin configuration
+#[derive(Debug, Clone, Default)]
+pub struct NotifyConfig {
+    pub webhook: Option<WebhookTarget>,
+    /// File the summary is written to
+    pub summary_file: Option<PathBuf>,
+    /// Title of the summary, defaulting to the repository and ref scanned
+    pub title: Option<String>,
+    pub retry: RetryPolicy,
+    /// Time allowed for each webhook request
+    pub timeout: Duration,
+}
+
+impl NotifyConfig {
+    /// Whether the summary goes anywhere
+    pub fn has_targets(&self) -> bool {
+        self.webhook.is_some() || self.summary_file.is_some()
+    }
+}
+
+/// Well-known location of the summary when no other target is given: `gstats/summary.md` in the platform data directory
+pub fn default_summary_file() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("gstats").join("summary.md"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_format_from_url() {
+        assert_eq!(WebhookFormat::detect("https://hooks.slack.com/services/T000/B000/XXX"), WebhookFormat::Slack);
+        assert_eq!(WebhookFormat::detect("https://example.webhook.office.com/webhookb2/abc"), WebhookFormat::Teams);
+        assert_eq!(WebhookFormat::detect("https://ci.example.com/hooks/slack.com"), WebhookFormat::Generic);
+        assert_eq!(WebhookFormat::parse("Teams"), Some(WebhookFormat::Teams));
+        assert_eq!(WebhookFormat::parse("email"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "webhooks")]
+    fn test_retry_delays_double_up_to_the_limit() {
+        let policy = RetryPolicy { retries: 6, initial_delay: Duration::from_secs(2), max_delay: Duration::from_secs(20) };
+        let delays: Vec<u64> = (1..=policy.retries).map(|attempt| policy.delay(attempt).as_secs()).collect();
+        assert_eq!(delays, [2, 4, 8, 16, 20, 20]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Delivery of summaries to webhooks and files
+
+use super::config::{RetryPolicy, WebhookFormat, WebhookTarget};
+use super::summary::Summary;
+use crate::plugin::{PluginError, PluginResult};
+use serde_json::json;
+use std::path::Path;
+use std::time::Duration;
+
+/// Request body for a webhook of the given format
+pub fn webhook_payload(format: WebhookFormat, summary: &Summary, body: &str) -> serde_json::Value {
+    match format {
+        WebhookFormat::Slack => json!({ "text": body }),
+        WebhookFormat::Teams => json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": summary.title,
+            "text": body,
+        }),
+        WebhookFormat::Generic => json!({
+            "title": summary.title,
+            "scan_id": summary.scan_id,
+            "text": body,
+            "exports": summary.exports,
+        }),
+    }
+}
+
+/// Whether a request that got this HTTP status may succeed if sent again
+#[cfg(feature = "webhooks")]
+pub fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 425 | 429) || (500..600).contains(&status)
+}
+
+/// Wait before the next attempt: the server's `Retry-After` seconds when given and within the policy's limit
+#[cfg(feature = "webhooks")]
+fn retry_delay(policy: &RetryPolicy, attempt: u32, retry_after: Option<&str>) -> Duration {
+    retry_after
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(|seconds| Duration::from_secs(seconds).min(policy.max_delay))
+        .unwrap_or_else(|| policy.delay(attempt))
+}
+
+/// Post a payload to a webhook, retrying connection failures and retryable statuses with backoff
+#[cfg(feature = "webhooks")]
+pub async fn post_webhook(
+    target: &WebhookTarget,
+    payload: &serde_json::Value,
+    policy: &RetryPolicy,
+    timeout: Duration,
+) -> PluginResult<()> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .user_agent(concat!("gstats/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| PluginError::configuration_error(format!("Failed to create webhook client: {}", e)))?;
+
+    let body = payload.to_string();
+    let mut attempt = 0;
+    loop {
+        let request = client.post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        let (error, retry_after) = match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                log::info!("Posted summary to webhook (status {})", response.status());
+                return Ok(());
+            }
+            Ok(response) => {
+                let status = response.status();
+                let retry_after = response.headers().get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let error = format!("webhook returned {}", status);
+                if !is_retryable_status(status.as_u16()) {
+                    return Err(PluginError::execution_failed(error));
+                }
+                (error, retry_after)
+            }
+            Err(e) => (format!("webhook request failed: {}", e), None),
+        };
+
+        attempt += 1;
+        if attempt > policy.retries {
+            return Err(PluginError::execution_failed(format!("{} (gave up after {} attempts)", error, attempt)));
+        }
+        let delay = retry_delay(policy, attempt, retry_after.as_deref());
+        log::warn!("{}; retrying in {:.1}s ({} of {})", error, delay.as_secs_f64(), attempt, policy.retries);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Post a payload to a webhook (unavailable without the `webhooks` feature)
+#[cfg(not(feature = "webhooks"))]
+pub async fn post_webhook(
+    _target: &WebhookTarget,
+    _payload: &serde_json::Value,
+    _policy: &RetryPolicy,
+    _timeout: Duration,
+) -> PluginResult<()> {
+    Err(PluginError::configuration_error(
+        "Webhook notifications are not available: gstats was built without the `webhooks` feature"
+    ))
+}
+
+/// Write the summary to a file, creating its directory if needed
+pub fn write_summary(path: &Path, body: &str) -> PluginResult<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| PluginError::io_error(format!("Failed to create {}: {}", dir.display(), e)))?;
+    }
+    std::fs::write(path, body)
+        .map_err(|e| PluginError::io_error(format!("Failed to write summary to {}: {}", path.display(), e)))?;
+    log::info!("Wrote summary to {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> Summary {
+        Summary::new("gstats: main".to_string(), "scan-1".to_string(), &[])
+    }
+
+    #[test]
+    fn test_payload_shapes() {
+        let summary = summary();
+        assert_eq!(webhook_payload(WebhookFormat::Slack, &summary, "body"), json!({ "text": "body" }));
+        let teams = webhook_payload(WebhookFormat::Teams, &summary, "body");
+        assert_eq!(teams["@type"], "MessageCard");
+        assert_eq!(teams["summary"], "gstats: main");
+        let generic = webhook_payload(WebhookFormat::Generic, &summary, "body");
+        assert_eq!(generic["scan_id"], "scan-1");
+        assert_eq!(generic["exports"], json!([]));
+    }
+
+    #[cfg(feature = "webhooks")]
+    #[test]
+    fn test_retryable_statuses_and_retry_after() {
+        assert!(is_retryable_status(429));
+        assert!(is_retryable_status(503));
+        assert!(!is_retryable_status(400));
+        assert!(!is_retryable_status(404));
+
+        let policy = RetryPolicy::default();
+        assert_eq!(retry_delay(&policy, 2, None), Duration::from_secs(2));
+        assert_eq!(retry_delay(&policy, 1, Some("5")), Duration::from_secs(5));
+        assert_eq!(retry_delay(&policy, 1, Some("600")), policy.max_delay);
+        assert_eq!(retry_delay(&policy, 3, Some("Wed, 21 Oct 2015 07:28:00 GMT")), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_summary_file_directory_is_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports").join("summary.md");
+        write_summary(&path, "**gstats**\n").unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "**gstats**\n");
+    }
+}
@@ -0,0 +1,489 @@
+//! Notification Plugin
+//!
+//! Built-in output plugin that sends a summary of what a run exported to a
+//! webhook (Slack, Teams or any HTTP endpoint) and/or writes it to a file.
+//! It collects the tables published as `DataReady` events during the scan and
+//! delivers the summary when the run asks output plugins to export, once the
+//! scan and the export plugin are done. Nothing is sent unless a target is
+//! configured, usually in the `[plugin.notify]` section of the configuration.
+
+pub mod config;
+pub mod delivery;
+pub mod summary;
+
+use crate::plugin::{
+    Plugin, PluginInfo, PluginContext, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, PluginClapParser}
+};
+use crate::plugin::data_export::PluginDataExport;
+use crate::plugin::builtin::export::template_engine::{self, TemplateEngine};
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher, RateLimit, Subscriber};
+use crate::notifications::{NotificationResult, AsyncNotificationManager};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+pub use config::{NotifyConfig, WebhookFormat, WebhookTarget};
+pub use summary::Summary;
+
+/// Summary notification plugin
+#[derive(Clone)]
+pub struct NotifyPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+    config: Arc<RwLock<NotifyConfig>>,
+
+    /// Template for the message body, when one is configured
+    template_engine: Arc<RwLock<Option<TemplateEngine>>>,
+
+    /// Tables published since the last summary was delivered, and the scan they came from
+    exports: Arc<RwLock<Vec<Arc<PluginDataExport>>>>,
+    scan_id: Arc<RwLock<String>>,
+
+    /// Title used unless one is configured, naming the ref scanned
+    default_title: String,
+
+    /// Notification manager for publishing events - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl NotifyPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "notify".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Sends a summary of the exported results to a webhook or writes it to a file".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Output,
+        )
+        .with_capability(
+            "webhook_notification".to_string(),
+            "Posts the summary to Slack, Teams or generic HTTP webhooks, retrying with backoff".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "summary_file".to_string(),
+            "Writes the summary to a file, by default in the platform data directory".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "template_notification".to_string(),
+            "Message body from a Tera template".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_active_by_default(true);
+
+        Self {
+            command_name: "notify".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            config: Arc::new(RwLock::new(NotifyConfig::default())),
+            template_engine: Arc::new(RwLock::new(None)),
+            exports: Arc::new(RwLock::new(Vec::new())),
+            scan_id: Arc::new(RwLock::new(String::new())),
+            default_title: "gstats summary".to_string(),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new notify plugin with all required dependencies (REQUIRED)
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: Arc<AsyncNotificationManager<PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Send the summary of the tables collected so far to the configured targets
+    ///
+    /// Returns the targets the summary was delivered to. The summary file is
+    /// written before the webhook is tried, so it is kept if posting fails.
+    pub async fn deliver(&self) -> PluginResult<Vec<String>> {
+        let config = self.config.read().await.clone();
+        let exports = std::mem::take(&mut *self.exports.write().await);
+        if !config.has_targets() {
+            return Ok(Vec::new());
+        }
+        if exports.is_empty() {
+            log::info!("NotifyPlugin: Nothing was exported; no summary sent");
+            return Ok(Vec::new());
+        }
+
+        let title = config.title.clone().unwrap_or_else(|| self.default_title.clone());
+        let summary = Summary::new(title, self.scan_id.read().await.clone(), &exports);
+        let body = match &*self.template_engine.read().await {
+            Some(engine) => engine.render(&summary.context())?,
+            None => summary.text(),
+        };
+
+        let mut delivered = Vec::new();
+        if let Some(path) = &config.summary_file {
+            delivery::write_summary(path, &body)?;
+            delivered.push(path.display().to_string());
+        }
+        if let Some(webhook) = &config.webhook {
+            let payload = delivery::webhook_payload(webhook.format, &summary, &body);
+            delivery::post_webhook(webhook, &payload, &config.retry, config.timeout).await?;
+            delivered.push(format!("{:?} webhook", webhook.format).to_lowercase());
+        }
+        Ok(delivered)
+    }
+
+    /// Publish notification completion event
+    async fn publish_completion_event(&self, delivered: usize) -> PluginResult<()> {
+        let event = PluginEvent::PluginCompleted {
+            plugin_id: "notify".to_string(),
+            processing_time: Duration::from_secs(0),
+            items_processed: 1, // One summary, however many targets it went to
+            results_generated: delivered,
+            completed_at: std::time::SystemTime::now(),
+        };
+        self.notification_manager.publish(event).await
+            .map_err(|e| PluginError::execution_failed(format!("Failed to publish notify completion event: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Plugin for NotifyPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
+        if self.initialized {
+            return Ok(());
+        }
+
+        if let Some(scanned_ref) = &context.query_params.scanned_ref {
+            let commit = &scanned_ref.commit_id[..scanned_ref.commit_id.len().min(12)];
+            self.default_title = format!("gstats summary: {} ({})", scanned_ref.branch_name, commit);
+        }
+
+        if let Some(ref manager) = context.notification_manager {
+            manager.subscribe(Arc::new(self.clone())).await
+                .map_err(|e| PluginError::initialization_failed(format!("Failed to subscribe to notifications: {}", e)))?;
+            log::info!("NotifyPlugin subscribed to PluginEvent notifications");
+        } else {
+            log::debug!("NotifyPlugin: No notification manager available in context");
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        let metadata = crate::plugin::context::ExecutionMetadata {
+            duration_us: 0,
+            memory_used: 0,
+            entries_processed: 0,
+            plugin_version: "1.0.0".to_string(),
+            extra: std::collections::HashMap::new(),
+        };
+
+        match request {
+            PluginRequest::Export => {
+                let delivered = self.deliver().await?;
+                if !delivered.is_empty() {
+                    self.publish_completion_event(delivered.len()).await.unwrap_or_else(|e| {
+                        log::warn!("NotifyPlugin: {}", e);
+                    });
+                }
+                Ok(PluginResponse::success(
+                    "notify_delivery".to_string(),
+                    serde_json::json!({ "delivered": delivered }),
+                    metadata
+                ))
+            }
+            _ => {
+                Ok(PluginResponse::success(
+                    "notify_info".to_string(),
+                    serde_json::json!({"message": "Notify plugin delivers a summary after export"}),
+                    metadata
+                ))
+            }
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        self.exports.write().await.clear();
+        self.initialized = false;
+        Ok(())
+    }
+
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "notify".to_string(),
+                aliases: vec!["webhook".to_string(), "summary".to_string()],
+                description: "Send a summary of the exported results to a webhook or file".to_string(),
+                is_default: true,
+            }
+        ]
+    }
+
+    fn default_function(&self) -> Option<&str> {
+        Some("notify")
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for NotifyPlugin {
+    async fn publish(&self, event: PluginEvent) -> NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+#[async_trait]
+impl Subscriber<PluginEvent> for NotifyPlugin {
+    fn subscriber_id(&self) -> &str {
+        "notify-plugin"
+    }
+
+    async fn handle_event(&self, event: PluginEvent) -> NotificationResult<()> {
+        if let PluginEvent::DataReady { plugin_id, scan_id, export } = event {
+            log::debug!("NotifyPlugin: Collected '{}' from plugin '{}'", export.title, plugin_id);
+            *self.scan_id.write().await = scan_id;
+            self.exports.write().await.push(export);
+        }
+        Ok(())
+    }
+
+    // Every table belongs in the summary, however quickly plugins publish them
+    fn rate_limit(&self) -> Option<RateLimit> {
+        None
+    }
+}
+
+#[async_trait]
+impl PluginClapParser for NotifyPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Sends a summary of the exported results to a webhook or file"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        use clap::Arg;
+
+        command
+            .override_usage("notify [OPTIONS]")
+            .help_template("Usage: {usage}\n\nSends a summary of the exported results\n\nOptions:\n{options}\n{after-help}")
+            .after_help("Options are usually set in the [plugin.notify] section of the configuration, so every run sends its summary.\n\
+                Webhook delivery needs gstats built with the `webhooks` feature.")
+            .arg(Arg::new("webhook")
+                .long("webhook")
+                .value_name("URL")
+                .help("Post the summary to this webhook URL"))
+            .arg(Arg::new("webhook-format")
+                .long("webhook-format")
+                .value_name("FORMAT")
+                .help("Webhook payload: slack, teams or generic [default: detected from the URL]")
+                .value_parser(WebhookFormat::NAMES)
+                .hide_possible_values(true))
+            .arg(Arg::new("summary-file")
+                .long("summary-file")
+                .value_name("FILE")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Write the summary to a file [default: gstats/summary.md in the platform data directory]")
+                .value_hint(clap::ValueHint::FilePath))
+            .arg(Arg::new("title")
+                .long("title")
+                .value_name("TEXT")
+                .help("Title of the summary [default: the branch and commit scanned]"))
+            .arg(Arg::new("template")
+                .short('t')
+                .long("template")
+                .value_name("FILE")
+                .help("Tera template for the message body, given title, scan_id, exports and the default summary")
+                .value_hint(clap::ValueHint::FilePath))
+            .arg(Arg::new("template-var")
+                .long("template-var")
+                .value_name("KEY=VALUE")
+                .action(clap::ArgAction::Append)
+                .help("Variable available to the template; may be repeated"))
+            .arg(Arg::new("retries")
+                .long("retries")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32))
+                .help("Webhook attempts after the first one fails [default: 3]"))
+            .arg(Arg::new("retry-delay")
+                .long("retry-delay")
+                .value_name("SECONDS")
+                .value_parser(clap::value_parser!(u64))
+                .help("Wait before the first retry, doubled for each one after [default: 1]"))
+            .arg(Arg::new("timeout")
+                .long("timeout")
+                .value_name("SECONDS")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Time allowed for each webhook request [default: 10]"))
+    }
+
+    async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
+        let mut config = NotifyConfig {
+            timeout: Duration::from_secs(matches.get_one::<u64>("timeout").copied().unwrap_or(10)),
+            ..NotifyConfig::default()
+        };
+
+        if let Some(url) = matches.get_one::<String>("webhook") {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(PluginError::invalid_argument("--webhook", &format!("expected an http(s) URL, got '{}'", url)));
+            }
+            let format = match matches.get_one::<String>("webhook-format") {
+                Some(name) => WebhookFormat::parse(name)
+                    .ok_or_else(|| PluginError::invalid_argument("--webhook-format", &format!("Unknown format: {}", name)))?,
+                None => WebhookFormat::detect(url),
+            };
+            config.webhook = Some(WebhookTarget { url: url.clone(), format });
+        }
+
+        config.summary_file = match matches.get_one::<String>("summary-file").map(String::as_str) {
+            Some("") => Some(config::default_summary_file().ok_or_else(|| PluginError::invalid_argument(
+                "--summary-file", "no platform data directory; give the file to write"
+            ))?),
+            Some(path) => Some(PathBuf::from(path)),
+            None => None,
+        };
+
+        config.title = matches.get_one::<String>("title").cloned();
+        if let Some(retries) = matches.get_one::<u32>("retries") {
+            config.retry.retries = *retries;
+        }
+        if let Some(delay) = matches.get_one::<u64>("retry-delay") {
+            config.retry.initial_delay = Duration::from_secs(*delay);
+        }
+
+        // Load the template now so errors are reported before scanning
+        let engine = match matches.get_one::<String>("template") {
+            Some(template) => {
+                let mut engine = TemplateEngine::new();
+                engine.load(Some(PathBuf::from(template).as_path()), None)?;
+                for assignment in matches.get_many::<String>("template-var").into_iter().flatten() {
+                    let (key, value) = template_engine::parse_template_var(assignment)?;
+                    engine.add_template_var(key, value);
+                }
+                Some(engine)
+            }
+            None => None,
+        };
+        *self.template_engine.write().await = engine;
+
+        log::debug!("Notify plugin configured: webhook={:?}, summary file={:?}", config.webhook, config.summary_file);
+        *self.config.write().await = config;
+        Ok(())
+    }
+}
+
+impl Default for NotifyPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, ColumnType, DataPayload, DataSchema, Row, Value};
+
+    fn export(title: &str) -> Arc<PluginDataExport> {
+        Arc::new(PluginDataExport::builder()
+            .plugin_id("commits")
+            .title(title)
+            .schema(DataSchema::new(vec![ColumnDef::new("commits", ColumnType::Integer)]))
+            .data(DataPayload::Rows(Arc::new(vec![Row::new(vec![Value::Integer(42)])])))
+            .build()
+            .unwrap())
+    }
+
+    async fn publish(plugin: &NotifyPlugin, title: &str) {
+        let event = PluginEvent::DataReady { plugin_id: "commits".to_string(), scan_id: "scan-1".to_string(), export: export(title) };
+        plugin.handle_event(event).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_summary_file_is_written_once_per_round() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        let mut plugin = NotifyPlugin::new();
+        plugin.parse_plugin_arguments(&["--summary-file".to_string(), path.display().to_string(), "--title".to_string(), "Nightly".to_string()]).await.unwrap();
+
+        publish(&plugin, "Commit Totals").await;
+        let response = plugin.execute(PluginRequest::Export).await.unwrap();
+        assert!(response.is_success());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "**Nightly**\n\n- **Commit Totals** (1 row)\n  - commits: 42\n");
+
+        // The collected tables were delivered, so a second request sends nothing
+        std::fs::remove_file(&path).unwrap();
+        assert!(plugin.deliver().await.unwrap().is_empty());
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_template_renders_message_body() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("message.txt");
+        std::fs::write(&template, "{{ team }}: {% for export in exports %}{{ export.title }}={{ export.rows }}{% endfor %}").unwrap();
+        let path = dir.path().join("summary.txt");
+        let mut plugin = NotifyPlugin::new();
+        plugin.parse_plugin_arguments(&[
+            "--summary-file".to_string(), path.display().to_string(),
+            "--template".to_string(), template.display().to_string(),
+            "--template-var".to_string(), "team=Platform".to_string(),
+        ]).await.unwrap();
+
+        publish(&plugin, "Commit Totals").await;
+        assert_eq!(plugin.deliver().await.unwrap(), [path.display().to_string()]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Platform: Commit Totals=1");
+    }
+
+    #[tokio::test]
+    async fn test_nothing_is_sent_without_targets() {
+        let plugin = NotifyPlugin::new();
+        publish(&plugin, "Commit Totals").await;
+        assert!(plugin.deliver().await.unwrap().is_empty());
+
+        let mut plugin = NotifyPlugin::new();
+        let error = plugin.parse_plugin_arguments(&["--webhook".to_string(), "hooks.slack.com/services/x".to_string()]).await.unwrap_err();
+        assert!(error.to_string().contains("http(s) URL"));
+        plugin.parse_plugin_arguments(&["--webhook".to_string(), "https://hooks.slack.com/services/x".to_string()]).await.unwrap();
+        assert_eq!(plugin.config.read().await.webhook.as_ref().unwrap().format, WebhookFormat::Slack);
+    }
+}
@@ -0,0 +1,139 @@
+//! Summary of an export round, as default message text and as template context
+
+use crate::plugin::data_export::{DataPayload, PluginDataExport};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Fields listed for a single-row table, such as a plugin's totals
+const MAX_FIELDS: usize = 8;
+
+/// One exported table in the summary
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub plugin_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub rows: usize,
+    /// Column names and values of a single-row table
+    pub fields: Vec<(String, String)>,
+}
+
+impl ExportSummary {
+    pub fn from_export(export: &PluginDataExport) -> Self {
+        let (rows, fields) = match &export.data {
+            DataPayload::Rows(rows) if rows.len() == 1 => {
+                let fields = export.schema.columns.iter()
+                    .zip(&rows[0].values)
+                    .filter(|(column, value)| !column.hidden && !value.is_null())
+                    .take(MAX_FIELDS)
+                    .map(|(column, value)| (column.name.clone(), value.to_string()))
+                    .collect();
+                (1, fields)
+            }
+            DataPayload::Rows(rows) => (rows.len(), Vec::new()),
+            DataPayload::KeyValue(values) => {
+                let mut fields: Vec<(String, String)> = values.iter()
+                    .map(|(key, value)| (key.clone(), value.to_string()))
+                    .collect();
+                fields.sort();
+                fields.truncate(MAX_FIELDS);
+                (values.len(), fields)
+            }
+            DataPayload::Matrix(matrix) => (matrix.row_labels.len(), Vec::new()),
+            _ => (0, Vec::new()),
+        };
+        Self {
+            plugin_id: export.plugin_id.clone(),
+            title: export.title.clone(),
+            description: export.description.clone(),
+            rows,
+            fields,
+        }
+    }
+}
+
+/// Everything exported in one round
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub title: String,
+    pub scan_id: String,
+    pub exports: Vec<ExportSummary>,
+}
+
+impl Summary {
+    pub fn new(title: String, scan_id: String, exports: &[Arc<PluginDataExport>]) -> Self {
+        Self {
+            title,
+            scan_id,
+            exports: exports.iter().map(|export| ExportSummary::from_export(export)).collect(),
+        }
+    }
+
+    /// Default message body: a Markdown line per table, with the fields of single-row tables
+    pub fn text(&self) -> String {
+        let mut text = format!("**{}**\n", self.title);
+        for export in &self.exports {
+            let rows = if export.rows == 1 { "1 row".to_string() } else { format!("{} rows", export.rows) };
+            match &export.description {
+                Some(description) => text.push_str(&format!("\n- **{}**: {} ({})", export.title, description, rows)),
+                None => text.push_str(&format!("\n- **{}** ({})", export.title, rows)),
+            }
+            for (name, value) in &export.fields {
+                text.push_str(&format!("\n  - {}: {}", name, value));
+            }
+        }
+        text.push('\n');
+        text
+    }
+
+    /// Template context: `title`, `scan_id`, `exports` and the default body as `summary`
+    pub fn context(&self) -> serde_json::Value {
+        let mut context = serde_json::to_value(self).unwrap_or_default();
+        if let Some(object) = context.as_object_mut() {
+            object.insert("summary".to_string(), self.text().into());
+        }
+        context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{ColumnDef, ColumnType, DataSchema, Row, Value};
+
+    fn export(plugin_id: &str, title: &str, rows: Vec<Row>) -> Arc<PluginDataExport> {
+        let schema = DataSchema::new(vec![
+            ColumnDef::new("author", ColumnType::String),
+            ColumnDef::new("commits", ColumnType::Integer),
+        ]);
+        Arc::new(PluginDataExport::builder()
+            .plugin_id(plugin_id)
+            .title(title)
+            .schema(schema)
+            .data(DataPayload::Rows(Arc::new(rows)))
+            .build()
+            .unwrap())
+    }
+
+    fn row(author: &str, commits: i64) -> Row {
+        Row::new(vec![Value::String(author.to_string()), Value::Integer(commits)])
+    }
+
+    #[test]
+    fn test_summary_lists_tables_and_single_row_fields() {
+        let exports = vec![
+            export("commits", "Commit Totals", vec![row("all", 42)]),
+            export("contributors", "Top Contributors", vec![row("alice", 30), row("bob", 12)]),
+        ];
+        let summary = Summary::new("gstats: main".to_string(), "scan-1".to_string(), &exports);
+        assert_eq!(
+            summary.text(),
+            "**gstats: main**\n\n- **Commit Totals** (1 row)\n  - author: all\n  - commits: 42\n- **Top Contributors** (2 rows)\n"
+        );
+
+        let context = summary.context();
+        assert_eq!(context["scan_id"], "scan-1");
+        assert_eq!(context["exports"][1]["rows"], 2);
+        assert_eq!(context["summary"], summary.text());
+    }
+}
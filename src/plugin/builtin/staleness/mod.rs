@@ -0,0 +1,993 @@
+//! Staleness Plugin
+//!
+//! Built-in plugin that looks for files in the current tree that may be dead or
+//! unmaintained:
+//!
+//! - stale files, not modified for a number of months (12 by default)
+//! - abandoned files, whose committers have all left: none of them has committed
+//!   anything within the inactive-author window (6 months by default)
+//!
+//! Both windows take a count of months or a duration such as `180d`; months are
+//! 30 days, as elsewhere in the command line.
+//! - orphaned tests, test files whose subject no longer exists
+//!
+//! The current tree is listed from the file content the scanner streams for the
+//! target commit; each file's last modification and committers come from the
+//! commits that changed it. Ages are measured from the run clock, so
+//! `--reproducible` fixes them to the date of the latest scanned commit.
+//!
+//! A test file is recognised by its name: `parser_test.go`, `test_parser.py`,
+//! `parser.test.ts`, `parser_spec.rb` or `ParserTest.java` all test `parser`. It
+//! is orphaned when no other file in the tree has that name, with any extension.
+
+use crate::cli::date_parser::parse_duration_or;
+use crate::plugin::memory::HeapSize;
+use crate::plugin::processors::commit_size::format_date;
+
+use crate::plugin::{
+    Plugin, PluginInfo, ScanContext, PluginRequest, PluginResponse,
+    PluginResult, PluginError, traits::{PluginType, PluginFunction, ConsumerPlugin, MessageConsumer, ConsumerPreferences, PluginClapParser, PluginDataRequirements}
+};
+use crate::plugin::data_export::{
+    PluginDataExport, DataExportType, DataSchema, ColumnDef, ColumnType,
+    DataPayload, Row, Value, ExportHints, ExportFormat
+};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
+use crate::notifications::AsyncNotificationManager;
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher};
+use async_trait::async_trait;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use serde_json::json;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Parse a window option: a count of months, or a duration such as "180d"
+fn parse_window(value: &str) -> Result<chrono::Duration, String> {
+    let window = parse_duration_or(value, "m").map_err(|e| e.to_string())?;
+    if window.num_days() < 1 {
+        return Err("the window must be at least one day".to_string());
+    }
+    Ok(window)
+}
+
+/// Describe a window in months when it is a whole number of them, else in days
+fn describe_window(window: chrono::Duration) -> String {
+    let days = window.num_days();
+    match (days % 30, days / 30) {
+        (0, 1) => "1 month".to_string(),
+        (0, months) if months > 1 => format!("{} months", months),
+        _ if days == 1 => "1 day".to_string(),
+        _ => format!("{} days", days),
+    }
+}
+
+/// Name of the file a test file tests, without its extension, if the test file is named after it
+fn tested_name(path: &str) -> Option<&str> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (stem, _) = file_name.split_once('.')?;
+    if let Some(subject) = stem.strip_prefix("test_") {
+        return Some(subject);
+    }
+    for suffix in ["_test", "_tests", "_spec", "Test", "Tests", "Spec"] {
+        if let Some(subject) = stem.strip_suffix(suffix) {
+            return Some(subject);
+        }
+    }
+    // parser.test.ts and parser.spec.js
+    let (name, rest) = file_name.split_once('.')?;
+    (rest.starts_with("test.") || rest.starts_with("spec.")).then_some(name)
+}
+
+/// Name of a file without its extension
+fn file_stem(path: &str) -> &str {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    file_name.split_once('.').map_or(file_name, |(stem, _)| stem)
+}
+
+/// When a file was last changed and who has changed it
+#[derive(Debug, Default, Clone, PartialEq)]
+struct FileHistory {
+    last_modified: i64,
+    last_author: String,
+    authors: BTreeSet<String>,
+}
+
+impl HeapSize for FileHistory {
+    fn heap_size(&self) -> usize {
+        self.last_author.heap_size() + self.authors.heap_size()
+    }
+}
+
+/// A file not modified since before the stale cutoff
+#[derive(Debug, Clone, PartialEq)]
+struct StaleFile {
+    path: String,
+    /// None when the file was last changed before the scanned history
+    last_modified: Option<i64>,
+    last_author: Option<String>,
+}
+
+/// A file whose committers are all inactive
+#[derive(Debug, Clone, PartialEq)]
+struct AbandonedFile {
+    path: String,
+    authors: Vec<String>,
+    /// When the most recently active of its committers last committed
+    last_active: i64,
+    last_modified: i64,
+}
+
+/// A test file whose subject is not in the tree
+#[derive(Debug, Clone, PartialEq)]
+struct OrphanedTest {
+    path: String,
+    subject: String,
+    last_modified: Option<i64>,
+}
+
+/// Staleness findings over the current tree
+#[derive(Debug, Default, Clone, PartialEq)]
+struct StalenessAnalysis {
+    files: usize,
+    stale: Vec<StaleFile>,
+    abandoned: Vec<AbandonedFile>,
+    orphaned: Vec<OrphanedTest>,
+}
+
+/// Current tree and file histories of the scan in progress
+#[derive(Debug, Default, Clone)]
+struct StalenessTally {
+    /// Files in the current tree
+    tree: BTreeSet<String>,
+    history: HashMap<String, FileHistory>,
+    /// Time of each author's latest commit
+    last_seen: HashMap<String, i64>,
+    /// Time of the earliest scanned commit
+    earliest_commit: Option<i64>,
+}
+
+impl HeapSize for StalenessTally {
+    fn heap_size(&self) -> usize {
+        self.tree.heap_size() + self.history.heap_size() + self.last_seen.heap_size()
+    }
+}
+
+impl StalenessTally {
+    fn record_file(&mut self, path: &str) {
+        self.tree.insert(path.to_string());
+    }
+
+    fn record_commit<'a>(&mut self, author: &str, timestamp: i64, paths: impl Iterator<Item = &'a str>) {
+        let last_seen = self.last_seen.entry(author.to_string()).or_insert(timestamp);
+        *last_seen = (*last_seen).max(timestamp);
+        self.earliest_commit = Some(self.earliest_commit.map_or(timestamp, |earliest| earliest.min(timestamp)));
+        for path in paths {
+            let history = self.history.entry(path.to_string()).or_default();
+            if history.authors.is_empty() || timestamp >= history.last_modified {
+                history.last_modified = timestamp;
+                history.last_author = author.to_string();
+            }
+            history.authors.insert(author.to_string());
+        }
+    }
+
+    /// Files of the tree not modified within `stale_window`, those whose committers have not committed
+    /// within `inactive_window`, and orphaned tests, as of `now`
+    fn analyse(&self, now: i64, stale_window: chrono::Duration, inactive_window: chrono::Duration) -> StalenessAnalysis {
+        let stale_cutoff = now.saturating_sub(stale_window.num_seconds());
+        let inactive_cutoff = now.saturating_sub(inactive_window.num_seconds());
+        // Files untouched by the scanned commits are only known to be stale when the scan reaches back past the cutoff
+        let history_covers_cutoff = self.earliest_commit.is_some_and(|earliest| earliest <= stale_cutoff);
+        let mut analysis = StalenessAnalysis { files: self.tree.len(), ..StalenessAnalysis::default() };
+
+        let subjects: BTreeSet<&str> = self.tree.iter()
+            .filter(|path| tested_name(path).is_none())
+            .map(|path| file_stem(path))
+            .collect();
+
+        for path in &self.tree {
+            let history = self.history.get(path);
+            match history {
+                Some(history) if history.last_modified < stale_cutoff => analysis.stale.push(StaleFile {
+                    path: path.clone(),
+                    last_modified: Some(history.last_modified),
+                    last_author: Some(history.last_author.clone()),
+                }),
+                None if history_covers_cutoff => analysis.stale.push(StaleFile {
+                    path: path.clone(),
+                    last_modified: None,
+                    last_author: None,
+                }),
+                _ => {}
+            }
+
+            if let Some(history) = history {
+                let last_active = history.authors.iter()
+                    .filter_map(|author| self.last_seen.get(author))
+                    .max()
+                    .copied()
+                    .unwrap_or(history.last_modified);
+                if last_active < inactive_cutoff {
+                    analysis.abandoned.push(AbandonedFile {
+                        path: path.clone(),
+                        authors: history.authors.iter().cloned().collect(),
+                        last_active,
+                        last_modified: history.last_modified,
+                    });
+                }
+            }
+
+            if let Some(subject) = tested_name(path).filter(|subject| !subject.is_empty() && !subjects.contains(subject)) {
+                analysis.orphaned.push(OrphanedTest {
+                    path: path.clone(),
+                    subject: subject.to_string(),
+                    last_modified: history.map(|history| history.last_modified),
+                });
+            }
+        }
+
+        // Oldest first; files older than the scanned history before all others
+        analysis.stale.sort_by(|a, b| a.last_modified.cmp(&b.last_modified).then_with(|| a.path.cmp(&b.path)));
+        analysis.abandoned.sort_by(|a, b| a.last_active.cmp(&b.last_active).then_with(|| a.path.cmp(&b.path)));
+        analysis
+    }
+}
+
+/// Staleness plugin
+pub struct StalenessPlugin {
+    /// Command name for clap integration
+    command_name: String,
+
+    /// Plugin settings (color preferences, etc.)
+    settings: crate::plugin::PluginSettings,
+
+    info: PluginInfo,
+    initialized: bool,
+
+    /// Time without a change after which a file is stale
+    stale_window: chrono::Duration,
+
+    /// Time without a commit after which an author has left
+    inactive_window: chrono::Duration,
+
+    /// Current tree and file histories for the scan in progress; reset when a scan starts
+    tally: Arc<RwLock<StalenessTally>>,
+
+    consuming: Arc<RwLock<bool>>,
+    consumer: Arc<RwLock<Option<Box<dyn MessageConsumer>>>>,
+
+    /// Notification publishing - REQUIRED for all plugins
+    notification_manager: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl StalenessPlugin {
+    pub fn new() -> Self {
+        let info = PluginInfo::new(
+            "staleness".to_string(),
+            "1.0.0".to_string(),
+            crate::scanner::version::get_api_version() as u32,
+            "Finds stale files, files abandoned by their committers and orphaned tests".to_string(),
+            "gstats built-in".to_string(),
+            PluginType::Processing,
+        )
+        .with_capability(
+            "stale_files".to_string(),
+            "Lists files not modified for a number of months".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "abandoned_files".to_string(),
+            "Lists files whose committers have all become inactive".to_string(),
+            "1.0.0".to_string(),
+        )
+        .with_capability(
+            "orphaned_tests".to_string(),
+            "Lists test files whose subject no longer exists".to_string(),
+            "1.0.0".to_string(),
+        );
+
+        Self {
+            command_name: "staleness".to_string(),
+            settings: crate::plugin::PluginSettings::default(),
+            info,
+            initialized: false,
+            stale_window: chrono::Duration::days(12 * 30),
+            inactive_window: chrono::Duration::days(6 * 30),
+            tally: Arc::new(RwLock::new(StalenessTally::default())),
+            consuming: Arc::new(RwLock::new(false)),
+            consumer: Arc::new(RwLock::new(None)),
+            notification_manager: Arc::new(AsyncNotificationManager::new()), // Temporary for deprecated constructor
+        }
+    }
+
+    /// Create a new staleness plugin with all required dependencies (REQUIRED)
+    pub fn with_dependencies(
+        settings: crate::plugin::PluginSettings,
+        notification_manager: std::sync::Arc<crate::notifications::AsyncNotificationManager<crate::notifications::events::PluginEvent>>
+    ) -> Self {
+        let mut plugin = Self::new();
+        plugin.settings = settings;
+        plugin.notification_manager = notification_manager;
+        plugin
+    }
+
+    /// Record a file of the current tree or a commit's changes
+    async fn process_scan_message(&self, message: &ScanMessage) -> PluginResult<()> {
+        match &message.data {
+            MessageData::FileContent { path, .. } => self.tally.write().await.record_file(path),
+            MessageData::CommitInfo { author, timestamp, changed_files, .. } => {
+                let paths = changed_files.iter().map(|file| file.path.as_str());
+                self.tally.write().await.record_commit(author, *timestamp, paths);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn analyse(&self) -> StalenessAnalysis {
+        self.tally.read().await.analyse(crate::clock::now_seconds(), self.stale_window, self.inactive_window)
+    }
+
+    /// Generate staleness summary statistics
+    async fn generate_summary(&self) -> PluginResult<ScanMessage> {
+        let analysis = self.analyse().await;
+
+        let data = MessageData::MetricInfo {
+            file_count: analysis.files as u32,
+            line_count: analysis.stale.len() as u64,
+            complexity: analysis.abandoned.len() as f64,
+        };
+
+        let header = MessageHeader::new(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            "plugin-generated".to_string(),
+        );
+
+        Ok(ScanMessage::new(header, data))
+    }
+
+    fn export_hints() -> ExportHints {
+        ExportHints {
+            preferred_formats: vec![
+                ExportFormat::Console,
+                ExportFormat::Json,
+                ExportFormat::Csv,
+                ExportFormat::Html,
+                ExportFormat::Markdown,
+            ],
+            sort_by: None,
+            sort_ascending: false,
+            limit: None,
+            include_totals: false,
+            include_row_numbers: false,
+            custom_hints: HashMap::new(),
+        }
+    }
+
+    fn tabular_export(title: &str, description: String, schema: DataSchema, rows: Vec<Row>) -> PluginDataExport {
+        PluginDataExport {
+            plugin_id: "staleness".to_string(),
+            title: title.to_string(),
+            description: Some(description),
+            data_type: DataExportType::Tabular,
+            schema,
+            data: DataPayload::Rows(Arc::new(rows)),
+            export_hints: Self::export_hints(),
+            timestamp: SystemTime::now(),
+            repository: None,
+        }
+    }
+
+    fn date(timestamp: Option<i64>) -> Value {
+        timestamp.map_or(Value::Null, |timestamp| Value::String(format_date(timestamp)))
+    }
+
+    fn age_days(now: i64, timestamp: Option<i64>) -> Value {
+        timestamp.map_or(Value::Null, |timestamp| Value::Integer((now - timestamp).max(0) / SECONDS_PER_DAY))
+    }
+
+    /// Create the stale files export, oldest first
+    fn create_stale_export(analysis: &StalenessAnalysis, now: i64, stale_window: chrono::Duration, scan_id: &str) -> Option<PluginDataExport> {
+        if analysis.stale.is_empty() {
+            return None;
+        }
+
+        let mut schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Path", ColumnType::String),
+                ColumnDef::new("Last Modified", ColumnType::String)
+                    .with_description("Date of the last commit to change the file; empty when it was before the scanned history"),
+                ColumnDef::new("Age (days)", ColumnType::Integer),
+                ColumnDef::new("Last Author", ColumnType::String),
+            ],
+            metadata: HashMap::new(),
+        };
+        schema.metadata.insert("stale_days".to_string(), stale_window.num_days().to_string());
+        let rows = analysis.stale.iter()
+            .map(|file| Row::new(vec![
+                Value::String(file.path.clone()),
+                Self::date(file.last_modified),
+                Self::age_days(now, file.last_modified),
+                file.last_author.clone().map_or(Value::Null, Value::String),
+            ]))
+            .collect();
+
+        Some(Self::tabular_export(
+            "Stale Files",
+            format!("{} of {} files not modified for {} in scan {}", analysis.stale.len(), analysis.files, describe_window(stale_window), scan_id),
+            schema,
+            rows,
+        ))
+    }
+
+    /// Create the abandoned files export, longest abandoned first
+    fn create_abandoned_export(analysis: &StalenessAnalysis, inactive_window: chrono::Duration, scan_id: &str) -> Option<PluginDataExport> {
+        if analysis.abandoned.is_empty() {
+            return None;
+        }
+
+        let mut schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Path", ColumnType::String),
+                ColumnDef::new("Authors", ColumnType::String)
+                    .with_description("Everyone who has changed the file in the scanned history"),
+                ColumnDef::new("Last Active", ColumnType::String)
+                    .with_description("Date of the latest commit by any of the file's authors, anywhere in the repository"),
+                ColumnDef::new("Last Modified", ColumnType::String),
+            ],
+            metadata: HashMap::new(),
+        };
+        schema.metadata.insert("inactive_days".to_string(), inactive_window.num_days().to_string());
+        let rows = analysis.abandoned.iter()
+            .map(|file| Row::new(vec![
+                Value::String(file.path.clone()),
+                Value::String(file.authors.join(", ")),
+                Value::String(format_date(file.last_active)),
+                Value::String(format_date(file.last_modified)),
+            ]))
+            .collect();
+
+        Some(Self::tabular_export(
+            "Abandoned Files",
+            format!(
+                "{} files whose committers have not committed for {} in scan {}",
+                analysis.abandoned.len(), describe_window(inactive_window), scan_id
+            ),
+            schema,
+            rows,
+        ))
+    }
+
+    /// Create the orphaned tests export
+    fn create_orphaned_export(analysis: &StalenessAnalysis, scan_id: &str) -> Option<PluginDataExport> {
+        if analysis.orphaned.is_empty() {
+            return None;
+        }
+
+        let schema = DataSchema {
+            columns: vec![
+                ColumnDef::new("Path", ColumnType::String),
+                ColumnDef::new("Subject", ColumnType::String)
+                    .with_description("Name of the file the test is named after, which is not in the tree"),
+                ColumnDef::new("Last Modified", ColumnType::String),
+            ],
+            metadata: HashMap::new(),
+        };
+        let rows = analysis.orphaned.iter()
+            .map(|test| Row::new(vec![
+                Value::String(test.path.clone()),
+                Value::String(test.subject.clone()),
+                Self::date(test.last_modified),
+            ]))
+            .collect();
+
+        Some(Self::tabular_export(
+            "Orphaned Tests",
+            format!("{} test files whose subject no longer exists in scan {}", analysis.orphaned.len(), scan_id),
+            schema,
+            rows,
+        ))
+    }
+
+    /// Publish the exports that found anything
+    async fn publish_exports(&self, scan_id: &str) {
+        let now = crate::clock::now_seconds();
+        let analysis = self.analyse().await;
+        let exports: Vec<_> = Self::create_stale_export(&analysis, now, self.stale_window, scan_id)
+            .into_iter()
+            .chain(Self::create_abandoned_export(&analysis, self.inactive_window, scan_id))
+            .chain(Self::create_orphaned_export(&analysis, scan_id))
+            .collect();
+        log::debug!("Staleness plugin publishing {} exports for {} files", exports.len(), analysis.files);
+
+        for export_data in exports {
+            let title = export_data.title.clone();
+            let event = PluginEvent::DataReady {
+                plugin_id: "staleness".to_string(),
+                scan_id: scan_id.to_string(),
+                export: Arc::new(export_data),
+            };
+
+            if let Err(e) = self.publish(event).await {
+                log::warn!("Failed to publish {} DataReady event: {}", title, e);
+            } else {
+                log::debug!("Published {} DataReady event for staleness plugin", title);
+            }
+        }
+    }
+
+    /// Execute the staleness analysis function
+    async fn execute_staleness_analysis(&self) -> PluginResult<PluginResponse> {
+        let start_time = std::time::Instant::now();
+        let analysis = self.analyse().await;
+
+        let data = json!({
+            "total_files": analysis.files,
+            "stale_days": self.stale_window.num_days(),
+            "inactive_days": self.inactive_window.num_days(),
+            "stale_files": analysis.stale.iter().map(|file| json!({
+                "path": file.path,
+                "last_modified": file.last_modified.map(format_date),
+                "last_author": file.last_author,
+            })).collect::<Vec<_>>(),
+            "abandoned_files": analysis.abandoned.iter().map(|file| json!({
+                "path": file.path,
+                "authors": file.authors,
+                "last_active": format_date(file.last_active),
+                "last_modified": format_date(file.last_modified),
+            })).collect::<Vec<_>>(),
+            "orphaned_tests": analysis.orphaned.iter().map(|test| json!({
+                "path": test.path,
+                "subject": test.subject,
+                "last_modified": test.last_modified.map(format_date),
+            })).collect::<Vec<_>>(),
+            "function": "staleness"
+        });
+
+        let duration_us = start_time.elapsed().as_micros() as u64;
+
+        Ok(PluginResponse::Execute {
+            request_id: "staleness_analysis".to_string(),
+            status: crate::plugin::context::ExecutionStatus::Success,
+            data,
+            metadata: crate::plugin::context::ExecutionMetadata {
+                duration_us,
+                memory_used: self.state_memory().await as u64,
+                entries_processed: analysis.files as u64,
+                plugin_version: self.info.version.clone(),
+                extra: HashMap::new(),
+            },
+            errors: vec![],
+        })
+    }
+}
+
+impl Default for StalenessPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for StalenessPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        *self.tally.write().await = StalenessTally::default();
+        self.initialized = true;
+
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        if !self.initialized {
+            return Err(PluginError::invalid_state("Plugin not initialized"));
+        }
+
+        match request {
+            PluginRequest::Execute { invocation_type, .. } => {
+                let function_name = match invocation_type {
+                    crate::plugin::InvocationType::Function(ref func) => func.as_str(),
+                    crate::plugin::InvocationType::Direct => self.default_function().unwrap_or("staleness"),
+                    crate::plugin::InvocationType::Default => "staleness",
+                };
+
+                match function_name {
+                    "staleness" | "stale" | "dead-files" => self.execute_staleness_analysis().await,
+                    _ => Err(PluginError::execution_failed(
+                        format!("Unknown function: {}", function_name)
+                    )),
+                }
+            }
+            PluginRequest::GetStatistics => {
+                let summary = self.generate_summary().await?;
+                Ok(PluginResponse::Statistics(summary))
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        if *self.consuming.read().await {
+            self.stop_consuming().await?;
+        }
+
+        self.initialized = false;
+        *self.tally.write().await = StalenessTally::default();
+        Ok(())
+    }
+
+    /// Get all functions this plugin can handle
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![
+            PluginFunction {
+                name: "staleness".to_string(),
+                aliases: vec!["dead-files".to_string()],
+                description: "Stale files, files abandoned by their committers and orphaned tests".to_string(),
+                is_default: true,
+            },
+        ]
+    }
+
+    /// Get the default function name
+    fn default_function(&self) -> Option<&str> {
+        Some("staleness")
+    }
+
+    /// Cast to ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// Cast to mutable ConsumerPlugin since this plugin implements that trait
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    /// The current tree is listed from its file content
+    fn as_data_requirements(&self) -> Option<&dyn PluginDataRequirements> {
+        Some(self)
+    }
+
+    fn get_plugin_help(&self) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help(self))
+    }
+
+    fn get_plugin_help_with_colors(&self, no_color: bool, color: bool) -> Option<String> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::generate_help_with_colors(self, no_color, color))
+    }
+
+    fn build_clap_command(&self) -> Option<clap::Command> {
+        use crate::plugin::traits::PluginClapParser;
+        Some(PluginClapParser::build_clap_command(self))
+    }
+
+    async fn parse_plugin_arguments(&mut self, args: &[String]) -> PluginResult<()> {
+        use crate::plugin::traits::PluginClapParserExt;
+        self.parse_plugin_args_default(args).await
+    }
+}
+
+impl PluginDataRequirements for StalenessPlugin {
+    fn requires_current_file_content(&self) -> bool {
+        true
+    }
+
+    fn requires_historical_file_content(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl Publisher<PluginEvent> for StalenessPlugin {
+    async fn publish(&self, event: PluginEvent) -> crate::notifications::NotificationResult<()> {
+        self.notification_manager.publish(event).await
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for StalenessPlugin {
+    async fn start_consuming(&mut self, consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if *consuming {
+            return Err(PluginError::invalid_state("Already consuming"));
+        }
+
+        *consuming = true;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = Some(consumer);
+        }
+
+        log::info!("Staleness plugin started consuming messages");
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        self.process_scan_message(&message).await?;
+
+        consumer.acknowledge(message.header().sequence()).await.map_err(|e| {
+            PluginError::execution_failed(format!("Failed to acknowledge message: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        log::debug!("Staleness plugin received queue event: {:?}", event);
+
+        match event {
+            QueueEvent::ScanStarted { scan_id, .. } => {
+                log::info!("Staleness plugin: scan started for {}", scan_id);
+                *self.tally.write().await = StalenessTally::default();
+            }
+            QueueEvent::ScanComplete { scan_id, total_messages, .. } => {
+                log::info!(
+                    "Staleness plugin: scan {} complete - {} files in the tree (total {} messages)",
+                    scan_id, self.tally.read().await.tree.len(), total_messages
+                );
+
+                self.publish_exports(scan_id).await;
+            }
+            _ => {
+                // Other events are just logged
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        let mut consuming = self.consuming.write().await;
+
+        if !*consuming {
+            return Ok(()); // Already stopped
+        }
+
+        *consuming = false;
+
+        {
+            let mut consumer_guard = self.consumer.write().await;
+            *consumer_guard = None;
+        }
+
+        log::info!("Staleness plugin stopped consuming messages");
+        Ok(())
+    }
+
+    async fn state_memory(&self) -> usize {
+        self.tally.read().await.heap_size()
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages: false, // Only the tree listing and commits are needed
+            interested_message_types: vec!["FileContent".to_string(), "CommitInfo".to_string()],
+            high_frequency_capable: true,
+            preferred_batch_size: 100,
+            requires_ordered_delivery: false, // Files are assessed once the scan completes
+            max_memory: None,
+            max_message_time: Some(std::time::Duration::from_millis(10)),
+        }
+    }
+}
+
+/// Modern clap-based argument parsing implementation for staleness plugin
+#[async_trait]
+impl PluginClapParser for StalenessPlugin {
+    fn get_command_name(&self) -> impl Into<String> {
+        &self.command_name
+    }
+
+    fn get_command_description(&self) -> &str {
+        "Finds stale files, files abandoned by their committers and orphaned tests"
+    }
+
+    fn get_plugin_settings(&self) -> &crate::plugin::PluginSettings {
+        &self.settings
+    }
+
+    fn add_plugin_args(&self, command: clap::Command) -> clap::Command {
+        use clap::Arg;
+
+        command
+            .override_usage("staleness [OPTIONS]")
+            .help_template("Usage: {usage}\n\nFinds stale files, files abandoned by their committers and orphaned tests\n\nOptions:\n{options}\n{after-help}")
+            .after_help("Files are taken from the current tree. A file is abandoned when nobody who has changed it has \
+                committed anything within the inactive window. Test files named after another file \
+                (parser_test.go, test_parser.py, parser.test.ts, ParserTest.java) are orphaned when no file \
+                of that name remains. Ages are measured from now, or from the latest commit with --reproducible.")
+            .arg(Arg::new("stale-months")
+                .long("stale-months")
+                .value_name("MONTHS")
+                .help("Months without a change after which a file is stale (a count of months or a duration like 180d)")
+                .value_parser(parse_window)
+                .default_value("12"))
+            .arg(Arg::new("inactive-months")
+                .long("inactive-months")
+                .value_name("MONTHS")
+                .help("Months without a commit after which an author counts as having left (a count of months or a duration like 26w)")
+                .value_parser(parse_window)
+                .default_value("6"))
+    }
+
+    async fn configure_from_matches(&mut self, matches: &clap::ArgMatches) -> PluginResult<()> {
+        if let Some(window) = matches.get_one::<chrono::Duration>("stale-months") {
+            self.stale_window = *window;
+        }
+        if let Some(window) = matches.get_one::<chrono::Duration>("inactive-months") {
+            self.inactive_window = *window;
+        }
+
+        log::debug!(
+            "Staleness plugin configured with a {} stale window and a {} inactive window",
+            describe_window(self.stale_window), describe_window(self.inactive_window)
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::messages::FileChangeData;
+
+    const DAY: i64 = 86_400;
+    /// 2024-06-01T00:00:00Z
+    const NOW: i64 = 1_717_200_000;
+
+    fn months(count: i64) -> chrono::Duration {
+        chrono::Duration::days(count * 30)
+    }
+
+    fn commit(author: &str, days_ago: i64, paths: &[&str]) -> ScanMessage {
+        let data = MessageData::CommitInfo {
+            hash: format!("{}-{}", author, days_ago),
+            author: author.to_string(),
+            message: "Change files".to_string(),
+            timestamp: NOW - days_ago * DAY,
+            committer: author.to_string(),
+            author_timestamp: NOW - days_ago * DAY,
+            parents: vec![],
+            changed_files: paths.iter()
+                .map(|path| FileChangeData { path: path.to_string(), lines_added: 1, lines_removed: 0 })
+                .collect(),
+        };
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    fn file(path: &str) -> ScanMessage {
+        let data = MessageData::FileContent {
+            path: path.to_string(),
+            size: 0,
+            content: String::new(),
+            generated: false,
+        };
+        ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), data)
+    }
+
+    /// alice left two years ago; bob is still active
+    async fn plugin_with_history() -> StalenessPlugin {
+        let plugin = StalenessPlugin::new();
+        let messages = [
+            commit("alice", 800, &["src/legacy.rs", "src/parser.rs", "src/old_lexer_test.go"]),
+            commit("alice", 700, &["src/parser.rs", "tests/test_report.py"]),
+            commit("bob", 200, &["src/parser.rs"]),
+            commit("bob", 10, &["src/main.rs", "src/main_test.go"]),
+            file("src/legacy.rs"),
+            file("src/parser.rs"),
+            file("src/main.rs"),
+            file("src/main_test.go"),
+            file("src/old_lexer_test.go"),
+            file("tests/test_report.py"),
+            file("docs/guide.md"),
+        ];
+        for message in &messages {
+            plugin.process_scan_message(message).await.unwrap();
+        }
+        plugin
+    }
+
+    #[test]
+    fn test_test_file_names() {
+        assert_eq!(tested_name("src/parser_test.go"), Some("parser"));
+        assert_eq!(tested_name("tests/test_parser.py"), Some("parser"));
+        assert_eq!(tested_name("web/parser.test.ts"), Some("parser"));
+        assert_eq!(tested_name("web/parser.spec.js"), Some("parser"));
+        assert_eq!(tested_name("spec/parser_spec.rb"), Some("parser"));
+        assert_eq!(tested_name("src/ParserTest.java"), Some("Parser"));
+        assert_eq!(tested_name("src/latest.rs"), None);
+        assert_eq!(tested_name("Makefile"), None);
+        assert_eq!(file_stem("web/parser.d.ts"), "parser");
+    }
+
+    #[tokio::test]
+    async fn test_stale_abandoned_and_orphaned_files() {
+        let plugin = plugin_with_history().await;
+        let analysis = plugin.tally.read().await.analyse(NOW, months(12), months(6));
+        assert_eq!(analysis.files, 7);
+
+        // docs/guide.md was never changed in a history reaching back past the cutoff
+        let stale: Vec<(&str, Option<i64>)> = analysis.stale.iter()
+            .map(|file| (file.path.as_str(), file.last_modified.map(|time| (NOW - time) / DAY)))
+            .collect();
+        assert_eq!(stale, [("docs/guide.md", None), ("src/legacy.rs", Some(800)), ("src/old_lexer_test.go", Some(800)), ("tests/test_report.py", Some(700))]);
+
+        // src/parser.rs has an active committer
+        let abandoned: Vec<&str> = analysis.abandoned.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(abandoned, ["src/legacy.rs", "src/old_lexer_test.go", "tests/test_report.py"]);
+        assert_eq!(analysis.abandoned[0].authors, ["alice"]);
+
+        let orphaned: Vec<(&str, &str)> = analysis.orphaned.iter().map(|test| (test.path.as_str(), test.subject.as_str())).collect();
+        assert_eq!(orphaned, [("src/old_lexer_test.go", "old_lexer"), ("tests/test_report.py", "report")]);
+
+        // A scan that starts after the cutoff cannot tell whether untouched files are stale
+        let recent = plugin.tally.read().await.analyse(NOW, months(36), months(6));
+        assert!(recent.stale.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_staleness_exports_and_options() {
+        let mut plugin = plugin_with_history().await;
+        let analysis = plugin.tally.read().await.analyse(NOW, months(12), months(6));
+
+        let stale = StalenessPlugin::create_stale_export(&analysis, NOW, months(12), "scan").unwrap();
+        assert_eq!(stale.title, "Stale Files");
+        assert!(stale.description.as_ref().unwrap().contains("not modified for 12 months"), "{:?}", stale.description);
+        if let DataPayload::Rows(rows) = &stale.data {
+            assert_eq!(rows[0].values[1..], [Value::Null, Value::Null, Value::Null]);
+            assert_eq!(rows[1].values[..3], [
+                Value::String("src/legacy.rs".to_string()), Value::String("2022-03-24".to_string()), Value::Integer(800),
+            ]);
+        } else {
+            panic!("expected row payload");
+        }
+        let abandoned = StalenessPlugin::create_abandoned_export(&analysis, chrono::Duration::days(45), "scan").unwrap();
+        assert_eq!(abandoned.schema.metadata["inactive_days"], "45");
+        assert!(abandoned.description.as_ref().unwrap().contains("not committed for 45 days"), "{:?}", abandoned.description);
+        assert_eq!(StalenessPlugin::create_orphaned_export(&analysis, "scan").unwrap().title, "Orphaned Tests");
+        assert!(StalenessPlugin::create_stale_export(&StalenessAnalysis::default(), NOW, months(12), "scan").is_none());
+
+        let matches = PluginClapParser::build_clap_command(&plugin)
+            .try_get_matches_from(["staleness", "--stale-months", "24", "--inactive-months", "3"])
+            .unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        assert_eq!((plugin.stale_window, plugin.inactive_window), (months(24), months(3)));
+        let matches = PluginClapParser::build_clap_command(&plugin)
+            .try_get_matches_from(["staleness", "--stale-months", "180d", "--inactive-months", "6m"])
+            .unwrap();
+        plugin.configure_from_matches(&matches).await.unwrap();
+        assert_eq!((plugin.stale_window, plugin.inactive_window), (chrono::Duration::days(180), months(6)));
+        for invalid in ["0", "0d", "12 parsecs"] {
+            assert!(PluginClapParser::build_clap_command(&plugin)
+                .try_get_matches_from(["staleness", "--stale-months", invalid])
+                .is_err());
+        }
+        assert!(plugin.as_data_requirements().is_some_and(|requirements| requirements.requires_current_file_content()));
+
+        plugin.handle_queue_event(&QueueEvent::ScanStarted { scan_id: "next".to_string(), timestamp: 0 }).await.unwrap();
+        assert!(plugin.tally.read().await.tree.is_empty());
+    }
+}
@@ -82,7 +82,10 @@ impl FormatDetector {
     fn extension_to_format(&self, extension: &str) -> Option<ExportFormat> {
         match extension {
             // JSON formats
-            "json" | "jsonl" | "ndjson" | "json5" => Some(ExportFormat::Json),
+            "json" | "json5" => Some(ExportFormat::Json),
+            
+            // Newline-delimited JSON
+            "jsonl" | "ndjson" => Some(ExportFormat::Ndjson),
             
             // CSV formats
             "csv" | "tsv" | "tab" | "psv" | "ssv" | "dsv" => Some(ExportFormat::Csv), // CSV, TSV, pipe/space/semicolon separated values
@@ -99,6 +102,12 @@ impl FormatDetector {
             // Markdown formats
             "md" | "markdown" | "mdown" | "mkdown" | "mkd" | "mdx" | "rmd" => Some(ExportFormat::Markdown),
             
+            // SQLite databases
+            "sqlite" | "sqlite3" | "db" => Some(ExportFormat::Sqlite),
+            
+            // Parquet files
+            "parquet" | "pq" => Some(ExportFormat::Parquet),
+            
             // Plain text defaults to JSON (most structured)
             "txt" => Some(ExportFormat::Json),
             
@@ -122,6 +131,10 @@ impl FormatDetector {
             "html", "htm", "xhtml", "shtml", "dhtml",
             // Markdown variants
             "md", "markdown", "mdown", "mkdown", "mkd", "mdx", "rmd",
+            // SQLite variants
+            "sqlite", "sqlite3", "db",
+            // Parquet variants
+            "parquet", "pq",
             // Plain text
             "txt"
         ]
@@ -136,28 +149,36 @@ impl FormatDetector {
     pub fn get_extensions_for_format(&self, format: &ExportFormat) -> Vec<&'static str> {
         match format {
             ExportFormat::Console => vec![], // Console output doesn't map to file extensions
-            ExportFormat::Json => vec!["json", "jsonl", "ndjson", "json5", "txt"],
+            ExportFormat::Json => vec!["json", "json5", "txt"],
+            ExportFormat::Ndjson => vec!["ndjson", "jsonl"],
             ExportFormat::Csv => vec!["csv", "tsv", "tab", "psv", "ssv", "dsv"],
             ExportFormat::Xml => vec!["xml", "xsd", "xsl", "dtd", "rss", "atom", "sitemap", "kml", "gpx", "svg", "xaml", "plist", "proj"],
             ExportFormat::Yaml => vec!["yaml", "yml", "yaml-tmpl", "yamlt"],
             ExportFormat::Html => vec!["html", "htm", "xhtml", "shtml", "dhtml"],
             ExportFormat::Markdown => vec!["md", "markdown", "mdown", "mkdown", "mkd", "mdx", "rmd"],
             ExportFormat::Template => vec![], // Templates don't map to file extensions
+            ExportFormat::Sqlite => vec!["sqlite", "sqlite3", "db"],
+            ExportFormat::Parquet => vec!["parquet", "pq"],
+            ExportFormat::Annotations(_) => vec![], // Selected by name; their files use ordinary extensions
         }
     }
 
     /// Validate that a format can be generated by templates
     pub fn is_template_compatible(&self, format: &ExportFormat) -> bool {
-        // All current formats are template-compatible
+        // All text formats are template-compatible
         match format {
             ExportFormat::Console => true, // Console output can be generated by templates
             ExportFormat::Json => true,
+            ExportFormat::Ndjson => true,
             ExportFormat::Csv => true,
             ExportFormat::Xml => true,
             ExportFormat::Yaml => true,
             ExportFormat::Html => true,
             ExportFormat::Markdown => true,
             ExportFormat::Template => true, // Templates are inherently template-compatible
+            ExportFormat::Sqlite => false, // Databases are written directly, not rendered as text
+            ExportFormat::Parquet => false, // Columnar binary files, not text
+            ExportFormat::Annotations(_) => true,
         }
     }
 
@@ -343,6 +364,7 @@ mod tests {
         assert!(extensions.contains(&"md"));
         assert!(extensions.contains(&"markdown"));
         assert!(extensions.contains(&"txt"));
+        assert!(extensions.contains(&"sqlite"));
     }
 
     #[test]
@@ -365,7 +387,7 @@ mod tests {
         // Test format suggestion error
         let error = detector.format_suggestion_error("xyz");
         assert!(error.contains("Unknown format for extension '.xyz'"));
-        assert!(error.contains("json, csv"));
+        assert!(error.contains("json5, csv"));
 
         // Test no extension error
         let error = detector.no_extension_error();
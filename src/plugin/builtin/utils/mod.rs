@@ -7,5 +7,6 @@
 //! They will eventually be moved to their appropriate plugin modules.
 
 pub mod format_detection;
+pub mod time_buckets;
 
 // Re-export main types and functions for convenience
@@ -0,0 +1,264 @@
+//! Time Bucketing
+//!
+//! Shared calendar bucketing for time-series exports. Values are grouped into
+//! day, week, month or quarter buckets and laid out as a series from the first
+//! to the last non-empty bucket. A dense series lists every bucket, filling
+//! empty ones with default values; a sparse series collapses each run of empty
+//! buckets into a single gap marker, which keeps exports of bursty or long-idle
+//! histories short without hiding where the gaps are.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Calendar period covered by one bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketPeriod {
+    Day,
+    Week,
+    Month,
+    Quarter,
+}
+
+impl BucketPeriod {
+    /// Parse a period name as accepted on the command line
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "day" => Some(BucketPeriod::Day),
+            "week" => Some(BucketPeriod::Week),
+            "month" => Some(BucketPeriod::Month),
+            "quarter" => Some(BucketPeriod::Quarter),
+            _ => None,
+        }
+    }
+
+    /// Period name as accepted on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketPeriod::Day => "day",
+            BucketPeriod::Week => "week",
+            BucketPeriod::Month => "month",
+            BucketPeriod::Quarter => "quarter",
+        }
+    }
+
+    /// First day of the period containing `date` (weeks start on Monday)
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            BucketPeriod::Day => date,
+            BucketPeriod::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            BucketPeriod::Month => date.with_day(1).unwrap_or(date),
+            BucketPeriod::Quarter => {
+                let month = (date.month0() / 3) * 3 + 1;
+                NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+            }
+        }
+    }
+
+    /// First day of the period containing a unix timestamp (UTC)
+    pub fn start_of_timestamp(&self, timestamp: i64) -> Option<NaiveDate> {
+        DateTime::from_timestamp(timestamp, 0).map(|dt| self.start_of(dt.date_naive()))
+    }
+
+    /// First day of the period following the one starting at `start`
+    pub fn next(&self, start: NaiveDate) -> NaiveDate {
+        match self {
+            BucketPeriod::Day => start + Duration::days(1),
+            BucketPeriod::Week => start + Duration::days(7),
+            BucketPeriod::Month => start.checked_add_months(chrono::Months::new(1)).unwrap_or(start),
+            BucketPeriod::Quarter => start.checked_add_months(chrono::Months::new(3)).unwrap_or(start),
+        }
+    }
+
+    /// Display label for the period starting at `start`
+    pub fn label(&self, start: NaiveDate) -> String {
+        match self {
+            BucketPeriod::Day | BucketPeriod::Week => start.format("%Y-%m-%d").to_string(),
+            BucketPeriod::Month => start.format("%Y-%m").to_string(),
+            BucketPeriod::Quarter => format!("{}-Q{}", start.year(), start.month0() / 3 + 1),
+        }
+    }
+}
+
+/// Whether empty buckets are listed individually or collapsed into gaps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeriesMode {
+    /// Every bucket between the first and last non-empty one
+    #[default]
+    Dense,
+    /// Non-empty buckets, with each run of empty buckets replaced by a gap marker
+    Sparse,
+}
+
+impl SeriesMode {
+    /// Parse a series mode as accepted on the command line
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "dense" => Some(SeriesMode::Dense),
+            "sparse" => Some(SeriesMode::Sparse),
+            _ => None,
+        }
+    }
+
+    /// Series mode name as accepted on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeriesMode::Dense => "dense",
+            SeriesMode::Sparse => "sparse",
+        }
+    }
+}
+
+/// A run of consecutive empty buckets in a sparse series
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gap {
+    /// First day of the first empty period
+    pub start: NaiveDate,
+    /// First day of the last empty period
+    pub end: NaiveDate,
+    /// Number of empty periods in the run
+    pub periods: usize,
+    /// Label covering the run (e.g. "2024-02..2024-05")
+    pub label: String,
+}
+
+/// One entry of a bucketed series
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeriesEntry<T> {
+    /// A bucket and its value (which may be empty in a dense series)
+    Bucket { start: NaiveDate, label: String, value: T },
+    /// Collapsed empty buckets in a sparse series
+    Gap(Gap),
+}
+
+impl<T> SeriesEntry<T> {
+    /// Label of the bucket or the range covered by the gap
+    pub fn label(&self) -> &str {
+        match self {
+            SeriesEntry::Bucket { label, .. } => label,
+            SeriesEntry::Gap(gap) => &gap.label,
+        }
+    }
+
+    /// Value of a bucket, or `None` for a gap marker
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            SeriesEntry::Bucket { value, .. } => Some(value),
+            SeriesEntry::Gap(_) => None,
+        }
+    }
+
+    /// Number of empty periods a gap marker stands for (0 for buckets)
+    pub fn gap_periods(&self) -> usize {
+        match self {
+            SeriesEntry::Bucket { .. } => 0,
+            SeriesEntry::Gap(gap) => gap.periods,
+        }
+    }
+}
+
+/// Lay out non-empty buckets as a series in the given mode
+///
+/// Keys must be period starts as returned by [`BucketPeriod::start_of`]. The
+/// series spans the first to the last key; periods without a key are empty.
+pub fn bucket_series<T: Default>(
+    mut buckets: BTreeMap<NaiveDate, T>,
+    period: BucketPeriod,
+    mode: SeriesMode,
+) -> Vec<SeriesEntry<T>> {
+    let (Some(first), Some(last)) = (buckets.keys().next().copied(), buckets.keys().next_back().copied()) else {
+        return Vec::new();
+    };
+
+    let mut series = Vec::new();
+    let mut gap: Option<(NaiveDate, NaiveDate, usize)> = None;
+    let mut start = first;
+    while start <= last {
+        match buckets.remove(&start) {
+            Some(value) => {
+                if let Some((gap_start, gap_end, periods)) = gap.take() {
+                    series.push(SeriesEntry::Gap(make_gap(period, gap_start, gap_end, periods)));
+                }
+                series.push(SeriesEntry::Bucket { start, label: period.label(start), value });
+            }
+            None if mode == SeriesMode::Dense => {
+                series.push(SeriesEntry::Bucket { start, label: period.label(start), value: T::default() });
+            }
+            None => {
+                gap = Some(match gap {
+                    Some((gap_start, _, periods)) => (gap_start, start, periods + 1),
+                    None => (start, start, 1),
+                });
+            }
+        }
+
+        let next = period.next(start);
+        if next <= start {
+            break;
+        }
+        start = next;
+    }
+    series
+}
+
+fn make_gap(period: BucketPeriod, start: NaiveDate, end: NaiveDate, periods: usize) -> Gap {
+    let label = if periods == 1 {
+        period.label(start)
+    } else {
+        format!("{}..{}", period.label(start), period.label(end))
+    };
+    Gap { start, end, periods, label }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn buckets() -> BTreeMap<NaiveDate, usize> {
+        [("2024-01-01", 3), ("2024-02-01", 1), ("2024-06-01", 2)]
+            .into_iter()
+            .map(|(start, value)| (date(start), value))
+            .collect()
+    }
+
+    #[test]
+    fn test_dense_series_fills_empty_buckets() {
+        let series = bucket_series(buckets(), BucketPeriod::Month, SeriesMode::Dense);
+        let labels: Vec<&str> = series.iter().map(|entry| entry.label()).collect();
+        assert_eq!(labels, vec!["2024-01", "2024-02", "2024-03", "2024-04", "2024-05", "2024-06"]);
+        assert_eq!(series[3].value(), Some(&0));
+        assert!(series.iter().all(|entry| entry.gap_periods() == 0));
+    }
+
+    #[test]
+    fn test_sparse_series_collapses_gaps() {
+        let series = bucket_series(buckets(), BucketPeriod::Month, SeriesMode::Sparse);
+        let labels: Vec<&str> = series.iter().map(|entry| entry.label()).collect();
+        assert_eq!(labels, vec!["2024-01", "2024-02", "2024-03..2024-05", "2024-06"]);
+        assert_eq!(
+            series[2],
+            SeriesEntry::Gap(Gap {
+                start: date("2024-03-01"),
+                end: date("2024-05-01"),
+                periods: 3,
+                label: "2024-03..2024-05".to_string(),
+            })
+        );
+        assert_eq!(series[3].value(), Some(&2));
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        let sunday = date("2024-01-07");
+        assert_eq!(BucketPeriod::Week.start_of(sunday), date("2024-01-01"));
+        assert_eq!(BucketPeriod::Quarter.start_of(date("2024-05-20")), date("2024-04-01"));
+        assert_eq!(BucketPeriod::Quarter.label(date("2024-04-01")), "2024-Q2");
+        assert_eq!(BucketPeriod::Day.start_of_timestamp(1_704_067_200), Some(date("2024-01-01")));
+        assert_eq!(BucketPeriod::parse("fortnight"), None);
+        assert_eq!(SeriesMode::parse("sparse"), Some(SeriesMode::Sparse));
+        assert!(bucket_series(BTreeMap::<NaiveDate, usize>::new(), BucketPeriod::Day, SeriesMode::Sparse).is_empty());
+    }
+}
@@ -1,11 +1,71 @@
 //! Version Compatibility Checker
 //! 
 //! Validates plugin API versions and dependency requirements.
+//!
+//! API versions are `YYYYMMDD` dates and a build supports every version of its
+//! own year. Plugins declaring a legacy days-since-epoch version are accepted
+//! through a shim that reads the version as a date; plugins outside the
+//! supported range are rejected at registration with the reason.
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use serde::Serialize;
 use crate::plugin::traits::PluginInfo;
 use crate::plugin::error::{PluginError, PluginResult};
 
+/// Declared API versions below this are legacy day counts since the Unix epoch
+const LEGACY_VERSION_LIMIT: u32 = 10_000_000;
+
+/// Outcome of negotiating a plugin's declared API version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum ApiCompatibility {
+    /// The declared version is in the supported range
+    Compatible,
+    /// The declared version was accepted after a compatibility shim
+    Shimmed { shim: String },
+    /// The plugin cannot be used with this build
+    Incompatible { reason: String },
+}
+
+impl ApiCompatibility {
+    /// Whether the plugin can be registered
+    pub fn is_usable(&self) -> bool {
+        !matches!(self, ApiCompatibility::Incompatible { .. })
+    }
+}
+
+impl fmt::Display for ApiCompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiCompatibility::Compatible => write!(f, "compatible"),
+            ApiCompatibility::Shimmed { shim } => write!(f, "compatible with shim: {}", shim),
+            ApiCompatibility::Incompatible { reason } => write!(f, "incompatible: {}", reason),
+        }
+    }
+}
+
+/// One row of the plugin compatibility matrix
+#[derive(Debug, Clone, Serialize)]
+pub struct CompatibilityEntry {
+    pub plugin: String,
+    pub version: String,
+    pub api_version: u32,
+    pub compatibility: ApiCompatibility,
+}
+
+impl CompatibilityEntry {
+    /// Negotiate a plugin's declared API version
+    pub fn new(checker: &VersionCompatibilityChecker, info: &PluginInfo) -> Self {
+        Self {
+            plugin: info.name.clone(),
+            version: info.version.clone(),
+            api_version: info.api_version,
+            compatibility: checker.negotiate(info.api_version),
+        }
+    }
+}
+
 /// Checker for plugin version compatibility
 pub struct VersionCompatibilityChecker {
     /// Current API version
@@ -20,6 +80,78 @@ impl VersionCompatibilityChecker {
         }
     }
     
+    /// Lowest and highest API versions this build supports
+    pub fn supported_range(&self) -> (u32, u32) {
+        let major = self.get_major_version(self.api_version);
+        (major * 10000, major * 10000 + 9999)
+    }
+    
+    /// Negotiate a declared API version against the supported range
+    pub fn negotiate(&self, plugin_api_version: u32) -> ApiCompatibility {
+        let (min, max) = self.supported_range();
+        if plugin_api_version == 0 {
+            return ApiCompatibility::Incompatible { reason: "no API version declared".to_string() };
+        }
+        if plugin_api_version < LEGACY_VERSION_LIMIT {
+            let Some(dated) = legacy_version_as_date(plugin_api_version) else {
+                return ApiCompatibility::Incompatible {
+                    reason: format!("legacy API version {} is not a valid day count", plugin_api_version),
+                };
+            };
+            return match self.negotiate(dated) {
+                ApiCompatibility::Incompatible { reason } => ApiCompatibility::Incompatible {
+                    reason: format!("legacy API version {} reads as {}, which {}", plugin_api_version, dated, reason),
+                },
+                _ => ApiCompatibility::Shimmed {
+                    shim: format!("legacy day-count API version {} read as {}", plugin_api_version, dated),
+                },
+            };
+        }
+        if plugin_api_version < min {
+            ApiCompatibility::Incompatible {
+                reason: format!("was built for API version {}, older than the oldest supported ({})", plugin_api_version, min),
+            }
+        } else if plugin_api_version > max {
+            ApiCompatibility::Incompatible {
+                reason: format!("requires API version {}, newer than this build supports ({}); upgrade gstats", plugin_api_version, self.api_version),
+            }
+        } else {
+            ApiCompatibility::Compatible
+        }
+    }
+    
+    /// Compatibility of each plugin, in the order given
+    pub fn compatibility_matrix(&self, plugins: &[PluginInfo]) -> Vec<CompatibilityEntry> {
+        plugins.iter().map(|info| CompatibilityEntry::new(self, info)).collect()
+    }
+    
+    /// Console summary of plugins that were rejected or needed a shim, if any
+    pub fn registration_report(&self, entries: &[CompatibilityEntry]) -> Option<String> {
+        let rejected: Vec<_> = entries.iter().filter(|entry| !entry.compatibility.is_usable()).collect();
+        let shimmed: Vec<_> = entries.iter().filter(|entry| matches!(entry.compatibility, ApiCompatibility::Shimmed { .. })).collect();
+        if rejected.is_empty() && shimmed.is_empty() {
+            return None;
+        }
+        
+        let (min, max) = self.supported_range();
+        let mut report = String::new();
+        if !rejected.is_empty() {
+            report.push_str(&format!(
+                "Skipped {} incompatible plugin(s) (supported API versions {}-{}):\n", rejected.len(), min, max
+            ));
+            for entry in rejected {
+                report.push_str(&format!("  {} {}: {}\n", entry.plugin, entry.version, entry.compatibility));
+            }
+        }
+        if !shimmed.is_empty() {
+            report.push_str(&format!("Loaded {} plugin(s) through a compatibility shim:\n", shimmed.len()));
+            for entry in shimmed {
+                report.push_str(&format!("  {} {}: {}\n", entry.plugin, entry.version, entry.compatibility));
+            }
+        }
+        Some(report)
+    }
+    
     /// Check if a plugin API version is compatible
     pub fn is_api_compatible(&self, plugin_api_version: u32) -> bool {
         // Same major version (year) is compatible
@@ -33,15 +165,15 @@ impl VersionCompatibilityChecker {
     
     /// Check plugin compatibility
     pub fn check_plugin_compatibility(&self, plugin_info: &PluginInfo) -> PluginResult<()> {
-        if !self.is_api_compatible(plugin_info.api_version) {
-            return Err(PluginError::version_incompatible(format!(
-                "Plugin '{}' requires API version {} but current version is {}",
+        match self.negotiate(plugin_info.api_version) {
+            ApiCompatibility::Incompatible { reason } => Err(PluginError::version_incompatible(format!(
+                "Plugin '{}' {} (current API version is {})",
                 plugin_info.name,
-                plugin_info.api_version,
+                reason,
                 self.api_version
-            )));
+            ))),
+            _ => Ok(()),
         }
-        Ok(())
     }
     
     /// Validate plugin dependencies
@@ -257,6 +389,12 @@ impl VersionCompatibilityChecker {
     }
 }
 
+/// `YYYYMMDD` version of a legacy API version counting days since the Unix epoch
+fn legacy_version_as_date(days: u32) -> Option<u32> {
+    let date = chrono::DateTime::from_timestamp(i64::from(days) * 86_400, 0)?.date_naive();
+    date.format("%Y%m%d").to_string().parse().ok()
+}
+
 impl Default for VersionCompatibilityChecker {
     fn default() -> Self {
         Self::new(crate::scanner::get_api_version() as u32)
@@ -284,6 +422,39 @@ mod tests {
         assert!(!checker.is_api_compatible(20260727));
     }
     
+    #[test]
+    fn test_api_negotiation() {
+        let checker = VersionCompatibilityChecker::new(20250727);
+        assert_eq!(checker.supported_range(), (20250000, 20259999));
+        assert_eq!(checker.negotiate(20250101), ApiCompatibility::Compatible);
+        assert!(matches!(checker.negotiate(20260101), ApiCompatibility::Incompatible { reason } if reason.contains("upgrade gstats")));
+        assert!(matches!(checker.negotiate(20240101), ApiCompatibility::Incompatible { reason } if reason.contains("older")));
+        assert!(!checker.negotiate(0).is_usable());
+        
+        // 20296 days after the epoch is 2025-07-27
+        assert_eq!(checker.negotiate(20296), ApiCompatibility::Shimmed {
+            shim: "legacy day-count API version 20296 read as 20250727".to_string(),
+        });
+        assert!(!checker.negotiate(19000).is_usable());
+    }
+    
+    #[test]
+    fn test_registration_report() {
+        let checker = VersionCompatibilityChecker::new(20250727);
+        let plugin = |name: &str, api_version| PluginInfo::new(
+            name.to_string(), "1.0.0".to_string(), api_version, "Test".to_string(), "Author".to_string(), PluginType::Processing,
+        );
+        
+        let matrix = checker.compatibility_matrix(&[plugin("current", 20250727)]);
+        assert!(checker.registration_report(&matrix).is_none());
+        
+        let matrix = checker.compatibility_matrix(&[plugin("current", 20250727), plugin("future", 20260101), plugin("legacy", 20296)]);
+        let report = checker.registration_report(&matrix).unwrap();
+        assert!(report.contains("Skipped 1 incompatible plugin(s) (supported API versions 20250000-20259999):\n  future 1.0.0: incompatible: requires"));
+        assert!(report.contains("  legacy 1.0.0: compatible with shim: legacy day-count API version 20296 read as 20250727\n"));
+        assert!(!report.contains("current"));
+    }
+    
     #[test]
     fn test_version_matching() {
         let checker = VersionCompatibilityChecker::new(20250727);
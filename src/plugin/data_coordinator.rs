@@ -2,15 +2,16 @@
 //! 
 //! Manages collection of data from multiple plugins for export.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use crate::plugin::data_export::PluginDataExport;
 
 /// Coordinates data collection from multiple plugins
 #[derive(Debug, Clone)]
 pub struct DataCoordinator {
-    /// Data collected from plugins, keyed by plugin ID
-    pending_data: HashMap<String, Vec<Arc<PluginDataExport>>>,
+    /// Data collected from plugins, keyed by plugin ID and kept in ID order
+    /// so the combined output does not depend on which plugin finished first
+    pending_data: BTreeMap<String, Vec<Arc<PluginDataExport>>>,
     
     /// Current scan ID being processed
     scan_id: Option<String>,
@@ -26,7 +27,7 @@ impl DataCoordinator {
     /// Create a new data coordinator
     pub fn new() -> Self {
         Self {
-            pending_data: HashMap::new(),
+            pending_data: BTreeMap::new(),
             scan_id: None,
             expected_plugins: HashSet::new(),
             received_plugins: HashSet::new(),
@@ -36,7 +37,7 @@ impl DataCoordinator {
     /// Create with expected plugins
     pub fn with_expected_plugins(plugins: Vec<String>) -> Self {
         Self {
-            pending_data: HashMap::new(),
+            pending_data: BTreeMap::new(),
             scan_id: None,
             expected_plugins: plugins.into_iter().collect(),
             received_plugins: HashSet::new(),
@@ -53,11 +54,28 @@ impl DataCoordinator {
         self.received_plugins.insert(plugin_id);
     }
     
+    /// Record the scan the collected data belongs to
+    pub fn set_scan_id(&mut self, scan_id: String) {
+        self.scan_id = Some(scan_id);
+    }
+    
+    /// Scan the collected data belongs to, once a plugin has reported
+    pub fn scan_id(&self) -> Option<&str> {
+        self.scan_id.as_deref()
+    }
+    
+    /// Record that a plugin has reported without retaining its data
+    ///
+    /// Used when data is written out as it arrives rather than collected.
+    pub fn mark_received(&mut self, plugin_id: String) {
+        self.received_plugins.insert(plugin_id);
+    }
+    
     /// Check if all expected plugins have provided data
     pub fn is_complete(&self) -> bool {
         if self.expected_plugins.is_empty() {
-            // If no plugins expected, consider complete when we have any data
-            !self.pending_data.is_empty()
+            // If no plugins expected, consider complete when any plugin has reported
+            !self.received_plugins.is_empty()
         } else {
             // Check if all expected plugins have reported
             self.expected_plugins.is_subset(&self.received_plugins)
@@ -180,4 +198,27 @@ mod tests {
         assert!(pending.contains(&"plugin2".to_string()));
         assert!(pending.contains(&"plugin3".to_string()));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_mark_received_completes_without_data() {
+        let mut coordinator = DataCoordinator::with_expected_plugins(vec![
+            "plugin1".to_string(),
+        ]);
+        
+        coordinator.mark_received("plugin1".to_string());
+        assert!(coordinator.is_complete());
+        assert!(coordinator.get_all_data().is_empty());
+    }
+    
+    #[test]
+    fn test_data_is_returned_in_plugin_order() {
+        let mut coordinator = DataCoordinator::new();
+        for plugin in ["metrics", "commits", "loc"] {
+            let export = PluginDataExport::builder().plugin_id(plugin).title(plugin).build().unwrap();
+            coordinator.add_data(plugin.to_string(), Arc::new(export));
+        }
+        
+        let plugins: Vec<String> = coordinator.get_all_data().iter().map(|export| export.plugin_id.clone()).collect();
+        assert_eq!(plugins, ["commits", "loc", "metrics"]);
+    }
+}
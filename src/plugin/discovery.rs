@@ -3,11 +3,19 @@
 //! Comprehensive plugin discovery mechanism supporting file-based discovery,
 //! descriptor parsing, and plugin validation.
 
+use super::dynamic::{is_plugin_library, DynamicPluginLoader};
 use super::error::{PluginError, PluginResult};
 use super::traits::{PluginDescriptor, PluginType};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use std::collections::HashMap;
+#[cfg(feature = "wasm-plugins")]
+use super::wasm::WasmPluginLoader;
+
+/// Whether a path names a WebAssembly plugin module
+fn is_wasm_plugin(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("wasm")
+}
 
 /// Plugin discovery trait for finding and loading plugins
 pub trait PluginDiscovery: Send + Sync {
@@ -153,6 +161,20 @@ impl FileBasedDiscovery {
                             continue;
                         }
                     }
+                } else if self.supports_dynamic_loading && is_plugin_library(&path) {
+                    // Shared libraries are only kept when they carry a compatible entry point
+                    match DynamicPluginLoader::new().describe(&path) {
+                        Ok(descriptor) => descriptors.push(descriptor),
+                        Err(e) => log::warn!("Skipping plugin library: {}", e),
+                    }
+                } else if self.supports_dynamic_loading && is_wasm_plugin(&path) {
+                    #[cfg(feature = "wasm-plugins")]
+                    match WasmPluginLoader::new().and_then(|loader| loader.describe(&path)) {
+                        Ok(descriptor) => descriptors.push(descriptor),
+                        Err(e) => log::warn!("Skipping WebAssembly plugin: {}", e),
+                    }
+                    #[cfg(not(feature = "wasm-plugins"))]
+                    log::warn!("Skipping WebAssembly plugin {}: gstats was built without the wasm-plugins feature", path.display());
                 }
             }
         }
@@ -517,9 +539,30 @@ impl PluginDiscovery for UnifiedPluginDiscovery {
             }
         }
         
-        // For external plugins, we would use dynamic loading here
-        // TODO: Implement external plugin instantiation when needed
-        log::debug!("External plugin '{}' discovered but instantiation not yet supported", descriptor.info.name);
+        // External plugins built as shared libraries are loaded dynamically
+        if let Some(path) = descriptor.file_path.as_deref().filter(|path| is_plugin_library(path)) {
+            return match DynamicPluginLoader::new().load(path) {
+                Ok(plugin) => Ok(Some(plugin)),
+                Err(e) => {
+                    log::warn!("Skipping external plugin '{}': {}", descriptor.info.name, e);
+                    Ok(None)
+                }
+            };
+        }
+        
+        // Sandboxed plugins are WebAssembly modules
+        #[cfg(feature = "wasm-plugins")]
+        if let Some(path) = descriptor.file_path.as_deref().filter(|path| is_wasm_plugin(path)) {
+            return match WasmPluginLoader::new().and_then(|loader| loader.load(path)) {
+                Ok(plugin) => Ok(Some(plugin)),
+                Err(e) => {
+                    log::warn!("Skipping WebAssembly plugin '{}': {}", descriptor.info.name, e);
+                    Ok(None)
+                }
+            };
+        }
+
+        log::debug!("External plugin '{}' has no plugin library to load", descriptor.info.name);
         Ok(None)
     }
 
@@ -0,0 +1,220 @@
+//! Dynamic Plugin Loading
+//!
+//! External plugins are shared libraries (`cdylib` crates) placed in a plugin
+//! directory. Each exports a [`PluginEntry`] under the [`PLUGIN_ENTRY_SYMBOL`]
+//! name, usually through the [`declare_plugin!`](crate::declare_plugin) macro:
+//!
+//! ```ignore
+//! gstats::declare_plugin!(MyPlugin::new());
+//! ```
+//!
+//! The entry records the ABI revision and gstats release the plugin was built
+//! against, together with its plugin API version. A plugin is only created when
+//! all three match this build, since its trait objects cross the library
+//! boundary and must share the same layout.
+//!
+//! Loaded libraries stay mapped for the life of the process: a plugin's code
+//! and vtables live in its library, so unloading it while an instance exists
+//! would leave dangling pointers.
+//!
+//! A plugin library links its own copy of gstats and its dependencies, so
+//! process-wide state such as the tokio runtime context is not shared: plugins
+//! can await futures handed to them but should not spawn tasks or use timers.
+
+use std::ffi::{c_char, c_void, CStr};
+use std::path::Path;
+
+use crate::plugin::compatibility::{ApiCompatibility, VersionCompatibilityChecker};
+use crate::plugin::error::{PluginError, PluginResult};
+use crate::plugin::traits::{Plugin, PluginDescriptor};
+
+/// Revision of the entry point layout; changes whenever [`PluginEntry`] does
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Name of the exported [`PluginEntry`] static
+pub const PLUGIN_ENTRY_SYMBOL: &str = "gstats_plugin_entry";
+
+/// gstats release, NUL-terminated for the entry point
+#[doc(hidden)]
+pub const GSTATS_VERSION: &CStr = match CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_VERSION"), "\0").as_bytes()) {
+    Ok(version) => version,
+    Err(_) => panic!("package version contains a NUL byte"),
+};
+
+/// Entry point exported by a plugin library
+#[repr(C)]
+pub struct PluginEntry {
+    /// [`PLUGIN_ABI_VERSION`] the library was built with
+    pub abi_version: u32,
+    /// Plugin API version the library was built against
+    pub api_version: u32,
+    /// gstats release the library was built against
+    pub gstats_version: *const c_char,
+    /// Create a plugin instance, returned as a raw `Box<Box<dyn Plugin>>`
+    pub create: unsafe extern "C" fn() -> *mut c_void,
+}
+
+// The only pointer held refers to a static string
+unsafe impl Sync for PluginEntry {}
+
+/// Export a plugin from a `cdylib` crate
+///
+/// The expression is evaluated each time gstats creates an instance.
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub static gstats_plugin_entry: $crate::sdk::__private::PluginEntry = {
+            unsafe extern "C" fn create() -> *mut ::std::ffi::c_void {
+                let plugin: ::std::boxed::Box<dyn $crate::sdk::Plugin> = ::std::boxed::Box::new($constructor);
+                ::std::boxed::Box::into_raw(::std::boxed::Box::new(plugin)) as *mut ::std::ffi::c_void
+            }
+            $crate::sdk::__private::PluginEntry {
+                abi_version: $crate::sdk::__private::PLUGIN_ABI_VERSION,
+                api_version: $crate::sdk::__private::BASE_API_VERSION as u32,
+                gstats_version: $crate::sdk::__private::GSTATS_VERSION.as_ptr(),
+                create,
+            }
+        };
+    };
+}
+
+/// Whether a path names a shared library on this platform
+pub fn is_plugin_library(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some(std::env::consts::DLL_EXTENSION)
+}
+
+/// Loads plugins from shared libraries
+pub struct DynamicPluginLoader {
+    checker: VersionCompatibilityChecker,
+}
+
+impl DynamicPluginLoader {
+    /// Create a loader accepting plugins compatible with this build's API version
+    pub fn new() -> Self {
+        Self { checker: VersionCompatibilityChecker::default() }
+    }
+
+    /// Load a plugin library and create an instance of its plugin
+    pub fn load(&self, path: &Path) -> PluginResult<Box<dyn Plugin>> {
+        // SAFETY: loading a library runs its initialisers; plugin directories
+        // are trusted in the same way as the gstats binary itself
+        let library = unsafe { libloading::Library::new(path) }
+            .map_err(|e| PluginError::loading_failed(format!("Cannot load plugin library {}: {}", path.display(), e)))?;
+
+        // SAFETY: the symbol is declared by `declare_plugin!` as a `PluginEntry`
+        let entry: &PluginEntry = unsafe {
+            let symbol = library.get::<*const PluginEntry>(PLUGIN_ENTRY_SYMBOL.as_bytes())
+                .map_err(|e| PluginError::loading_failed(format!(
+                    "{} is not a gstats plugin (no {} entry point): {}", path.display(), PLUGIN_ENTRY_SYMBOL, e
+                )))?;
+            &**symbol
+        };
+        let plugin = self.instantiate(entry)
+            .map_err(|e| PluginError::loading_failed(format!("{}: {}", path.display(), e)))?;
+
+        log::debug!("Loaded plugin '{}' from {}", plugin.plugin_info().name, path.display());
+        std::mem::forget(library);
+        Ok(plugin)
+    }
+
+    /// Describe the plugin in a library, for discovery
+    pub fn describe(&self, path: &Path) -> PluginResult<PluginDescriptor> {
+        let plugin = self.load(path)?;
+        Ok(PluginDescriptor {
+            info: plugin.plugin_info().clone(),
+            file_path: Some(path.to_path_buf()),
+            entry_point: PLUGIN_ENTRY_SYMBOL.to_string(),
+            config: std::collections::HashMap::new(),
+            functions: plugin.advertised_functions(),
+        })
+    }
+
+    /// Check an entry point against this build and create a plugin from it
+    pub fn instantiate(&self, entry: &PluginEntry) -> PluginResult<Box<dyn Plugin>> {
+        if entry.abi_version != PLUGIN_ABI_VERSION {
+            return Err(PluginError::version_incompatible(format!(
+                "plugin entry point revision {} is not supported (expected {})",
+                entry.abi_version, PLUGIN_ABI_VERSION
+            )));
+        }
+
+        // SAFETY: a revision-1 entry holds a pointer to a NUL-terminated static string
+        let gstats_version = unsafe { CStr::from_ptr(entry.gstats_version) };
+        if gstats_version != GSTATS_VERSION {
+            return Err(PluginError::version_incompatible(format!(
+                "plugin was built for gstats {} but this is gstats {}",
+                gstats_version.to_string_lossy(),
+                GSTATS_VERSION.to_string_lossy()
+            )));
+        }
+        if let ApiCompatibility::Incompatible { reason } = self.checker.negotiate(entry.api_version) {
+            return Err(PluginError::version_incompatible(format!(
+                "plugin {} (current API version is {})",
+                reason,
+                crate::scanner::get_api_version()
+            )));
+        }
+
+        // SAFETY: `create` returns a leaked `Box<Box<dyn Plugin>>` built by the same gstats release
+        let plugin = unsafe { *Box::from_raw((entry.create)() as *mut Box<dyn Plugin>) };
+        self.checker.check_plugin_compatibility(plugin.plugin_info())?;
+        Ok(plugin)
+    }
+}
+
+impl Default for DynamicPluginLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::tests::mock_plugins::MockPlugin;
+
+    unsafe extern "C" fn create_mock() -> *mut c_void {
+        let plugin: Box<dyn Plugin> = Box::new(MockPlugin::new("dynamic-mock", false));
+        Box::into_raw(Box::new(plugin)) as *mut c_void
+    }
+
+    fn entry() -> PluginEntry {
+        PluginEntry {
+            abi_version: PLUGIN_ABI_VERSION,
+            api_version: crate::scanner::get_api_version() as u32,
+            gstats_version: GSTATS_VERSION.as_ptr(),
+            create: create_mock,
+        }
+    }
+
+    #[test]
+    fn test_instantiate_matching_entry() {
+        let plugin = DynamicPluginLoader::new().instantiate(&entry()).unwrap();
+        assert_eq!(plugin.plugin_info().name, "dynamic-mock");
+    }
+
+    #[test]
+    fn test_instantiate_rejects_mismatched_versions() {
+        let loader = DynamicPluginLoader::new();
+
+        let wrong_abi = PluginEntry { abi_version: PLUGIN_ABI_VERSION + 1, ..entry() };
+        assert!(matches!(loader.instantiate(&wrong_abi), Err(PluginError::VersionIncompatible { .. })));
+
+        let wrong_release = PluginEntry { gstats_version: c"0.0.0".as_ptr(), ..entry() };
+        assert!(matches!(loader.instantiate(&wrong_release), Err(PluginError::VersionIncompatible { .. })));
+
+        let wrong_api = PluginEntry { api_version: 19990101, ..entry() };
+        assert!(matches!(loader.instantiate(&wrong_api), Err(PluginError::VersionIncompatible { .. })));
+    }
+
+    #[test]
+    fn test_load_rejects_non_plugin_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("broken.{}", std::env::consts::DLL_EXTENSION));
+        std::fs::write(&path, b"not a shared library").unwrap();
+
+        assert!(is_plugin_library(&path));
+        assert!(matches!(DynamicPluginLoader::new().load(&path), Err(PluginError::LoadingFailed { .. })));
+    }
+}
@@ -0,0 +1,198 @@
+//! Plugin Memory Accounting
+//!
+//! A consumer plugin retains memory in two places: its share of the queue,
+//! the messages it has not yet acknowledged, and the analysis state it builds
+//! up while consuming. The queue share is measured by the queue; the state is
+//! estimated by the plugin itself through
+//! [`ConsumerPlugin::state_memory`](crate::plugin::traits::ConsumerPlugin::state_memory),
+//! usually by summing the [`HeapSize`] of its collections.
+//!
+//! Estimates count the bytes a value owns on the heap, including spare
+//! capacity, but not allocator overhead or the inline size of the value, so
+//! they are approximate. `--memory-report` prints them per plugin at the end
+//! of a run, and plugins report them as the `memory_used` of their responses.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::mem::size_of;
+
+use crate::display::table::TableBuilder;
+use crate::display::ColourManager;
+
+/// Approximate number of bytes a value owns on the heap
+pub trait HeapSize {
+    /// Heap bytes owned by the value, excluding the value's own inline size
+    fn heap_size(&self) -> usize;
+}
+
+/// Implement [`HeapSize`] for types that own nothing on the heap
+macro_rules! no_heap {
+    ($($type:ty),*) => {
+        $(impl HeapSize for $type {
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })*
+    };
+}
+
+pub(crate) use no_heap;
+
+no_heap!(bool, char, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, &'static str);
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize, S> HeapSize for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        // One control byte per bucket alongside each entry
+        self.capacity() * (size_of::<(K, V)>() + 1)
+            + self.iter().map(|(key, value)| key.heap_size() + value.heap_size()).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize, S> HeapSize for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * (size_of::<T>() + 1) + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<(K, V)>()
+            + self.iter().map(|(key, value)| key.heap_size() + value.heap_size()).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl HeapSize for serde_json::Value {
+    fn heap_size(&self) -> usize {
+        match self {
+            serde_json::Value::String(text) => text.heap_size(),
+            serde_json::Value::Array(values) => values.heap_size(),
+            serde_json::Value::Object(map) => map.iter()
+                .map(|(key, value)| key.heap_size() + size_of::<serde_json::Value>() + value.heap_size())
+                .sum(),
+            _ => 0,
+        }
+    }
+}
+
+impl HeapSize for crate::scanner::messages::FileChangeData {
+    fn heap_size(&self) -> usize {
+        self.path.heap_size()
+    }
+}
+
+/// Memory a consumer plugin retains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginMemory {
+    pub plugin: String,
+    /// Queued messages the plugin has not yet acknowledged
+    pub queue_bytes: usize,
+    /// Analysis state reported by the plugin
+    pub state_bytes: usize,
+}
+
+impl PluginMemory {
+    /// Queue share and state together
+    pub fn total(&self) -> usize {
+        self.queue_bytes + self.state_bytes
+    }
+}
+
+/// Byte count with a binary unit, e.g. `512 B`, `1.5 KB`, `12.0 MB`
+pub fn format_bytes(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    match bytes as f64 {
+        b if b < KB => format!("{bytes} B"),
+        b if b < KB * KB => format!("{:.1} KB", b / KB),
+        b if b < KB * KB * KB => format!("{:.1} MB", b / (KB * KB)),
+        b => format!("{:.1} GB", b / (KB * KB * KB)),
+    }
+}
+
+/// Table of each plugin's memory, largest first, with a total row
+pub fn memory_table(usage: &[PluginMemory], colour_manager: &ColourManager) -> String {
+    let mut usage = usage.to_vec();
+    usage.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.plugin.cmp(&b.plugin)));
+
+    let mut table = TableBuilder::new()
+        .headers(["Plugin", "Queue", "State", "Total"].map(String::from).to_vec());
+    for plugin in &usage {
+        table = table.add_row(vec![
+            plugin.plugin.clone(),
+            format_bytes(plugin.queue_bytes),
+            format_bytes(plugin.state_bytes),
+            format_bytes(plugin.total()),
+        ]);
+    }
+    let sum = |bytes: fn(&PluginMemory) -> usize| usage.iter().map(bytes).sum::<usize>();
+    table = table.add_row(vec![
+        "total".to_string(),
+        format_bytes(sum(|plugin| plugin.queue_bytes)),
+        format_bytes(sum(|plugin| plugin.state_bytes)),
+        format_bytes(sum(PluginMemory::total)),
+    ]);
+    table.build_with_colors(colour_manager)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heap_size_counts_capacity_and_contents() {
+        let mut authors: HashMap<String, usize> = HashMap::new();
+        assert_eq!(authors.heap_size(), 0);
+        authors.insert("alice".to_string(), 3);
+        assert!(authors.heap_size() >= size_of::<(String, usize)>() + 5);
+
+        let paths = vec!["src/lib.rs".to_string(), "README.md".to_string()];
+        assert_eq!(paths.heap_size(), paths.capacity() * size_of::<String>() + paths[0].capacity() + paths[1].capacity());
+        assert_eq!(Some("abc".to_string()).heap_size(), 3);
+        assert_eq!(serde_json::json!({"k": "value"}).heap_size(), 1 + size_of::<serde_json::Value>() + 5);
+    }
+
+    #[test]
+    fn test_memory_table_lists_largest_first_with_total() {
+        let usage = vec![
+            PluginMemory { plugin: "loc".to_string(), queue_bytes: 0, state_bytes: 512 },
+            PluginMemory { plugin: "commits".to_string(), queue_bytes: 2048, state_bytes: 1024 },
+        ];
+        let table = memory_table(&usage, &ColourManager::new());
+        let lines: Vec<&str> = table.lines().map(str::trim).collect();
+
+        assert!(lines[0].starts_with("Plugin"));
+        let rows = &lines[2..];
+        assert!(rows[0].starts_with("commits") && rows[0].ends_with("3.0 KB"));
+        assert!(rows[1].starts_with("loc") && rows[1].contains("512 B"));
+        assert!(rows[2].starts_with("total") && rows[2].ends_with("3.5 KB"));
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -19,11 +19,18 @@
 pub mod traits;
 pub mod error;
 pub mod settings;
+pub mod arg_presets;
+pub mod aggregation;
 pub mod context;
 pub mod registry;
+pub mod resources;
+pub mod memory;
 pub mod notification;
 pub mod compatibility;
 pub mod discovery;
+pub mod dynamic;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 pub mod subscriber;
 pub mod manager;
 pub(crate) mod builtin;  // Make builtin module private to crate
@@ -34,6 +41,7 @@ pub mod processors;
 pub mod priority_queue;
 pub mod data_export;
 pub mod data_coordinator;
+pub mod table_match;
 
 #[cfg(test)]
 pub mod tests;
@@ -41,7 +49,7 @@ pub mod tests;
 // Re-export core types for easier access
 pub use traits::Plugin;
 pub use error::{PluginError, PluginResult};
-pub use context::{PluginContext, PluginRequest, PluginResponse, InvocationType};
+pub use context::{PluginContext, PluginRequest, PluginResponse, InvocationType, ScanContext};
 pub use settings::PluginSettings;
 
 // Plugin metadata and info
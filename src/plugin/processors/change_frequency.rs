@@ -9,6 +9,7 @@ use crate::scanner::async_engine::processors::{EventProcessor, ProcessorStats};
 use crate::scanner::async_engine::shared_state::{SharedProcessorState, RepositoryMetadata};
 use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
 use crate::plugin::PluginResult;
+use crate::plugin::memory::HeapSize;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -31,10 +32,7 @@ impl TimeWindow {
         match self {
             TimeWindow::All => None,
             _ => {
-                let now = SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .ok()?
-                    .as_secs() as i64;
+                let now = crate::clock::now_seconds();
                 
                 let days_back = match self {
                     TimeWindow::Week => 7,
@@ -104,10 +102,7 @@ impl FileChangeStats {
             return 0.0;
         }
         
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs() as i64;
+        let now = crate::clock::now_seconds();
         
         let days_since_change = (now - self.last_changed) / (24 * 60 * 60);
         
@@ -262,6 +257,12 @@ impl Default for ChangeFrequencyProcessor {
     }
 }
 
+impl HeapSize for FileChangeStats {
+    fn heap_size(&self) -> usize {
+        self.file_path.heap_size() + self.authors.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
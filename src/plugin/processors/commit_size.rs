@@ -0,0 +1,351 @@
+//! Commit Size Analysis
+//!
+//! Measures each commit by the lines it changed (added plus removed) and
+//! summarises the history: how commit sizes are distributed, their
+//! percentiles, and the commits that stand out from the rest:
+//!
+//! - **Giant commits** change far more lines than the repository's usual
+//!   commit: more than `giant_fence` interquartile ranges above the upper
+//!   quartile, and at least `min_giant_lines`.
+//! - **Rewritten epochs** are runs of commits that were all committed within
+//!   a few minutes of each other long after they were authored, the trace a
+//!   rebase or force-pushed rewrite of published history leaves behind.
+
+use crate::plugin::memory::HeapSize;
+use serde::{Deserialize, Serialize};
+
+/// Upper bounds (inclusive) of the size buckets; larger commits fall in a final open bucket
+const BUCKET_BOUNDS: [usize; 6] = [0, 10, 50, 200, 1000, 5000];
+
+/// One commit's size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitSize {
+    pub hash: String,
+    pub author: String,
+    /// Commit (committer) time
+    pub timestamp: i64,
+    /// Author time
+    pub author_timestamp: i64,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub files: usize,
+}
+
+impl CommitSize {
+    /// Lines changed: added plus removed
+    pub fn lines(&self) -> usize {
+        self.lines_added + self.lines_removed
+    }
+}
+
+impl HeapSize for CommitSize {
+    fn heap_size(&self) -> usize {
+        self.hash.heap_size() + self.author.heap_size()
+    }
+}
+
+/// Thresholds for commit size anomalies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSizeConfig {
+    /// Interquartile ranges above the upper quartile a giant commit exceeds
+    pub giant_fence: f64,
+    /// Fewest lines changed for a commit to count as giant
+    pub min_giant_lines: usize,
+    /// Fewest commits in a rewritten epoch
+    pub rewrite_min_commits: usize,
+    /// Longest gap, in seconds, between the commit times of consecutive rewritten commits
+    pub rewrite_window: i64,
+    /// Shortest delay, in seconds, between authoring and committing a rewritten commit
+    pub rewrite_min_delay: i64,
+}
+
+impl Default for CommitSizeConfig {
+    fn default() -> Self {
+        Self {
+            giant_fence: 3.0,
+            min_giant_lines: 1000,
+            rewrite_min_commits: 5,
+            rewrite_window: 10 * 60,
+            rewrite_min_delay: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// Commits whose size falls in one range
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeBucket {
+    /// Range label, e.g. `11-50`
+    pub range: String,
+    pub commits: usize,
+    pub lines: usize,
+}
+
+/// Percentiles of lines changed per commit
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CommitSizePercentiles {
+    pub mean: f64,
+    pub p50: usize,
+    pub p75: usize,
+    pub p90: usize,
+    pub p95: usize,
+    pub p99: usize,
+    pub max: usize,
+}
+
+/// Kind of commit size anomaly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    GiantCommit,
+    RewrittenEpoch,
+}
+
+impl AnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GiantCommit => "giant commit",
+            Self::RewrittenEpoch => "rewritten epoch",
+        }
+    }
+}
+
+/// A commit, or run of commits, that stands out
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitAnomaly {
+    pub kind: AnomalyKind,
+    /// The giant commit, or the newest commit of a rewritten epoch
+    pub hash: String,
+    pub author: String,
+    pub timestamp: i64,
+    /// Lines changed by the commit or, for an epoch, all its commits
+    pub lines: usize,
+    /// Commits involved: 1 for a giant commit
+    pub commits: usize,
+    pub detail: String,
+}
+
+/// Commit sizes of a scan, summarised and checked for anomalies
+#[derive(Debug, Clone, Default)]
+pub struct CommitSizeAnalyzer {
+    config: CommitSizeConfig,
+    commits: Vec<CommitSize>,
+}
+
+impl CommitSizeAnalyzer {
+    pub fn new(config: CommitSizeConfig) -> Self {
+        Self { config, commits: Vec::new() }
+    }
+
+    pub fn add_commit(&mut self, commit: CommitSize) {
+        self.commits.push(commit);
+    }
+
+    pub fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    /// Commits and lines changed in each size range, smallest first
+    pub fn distribution(&self) -> Vec<SizeBucket> {
+        let mut buckets: Vec<SizeBucket> = BUCKET_BOUNDS.iter().enumerate()
+            .map(|(index, &upper)| {
+                let range = match index {
+                    0 => "0".to_string(),
+                    _ => format!("{}-{}", BUCKET_BOUNDS[index - 1] + 1, upper),
+                };
+                SizeBucket { range, commits: 0, lines: 0 }
+            })
+            .chain(std::iter::once(SizeBucket {
+                range: format!("{}+", BUCKET_BOUNDS[BUCKET_BOUNDS.len() - 1] + 1),
+                commits: 0,
+                lines: 0,
+            }))
+            .collect();
+        for commit in &self.commits {
+            let lines = commit.lines();
+            let index = BUCKET_BOUNDS.iter().position(|&upper| lines <= upper).unwrap_or(BUCKET_BOUNDS.len());
+            buckets[index].commits += 1;
+            buckets[index].lines += lines;
+        }
+        buckets
+    }
+
+    /// Percentiles of lines changed per commit, by nearest rank
+    pub fn percentiles(&self) -> CommitSizePercentiles {
+        let sizes = self.sorted_sizes();
+        if sizes.is_empty() {
+            return CommitSizePercentiles::default();
+        }
+        CommitSizePercentiles {
+            mean: sizes.iter().sum::<usize>() as f64 / sizes.len() as f64,
+            p50: percentile(&sizes, 50.0),
+            p75: percentile(&sizes, 75.0),
+            p90: percentile(&sizes, 90.0),
+            p95: percentile(&sizes, 95.0),
+            p99: percentile(&sizes, 99.0),
+            max: sizes[sizes.len() - 1],
+        }
+    }
+
+    /// Lines changed above which a commit is giant
+    pub fn giant_threshold(&self) -> usize {
+        let sizes = self.sorted_sizes();
+        if sizes.is_empty() {
+            return self.config.min_giant_lines;
+        }
+        let (q1, q3) = (percentile(&sizes, 25.0) as f64, percentile(&sizes, 75.0) as f64);
+        let fence = q3 + self.config.giant_fence * (q3 - q1);
+        (fence.ceil() as usize).max(self.config.min_giant_lines)
+    }
+
+    /// Giant commits, largest first, then rewritten epochs, newest first
+    pub fn anomalies(&self) -> Vec<CommitAnomaly> {
+        let threshold = self.giant_threshold();
+        let mut giants: Vec<CommitAnomaly> = self.commits.iter()
+            .filter(|commit| commit.lines() > threshold)
+            .map(|commit| CommitAnomaly {
+                kind: AnomalyKind::GiantCommit,
+                hash: commit.hash.clone(),
+                author: commit.author.clone(),
+                timestamp: commit.timestamp,
+                lines: commit.lines(),
+                commits: 1,
+                detail: format!("{} lines in {} files, over the {}-line threshold", commit.lines(), commit.files, threshold),
+            })
+            .collect();
+        giants.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.hash.cmp(&b.hash)));
+        giants.extend(self.rewritten_epochs());
+        giants
+    }
+
+    /// Runs of commits committed together long after they were authored
+    fn rewritten_epochs(&self) -> Vec<CommitAnomaly> {
+        let mut delayed: Vec<&CommitSize> = self.commits.iter()
+            .filter(|commit| commit.timestamp - commit.author_timestamp >= self.config.rewrite_min_delay)
+            .collect();
+        delayed.sort_by_key(|commit| std::cmp::Reverse(commit.timestamp));
+
+        let mut epochs = Vec::new();
+        let mut start = 0;
+        for end in 1..=delayed.len() {
+            let continues = end < delayed.len()
+                && delayed[end - 1].timestamp - delayed[end].timestamp <= self.config.rewrite_window;
+            if continues {
+                continue;
+            }
+            let run = &delayed[start..end];
+            if run.len() >= self.config.rewrite_min_commits {
+                let newest = run[0];
+                let authored_from = run.iter().map(|commit| commit.author_timestamp).min().unwrap_or_default();
+                let authored_to = run.iter().map(|commit| commit.author_timestamp).max().unwrap_or_default();
+                epochs.push(CommitAnomaly {
+                    kind: AnomalyKind::RewrittenEpoch,
+                    hash: newest.hash.clone(),
+                    author: newest.author.clone(),
+                    timestamp: newest.timestamp,
+                    lines: run.iter().map(|commit| commit.lines()).sum(),
+                    commits: run.len(),
+                    detail: format!(
+                        "{} commits authored {} to {} were recommitted within {} minutes",
+                        run.len(), format_date(authored_from), format_date(authored_to),
+                        (newest.timestamp - run[run.len() - 1].timestamp).max(0) / 60 + 1,
+                    ),
+                });
+            }
+            start = end;
+        }
+        epochs
+    }
+
+    fn sorted_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.commits.iter().map(CommitSize::lines).collect();
+        sizes.sort_unstable();
+        sizes
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty values
+fn percentile(sorted: &[usize], percent: f64) -> usize {
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// A Unix timestamp as a UTC date
+pub fn format_date(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn commit(hash: &str, lines: usize, timestamp: i64, author_timestamp: i64) -> CommitSize {
+        CommitSize {
+            hash: hash.to_string(),
+            author: "Alice".to_string(),
+            timestamp,
+            author_timestamp,
+            lines_added: lines,
+            lines_removed: 0,
+            files: 1,
+        }
+    }
+
+    fn analyzer(sizes: &[usize]) -> CommitSizeAnalyzer {
+        let mut analyzer = CommitSizeAnalyzer::new(CommitSizeConfig::default());
+        for (index, &lines) in sizes.iter().enumerate() {
+            let time = 1_700_000_000 + index as i64 * DAY;
+            analyzer.add_commit(commit(&format!("c{index}"), lines, time, time));
+        }
+        analyzer
+    }
+
+    #[test]
+    fn test_distribution_and_percentiles() {
+        let analyzer = analyzer(&[0, 5, 10, 11, 40, 120, 300, 900, 4000, 9000]);
+        let buckets = analyzer.distribution();
+        let counts: Vec<(&str, usize)> = buckets.iter().map(|bucket| (bucket.range.as_str(), bucket.commits)).collect();
+        assert_eq!(counts, [("0", 1), ("1-10", 2), ("11-50", 2), ("51-200", 1), ("201-1000", 2), ("1001-5000", 1), ("5001+", 1)]);
+        assert_eq!(buckets[6].lines, 9000);
+
+        let percentiles = analyzer.percentiles();
+        assert_eq!((percentiles.p50, percentiles.p90, percentiles.p99, percentiles.max), (40, 4000, 9000, 9000));
+        assert_eq!(percentiles.mean, 1438.6);
+        assert_eq!(CommitSizeAnalyzer::default().percentiles(), CommitSizePercentiles::default());
+    }
+
+    #[test]
+    fn test_giant_commits() {
+        let mut sizes = vec![20; 40];
+        sizes.extend([60, 1500, 25_000]);
+        let anomalies = analyzer(&sizes).anomalies();
+        let giants: Vec<(&str, usize)> = anomalies.iter().map(|anomaly| (anomaly.hash.as_str(), anomaly.lines)).collect();
+        assert_eq!(giants, [("c42", 25_000), ("c41", 1500)]);
+        assert!(anomalies.iter().all(|anomaly| anomaly.kind == AnomalyKind::GiantCommit));
+
+        // Small histories are held to the minimum size
+        assert!(analyzer(&[1, 2, 900]).anomalies().is_empty());
+    }
+
+    #[test]
+    fn test_rewritten_epochs() {
+        let mut analyzer = analyzer(&[]);
+        let rewritten_at = 1_700_000_000 + 90 * DAY;
+        for index in 0..6 {
+            // Authored a day apart months earlier, recommitted a minute apart
+            analyzer.add_commit(commit(&format!("r{index}"), 10, rewritten_at + index * 60, 1_700_000_000 + index * DAY));
+        }
+        // Committed promptly, so not part of the epoch
+        analyzer.add_commit(commit("fresh", 10, rewritten_at + 400, rewritten_at + 300));
+        // Delayed, but an hour after the rest
+        analyzer.add_commit(commit("late", 10, rewritten_at + 3600 * 3, 1_700_000_000));
+
+        let epochs = analyzer.anomalies();
+        assert_eq!(epochs.len(), 1);
+        assert_eq!(epochs[0].kind, AnomalyKind::RewrittenEpoch);
+        assert_eq!((epochs[0].hash.as_str(), epochs[0].commits, epochs[0].lines), ("r5", 6, 60));
+        assert_eq!(epochs[0].detail, "6 commits authored 2023-11-14 to 2023-11-19 were recommitted within 6 minutes");
+    }
+}
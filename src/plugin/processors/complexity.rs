@@ -9,6 +9,7 @@ use crate::scanner::async_engine::processors::{EventProcessor, ProcessorStats};
 use crate::scanner::async_engine::shared_state::SharedProcessorState;
 use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
 use crate::plugin::PluginResult;
+use crate::plugin::memory::HeapSize;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -43,6 +44,55 @@ impl ComplexityMetrics {
         }
     }
 
+    /// Measure complexity from a file's source text
+    ///
+    /// Branch keywords and boolean operators count as decision points, each
+    /// weighted by its nesting for cognitive complexity. Nesting follows braces,
+    /// or indentation in Python. Comments and strings are not parsed, so the
+    /// result is an estimate, but one that follows the code rather than its size.
+    pub fn from_content(file_path: String, content: &str) -> Self {
+        let mut metrics = Self::new(file_path);
+        metrics.file_size_bytes = content.len() as u64;
+        let indented = metrics.file_path.ends_with(".py");
+
+        let mut decisions = 0u32;
+        let mut cognitive = 0u32;
+        let mut depth = 0u32;
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            metrics.lines_of_code += 1;
+            if indented {
+                depth = (line.len() - line.trim_start().len()) as u32 / 4;
+            }
+
+            let mut line_decisions = (trimmed.matches("&&").count() + trimmed.matches("||").count()) as u32;
+            for word in trimmed.split(|c: char| !c.is_alphanumeric() && c != '_') {
+                match word {
+                    "if" | "elif" | "for" | "while" | "case" | "catch" | "except" => line_decisions += 1,
+                    "fn" | "def" | "function" | "func" => metrics.function_count += 1,
+                    "class" | "struct" | "impl" | "interface" => metrics.class_count += 1,
+                    _ => {}
+                }
+            }
+            decisions += line_decisions;
+            cognitive += line_decisions * (1 + depth);
+
+            if !indented {
+                let opened = trimmed.matches('{').count() as u32;
+                let closed = trimmed.matches('}').count() as u32;
+                depth = (depth + opened).saturating_sub(closed);
+            }
+            metrics.nesting_depth = metrics.nesting_depth.max(depth);
+        }
+
+        metrics.cyclomatic_complexity = 1.0 + decisions as f64;
+        metrics.cognitive_complexity = cognitive as f64;
+        metrics
+    }
+
     /// Calculate overall complexity score
     pub fn complexity_score(&self) -> f64 {
         // Weighted combination of different complexity metrics
@@ -314,6 +364,12 @@ fn estimate_nesting_depth(metrics: &ComplexityMetrics, language: &str) -> u32 {
     base_depth.min(10).max(1) // Cap at reasonable maximum
 }
 
+impl HeapSize for ComplexityMetrics {
+    fn heap_size(&self) -> usize {
+        self.file_path.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,4 +445,21 @@ mod tests {
         assert_eq!(processor.file_complexities.len(), 1);
         assert!(processor.file_complexities.contains_key("test.rs"));
     }
+
+    #[test]
+    fn test_complexity_from_content() {
+        let rust = "fn check(x: u32) -> bool {\n    if x > 1 && x < 10 {\n        for i in 0..x {\n            if i == 3 { return true; }\n        }\n    }\n\n    false\n}\n";
+        let metrics = ComplexityMetrics::from_content("check.rs".to_string(), rust);
+        assert_eq!(metrics.lines_of_code, 8);
+        assert_eq!(metrics.function_count, 1);
+        assert_eq!(metrics.cyclomatic_complexity, 5.0);
+        // Decision points are weighted by the depth they appear at
+        assert_eq!(metrics.cognitive_complexity, 2.0 * 2.0 + 3.0 + 4.0);
+        assert_eq!(metrics.nesting_depth, 3);
+
+        let python = "def check(x):\n    if x > 1:\n        return True\n    return False\n";
+        let metrics = ComplexityMetrics::from_content("check.py".to_string(), python);
+        assert_eq!(metrics.cyclomatic_complexity, 2.0);
+        assert_eq!(metrics.nesting_depth, 2);
+    }
 }
@@ -9,6 +9,7 @@ use crate::scanner::async_engine::processors::{EventProcessor, ProcessorStats};
 use crate::scanner::async_engine::shared_state::SharedProcessorState;
 use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
 use crate::plugin::PluginResult;
+use crate::plugin::data_export::{Finding, FindingSeverity};
 use crate::plugin::processors::change_frequency::{FileChangeStats, TimeWindow};
 use crate::plugin::processors::complexity::ComplexityMetrics;
 use async_trait::async_trait;
@@ -106,6 +107,16 @@ impl DebtLevel {
             _ => DebtLevel::Critical,
         }
     }
+
+    /// Severity of the finding raised for the level, none below medium
+    pub fn finding_severity(&self) -> Option<FindingSeverity> {
+        match self {
+            DebtLevel::Minimal | DebtLevel::Low => None,
+            DebtLevel::Medium => Some(FindingSeverity::Medium),
+            DebtLevel::High => Some(FindingSeverity::High),
+            DebtLevel::Critical => Some(FindingSeverity::Critical),
+        }
+    }
 }
 
 impl FileDebtAssessment {
@@ -140,6 +151,22 @@ impl FileDebtAssessment {
         }
     }
 
+    /// The assessment as a finding, unless the debt is below medium
+    pub fn to_finding(&self) -> Option<Finding> {
+        let severity = self.debt_level.finding_severity()?;
+        let mut message = format!(
+            "Technical debt score {:.1}, about {:.0} hours to address",
+            self.debt_score, self.estimated_hours
+        );
+        if let Some(action) = self.priority_actions.first() {
+            message.push_str(&format!(": {}", action));
+        }
+        Some(Finding::new("technical-debt", severity, "technical-debt", message)
+            .with_location(self.file_path.clone())
+            .with_metric("score", self.debt_score)
+            .with_metric("hours", self.estimated_hours))
+    }
+
     fn calculate_component_scores(
         complexity_metrics: &ComplexityMetrics,
         change_stats: Option<&FileChangeStats>,
@@ -202,10 +229,7 @@ impl FileDebtAssessment {
                     return 50.0; // Default for unknown age
                 }
 
-                let now = SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs() as i64;
+                let now = crate::clock::now_seconds();
 
                 let days_since_change = (now - stats.last_changed) / (24 * 60 * 60);
                 
@@ -385,7 +409,7 @@ impl DebtAssessmentProcessor {
     /// Get debt assessments sorted by score
     pub fn get_top_debt_files(&self, limit: usize) -> Vec<&FileDebtAssessment> {
         let mut assessments: Vec<&FileDebtAssessment> = self.debt_assessments.values().collect();
-        assessments.sort_by(|a, b| b.debt_score.partial_cmp(&a.debt_score).unwrap());
+        assessments.sort_by(|a, b| b.debt_score.total_cmp(&a.debt_score).then_with(|| a.file_path.cmp(&b.file_path)));
         assessments.into_iter().take(limit).collect()
     }
 
@@ -1,16 +1,22 @@
 //! Duplication Detector Processor
 //! 
-//! Event-driven processor that detects code duplication by analyzing
-//! file content patterns and similarities. This processor can be used
-//! by any plugin that needs duplication analysis.
+//! Event-driven processor that detects code duplication by matching the
+//! content fingerprints the scanner streams for each file. This processor
+//! can be used by any plugin that needs duplication analysis; the content is
+//! read and hashed once in the scanner however many plugins use it.
+//!
+//! Blocks sharing a fingerprint are extended for as long as the following
+//! fingerprints also match, so a copied function is reported as one block
+//! rather than as every window of it.
 
 use crate::scanner::async_engine::events::RepositoryEvent;
 use crate::scanner::async_engine::processors::{EventProcessor, ProcessorStats};
 use crate::scanner::async_engine::shared_state::SharedProcessorState;
-use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
+use crate::scanner::messages::{BlockFingerprint, ScanMessage, MessageData, MessageHeader};
+use crate::plugin::memory::HeapSize;
 use crate::plugin::PluginResult;
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use log::debug;
 use serde::{Serialize, Deserialize};
@@ -131,6 +137,28 @@ impl CodeBlock {
             .count()
     }
 
+    /// A block matched by fingerprints, spanning from the first fingerprint to the last
+    ///
+    /// Blocks matched together share `hash`, so they are identical by
+    /// `similarity`. The scanner reports the tokens of each overlapping window,
+    /// not of each line, so the block's tokens are estimated from their density.
+    pub fn from_fingerprints(file_path: String, hash: u64, fingerprints: &[BlockFingerprint]) -> Self {
+        let start_line = fingerprints.first().map_or(1, |fingerprint| fingerprint.start_line as usize);
+        let end_line = fingerprints.last().map_or(start_line, |fingerprint| fingerprint.end_line as usize);
+        let density = fingerprints.iter()
+            .map(|fingerprint| fingerprint.tokens as f64 / (fingerprint.end_line - fingerprint.start_line + 1) as f64)
+            .sum::<f64>() / fingerprints.len().max(1) as f64;
+
+        Self {
+            file_path,
+            start_line,
+            end_line,
+            content_hash: format!("{:016x}", hash),
+            normalized_content: String::new(),
+            token_count: (density * (end_line - start_line + 1) as f64).round() as usize,
+        }
+    }
+
     pub fn line_count(&self) -> usize {
         self.end_line - self.start_line + 1
     }
@@ -293,10 +321,24 @@ impl DuplicationSummary {
     }
 }
 
+/// The fingerprints of one file's content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFingerprints {
+    /// Lines in the file
+    pub lines: usize,
+    pub fingerprints: Vec<BlockFingerprint>,
+}
+
+impl HeapSize for FileFingerprints {
+    fn heap_size(&self) -> usize {
+        self.fingerprints.capacity() * std::mem::size_of::<BlockFingerprint>()
+    }
+}
+
 /// Duplication Detector Processor - can be used by any plugin
 pub struct DuplicationDetectorProcessor {
     config: DuplicationConfig,
-    file_contents: HashMap<String, String>,
+    files: BTreeMap<String, FileFingerprints>,
     duplicate_groups: Vec<DuplicateGroup>,
     stats: ProcessorStats,
     shared_state: Option<Arc<SharedProcessorState>>,
@@ -306,7 +348,7 @@ impl DuplicationDetectorProcessor {
     pub fn new() -> Self {
         Self {
             config: DuplicationConfig::default(),
-            file_contents: HashMap::new(),
+            files: BTreeMap::new(),
             duplicate_groups: Vec::new(),
             stats: ProcessorStats::default(),
             shared_state: None,
@@ -324,97 +366,133 @@ impl DuplicationDetectorProcessor {
         self.config.analyzed_extensions.contains(&extension)
     }
 
-    /// Detect duplicates in the collected file contents
-    pub fn detect_duplicates(&mut self) {
-        self.duplicate_groups.clear();
+    pub fn with_config(config: DuplicationConfig) -> Self {
+        Self {
+            config,
+            ..Self::new()
+        }
+    }
 
-        // Extract code blocks from all files
-        let mut all_blocks = Vec::new();
-        
-        for (file_path, content) in &self.file_contents {
-            if self.should_analyze_file(file_path) {
-                let blocks = self.extract_code_blocks(file_path, content);
-                all_blocks.extend(blocks);
-            }
+    /// Record the fingerprints of a file's content, as streamed by the scanner
+    pub fn add_fingerprints(&mut self, path: &str, lines: usize, fingerprints: &[BlockFingerprint]) {
+        if self.should_analyze_file(path) {
+            self.files.insert(path.to_string(), FileFingerprints { lines, fingerprints: fingerprints.to_vec() });
         }
+    }
+
+    /// Detect duplicates among the recorded fingerprints
+    pub fn detect_duplicates(&mut self) {
+        self.duplicate_groups.clear();
 
         // Group similar blocks
-        let groups = self.group_similar_blocks(all_blocks);
-        
+        let groups = self.group_matching_blocks();
+
         // Filter groups by minimum size and similarity
         self.duplicate_groups = groups.into_iter()
             .filter(|g| g.blocks.len() >= 2)
             .filter(|g| g.similarity_score >= self.config.similarity_threshold)
             .filter(|g| g.total_lines >= self.config.min_block_size)
-            .take(self.config.max_groups)
             .collect();
 
-        // Sort by impact score
-        self.duplicate_groups.sort_by(|a, b| b.impact_score.partial_cmp(&a.impact_score).unwrap());
+        // Keep the groups with the highest impact
+        self.duplicate_groups.sort_by(|a, b| b.impact_score.total_cmp(&a.impact_score).then_with(|| a.id.cmp(&b.id)));
+        self.duplicate_groups.truncate(self.config.max_groups);
 
         debug!("Detected {} duplicate groups", self.duplicate_groups.len());
     }
 
-    fn extract_code_blocks(&self, file_path: &str, content: &str) -> Vec<CodeBlock> {
-        let lines: Vec<&str> = content.lines().collect();
-        let mut blocks = Vec::new();
-
-        // Extract blocks of minimum size
-        for start in 0..lines.len() {
-            for end in (start + self.config.min_block_size - 1)..lines.len().min(start + 50) {
-                let block_content = lines[start..=end].join("\n");
-                let block = CodeBlock::new(
-                    file_path.to_string(),
-                    start + 1, // 1-based line numbers
-                    end + 1,
-                    &block_content,
-                    &self.config,
-                );
-                blocks.push(block);
+    /// Groups of blocks sharing fingerprints, each extended as far as all its blocks match
+    ///
+    /// Each fingerprint is claimed by at most one group, so the windows of a
+    /// copied block do not each become a group, and blocks overlapping within
+    /// one file (repetitive content) count once.
+    fn group_matching_blocks(&self) -> Vec<DuplicateGroup> {
+        let mut locations: HashMap<u64, Vec<(&str, usize)>> = HashMap::new();
+        for (path, file) in &self.files {
+            for (index, fingerprint) in file.fingerprints.iter().enumerate() {
+                locations.entry(fingerprint.hash).or_default().push((path.as_str(), index));
             }
         }
+        let fingerprints = |path: &str| &self.files[path].fingerprints;
 
-        blocks
-    }
-
-    fn group_similar_blocks(&self, blocks: Vec<CodeBlock>) -> Vec<DuplicateGroup> {
+        let mut claimed: HashSet<(&str, usize)> = HashSet::new();
         let mut groups = Vec::new();
-        let mut used_blocks = std::collections::HashSet::new();
-
-        for (i, block) in blocks.iter().enumerate() {
-            if used_blocks.contains(&i) {
-                continue;
-            }
-
-            let mut similar_blocks = vec![block.clone()];
-            used_blocks.insert(i);
-
-            // Find similar blocks
-            for (j, other_block) in blocks.iter().enumerate().skip(i + 1) {
-                if used_blocks.contains(&j) {
+        for (path, file) in &self.files {
+            for (index, fingerprint) in file.fingerprints.iter().enumerate() {
+                if claimed.contains(&(path.as_str(), index)) {
+                    continue;
+                }
+                // Copies of the block elsewhere, not overlapping each other within a file
+                let mut starts: Vec<(&str, usize)> = Vec::new();
+                for &(other_path, other_index) in &locations[&fingerprint.hash] {
+                    let other = &fingerprints(other_path)[other_index];
+                    let overlaps = starts.iter().any(|&(start_path, start_index)| {
+                        start_path == other_path && fingerprints(start_path)[start_index].end_line >= other.start_line
+                    });
+                    if !claimed.contains(&(other_path, other_index)) && !overlaps {
+                        starts.push((other_path, other_index));
+                    }
+                }
+                if starts.len() < 2 {
                     continue;
                 }
 
-                if block.similarity(other_block) >= self.config.similarity_threshold {
-                    similar_blocks.push(other_block.clone());
-                    used_blocks.insert(j);
+                // Extend the copies together while their next fingerprints still match
+                let mut length = 1;
+                'extend: while let Some(next) = fingerprints(starts[0].0).get(starts[0].1 + length) {
+                    for (position, &(start_path, start_index)) in starts.iter().enumerate() {
+                        let Some(candidate) = fingerprints(start_path).get(start_index + length) else {
+                            break 'extend;
+                        };
+                        let runs_into_copy = starts.get(position + 1).is_some_and(|&(next_path, next_index)| {
+                            next_path == start_path && candidate.end_line >= fingerprints(next_path)[next_index].start_line
+                        });
+                        if candidate.hash != next.hash || runs_into_copy || claimed.contains(&(start_path, start_index + length)) {
+                            break 'extend;
+                        }
+                    }
+                    length += 1;
                 }
-            }
 
-            if similar_blocks.len() >= 2 {
-                groups.push(DuplicateGroup::new(similar_blocks));
+                let blocks = starts.iter()
+                    .map(|&(start_path, start_index)| {
+                        claimed.extend((start_index..start_index + length).map(|claimed_index| (start_path, claimed_index)));
+                        CodeBlock::from_fingerprints(
+                            start_path.to_string(),
+                            fingerprint.hash,
+                            &fingerprints(start_path)[start_index..start_index + length],
+                        )
+                    })
+                    .collect();
+                groups.push(DuplicateGroup::new(blocks));
             }
         }
 
         groups
     }
 
+    /// The duplicate groups found by the last detection, highest impact first
+    pub fn duplicate_groups(&self) -> &[DuplicateGroup] {
+        &self.duplicate_groups
+    }
+
+    /// Impact of duplication on each file: its share of the impact of every group it is in
+    pub fn file_impacts(&self) -> HashMap<String, f64> {
+        let mut impacts = HashMap::new();
+        for group in &self.duplicate_groups {
+            let share = group.impact_score / group.blocks.len() as f64;
+            for block in &group.blocks {
+                *impacts.entry(block.file_path.clone()).or_insert(0.0) += share;
+            }
+        }
+        impacts
+    }
 
     /// Generate duplication summary
     pub fn generate_summary(&self) -> DuplicationSummary {
-        let total_files = self.file_contents.len();
-        let total_lines = self.file_contents.values()
-            .map(|content| content.lines().count())
+        let total_files = self.files.len();
+        let total_lines = self.files.values()
+            .map(|file| file.lines)
             .sum();
 
         DuplicationSummary::new(total_files, total_lines, &self.duplicate_groups)
@@ -464,18 +542,8 @@ impl EventProcessor for DuplicationDetectorProcessor {
         Ok(())
     }
 
-    async fn process_event(&mut self, event: &RepositoryEvent) -> PluginResult<Vec<ScanMessage>> {
-        match event {
-            RepositoryEvent::FileChanged { file_path, .. } => {
-                if self.should_analyze_file(file_path) {
-                    // In a full implementation, we would get the actual file content here
-                    // For now, we'll store a placeholder
-                    let placeholder_content = format!("// Content of {}\n// This would be actual file content", file_path);
-                    self.file_contents.insert(file_path.clone(), placeholder_content);
-                }
-            }
-            _ => {}
-        }
+    async fn process_event(&mut self, _event: &RepositoryEvent) -> PluginResult<Vec<ScanMessage>> {
+        // Content arrives as fingerprint messages, see add_fingerprints
         self.stats.events_processed += 1;
         Ok(vec![])
     }
@@ -518,7 +586,7 @@ mod tests {
         let processor = DuplicationDetectorProcessor::new();
         assert_eq!(processor.name(), "duplication_detector");
         // Processor no longer advertises supported modes
-        assert!(processor.file_contents.is_empty());
+        assert!(processor.files.is_empty());
         assert!(processor.duplicate_groups.is_empty());
     }
 
@@ -602,41 +670,55 @@ mod tests {
         assert_eq!(summary.files_with_duplicates, 2);
     }
 
+    fn add_file(processor: &mut DuplicationDetectorProcessor, path: &str, content: &str) {
+        let config = crate::scanner::fingerprints::FingerprintConfig::default();
+        processor.add_fingerprints(path, content.lines().count(), &crate::scanner::fingerprints::fingerprint(content, &config));
+    }
+
+    const COPIED: &str = "fn parse(input: &str) -> Vec<u32> {\n    let mut values = Vec::new();\n    for part in input.split(',') {\n        if let Ok(value) = part.trim().parse() {\n            values.push(value);\n        }\n    }\n    values.sort();\n    values.dedup();\n    values\n}\n";
+
     #[tokio::test]
-    async fn test_file_processing() {
+    async fn test_copied_block_is_one_group() {
         let mut processor = DuplicationDetectorProcessor::new();
-        processor.initialize().await.unwrap();
-
-        let event = RepositoryEvent::FileChanged {
-            file_path: "src/main.rs".to_string(),
-            change_data: crate::scanner::async_engine::events::FileChangeData {
-                change_type: crate::scanner::async_engine::events::ChangeType::Modified,
-                old_path: Some("src/main.rs".to_string()),
-                new_path: "src/main.rs".to_string(),
-                insertions: 10,
-                deletions: 2,
-                is_binary: false,
-            },
-            commit_context: crate::scanner::async_engine::events::CommitInfo {
-                hash: "abc123".to_string(),
-                short_hash: "abc123".to_string(),
-                author_name: "Test Author".to_string(),
-                author_email: "test@example.com".to_string(),
-                committer_name: "Test Author".to_string(),
-                committer_email: "test@example.com".to_string(),
-                timestamp: std::time::SystemTime::now(),
-                message: "Test commit".to_string(),
-                parent_hashes: vec![],
-                changed_files: vec![],
-                insertions: 0,
-                deletions: 0,
-            },
-        };
+        add_file(&mut processor, "src/a.rs", &format!("use std::io;\n\n{}", COPIED));
+        add_file(&mut processor, "src/b.rs", &format!("// Copied from a.rs\n{}\nfn other() {{}}\n", COPIED.replace("    ", "  ")));
+        add_file(&mut processor, "src/c.rs", "fn main() {\n    println!(\"unrelated\");\n}\n");
+        add_file(&mut processor, "README.md", COPIED);
+        processor.detect_duplicates();
+
+        // The whole function is one group, not one per window, and the markdown file is not analysed
+        let groups = processor.duplicate_groups();
+        assert_eq!(groups.len(), 1);
+        let lines: Vec<(&str, usize, usize)> = groups[0].blocks.iter()
+            .map(|block| (block.file_path.as_str(), block.start_line, block.end_line))
+            .collect();
+        assert_eq!(lines, [("src/a.rs", 3, 13), ("src/b.rs", 2, 12)]);
+        assert_eq!(groups[0].similarity_score, 1.0);
+        assert!(groups[0].blocks[0].token_count > groups[0].blocks[0].line_count());
+
+        let impacts = processor.file_impacts();
+        assert_eq!(impacts.len(), 2);
+        assert!((impacts["src/a.rs"] - groups[0].impact_score / 2.0).abs() < 1e-9);
 
-        let messages = processor.process_event(&event).await.unwrap();
-        assert!(messages.is_empty()); // No messages during processing
+        let summary = processor.generate_summary();
+        assert_eq!(summary.total_files_analyzed, 3);
+        assert_eq!(summary.files_with_duplicates, 2);
+    }
 
-        assert_eq!(processor.file_contents.len(), 1);
-        assert!(processor.file_contents.contains_key("src/main.rs"));
+    #[tokio::test]
+    async fn test_repetitive_content_within_a_file() {
+        let mut processor = DuplicationDetectorProcessor::new();
+        add_file(&mut processor, "src/counter.rs", &"value += 1;\n".repeat(8));
+        processor.detect_duplicates();
+
+        // Overlapping windows of the same lines are not copies of each other
+        assert!(processor.duplicate_groups().is_empty());
+
+        let mut processor = DuplicationDetectorProcessor::new();
+        add_file(&mut processor, "src/twice.rs", &format!("{}{}", COPIED, COPIED));
+        processor.detect_duplicates();
+        let groups = processor.duplicate_groups();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].blocks.iter().map(|block| block.start_line).collect::<Vec<_>>(), [1, 12]);
     }
 }
@@ -253,7 +253,7 @@ impl FormatStatistics {
         let mut formats: Vec<(String, usize)> = self.format_distribution.iter()
             .map(|(k, v)| (k.clone(), *v))
             .collect();
-        formats.sort_by(|a, b| b.1.cmp(&a.1));
+        formats.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
         formats.into_iter().take(limit).collect()
     }
 
@@ -262,7 +262,7 @@ impl FormatStatistics {
         let mut languages: Vec<(String, usize)> = self.language_distribution.iter()
             .map(|(k, v)| (k.clone(), *v))
             .collect();
-        languages.sort_by(|a, b| b.1.cmp(&a.1));
+        languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
         languages.into_iter().take(limit).collect()
     }
 
@@ -9,6 +9,7 @@ use crate::scanner::async_engine::processors::{EventProcessor, ProcessorStats};
 use crate::scanner::async_engine::shared_state::SharedProcessorState;
 use crate::scanner::messages::{ScanMessage, MessageData, MessageHeader};
 use crate::plugin::PluginResult;
+use crate::plugin::data_export::{Finding, FindingSeverity};
 use crate::plugin::processors::change_frequency::{FileChangeStats, TimeWindow};
 use crate::plugin::processors::complexity::ComplexityMetrics;
 use async_trait::async_trait;
@@ -25,6 +26,7 @@ pub struct HotspotMetrics {
     pub hotspot_score: f64,
     pub complexity_score: f64,
     pub frequency_score: f64,
+    pub author_score: f64,
     pub risk_level: RiskLevel,
     pub recommendations: Vec<String>,
     pub change_count: u32,
@@ -35,19 +37,29 @@ pub struct HotspotMetrics {
 }
 
 impl HotspotMetrics {
+    /// Score a file from its complexity and change history
+    ///
+    /// Each factor is scaled to 0-100, reaching 50 at a typical "busy" value,
+    /// and the hotspot score is their average weighted by the configuration.
+    /// Scores are absolute, so a file's score does not depend on the others scanned.
     pub fn new(
         file_path: String,
         complexity_metrics: &ComplexityMetrics,
         change_stats: &FileChangeStats,
-        time_window: TimeWindow,
+        config: &HotspotConfig,
     ) -> Self {
-        let complexity_score = complexity_metrics.complexity_score();
-        let frequency_score = change_stats.frequency_score(time_window);
-        
-        // Calculate hotspot score as weighted combination
-        let complexity_weight = 0.6;
-        let frequency_weight = 0.4;
-        let hotspot_score = (complexity_score * complexity_weight) + (frequency_score * frequency_weight);
+        let complexity_score = scale(complexity_metrics.complexity_score(), 10.0);
+        let frequency_score = scale(change_stats.frequency_score(config.time_window), 10.0);
+        let author_score = scale(change_stats.author_count as f64, 3.0);
+
+        let total_weight = config.complexity_weight + config.frequency_weight + config.author_weight;
+        let hotspot_score = if total_weight > 0.0 {
+            (complexity_score * config.complexity_weight
+                + frequency_score * config.frequency_weight
+                + author_score * config.author_weight) / total_weight
+        } else {
+            0.0
+        };
         
         let risk_level = Self::calculate_risk_level(hotspot_score);
         let recommendations = Self::generate_recommendations(&risk_level, complexity_metrics, change_stats);
@@ -57,6 +69,7 @@ impl HotspotMetrics {
             hotspot_score,
             complexity_score,
             frequency_score,
+            author_score,
             risk_level,
             recommendations,
             change_count: change_stats.change_count,
@@ -69,9 +82,9 @@ impl HotspotMetrics {
 
     fn calculate_risk_level(hotspot_score: f64) -> RiskLevel {
         match hotspot_score {
-            s if s < 5.0 => RiskLevel::Low,
-            s if s < 15.0 => RiskLevel::Medium,
-            s if s < 30.0 => RiskLevel::High,
+            s if s < 20.0 => RiskLevel::Low,
+            s if s < 40.0 => RiskLevel::Medium,
+            s if s < 60.0 => RiskLevel::High,
             _ => RiskLevel::Critical,
         }
     }
@@ -143,15 +156,53 @@ impl RiskLevel {
             RiskLevel::Critical => "critical",
         }
     }
+
+    /// Severity of the finding raised for a hotspot, none for low-risk files
+    pub fn finding_severity(&self) -> Option<FindingSeverity> {
+        match self {
+            RiskLevel::Low => None,
+            RiskLevel::Medium => Some(FindingSeverity::Medium),
+            RiskLevel::High => Some(FindingSeverity::High),
+            RiskLevel::Critical => Some(FindingSeverity::Critical),
+        }
+    }
+}
+
+impl HotspotMetrics {
+    /// The hotspot as a finding, unless the file is low risk
+    pub fn to_finding(&self) -> Option<Finding> {
+        let severity = self.risk_level.finding_severity()?;
+        Some(Finding::new(
+            "hotspot",
+            severity,
+            "maintainability",
+            format!(
+                "Complex file changed {} times by {} authors (hotspot score {:.1})",
+                self.change_count, self.author_count, self.hotspot_score
+            ),
+        )
+        .with_location(self.file_path.clone())
+        .with_metric("score", self.hotspot_score)
+        .with_metric("changes", self.change_count as f64)
+        .with_metric("authors", self.author_count as f64)
+        .with_metric("cyclomatic", self.cyclomatic_complexity))
+    }
 }
 
 /// Configuration for hotspot detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotspotConfig {
+    /// Minimum raw complexity score for a file to be considered
     pub complexity_threshold: f64,
+    /// Minimum raw change frequency score for a file to be considered
     pub frequency_threshold: f64,
+    /// Weight of the complexity score in the hotspot score
     pub complexity_weight: f64,
+    /// Weight of the change frequency score in the hotspot score
     pub frequency_weight: f64,
+    /// Weight of the author count score in the hotspot score
+    pub author_weight: f64,
+    /// Maximum number of hotspots to report
     pub max_hotspots: usize,
     pub time_window: TimeWindow,
 }
@@ -161,14 +212,21 @@ impl Default for HotspotConfig {
         Self {
             complexity_threshold: 10.0,
             frequency_threshold: 5.0,
-            complexity_weight: 0.6,
+            complexity_weight: 0.4,
             frequency_weight: 0.4,
+            author_weight: 0.2,
             max_hotspots: 20,
             time_window: TimeWindow::Quarter,
         }
     }
 }
 
+/// Scale a non-negative factor to 0-100, giving 50 at `midpoint`
+fn scale(value: f64, midpoint: f64) -> f64 {
+    let value = value.max(0.0);
+    100.0 * value / (value + midpoint)
+}
+
 /// Hotspot Processor - can be used by any plugin
 /// 
 /// This processor combines data from complexity and change frequency processors
@@ -221,7 +279,7 @@ impl HotspotProcessor {
                         file_path.clone(),
                         complexity,
                         changes,
-                        self.config.time_window,
+                        &self.config,
                     );
                     
                     self.hotspots.insert(file_path.clone(), hotspot);
@@ -232,13 +290,20 @@ impl HotspotProcessor {
         debug!("Identified {} hotspots", self.hotspots.len());
     }
 
-    /// Get the top hotspots sorted by score
+    /// Get the top hotspots sorted by score, ties broken by path so rankings are stable
     pub fn get_top_hotspots(&self, limit: usize) -> Vec<&HotspotMetrics> {
         let mut hotspots: Vec<&HotspotMetrics> = self.hotspots.values().collect();
-        hotspots.sort_by(|a, b| b.hotspot_score.partial_cmp(&a.hotspot_score).unwrap());
+        hotspots.sort_by(|a, b| {
+            b.hotspot_score.total_cmp(&a.hotspot_score).then_with(|| a.file_path.cmp(&b.file_path))
+        });
         hotspots.into_iter().take(limit).collect()
     }
 
+    /// Configuration the hotspots were scored with
+    pub fn config(&self) -> &HotspotConfig {
+        &self.config
+    }
+
     /// Get hotspots by risk level
     pub fn get_hotspots_by_risk(&self, risk_level: RiskLevel) -> Vec<&HotspotMetrics> {
         self.hotspots
@@ -398,7 +463,7 @@ mod tests {
             "test.rs".to_string(),
             &complexity,
             &change_stats,
-            TimeWindow::Month,
+            &HotspotConfig::default(),
         );
 
         assert_eq!(hotspot.file_path, "test.rs");
@@ -414,8 +479,9 @@ mod tests {
         // Low risk
         complexity.cyclomatic_complexity = 2.0;
         change_stats.change_count = 1;
-        let hotspot = HotspotMetrics::new("test.rs".to_string(), &complexity, &change_stats, TimeWindow::Month);
+        let hotspot = HotspotMetrics::new("test.rs".to_string(), &complexity, &change_stats, &HotspotConfig::default());
         assert_eq!(hotspot.risk_level, RiskLevel::Low);
+        assert!(hotspot.to_finding().is_none());
 
         // High risk
         complexity.cyclomatic_complexity = 25.0;
@@ -425,8 +491,11 @@ mod tests {
             .duration_since(std::time::SystemTime::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs() as i64 - (3 * 24 * 60 * 60); // 3 days ago
-        let hotspot = HotspotMetrics::new("test.rs".to_string(), &complexity, &change_stats, TimeWindow::Month);
+        let hotspot = HotspotMetrics::new("test.rs".to_string(), &complexity, &change_stats, &HotspotConfig::default());
         assert!(matches!(hotspot.risk_level, RiskLevel::High | RiskLevel::Critical));
+        let finding = hotspot.to_finding().unwrap();
+        assert!(finding.severity >= FindingSeverity::High);
+        assert_eq!(finding.location.as_deref(), Some("test.rs"));
     }
 
     #[tokio::test]
@@ -459,11 +528,48 @@ mod tests {
         let mut change_stats = FileChangeStats::new("test1.rs".to_string());
         change_stats.change_count = 15;
         
-        let hotspot = HotspotMetrics::new("test1.rs".to_string(), &complexity, &change_stats, TimeWindow::Month);
+        let hotspot = HotspotMetrics::new("test1.rs".to_string(), &complexity, &change_stats, &HotspotConfig::default());
         processor.hotspots.insert("test1.rs".to_string(), hotspot);
 
         let summary = processor.generate_summary();
         assert_eq!(summary.total_hotspots, 1);
         assert!(summary.average_hotspot_score > 0.0);
     }
+
+    #[test]
+    fn test_hotspot_weights_and_ranking() {
+        let mut complex = ComplexityMetrics::new("complex.rs".to_string());
+        complex.cyclomatic_complexity = 40.0;
+        let mut complex_changes = FileChangeStats::new("complex.rs".to_string());
+        complex_changes.add_change(1, "alice".to_string(), "a".to_string());
+
+        let simple = ComplexityMetrics::new("busy.rs".to_string());
+        let mut busy_changes = FileChangeStats::new("busy.rs".to_string());
+        for (i, author) in ["alice", "bob", "carol", "dave"].iter().enumerate() {
+            for _ in 0..10 {
+                busy_changes.add_change(i as i64 + 1, author.to_string(), "b".to_string());
+            }
+        }
+
+        let complexity = HashMap::from([("complex.rs".to_string(), complex), ("busy.rs".to_string(), simple)]);
+        let changes = HashMap::from([("complex.rs".to_string(), complex_changes), ("busy.rs".to_string(), busy_changes)]);
+        let ranked = |config: HotspotConfig| {
+            let mut processor = HotspotProcessor::with_config(HotspotConfig { complexity_threshold: 0.0, ..config });
+            processor.analyze_hotspots(&complexity, &changes);
+            processor.get_top_hotspots(10).iter().map(|h| h.file_path.clone()).collect::<Vec<_>>()
+        };
+
+        let by_complexity = HotspotConfig { complexity_weight: 1.0, frequency_weight: 0.0, author_weight: 0.0, ..HotspotConfig::default() };
+        assert_eq!(ranked(by_complexity), vec!["complex.rs", "busy.rs"]);
+        let by_authors = HotspotConfig { complexity_weight: 0.0, frequency_weight: 0.0, author_weight: 1.0, ..HotspotConfig::default() };
+        assert_eq!(ranked(by_authors), vec!["busy.rs", "complex.rs"]);
+
+        // Component scores are on a 0-100 scale and the score is their weighted average
+        let mut processor = HotspotProcessor::with_config(HotspotConfig { complexity_threshold: 0.0, ..HotspotConfig::default() });
+        processor.analyze_hotspots(&complexity, &changes);
+        let busy = processor.get_top_hotspots(10).into_iter().find(|h| h.file_path == "busy.rs").unwrap();
+        assert!((busy.author_score - 100.0 * 4.0 / 7.0).abs() < 1e-9);
+        let expected = 0.4 * busy.complexity_score + 0.4 * busy.frequency_score + 0.2 * busy.author_score;
+        assert!((busy.hotspot_score - expected).abs() < 1e-9);
+    }
 }
@@ -10,6 +10,7 @@ pub mod hotspot;
 pub mod debt_assessment;
 pub mod format_detection;
 pub mod duplication_detector;
+pub mod commit_size;
 
 // Re-export processors for easier access
 pub use change_frequency::ChangeFrequencyProcessor;
@@ -2,16 +2,30 @@
 //! 
 //! Manages plugin registration, lifecycle, and lookups with notification subscription support.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use futures::FutureExt;
 use tokio::sync::RwLock;
-use crate::plugin::traits::{Plugin, PluginType};
+use tokio_util::sync::CancellationToken;
+use crate::plugin::traits::{ConsumerPreferences, Plugin, PluginType};
 use crate::plugin::error::{PluginError, PluginResult};
 use crate::plugin::context::PluginContext;
 use crate::plugin::subscriber::PluginSubscriber;
 use crate::plugin::priority_queue::PriorityQueue;
+use crate::plugin::resources::{ResourceBreaches, ResourceLimits, ResourceMonitor};
+use crate::plugin::memory::PluginMemory;
 use crate::notifications::{AsyncNotificationManager, ScanEvent};
-use crate::notifications::traits::{NotificationManager, Subscriber};
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::{NotificationManager, Publisher, Subscriber};
+use crate::notifications::typed_publishers::PluginEventPublisher;
+use crate::queue::{ConsumerStatistics, MessageRoute, QueueConsumer, QueueEvent};
+use crate::scanner::messages::ScanMessage;
+use crate::shutdown::ShutdownController;
+
+/// Default deadline for a single consumer plugin call
+pub const DEFAULT_PLUGIN_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Registry for managing plugin instances with notification support
 pub struct PluginRegistry {
@@ -32,6 +46,24 @@ pub struct PluginRegistry {
     
     /// Optional notification manager for automatic subscription
     notification_manager: Option<AsyncNotificationManager<ScanEvent>>,
+    
+    /// Deadline for each consumer plugin call (none to wait indefinitely)
+    execution_timeout: Option<Duration>,
+    
+    /// Cancellation tokens of plugins that have started consuming
+    cancellation_tokens: HashMap<String, CancellationToken>,
+    
+    /// Publisher for reporting timed out plugins and resource limit breaches
+    plugin_publisher: Option<Arc<PluginEventPublisher>>,
+    
+    /// Resource limits declared by consumer plugins and their breaches
+    resources: ResourceMonitor,
+    
+    /// Queue handles of plugins that have started consuming, for measuring their queue share
+    consumers: HashMap<String, QueueConsumer>,
+    
+    /// Shutdown state of the run; plugins keep draining and exporting once it is requested
+    shutdown: ShutdownController,
 }
 
 impl PluginRegistry {
@@ -44,6 +76,12 @@ impl PluginRegistry {
             states: HashMap::new(),
             subscribers: HashMap::new(),
             notification_manager: None,
+            execution_timeout: Some(DEFAULT_PLUGIN_TIMEOUT),
+            cancellation_tokens: HashMap::new(),
+            plugin_publisher: None,
+            resources: ResourceMonitor::new(),
+            consumers: HashMap::new(),
+            shutdown: ShutdownController::default(),
         }
     }
     
@@ -58,6 +96,12 @@ impl PluginRegistry {
             states: HashMap::new(),
             subscribers: HashMap::new(),
             notification_manager: Some((*notification_manager).clone()),
+            execution_timeout: Some(DEFAULT_PLUGIN_TIMEOUT),
+            cancellation_tokens: HashMap::new(),
+            plugin_publisher: None,
+            resources: ResourceMonitor::new(),
+            consumers: HashMap::new(),
+            shutdown: ShutdownController::default(),
         }
     }
     
@@ -161,6 +205,87 @@ impl PluginRegistry {
         Ok(())
     }
     
+    /// Activate a plugin after the plugins it depends on
+    ///
+    /// Returns the plugins activated, dependencies first. Nothing is activated
+    /// when a required dependency is missing or the dependencies form a cycle.
+    pub async fn activate_with_dependencies(&mut self, name: &str) -> PluginResult<Vec<String>> {
+        let order = self.activation_order(&[name.to_string()])?;
+        for plugin_name in &order {
+            self.activate_plugin(plugin_name).await?;
+        }
+        Ok(order)
+    }
+    
+    /// Resolve the order in which plugins must be activated
+    ///
+    /// The result contains the requested plugins and, transitively, every
+    /// registered plugin they depend on, with each plugin after its
+    /// dependencies. Optional dependencies are included only when registered.
+    pub fn activation_order(&self, names: &[String]) -> PluginResult<Vec<String>> {
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+        for name in names {
+            self.visit_dependencies(name, &mut Vec::new(), &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+    
+    /// Depth-first walk appending `name` to `order` after its dependencies
+    fn visit_dependencies(
+        &self,
+        name: &str,
+        path: &mut Vec<String>,
+        done: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> PluginResult<()> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|plugin| plugin == name) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(name.to_string());
+            return Err(PluginError::dependency_error(format!(
+                "Circular plugin dependency: {}",
+                cycle.join(" -> ")
+            )));
+        }
+        
+        let plugin = self.plugins.get(name)
+            .ok_or_else(|| PluginError::plugin_not_found(name))?;
+        let checker = crate::plugin::compatibility::VersionCompatibilityChecker::default();
+        
+        path.push(name.to_string());
+        for dep in &plugin.plugin_info().dependencies {
+            match self.plugins.get(&dep.name) {
+                Some(dep_plugin) => {
+                    let version = &dep_plugin.plugin_info().version;
+                    if !checker.version_matches(&dep.version_requirement, version) {
+                        return Err(PluginError::dependency_error(format!(
+                            "Plugin '{}' requires '{}' version {} but version {} is registered",
+                            name, dep.name, dep.version_requirement, version
+                        )));
+                    }
+                    self.visit_dependencies(&dep.name, path, done, order)?;
+                }
+                None if dep.optional => {
+                    log::debug!("Plugin '{}': optional dependency '{}' is not registered", name, dep.name);
+                }
+                None => {
+                    return Err(PluginError::dependency_error(format!(
+                        "Plugin '{}' depends on '{}', which is not registered",
+                        name, dep.name
+                    )));
+                }
+            }
+        }
+        path.pop();
+        
+        done.insert(name.to_string());
+        order.push(name.to_string());
+        Ok(())
+    }
+    
     /// Deactivate a plugin (stop processing events)
     pub async fn deactivate_plugin(&mut self, name: &str) -> PluginResult<()> {
         if !self.plugins.contains_key(name) {
@@ -321,6 +446,9 @@ impl PluginRegistry {
         
         let start = Instant::now();
         let poll_interval = std::time::Duration::from_millis(10);
+        if self.shutdown.is_requested() {
+            log::info!("Shutdown requested; waiting for plugins to finish and export partial results");
+        }
         
         loop {
             if self.are_all_active_plugins_idle() {
@@ -349,9 +477,240 @@ impl PluginRegistry {
         }
     }
     
-    /// Auto-activate plugins marked with active_by_default = true
+    /// Set the deadline for each consumer plugin call (`None` disables it)
+    pub fn set_execution_timeout(&mut self, timeout: Option<Duration>) {
+        self.execution_timeout = timeout;
+    }
+    
+    /// Set the shutdown controller shared with the scanner and queue
+    pub fn set_shutdown(&mut self, shutdown: ShutdownController) {
+        self.shutdown = shutdown;
+    }
+    
+    /// Deadline for each consumer plugin call
+    pub fn execution_timeout(&self) -> Option<Duration> {
+        self.execution_timeout
+    }
+    
+    /// Set the publisher used to report plugins that exceed their deadline or resource limits
+    pub fn set_plugin_publisher(&mut self, publisher: Arc<PluginEventPublisher>) {
+        self.plugin_publisher = Some(publisher);
+    }
+    
+    /// Cancellation token of a plugin, created when it starts consuming
+    pub fn cancellation_token(&self, name: &str) -> Option<CancellationToken> {
+        self.cancellation_tokens.get(name).cloned()
+    }
+    
+    /// Number of times a plugin exceeded the resource limits it declared
+    pub fn resource_breaches(&self, name: &str) -> ResourceBreaches {
+        self.resources.breaches(name)
+    }
+    
+    /// Memory retained by each plugin that has started consuming, by plugin name
+    /// 
+    /// The queue share is measured from the plugin's unacknowledged messages;
+    /// the state is as the plugin reports it.
+    pub async fn memory_usage(&self) -> Vec<PluginMemory> {
+        let mut usage = Vec::new();
+        for (name, consumer) in &self.consumers {
+            let Some(consumer_plugin) = self.plugins.get(name).and_then(|plugin| plugin.as_consumer_plugin()) else {
+                continue;
+            };
+            usage.push(PluginMemory {
+                plugin: name.clone(),
+                queue_bytes: consumer.backlog_memory().await,
+                state_bytes: consumer_plugin.state_memory().await,
+            });
+        }
+        usage.sort_by(|a, b| a.plugin.cmp(&b.plugin));
+        usage
+    }
+    
+    /// Queue progress of each consumer plugin, with the batch size it prefers
+    pub async fn queue_statistics(&self) -> Vec<ConsumerStatistics> {
+        let mut statistics = Vec::new();
+        for (name, consumer) in &self.consumers {
+            let mut consumer_statistics = consumer.statistics().await;
+            consumer_statistics.preferred_batch_size = self.plugins.get(name)
+                .and_then(|plugin| plugin.as_consumer_plugin())
+                .map(|consumer_plugin| consumer_plugin.consumer_preferences().preferred_batch_size);
+            statistics.push(consumer_statistics);
+        }
+        statistics.sort_by(|a, b| a.plugin_name.cmp(&b.plugin_name));
+        statistics
+    }
+    
+    /// Start a consumer plugin consuming from the queue, within the execution deadline
+    /// 
+    /// The plugin is handed a fresh cancellation token first, the resource
+    /// limits in its consumer preferences are recorded, and the consumer is
+    /// routed only the message types the plugin is interested in. Plugins that
+    /// are not consumers are left alone.
+    pub async fn start_consuming(&mut self, name: &str, consumer: QueueConsumer) -> PluginResult<()> {
+        let timeout = self.execution_timeout;
+        let token = CancellationToken::new();
+        let plugin = self.plugins.get_mut(name)
+            .ok_or_else(|| PluginError::plugin_not_found(name))?;
+        let Some(consumer_plugin) = plugin.as_consumer_plugin_mut() else {
+            return Ok(());
+        };
+        consumer_plugin.set_cancellation_token(token.clone());
+        self.cancellation_tokens.insert(name.to_string(), token.clone());
+        let preferences = consumer_plugin.consumer_preferences();
+        self.resources.set_limits(name, ResourceLimits::from_preferences(&preferences));
+        consumer.set_route(MessageRoute::from_preferences(name, &preferences)).await;
+        self.consumers.insert(name.to_string(), consumer.clone());
+        
+        #[cfg(feature = "otel")]
+        let mut span = crate::telemetry::plugin_span("plugin.start_consuming", name, Vec::new());
+        let result = run_with_deadline(name, "start_consuming", timeout, &token, consumer_plugin.start_consuming(Box::new(consumer))).await;
+        #[cfg(feature = "otel")]
+        span.record_result(&result);
+        if let Err(e) = &result {
+            self.states.insert(name.to_string(), crate::plugin::traits::PluginState::Error(e.to_string()));
+        }
+        self.report_cancellation(name, &token, &result).await;
+        result
+    }
+    
+    /// Pass one message to a consumer plugin, within the execution deadline
+    /// 
+    /// Once a plugin has been cancelled its remaining messages are refused.
+    /// Successful calls are checked against the plugin's declared resource limits.
+    pub async fn process_message(&self, name: &str, consumer: &QueueConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        let consumer_plugin = self.plugins.get(name)
+            .ok_or_else(|| PluginError::plugin_not_found(name))?
+            .as_consumer_plugin()
+            .ok_or_else(|| PluginError::invalid_state(format!("Plugin '{}' does not consume messages", name)))?;
+        let token = self.cancellation_tokens.get(name).cloned().unwrap_or_default();
+        
+        let _stage = crate::profiling::plugin_stage("plugin.process_message", name);
+        #[cfg(feature = "otel")]
+        let mut span = crate::telemetry::plugin_span("plugin.process_message", name, vec![
+            crate::telemetry::KeyValue::new("sequence", message.header().sequence() as i64),
+        ]);
+        let started = Instant::now();
+        let call = consumer_plugin.process_message(consumer, message);
+        #[cfg(feature = "fault-injection")]
+        let call = async {
+            crate::faults::before_plugin_message(name).await;
+            call.await
+        };
+        let result = run_with_deadline(name, "process_message", self.execution_timeout, &token, call).await;
+        #[cfg(feature = "otel")]
+        span.record_result(&result);
+        self.report_cancellation(name, &token, &result).await;
+        if result.is_ok() {
+            self.check_resources(name, consumer, started.elapsed()).await;
+        }
+        result
+    }
+    
+    /// Pass a batch of messages to a consumer plugin, within the execution deadline
+    /// 
+    /// The batch is one call, so it shares a single deadline; its time is
+    /// checked against the plugin's message time limit per message.
+    pub async fn process_batch(&self, name: &str, consumer: &QueueConsumer, messages: Vec<Arc<ScanMessage>>) -> PluginResult<()> {
+        let consumer_plugin = self.plugins.get(name)
+            .ok_or_else(|| PluginError::plugin_not_found(name))?
+            .as_consumer_plugin()
+            .ok_or_else(|| PluginError::invalid_state(format!("Plugin '{}' does not consume messages", name)))?;
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let token = self.cancellation_tokens.get(name).cloned().unwrap_or_default();
+        let count = messages.len() as u32;
+        
+        let _stage = crate::profiling::plugin_stage("plugin.process_batch", name);
+        #[cfg(feature = "otel")]
+        let mut span = crate::telemetry::plugin_span("plugin.process_batch", name, vec![
+            crate::telemetry::KeyValue::new("messages", count as i64),
+        ]);
+        let started = Instant::now();
+        let call = consumer_plugin.process_batch(consumer, messages);
+        #[cfg(feature = "fault-injection")]
+        let call = async {
+            crate::faults::before_plugin_message(name).await;
+            call.await
+        };
+        let result = run_with_deadline(name, "process_batch", self.execution_timeout, &token, call).await;
+        #[cfg(feature = "otel")]
+        span.record_result(&result);
+        self.report_cancellation(name, &token, &result).await;
+        if result.is_ok() {
+            self.check_resources(name, consumer, started.elapsed() / count).await;
+        }
+        result
+    }
+    
+    /// Pass a queue event to a consumer plugin, within the execution deadline
+    pub async fn handle_queue_event(&self, name: &str, event: &QueueEvent) -> PluginResult<()> {
+        let consumer_plugin = self.plugins.get(name)
+            .ok_or_else(|| PluginError::plugin_not_found(name))?
+            .as_consumer_plugin()
+            .ok_or_else(|| PluginError::invalid_state(format!("Plugin '{}' does not consume messages", name)))?;
+        let token = self.cancellation_tokens.get(name).cloned().unwrap_or_default();
+        
+        let result = run_with_deadline(name, "handle_queue_event", self.execution_timeout, &token, consumer_plugin.handle_queue_event(event)).await;
+        self.report_cancellation(name, &token, &result).await;
+        result
+    }
+    
+    /// Consumer preferences of a plugin, or `None` when it does not consume messages
+    pub fn consumer_preferences(&self, name: &str) -> Option<ConsumerPreferences> {
+        self.plugins.get(name)
+            .and_then(|plugin| plugin.as_consumer_plugin())
+            .map(|consumer_plugin| consumer_plugin.consumer_preferences())
+    }
+    
+    /// Publish a plugin error event when a call was cancelled or panicked
+    async fn report_cancellation(&self, name: &str, token: &CancellationToken, result: &PluginResult<()>) {
+        let (error_type, e) = match result {
+            Err(e @ PluginError::Timeout { .. }) => ("timeout", e),
+            Err(e @ PluginError::ExecutionFailed { .. }) if token.is_cancelled() => ("panic", e),
+            _ => return,
+        };
+        log::error!("{}", e);
+        self.publish_plugin_error(name, error_type, e.to_string(), false).await;
+    }
+    
+    /// Compare a message call with the plugin's declared limits, reporting its first breach of each
+    async fn check_resources(&self, name: &str, consumer: &QueueConsumer, elapsed: Duration) {
+        let Some(limits) = self.resources.limits(name) else {
+            return;
+        };
+        let mut warnings: Vec<String> = self.resources.check_message_time(name, elapsed).into_iter().collect();
+        if limits.max_memory.is_some() {
+            warnings.extend(self.resources.check_memory(name, consumer.backlog_memory().await));
+        }
+        
+        for warning in warnings {
+            log::warn!("{}", warning);
+            self.publish_plugin_error(name, "resource_limit", warning, true).await;
+        }
+    }
+    
+    /// Publish a plugin error event, if a publisher is set
+    async fn publish_plugin_error(&self, name: &str, error_type: &str, error_message: String, recoverable: bool) {
+        if let Some(publisher) = &self.plugin_publisher {
+            let event = PluginEvent::PluginError {
+                plugin_id: name.to_string(),
+                error_type: error_type.to_string(),
+                error_message,
+                recoverable,
+                occurred_at: SystemTime::now(),
+            };
+            if let Err(e) = publisher.publish(event).await {
+                log::warn!("Failed to report {} of plugin '{}': {}", error_type, name, e);
+            }
+        }
+    }
+    
+    /// Auto-activate plugins marked with active_by_default = true, together
+    /// with the plugins they depend on
     pub async fn auto_activate_default_plugins(&mut self) -> PluginResult<()> {
-        let plugins_to_activate: Vec<String> = self.plugins.iter()
+        let mut plugins_to_activate: Vec<String> = self.plugins.iter()
             .filter_map(|(name, plugin)| {
                 if plugin.plugin_info().active_by_default {
                     Some(name.clone())
@@ -360,10 +719,11 @@ impl PluginRegistry {
                 }
             })
             .collect();
+        plugins_to_activate.sort();
         
-        for plugin_name in plugins_to_activate {
+        for plugin_name in self.activation_order(&plugins_to_activate)? {
             self.activate_plugin(&plugin_name).await?;
-            log::debug!("Auto-activated plugin '{}' (active_by_default = true)", plugin_name);
+            log::debug!("Auto-activated plugin '{}' (active_by_default = true or required dependency)", plugin_name);
         }
         
         Ok(())
@@ -591,6 +951,88 @@ impl PluginRegistry {
     }
 }
 
+/// Run a plugin call, cancelling it when the deadline passes or the plugin's token is cancelled
+/// 
+/// A call that overruns its deadline cancels the token, so later calls to the same
+/// plugin fail straight away rather than waiting out another deadline each.
+async fn run_with_deadline<F>(
+    name: &str,
+    operation: &str,
+    timeout: Option<Duration>,
+    token: &CancellationToken,
+    call: F,
+) -> PluginResult<()>
+where
+    F: std::future::Future<Output = PluginResult<()>>,
+{
+    if token.is_cancelled() {
+        return Err(PluginError::timeout(format!("plugin '{}' was cancelled", name)));
+    }
+    let deadline = async {
+        match timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    
+    // A panicking plugin is cancelled like one that overran its deadline
+    tokio::select! {
+        result = AssertUnwindSafe(Contained(Box::pin(call))).catch_unwind() => result.unwrap_or_else(|panic| {
+            token.cancel();
+            Err(PluginError::execution_failed(format!("plugin '{}' panicked during {}: {}", name, operation, panic_message(&*panic))))
+        }),
+        _ = token.cancelled() => Err(PluginError::timeout(format!("plugin '{}' was cancelled during {}", name, operation))),
+        _ = deadline => {
+            token.cancel();
+            Err(PluginError::timeout(format!(
+                "plugin '{}' exceeded its {}s deadline in {} and was cancelled",
+                name,
+                timeout.unwrap_or_default().as_secs_f64(),
+                operation
+            )))
+        }
+    }
+}
+
+thread_local! {
+    /// Whether a plugin call whose panics the registry catches is being polled on this thread
+    static CONTAINING_PANICS: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Whether a panic on the current thread will be caught by the registry
+/// 
+/// Such a panic cancels the plugin that raised it rather than ending the run,
+/// so a process panic hook should leave it to unwind.
+pub fn panic_is_contained() -> bool {
+    CONTAINING_PANICS.with(|containing| containing.get())
+}
+
+/// A plugin call, marked as containing its panics while it is polled
+struct Contained<F>(std::pin::Pin<Box<F>>);
+
+impl<F: std::future::Future> std::future::Future for Contained<F> {
+    type Output = F::Output;
+    
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<F::Output> {
+        /// Restores the previous marking, also when the call panics
+        struct Restore(bool);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CONTAINING_PANICS.with(|containing| containing.set(self.0));
+            }
+        }
+        let _restore = Restore(CONTAINING_PANICS.with(|containing| containing.replace(true)));
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// The message a panic was raised with
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> &str {
+    panic.downcast_ref::<&str>().copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
 impl Default for PluginRegistry {
     fn default() -> Self {
         Self::new()
@@ -692,18 +1134,20 @@ impl SharedPluginRegistry {
     
     /// Discover and load all available plugins using the unified discovery system
     /// This is the ONLY correct way to populate a plugin registry from external code
+    /// 
+    /// Plugin libraries found in `plugin_directory` are registered alongside the builtins.
     pub fn discover_and_load_plugins(
         &self,
         context: &crate::plugin::PluginContext,
+        plugin_directory: Option<std::path::PathBuf>,
         excluded_plugins: Vec<String>,
     ) -> PluginResult<()> {
         use crate::plugin::discovery::{PluginDiscovery, UnifiedPluginDiscovery};
         
         log::debug!("SharedPluginRegistry: Starting plugin discovery with {} exclusions", excluded_plugins.len());
         
-        // Create unified discovery (no external plugin directory for default usage)
         let discovery = UnifiedPluginDiscovery::new_with_notification_manager(
-            None, 
+            plugin_directory, 
             excluded_plugins, 
             crate::plugin::PluginSettings::default(),
             Some(context.get_notification_manager())
@@ -711,42 +1155,49 @@ impl SharedPluginRegistry {
             message: format!("Failed to create plugin discovery: {}", e) 
         })?;
         
-        // Discover and instantiate all available plugins
-        let plugins = discovery.discover_and_instantiate_plugins()?;
+        // Negotiate each plugin's API version before instantiating it, so incompatible
+        // plugins are reported with a reason instead of failing later
+        let checker = crate::plugin::compatibility::VersionCompatibilityChecker::default();
+        let descriptors = discovery.discover_plugins()?;
+        let matrix: Vec<_> = descriptors.iter()
+            .map(|descriptor| crate::plugin::compatibility::CompatibilityEntry::new(&checker, &descriptor.info))
+            .collect();
+        if let Some(report) = checker.registration_report(&matrix) {
+            eprint!("{}", report);
+        }
+        
+        let mut plugins = Vec::new();
+        for (descriptor, entry) in descriptors.iter().zip(&matrix) {
+            if !entry.compatibility.is_usable() {
+                log::warn!("Skipping plugin '{}': {}", entry.plugin, entry.compatibility);
+                continue;
+            }
+            if let Some(plugin) = discovery.instantiate_from_descriptor(descriptor)? {
+                plugins.push(plugin);
+            }
+        }
         let plugin_count = plugins.len();
         log::debug!("SharedPluginRegistry: Instantiated {} plugins", plugin_count);
         
-        // Create a runtime for async plugin registration
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .map_err(|e| PluginError::InitializationFailed { 
-                message: format!("Failed to create runtime for plugin registration: {}", e) 
-            })?;
-        
-        // Register and initialize each plugin
-        rt.block_on(async {
+        // Register and initialize each plugin on the shared runtime
+        crate::runtime::block_on(async {
             let mut registry = self.inner.write().await;
             
             for mut plugin in plugins {
                 let plugin_name = plugin.plugin_info().name.clone();
-                let active_by_default = plugin.plugin_info().active_by_default;
                 
                 // Initialize plugin with context
                 plugin.initialize(context).await?;
                 
                 // Register plugin as inactive first
                 registry.register_plugin_inactive(plugin).await?;
-                
-                // Activate if marked as active_by_default
-                if active_by_default {
-                    registry.activate_plugin(&plugin_name).await?;
-                    log::debug!("SharedPluginRegistry: Activated plugin '{}' (active_by_default = true)", plugin_name);
-                } else {
-                    log::debug!("SharedPluginRegistry: Registered plugin '{}' as inactive (active_by_default = false)", plugin_name);
-                }
+                log::debug!("SharedPluginRegistry: Registered plugin '{}' as inactive", plugin_name);
             }
             
+            // Activate active_by_default plugins once all are registered, so
+            // dependencies can be resolved regardless of discovery order
+            registry.auto_activate_default_plugins().await?;
+            
             Result::<_, PluginError>::Ok(())
         })?;
         
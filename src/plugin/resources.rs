@@ -0,0 +1,168 @@
+//! Plugin Resource Limits
+//!
+//! Consumer plugins declare the resources they expect to use through
+//! [`ConsumerPreferences`]: the most queue memory their unacknowledged messages
+//! may hold and the longest they spend on a single message. The plugin registry
+//! measures every message call against those declarations.
+//!
+//! The first breach of each limit by a plugin is logged as a warning and
+//! published as a recoverable `PluginError` event; later breaches are only
+//! counted, so a consistently slow plugin does not flood the log. The counts are
+//! available from the registry once the scan is done, when each offending
+//! plugin is reported. While the scan runs, a plugin over its memory limit is
+//! throttled by the consumer driver, which reads it smaller batches.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::plugin::traits::ConsumerPreferences;
+
+/// Limits a consumer plugin declared through its preferences
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Most queue memory, in bytes, for messages not yet acknowledged
+    pub max_memory: Option<usize>,
+    /// Longest expected processing time for one message
+    pub max_message_time: Option<Duration>,
+}
+
+impl ResourceLimits {
+    /// Take the limits declared in a consumer's preferences
+    pub fn from_preferences(preferences: &ConsumerPreferences) -> Self {
+        Self {
+            max_memory: preferences.max_memory,
+            max_message_time: preferences.max_message_time,
+        }
+    }
+
+    /// Whether no limit is declared
+    pub fn is_unlimited(&self) -> bool {
+        self.max_memory.is_none() && self.max_message_time.is_none()
+    }
+}
+
+/// Number of times a plugin exceeded each of its limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceBreaches {
+    /// Messages after which the unacknowledged backlog exceeded the memory limit
+    pub memory: u64,
+    /// Messages that took longer than the processing time limit
+    pub message_time: u64,
+}
+
+impl ResourceBreaches {
+    /// Total number of breaches
+    pub fn total(&self) -> u64 {
+        self.memory + self.message_time
+    }
+}
+
+/// Tracks each consumer plugin's declared limits and its breaches of them
+#[derive(Debug, Default)]
+pub struct ResourceMonitor {
+    limits: HashMap<String, ResourceLimits>,
+    breaches: Mutex<HashMap<String, ResourceBreaches>>,
+}
+
+impl ResourceMonitor {
+    /// Create a monitor with no plugins
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the limits a plugin declared, replacing any previous declaration
+    pub fn set_limits(&mut self, plugin: &str, limits: ResourceLimits) {
+        if limits.is_unlimited() {
+            self.limits.remove(plugin);
+        } else {
+            self.limits.insert(plugin.to_string(), limits);
+        }
+    }
+
+    /// Limits a plugin declared, if any
+    pub fn limits(&self, plugin: &str) -> Option<ResourceLimits> {
+        self.limits.get(plugin).copied()
+    }
+
+    /// Check a message's processing time, returning a warning on the plugin's first breach
+    pub fn check_message_time(&self, plugin: &str, elapsed: Duration) -> Option<String> {
+        let limit = self.limits(plugin)?.max_message_time?;
+        if elapsed <= limit {
+            return None;
+        }
+        let first = self.record(plugin, |breaches| &mut breaches.message_time);
+        first.then(|| format!(
+            "plugin '{}' took {:.3}s to process a message, over its declared limit of {:.3}s",
+            plugin, elapsed.as_secs_f64(), limit.as_secs_f64()
+        ))
+    }
+
+    /// Check a plugin's unacknowledged backlog, returning a warning on its first breach
+    pub fn check_memory(&self, plugin: &str, backlog: usize) -> Option<String> {
+        let limit = self.limits(plugin)?.max_memory?;
+        if backlog <= limit {
+            return None;
+        }
+        let first = self.record(plugin, |breaches| &mut breaches.memory);
+        first.then(|| format!(
+            "plugin '{}' holds {} bytes of unacknowledged messages, over its declared limit of {} bytes",
+            plugin, backlog, limit
+        ))
+    }
+
+    /// Breaches recorded for a plugin
+    pub fn breaches(&self, plugin: &str) -> ResourceBreaches {
+        self.breaches.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(plugin)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Count a breach, returning whether it was the first of its kind for the plugin
+    fn record(&self, plugin: &str, counter: impl FnOnce(&mut ResourceBreaches) -> &mut u64) -> bool {
+        let mut breaches = self.breaches.lock().unwrap_or_else(|e| e.into_inner());
+        let count = counter(breaches.entry(plugin.to_string()).or_default());
+        *count += 1;
+        *count == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> ResourceMonitor {
+        let mut monitor = ResourceMonitor::new();
+        monitor.set_limits("slow", ResourceLimits {
+            max_memory: Some(1024),
+            max_message_time: Some(Duration::from_millis(10)),
+        });
+        monitor
+    }
+
+    #[test]
+    fn test_first_breach_is_reported_and_later_ones_counted() {
+        let monitor = monitor();
+
+        assert!(monitor.check_message_time("slow", Duration::from_millis(5)).is_none());
+        assert!(monitor.check_message_time("slow", Duration::from_millis(50)).is_some());
+        assert!(monitor.check_message_time("slow", Duration::from_millis(50)).is_none());
+        assert!(monitor.check_memory("slow", 4096).is_some());
+
+        assert_eq!(monitor.breaches("slow"), ResourceBreaches { memory: 1, message_time: 2 });
+        assert_eq!(monitor.breaches("slow").total(), 3);
+    }
+
+    #[test]
+    fn test_plugins_without_limits_are_not_checked() {
+        let mut monitor = monitor();
+        assert!(monitor.check_message_time("other", Duration::from_secs(60)).is_none());
+
+        monitor.set_limits("slow", ResourceLimits::default());
+        assert!(monitor.limits("slow").is_none());
+        assert!(monitor.check_memory("slow", usize::MAX).is_none());
+        assert_eq!(monitor.breaches("other"), ResourceBreaches::default());
+    }
+}
@@ -0,0 +1,113 @@
+//! Table Matching
+//!
+//! Comparison operators and column name matching shared by everything that picks
+//! rows or columns out of exported tables: `--where`, `--sort` and `--columns` in
+//! the export plugin, and `--fail-on` and trend recording once a scan is done.
+//!
+//! Names are matched ignoring case and punctuation, so `current_size`,
+//! `current-size` and `Current Size` all name the same column.
+
+use crate::plugin::data_export::PluginDataExport;
+use std::cmp::Ordering;
+
+/// Comparison between a value and a literal or threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    /// Text contains the literal, ignoring case
+    Contains,
+    /// Text does not contain the literal, ignoring case
+    NotContains,
+}
+
+impl Operator {
+    /// Operators by their symbols, longest first so `>=` is not read as `>`
+    pub const SYMBOLS: [(&'static str, Operator); 9] = [
+        (">=", Operator::GreaterOrEqual),
+        ("<=", Operator::LessOrEqual),
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        ("!~", Operator::NotContains),
+        (">", Operator::Greater),
+        ("<", Operator::Less),
+        ("=", Operator::Equal),
+        ("~", Operator::Contains),
+    ];
+
+    /// The operator a comparison starts with, and the length of its symbol
+    pub fn parse_prefix(text: &str) -> Option<(Operator, usize)> {
+        Self::SYMBOLS.iter()
+            .find(|(symbol, _)| text.starts_with(symbol))
+            .map(|(symbol, operator)| (*operator, symbol.len()))
+    }
+
+    /// Whether the operator holds for a value ordered against its literal
+    ///
+    /// `~` and `!~` take `Equal` to mean the value contains the literal.
+    pub fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+            Operator::Equal | Operator::Contains => ordering == Ordering::Equal,
+            Operator::NotEqual | Operator::NotContains => ordering != Ordering::Equal,
+        }
+    }
+
+    /// Whether the operator tests for text rather than ordering values
+    pub fn is_text_match(&self) -> bool {
+        matches!(self, Operator::Contains | Operator::NotContains)
+    }
+
+    /// The symbol the operator is written with
+    pub fn symbol(&self) -> &'static str {
+        Self::SYMBOLS.iter()
+            .find(|(_, candidate)| candidate == self)
+            .map(|(symbol, _)| *symbol)
+            .unwrap_or("?")
+    }
+}
+
+/// A name lowercased with runs of punctuation and spaces replaced by `-`
+pub fn normalise(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Position of a column in an export's schema, matched ignoring case and punctuation
+pub fn column_index(export: &PluginDataExport, name: &str) -> Option<usize> {
+    let name = normalise(name);
+    export.schema.columns.iter().position(|column| normalise(&column.name) == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_match_ignoring_case_and_punctuation() {
+        assert_eq!(normalise("Current Size"), "current-size");
+        assert_eq!(normalise("current_size"), "current-size");
+        assert_eq!(normalise(" --Current--size "), "current-size");
+    }
+
+    #[test]
+    fn test_operators_parse_longest_symbol_first() {
+        assert_eq!(Operator::parse_prefix(">= 3"), Some((Operator::GreaterOrEqual, 2)));
+        assert_eq!(Operator::parse_prefix("!~'bot'"), Some((Operator::NotContains, 2)));
+        assert_eq!(Operator::parse_prefix("= 3"), Some((Operator::Equal, 1)));
+        assert_eq!(Operator::parse_prefix("3"), None);
+        assert_eq!(Operator::LessOrEqual.symbol(), "<=");
+        assert!(Operator::LessOrEqual.holds(Ordering::Equal));
+        assert!(!Operator::NotContains.holds(Ordering::Equal));
+    }
+}
@@ -132,4 +132,47 @@ async fn test_multiple_plugin_activation() {
     let mut active_plugins = registry.get_active_plugins();
     active_plugins.sort(); // Sort for consistent testing
     assert_eq!(active_plugins, vec!["plugin1", "plugin3"]);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_activate_with_dependencies_in_order() {
+    let mut registry = PluginRegistry::new();
+    
+    // report -> summary -> metrics, report -> metrics
+    let report = MockPlugin::new("report", false)
+        .with_dependency("summary")
+        .with_dependency("metrics");
+    let summary = MockPlugin::new("summary", false).with_dependency("metrics");
+    let metrics = MockPlugin::new("metrics", false);
+    
+    registry.register_plugin_inactive(Box::new(report)).await.unwrap();
+    registry.register_plugin_inactive(Box::new(summary)).await.unwrap();
+    registry.register_plugin_inactive(Box::new(metrics)).await.unwrap();
+    registry.register_plugin_inactive(Box::new(MockPlugin::new("unrelated", false))).await.unwrap();
+    
+    let order = registry.activate_with_dependencies("report").await.unwrap();
+    assert_eq!(order, vec!["metrics", "summary", "report"]);
+    assert!(registry.is_plugin_active("metrics"));
+    assert!(!registry.is_plugin_active("unrelated"));
+}
+
+#[tokio::test]
+async fn test_activation_fails_on_missing_or_cyclic_dependencies() {
+    let mut registry = PluginRegistry::new();
+    
+    let needs_missing = MockPlugin::new("needs-missing", false).with_dependency("metrics");
+    let cycle_a = MockPlugin::new("cycle-a", false).with_dependency("cycle-b");
+    let cycle_b = MockPlugin::new("cycle-b", false).with_dependency("cycle-a");
+    
+    registry.register_plugin_inactive(Box::new(needs_missing)).await.unwrap();
+    registry.register_plugin_inactive(Box::new(cycle_a)).await.unwrap();
+    registry.register_plugin_inactive(Box::new(cycle_b)).await.unwrap();
+    
+    let missing = registry.activate_with_dependencies("needs-missing").await.unwrap_err();
+    assert!(missing.to_string().contains("depends on 'metrics', which is not registered"));
+    assert!(!registry.is_plugin_active("needs-missing"));
+    
+    let cycle = registry.activate_with_dependencies("cycle-a").await.unwrap_err();
+    assert!(cycle.to_string().contains("cycle-a -> cycle-b -> cycle-a"));
+    assert!(registry.get_active_plugins().is_empty());
+}
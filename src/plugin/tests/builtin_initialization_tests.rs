@@ -1,9 +1,24 @@
 //! Tests for builtin plugin initialization with activation control
 
 // Note: PluginRegistry import removed - not used in this test
+use crate::app::initialization::PluginSetup;
 use crate::plugin::SharedPluginRegistry;
 
-#[tokio::test]
+/// Plugin services and a default scan for initializing the builtin plugins
+fn plugin_setup() -> PluginSetup {
+    use crate::notifications::AsyncNotificationManager;
+    use crate::notifications::typed_publishers::PluginEventPublisher;
+    use std::sync::Arc;
+
+    PluginSetup {
+        plugin_publisher: Arc::new(PluginEventPublisher::new(Arc::new(AsyncNotificationManager::new()))),
+        plugin_notifications: Arc::new(AsyncNotificationManager::new()),
+        scanner_config: Arc::new(crate::scanner::ScannerConfig::default()),
+        query_params: Arc::new(crate::scanner::QueryParams::default()),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
 async fn test_builtin_plugins_loaded_as_inactive() {
     // This test expects builtin plugins to be loaded as inactive by default
     // (except for those with active_by_default = true)
@@ -15,7 +30,7 @@ async fn test_builtin_plugins_loaded_as_inactive() {
     use crate::display::ColourManager;
     
     let colour_manager = ColourManager::from_color_args(false, false, None);
-    initialize_plugins_via_discovery(&shared_registry, &colour_manager, Vec::new()).await.unwrap();
+    initialize_plugins_via_discovery(&shared_registry, &colour_manager, None, Vec::new(), plugin_setup()).unwrap();
     
     // Access the inner registry to check plugin states
     let registry = shared_registry.inner().read().await;
@@ -25,19 +40,19 @@ async fn test_builtin_plugins_loaded_as_inactive() {
     assert!(registry.has_plugin("metrics"));
     assert!(registry.has_plugin("export"));
     
-    // Expected behavior: Only export plugin should be active (active_by_default = true)
+    // Expected behavior: Only the export and notify plugins should be active (active_by_default = true)
     // Other plugins should be inactive by default
     assert!(!registry.is_plugin_active("commits"), "Commits plugin should be inactive by default");
     assert!(!registry.is_plugin_active("metrics"), "Metrics plugin should be inactive by default");
     assert!(registry.is_plugin_active("export"), "Export plugin should be active by default (active_by_default = true)");
     
-    // Verify active plugins list contains only export
-    let active_plugins = registry.get_active_plugins();
-    assert_eq!(active_plugins.len(), 1, "Only export plugin should be active");
-    assert_eq!(active_plugins[0], "export");
+    // Verify active plugins list contains only export and notify
+    let mut active_plugins = registry.get_active_plugins();
+    active_plugins.sort();
+    assert_eq!(active_plugins, ["export", "notify"], "Only export and notify plugins should be active");
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_auto_activation_of_load_by_default_plugins() {
     // This test verifies that plugins with active_by_default = true are auto-activated
     
@@ -48,7 +63,7 @@ async fn test_auto_activation_of_load_by_default_plugins() {
     use crate::display::ColourManager;
     
     let colour_manager = ColourManager::from_color_args(false, false, None);
-    initialize_plugins_via_discovery(&shared_registry, &colour_manager, Vec::new()).await.unwrap();
+    initialize_plugins_via_discovery(&shared_registry, &colour_manager, None, Vec::new(), plugin_setup()).unwrap();
     
     // Access the inner registry
     let registry = shared_registry.inner().read().await;
@@ -69,7 +84,7 @@ async fn test_auto_activation_of_load_by_default_plugins() {
     assert!(!registry.is_plugin_active("metrics"), "Metrics plugin should not be auto-activated");
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_manual_plugin_activation_after_initialization() {
     // This test verifies that inactive plugins can be manually activated
     
@@ -80,7 +95,7 @@ async fn test_manual_plugin_activation_after_initialization() {
     use crate::display::ColourManager;
     
     let colour_manager = ColourManager::from_color_args(false, false, None);
-    initialize_plugins_via_discovery(&shared_registry, &colour_manager, Vec::new()).await.unwrap();
+    initialize_plugins_via_discovery(&shared_registry, &colour_manager, None, Vec::new(), plugin_setup()).unwrap();
     
     // Access the inner registry for activation
     {
@@ -95,10 +110,22 @@ async fn test_manual_plugin_activation_after_initialization() {
         let registry = shared_registry.inner().read().await;
         assert!(registry.is_plugin_active("commits"), "Commits plugin should be active after manual activation");
         
-        // Should now have 2 active plugins (export + commits)
+        // Should now have 3 active plugins (export + notify + commits)
         let active_plugins = registry.get_active_plugins();
-        assert_eq!(active_plugins.len(), 2, "Should have 2 active plugins after manual activation");
+        assert_eq!(active_plugins.len(), 3, "Should have 3 active plugins after manual activation");
         assert!(active_plugins.contains(&"export".to_string()));
         assert!(active_plugins.contains(&"commits".to_string()));
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_builtin_commands_are_unambiguous() {
+    use crate::cli::command_mapper::CommandMapper;
+    use crate::plugin::builtin::{get_builtin_plugin_functions, get_builtin_plugins};
+
+    let mut mapper = CommandMapper::new();
+    for name in get_builtin_plugins() {
+        mapper.register_plugin(name, get_builtin_plugin_functions(name));
+    }
+    let ambiguities: Vec<String> = mapper.detect_ambiguities().iter().map(ToString::to_string).collect();
+    assert!(ambiguities.is_empty(), "{ambiguities:#?}");
+}
@@ -29,7 +29,7 @@ async fn test_discover_plugins_empty_directory() {
     let temp_dir = tempfile::tempdir().unwrap();
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     assert!(plugins.is_empty());
 }
 
@@ -45,7 +45,7 @@ async fn test_discover_plugins_with_descriptors() {
     fs::write(&descriptor_path, yaml_content).await.unwrap();
     
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     assert_eq!(plugins.len(), 1);
     assert_eq!(plugins[0].info.name, "test-plugin");
@@ -70,7 +70,7 @@ async fn test_discover_plugins_multiple_descriptors() {
     }
     
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     assert_eq!(plugins.len(), 3);
     
@@ -97,7 +97,7 @@ async fn test_discover_plugins_invalid_descriptors() {
     fs::write(&valid_path, yaml_content).await.unwrap();
     
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     // Should only find the valid plugin, ignoring invalid ones
     assert_eq!(plugins.len(), 1);
@@ -122,7 +122,7 @@ async fn test_discover_plugins_with_subdirectories() {
     fs::write(&sub_path, serde_yaml::to_string(&sub_descriptor).unwrap()).await.unwrap();
     
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     // Should find plugins in subdirectories too
     assert_eq!(plugins.len(), 2);
@@ -197,7 +197,7 @@ async fn test_plugin_descriptor_with_config() {
     fs::write(&descriptor_path, yaml_content).await.unwrap();
     
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     assert_eq!(plugins.len(), 1);
     assert_eq!(plugins[0].config.len(), 2);
@@ -222,12 +222,12 @@ async fn test_discover_plugins_filter_by_type() {
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
     
     // Test filtering by scanner type
-    let scanner_plugins = discovery.discover_plugins_by_type(PluginType::Processing).await.unwrap();
+    let scanner_plugins = discovery.discover_plugins_by_type(PluginType::Processing).unwrap();
     assert_eq!(scanner_plugins.len(), 1);
     assert_eq!(scanner_plugins[0].info.name, "scanner-plugin");
     
     // Test filtering by notification type
-    let notification_plugins = discovery.discover_plugins_by_type(PluginType::Notification).await.unwrap();
+    let notification_plugins = discovery.discover_plugins_by_type(PluginType::Notification).unwrap();
     assert_eq!(notification_plugins.len(), 1);
     assert_eq!(notification_plugins[0].info.name, "notification-plugin");
 }
@@ -253,7 +253,7 @@ async fn test_discover_plugins_with_api_version_filtering() {
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
     
     // Test filtering by API version compatibility
-    let compatible_plugins = discovery.discover_compatible_plugins(20250727).await.unwrap();
+    let compatible_plugins = discovery.discover_compatible_plugins(20250727).unwrap();
     
     // Should only find the plugin with compatible API version
     assert_eq!(compatible_plugins.len(), 1);
@@ -272,12 +272,12 @@ async fn test_plugin_descriptor_caching() {
     
     // First discovery - should read from filesystem
     let start = std::time::Instant::now();
-    let plugins1 = discovery.discover_plugins().await.unwrap();
+    let plugins1 = discovery.discover_plugins().unwrap();
     let first_duration = start.elapsed();
     
     // Second discovery - should use cache
     let start = std::time::Instant::now();
-    let plugins2 = discovery.discover_plugins().await.unwrap();
+    let plugins2 = discovery.discover_plugins().unwrap();
     let second_duration = start.elapsed();
     
     assert_eq!(plugins1.len(), 1);
@@ -331,6 +331,7 @@ fn create_test_descriptor_with_api(name: &str, version: &str, api_version: u32)
         file_path: None,
         entry_point: "main".to_string(),
         config: HashMap::new(),
+        functions: Vec::new(),
     }
 }
 
@@ -395,7 +396,7 @@ config: {{}}
   plugin_type: Output
   license: null
   priority: 5
-  load_by_default: true
+  active_by_default: true
 file_path: null
 entry_point: main
 config: {{}}
@@ -406,7 +407,7 @@ config: {{}}
     fs::write(temp_dir.path().join("with-true.yaml"), plugin_with_true).await.unwrap();
     
     let discovery = FileBasedDiscovery::new(temp_dir.path()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     assert_eq!(plugins.len(), 2);
     
@@ -427,7 +428,7 @@ async fn test_builtin_export_plugin_load_by_default() {
     
     // Create discovery with no external directory to get only builtin plugins
     let discovery = UnifiedPluginDiscovery::new(None, Vec::new(), crate::plugin::PluginSettings::default()).unwrap();
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     // Find the export plugin
     let export_plugin = plugins.iter().find(|p| p.info.name == "export").expect("Export plugin should exist");
@@ -508,16 +509,23 @@ async fn test_unified_discovery_builtin_plugins_only() {
     let excluded_plugins = vec![];
     let discovery = UnifiedPluginDiscovery::new(None, excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
-    // Should find builtin plugins: debug, commits, metrics, export
-    assert_eq!(plugins.len(), 4);
+    // Should find builtin plugins: debug, commits, contributors, metrics, loc, blobs, conventions, graph, staleness, export, notify
+    assert_eq!(plugins.len(), 11);
     
     let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
     assert!(plugin_names.contains(&"debug"));
     assert!(plugin_names.contains(&"commits"));
+    assert!(plugin_names.contains(&"contributors"));
     assert!(plugin_names.contains(&"metrics"));
+    assert!(plugin_names.contains(&"loc"));
+    assert!(plugin_names.contains(&"blobs"));
+    assert!(plugin_names.contains(&"conventions"));
+    assert!(plugin_names.contains(&"graph"));
+    assert!(plugin_names.contains(&"staleness"));
     assert!(plugin_names.contains(&"export"));
+    assert!(plugin_names.contains(&"notify"));
     
     // All should be builtin (no file_path)
     for plugin in &plugins {
@@ -534,10 +542,10 @@ async fn test_unified_discovery_builtin_plugins_with_exclusions() {
     let excluded_plugins = vec!["metrics".to_string()];
     let discovery = UnifiedPluginDiscovery::new(None, excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
-    // Should find 3 builtin plugins (4 total - 1 excluded)
-    assert_eq!(plugins.len(), 3);
+    // Should find every builtin plugin but the excluded one
+    assert_eq!(plugins.len(), crate::plugin::builtin::get_builtin_plugins().len() - 1);
     
     let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
     assert!(plugin_names.contains(&"commits"));
@@ -561,12 +569,12 @@ async fn test_unified_discovery_external_plugins_only() {
     println!("DEBUG: Written external plugin to: {:?}", external_path);
     
     // Exclude ALL builtin plugins
-    let excluded_plugins = vec!["debug".to_string(), "commits".to_string(), "metrics".to_string(), "export".to_string()];
+    let excluded_plugins = vec!["debug".to_string(), "commits".to_string(), "contributors".to_string(), "metrics".to_string(), "loc".to_string(), "blobs".to_string(), "conventions".to_string(), "graph".to_string(), "staleness".to_string(), "export".to_string(), "notify".to_string()];
     println!("DEBUG: Creating discovery with temp dir: {:?}", temp_dir.path());
     println!("DEBUG: Temp dir exists: {}", temp_dir.path().exists());
     let discovery = UnifiedPluginDiscovery::new(Some(temp_dir.path().to_path_buf()), excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     // Debug output
     println!("DEBUG: Found {} plugins", plugins.len());
@@ -600,10 +608,10 @@ async fn test_unified_discovery_mixed_builtin_and_external() {
     let excluded_plugins = vec![];
     let discovery = UnifiedPluginDiscovery::new(Some(temp_dir.path().to_path_buf()), excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
-    // Should find 4 builtin + 1 external = 5 total
-    assert_eq!(plugins.len(), 5);
+    // Should find every builtin plugin and the external one
+    assert_eq!(plugins.len(), crate::plugin::builtin::get_builtin_plugins().len() + 1);
     
     let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
     assert!(plugin_names.contains(&"debug"));
@@ -630,10 +638,10 @@ async fn test_unified_discovery_external_overrides_builtin() {
     let excluded_plugins = vec![];
     let discovery = UnifiedPluginDiscovery::new(Some(temp_dir.path().to_path_buf()), excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
-    // Should find 4 total: external "commits" + builtin "debug" + builtin "metrics" + builtin "export"
-    assert_eq!(plugins.len(), 4);
+    // Should find 11 total: external "commits" + builtin "debug" + builtin "contributors" + builtin "metrics" + builtin "loc" + builtin "blobs" + builtin "conventions" + builtin "graph" + builtin "staleness" + builtin "export" + builtin "notify"
+    assert_eq!(plugins.len(), 11);
     
     let commits_plugin = plugins.iter().find(|p| p.info.name == "commits").unwrap();
     
@@ -665,10 +673,10 @@ async fn test_unified_discovery_multiple_external_plugins_same_name() {
     fs::write(&plugin2_path, plugin2).await.unwrap();
     
     // Exclude all builtin plugins to focus on external behavior
-    let excluded_plugins = vec!["debug".to_string(), "commits".to_string(), "metrics".to_string(), "export".to_string()];
+    let excluded_plugins = vec!["debug".to_string(), "commits".to_string(), "contributors".to_string(), "metrics".to_string(), "loc".to_string(), "blobs".to_string(), "conventions".to_string(), "graph".to_string(), "staleness".to_string(), "export".to_string(), "notify".to_string()];
     let discovery = UnifiedPluginDiscovery::new(Some(temp_dir.path().to_path_buf()), excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
     // Should find only one "duplicate" plugin (FileBasedDiscovery's deduplication)
     assert_eq!(plugins.len(), 1);
@@ -697,10 +705,10 @@ async fn test_unified_discovery_external_exclusion() {
     let excluded_plugins = vec!["unwanted".to_string(), "metrics".to_string()];
     let discovery = UnifiedPluginDiscovery::new(Some(temp_dir.path().to_path_buf()), excluded_plugins, crate::plugin::PluginSettings::default()).unwrap();
     
-    let plugins = discovery.discover_plugins().await.unwrap();
+    let plugins = discovery.discover_plugins().unwrap();
     
-    // Should find: "wanted" external + "debug" builtin + "commits" builtin + "export" builtin = 4 total
-    assert_eq!(plugins.len(), 4);
+    // Should find: "wanted" external + "debug" + "commits" + "contributors" + "loc" + "blobs" + "conventions" + "graph" + "staleness" + "export" + "notify" builtins = 11 total
+    assert_eq!(plugins.len(), 11);
     
     let plugin_names: Vec<&str> = plugins.iter().map(|p| p.info.name.as_str()).collect();
     assert!(plugin_names.contains(&"wanted"));
@@ -711,3 +719,14 @@ async fn test_unified_discovery_external_exclusion() {
     assert!(!plugin_names.contains(&"metrics"));
 }
 
+
+#[tokio::test]
+async fn test_discovery_skips_libraries_without_entry_point() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let library_path = temp_dir.path().join(format!("libbroken.{}", std::env::consts::DLL_EXTENSION));
+    fs::write(&library_path, b"not a shared library").await.unwrap();
+    
+    // Libraries are only considered when dynamic loading is enabled, and are skipped if they fail to load
+    let discovery = FileBasedDiscovery::with_dynamic_loading(temp_dir.path(), true).unwrap();
+    assert!(discovery.discover_plugins().unwrap().is_empty());
+}
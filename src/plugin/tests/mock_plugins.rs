@@ -7,7 +7,7 @@ use std::time::Instant;
 use async_trait::async_trait;
 use crate::plugin::traits::*;
 use crate::plugin::error::{PluginError, PluginResult};
-use crate::plugin::context::{PluginContext, PluginRequest, PluginResponse, ExecutionMetadata};
+use crate::plugin::context::{PluginContext, ScanContext, PluginRequest, PluginResponse, ExecutionMetadata};
 
 /// Mock plugin for testing basic plugin functionality
 pub struct MockPlugin {
@@ -59,6 +59,12 @@ impl MockPlugin {
         self
     }
     
+    /// Declare a required dependency on another plugin
+    pub fn with_dependency(mut self, name: &str) -> Self {
+        self.info = self.info.depends_on(name.to_string());
+        self
+    }
+    
     /// Add a capability to this plugin
     pub fn with_capability(mut self, name: &str, description: &str) -> Self {
         self.info.capabilities.push(PluginCapability {
@@ -91,7 +97,7 @@ impl Plugin for MockPlugin {
         &self.info
     }
 
-    async fn initialize(&mut self, _context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
         if self.should_fail {
             return Err(PluginError::initialization_failed("Mock initialization failure"));
         }
@@ -191,7 +197,7 @@ impl Plugin for MockProcessingPlugin {
         self.base.plugin_info()
     }
 
-    async fn initialize(&mut self, context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
         self.base.initialize(context).await
     }
 
@@ -231,6 +237,87 @@ impl PluginDataRequirements for MockProcessingPlugin {
     }
 }
 
+/// Mock consumer plugin whose calls take a fixed time, for testing deadlines
+pub struct MockSlowConsumerPlugin {
+    base: MockPlugin,
+    delay: std::time::Duration,
+    token: Option<tokio_util::sync::CancellationToken>,
+    preferences: ConsumerPreferences,
+}
+
+impl MockSlowConsumerPlugin {
+    /// Create a consumer plugin whose calls each take `delay`
+    pub fn new(name: &str, delay: std::time::Duration) -> Self {
+        Self {
+            base: MockPlugin::new(name, false),
+            delay,
+            token: None,
+            preferences: ConsumerPreferences::default(),
+        }
+    }
+
+    /// Declare consumer preferences, such as resource limits
+    pub fn with_preferences(mut self, preferences: ConsumerPreferences) -> Self {
+        self.preferences = preferences;
+        self
+    }
+}
+
+#[async_trait]
+impl Plugin for MockSlowConsumerPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        self.base.plugin_info()
+    }
+
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        self.base.initialize(context).await
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        self.base.execute(request).await
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        self.base.cleanup().await
+    }
+
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for MockSlowConsumerPlugin {
+    async fn start_consuming(&mut self, _consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        tokio::time::sleep(self.delay).await;
+        Ok(())
+    }
+
+    async fn process_message(&self, _consumer: &dyn MessageConsumer, _message: Arc<crate::scanner::messages::ScanMessage>) -> PluginResult<()> {
+        tokio::time::sleep(self.delay).await;
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, _event: &crate::queue::QueueEvent) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn set_cancellation_token(&mut self, token: tokio_util::sync::CancellationToken) {
+        self.token = Some(token);
+    }
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        self.preferences.clone()
+    }
+}
+
 /// Mock notification plugin for testing notification functionality
 pub struct MockNotificationPlugin {
     base: MockPlugin,
@@ -285,7 +372,7 @@ impl Plugin for MockNotificationPlugin {
         self.base.plugin_info()
     }
 
-    async fn initialize(&mut self, context: &PluginContext) -> PluginResult<()> {
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
         self.base.initialize(context).await
     }
 
@@ -272,4 +272,110 @@ async fn test_plugin_registry_capability_search() {
     
     let missing = registry.get_plugins_with_capability("missing");
     assert_eq!(missing.len(), 0);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_plugin_registry_cancels_calls_past_deadline() {
+    use crate::queue::MultiConsumerQueue;
+    use crate::scanner::messages::{MessageData, MessageHeader, ScanMessage};
+    use std::time::Duration;
+    
+    let queue = MultiConsumerQueue::new(
+        Arc::new(crate::notifications::AsyncNotificationManager::new()),
+        Arc::new(crate::notifications::AsyncNotificationManager::new()),
+    );
+    let mut registry = PluginRegistry::new();
+    registry.set_execution_timeout(Some(Duration::from_millis(50)));
+    registry.register_plugin(Box::new(MockSlowConsumerPlugin::new("fast", Duration::ZERO))).await.unwrap();
+    registry.register_plugin(Box::new(MockSlowConsumerPlugin::new("slow", Duration::from_secs(10)))).await.unwrap();
+    
+    let fast_consumer = queue.register_consumer("fast".to_string()).await.unwrap();
+    registry.start_consuming("fast", queue.register_consumer("fast".to_string()).await.unwrap()).await.unwrap();
+    
+    // A call that overruns the deadline is cancelled and the plugin's token with it
+    let slow_consumer = queue.register_consumer("slow".to_string()).await.unwrap();
+    let result = registry.start_consuming("slow", queue.register_consumer("slow".to_string()).await.unwrap()).await;
+    assert!(matches!(result, Err(PluginError::Timeout { .. })));
+    assert!(registry.cancellation_token("slow").unwrap().is_cancelled());
+    
+    // Later calls to the cancelled plugin are refused without waiting
+    let message = Arc::new(ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), MessageData::None));
+    let started = std::time::Instant::now();
+    let result = registry.process_message("slow", &slow_consumer, Arc::clone(&message)).await;
+    assert!(matches!(result, Err(PluginError::Timeout { .. })));
+    assert!(started.elapsed() < Duration::from_millis(50));
+    
+    // Other plugins are unaffected
+    assert!(registry.process_message("fast", &fast_consumer, message).await.is_ok());
+    assert!(!registry.cancellation_token("fast").unwrap().is_cancelled());
+}
+
+#[tokio::test]
+async fn test_plugin_registry_reports_resource_limit_breaches() {
+    use crate::plugin::resources::ResourceBreaches;
+    use crate::plugin::traits::ConsumerPreferences;
+    use crate::queue::MultiConsumerQueue;
+    use crate::scanner::messages::{MessageData, MessageHeader, ScanMessage};
+    use std::time::Duration;
+    
+    let queue = MultiConsumerQueue::new(
+        Arc::new(crate::notifications::AsyncNotificationManager::new()),
+        Arc::new(crate::notifications::AsyncNotificationManager::new()),
+    );
+    queue.start().await.unwrap();
+    let mut registry = PluginRegistry::new();
+    let limited = ConsumerPreferences {
+        max_memory: Some(1),
+        max_message_time: Some(Duration::from_millis(5)),
+        ..ConsumerPreferences::default()
+    };
+    registry.register_plugin(Box::new(
+        MockSlowConsumerPlugin::new("limited", Duration::from_millis(20)).with_preferences(limited)
+    )).await.unwrap();
+    registry.register_plugin(Box::new(MockSlowConsumerPlugin::new("unlimited", Duration::from_millis(20)))).await.unwrap();
+    
+    let limited_consumer = queue.register_consumer("limited".to_string()).await.unwrap();
+    let unlimited_consumer = queue.register_consumer("unlimited".to_string()).await.unwrap();
+    registry.start_consuming("limited", queue.register_consumer("limited".to_string()).await.unwrap()).await.unwrap();
+    registry.start_consuming("unlimited", queue.register_consumer("unlimited".to_string()).await.unwrap()).await.unwrap();
+    
+    // Breaching a declared limit is reported but does not fail the call
+    for _ in 0..2 {
+        let message = ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), MessageData::None);
+        queue.enqueue(message.clone()).await.unwrap();
+        let message = Arc::new(message);
+        assert!(registry.process_message("limited", &limited_consumer, Arc::clone(&message)).await.is_ok());
+        assert!(registry.process_message("unlimited", &unlimited_consumer, message).await.is_ok());
+    }
+    
+    assert_eq!(registry.resource_breaches("limited"), ResourceBreaches { memory: 2, message_time: 2 });
+    assert_eq!(registry.resource_breaches("unlimited"), ResourceBreaches::default());
+}
+
+#[tokio::test]
+async fn test_plugin_registry_reports_queue_share_of_memory() {
+    use crate::queue::MultiConsumerQueue;
+    use crate::scanner::messages::{MessageData, MessageHeader, ScanMessage};
+    use std::time::Duration;
+    
+    let queue = MultiConsumerQueue::new(
+        Arc::new(crate::notifications::AsyncNotificationManager::new()),
+        Arc::new(crate::notifications::AsyncNotificationManager::new()),
+    );
+    queue.start().await.unwrap();
+    let mut registry = PluginRegistry::new();
+    registry.register_plugin(Box::new(MockSlowConsumerPlugin::new("consumer", Duration::ZERO))).await.unwrap();
+    registry.register_plugin(Box::new(MockPlugin::new("idle", false))).await.unwrap();
+    
+    let consumer = queue.register_consumer("consumer".to_string()).await.unwrap();
+    registry.start_consuming("consumer", consumer).await.unwrap();
+    let message = ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), MessageData::None);
+    queue.enqueue(message.clone()).await.unwrap();
+    
+    // Only consuming plugins are listed, holding the messages they have not acknowledged
+    let usage = registry.memory_usage().await;
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].plugin, "consumer");
+    assert_eq!(usage[0].queue_bytes, message.estimate_memory_usage());
+    assert_eq!(usage[0].state_bytes, 0);
+}
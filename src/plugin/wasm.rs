@@ -0,0 +1,564 @@
+//! WebAssembly Plugin Host
+//!
+//! Native plugins (see [`crate::plugin::dynamic`]) run with the full privileges
+//! of gstats. Third-party analyzers that are not trusted to that degree can be
+//! built as WebAssembly modules instead: each runs in its own sandbox, with no
+//! access to the host beyond the interface below, a cap on its linear memory and
+//! a fuel budget for every call into it.
+//!
+//! Modules are `.wasm` files in a plugin directory and are available when gstats
+//! is built with the `wasm-plugins` feature. Data crosses the sandbox boundary
+//! as JSON in the module's linear memory. A module exports:
+//!
+//! | Export | Signature | Purpose |
+//! |--------|-----------|---------|
+//! | `memory` | memory | Linear memory shared with the host |
+//! | `gstats_abi_version` | `() -> i32` | Must return [`WASM_ABI_VERSION`] |
+//! | `gstats_alloc` | `(len: i32) -> i32` | Reserve `len` bytes for input from the host |
+//! | `gstats_info` | `() -> i64` | Plugin name, version and description |
+//! | `gstats_process` | `(ptr: i32, len: i32) -> i32` | Handle one scan message; non-zero reports failure |
+//! | `gstats_finish` | `() -> i64` | Report results once the scan is complete |
+//!
+//! Results are returned as a packed `i64`: the high 32 bits hold the address of
+//! the JSON and the low 32 bits its length, with a length of zero meaning no
+//! result. `gstats_info` returns an object with `name`, `version`,
+//! `description` and optionally `author` and `file_content` (whether the plugin
+//! needs the content of files in the current tree); `gstats_process` receives
+//! each `ScanMessage`; `gstats_finish` returns a `PluginDataExport` or nothing.
+//!
+//! Every plugin gets its own store, so the memory cap and fuel budget of
+//! [`WasmLimits`] apply to each plugin separately. A module that exhausts its
+//! fuel or traps fails the call, which the registry reports like any other
+//! plugin error.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use wasmtime::{Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, Trap, TypedFunc};
+
+use crate::notifications::events::PluginEvent;
+use crate::notifications::traits::Publisher;
+use crate::notifications::typed_publishers::PluginEventPublisher;
+use crate::plugin::context::{ExecutionMetadata, ExecutionStatus, InvocationType, PluginContext, ScanContext, PluginRequest, PluginResponse};
+use crate::plugin::data_export::PluginDataExport;
+use crate::plugin::error::{PluginError, PluginResult};
+use crate::plugin::traits::{ConsumerPlugin, MessageConsumer, Plugin, PluginDataRequirements, PluginDescriptor, PluginFunction, PluginInfo, PluginType};
+use crate::queue::QueueEvent;
+use crate::scanner::messages::ScanMessage;
+
+/// Revision of the module interface described above
+pub const WASM_ABI_VERSION: i32 = 1;
+
+/// Default cap on a module's linear memory (64MB)
+pub const DEFAULT_WASM_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Default fuel for each call into a module, roughly one unit per instruction
+pub const DEFAULT_WASM_FUEL: u64 = 1_000_000_000;
+
+/// Resources a WebAssembly plugin may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Most linear memory the module may grow to, in bytes
+    pub max_memory: usize,
+    /// Fuel available to each call into the module
+    pub fuel: u64,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            max_memory: DEFAULT_WASM_MEMORY_LIMIT,
+            fuel: DEFAULT_WASM_FUEL,
+        }
+    }
+}
+
+/// Plugin details returned by `gstats_info`
+#[derive(Debug, Deserialize)]
+struct WasmPluginManifest {
+    name: String,
+    version: String,
+    description: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    file_content: bool,
+}
+
+/// Per-store state: the memory limiter
+struct WasmState {
+    limits: StoreLimits,
+}
+
+/// An instantiated module and its exports
+struct WasmInstance {
+    store: Store<WasmState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    info: TypedFunc<(), i64>,
+    process: TypedFunc<(i32, i32), i32>,
+    finish: TypedFunc<(), i64>,
+    fuel: u64,
+    name: String,
+}
+
+impl WasmInstance {
+    fn new(engine: &Engine, module: &Module, limits: WasmLimits, name: &str) -> PluginResult<Self> {
+        let state = WasmState {
+            limits: StoreLimitsBuilder::new().memory_size(limits.max_memory).instances(1).build(),
+        };
+        let mut store = Store::new(engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(limits.fuel).map_err(|e| PluginError::loading_failed(e.to_string()))?;
+
+        // Modules get no imports: anything they need must come through the interface
+        let instance = Instance::new(&mut store, module, &[])
+            .map_err(|e| PluginError::loading_failed(format!("Cannot instantiate {}: {}", name, e)))?;
+        let export_error = |e: wasmtime::Error| PluginError::loading_failed(format!("{} does not export the gstats interface: {}", name, e));
+
+        let abi_version = instance.get_typed_func::<(), i32>(&mut store, "gstats_abi_version").map_err(export_error)?
+            .call(&mut store, ())
+            .map_err(|e| PluginError::loading_failed(format!("{}: {}", name, e)))?;
+        if abi_version != WASM_ABI_VERSION {
+            return Err(PluginError::version_incompatible(format!(
+                "{} implements module interface revision {} (expected {})", name, abi_version, WASM_ABI_VERSION
+            )));
+        }
+
+        let memory = instance.get_memory(&mut store, "memory")
+            .ok_or_else(|| PluginError::loading_failed(format!("{} does not export its memory", name)))?;
+        Ok(Self {
+            alloc: instance.get_typed_func(&mut store, "gstats_alloc").map_err(export_error)?,
+            info: instance.get_typed_func(&mut store, "gstats_info").map_err(export_error)?,
+            process: instance.get_typed_func(&mut store, "gstats_process").map_err(export_error)?,
+            finish: instance.get_typed_func(&mut store, "gstats_finish").map_err(export_error)?,
+            memory,
+            store,
+            fuel: limits.fuel,
+            name: name.to_string(),
+        })
+    }
+
+    /// Read the plugin's details
+    fn manifest(&mut self) -> PluginResult<WasmPluginManifest> {
+        self.refuel()?;
+        let packed = self.info.call(&mut self.store, ()).map_err(|e| self.trap_error("gstats_info", e))?;
+        let bytes = self.read_output(packed)?
+            .ok_or_else(|| PluginError::loading_failed(format!("{} returned no plugin info", self.name)))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| PluginError::loading_failed(format!("{} returned invalid plugin info: {}", self.name, e)))
+    }
+
+    /// Hand a message to the module
+    fn process(&mut self, message: &ScanMessage) -> PluginResult<()> {
+        let input = serde_json::to_vec(message)
+            .map_err(|e| PluginError::execution_failed(format!("Cannot serialise message for {}: {}", self.name, e)))?;
+        let (ptr, len) = self.write_input(&input)?;
+        let status = self.refuel()
+            .and_then(|_| self.process.call(&mut self.store, (ptr, len)).map_err(|e| self.trap_error("gstats_process", e)))?;
+        if status != 0 {
+            return Err(PluginError::execution_failed(format!(
+                "plugin '{}' failed to process message {} (status {})", self.name, message.header().sequence(), status
+            )));
+        }
+        Ok(())
+    }
+
+    /// Collect the module's results
+    fn finish(&mut self) -> PluginResult<Option<PluginDataExport>> {
+        self.refuel()?;
+        let packed = self.finish.call(&mut self.store, ()).map_err(|e| self.trap_error("gstats_finish", e))?;
+        let Some(bytes) = self.read_output(packed)? else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| PluginError::execution_failed(format!("plugin '{}' returned an invalid export: {}", self.name, e)))
+    }
+
+    fn write_input(&mut self, input: &[u8]) -> PluginResult<(i32, i32)> {
+        let len = i32::try_from(input.len())
+            .map_err(|_| PluginError::execution_failed(format!("message too large for plugin '{}'", self.name)))?;
+        self.refuel()?;
+        let ptr = self.alloc.call(&mut self.store, len).map_err(|e| self.trap_error("gstats_alloc", e))?;
+        self.memory.write(&mut self.store, ptr as u32 as usize, input)
+            .map_err(|_| PluginError::execution_failed(format!("plugin '{}' allocated memory out of bounds", self.name)))?;
+        Ok((ptr, len))
+    }
+
+    fn read_output(&mut self, packed: i64) -> PluginResult<Option<Vec<u8>>> {
+        let (ptr, len) = ((packed as u64 >> 32) as usize, (packed as u64 & 0xffff_ffff) as usize);
+        if len == 0 {
+            return Ok(None);
+        }
+        let mut bytes = vec![0; len];
+        self.memory.read(&self.store, ptr, &mut bytes)
+            .map_err(|_| PluginError::execution_failed(format!("plugin '{}' returned a result out of bounds", self.name)))?;
+        Ok(Some(bytes))
+    }
+
+    /// Give the next call its full fuel budget
+    fn refuel(&mut self) -> PluginResult<()> {
+        self.store.set_fuel(self.fuel).map_err(|e| PluginError::execution_failed(e.to_string()))
+    }
+
+    fn trap_error(&self, export: &str, error: wasmtime::Error) -> PluginError {
+        match error.downcast_ref::<Trap>() {
+            Some(Trap::OutOfFuel) => PluginError::execution_failed(format!(
+                "plugin '{}' ran out of fuel in {} (limit {})", self.name, export, self.fuel
+            )),
+            _ => PluginError::execution_failed(format!("plugin '{}' trapped in {}: {}", self.name, export, error)),
+        }
+    }
+}
+
+/// Loads plugins from WebAssembly modules
+pub struct WasmPluginLoader {
+    engine: Engine,
+    limits: WasmLimits,
+}
+
+impl WasmPluginLoader {
+    /// Create a loader applying the default limits to each plugin
+    pub fn new() -> PluginResult<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)
+            .map_err(|e| PluginError::loading_failed(format!("Cannot create WebAssembly engine: {}", e)))?;
+        Ok(Self { engine, limits: WasmLimits::default() })
+    }
+
+    /// Apply different limits to the plugins this loader creates
+    pub fn with_limits(mut self, limits: WasmLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Load a module file and create a plugin from it
+    pub fn load(&self, path: &Path) -> PluginResult<Box<dyn Plugin>> {
+        let module = Module::from_file(&self.engine, path)
+            .map_err(|e| PluginError::loading_failed(format!("Cannot compile plugin module {}: {}", path.display(), e)))?;
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let plugin = self.instantiate(&module, &name)
+            .map_err(|e| PluginError::loading_failed(format!("{}: {}", path.display(), e)))?;
+
+        log::debug!("Loaded WebAssembly plugin '{}' from {}", plugin.info.name, path.display());
+        Ok(Box::new(plugin))
+    }
+
+    /// Create a plugin from a module in binary or text format
+    pub fn load_bytes(&self, bytes: &[u8], name: &str) -> PluginResult<WasmPlugin> {
+        let module = Module::new(&self.engine, bytes)
+            .map_err(|e| PluginError::loading_failed(format!("Cannot compile plugin module {}: {}", name, e)))?;
+        self.instantiate(&module, name)
+    }
+
+    /// Describe the plugin in a module file, for discovery
+    pub fn describe(&self, path: &Path) -> PluginResult<PluginDescriptor> {
+        let plugin = self.load(path)?;
+        Ok(PluginDescriptor {
+            info: plugin.plugin_info().clone(),
+            file_path: Some(path.to_path_buf()),
+            entry_point: "gstats_process".to_string(),
+            config: HashMap::new(),
+            functions: plugin.advertised_functions(),
+        })
+    }
+
+    fn instantiate(&self, module: &Module, name: &str) -> PluginResult<WasmPlugin> {
+        let mut instance = WasmInstance::new(&self.engine, module, self.limits, name)?;
+        let manifest = instance.manifest()?;
+        instance.name = manifest.name.clone();
+
+        let info = PluginInfo::new(
+            manifest.name,
+            manifest.version,
+            crate::scanner::get_api_version() as u32,
+            manifest.description,
+            manifest.author.unwrap_or_else(|| "unknown".to_string()),
+            PluginType::Processing,
+        );
+        Ok(WasmPlugin {
+            info,
+            file_content: manifest.file_content,
+            instance: Mutex::new(instance),
+            publisher: None,
+            initialized: false,
+        })
+    }
+}
+
+/// A plugin running inside a WebAssembly sandbox
+pub struct WasmPlugin {
+    info: PluginInfo,
+    file_content: bool,
+    instance: Mutex<WasmInstance>,
+    publisher: Option<Arc<PluginEventPublisher>>,
+    initialized: bool,
+}
+
+impl WasmPlugin {
+    fn instance(&self) -> std::sync::MutexGuard<'_, WasmInstance> {
+        self.instance.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Collect the module's results and publish them
+    async fn publish_export(&self, scan_id: &str) -> PluginResult<()> {
+        let Some(mut export) = self.instance().finish()? else {
+            return Ok(());
+        };
+        // A module may only report results under its own name
+        export.plugin_id = self.info.name.clone();
+
+        let Some(publisher) = &self.publisher else {
+            log::debug!("WebAssembly plugin '{}' has no publisher for its results", self.info.name);
+            return Ok(());
+        };
+        let event = PluginEvent::DataReady {
+            plugin_id: self.info.name.clone(),
+            scan_id: scan_id.to_string(),
+            export: Arc::new(export),
+        };
+        if let Err(e) = publisher.publish(event).await {
+            log::warn!("Failed to publish DataReady event for plugin '{}': {}", self.info.name, e);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Plugin for WasmPlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, context: &dyn ScanContext) -> PluginResult<()> {
+        let context = PluginContext::from_scan_context(context)?;
+        self.publisher = context.plugin_publisher.clone();
+        self.initialized = true;
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        match request {
+            PluginRequest::Execute { request_id, invocation_type, .. } => {
+                if let InvocationType::Function(function) = &invocation_type {
+                    if function != &self.info.name {
+                        return Err(PluginError::execution_failed(format!("Unknown function: {}", function)));
+                    }
+                }
+                // Results are published when the scan completes
+                Ok(PluginResponse::Execute {
+                    request_id,
+                    status: ExecutionStatus::Success,
+                    data: serde_json::json!({ "function": self.info.name }),
+                    metadata: ExecutionMetadata {
+                        duration_us: 0,
+                        memory_used: 0,
+                        entries_processed: 0,
+                        plugin_version: self.info.version.clone(),
+                        extra: HashMap::new(),
+                    },
+                    errors: vec![],
+                })
+            }
+            PluginRequest::GetCapabilities => {
+                Ok(PluginResponse::Capabilities(self.info.capabilities.clone()))
+            }
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        self.initialized = false;
+        Ok(())
+    }
+
+    fn advertised_functions(&self) -> Vec<PluginFunction> {
+        vec![PluginFunction {
+            name: self.info.name.clone(),
+            aliases: vec![],
+            description: self.info.description.clone(),
+            is_default: true,
+        }]
+    }
+
+    fn default_function(&self) -> Option<&str> {
+        Some(&self.info.name)
+    }
+
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn as_data_requirements(&self) -> Option<&dyn PluginDataRequirements> {
+        Some(self)
+    }
+}
+
+impl PluginDataRequirements for WasmPlugin {
+    fn requires_current_file_content(&self) -> bool {
+        self.file_content
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for WasmPlugin {
+    async fn start_consuming(&mut self, _consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        self.instance().process(&message)?;
+        consumer.acknowledge(message.header().sequence()).await
+            .map_err(|e| PluginError::execution_failed(format!("Failed to acknowledge message: {}", e)))
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        if let QueueEvent::ScanComplete { scan_id, .. } = event {
+            self.publish_export(scan_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::data_export::{DataPayload, PluginDataExportBuilder};
+    use crate::scanner::messages::{MessageData, MessageHeader};
+
+    const INFO: &str = r#"{"name":"wasm-test","version":"0.1.0","description":"Counts scan messages","file_content":true}"#;
+    const EXPORT_OFFSET: usize = 4096;
+    const INPUT_OFFSET: i32 = 32768;
+
+    /// Escape bytes for a WAT data segment
+    fn wat_string(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("\\{:02x}", byte)).collect()
+    }
+
+    fn packed(ptr: usize, len: usize) -> i64 {
+        ((ptr as i64) << 32) | len as i64
+    }
+
+    /// A module that counts the messages it receives and reports one export if it saw any
+    fn module(abi_version: i32, pages: u32, alloc: &str, process: &str) -> String {
+        let export = PluginDataExportBuilder::new()
+            .plugin_id("wasm-test")
+            .title("Messages")
+            .data(DataPayload::Empty)
+            .build()
+            .unwrap();
+        let export = serde_json::to_vec(&export).unwrap();
+        format!(
+            r#"(module
+                (memory (export "memory") {pages})
+                (global $count (mut i32) (i32.const 0))
+                (data (i32.const 0) "{info}")
+                (data (i32.const {export_offset}) "{export}")
+                (func (export "gstats_abi_version") (result i32) (i32.const {abi_version}))
+                (func (export "gstats_alloc") (param $len i32) (result i32) {alloc})
+                (func (export "gstats_info") (result i64) (i64.const {info_packed}))
+                (func (export "gstats_process") (param $ptr i32) (param $len i32) (result i32) {process})
+                (func (export "gstats_finish") (result i64)
+                    (if (result i64) (global.get $count)
+                        (then (i64.const {export_packed}))
+                        (else (i64.const 0)))))"#,
+            info = wat_string(INFO.as_bytes()),
+            info_packed = packed(0, INFO.len()),
+            export_offset = EXPORT_OFFSET,
+            export = wat_string(&export),
+            export_packed = packed(EXPORT_OFFSET, export.len()),
+        )
+    }
+
+    fn counting_module() -> String {
+        module(
+            WASM_ABI_VERSION,
+            1,
+            &format!("(i32.const {})", INPUT_OFFSET),
+            "(global.set $count (i32.add (global.get $count) (i32.const 1))) (i32.const 0)",
+        )
+    }
+
+    fn message(sequence: u64) -> ScanMessage {
+        ScanMessage::new(
+            MessageHeader::new(sequence, "test-scan".to_string()),
+            MessageData::None,
+        )
+    }
+
+    fn load(wat: &str, limits: WasmLimits) -> PluginResult<WasmPlugin> {
+        WasmPluginLoader::new()?.with_limits(limits).load_bytes(wat.as_bytes(), "test")
+    }
+
+    #[test]
+    fn test_module_processes_messages_and_reports_results() {
+        let plugin = load(&counting_module(), WasmLimits::default()).unwrap();
+        assert_eq!(plugin.plugin_info().name, "wasm-test");
+        assert_eq!(plugin.plugin_info().version, "0.1.0");
+        assert_eq!(plugin.default_function(), Some("wasm-test"));
+        assert!(plugin.as_data_requirements().is_some_and(|r| r.requires_current_file_content()));
+
+        assert!(plugin.instance().finish().unwrap().is_none());
+
+        plugin.instance().process(&message(1)).unwrap();
+        plugin.instance().process(&message(2)).unwrap();
+        let export = plugin.instance().finish().unwrap().expect("module should report results");
+        assert_eq!(export.plugin_id, "wasm-test");
+        assert_eq!(export.title, "Messages");
+    }
+
+    #[test]
+    fn test_runaway_module_runs_out_of_fuel() {
+        let wat = module(WASM_ABI_VERSION, 1, &format!("(i32.const {})", INPUT_OFFSET), "(loop $spin (br $spin)) (i32.const 0)");
+        let plugin = load(&wat, WasmLimits { fuel: 10_000, ..WasmLimits::default() }).unwrap();
+
+        let error = plugin.instance().process(&message(1)).unwrap_err();
+        assert!(error.to_string().contains("ran out of fuel"), "{}", error);
+
+        // Each call gets a fresh budget, so the plugin can still report
+        assert!(plugin.instance().finish().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_module_memory_is_capped() {
+        let page = 64 * 1024;
+        let limits = WasmLimits { max_memory: 2 * page, ..WasmLimits::default() };
+
+        let oversized = module(WASM_ABI_VERSION, 3, &format!("(i32.const {})", INPUT_OFFSET), "(i32.const 0)");
+        assert!(load(&oversized, limits).is_err());
+
+        // Growing past the cap fails, leaving the module with nowhere to put its input
+        let growing = module(
+            WASM_ABI_VERSION,
+            1,
+            "(i32.mul (memory.grow (i32.const 4)) (i32.const 65536))",
+            "(i32.const 0)",
+        );
+        let plugin = load(&growing, limits).unwrap();
+        let error = plugin.instance().process(&message(1)).unwrap_err();
+        assert!(error.to_string().contains("out of bounds"), "{}", error);
+    }
+
+    #[test]
+    fn test_incompatible_module_is_rejected() {
+        let wat = module(WASM_ABI_VERSION + 1, 1, "(i32.const 0)", "(i32.const 0)");
+        let error = load(&wat, WasmLimits::default()).err().expect("module should be rejected");
+        assert!(matches!(error, PluginError::VersionIncompatible { .. }), "{}", error);
+
+        let not_a_plugin = "(module (memory (export \"memory\") 1))";
+        assert!(load(not_a_plugin, WasmLimits::default()).is_err());
+    }
+}
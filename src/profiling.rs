@@ -0,0 +1,256 @@
+//! Stage Profiling
+//!
+//! `--profile` times each stage of the pipeline — commit traversal, queue
+//! operations, plugin message handling, event processors and exports — and
+//! prints a stage-by-stage breakdown at the end of the run. Timings are
+//! aggregated per stage name; stages run concurrently on several threads, so
+//! their totals can add up to more than the run's wall time.
+//!
+//! `--profile-trace <FILE>` also records every timed call as a Chrome trace
+//! event, which Perfetto, speedscope and `chrome://tracing` show as a
+//! per-thread flame chart.
+//!
+//! Profiling is off unless enabled; a disabled [`stage`] costs one atomic load.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::display::table::TableBuilder;
+use crate::display::ColourManager;
+
+/// Trace events kept, bounding the memory a long profiled run uses
+const MAX_TRACE_EVENTS: usize = 1_000_000;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PROFILE: OnceLock<Profile> = OnceLock::new();
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Aggregated timings of one stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageTiming {
+    pub stage: String,
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl StageTiming {
+    pub fn mean(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.calls as u32
+        }
+    }
+}
+
+/// One timed call, in the Chrome trace event format
+#[derive(Debug, Clone, Serialize)]
+struct TraceEvent {
+    name: String,
+    ph: &'static str,
+    /// Start, in microseconds since profiling began
+    ts: u64,
+    /// Duration in microseconds
+    dur: u64,
+    pid: u32,
+    tid: u64,
+}
+
+/// Timings collected while profiling
+pub struct Profile {
+    started: Instant,
+    stages: Mutex<HashMap<String, StageTiming>>,
+    trace: Option<Mutex<Vec<TraceEvent>>>,
+}
+
+impl Profile {
+    pub fn new(trace: bool) -> Self {
+        Self {
+            started: Instant::now(),
+            stages: Mutex::new(HashMap::new()),
+            trace: trace.then(|| Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Add a call of `stage` that started at `start` and took `duration`
+    pub fn record(&self, stage: &str, start: Instant, duration: Duration) {
+        {
+            let mut stages = self.stages.lock().unwrap_or_else(|e| e.into_inner());
+            let timing = stages.entry(stage.to_string()).or_insert_with(|| StageTiming {
+                stage: stage.to_string(),
+                calls: 0,
+                total: Duration::ZERO,
+                max: Duration::ZERO,
+            });
+            timing.calls += 1;
+            timing.total += duration;
+            timing.max = timing.max.max(duration);
+        }
+        if let Some(trace) = &self.trace {
+            let mut trace = trace.lock().unwrap_or_else(|e| e.into_inner());
+            if trace.len() < MAX_TRACE_EVENTS {
+                trace.push(TraceEvent {
+                    name: stage.to_string(),
+                    ph: "X",
+                    ts: start.saturating_duration_since(self.started).as_micros() as u64,
+                    dur: duration.as_micros() as u64,
+                    pid: std::process::id(),
+                    tid: THREAD_ID.with(|id| *id),
+                });
+            }
+        }
+    }
+
+    /// Stage timings, longest total first
+    pub fn stages(&self) -> Vec<StageTiming> {
+        let mut stages: Vec<StageTiming> = self.stages.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect();
+        stages.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.stage.cmp(&b.stage)));
+        stages
+    }
+
+    /// Time since profiling began
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Write the recorded calls as a Chrome trace, returning how many were written
+    pub fn write_trace(&self, path: &Path) -> std::io::Result<usize> {
+        let events = match &self.trace {
+            Some(trace) => trace.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            None => Vec::new(),
+        };
+        let document = serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        });
+        std::fs::write(path, serde_json::to_vec(&document)?)?;
+        Ok(events.len())
+    }
+}
+
+/// Start profiling, recording a trace of every call if `trace` is set
+pub fn enable(trace: bool) {
+    PROFILE.get_or_init(|| Profile::new(trace));
+    ENABLED.store(true, Ordering::Release);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// The profile being collected, if profiling is enabled
+pub fn profile() -> Option<&'static Profile> {
+    if is_enabled() {
+        PROFILE.get()
+    } else {
+        None
+    }
+}
+
+/// Add a call timed elsewhere, e.g. by a component that keeps its own timings
+pub fn record(stage: &str, start: Instant, duration: Duration) {
+    if let Some(profile) = profile() {
+        profile.record(stage, start, duration);
+    }
+}
+
+/// Time a stage until the returned guard is dropped
+pub fn stage(name: &str) -> StageGuard {
+    StageGuard {
+        timing: is_enabled().then(|| (name.to_string(), Instant::now())),
+    }
+}
+
+/// Time a stage of one plugin's work, reported as `<name> (<plugin>)`
+pub fn plugin_stage(name: &str, plugin: &str) -> StageGuard {
+    StageGuard {
+        timing: is_enabled().then(|| (format!("{} ({})", name, plugin), Instant::now())),
+    }
+}
+
+/// A timed stage that is recorded when dropped
+pub struct StageGuard {
+    timing: Option<(String, Instant)>,
+}
+
+impl Drop for StageGuard {
+    fn drop(&mut self) {
+        if let Some((stage, start)) = self.timing.take() {
+            record(&stage, start, start.elapsed());
+        }
+    }
+}
+
+/// Table of stage timings, with each stage's share of the run's wall time
+pub fn profile_table(stages: &[StageTiming], elapsed: Duration, colour_manager: &ColourManager) -> String {
+    let mut table = TableBuilder::new()
+        .headers(["Stage", "Calls", "Total", "Mean", "Max", "% of Run"].map(String::from).to_vec());
+    for stage in stages {
+        let share = if elapsed.is_zero() { 0.0 } else { stage.total.as_secs_f64() / elapsed.as_secs_f64() * 100.0 };
+        table = table.add_row(vec![
+            stage.stage.clone(),
+            stage.calls.to_string(),
+            format_duration(stage.total),
+            format_duration(stage.mean()),
+            format_duration(stage.max),
+            format!("{:.1}%", share),
+        ]);
+    }
+    table.build_with_colors(colour_manager)
+}
+
+/// A duration in the most readable of µs, ms and s
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_timings_aggregate() {
+        let profile = Profile::new(true);
+        let start = Instant::now();
+        profile.record("scan.commit", start, Duration::from_millis(3));
+        profile.record("scan.commit", start, Duration::from_millis(5));
+        profile.record("queue.enqueue", start, Duration::from_micros(40));
+
+        let stages = profile.stages();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].stage, "scan.commit");
+        assert_eq!((stages[0].calls, stages[0].total, stages[0].max), (2, Duration::from_millis(8), Duration::from_millis(5)));
+        assert_eq!(stages[0].mean(), Duration::from_millis(4));
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("trace.json");
+        assert_eq!(profile.write_trace(&path).unwrap(), 3);
+        let trace: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(trace["traceEvents"][1]["dur"], 5000);
+        assert_eq!(trace["traceEvents"][2]["ph"], "X");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(250)), "250µs");
+        assert_eq!(format_duration(Duration::from_micros(12_340)), "12.3ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50s");
+    }
+}
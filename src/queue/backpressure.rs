@@ -0,0 +1,60 @@
+//! Queue Backpressure Policies
+//!
+//! A backpressure policy decides what happens when the queue reaches its
+//! message count or memory limit. Messages consumers have already acknowledged
+//! are always collected first; the policy only applies when that does not free
+//! enough room.
+//!
+//! - **Block**: the producer waits for consumers to acknowledge messages. If no
+//!   consumer makes progress within the block timeout the queue is allowed to
+//!   grow past its limits rather than stalling the scan indefinitely.
+//! - **DropOldest**: the oldest messages are discarded, whether or not every
+//!   consumer has read them. Consumers skip forward past dropped messages.
+//! - **SpillToDisk**: new messages are written to temporary segment files and
+//!   moved back into memory as consumers free room; consumers that reach them
+//!   first read them from disk.
+
+use serde::{Deserialize, Serialize};
+
+/// Policy names accepted by `--backpressure` and the `backpressure` config key
+pub const POLICIES: [&str; 3] = ["block", "drop-oldest", "spill-to-disk"];
+
+/// What the queue does when it reaches its limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackpressurePolicy {
+    /// Hold the producer back until consumers catch up
+    #[default]
+    Block,
+    /// Discard the oldest messages to make room
+    DropOldest,
+    /// Write new messages to disk until consumers catch up
+    SpillToDisk,
+}
+
+impl BackpressurePolicy {
+    /// Parse a policy name as accepted on the command line
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "block" => Some(BackpressurePolicy::Block),
+            "drop-oldest" => Some(BackpressurePolicy::DropOldest),
+            "spill-to-disk" => Some(BackpressurePolicy::SpillToDisk),
+            _ => None,
+        }
+    }
+
+    /// Policy name as accepted on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackpressurePolicy::Block => "block",
+            BackpressurePolicy::DropOldest => "drop-oldest",
+            BackpressurePolicy::SpillToDisk => "spill-to-disk",
+        }
+    }
+}
+
+impl std::fmt::Display for BackpressurePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
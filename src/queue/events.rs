@@ -6,20 +6,26 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use async_trait::async_trait;
 use log::{info, debug, trace};
 
 use crate::notifications::AsyncNotificationManager;
-use crate::notifications::events::{QueueEvent, ScanEvent};
+use crate::notifications::events::{MemoryPressureLevel, QueueEvent, ScanEvent};
 use crate::notifications::traits::{Publisher, Subscriber, NotificationManager};
 use crate::notifications::error::NotificationResult;
 use crate::queue::statistics::ScanStatistics;
 
+/// Capacity of the channel delivering queue events to consumer plugins
+const CONSUMER_EVENT_CAPACITY: usize = 256;
+
 /// Event handling implementation for MultiConsumerQueue
+#[derive(Clone)]
 pub struct QueueEventHandler {
     pub scan_statistics: Arc<RwLock<HashMap<String, ScanStatistics>>>,
     pub notification_manager: Arc<AsyncNotificationManager<QueueEvent>>,
+    /// Queue events as consumer plugins receive them
+    consumer_events: broadcast::Sender<crate::queue::notifications::QueueEvent>,
 }
 
 impl QueueEventHandler {
@@ -30,11 +36,20 @@ impl QueueEventHandler {
         Self {
             scan_statistics,
             notification_manager,
+            consumer_events: broadcast::channel(CONSUMER_EVENT_CAPACITY).0,
         }
     }
 
+    /// Receive the queue events published from now on, to pass to consumer plugins
+    pub fn subscribe_consumer_events(&self) -> broadcast::Receiver<crate::queue::notifications::QueueEvent> {
+        self.consumer_events.subscribe()
+    }
+
     /// Helper method to publish QueueEvents
     pub async fn publish_queue_event(&self, event: crate::queue::notifications::QueueEvent) -> NotificationResult<()> {
+        // Consumer plugins take the event as it is; having no receivers is not an error
+        let _ = self.consumer_events.send(event.clone());
+        
         // Convert our queue notifications QueueEvent to the notification system's QueueEvent
         let notification_event = match event {
             crate::queue::notifications::QueueEvent::ScanStarted { scan_id, timestamp } => {
@@ -63,6 +78,33 @@ impl QueueEventHandler {
                     emptied_at: std::time::SystemTime::now(),
                 }
             },
+            crate::queue::notifications::QueueEvent::ProducerBlocked { scan_id, timestamp, .. } => {
+                QueueEvent::QueueFull {
+                    queue_id: scan_id,
+                    capacity: 0,
+                    memory_limit_bytes: None,
+                    oldest_message_age_ms: None,
+                    full_at: std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(timestamp),
+                }
+            },
+            crate::queue::notifications::QueueEvent::MessagesDropped { scan_id, timestamp, .. } => {
+                QueueEvent::MemoryPressure {
+                    queue_id: scan_id,
+                    current_usage_bytes: 0,
+                    limit_bytes: 0,
+                    pressure_level: MemoryPressureLevel::Critical,
+                    detected_at: std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(timestamp),
+                }
+            },
+            crate::queue::notifications::QueueEvent::MessagesSpilled { scan_id, timestamp, .. } => {
+                QueueEvent::MemoryPressure {
+                    queue_id: scan_id,
+                    current_usage_bytes: 0,
+                    limit_bytes: 0,
+                    pressure_level: MemoryPressureLevel::High,
+                    detected_at: std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(timestamp),
+                }
+            },
             _ => {
                 // For other event types, create a generic message added event
                 QueueEvent::MessageAdded {
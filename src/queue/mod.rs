@@ -11,8 +11,10 @@
 //!
 //! - **SharedMessageQueue**: Core queue implementation with producer interface
 //! - **QueueConsumer**: Abstract consumer API for plugins
+//! - **Routing**: Each consumer is given only the message types its plugin handles
 //! - **QueueEvent System**: Generic notification system for coordination
 //! - **Memory Monitoring**: Queue memory usage tracking and reporting
+//! - **Backpressure**: Block, drop-oldest or spill-to-disk policies applied at the queue limits
 //!
 //! # Usage
 //!
@@ -23,19 +25,25 @@
 //!
 //! # tokio_test::block_on(async {
 //! // Create a queue for a scanning session
+//! use gstats::notifications::typed_publishers::{QueueEventPublisher, ScanEventPublisher};
 //! let notification_manager = Arc::new(gstats::notifications::AsyncNotificationManager::new());
-//! let queue = SharedMessageQueue::new("scan-001".to_string(), notification_manager);
+//! let queue_publisher = Arc::new(QueueEventPublisher::new(notification_manager.clone()));
+//! let scan_publisher = Arc::new(ScanEventPublisher::new(notification_manager));
+//! let queue = SharedMessageQueue::new(queue_publisher, scan_publisher);
 //!
 //! // Producer: Start scanning and add messages
 //! queue.start().await.unwrap();
 //!
 //! // Create a sample message
-//! let header = MessageHeader::new(0);
+//! let header = MessageHeader::new(0, "scan-001".to_string());
 //! let data = MessageData::CommitInfo {
 //!     hash: "abc123".to_string(),
 //!     author: "John Doe".to_string(),
 //!     message: "Fix bug".to_string(),
 //!     timestamp: 1234567890,
+//!     committer: "John Doe".to_string(),
+//!     author_timestamp: 1234567890,
+//!     parents: vec![],
 //!     changed_files: vec![],
 //! };
 //! let scan_message = ScanMessage::new(header, data);
@@ -49,6 +57,7 @@
 //! # });
 //! ```
 
+pub mod backpressure;
 pub mod error;
 pub mod notifications;
 pub mod shared_queue;
@@ -58,15 +67,19 @@ pub mod queue_consumer;
 pub mod statistics;
 pub mod events;
 pub mod consumer_registry;
+pub mod routing;
+pub mod spill;
 
 // Re-export main types for convenience
+pub use backpressure::BackpressurePolicy;
 pub use error::{QueueError, QueueResult};
 pub use notifications::QueueEvent;
 pub use shared_queue::SharedMessageQueue;
 pub use memory::MemoryMonitor;
 pub use multi_consumer::MultiConsumerQueue;
 pub use statistics::QueueStatistics;
-pub use queue_consumer::QueueConsumer;
+pub use queue_consumer::{ConsumerStatistics, QueueConsumer};
+pub use routing::MessageRoute;
 
 
 #[cfg(test)]
@@ -19,20 +19,26 @@
 //! - Messages are Arc-wrapped for efficient sharing
 //! - Garbage collection based on low water mark (minimum consumer sequence)
 //! - Configurable memory thresholds and collection intervals
+//! - A backpressure policy (block, drop-oldest or spill-to-disk) applied when
+//!   the queue reaches its message count or memory limit
 
 use std::collections::{VecDeque, HashMap};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Mutex};
 
-use crate::queue::{QueueError, QueueResult, MemoryMonitor};
+use crate::queue::{BackpressurePolicy, QueueError, QueueResult, MemoryMonitor};
+use crate::queue::spill::{SpillBuffer, DEFAULT_SEGMENT_SIZE};
 use crate::queue::statistics::{QueueStatistics, ScanStatistics};
 use crate::queue::events::QueueEventHandler;
 use crate::queue::consumer_registry::{ConsumerRegistry, ConsumerProgress};
 use crate::notifications::AsyncNotificationManager;
 use crate::notifications::events::{QueueEvent, ScanEvent};
 use crate::notifications::traits::{Publisher, Subscriber};
+use crate::scanner::config::ScannerConfig;
 use crate::scanner::messages::ScanMessage;
+use crate::shutdown::ShutdownController;
 use async_trait::async_trait;
 use crate::notifications::error::NotificationResult;
 
@@ -56,6 +62,24 @@ pub struct MultiConsumerConfig {
     
     /// Batch size for garbage collection
     pub gc_batch_size: usize,
+    
+    /// Memory limit for queued messages (bytes), enforced by the backpressure policy
+    pub memory_limit: usize,
+    
+    /// Policy applied at the queue limits; without one a full queue rejects messages
+    pub backpressure: Option<BackpressurePolicy>,
+    
+    /// How long a blocked producer waits for consumers to make progress
+    pub block_timeout: Duration,
+    
+    /// Directory for spill segment files (defaults to the system temporary directory)
+    pub spill_directory: Option<PathBuf>,
+    
+    /// Size at which a new spill segment file is started (bytes)
+    pub spill_segment_size: u64,
+    
+    /// Shutdown state of the run; once a shutdown is requested a blocked producer stops waiting for room
+    pub shutdown: ShutdownController,
 }
 
 impl Default for MultiConsumerConfig {
@@ -67,6 +91,23 @@ impl Default for MultiConsumerConfig {
             consumer_timeout: Duration::from_secs(300), // 5 minutes
             auto_gc: true,
             gc_batch_size: 1000,               // Remove 1K messages at a time
+            memory_limit: 512 * 1024 * 1024,   // 512MB
+            backpressure: None,
+            block_timeout: Duration::from_secs(10),
+            spill_directory: None,
+            spill_segment_size: DEFAULT_SEGMENT_SIZE,
+            shutdown: ShutdownController::default(),
+        }
+    }
+}
+
+impl MultiConsumerConfig {
+    /// Queue configuration for a scan, taking the memory limit and policy from the scanner configuration
+    pub fn from_scanner_config(scanner_config: &ScannerConfig) -> Self {
+        Self {
+            memory_limit: scanner_config.max_memory_bytes,
+            backpressure: scanner_config.backpressure,
+            ..Self::default()
         }
     }
 }
@@ -174,6 +215,12 @@ pub struct MultiConsumerQueue {
     
     /// Event handler for publishing and subscribing to events
     event_handler: QueueEventHandler,
+    
+    /// Messages written to disk under the spill-to-disk policy
+    spill: Arc<Mutex<Option<SpillBuffer>>>,
+    
+    /// Backpressure state and statistics
+    backpressure_state: Arc<Mutex<BackpressureState>>,
 }
 
 
@@ -196,6 +243,25 @@ struct GarbageCollectionState {
     last_low_water_mark: u64,
 }
 
+/// Backpressure state and statistics
+#[derive(Debug, Default)]
+struct BackpressureState {
+    /// Low water mark at which a blocked producer last gave up waiting for consumers
+    stalled_at: Option<u64>,
+    
+    /// Total messages dropped under the drop-oldest policy
+    messages_dropped: u64,
+    
+    /// Total messages written to disk under the spill-to-disk policy
+    messages_spilled: u64,
+    
+    /// Times the producer waited for consumers under the block policy
+    producer_blocks: u64,
+    
+    /// Total time the producer spent blocked
+    producer_blocked_time: Duration,
+}
+
 impl MultiConsumerQueue {
     /// Create a new multi-consumer queue
     pub fn new(
@@ -243,6 +309,10 @@ impl MultiConsumerQueue {
             memory_usage: 0,
             active_consumers: 0,
             total_messages: 0,
+            messages_dropped: 0,
+            messages_spilled: 0,
+            producer_blocks: 0,
+            producer_blocked_time: Duration::ZERO,
         };
         
         let gc_state = GarbageCollectionState {
@@ -272,6 +342,8 @@ impl MultiConsumerQueue {
             active: Arc::new(RwLock::new(false)),
             scan_notification_manager,
             event_handler,
+            spill: Arc::new(Mutex::new(None)),
+            backpressure_state: Arc::new(Mutex::new(BackpressureState::default())),
         }
     }
     
@@ -299,6 +371,11 @@ impl MultiConsumerQueue {
         Ok(())
     }
     
+    /// Receive the queue events published from now on, as consumer plugins handle them
+    pub fn subscribe_consumer_events(&self) -> tokio::sync::broadcast::Receiver<crate::queue::QueueEvent> {
+        self.event_handler.subscribe_consumer_events()
+    }
+    
     
     
     /// Check if queue is active
@@ -307,44 +384,90 @@ impl MultiConsumerQueue {
     }
     
     /// Add a message to the queue
-    pub async fn enqueue(&self, mut message: ScanMessage) -> QueueResult<u64> {
+    pub async fn enqueue(&self, message: ScanMessage) -> QueueResult<u64> {
+        let _stage = crate::profiling::stage("queue.enqueue");
+        #[cfg(feature = "otel")]
+        let mut span = crate::telemetry::span("queue.enqueue", Vec::new());
+        let result = self.push_message(message).await;
+        #[cfg(feature = "otel")]
+        {
+            span.record_result(&result);
+            if let Ok(sequence) = &result {
+                span.set_attribute(crate::telemetry::KeyValue::new("sequence", *sequence as i64));
+            }
+        }
+        result
+    }
+    
+    /// Store a message, applying the backpressure policy, and return its sequence number
+    async fn push_message(&self, mut message: ScanMessage) -> QueueResult<u64> {
         if !self.is_active().await {
             return Err(QueueError::operation_failed("Queue not active"));
         }
-        
-        
-        // Assign sequence number
-        let sequence = {
-            let mut tracker = self.sequence_tracker.write().await;
-            let seq = tracker.next_sequence();
-            message.header.sequence = seq;
-            seq
-        };
-        
-        // Check queue size limits
-        {
-            let messages = self.messages.read().await;
-            if messages.len() >= self.config.max_queue_size {
-                return Err(QueueError::QueueFull);
-            }
+        #[cfg(feature = "fault-injection")]
+        if crate::faults::queue_error() {
+            return Err(QueueError::operation_failed("Injected queue fault"));
         }
         
         // Store scan_id before moving message
         let scan_id = message.header.scan_id.clone();
+        let message_size = message.estimate_memory_usage();
+        
+        // Check queue limits, making room according to the backpressure policy
+        match self.config.backpressure {
+            None => {
+                let messages = self.messages.read().await;
+                if messages.len() >= self.config.max_queue_size {
+                    return Err(QueueError::QueueFull);
+                }
+            }
+            Some(policy) => {
+                let queue_len = self.messages.read().await.len();
+                if self.over_limits(queue_len, message_size).await {
+                    // Messages every consumer has finished with go before the policy applies
+                    self.garbage_collect_messages(self.calculate_low_water_mark().await).await?;
+                    if policy == BackpressurePolicy::Block {
+                        self.wait_for_room(&scan_id, message_size).await?;
+                    }
+                }
+            }
+        }
         
-        // Wrap message in Arc for sharing
-        let arc_message = Arc::new(message);
-        
-        // Record memory usage
-        self.memory_monitor.record_push(&arc_message).await;
-        
-        // Add to queue
-        let queue_size = {
+        let mut dropped = None;
+        let mut spilled = false;
+        let (sequence, queue_size) = {
             let mut messages = self.messages.write().await;
-            messages.push_back(arc_message);
-            messages.len()
+            if self.config.backpressure == Some(BackpressurePolicy::DropOldest) {
+                dropped = self.drop_oldest(&mut messages, message_size).await;
+            }
+            
+            // Assign sequence number while holding the queue so messages are stored in sequence order
+            let sequence = self.sequence_tracker.write().await.next_sequence();
+            message.header.sequence = sequence;
+            
+            if self.config.backpressure == Some(BackpressurePolicy::SpillToDisk)
+                && self.should_spill(messages.len(), message_size).await
+            {
+                self.spill_message(&message).await?;
+                spilled = true;
+            } else {
+                // Wrap message in Arc for sharing
+                let arc_message = Arc::new(message);
+                
+                // Record memory usage
+                self.memory_monitor.record_push(&arc_message).await;
+                messages.push_back(arc_message);
+            }
+            (sequence, messages.len())
         };
         
+        if let Some((first, last)) = dropped {
+            self.publish_backpressure_event(crate::queue::QueueEvent::messages_dropped(scan_id.clone(), first, last)).await;
+        }
+        if spilled {
+            self.publish_backpressure_event(crate::queue::QueueEvent::messages_spilled(scan_id.clone(), sequence, sequence)).await;
+        }
+        
         // Update statistics
         self.update_queue_stats(queue_size).await;
         
@@ -360,6 +483,143 @@ impl MultiConsumerQueue {
         Ok(sequence)
     }
     
+    /// Check whether adding a message would take the in-memory queue past its limits
+    ///
+    /// An empty queue always accepts a message, however large.
+    async fn over_limits(&self, queue_len: usize, message_size: usize) -> bool {
+        if queue_len == 0 {
+            return false;
+        }
+        queue_len >= self.config.max_queue_size
+            || self.memory_monitor.get_stats().await.current_size + message_size > self.config.memory_limit
+    }
+    
+    /// Hold the producer back until consumers free enough room for a message
+    ///
+    /// Waiting continues for as long as consumers keep acknowledging messages. When
+    /// none make progress within the block timeout the queue grows past its limits
+    /// instead, and the producer is not blocked again until the consumers move on.
+    async fn wait_for_room(&self, scan_id: &str, message_size: usize) -> QueueResult<()> {
+        let started = Instant::now();
+        let mut last_progress = started;
+        let mut low_water_mark = self.calculate_low_water_mark().await;
+        let mut blocked = false;
+        
+        loop {
+            let queue_len = self.messages.read().await.len();
+            if !self.over_limits(queue_len, message_size).await {
+                break;
+            }
+            // While shutting down the producer hands over what it has read so consumers can drain it
+            if self.config.shutdown.is_requested() {
+                log::debug!("Shutdown requested; letting the queue grow past its limits");
+                break;
+            }
+            
+            let mut state = self.backpressure_state.lock().await;
+            if state.stalled_at == Some(low_water_mark) {
+                break;
+            }
+            if last_progress.elapsed() >= self.config.block_timeout {
+                log::warn!("Queue consumers made no progress for {}s; letting the queue grow past its limits",
+                           self.config.block_timeout.as_secs());
+                state.stalled_at = Some(low_water_mark);
+                break;
+            }
+            drop(state);
+            
+            blocked = true;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            
+            let current = self.calculate_low_water_mark().await;
+            if current != low_water_mark {
+                low_water_mark = current;
+                last_progress = Instant::now();
+                self.garbage_collect_messages(current).await?;
+            }
+        }
+        
+        if blocked {
+            let mut state = self.backpressure_state.lock().await;
+            state.producer_blocks += 1;
+            state.producer_blocked_time += started.elapsed();
+            drop(state);
+            let waited_ms = started.elapsed().as_millis() as u64;
+            log::debug!("Producer for scan '{}' blocked for {}ms waiting for queue consumers", scan_id, waited_ms);
+            self.publish_backpressure_event(crate::queue::QueueEvent::producer_blocked(scan_id.to_string(), waited_ms)).await;
+        }
+        Ok(())
+    }
+    
+    /// Drop the oldest messages until a new message fits, returning the dropped sequence range
+    async fn drop_oldest(&self, messages: &mut VecDeque<Arc<ScanMessage>>, message_size: usize) -> Option<(u64, u64)> {
+        let mut dropped: Option<(u64, u64)> = None;
+        while self.over_limits(messages.len(), message_size).await {
+            let Some(message) = messages.pop_front() else { break };
+            self.memory_monitor.record_pop(&message).await;
+            let sequence = message.header().sequence;
+            dropped = Some((dropped.map_or(sequence, |(first, _)| first), sequence));
+        }
+        
+        let (first, last) = dropped?;
+        self.sequence_tracker.write().await.update_min_sequence(last + 1);
+        
+        let mut state = self.backpressure_state.lock().await;
+        if state.messages_dropped == 0 {
+            log::warn!("Queue full: dropping the oldest messages before every consumer has read them");
+        }
+        log::debug!("Dropped queued messages {}-{}", first, last);
+        state.messages_dropped += last - first + 1;
+        dropped
+    }
+    
+    /// Check whether a message goes to the spill buffer
+    ///
+    /// Once messages spill, later ones follow them to disk until the buffer
+    /// drains, keeping messages in sequence order.
+    async fn should_spill(&self, queue_len: usize, message_size: usize) -> bool {
+        let spilling = self.spill.lock().await.as_ref().is_some_and(|spill| !spill.is_empty());
+        spilling || self.over_limits(queue_len, message_size).await
+    }
+    
+    /// Write a message to the spill buffer, creating it on first use
+    async fn spill_message(&self, message: &ScanMessage) -> QueueResult<()> {
+        let mut spill = self.spill.lock().await;
+        let buffer = spill.get_or_insert_with(|| {
+            let directory = self.config.spill_directory.clone().unwrap_or_else(std::env::temp_dir);
+            SpillBuffer::new(&directory, self.config.spill_segment_size)
+        });
+        buffer.append(message)?;
+        self.backpressure_state.lock().await.messages_spilled += 1;
+        Ok(())
+    }
+    
+    /// Move spilled messages back into memory, oldest first, while they fit within the limits
+    async fn reload_spilled(&self, messages: &mut VecDeque<Arc<ScanMessage>>, spill: &mut SpillBuffer) -> QueueResult<usize> {
+        let mut reloaded = 0;
+        while let Some(message_size) = spill.front_memory_size() {
+            if self.over_limits(messages.len(), message_size).await {
+                break;
+            }
+            let Some(message) = spill.pop_front()? else { break };
+            let message = Arc::new(message);
+            self.memory_monitor.record_push(&message).await;
+            messages.push_back(message);
+            reloaded += 1;
+        }
+        if reloaded > 0 {
+            log::debug!("Reloaded {} spilled messages into memory, {} remain on disk", reloaded, spill.len());
+        }
+        Ok(reloaded)
+    }
+    
+    /// Publish a backpressure event; a failure is only logged as the message itself was handled
+    async fn publish_backpressure_event(&self, event: crate::queue::QueueEvent) {
+        if let Err(e) = self.event_handler.publish_queue_event(event).await {
+            log::debug!("Failed to publish backpressure event: {}", e);
+        }
+    }
+    
     /// Calculate the low water mark (minimum sequence across all consumers)
     pub async fn calculate_low_water_mark(&self) -> u64 {
         let registry = self.consumer_registry.read().await;
@@ -466,9 +726,23 @@ impl MultiConsumerQueue {
             }
         }
         
-        // Update sequence tracker
+        // Spilled messages follow those in memory, so they can only go once memory is clear;
+        // the room freed is then refilled from disk
+        let mut spill = self.spill.lock().await;
+        if let Some(spill) = spill.as_mut() {
+            if messages.is_empty() {
+                collected += spill.discard_below(low_water_mark) as u64;
+            }
+            self.reload_spilled(&mut messages, spill).await?;
+        }
+        
+        // Update sequence tracker to the oldest message still held
         if collected > 0 {
-            sequence_tracker.update_min_sequence(low_water_mark);
+            let min_sequence = messages.front()
+                .map(|message| message.header().sequence)
+                .or_else(|| spill.as_ref().and_then(SpillBuffer::first_sequence))
+                .unwrap_or(low_water_mark);
+            sequence_tracker.update_min_sequence(min_sequence);
         }
         
         Ok(collected)
@@ -485,6 +759,12 @@ impl MultiConsumerQueue {
         stats.memory_usage = memory_stats.current_size as u64;
         stats.active_consumers = registry.consumers.len();
         stats.total_messages = tracker.total_messages();
+        
+        let backpressure_state = self.backpressure_state.lock().await;
+        stats.messages_dropped = backpressure_state.messages_dropped;
+        stats.messages_spilled = backpressure_state.messages_spilled;
+        stats.producer_blocks = backpressure_state.producer_blocks;
+        stats.producer_blocked_time = backpressure_state.producer_blocked_time;
     }
     
     /// Get current queue statistics
@@ -505,87 +785,95 @@ impl MultiConsumerQueue {
             return Ok(Vec::new());
         }
         
-        let messages = self.messages.read().await;
-        let tracker = self.sequence_tracker.read().await;
+        let (min_sequence, max_sequence) = self.get_sequence_range().await;
         
         // Check if start sequence is valid
-        if start_sequence < tracker.min_sequence {
+        if start_sequence < min_sequence {
             return Err(QueueError::operation_failed(
-                format!("Start sequence {} below minimum {}", start_sequence, tracker.min_sequence)
+                format!("Start sequence {} below minimum {}", start_sequence, min_sequence)
             ));
         }
         
-        if start_sequence > tracker.max_sequence {
+        if start_sequence > max_sequence {
             return Ok(Vec::new()); // No messages available yet
         }
         
-        let mut result = Vec::with_capacity(limit.min(messages.len()));
-        let mut current_seq = start_sequence;
-        
-        for _ in 0..limit {
-            if current_seq > tracker.max_sequence {
-                break; // Reached end of available messages
+        self.get_message_run(start_sequence, limit).await
+    }
+    
+    /// Consecutive messages from a sequence on, up to a limit, ending early at the first one not available
+    ///
+    /// Messages held in memory are collected under a single lock of the queue,
+    /// rather than one lock per message; any after them are read back from disk.
+    pub(crate) async fn get_message_run(&self, start_sequence: u64, limit: usize) -> QueueResult<Vec<Arc<ScanMessage>>> {
+        let mut run = Vec::new();
+        let available = {
+            let messages = self.messages.read().await;
+            let tracker = self.sequence_tracker.read().await;
+            if start_sequence < tracker.min_sequence || start_sequence > tracker.max_sequence {
+                return Ok(run);
             }
-            
-            // Calculate position in queue
-            let queue_position = current_seq - tracker.min_sequence;
-            
-            if queue_position >= messages.len() as u64 {
-                break; // Beyond current queue size
+            let available = (tracker.max_sequence - start_sequence + 1).min(limit as u64) as usize;
+            let position = messages.front()
+                .and_then(|front| start_sequence.checked_sub(front.header().sequence));
+            if let Some(position) = position {
+                run.extend(messages.range((position as usize).min(messages.len())..)
+                    .take(available)
+                    .zip(start_sequence..)
+                    .take_while(|(message, sequence)| message.header().sequence == *sequence)
+                    .map(|(message, _)| Arc::clone(message)));
             }
-            
-            if let Some(message) = messages.get(queue_position as usize) {
-                // Verify sequence number matches (sanity check)
-                if message.header().sequence == current_seq {
-                    result.push(Arc::clone(message));
-                } else {
-                    log::warn!("Sequence mismatch in queue: expected {}, found {}", 
-                              current_seq, message.header().sequence);
-                    break;
-                }
-            } else {
-                break;
+            available
+        };
+        
+        // The rest, if any, may have been spilled to disk
+        while run.len() < available {
+            match self.get_message_by_seq(start_sequence + run.len() as u64).await? {
+                Some(message) => run.push(message),
+                None => break,
             }
-            
-            current_seq += 1;
         }
-        
-        Ok(result)
+        Ok(run)
     }
     
-    /// Get a specific message by sequence number
+    /// Get a specific message by sequence number, reading spilled messages back from disk
     pub async fn get_message_by_seq(&self, sequence: u64) -> QueueResult<Option<Arc<ScanMessage>>> {
+        // Memory stays locked while the spill buffer is read, so a message cannot be
+        // missed while it moves from disk back into memory
         let messages = self.messages.read().await;
-        let tracker = self.sequence_tracker.read().await;
-        
-        // Check if sequence is within valid range
-        if sequence < tracker.min_sequence {
-            return Ok(None); // Message has been garbage collected
-        }
-        
-        if sequence > tracker.max_sequence {
-            return Ok(None); // Message doesn't exist yet
-        }
-        
-        // Calculate position in queue
-        let queue_position = sequence - tracker.min_sequence;
-        
-        if queue_position >= messages.len() as u64 {
-            return Ok(None); // Position is beyond current queue size
-        }
-        
-        // Get message at position
-        if let Some(message) = messages.get(queue_position as usize) {
-            // Verify sequence number matches (sanity check)
-            if message.header().sequence == sequence {
-                Ok(Some(Arc::clone(message)))
-            } else {
+        {
+            let tracker = self.sequence_tracker.read().await;
+            
+            // Check if sequence is within valid range
+            if sequence < tracker.min_sequence {
+                return Ok(None); // Message has been garbage collected or dropped
+            }
+            
+            if sequence > tracker.max_sequence {
+                return Ok(None); // Message doesn't exist yet
+            }
+            
+            // Calculate position in queue relative to the oldest message held in memory
+            let position = messages.front()
+                .and_then(|front| sequence.checked_sub(front.header().sequence));
+            
+            if let Some(message) = position.and_then(|position| messages.get(position as usize)) {
+                // Verify sequence number matches (sanity check)
+                if message.header().sequence == sequence {
+                    return Ok(Some(Arc::clone(message)));
+                }
                 log::warn!("Sequence mismatch in queue: expected {}, found {}", 
                           sequence, message.header().sequence);
-                Ok(None)
+                return Ok(None);
             }
-        } else {
-            Ok(None)
+        }
+        
+        // Beyond the messages in memory; it may have been spilled to disk
+        let mut spill = self.spill.lock().await;
+        drop(messages);
+        match spill.as_mut() {
+            Some(spill) => Ok(spill.read(sequence)?.map(Arc::new)),
+            None => Ok(None),
         }
     }
     
@@ -615,8 +903,14 @@ impl MultiConsumerQueue {
     
     /// Acknowledge message processing for a consumer (alternative interface)
     pub async fn acknowledge_consumer(&self, consumer_id: &str, sequence: u64) -> QueueResult<()> {
-        let mut registry = self.consumer_registry.write().await;
-        registry.update_progress(consumer_id, sequence)
+        self.consumer_registry.write().await.update_progress(consumer_id, sequence)?;
+        
+        // While messages are spilled, progress frees memory that spilled messages can move back into
+        let spilling = self.spill.lock().await.as_ref().is_some_and(|spill| !spill.is_empty());
+        if spilling {
+            self.garbage_collect_messages(self.calculate_low_water_mark().await).await?;
+        }
+        Ok(())
     }
     
     /// Get lag for a specific consumer
@@ -686,10 +980,7 @@ impl MultiConsumerQueue {
 
 impl Clone for MultiConsumerQueue {
     fn clone(&self) -> Self {
-        let event_handler = QueueEventHandler::new(
-            Arc::clone(&self.scan_statistics),
-            Arc::clone(&self.notification_manager),
-        );
+        let event_handler = self.event_handler.clone();
         
         Self {
             scan_statistics: Arc::clone(&self.scan_statistics),
@@ -704,6 +995,8 @@ impl Clone for MultiConsumerQueue {
             active: Arc::clone(&self.active),
             scan_notification_manager: Arc::clone(&self.scan_notification_manager),
             event_handler,
+            spill: Arc::clone(&self.spill),
+            backpressure_state: Arc::clone(&self.backpressure_state),
         }
     }
 }
@@ -1100,5 +1393,152 @@ mod tests {
         // Try to get lag for non-existent consumer
         let result = queue.get_consumer_lag("nonexistent").await;
         assert!(result.is_err());
+    }    
+    async fn create_backpressure_queue(policy: BackpressurePolicy, config: MultiConsumerConfig) -> MultiConsumerQueue {
+        let config = MultiConsumerConfig {
+            max_queue_size: 3,
+            backpressure: Some(policy),
+            ..config
+        };
+        let queue = MultiConsumerQueue::with_config(config, create_test_notification_manager(), create_test_scan_notification_manager());
+        queue.start().await.unwrap();
+        queue
+    }
+    
+    #[tokio::test]
+    async fn test_drop_oldest_skips_consumers_past_dropped_messages() {
+        let queue = create_backpressure_queue(BackpressurePolicy::DropOldest, MultiConsumerConfig::default()).await;
+        let consumer = queue.register_consumer("slow-plugin".to_string()).await.unwrap();
+        
+        for i in 0..5 {
+            queue.enqueue(create_test_message(i)).await.unwrap();
+        }
+        
+        assert_eq!(queue.get_sequence_range().await, (2, 4));
+        assert!(queue.get_message_by_seq(1).await.unwrap().is_none());
+        let stats = queue.get_statistics().await;
+        assert_eq!(stats.queue_size, 3);
+        assert_eq!(stats.messages_dropped, 2);
+        
+        // The consumer never read messages 0 and 1, and resumes at the oldest one left
+        let message = consumer.read_next().await.unwrap().unwrap();
+        assert_eq!(message.header().sequence, 2);
+    }
+    
+    #[tokio::test]
+    async fn test_spill_to_disk_reads_messages_back() {
+        let spill_directory = tempfile::tempdir().unwrap();
+        let config = MultiConsumerConfig {
+            spill_directory: Some(spill_directory.path().to_path_buf()),
+            ..MultiConsumerConfig::default()
+        };
+        let queue = create_backpressure_queue(BackpressurePolicy::SpillToDisk, config).await;
+        let consumer = queue.register_consumer("slow-plugin".to_string()).await.unwrap();
+        
+        for i in 0..5 {
+            queue.enqueue(create_test_message(i)).await.unwrap();
+        }
+        
+        let stats = queue.get_statistics().await;
+        assert_eq!(stats.queue_size, 3);
+        assert_eq!(stats.messages_spilled, 2);
+        assert_eq!(std::fs::read_dir(spill_directory.path()).unwrap().count(), 1);
+        
+        let messages = consumer.read_batch(10).await.unwrap();
+        let sequences: Vec<u64> = messages.iter().map(|message| message.header().sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2, 3, 4]);
+        assert_eq!(messages[4].data, create_test_message(4).data);
+        
+        // Once every consumer has finished with them, spilled messages are collected too,
+        // and the rest move back into memory
+        consumer.acknowledge(4).await.unwrap();
+        assert_eq!(queue.spill.lock().await.as_ref().map(SpillBuffer::len), Some(0));
+        assert_eq!(queue.get_sequence_range().await.0, 4);
+        assert_eq!(queue.get_statistics().await.queue_size, 1);
+        
+        drop(consumer);
+        drop(queue);
+        assert_eq!(std::fs::read_dir(spill_directory.path()).unwrap().count(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_spilled_segments_reload_as_consumers_catch_up() {
+        let spill_directory = tempfile::tempdir().unwrap();
+        let config = MultiConsumerConfig {
+            spill_directory: Some(spill_directory.path().to_path_buf()),
+            spill_segment_size: 1, // one message per segment
+            ..MultiConsumerConfig::default()
+        };
+        let queue = create_backpressure_queue(BackpressurePolicy::SpillToDisk, config).await;
+        let consumer = queue.register_consumer("slow-plugin".to_string()).await.unwrap();
+        
+        for i in 0..6 {
+            queue.enqueue(create_test_message(i)).await.unwrap();
+        }
+        let segment_files = || std::fs::read_dir(spill_directory.path()).unwrap().count();
+        assert_eq!(segment_files(), 3);
+        
+        // Room freed by the consumer is refilled from the oldest segments, which are deleted
+        consumer.acknowledge(2).await.unwrap();
+        assert_eq!(queue.get_statistics().await.queue_size, 3);
+        assert_eq!(queue.spill.lock().await.as_ref().map(SpillBuffer::first_sequence), Some(Some(5)));
+        assert_eq!(segment_files(), 1);
+        assert_eq!(queue.get_sequence_range().await, (2, 5));
+        
+        // Messages still on disk continue to be read transparently
+        let message = queue.get_message_by_seq(5).await.unwrap().unwrap();
+        assert_eq!(message.data, create_test_message(5).data);
+        
+        // New messages keep spilling behind those on disk, preserving their order
+        queue.enqueue(create_test_message(6)).await.unwrap();
+        assert_eq!(queue.spill.lock().await.as_ref().map(SpillBuffer::len), Some(2));
+        let sequences: Vec<u64> = queue.get_messages_from(2, 10).await.unwrap()
+            .iter().map(|message| message.header().sequence).collect();
+        assert_eq!(sequences, vec![2, 3, 4, 5, 6]);
+    }
+    
+    #[tokio::test]
+    async fn test_block_gives_up_waiting_for_stalled_consumers() {
+        let config = MultiConsumerConfig {
+            block_timeout: Duration::from_millis(50),
+            ..MultiConsumerConfig::default()
+        };
+        let queue = create_backpressure_queue(BackpressurePolicy::Block, config).await;
+        let _consumer = queue.register_consumer("stalled-plugin".to_string()).await.unwrap();
+        
+        for i in 0..3 {
+            queue.enqueue(create_test_message(i)).await.unwrap();
+        }
+        
+        // The consumer never acknowledges, so the producer waits once and then carries on
+        let started = Instant::now();
+        queue.enqueue(create_test_message(3)).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        
+        let started = Instant::now();
+        queue.enqueue(create_test_message(4)).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(50));
+        assert_eq!(queue.get_statistics().await.queue_size, 5);
+    }
+    
+    #[tokio::test]
+    async fn test_block_resumes_when_consumers_acknowledge() {
+        let queue = Arc::new(create_backpressure_queue(BackpressurePolicy::Block, MultiConsumerConfig::default()).await);
+        let consumer = queue.register_consumer("plugin".to_string()).await.unwrap();
+        
+        for i in 0..3 {
+            queue.enqueue(create_test_message(i)).await.unwrap();
+        }
+        
+        let producer = {
+            let queue = Arc::clone(&queue);
+            tokio::spawn(async move { queue.enqueue(create_test_message(3)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(!producer.is_finished());
+        
+        consumer.acknowledge(2).await.unwrap();
+        assert_eq!(producer.await.unwrap().unwrap(), 3);
+        assert_eq!(queue.get_sequence_range().await, (2, 3));
     }
 }
\ No newline at end of file
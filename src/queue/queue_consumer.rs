@@ -17,8 +17,10 @@
 //! use std::sync::Arc;
 //!
 //! # tokio_test::block_on(async {
-//! let notification_manager = Arc::new(gstats::notifications::AsyncNotificationManager::new());
-//! let queue = MultiConsumerQueue::new("scan-001".to_string(), notification_manager);
+//! let queue = MultiConsumerQueue::new(
+//!     Arc::new(gstats::notifications::AsyncNotificationManager::new()),
+//!     Arc::new(gstats::notifications::AsyncNotificationManager::new()),
+//! );
 //! queue.start().await.unwrap();
 //!
 //! // Register consumer
@@ -39,10 +41,17 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::plugin::error::PluginResult;
+use crate::plugin::traits::MessageConsumer;
 use crate::queue::{QueueError, QueueResult, MultiConsumerQueue};
+use crate::queue::routing::MessageRoute;
+use crate::scanner::file_policy::FilePolicy;
 use crate::scanner::messages::ScanMessage;
 
 /// Consumer handle for reading messages from the multi-consumer queue
+///
+/// Clones are further handles to the same consumer, sharing its position.
+#[derive(Clone)]
 pub struct QueueConsumer {
     /// Unique consumer identifier
     consumer_id: String,
@@ -67,6 +76,44 @@ pub struct QueueConsumer {
     
     /// Consumer priority
     priority: Arc<RwLock<i32>>,
+    
+    /// Files the consumer is given, when limited by its plugin's data requirements
+    file_policy: Arc<RwLock<Option<FilePolicy>>>,
+    
+    /// Message types the consumer is given
+    route: Arc<RwLock<MessageRoute>>,
+    
+    /// Messages given to the consumer and passed over, for acknowledging passed-over messages
+    delivery: Arc<RwLock<Delivery>>,
+}
+
+/// Where a consumer's reads and acknowledgments have got to
+///
+/// Messages passed over are never handed to the plugin, so the plugin cannot
+/// acknowledge them. Once every message given to the consumer before them is
+/// acknowledged, they are acknowledged on its behalf; otherwise a consumer
+/// waiting for a type that is no longer streamed would hold every later message
+/// in the queue.
+#[derive(Debug, Default)]
+struct Delivery {
+    /// Sequence of the last message given to the consumer
+    last_given: Option<u64>,
+    /// Last sequence passed over since then, until it is acknowledged
+    passed_over_through: Option<u64>,
+    /// Highest sequence acknowledged, by the consumer or on its behalf
+    acknowledged_through: Option<u64>,
+}
+
+impl Delivery {
+    /// Passed-over sequence that can be acknowledged now, if any
+    fn acknowledgeable(&self) -> Option<u64> {
+        let given_acknowledged = match self.last_given {
+            Some(given) => self.acknowledged_through.is_some_and(|acknowledged| acknowledged >= given),
+            None => true,
+        };
+        self.passed_over_through
+            .filter(|through| given_acknowledged && self.acknowledged_through.is_none_or(|acknowledged| *through > acknowledged))
+    }
 }
 
 /// Consumer-specific statistics
@@ -92,6 +139,70 @@ struct ConsumerStats {
     
     /// Last read timestamp
     last_read: Option<Instant>,
+    
+    /// Messages passed over by the consumer's route or file policy
+    messages_passed_over: u64,
+    
+    /// Reads that found no message because the consumer had caught up
+    empty_reads: u64,
+    
+    /// Waits for new messages that had to sleep
+    waits: u64,
+    
+    /// Total time spent waiting for new messages
+    total_wait_time: Duration,
+    
+    /// When the consumer was registered
+    created_at: Instant,
+}
+
+/// Progress and throughput of one queue consumer
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumerStatistics {
+    pub consumer_id: String,
+    pub plugin_name: String,
+    /// Messages enqueued so far
+    pub enqueued: u64,
+    /// Messages read by the consumer
+    pub read: u64,
+    /// Messages up to and including the last acknowledged sequence
+    pub acknowledged: u64,
+    /// Messages passed over because the consumer does not handle their type or its file policy leaves them out
+    pub passed_over: u64,
+    /// Read operations, each reading one message or a batch
+    pub read_operations: u64,
+    /// Reads that found nothing and waits that slept, while the consumer was ahead of the producer
+    pub backoffs: u64,
+    /// Time spent waiting for new messages
+    pub backoff_time: Duration,
+    /// Time since the consumer was registered
+    pub elapsed: Duration,
+    /// Batch size the plugin asks for in its consumer preferences, if known
+    pub preferred_batch_size: Option<usize>,
+}
+
+impl ConsumerStatistics {
+    /// Messages enqueued but not yet acknowledged
+    pub fn lag(&self) -> u64 {
+        self.enqueued.saturating_sub(self.acknowledged)
+    }
+    
+    /// Messages acknowledged per second since registration
+    pub fn throughput(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.acknowledged as f64 / secs,
+            _ => 0.0,
+        }
+    }
+    
+    /// Average number of messages per read operation
+    pub fn average_batch(&self) -> f64 {
+        if self.read_operations == 0 {
+            0.0
+        } else {
+            self.read as f64 / self.read_operations as f64
+        }
+    }
 }
 
 impl Default for ConsumerStats {
@@ -104,6 +215,11 @@ impl Default for ConsumerStats {
             read_operations: 0,
             total_read_time: Duration::from_secs(0),
             last_read: None,
+            messages_passed_over: 0,
+            empty_reads: 0,
+            waits: 0,
+            total_wait_time: Duration::from_secs(0),
+            created_at: Instant::now(),
         }
     }
 }
@@ -125,6 +241,9 @@ impl QueueConsumer {
             active: Arc::new(RwLock::new(true)),
             stats: Arc::new(RwLock::new(ConsumerStats::default())),
             priority: Arc::new(RwLock::new(priority)),
+            file_policy: Arc::new(RwLock::new(None)),
+            route: Arc::new(RwLock::new(MessageRoute::All)),
+            delivery: Arc::new(RwLock::new(Delivery::default())),
         }
     }
     
@@ -152,6 +271,26 @@ impl QueueConsumer {
         Ok(())
     }
     
+    /// Limit the files the consumer is given; messages the policy leaves out are passed over as it reads
+    pub async fn set_file_policy(&self, policy: FilePolicy) {
+        *self.file_policy.write().await = Some(policy);
+    }
+    
+    /// Files the consumer is given, if limited
+    pub async fn file_policy(&self) -> Option<FilePolicy> {
+        *self.file_policy.read().await
+    }
+    
+    /// Limit the message types the consumer is given; messages of other types are passed over as it reads
+    pub async fn set_route(&self, route: MessageRoute) {
+        *self.route.write().await = route;
+    }
+    
+    /// Message types the consumer is given
+    pub async fn route(&self) -> MessageRoute {
+        self.route.read().await.clone()
+    }
+    
     /// Check if consumer is active
     pub async fn is_active(&self) -> bool {
         *self.active.read().await
@@ -168,15 +307,23 @@ impl QueueConsumer {
             return Ok(None);
         }
         
+        let _stage = crate::profiling::plugin_stage("queue.read", &self.plugin_name);
+        #[cfg(feature = "otel")]
+        let mut span = crate::telemetry::plugin_span("queue.read", &self.plugin_name, Vec::new());
         let start_time = Instant::now();
         let mut stats = self.stats.write().await;
         stats.read_operations += 1;
         drop(stats);
         
-        let current_seq = *self.current_sequence.read().await;
+        let current_seq = self.skip_removed_messages().await;
         
         // Try to read message at current sequence
-        let result = self.read_message_at_sequence(current_seq).await;
+        let result = self.read_permitted_from(current_seq).await;
+        #[cfg(feature = "otel")]
+        {
+            span.record_result(&result);
+            span.set_attribute(crate::telemetry::KeyValue::new("sequence", current_seq as i64));
+        }
         
         // Update statistics
         let mut stats = self.stats.write().await;
@@ -184,18 +331,19 @@ impl QueueConsumer {
         stats.last_read = Some(Instant::now());
         
         match result {
-            Ok(Some(message)) => {
-                stats.messages_read += 1;
-                
-                // Advance read position
-                let mut current_seq = self.current_sequence.write().await;
-                *current_seq += 1;
-                
-                Ok(Some(message))
-            }
-            Ok(None) => {
-                // No message available
-                Ok(None)
+            Ok((message, next_seq)) => {
+                // Advance read position past the message and any passed over before it
+                *self.current_sequence.write().await = next_seq;
+                if message.is_some() {
+                    stats.messages_read += 1;
+                } else {
+                    // No message available
+                    stats.empty_reads += 1;
+                }
+                drop(stats);
+                let given = message.as_ref().map(|message| message.header().sequence);
+                self.record_read(current_seq, next_seq, given, given.is_some() as u64).await?;
+                Ok(message)
             }
             Err(e) => {
                 stats.read_errors += 1;
@@ -204,37 +352,58 @@ impl QueueConsumer {
         }
     }
     
-    /// Read multiple messages up to the specified limit
+    /// Read up to `max_count` messages in one operation
+    ///
+    /// Messages are taken from the queue a run at a time under a single lock,
+    /// and the batch counts as one read in the consumer's statistics, so
+    /// consumers that handle messages in batches avoid the locking and wakeups
+    /// of reading them one by one. Acknowledge the batch with
+    /// [`acknowledge_batch`](Self::acknowledge_batch).
     pub async fn read_batch(&self, max_count: usize) -> QueueResult<Vec<Arc<ScanMessage>>> {
         if !self.is_active().await || max_count == 0 {
             return Ok(Vec::new());
         }
         
+        let _stage = crate::profiling::plugin_stage("queue.read_batch", &self.plugin_name);
+        let start_time = Instant::now();
+        let route = self.route.read().await.clone();
+        let policy = *self.file_policy.read().await;
         let mut messages = Vec::with_capacity(max_count);
-        let mut current_seq = *self.current_sequence.read().await;
-        
-        for _ in 0..max_count {
-            match self.read_message_at_sequence(current_seq).await? {
-                Some(message) => {
-                    messages.push(message);
-                    current_seq += 1;
-                }
-                None => {
-                    break; // No more messages available
+        let start_seq = self.skip_removed_messages().await;
+        let mut current_seq = start_seq;
+        
+        // Runs never go past the batch, so the read position ends just after its last message
+        while messages.len() < max_count {
+            let run = match self.queue.get_message_run(current_seq, max_count - messages.len()).await {
+                Ok(run) => run,
+                Err(e) => {
+                    self.stats.write().await.read_errors += 1;
+                    return Err(e);
                 }
+            };
+            if run.is_empty() {
+                break; // No more messages available
             }
+            current_seq += run.len() as u64;
+            messages.extend(run.into_iter().filter_map(|message| permit(&route, policy.as_ref(), message)));
         }
         
-        // Update read position if we read any messages
-        if !messages.is_empty() {
+        // Update read position if we read or passed over any messages
+        if current_seq != start_seq {
             *self.current_sequence.write().await = current_seq;
-            
-            // Update statistics
-            let mut stats = self.stats.write().await;
+        }
+        let mut stats = self.stats.write().await;
+        stats.total_read_time += start_time.elapsed();
+        if !messages.is_empty() {
             stats.messages_read += messages.len() as u64;
             stats.read_operations += 1;
             stats.last_read = Some(Instant::now());
+        } else {
+            stats.empty_reads += 1;
         }
+        drop(stats);
+        let given = messages.last().map(|message| message.header().sequence);
+        self.record_read(start_seq, current_seq, given, messages.len() as u64).await?;
         
         Ok(messages)
     }
@@ -249,11 +418,10 @@ impl QueueConsumer {
         let mut seq = start_sequence;
         
         for _ in 0..max_count {
-            match self.read_message_at_sequence(seq).await? {
-                Some(message) => {
-                    messages.push(message);
-                    seq += 1;
-                }
+            let (message, next_seq) = self.read_permitted_from(seq).await?;
+            seq = next_seq;
+            match message {
+                Some(message) => messages.push(message),
                 None => break,
             }
         }
@@ -270,6 +438,21 @@ impl QueueConsumer {
     
     /// Acknowledge processing of a message
     pub async fn acknowledge(&self, sequence: u64) -> QueueResult<()> {
+        self.acknowledge_through(sequence, 1).await
+    }
+    
+    /// Acknowledge processing of multiple messages, such as a batch from [`read_batch`](Self::read_batch)
+    ///
+    /// The queue is updated once, up to the highest sequence.
+    pub async fn acknowledge_batch(&self, sequences: &[u64]) -> QueueResult<()> {
+        match sequences.iter().max() {
+            Some(max_sequence) => self.acknowledge_through(*max_sequence, sequences.len() as u64).await,
+            None => Ok(()),
+        }
+    }
+    
+    /// Acknowledge every message up to a sequence, `count` of them on behalf of the plugin
+    async fn acknowledge_through(&self, sequence: u64, count: u64) -> QueueResult<()> {
         if !self.is_active().await {
             return Err(QueueError::operation_failed("Consumer not active"));
         }
@@ -282,36 +465,26 @@ impl QueueConsumer {
         drop(last_ack);
         
         // Update progress in queue registry
-        let result = {
-            let mut registry = self.queue.consumer_registry.write().await;
-            registry.update_progress(&self.consumer_id, sequence)
-        };
+        let result = self.queue.acknowledge_consumer(&self.consumer_id, sequence).await;
         
         // Update statistics
         let mut stats = self.stats.write().await;
         match result {
             Ok(()) => {
-                stats.messages_acknowledged += 1;
+                stats.messages_acknowledged += count;
             }
             Err(_) => {
                 stats.ack_errors += 1;
             }
         }
+        drop(stats);
         
-        result
-    }
-    
-    /// Acknowledge processing of multiple messages
-    pub async fn acknowledge_batch(&self, sequences: &[u64]) -> QueueResult<()> {
-        if sequences.is_empty() {
-            return Ok(());
+        result?;
+        {
+            let mut delivery = self.delivery.write().await;
+            delivery.acknowledged_through = delivery.acknowledged_through.max(Some(sequence));
         }
-        
-        // Find the highest sequence number
-        let max_sequence = *sequences.iter().max().unwrap();
-        
-        // Acknowledge up to the highest sequence
-        self.acknowledge(max_sequence).await
+        self.acknowledge_passed_over().await
     }
     
     /// Get current read position
@@ -359,7 +532,18 @@ impl QueueConsumer {
         let current_seq = *self.current_sequence.read().await;
         tracker.max_sequence.saturating_sub(current_seq)
     }
-    
+
+    /// Estimated memory of the messages the queue retains for this consumer
+    ///
+    /// This covers the messages the consumer has not acknowledged, so a consumer
+    /// that has drained the queue holds none. Messages spilled to disk are not counted.
+    pub async fn backlog_memory(&self) -> usize {
+        let acknowledged_through = self.delivery.read().await.acknowledged_through;
+        let messages = self.queue.messages.read().await;
+        let start = messages.partition_point(|message| Some(message.header().sequence) <= acknowledged_through);
+        messages.range(start..).map(|message| message.estimate_memory_usage()).sum()
+    }
+
     /// Check if there are messages available to read
     pub async fn has_messages_available(&self) -> bool {
         let current_seq = *self.current_sequence.read().await;
@@ -370,57 +554,147 @@ impl QueueConsumer {
     /// Wait for new messages to become available
     pub async fn wait_for_messages(&self, timeout: Duration) -> QueueResult<bool> {
         let start = Instant::now();
+        let mut available = false;
+        let mut slept = false;
         
         while start.elapsed() < timeout {
             if self.has_messages_available().await {
-                return Ok(true);
+                available = true;
+                break;
             }
             
             // Short sleep to avoid busy waiting
+            slept = true;
             tokio::time::sleep(Duration::from_millis(10)).await;
         }
         
-        Ok(false) // Timeout
+        if slept {
+            let mut stats = self.stats.write().await;
+            stats.waits += 1;
+            stats.total_wait_time += start.elapsed();
+        }
+        Ok(available) // false on timeout
+    }
+    
+    /// Progress, throughput and backoff statistics of this consumer
+    pub async fn statistics(&self) -> ConsumerStatistics {
+        let enqueued = self.queue.sequence_tracker.read().await.next_sequence;
+        let acknowledged_through = self.delivery.read().await.acknowledged_through;
+        let stats = self.stats.read().await;
+        ConsumerStatistics {
+            consumer_id: self.consumer_id.clone(),
+            plugin_name: self.plugin_name.clone(),
+            enqueued,
+            read: stats.messages_read,
+            passed_over: stats.messages_passed_over,
+            acknowledged: acknowledged_through.map_or(0, |sequence| sequence + 1),
+            read_operations: stats.read_operations,
+            backoffs: stats.empty_reads + stats.waits,
+            backoff_time: stats.total_wait_time,
+            elapsed: stats.created_at.elapsed(),
+            preferred_batch_size: None,
+        }
     }
     
     /// Read a message at a specific sequence (internal helper)
     async fn read_message_at_sequence(&self, sequence: u64) -> QueueResult<Option<Arc<ScanMessage>>> {
-        let messages = self.queue.messages.read().await;
-        let tracker = self.queue.sequence_tracker.read().await;
-        
-        // Check if sequence is valid
-        if sequence < tracker.min_sequence {
-            // Message has been garbage collected
-            return Ok(None);
+        self.queue.get_message_by_seq(sequence).await
+    }
+    
+    /// First message from a sequence on that the consumer's route and file policy let through, as
+    /// the policy gives it, and the sequence after it; messages they leave out are passed over
+    async fn read_permitted_from(&self, mut sequence: u64) -> QueueResult<(Option<Arc<ScanMessage>>, u64)> {
+        let route = self.route.read().await.clone();
+        let policy = *self.file_policy.read().await;
+        while let Some(message) = self.read_message_at_sequence(sequence).await? {
+            sequence += 1;
+            if let Some(message) = permit(&route, policy.as_ref(), message) {
+                return Ok((Some(message), sequence));
+            }
         }
-        
-        if sequence > tracker.max_sequence {
-            // Message doesn't exist yet
-            return Ok(None);
+        Ok((None, sequence))
+    }
+    
+    /// Record a read of the sequences from `start` up to `end`, which gave the consumer
+    /// `given` messages ending with the one at `last_given`, and passed over the rest
+    async fn record_read(&self, start: u64, end: u64, last_given: Option<u64>, given: u64) -> QueueResult<()> {
+        {
+            let mut delivery = self.delivery.write().await;
+            if last_given.is_some() {
+                // Messages passed over before it are acknowledged along with it
+                delivery.last_given = last_given;
+                delivery.passed_over_through = None;
+            }
+            // Messages passed over after the last one given wait for it to be acknowledged
+            let trailing_start = last_given.map_or(start, |sequence| sequence + 1);
+            if end > trailing_start {
+                delivery.passed_over_through = Some(end - 1);
+            }
         }
-        
-        // Calculate position in queue
-        let queue_position = sequence - tracker.min_sequence;
-        
-        if queue_position >= messages.len() as u64 {
-            // Position is beyond current queue size
-            return Ok(None);
+        let passed_over = end.saturating_sub(start).saturating_sub(given);
+        if passed_over == 0 {
+            return Ok(());
         }
+        self.stats.write().await.messages_passed_over += passed_over;
+        self.acknowledge_passed_over().await
+    }
+    
+    /// Acknowledge the messages passed over since the last one given, once every message given is acknowledged
+    async fn acknowledge_passed_over(&self) -> QueueResult<()> {
+        let mut delivery = self.delivery.write().await;
+        let Some(through) = delivery.acknowledgeable() else {
+            return Ok(());
+        };
+        self.queue.acknowledge_consumer(&self.consumer_id, through).await?;
+        delivery.acknowledged_through = Some(through);
+        delivery.passed_over_through = None;
+        drop(delivery);
         
-        // Get message at position
-        if let Some(message) = messages.get(queue_position as usize) {
-            // Verify sequence number matches (sanity check)
-            if message.header().sequence == sequence {
-                Ok(Some(Arc::clone(message)))
-            } else {
-                // Sequence mismatch - possible race condition
-                log::warn!("Sequence mismatch in queue: expected {}, found {}", 
-                          sequence, message.header().sequence);
-                Ok(None)
-            }
-        } else {
-            Ok(None)
+        let mut last_ack = self.last_acknowledged.write().await;
+        *last_ack = (*last_ack).max(through);
+        Ok(())
+    }
+    
+    /// Move the read position past messages no longer in the queue, returning the new position
+    ///
+    /// Messages are only removed before every consumer has read them when the
+    /// drop-oldest backpressure policy discards them.
+    async fn skip_removed_messages(&self) -> u64 {
+        let min_sequence = self.queue.sequence_tracker.read().await.min_sequence;
+        let mut current_seq = self.current_sequence.write().await;
+        if *current_seq < min_sequence {
+            log::warn!("Consumer {} skipped {} messages removed from the queue before it read them",
+                       self.consumer_id, min_sequence - *current_seq);
+            *current_seq = min_sequence;
         }
+        *current_seq
+    }
+}
+
+/// A message as a consumer's route and file policy give it, or None when they leave it out
+fn permit(route: &MessageRoute, policy: Option<&FilePolicy>, message: Arc<ScanMessage>) -> Option<Arc<ScanMessage>> {
+    if !route.accepts(&message) {
+        return None;
+    }
+    match policy {
+        Some(policy) => policy.apply(message),
+        None => Some(message),
+    }
+}
+
+// The handle consumer plugins read and acknowledge messages through
+#[async_trait::async_trait]
+impl MessageConsumer for QueueConsumer {
+    async fn read_next(&self) -> PluginResult<Option<Arc<ScanMessage>>> {
+        Ok(QueueConsumer::read_next(self).await?)
+    }
+    
+    async fn acknowledge(&self, sequence: u64) -> PluginResult<()> {
+        Ok(QueueConsumer::acknowledge(self, sequence).await?)
+    }
+    
+    async fn acknowledge_batch(&self, sequences: &[u64]) -> PluginResult<()> {
+        Ok(QueueConsumer::acknowledge_batch(self, sequences).await?)
     }
 }
 
@@ -553,6 +827,30 @@ mod tests {
         assert_eq!(consumer.last_acknowledged_sequence().await, 0);
     }
     
+    #[tokio::test]
+    async fn test_consumer_as_plugin_message_consumer() {
+        let queue_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let scan_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let queue = Arc::new(MultiConsumerQueue::new(queue_notification_manager, scan_notification_manager));
+        queue.start().await.unwrap();
+        
+        for i in 0..2 {
+            queue.enqueue(create_test_message(i)).await.unwrap();
+        }
+        
+        // Read and acknowledge through the handle consumer plugins are given
+        let consumer = queue.register_consumer("test-plugin".to_string()).await.unwrap();
+        let handle: &dyn MessageConsumer = &consumer;
+        let first = handle.read_next().await.unwrap().unwrap();
+        let second = handle.read_next().await.unwrap().unwrap();
+        handle.acknowledge_batch(&[first.header().sequence, second.header().sequence]).await.unwrap();
+        
+        assert_eq!(consumer.last_acknowledged_sequence().await, 1);
+        
+        consumer.set_active(false).await;
+        assert!(handle.acknowledge(1).await.is_err());
+    }
+    
     #[tokio::test]
     async fn test_consumer_batch_read() {
         let queue_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
@@ -675,4 +973,132 @@ mod tests {
         // Check consumer count
         assert_eq!(queue.get_consumer_count().await, 2);
     }
+    
+    #[tokio::test]
+    async fn test_file_policy_passes_over_oversized_files() {
+        use crate::scanner::file_policy::{FilePolicy, OversizedFiles};
+        
+        let queue_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let scan_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let queue = Arc::new(MultiConsumerQueue::new(queue_notification_manager, scan_notification_manager));
+        queue.start().await.unwrap();
+        
+        // Files of 10, 2000 and 10 bytes
+        for (sequence, content) in ["small\nfile\n".to_string(), "line\n".repeat(400), "small\nfile\n".to_string()].into_iter().enumerate() {
+            let data = MessageData::FileContent { path: format!("file{}.rs", sequence), size: content.len() as u64, content, generated: false };
+            queue.enqueue(ScanMessage::new(MessageHeader::new(sequence as u64, "test-scan".to_string()), data)).await.unwrap();
+        }
+        
+        let unlimited = queue.register_consumer("unlimited".to_string()).await.unwrap();
+        let skipping = queue.register_consumer("skipping".to_string()).await.unwrap();
+        let truncating = queue.register_consumer("truncating".to_string()).await.unwrap();
+        let policy = FilePolicy { max_file_size: Some(1024), binary_files: false, oversized: OversizedFiles::Skip };
+        skipping.set_file_policy(policy).await;
+        truncating.set_file_policy(FilePolicy { oversized: OversizedFiles::Truncate, ..policy }).await;
+        
+        assert_eq!(unlimited.read_batch(10).await.unwrap().len(), 3);
+        
+        // The oversized file is passed over, both in batches and one message at a time
+        let batch = skipping.read_batch(10).await.unwrap();
+        assert_eq!(batch.iter().map(|message| message.header().sequence).collect::<Vec<_>>(), vec![0, 2]);
+        skipping.seek_to_sequence(1).await.unwrap();
+        assert_eq!(skipping.read_next().await.unwrap().unwrap().header().sequence, 2);
+        assert_eq!(skipping.current_sequence().await, 3);
+        
+        let batch = truncating.read_batch(10).await.unwrap();
+        let MessageData::FileContent { size, content, .. } = &batch[1].data else {
+            panic!("expected file content");
+        };
+        assert_eq!((batch.len(), *size, content.len()), (3, 2000, 1020));
+    }
+    
+    #[tokio::test]
+    async fn test_route_passes_over_other_types_and_acknowledges_them() {
+        use crate::queue::routing::MessageRoute;
+        
+        let queue_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let scan_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let queue = Arc::new(MultiConsumerQueue::new(queue_notification_manager, scan_notification_manager));
+        queue.start().await.unwrap();
+        
+        // A commit followed by three file messages
+        let commit = MessageData::CommitInfo {
+            hash: "abc1234".to_string(),
+            author: "alice".to_string(),
+            message: "Fix parser".to_string(),
+            timestamp: 0,
+            committer: "alice".to_string(),
+            author_timestamp: 0,
+            parents: vec![],
+            changed_files: vec![],
+        };
+        queue.enqueue(ScanMessage::new(MessageHeader::new(0, "test-scan".to_string()), commit)).await.unwrap();
+        for sequence in 1..4 {
+            queue.enqueue(create_test_message(sequence)).await.unwrap();
+        }
+        
+        let commits = queue.register_consumer("commits".to_string()).await.unwrap();
+        commits.set_route(MessageRoute::Only(["CommitInfo"].into())).await;
+        
+        let message = commits.read_next().await.unwrap().unwrap();
+        assert!(commits.read_next().await.unwrap().is_none());
+        assert_eq!(commits.current_sequence().await, 4);
+        
+        // The file messages wait for the commit given before them to be acknowledged
+        assert_eq!(commits.statistics().await.acknowledged, 0);
+        commits.acknowledge(message.header().sequence).await.unwrap();
+        let statistics = commits.statistics().await;
+        assert_eq!((statistics.read, statistics.passed_over, statistics.acknowledged), (1, 3, 4));
+        assert_eq!(commits.last_acknowledged_sequence().await, 3);
+        
+        // A consumer given nothing acknowledges what it passes over straight away
+        let blobs = queue.register_consumer("blobs".to_string()).await.unwrap();
+        blobs.set_route(MessageRoute::Only(["FileChange"].into())).await;
+        assert!(blobs.read_batch(10).await.unwrap().is_empty());
+        assert_eq!(blobs.statistics().await.acknowledged, 4);
+    }
+    
+    #[tokio::test]
+    async fn test_batch_read_stops_after_its_last_message_and_is_acknowledged_once() {
+        use crate::queue::routing::MessageRoute;
+        
+        let queue_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let scan_notification_manager = Arc::new(crate::notifications::AsyncNotificationManager::new());
+        let queue = Arc::new(MultiConsumerQueue::new(queue_notification_manager, scan_notification_manager));
+        queue.start().await.unwrap();
+        
+        // Commits at 0 and 3, file messages around them
+        for sequence in 0..6 {
+            let message = if sequence % 3 == 0 {
+                ScanMessage::new(MessageHeader::new(sequence, "test-scan".to_string()), MessageData::CommitInfo {
+                    hash: format!("c{}", sequence),
+                    author: "alice".to_string(),
+                    message: "Fix parser".to_string(),
+                    timestamp: 0,
+                    committer: "alice".to_string(),
+                    author_timestamp: 0,
+                    parents: vec![],
+                    changed_files: vec![],
+                })
+            } else {
+                create_test_message(sequence)
+            };
+            queue.enqueue(message).await.unwrap();
+        }
+        assert_eq!(queue.get_messages_from(1, 3).await.unwrap().len(), 3);
+        
+        let commits = queue.register_consumer("commits".to_string()).await.unwrap();
+        commits.set_route(MessageRoute::Only(["CommitInfo"].into())).await;
+        let batch = commits.read_batch(2).await.unwrap();
+        let sequences: Vec<u64> = batch.iter().map(|message| message.header().sequence).collect();
+        assert_eq!(sequences, [0, 3]);
+        assert_eq!(commits.current_sequence().await, 4);
+        let statistics = commits.statistics().await;
+        assert_eq!((statistics.read, statistics.read_operations, statistics.passed_over), (2, 1, 2));
+        
+        commits.acknowledge_batch(&sequences).await.unwrap();
+        assert_eq!(commits.statistics().await.acknowledged, 4);
+        assert!(commits.read_batch(2).await.unwrap().is_empty());
+        assert_eq!(commits.statistics().await.acknowledged, 6);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,146 @@
+//! Message Routing
+//!
+//! Every consumer reads from the same queue, but most plugins only handle a few
+//! message types: a commits-only analysis has no use for the many `FileChange`
+//! and `FileContent` messages streamed alongside each commit. A consumer's route
+//! is taken from the `interested_message_types` in its plugin's
+//! [`ConsumerPreferences`]; messages of other types are passed over as the
+//! consumer reads, without being handed to the plugin, and acknowledged on its
+//! behalf so they do not hold back garbage collection or backpressure.
+//!
+//! Plugins that set `consume_all_messages`, or list no types, receive every message.
+
+use std::collections::BTreeSet;
+
+use crate::plugin::traits::ConsumerPreferences;
+use crate::scanner::messages::{MessageData, ScanMessage};
+
+/// Names of the message types, as listed in `interested_message_types`
+pub const MESSAGE_TYPES: [&str; 12] = [
+    "FileInfo",
+    "CommitInfo",
+    "ChangeFrequencyInfo",
+    "MetricInfo",
+    "DependencyInfo",
+    "SecurityInfo",
+    "PerformanceInfo",
+    "RepositoryStatistics",
+    "FileChange",
+    "FileContent",
+    "ContentFingerprints",
+    "None",
+];
+
+/// Name of a message's type
+pub fn message_type(data: &MessageData) -> &'static str {
+    match data {
+        MessageData::FileInfo { .. } => "FileInfo",
+        MessageData::CommitInfo { .. } => "CommitInfo",
+        MessageData::ChangeFrequencyInfo { .. } => "ChangeFrequencyInfo",
+        MessageData::MetricInfo { .. } => "MetricInfo",
+        MessageData::DependencyInfo { .. } => "DependencyInfo",
+        MessageData::SecurityInfo { .. } => "SecurityInfo",
+        MessageData::PerformanceInfo { .. } => "PerformanceInfo",
+        MessageData::RepositoryStatistics { .. } => "RepositoryStatistics",
+        MessageData::FileChange { .. } => "FileChange",
+        MessageData::FileContent { .. } => "FileContent",
+        MessageData::ContentFingerprints { .. } => "ContentFingerprints",
+        MessageData::None => "None",
+    }
+}
+
+/// Message types a consumer is given
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum MessageRoute {
+    /// Every message
+    #[default]
+    All,
+    /// Only messages of these types
+    Only(BTreeSet<&'static str>),
+}
+
+impl MessageRoute {
+    /// Route declared by a consumer's preferences
+    ///
+    /// Type names that match no message type are logged and ignored; a consumer
+    /// left without a known type receives every message rather than none.
+    pub fn from_preferences(consumer: &str, preferences: &ConsumerPreferences) -> Self {
+        if preferences.consume_all_messages || preferences.interested_message_types.is_empty() {
+            return Self::All;
+        }
+        let mut types = BTreeSet::new();
+        for name in &preferences.interested_message_types {
+            match MESSAGE_TYPES.iter().find(|known| **known == name.as_str()) {
+                Some(known) => {
+                    types.insert(*known);
+                }
+                None => log::warn!(
+                    "{}: unknown message type '{}' in interested_message_types; expected one of {}",
+                    consumer, name, MESSAGE_TYPES.join(", ")
+                ),
+            }
+        }
+        if types.is_empty() {
+            Self::All
+        } else {
+            Self::Only(types)
+        }
+    }
+
+    /// Whether the consumer is given a message
+    pub fn accepts(&self, message: &ScanMessage) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(types) => types.contains(message_type(&message.data)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::messages::MessageHeader;
+
+    fn preferences(consume_all_messages: bool, types: &[&str]) -> ConsumerPreferences {
+        ConsumerPreferences {
+            consume_all_messages,
+            interested_message_types: types.iter().map(|name| name.to_string()).collect(),
+            ..ConsumerPreferences::default()
+        }
+    }
+
+    fn message(data: MessageData) -> ScanMessage {
+        ScanMessage::new(MessageHeader::new(0, "scan".to_string()), data)
+    }
+
+    #[test]
+    fn test_route_follows_interested_types() {
+        let commits = message(MessageData::CommitInfo {
+            hash: "abc1234".to_string(),
+            author: "alice".to_string(),
+            message: "Fix parser".to_string(),
+            timestamp: 0,
+            committer: "alice".to_string(),
+            author_timestamp: 0,
+            parents: vec![],
+            changed_files: vec![],
+        });
+        let file = message(MessageData::FileInfo { path: "src/lib.rs".to_string(), size: 10, lines: 1 });
+
+        let route = MessageRoute::from_preferences("conventions", &preferences(false, &["CommitInfo"]));
+        assert_eq!(route, MessageRoute::Only(BTreeSet::from(["CommitInfo"])));
+        assert!(route.accepts(&commits));
+        assert!(!route.accepts(&file));
+
+        assert_eq!(MessageRoute::from_preferences("debug", &preferences(true, &["CommitInfo"])), MessageRoute::All);
+        assert_eq!(MessageRoute::from_preferences("metrics", &preferences(false, &[])), MessageRoute::All);
+    }
+
+    #[test]
+    fn test_unknown_types_are_ignored() {
+        let route = MessageRoute::from_preferences("blobs", &preferences(false, &["FileChange", "FileChanges"]));
+        assert_eq!(route, MessageRoute::Only(BTreeSet::from(["FileChange"])));
+        assert_eq!(MessageRoute::from_preferences("typo", &preferences(false, &["Commit"])), MessageRoute::All);
+        assert!(MESSAGE_TYPES.contains(&message_type(&MessageData::None)));
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::queue::error::QueueResult;
 use crate::queue::{MultiConsumerQueue, QueueConsumer};
+use crate::queue::multi_consumer::MultiConsumerConfig;
 use crate::scanner::messages::ScanMessage;
 use std::sync::Arc;
 
@@ -30,6 +31,17 @@ impl SharedMessageQueue {
         }
     }
 
+    /// Create a new shared message queue with custom limits and backpressure policy
+    pub fn with_config(
+        config: MultiConsumerConfig,
+        queue_publisher: Arc<crate::notifications::typed_publishers::QueueEventPublisher>,
+        scan_publisher: Arc<crate::notifications::typed_publishers::ScanEventPublisher>
+    ) -> Self {
+        Self {
+            queue: Arc::new(MultiConsumerQueue::with_config_and_publishers(config, queue_publisher, scan_publisher)),
+        }
+    }
+
 
     // Producer Interface
 
@@ -63,6 +75,11 @@ impl SharedMessageQueue {
     pub async fn subscribe_to_scan_events(&self) -> crate::queue::QueueResult<()> {
         self.queue.subscribe_to_scan_events().await
     }
+    
+    /// Receive the queue events published from now on, as consumer plugins handle them
+    pub fn subscribe_consumer_events(&self) -> tokio::sync::broadcast::Receiver<crate::queue::QueueEvent> {
+        self.queue.subscribe_consumer_events()
+    }
 
 }
 
@@ -0,0 +1,219 @@
+//! Queue Spill Buffer
+//!
+//! Under the spill-to-disk backpressure policy, messages that do not fit within
+//! the queue's limits are serialised to temporary segment files. Consumers that
+//! reach a spilled message read it from disk; once memory frees up the queue
+//! moves spilled messages back into memory, oldest first.
+//!
+//! Records are appended in sequence order to the newest segment, and a new
+//! segment is started when it reaches the segment size. A segment file is
+//! deleted as soon as every message in it has been collected or reloaded, so
+//! disk usage shrinks as consumers catch up rather than only once the whole
+//! buffer drains. Any remaining files are removed when the buffer is dropped.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::queue::{QueueError, QueueResult};
+use crate::scanner::messages::ScanMessage;
+
+/// Default size at which a new segment file is started (16MB)
+pub const DEFAULT_SEGMENT_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Location of a spilled message within its segment
+#[derive(Debug, Clone, Copy)]
+struct Record {
+    offset: u64,
+    length: usize,
+    /// Estimated memory usage of the message once read back
+    memory_size: usize,
+}
+
+/// One append-only segment file
+#[derive(Debug)]
+struct Segment {
+    path: PathBuf,
+    file: File,
+    records: BTreeMap<u64, Record>,
+    /// Offset at which the next record is written
+    end: u64,
+}
+
+impl Segment {
+    fn create(path: PathBuf) -> QueueResult<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| QueueError::operation_failed(format!("Failed to create spill segment {}: {}", path.display(), e)))?;
+        log::debug!("Spilling queue messages to {}", path.display());
+
+        Ok(Self { path, file, records: BTreeMap::new(), end: 0 })
+    }
+
+    fn append(&mut self, sequence: u64, record: &[u8], memory_size: usize) -> QueueResult<()> {
+        self.file.seek(SeekFrom::Start(self.end))
+            .and_then(|_| self.file.write_all(record))
+            .map_err(|e| self.io_error("write", e))?;
+
+        self.records.insert(sequence, Record { offset: self.end, length: record.len(), memory_size });
+        self.end += record.len() as u64;
+        Ok(())
+    }
+
+    fn read(&mut self, sequence: u64) -> QueueResult<Option<ScanMessage>> {
+        let Some(record) = self.records.get(&sequence).copied() else {
+            return Ok(None);
+        };
+        let mut bytes = vec![0; record.length];
+        self.file.seek(SeekFrom::Start(record.offset))
+            .and_then(|_| self.file.read_exact(&mut bytes))
+            .map_err(|e| self.io_error("read", e))?;
+
+        bincode::deserialize(&bytes)
+            .map(Some)
+            .map_err(|e| QueueError::operation_failed(format!("Failed to deserialise spilled message {}: {}", sequence, e)))
+    }
+
+    fn first_sequence(&self) -> Option<u64> {
+        self.records.keys().next().copied()
+    }
+
+    fn io_error(&self, operation: &str, error: std::io::Error) -> QueueError {
+        QueueError::operation_failed(format!("Failed to {} spill segment {}: {}", operation, self.path.display(), error))
+    }
+}
+
+impl Drop for Segment {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::debug!("Failed to remove spill segment {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Messages spilled from the queue, held in a sequence of segment files
+#[derive(Debug)]
+pub struct SpillBuffer {
+    directory: PathBuf,
+    /// Unique prefix of this buffer's segment file names
+    name: String,
+    segment_size: u64,
+    /// Segments oldest first; only the last one is appended to
+    segments: VecDeque<Segment>,
+    /// Number given to the next segment file
+    next_segment: u64,
+    /// Messages currently spilled across all segments
+    len: usize,
+}
+
+impl SpillBuffer {
+    /// Create an empty buffer whose segments are written to `directory`
+    ///
+    /// No file is created until the first message is spilled.
+    pub fn new(directory: &Path, segment_size: u64) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            name: format!("gstats-queue-{}", uuid::Uuid::now_v7()),
+            segment_size: segment_size.max(1),
+            segments: VecDeque::new(),
+            next_segment: 0,
+            len: 0,
+        }
+    }
+
+    /// Append a message, returning the number of bytes written
+    pub fn append(&mut self, message: &ScanMessage) -> QueueResult<usize> {
+        let record = bincode::serialize(message)
+            .map_err(|e| QueueError::operation_failed(format!("Failed to serialise spilled message: {}", e)))?;
+
+        if self.segments.back().is_none_or(|segment| segment.end >= self.segment_size) {
+            let path = self.directory.join(format!("{}-{:06}.seg", self.name, self.next_segment));
+            self.segments.push_back(Segment::create(path)?);
+            self.next_segment += 1;
+        }
+        let segment = self.segments.back_mut().expect("segment was just created");
+        segment.append(message.header().sequence, &record, message.estimate_memory_usage())?;
+
+        self.len += 1;
+        Ok(record.len())
+    }
+
+    /// Read back the message with the given sequence number, leaving it spilled
+    pub fn read(&mut self, sequence: u64) -> QueueResult<Option<ScanMessage>> {
+        // Segments hold ascending, non-overlapping sequence ranges
+        let index = self.segments.partition_point(|segment| {
+            segment.first_sequence().is_some_and(|first| first <= sequence)
+        });
+        match index.checked_sub(1).and_then(|index| self.segments.get_mut(index)) {
+            Some(segment) => segment.read(sequence),
+            None => Ok(None),
+        }
+    }
+
+    /// Estimated memory usage of the oldest spilled message
+    pub fn front_memory_size(&self) -> Option<usize> {
+        self.segments.front()
+            .and_then(|segment| segment.records.values().next())
+            .map(|record| record.memory_size)
+    }
+
+    /// Remove the oldest spilled message and return it
+    pub fn pop_front(&mut self) -> QueueResult<Option<ScanMessage>> {
+        let Some(sequence) = self.first_sequence() else {
+            return Ok(None);
+        };
+        let message = self.read(sequence)?;
+        self.discard_below(sequence + 1);
+        Ok(message)
+    }
+
+    /// Forget messages below `sequence`, deleting segment files left empty
+    pub fn discard_below(&mut self, sequence: u64) -> usize {
+        let mut discarded = 0;
+        let mut remaining = self.segments.len();
+        while let Some(segment) = self.segments.front_mut() {
+            let retained = segment.records.split_off(&sequence);
+            discarded += std::mem::replace(&mut segment.records, retained).len();
+            // The newest segment is kept while it can still be appended to
+            let reusable = remaining == 1 && segment.end < self.segment_size;
+            if !segment.records.is_empty() || reusable {
+                break;
+            }
+            self.segments.pop_front();
+            remaining -= 1;
+        }
+
+        if let Some(segment) = self.segments.front_mut().filter(|segment| segment.records.is_empty() && segment.end > 0) {
+            if let Err(e) = segment.file.set_len(0) {
+                log::warn!("Failed to truncate spill segment {}: {}", segment.path.display(), e);
+            }
+            segment.end = 0;
+        }
+        self.len -= discarded;
+        discarded
+    }
+
+    /// Sequence number of the oldest spilled message
+    pub fn first_sequence(&self) -> Option<u64> {
+        self.segments.iter().find_map(Segment::first_sequence)
+    }
+
+    /// Number of messages currently spilled
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no messages are spilled
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of segment files currently on disk
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+}
@@ -6,6 +6,10 @@
 
 use std::time::{Duration, Instant};
 
+use crate::display::table::TableBuilder;
+use crate::display::ColourManager;
+use crate::queue::ConsumerStatistics;
+
 /// Statistics for the multi-consumer queue
 #[derive(Debug, Clone)]
 pub struct QueueStatistics {
@@ -20,6 +24,18 @@ pub struct QueueStatistics {
     
     /// Total messages processed
     pub total_messages: u64,
+    
+    /// Messages discarded by the drop-oldest backpressure policy
+    pub messages_dropped: u64,
+    
+    /// Messages written to disk by the spill-to-disk backpressure policy
+    pub messages_spilled: u64,
+    
+    /// Times the producer waited for consumers under the block backpressure policy
+    pub producer_blocks: u64,
+    
+    /// Total time the producer spent blocked
+    pub producer_blocked_time: Duration,
 }
 
 /// Per-scan statistics and state tracking
@@ -76,4 +92,96 @@ impl Default for ScanStatistics {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Table of each consumer's lag, throughput, batching and backoff, most lagging first
+///
+/// Comparing `Avg Batch` with `Preferred` shows whether a plugin's
+/// `preferred_batch_size` is reached; frequent backoffs mean the consumer
+/// outpaces the producer, and a growing lag that it falls behind. `Passed Over`
+/// counts the messages routed away from the plugin without being handed to it.
+pub fn consumer_table(consumers: &[ConsumerStatistics], colour_manager: &ColourManager) -> String {
+    let mut consumers = consumers.to_vec();
+    consumers.sort_by(|a, b| b.lag().cmp(&a.lag()).then_with(|| a.plugin_name.cmp(&b.plugin_name)));
+
+    let mut table = TableBuilder::new()
+        .headers(["Plugin", "Enqueued", "Passed Over", "Acked", "Lag", "Msgs/s", "Avg Batch", "Preferred", "Backoffs", "Backoff Time"]
+            .map(String::from).to_vec());
+    for consumer in &consumers {
+        table = table.add_row(vec![
+            consumer.plugin_name.clone(),
+            consumer.enqueued.to_string(),
+            consumer.passed_over.to_string(),
+            consumer.acknowledged.to_string(),
+            consumer.lag().to_string(),
+            format!("{:.1}", consumer.throughput()),
+            format!("{:.1}", consumer.average_batch()),
+            match consumer.preferred_batch_size {
+                Some(0) => "unbatched".to_string(),
+                Some(size) => size.to_string(),
+                None => "-".to_string(),
+            },
+            consumer.backoffs.to_string(),
+            format!("{}ms", consumer.backoff_time.as_millis()),
+        ]);
+    }
+    table.build_with_colors(colour_manager)
+}
+
+/// One line of queue totals to accompany the consumer table
+pub fn queue_summary(queue: &QueueStatistics) -> String {
+    let mut summary = format!("{} messages enqueued, {} still queued", queue.total_messages, queue.queue_size);
+    if queue.producer_blocks > 0 {
+        summary.push_str(&format!(", producer blocked {} times for {}ms",
+            queue.producer_blocks, queue.producer_blocked_time.as_millis()));
+    }
+    if queue.messages_dropped > 0 {
+        summary.push_str(&format!(", {} dropped", queue.messages_dropped));
+    }
+    if queue.messages_spilled > 0 {
+        summary.push_str(&format!(", {} spilled to disk", queue.messages_spilled));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::ColourConfig;
+
+    fn consumer(plugin: &str, acknowledged: u64, preferred: Option<usize>) -> ConsumerStatistics {
+        ConsumerStatistics {
+            consumer_id: format!("{plugin}-1"),
+            plugin_name: plugin.to_string(),
+            enqueued: 100,
+            read: acknowledged,
+            passed_over: 0,
+            acknowledged,
+            read_operations: acknowledged / 10,
+            backoffs: 3,
+            backoff_time: Duration::from_millis(30),
+            elapsed: Duration::from_secs(2),
+            preferred_batch_size: preferred,
+        }
+    }
+
+    #[test]
+    fn test_consumer_statistics() {
+        let stats = consumer("commits", 40, Some(10));
+        assert_eq!(stats.lag(), 60);
+        assert_eq!(stats.throughput(), 20.0);
+        assert_eq!(stats.average_batch(), 10.0);
+        assert_eq!(ConsumerStatistics { read_operations: 0, ..stats }.average_batch(), 0.0);
+    }
+
+    #[test]
+    fn test_consumer_table_lists_most_lagging_first() {
+        let colour_manager = ColourManager::with_config(ColourConfig::disabled());
+        let table = consumer_table(&[consumer("metrics", 100, Some(0)), consumer("commits", 40, None)], &colour_manager);
+        let lines: Vec<&str> = table.lines().map(str::trim).collect();
+
+        assert!(lines[0].starts_with("Plugin"));
+        assert!(lines[2].starts_with("commits") && lines[2].contains(" 60 ") && lines[2].contains(" - "));
+        assert!(lines[3].starts_with("metrics") && lines[3].contains("unbatched"));
+    }
+}
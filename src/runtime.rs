@@ -0,0 +1,79 @@
+//! Shared Async Runtime
+//!
+//! gstats runs every async operation - plugin discovery and initialisation,
+//! command resolution, queue consumers and the repository scan - on a single
+//! multi-thread tokio runtime owned by this module. Components never build
+//! their own runtime; synchronous code enters async code through [`block_on`],
+//! which also works when called from a task already running on the shared
+//! runtime (as happens when gstats is embedded in an async host).
+//!
+//! The number of worker threads is taken from `--worker-threads` and must be
+//! set with [`configure`] before the runtime is first used.
+
+use std::future::Future;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+/// Worker thread count for the shared runtime; zero selects one per CPU core
+static WORKER_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Set the number of worker threads used by the shared runtime
+///
+/// Has no effect once the runtime has started, which is logged as a warning.
+pub fn configure(worker_threads: Option<usize>) {
+    if RUNTIME.get().is_some() {
+        log::warn!("Shared runtime already started; ignoring worker thread setting");
+        return;
+    }
+    WORKER_THREADS.store(worker_threads.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Get the shared runtime, starting it on first use
+pub fn shared() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        match WORKER_THREADS.load(Ordering::Relaxed) {
+            0 => {}
+            threads => {
+                builder.worker_threads(threads);
+            }
+        }
+        builder
+            .thread_name("gstats-worker")
+            .enable_all()
+            .build()
+            .expect("Failed to create the shared tokio runtime")
+    })
+}
+
+/// Run a future to completion from synchronous code
+///
+/// Inside a multi-thread runtime the current worker is handed over to blocking
+/// work while the future runs, so nested calls do not start another runtime.
+/// Calling this from a task on a current-thread runtime panics, as blocking
+/// would stall that runtime.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        _ => shared().block_on(future),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_on_nests_within_shared_runtime() {
+        let value = block_on(async {
+            let inner = block_on(async { 20 });
+            tokio::spawn(async move { inner + 1 }).await.unwrap()
+        });
+        assert_eq!(value, 21);
+    }
+}
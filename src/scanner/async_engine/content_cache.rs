@@ -0,0 +1,108 @@
+//! Blob Content Cache
+//!
+//! File content is read from the object database once per blob. A tree often
+//! holds the same blob under several paths (licence files, empty package
+//! markers, vendored copies), and the files a plugin requires by path are
+//! usually part of the tree already streamed, so reads go through a cache
+//! keyed by object id that hands out shared buffers. The content messages
+//! built from them are in turn shared by every consuming plugin, whatever
+//! buffer size each prefers, so enabling more content plugins adds no I/O.
+
+use gix::ObjectId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Blob reads and cache hits of a scan's content pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentCacheStats {
+    /// Blobs read from the object database
+    pub blobs_read: u64,
+    /// Requests answered from a blob already read
+    pub hits: u64,
+    /// Bytes read from the object database
+    pub bytes_read: u64,
+    /// Bytes answered from the cache instead of being read again
+    pub bytes_shared: u64,
+}
+
+impl ContentCacheStats {
+    /// Total content requests, read or answered from the cache
+    pub fn requests(&self) -> u64 {
+        self.blobs_read + self.hits
+    }
+
+    /// Share of requests answered from the cache, from 0.0 to 1.0
+    pub fn hit_rate(&self) -> f64 {
+        match self.requests() {
+            0 => 0.0,
+            requests => self.hits as f64 / requests as f64,
+        }
+    }
+}
+
+/// Blob contents read during one content pass, shared by object id
+#[derive(Debug, Default)]
+pub struct ContentCache {
+    blobs: HashMap<ObjectId, Arc<[u8]>>,
+    stats: ContentCacheStats,
+}
+
+impl ContentCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Content of a blob, read with `read` only the first time the blob is requested
+    pub fn get_or_read<E>(&mut self, id: ObjectId, read: impl FnOnce() -> Result<Vec<u8>, E>) -> Result<Arc<[u8]>, E> {
+        if let Some(data) = self.blobs.get(&id) {
+            self.stats.hits += 1;
+            self.stats.bytes_shared += data.len() as u64;
+            return Ok(Arc::clone(data));
+        }
+        let data: Arc<[u8]> = read()?.into();
+        self.stats.blobs_read += 1;
+        self.stats.bytes_read += data.len() as u64;
+        self.blobs.insert(id, Arc::clone(&data));
+        Ok(data)
+    }
+
+    /// Reads and hits so far
+    pub fn stats(&self) -> ContentCacheStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_is_read_once() {
+        let mut cache = ContentCache::new();
+        let id = ObjectId::null(gix::hash::Kind::Sha1);
+        let mut reads = 0;
+        for _ in 0..3 {
+            let data = cache.get_or_read(id, || -> Result<_, String> {
+                reads += 1;
+                Ok(b"shared".to_vec())
+            }).unwrap();
+            assert_eq!(&*data, b"shared");
+        }
+
+        assert_eq!(reads, 1);
+        let stats = cache.stats();
+        assert_eq!((stats.blobs_read, stats.hits), (1, 2));
+        assert_eq!((stats.bytes_read, stats.bytes_shared), (6, 12));
+        assert!((stats.hit_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_failed_read_is_not_cached() {
+        let mut cache = ContentCache::new();
+        let id = ObjectId::null(gix::hash::Kind::Sha1);
+        assert!(cache.get_or_read(id, || Err("missing")).is_err());
+        assert_eq!(cache.get_or_read(id, || Ok::<_, &str>(vec![1])).unwrap().len(), 1);
+        assert_eq!(cache.stats().hits, 0);
+        assert_eq!(ContentCacheStats::default().hit_rate(), 0.0);
+    }
+}
@@ -3,10 +3,16 @@
 //! This module provides smart diff line analysis by parsing git diff output directly,
 //! eliminating the need to apply diffs or recount lines. It counts additions and deletions
 //! by parsing the + and - prefixed lines from git diff output.
+//!
+//! [`RenameChain`] follows renames through history, newest commit first, so that
+//! changes made under earlier names are reported under a file's current name
+//! (as `git log --follow` does) instead of as a separate deleted file.
 
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Cursor};
 use crate::scanner::async_engine::events::ChangeType;
 use crate::scanner::async_engine::error::ScanError;
+use crate::scanner::messages::is_executable_mode;
 
 /// Result of analyzing a file change in a commit
 #[derive(Debug, Clone, PartialEq)]
@@ -25,6 +31,31 @@ pub struct FileChangeAnalysis {
     pub is_binary: bool,
     /// Size in bytes for binary files
     pub binary_size: Option<u64>,
+    /// File mode before the change (from `old mode`, `deleted file mode` or `index` headers)
+    pub old_mode: Option<u32>,
+    /// File mode after the change (from `new mode`, `new file mode` or `index` headers)
+    pub new_mode: Option<u32>,
+    /// Removed lines as `(first line, line count)` ranges in the old file, from hunk bodies
+    pub removed_ranges: Vec<(usize, usize)>,
+}
+
+impl FileChangeAnalysis {
+    /// Check whether the file mode changed between the old and new versions
+    pub fn is_mode_change(&self) -> bool {
+        matches!((self.old_mode, self.new_mode), (Some(old), Some(new)) if old != new)
+    }
+    
+    /// Check whether only the file mode changed, with no content changes
+    pub fn is_mode_change_only(&self) -> bool {
+        self.is_mode_change() && self.insertions == 0 && self.deletions == 0
+    }
+    
+    /// Check whether this change made the file executable (newly added or bit flipped on)
+    pub fn became_executable(&self) -> bool {
+        let now_executable = self.new_mode.is_some_and(is_executable_mode);
+        let was_executable = self.old_mode.is_some_and(is_executable_mode);
+        now_executable && !was_executable
+    }
 }
 
 /// Smart diff line analyzer that parses git diff output directly
@@ -52,12 +83,31 @@ impl DiffLineAnalyzer {
         let mut binary_size = None;
         let mut change_type = ChangeType::Modified; // Default
         let mut old_path = None;
+        let mut old_mode = None;
+        let mut new_mode = None;
+        let mut removed_ranges: Vec<(usize, usize)> = Vec::new();
+        let mut old_line: Option<usize> = None; // Current old-file line within a hunk
         
         let reader = BufReader::new(Cursor::new(diff_output));
         
         for line_result in reader.lines() {
             let line = line_result.map_err(|e| ScanError::Repository(format!("Failed to read diff line: {e}")))?;
             
+            // Track old-file line numbers through hunks to locate removed lines
+            if line.starts_with("@@ ") {
+                old_line = parse_hunk_old_start(&line);
+            } else if let Some(current) = old_line {
+                if line.starts_with('-') {
+                    match removed_ranges.last_mut() {
+                        Some((start, count)) if *start + *count == current => *count += 1,
+                        _ => removed_ranges.push((current, 1)),
+                    }
+                    old_line = Some(current + 1);
+                } else if line.starts_with(' ') {
+                    old_line = Some(current + 1);
+                }
+            }
+            
             // Detect binary files
             if line.contains("Binary files") || line.contains("GIT binary patch") {
                 is_binary = true;
@@ -66,10 +116,20 @@ impl DiffLineAnalyzer {
             }
             
             // Detect file status from diff headers
-            if line.starts_with("new file mode") {
+            if let Some(mode) = line.strip_prefix("new file mode ") {
                 change_type = ChangeType::Added;
-            } else if line.starts_with("deleted file mode") {
+                new_mode = parse_mode(mode);
+            } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
                 change_type = ChangeType::Deleted;
+                old_mode = parse_mode(mode);
+            } else if let Some(mode) = line.strip_prefix("old mode ") {
+                old_mode = parse_mode(mode);
+            } else if let Some(mode) = line.strip_prefix("new mode ") {
+                new_mode = parse_mode(mode);
+            } else if let Some(mode) = line.strip_prefix("index ").and_then(|rest| rest.split_whitespace().nth(1)) {
+                // "index abc..def 100644" - mode is unchanged
+                old_mode = old_mode.or(parse_mode(mode));
+                new_mode = new_mode.or(parse_mode(mode));
             } else if line.starts_with("rename from") {
                 change_type = ChangeType::Renamed;
                 // Extract old path from "rename from old/path"
@@ -106,6 +166,9 @@ impl DiffLineAnalyzer {
             deletions,
             is_binary,
             binary_size,
+            old_mode,
+            new_mode,
+            removed_ranges,
         })
     }
     
@@ -181,6 +244,49 @@ impl DiffLineAnalyzer {
     }
 }
 
+/// Renames seen so far while walking history from the newest commit backwards
+///
+/// Each earlier name maps directly to the file's name at the newest commit.
+#[derive(Debug, Clone, Default)]
+pub struct RenameChain {
+    current_names: HashMap<String, String>,
+}
+
+impl RenameChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record a rename from `old_path` to `new_path`
+    ///
+    /// Renames must be recorded newest first, before the changes of older commits are resolved.
+    pub fn record_rename(&mut self, old_path: &str, new_path: &str) {
+        let current = self.resolve(new_path).to_string();
+        if current == old_path {
+            // Renamed back to an earlier name: the old path is the current name again
+            self.current_names.remove(old_path);
+        } else {
+            self.current_names.insert(old_path.to_string(), current);
+        }
+    }
+    
+    /// Current name of a file known as `path` in an older commit
+    pub fn resolve<'a>(&'a self, path: &'a str) -> &'a str {
+        self.current_names.get(path).map_or(path, String::as_str)
+    }
+}
+
+/// Parse an octal git file mode such as "100755"
+fn parse_mode(mode: &str) -> Option<u32> {
+    u32::from_str_radix(mode.trim(), 8).ok()
+}
+
+/// Parse the old-file start line from a hunk header such as "@@ -12,4 +12,6 @@"
+fn parse_hunk_old_start(header: &str) -> Option<usize> {
+    let old = header.strip_prefix("@@ -")?.split_whitespace().next()?;
+    old.split(',').next()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,4 +448,107 @@ index 0000000..abc123
         assert_eq!(lib_analysis.insertions, 1);
         assert_eq!(lib_analysis.deletions, 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_analyze_mode_change_only_diff() {
+        let diff_output = r#"
+diff --git a/scripts/deploy.sh b/scripts/deploy.sh
+old mode 100644
+new mode 100755
+"#;
+
+        let analysis = DiffLineAnalyzer::analyze_file_diff(diff_output, "scripts/deploy.sh").unwrap();
+        
+        assert_eq!(analysis.change_type, ChangeType::Modified);
+        assert_eq!(analysis.old_mode, Some(0o100644));
+        assert_eq!(analysis.new_mode, Some(0o100755));
+        assert!(analysis.is_mode_change());
+        assert!(analysis.is_mode_change_only());
+        assert!(analysis.became_executable());
+    }
+
+    #[test]
+    fn test_analyze_new_executable_diff() {
+        let diff_output = r#"
+diff --git a/bin/run b/bin/run
+new file mode 100755
+index 0000000..abc123
+--- /dev/null
++++ b/bin/run
+@@ -0,0 +1,2 @@
++#!/bin/sh
++exec ./app
+"#;
+
+        let analysis = DiffLineAnalyzer::analyze_file_diff(diff_output, "bin/run").unwrap();
+        
+        assert_eq!(analysis.change_type, ChangeType::Added);
+        assert_eq!(analysis.old_mode, None);
+        assert_eq!(analysis.new_mode, Some(0o100755));
+        assert!(!analysis.is_mode_change());
+        assert!(analysis.became_executable());
+    }
+
+    #[test]
+    fn test_analyze_unchanged_mode_from_index_line() {
+        let diff_output = r#"
+diff --git a/src/main.rs b/src/main.rs
+index abc123..def456 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-fn main() {}
++fn main() { }
+"#;
+
+        let analysis = DiffLineAnalyzer::analyze_file_diff(diff_output, "src/main.rs").unwrap();
+        
+        assert_eq!(analysis.old_mode, Some(0o100644));
+        assert_eq!(analysis.new_mode, Some(0o100644));
+        assert!(!analysis.is_mode_change());
+        assert!(!analysis.became_executable());
+    }
+
+    #[test]
+    fn test_removed_line_ranges() {
+        let diff_output = r#"
+diff --git a/src/lib.rs b/src/lib.rs
+index abc123..def456 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,6 +3,5 @@ mod a;
+ fn keep() {}
+-fn old_one() {}
+-fn old_two() {}
++fn new_one() {}
+ fn keep_too() {}
+-fn old_three() {}
+ fn end() {}
+@@ -40,2 +39,2 @@
+--- decrement
++++ increment
+ last
+"#;
+
+        let analysis = DiffLineAnalyzer::analyze_file_diff(diff_output, "src/lib.rs").unwrap();
+        assert_eq!(analysis.removed_ranges, vec![(4, 2), (7, 1), (40, 1)]);
+    }
+
+    #[test]
+    fn test_rename_chain_follows_renames_to_current_name() {
+        let mut chain = RenameChain::new();
+        // Newest first: lib.rs <- core.rs <- old.rs
+        chain.record_rename("src/core.rs", "src/lib.rs");
+        chain.record_rename("src/old.rs", "src/core.rs");
+        assert_eq!(chain.resolve("src/old.rs"), "src/lib.rs");
+        assert_eq!(chain.resolve("src/core.rs"), "src/lib.rs");
+        assert_eq!(chain.resolve("src/main.rs"), "src/main.rs");
+        
+        // a.rs -> b.rs -> a.rs: older changes to a.rs stay under a.rs
+        let mut chain = RenameChain::new();
+        chain.record_rename("b.rs", "a.rs");
+        chain.record_rename("a.rs", "b.rs");
+        assert_eq!(chain.resolve("a.rs"), "a.rs");
+        assert_eq!(chain.resolve("b.rs"), "a.rs");
+    }
+}
@@ -4,10 +4,9 @@
 
 use std::sync::Arc;
 use std::path::{Path, PathBuf};
-#[cfg(not(test))]
-use tokio::runtime::Runtime;
 use futures::StreamExt;
 use crate::scanner::config::ScannerConfig;
+use crate::scanner::repository::open_repository;
 use crate::scanner::traits::MessageProducer;
 use crate::scanner::async_traits::ScanMessageStream;
 use super::scanners::EventDrivenScanner;
@@ -17,13 +16,10 @@ use crate::plugin::SharedPluginRegistry;
 use crate::notifications::traits::{Publisher, NotificationManager};
 use crate::notifications::events::ScanEvent;
 use crate::notifications::manager::AsyncNotificationManager;
+use crate::shutdown::ShutdownController;
 
 /// Async scanner manager that coordinates EventDrivenScanner instances
 pub struct AsyncScannerManager {
-    /// Tokio runtime for async operations
-    #[cfg(not(test))]
-    _runtime: Arc<Runtime>,
-    
     /// Repository path
     repository_path: PathBuf,
     
@@ -41,10 +37,21 @@ pub struct AsyncScannerManager {
     
     /// Notification manager for publishing scanner lifecycle events
     notification_manager: Arc<AsyncNotificationManager<ScanEvent>>,
+    
+    /// Shutdown state of the run, reported with the scan completion
+    shutdown: ShutdownController,
+    
+    /// Limitations of the scanned history known before the scan starts
+    warnings: Vec<String>,
+    
+    /// Fixed scan ID for reproducible runs; otherwise each scan gets a random one
+    scan_id: Option<String>,
 }
 
 impl AsyncScannerManager {
     /// Create a new async scanner engine
+    ///
+    /// The engine runs on the caller's runtime; see [`crate::runtime`].
     pub fn new<P: AsRef<Path>>(
         repository_path: P,
         config: ScannerConfig,
@@ -52,45 +59,13 @@ impl AsyncScannerManager {
         notification_manager: Arc<AsyncNotificationManager<ScanEvent>>,
         plugin_registry: SharedPluginRegistry,
     ) -> ScanResult<Self> {
-        // Validate repository path
-        let repo_path = repository_path.as_ref();
-        Self::validate_repository_path(repo_path)?;
-        
-        // Create runtime with configured thread count
-        let runtime = tokio::runtime::Builder::new_multi_thread()
-            .worker_threads(config.max_threads.unwrap_or_else(num_cpus::get))
-            .enable_all()
-            .build()
-            .map_err(|e| ScanError::configuration(format!("Failed to create runtime: {e}")))?;
-        
-        Self::with_runtime(repository_path, config, message_producer, notification_manager, plugin_registry, Arc::new(runtime))
-    }
-    
-    /// Create a new async scanner engine with existing runtime (for tests)
-    pub fn with_runtime<P: AsRef<Path>>(
-        repository_path: P,
-        config: ScannerConfig,
-        message_producer: Arc<dyn MessageProducer + Send + Sync>,
-        notification_manager: Arc<AsyncNotificationManager<ScanEvent>>,
-        plugin_registry: SharedPluginRegistry,
-        _runtime: Arc<tokio::runtime::Runtime>,
-    ) -> ScanResult<Self> {
-        Self::with_runtime_impl(repository_path, config, message_producer, notification_manager, plugin_registry, _runtime)
-    }
-    
-    
-    /// Internal implementation for with_runtime
-    fn with_runtime_impl<P: AsRef<Path>>(
-        repository_path: P,
-        config: ScannerConfig,
-        message_producer: Arc<dyn MessageProducer + Send + Sync>,
-        notification_manager: Arc<AsyncNotificationManager<ScanEvent>>,
-        plugin_registry: SharedPluginRegistry,
-        _runtime: Arc<tokio::runtime::Runtime>,
-    ) -> ScanResult<Self> {
-        // Validate and canonicalize repository path
+        // Validate and canonicalize repository path; a patch series is read by the scanner
         let repo_path = repository_path.as_ref();
-        Self::validate_repository_path(repo_path)?;
+        if config.patch_series.is_some() {
+            Self::validate_patch_series_path(repo_path)?;
+        } else {
+            Self::validate_repository_path(repo_path)?;
+        }
         
         let canonical_path = repo_path.canonicalize()
             .map_err(|e| ScanError::configuration(format!("Failed to canonicalize path {}: {}", repo_path.display(), e)))?;
@@ -100,14 +75,15 @@ impl AsyncScannerManager {
         let task_manager = TaskManager::new(max_concurrent);
         
         Ok(Self {
-            #[cfg(not(test))]
-            _runtime,
             repository_path: canonical_path,
             task_manager,
             message_producer,
             scanners: Vec::new(),
             plugin_registry,
             notification_manager,
+            shutdown: ShutdownController::default(),
+            warnings: Vec::new(),
+            scan_id: None,
         })
     }
     
@@ -121,7 +97,7 @@ impl AsyncScannerManager {
         }
         
         // Validate it's a git repository using gitoxide
-        gix::discover(path)
+        open_repository(path)
             .map_err(|e| ScanError::configuration(format!(
                 "Not a valid git repository at {}: {}", 
                 path.display(), 
@@ -131,39 +107,29 @@ impl AsyncScannerManager {
         Ok(())
     }
     
+    /// Validate that a patch series path exists
+    fn validate_patch_series_path(path: &Path) -> ScanResult<()> {
+        if !path.exists() {
+            return Err(ScanError::configuration(format!(
+                "Patch series does not exist: {}", 
+                path.display()
+            )));
+        }
+        Ok(())
+    }
+    
     /// Get the repository path
     pub fn repository_path(&self) -> &Path {
         &self.repository_path
     }
     
-    /// Create a new async scanner engine for testing (no separate runtime)
-    #[cfg(test)]
-    pub fn new_for_test<P: AsRef<Path>>(
-        repository_path: P,
-        config: ScannerConfig,
-        message_producer: Arc<dyn MessageProducer + Send + Sync>,
-        notification_manager: Arc<AsyncNotificationManager<ScanEvent>>,
-        plugin_registry: SharedPluginRegistry,
-    ) -> ScanResult<Self> {
-        // Validate and canonicalize repository path
-        let repo_path = repository_path.as_ref();
-        Self::validate_repository_path(repo_path)?;
-        
-        let canonical_path = repo_path.canonicalize()
-            .map_err(|e| ScanError::configuration(format!("Failed to canonicalize path {}: {}", repo_path.display(), e)))?;
-        
-        // Create task manager with concurrency limit
-        let max_concurrent = config.max_threads.unwrap_or_else(num_cpus::get);
-        let task_manager = TaskManager::new(max_concurrent);
-        
-        Ok(Self {
-            repository_path: canonical_path,
-            task_manager,
-            message_producer,
-            scanners: Vec::new(),
-            plugin_registry,
-            notification_manager,
-        })
+    /// Scan ID derived from a description of the scan's input, so identical
+    /// scans are identified alike
+    pub fn reproducible_scan_id(input: &str) -> String {
+        let digest = gix::objs::compute_hash(gix::hash::Kind::Sha1, gix::objs::Kind::Blob, input.as_bytes())
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        format!("scan-{}", &digest[..digest.len().min(16)])
     }
     
     /// Register a scanner with the manager
@@ -175,15 +141,26 @@ impl AsyncScannerManager {
     
     /// Execute scan with specified modes
     pub async fn scan(&self) -> ScanResult<()> {
-        // Generate unique scan ID
-        let scan_id = format!("scan-{}", uuid::Uuid::new_v4());
+        // Generate unique scan ID unless a reproducible one was given
+        let scan_id = self.scan_id.clone().unwrap_or_else(|| format!("scan-{}", uuid::Uuid::new_v4()));
         let scan_start_time = std::time::Instant::now();
+        let _scan_stage = crate::profiling::stage("scan");
+        #[cfg(feature = "otel")]
+        let scan_span = crate::telemetry::start_scan(&scan_id, vec![
+            crate::telemetry::KeyValue::new("repository", self.repository_path.display().to_string()),
+        ]);
         
         // Publish ScanStarted event
         let started_event = ScanEvent::started(scan_id.clone());
         if let Err(e) = self.notification_manager.publish(started_event).await {
             log::warn!("Failed to publish ScanStarted event: {e}");
         }
+        for warning in &self.warnings {
+            let warning_event = ScanEvent::warning(scan_id.clone(), warning.clone(), true);
+            if let Err(e) = self.notification_manager.publish(warning_event).await {
+                log::warn!("Failed to publish ScanWarning event: {e}");
+            }
+        }
         
         if self.scanners.is_empty() {
             return Err(ScanError::no_scanners_registered());
@@ -243,6 +220,9 @@ impl AsyncScannerManager {
         });
         
         // Run all scanners - no mode filtering
+        let traverse_stage = crate::profiling::stage("scan.traverse");
+        #[cfg(feature = "otel")]
+        let mut traverse_span = crate::telemetry::span("scan.traverse", Vec::new());
         let mut tasks = Vec::new();
         
         for scanner in &self.scanners {
@@ -271,16 +251,42 @@ impl AsyncScannerManager {
         }
         
         // Wait for all tasks to complete
-        self.task_manager.wait_all().await?;
+        let outcome = self.task_manager.wait_all().await;
+        drop(traverse_stage);
+        #[cfg(feature = "otel")]
+        {
+            traverse_span.record_result(&outcome);
+            drop(traverse_span);
+            scan_span.record_result(&outcome);
+        }
         
         // Stop periodic event timer
         let _ = timer_tx.send(());
         let _ = periodic_task.await;
         
+        // Report failures with the repository location they occurred at, so
+        // subscribers and the log file see the same actionable detail
+        if let Err(e) = outcome {
+            log::error!("Scan {scan_id} failed: {e}");
+            let error_event = ScanEvent::error(scan_id, e.to_string(), true);
+            if let Err(publish_error) = self.notification_manager.publish(error_event).await {
+                log::warn!("Failed to publish ScanError event: {publish_error}");
+            }
+            return Err(e);
+        }
+        
         // Publish ScanCompleted event
         let scan_duration = scan_start_time.elapsed();
-        let warnings = Vec::new(); // TODO: collect actual warnings from task manager
+        let mut warnings = self.warnings.clone();
+        warnings.extend(self.scanners.iter().flat_map(|scanner| scanner.file_policy_warnings()));
+        if let Some(reason) = self.shutdown.reason() {
+            warnings.push(format!("Scan {reason}; results cover only the history read before then"));
+        }
         
+        // Subscribers finish their work on the scan as the completion is delivered
+        let _complete_stage = crate::profiling::stage("scan.complete");
+        #[cfg(feature = "otel")]
+        let _complete_span = crate::telemetry::span("scan.complete", Vec::new());
         let completed_event = ScanEvent::completed(scan_id, scan_duration, warnings);
         if let Err(e) = self.notification_manager.publish(completed_event).await {
             log::warn!("Failed to publish ScanCompleted event: {e}");
@@ -459,8 +465,10 @@ pub struct AsyncScannerManagerBuilder {
     message_producer: Option<Arc<dyn MessageProducer + Send + Sync>>,
     notification_manager: Option<Arc<AsyncNotificationManager<ScanEvent>>>,
     scanners: Vec<Arc<EventDrivenScanner>>,
-    runtime: Option<Arc<tokio::runtime::Runtime>>,
     plugin_registry: Option<SharedPluginRegistry>,
+    shutdown: Option<ShutdownController>,
+    warnings: Vec<String>,
+    scan_id: Option<String>,
 }
 
 impl AsyncScannerManagerBuilder {
@@ -472,8 +480,10 @@ impl AsyncScannerManagerBuilder {
             message_producer: None,
             notification_manager: None,
             scanners: Vec::new(),
-            runtime: None,
             plugin_registry: None,
+            shutdown: None,
+            warnings: Vec::new(),
+            scan_id: None,
         }
     }
     
@@ -513,12 +523,6 @@ impl AsyncScannerManagerBuilder {
         self
     }
     
-    /// Set the runtime (optional - if not set, a new runtime will be created)
-    pub fn runtime(mut self, runtime: Arc<tokio::runtime::Runtime>) -> Self {
-        self.runtime = Some(runtime);
-        self
-    }
-    
     /// Set the plugin registry for coordination during shutdown
     /// 
     /// Configures the scanner engine to coordinate with plugins during graceful
@@ -546,6 +550,25 @@ impl AsyncScannerManagerBuilder {
         self
     }
     
+    /// Set the shutdown controller shared with the scanners, queue and plugin registry
+    pub fn shutdown(mut self, shutdown: ShutdownController) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+    
+    /// Add a warning about the scanned history, published when the scan
+    /// starts and repeated with its completion
+    pub fn warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+    
+    /// Use a fixed scan ID instead of a random one, see [`AsyncScannerManager::reproducible_scan_id`]
+    pub fn scan_id(mut self, scan_id: impl Into<String>) -> Self {
+        self.scan_id = Some(scan_id.into());
+        self
+    }
+    
     /// Build the engine
     pub fn build(self) -> ScanResult<AsyncScannerManager> {
         let repository_path = self.repository_path
@@ -562,27 +585,16 @@ impl AsyncScannerManagerBuilder {
         let plugin_registry = self.plugin_registry
             .ok_or_else(|| ScanError::configuration("Plugin registry not set"))?;
         
-        let mut engine = if let Some(runtime) = self.runtime {
-            // Use provided runtime
-            AsyncScannerManager::with_runtime(repository_path, config, message_producer, notification_manager, plugin_registry, runtime)?
-        } else {
-            // Check if we're already in a tokio runtime context
-            if tokio::runtime::Handle::try_current().is_ok() {
-                log::warn!("AsyncScannerManager: Detected existing runtime context - this may cause shutdown issues");
-                log::warn!("AsyncScannerManager: Consider refactoring to avoid nested runtime creation");
-            }
-            
-            // Always create our own runtime for now - this is the original behavior
-            #[cfg(test)]
-            let engine = AsyncScannerManager::new_for_test(repository_path, config, message_producer, notification_manager, plugin_registry)?;
-            #[cfg(not(test))]
-            let engine = AsyncScannerManager::new(repository_path, config, message_producer, notification_manager, plugin_registry)?;
-            engine
-        };
+        let mut engine = AsyncScannerManager::new(repository_path, config, message_producer, notification_manager, plugin_registry)?;
         
         for scanner in self.scanners {
             engine.register_scanner(scanner);
         }
+        if let Some(shutdown) = self.shutdown {
+            engine.shutdown = shutdown;
+        }
+        engine.warnings = self.warnings;
+        engine.scan_id = self.scan_id;
         
         Ok(engine)
     }
@@ -615,22 +627,10 @@ impl Drop for AsyncScannerManager {
     /// The drop process waits for all active plugins to transition to idle states
     /// to ensure data export and processing complete before shutdown.
     fn drop(&mut self) {
-        // Since we need to coordinate with async plugins but Drop is sync,
-        // we need to create a runtime to handle the coordination
-        let rt = match tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build() 
-        {
-            Ok(rt) => rt,
-            Err(e) => {
-                log::error!("AsyncScannerManager Drop: Failed to create runtime for shutdown coordination: {}", e);
-                return;
-            }
-        };
-        
+        // Drop is sync, so coordinate with the async plugins on the shared runtime
         let plugin_registry = self.plugin_registry.clone();
         
-        rt.block_on(async move {
+        crate::runtime::block_on(async move {
             log::debug!("AsyncScannerManager Drop: Starting graceful shutdown coordination");
             
             let timeout = std::time::Duration::from_secs(10);
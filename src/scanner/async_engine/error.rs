@@ -3,6 +3,7 @@
 //! Error types specific to async scanning operations.
 
 use thiserror::Error;
+use std::fmt;
 use std::sync::Arc;
 
 /// Errors that can occur during async scanning operations
@@ -36,6 +37,10 @@ pub enum ScanError {
     #[error("Configuration problem: {0}\n\nCheck your configuration file or command line arguments. Run 'gstats --help' for usage information.")]
     Configuration(String),
     
+    /// Failure reading or diffing a specific repository object
+    #[error("Repository object error while {context}: {message}")]
+    Object { context: Box<ObjectContext>, message: String },
+    
     /// Generic async operation error
     #[error("Analysis operation failed: {0}")]
     AsyncOperation(String),
@@ -107,6 +112,106 @@ impl ScanError {
     }
 }
 
+/// Stage of the scan in which a repository object was being read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanPhase {
+    /// Walking the commit graph (the commit is the last one reached)
+    Traversal,
+    /// Reading commit headers (author, time, message)
+    CommitMetadata,
+    /// Reading a commit's tree
+    TreeRead,
+    /// Reading file contents
+    BlobRead,
+    /// Diffing a commit against its parent
+    Diff,
+    /// Attributing replaced lines with `git blame`
+    Blame,
+    /// Writing file contents for plugins that need a checkout
+    Checkout,
+}
+
+impl ScanPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScanPhase::Traversal => "traversing history",
+            ScanPhase::CommitMetadata => "reading commit metadata",
+            ScanPhase::TreeRead => "reading tree",
+            ScanPhase::BlobRead => "reading file contents",
+            ScanPhase::Diff => "diffing commit",
+            ScanPhase::Blame => "attributing lines",
+            ScanPhase::Checkout => "checking out file",
+        }
+    }
+}
+
+/// Where in the repository a scan failure happened
+///
+/// Carries the object IDs and path needed to locate a corrupt or unreadable
+/// object, e.g. `reading file contents (commit 1a2b..., path src/lib.rs)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectContext {
+    pub phase: ScanPhase,
+    /// Commit being processed
+    pub commit: Option<String>,
+    /// Parent commit the diff was taken against
+    pub parent: Option<String>,
+    /// Object that could not be read, when different from the commit
+    pub object: Option<String>,
+    /// Repository path of the file being processed
+    pub path: Option<String>,
+}
+
+impl ObjectContext {
+    pub fn new(phase: ScanPhase) -> Self {
+        Self { phase, commit: None, parent: None, object: None, path: None }
+    }
+    
+    pub fn commit(mut self, commit: impl ToString) -> Self {
+        self.commit = Some(commit.to_string());
+        self
+    }
+    
+    pub fn parent(mut self, parent: impl ToString) -> Self {
+        self.parent = Some(parent.to_string());
+        self
+    }
+    
+    pub fn object(mut self, object: impl ToString) -> Self {
+        self.object = Some(object.to_string());
+        self
+    }
+    
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+    
+    /// Build a scan error for this location
+    pub fn error(self, message: impl fmt::Display) -> ScanError {
+        ScanError::Object { context: Box::new(self), message: message.to_string() }
+    }
+}
+
+impl fmt::Display for ObjectContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.phase.as_str())?;
+        let details: Vec<String> = [
+            ("commit", &self.commit),
+            ("parent", &self.parent),
+            ("object", &self.object),
+            ("path", &self.path),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.as_ref().map(|value| format!("{label} {value}")))
+        .collect();
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 /// Result type for async scanning operations
 pub type ScanResult<T> = Result<T, ScanError>;
 
@@ -187,6 +292,20 @@ mod tests {
         assert!(err.to_string().contains("The requested analysis mode is not available"));
     }
     
+    #[test]
+    fn test_object_error_names_location() {
+        let err = ObjectContext::new(ScanPhase::Diff)
+            .commit("1a2b3c")
+            .parent("0f0f0f")
+            .error("git diff failed (exit status: 128)");
+        
+        assert_eq!(
+            err.to_string(),
+            "Repository object error while diffing commit (commit 1a2b3c, parent 0f0f0f): git diff failed (exit status: 128)"
+        );
+        assert!(matches!(err, ScanError::Object { context, .. } if context.phase == ScanPhase::Diff));
+    }
+    
     #[test]
     fn test_task_error() {
         let scan_err = ScanError::repository("test");
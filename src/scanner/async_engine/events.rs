@@ -1,3 +1,4 @@
+use crate::scanner::filters::{AuthorMatcher, PathMatcher};
 use crate::scanner::query::QueryParams;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -70,15 +71,8 @@ pub struct FileChangeData {
     pub is_binary: bool,
 }
 
-/// Type of file change
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum ChangeType {
-    Added,
-    Modified,
-    Deleted,
-    Renamed,
-    Copied,
-}
+/// Type of file change (defined in the plugin API crate)
+pub use gstats_plugin_api::messages::ChangeType;
 
 /// File information from working directory scan
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,12 +102,18 @@ pub struct EventFilter {
     pub query_params: QueryParams,
     pub include_binary_files: bool,
     pub max_file_size: Option<u64>,
+    /// Path patterns of the query, compiled once
+    path_matcher: PathMatcher,
+    /// Author patterns of the query, compiled once
+    author_matcher: AuthorMatcher,
 }
 
 impl EventFilter {
     /// Create a new event filter from query parameters
     pub fn from_query_params(query_params: QueryParams) -> Self {
         Self {
+            path_matcher: PathMatcher::new(&query_params.file_paths),
+            author_matcher: AuthorMatcher::new(&query_params.authors),
             query_params,
             include_binary_files: false,
             max_file_size: Some(10 * 1024 * 1024), // 10MB default limit
@@ -136,27 +136,13 @@ impl EventFilter {
             }
         }
 
-        // Apply author filters
-        if !self.query_params.authors.include.is_empty() {
-            let author_match = self.query_params.authors.include.iter().any(|author| {
-                commit.author_name.contains(author) || commit.author_email.contains(author)
-            });
-            if !author_match {
-                return false;
-            }
-        }
-
-        // Apply author exclusion filters
-        if !self.query_params.authors.exclude.is_empty() {
-            let author_excluded = self.query_params.authors.exclude.iter().any(|author| {
-                commit.author_name.contains(author) || commit.author_email.contains(author)
-            });
-            if author_excluded {
-                return false;
-            }
-        }
+        // Apply author include and exclusion filters
+        self.includes_author(&commit.author_name, &commit.author_email)
+    }
 
-        true
+    /// Check if an author passes the query's author filters
+    pub fn includes_author(&self, name: &str, email: &str) -> bool {
+        self.author_matcher.matches(name, email)
     }
 
     /// Check if a file should be included based on filters
@@ -173,27 +159,18 @@ impl EventFilter {
             }
         }
 
-        // Apply file path filters
-        if !self.query_params.file_paths.include.is_empty() {
-            let path_match = self.query_params.file_paths.include.iter().any(|pattern| {
-                file_info.relative_path.contains(pattern.to_string_lossy().as_ref())
-            });
-            if !path_match {
-                return false;
-            }
-        }
+        // Apply file path include and exclusion filters
+        self.includes_path(&file_info.relative_path)
+    }
 
-        // Apply file path exclusion filters
-        if !self.query_params.file_paths.exclude.is_empty() {
-            let path_excluded = self.query_params.file_paths.exclude.iter().any(|pattern| {
-                file_info.relative_path.contains(pattern.to_string_lossy().as_ref())
-            });
-            if path_excluded {
-                return false;
-            }
-        }
+    /// Check if a repository-relative path passes the query's path filters
+    pub fn includes_path(&self, path: &str) -> bool {
+        self.path_matcher.matches(path)
+    }
 
-        true
+    /// Factor the line changes of a repository-relative path are scaled by
+    pub fn path_weight(&self, path: &str) -> f64 {
+        self.path_matcher.weight(path)
     }
 
     /// Check if a file change should be included
@@ -208,31 +185,9 @@ impl EventFilter {
             return false;
         }
 
-        // Apply file path filters to the changed file
-        if !self.query_params.file_paths.include.is_empty() {
-            let path_match = self.query_params.file_paths.include.iter().any(|pattern| {
-                let pattern_str = pattern.to_string_lossy();
-                change.new_path.contains(pattern_str.as_ref()) || 
-                change.old_path.as_ref().is_some_and(|old| old.contains(pattern_str.as_ref()))
-            });
-            if !path_match {
-                return false;
-            }
-        }
-
-        // Apply file path exclusion filters
-        if !self.query_params.file_paths.exclude.is_empty() {
-            let path_excluded = self.query_params.file_paths.exclude.iter().any(|pattern| {
-                let pattern_str = pattern.to_string_lossy();
-                change.new_path.contains(pattern_str.as_ref()) || 
-                change.old_path.as_ref().is_some_and(|old| old.contains(pattern_str.as_ref()))
-            });
-            if path_excluded {
-                return false;
-            }
-        }
-
-        true
+        // Apply file path filters to the changed file, under its new or old name
+        self.includes_path(&change.new_path)
+            || change.old_path.as_ref().is_some_and(|old| self.includes_path(old))
     }
 }
 
@@ -473,6 +473,9 @@ mod tests {
             deletions: 0,
             is_binary: false,
             binary_size: None,
+            old_mode: None,
+            new_mode: None,
+            removed_ranges: vec![],
         };
         
         state.apply_change_backwards(&change).unwrap();
@@ -494,6 +497,9 @@ mod tests {
             deletions: 75,
             is_binary: false,
             binary_size: None,
+            old_mode: None,
+            new_mode: None,
+            removed_ranges: vec![],
         };
         
         state.apply_change_backwards(&change).unwrap();
@@ -515,6 +521,9 @@ mod tests {
             deletions: 5,   // These lines were removed
             is_binary: false,
             binary_size: None,
+            old_mode: None,
+            new_mode: None,
+            removed_ranges: vec![],
         };
         
         state.apply_change_backwards(&change).unwrap();
@@ -536,6 +545,9 @@ mod tests {
             deletions: 2,
             is_binary: false,
             binary_size: None,
+            old_mode: None,
+            new_mode: None,
+            removed_ranges: vec![],
         };
         
         state.apply_change_backwards(&change).unwrap();
@@ -0,0 +1,147 @@
+//! Empty Scan Diagnosis
+//!
+//! Explains why a scan produced nothing to report. The history is walked again
+//! counting how many commits survive each query filter in turn (date range,
+//! authors, paths), so the first filter that removed every remaining commit can
+//! be named together with the numbers that led there.
+
+use crate::scanner::query::QueryParams;
+
+/// Commits and file changes emitted by a scan after filtering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanMatchCounts {
+    pub commits: usize,
+    pub file_changes: usize,
+}
+
+impl ScanMatchCounts {
+    /// Check whether the scan left nothing to report for this query
+    ///
+    /// Commits without any file changes only count as empty when path filters
+    /// are active, since otherwise the commits themselves are the data.
+    pub fn is_empty(&self, query_params: &QueryParams) -> bool {
+        self.commits == 0 || (self.file_changes == 0 && query_params.has_path_filter())
+    }
+}
+
+/// Number of commits remaining after each filter is applied in turn
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterFunnel {
+    /// Commits reachable from the scanned branch
+    pub total: usize,
+    /// Commits within the date range
+    pub in_date_range: usize,
+    /// Commits within the date range that pass the author filters
+    pub matching_authors: usize,
+    /// Commits above with at least one file change passing the path filters
+    pub matching_paths: usize,
+}
+
+impl FilterFunnel {
+    /// Describe the filter that eliminated every commit, if any did
+    pub fn explain(&self, query_params: &QueryParams) -> Option<String> {
+        let branch = query_params.branch.as_deref()
+            .map(|branch| format!("branch '{branch}'"))
+            .unwrap_or_else(|| "the scanned branch".to_string());
+
+        if self.total == 0 {
+            return Some(format!("No commits found on {branch}"));
+        }
+
+        if self.in_date_range == 0 {
+            let range = query_params.date_range.as_ref()
+                .map(|range| range.describe())
+                .unwrap_or_else(|| "all history".to_string());
+            return Some(format!(
+                "No commits match the date range ({range}) set by --since/--until: all {} commits on {branch} fall outside it",
+                self.total
+            ));
+        }
+
+        if self.matching_authors == 0 {
+            let mut filters = Vec::new();
+            if !query_params.authors.include.is_empty() {
+                filters.push(format!("--author {}", query_params.authors.include.join(", ")));
+            }
+            if !query_params.authors.exclude.is_empty() {
+                filters.push(format!("--exclude-author {}", query_params.authors.exclude.join(", ")));
+            }
+            return Some(format!(
+                "No commits match the author filters ({}): {} commits in the date range were all excluded",
+                filters.join("; "),
+                self.in_date_range
+            ));
+        }
+
+        if self.matching_paths == 0 {
+            let mut filters = Vec::new();
+            if !query_params.file_paths.include.is_empty() {
+                filters.push(format!("--include-path {}", join_paths(&query_params.file_paths.include)));
+            }
+            if !query_params.file_paths.exclude.is_empty() {
+                filters.push(format!("--exclude-path {}", join_paths(&query_params.file_paths.exclude)));
+            }
+            return Some(format!(
+                "No file changes match the path filters ({}): none of the {} matching commits change a file that passes them",
+                filters.join("; "),
+                self.matching_authors
+            ));
+        }
+
+        None
+    }
+}
+
+fn join_paths(paths: &[std::path::PathBuf]) -> String {
+    paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::query::{AuthorFilter, DateRange, FilePathFilter};
+    use std::path::PathBuf;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn funnel(total: usize, in_date_range: usize, matching_authors: usize, matching_paths: usize) -> FilterFunnel {
+        FilterFunnel { total, in_date_range, matching_authors, matching_paths }
+    }
+
+    #[test]
+    fn test_explain_names_eliminating_filter() {
+        let query = QueryParams {
+            date_range: Some(DateRange::from(UNIX_EPOCH + Duration::from_secs(1_893_456_000))),
+            authors: AuthorFilter { include: vec!["alcie".to_string()], exclude: Vec::new() },
+            file_paths: FilePathFilter { include: vec![PathBuf::from("srcs/")], exclude: Vec::new(), weights: Vec::new() },
+            ..Default::default()
+        };
+
+        let date = funnel(12, 0, 0, 0).explain(&query).unwrap();
+        assert!(date.contains("--since/--until"));
+        assert!(date.contains("since 2030-01-01"));
+        assert!(date.contains("all 12 commits"));
+
+        let author = funnel(12, 5, 0, 0).explain(&query).unwrap();
+        assert!(author.contains("--author alcie"));
+        assert!(author.contains("5 commits in the date range"));
+
+        let path = funnel(12, 5, 3, 0).explain(&query).unwrap();
+        assert!(path.contains("--include-path srcs/"));
+
+        assert_eq!(funnel(12, 5, 3, 1).explain(&query), None);
+        assert!(funnel(0, 0, 0, 0).explain(&QueryParams::default()).unwrap().contains("No commits found"));
+    }
+
+    #[test]
+    fn test_match_counts_is_empty() {
+        let path_query = QueryParams {
+            file_paths: FilePathFilter { include: vec![PathBuf::from("src/")], exclude: Vec::new(), weights: Vec::new() },
+            ..Default::default()
+        };
+
+        assert!(ScanMatchCounts::default().is_empty(&QueryParams::default()));
+        assert!(!ScanMatchCounts { commits: 2, file_changes: 0 }.is_empty(&QueryParams::default()));
+        assert!(ScanMatchCounts { commits: 2, file_changes: 0 }.is_empty(&path_query));
+        assert!(!ScanMatchCounts { commits: 2, file_changes: 4 }.is_empty(&path_query));
+    }
+}
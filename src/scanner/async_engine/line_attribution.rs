@@ -0,0 +1,194 @@
+//! Line Attribution Module
+//!
+//! Attributes lines removed or rewritten by a commit to the authors who last
+//! touched them, using `git blame` on the parent revision. Only the removed line
+//! ranges reported by the diff analyzer are blamed, so the cost scales with the
+//! size of the change rather than the size of the file.
+
+use std::collections::HashMap;
+use std::path::Path;
+use crate::scanner::async_engine::error::{ObjectContext, ScanError, ScanPhase};
+use crate::scanner::identity::IdentityResolver;
+use crate::scanner::imports::ImportedCode;
+use crate::scanner::messages::LineAttribution;
+
+/// Blame the given line ranges of `path` at `revision`
+///
+/// # Arguments
+/// * `work_dir` - Repository directory to run git in
+/// * `revision` - Revision containing the lines (the parent of the changing commit)
+/// * `path` - File path at that revision
+/// * `ranges` - `(first line, line count)` ranges to attribute
+/// * `imports` - Imported code, whose lines are attributed to the import author
+/// * `identities` - Canonical identities that blamed authors are reported under
+///
+/// # Returns
+/// Line counts per author, largest first
+pub fn attribute_lines(
+    work_dir: &Path,
+    revision: &str,
+    path: &str,
+    ranges: &[(usize, usize)],
+    imports: Option<&ImportedCode>,
+    identities: Option<&IdentityResolver>,
+) -> Result<Vec<LineAttribution>, ScanError> {
+    if ranges.is_empty() {
+        return Ok(Vec::new());
+    }
+    if let Some(imports) = imports.filter(|imports| imports.matches_path(path)) {
+        let lines = ranges.iter().map(|(_, count)| count).sum();
+        return Ok(vec![LineAttribution { author: imports.author().to_string(), lines }]);
+    }
+
+    let mut command = std::process::Command::new("git");
+    command.arg("-C").arg(work_dir).arg("blame").arg("--porcelain");
+    for (start, count) in ranges {
+        command.arg("-L").arg(format!("{},{}", start, start + count - 1));
+    }
+    command.arg(revision).arg("--").arg(path);
+
+    let context = || ObjectContext::new(ScanPhase::Blame).commit(revision).path(path);
+    let output = command.output()
+        .map_err(|e| context().error(format!("Failed to run git blame: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(context().error(format!("git blame failed ({}): {}", output.status, stderr.trim())));
+    }
+
+    Ok(parse_blame_porcelain_with(&String::from_utf8_lossy(&output.stdout), imports, identities))
+}
+
+/// Count blamed lines per author from `git blame --porcelain` output
+///
+/// Porcelain output starts each line with a `<hash> <orig> <final> [<count>]`
+/// header; commit details such as `author` follow only the first time a commit
+/// appears, and the line content itself is prefixed with a tab.
+pub fn parse_blame_porcelain(output: &str) -> Vec<LineAttribution> {
+    parse_blame_porcelain_with(output, None, None)
+}
+
+/// Count blamed lines per author, crediting lines from imported commits to the import author
+/// and reporting other authors under their canonical identities
+pub fn parse_blame_porcelain_with(
+    output: &str,
+    imports: Option<&ImportedCode>,
+    identities: Option<&IdentityResolver>,
+) -> Vec<LineAttribution> {
+    let mut authors: HashMap<&str, &str> = HashMap::new();
+    let mut emails: HashMap<&str, &str> = HashMap::new();
+    let mut line_counts: HashMap<&str, usize> = HashMap::new();
+    let mut current: Option<&str> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some(hash) = current {
+                *line_counts.entry(hash).or_insert(0) += 1;
+            }
+        } else if let Some(author) = line.strip_prefix("author ") {
+            if let Some(hash) = current {
+                authors.insert(hash, author);
+            }
+        } else if let Some(email) = line.strip_prefix("author-mail ") {
+            if let Some(hash) = current {
+                emails.insert(hash, email.trim_start_matches('<').trim_end_matches('>'));
+            }
+        } else if let Some(hash) = line.split(' ').next().filter(|h| is_commit_hash(h)) {
+            current = Some(hash);
+        }
+    }
+
+    let mut by_author: HashMap<String, usize> = HashMap::new();
+    for (hash, lines) in line_counts {
+        let author = match (imports, identities, authors.get(hash)) {
+            (Some(imports), _, _) if imports.contains_commit(hash) => imports.author().to_string(),
+            (_, Some(identities), Some(author)) => identities.resolve(author, emails.get(hash).copied().unwrap_or_default()).0,
+            (_, _, author) => author.copied().unwrap_or("Unknown").to_string(),
+        };
+        *by_author.entry(author).or_insert(0) += lines;
+    }
+
+    let mut attributions: Vec<LineAttribution> = by_author
+        .into_iter()
+        .map(|(author, lines)| LineAttribution { author, lines })
+        .collect();
+    attributions.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.author.cmp(&b.author)));
+    attributions
+}
+
+/// Check whether a token is a full SHA-1 or SHA-256 commit hash
+fn is_commit_hash(token: &str) -> bool {
+    matches!(token.len(), 40 | 64) && token.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "1111111111111111111111111111111111111111";
+    const BOB: &str = "2222222222222222222222222222222222222222";
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = format!(
+            "{ALICE} 1 1 2\n\
+             author Alice\n\
+             author-mail <alice@example.com>\n\
+             summary Initial\n\
+             filename src/lib.rs\n\
+             \tfn one() {{}}\n\
+             {ALICE} 2 2\n\
+             \tfn two() {{}}\n\
+             {BOB} 7 10 1\n\
+             author Bob\n\
+             summary Tweak\n\
+             filename src/lib.rs\n\
+             \tfn three() {{}}\n"
+        );
+
+        let attributions = parse_blame_porcelain(&output);
+        assert_eq!(attributions, vec![
+            LineAttribution { author: "Alice".to_string(), lines: 2 },
+            LineAttribution { author: "Bob".to_string(), lines: 1 },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain_ignores_content_lookalikes() {
+        // Content lines are tab-prefixed, so text resembling headers is not misread
+        let output = format!("{BOB} 1 1 1\nauthor Bob\n\tauthor Mallory\n");
+
+        let attributions = parse_blame_porcelain(&output);
+        assert_eq!(attributions.len(), 1);
+        assert_eq!(attributions[0].author, "Bob");
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain_resolves_identities() {
+        let output = format!(
+            "{ALICE} 1 1 1
+author alice
+author-mail <alice@home.net>
+	one
+             {BOB} 2 2 1
+author Alice
+author-mail <alice@example.com>
+	two
+"
+        );
+        let rules = crate::scanner::identity::IdentityRules {
+            aliases: vec![("Alice <alice@example.com>".to_string(), vec!["alice@home.net".to_string()])],
+            use_mailmap: false,
+        };
+        let identities = rules.resolve(None).unwrap();
+
+        let attributions = parse_blame_porcelain_with(&output, None, Some(&identities));
+        assert_eq!(attributions, vec![LineAttribution { author: "Alice".to_string(), lines: 2 }]);
+    }
+
+    #[test]
+    fn test_attribute_lines_without_ranges() {
+        let result = attribute_lines(Path::new("."), "HEAD", "Cargo.toml", &[], None, None).unwrap();
+        assert!(result.is_empty());
+    }
+}
@@ -12,6 +12,10 @@ pub mod shared_state;
 pub mod diff_analyzer;
 pub mod file_tracker;
 pub mod checkout_manager;
+pub mod content_cache;
+pub mod line_attribution;
+pub mod filter_diagnosis;
+pub mod progress;
 
 #[cfg(test)]
 mod tests;
@@ -62,6 +62,12 @@ impl HistoryEventProcessor {
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs() as i64,
+            committer: commit.author_name.clone(),
+            author_timestamp: commit.timestamp
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64,
+            parents: vec![],
             changed_files: vec![], // We'll populate this when we have the data structure
         };
 
@@ -203,8 +203,10 @@ impl EventProcessingCoordinator {
         
         let messages = self.registry.process_event(event).await?;
         
+        let elapsed = start_time.elapsed();
         self.total_events_processed += 1;
-        self.total_processing_time += start_time.elapsed();
+        self.total_processing_time += elapsed;
+        crate::profiling::record("processor.event", start_time, elapsed);
         
         Ok(messages)
     }
@@ -212,6 +214,7 @@ impl EventProcessingCoordinator {
     /// Finalize all processors
     pub async fn finalize(&mut self) -> PluginResult<Vec<ScanMessage>> {
         info!("Finalizing {} processors", self.registry.processor_count());
+        let _stage = crate::profiling::stage("processor.finalize");
         self.registry.finalize_all().await
     }
 
@@ -0,0 +1,23 @@
+//! Scan Progress Reporting
+//!
+//! The scanner walks the whole history before any message reaches a plugin,
+//! which on a large repository can take minutes. A [`ProgressReporter`] is told
+//! how much work the scan has ahead of it, through
+//! [`RepositoryEvent::RepositoryStarted`], and is then notified as each commit
+//! and file is scanned, so that a front end can show how far along the scan is.
+
+use super::events::RepositoryEvent;
+
+/// Receives progress from the scanner as it walks a repository
+///
+/// Reporters are called from the scanner's blocking task and must return quickly.
+pub trait ProgressReporter: Send + Sync {
+    /// A scan started (`RepositoryStarted`, with the estimated totals) or completed (`RepositoryCompleted`)
+    fn repository_event(&self, event: &RepositoryEvent);
+
+    /// One more commit has been scanned
+    fn commit_scanned(&self);
+
+    /// One more file has been read from the target tree
+    fn file_scanned(&self);
+}
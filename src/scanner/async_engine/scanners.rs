@@ -26,23 +26,40 @@
 //! ├── get_commit_file_changes()     // Git diff analysis with conditional checkout
 //! │   ├── FileTracker               // Backwards file state tracking
 //! │   └── CheckoutManager           // Conditional file content access
+//! ├── current_file_messages()       // Text file content of the target tree, on request
 //! └── Message builders              // Clean message construction
 //! ```
 
-use crate::scanner::query::QueryParams;
-use crate::scanner::messages::{ScanMessage, MessageHeader, MessageData, FileChangeData};
+use crate::cache::{CachedCommit, ScanCache, ScanCacheEntry};
+use crate::scanner::query::{DateRange, QueryParams};
+use crate::scanner::messages::{ScanMessage, MessageHeader, MessageData, FileChangeData, LineAttribution};
 use crate::scanner::branch_detection::BranchDetection;
-use super::error::{ScanError, ScanResult};
+use crate::scanner::encoding::ContentEncoding;
+use crate::scanner::file_policy::{FilePolicy, FilePolicyCounts};
+use crate::scanner::fingerprints::{self, FingerprintConfig};
+use crate::scanner::generated;
+use crate::scanner::identity::{IdentityResolver, IdentityRules};
+use crate::scanner::imports::{ImportRules, ImportedCode};
+use crate::scanner::patch::{read_patch_series, PatchCommit};
+use crate::scanner::pending::PendingChanges;
+use crate::scanner::repository::{command_dir, open_repository};
+use crate::shutdown::ShutdownController;
+use super::error::{ObjectContext, ScanError, ScanPhase, ScanResult};
 use futures::stream::BoxStream;
 
 /// Type alias for scan message streams
 pub type ScanMessageStream = BoxStream<'static, ScanResult<crate::scanner::messages::ScanMessage>>;
-use super::events::{EventFilter, CommitInfo, FileInfo, ChangeType};
-use super::diff_analyzer::DiffLineAnalyzer;
+use super::events::{EventFilter, CommitInfo, FileInfo, ChangeType, RepositoryEvent, RepositoryStats};
+use super::progress::ProgressReporter;
+use super::diff_analyzer::{DiffLineAnalyzer, RenameChain};
+use super::line_attribution::attribute_lines;
+use super::filter_diagnosis::{FilterFunnel, ScanMatchCounts};
 use super::checkout_manager::CheckoutManager;
+use super::content_cache::{ContentCache, ContentCacheStats};
 use crate::scanner::config::RuntimeScannerConfig;
-use log::debug;
+use log::{debug, info, warn};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::{UNIX_EPOCH, Duration, SystemTime};
 
 /// Builder for creating CommitInfo messages (GS-76 Phase 1.2)
@@ -52,6 +69,9 @@ pub struct CommitMessageBuilder {
     author: Option<String>,
     message: Option<String>,
     timestamp: Option<i64>,
+    committer: Option<String>,
+    author_timestamp: Option<i64>,
+    parents: Vec<String>,
     changed_files: Vec<FileChangeData>,
 }
 
@@ -63,6 +83,9 @@ impl CommitMessageBuilder {
             author: None,
             message: None,
             timestamp: None,
+            committer: None,
+            author_timestamp: None,
+            parents: Vec::new(),
             changed_files: Vec::new(),
         }
     }
@@ -91,6 +114,24 @@ impl CommitMessageBuilder {
         self
     }
     
+    /// Set the committer, when it differs from the author
+    pub fn committer(mut self, committer: String) -> Self {
+        self.committer = Some(committer);
+        self
+    }
+    
+    /// Set the author timestamp, when it differs from the commit timestamp
+    pub fn author_timestamp(mut self, timestamp: i64) -> Self {
+        self.author_timestamp = Some(timestamp);
+        self
+    }
+    
+    /// Set the parent commit hashes, first parent first
+    pub fn parents(mut self, parents: Vec<String>) -> Self {
+        self.parents = parents;
+        self
+    }
+    
     /// Add changed files
     pub fn changed_files(mut self, files: Vec<FileChangeData>) -> Self {
         self.changed_files = files;
@@ -106,9 +147,12 @@ impl CommitMessageBuilder {
         
         Ok(MessageData::CommitInfo {
             hash,
+            committer: self.committer.unwrap_or_else(|| author.clone()),
+            author_timestamp: self.author_timestamp.unwrap_or(timestamp),
             author,
             message,
             timestamp,
+            parents: self.parents,
             changed_files: self.changed_files,
         })
     }
@@ -128,6 +172,9 @@ pub struct FileChangeMessageBuilder {
     commit_hash: Option<String>,
     commit_timestamp: Option<i64>,
     checkout_path: Option<std::path::PathBuf>,
+    old_mode: Option<u32>,
+    new_mode: Option<u32>,
+    replaced_lines: Vec<LineAttribution>,
 }
 
 impl FileChangeMessageBuilder {
@@ -145,6 +192,9 @@ impl FileChangeMessageBuilder {
             commit_hash: None,
             commit_timestamp: None,
             checkout_path: None,
+            old_mode: None,
+            new_mode: None,
+            replaced_lines: Vec::new(),
         }
     }
     
@@ -184,7 +234,7 @@ impl FileChangeMessageBuilder {
         self
     }
     
-    /// Set binary file size
+    /// Set the size in bytes of the file's blob after the change
     pub fn binary_size(mut self, size: Option<u64>) -> Self {
         self.binary_size = size;
         self
@@ -202,6 +252,19 @@ impl FileChangeMessageBuilder {
         self
     }
     
+    /// Set the file modes before and after the change
+    pub fn modes(mut self, old_mode: Option<u32>, new_mode: Option<u32>) -> Self {
+        self.old_mode = old_mode;
+        self.new_mode = new_mode;
+        self
+    }
+    
+    /// Set the authors of the lines this change removed or rewrote
+    pub fn replaced_lines(mut self, replaced_lines: Vec<LineAttribution>) -> Self {
+        self.replaced_lines = replaced_lines;
+        self
+    }
+    
     /// Set commit context
     pub fn commit_context(mut self, commit_hash: String, commit_timestamp: i64) -> Self {
         self.commit_hash = Some(commit_hash);
@@ -226,11 +289,14 @@ impl FileChangeMessageBuilder {
             insertions,
             deletions,
             is_binary,
-            binary_size: self.binary_size, // Set by smart diff analysis
+            binary_size: self.binary_size, // Blob size from the commit's tree
             line_count: self.line_count, // Set by file state tracking
             commit_hash,
             commit_timestamp,
             checkout_path: self.checkout_path, // Set by CheckoutManager when needed
+            old_mode: self.old_mode,
+            new_mode: self.new_mode,
+            replaced_lines: self.replaced_lines,
         })
     }
 }
@@ -244,6 +310,24 @@ pub struct EventDrivenScanner {
     query_params: QueryParams,
     name: String,
     event_filter: EventFilter,
+    cache: Option<ScanCache>,
+    line_attribution: bool,
+    follow_renames: bool,
+    imports: ImportRules,
+    identities: IdentityRules,
+    current_file_content: bool,
+    content_fingerprints: Option<FingerprintConfig>,
+    content_encoding: ContentEncoding,
+    required_files: Vec<String>,
+    patch_series: bool,
+    pending: Option<PendingChanges>,
+    shutdown: ShutdownController,
+    progress: Option<Arc<dyn ProgressReporter>>,
+    repository: Option<String>,
+    match_counts: Arc<Mutex<ScanMatchCounts>>,
+    file_policies: Vec<(String, FilePolicy)>,
+    file_policy_counts: Arc<Mutex<Vec<FilePolicyCounts>>>,
+    content_stats: Arc<Mutex<ContentCacheStats>>,
 }
 
 impl EventDrivenScanner {
@@ -254,6 +338,24 @@ impl EventDrivenScanner {
             query_params,
             name: "EventDrivenScanner".to_string(),
             event_filter,
+            cache: None,
+            line_attribution: false,
+            follow_renames: true,
+            imports: ImportRules::default(),
+            identities: IdentityRules::default(),
+            current_file_content: false,
+            content_fingerprints: None,
+            content_encoding: ContentEncoding::default(),
+            required_files: Vec::new(),
+            patch_series: false,
+            pending: None,
+            shutdown: ShutdownController::default(),
+            progress: None,
+            repository: None,
+            match_counts: Arc::new(Mutex::new(ScanMatchCounts::default())),
+            file_policies: Vec::new(),
+            file_policy_counts: Arc::new(Mutex::new(Vec::new())),
+            content_stats: Arc::new(Mutex::new(ContentCacheStats::default())),
         }
     }
     
@@ -264,8 +366,141 @@ impl EventDrivenScanner {
             query_params,
             name,
             event_filter,
+            cache: None,
+            line_attribution: false,
+            follow_renames: true,
+            imports: ImportRules::default(),
+            identities: IdentityRules::default(),
+            current_file_content: false,
+            content_fingerprints: None,
+            content_encoding: ContentEncoding::default(),
+            required_files: Vec::new(),
+            patch_series: false,
+            pending: None,
+            shutdown: ShutdownController::default(),
+            progress: None,
+            repository: None,
+            match_counts: Arc::new(Mutex::new(ScanMatchCounts::default())),
+            file_policies: Vec::new(),
+            file_policy_counts: Arc::new(Mutex::new(Vec::new())),
+            content_stats: Arc::new(Mutex::new(ContentCacheStats::default())),
         }
     }
+    
+    /// Use an incremental scan cache so repeated scans only process new commits
+    pub fn with_cache(mut self, cache: ScanCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    
+    /// Attribute removed lines to their previous authors (runs `git blame` per changed file)
+    pub fn with_line_attribution(mut self, enabled: bool) -> Self {
+        self.line_attribution = enabled;
+        self
+    }
+    
+    /// Detect renames and report earlier changes under each file's current name
+    pub fn with_follow_renames(mut self, enabled: bool) -> Self {
+        self.follow_renames = enabled;
+        self
+    }
+    
+    /// Report commits matching the import rules under the import author
+    pub fn with_imports(mut self, imports: ImportRules) -> Self {
+        self.imports = imports;
+        self
+    }
+    
+    /// Report commit authors under their canonical identities from `.mailmap` and configured aliases
+    pub fn with_identities(mut self, identities: IdentityRules) -> Self {
+        self.identities = identities;
+        self
+    }
+    
+    /// Report changes to binary files, which are left out by default
+    pub fn with_binary_files(mut self, enabled: bool) -> Self {
+        self.event_filter.include_binary_files = enabled;
+        self
+    }
+    
+    /// Stream the content of text files in the target tree after the commit history
+    pub fn with_current_file_content(mut self, enabled: bool) -> Self {
+        self.current_file_content = enabled;
+        self
+    }
+    
+    /// Stream fingerprints of the content of text files in the target tree after the commit history,
+    /// for duplicate detection, whether or not the content itself is streamed
+    pub fn with_content_fingerprints(mut self, config: Option<FingerprintConfig>) -> Self {
+        self.content_fingerprints = config;
+        self
+    }
+    
+    /// Decode file content that is not UTF-8 with this encoding, or detect it for each file
+    pub fn with_content_encoding(mut self, encoding: ContentEncoding) -> Self {
+        self.content_encoding = encoding;
+        self
+    }
+    
+    /// Stream the content of these files in the target tree after the commit history, even when path filters exclude them
+    pub fn with_required_files(mut self, paths: Vec<String>) -> Self {
+        self.required_files = paths;
+        self
+    }
+    
+    /// Read the scanned path as a patch series (an mbox or a directory of patches) instead of a repository
+    pub fn with_patch_series(mut self, enabled: bool) -> Self {
+        self.patch_series = enabled;
+        self
+    }
+    
+    /// Scan the uncommitted changes of the repository as one pending commit instead of its history
+    pub fn with_pending_changes(mut self, pending: Option<PendingChanges>) -> Self {
+        self.pending = pending;
+        self
+    }
+    
+    /// Stop reading history once the controller requests a shutdown
+    pub fn with_shutdown(mut self, shutdown: ShutdownController) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+    
+    /// Report the progress of repository scans
+    pub fn with_progress(mut self, reporter: Arc<dyn ProgressReporter>) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+    
+    /// Tag every message with the identifier of the repository it came from
+    pub fn with_repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = Some(repository.into());
+        self
+    }
+    
+    /// Count the files each consumer's policy leaves out or truncates, see [`FilePolicy`]
+    pub fn with_file_policies(mut self, policies: Vec<(String, FilePolicy)>) -> Self {
+        self.file_policies = policies;
+        self
+    }
+    
+    /// Files each consumer's policy left out or truncated in the most recent scan, as warnings
+    pub fn file_policy_warnings(&self) -> Vec<String> {
+        let counts = self.file_policy_counts.lock().unwrap_or_else(|e| e.into_inner());
+        self.file_policies.iter().zip(counts.iter())
+            .filter_map(|((consumer, policy), counts)| counts.warning(consumer, policy))
+            .collect()
+    }
+    
+    /// Commits and file changes that passed the filters in the most recent scan
+    pub fn match_counts(&self) -> ScanMatchCounts {
+        *self.match_counts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// Blob reads and cache hits of the file content streamed by the most recent scan
+    pub fn content_cache_stats(&self) -> ContentCacheStats {
+        *self.content_stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 // ===== GS-76 Phase 2.1: Helper Functions to Reduce Complexity =====
@@ -277,38 +512,55 @@ struct CommitMetadata {
     short_hash: String,
     author_name: String,
     author_email: String,
+    committer_name: String,
+    committer_email: String,
     message: String,
     timestamp_seconds: i64,
     timestamp: SystemTime,
+    author_seconds: i64,
+    /// Parent commit hashes, first parent first
+    parents: Vec<String>,
 }
 
 /// Extract commit metadata from gix commit (GS-76 Phase 2.1)
 fn extract_commit_metadata(commit: &gix::Commit) -> Result<CommitMetadata, ScanError> {
     let hash = commit.id().to_string();
     let short_hash = hash.chars().take(8).collect();
+    let context = || ObjectContext::new(ScanPhase::CommitMetadata).commit(&hash);
     
     let commit_message = commit.message()
-        .map_err(|e| ScanError::Repository(format!("Failed to get commit message: {e}")))?
+        .map_err(|e| context().error(format!("Failed to get commit message: {e}")))?
         .title.to_string();
         
     let author_info = commit.author()
-        .map_err(|e| ScanError::Repository(format!("Failed to get commit author: {e}")))?;
+        .map_err(|e| context().error(format!("Failed to get commit author: {e}")))?;
     let author_name = author_info.name.to_string();
     let author_email = author_info.email.to_string();
+    let author_seconds = author_info.seconds();
+    
+    let committer_info = commit.committer()
+        .map_err(|e| context().error(format!("Failed to get commit committer: {e}")))?;
+    let committer_name = committer_info.name.to_string();
+    let committer_email = committer_info.email.to_string();
     
     let timestamp_seconds = commit.time()
-        .map_err(|e| ScanError::Repository(format!("Failed to get commit time: {e}")))?
+        .map_err(|e| context().error(format!("Failed to get commit time: {e}")))?
         .seconds;
     let timestamp = UNIX_EPOCH + Duration::from_secs(timestamp_seconds as u64);
+    let parents = commit.parent_ids().map(|id| id.to_string()).collect();
     
     Ok(CommitMetadata {
         hash,
         short_hash,
         author_name,
         author_email,
+        committer_name,
+        committer_email,
         message: commit_message,
         timestamp_seconds,
         timestamp,
+        author_seconds,
+        parents,
     })
 }
 
@@ -321,6 +573,22 @@ struct FileChange {
     insertions: usize,
     deletions: usize,
     is_binary: bool,
+    old_mode: Option<u32>,
+    new_mode: Option<u32>,
+    /// Size in bytes of the file's blob after the change; `None` for deletions or when not known
+    size: Option<u64>,
+    /// Removed line ranges in the parent revision, as `(first line, line count)`
+    removed_ranges: Vec<(usize, usize)>,
+}
+
+/// First parent of a commit, unless the commit is a root or its parent was not
+/// fetched; shallow clones stop history at commits whose parents are missing,
+/// and those are treated as roots the way git itself does
+fn fetched_parent(repo: &gix::Repository, commit: &gix::Commit) -> Option<gix::ObjectId> {
+    commit.parent_ids()
+        .next()
+        .map(|id| id.detach())
+        .filter(|id| repo.has_object(id))
 }
 
 /// Get real file changes for a commit using git diff (GS-76 Phase 2.1)
@@ -331,28 +599,38 @@ fn get_commit_file_changes(
     commit: &gix::Commit,
     checkout_manager: Option<&mut CheckoutManager>,
     runtime_config: Option<&RuntimeScannerConfig>,
+    find_renames: bool,
 ) -> Result<Vec<FileChange>, ScanError> {
-    // Handle initial commit (no parent)
-    if commit.parent_ids().next().is_none() {
+    // Handle initial commit (no parent), or the boundary commit of a shallow clone
+    let Some(parent_id) = fetched_parent(repo, commit) else {
         // For initial commits, all files are "Added"
+        let tree_context = || ObjectContext::new(ScanPhase::TreeRead).commit(commit.id);
         let tree = commit.tree()
-            .map_err(|e| ScanError::Repository(format!("Failed to get initial commit tree: {e}")))?;
+            .map_err(|e| tree_context().error(format!("Failed to get initial commit tree: {e}")))?;
             
         let files = tree.traverse().breadthfirst.files()
-            .map_err(|e| ScanError::Repository(format!("Failed to traverse initial commit files: {e}")))?;
+            .map_err(|e| tree_context().object(tree.id).error(format!("Failed to traverse initial commit files: {e}")))?;
             
         let mut changes = Vec::new();
-        for entry in files {
+        // The traversal records directories as well as the files in them
+        for entry in files.into_iter().filter(|entry| !entry.mode.is_tree()) {
             let path = entry.filepath.to_string();
             // For initial commits, count all lines as additions
+            // An unreadable blob is reported and counted as empty rather than failing the scan
             let object_id = entry.oid;
             let blob = repo.find_object(object_id)
-                .ok()
-                .and_then(|obj| obj.try_into_blob().ok());
-            let (line_count, is_binary) = if let Some(blob) = blob {
-                count_lines_in_blob(&blob)
-            } else {
-                (0, false)
+                .map_err(|e| e.to_string())
+                .and_then(|obj| obj.try_into_blob().map_err(|e| e.to_string()));
+            let (line_count, is_binary, size) = match blob {
+                Ok(blob) => {
+                    let (line_count, is_binary) = count_lines_in_blob(&blob);
+                    (line_count, is_binary, Some(blob.data.len() as u64))
+                }
+                Err(e) => {
+                    warn!("Skipping unreadable file while {}: {}",
+                        ObjectContext::new(ScanPhase::BlobRead).commit(commit.id).object(object_id).path(path.as_str()), e);
+                    (0, false, None)
+                }
             };
             
             changes.push(FileChange {
@@ -362,42 +640,50 @@ fn get_commit_file_changes(
                 insertions: line_count,
                 deletions: 0,
                 is_binary,
+                old_mode: None,
+                new_mode: Some(entry.mode.value() as u32),
+                size,
+                removed_ranges: Vec::new(),
             });
         }
         return Ok(changes);
-    }
+    };
     
     // Handle regular commits with parents - use git diff with smart analysis
-    let parent_id = commit.parent_ids().next().unwrap();
-    
     // Get diff output using git command (gix doesn't have high-level diff text output yet)
     let commit_id = commit.id.to_string();
     let parent_id_str = parent_id.to_string();
+    let diff_context = || ObjectContext::new(ScanPhase::Diff).commit(&commit_id).parent(&parent_id_str);
     
     // Use git command to get diff output for parsing
     let diff_output = std::process::Command::new("git")
         .arg("-C")
-        .arg(repo.worktree().map(|w| w.base()).unwrap_or(repo.git_dir()))
+        .arg(command_dir(repo))
         .arg("diff")
         .arg("--no-color")
-        .arg("--no-renames") // Disable rename detection for now
+        .arg(if find_renames { "--find-renames" } else { "--no-renames" })
         .arg(&parent_id_str)
         .arg(&commit_id)
         .output()
-        .map_err(|e| ScanError::Repository(format!("Failed to run git diff: {e}")))?;
+        .map_err(|e| diff_context().error(format!("Failed to run git diff: {e}")))?;
     
     if !diff_output.status.success() {
         let stderr = String::from_utf8_lossy(&diff_output.stderr);
-        return Err(ScanError::Repository(format!("Git diff failed: {stderr}")));
+        return Err(diff_context().error(format!("git diff failed ({}): {}", diff_output.status, stderr.trim())));
     }
     
     let diff_text = String::from_utf8_lossy(&diff_output.stdout);
     
     // Use our smart diff analyzer to parse the output
-    let file_analyses = DiffLineAnalyzer::analyze_commit_diff(&diff_text)?;
+    let file_analyses = DiffLineAnalyzer::analyze_commit_diff(&diff_text)
+        .map_err(|e| diff_context().error(e))?;
     
-    // Convert to our FileChange format
+    // Convert to our FileChange format, sizing each file from the commit's tree
+    let tree = commit.tree().ok();
     let changes: Vec<FileChange> = file_analyses.into_iter().map(|analysis| {
+        let size = tree.as_ref()
+            .filter(|_| analysis.change_type != ChangeType::Deleted)
+            .and_then(|tree| blob_size(repo, tree, &analysis.path));
         FileChange {
             path: analysis.path,
             change_type: analysis.change_type,
@@ -405,6 +691,10 @@ fn get_commit_file_changes(
             insertions: analysis.insertions,
             deletions: analysis.deletions,
             is_binary: analysis.is_binary,
+            old_mode: analysis.old_mode,
+            new_mode: analysis.new_mode,
+            size,
+            removed_ranges: analysis.removed_ranges,
         }
     }).collect();
     
@@ -431,11 +721,12 @@ fn get_commit_file_changes(
                                 if let Ok(object) = entry.object() {
                                     if let Ok(blob) = object.try_into_blob() {
                                         // Checkout the file content
-                                        if let Some(_checkout_path) = checkout_manager.checkout_file(
-                                            &commit_hash,
-                                            &change.path,
-                                            &blob.data,
-                                        )? {
+                                        let checkout = checkout_manager.checkout_file(&commit_hash, &change.path, &blob.data)
+                                            .map_err(|e| ObjectContext::new(ScanPhase::Checkout)
+                                                .commit(&commit_hash)
+                                                .path(change.path.as_str())
+                                                .error(e))?;
+                                        if let Some(_checkout_path) = checkout {
                                             debug!("Checked out file: {} for commit {}", change.path, &commit_hash[..8]);
                                         }
                                     }
@@ -451,6 +742,22 @@ fn get_commit_file_changes(
     Ok(changes)
 }
 
+/// Look up a commit reached by the history walk
+fn find_commit(repo: &gix::Repository, commit_id: gix::ObjectId) -> Result<gix::Commit<'_>, ScanError> {
+    let context = || ObjectContext::new(ScanPhase::CommitMetadata).commit(commit_id);
+    repo.find_object(commit_id)
+        .map_err(|e| context().error(format!("Failed to find commit: {e}")))?
+        .try_into_commit()
+        .map_err(|e| context().error(format!("Failed to convert to commit: {e}")))
+}
+
+/// Size in bytes of the blob at `path` in a tree, read from the object header
+/// so the content is not decompressed
+fn blob_size(repo: &gix::Repository, tree: &gix::Tree, path: &str) -> Option<u64> {
+    let entry = tree.lookup_entry_by_path(path).ok().flatten()?;
+    repo.find_header(entry.object_id()).ok().map(|header| header.size())
+}
+
 /// Count lines in a git blob, detecting binary files
 fn count_lines_in_blob(blob: &gix::Blob) -> (usize, bool) {
     let data = &blob.data;
@@ -479,7 +786,16 @@ fn determine_target_commit<'a>(
 ) -> Result<gix::Commit<'a>, ScanError> {
     let branch_detection = BranchDetection::new();
     
-    if let Some(ref branch_name) = query_params.branch {
+    if let Some(scanned_ref) = &query_params.scanned_ref {
+        // Already resolved before the scan, so the reported ref is exactly the one scanned
+        let oid = gix::ObjectId::from_hex(scanned_ref.commit_id.as_bytes())
+            .map_err(|e| ScanError::Repository(format!("Invalid commit ID {}: {}", scanned_ref.commit_id, e)))?;
+        
+        repo.find_object(oid)
+            .map_err(|e| ScanError::Repository(format!("Failed to find commit {}: {}", scanned_ref.commit_id, e)))?
+            .try_into_commit()
+            .map_err(|e| ScanError::Repository(format!("Failed to convert to commit: {e}")))
+    } else if let Some(ref branch_name) = query_params.branch {
         // Use specific branch
         let commit_id = branch_detection.resolve_branch_ref(repo_path, branch_name)
             .map_err(|e| ScanError::Repository(format!("Branch '{branch_name}' not found: {e}")))?;
@@ -510,22 +826,216 @@ fn determine_target_commit<'a>(
     }
 }
 
+/// Tips the history walk starts from: the scanned branch, then with `all_branches`
+/// every other local and remote-tracking branch
+fn branch_tips(
+    repo: &gix::Repository,
+    branch: Option<&str>,
+    head_id: gix::ObjectId,
+    all_branches: bool,
+) -> Result<Vec<(Option<String>, gix::ObjectId)>, ScanError> {
+    let mut tips = vec![(branch.map(str::to_string), head_id)];
+    if all_branches {
+        let branches = BranchDetection::new().list_branches(repo)
+            .map_err(|e| ScanError::Repository(e.to_string()))?;
+        for (name, commit_id) in branches {
+            if Some(name.as_str()) == branch {
+                continue;
+            }
+            let oid = gix::ObjectId::from_hex(commit_id.as_bytes())
+                .map_err(|e| ScanError::Repository(format!("Invalid commit ID {commit_id}: {e}")))?;
+            tips.push((Some(name), oid));
+        }
+        debug!("Scanning {} branches", tips.len());
+    }
+    Ok(tips)
+}
+
+/// Configure the commit walk for the query's date range
+/// With a start date the walk is ordered newest-first and cut off at the start,
+/// so history older than the window is never traversed
+fn build_commit_walk<'repo>(
+    repo: &'repo gix::Repository,
+    head_id: gix::ObjectId,
+    query_params: &QueryParams,
+) -> gix::revision::walk::Platform<'repo> {
+    use gix::revision::walk::Sorting;
+    use gix::traverse::commit::simple::CommitTimeOrder;
+
+    let walk = repo.rev_walk([head_id]);
+    match query_params.date_range.as_ref() {
+        Some(range) => match range.start_seconds() {
+            Some(seconds) => walk.sorting(Sorting::ByCommitTimeCutoff {
+                order: CommitTimeOrder::NewestFirst,
+                seconds,
+            }),
+            None => walk.sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst)),
+        },
+        None => walk,
+    }
+}
+
+/// Check whether a commit time falls after the end of the query's date range
+/// Such commits are skipped before any diff work is done
+fn is_after_date_range(commit_time: Option<i64>, query_params: &QueryParams) -> bool {
+    match (commit_time, query_params.date_range.as_ref().and_then(|r| r.end_seconds())) {
+        (Some(time), Some(end)) => time > end,
+        _ => false,
+    }
+}
+
+/// Check whether a cached commit falls within the query's date range
+fn is_in_date_range(commit: &CachedCommit, query_params: &QueryParams) -> bool {
+    let Some(range) = &query_params.date_range else {
+        return true;
+    };
+    let timestamp = commit.messages.iter().find_map(|message| match message {
+        MessageData::CommitInfo { timestamp, .. } => Some(*timestamp),
+        _ => None,
+    });
+    timestamp.is_none_or(|timestamp| {
+        range.start_seconds().is_none_or(|start| timestamp >= start)
+            && range.end_seconds().is_none_or(|end| timestamp <= end)
+    })
+}
+
+/// Identify a repository for the scan cache by its canonical git directory
+fn repository_cache_key(repo: &gix::Repository) -> PathBuf {
+    std::fs::canonicalize(repo.git_dir()).unwrap_or_else(|_| repo.git_dir().to_path_buf())
+}
+
+/// Check that a cached entry's tip is reachable from the target commit
+/// Returns None when history was rewritten (or the tip is gone) so a full scan is performed
+fn validate_cached_tip(
+    repo: &gix::Repository,
+    head_id: gix::ObjectId,
+    entry: ScanCacheEntry,
+) -> Option<(gix::ObjectId, ScanCacheEntry)> {
+    let tip = gix::ObjectId::from_hex(entry.tip.as_bytes()).ok()?;
+    if tip == head_id {
+        return Some((tip, entry));
+    }
+    match repo.merge_base(head_id, tip) {
+        Ok(base) if base.detach() == tip => Some((tip, entry)),
+        _ => {
+            debug!("Scan cache tip {} is not an ancestor of {}; rescanning", tip, head_id);
+            None
+        }
+    }
+}
+
+/// Attribute the lines a file change removed to their authors in the parent revision
+/// Blame failures are logged and treated as having no attribution
+fn attribute_replaced_lines(
+    repo: &gix::Repository,
+    commit: &gix::Commit,
+    file_change: &FileChange,
+    imports: Option<&ImportedCode>,
+    identities: &IdentityResolver,
+) -> Vec<LineAttribution> {
+    let Some(parent_id) = fetched_parent(repo, commit) else {
+        return Vec::new();
+    };
+    if file_change.is_binary || file_change.removed_ranges.is_empty() {
+        return Vec::new();
+    }
+    
+    let path = file_change.old_path.as_deref().unwrap_or(&file_change.path);
+    match attribute_lines(command_dir(repo), &parent_id.to_string(), path, &file_change.removed_ranges, imports, Some(identities)) {
+        Ok(attributions) => attributions,
+        Err(e) => {
+            warn!("Line attribution skipped for commit {}: {}", commit.id, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Rebuild scan messages for a cached commit, numbering them as process_single_commit does
+fn replay_cached_commit(commit: &CachedCommit, scan_id: &str) -> Vec<ScanMessage> {
+    commit.messages
+        .iter()
+        .enumerate()
+        .map(|(index, data)| ScanMessage::new(MessageHeader::new(index as u64, scan_id.to_string()), data.clone()))
+        .collect()
+}
+
+/// Create FileInfo for file filtering (compatibility)
+fn file_filter_info(file_change: &FileChange, timestamp: SystemTime) -> FileInfo {
+    FileInfo {
+        path: PathBuf::from(&file_change.path),
+        relative_path: file_change.path.clone(),
+        size: 0, // Will be calculated in Phase 2.3 (no more rough estimates)
+        extension: PathBuf::from(&file_change.path).extension().map(|s| s.to_string_lossy().to_string()),
+        is_binary: file_change.is_binary,
+        line_count: None, // Will be calculated in Phase 2.3 (no more dummy values)
+        last_modified: Some(timestamp),
+    }
+}
+
 /// Process a single commit and return scan messages (GS-76 Phase 2.1)
 /// This reduces the main scan loop complexity by handling all commit processing
+#[allow(clippy::too_many_arguments)]
 fn process_single_commit(
     repo: &gix::Repository,
     commit: &gix::Commit,
     event_filter: &EventFilter,
     scan_id: &str,
+    line_attribution: bool,
+    follow_renames: bool,
+    imports: Option<&ImportedCode>,
+    identities: &IdentityResolver,
 ) -> Result<Vec<ScanMessage>, ScanError> {
-    let mut messages = Vec::new();
-    let mut message_index = 0u64; // Will be properly managed in Phase 3
+    // Extract commit metadata using helper function, under canonical identities
+    let mut metadata = extract_commit_metadata(commit)?;
+    (metadata.author_name, metadata.author_email) = identities.resolve(&metadata.author_name, &metadata.author_email);
+    (metadata.committer_name, metadata.committer_email) = identities.resolve(&metadata.committer_name, &metadata.committer_email);
     
-    // Extract commit metadata using helper function
-    let metadata = extract_commit_metadata(commit)?;
+    // Commits by filtered-out authors are dropped before diffing, unless an import could reattribute them
+    if imports.is_none() && !event_filter.includes_author(&metadata.author_name, &metadata.author_email) {
+        return Ok(Vec::new());
+    }
     
     // Get real file changes using helper function
-    let file_changes = get_commit_file_changes(repo, commit, None, None)?;
+    let file_changes = get_commit_file_changes(repo, commit, None, None, follow_renames)?;
+    
+    // Imported code is reported under the import author, before author filters apply
+    if let Some(imports) = imports {
+        let paths: Vec<String> = file_changes.iter().map(|fc| fc.path.clone()).collect();
+        if imports.is_imported_commit(&commit.id, &paths) {
+            metadata.author_name = imports.author().to_string();
+            metadata.author_email = String::new();
+        }
+    }
+    
+    let replaced_lines = |file_change: &FileChange| if line_attribution {
+        attribute_replaced_lines(repo, commit, file_change, imports, identities)
+    } else {
+        Vec::new()
+    };
+    
+    commit_messages(metadata, &file_changes, event_filter, scan_id, replaced_lines)
+}
+
+/// Build the messages for a commit and its file changes, applying the commit and file filters
+fn commit_messages(
+    metadata: CommitMetadata,
+    file_changes: &[FileChange],
+    event_filter: &EventFilter,
+    scan_id: &str,
+    mut replaced_lines: impl FnMut(&FileChange) -> Vec<LineAttribution>,
+) -> Result<Vec<ScanMessage>, ScanError> {
+    let mut messages = Vec::new();
+    let mut message_index = 0u64; // Will be properly managed in Phase 3
+    
+    // Path filters narrow the commit to the files they select, and drop it when it changes none;
+    // path weights then scale the line changes of the files they name
+    let file_changes: Vec<FileChange> = file_changes.iter()
+        .filter(|fc| event_filter.includes_path(&fc.path))
+        .map(|fc| weighted_change(fc, event_filter.path_weight(&fc.path)))
+        .collect();
+    if file_changes.is_empty() && event_filter.query_params.has_path_filter() {
+        return Ok(messages);
+    }
     
     // Convert FileChange to FileChangeData for compatibility (temporary)
     let changed_files: Vec<FileChangeData> = file_changes.iter().map(|fc| {
@@ -544,12 +1054,12 @@ fn process_single_commit(
         short_hash: metadata.short_hash.clone(),
         author_name: metadata.author_name.clone(),
         author_email: metadata.author_email.clone(),
-        committer_name: metadata.author_name.clone(), // Use author as committer for simplicity
-        committer_email: metadata.author_email.clone(),
+        committer_name: metadata.committer_name.clone(),
+        committer_email: metadata.committer_email.clone(),
         timestamp: metadata.timestamp,
         message: metadata.message.clone(),
-        parent_hashes: vec![], // TODO: Extract parent hashes if needed
-        changed_files: changed_file_paths.clone(),
+        parent_hashes: metadata.parents.clone(),
+        changed_files: changed_file_paths,
         insertions: file_changes.iter().map(|fc| fc.insertions).sum(),
         deletions: file_changes.iter().map(|fc| fc.deletions).sum(),
     };
@@ -562,6 +1072,9 @@ fn process_single_commit(
             .author(metadata.author_name)
             .message(metadata.message)
             .timestamp(metadata.timestamp_seconds)
+            .committer(metadata.committer_name)
+            .author_timestamp(metadata.author_seconds)
+            .parents(metadata.parents)
             .changed_files(changed_files)
             .build()?;
             
@@ -574,20 +1087,11 @@ fn process_single_commit(
         message_index += 1;
         
         // Process file changes for this commit
-        for (file_change, file_path) in file_changes.iter().zip(changed_file_paths.iter()) {
-            // Create FileInfo for filtering (compatibility)
-            let file_info = FileInfo {
-                path: PathBuf::from(file_path),
-                relative_path: file_path.clone(),
-                size: 0, // Will be calculated in Phase 2.3 (no more rough estimates)
-                extension: PathBuf::from(file_path).extension().map(|s| s.to_string_lossy().to_string()),
-                is_binary: file_change.is_binary,
-                line_count: None, // Will be calculated in Phase 2.3 (no more dummy values)
-                last_modified: Some(metadata.timestamp),
-            };
-            
+        for file_change in &file_changes {
             // Apply file filtering
-            if event_filter.should_include_file(&file_info) {
+            if event_filter.should_include_file(&file_filter_info(file_change, metadata.timestamp)) {
+                let replaced_lines = replaced_lines(file_change);
+                
                 // Build file change message using builder pattern (will be enhanced in Phase 3)
                 let file_change_data = FileChangeMessageBuilder::new()
                     .path(file_change.path.clone())
@@ -596,6 +1100,9 @@ fn process_single_commit(
                     .insertions(file_change.insertions)
                     .deletions(file_change.deletions)
                     .is_binary(file_change.is_binary)
+                    .binary_size(file_change.size)
+                    .modes(file_change.old_mode, file_change.new_mode)
+                    .replaced_lines(replaced_lines)
                     .commit_context(commit_info.hash.clone(), metadata.timestamp_seconds)
                     .build()?;
                     
@@ -613,6 +1120,56 @@ fn process_single_commit(
     Ok(messages)
 }
 
+/// A file change with its line counts scaled by the weight of its path
+fn weighted_change(file_change: &FileChange, weight: f64) -> FileChange {
+    let mut file_change = file_change.clone();
+    if weight < 1.0 {
+        let scale = |lines: usize| (lines as f64 * weight).round() as usize;
+        file_change.insertions = scale(file_change.insertions);
+        file_change.deletions = scale(file_change.deletions);
+    }
+    file_change
+}
+
+/// Build the messages for a commit read from a patch series
+fn patch_commit_messages(
+    commit: PatchCommit,
+    event_filter: &EventFilter,
+    identities: &IdentityResolver,
+    scan_id: &str,
+) -> Result<Vec<ScanMessage>, ScanError> {
+    let (author_name, author_email) = identities.resolve(&commit.author_name, &commit.author_email);
+    let metadata = CommitMetadata {
+        short_hash: commit.hash.chars().take(8).collect(),
+        hash: commit.hash,
+        committer_name: author_name.clone(),
+        committer_email: author_email.clone(),
+        author_name,
+        author_email,
+        message: commit.subject,
+        timestamp_seconds: commit.timestamp,
+        timestamp: UNIX_EPOCH + Duration::from_secs(commit.timestamp.max(0) as u64),
+        author_seconds: commit.timestamp,
+        // Patches do not record the commits they were made from
+        parents: Vec::new(),
+    };
+    let file_changes: Vec<FileChange> = commit.files.into_iter().map(|file| FileChange {
+        path: file.path,
+        change_type: file.change_type,
+        old_path: file.old_path,
+        insertions: file.insertions,
+        deletions: file.deletions,
+        is_binary: file.is_binary,
+        old_mode: file.old_mode,
+        new_mode: file.new_mode,
+        size: None,
+        removed_ranges: Vec::new(),
+    }).collect();
+    
+    // Removed lines cannot be attributed without the history the patches apply to
+    commit_messages(metadata, &file_changes, event_filter, scan_id, |_| Vec::new())
+}
+
 // ===== GS-76 Phase 2.2: Change Type Mapping and Git Event Detection =====
 
 /// Git event types for special commits (GS-76 Phase 2.2)
@@ -649,57 +1206,643 @@ impl EventDrivenScanner {
     pub async fn scan_async(&self, repository_path: &Path) -> ScanResult<ScanMessageStream> {
         debug!("EventDrivenScanner: Starting scan for path: {:?}", repository_path);
         
+        if self.patch_series {
+            return self.scan_patch_series(repository_path).await;
+        }
+        if let Some(pending) = self.pending {
+            return self.scan_pending_changes(repository_path, pending).await;
+        }
+        
         // Repository-owning pattern: extract Send+Sync data immediately using spawn_blocking
         let repo_path = repository_path.to_path_buf();
         let event_filter = self.event_filter.clone();
         let query_params = self.query_params.clone();
+        let line_attribution = self.line_attribution;
+        let follow_renames = self.follow_renames;
+        let import_rules = self.imports.clone();
+        let identity_rules = self.identities.clone();
+        let current_file_content = self.current_file_content;
+        let content_fingerprints = self.content_fingerprints;
+        let content_encoding = self.content_encoding;
+        let required_files = self.required_files.clone();
+        let shutdown = self.shutdown.clone();
+        let progress = self.progress.clone();
+        let content_stats = Arc::clone(&self.content_stats);
+        // Attributed and unattributed scans produce different messages, so they are cached separately,
+        // as are scans including binary files, scans with rename detection and scans that credit imported code differently
+        // A union of branches has no single tip to validate cached history against
+        let cache = self.cache.as_ref().filter(|_| !query_params.all_branches).map(|cache| {
+            let mut cache = cache.clone();
+            if line_attribution {
+                cache = cache.scoped("line-attribution");
+            }
+            if event_filter.include_binary_files {
+                cache = cache.scoped("binary-files");
+            }
+            if follow_renames {
+                cache = cache.scoped("renames");
+            }
+            if !import_rules.is_empty() {
+                cache = cache.scoped(&format!("imports-{:016x}", import_rules.fingerprint()));
+            }
+            cache
+        });
         
         // Extract all required data in spawn_blocking to ensure Send+Sync compliance
         let scan_data = tokio::task::spawn_blocking(move || -> ScanResult<Vec<ScanMessage>> {
-            let repo = gix::discover(&repo_path)
+            let repo = open_repository(&repo_path)
                 .map_err(|e| ScanError::Repository(format!("Invalid repository at {}: {}", repo_path.display(), e)))?;
             
             let mut messages = Vec::new();
+            let imports = if import_rules.is_empty() { None } else { Some(import_rules.resolve(&repo)?) };
+            let identities = identity_rules.resolve(Some(&repo))?;
+            // Authors are cached under their canonical identities, so each set of mappings has its own cache
+            let cache = cache.map(|cache| if identities.is_empty() {
+                cache
+            } else {
+                cache.scoped(&format!("identities-{:016x}", identities.fingerprint()))
+            });
 
             // GS-75: Use helper function to determine target commit with proper error handling
             let target_commit = determine_target_commit(&repo, &repo_path, &query_params)?;
             
             let head_id = target_commit.id;
+            
+            // Reuse cached history when it reaches back to the window's start
+            // and the cached tip is still an ancestor of the target commit
+            let repository = repository_cache_key(&repo);
+            let window_start = query_params.date_range.as_ref().and_then(|range| range.start_seconds());
+            let cached = cache.as_ref()
+                .and_then(|cache| cache.load(&repository, &query_params))
+                .filter(|entry| entry.covers(window_start))
+                .and_then(|entry| validate_cached_tip(&repo, head_id, entry));
+            
+            // Cached history is kept whole, back to where it starts, and the window is applied as it is read,
+            // so the same entry serves any window it covers
+            let cached_since = cached.as_ref().map_or(window_start, |(_, entry)| entry.since);
+            let walk_query = match &cache {
+                Some(_) => QueryParams {
+                    date_range: cached_since.map(|since| DateRange::from(UNIX_EPOCH + Duration::from_secs(since.max(0) as u64))),
+                    ..query_params.clone()
+                },
+                None => query_params.clone(),
+            };
+            let commit_filter = match &cache {
+                Some(_) => {
+                    let mut filter = EventFilter::from_query_params(QueryParams { date_range: None, ..query_params.clone() });
+                    filter.include_binary_files = event_filter.include_binary_files;
+                    filter.max_file_size = event_filter.max_file_size;
+                    filter
+                }
+                None => event_filter.clone(),
+            };
 
-            // GS-75: Single-phase traversal - process commits with their files together
-            let walk = repo.rev_walk([head_id]);
-            let commits = walk.all()
-                .map_err(|e| ScanError::Repository(format!("Commit walk error: {e}")))?;
+            // Messages are tagged with the branch they were read from
+            let branch = query_params.scanned_ref.as_ref().map(|scanned| scanned.branch_name.clone())
+                .or_else(|| query_params.branch.clone());
+            let tips = branch_tips(&repo, branch.as_deref(), head_id, query_params.all_branches)?;
             
-            for commit_info in commits { // Process all commits using helper function (GS-76 Phase 2.1)
-                let commit_info = commit_info
-                    .map_err(|e| ScanError::Repository(format!("Failed to get commit info: {e}")))?;
+            // GS-75: Single-phase traversal - process commits with their files together
+            // The walk is cheap next to diffing, so it is completed first to know how many commits lie ahead.
+            // Each branch's walk hides the branches walked before it, so a commit is read once,
+            // for the first branch that reaches it.
+            let mut commits: Vec<(gix::ObjectId, Option<String>, i64)> = Vec::new();
+            for (index, (tip_branch, tip)) in tips.iter().enumerate() {
+                let mut walk = build_commit_walk(&repo, *tip, &walk_query)
+                    .with_hidden(tips[..index].iter().map(|(_, hidden)| *hidden));
+                if let Some((cached_tip, _)) = &cached {
+                    walk = walk.with_hidden([*cached_tip]);
+                }
+                let walk = walk.all()
+                    .map_err(|e| ScanError::Repository(format!("Commit walk error: {e}")))?;
                 
-                let commit_id = commit_info.id;
-                let commit = repo.find_object(commit_id)
-                    .map_err(|e| ScanError::Repository(format!("Failed to find commit: {e}")))?
-                    .try_into_commit()
-                    .map_err(|e| ScanError::Repository(format!("Failed to convert to commit: {e}")))?;
+                let mut last_commit = *tip;
+                for commit_info in walk {
+                    let commit_info = commit_info
+                        .map_err(|e| ObjectContext::new(ScanPhase::Traversal).commit(last_commit)
+                            .error(format!("Failed to get commit info: {e}")))?;
+                    last_commit = commit_info.id;
+                    
+                    // Skip commits newer than the requested window without computing diffs, unless they are cached
+                    if cache.is_some() || !is_after_date_range(commit_info.commit_time, &query_params) {
+                        commits.push((commit_info.id, tip_branch.clone(), commit_info.commit_time.unwrap_or_default()));
+                    }
+                }
+            }
+            // Several branches are interleaved newest first, as a single branch's history is
+            if tips.len() > 1 {
+                for (commit_id, _, time) in &mut commits {
+                    if *time == 0 {
+                        *time = find_commit(&repo, *commit_id)?.time().map(|time| time.seconds).unwrap_or_default();
+                    }
+                }
+                commits.sort_by_key(|commit| std::cmp::Reverse(commit.2));
+            }
+            
+            let scan_start = std::time::Instant::now();
+            let total_commits = commits.len();
+            let read_content = current_file_content || content_fingerprints.is_some();
+            let total_files = if read_content { count_tree_files(&target_commit) } else { None };
+            if let Some(progress) = &progress {
+                progress.repository_event(&RepositoryEvent::RepositoryStarted {
+                    total_commits: Some(total_commits),
+                    total_files,
+                });
+            }
+            
+            let mut scanned_commits = Vec::new();
+            for (scanned, (commit_id, commit_branch, _)) in commits.into_iter().enumerate() { // Process all commits using helper function (GS-76 Phase 2.1)
+                if shutdown.is_requested() {
+                    info!("Scan stopped after {} of {} commits", scanned, total_commits);
+                    break;
+                }
+                let commit = find_commit(&repo, commit_id)?;
+                let commit_stage = crate::profiling::stage("scan.commit");
+                #[cfg(feature = "otel")]
+                let mut commit_span = crate::telemetry::span("scan.commit", vec![
+                    crate::telemetry::KeyValue::new("commit", commit_id.to_string()),
+                ]);
                 
                 // Use helper function to process the entire commit - reduces complexity
-                let commit_messages = process_single_commit(&repo, &commit, &event_filter, "default-scan")?;
+                let commit_messages = process_single_commit(
+                    &repo, &commit, &commit_filter, "default-scan", line_attribution, follow_renames, imports.as_ref(), &identities,
+                );
+                #[cfg(feature = "otel")]
+                {
+                    commit_span.record_result(&commit_messages);
+                    if let Ok(messages) = &commit_messages {
+                        commit_span.set_attribute(crate::telemetry::KeyValue::new("messages", messages.len() as i64));
+                    }
+                }
+                drop(commit_stage);
+                let mut commit_messages = commit_messages?;
+                for message in &mut commit_messages {
+                    message.header.branch = commit_branch.clone();
+                }
+                if cache.is_some() {
+                    let cached_commit = CachedCommit {
+                        hash: commit_id.to_string(),
+                        messages: commit_messages.iter().map(|m| m.data.clone()).collect(),
+                    };
+                    let in_window = is_in_date_range(&cached_commit, &query_params);
+                    scanned_commits.push(cached_commit);
+                    if !in_window {
+                        commit_messages.clear();
+                    }
+                }
                 for message in commit_messages {
                     messages.push(message);
                 }
+                if let Some(progress) = &progress {
+                    progress.commit_scanned();
+                }
             }
             
+            // An interrupted scan has gaps in its history, so it must not be cached or merged with cached history
+            let interrupted = shutdown.is_requested();
+            if let Some(cache) = cache.as_ref().filter(|_| !interrupted) {
+                debug!("Scan cache: {} new commits, {} cached commits",
+                    scanned_commits.len(), cached.as_ref().map_or(0, |(_, entry)| entry.commits.len()));
+                
+                // Merge cached history after the newly scanned commits, preserving newest-first order
+                if let Some((_, entry)) = cached {
+                    for cached_commit in entry.commits.iter().filter(|commit| is_in_date_range(commit, &query_params)) {
+                        messages.extend(replay_cached_commit(cached_commit, "default-scan"));
+                    }
+                    scanned_commits.extend(entry.commits);
+                }
+                
+                let entry = ScanCacheEntry::new(repository, head_id.to_string(), scanned_commits).with_since(cached_since);
+                if let Err(e) = cache.store(&query_params, &entry) {
+                    warn!("Failed to update scan cache: {e}");
+                }
+            }
+            
+            // Renames are followed over the whole history, including replayed commits,
+            // so the cache keeps the paths as they were at each commit
+            if follow_renames {
+                follow_rename_chain(&mut messages);
+            }
+            
+            // File content is read from the target tree on every scan, it is never cached
+            // between scans, but each blob is read only once within one
+            let mut content_cache = ContentCache::new();
+            if read_content && !interrupted {
+                let content = current_file_messages(&repo, &target_commit, &event_filter, content_encoding, "default-scan", progress.as_deref(), &mut content_cache)?;
+                messages.extend(fingerprint_content(content, content_fingerprints.as_ref(), current_file_content));
+            }
+            let required_files: Vec<&str> = required_files.iter()
+                .map(String::as_str)
+                .filter(|required| !messages.iter().any(|message| {
+                    matches!(&message.data, MessageData::FileContent { path, .. } if path == required)
+                }))
+                .collect();
+            if !required_files.is_empty() && !interrupted {
+                messages.extend(required_file_messages(&target_commit, &required_files, content_encoding, "default-scan", &mut content_cache)?);
+            }
+            let stats = content_cache.stats();
+            if stats.requests() > 0 {
+                debug!("Content cache: {} blobs read, {} hits ({} bytes shared)", stats.blobs_read, stats.hits, stats.bytes_shared);
+            }
+            *content_stats.lock().unwrap_or_else(|e| e.into_inner()) = stats;
+            
+            // Replayed history and file content come from the scanned branch
+            if let Some(branch) = &branch {
+                for message in messages.iter_mut().filter(|message| message.header.branch.is_none()) {
+                    message.header.branch = Some(branch.clone());
+                }
+            }
+            
+            if let Some(progress) = &progress {
+                progress.repository_event(&RepositoryEvent::RepositoryCompleted {
+                    stats: RepositoryStats {
+                        total_commits,
+                        total_files: total_files.unwrap_or(0),
+                        total_changes: messages.iter().filter(|m| matches!(m.data, MessageData::FileChange { .. })).count(),
+                        scan_duration: scan_start.elapsed(),
+                        events_emitted: messages.len(),
+                    },
+                });
+            }
+            
+            Ok(messages)
+        }).await
+        .map_err(|e| ScanError::Repository(format!("Spawn blocking failed: {e}")))??;
+        
+        Ok(self.message_stream(scan_data))
+    }
+    
+    /// Scan the commits of a patch series, newest first as for a repository
+    async fn scan_patch_series(&self, series_path: &Path) -> ScanResult<ScanMessageStream> {
+        let series_path = series_path.to_path_buf();
+        let event_filter = self.event_filter.clone();
+        let follow_renames = self.follow_renames;
+        let identity_rules = self.identities.clone();
+        
+        let scan_data = tokio::task::spawn_blocking(move || -> ScanResult<Vec<ScanMessage>> {
+            // A patch series has no repository, so only configured aliases apply
+            let identities = identity_rules.resolve(None)?;
+            let commits = read_patch_series(&series_path)?;
+            debug!("Read {} patches from {}", commits.len(), series_path.display());
+            
+            let mut messages = Vec::new();
+            for commit in commits.into_iter().rev() {
+                messages.extend(patch_commit_messages(commit, &event_filter, &identities, "default-scan")?);
+            }
+            if follow_renames {
+                follow_rename_chain(&mut messages);
+            }
             Ok(messages)
         }).await
         .map_err(|e| ScanError::Repository(format!("Spawn blocking failed: {e}")))??;
         
+        Ok(self.message_stream(scan_data))
+    }
+    
+    /// Scan the uncommitted changes of a repository as one pending commit
+    ///
+    /// Plugins reading file content are given the pending content of the touched files,
+    /// and files they require by path are read from the same place.
+    async fn scan_pending_changes(&self, repository_path: &Path, pending: PendingChanges) -> ScanResult<ScanMessageStream> {
+        let repo_path = repository_path.to_path_buf();
+        let event_filter = self.event_filter.clone();
+        let identity_rules = self.identities.clone();
+        let current_file_content = self.current_file_content;
+        let content_fingerprints = self.content_fingerprints;
+        let content_encoding = self.content_encoding;
+        let required_files = self.required_files.clone();
+        
+        let scan_data = tokio::task::spawn_blocking(move || -> ScanResult<Vec<ScanMessage>> {
+            let repo = open_repository(&repo_path)
+                .map_err(|e| ScanError::Repository(format!("Invalid repository at {}: {}", repo_path.display(), e)))?;
+            let identities = identity_rules.resolve(Some(&repo))?;
+            let commit = pending.read(&repo)?;
+            debug!("{}: {} files", commit.subject, commit.files.len());
+            
+            let touched: Vec<String> = commit.files.iter()
+                .filter(|file| file.change_type != ChangeType::Deleted && !file.is_binary)
+                .map(|file| file.path.clone())
+                .collect();
+            let mut messages = patch_commit_messages(commit, &event_filter, &identities, "default-scan")?;
+            if current_file_content || content_fingerprints.is_some() {
+                let mut content = Vec::new();
+                for path in touched {
+                    if let Some(data) = pending.content(&repo, &path) {
+                        content.extend(file_content_message(path, &data, &event_filter, content_encoding, "default-scan", (messages.len() + content.len()) as u64));
+                    }
+                }
+                messages.extend(fingerprint_content(content, content_fingerprints.as_ref(), current_file_content));
+            }
+            for path in required_files {
+                if messages.iter().any(|message| matches!(&message.data, MessageData::FileContent { path: streamed, .. } if *streamed == path)) {
+                    continue;
+                }
+                let Some(data) = pending.content(&repo, &path) else {
+                    continue;
+                };
+                let Some(content) = decode_content(&path, &data, content_encoding) else {
+                    continue;
+                };
+                messages.push(ScanMessage::new(
+                    MessageHeader::new(messages.len() as u64, "default-scan".to_string()),
+                    MessageData::FileContent {
+                        size: data.len() as u64,
+                        content,
+                        path,
+                        generated: false,
+                    },
+                ));
+            }
+            Ok(messages)
+        }).await
+        .map_err(|e| ScanError::Repository(format!("Spawn blocking failed: {e}")))??;
+        
+        Ok(self.message_stream(scan_data))
+    }
+    
+    /// Record the match counts of a scan and stream its messages
+    fn message_stream(&self, mut scan_data: Vec<ScanMessage>) -> ScanMessageStream {
         debug!("EventDrivenScanner: Extracted {} messages", scan_data.len());
         
+        let mut counts = ScanMatchCounts::default();
+        let mut file_counts = vec![FilePolicyCounts::default(); self.file_policies.len()];
+        for message in &mut scan_data {
+            if let Some(repository) = &self.repository {
+                message.header.repository = Some(repository.clone());
+            }
+            match message.data {
+                MessageData::CommitInfo { .. } => counts.commits += 1,
+                MessageData::FileChange { .. } => counts.file_changes += 1,
+                _ => {}
+            }
+            for ((_, policy), file_counts) in self.file_policies.iter().zip(&mut file_counts) {
+                file_counts.record(&policy.decide(message));
+            }
+        }
+        *self.match_counts.lock().unwrap_or_else(|e| e.into_inner()) = counts;
+        *self.file_policy_counts.lock().unwrap_or_else(|e| e.into_inner()) = file_counts;
+        
         // Convert to stream with correct Result type
         let stream = futures::stream::iter(scan_data.into_iter().map(Ok));
-        Ok(Box::pin(stream))
+        Box::pin(stream)
     }
 }
 
+/// Content of the text files in a commit's tree, for plugins that require current file content
+///
+/// Files are subject to the scanner's file filters: binary files, files over the
+/// size limit and excluded paths are skipped, as are symlinks and submodules.
+/// Files whose content looks generated or minified are tagged as such.
+/// Blobs found under several paths are read once, through `content_cache`.
+fn current_file_messages(
+    repo: &gix::Repository,
+    commit: &gix::Commit,
+    event_filter: &EventFilter,
+    encoding: ContentEncoding,
+    scan_id: &str,
+    progress: Option<&dyn ProgressReporter>,
+    content_cache: &mut ContentCache,
+) -> Result<Vec<ScanMessage>, ScanError> {
+    let tree_context = || ObjectContext::new(ScanPhase::TreeRead).commit(commit.id);
+    let tree = commit.tree()
+        .map_err(|e| tree_context().error(format!("Failed to get commit tree: {e}")))?;
+    let entries = tree.traverse().breadthfirst.files()
+        .map_err(|e| tree_context().object(tree.id).error(format!("Failed to traverse commit files: {e}")))?;
+    
+    let mut messages = Vec::new();
+    for entry in entries.into_iter().filter(|entry| entry.mode.is_blob()) {
+        if let Some(progress) = progress {
+            progress.file_scanned();
+        }
+        let path = entry.filepath.to_string();
+        let data = match content_cache.get_or_read(entry.oid, || repo.find_object(entry.oid).map_err(|e| e.to_string())
+            .and_then(|obj| obj.try_into_blob().map_err(|e| e.to_string()))
+            .map(|blob| blob.detach().data))
+        {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Skipping unreadable file while {}: {}",
+                    ObjectContext::new(ScanPhase::BlobRead).commit(commit.id).object(entry.oid).path(path.as_str()), e);
+                continue;
+            }
+        };
+        
+        messages.extend(file_content_message(path, &data, event_filter, encoding, scan_id, messages.len() as u64));
+    }
+    
+    debug!("Streaming content of {} files from commit {}", messages.len(), commit.id);
+    Ok(messages)
+}
+
+/// Content messages followed by the fingerprints of each file's content
+///
+/// The content messages themselves are kept only when `keep_content` is set,
+/// so fingerprinting alone does not stream content.
+fn fingerprint_content(content: Vec<ScanMessage>, config: Option<&FingerprintConfig>, keep_content: bool) -> Vec<ScanMessage> {
+    let Some(config) = config else {
+        return content;
+    };
+    let fingerprinted: Vec<ScanMessage> = content.iter()
+        .filter_map(|message| match &message.data {
+            MessageData::FileContent { path, content, generated, .. } => Some(ScanMessage::new(
+                message.header.clone(),
+                MessageData::ContentFingerprints {
+                    path: path.clone(),
+                    lines: content.lines().count(),
+                    generated: *generated,
+                    fingerprints: fingerprints::fingerprint(content, config),
+                },
+            )),
+            _ => None,
+        })
+        .collect();
+    if keep_content {
+        content.into_iter().chain(fingerprinted).collect()
+    } else {
+        fingerprinted
+    }
+}
+
+/// Content message for a text file, or None when it is binary or the filters exclude it
+fn file_content_message(
+    path: String,
+    data: &[u8],
+    event_filter: &EventFilter,
+    encoding: ContentEncoding,
+    scan_id: &str,
+    sequence: u64,
+) -> Option<ScanMessage> {
+    let size = data.len() as u64;
+    let content = decode_content(&path, data, encoding);
+    let is_binary = content.is_none();
+    let file_info = FileInfo {
+        path: PathBuf::from(&path),
+        relative_path: path.clone(),
+        size,
+        extension: Path::new(&path).extension().map(|s| s.to_string_lossy().to_string()),
+        is_binary,
+        line_count: None,
+        last_modified: None,
+    };
+    if !event_filter.should_include_file(&file_info) {
+        return None;
+    }
+    
+    // Binary files have no text content, even when their changes are streamed
+    let content = content?;
+    let generated = generated::detect(&content);
+    if let Some(reason) = generated {
+        debug!("Tagging {} as generated ({})", path, reason);
+    }
+    Some(ScanMessage::new(
+        MessageHeader::new(sequence, scan_id.to_string()),
+        MessageData::FileContent {
+            path,
+            size,
+            content,
+            generated: generated.is_some(),
+        },
+    ))
+}
+
+/// Text of a file's content, or None when it is binary
+fn decode_content(path: &str, data: &[u8], encoding: ContentEncoding) -> Option<String> {
+    let (content, decoded_from) = encoding.decode(data)?;
+    if decoded_from != encoding_rs::UTF_8 {
+        debug!("Decoding {} as {}", path, decoded_from.name());
+    }
+    Some(content.into_owned())
+}
+
+/// Content of the named text files in a commit's tree; missing and binary files are skipped
+fn required_file_messages(
+    commit: &gix::Commit,
+    paths: &[&str],
+    encoding: ContentEncoding,
+    scan_id: &str,
+    content_cache: &mut ContentCache,
+) -> Result<Vec<ScanMessage>, ScanError> {
+    let tree = commit.tree()
+        .map_err(|e| ObjectContext::new(ScanPhase::TreeRead).commit(commit.id).error(format!("Failed to get commit tree: {e}")))?;
+    
+    let mut messages = Vec::new();
+    for path in paths {
+        let Some(entry) = tree.lookup_entry_by_path(path).ok().flatten() else {
+            continue;
+        };
+        if !entry.mode().is_blob() {
+            continue;
+        }
+        let data = match content_cache.get_or_read(entry.oid().to_owned(), || entry.object().map_err(|e| e.to_string())
+            .and_then(|obj| obj.try_into_blob().map_err(|e| e.to_string()))
+            .map(|blob| blob.detach().data))
+        {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Skipping unreadable file while {}: {}",
+                    ObjectContext::new(ScanPhase::BlobRead).commit(commit.id).object(entry.oid()).path(*path), e);
+                continue;
+            }
+        };
+        
+        let Some(content) = decode_content(path, &data, encoding) else {
+            continue;
+        };
+        messages.push(ScanMessage::new(
+            MessageHeader::new(messages.len() as u64, scan_id.to_string()),
+            MessageData::FileContent {
+                path: path.to_string(),
+                size: data.len() as u64,
+                content,
+                generated: false,
+            },
+        ));
+    }
+    
+    debug!("Streaming content of {} required files from commit {}", messages.len(), commit.id);
+    Ok(messages)
+}
+
+/// Number of files in a commit's tree, the files `current_file_messages` reads
+fn count_tree_files(commit: &gix::Commit) -> Option<usize> {
+    let tree = commit.tree().ok()?;
+    let entries = tree.traverse().breadthfirst.files().ok()?;
+    Some(entries.iter().filter(|entry| entry.mode.is_blob()).count())
+}
+
+/// Report file changes under each file's current name, following renames newest first
+///
+/// Messages must be in newest-first commit order, as produced by the scan.
+fn follow_rename_chain(messages: &mut [ScanMessage]) {
+    let mut chain = RenameChain::new();
+    for message in messages {
+        match &mut message.data {
+            MessageData::CommitInfo { changed_files, .. } => {
+                for file in changed_files {
+                    let current = chain.resolve(&file.path).to_string();
+                    file.path = current;
+                }
+            }
+            MessageData::FileChange { path, change_type, old_path, .. } => {
+                let current = chain.resolve(path).to_string();
+                if let (ChangeType::Renamed, Some(old_path)) = (&*change_type, old_path.as_deref()) {
+                    chain.record_rename(old_path, &current);
+                }
+                *path = current;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Count how many commits survive each query filter in turn
+///
+/// Used to explain an empty scan. The full history of the target commit is
+/// walked; diffs are only computed when path filters are set, and then only
+/// for commits that passed the date and author filters.
+pub fn diagnose_empty_scan(
+    repository_path: &Path,
+    query_params: &QueryParams,
+    identities: &IdentityRules,
+) -> ScanResult<FilterFunnel> {
+    let repo = open_repository(repository_path)
+        .map_err(|e| ScanError::Repository(format!("Invalid repository at {}: {}", repository_path.display(), e)))?;
+    let identities = identities.resolve(Some(&repo))?;
+    let target_commit = determine_target_commit(&repo, repository_path, query_params)?;
+    let event_filter = EventFilter::from_query_params(query_params.clone());
+    
+    let mut funnel = FilterFunnel::default();
+    let commits = repo.rev_walk([target_commit.id]).all()
+        .map_err(|e| ScanError::Repository(format!("Commit walk error: {e}")))?;
+    for commit_info in commits {
+        let commit_info = commit_info
+            .map_err(|e| ObjectContext::new(ScanPhase::Traversal).error(format!("Failed to get commit info: {e}")))?;
+        let commit = find_commit(&repo, commit_info.id)?;
+        let mut metadata = extract_commit_metadata(&commit)?;
+        (metadata.author_name, metadata.author_email) = identities.resolve(&metadata.author_name, &metadata.author_email);
+        
+        funnel.total += 1;
+        if !query_params.date_range.as_ref().is_none_or(|range| range.contains(metadata.timestamp)) {
+            continue;
+        }
+        funnel.in_date_range += 1;
+        if !event_filter.includes_author(&metadata.author_name, &metadata.author_email) {
+            continue;
+        }
+        funnel.matching_authors += 1;
+        
+        if query_params.has_path_filter() {
+            let file_changes = get_commit_file_changes(&repo, &commit, None, None, true)?;
+            if !file_changes.iter().any(|fc| event_filter.should_include_file(&file_filter_info(fc, metadata.timestamp))) {
+                continue;
+            }
+        }
+        funnel.matching_paths += 1;
+    }
+    
+    Ok(funnel)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -862,7 +2005,7 @@ mod tests {
 
         let message_data = builder.build().unwrap();
         
-        if let MessageData::CommitInfo { hash, author, message, timestamp, changed_files } = message_data {
+        if let MessageData::CommitInfo { hash, author, message, timestamp, changed_files, .. } = message_data {
             assert_eq!(hash, "abc123def456");
             assert_eq!(author, "Test Author");
             assert_eq!(message, "Test commit message");
@@ -966,6 +2109,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_change_builder_with_modes() {
+        let message_data = FileChangeMessageBuilder::new()
+            .path("scripts/deploy.sh".to_string())
+            .change_type(ChangeType::Modified)
+            .insertions(0)
+            .deletions(0)
+            .is_binary(false)
+            .modes(Some(0o100644), Some(0o100755))
+            .commit_context("mode123".to_string(), 1672531200)
+            .build()
+            .unwrap();
+
+        if let MessageData::FileChange { old_mode, new_mode, .. } = message_data {
+            assert_eq!(old_mode, Some(0o100644));
+            assert_eq!(new_mode, Some(0o100755));
+        } else {
+            panic!("Expected FileChange message data");
+        }
+    }
+
     #[test]
     fn test_file_change_builder_missing_fields() {
         let builder = FileChangeMessageBuilder::new()
@@ -978,6 +2142,176 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Missing"));
     }
 
+    #[test]
+    fn test_is_after_date_range() {
+        use crate::scanner::query::DateRange;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let unbounded = QueryParams::default();
+        assert!(!is_after_date_range(Some(5000), &unbounded));
+
+        let query = QueryParams {
+            date_range: Some(DateRange::until(UNIX_EPOCH + Duration::from_secs(2000))),
+            ..Default::default()
+        };
+        assert!(!is_after_date_range(Some(1500), &query));
+        assert!(!is_after_date_range(Some(2000), &query));
+        assert!(is_after_date_range(Some(2500), &query));
+        assert!(!is_after_date_range(None, &query));
+    }
+
+    #[tokio::test]
+    async fn test_date_range_scanning_excludes_commits_outside_window() {
+        use crate::scanner::query::DateRange;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        // A window in the distant past should yield no commits at all
+        let query = QueryParams {
+            date_range: Some(DateRange::new(UNIX_EPOCH, UNIX_EPOCH + Duration::from_secs(86400))),
+            ..Default::default()
+        };
+        let scanner = EventDrivenScanner::new(query);
+
+        match scanner.scan_async(Path::new(".")).await {
+            Ok(stream) => {
+                use futures::StreamExt;
+                let messages: Vec<_> = stream.collect().await;
+                assert!(messages.is_empty(), "expected no commits in window, got {}", messages.len());
+            }
+            Err(ScanError::Repository(_)) => {
+                println!("⚠️  Expected error: Not in git repository");
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_date_only_until_includes_commits_during_that_day() {
+        use crate::cli::date_parser::parse_end_date;
+        use crate::scanner::query::DateRange;
+        use crate::test_support::TestRepo;
+        use chrono::{Local, NaiveDate, TimeZone};
+        use futures::StreamExt;
+
+        let midday = |day: u32| {
+            let time = NaiveDate::from_ymd_opt(2024, 6, day).unwrap().and_hms_opt(12, 0, 0).unwrap();
+            Local.from_local_datetime(&time).unwrap().timestamp()
+        };
+        let repo = TestRepo::builder()
+            .file("a.txt", "one\n").commit_at("the day before", midday(29))
+            .file("a.txt", "two\n").commit_at("on the day", midday(30))
+            .file("a.txt", "three\n").commit_at("the day after", midday(30) + 24 * 60 * 60)
+            .build()
+            .unwrap();
+
+        let query = QueryParams {
+            date_range: Some(DateRange::until(parse_end_date("2024-06-30").unwrap())),
+            ..Default::default()
+        };
+        let messages: Vec<_> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap().collect().await;
+        let mut subjects: Vec<String> = messages.into_iter()
+            .filter_map(|message| match message.unwrap().data {
+                MessageData::CommitInfo { message, .. } => Some(message.trim().to_string()),
+                _ => None,
+            })
+            .collect();
+        subjects.sort();
+        assert_eq!(subjects, ["on the day", "the day before"]);
+    }
+
+    #[tokio::test]
+    async fn test_change_type_detection() {
+        use crate::test_support::TestRepo;
+        use futures::StreamExt;
+
+        let repo = TestRepo::builder()
+            .file("kept.txt", "one\n")
+            .file("moved.txt", "stays the same\n")
+            .file("gone.txt", "bye\n")
+            .commit("initial")
+            .file("kept.txt", "two\n")
+            .file("added.txt", "new\n")
+            .rename("moved.txt", "renamed.txt")
+            .remove("gone.txt")
+            .commit("change everything")
+            .build()
+            .unwrap();
+
+        let head = repo.rev_parse("HEAD").unwrap();
+        let messages: Vec<_> = EventDrivenScanner::new(QueryParams::default()).scan_async(repo.path()).await.unwrap().collect().await;
+        let mut changes: Vec<(String, ChangeType, Option<String>)> = messages.into_iter()
+            .filter_map(|message| match message.unwrap().data {
+                MessageData::FileChange { path, change_type, old_path, commit_hash, .. } if commit_hash == head => {
+                    Some((path, change_type, old_path))
+                }
+                _ => None,
+            })
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(changes, [
+            ("added.txt".to_string(), ChangeType::Added, None),
+            ("gone.txt".to_string(), ChangeType::Deleted, None),
+            ("kept.txt".to_string(), ChangeType::Modified, None),
+            ("renamed.txt".to_string(), ChangeType::Renamed, Some("moved.txt".to_string())),
+        ]);
+    }
+
+    #[derive(Default)]
+    struct RecordingReporter {
+        started: Mutex<Option<(Option<usize>, Option<usize>)>>,
+        completed: Mutex<Option<RepositoryStats>>,
+        commits: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ProgressReporter for RecordingReporter {
+        fn repository_event(&self, event: &RepositoryEvent) {
+            match event {
+                RepositoryEvent::RepositoryStarted { total_commits, total_files } => {
+                    *self.started.lock().unwrap() = Some((*total_commits, *total_files));
+                }
+                RepositoryEvent::RepositoryCompleted { stats } => {
+                    *self.completed.lock().unwrap() = Some(stats.clone());
+                }
+                _ => {}
+            }
+        }
+
+        fn commit_scanned(&self) {
+            self.commits.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        fn file_scanned(&self) {}
+    }
+
+    #[tokio::test]
+    async fn test_scan_reports_progress_against_commit_total() {
+        let reporter = Arc::new(RecordingReporter::default());
+        let scanner = EventDrivenScanner::new(QueryParams::default()).with_progress(reporter.clone());
+
+        match scanner.scan_async(Path::new(".")).await {
+            Ok(stream) => {
+                use futures::StreamExt;
+                let messages: Vec<_> = stream.collect().await;
+                let commits = messages.iter()
+                    .filter(|m| matches!(m.as_ref().map(|m| &m.data), Ok(MessageData::CommitInfo { .. })))
+                    .count();
+
+                // Every walked commit is counted, including those the filters then leave out
+                let scanned = reporter.commits.load(std::sync::atomic::Ordering::Relaxed);
+                assert_eq!(*reporter.started.lock().unwrap(), Some((Some(scanned), None)));
+                assert!(commits <= scanned);
+                let stats = reporter.completed.lock().unwrap().clone().expect("scan should report completion");
+                assert_eq!(stats.total_commits, scanned);
+                assert_eq!(stats.events_emitted, messages.len());
+            }
+            Err(ScanError::Repository(_)) => {
+                println!("⚠️  Expected error: Not in git repository");
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     // ===== GS-76 Phase 1.3: Failing Tests for Refactored Architecture (RED PHASE) =====
 
     #[test]
@@ -1033,28 +2367,4 @@ mod tests {
         assert!(no_estimates_policy, "Accurate line counts test - implement in Phase 2");
     }
 
-    #[test]
-    fn test_change_type_detection() {
-        // This test now passes since change type detection is implemented
-        // Tests that ChangeType enum values are correctly mapped from git
-        
-        let change_types_implemented = true; // Now implemented in Phase 2.2
-        
-        // Test git status string mapping
-        assert_eq!(map_change_type_from_git_status("A"), ChangeType::Added);
-        assert_eq!(map_change_type_from_git_status("M"), ChangeType::Modified);
-        assert_eq!(map_change_type_from_git_status("D"), ChangeType::Deleted);
-        assert_eq!(map_change_type_from_git_status("R"), ChangeType::Renamed);
-        assert_eq!(map_change_type_from_git_status("C"), ChangeType::Copied);
-        assert_eq!(map_change_type_from_git_status("X"), ChangeType::Modified); // Unknown defaults to Modified
-        
-        // Test boolean flag mapping  
-        assert_eq!(map_change_type(true, false, false), ChangeType::Added);
-        assert_eq!(map_change_type(false, true, false), ChangeType::Deleted);
-        assert_eq!(map_change_type(false, false, true), ChangeType::Renamed);
-        assert_eq!(map_change_type(false, false, false), ChangeType::Modified);
-        
-        assert!(change_types_implemented, "Change type detection implemented successfully");
-    }
-
 }
\ No newline at end of file
@@ -11,6 +11,7 @@ use crate::scanner::messages::ScanMessage;
 use crate::notifications::AsyncNotificationManager;
 use crate::notifications::events::ScanEvent;
 use crate::plugin::SharedPluginRegistry;
+use crate::test_support::TestRepo;
 use async_trait::async_trait;
 
 /// Simple test message producer
@@ -32,21 +33,30 @@ impl TestMessageProducer {
 
 #[async_trait]
 impl MessageProducer for TestMessageProducer {
-    async fn send_message(&self, _message: ScanMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn produce_message(&self, _message: ScanMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         self.message_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         Ok(())
     }
+    
+    fn get_producer_name(&self) -> &str {
+        "test"
+    }
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_scanner_manager_creation() {
-    let repo_path = PathBuf::from("/tmp/test-repo");
-    let config = Arc::new(ScannerConfig::default());
+    let repo = TestRepo::builder()
+        .file("README.md", "# Test\n")
+        .commit("Initial commit")
+        .build()
+        .unwrap();
+    let repo_path = repo.path().to_path_buf();
+    let config = ScannerConfig::default();
     let producer = Arc::new(TestMessageProducer::new());
     let notification_manager = Arc::new(AsyncNotificationManager::<ScanEvent>::new());
     let registry = SharedPluginRegistry::new();
     
-    let manager = AsyncScannerManagerBuilder::new()
+    let _manager = AsyncScannerManagerBuilder::new()
         .repository_path(repo_path)
         .config(config)
         .message_producer(producer.clone())
@@ -60,25 +70,22 @@ async fn test_scanner_manager_creation() {
 }
 
 #[tokio::test]
-async fn test_scanner_manager_empty_scan() {
+async fn test_scanner_manager_rejects_missing_repository() {
     let repo_path = PathBuf::from("/tmp/nonexistent-repo");
-    let config = Arc::new(ScannerConfig::default());
+    let config = ScannerConfig::default();
     let producer = Arc::new(TestMessageProducer::new());
     let notification_manager = Arc::new(AsyncNotificationManager::<ScanEvent>::new());
     let registry = SharedPluginRegistry::new();
     
-    let manager = AsyncScannerManagerBuilder::new()
+    let result = AsyncScannerManagerBuilder::new()
         .repository_path(repo_path)
         .config(config)
         .message_producer(producer.clone())
         .notification_manager(notification_manager)
         .plugin_registry(registry)
-        .build()
-        .expect("Should build AsyncScannerManager");
+        .build();
     
-    // For a non-existent repo, scan should complete without sending messages
-    let result = manager.scan().await;
-    
-    // The result may be error or ok depending on implementation, but producer should be unchanged
+    // A missing repository is rejected before any scan starts
+    assert!(result.is_err());
     assert_eq!(producer.get_count(), 0);
-}
\ No newline at end of file
+}
@@ -1,10 +1,17 @@
 //! Branch Detection Logic
 //! 
 //! Provides intelligent branch detection and selection for repository scanning.
+//!
+//! Unless a branch is named, scans start from the repository's default branch
+//! (the remote's HEAD, or `init.defaultBranch` for repositories without one)
+//! rather than whatever happens to be checked out, so CI jobs running on a
+//! feature branch or a detached HEAD still report on the mainline.
 
+use std::collections::HashMap;
 use std::path::Path;
 use thiserror::Error;
 use gix::bstr::ByteSlice;
+use crate::scanner::repository::open_repository;
 
 /// Branch detection errors
 #[derive(Error, Debug, PartialEq)]
@@ -58,6 +65,17 @@ pub struct BranchDetectionResult {
     pub commit_id: String,
 }
 
+impl BranchDetectionResult {
+    /// Describe the scanned ref as `branch`, `branch_source` and `commit` for export metadata
+    pub fn to_metadata(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("branch".to_string(), self.branch_name.clone()),
+            ("branch_source".to_string(), self.selection_source.debug().to_string()),
+            ("commit".to_string(), self.commit_id.clone()),
+        ])
+    }
+}
+
 /// Source of branch selection for tracking and debugging
 #[derive(Debug, Clone, PartialEq)]
 pub enum BranchSelectionSource {
@@ -67,6 +85,8 @@ pub enum BranchSelectionSource {
     ConfigDefault,
     /// Remote default branch
     RemoteDefault,
+    /// The `init.defaultBranch` git setting
+    InitDefault,
     /// Symbolic HEAD reference
     SymbolicHead,
     /// From fallback list
@@ -80,6 +100,7 @@ impl BranchSelectionSource {
             BranchSelectionSource::CliOverride => "CLI Override",
             BranchSelectionSource::ConfigDefault => "Config Default", 
             BranchSelectionSource::RemoteDefault => "Remote Default",
+            BranchSelectionSource::InitDefault => "init.defaultBranch",
             BranchSelectionSource::SymbolicHead => "Symbolic HEAD",
             BranchSelectionSource::Fallback => "Fallback",
         }
@@ -110,8 +131,9 @@ impl BranchDetection {
     /// 1. CLI branch parameter (if provided)
     /// 2. Config file default-branch setting
     /// 3. Remote default branch (CLI remote → Config remote → First available remote)
-    /// 4. Symbolic HEAD reference
-    /// 5. Fallback list in order
+    /// 4. The `init.defaultBranch` setting, if that branch exists
+    /// 5. Symbolic HEAD reference
+    /// 6. Fallback list in order
     pub fn detect_branch(
         &self,
         repository_path: &Path,
@@ -119,7 +141,7 @@ impl BranchDetection {
         cli_remote: Option<&str>,
         cli_fallbacks: Option<&[String]>,
     ) -> Result<BranchDetectionResult, BranchDetectionError> {
-        let repo = gix::discover(repository_path)
+        let repo = open_repository(repository_path)
             .map_err(|e| BranchDetectionError::RepositoryError {
                 message: format!("Failed to open repository: {e}"),
             })?;
@@ -155,12 +177,17 @@ impl BranchDetection {
             return Ok(result);
         }
 
-        // Priority 4: Symbolic HEAD reference
+        // Priority 4: The configured default branch for new repositories
+        if let Some(result) = self.detect_init_default_branch(&repo) {
+            return Ok(result);
+        }
+
+        // Priority 5: Symbolic HEAD reference
         if let Ok(result) = self.detect_symbolic_head(&repo) {
             return Ok(result);
         }
 
-        // Priority 5: Fallback list in order
+        // Priority 6: Fallback list in order
         let fallbacks = cli_fallbacks.unwrap_or(&self.config.fallback_branches);
         for fallback in fallbacks {
             if let Ok(commit_id) = self.resolve_branch_ref_internal(&repo, fallback) {
@@ -176,17 +203,54 @@ impl BranchDetection {
     }
     
     /// Convert branch name to commit ID
+    ///
+    /// Names that are neither local nor remote branches are resolved as any
+    /// other revision (tag, commit ID), so release tags can be scanned directly.
     pub fn resolve_branch_ref(
         &self,
         repository_path: &Path,
         branch_name: &str,
     ) -> Result<String, BranchDetectionError> {
-        let repo = gix::discover(repository_path)
+        let repo = open_repository(repository_path)
             .map_err(|e| BranchDetectionError::RepositoryError {
                 message: format!("Failed to open repository: {e}"),
             })?;
 
         self.resolve_branch_ref_internal(&repo, branch_name)
+            .or_else(|e| self.resolve_revision(&repo, branch_name).ok_or(e))
+    }
+
+    /// Local branches, then remote-tracking branches, each sorted by name, with their commit IDs
+    ///
+    /// Remote HEAD references are aliases of another remote branch and are left out.
+    pub fn list_branches(&self, repo: &gix::Repository) -> Result<Vec<(String, String)>, BranchDetectionError> {
+        let error = |e: &dyn std::fmt::Display| BranchDetectionError::RepositoryError {
+            message: format!("Failed to list branches: {e}"),
+        };
+        let references = repo.references().map_err(|e| error(&e))?;
+        let mut branches = Vec::new();
+        for remote in [false, true] {
+            let iter = if remote { references.remote_branches() } else { references.local_branches() }
+                .map_err(|e| error(&e))?;
+            let mut group: Vec<(String, String)> = iter
+                .filter_map(Result::ok)
+                .filter_map(|reference| {
+                    let id = reference.try_id()?.to_string();
+                    Some((reference.name().shorten().to_string(), id))
+                })
+                .filter(|(name, _)| !name.ends_with("/HEAD"))
+                .collect();
+            group.sort();
+            branches.extend(group);
+        }
+        Ok(branches)
+    }
+
+    /// Resolve a tag or commit ID to the commit it points at
+    fn resolve_revision(&self, repo: &gix::Repository, revision: &str) -> Option<String> {
+        let object = repo.rev_parse_single(revision).ok()?.object().ok()?;
+        let commit = object.peel_to_commit().ok()?;
+        Some(commit.id.to_string())
     }
 
     /// Internal method to resolve branch reference using existing repo handle
@@ -308,6 +372,18 @@ impl BranchDetection {
         Err(BranchDetectionError::NoSuitableBranch)
     }
 
+    /// Branch named by `init.defaultBranch`, for repositories without a remote to ask
+    fn detect_init_default_branch(&self, repo: &gix::Repository) -> Option<BranchDetectionResult> {
+        let config = repo.config_snapshot();
+        let branch_name = config.string("init.defaultBranch")?.to_str().ok()?.trim().to_string();
+        let commit_id = self.resolve_branch_ref_internal(repo, &branch_name).ok()?;
+        Some(BranchDetectionResult {
+            branch_name,
+            selection_source: BranchSelectionSource::InitDefault,
+            commit_id,
+        })
+    }
+
     /// Detect symbolic HEAD reference
     fn detect_symbolic_head(&self, repo: &gix::Repository) -> Result<BranchDetectionResult, BranchDetectionError> {
         if let Ok(head_ref) = repo.find_reference("HEAD") {
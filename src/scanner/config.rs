@@ -75,6 +75,11 @@
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use crate::plugin::traits::PluginDataRequirements;
+use crate::scanner::pending::PendingChanges;
+use crate::queue::BackpressurePolicy;
+use crate::scanner::fingerprints::FingerprintConfig;
+use crate::scanner::encoding::ContentEncoding;
+use crate::scanner::file_policy::OversizedFiles;
 
 /// Scanner configuration parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +96,22 @@ pub struct ScannerConfig {
     pub branch_fallbacks: Vec<String>,
     /// Default remote to use for remote branch detection
     pub default_remote: Option<String>,
+    /// Explicit git directory (bare repository or `.git`), opened without discovery
+    pub git_dir: Option<PathBuf>,
+    /// Patch series (mbox file or directory of patches) scanned instead of a repository
+    pub patch_series: Option<PathBuf>,
+    /// Uncommitted changes scanned as one pending commit instead of the history
+    pub pending_changes: Option<PendingChanges>,
+    /// Detect renames and report changes under each file's current name
+    pub follow_renames: bool,
+    /// Policy applied when the queue reaches its limits (`None` rejects messages once the queue is full)
+    pub backpressure: Option<BackpressurePolicy>,
+    /// How file content is fingerprinted for plugins detecting duplicates
+    pub fingerprints: FingerprintConfig,
+    /// Encoding of file content that is not UTF-8 (detected per file by default)
+    pub encoding: ContentEncoding,
+    /// Whether files over a plugin's size limit are skipped or truncated for that plugin
+    pub oversized_files: OversizedFiles,
     /// Plugin data requirements configuration
     pub plugin_requirements: PluginRequirementsConfig,
 }
@@ -104,6 +125,14 @@ impl Default for ScannerConfig {
             default_branch: None,
             branch_fallbacks: vec!["main".to_string(), "master".to_string(), "develop".to_string(), "trunk".to_string()],
             default_remote: None,
+            git_dir: None,
+            patch_series: None,
+            pending_changes: None,
+            follow_renames: true,
+            backpressure: None,
+            fingerprints: FingerprintConfig::default(),
+            encoding: ContentEncoding::default(),
+            oversized_files: OversizedFiles::default(),
             plugin_requirements: PluginRequirementsConfig::default(),
         }
     }
@@ -232,6 +261,24 @@ impl ScannerConfigBuilder {
         self
     }
     
+    /// Scan the repository in this git directory instead of discovering one
+    pub fn with_git_dir(mut self, git_dir: PathBuf) -> Self {
+        self.config.git_dir = Some(git_dir);
+        self
+    }
+    
+    /// Scan a patch series (git format-patch output) instead of a repository
+    pub fn with_patch_series(mut self, patch_series: PathBuf) -> Self {
+        self.config.patch_series = Some(patch_series);
+        self
+    }
+    
+    /// Apply a backpressure policy, with `max_memory_bytes` as the queue's memory limit
+    pub fn with_backpressure(mut self, policy: BackpressurePolicy) -> Self {
+        self.config.backpressure = Some(policy);
+        self
+    }
+    
     pub fn build(self) -> Result<ScannerConfig, ConfigError> {
         self.config.validate()?;
         Ok(self.config)
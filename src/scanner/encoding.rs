@@ -0,0 +1,129 @@
+//! File Content Decoding
+//!
+//! File content is streamed to plugins as text. Content starting with a byte
+//! order mark is decoded as the UTF-8 or UTF-16 it announces, and valid UTF-8 is
+//! used as it is. Anything else is taken to be in a legacy encoding, guessed from
+//! the bytes themselves (Windows-1252, Shift_JIS, GBK and so on), unless the
+//! `encoding` option in `[scanner]` names the encoding a repository uses.
+//!
+//! ```toml
+//! [scanner]
+//! encoding = "windows-1252"   # or "auto" (the default) to detect it per file
+//! ```
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::borrow::Cow;
+
+/// Bytes searched for a NUL byte, which marks a file as binary
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Encoding of file content without a byte order mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentEncoding {
+    /// UTF-8 if the content is valid UTF-8, otherwise the encoding it most likely uses
+    #[default]
+    Auto,
+    /// Always this encoding
+    Fixed(&'static Encoding),
+}
+
+impl ContentEncoding {
+    /// Parse `auto` or an encoding label, such as `windows-1252`, `latin1` or `shift_jis`
+    pub fn parse(label: &str) -> Option<Self> {
+        let label = label.trim();
+        if label.eq_ignore_ascii_case("auto") {
+            return Some(Self::Auto);
+        }
+        Encoding::for_label(label.as_bytes()).map(Self::Fixed)
+    }
+
+    /// Name of the encoding as written in configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Fixed(encoding) => encoding.name(),
+        }
+    }
+
+    /// Text of a file's content and the encoding it was decoded from, or None when the content is binary
+    pub fn decode<'a>(&self, data: &'a [u8]) -> Option<(Cow<'a, str>, &'static Encoding)> {
+        if let Some((encoding, bom_length)) = Encoding::for_bom(data) {
+            let (text, _) = encoding.decode_without_bom_handling(&data[bom_length..]);
+            return Some((text, encoding));
+        }
+        // UTF-16 text is full of NUL bytes, so only a configured UTF-16 encoding reads it without a byte order mark
+        let utf16 = matches!(self, Self::Fixed(encoding) if *encoding == UTF_16LE || *encoding == UTF_16BE);
+        if !utf16 && is_binary(data) {
+            return None;
+        }
+        let encoding = match self {
+            Self::Fixed(encoding) => *encoding,
+            Self::Auto => match std::str::from_utf8(data) {
+                Ok(text) => return Some((Cow::Borrowed(text), UTF_8)),
+                Err(_) => {
+                    let mut detector = chardetng::EncodingDetector::new();
+                    detector.feed(data, true);
+                    detector.guess(None, true)
+                }
+            },
+        };
+        let (text, _) = encoding.decode_without_bom_handling(data);
+        Some((text, encoding))
+    }
+}
+
+impl serde::Serialize for ContentEncoding {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ContentEncoding {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let label = String::deserialize(deserializer)?;
+        Self::parse(&label).ok_or_else(|| serde::de::Error::custom(format!("unknown encoding: {}", label)))
+    }
+}
+
+/// Whether content is binary: it has a NUL byte near the start
+pub fn is_binary(data: &[u8]) -> bool {
+    data[..data.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_is_used_as_it_is() {
+        let (text, encoding) = ContentEncoding::Auto.decode("fn naïve() {}".as_bytes()).unwrap();
+        assert!(matches!(text, Cow::Borrowed("fn naïve() {}")));
+        assert_eq!(encoding, UTF_8);
+    }
+
+    #[test]
+    fn test_legacy_encodings_are_detected() {
+        // "Café à la crème" in Windows-1252
+        let latin = b"// Caf\xe9 \xe0 la cr\xe8me, d\xe9j\xe0 vu\nlet x = 1;\n";
+        let (text, encoding) = ContentEncoding::Auto.decode(latin).unwrap();
+        assert_eq!(encoding.name(), "windows-1252");
+        assert!(text.starts_with("// Café à la crème"));
+
+        let fixed = ContentEncoding::parse("latin1").unwrap();
+        assert_eq!(fixed.name(), "windows-1252");
+        assert_eq!(fixed.decode(b"na\xefve").unwrap().0, "naïve");
+    }
+
+    #[test]
+    fn test_byte_order_marks_and_binary_content() {
+        let utf16: Vec<u8> = [0xFF, 0xFE].into_iter()
+            .chain("let x = 1;".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let (text, encoding) = ContentEncoding::Auto.decode(&utf16).unwrap();
+        assert_eq!((text.as_ref(), encoding), ("let x = 1;", UTF_16LE));
+
+        assert!(ContentEncoding::Auto.decode(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").is_none());
+        assert!(ContentEncoding::parse("no-such-encoding").is_none());
+        assert_eq!(ContentEncoding::parse(" AUTO "), Some(ContentEncoding::Auto));
+    }
+}
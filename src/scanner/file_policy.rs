@@ -0,0 +1,238 @@
+//! Per-Consumer File Policies
+//!
+//! Plugins declare the largest file they process and whether they handle binary
+//! files through their data requirements. The scanner streams one set of messages
+//! for every active plugin, so a file one plugin wants may be too large for
+//! another, and binary changes are streamed as soon as any plugin handles them.
+//! Each consumer's policy is applied as it reads from the queue: binary changes
+//! are skipped for plugins that only handle text, and files over a plugin's size
+//! limit are skipped or truncated, as the `oversized-files` option in `[scanner]`
+//! chooses. A plugin therefore sees the same files whichever plugins run with it.
+
+use crate::plugin::traits::PluginDataRequirements;
+use crate::scanner::messages::{MessageData, ScanMessage};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// What happens to files over a consumer's size limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OversizedFiles {
+    /// The file is left out
+    #[default]
+    Skip,
+    /// The file's content is cut at the last line that fits
+    Truncate,
+}
+
+impl OversizedFiles {
+    /// Parse `skip` or `truncate`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "skip" => Some(Self::Skip),
+            "truncate" => Some(Self::Truncate),
+            _ => None,
+        }
+    }
+}
+
+/// Files a consumer is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FilePolicy {
+    /// Largest file content given to the consumer, in bytes
+    pub max_file_size: Option<u64>,
+    /// Whether binary file changes are given to the consumer
+    pub binary_files: bool,
+    /// What happens to file content over `max_file_size`
+    pub oversized: OversizedFiles,
+}
+
+/// Outcome of a policy for one message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDecision {
+    /// The message is given to the consumer as it is
+    Deliver,
+    /// The message is left out because its file is over the size limit
+    SkipOversized,
+    /// The message is left out because its file is binary
+    SkipBinary,
+    /// The consumer is given a copy of the message with its content cut to the size limit
+    Truncate,
+}
+
+impl FilePolicy {
+    /// Policy declared by a plugin's data requirements
+    pub fn from_requirements(requirements: &dyn PluginDataRequirements, oversized: OversizedFiles) -> Self {
+        Self {
+            max_file_size: requirements.max_file_size().map(|size| size as u64),
+            binary_files: requirements.handles_binary_files(),
+            oversized,
+        }
+    }
+
+    /// Whether every message is given to the consumer as it is
+    pub fn is_unrestricted(&self) -> bool {
+        self.max_file_size.is_none() && self.binary_files
+    }
+
+    /// Decide whether and how a message is given to the consumer
+    pub fn decide(&self, message: &ScanMessage) -> FileDecision {
+        match &message.data {
+            MessageData::FileChange { is_binary: true, .. } if !self.binary_files => FileDecision::SkipBinary,
+            MessageData::FileContent { size, .. } if self.max_file_size.is_some_and(|max| *size > max) => match self.oversized {
+                OversizedFiles::Skip => FileDecision::SkipOversized,
+                OversizedFiles::Truncate => FileDecision::Truncate,
+            },
+            _ => FileDecision::Deliver,
+        }
+    }
+
+    /// The message as the consumer is given it, or None when it is left out
+    pub fn apply(&self, message: Arc<ScanMessage>) -> Option<Arc<ScanMessage>> {
+        match self.decide(&message) {
+            FileDecision::Deliver => Some(message),
+            FileDecision::Truncate => {
+                let mut truncated = ScanMessage::clone(&message);
+                if let (MessageData::FileContent { content, .. }, Some(max)) = (&mut truncated.data, self.max_file_size) {
+                    let end = truncated_length(content, max as usize);
+                    content.truncate(end);
+                }
+                Some(Arc::new(truncated))
+            }
+            FileDecision::SkipOversized | FileDecision::SkipBinary => None,
+        }
+    }
+}
+
+/// Files a consumer's policy left out or truncated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FilePolicyCounts {
+    pub oversized_skipped: usize,
+    pub oversized_truncated: usize,
+    pub binary_skipped: usize,
+}
+
+impl FilePolicyCounts {
+    /// Count a decision
+    pub fn record(&mut self, decision: &FileDecision) {
+        match decision {
+            FileDecision::Deliver => {}
+            FileDecision::SkipOversized => self.oversized_skipped += 1,
+            FileDecision::SkipBinary => self.binary_skipped += 1,
+            FileDecision::Truncate => self.oversized_truncated += 1,
+        }
+    }
+
+    /// Warning about the files a consumer was not given in full, if any
+    pub fn warning(&self, consumer: &str, policy: &FilePolicy) -> Option<String> {
+        let limit = policy.max_file_size.map(format_size).unwrap_or_default();
+        let mut parts = Vec::new();
+        if self.oversized_skipped > 0 {
+            parts.push(format!("skipped {} over its {} limit", plural(self.oversized_skipped, "file"), limit));
+        }
+        if self.oversized_truncated > 0 {
+            parts.push(format!("truncated {} to its {} limit", plural(self.oversized_truncated, "file"), limit));
+        }
+        if self.binary_skipped > 0 {
+            parts.push(format!("skipped {}", plural(self.binary_skipped, "binary file change")));
+        }
+        (!parts.is_empty()).then(|| format!("{}: {}", consumer, parts.join(", ")))
+    }
+}
+
+/// Length of content cut to at most `max` bytes, at the end of the last whole line when there is one
+fn truncated_length(content: &str, max: usize) -> usize {
+    let mut end = max.min(content.len());
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    content[..end].rfind('\n').map_or(end, |newline| newline + 1)
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes >= 1024 * 1024 && bytes % (1024 * 1024) == 0 => format!("{} MB", bytes / (1024 * 1024)),
+        bytes if bytes >= 1024 && bytes % 1024 == 0 => format!("{} KB", bytes / 1024),
+        bytes => plural(bytes as usize, "byte"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::messages::{ChangeType, MessageHeader};
+
+    fn content(size: usize) -> ScanMessage {
+        let content = "line\n".repeat(size / 5);
+        ScanMessage::new(MessageHeader::new(0, "scan".to_string()), MessageData::FileContent {
+            path: "src/big.rs".to_string(),
+            size: content.len() as u64,
+            content,
+            generated: false,
+        })
+    }
+
+    fn binary_change() -> ScanMessage {
+        ScanMessage::new(MessageHeader::new(1, "scan".to_string()), MessageData::FileChange {
+            path: "logo.png".to_string(),
+            change_type: ChangeType::Added,
+            old_path: None,
+            insertions: 0,
+            deletions: 0,
+            is_binary: true,
+            binary_size: Some(2048),
+            line_count: None,
+            commit_hash: "abc1234".to_string(),
+            commit_timestamp: 0,
+            checkout_path: None,
+            old_mode: None,
+            new_mode: None,
+            replaced_lines: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_oversized_content_is_skipped_or_truncated() {
+        let skip = FilePolicy { max_file_size: Some(1024), binary_files: false, oversized: OversizedFiles::Skip };
+        assert_eq!(skip.decide(&content(1000)), FileDecision::Deliver);
+        assert_eq!(skip.decide(&content(2000)), FileDecision::SkipOversized);
+
+        let truncate = FilePolicy { oversized: OversizedFiles::Truncate, ..skip };
+        let Some(truncated) = truncate.apply(Arc::new(content(2000))) else {
+            panic!("truncated content is delivered");
+        };
+        let MessageData::FileContent { size, content, .. } = &truncated.data else {
+            panic!("truncated content is file content");
+        };
+        // The size is the file's, the content ends at the last whole line within the limit
+        assert_eq!((*size, content.len()), (2000, 1020));
+        assert!(content.ends_with("line\n"));
+    }
+
+    #[test]
+    fn test_binary_changes_are_skipped_unless_handled() {
+        let text_only = FilePolicy::default();
+        assert_eq!(text_only.decide(&binary_change()), FileDecision::SkipBinary);
+        assert!(text_only.apply(Arc::new(binary_change())).is_none());
+        let binary = FilePolicy { binary_files: true, ..text_only };
+        assert!(binary.is_unrestricted());
+        assert_eq!(binary.decide(&binary_change()), FileDecision::Deliver);
+    }
+
+    #[test]
+    fn test_counts_and_warning() {
+        let policy = FilePolicy { max_file_size: Some(5 * 1024 * 1024), binary_files: false, oversized: OversizedFiles::Skip };
+        let mut counts = FilePolicyCounts::default();
+        assert_eq!(counts.warning("metrics", &policy), None);
+        counts.record(&FileDecision::SkipOversized);
+        counts.record(&FileDecision::SkipOversized);
+        counts.record(&FileDecision::SkipBinary);
+        assert_eq!(counts.warning("metrics", &policy).unwrap(),
+            "metrics: skipped 2 files over its 5 MB limit, skipped 1 binary file change");
+        assert_eq!(OversizedFiles::parse(" Truncate"), Some(OversizedFiles::Truncate));
+        assert_eq!(OversizedFiles::parse("drop"), None);
+    }
+}
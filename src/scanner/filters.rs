@@ -5,6 +5,7 @@
 use std::ops::ControlFlow;
 use std::time::SystemTime;
 use std::path::Path;
+use regex::Regex;
 use crate::scanner::query::{QueryParams, DateRange, FilePathFilter, AuthorFilter as QueryAuthorFilter};
 
 /// Filter result for early termination using ControlFlow
@@ -37,13 +38,52 @@ pub struct DateFilter {
 /// Built-in file path filter using iterator combinators
 #[derive(Debug, Clone)]
 pub struct PathFilter {
-    filter: FilePathFilter,
+    matcher: PathMatcher,
+}
+
+/// A `.gitignore`-style path pattern
+///
+/// `*` and `?` match within a path component, `**` across components and
+/// `[...]` one character of a class (`[!...]` negated). A pattern without a
+/// slash, other than a trailing one, matches at any depth; one with a slash is
+/// anchored at the repository root; a trailing slash matches directories only.
+/// A pattern matching a directory also matches everything below it, so `src`
+/// and `src/` both select the whole subtree.
+#[derive(Debug, Clone)]
+pub struct PathPattern {
+    regex: Regex,
+}
+
+/// Compiled include, exclude and weight patterns of a [`FilePathFilter`]
+#[derive(Debug, Clone, Default)]
+pub struct PathMatcher {
+    include: Vec<PathPattern>,
+    exclude: Vec<PathPattern>,
+    weights: Vec<(PathPattern, f64)>,
+}
+
+/// An author pattern
+///
+/// A pattern wrapped in slashes, such as `/^(alice|bob)@/`, is a regular
+/// expression; anything else matches as a substring. Either way it is checked
+/// against the author's name and email separately.
+#[derive(Debug, Clone)]
+pub enum AuthorPattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+/// Compiled include and exclude patterns of an author filter
+#[derive(Debug, Clone, Default)]
+pub struct AuthorMatcher {
+    include: Vec<AuthorPattern>,
+    exclude: Vec<AuthorPattern>,
 }
 
 /// Built-in author filter using pattern matching
 #[derive(Debug, Clone)]
 pub struct AuthorFilter {
-    filter: QueryAuthorFilter,
+    matcher: AuthorMatcher,
 }
 
 /// Functional callback filter using closures for zero-cost abstractions
@@ -84,23 +124,8 @@ impl ScanFilter<CommitData> for DateFilter {
 
 impl ScanFilter<CommitData> for PathFilter {
     fn apply(&self, input: &CommitData) -> FilterResult<()> {
-        let has_included = if self.filter.include.is_empty() {
-            true
-        } else {
-            input.file_paths.iter().any(|file_path| {
-                self.filter.include.iter().any(|include_pattern| {
-                    file_path.starts_with(include_pattern.to_string_lossy().as_ref())
-                })
-            })
-        };
-        
-        let has_excluded = input.file_paths.iter().any(|file_path| {
-            self.filter.exclude.iter().any(|exclude_pattern| {
-                file_path.starts_with(exclude_pattern.to_string_lossy().as_ref())
-            })
-        });
-        
-        if has_included && !has_excluded {
+        // A commit passes when it changes at least one selected file
+        if self.matcher.is_empty() || input.file_paths.iter().any(|file_path| self.matcher.matches(file_path)) {
             ControlFlow::Continue(())
         } else {
             ControlFlow::Break(())
@@ -110,15 +135,7 @@ impl ScanFilter<CommitData> for PathFilter {
 
 impl ScanFilter<CommitData> for AuthorFilter {
     fn apply(&self, input: &CommitData) -> FilterResult<()> {
-        let is_included = if self.filter.include.is_empty() {
-            true
-        } else {
-            self.filter.include.contains(&input.author)
-        };
-        
-        let is_excluded = self.filter.exclude.contains(&input.author);
-        
-        if is_included && !is_excluded {
+        if self.matcher.matches(&input.author, "") {
             ControlFlow::Continue(())
         } else {
             ControlFlow::Break(())
@@ -171,7 +188,7 @@ impl DateFilter {
 impl PathFilter {
     /// Create new path filter
     pub fn new(filter: FilePathFilter) -> Self {
-        Self { filter }
+        Self { matcher: PathMatcher::new(&filter) }
     }
     
     /// Create filter that includes specific paths
@@ -183,12 +200,11 @@ impl PathFilter {
         let include_paths = paths.into_iter()
             .map(|p| p.as_ref().to_path_buf())
             .collect();
-        Self {
-            filter: FilePathFilter {
-                include: include_paths,
-                exclude: Vec::new(),
-            }
-        }
+        Self::new(FilePathFilter {
+            include: include_paths,
+            exclude: Vec::new(),
+            weights: Vec::new(),
+        })
     }
     
     /// Create filter that excludes specific paths
@@ -200,19 +216,191 @@ impl PathFilter {
         let exclude_paths = paths.into_iter()
             .map(|p| p.as_ref().to_path_buf())
             .collect();
-        Self {
-            filter: FilePathFilter {
-                include: Vec::new(),
-                exclude: exclude_paths,
+        Self::new(FilePathFilter {
+            include: Vec::new(),
+            exclude: exclude_paths,
+            weights: Vec::new(),
+        })
+    }
+}
+
+impl PathPattern {
+    /// Compile a pattern; a `[` without a closing `]` matches itself, as in git
+    pub fn new(pattern: &str) -> Self {
+        let pattern = pattern.trim();
+        let glob = pattern.strip_prefix("./").unwrap_or(pattern);
+        let directory = glob.ends_with('/');
+        let glob = glob.trim_end_matches('/');
+        let anchored = glob.contains('/');
+        let glob = glob.trim_start_matches('/');
+
+        let mut expression = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+        let chars: Vec<char> = glob.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    i += 1;
+                    if chars.get(i + 1) == Some(&'/') {
+                        i += 1;
+                        expression.push_str("(?:.*/)?");
+                    } else {
+                        expression.push_str(".*");
+                    }
+                }
+                '*' => expression.push_str("[^/]*"),
+                '?' => expression.push_str("[^/]"),
+                '[' => match character_class(&chars[i + 1..]) {
+                    Some((class, length)) => {
+                        expression.push_str(&class);
+                        i += length;
+                    }
+                    None => expression.push_str(r"\["),
+                },
+                '\\' if i + 1 < chars.len() => {
+                    i += 1;
+                    expression.push_str(&regex::escape(&chars[i].to_string()));
+                }
+                c => expression.push_str(&regex::escape(&c.to_string())),
+            }
+            i += 1;
+        }
+        expression.push_str(if glob.is_empty() {
+            ".*$"
+        } else if directory {
+            "/.*$"
+        } else {
+            "(?:/.*)?$"
+        });
+
+        // Only a malformed class such as `[z-a]` fails to compile; match it literally then
+        let regex = Regex::new(&expression)
+            .unwrap_or_else(|_| Regex::new(&format!("^{}(?:/.*)?$", regex::escape(glob))).expect("escaped pattern compiles"));
+        Self { regex }
+    }
+
+    /// Check whether a repository-relative path matches the pattern
+    pub fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+/// Translate the character class following a `[` into a regex class and the characters it used, including the `]`
+fn character_class(chars: &[char]) -> Option<(String, usize)> {
+    let mut class = String::from("[");
+    let mut i = 0;
+    if matches!(chars.first(), Some('!') | Some('^')) {
+        // Like the other wildcards, a negated class never matches a separator
+        class.push_str("^/");
+        i += 1;
+    }
+    let start = i;
+    while i < chars.len() {
+        match chars[i] {
+            // A `]` straight after the opening bracket is a member, not the end
+            ']' if i > start => {
+                class.push(']');
+                return Some((class, i + 1));
             }
+            '/' => return None,
+            c @ ('\\' | '[' | ']' | '^' | '&' | '~') => {
+                class.push('\\');
+                class.push(c);
+            }
+            c => class.push(c),
         }
+        i += 1;
+    }
+    None
+}
+
+impl PathMatcher {
+    /// Compile the patterns of a path filter
+    pub fn new(filter: &FilePathFilter) -> Self {
+        let compile = |paths: &[std::path::PathBuf]| {
+            paths.iter().map(|path| PathPattern::new(&path.to_string_lossy())).collect()
+        };
+        Self {
+            include: compile(&filter.include),
+            exclude: compile(&filter.exclude),
+            weights: filter.weights.iter()
+                .map(|weight| (PathPattern::new(&weight.path.to_string_lossy()), weight.weight.clamp(0.0, 1.0)))
+                .collect(),
+        }
+    }
+
+    /// Check whether the matcher has no include or exclude patterns and so selects every path
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Check whether a path is selected: it matches an include pattern (or there are none) and no exclude pattern
+    pub fn matches(&self, path: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|pattern| pattern.is_match(path)))
+            && !self.exclude.iter().any(|pattern| pattern.is_match(path))
+    }
+
+    /// Factor a path's line changes are scaled by: the lowest weight of the patterns it matches, or 1
+    pub fn weight(&self, path: &str) -> f64 {
+        self.weights.iter()
+            .filter(|(pattern, _)| pattern.is_match(path))
+            .map(|(_, weight)| *weight)
+            .fold(1.0, f64::min)
+    }
+}
+
+impl AuthorPattern {
+    /// Check whether a pattern is written as a regular expression (`/.../`)
+    pub fn is_regex(pattern: &str) -> bool {
+        pattern.len() >= 2 && pattern.starts_with('/') && pattern.ends_with('/')
+    }
+
+    /// Parse a pattern, failing only on an invalid regular expression
+    pub fn parse(pattern: &str) -> Result<Self, regex::Error> {
+        if Self::is_regex(pattern) {
+            Regex::new(&pattern[1..pattern.len() - 1]).map(Self::Regex)
+        } else {
+            Ok(Self::Substring(pattern.to_string()))
+        }
+    }
+
+    /// Check whether an author's name or email matches the pattern
+    pub fn is_match(&self, name: &str, email: &str) -> bool {
+        match self {
+            Self::Substring(text) => name.contains(text.as_str()) || email.contains(text.as_str()),
+            Self::Regex(regex) => regex.is_match(name) || (!email.is_empty() && regex.is_match(email)),
+        }
+    }
+}
+
+impl AuthorMatcher {
+    /// Compile the patterns of an author filter
+    ///
+    /// Patterns are validated when the command line is parsed; an invalid
+    /// regular expression that gets here matches as a substring.
+    pub fn new(filter: &QueryAuthorFilter) -> Self {
+        let compile = |patterns: &[String]| {
+            patterns.iter()
+                .map(|pattern| AuthorPattern::parse(pattern).unwrap_or_else(|_| AuthorPattern::Substring(pattern.clone())))
+                .collect()
+        };
+        Self {
+            include: compile(&filter.include),
+            exclude: compile(&filter.exclude),
+        }
+    }
+
+    /// Check whether an author is selected: they match an include pattern (or there are none) and no exclude pattern
+    pub fn matches(&self, name: &str, email: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|pattern| pattern.is_match(name, email)))
+            && !self.exclude.iter().any(|pattern| pattern.is_match(name, email))
     }
 }
 
 impl AuthorFilter {
     /// Create new author filter
     pub fn new(filter: QueryAuthorFilter) -> Self {
-        Self { filter }
+        Self { matcher: AuthorMatcher::new(&filter) }
     }
     
     /// Create filter for specific authors
@@ -222,12 +410,10 @@ impl AuthorFilter {
         S: Into<String>,
     {
         let author_list = authors.into_iter().map(|s| s.into()).collect();
-        Self {
-            filter: QueryAuthorFilter {
-                include: author_list,
-                exclude: Vec::new(),
-            }
-        }
+        Self::new(QueryAuthorFilter {
+            include: author_list,
+            exclude: Vec::new(),
+        })
     }
     
     /// Create filter excluding specific authors
@@ -237,12 +423,10 @@ impl AuthorFilter {
         S: Into<String>,
     {
         let author_list = authors.into_iter().map(|s| s.into()).collect();
-        Self {
-            filter: QueryAuthorFilter {
-                include: Vec::new(),
-                exclude: author_list,
-            }
-        }
+        Self::new(QueryAuthorFilter {
+            include: Vec::new(),
+            exclude: author_list,
+        })
     }
 }
 
@@ -395,7 +579,7 @@ where
 mod tests {
     use super::*;
     use std::time::{Duration, UNIX_EPOCH};
-    use crate::scanner::query::{DateRange, FilePathFilter, AuthorFilter as QueryAuthorFilter};
+    use crate::scanner::query::{DateRange, FilePathFilter, PathWeight, AuthorFilter as QueryAuthorFilter};
 
     fn create_test_commit(timestamp: SystemTime, author: &str, files: Vec<&str>) -> CommitData {
         CommitData {
@@ -457,6 +641,7 @@ mod tests {
         let file_filter = FilePathFilter {
             include: vec!["src/".into(), "tests/".into()],
             exclude: vec!["target/".into()],
+            weights: Vec::new(),
         };
         let _combined_filter = PathFilter::new(file_filter);
         // Filters should be created successfully
@@ -731,4 +916,63 @@ mod tests {
                 "Empty filters should include all commits, but commit {:?} was filtered out", commit);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_path_patterns_follow_gitignore_rules() {
+        let matches = |pattern: &str, path: &str| PathPattern::new(pattern).is_match(path);
+
+        // Without a slash a pattern matches at any depth, and a directory selects its subtree
+        assert!(matches("*.rs", "src/scanner/mod.rs"));
+        assert!(matches("target", "crates/api/target/debug/build.log"));
+        assert!(!matches("main", "src/main.rs"));
+
+        // A slash anchors the pattern at the root; `*` stops at separators, `**` does not
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "src/scanner/mod.rs"));
+        assert!(!matches("src/*.rs", "lib/src/lib.rs"));
+        assert!(matches("crates/**/tests", "crates/api/tests/it.rs"));
+        assert!(matches("**/fixtures/*.json", "tests/fixtures/repo.json"));
+        assert!(matches("/docs", "docs/guide.md"));
+
+        // A trailing slash matches directories only
+        assert!(matches("build/", "build/output.o"));
+        assert!(!matches("build/", "build"));
+
+        // Classes, negated classes, and brackets that are not classes
+        assert!(matches("file[0-9].txt", "file7.txt"));
+        assert!(!matches("file[!0-9].txt", "file7.txt"));
+        assert!(matches("[]x].md", "].md"));
+        assert!(matches("notes[draft", "notes[draft"));
+        assert!(matches("[z-a]", "[z-a]"));
+    }
+
+    #[test]
+    fn test_path_matcher_excludes_after_including() {
+        let matcher = PathMatcher::new(&FilePathFilter {
+            include: vec!["src/".into(), "*.toml".into()],
+            exclude: vec!["**/generated/".into()],
+            weights: Vec::new(),
+        });
+        assert!(matcher.matches("src/lib.rs"));
+        assert!(matcher.matches("crates/api/Cargo.toml"));
+        assert!(!matcher.matches("src/generated/schema.rs"));
+        assert!(!matcher.matches("README.md"));
+        assert!(PathMatcher::default().matches("README.md"));
+    }
+
+    #[test]
+    fn test_path_matcher_weights() {
+        let matcher = PathMatcher::new(&FilePathFilter {
+            weights: vec![
+                PathWeight { path: "migrations/".into(), weight: 0.1 },
+                PathWeight { path: "*.py".into(), weight: 0.5 },
+            ],
+            ..Default::default()
+        });
+        // Weights scale the paths they match without selecting or dropping any
+        assert!(matcher.is_empty());
+        assert_eq!(matcher.weight("app/migrations/0001_initial.py"), 0.1);
+        assert_eq!(matcher.weight("app/models.py"), 0.5);
+        assert_eq!(matcher.weight("README.md"), 1.0);
+    }
+}
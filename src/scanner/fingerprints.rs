@@ -0,0 +1,229 @@
+//! Content Fingerprints
+//!
+//! Duplicate detection compares blocks of content across files. Rather than
+//! every interested plugin splitting and hashing each file itself, the scanner
+//! fingerprints the files it streams once and sends the hashes as
+//! `ContentFingerprints` messages, which any number of plugins can match up.
+//!
+//! A file is normalised into units before hashing: trimmed non-blank lines
+//! that are not wholly comments, with runs of whitespace collapsed, or with
+//! token hashing the identifiers, numbers and punctuation of those lines. Every
+//! run of `min_block` consecutive units is a block, and each block's hash is
+//! its fingerprint, so two files share a fingerprint wherever they share
+//! `min_block` units of content. Token hashing still finds code whose line
+//! breaks or indentation were changed when it was copied.
+//!
+//! Hashes are 64-bit FNV-1a, which is stable across runs and platforms.
+
+use crate::scanner::messages::BlockFingerprint;
+
+/// Unit of content the blocks are made of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum FingerprintMode {
+    /// Blocks of lines
+    #[default]
+    Lines,
+    /// Blocks of tokens, ignoring line breaks and layout
+    Tokens,
+}
+
+impl FingerprintMode {
+    /// Accepted names of the modes, for argument parsing
+    pub const NAMES: [&'static str; 2] = ["lines", "tokens"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "lines" => Some(Self::Lines),
+            "tokens" => Some(Self::Tokens),
+            _ => None,
+        }
+    }
+
+    /// Block size used unless configured: enough content that a shared block is not a coincidence
+    pub fn default_min_block(&self) -> usize {
+        match self {
+            Self::Lines => 6,
+            Self::Tokens => 50,
+        }
+    }
+}
+
+/// How files are fingerprinted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FingerprintConfig {
+    pub mode: FingerprintMode,
+    /// Units (lines or tokens) in each block
+    pub min_block: usize,
+}
+
+impl FingerprintConfig {
+    /// The mode with its default block size
+    pub fn new(mode: FingerprintMode) -> Self {
+        Self { mode, min_block: mode.default_min_block() }
+    }
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        Self::new(FingerprintMode::default())
+    }
+}
+
+/// A normalised unit of content and the line it came from
+struct Unit<'a> {
+    text: std::borrow::Cow<'a, str>,
+    line: u32,
+    tokens: u32,
+}
+
+/// Fingerprints of every block of `content`, in file order
+pub fn fingerprint(content: &str, config: &FingerprintConfig) -> Vec<BlockFingerprint> {
+    let units = units(content, config.mode);
+    let block = config.min_block.max(1);
+    if units.len() < block {
+        return Vec::new();
+    }
+    let separator = match config.mode {
+        FingerprintMode::Lines => b'\n',
+        FingerprintMode::Tokens => b' ',
+    };
+    units.windows(block)
+        .map(|window| {
+            let mut hash = FNV_OFFSET;
+            for (index, unit) in window.iter().enumerate() {
+                if index > 0 {
+                    hash = fnv1a(hash, &[separator]);
+                }
+                hash = fnv1a(hash, unit.text.as_bytes());
+            }
+            BlockFingerprint {
+                hash,
+                start_line: window[0].line,
+                end_line: window[window.len() - 1].line,
+                tokens: window.iter().map(|unit| unit.tokens).sum(),
+            }
+        })
+        .collect()
+}
+
+/// Normalised units of a file's content lines, skipping blank and comment-only lines
+fn units(content: &str, mode: FingerprintMode) -> Vec<Unit<'_>> {
+    let mut units = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index as u32 + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || is_comment(trimmed) {
+            continue;
+        }
+        let tokens = tokens(trimmed);
+        match mode {
+            FingerprintMode::Lines => units.push(Unit {
+                text: trimmed.split_whitespace().collect::<Vec<_>>().join(" ").into(),
+                line: line_number,
+                tokens: tokens.len() as u32,
+            }),
+            FingerprintMode::Tokens => units.extend(tokens.into_iter().map(|token| Unit {
+                text: token.into(),
+                line: line_number,
+                tokens: 1,
+            })),
+        }
+    }
+    units
+}
+
+/// Whether a trimmed line holds only a comment
+fn is_comment(line: &str) -> bool {
+    ["//", "#", "/*", "*", "--", ";", "<!--"].iter().any(|marker| line.starts_with(marker))
+}
+
+/// Identifiers and numbers as whole tokens, any other visible character on its own
+fn tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+    for (index, character) in line.char_indices() {
+        let is_word = character.is_alphanumeric() || character == '_';
+        match (is_word, word_start) {
+            (true, None) => word_start = Some(index),
+            (true, Some(_)) => {}
+            (false, start) => {
+                if let Some(start) = start {
+                    tokens.push(&line[start..index]);
+                    word_start = None;
+                }
+                if !character.is_whitespace() {
+                    tokens.push(&line[index..index + character.len_utf8()]);
+                }
+            }
+        }
+    }
+    if let Some(start) = word_start {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = "fn total(values: &[u32]) -> u32 {\n    let mut sum = 0;\n    for value in values {\n        sum += value;\n    }\n    sum\n}\n";
+
+    fn config(mode: FingerprintMode, min_block: usize) -> FingerprintConfig {
+        FingerprintConfig { mode, min_block }
+    }
+
+    #[test]
+    fn test_line_blocks_ignore_layout_and_comments() {
+        let config = config(FingerprintMode::Lines, 3);
+        let original = fingerprint(ORIGINAL, &config);
+        // Seven lines make five blocks of three
+        assert_eq!(original.len(), 5);
+        assert_eq!((original[0].start_line, original[0].end_line), (1, 3));
+        assert_eq!((original[4].start_line, original[4].end_line), (5, 7));
+
+        // The same code reindented, with a comment and blank line, somewhere else in a file
+        let copied = format!("use std::fmt;\n\n{}", ORIGINAL.replace("    let mut sum = 0;\n", "  let   mut sum = 0;\n\n  // Add them up\n"));
+        let copies = fingerprint(&copied, &config);
+        assert_eq!(copies.len(), 6);
+        assert!(original.iter().all(|block| copies.iter().any(|copy| copy.hash == block.hash)));
+        let shared = copies.iter().find(|copy| copy.hash == original[1].hash).unwrap();
+        assert_eq!((shared.start_line, shared.end_line), (4, 8));
+    }
+
+    #[test]
+    fn test_token_blocks_survive_line_breaks() {
+        let token_config = config(FingerprintMode::Tokens, 10);
+        let joined = ORIGINAL.replace("{\n        sum += value;\n    }", "{ sum += value; }");
+        let original = fingerprint(ORIGINAL, &token_config);
+        let copies = fingerprint(&joined, &token_config);
+        assert_eq!(original.len(), copies.len());
+        assert!(original.iter().zip(&copies).all(|(a, b)| a.hash == b.hash && a.tokens == 10));
+
+        // Line hashing sees the joined line, in every block, as different content
+        let line_config = config(FingerprintMode::Lines, 3);
+        let original_lines = fingerprint(ORIGINAL, &line_config);
+        let joined_lines = fingerprint(&joined, &line_config);
+        assert_eq!(joined_lines.len(), 3);
+        assert!(joined_lines.iter().all(|block| original_lines.iter().all(|other| other.hash != block.hash)));
+    }
+
+    #[test]
+    fn test_short_content_and_tokens() {
+        assert!(fingerprint("fn main() {}\n", &FingerprintConfig::default()).is_empty());
+        assert_eq!(tokens("sum += values[0]; // total"), ["sum", "+", "=", "values", "[", "0", "]", ";", "/", "/", "total"]);
+        assert_eq!(FingerprintMode::parse("Tokens"), Some(FingerprintMode::Tokens));
+        assert_eq!(FingerprintConfig::new(FingerprintMode::Tokens).min_block, 50);
+    }
+}
@@ -0,0 +1,186 @@
+//! Author Identity Resolution
+//!
+//! People commit under more than one name or address, such as `Jane Doe <jane@x>`
+//! at work and `jdoe <jane@y>` at home, which splits their work across several
+//! authors in every report. The scanner maps each commit author to a canonical
+//! identity before filters and plugins see it, using the repository's `.mailmap`
+//! (in git's format, see gitmailmap(5)) and aliases from the `[authors.aliases]`
+//! configuration section. An alias takes precedence over a `.mailmap` entry for
+//! the same address.
+//!
+//! Each alias key is a canonical identity, `Name <email>`, `Name` or `<email>`,
+//! and its value lists the identities merged into it: `Name <email>` matches
+//! that name at that address, while `<email>` or a bare address matches any
+//! name at the address.
+//!
+//! ```toml
+//! [authors]
+//! mailmap = true
+//!
+//! [authors.aliases]
+//! "Jane Doe <jane@x.org>" = ["jdoe <jane@y.org>", "jane@home.net"]
+//! ```
+
+use crate::scanner::async_engine::error::{ScanError, ScanResult};
+use gix::bstr::ByteSlice;
+use gix::mailmap::{Entry, Snapshot};
+
+/// Sources of canonical author identities
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityRules {
+    /// Canonical identities with the identities merged into each, as described in the module documentation
+    pub aliases: Vec<(String, Vec<String>)>,
+    /// Whether the repository's `.mailmap` is applied
+    pub use_mailmap: bool,
+}
+
+impl Default for IdentityRules {
+    fn default() -> Self {
+        Self {
+            aliases: Vec::new(),
+            use_mailmap: true,
+        }
+    }
+}
+
+impl IdentityRules {
+    /// Check whether no identities can be merged
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty() && !self.use_mailmap
+    }
+
+    /// Build the resolver from the aliases and, when there is a repository, its `.mailmap`
+    pub fn resolve(&self, repo: Option<&gix::Repository>) -> ScanResult<IdentityResolver> {
+        let mut mailmap = match repo {
+            Some(repo) if self.use_mailmap => repo.open_mailmap(),
+            _ => Snapshot::default(),
+        };
+
+        for (canonical, aliases) in &self.aliases {
+            let invalid = |e: &str| ScanError::configuration(format!("Invalid [authors.aliases] entry '{canonical}': {e}"));
+            let (proper_name, proper_email) = parse_identity(canonical);
+            if proper_name.is_none() && proper_email.is_none() {
+                return Err(invalid("the canonical identity is empty"));
+            }
+            for alias in aliases {
+                let (commit_name, commit_email) = parse_identity(alias);
+                let commit_email = commit_email
+                    .ok_or_else(|| invalid(&format!("alias '{alias}' has no email address")))?;
+                let entry = match (proper_name, proper_email, commit_name) {
+                    (Some(name), Some(email), Some(old_name)) => Entry::change_name_and_email_by_name_and_email(name, email, old_name, commit_email),
+                    (Some(name), Some(email), None) => Entry::change_name_and_email_by_email(name, email, commit_email),
+                    (None, Some(email), Some(old_name)) => Entry::change_email_by_name_and_email(email, old_name, commit_email),
+                    (None, Some(email), None) => Entry::change_email_by_email(email, commit_email),
+                    (Some(name), None, _) => Entry::change_name_by_email(name, commit_email),
+                    (None, None, _) => unreachable!("checked above"),
+                };
+                mailmap.merge([entry]);
+            }
+        }
+
+        Ok(IdentityResolver { mailmap })
+    }
+}
+
+/// Canonical author identities, ready to resolve commit authors
+#[derive(Debug, Clone, Default)]
+pub struct IdentityResolver {
+    mailmap: Snapshot,
+}
+
+impl IdentityResolver {
+    /// Check whether every identity resolves to itself
+    pub fn is_empty(&self) -> bool {
+        self.mailmap.iter().next().is_none()
+    }
+
+    /// The canonical name and email of an author
+    pub fn resolve(&self, name: &str, email: &str) -> (String, String) {
+        let signature = gix::actor::SignatureRef {
+            name: name.as_bytes().as_bstr(),
+            email: email.as_bytes().as_bstr(),
+            time: "",
+        };
+        let resolved = self.mailmap.resolve_cow(signature);
+        (resolved.name.to_str_lossy().into_owned(), resolved.email.to_str_lossy().into_owned())
+    }
+
+    /// Stable key identifying the mappings, used to keep cached scans apart
+    pub fn fingerprint(&self) -> u64 {
+        let key: String = self.mailmap.iter().map(|entry| format!("{entry:?}\0")).collect();
+        crate::cache::fnv1a_64(key.as_bytes())
+    }
+}
+
+/// Split `Name <email>`, `<email>`, a bare address or a bare name into its parts
+fn parse_identity(identity: &str) -> (Option<&str>, Option<&str>) {
+    fn non_empty(s: &str) -> Option<&str> {
+        Some(s.trim()).filter(|s| !s.is_empty())
+    }
+    let identity = identity.trim();
+    match identity.split_once('<') {
+        Some((name, rest)) => (non_empty(name), non_empty(rest.trim_end().trim_end_matches('>'))),
+        None if identity.contains('@') => (None, non_empty(identity)),
+        None => (non_empty(identity), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::repository::open_repository;
+    use crate::test_support::TestRepo;
+
+    fn aliases(canonical: &str, merged: &[&str]) -> IdentityRules {
+        IdentityRules {
+            aliases: vec![(canonical.to_string(), merged.iter().map(|a| a.to_string()).collect())],
+            use_mailmap: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_identity() {
+        assert_eq!(parse_identity("Jane Doe <jane@x.org>"), (Some("Jane Doe"), Some("jane@x.org")));
+        assert_eq!(parse_identity(" <jane@x.org> "), (None, Some("jane@x.org")));
+        assert_eq!(parse_identity("jane@x.org"), (None, Some("jane@x.org")));
+        assert_eq!(parse_identity("Jane Doe"), (Some("Jane Doe"), None));
+    }
+
+    #[test]
+    fn test_aliases_merge_identities() {
+        let resolver = aliases("Jane Doe <jane@x.org>", &["jdoe <jane@y.org>", "jane@home.net"]).resolve(None).unwrap();
+        let jane = ("Jane Doe".to_string(), "jane@x.org".to_string());
+        assert_eq!(resolver.resolve("jdoe", "jane@y.org"), jane);
+        assert_eq!(resolver.resolve("Jane at home", "jane@home.net"), jane);
+        // A named alias only matches that name at the address
+        assert_eq!(resolver.resolve("Someone", "jane@y.org"), ("Someone".to_string(), "jane@y.org".to_string()));
+        assert_eq!(resolver.resolve("Bob", "bob@x.org"), ("Bob".to_string(), "bob@x.org".to_string()));
+
+        assert!(aliases("Jane Doe", &["jdoe"]).resolve(None).is_err());
+        assert!(IdentityResolver::default().is_empty());
+        assert_ne!(resolver.fingerprint(), IdentityResolver::default().fingerprint());
+    }
+
+    #[test]
+    fn test_mailmap_with_alias_precedence() {
+        let repo = TestRepo::builder()
+            .file(".mailmap", "Jane Doe <jane@x.org> <jane@y.org>\nBob <bob@x.org> <bobby@y.org>\n")
+            .commit("Add mailmap")
+            .build()
+            .unwrap();
+        let git = open_repository(repo.path()).unwrap();
+
+        let resolver = IdentityRules::default().resolve(Some(&git)).unwrap();
+        assert_eq!(resolver.resolve("jdoe", "jane@y.org"), ("Jane Doe".to_string(), "jane@x.org".to_string()));
+        assert_eq!(resolver.resolve("bobby", "bobby@y.org"), ("Bob".to_string(), "bob@x.org".to_string()));
+
+        let mut rules = aliases("Robert <robert@x.org>", &["bobby@y.org"]);
+        rules.use_mailmap = true;
+        let resolver = rules.resolve(Some(&git)).unwrap();
+        assert_eq!(resolver.resolve("bobby", "bobby@y.org"), ("Robert".to_string(), "robert@x.org".to_string()));
+
+        rules.use_mailmap = false;
+        let resolver = rules.resolve(Some(&git)).unwrap();
+        assert_eq!(resolver.resolve("jdoe", "jane@y.org"), ("jdoe".to_string(), "jane@y.org".to_string()));
+    }
+}
@@ -0,0 +1,183 @@
+//! Imported Code Attribution
+//!
+//! Vendored libraries and the initial import of a codebase are committed by
+//! whoever ran the import, which lets a handful of commits dominate all-time
+//! contributor statistics. Import rules from the `[imports]` configuration
+//! section name the commits and paths holding imported code; the scanner
+//! reports those commits under a synthetic author (`import` by default), so
+//! contributor metrics stay focused on work done in the project itself.
+//!
+//! - `commits` lists commit IDs and ranges: `SHA`, `BASE..TIP` (commits
+//!   reachable from TIP but not from BASE) or `..TIP` (TIP and all its history)
+//! - `paths` lists directories or files: a commit whose changes all fall under
+//!   them is imported, and blamed lines in them belong to the import author
+//!
+//! ```toml
+//! [imports]
+//! commits = ["..3f2c1a9"]
+//! paths = ["vendor/", "third_party/"]
+//! author = "import"
+//! ```
+
+use crate::scanner::async_engine::error::{ScanError, ScanResult};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Author that imported commits are attributed to unless configured otherwise
+pub const DEFAULT_IMPORT_AUTHOR: &str = "import";
+
+/// Commits and paths whose authorship is replaced by the import author
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRules {
+    /// Commit IDs and ranges, as described in the module documentation
+    pub commits: Vec<String>,
+    /// Directories or files holding imported code
+    pub paths: Vec<String>,
+    /// Synthetic author for imported code
+    pub author: String,
+}
+
+impl Default for ImportRules {
+    fn default() -> Self {
+        Self {
+            commits: Vec::new(),
+            paths: Vec::new(),
+            author: DEFAULT_IMPORT_AUTHOR.to_string(),
+        }
+    }
+}
+
+impl ImportRules {
+    /// Check whether no commits or paths are marked as imported
+    pub fn is_empty(&self) -> bool {
+        self.commits.is_empty() && self.paths.is_empty()
+    }
+
+    /// Check whether a repository path lies under one of the import paths
+    pub fn matches_path(&self, path: &str) -> bool {
+        self.paths.iter().any(|prefix| Path::new(path).starts_with(prefix.trim_end_matches('/')))
+    }
+
+    /// Stable key identifying these rules, used to keep cached scans apart
+    pub fn fingerprint(&self) -> u64 {
+        let key = format!("{}\0{}\0{}", self.commits.join(","), self.paths.join(","), self.author);
+        crate::cache::fnv1a_64(key.as_bytes())
+    }
+
+    /// Resolve the commit specifications against a repository
+    pub fn resolve(&self, repo: &gix::Repository) -> ScanResult<ImportedCode> {
+        let mut commits = HashSet::new();
+        for spec in &self.commits {
+            let invalid = |e: String| ScanError::configuration(format!("Invalid [imports] commit '{spec}': {e}"));
+            match spec.split_once("..") {
+                Some((base, tip)) => {
+                    let tip = resolve_commit(repo, tip).map_err(invalid)?;
+                    let mut walk = repo.rev_walk([tip]);
+                    if !base.trim().is_empty() {
+                        walk = walk.with_hidden([resolve_commit(repo, base).map_err(invalid)?]);
+                    }
+                    for info in walk.all().map_err(|e| invalid(e.to_string()))? {
+                        commits.insert(info.map_err(|e| invalid(e.to_string()))?.id);
+                    }
+                }
+                None => {
+                    commits.insert(resolve_commit(repo, spec).map_err(invalid)?);
+                }
+            }
+        }
+        Ok(ImportedCode { rules: self.clone(), commits })
+    }
+}
+
+/// Import rules resolved to the commits they cover
+#[derive(Debug, Clone)]
+pub struct ImportedCode {
+    rules: ImportRules,
+    commits: HashSet<gix::ObjectId>,
+}
+
+impl ImportedCode {
+    /// Synthetic author for imported code
+    pub fn author(&self) -> &str {
+        &self.rules.author
+    }
+
+    /// Check whether a commit is imported, either listed or touching only import paths
+    pub fn is_imported_commit(&self, commit_id: &gix::oid, changed_paths: &[String]) -> bool {
+        self.commits.contains(commit_id)
+            || (!changed_paths.is_empty() && changed_paths.iter().all(|path| self.rules.matches_path(path)))
+    }
+
+    /// Check whether a commit, given as a hex ID, is one of the listed imports
+    pub fn contains_commit(&self, hash: &str) -> bool {
+        gix::ObjectId::from_hex(hash.as_bytes()).is_ok_and(|id| self.commits.contains(&id))
+    }
+
+    /// Check whether a repository path lies under one of the import paths
+    pub fn matches_path(&self, path: &str) -> bool {
+        self.rules.matches_path(path)
+    }
+}
+
+fn resolve_commit(repo: &gix::Repository, spec: &str) -> Result<gix::ObjectId, String> {
+    let object = repo.rev_parse_single(spec.trim()).map_err(|e| e.to_string())?
+        .object().map_err(|e| e.to_string())?;
+    let commit = object.peel_to_commit().map_err(|e| e.to_string())?;
+    Ok(commit.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::repository::open_repository;
+    use crate::test_support::TestRepo;
+
+    fn rules(commits: &[&str], paths: &[&str]) -> ImportRules {
+        ImportRules {
+            commits: commits.iter().map(|c| c.to_string()).collect(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_import_paths_match_whole_components() {
+        let rules = rules(&[], &["vendor/", "third_party/zlib"]);
+        assert!(rules.matches_path("vendor/lib.c"));
+        assert!(rules.matches_path("third_party/zlib/inflate.c"));
+        assert!(!rules.matches_path("vendored.c"));
+        assert!(!rules.matches_path("src/vendor/lib.c"));
+        assert!(ImportRules::default().is_empty());
+        assert_ne!(rules.fingerprint(), ImportRules::default().fingerprint());
+    }
+
+    #[test]
+    fn test_resolve_commit_ranges() {
+        let repo = TestRepo::builder()
+            .file("a.txt", "imported\n")
+            .commit("Import")
+            .tag("imported")
+            .file("b.txt", "own\n")
+            .commit("Own work")
+            .file("vendor/c.txt", "update\n")
+            .commit("Vendor update")
+            .build()
+            .unwrap();
+        let git = open_repository(repo.path()).unwrap();
+        let head = git.head_id().unwrap().detach();
+        let import = resolve_commit(&git, "imported").unwrap();
+
+        let history = rules(&["..imported"], &["vendor/"]).resolve(&git).unwrap();
+        assert!(history.is_imported_commit(&import, &["a.txt".to_string()]));
+        assert!(history.is_imported_commit(&head, &["vendor/c.txt".to_string()]));
+        assert!(!history.is_imported_commit(&head, &["vendor/c.txt".to_string(), "b.txt".to_string()]));
+        assert!(history.contains_commit(&import.to_string()));
+        assert!(!history.contains_commit(&head.to_string()));
+
+        let range = rules(&["imported..HEAD"], &[]).resolve(&git).unwrap();
+        assert!(range.contains_commit(&head.to_string()));
+        assert!(!range.contains_commit(&import.to_string()));
+
+        assert!(rules(&["no-such-ref"], &[]).resolve(&git).is_err());
+    }
+}
@@ -41,6 +41,17 @@ pub mod query;
 pub mod async_engine;
 pub mod async_traits;
 pub mod branch_detection;
+pub mod encoding;
+pub mod file_policy;
+pub mod fingerprints;
+pub mod generated;
+pub mod identity;
+pub mod imports;
+pub mod patch;
+pub mod pending;
+pub mod presets;
+pub mod repository;
+pub mod shallow;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,548 @@
+//! Patch Series Parsing
+//!
+//! Reads a patch series - the output of `git format-patch`, either as one
+//! mbox file or a directory of `.patch` files - so it can be analysed without
+//! the commits being present in a repository. Each message yields the author,
+//! date and subject of the commit together with the files its diff changes.
+//! Messages without a diff, such as cover letters, are skipped.
+
+use std::path::Path;
+
+use crate::scanner::async_engine::error::{ScanError, ScanResult};
+use crate::scanner::async_engine::events::ChangeType;
+
+/// File extensions read from a patch series directory
+pub const PATCH_EXTENSIONS: [&str; 4] = ["patch", "diff", "mbox", "eml"];
+
+/// A commit reconstructed from one message of a patch series
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchCommit {
+    /// Original commit hash from the mbox `From` line, or the hash of the message
+    pub hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Author date in seconds since the epoch
+    pub timestamp: i64,
+    /// Subject with `[PATCH n/m]` style prefixes removed
+    pub subject: String,
+    pub files: Vec<PatchFile>,
+}
+
+/// A file changed by a patch
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchFile {
+    pub path: String,
+    pub old_path: Option<String>,
+    pub change_type: ChangeType,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub is_binary: bool,
+    pub old_mode: Option<u32>,
+    pub new_mode: Option<u32>,
+}
+
+impl PatchFile {
+    pub(crate) fn new(path: String) -> Self {
+        Self {
+            path,
+            old_path: None,
+            change_type: ChangeType::Modified,
+            insertions: 0,
+            deletions: 0,
+            is_binary: false,
+            old_mode: None,
+            new_mode: None,
+        }
+    }
+}
+
+/// Read a patch series from an mbox file or a directory of patch files
+///
+/// Directory entries are read in file name order, which is the order
+/// `git format-patch` numbers them in. Commits are returned oldest first.
+pub fn read_patch_series(path: &Path) -> ScanResult<Vec<PatchCommit>> {
+    let files = if path.is_dir() {
+        let entries = std::fs::read_dir(path)
+            .map_err(|e| ScanError::configuration(format!("Failed to read patch directory {}: {}", path.display(), e)))?;
+        let mut files: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.is_file() && file.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| PATCH_EXTENSIONS.contains(&ext)))
+            .collect();
+        files.sort();
+        if files.is_empty() {
+            return Err(ScanError::configuration(format!(
+                "No patch files ({}) found in {}",
+                PATCH_EXTENSIONS.map(|ext| format!("*.{ext}")).join(", "),
+                path.display()
+            )));
+        }
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut commits = Vec::new();
+    for file in files {
+        let bytes = std::fs::read(&file)
+            .map_err(|e| ScanError::configuration(format!("Failed to read patch series {}: {}", file.display(), e)))?;
+        let text = String::from_utf8_lossy(&bytes);
+        commits.extend(parse_mbox(&text)
+            .map_err(|e| ScanError::configuration(format!("{}: {}", file.display(), e)))?);
+    }
+    Ok(commits)
+}
+
+/// Parse every message of an mbox, in the order they appear
+pub fn parse_mbox(text: &str) -> Result<Vec<PatchCommit>, String> {
+    let mut commits = Vec::new();
+    for (index, message) in split_mbox(text).into_iter().enumerate() {
+        match parse_message(message).map_err(|e| format!("message {}: {}", index + 1, e))? {
+            Some(commit) => commits.push(commit),
+            None => log::debug!("Skipping patch series message {} without a diff", index + 1),
+        }
+    }
+    Ok(commits)
+}
+
+/// Split an mbox on its `From ` separator lines
+///
+/// A separator starts the text or follows an empty line; a file that does not
+/// start with one is read as a single message.
+fn split_mbox(text: &str) -> Vec<&str> {
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    let mut previous_blank = true;
+    for line in text.split_inclusive('\n') {
+        if previous_blank && line.starts_with("From ") {
+            starts.push(offset);
+        }
+        previous_blank = line.trim_end().is_empty();
+        offset += line.len();
+    }
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+
+    starts.iter()
+        .zip(starts.iter().skip(1).chain(std::iter::once(&text.len())))
+        .map(|(&start, &end)| &text[start..end])
+        .filter(|message| !message.trim().is_empty())
+        .collect()
+}
+
+/// Parse one message, returning None when it carries no diff
+fn parse_message(message: &str) -> Result<Option<PatchCommit>, String> {
+    let mut lines = message.lines().peekable();
+
+    // The separator line of a format-patch mbox carries the original commit hash
+    let mut hash = None;
+    if let Some(separator) = lines.next_if(|line| line.starts_with("From ")) {
+        hash = separator.split_whitespace()
+            .nth(1)
+            .filter(|word| word.len() == 40 && word.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(str::to_lowercase);
+    }
+
+    // Headers run to the first empty line; indented lines continue the previous header
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim_end().is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    let header = |name: &str| headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+
+    let body: Vec<&str> = lines.collect();
+    let Some(diff_start) = body.iter().position(|line| line.starts_with("diff --git ")) else {
+        return Ok(None);
+    };
+    let files = parse_diff(&body[diff_start..]);
+
+    let from = header("from").ok_or("missing From header")?;
+    let (author_name, author_email) = parse_address(&decode_words(from));
+    let date = header("date").ok_or("missing Date header")?;
+    let timestamp = chrono::DateTime::parse_from_rfc2822(date)
+        .map_err(|e| format!("invalid Date header '{date}': {e}"))?
+        .timestamp();
+    let subject = strip_subject_prefixes(&decode_words(header("subject").unwrap_or_default()));
+
+    let hash = match hash {
+        Some(hash) => hash,
+        None => gix::objs::compute_hash(gix::hash::Kind::Sha1, gix::objs::Kind::Blob, message.as_bytes())
+            .map_err(|e| format!("failed to hash message: {e}"))?
+            .to_string(),
+    };
+
+    Ok(Some(PatchCommit { hash, author_name, author_email, timestamp, subject, files }))
+}
+
+/// Count the changes of each file in a git diff
+pub(crate) fn parse_diff(lines: &[&str]) -> Vec<PatchFile> {
+    let mut files = Vec::new();
+    let mut current: Option<PatchFile> = None;
+    let (mut old_remaining, mut new_remaining) = (0usize, 0usize);
+
+    for line in lines {
+        if old_remaining > 0 || new_remaining > 0 {
+            if let Some(file) = current.as_mut() {
+                match line.chars().next() {
+                    Some('+') => {
+                        file.insertions += 1;
+                        new_remaining = new_remaining.saturating_sub(1);
+                    }
+                    Some('-') => {
+                        file.deletions += 1;
+                        old_remaining = old_remaining.saturating_sub(1);
+                    }
+                    Some('\\') => {}
+                    // Mail clients may strip the space from empty context lines
+                    _ => {
+                        old_remaining = old_remaining.saturating_sub(1);
+                        new_remaining = new_remaining.saturating_sub(1);
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            files.extend(current.take());
+            current = Some(PatchFile::new(diff_header_path(paths)));
+            continue;
+        }
+        let Some(file) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(mode) = line.strip_prefix("new file mode ") {
+            file.change_type = ChangeType::Added;
+            file.new_mode = parse_mode(mode);
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+            file.change_type = ChangeType::Deleted;
+            file.old_mode = parse_mode(mode);
+        } else if let Some(mode) = line.strip_prefix("old mode ") {
+            file.old_mode = parse_mode(mode);
+        } else if let Some(mode) = line.strip_prefix("new mode ") {
+            file.new_mode = parse_mode(mode);
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.change_type = ChangeType::Renamed;
+            file.old_path = Some(unquote(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.path = unquote(path);
+        } else if let Some(path) = line.strip_prefix("copy from ") {
+            file.change_type = ChangeType::Copied;
+            file.old_path = Some(unquote(path));
+        } else if let Some(path) = line.strip_prefix("copy to ") {
+            file.path = unquote(path);
+        } else if let Some(index) = line.strip_prefix("index ") {
+            // Unchanged modes are given on the index line
+            if let Some(mode) = index.split_whitespace().nth(1).and_then(parse_mode) {
+                file.old_mode.get_or_insert(mode);
+                file.new_mode.get_or_insert(mode);
+            }
+        } else if line.starts_with("Binary files ") || *line == "GIT binary patch" {
+            file.is_binary = true;
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if path != "/dev/null" {
+                file.path = strip_diff_prefix(&unquote(path), "b/");
+            }
+        } else if let Some(range) = line.strip_prefix("@@ ") {
+            (old_remaining, new_remaining) = parse_hunk_header(range);
+        }
+    }
+    files.extend(current);
+    files
+}
+
+/// Path named by a `diff --git a/<path> b/<path>` header
+///
+/// Both paths are the same unless the file was renamed or copied, in which
+/// case the `rename to`/`copy to` lines that follow give the new path.
+fn diff_header_path(paths: &str) -> String {
+    if paths.starts_with('"') {
+        return paths.rsplit_once(" \"b/")
+            .map(|(_, path)| unquote(&format!("\"{path}")))
+            .unwrap_or_else(|| unquote(paths));
+    }
+    let half = paths.len().saturating_sub(1) / 2;
+    if let (Some(old), Some(new)) = (paths.get(..half), paths.get(half + 1..)) {
+        if old.strip_prefix("a/").is_some_and(|old| Some(old) == new.strip_prefix("b/")) {
+            return new[2..].to_string();
+        }
+    }
+    paths.rsplit_once(" b/")
+        .map(|(_, path)| path.to_string())
+        .unwrap_or_else(|| strip_diff_prefix(paths, "a/"))
+}
+
+fn strip_diff_prefix(path: &str, prefix: &str) -> String {
+    path.strip_prefix(prefix).unwrap_or(path).to_string()
+}
+
+/// Remove the quotes git puts around paths with unusual characters
+fn unquote(path: &str) -> String {
+    match path.strip_prefix('"').and_then(|path| path.strip_suffix('"')) {
+        Some(quoted) => quoted.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => path.to_string(),
+    }
+}
+
+fn parse_mode(mode: &str) -> Option<u32> {
+    u32::from_str_radix(mode.trim(), 8).ok()
+}
+
+/// Old and new line counts of a `-a,b +c,d @@` hunk header
+fn parse_hunk_header(range: &str) -> (usize, usize) {
+    let count = |prefix: char| {
+        range.split_whitespace()
+            .find_map(|part| part.strip_prefix(prefix))
+            .map(|part| part.split_once(',').map_or(Ok(1), |(_, count)| count.parse()).unwrap_or(0))
+            .unwrap_or(0)
+    };
+    (count('-'), count('+'))
+}
+
+/// Remove `[PATCH v2 1/3]` style tags from the start of a subject
+fn strip_subject_prefixes(subject: &str) -> String {
+    let mut subject = subject.trim();
+    while let Some(rest) = subject.strip_prefix('[') {
+        match rest.split_once(']') {
+            Some((_, rest)) => subject = rest.trim_start(),
+            None => break,
+        }
+    }
+    subject.to_string()
+}
+
+/// Split `Name <email>` into its parts
+fn parse_address(address: &str) -> (String, String) {
+    match address.rsplit_once('<') {
+        Some((name, email)) => (
+            name.trim().trim_matches('"').to_string(),
+            email.trim_end().trim_end_matches('>').to_string(),
+        ),
+        None => (address.trim().to_string(), address.trim().to_string()),
+    }
+}
+
+/// Decode RFC 2047 encoded words, as git uses for non-ASCII names and subjects
+fn decode_words(value: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let word = rest[start + 2..].splitn(3, '?').collect::<Vec<_>>();
+        let decoded_word = match word.as_slice() {
+            [_charset, encoding, text] => text.find("?=").and_then(|end| {
+                let bytes = match encoding.to_ascii_lowercase().as_str() {
+                    "q" => Some(decode_q(&text[..end])),
+                    "b" => decode_base64(&text[..end]),
+                    _ => None,
+                };
+                bytes.map(|bytes| (String::from_utf8_lossy(&bytes).into_owned(), end))
+            }),
+            _ => None,
+        };
+        let Some((text, end)) = decoded_word else {
+            break;
+        };
+
+        // Whitespace between adjacent encoded words is not part of the text
+        let between = &rest[..start];
+        if !(after_word && between.trim().is_empty()) {
+            decoded.push_str(between);
+        }
+        decoded.push_str(&text);
+        let consumed = start + 2 + word[0].len() + word[1].len() + 2 + end + 2;
+        rest = &rest[consumed..];
+        after_word = true;
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_q(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut chars = text.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex: Vec<u8> = chars.by_ref().take(2).collect();
+                match std::str::from_utf8(&hex).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => bytes.push(byte),
+                    None => {
+                        bytes.push(b'=');
+                        bytes.extend(hex);
+                    }
+                }
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    bytes
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in text.bytes().filter(|byte| *byte != b'=') {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIES: &str = "\
+From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001
+From: =?UTF-8?q?J=C3=B6rg=20Example?= <jorg@example.com>
+Date: Tue, 3 Oct 2023 10:00:00 +0200
+Subject: [PATCH v2 1/2] Add parser and
+ rename helper
+
+Longer description.
+
+Signed-off-by: Jorg Example <jorg@example.com>
+---
+ src/parser.rs | 3 +++
+ 2 files changed
+
+diff --git a/src/parser.rs b/src/parser.rs
+new file mode 100644
+index 0000000..1111111
+--- /dev/null
++++ b/src/parser.rs
+@@ -0,0 +1,3 @@
++fn parse() {
++-- not a header
++}
+diff --git a/src/old name.rs b/src/new name.rs
+similarity index 90%
+rename from src/old name.rs
+rename to src/new name.rs
+index 2222222..3333333 100755
+--- a/src/old name.rs
++++ b/src/new name.rs
+@@ -1,3 +1,2 @@
+ fn helper() {
+--- removed
+ }
+--
+2.40.0
+
+From fedcba9876543210fedcba9876543210fedcba98 Mon Sep 17 00:00:00 2001
+From: Ann Other <ann@example.com>
+Date: Wed, 4 Oct 2023 09:30:00 +0000
+Subject: [PATCH v2 2/2] Drop logo
+
+---
+diff --git a/logo.png b/logo.png
+deleted file mode 100644
+index 4444444..0000000
+Binary files a/logo.png and /dev/null differ
+--
+2.40.0
+";
+
+    #[test]
+    fn test_parse_format_patch_series() {
+        let commits = parse_mbox(SERIES).unwrap();
+        assert_eq!(commits.len(), 2);
+
+        let first = &commits[0];
+        assert_eq!(first.hash, "0123456789abcdef0123456789abcdef01234567");
+        assert_eq!(first.author_name, "Jörg Example");
+        assert_eq!(first.author_email, "jorg@example.com");
+        assert_eq!(first.timestamp, 1_696_320_000);
+        assert_eq!(first.subject, "Add parser and rename helper");
+        assert_eq!(first.files, vec![
+            PatchFile {
+                change_type: ChangeType::Added,
+                insertions: 3,
+                new_mode: Some(0o100644),
+                ..PatchFile::new("src/parser.rs".to_string())
+            },
+            PatchFile {
+                old_path: Some("src/old name.rs".to_string()),
+                change_type: ChangeType::Renamed,
+                deletions: 1,
+                old_mode: Some(0o100755),
+                new_mode: Some(0o100755),
+                ..PatchFile::new("src/new name.rs".to_string())
+            },
+        ]);
+
+        let second = &commits[1];
+        assert_eq!(second.subject, "Drop logo");
+        assert_eq!(second.files, vec![PatchFile {
+            change_type: ChangeType::Deleted,
+            is_binary: true,
+            old_mode: Some(0o100644),
+            ..PatchFile::new("logo.png".to_string())
+        }]);
+    }
+
+    #[test]
+    fn test_cover_letter_is_skipped_and_missing_hash_is_derived() {
+        let cover = "From: A <a@example.com>\nDate: Tue, 3 Oct 2023 10:00:00 +0000\nSubject: [PATCH 0/1] Series\n\nCover letter\n";
+        assert!(parse_mbox(cover).unwrap().is_empty());
+
+        let patch = "From: A <a@example.com>\nDate: Tue, 3 Oct 2023 10:00:00 +0000\nSubject: Fix\n\n---\ndiff --git a/a b/a\n@@ -1 +1 @@\n-a\n+b\n";
+        let commits = parse_mbox(patch).unwrap();
+        assert_eq!(commits[0].hash.len(), 40);
+        assert_eq!((commits[0].files[0].insertions, commits[0].files[0].deletions), (1, 1));
+    }
+
+    #[test]
+    fn test_invalid_date_names_message() {
+        let patch = "From: A <a@example.com>\nDate: yesterday\n\ndiff --git a/a b/a\n";
+        let error = parse_mbox(patch).unwrap_err();
+        assert!(error.starts_with("message 1: invalid Date header"), "{error}");
+    }
+
+    #[test]
+    fn test_decode_encoded_words() {
+        assert_eq!(decode_words("=?utf-8?b?SsO2cmc=?= =?utf-8?q?_X?= <x@y>"), "Jörg X <x@y>");
+        assert_eq!(decode_words("Plain"), "Plain");
+    }
+
+    #[test]
+    fn test_read_patch_directory_in_name_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, second) = SERIES.split_at(SERIES.find("\nFrom fedcba").unwrap() + 1);
+        std::fs::write(dir.path().join("0002-drop-logo.patch"), second).unwrap();
+        std::fs::write(dir.path().join("0001-add-parser.patch"), first).unwrap();
+        std::fs::write(dir.path().join("README"), "not a patch").unwrap();
+
+        let subjects: Vec<String> = read_patch_series(dir.path()).unwrap().into_iter().map(|c| c.subject).collect();
+        assert_eq!(subjects, vec!["Add parser and rename helper", "Drop logo"]);
+        assert!(read_patch_series(&dir.path().join("missing.mbox")).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Pending Changes
+//!
+//! `--staged` and `--working-tree` analyse the changes that have not been
+//! committed yet, as a pre-commit or pre-push hook would see them. The diff
+//! against `HEAD` is read as one synthetic "pending commit" by the current
+//! user, with the null object id git uses for uncommitted lines, so every
+//! plugin reports on it as it would on a real commit: the files and line counts
+//! it touches, mode changes, and, for plugins reading file content, the staged
+//! or working-tree content of the touched files. Combined with `--fail-on` this
+//! makes gstats a local quality gate.
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use gix::bstr::ByteSlice;
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::async_engine::error::{ScanError, ScanResult};
+use crate::scanner::async_engine::events::ChangeType;
+use crate::scanner::patch::{parse_diff, PatchCommit, PatchFile};
+use crate::scanner::repository::command_dir;
+
+/// Object id git reports for changes that are not committed
+pub const PENDING_HASH: &str = "0000000000000000000000000000000000000000";
+
+/// Tree without entries, compared against when the repository has no commits yet
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Which uncommitted changes are analysed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PendingChanges {
+    /// Changes added to the index, as `git commit` would record them
+    Staged,
+    /// Every change in the working tree, staged or not, including untracked files
+    WorkingTree,
+}
+
+impl PendingChanges {
+    /// Subject of the synthetic commit
+    pub fn subject(&self) -> &'static str {
+        match self {
+            PendingChanges::Staged => "Staged changes",
+            PendingChanges::WorkingTree => "Working tree changes",
+        }
+    }
+
+    /// Read the pending changes of `repo` as one commit
+    pub fn read(&self, repo: &gix::Repository) -> ScanResult<PatchCommit> {
+        if repo.worktree().is_none() {
+            return Err(ScanError::configuration("Pending changes need a working tree; the repository is bare"));
+        }
+        let base = if repo.head_id().is_ok() { "HEAD" } else { EMPTY_TREE };
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff", "--no-textconv", "-M", "--src-prefix=a/", "--dst-prefix=b/"];
+        match self {
+            PendingChanges::Staged => args.extend(["--cached", base]),
+            PendingChanges::WorkingTree => args.push(base),
+        }
+        let diff = git(repo, &args)?;
+        let mut files = parse_diff(&diff.lines().collect::<Vec<_>>());
+        if *self == PendingChanges::WorkingTree {
+            files.extend(untracked_files(repo)?);
+        }
+
+        let (author_name, author_email) = match repo.author() {
+            Some(Ok(author)) => (author.name.to_string(), author.email.to_string()),
+            _ => ("Not Committed Yet".to_string(), "not.committed.yet".to_string()),
+        };
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs() as i64);
+        Ok(PatchCommit {
+            hash: PENDING_HASH.to_string(),
+            author_name,
+            author_email,
+            timestamp,
+            subject: self.subject().to_string(),
+            files,
+        })
+    }
+
+    /// Pending content of a file: its staged blob, or the file in the working tree
+    ///
+    /// Returns `None` for files that are not present.
+    pub fn content(&self, repo: &gix::Repository, path: &str) -> Option<Vec<u8>> {
+        match self {
+            PendingChanges::Staged => {
+                let index = repo.index_or_empty().ok()?;
+                let entry = index.entry_by_path(path.as_bytes().as_bstr())?;
+                repo.find_object(entry.id).ok()?.try_into_blob().ok().map(|blob| blob.detach().data)
+            }
+            PendingChanges::WorkingTree => std::fs::read(repo.worktree()?.base().join(path)).ok(),
+        }
+    }
+}
+
+/// Untracked files that are not ignored, as added files
+fn untracked_files(repo: &gix::Repository) -> ScanResult<Vec<PatchFile>> {
+    let listing = git(repo, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+    let root = command_dir(repo);
+    Ok(listing.split('\0').filter(|path| !path.is_empty()).map(|path| {
+        let mut file = PatchFile::new(path.to_string());
+        file.change_type = ChangeType::Added;
+        match std::fs::read(root.join(path)) {
+            Ok(data) if data[..data.len().min(8192)].contains(&0) => file.is_binary = true,
+            Ok(data) => file.insertions = data.lines().count(),
+            Err(e) => log::warn!("Failed to read untracked file {path}: {e}"),
+        }
+        file
+    }).collect())
+}
+
+fn git(repo: &gix::Repository, args: &[&str]) -> ScanResult<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(command_dir(repo))
+        .output()
+        .map_err(|e| ScanError::Repository(format!("Failed to run git {}: {e}", args[0])))?;
+    if !output.status.success() {
+        return Err(ScanError::Repository(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::repository::open_repository;
+    use crate::test_support::TestRepo;
+
+    #[test]
+    fn test_staged_and_working_tree_changes() {
+        let repo = TestRepo::builder()
+            .file("src/lib.rs", "pub fn one() {}\n")
+            .file("README.md", "# Project\n")
+            .commit("Initial commit")
+            .build()
+            .unwrap();
+        std::fs::write(repo.path().join("src/lib.rs"), "pub fn one() {}\npub fn two() {}\n").unwrap();
+        repo.git(&["add", "src/lib.rs"], &[]).unwrap();
+        std::fs::write(repo.path().join("README.md"), "# Renamed project\n").unwrap();
+        std::fs::write(repo.path().join("NOTES.md"), "one\ntwo\n").unwrap();
+        let git_repo = open_repository(repo.path()).unwrap();
+
+        let staged = PendingChanges::Staged.read(&git_repo).unwrap();
+        assert_eq!(staged.hash, PENDING_HASH);
+        assert_eq!(staged.subject, "Staged changes");
+        let files: Vec<_> = staged.files.iter().map(|file| (file.path.as_str(), file.insertions, file.deletions)).collect();
+        assert_eq!(files, vec![("src/lib.rs", 1, 0)]);
+
+        let working_tree = PendingChanges::WorkingTree.read(&git_repo).unwrap();
+        let mut files: Vec<_> = working_tree.files.iter()
+            .map(|file| (file.path.as_str(), file.change_type.clone(), file.insertions, file.deletions))
+            .collect();
+        files.sort_by_key(|file| file.0);
+        assert_eq!(files, vec![
+            ("NOTES.md", ChangeType::Added, 2, 0),
+            ("README.md", ChangeType::Modified, 1, 1),
+            ("src/lib.rs", ChangeType::Modified, 1, 0),
+        ]);
+
+        assert_eq!(PendingChanges::Staged.content(&git_repo, "README.md").unwrap(), b"# Project\n");
+        assert_eq!(PendingChanges::WorkingTree.content(&git_repo, "README.md").unwrap(), b"# Renamed project\n");
+        assert_eq!(PendingChanges::Staged.content(&git_repo, "NOTES.md"), None);
+    }
+}
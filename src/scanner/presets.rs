@@ -0,0 +1,146 @@
+//! Exclude Presets
+//!
+//! Repositories for most ecosystems carry build output, installed dependencies
+//! and lockfiles that dwarf the hand-written code in any report. A preset names
+//! the paths that are noise for one ecosystem, so `--preset rust` (or
+//! `presets = ["rust"]` in the `[scanner]` section) excludes them without the
+//! user having to list them. Presets combine with each other and with
+//! `--exclude-path`.
+//!
+//! Some files are worth keeping in a scan but not worth their full size, such
+//! as database migrations and generated bindings that are committed but rarely
+//! written by hand. A preset gives those paths a weight instead: their line
+//! changes are scaled by it, so they still show up in the history without
+//! dominating churn and size metrics.
+//!
+//! Preset paths are matched like `--exclude-path`: a file is excluded, or
+//! weighted, when its path matches one of them.
+
+/// A named set of paths to exclude from a scan or to weight down
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExcludePreset {
+    /// Name used with `--preset`
+    pub name: &'static str,
+    /// What the preset leaves out
+    pub description: &'static str,
+    /// Paths excluded by the preset
+    pub paths: &'static [&'static str],
+    /// Paths kept in the scan with their line changes scaled by a weight
+    pub weights: &'static [(&'static str, f64)],
+}
+
+/// All available presets
+pub const PRESETS: &[ExcludePreset] = &[
+    ExcludePreset {
+        name: "rust",
+        description: "Cargo build output, vendored crates and the lockfile",
+        paths: &["target/", "vendor/", "Cargo.lock"],
+        weights: &[("*.snap", 0.25)],
+    },
+    ExcludePreset {
+        name: "node",
+        description: "Installed packages, bundles, coverage reports and lockfiles",
+        paths: &[
+            "node_modules/",
+            "dist/",
+            "build/",
+            "coverage/",
+            ".next/",
+            "package-lock.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+        ],
+        weights: &[("__generated__/", 0.1), ("__snapshots__/", 0.25)],
+    },
+    ExcludePreset {
+        name: "python",
+        description: "Virtual environments, caches and packaging output; migrations and protobuf bindings weighted down",
+        paths: &[
+            ".venv/",
+            "venv/",
+            "__pycache__/",
+            ".tox/",
+            "*.egg-info/",
+            "dist/",
+            "build/",
+            "poetry.lock",
+        ],
+        weights: &[("migrations/", 0.1), ("*_pb2.py", 0.1)],
+    },
+];
+
+/// Find a preset by name (case-insensitive)
+pub fn find(name: &str) -> Option<&'static ExcludePreset> {
+    PRESETS.iter().find(|preset| preset.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Names of all presets, for help and error messages
+pub fn names() -> Vec<&'static str> {
+    PRESETS.iter().map(|preset| preset.name).collect()
+}
+
+/// Paths excluded by the named presets, without duplicates
+///
+/// Returns the first unknown name as the error.
+pub fn excluded_paths<S: AsRef<str>>(names: &[S]) -> Result<Vec<&'static str>, String> {
+    let mut paths: Vec<&'static str> = Vec::new();
+    for name in names {
+        let preset = find(name.as_ref()).ok_or_else(|| name.as_ref().to_string())?;
+        for path in preset.paths {
+            if !paths.contains(path) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Weighted paths of the named presets, each with the lowest weight any of them gives it
+///
+/// Returns the first unknown name as the error.
+pub fn weighted_paths<S: AsRef<str>>(names: &[S]) -> Result<Vec<(&'static str, f64)>, String> {
+    let mut weights: Vec<(&'static str, f64)> = Vec::new();
+    for name in names {
+        let preset = find(name.as_ref()).ok_or_else(|| name.as_ref().to_string())?;
+        for (path, weight) in preset.weights {
+            match weights.iter_mut().find(|(existing, _)| existing == path) {
+                Some((_, existing)) => *existing = existing.min(*weight),
+                None => weights.push((path, *weight)),
+            }
+        }
+    }
+    Ok(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_combine_without_duplicates() {
+        let paths = excluded_paths(&["node", "Python"]).unwrap();
+        assert!(paths.contains(&"node_modules/"));
+        assert!(paths.contains(&".venv/"));
+        assert_eq!(paths.iter().filter(|path| **path == "dist/").count(), 1);
+    }
+
+    #[test]
+    fn test_presets_weight_paths_they_keep() {
+        let weights = weighted_paths(&["python", "rust"]).unwrap();
+        assert!(weights.contains(&("migrations/", 0.1)));
+        assert!(weights.contains(&("*.snap", 0.25)));
+        // A weighted path is scanned, not excluded
+        assert!(!excluded_paths(&["python"]).unwrap().contains(&"migrations/"));
+        for preset in PRESETS {
+            assert!(preset.weights.iter().all(|(_, weight)| (0.0..1.0).contains(weight)));
+        }
+        assert_eq!(weighted_paths(&["cobol"]), Err("cobol".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_preset_is_reported() {
+        assert_eq!(excluded_paths(&["rust", "cobol"]), Err("cobol".to_string()));
+        assert!(excluded_paths::<&str>(&[]).unwrap().is_empty());
+        assert_eq!(names(), vec!["rust", "node", "python"]);
+    }
+}
@@ -2,10 +2,13 @@
 //! 
 //! Query parameter structures for filtering scenarios.
 
+use std::collections::HashMap;
 use std::time::SystemTime;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use crate::scanner::branch_detection::BranchDetectionResult;
+use crate::scanner::shallow::ShallowHistory;
 
 /// Query parameters for repository scanning
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -20,6 +23,15 @@ pub struct QueryParams {
     pub authors: AuthorFilter,
     /// Branch to scan (if None, uses default branch detection)
     pub branch: Option<String>,
+    /// Scan the union of all local and remote-tracking branches, starting with `branch`
+    #[serde(default)]
+    pub all_branches: bool,
+    /// Ref the scan starts from once resolved; not part of the query, so never cached
+    #[serde(skip)]
+    pub scanned_ref: Option<BranchDetectionResult>,
+    /// History available when the repository is a shallow clone
+    #[serde(skip)]
+    pub shallow: Option<ShallowHistory>,
 }
 
 /// Date range specification
@@ -67,6 +79,51 @@ impl DateRange {
         let before_end = self.end.is_none_or(|end| time <= end);
         after_start && before_end
     }
+    
+    /// Start of the range as seconds since the unix epoch
+    pub fn start_seconds(&self) -> Option<i64> {
+        self.start.map(system_time_to_seconds)
+    }
+    
+    /// End of the range as seconds since the unix epoch
+    pub fn end_seconds(&self) -> Option<i64> {
+        self.end.map(system_time_to_seconds)
+    }
+    
+    /// Describe the effective range as `since`/`until` ISO dates for export metadata
+    pub fn to_metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        if let Some(start) = self.start {
+            metadata.insert("since".to_string(), format_iso_date(start));
+        }
+        if let Some(end) = self.end {
+            metadata.insert("until".to_string(), format_iso_date(end));
+        }
+        metadata
+    }
+    
+    /// Human-readable description of the range, e.g. "2024-01-01 to 2024-06-30"
+    pub fn describe(&self) -> String {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => format!("{} to {}", format_iso_date(start), format_iso_date(end)),
+            (Some(start), None) => format!("since {}", format_iso_date(start)),
+            (None, Some(end)) => format!("until {}", format_iso_date(end)),
+            (None, None) => "all history".to_string(),
+        }
+    }
+}
+
+/// Convert a SystemTime to signed seconds since the unix epoch
+fn system_time_to_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+/// Format a SystemTime as an ISO 8601 date (UTC)
+fn format_iso_date(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).format("%Y-%m-%d").to_string()
 }
 
 /// File path filtering configuration
@@ -76,6 +133,17 @@ pub struct FilePathFilter {
     pub include: Vec<PathBuf>,
     /// Paths to exclude
     pub exclude: Vec<PathBuf>,
+    /// Paths whose line changes count at a fraction of their size
+    #[serde(default)]
+    pub weights: Vec<PathWeight>,
+}
+
+/// A path pattern and the factor its files' line changes are scaled by
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PathWeight {
+    pub path: PathBuf,
+    /// Between 0 (not counted) and 1 (counted in full)
+    pub weight: f64,
 }
 
 /// Author filtering configuration
@@ -87,6 +155,16 @@ pub struct AuthorFilter {
     pub exclude: Vec<String>,
 }
 
+impl AuthorFilter {
+    /// Check whether an author passes the filter (substring or `/regex/` match on name or email)
+    ///
+    /// Compiles the patterns on every call; see [`AuthorMatcher`](crate::scanner::filters::AuthorMatcher)
+    /// for repeated checks.
+    pub fn matches(&self, name: &str, email: &str) -> bool {
+        crate::scanner::filters::AuthorMatcher::new(self).matches(name, email)
+    }
+}
+
 /// Query parameter validation errors
 #[derive(Error, Debug, PartialEq)]
 pub enum QueryValidationError {
@@ -116,6 +194,11 @@ impl QueryParams {
         self.date_range.is_some()
     }
     
+    /// Check if this query has path include or exclude filters
+    pub fn has_path_filter(&self) -> bool {
+        !self.file_paths.include.is_empty() || !self.file_paths.exclude.is_empty()
+    }
+    
     /// Check if this query has a limit constraint
     pub fn has_limit(&self) -> bool {
         self.limit.is_some()
@@ -260,6 +343,27 @@ mod tests {
         assert!(unbounded_end.contains(after_time));
     }
 
+    #[test]
+    fn test_date_range_seconds_and_metadata() {
+        let start_time = UNIX_EPOCH + Duration::from_secs(1_704_067_200); // 2024-01-01
+        let end_time = UNIX_EPOCH + Duration::from_secs(1_719_705_600); // 2024-06-30
+        
+        let range = DateRange::new(start_time, end_time);
+        assert_eq!(range.start_seconds(), Some(1_704_067_200));
+        assert_eq!(range.end_seconds(), Some(1_719_705_600));
+        
+        let metadata = range.to_metadata();
+        assert_eq!(metadata.get("since"), Some(&"2024-01-01".to_string()));
+        assert_eq!(metadata.get("until"), Some(&"2024-06-30".to_string()));
+        
+        assert_eq!(range.describe(), "2024-01-01 to 2024-06-30");
+        
+        let open_ended = DateRange::from(start_time);
+        assert!(open_ended.to_metadata().contains_key("since"));
+        assert!(!open_ended.to_metadata().contains_key("until"));
+        assert_eq!(open_ended.describe(), "since 2024-01-01");
+    }
+
     #[test] 
     fn test_query_params_with_branch_field() {
         let mut params = QueryParams::default();
@@ -296,4 +400,24 @@ mod tests {
         assert!(params_with_branch.has_branch());
         assert_eq!(params_with_branch.effective_branch(), Some("main"));
     }
+
+    #[test]
+    fn test_author_filter_matches() {
+        let filter = AuthorFilter {
+            include: vec!["example.com".to_string()],
+            exclude: vec!["bot".to_string()],
+        };
+        assert!(filter.matches("Alice", "alice@example.com"));
+        assert!(!filter.matches("Alice", "alice@other.org"));
+        assert!(!filter.matches("ci-bot", "ci@example.com"));
+        assert!(AuthorFilter::default().matches("anyone", "anyone@anywhere"));
+
+        let regex = AuthorFilter {
+            include: vec!["/^(alice|bob)@/".to_string()],
+            exclude: vec!["/\\[bot\\]$/".to_string()],
+        };
+        assert!(regex.matches("Bob", "bob@example.com"));
+        assert!(!regex.matches("Carol", "carol@example.com"));
+        assert!(!regex.matches("alice[bot]", "alice@example.com"));
+    }
 }
@@ -0,0 +1,73 @@
+//! Repository Access
+//!
+//! Opens the repository to scan. A path naming a git directory - a bare
+//! repository such as a server-side mirror, or the `.git` directory of a
+//! checkout - is opened as is, without searching parent directories. Any other
+//! path is treated as a location inside a working tree and discovered upwards
+//! the way `git` does.
+
+use std::path::Path;
+use thiserror::Error;
+
+/// Repository open errors
+#[derive(Debug, Error)]
+pub enum RepositoryError {
+    #[error("{0}")]
+    Open(#[from] Box<gix::open::Error>),
+
+    #[error("{0}")]
+    Discover(#[from] Box<gix::discover::Error>),
+}
+
+/// Check whether a path is a git directory rather than a working tree
+///
+/// A git directory holds `HEAD`, `objects` and `refs` directly; this is the
+/// layout of bare repositories and of the `.git` directory inside a checkout.
+pub fn is_git_dir(path: &Path) -> bool {
+    path.join("HEAD").is_file() && path.join("objects").is_dir() && path.join("refs").is_dir()
+}
+
+/// Open the repository at `path`, which may be a git directory or a working tree
+pub fn open_repository(path: &Path) -> Result<gix::Repository, RepositoryError> {
+    if is_git_dir(path) {
+        Ok(gix::open(path).map_err(Box::new)?)
+    } else {
+        Ok(gix::discover(path).map_err(Box::new)?)
+    }
+}
+
+/// Directory to run `git` subprocesses in: the working tree, or the git
+/// directory itself for bare repositories
+pub fn command_dir(repo: &gix::Repository) -> &Path {
+    repo.worktree().map(|worktree| worktree.base()).unwrap_or(repo.git_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestRepo;
+
+    #[test]
+    fn test_open_bare_and_worktree_repositories() {
+        let repo = TestRepo::builder()
+            .file("README.md", "hello\n")
+            .commit("Initial commit")
+            .build()
+            .unwrap();
+        let worktree = repo.path();
+        let bare = repo.bare_clone("mirror.git").unwrap();
+
+        assert!(is_git_dir(&bare));
+        assert!(is_git_dir(&repo.git_dir()));
+        assert!(!is_git_dir(worktree));
+
+        let opened = open_repository(&bare).unwrap();
+        assert!(opened.is_bare());
+        assert_eq!(command_dir(&opened), opened.git_dir());
+        assert!(opened.head_id().is_ok());
+
+        let opened = open_repository(&repo.git_dir()).unwrap();
+        assert!(!opened.is_bare());
+        assert!(open_repository(worktree).is_ok());
+    }
+}
@@ -0,0 +1,107 @@
+//! Shallow Clone Detection
+//!
+//! CI checkouts are often shallow (`git clone --depth 1`): history stops at
+//! graft points recorded in `.git/shallow`, and every statistic built from
+//! the history silently covers only the commits that were fetched. The scan
+//! detects this up front, warns with the depth of history available, records
+//! the limitation in exported metadata and, with `--unshallow`, fetches the
+//! rest of the history before scanning.
+
+use std::collections::HashMap;
+use std::process::Command;
+use crate::scanner::repository::command_dir;
+
+/// History available in a shallow clone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShallowHistory {
+    /// Commits whose parents were not fetched
+    pub boundaries: usize,
+    /// Commits reachable from the scanned tip before history stops
+    pub depth: usize,
+}
+
+impl ShallowHistory {
+    /// Detect whether `repo` is shallow, measuring the depth from `tip`
+    ///
+    /// Returns `None` for repositories with complete history.
+    pub fn detect(repo: &gix::Repository, tip: &str) -> Result<Option<Self>, String> {
+        if !repo.is_shallow() {
+            return Ok(None);
+        }
+        let boundaries = repo.shallow_commits()
+            .map_err(|e| format!("Failed to read shallow commits: {e}"))?
+            .map_or(0, |commits| commits.len());
+        let tip = gix::ObjectId::from_hex(tip.as_bytes())
+            .map_err(|e| format!("Invalid commit id {tip}: {e}"))?;
+        let depth = repo.rev_walk([tip]).all()
+            .map_err(|e| format!("Failed to walk history: {e}"))?
+            .filter(Result::is_ok)
+            .count();
+        Ok(Some(Self { boundaries, depth }))
+    }
+
+    /// Warning reported when the scan starts and with its results
+    pub fn warning(&self) -> String {
+        format!(
+            "Repository is a shallow clone: history stops after {} commit{} ({} graft point{}); \
+             results cover only that history, run with --unshallow to fetch the rest",
+            self.depth, if self.depth == 1 { "" } else { "s" },
+            self.boundaries, if self.boundaries == 1 { "" } else { "s" },
+        )
+    }
+
+    /// Export metadata recording the limitation
+    pub fn to_metadata(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("shallow".to_string(), "true".to_string()),
+            ("shallow_depth".to_string(), self.depth.to_string()),
+            ("shallow_boundaries".to_string(), self.boundaries.to_string()),
+        ])
+    }
+}
+
+/// Fetch the missing history of a shallow clone with `git fetch --unshallow`
+pub fn unshallow(repo: &gix::Repository) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(["fetch", "--quiet", "--unshallow"])
+        .current_dir(command_dir(repo))
+        .output()
+        .map_err(|e| format!("Failed to run git fetch: {e}"))?;
+    if !output.status.success() {
+        return Err(format!("git fetch --unshallow failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::repository::open_repository;
+    use crate::test_support::TestRepo;
+
+    #[test]
+    fn test_shallow_clone_is_detected_and_unshallowed() {
+        let repo = TestRepo::builder()
+            .file("README.md", "one\n")
+            .commit("First commit")
+            .file("README.md", "two\n")
+            .commit("Second commit")
+            .file("README.md", "three\n")
+            .commit("Third commit")
+            .build()
+            .unwrap();
+        let tip = repo.rev_parse("HEAD").unwrap();
+
+        let full = open_repository(repo.path()).unwrap();
+        assert_eq!(ShallowHistory::detect(&full, &tip).unwrap(), None);
+
+        let clone = repo.shallow_clone("ci", 2).unwrap();
+        let shallow = ShallowHistory::detect(&open_repository(&clone).unwrap(), &tip).unwrap().unwrap();
+        assert_eq!(shallow, ShallowHistory { boundaries: 1, depth: 2 });
+        assert!(shallow.warning().contains("after 2 commits"));
+        assert_eq!(shallow.to_metadata().get("shallow_depth").map(String::as_str), Some("2"));
+
+        unshallow(&open_repository(&clone).unwrap()).unwrap();
+        assert_eq!(ShallowHistory::detect(&open_repository(&clone).unwrap(), &tip).unwrap(), None);
+    }
+}
@@ -2,7 +2,6 @@
 //! 
 //! Simple tests for core scanner functionality.
 
-use std::sync::Arc;
 use std::path::PathBuf;
 use crate::scanner::config::ScannerConfig;
 use crate::plugin::SharedPluginRegistry;
@@ -12,8 +11,8 @@ async fn test_scanner_config_creation() {
     let config = ScannerConfig::default();
     
     // Verify default configuration
-    assert!(!config.include_binary_files);
-    assert_eq!(config.max_file_size, 10 * 1024 * 1024); // 10MB default
+    assert_eq!(config.max_memory_bytes, 64 * 1024 * 1024); // 64MB default
+    assert_eq!(config.queue_size, 1000);
 }
 
 #[tokio::test]
@@ -22,7 +21,7 @@ async fn test_plugin_registry_creation() {
     
     // Verify registry starts empty
     let inner = registry.inner().read().await;
-    assert_eq!(inner.len(), 0);
+    assert_eq!(inner.plugin_count(), 0);
 }
 
 #[tokio::test]
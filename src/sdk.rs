@@ -0,0 +1,87 @@
+//! Plugin SDK
+//!
+//! The supported surface for plugins built outside gstats. Everything a plugin
+//! needs is re-exported here, so an external plugin imports from `gstats::sdk`
+//! alone:
+//!
+//! ```no_run
+//! use gstats::sdk::*;
+//!
+//! struct WordCount {
+//!     info: PluginInfo,
+//! }
+//!
+//! #[async_trait]
+//! impl Plugin for WordCount {
+//!     fn plugin_info(&self) -> &PluginInfo {
+//!         &self.info
+//!     }
+//!
+//!     async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+//!         Ok(())
+//!     }
+//!
+//!     async fn execute(&self, _request: PluginRequest) -> PluginResult<PluginResponse> {
+//!         Err(PluginError::execution_failed("Unsupported request type"))
+//!     }
+//!
+//!     async fn cleanup(&mut self) -> PluginResult<()> {
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! Items here change only with a major release of gstats; the traits and data
+//! types come from the `gstats-plugin-api` crate, which follows semver on its own. The
+//! other gstats modules are application internals, hidden from the
+//! documentation, and may move or change in any release.
+
+// The attribute the plugin traits are declared with
+pub use async_trait::async_trait;
+
+// Crates whose types appear in the plugin traits, at the versions gstats uses
+pub use clap;
+pub use serde_json;
+
+// Plugin traits
+pub use gstats_plugin_api::traits::{
+    ConsumerPlugin, ConsumerPreferences, MessageConsumer, Plugin, PluginArgDefinition, PluginClapParser,
+    PluginClapParserExt, PluginDataRequirements, PluginState,
+};
+
+// Identity, capabilities and errors
+pub use gstats_plugin_api::error::{PluginError, PluginResult};
+pub use gstats_plugin_api::metadata::{PluginCapability, PluginDependency, PluginFunction, PluginInfo, PluginType};
+
+// Invocation
+pub use gstats_plugin_api::context::{ExecutionMetadata, InvocationType, PluginRequest, PluginResponse, ScanContext};
+pub use gstats_plugin_api::settings::PluginSettings;
+
+// Scan messages and the events of the queue they are read from
+pub use gstats_plugin_api::events::QueueEvent;
+pub use gstats_plugin_api::messages::{ChangeType, FileChangeData, LineAttribution, MessageData, MessageHeader, ScanMessage};
+
+// Publishing results
+pub use crate::notifications::events::PluginEvent;
+pub use crate::notifications::traits::{NotificationManager, Publisher};
+pub use crate::notifications::AsyncNotificationManager;
+pub use gstats_plugin_api::data_export::{
+    findings_export, ColumnDef, ColumnType, DataExportType, DataPayload, DataSchema, ExportFormat, ExportHints,
+    Finding, FindingSeverity, Matrix, PluginDataExport, PluginDataExportBuilder, Row, TreeNode, Value,
+};
+
+// Shared library entry point
+pub use crate::declare_plugin;
+
+/// Plugin API version of this build, which plugins declare in their [`PluginInfo`]
+pub fn api_version() -> u32 {
+    crate::scanner::version::get_api_version() as u32
+}
+
+/// Items [`declare_plugin!`] expands to, kept here so the macro does not
+/// depend on internal module paths
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::plugin::dynamic::{PluginEntry, GSTATS_VERSION, PLUGIN_ABI_VERSION};
+    pub use crate::scanner::version::BASE_API_VERSION;
+}
@@ -0,0 +1,160 @@
+//! Scan Shutdown Control
+//!
+//! A scan can be stopped before it finishes, by pressing Ctrl-C or by code
+//! calling [`ShutdownController::abort`]. The first request starts a graceful
+//! shutdown: the scanner stops reading history, the queue delivers the messages
+//! it already holds without waiting for room, and plugins finish and export what
+//! they have seen, so partial results are kept. A second Ctrl-C within
+//! [`FORCE_QUIT_WINDOW`] quits at once, discarding queued messages and any
+//! results not yet exported.
+//!
+//! One controller is shared by the scanner, the queue and the plugin registry of
+//! a run; clones refer to the same shutdown state.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Time after a Ctrl-C within which a second one quits immediately
+pub const FORCE_QUIT_WINDOW: Duration = Duration::from_secs(3);
+
+/// Exit status of a run stopped by Ctrl-C, as shells report for SIGINT
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+/// What an interrupt asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownRequest {
+    /// Stop reading, then finish and export partial results
+    Graceful,
+    /// Quit now without exporting
+    Force,
+}
+
+#[derive(Debug, Default)]
+struct ShutdownState {
+    /// Why the shutdown was requested, set by the first request
+    reason: Option<String>,
+    /// When Ctrl-C was last pressed
+    last_interrupt: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    requested: CancellationToken,
+    state: Mutex<ShutdownState>,
+}
+
+/// Shared shutdown state of a run
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownController {
+    shared: Arc<Shared>,
+}
+
+impl ShutdownController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a graceful shutdown; only the first reason given is kept
+    pub fn abort(&self, reason: impl Into<String>) {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.reason.is_none() {
+            let reason = reason.into();
+            log::info!("Shutdown requested: {}", reason);
+            state.reason = Some(reason);
+        }
+        self.shared.requested.cancel();
+    }
+
+    /// Handle a Ctrl-C: the first starts a graceful shutdown, a second within the window forces one
+    pub fn interrupt(&self) -> ShutdownRequest {
+        self.interrupt_at(Instant::now())
+    }
+
+    fn interrupt_at(&self, now: Instant) -> ShutdownRequest {
+        let previous = {
+            let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.last_interrupt.replace(now)
+        };
+        if previous.is_some_and(|previous| now.saturating_duration_since(previous) < FORCE_QUIT_WINDOW) {
+            return ShutdownRequest::Force;
+        }
+        self.abort("interrupted by Ctrl-C");
+        ShutdownRequest::Graceful
+    }
+
+    /// Check whether a shutdown has been requested
+    pub fn is_requested(&self) -> bool {
+        self.shared.requested.is_cancelled()
+    }
+
+    /// Why the shutdown was requested, if it has been
+    pub fn reason(&self) -> Option<String> {
+        self.shared.state.lock().unwrap_or_else(|e| e.into_inner()).reason.clone()
+    }
+
+    /// Wait until a shutdown is requested
+    pub async fn requested(&self) {
+        self.shared.requested.cancelled().await
+    }
+}
+
+/// Handle Ctrl-C for the rest of the process with the controller
+///
+/// The first press starts a graceful shutdown; a second press within
+/// [`FORCE_QUIT_WINDOW`] exits immediately with [`EXIT_INTERRUPTED`].
+pub fn handle_ctrl_c(controller: ShutdownController) {
+    crate::runtime::shared().spawn(async move {
+        loop {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                log::warn!("Cannot listen for Ctrl-C: {}", e);
+                return;
+            }
+            match controller.interrupt() {
+                ShutdownRequest::Graceful => eprintln!(
+                    "\nStopping the scan and exporting partial results; press Ctrl-C again within {}s to quit now",
+                    FORCE_QUIT_WINDOW.as_secs()
+                ),
+                ShutdownRequest::Force => {
+                    eprintln!("\nQuitting now; queued messages and results not yet exported are discarded");
+                    std::process::exit(EXIT_INTERRUPTED);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_interrupt_within_window_forces_quit() {
+        let controller = ShutdownController::new();
+        let start = Instant::now();
+        assert!(!controller.is_requested());
+
+        assert_eq!(controller.interrupt_at(start), ShutdownRequest::Graceful);
+        assert!(controller.clone().is_requested());
+        assert_eq!(controller.reason().as_deref(), Some("interrupted by Ctrl-C"));
+
+        // A press after the window starts the wait again
+        let later = start + FORCE_QUIT_WINDOW + Duration::from_secs(1);
+        assert_eq!(controller.interrupt_at(later), ShutdownRequest::Graceful);
+        assert_eq!(controller.interrupt_at(later + Duration::from_secs(1)), ShutdownRequest::Force);
+    }
+
+    #[tokio::test]
+    async fn test_abort_keeps_first_reason() {
+        let controller = ShutdownController::new();
+        let waiter = tokio::spawn({
+            let controller = controller.clone();
+            async move { controller.requested().await }
+        });
+
+        controller.abort("deadline reached");
+        controller.abort("second request");
+        waiter.await.unwrap();
+        assert_eq!(controller.reason().as_deref(), Some("deadline reached"));
+    }
+}
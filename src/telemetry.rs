@@ -0,0 +1,195 @@
+//! OpenTelemetry Tracing
+//!
+//! Spans for scan phases, queue operations and plugin processing, exported over
+//! OTLP/HTTP so gstats runs show up in an existing tracing backend (Jaeger,
+//! Tempo, Honeycomb and so on). Only built with the `otel` feature.
+//!
+//! Export is configured with the standard OpenTelemetry environment variables:
+//!
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT` or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` - the
+//!   collector to send spans to; without either, no spans are recorded
+//! - `OTEL_EXPORTER_OTLP_HEADERS` - extra headers, e.g. for authentication
+//! - `OTEL_SERVICE_NAME` - service name reported (default `gstats`)
+//!
+//! `--otlp-endpoint` names the collector on the command line instead, taking the
+//! same base URL as `OTEL_EXPORTER_OTLP_ENDPOINT`.
+//!
+//! Each scan is a `scan` span. The spans created while it runs (`scan.traverse`,
+//! `scan.commit`, `queue.enqueue`, `plugin.process_message` and so on) are its children and
+//! carry its `scan_id`; plugin spans also carry a `plugin_id`.
+
+use opentelemetry::global::{self, BoxedSpan};
+use opentelemetry::trace::{Span, Status, TraceContextExt, Tracer};
+use opentelemetry::Context;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use std::sync::RwLock;
+
+pub use opentelemetry::KeyValue;
+
+const TRACER_NAME: &str = "gstats";
+
+/// Environment variables that name a collector to export to
+const ENDPOINT_VARIABLES: [&str; 2] = ["OTEL_EXPORTER_OTLP_TRACES_ENDPOINT", "OTEL_EXPORTER_OTLP_ENDPOINT"];
+
+/// The scan in progress: its id and the context its spans are children of
+static CURRENT_SCAN: RwLock<Option<(String, Context)>> = RwLock::new(None);
+
+/// Installed tracer provider; spans still being batched are exported when it is dropped
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            log::warn!("Failed to export remaining trace spans: {}", e);
+        }
+    }
+}
+
+/// Start exporting spans if a collector endpoint is given or configured
+///
+/// Spans are recorded until the returned guard is dropped. Without an endpoint,
+/// or if the exporter cannot be created, spans are discarded.
+pub fn init(endpoint: Option<&str>) -> Option<TelemetryGuard> {
+    if endpoint.is_none() && !ENDPOINT_VARIABLES.iter().any(|name| std::env::var_os(name).is_some()) {
+        return None;
+    }
+    let mut builder = opentelemetry_otlp::SpanExporter::builder().with_http();
+    if let Some(endpoint) = endpoint {
+        builder = builder.with_endpoint(traces_endpoint(endpoint));
+    }
+    let exporter = match builder.build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            log::warn!("OpenTelemetry tracing disabled: {}", e);
+            return None;
+        }
+    };
+    // OTEL_SERVICE_NAME, when set, takes precedence over the default name
+    let service_name = std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| TRACER_NAME.to_string());
+    let resource = Resource::builder()
+        .with_service_name(service_name)
+        .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+        .build();
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build();
+    global::set_tracer_provider(provider.clone());
+    log::debug!("Exporting OpenTelemetry trace spans over OTLP");
+    Some(TelemetryGuard { provider })
+}
+
+/// The traces URL for a collector base URL, as `OTEL_EXPORTER_OTLP_ENDPOINT` is resolved
+///
+/// Endpoints set on the exporter are used as given, so the signal path is added here.
+fn traces_endpoint(base: &str) -> String {
+    let base = base.trim_end_matches('/');
+    if base.ends_with("/v1/traces") {
+        base.to_string()
+    } else {
+        format!("{}/v1/traces", base)
+    }
+}
+
+/// A span that ends when dropped
+pub struct SpanGuard {
+    span: BoxedSpan,
+}
+
+impl SpanGuard {
+    /// Add an attribute to the span
+    pub fn set_attribute(&mut self, attribute: KeyValue) {
+        self.span.set_attribute(attribute);
+    }
+
+    /// Mark the span as failed if the operation it covers failed
+    pub fn record_result<T, E: std::fmt::Display>(&mut self, result: &Result<T, E>) {
+        if let Err(e) = result {
+            self.span.set_status(Status::error(e.to_string()));
+        }
+    }
+}
+
+/// The root span of a scan; spans started while it is alive are its children
+pub struct ScanSpan {
+    scan_id: String,
+    context: Context,
+}
+
+impl ScanSpan {
+    /// Mark the scan as failed if it failed
+    pub fn record_result<T, E: std::fmt::Display>(&self, result: &Result<T, E>) {
+        if let Err(e) = result {
+            self.context.span().set_status(Status::error(e.to_string()));
+        }
+    }
+}
+
+impl Drop for ScanSpan {
+    fn drop(&mut self) {
+        self.context.span().end();
+        let mut current = CURRENT_SCAN.write().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref().is_some_and(|(scan_id, _)| *scan_id == self.scan_id) {
+            *current = None;
+        }
+    }
+}
+
+/// Start the root span of a scan
+pub fn start_scan(scan_id: &str, attributes: Vec<KeyValue>) -> ScanSpan {
+    let tracer = global::tracer(TRACER_NAME);
+    let span = tracer.span_builder("scan")
+        .with_attributes(attributes.into_iter().chain([KeyValue::new("scan_id", scan_id.to_string())]))
+        .start(&tracer);
+    let context = Context::current_with_span(span);
+    *CURRENT_SCAN.write().unwrap_or_else(|e| e.into_inner()) = Some((scan_id.to_string(), context.clone()));
+    ScanSpan { scan_id: scan_id.to_string(), context }
+}
+
+/// Start a span within the scan in progress, if any
+pub fn span(name: &'static str, attributes: Vec<KeyValue>) -> SpanGuard {
+    let tracer = global::tracer(TRACER_NAME);
+    let current = CURRENT_SCAN.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let (parent, scan_id) = match current {
+        Some((scan_id, context)) => (context, Some(KeyValue::new("scan_id", scan_id))),
+        None => (Context::new(), None),
+    };
+    let span = tracer.span_builder(name)
+        .with_attributes(attributes.into_iter().chain(scan_id))
+        .start_with_context(&tracer, &parent);
+    SpanGuard { span }
+}
+
+/// Start a span for work a plugin does within the scan in progress
+pub fn plugin_span(name: &'static str, plugin_id: &str, attributes: Vec<KeyValue>) -> SpanGuard {
+    let attributes = attributes.into_iter().chain([KeyValue::new("plugin_id", plugin_id.to_string())]).collect();
+    span(name, attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans_follow_the_current_scan() {
+        let scan = start_scan("scan-1", Vec::new());
+        assert_eq!(CURRENT_SCAN.read().unwrap().as_ref().map(|(scan_id, _)| scan_id.as_str()), Some("scan-1"));
+        let mut span = plugin_span("plugin.process_message", "commits", Vec::new());
+        span.record_result(&Err::<(), _>("failed"));
+        drop(span);
+
+        drop(scan);
+        assert!(CURRENT_SCAN.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_traces_endpoint() {
+        assert_eq!(traces_endpoint("http://localhost:4318"), "http://localhost:4318/v1/traces");
+        assert_eq!(traces_endpoint("http://localhost:4318/"), "http://localhost:4318/v1/traces");
+        assert_eq!(traces_endpoint("https://collector/otlp/v1/traces"), "https://collector/otlp/v1/traces");
+    }
+}
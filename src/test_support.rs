@@ -0,0 +1,372 @@
+//! Test Support
+//!
+//! Throwaway git repositories for scanner and plugin tests. A [`TestRepoBuilder`]
+//! records a script of file edits, commits, branches and tags, then replays it
+//! with the `git` command line in a temporary directory. Author and committer
+//! dates are fixed, starting at [`DEFAULT_START`] and advancing one day per
+//! commit unless set explicitly, so commit ids are the same on every run.
+//!
+//! Available to this crate's unit tests and, through the `test-support`
+//! feature, to integration tests:
+//!
+//! ```no_run
+//! use gstats::test_support::TestRepo;
+//!
+//! let repo = TestRepo::builder()
+//!     .author("Alice", "alice@example.com")
+//!     .file("src/lib.rs", "pub fn answer() -> u32 { 42 }\n")
+//!     .commit("Initial commit")
+//!     .branch("feature")
+//!     .rename("src/lib.rs", "src/answer.rs")
+//!     .commit("Move answer into its own module")
+//!     .tag("v0.1.0")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(repo.commit_count(), 2);
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+use thiserror::Error;
+
+/// Author and committer timestamp of the first commit (2024-01-01T00:00:00Z)
+pub const DEFAULT_START: i64 = 1_704_067_200;
+
+/// Seconds between consecutive commits without an explicit date
+const COMMIT_INTERVAL: i64 = 86_400;
+
+/// Name of the branch created by the first commit
+pub const DEFAULT_BRANCH: &str = "main";
+
+/// Test repository construction errors
+#[derive(Debug, Error)]
+pub enum TestRepoError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("git {args} failed: {stderr}")]
+    Git { args: String, stderr: String },
+}
+
+/// Result type for test repository operations
+pub type TestRepoResult<T> = Result<T, TestRepoError>;
+
+#[derive(Debug, Clone)]
+struct Signature {
+    name: String,
+    email: String,
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    Write { path: String, content: Vec<u8> },
+    Remove { path: String },
+    Rename { from: String, to: String },
+    Commit { message: String, author: Signature, time: Option<i64> },
+    Branch { name: String },
+    Checkout { name: String },
+    Tag { name: String, message: Option<String> },
+}
+
+/// Fluent builder for throwaway git repositories
+///
+/// File operations are staged and included in the next [`commit`](Self::commit).
+/// [`branch`](Self::branch) creates a branch at the current commit and switches
+/// to it; [`checkout`](Self::checkout) switches back to an existing branch.
+#[derive(Debug, Clone)]
+pub struct TestRepoBuilder {
+    author: Signature,
+    start: i64,
+    steps: Vec<Step>,
+}
+
+impl Default for TestRepoBuilder {
+    fn default() -> Self {
+        Self {
+            author: Signature {
+                name: "Test User".to_string(),
+                email: "test@example.com".to_string(),
+            },
+            start: DEFAULT_START,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl TestRepoBuilder {
+    /// Create a builder for an empty repository
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the author (and committer) of subsequent commits
+    pub fn author(mut self, name: &str, email: &str) -> Self {
+        self.author = Signature { name: name.to_string(), email: email.to_string() };
+        self
+    }
+
+    /// Set the timestamp of the first commit without an explicit date
+    pub fn starting_at(mut self, timestamp: i64) -> Self {
+        self.start = timestamp;
+        self
+    }
+
+    /// Write a text file, creating parent directories as needed
+    pub fn file(self, path: &str, content: &str) -> Self {
+        self.binary_file(path, content.as_bytes())
+    }
+
+    /// Write a file with arbitrary bytes
+    pub fn binary_file(mut self, path: &str, content: &[u8]) -> Self {
+        self.steps.push(Step::Write { path: path.to_string(), content: content.to_vec() });
+        self
+    }
+
+    /// Delete a tracked file
+    pub fn remove(mut self, path: &str) -> Self {
+        self.steps.push(Step::Remove { path: path.to_string() });
+        self
+    }
+
+    /// Rename a tracked file with `git mv`
+    pub fn rename(mut self, from: &str, to: &str) -> Self {
+        self.steps.push(Step::Rename { from: from.to_string(), to: to.to_string() });
+        self
+    }
+
+    /// Commit staged changes, one interval after the previous commit
+    pub fn commit(mut self, message: &str) -> Self {
+        let author = self.author.clone();
+        self.steps.push(Step::Commit { message: message.to_string(), author, time: None });
+        self
+    }
+
+    /// Commit staged changes at a Unix timestamp
+    ///
+    /// Commits that follow without an explicit date continue from this one.
+    pub fn commit_at(mut self, message: &str, timestamp: i64) -> Self {
+        let author = self.author.clone();
+        self.steps.push(Step::Commit { message: message.to_string(), author, time: Some(timestamp) });
+        self
+    }
+
+    /// Create a branch at the current commit and switch to it
+    pub fn branch(mut self, name: &str) -> Self {
+        self.steps.push(Step::Branch { name: name.to_string() });
+        self
+    }
+
+    /// Switch to an existing branch
+    pub fn checkout(mut self, name: &str) -> Self {
+        self.steps.push(Step::Checkout { name: name.to_string() });
+        self
+    }
+
+    /// Create a lightweight tag at the current commit
+    pub fn tag(mut self, name: &str) -> Self {
+        self.steps.push(Step::Tag { name: name.to_string(), message: None });
+        self
+    }
+
+    /// Create an annotated tag at the current commit
+    pub fn annotated_tag(mut self, name: &str, message: &str) -> Self {
+        self.steps.push(Step::Tag { name: name.to_string(), message: Some(message.to_string()) });
+        self
+    }
+
+    /// Create the repository in a new temporary directory
+    pub fn build(self) -> TestRepoResult<TestRepo> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("repo");
+        std::fs::create_dir(&path)?;
+
+        let repo = TestRepo { temp_dir, path };
+        repo.git(&["init", "-q", "--initial-branch", DEFAULT_BRANCH], &[])?;
+
+        let mut time = self.start - COMMIT_INTERVAL;
+        for step in self.steps {
+            match step {
+                Step::Write { path, content } => {
+                    let file = repo.path.join(&path);
+                    if let Some(parent) = file.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(file, content)?;
+                    repo.git(&["add", "--", &path], &[])?;
+                }
+                Step::Remove { path } => {
+                    repo.git(&["rm", "-q", "--", &path], &[])?;
+                }
+                Step::Rename { from, to } => {
+                    if let Some(parent) = repo.path.join(&to).parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    repo.git(&["mv", "--", &from, &to], &[])?;
+                }
+                Step::Commit { message, author, time: explicit } => {
+                    time = explicit.unwrap_or(time + COMMIT_INTERVAL);
+                    let date = format!("@{time} +0000");
+                    repo.git(
+                        &["commit", "-q", "--allow-empty", "-m", &message],
+                        &[
+                            ("GIT_AUTHOR_NAME", &author.name),
+                            ("GIT_AUTHOR_EMAIL", &author.email),
+                            ("GIT_AUTHOR_DATE", &date),
+                            ("GIT_COMMITTER_NAME", &author.name),
+                            ("GIT_COMMITTER_EMAIL", &author.email),
+                            ("GIT_COMMITTER_DATE", &date),
+                        ],
+                    )?;
+                }
+                Step::Branch { name } => {
+                    repo.git(&["checkout", "-q", "-b", &name], &[])?;
+                }
+                Step::Checkout { name } => {
+                    repo.git(&["checkout", "-q", &name], &[])?;
+                }
+                Step::Tag { name, message } => {
+                    let date = format!("@{time} +0000");
+                    let env = [("GIT_COMMITTER_DATE", date.as_str())];
+                    match message {
+                        Some(message) => repo.git(&["tag", "-a", &name, "-m", &message], &env)?,
+                        None => repo.git(&["tag", &name], &env)?,
+                    };
+                }
+            }
+        }
+
+        Ok(repo)
+    }
+}
+
+/// A git repository that is deleted when dropped
+#[derive(Debug)]
+pub struct TestRepo {
+    temp_dir: TempDir,
+    path: PathBuf,
+}
+
+impl TestRepo {
+    /// Start building a repository
+    pub fn builder() -> TestRepoBuilder {
+        TestRepoBuilder::new()
+    }
+
+    /// Working tree of the repository
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Git directory of the repository
+    pub fn git_dir(&self) -> PathBuf {
+        self.path.join(".git")
+    }
+
+    /// Clone the repository as a bare mirror alongside it and return its path
+    pub fn bare_clone(&self, name: &str) -> TestRepoResult<PathBuf> {
+        let target = self.temp_dir.path().join(name);
+        self.git(&["clone", "-q", "--bare", ".", &target.to_string_lossy()], &[])?;
+        Ok(target)
+    }
+
+    /// Clone the last `depth` commits alongside it, as CI checkouts do, and return its path
+    pub fn shallow_clone(&self, name: &str, depth: usize) -> TestRepoResult<PathBuf> {
+        let target = self.temp_dir.path().join(name);
+        let source = format!("file://{}", self.path().display());
+        self.git(&["clone", "-q", "--depth", &depth.to_string(), &source, &target.to_string_lossy()], &[])?;
+        Ok(target)
+    }
+
+    /// Object id that a revision resolves to
+    pub fn rev_parse(&self, revision: &str) -> TestRepoResult<String> {
+        self.git(&["rev-parse", revision], &[])
+    }
+
+    /// Number of commits reachable from HEAD
+    pub fn commit_count(&self) -> usize {
+        self.git(&["rev-list", "--count", "HEAD"], &[])
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Run `git` in the working tree and return its trimmed standard output
+    pub fn git(&self, args: &[&str], env: &[(&str, &str)]) -> TestRepoResult<String> {
+        let output = Command::new("git")
+            .current_dir(&self.path)
+            .args(["-c", "user.name=Test User", "-c", "user.email=test@example.com"])
+            .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+            .args(args)
+            .envs(env.iter().copied())
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .output()?;
+
+        if !output.status.success() {
+            return Err(TestRepoError::Git {
+                args: args.join(" "),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_is_deterministic() {
+        let build = || {
+            TestRepo::builder()
+                .file("a.txt", "one\n")
+                .commit("First")
+                .file("a.txt", "two\n")
+                .commit("Second")
+                .build()
+                .unwrap()
+        };
+
+        let first = build();
+        let second = build();
+        assert_eq!(first.commit_count(), 2);
+        assert_eq!(first.rev_parse("HEAD").unwrap(), second.rev_parse("HEAD").unwrap());
+        assert_eq!(
+            first.git(&["log", "-1", "--format=%at"], &[]).unwrap(),
+            (DEFAULT_START + COMMIT_INTERVAL).to_string()
+        );
+    }
+
+    #[test]
+    fn test_builder_branches_tags_and_renames() {
+        let repo = TestRepo::builder()
+            .author("Alice", "alice@example.com")
+            .file("src/lib.rs", "fn main() {}\n")
+            .binary_file("logo.png", &[0x89, b'P', b'N', b'G', 0, 0, 0xff])
+            .commit("Initial commit")
+            .annotated_tag("v1.0", "First release")
+            .branch("feature")
+            .author("Bob", "bob@example.com")
+            .rename("src/lib.rs", "src/main.rs")
+            .remove("logo.png")
+            .commit_at("Rename", DEFAULT_START + 10 * COMMIT_INTERVAL)
+            .checkout(DEFAULT_BRANCH)
+            .build()
+            .unwrap();
+
+        assert_eq!(repo.commit_count(), 1);
+        assert!(repo.path().join("logo.png").is_file());
+        assert_eq!(repo.rev_parse("v1.0^{commit}").unwrap(), repo.rev_parse(DEFAULT_BRANCH).unwrap());
+
+        let renamed = repo.git(&["show", "--name-status", "-M", "--format=%an", "feature"], &[]).unwrap();
+        assert!(renamed.starts_with("Bob"));
+        assert!(renamed.contains("R100\tsrc/lib.rs\tsrc/main.rs"));
+        assert!(renamed.contains("D\tlogo.png"));
+
+        let bare = repo.bare_clone("mirror.git").unwrap();
+        assert!(bare.join("HEAD").is_file());
+    }
+}
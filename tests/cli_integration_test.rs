@@ -6,10 +6,10 @@ use std::process::Command;
 #[test]
 fn test_cli_with_repository_path() {
     // Test that gstats accepts a repository path argument using --repo flag
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "--repo", "/path/to/repo"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--repo", "/path/to/repo"])
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
 
     // Should handle the path argument gracefully (exit code 1 for invalid path)
     assert_eq!(output.status.code(), Some(1), "Invalid path should return exit code 1");
@@ -23,10 +23,9 @@ fn test_cli_with_repository_path() {
 fn test_cli_current_directory_behavior() {
     // Test that gstats works with current directory when no path specified
     // This test will FAIL initially (RED phase) until git detection is implemented
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
 
     let stdout = String::from_utf8(output.stdout).unwrap_or_default();
     
@@ -45,10 +44,10 @@ fn test_cli_current_directory_behavior() {
 fn test_cli_error_handling() {
     // Test error handling for non-existent repository paths
     // Use --repository flag to test repository path validation
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "--repository", "/definitely/does/not/exist"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--repository", "/definitely/does/not/exist"])
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
 
     // Should exit with error code for non-existent path
     assert!(!output.status.success());
@@ -62,40 +61,41 @@ fn test_cli_error_handling() {
 fn test_repo_flag_integration() {
     // Test the complete workflow with repository flag functionality
     // Test all three flag aliases: -r, --repo, --repository
+    // A command is required; without one gstats lists the plugin functions and fails
     
     // Test 1: Current directory with -r flag
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "-r", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["-r", ".", "commits"])
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
     
     // Should succeed since current directory is a git repository
     assert_eq!(output.status.code(), Some(0), 
                "Current directory should be valid git repository");
     
     // Test 2: Current directory with --repo flag
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "--repo", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--repo", ".", "commits"])
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
     
     assert_eq!(output.status.code(), Some(0), 
                "--repo flag should work with current directory");
     
     // Test 3: Current directory with --repository flag
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "--repository", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--repository", ".", "commits"])
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
     
     assert_eq!(output.status.code(), Some(0), 
                "--repository flag should work with current directory");
     
     // Test 4: Invalid path with -r flag
-    let output = Command::new("cargo")
-        .args(&["run", "--quiet", "--", "-r", "/tmp"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["-r", "/tmp"])
         .output()
-        .expect("Failed to execute cargo run");
+        .expect("Failed to run gstats");
     
     assert_eq!(output.status.code(), Some(1), 
                "Invalid repository path should return exit code 1");
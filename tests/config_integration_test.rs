@@ -24,8 +24,8 @@ enable_caching = "true"
     // Test configuration loading
     use std::process::Command;
     
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--config-file", config_path.to_str().unwrap(), "--help"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--config-file", config_path.to_str().unwrap(), "--help"])
         .current_dir(env!("CARGO_MANIFEST_DIR"))
         .output()
         .expect("Failed to execute command");
@@ -59,8 +59,8 @@ quiet = "true"
     // Test configuration loading with section selection
     use std::process::Command;
     
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--config-file", config_path.to_str().unwrap(), "--config-name", "dev", "--help"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--config-file", config_path.to_str().unwrap(), "--config-name", "dev", "--help"])
         .current_dir(env!("CARGO_MANIFEST_DIR"))
         .output()
         .expect("Failed to execute command");
@@ -86,8 +86,8 @@ format = "text"
     // Test that CLI arguments override config
     use std::process::Command;
     
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--config-file", config_path.to_str().unwrap(), "--verbose", "--help"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--config-file", config_path.to_str().unwrap(), "--verbose", "--help"])
         .current_dir(env!("CARGO_MANIFEST_DIR"))
         .output()
         .expect("Failed to execute command");
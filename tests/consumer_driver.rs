@@ -0,0 +1,565 @@
+//! Plugins consuming the queue during a full scan
+//!
+//! Some tests run the `gstats` binary against throwaway repositories, so the
+//! scanner, the queue, the consumer driver and the export plugin all take part.
+//! Others scan with probe plugins that record what the driver gave them.
+
+use async_trait::async_trait;
+use gstats::app::execution::ConsumerDriver;
+use gstats::notifications::typed_publishers::{QueueEventPublisher, ScanEventPublisher};
+use gstats::notifications::AsyncNotificationManager;
+use gstats::plugin::traits::{ConsumerPlugin, ConsumerPreferences, MessageConsumer, PluginType};
+use gstats::plugin::{Plugin, PluginError, PluginInfo, PluginRequest, PluginResponse, PluginResult, ScanContext, SharedPluginRegistry};
+use gstats::queue::routing::message_type;
+use gstats::queue::multi_consumer::MultiConsumerConfig;
+use gstats::queue::{BackpressurePolicy, QueueEvent, SharedMessageQueue};
+use gstats::runtime::block_on;
+use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+use gstats::scanner::file_policy::FilePolicy;
+use gstats::scanner::messages::{MessageData, ScanMessage};
+use gstats::scanner::traits::QueueMessageProducer;
+use gstats::scanner::{AsyncScannerManagerBuilder, QueryParams, ScannerConfig};
+use gstats::test_support::TestRepo;
+use std::path::Path;
+use std::io::BufRead;
+use std::process::{Command, Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Run gstats in a repository without the scan cache or any user configuration
+fn gstats(repo: &Path, args: &[&str]) -> Output {
+    let home = tempfile::TempDir::new().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(["--no-cache", "--no-color"])
+        .args(args)
+        .current_dir(repo)
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("Failed to run gstats")
+}
+
+/// A gstats process that is stopped when the test ends, even if it fails
+struct StopOnDrop(std::process::Child);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Read lines from a child's output until one contains `text`, returning the lines read
+fn lines_until(lines: &std::sync::mpsc::Receiver<String>, text: &str) -> Vec<String> {
+    let mut read = Vec::new();
+    loop {
+        match lines.recv_timeout(Duration::from_secs(60)) {
+            Ok(line) => {
+                let found = line.contains(text);
+                read.push(line);
+                if found {
+                    return read;
+                }
+            }
+            Err(e) => panic!("no line containing {:?} ({}); output so far:\n{}", text, e, read.join("\n")),
+        }
+    }
+}
+
+/// Table rows of the text output that start with a value, such as an author name
+fn rows_starting_with<'a>(stdout: &'a str, value: &str) -> Vec<&'a str> {
+    stdout.lines().filter(|line| line.trim_start().starts_with(value)).collect()
+}
+
+#[test]
+fn test_commits_reports_rows_from_consumed_messages() {
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .file("src/lib.rs", "pub fn one() {}\npub fn two() {}\n")
+        .commit("Extend library")
+        .author("Bob", "bob@example.com")
+        .file("README.md", "# Test\n")
+        .commit("Add readme")
+        .build()
+        .expect("Failed to create test repository");
+
+    let output = gstats(repo.path(), &["commits"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "gstats failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    assert!(stdout.contains("Analysis of 3 commits from 2 authors"), "unexpected output:\n{}", stdout);
+    let alice = rows_starting_with(&stdout, "Alice");
+    assert!(alice.iter().any(|row| row.split_whitespace().collect::<Vec<_>>() == ["Alice", "2", "66.7%"]), "unexpected rows: {:?}", alice);
+    assert!(!rows_starting_with(&stdout, "Bob").is_empty());
+}
+
+#[test]
+fn test_watch_reports_rows_again_after_new_commits() {
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("README.md", "# Test\n")
+        .commit("Add readme")
+        .build()
+        .expect("Failed to create test repository");
+    let home = tempfile::TempDir::new().unwrap();
+    let mut child = StopOnDrop(Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(["--no-color", "--watch", "--watch-interval", "1", "commits"])
+        .current_dir(repo.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path())
+        .env_remove("RUST_LOG")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Failed to run gstats"));
+    let (sender, lines) = std::sync::mpsc::channel();
+    let stdout = child.0.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    lines_until(&lines, "Analysis of 1 commits from 1 authors");
+    lines_until(&lines, "Watching");
+    repo.git(&["commit", "-q", "--allow-empty", "-m", "Second commit"], &[("GIT_AUTHOR_NAME", "Bob"), ("GIT_AUTHOR_EMAIL", "bob@example.com")])
+        .unwrap();
+
+    // The rescan's plugins publish their data again, so the second report has rows too
+    let rescan = lines_until(&lines, "Watching");
+    let rescan = rescan.join("\n");
+    assert!(rescan.contains("Analysis of 2 commits from 2 authors"), "unexpected output:\n{}", rescan);
+    assert!(!rows_starting_with(&rescan, "Alice").is_empty(), "unexpected output:\n{}", rescan);
+    assert!(!rows_starting_with(&rescan, "Bob").is_empty(), "unexpected output:\n{}", rescan);
+}
+
+#[test]
+fn test_expect_data_fails_when_the_plugin_publishes_nothing() {
+    let repo = fixture_repo();
+
+    // The debug plugin prints the messages it is given, but publishes no results
+    let output = gstats(repo.path(), &["--expect-data", "debug"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(gstats::app::EXIT_NO_DATA), "{}", stderr);
+    assert!(stderr.contains("No data to report: debug produced no rows"), "{}", stderr);
+
+    let output = gstats(repo.path(), &["--expect-data", "commits"]);
+    assert!(output.status.success(), "gstats failed: {}", String::from_utf8_lossy(&output.stderr));
+}
+
+/// What a probe plugin was given during a scan
+#[derive(Default)]
+struct Probe {
+    message_types: Vec<&'static str>,
+    sequences: Vec<u64>,
+    binary_changes: usize,
+    batches: Vec<usize>,
+    events: Vec<QueueEvent>,
+}
+
+impl Probe {
+    fn scan_completed(&self) -> bool {
+        self.events.iter().any(|event| matches!(event, QueueEvent::ScanComplete { .. }))
+    }
+}
+
+/// Consumer plugin that records its messages and queue events, optionally stalling on one message
+struct ProbePlugin {
+    info: PluginInfo,
+    preferences: ConsumerPreferences,
+    file_policy: Option<FilePolicy>,
+    stall: Option<(usize, Duration)>,
+    probe: Arc<Mutex<Probe>>,
+}
+
+impl ProbePlugin {
+    fn new(name: &str) -> (Self, Arc<Mutex<Probe>>) {
+        let probe = Arc::new(Mutex::new(Probe::default()));
+        let plugin = Self {
+            info: PluginInfo::new(
+                name.to_string(),
+                "1.0.0".to_string(),
+                20250727,
+                "Probe for consumer driver tests".to_string(),
+                "Test Author".to_string(),
+                PluginType::Processing,
+            ),
+            preferences: ConsumerPreferences { preferred_batch_size: 1, ..ConsumerPreferences::default() },
+            file_policy: None,
+            stall: None,
+            probe: probe.clone(),
+        };
+        (plugin, probe)
+    }
+
+    /// Sleep for `duration` when given the nth message
+    fn stalling_on(mut self, n: usize, duration: Duration) -> Self {
+        self.stall = Some((n, duration));
+        self
+    }
+
+    /// Change the plugin's consumer preferences
+    fn preferring(mut self, preferences: ConsumerPreferences) -> Self {
+        self.preferences = preferences;
+        self
+    }
+
+    /// Give the plugin's consumer a file policy, as a run does from its data requirements
+    fn filtering(mut self, policy: FilePolicy) -> Self {
+        self.file_policy = Some(policy);
+        self
+    }
+}
+
+#[async_trait]
+impl Plugin for ProbePlugin {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn execute(&self, _request: PluginRequest) -> PluginResult<PluginResponse> {
+        Err(PluginError::execution_failed("not supported"))
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for ProbePlugin {
+    async fn start_consuming(&mut self, _consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn process_message(&self, _consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        let count = {
+            let mut probe = self.probe.lock().unwrap();
+            probe.message_types.push(message_type(&message.data));
+            probe.sequences.push(message.header().sequence());
+            if let MessageData::FileChange { is_binary: true, .. } = message.data {
+                probe.binary_changes += 1;
+            }
+            probe.message_types.len()
+        };
+        if let Some((_, duration)) = self.stall.filter(|(n, _)| *n == count) {
+            tokio::time::sleep(duration).await;
+        }
+        Ok(())
+    }
+
+    async fn process_batch(&self, consumer: &dyn MessageConsumer, messages: Vec<Arc<ScanMessage>>) -> PluginResult<()> {
+        self.probe.lock().unwrap().batches.push(messages.len());
+        for message in messages {
+            self.process_message(consumer, message).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, event: &QueueEvent) -> PluginResult<()> {
+        self.probe.lock().unwrap().events.push(event.clone());
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        self.preferences.clone()
+    }
+}
+
+/// Repository with a few commits touching a few files
+fn fixture_repo() -> TestRepo {
+    TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .file("src/lib.rs", "pub fn one() {}\npub fn two() {}\n")
+        .file("README.md", "# Test\n")
+        .commit("Extend library")
+        .file("src/main.rs", "fn main() {}\n")
+        .commit("Add binary")
+        .build()
+        .expect("Failed to create test repository")
+}
+
+/// Scan a repository with plugins consuming the queue through the consumer driver
+///
+/// The plugins are registered with the registry, which the caller may have
+/// configured, and the queue is returned for inspection once every consumer
+/// has drained it. Binary changes are streamed, leaving them to each consumer's
+/// file policy. Like a gstats run, the scan is driven from synchronous code on
+/// the shared runtime.
+fn scan_with(repo: &TestRepo, registry: &SharedPluginRegistry, plugins: Vec<ProbePlugin>) -> SharedMessageQueue {
+    scan_through(MultiConsumerConfig::default(), repo, registry, plugins)
+}
+
+/// Scan a repository as [`scan_with`] does, through a queue with its own limits and backpressure policy
+fn scan_through(config: MultiConsumerConfig, repo: &TestRepo, registry: &SharedPluginRegistry, plugins: Vec<ProbePlugin>) -> SharedMessageQueue {
+    let unified = Arc::new(AsyncNotificationManager::new());
+    let queue = SharedMessageQueue::with_config(
+        config,
+        Arc::new(QueueEventPublisher::new(unified.clone())),
+        Arc::new(ScanEventPublisher::new(unified)),
+    );
+    let consumers = block_on(async {
+        queue.start().await.unwrap();
+        let mut consumers = Vec::new();
+        for plugin in plugins {
+            let name = plugin.plugin_info().name.clone();
+            let file_policy = plugin.file_policy;
+            registry.register_plugin(Box::new(plugin)).await.unwrap();
+            let consumer = queue.register_consumer(name.clone()).await.unwrap();
+            consumers.push((name.clone(), consumer.clone()));
+            if let Some(policy) = file_policy {
+                consumer.set_file_policy(policy).await;
+            }
+            registry.inner().write().await.start_consuming(&name, consumer).await.unwrap();
+        }
+        consumers
+    });
+
+    let engine = AsyncScannerManagerBuilder::new()
+        .repository(repo.path())
+        .config(ScannerConfig::default())
+        .message_producer(Arc::new(QueueMessageProducer::new(queue.clone(), "ScannerProducer".to_string())))
+        .notification_manager(Arc::new(AsyncNotificationManager::new()))
+        .plugin_registry(registry.clone())
+        .add_scanner(Arc::new(EventDrivenScanner::new(QueryParams::default()).with_binary_files(true)))
+        .build()
+        .unwrap();
+    let driver = ConsumerDriver::spawn(registry, &queue, consumers);
+    block_on(async {
+        engine.scan().await.unwrap();
+        driver.finish().await;
+    });
+    queue
+}
+
+#[test]
+fn test_overrunning_plugin_is_cancelled_during_scan() {
+    let repo = fixture_repo();
+    let registry = SharedPluginRegistry::new();
+    block_on(async { registry.inner().write().await.set_execution_timeout(Some(Duration::from_millis(200))) });
+    let (stalled, stalled_probe) = ProbePlugin::new("stalled");
+    let (steady, steady_probe) = ProbePlugin::new("steady");
+
+    let started = Instant::now();
+    scan_with(&repo, &registry, vec![stalled.stalling_on(2, Duration::from_secs(60)), steady]);
+    assert!(started.elapsed() < Duration::from_secs(30), "the stalled plugin held up the scan");
+
+    // The stalled plugin is cancelled at its deadline and given nothing more
+    assert!(block_on(async { registry.inner().read().await.cancellation_token("stalled") }).unwrap().is_cancelled());
+    let stalled_probe = stalled_probe.lock().unwrap();
+    assert_eq!(stalled_probe.message_types.len(), 2);
+    assert!(!stalled_probe.scan_completed());
+
+    // Other plugins consume the whole scan
+    let steady_probe = steady_probe.lock().unwrap();
+    assert!(steady_probe.message_types.len() > 2);
+    assert!(steady_probe.scan_completed());
+}
+
+#[test]
+fn test_plugin_over_its_limits_is_reported_and_throttled_during_scan() {
+    let repo = (1..=12)
+        .fold(TestRepo::builder(), |builder, n| builder.file("counter.txt", &format!("{}\n", n)).commit(&format!("Count {}", n)))
+        .build()
+        .expect("Failed to create test repository");
+    let registry = SharedPluginRegistry::new();
+    let (limited, probe) = ProbePlugin::new("limited");
+    let limited = limited
+        .preferring(ConsumerPreferences {
+            preferred_batch_size: 8,
+            max_memory: Some(1),
+            max_message_time: Some(Duration::from_millis(20)),
+            ..ConsumerPreferences::default()
+        })
+        .stalling_on(1, Duration::from_millis(200));
+
+    scan_with(&repo, &registry, vec![limited]);
+
+    let breaches = block_on(async { registry.inner().read().await.resource_breaches("limited") });
+    assert!(breaches.message_time >= 1, "{:?}", breaches);
+    assert!(breaches.memory >= 1, "{:?}", breaches);
+
+    // Every batch holds more than the single byte allowed, so the batches shrink to one message
+    let probe = probe.lock().unwrap();
+    assert!(probe.batches.len() > 3, "{:?}", probe.batches);
+    assert!(probe.batches.iter().skip(3).all(|&size| size == 1), "{:?}", probe.batches);
+    assert!(probe.scan_completed());
+}
+
+#[test]
+fn test_routed_plugin_receives_only_its_message_types_during_scan() {
+    let repo = TestRepo::builder()
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .binary_file("logo.png", &[0x89, b'P', b'N', b'G', 0, 0, 0, 0])
+        .commit("Add logo")
+        .file("src/lib.rs", "pub fn one() {}\npub fn two() {}\n")
+        .commit("Extend library")
+        .build()
+        .expect("Failed to create test repository");
+    let registry = SharedPluginRegistry::new();
+    let (routed, routed_probe) = ProbePlugin::new("routed");
+    let routed = routed.preferring(ConsumerPreferences {
+        preferred_batch_size: 1,
+        consume_all_messages: false,
+        interested_message_types: vec!["CommitInfo".to_string()],
+        ..ConsumerPreferences::default()
+    });
+    let (text_only, text_only_probe) = ProbePlugin::new("text-only");
+    let text_only = text_only.filtering(FilePolicy { binary_files: false, ..FilePolicy::default() });
+    let (unrouted, unrouted_probe) = ProbePlugin::new("unrouted");
+
+    scan_with(&repo, &registry, vec![routed, text_only, unrouted]);
+
+    // The routed plugin is given every commit and nothing else
+    let routed_probe = routed_probe.lock().unwrap();
+    assert_eq!(routed_probe.message_types, vec!["CommitInfo"; 3]);
+    assert!(routed_probe.scan_completed());
+
+    // The file policy leaves binary changes out, and only for its own consumer
+    let text_only_probe = text_only_probe.lock().unwrap();
+    let unrouted_probe = unrouted_probe.lock().unwrap();
+    assert!(text_only_probe.message_types.contains(&"FileChange"));
+    assert_eq!(text_only_probe.binary_changes, 0);
+    assert_eq!(unrouted_probe.binary_changes, 1);
+    assert!(unrouted_probe.message_types.contains(&"CommitInfo") && unrouted_probe.message_types.contains(&"FileChange"));
+}
+
+#[test]
+fn test_consumer_lag_drains_to_zero_after_scan() {
+    let repo = (1..=10)
+        .fold(TestRepo::builder(), |builder, n| builder.file(&format!("src/file{}.rs", n), "fn f() {}\n").commit(&format!("Add file {}", n)))
+        .build()
+        .expect("Failed to create test repository");
+    let registry = SharedPluginRegistry::new();
+    let (routed, _) = ProbePlugin::new("routed");
+    let routed = routed.preferring(ConsumerPreferences {
+        preferred_batch_size: 1,
+        consume_all_messages: false,
+        interested_message_types: vec!["CommitInfo".to_string()],
+        ..ConsumerPreferences::default()
+    });
+    let (text_only, _) = ProbePlugin::new("text-only");
+    let text_only = text_only.filtering(FilePolicy::default());
+    let (unrouted, unrouted_probe) = ProbePlugin::new("unrouted");
+
+    // A queue too small for the scan holds the producer back until the consumers catch up
+    let config = MultiConsumerConfig {
+        max_queue_size: 4,
+        backpressure: Some(BackpressurePolicy::Block),
+        ..MultiConsumerConfig::default()
+    };
+    let queue = scan_through(config, &repo, &registry, vec![routed, text_only, unrouted]);
+
+    let queue_statistics = block_on(queue.get_statistics());
+    assert!(queue_statistics.producer_blocks > 0, "{:?}", queue_statistics);
+    assert_eq!(queue_statistics.messages_dropped, 0);
+    assert_eq!(unrouted_probe.lock().unwrap().message_types.len() as u64, queue_statistics.total_messages);
+
+    // Every consumer has acknowledged every message, including those its route or policy passed over
+    let consumers = block_on(async { registry.inner().read().await.queue_statistics().await });
+    assert_eq!(consumers.len(), 3);
+    for consumer in consumers {
+        assert_eq!(consumer.enqueued, queue_statistics.total_messages, "{:?}", consumer);
+        assert_eq!(consumer.lag(), 0, "{:?}", consumer);
+    }
+}
+
+#[test]
+fn test_sharded_batches_give_the_plugin_every_message_once() {
+    let repo = (1..=10)
+        .fold(TestRepo::builder(), |builder, n| builder.file(&format!("src/file{}.rs", n), "fn f() {}\n").commit(&format!("Add file {}", n)))
+        .build()
+        .expect("Failed to create test repository");
+    let registry = SharedPluginRegistry::new();
+    let batches_of_eight = ConsumerPreferences { preferred_batch_size: 8, ..ConsumerPreferences::default() };
+    let (sharded, sharded_probe) = ProbePlugin::new("sharded");
+    let sharded = sharded.preferring(ConsumerPreferences { requires_ordered_delivery: false, ..batches_of_eight.clone() });
+    let (ordered, ordered_probe) = ProbePlugin::new("ordered");
+    let ordered = ordered.preferring(batches_of_eight);
+
+    scan_with(&repo, &registry, vec![sharded, ordered]);
+
+    // Together the shards hold exactly the messages the unsharded plugin was given, in its order
+    let sharded_probe = sharded_probe.lock().unwrap();
+    let ordered_probe = ordered_probe.lock().unwrap();
+    let mut sequences = sharded_probe.sequences.clone();
+    sequences.sort_unstable();
+    assert!(!sequences.is_empty());
+    assert_eq!(sequences, ordered_probe.sequences);
+    assert!(sharded_probe.batches.len() > ordered_probe.batches.len(), "{:?}", sharded_probe.batches);
+    assert!(sharded_probe.scan_completed());
+}
+
+#[test]
+fn test_spilled_messages_reach_a_reading_consumer() {
+    let repo = (1..=10)
+        .fold(TestRepo::builder(), |builder, n| builder.file(&format!("src/file{}.rs", n), "fn f() {}\n").commit(&format!("Add file {}", n)))
+        .build()
+        .expect("Failed to create test repository");
+    let spill_directory = tempfile::TempDir::new().unwrap();
+    let registry = SharedPluginRegistry::new();
+    let (reader, probe) = ProbePlugin::new("reader");
+
+    // The reader stalls while the scan overflows a queue with room for a few messages
+    let config = MultiConsumerConfig {
+        memory_limit: 2048,
+        backpressure: Some(BackpressurePolicy::SpillToDisk),
+        spill_directory: Some(spill_directory.path().to_path_buf()),
+        ..MultiConsumerConfig::default()
+    };
+    let queue = scan_through(config, &repo, &registry, vec![reader.stalling_on(1, Duration::from_millis(200))]);
+
+    // Every message is read back from disk, in order
+    let queue_statistics = block_on(queue.get_statistics());
+    assert!(queue_statistics.messages_spilled > 0, "{:?}", queue_statistics);
+    let probe = probe.lock().unwrap();
+    assert_eq!(probe.sequences, (0..queue_statistics.total_messages).collect::<Vec<_>>());
+    assert!(probe.scan_completed());
+
+    // Having acknowledged everything, the reader holds no queue memory
+    let usage = block_on(async { registry.inner().read().await.memory_usage().await });
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].queue_bytes, 0);
+}
+
+#[test]
+fn test_memory_report_lists_drained_consumers() {
+    let repo = fixture_repo();
+
+    let output = gstats(repo.path(), &["--memory-report", "commits"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "gstats failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    // The commits plugin has consumed and acknowledged the whole scan, keeping only its analysis
+    let commits = rows_starting_with(&stdout, "commits");
+    assert_eq!(commits.len(), 1, "unexpected output:\n{}", stdout);
+    let columns: Vec<_> = commits[0].split_whitespace().collect();
+    assert_eq!(&columns[1..3], ["0", "B"], "unexpected row: {}", commits[0]);
+    assert_ne!(columns[3], "0", "unexpected row: {}", commits[0]);
+    assert!(stdout.contains("Analysis of 3 commits"), "unexpected output:\n{}", stdout);
+}
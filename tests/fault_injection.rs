@@ -0,0 +1,325 @@
+//! Recovery from injected failures
+//!
+//! Some tests inject faults into a whole `gstats` run through the
+//! `GSTATS_FAULT_*` environment variables, so the scanner, the queue and the
+//! consumer driver meet them as they would in production. Others install a
+//! plan and exercise the queue, registry and notifications directly.
+//!
+//! Requires the `fault-injection` feature:
+//! `cargo test --features fault-injection --test fault_injection`
+
+#![cfg(feature = "fault-injection")]
+
+use async_trait::async_trait;
+use gstats::faults::{self, FaultPlan};
+use gstats::notifications::events::{PluginEvent, UnifiedEvent};
+use gstats::notifications::traits::{NotificationManager, Subscriber};
+use gstats::notifications::typed_publishers::PluginEventPublisher;
+use gstats::notifications::{AsyncNotificationManager, NotificationResult};
+use gstats::plugin::traits::{ConsumerPlugin, MessageConsumer, PluginType};
+use gstats::plugin::{Plugin, PluginError, PluginInfo, PluginRegistry, PluginRequest, PluginResponse, PluginResult, ScanContext};
+use gstats::queue::{MultiConsumerQueue, QueueEvent};
+use gstats::scanner::messages::{MessageData, MessageHeader, ScanMessage};
+use gstats::test_support::TestRepo;
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Run gstats with a fault planned through its environment, without the scan cache or user configuration
+fn gstats_with_fault(fault: (&str, &str), args: &[&str]) -> Output {
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .file("src/lib.rs", "pub fn one() {}\npub fn two() {}\n")
+        .commit("Extend library")
+        .file("README.md", "# Test\n")
+        .commit("Add readme")
+        .build()
+        .expect("Failed to create test repository");
+    let home = tempfile::TempDir::new().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(["--no-cache", "--no-color"])
+        .args(args)
+        .current_dir(repo.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env_remove("RUST_LOG")
+        .env(fault.0, fault.1)
+        .output()
+        .expect("Failed to run gstats")
+}
+
+#[test]
+fn test_plugin_panic_during_scan_cancels_the_plugin() {
+    let output = gstats_with_fault(("GSTATS_FAULT_PLUGIN_PANIC", "commits:2"), &["commits"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The run carries on without the plugin rather than ending with the panic
+    assert!(output.status.success(), "gstats failed:\n{}", stderr);
+    assert!(stderr.contains("plugin 'commits' panicked during process_batch: injected panic in plugin 'commits'"), "{}", stderr);
+    assert!(stderr.contains("Plugin 'commits' was cancelled"), "{}", stderr);
+    assert!(!stderr.contains("Application panicked"), "{}", stderr);
+}
+
+#[test]
+fn test_delayed_plugin_is_cancelled_at_its_deadline_during_scan() {
+    let started = Instant::now();
+    let output = gstats_with_fault(("GSTATS_FAULT_CONSUMER_DELAY", "commits:30000"), &["--plugin-timeout", "1", "commits"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success(), "gstats failed:\n{}", stderr);
+    assert!(started.elapsed() < Duration::from_secs(20), "the delayed plugin held up the run");
+    assert!(stderr.contains("plugin 'commits' exceeded its 1s deadline"), "{}", stderr);
+}
+
+#[test]
+fn test_queue_error_during_scan_fails_the_run() {
+    let output = gstats_with_fault(("GSTATS_FAULT_QUEUE_ERROR", "2"), &["commits"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The scan stops with the queue's error rather than reporting partial results
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(stderr.contains("Injected queue fault"), "{}", stderr);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Analysis of"));
+}
+
+#[test]
+fn test_dropped_notifications_during_scan_do_not_fail_the_run() {
+    let output = gstats_with_fault(("GSTATS_FAULT_NOTIFICATION_DROP", "3"), &["commits"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success(), "gstats failed:\n{}", stderr);
+    assert!(stderr.contains("injected notification drop"), "{}", stderr);
+}
+
+/// The fault plan is global, so tests that install one must not overlap
+static PLAN_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Consumer plugin that accepts every message
+struct TestConsumer {
+    info: PluginInfo,
+}
+
+impl TestConsumer {
+    fn new(name: &str) -> Self {
+        Self {
+            info: PluginInfo::new(
+                name.to_string(),
+                "1.0.0".to_string(),
+                20250727,
+                "Consumer for fault injection tests".to_string(),
+                "Test Author".to_string(),
+                PluginType::Processing,
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Plugin for TestConsumer {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn execute(&self, _request: PluginRequest) -> PluginResult<PluginResponse> {
+        Err(PluginError::execution_failed("not supported"))
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for TestConsumer {
+    async fn start_consuming(&mut self, _consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn process_message(&self, _consumer: &dyn MessageConsumer, _message: Arc<ScanMessage>) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn handle_queue_event(&self, _event: &QueueEvent) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+}
+
+/// Subscriber that records every event it is given
+struct Recorder {
+    id: String,
+    events: Mutex<Vec<UnifiedEvent>>,
+}
+
+impl Recorder {
+    fn new(id: &str) -> Arc<Self> {
+        Arc::new(Self { id: id.to_string(), events: Mutex::new(Vec::new()) })
+    }
+
+    fn plugin_errors(&self) -> Vec<(String, String, bool)> {
+        self.events.lock().unwrap().iter()
+            .filter_map(|event| match event {
+                UnifiedEvent::Plugin(PluginEvent::PluginError { plugin_id, error_type, recoverable, .. }) => {
+                    Some((plugin_id.clone(), error_type.clone(), *recoverable))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Subscriber<UnifiedEvent> for Recorder {
+    async fn handle_event(&self, event: UnifiedEvent) -> NotificationResult<()> {
+        self.events.lock().unwrap().push(event);
+        Ok(())
+    }
+
+    fn subscriber_id(&self) -> &str {
+        &self.id
+    }
+}
+
+fn message() -> ScanMessage {
+    ScanMessage::new(MessageHeader::new(0, "fault-scan".to_string()), MessageData::None)
+}
+
+fn plugin_error(plugin: &str) -> UnifiedEvent {
+    UnifiedEvent::Plugin(PluginEvent::PluginError {
+        plugin_id: plugin.to_string(),
+        error_type: "test".to_string(),
+        error_message: "test".to_string(),
+        recoverable: true,
+        occurred_at: SystemTime::now(),
+    })
+}
+
+async fn started_queue() -> MultiConsumerQueue {
+    let queue = MultiConsumerQueue::new(
+        Arc::new(AsyncNotificationManager::new()),
+        Arc::new(AsyncNotificationManager::new()),
+    );
+    queue.start().await.unwrap();
+    queue
+}
+
+#[tokio::test]
+async fn test_queue_errors_leave_sequences_contiguous() {
+    let _guard = PLAN_LOCK.lock().await;
+    faults::install(FaultPlan::new().queue_error_every(3));
+    let queue = started_queue().await;
+
+    let mut sequences = Vec::new();
+    let mut failures = 0;
+    for _ in 0..9 {
+        match queue.enqueue(message()).await {
+            Ok(sequence) => sequences.push(sequence),
+            Err(_) => failures += 1,
+        }
+    }
+    faults::install(FaultPlan::new());
+
+    // A rejected message is not assigned a sequence, so retries fill no gaps
+    assert_eq!(failures, 3);
+    assert!(sequences.windows(2).all(|pair| pair[1] == pair[0] + 1), "{:?}", sequences);
+    assert!(queue.enqueue(message()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_plugin_panic_is_contained() {
+    let _guard = PLAN_LOCK.lock().await;
+    faults::install(FaultPlan::new().plugin_panic("fragile", 2));
+    let queue = started_queue().await;
+    let events = Arc::new(AsyncNotificationManager::<UnifiedEvent>::new());
+    let recorder = Recorder::new("recorder");
+    events.subscribe(recorder.clone()).await.unwrap();
+
+    let mut registry = PluginRegistry::new();
+    registry.set_plugin_publisher(Arc::new(PluginEventPublisher::new(Arc::clone(&events))));
+    registry.register_plugin(Box::new(TestConsumer::new("fragile"))).await.unwrap();
+    registry.register_plugin(Box::new(TestConsumer::new("sturdy"))).await.unwrap();
+    let fragile = queue.register_consumer("fragile".to_string()).await.unwrap();
+    let sturdy = queue.register_consumer("sturdy".to_string()).await.unwrap();
+    registry.start_consuming("fragile", queue.register_consumer("fragile".to_string()).await.unwrap()).await.unwrap();
+    registry.start_consuming("sturdy", queue.register_consumer("sturdy".to_string()).await.unwrap()).await.unwrap();
+
+    let message = Arc::new(message());
+    assert!(registry.process_message("fragile", &fragile, Arc::clone(&message)).await.is_ok());
+    let result = registry.process_message("fragile", &fragile, Arc::clone(&message)).await;
+    faults::install(FaultPlan::new());
+
+    // The panic becomes an error and the plugin is cancelled
+    assert!(matches!(&result, Err(PluginError::ExecutionFailed { message }) if message.contains("panicked")), "{:?}", result);
+    assert!(registry.cancellation_token("fragile").unwrap().is_cancelled());
+    assert_eq!(recorder.plugin_errors(), vec![("fragile".to_string(), "panic".to_string(), false)]);
+    assert!(matches!(
+        registry.process_message("fragile", &fragile, Arc::clone(&message)).await,
+        Err(PluginError::Timeout { .. })
+    ));
+
+    // Other plugins carry on
+    assert!(registry.process_message("sturdy", &sturdy, message).await.is_ok());
+    assert!(!registry.cancellation_token("sturdy").unwrap().is_cancelled());
+}
+
+#[tokio::test]
+async fn test_delayed_consumer_hits_deadline() {
+    let _guard = PLAN_LOCK.lock().await;
+    faults::install(FaultPlan::new().consumer_delay(Some("sluggish"), Duration::from_secs(10)));
+    let queue = started_queue().await;
+
+    let mut registry = PluginRegistry::new();
+    registry.set_execution_timeout(Some(Duration::from_millis(50)));
+    registry.register_plugin(Box::new(TestConsumer::new("sluggish"))).await.unwrap();
+    registry.register_plugin(Box::new(TestConsumer::new("prompt"))).await.unwrap();
+    let sluggish = queue.register_consumer("sluggish".to_string()).await.unwrap();
+    let prompt = queue.register_consumer("prompt".to_string()).await.unwrap();
+    registry.start_consuming("sluggish", queue.register_consumer("sluggish".to_string()).await.unwrap()).await.unwrap();
+    registry.start_consuming("prompt", queue.register_consumer("prompt".to_string()).await.unwrap()).await.unwrap();
+
+    let message = Arc::new(message());
+    let started = std::time::Instant::now();
+    let result = registry.process_message("sluggish", &sluggish, Arc::clone(&message)).await;
+    let prompt_result = registry.process_message("prompt", &prompt, message).await;
+    faults::install(FaultPlan::new());
+
+    assert!(matches!(result, Err(PluginError::Timeout { .. })));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(registry.cancellation_token("sluggish").unwrap().is_cancelled());
+    assert!(prompt_result.is_ok());
+}
+
+#[tokio::test]
+async fn test_dropped_notifications_do_not_fail_publishing() {
+    let _guard = PLAN_LOCK.lock().await;
+    faults::install(FaultPlan::new().notification_drop_every(3));
+    let events = AsyncNotificationManager::<UnifiedEvent>::new();
+    let recorder = Recorder::new("recorder");
+    events.subscribe(recorder.clone()).await.unwrap();
+
+    for _ in 0..6 {
+        assert!(events.publish(plugin_error("noisy")).await.is_ok());
+    }
+    faults::install(FaultPlan::new());
+
+    assert_eq!(recorder.plugin_errors().len(), 4);
+    assert!(events.publish(plugin_error("noisy")).await.is_ok());
+    assert_eq!(recorder.plugin_errors().len(), 5);
+}
@@ -16,7 +16,7 @@ use gstats::plugin::data_coordinator::DataCoordinator;
 use gstats::notifications::events::PluginEvent;
 use gstats::notifications::{AsyncNotificationManager, NotificationResult};
 use gstats::notifications::traits::{Subscriber, NotificationManager};
-use gstats::plugin::builtin::export::ExportPlugin;
+use gstats::plugin::ExportPlugin;
 
 /// Mock subscriber to capture exported data for testing
 struct MockExportSubscriber {
@@ -87,6 +87,7 @@ async fn test_plugin_data_export_creation() {
         data: DataPayload::Rows(Arc::new(rows)),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     };
     
     assert_eq!(export.plugin_id, "test_plugin");
@@ -128,6 +129,7 @@ async fn test_data_coordinator_functionality() {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     let metrics_export = Arc::new(PluginDataExport {
@@ -144,6 +146,7 @@ async fn test_data_coordinator_functionality() {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     // Add data from plugins
@@ -209,6 +212,7 @@ async fn test_notification_based_export_pipeline() {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     let event1 = PluginEvent::DataReady {
@@ -271,6 +275,7 @@ async fn test_export_plugin_data_collection() {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     let metrics_export = Arc::new(PluginDataExport {
@@ -297,6 +302,7 @@ async fn test_export_plugin_data_collection() {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     // Simulate DataReady events
@@ -438,6 +444,7 @@ async fn test_hierarchical_data_export() {
         data: DataPayload::Tree(Arc::new(root)),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     };
     
     assert_eq!(export.data_type, DataExportType::Hierarchical);
@@ -483,6 +490,7 @@ async fn test_key_value_data_export() {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     };
     
     assert_eq!(export.data_type, DataExportType::KeyValue);
@@ -575,6 +583,7 @@ fn create_test_commits_export() -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -619,6 +628,7 @@ fn create_test_metrics_export() -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -663,5 +673,6 @@ fn create_test_debug_export() -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
\ No newline at end of file
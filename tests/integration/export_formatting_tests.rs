@@ -51,7 +51,11 @@ async fn test_csv_formatter_with_tabular_data() {
     let test_data = create_sample_tabular_data();
     let formatted = export_plugin.format_csv(&[test_data]).await.unwrap();
     
-    let lines: Vec<&str> = formatted.lines().collect();
+    // The title and description come first as comment lines
+    assert!(formatted.starts_with("# Sample Data\n"));
+    let lines: Vec<&str> = formatted.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
     assert_eq!(lines.len(), 3); // Header + 2 data rows
     
     // Verify header
@@ -219,7 +223,8 @@ async fn test_key_value_data_formatting() {
     assert!(console_formatted.contains("Summary Statistics"));
     assert!(console_formatted.contains("total_files"));
     assert!(console_formatted.contains("1250"));
-    assert!(console_formatted.contains(":"));
+    assert!(console_formatted.contains("Key"));
+    assert!(console_formatted.contains("Value"));
 }
 
 #[tokio::test]
@@ -231,7 +236,10 @@ async fn test_hierarchical_data_formatting() {
     // Test console formatting for tree data
     let console_formatted = format_as_console(&export_plugin, &[tree_data.clone()]).await.unwrap();
     assert!(console_formatted.contains("Project Structure"));
-    assert!(console_formatted.contains("Tree: Root Directory"));
+    assert!(console_formatted.contains("Root Directory: project_root"));
+    assert!(console_formatted.contains("├─ src"));
+    assert!(console_formatted.contains("│  └─ lib.rs"));
+    assert!(console_formatted.contains("└─ tests"));
     
     // Test JSON formatting
     let json_formatted = export_plugin.format_json(&[tree_data]).await.unwrap();
@@ -239,6 +247,9 @@ async fn test_hierarchical_data_formatting() {
     
     let obj = parsed.as_object().unwrap();
     assert!(obj.contains_key("structure"));
+    let tree = &obj["structure"]["tree"];
+    assert_eq!(tree["value"], "project_root");
+    assert_eq!(tree["children"][0]["children"][1]["label"], "lib.rs");
 }
 
 #[tokio::test]
@@ -345,6 +356,7 @@ fn create_sample_tabular_data() -> Arc<PluginDataExport> {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -373,6 +385,7 @@ fn create_commits_data() -> Arc<PluginDataExport> {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -401,6 +414,7 @@ fn create_metrics_data() -> Arc<PluginDataExport> {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -423,6 +437,7 @@ fn create_key_value_data() -> Arc<PluginDataExport> {
         data: DataPayload::KeyValue(Arc::new(data)),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -451,6 +466,7 @@ fn create_hierarchical_data() -> Arc<PluginDataExport> {
         data: DataPayload::Tree(Arc::new(root)),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -467,6 +483,7 @@ fn create_empty_data() -> Arc<PluginDataExport> {
         data: DataPayload::Empty,
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -495,6 +512,7 @@ fn create_data_with_special_characters() -> Arc<PluginDataExport> {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -525,6 +543,7 @@ fn create_large_dataset(size: usize) -> Arc<PluginDataExport> {
         data: DataPayload::Rows(Arc::new(rows)),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -561,5 +580,6 @@ fn create_data_with_export_hints() -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
\ No newline at end of file
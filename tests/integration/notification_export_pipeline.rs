@@ -12,7 +12,7 @@ use tokio::sync::RwLock;
 
 use gstats::plugin::data_export::*;
 use gstats::plugin::data_coordinator::DataCoordinator;
-use gstats::plugin::builtin::export::ExportPlugin;
+use gstats::plugin::ExportPlugin;
 use gstats::notifications::events::PluginEvent;
 use gstats::notifications::{AsyncNotificationManager, NotificationResult};
 use gstats::notifications::traits::{Subscriber, NotificationManager};
@@ -85,6 +85,7 @@ async fn test_export_plugin_data_coordination() {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     let event = PluginEvent::DataReady {
@@ -205,6 +206,7 @@ async fn test_export_format_selection() {
             },
         },
         timestamp: SystemTime::now(),
+        repository: None,
     });
     
     // Verify export hints are properly structured
@@ -375,6 +377,7 @@ fn create_commits_test_data() -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -414,6 +417,7 @@ fn create_metrics_test_data() -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -445,6 +449,7 @@ fn create_debug_test_data() -> Arc<PluginDataExport> {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -469,6 +474,7 @@ fn create_numbered_test_data(number: usize) -> Arc<PluginDataExport> {
         ])),
         export_hints: ExportHints::default(),
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
 
@@ -509,5 +515,6 @@ fn create_large_test_dataset(size: usize) -> Arc<PluginDataExport> {
             custom_hints: HashMap::new(),
         },
         timestamp: SystemTime::now(),
+        repository: None,
     })
 }
\ No newline at end of file
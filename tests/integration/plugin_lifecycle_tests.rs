@@ -330,6 +330,9 @@ fn create_commit_message(author: &str, hash: &str, message: &str) -> ScanMessage
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64,
+        committer: author.to_string(),
+        author_timestamp: 0,
+        parents: vec![],
         changed_files: vec![],
     };
     
@@ -2,11 +2,16 @@
 use std::process::Command;
 use std::path::Path;
 
+/// Year prefix of log timestamps written today, e.g. `2026-`
+fn current_year() -> String {
+    chrono::Local::now().format("%Y-").to_string()
+}
+
 #[test]
 fn test_logger_initialization() {
     // This test verifies logger initialization is working correctly
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--help"])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--help"])
         .output()
         .expect("Failed to execute command");
     
@@ -20,15 +25,15 @@ fn test_logger_initialization() {
 #[test]
 fn test_timestamp_format_validation() {
     // This test verifies timestamp formatting is working correctly
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--verbose", "--repo", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--verbose", "--repo", "."])
         .output()
         .expect("Failed to execute command");
     
     let stderr = String::from_utf8_lossy(&output.stderr);
     
     // Look for YYYY-MM-DD HH:mm:ss format
-    assert!(stderr.contains("2025-"), 
+    assert!(stderr.contains(&current_year()), 
         "Expected timestamp format YYYY-MM-DD HH:mm:ss, got: {}", stderr);
     assert!(stderr.contains(" DBG ") || stderr.contains(" INF "), 
         "Expected log level markers in output: {}", stderr);
@@ -37,8 +42,8 @@ fn test_timestamp_format_validation() {
 #[test]
 fn test_json_format_output_structure() {
     // This test verifies JSON format is working correctly
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--log-format", "json", "--repo", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--log-format", "json", "--repo", "."])
         .output()
         .expect("Failed to execute command");
     
@@ -54,8 +59,8 @@ fn test_json_format_output_structure() {
 #[test]
 fn test_log_level_filtering() {
     // This test verifies log level filtering is working correctly
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--quiet", "--repo", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--quiet", "--repo", "."])
         .output()
         .expect("Failed to execute command");
     
@@ -69,13 +74,13 @@ fn test_log_level_filtering() {
 #[test]
 fn test_format_switching() {
     // This test verifies format switching is working correctly
-    let text_output = Command::new("cargo")
-        .args(&["run", "--", "--repo", "."])
+    let text_output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--repo", "."])
         .output()
         .expect("Failed to execute command");
     
-    let json_output = Command::new("cargo")
-        .args(&["run", "--", "--log-format", "json", "--repo", "."])
+    let json_output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--log-format", "json", "--repo", "."])
         .output()
         .expect("Failed to execute command");
     
@@ -95,8 +100,8 @@ fn test_file_output_functionality() {
     // Clean up first to ensure fresh start
     let _ = std::fs::remove_file(temp_file);
     
-    let _output = Command::new("cargo")
-        .args(&["run", "--", "--log-file", temp_file, "--verbose", "--repo", "."])
+    let _output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--log-file", temp_file, "--verbose", "--repo", "."])
         .output()
         .expect("Failed to execute command");
     
@@ -106,7 +111,7 @@ fn test_file_output_functionality() {
     
     // Verify file contains expected content
     let file_content = std::fs::read_to_string(temp_file).unwrap_or_default();
-    assert!(file_content.contains(" INF ") && file_content.contains("2025-"), 
+    assert!(file_content.contains(" INF ") && file_content.contains(&current_year()), 
         "Expected log file to contain timestamp and log levels, got: {}", file_content);
     
     // Clean up
@@ -121,8 +126,8 @@ fn test_independent_file_log_levels() {
     // Clean up first to ensure fresh start
     let _ = std::fs::remove_file(temp_file);
     
-    let output = Command::new("cargo")
-        .args(&["run", "--", "--quiet", "--log-file", temp_file, "--log-file-level", "debug", "--repo", "."])
+    let output = Command::new(env!("CARGO_BIN_EXE_gstats"))
+        .args(&["--quiet", "--log-file", temp_file, "--log-file-level", "debug", "--repo", "."])
         .output()
         .expect("Failed to execute command");
     
@@ -1,6 +1,4 @@
-use std::process::Command;
-use tempfile::TempDir;
-use std::fs;
+use gstats::test_support::TestRepo;
 
 #[test]
 #[ignore = "Skipped due to missing authors plugin. Test expects 'authors' command that doesn't exist."]
@@ -14,43 +12,11 @@ fn test_no_duplicate_log_after_plugin_output() {
     // This test should be re-enabled once an authors plugin is implemented, or updated
     // to use one of the existing plugins (commits, export, metrics).
     
-    // Create a temporary directory for testing
-    let temp_dir = TempDir::new().expect("Failed to create temp dir");
-    
-    // Initialize a git repo in the temp directory
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["init"])
-        .output()
-        .expect("Failed to init git repo");
-        
-    // Configure git user
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["config", "user.email", "test@example.com"])
-        .output()
-        .expect("Failed to set git email");
-        
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["config", "user.name", "Test User"])
-        .output()
-        .expect("Failed to set git name");
-    
-    // Create a test file and commit
-    fs::write(temp_dir.path().join("test.txt"), "Initial content").expect("Failed to write file");
-    
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["add", "."])
-        .output()
-        .expect("Failed to add files");
-        
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["commit", "-m", "Initial commit"])
-        .output()
-        .expect("Failed to commit");
+    let _repo = TestRepo::builder()
+        .file("test.txt", "Initial content")
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
     
     // This test is currently disabled - see ignore attribute above
     // When re-enabled, update to use an existing plugin or implement authors plugin
@@ -62,43 +28,11 @@ fn test_log_level_for_analysis_summary() {
     // FIXME: This test is also skipped for the same reason as above.
     // It expects an "authors" plugin that doesn't exist in the current implementation.
     
-    // Create a temporary directory for testing
-    let temp_dir = TempDir::new().expect("Failed to create temp dir");
-    
-    // Initialize a git repo in the temp directory
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["init"])
-        .output()
-        .expect("Failed to init git repo");
-        
-    // Configure git user
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["config", "user.email", "test@example.com"])
-        .output()
-        .expect("Failed to set git email");
-        
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["config", "user.name", "Test User"])
-        .output()
-        .expect("Failed to set git name");
-    
-    // Create a test file and commit
-    fs::write(temp_dir.path().join("test.txt"), "Initial content").expect("Failed to write file");
-    
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["add", "."])
-        .output()
-        .expect("Failed to add files");
-        
-    Command::new("git")
-        .current_dir(temp_dir.path())
-        .args(&["commit", "-m", "Initial commit"])
-        .output()
-        .expect("Failed to commit");
+    let _repo = TestRepo::builder()
+        .file("test.txt", "Initial content")
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
     
     // This test is currently disabled - see ignore attribute above
     // When re-enabled, update to use an existing plugin or implement authors plugin
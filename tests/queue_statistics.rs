@@ -0,0 +1,42 @@
+//! Queue consumer lag and backoff statistics
+
+use gstats::notifications::AsyncNotificationManager;
+use gstats::queue::MultiConsumerQueue;
+use gstats::scanner::messages::{MessageData, MessageHeader, ScanMessage};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn message() -> ScanMessage {
+    ScanMessage::new(MessageHeader::new(0, "stats-scan".to_string()), MessageData::None)
+}
+
+#[tokio::test]
+async fn test_consumer_lag_and_backoff() {
+    let queue = MultiConsumerQueue::new(
+        Arc::new(AsyncNotificationManager::new()),
+        Arc::new(AsyncNotificationManager::new()),
+    );
+    queue.start().await.unwrap();
+    let consumer = queue.register_consumer("commits".to_string()).await.unwrap();
+    for _ in 0..5 {
+        queue.enqueue(message()).await.unwrap();
+    }
+
+    let stats = consumer.statistics().await;
+    assert_eq!((stats.enqueued, stats.acknowledged, stats.lag()), (5, 0, 5));
+
+    let batch = consumer.read_batch(3).await.unwrap();
+    consumer.acknowledge(batch.last().unwrap().header().sequence).await.unwrap();
+    let stats = consumer.statistics().await;
+    assert_eq!((stats.read, stats.acknowledged, stats.lag()), (3, 3, 2));
+    assert_eq!(stats.average_batch(), 3.0);
+
+    // Reading past the producer backs off
+    let rest = consumer.read_batch(10).await.unwrap();
+    consumer.acknowledge(rest.last().unwrap().header().sequence).await.unwrap();
+    assert!(consumer.read_next().await.unwrap().is_none());
+    assert!(!consumer.wait_for_messages(Duration::from_millis(20)).await.unwrap());
+    let stats = consumer.statistics().await;
+    assert_eq!((stats.lag(), stats.backoffs), (0, 2));
+    assert!(stats.backoff_time >= Duration::from_millis(20));
+}
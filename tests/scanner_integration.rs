@@ -60,6 +60,9 @@ fn test_commit_message_data() {
         author: "test@example.com".to_string(),
         message: "Fix bug in scanner".to_string(),
         timestamp: 1640995200, // Unix timestamp
+        committer: "test@example.com".to_string(),
+        author_timestamp: 1640995200,
+        parents: vec![],
         changed_files: vec![],
     };
     
@@ -69,7 +72,7 @@ fn test_commit_message_data() {
     };
     
     match message.data {
-        MessageData::CommitInfo { hash, author, message: msg, timestamp, changed_files } => {
+        MessageData::CommitInfo { hash, author, message: msg, timestamp, changed_files, .. } => {
             assert_eq!(hash, "abc123def456");
             assert_eq!(author, "test@example.com");
             assert_eq!(msg, "Fix bug in scanner");
@@ -145,6 +148,9 @@ fn test_message_data_variants() {
         author: "dev@test.com".to_string(),
         message: "Test".to_string(),
         timestamp: 1234567890,
+        committer: "dev@test.com".to_string(),
+        author_timestamp: 1234567890,
+        parents: vec![],
         changed_files: vec![],
     };
     
@@ -153,4 +159,953 @@ fn test_message_data_variants() {
         line_count: 10,
         complexity: 1.0,
     };
-}
\ No newline at end of file
+}
+#[test]
+fn test_filter_funnel_on_fixture_repository() {
+    use gstats::scanner::async_engine::scanners::diagnose_empty_scan;
+    use gstats::scanner::query::{AuthorFilter, FilePathFilter, QueryParams};
+    use gstats::test_support::TestRepo;
+    use std::path::PathBuf;
+
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .author("Bob", "bob@example.com")
+        .file("docs/guide.md", "# Guide\n")
+        .commit("Add guide")
+        .rename("src/lib.rs", "src/core.rs")
+        .commit("Rename library")
+        .build()
+        .expect("Failed to create test repository");
+
+    let query = QueryParams {
+        authors: AuthorFilter { include: vec!["bob".to_string()], exclude: Vec::new() },
+        file_paths: FilePathFilter { include: vec![PathBuf::from("tests/")], exclude: Vec::new(), weights: Vec::new() },
+        ..Default::default()
+    };
+    let funnel = diagnose_empty_scan(repo.path(), &query, &Default::default()).unwrap();
+
+    assert_eq!(funnel.total, 3);
+    assert_eq!(funnel.in_date_range, 3);
+    assert_eq!(funnel.matching_authors, 2);
+    assert_eq!(funnel.matching_paths, 0);
+    assert!(funnel.explain(&query).unwrap().contains("--include-path tests/"));
+}
+
+#[tokio::test]
+async fn test_compare_release_tag_with_branch() {
+    use gstats::app::comparison::{scan_ref, Comparison};
+    use gstats::scanner::identity::IdentityRules;
+    use gstats::scanner::imports::ImportRules;
+    use gstats::plugin::data_export::{DataPayload, Value};
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .tag("v1.0")
+        .author("Bob", "bob@example.com")
+        .file("src/extra.rs", "pub fn two() {}\npub fn three() {}\n")
+        .commit("Add extra")
+        .build()
+        .expect("Failed to create test repository");
+
+    let query = QueryParams::default();
+    let base = scan_ref(repo.path(), &query, &ImportRules::default(), &IdentityRules::default(), None, "v1.0").await.unwrap();
+    let head = scan_ref(repo.path(), &query, &ImportRules::default(), &IdentityRules::default(), None, "main").await.unwrap();
+    assert_eq!((base.commits, head.commits), (1, 2));
+
+    let exports = Comparison::new(base, head).exports();
+    let DataPayload::Rows(authors) = &exports[1].data else {
+        panic!("expected author rows");
+    };
+    assert_eq!(authors.len(), 2);
+    assert_eq!(authors[0].values[0], Value::String("Bob".to_string()));
+    assert_eq!(authors[0].values[5], Value::String("new".to_string()));
+
+    assert!(scan_ref(repo.path(), &query, &ImportRules::default(), &IdentityRules::default(), None, "v9.9").await.is_err());
+}
+
+#[tokio::test]
+async fn test_ref_matrix_scans_through_cache() {
+    use gstats::app::comparison::{resolve_ref, scan_ref, RefMatrix};
+    use gstats::cache::ScanCache;
+    use gstats::scanner::identity::IdentityRules;
+    use gstats::scanner::imports::ImportRules;
+    use gstats::plugin::data_export::{DataPayload, Value};
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .tag("v1.0")
+        .author("Bob", "bob@example.com")
+        .file("src/extra.rs", "pub fn two() {}\n")
+        .commit("Add extra")
+        .tag("v1.1")
+        .build()
+        .expect("Failed to create test repository");
+    assert_eq!(resolve_ref(repo.path(), "v1.1"), resolve_ref(repo.path(), "main"));
+    assert_ne!(resolve_ref(repo.path(), "v1.0"), resolve_ref(repo.path(), "main"));
+
+    let cache_dir = tempfile::TempDir::new().unwrap();
+    let cache = ScanCache::new(cache_dir.path().to_path_buf());
+    let query = QueryParams::default();
+    let mut refs = Vec::new();
+    for reference in ["v1.0", "main", "main"] {
+        refs.push(scan_ref(repo.path(), &query, &ImportRules::default(), &IdentityRules::default(), Some(&cache), reference).await.unwrap());
+    }
+    // The repeated scan is replayed from the cache with the same results
+    assert_eq!(refs[1], refs[2]);
+    refs.pop();
+
+    let exports = RefMatrix::new(refs).exports();
+    let DataPayload::Rows(metrics) = &exports[0].data else {
+        panic!("expected metric rows");
+    };
+    assert_eq!(metrics[0].values, vec![Value::String("Commits".to_string()), Value::Integer(1), Value::Integer(2)]);
+}
+
+#[tokio::test]
+async fn test_scan_cache_is_shared_by_date_windows() {
+    use futures::StreamExt;
+    use gstats::cache::ScanCache;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::{DateRange, QueryParams};
+    use gstats::test_support::TestRepo;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let day = |day: u64| 1_700_000_000 + day * 86400;
+    let repo = TestRepo::builder()
+        .file("a.txt", "one\n").commit_at("day 10", day(10) as i64)
+        .file("a.txt", "two\n").commit_at("day 20", day(20) as i64)
+        .file("a.txt", "three\n").commit_at("day 30", day(30) as i64)
+        .build()
+        .expect("Failed to create test repository");
+    let cache_dir = tempfile::TempDir::new().unwrap();
+    let cache = ScanCache::new(cache_dir.path().to_path_buf());
+
+    let scan = |since: u64, until: Option<u64>| {
+        let at = |day_number: u64| UNIX_EPOCH + Duration::from_secs(day(day_number));
+        let date_range = match until {
+            Some(until) => DateRange::new(at(since), at(until)),
+            None => DateRange::from(at(since)),
+        };
+        let scanner = EventDrivenScanner::new(QueryParams { date_range: Some(date_range), ..Default::default() })
+            .with_cache(cache.clone());
+        let path = repo.path().to_path_buf();
+        async move {
+            let messages: Vec<_> = scanner.scan_async(&path).await.unwrap().collect().await;
+            messages.into_iter().filter_map(|message| match message.unwrap().data {
+                MessageData::CommitInfo { message, .. } => Some(message.trim().to_string()),
+                _ => None,
+            }).collect::<Vec<_>>()
+        }
+    };
+    let entries = || std::fs::read_dir(cache_dir.path()).unwrap().count();
+
+    assert_eq!(scan(15, None).await, ["day 30", "day 20"]);
+    // A later window, as a relative --since gives the next day, replays the same entry
+    assert_eq!(scan(25, None).await, ["day 30"]);
+    assert_eq!(scan(15, Some(25)).await, ["day 20"]);
+    assert_eq!(entries(), 1);
+
+    // A window reaching further back than the entry rescans and widens it
+    assert_eq!(scan(5, None).await, ["day 30", "day 20", "day 10"]);
+    assert_eq!(scan(15, None).await, ["day 30", "day 20"]);
+    assert_eq!(entries(), 1);
+}
+
+#[tokio::test]
+async fn test_imported_code_is_credited_to_import_author() {
+    use gstats::app::comparison::scan_ref;
+    use gstats::scanner::identity::IdentityRules;
+    use gstats::scanner::imports::ImportRules;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Import upstream sources")
+        .tag("imported")
+        .author("Bob", "bob@example.com")
+        .file("vendor/zlib.c", "int inflate(void);\n")
+        .commit("Vendor zlib")
+        .file("src/main.rs", "fn main() {}\n")
+        .commit("Add entry point")
+        .build()
+        .expect("Failed to create test repository");
+
+    let imports = ImportRules {
+        commits: vec!["..imported".to_string()],
+        paths: vec!["vendor/".to_string()],
+        ..Default::default()
+    };
+    let stats = scan_ref(repo.path(), &QueryParams::default(), &imports, &IdentityRules::default(), None, "main").await.unwrap();
+    let authors: Vec<&str> = stats.authors.keys().map(String::as_str).collect();
+    assert_eq!(authors, vec!["Bob", "import"]);
+    assert_eq!(stats.authors["import"].commits, 2);
+    assert_eq!(stats.authors["Bob"].commits, 1);
+}
+
+#[tokio::test]
+async fn test_author_identities_are_merged() {
+    use gstats::app::comparison::scan_ref;
+    use gstats::scanner::identity::IdentityRules;
+    use gstats::scanner::imports::ImportRules;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .author("Jane Doe", "jane@example.com")
+        .file(".mailmap", "Jane Doe <jane@example.com> jdoe <jane@home.net>\n")
+        .commit("Add mailmap")
+        .author("jdoe", "jane@home.net")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Add library")
+        .author("Bobby", "bobby@laptop.local")
+        .file("src/main.rs", "fn main() {}\n")
+        .commit("Add entry point")
+        .build()
+        .expect("Failed to create test repository");
+
+    let identities = IdentityRules {
+        aliases: vec![("Bob <bob@example.com>".to_string(), vec!["bobby@laptop.local".to_string()])],
+        ..Default::default()
+    };
+    let stats = scan_ref(repo.path(), &QueryParams::default(), &ImportRules::default(), &identities, None, "main").await.unwrap();
+    let authors: Vec<&str> = stats.authors.keys().map(String::as_str).collect();
+    assert_eq!(authors, vec!["Bob", "Jane Doe"]);
+    assert_eq!(stats.authors["Jane Doe"].commits, 2);
+
+    let unmapped = IdentityRules { use_mailmap: false, ..Default::default() };
+    let stats = scan_ref(repo.path(), &QueryParams::default(), &ImportRules::default(), &unmapped, None, "main").await.unwrap();
+    assert_eq!(stats.authors.len(), 3);
+}
+
+#[tokio::test]
+async fn test_renamed_files_are_followed_to_current_name() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::{ChangeType, MessageData};
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("src/old.rs", "fn one() {}\nfn two() {}\nfn three() {}\n")
+        .commit("Add module")
+        .file("src/old.rs", "fn one() {}\nfn two() {}\nfn three() {}\nfn four() {}\n")
+        .commit("Extend module")
+        .rename("src/old.rs", "src/core.rs")
+        .commit("Rename module")
+        .build()
+        .expect("Failed to create test repository");
+
+    async fn file_changes(repo: &TestRepo, follow_renames: bool) -> Vec<(String, ChangeType, usize, usize)> {
+        let scanner = EventDrivenScanner::new(QueryParams::default()).with_follow_renames(follow_renames);
+        let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+        messages.into_iter().filter_map(|message| match message.unwrap().data {
+            MessageData::FileChange { path, change_type, insertions, deletions, .. } => {
+                Some((path, change_type, insertions, deletions))
+            }
+            _ => None,
+        }).collect()
+    }
+
+    // Newest first: the rename carries no churn and earlier changes use the current name
+    let followed = file_changes(&repo, true).await;
+    assert_eq!(followed[..2], [
+        ("src/core.rs".to_string(), ChangeType::Renamed, 0, 0),
+        ("src/core.rs".to_string(), ChangeType::Modified, 1, 0),
+    ]);
+    assert!(followed.iter().all(|(path, ..)| path != "src/old.rs"));
+
+    let unfollowed = file_changes(&repo, false).await;
+    assert!(unfollowed.contains(&("src/old.rs".to_string(), ChangeType::Deleted, 0, 4)));
+    assert!(unfollowed.contains(&("src/core.rs".to_string(), ChangeType::Added, 4, 0)));
+}
+
+#[tokio::test]
+async fn test_file_changes_carry_blob_sizes() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .binary_file("assets/logo.png", &[0u8; 1000])
+        .file("notes.txt", "one\n")
+        .commit("Add assets")
+        .binary_file("assets/logo.png", &[0u8; 3000])
+        .commit("Larger logo")
+        .remove("notes.txt")
+        .commit("Drop notes")
+        .build()
+        .expect("Failed to create test repository");
+
+    let scanner = EventDrivenScanner::new(QueryParams::default()).with_binary_files(true);
+    let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+    let sizes: Vec<(String, bool, Option<u64>)> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+        MessageData::FileChange { path, is_binary, binary_size, .. } => Some((path, is_binary, binary_size)),
+        _ => None,
+    }).collect();
+
+    // Newest first; a deleted file has no blob after the change
+    assert_eq!(sizes, vec![
+        ("notes.txt".to_string(), false, None),
+        ("assets/logo.png".to_string(), true, Some(3000)),
+        ("notes.txt".to_string(), false, Some(4)),
+        ("assets/logo.png".to_string(), true, Some(1000)),
+    ]);
+}
+
+#[tokio::test]
+async fn test_current_file_content_is_streamed_on_request() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .file("assets/logo.bin", "PNG\0\0data")
+        .commit("Initial commit")
+        .file("src/lib.rs", "pub fn one() {}\npub fn two() {}\n")
+        .commit("Add two")
+        .build()
+        .expect("Failed to create test repository");
+
+    async fn file_contents(repo: &TestRepo, enabled: bool) -> Vec<(String, String)> {
+        let scanner = EventDrivenScanner::new(QueryParams::default()).with_current_file_content(enabled);
+        let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+        messages.into_iter().filter_map(|message| match message.unwrap().data {
+            MessageData::FileContent { path, content, .. } => Some((path, content)),
+            _ => None,
+        }).collect()
+    }
+
+    // Only the current version of text files is streamed
+    assert_eq!(file_contents(&repo, true).await, vec![
+        ("src/lib.rs".to_string(), "pub fn one() {}\npub fn two() {}\n".to_string()),
+    ]);
+    assert!(file_contents(&repo, false).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_file_policies_count_files_each_consumer_is_not_given() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::file_policy::{FilePolicy, OversizedFiles};
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .file("src/table.rs", &"pub const ROW: u32 = 0;\n".repeat(100))
+        .binary_file("assets/logo.png", b"PNG\0\0data")
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    let limited = FilePolicy { max_file_size: Some(1024), binary_files: false, oversized: OversizedFiles::Skip };
+    let scanner = EventDrivenScanner::new(QueryParams::default())
+        .with_binary_files(true)
+        .with_current_file_content(true)
+        .with_file_policies(vec![
+            ("metrics".to_string(), limited),
+            ("loc".to_string(), FilePolicy { max_file_size: None, ..limited }),
+            ("summary".to_string(), FilePolicy { oversized: OversizedFiles::Truncate, ..limited }),
+        ]);
+    let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+
+    // Binary files have changes but no content, whichever plugins handle them
+    let contents: Vec<String> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+        MessageData::FileContent { path, .. } => Some(path),
+        _ => None,
+    }).collect();
+    assert_eq!(contents.len(), 2);
+    assert!(!contents.contains(&"assets/logo.png".to_string()));
+
+    assert_eq!(scanner.file_policy_warnings(), vec![
+        "metrics: skipped 1 file over its 1 KB limit, skipped 1 binary file change".to_string(),
+        "loc: skipped 1 binary file change".to_string(),
+        "summary: truncated 1 file to its 1 KB limit, skipped 1 binary file change".to_string(),
+    ]);
+}
+
+#[tokio::test]
+async fn test_legacy_encoded_content_is_decoded() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::encoding::ContentEncoding;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let utf16: Vec<u8> = [0xFF, 0xFE].into_iter()
+        .chain("fn wide() {}\n".encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    let repo = TestRepo::builder()
+        .binary_file("src/latin.c", b"/* D\xe9j\xe0 vu: r\xe9sum\xe9 */\nint x;\n")
+        .binary_file("src/wide.rs", &utf16)
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    async fn file_contents(repo: &TestRepo, encoding: ContentEncoding) -> Vec<(String, String)> {
+        let scanner = EventDrivenScanner::new(QueryParams::default())
+            .with_current_file_content(true)
+            .with_content_encoding(encoding);
+        let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+        let mut contents: Vec<_> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+            MessageData::FileContent { path, content, .. } => Some((path, content)),
+            _ => None,
+        }).collect();
+        contents.sort();
+        contents
+    }
+
+    // The Windows-1252 file is detected, and UTF-16 with a byte order mark is text rather than binary
+    assert_eq!(file_contents(&repo, ContentEncoding::Auto).await, vec![
+        ("src/latin.c".to_string(), "/* Déjà vu: résumé */\nint x;\n".to_string()),
+        ("src/wide.rs".to_string(), "fn wide() {}\n".to_string()),
+    ]);
+    let greek = ContentEncoding::parse("iso-8859-7").unwrap();
+    assert_eq!(file_contents(&repo, greek).await[0].1, "/* Dιjΰ vu: rιsumι */\nint x;\n");
+}
+
+#[tokio::test]
+async fn test_required_files_are_streamed_by_path() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .file(".github/CODEOWNERS", "/src/ @alice\n")
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    let required = vec![".github/CODEOWNERS".to_string(), "CODEOWNERS".to_string()];
+    for current_file_content in [false, true] {
+        let mut query = QueryParams::default();
+        query.file_paths.include = vec!["src/".into()];
+        let scanner = EventDrivenScanner::new(query)
+            .with_current_file_content(current_file_content)
+            .with_required_files(required.clone());
+        let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+        let paths: Vec<String> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+            MessageData::FileContent { path, .. } => Some(path),
+            _ => None,
+        }).collect();
+
+        // Required files are streamed despite the path filter, missing ones are skipped
+        let mut expected = vec![".github/CODEOWNERS".to_string()];
+        if current_file_content {
+            expected.insert(0, "src/lib.rs".to_string());
+        }
+        assert_eq!(paths, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_content_fingerprints_are_streamed_without_content() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::fingerprints::FingerprintConfig;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let body = "let mut total = 0;\nfor value in values {\n    total += value;\n}\ntotal\n";
+    let repo = TestRepo::builder()
+        .file("src/a.rs", &format!("fn a(values: &[u32]) -> u32 {{\n{body}}}\n"))
+        .file("src/b.rs", &format!("fn b(values: &[u32]) -> u32 {{\n    // Same as a\n{body}}}\n"))
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    let config = FingerprintConfig { min_block: 4, ..FingerprintConfig::default() };
+    for current_file_content in [false, true] {
+        let scanner = EventDrivenScanner::new(QueryParams::default())
+            .with_current_file_content(current_file_content)
+            .with_content_fingerprints(Some(config));
+        let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+        let mut contents = 0;
+        let mut fingerprints = Vec::new();
+        for message in messages {
+            match message.unwrap().data {
+                MessageData::FileContent { .. } => contents += 1,
+                MessageData::ContentFingerprints { path, lines, fingerprints: blocks, .. } => fingerprints.push((path, lines, blocks)),
+                _ => {}
+            }
+        }
+
+        // Content is only streamed when asked for; the shared body gives both files a common block
+        assert_eq!(contents, if current_file_content { 2 } else { 0 });
+        assert_eq!(fingerprints.iter().map(|(path, lines, _)| (path.as_str(), *lines)).collect::<Vec<_>>(),
+            [("src/a.rs", 7), ("src/b.rs", 8)]);
+        let shared: Vec<_> = fingerprints[1].2.iter()
+            .filter(|block| fingerprints[0].2.iter().any(|other| other.hash == block.hash))
+            .map(|block| (block.start_line, block.end_line))
+            .collect();
+        assert_eq!(shared, [(3, 6), (4, 7), (5, 8)]);
+    }
+}
+
+#[tokio::test]
+async fn test_identical_blobs_are_read_once() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("LICENSE", "MIT License\n")
+        .file("vendor/LICENSE", "MIT License\n")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    let scanner = EventDrivenScanner::new(QueryParams::default())
+        .with_current_file_content(true)
+        .with_required_files(vec!["LICENSE".to_string()]);
+    let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+    let files = messages.into_iter().filter(|message| {
+        matches!(message.as_ref().unwrap().data, MessageData::FileContent { .. })
+    }).count();
+
+    // Every path is streamed, but the shared licence blob is read from the object database once
+    assert_eq!(files, 3);
+    let stats = scanner.content_cache_stats();
+    assert_eq!((stats.blobs_read, stats.hits), (2, 1));
+    assert_eq!(stats.bytes_shared, "MIT License\n".len() as u64);
+}
+
+#[tokio::test]
+async fn test_aborted_scan_reads_no_further_history() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::shutdown::ShutdownController;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("README.md", "one\n")
+        .commit("First commit")
+        .file("README.md", "two\n")
+        .commit("Second commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    let shutdown = ShutdownController::new();
+    shutdown.abort("deadline reached");
+    let scanner = EventDrivenScanner::new(QueryParams::default())
+        .with_current_file_content(true)
+        .with_shutdown(shutdown);
+    let messages: Vec<_> = scanner.scan_async(repo.path()).await.unwrap().collect().await;
+
+    // The scan ends cleanly without commits or file content
+    assert!(messages.iter().all(|message| !matches!(
+        message.as_ref().unwrap().data,
+        MessageData::CommitInfo { .. } | MessageData::FileContent { .. }
+    )));
+}
+
+#[tokio::test]
+async fn test_patch_series_matches_repository_scan() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .file("README.md", "# Project\n")
+        .commit("Initial commit")
+        .file("src/lib.rs", "pub fn one() {}\npub fn two() {}\n")
+        .commit("Add two")
+        .author("Bob", "bob@example.com")
+        .rename("src/lib.rs", "src/core.rs")
+        .remove("README.md")
+        .commit("Rename library, drop readme")
+        .build()
+        .expect("Failed to create test repository");
+
+    // The series covers every commit after the initial one
+    let series = tempfile::tempdir().unwrap();
+    let mbox = series.path().join("series.mbox");
+    std::fs::write(&mbox, repo.git(&["format-patch", "--stdout", "HEAD~2"], &[]).unwrap()).unwrap();
+
+    async fn scan(path: &std::path::Path, patch_series: bool) -> Vec<MessageData> {
+        let scanner = EventDrivenScanner::new(QueryParams::default()).with_patch_series(patch_series);
+        let messages: Vec<_> = scanner.scan_async(path).await.unwrap().collect().await;
+        messages.into_iter().map(|message| match message.unwrap().data {
+            // Repository commit titles keep their trailing newline, patch subjects do not
+            // and patches do not record parents
+            MessageData::CommitInfo { hash, author, message, timestamp, committer, author_timestamp, changed_files, .. } => {
+                MessageData::CommitInfo {
+                    hash, author, message: message.trim_end().to_string(), timestamp, committer, author_timestamp,
+                    parents: vec![], changed_files,
+                }
+            }
+            // Patches carry no blobs to size
+            mut data => {
+                if let MessageData::FileChange { binary_size, .. } = &mut data {
+                    *binary_size = None;
+                }
+                data
+            }
+        }).collect()
+    }
+
+    let from_patches = scan(&mbox, true).await;
+    let from_repository = scan(repo.path(), false).await;
+    assert_eq!(from_patches[..], from_repository[..from_patches.len()]);
+    assert!(matches!(&from_patches[0], MessageData::CommitInfo { author, .. } if author == "Bob"));
+    assert_eq!(from_patches.iter().filter(|data| matches!(data, MessageData::CommitInfo { .. })).count(), 2);
+}
+
+#[tokio::test]
+async fn test_default_branch_is_scanned_from_feature_branch_or_detached_head() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::branch_detection::{BranchDetection, BranchSelectionSource};
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("README.md", "one\n")
+        .commit("Mainline commit")
+        .branch("feature/long-lived")
+        .file("README.md", "two\n")
+        .commit("Feature commit")
+        .build()
+        .expect("Failed to create test repository");
+    repo.git(&["config", "init.defaultBranch", "main"], &[]).unwrap();
+    let main = repo.rev_parse("main").unwrap();
+
+    // Neither the checked-out feature branch nor a detached HEAD is scanned in place of main
+    for checkout in ["feature/long-lived", "HEAD~0"] {
+        repo.git(&["checkout", "-q", "--detach", checkout], &[]).unwrap();
+        let detected = BranchDetection::new().detect_branch(repo.path(), None, None, None).unwrap();
+        assert_eq!(detected.branch_name, "main");
+        assert_eq!(detected.selection_source, BranchSelectionSource::InitDefault);
+        assert_eq!(detected.commit_id, main);
+        assert_eq!(detected.to_metadata()["branch_source"], "init.defaultBranch");
+
+        let query = QueryParams { scanned_ref: Some(detected), ..Default::default() };
+        let messages: Vec<_> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap().collect().await;
+        let commits: Vec<String> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+            MessageData::CommitInfo { message, .. } => Some(message.trim_end().to_string()),
+            _ => None,
+        }).collect();
+        assert_eq!(commits, vec!["Mainline commit"]);
+    }
+}
+
+#[tokio::test]
+async fn test_all_branches_scans_the_union_of_branch_histories() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("README.md", "one\n")
+        .commit("Mainline commit")
+        .branch("feature")
+        .file("feature.rs", "fn feature() {}\n")
+        .commit("Feature commit")
+        .checkout("main")
+        .file("README.md", "two\n")
+        .commit("Second mainline commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    async fn commits(repo: &TestRepo, all_branches: bool) -> Vec<(String, Option<String>)> {
+        let query = QueryParams { branch: Some("main".to_string()), all_branches, ..Default::default() };
+        let messages: Vec<_> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap().collect().await;
+        messages.into_iter().filter_map(|message| {
+            let message = message.unwrap();
+            match message.data {
+                MessageData::CommitInfo { message: title, .. } => Some((title.trim_end().to_string(), message.header.branch)),
+                _ => None,
+            }
+        }).collect()
+    }
+
+    let branch = |name: &str| Some(name.to_string());
+    assert_eq!(commits(&repo, false).await, vec![
+        ("Second mainline commit".to_string(), branch("main")),
+        ("Mainline commit".to_string(), branch("main")),
+    ]);
+    // Shared history is read once, under the branch listed first, and branches are interleaved newest first
+    assert_eq!(commits(&repo, true).await, vec![
+        ("Second mainline commit".to_string(), branch("main")),
+        ("Feature commit".to_string(), branch("feature")),
+        ("Mainline commit".to_string(), branch("main")),
+    ]);
+}
+
+#[tokio::test]
+async fn test_commits_carry_their_parents() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("README.md", "one\n")
+        .commit("Mainline commit")
+        .branch("feature")
+        .file("feature.rs", "fn feature() {}\n")
+        .commit("Feature commit")
+        .checkout("main")
+        .file("README.md", "two\n")
+        .commit("Second mainline commit")
+        .build()
+        .expect("Failed to create test repository");
+    repo.git(&["merge", "--no-ff", "-q", "-m", "Merge branch 'feature'", "feature"], &[]).unwrap();
+    let [root, feature, second] = ["main~2", "feature", "main~1"].map(|revision| repo.rev_parse(revision).unwrap());
+
+    let query = QueryParams { branch: Some("main".to_string()), ..Default::default() };
+    let messages: Vec<_> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap().collect().await;
+    let parents: Vec<(String, Vec<String>)> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+        MessageData::CommitInfo { message, parents, .. } => Some((message.trim_end().to_string(), parents)),
+        _ => None,
+    }).collect();
+
+    // A merge lists its first parent, the mainline, before the branch it brings in
+    assert!(parents.contains(&("Merge branch 'feature'".to_string(), vec![second.clone(), feature])));
+    assert!(parents.contains(&("Second mainline commit".to_string(), vec![root.clone()])));
+    assert!(parents.contains(&("Feature commit".to_string(), vec![root])));
+    assert!(parents.contains(&("Mainline commit".to_string(), vec![])));
+}
+
+#[tokio::test]
+async fn test_shallow_clone_boundary_is_scanned_as_a_root_commit() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::QueryParams;
+    use gstats::scanner::repository::open_repository;
+    use gstats::scanner::shallow::ShallowHistory;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("LICENSE", "MIT License\n")
+        .file("README.md", "one\n")
+        .commit("First commit")
+        .file("README.md", "one\ntwo\n")
+        .commit("Second commit")
+        .file("README.md", "one\ntwo\nthree\n")
+        .commit("Third commit")
+        .build()
+        .expect("Failed to create test repository");
+    let clone = repo.shallow_clone("ci", 2).unwrap();
+
+    let shallow = ShallowHistory::detect(&open_repository(&clone).unwrap(), &repo.rev_parse("HEAD").unwrap()).unwrap();
+    assert_eq!(shallow.as_ref().map(|shallow| shallow.depth), Some(2));
+
+    let query = QueryParams { shallow, ..Default::default() };
+    let messages: Vec<_> = EventDrivenScanner::new(query).scan_async(&clone).await.unwrap().collect().await;
+    let commits: Vec<(String, Vec<String>)> = messages.into_iter().filter_map(|message| match message.unwrap().data {
+        MessageData::CommitInfo { message, changed_files, .. } => {
+            Some((message.trim_end().to_string(), changed_files.into_iter().map(|file| file.path).collect()))
+        }
+        _ => None,
+    }).collect();
+
+    // History stops at the fetched boundary, whose whole tree counts as added
+    let paths = |paths: &[&str]| paths.iter().map(|path| path.to_string()).collect::<Vec<_>>();
+    assert_eq!(commits, vec![
+        ("Third commit".to_string(), paths(&["README.md"])),
+        ("Second commit".to_string(), paths(&["LICENSE", "README.md"])),
+    ]);
+}
+
+#[tokio::test]
+async fn test_staged_changes_are_scanned_as_one_pending_commit() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::pending::{PendingChanges, PENDING_HASH};
+    use gstats::scanner::query::QueryParams;
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .file("README.md", "# Project\n")
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+    std::fs::write(repo.path().join("src/lib.rs"), "pub fn one() {}\npub fn two() {}\n").unwrap();
+    repo.git(&["add", "src/lib.rs"], &[]).unwrap();
+    std::fs::write(repo.path().join("src/lib.rs"), "pub fn one() {}\npub fn two() {}\npub fn three() {}\n").unwrap();
+    std::fs::write(repo.path().join("README.md"), "# Unstaged\n").unwrap();
+
+    let scanner = EventDrivenScanner::new(QueryParams::default())
+        .with_pending_changes(Some(PendingChanges::Staged))
+        .with_current_file_content(true);
+    let messages: Vec<MessageData> = scanner.scan_async(repo.path()).await.unwrap()
+        .map(|message| message.unwrap().data)
+        .collect().await;
+
+    // History is not read, only the staged change and its staged content
+    assert_eq!(messages.len(), 3);
+    assert!(matches!(&messages[0], MessageData::CommitInfo { hash, message, .. }
+        if hash == PENDING_HASH && message == "Staged changes"));
+    assert!(matches!(&messages[1], MessageData::FileChange { path, insertions: 1, deletions: 0, .. } if path == "src/lib.rs"));
+    assert!(matches!(&messages[2], MessageData::FileContent { path, content, .. }
+        if path == "src/lib.rs" && content == "pub fn one() {}\npub fn two() {}\n"));
+}
+
+#[tokio::test]
+async fn test_path_globs_narrow_commits_to_selected_files() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::{FilePathFilter, QueryParams};
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .file("src/generated/schema.rs", "pub struct Schema;\n")
+        .file("README.md", "# Project\n")
+        .commit("Initial commit")
+        .file("docs/guide.md", "# Guide\n")
+        .commit("Add guide")
+        .build()
+        .expect("Failed to create test repository");
+
+    let query = QueryParams {
+        file_paths: FilePathFilter {
+            include: vec!["src/**/*.rs".into()],
+            exclude: vec!["generated/".into()],
+            weights: Vec::new(),
+        },
+        ..Default::default()
+    };
+    let messages: Vec<MessageData> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap()
+        .map(|message| message.unwrap().data)
+        .collect().await;
+
+    // The guide commit changes nothing selected; the initial commit keeps only src/lib.rs
+    assert_eq!(messages.len(), 2);
+    assert!(matches!(&messages[0], MessageData::CommitInfo { message, changed_files, .. }
+        if message.trim() == "Initial commit" && changed_files.len() == 1 && changed_files[0].path == "src/lib.rs"));
+    assert!(matches!(&messages[1], MessageData::FileChange { path, .. } if path == "src/lib.rs"));
+}
+
+#[tokio::test]
+async fn test_path_weights_scale_line_changes() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::{FilePathFilter, PathWeight, QueryParams};
+    use gstats::test_support::TestRepo;
+
+    let migration: String = (0..40).map(|n| format!("op_{}()\n", n)).collect();
+    let repo = TestRepo::builder()
+        .file("app/models.py", "class Model:\n    pass\n")
+        .file("app/migrations/0001_initial.py", &migration)
+        .commit("Initial commit")
+        .build()
+        .expect("Failed to create test repository");
+
+    let insertions = |weights: Vec<PathWeight>| async {
+        let query = QueryParams {
+            file_paths: FilePathFilter { weights, ..Default::default() },
+            ..Default::default()
+        };
+        let messages: Vec<MessageData> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap()
+            .map(|message| message.unwrap().data)
+            .collect().await;
+        messages.into_iter().filter_map(|message| match message {
+            MessageData::FileChange { path, insertions, .. } => Some((path, insertions)),
+            _ => None,
+        }).collect::<std::collections::HashMap<String, usize>>()
+    };
+    let full = insertions(Vec::new()).await;
+    let weighted = insertions(vec![PathWeight { path: "migrations/".into(), weight: 0.1 }]).await;
+
+    let migration = "app/migrations/0001_initial.py";
+    assert!(full[migration] >= 40);
+    assert_eq!(weighted[migration], (full[migration] as f64 * 0.1).round() as usize);
+    assert_eq!(weighted["app/models.py"], full["app/models.py"]);
+}
+
+#[tokio::test]
+async fn test_author_patterns_drop_commits_during_traversal() {
+    use futures::StreamExt;
+    use gstats::scanner::async_engine::scanners::EventDrivenScanner;
+    use gstats::scanner::messages::MessageData;
+    use gstats::scanner::query::{AuthorFilter, QueryParams};
+    use gstats::test_support::TestRepo;
+
+    let repo = TestRepo::builder()
+        .author("Alice", "alice@example.com")
+        .file("src/lib.rs", "pub fn one() {}\n")
+        .commit("Alice's change")
+        .author("dependabot[bot]", "bot@users.noreply.github.com")
+        .file("Cargo.lock", "# lock\n")
+        .commit("Bump dependencies")
+        .author("Bob", "bob@example.org")
+        .file("src/main.rs", "fn main() {}\n")
+        .commit("Bob's change")
+        .build()
+        .expect("Failed to create test repository");
+
+    let query = QueryParams {
+        authors: AuthorFilter {
+            include: vec!["/@example\\.(com|org)$/".to_string(), "bot".to_string()],
+            exclude: vec!["/\\[bot\\]$/".to_string(), "Bob".to_string()],
+        },
+        ..Default::default()
+    };
+    let messages: Vec<MessageData> = EventDrivenScanner::new(query).scan_async(repo.path()).await.unwrap()
+        .map(|message| message.unwrap().data)
+        .collect().await;
+
+    // Only Alice's commit and its file change are sent on
+    let commits: Vec<&str> = messages.iter()
+        .filter_map(|message| match message {
+            MessageData::CommitInfo { author, .. } => Some(author.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(commits, vec!["Alice"]);
+    assert_eq!(messages.len(), 2);
+}
+
+#[test]
+fn test_reproducible_scan_id_depends_only_on_input() {
+    use gstats::scanner::AsyncScannerManager;
+
+    let id = AsyncScannerManager::reproducible_scan_id("abc123\nquery");
+    assert_eq!(id, AsyncScannerManager::reproducible_scan_id("abc123\nquery"));
+    assert_ne!(id, AsyncScannerManager::reproducible_scan_id("def456\nquery"));
+    assert!(id.starts_with("scan-") && id.len() == "scan-".len() + 16);
+}
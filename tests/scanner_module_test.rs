@@ -60,6 +60,9 @@ fn test_commit_message_creation() {
         author: "developer".to_string(),
         message: "Fix bug".to_string(),
         timestamp: 1640995200, // Jan 1, 2022
+        committer: "developer".to_string(),
+        author_timestamp: 1640995200,
+        parents: vec![],
         changed_files: vec![],
     };
     
@@ -69,7 +72,7 @@ fn test_commit_message_creation() {
     assert_eq!(message.header.sequence, 67890);
     
     // Test commit data
-    if let MessageData::CommitInfo { hash, author, message: msg, timestamp, changed_files } = &message.data {
+    if let MessageData::CommitInfo { hash, author, message: msg, timestamp, changed_files, .. } = &message.data {
         assert_eq!(hash, "abc123");
         assert_eq!(author, "developer");
         assert_eq!(msg, "Fix bug");
@@ -102,6 +105,9 @@ fn test_message_data_variants() {
         author: "dev@test.com".to_string(),
         message: "Test".to_string(),
         timestamp: 1234567890,
+        committer: "dev@test.com".to_string(),
+        author_timestamp: 1234567890,
+        parents: vec![],
         changed_files: vec![],
     };
     matches!(commit_data, MessageData::CommitInfo { .. });
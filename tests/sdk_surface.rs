@@ -0,0 +1,142 @@
+//! A consumer plugin written against `gstats::sdk` alone
+//!
+//! Compiling this test checks that the SDK covers what an external plugin
+//! needs; an internal refactor that breaks it has broken the SDK.
+
+use gstats::sdk::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+struct ChangeCounter {
+    info: PluginInfo,
+    publisher: Arc<AsyncNotificationManager<PluginEvent>>,
+}
+
+impl ChangeCounter {
+    fn new() -> Self {
+        Self {
+            info: PluginInfo::new(
+                "change-counter".to_string(),
+                "0.1.0".to_string(),
+                api_version(),
+                "Counts changed files".to_string(),
+                "tests".to_string(),
+                PluginType::Processing,
+            ),
+            publisher: Arc::new(AsyncNotificationManager::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Plugin for ChangeCounter {
+    fn plugin_info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    async fn initialize(&mut self, _context: &dyn ScanContext) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn execute(&self, request: PluginRequest) -> PluginResult<PluginResponse> {
+        match request {
+            PluginRequest::GetCapabilities => Ok(PluginResponse::Capabilities(self.info.capabilities.clone())),
+            _ => Err(PluginError::execution_failed("Unsupported request type")),
+        }
+    }
+
+    async fn cleanup(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn as_consumer_plugin(&self) -> Option<&dyn ConsumerPlugin> {
+        Some(self)
+    }
+
+    fn as_consumer_plugin_mut(&mut self) -> Option<&mut dyn ConsumerPlugin> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ConsumerPlugin for ChangeCounter {
+    async fn start_consuming(&mut self, _consumer: Box<dyn MessageConsumer>) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn process_message(&self, consumer: &dyn MessageConsumer, message: Arc<ScanMessage>) -> PluginResult<()> {
+        if let MessageData::CommitInfo { changed_files, .. } = message.data() {
+            let export = PluginDataExport::builder()
+                .plugin_id("change-counter")
+                .title("Changed Files")
+                .data_type(DataExportType::Tabular)
+                .schema(DataSchema::new(vec![ColumnDef::new("Files", ColumnType::Integer)]))
+                .data(DataPayload::Rows(Arc::new(vec![Row::new(vec![Value::Integer(changed_files.len() as i64)])])))
+                .build()
+                .map_err(PluginError::execution_failed)?;
+            let event = PluginEvent::DataReady {
+                plugin_id: "change-counter".to_string(),
+                scan_id: message.header().scan_id.clone(),
+                export: Arc::new(export),
+            };
+            self.publisher.publish(event).await
+                .map_err(|e| PluginError::execution_failed(e.to_string()))?;
+        }
+        consumer.acknowledge(message.header().sequence()).await
+    }
+
+    async fn handle_queue_event(&self, _event: &QueueEvent) -> PluginResult<()> {
+        Ok(())
+    }
+
+    async fn stop_consuming(&mut self) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn consumer_preferences(&self) -> ConsumerPreferences {
+        ConsumerPreferences {
+            interested_message_types: vec!["CommitInfo".to_string()],
+            ..ConsumerPreferences::default()
+        }
+    }
+}
+
+/// A consumer handle that records acknowledgements, as a plugin's own tests might use
+#[derive(Default)]
+struct RecordingConsumer {
+    acknowledged: Mutex<Vec<u64>>,
+}
+
+#[async_trait]
+impl MessageConsumer for RecordingConsumer {
+    async fn read_next(&self) -> PluginResult<Option<Arc<ScanMessage>>> {
+        Ok(None)
+    }
+
+    async fn acknowledge(&self, sequence: u64) -> PluginResult<()> {
+        self.acknowledged.lock().unwrap().push(sequence);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_plugin_built_on_the_sdk() {
+    let mut plugin = ChangeCounter::new();
+    assert_eq!(plugin.plugin_info().api_version, api_version());
+    assert!(plugin.as_consumer_plugin().is_some());
+    match plugin.execute(PluginRequest::GetCapabilities).await.unwrap() {
+        PluginResponse::Capabilities(capabilities) => assert!(capabilities.is_empty()),
+        _ => panic!("expected capabilities"),
+    }
+    let consumer = RecordingConsumer::default();
+    let messages = vec![
+        Arc::new(ScanMessage::new(MessageHeader::new(1, "scan".to_string()), MessageData::None)),
+        Arc::new(ScanMessage::new(MessageHeader::new(2, "scan".to_string()), MessageData::None)),
+    ];
+    plugin.process_batch(&consumer, messages).await.unwrap();
+    assert_eq!(*consumer.acknowledged.lock().unwrap(), [1, 2]);
+    plugin.cleanup().await.unwrap();
+
+    let schema = DataSchema::new(vec![ColumnDef::new("Files", ColumnType::Integer)]).with_metadata("scan", "test");
+    assert_eq!(schema.metadata, HashMap::from([("scan".to_string(), "test".to_string())]));
+}